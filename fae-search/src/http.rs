@@ -17,6 +17,9 @@ const USER_AGENTS: &[&str] = &[
     "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:133.0) Gecko/20100101 Firefox/133.0",
 ];
 
+/// Redirects followed before a request fails.
+pub const MAX_REDIRECTS: usize = 10;
+
 /// Build a [`reqwest::Client`] configured for search engine scraping.
 ///
 /// The client has:
@@ -24,11 +27,24 @@ const USER_AGENTS: &[&str] = &[
 /// - Timeout from config
 /// - Random User-Agent from built-in rotation list (or custom if configured)
 /// - Brotli and gzip decompression
+/// - Up to [`MAX_REDIRECTS`] redirects followed
 ///
 /// # Errors
 ///
 /// Returns [`SearchError::Http`] if the client cannot be constructed.
 pub fn build_client(config: &SearchConfig) -> Result<reqwest::Client, SearchError> {
+    build_client_with_redirects(config, reqwest::redirect::Policy::limited(MAX_REDIRECTS))
+}
+
+/// Build a client like [`build_client`] that follows redirects by `policy`.
+///
+/// # Errors
+///
+/// Returns [`SearchError::Http`] if the client cannot be constructed.
+pub fn build_client_with_redirects(
+    config: &SearchConfig,
+    policy: reqwest::redirect::Policy,
+) -> Result<reqwest::Client, SearchError> {
     let ua = match config.user_agent {
         Some(ref custom) => custom.clone(),
         None => random_user_agent().to_owned(),
@@ -38,7 +54,7 @@ pub fn build_client(config: &SearchConfig) -> Result<reqwest::Client, SearchErro
         .cookie_store(true)
         .timeout(Duration::from_secs(config.timeout_seconds))
         .user_agent(ua)
-        .redirect(reqwest::redirect::Policy::limited(10))
+        .redirect(policy)
         .build()
         .map_err(|e| SearchError::Http(format!("failed to build HTTP client: {e}")))
 }
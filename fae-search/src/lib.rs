@@ -37,6 +37,11 @@ pub use engine::SearchEngineTrait;
 pub use error::{Result, SearchError};
 pub use types::{PageContent, SearchEngine, SearchResult};
 
+use std::sync::Arc;
+
+/// Decides whether a URL may be fetched, returning the reason when not.
+pub type UrlCheck = dyn Fn(&str) -> std::result::Result<(), String> + Send + Sync;
+
 /// Search the web using multiple engines concurrently.
 ///
 /// Queries all engines specified in `config`, merges and ranks results
@@ -131,14 +136,36 @@ pub async fn search_default(query: &str) -> Result<Vec<SearchResult>> {
 /// # }
 /// ```
 pub async fn fetch_page_content(url: &str) -> Result<PageContent> {
+    fetch_page_content_checked(url, Arc::new(|_| Ok(()))).await
+}
+
+/// Fetch a page like [`fetch_page_content`], passing every redirect target
+/// to `check` before following it.
+///
+/// `url` itself is not checked; callers check it before fetching.
+///
+/// # Errors
+///
+/// Returns [`SearchError::Http`] if the page cannot be fetched, including
+/// when `check` refuses a redirect (the reason is in the message), or
+/// [`SearchError::Parse`] if the HTML cannot be meaningfully extracted.
+pub async fn fetch_page_content_checked(url: &str, check: Arc<UrlCheck>) -> Result<PageContent> {
     let config = SearchConfig::default();
-    let client = http::build_client(&config)?;
+    let policy = reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= http::MAX_REDIRECTS {
+            return attempt.error("too many redirects");
+        }
+        match check(attempt.url().as_str()) {
+            Ok(()) => attempt.follow(),
+            Err(reason) => attempt.error(reason),
+        }
+    });
+    let client = http::build_client_with_redirects(&config, policy)?;
 
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| SearchError::Http(format!("failed to fetch {url}: {e}")))?;
+    let response =
+        client.get(url).send().await.map_err(|e| {
+            SearchError::Http(format!("failed to fetch {url}: {}", error_chain(&e)))
+        })?;
 
     let status = response.status();
     if !status.is_success() {
@@ -153,6 +180,17 @@ pub async fn fetch_page_content(url: &str) -> Result<PageContent> {
     content::extract_content(&html, url)
 }
 
+/// `e` and its sources, so a refused redirect's reason is not lost.
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,6 +228,32 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("timeout"));
     }
 
+    #[tokio::test]
+    async fn checked_fetch_refuses_redirects_the_check_denies() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/page", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).await;
+            let response = "HTTP/1.1 302 Found\r\nlocation: http://denied.test/\r\n\
+                            content-length: 0\r\nconnection: close\r\n\r\n";
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let check: Arc<UrlCheck> = Arc::new(|url| {
+            if url.contains("denied.test") {
+                Err("denied.test is blocked".to_owned())
+            } else {
+                Ok(())
+            }
+        });
+        let err = fetch_page_content_checked(&url, check).await.unwrap_err();
+        assert!(err.to_string().contains("denied.test is blocked"), "{err}");
+    }
+
     // Note: fetch_page_content() now makes real HTTP requests.
     // Live tests are in integration tests (marked #[ignore]).
     // Content extraction logic is tested in content::tests.
@@ -2,7 +2,7 @@
  * @file fae.h
  * @brief C ABI surface for embedding the Fae runtime in native shells.
  *
- * This header declares the 8 extern "C" functions exported by libfae.a.
+ * This header declares the 9 extern "C" functions exported by libfae.a.
  * Swift can import this header via a bridging header or a C module map.
  *
  * ## Lifecycle
@@ -124,6 +124,82 @@ void fae_core_stop(FaeCoreHandle handle);
  */
 void fae_core_destroy(FaeCoreHandle handle);
 
+/**
+ * Turn offline mode on (offline != 0) or off.
+ *
+ * Blocks all network egress (web tools, channels, update checks, model
+ * downloads) while on. The setting is saved to config and a
+ * "pipeline.offline_mode_changed" event is emitted.
+ *
+ * @param handle   Handle from fae_core_init (runtime must be started).
+ * @param offline  Non-zero to go offline, zero to go back online.
+ * @return 0 on success, -1 on failure.
+ */
+int32_t fae_core_set_offline_mode(FaeCoreHandle handle, int32_t offline);
+
+/**
+ * Start (active != 0) or end meeting mode.
+ *
+ * While a meeting runs Fae transcribes everyone, labelled by speaker, and
+ * does not reply. Starting requires consent != 0: the user agreed to the
+ * meeting being transcribed. Ending it produces minutes with action items,
+ * announced by a "pipeline.meeting_minutes_ready" event carrying
+ * "meeting_id", "path" and "action_items". Every switch emits
+ * "pipeline.meeting_mode_changed".
+ *
+ * @param handle   Handle from fae_core_init (runtime must be started).
+ * @param active   Non-zero to start a meeting, zero to end it.
+ * @param consent  Non-zero when the user consented to transcription.
+ * @return 0 on success, -1 on failure.
+ */
+int32_t fae_core_set_meeting_mode(FaeCoreHandle handle, int32_t active, int32_t consent);
+
+/**
+ * Set the accessibility announcement level.
+ *
+ * While on, every event is followed by an "accessibility.announcement" event
+ * whose payload carries screen-reader-friendly "text", a "priority"
+ * ("polite" or "assertive") and the "source_event" name. The setting is
+ * saved to config.
+ *
+ * @param handle  Handle from fae_core_init (runtime must be started).
+ * @param level   0 = off, 1 = minimal, 2 = standard, 3 = verbose.
+ * @return 0 on success, -1 on failure.
+ */
+int32_t fae_core_set_accessibility(FaeCoreHandle handle, int32_t level);
+
+/**
+ * Set the live captions mode.
+ *
+ * While on, each line of assistant speech is emitted as a "pipeline.caption"
+ * event with "utterance_id", "index", "text", "start_ms" and "end_ms"
+ * (milliseconds from the start of the utterance, matching the audio). A
+ * "pipeline.captions_ended" event closes each utterance; when its
+ * "interrupted" flag is set, remaining lines should be removed. In
+ * captions-only mode nothing is spoken and lines are paced for reading.
+ * The setting is saved to config.
+ *
+ * @param handle  Handle from fae_core_init (runtime must be started).
+ * @param mode    0 = off, 1 = captions with speech, 2 = captions only.
+ * @return 0 on success, -1 on failure.
+ */
+int32_t fae_core_set_captions(FaeCoreHandle handle, int32_t mode);
+
+/**
+ * Read text aloud with the current voice and save it as an audio file.
+ *
+ * The file type follows the extension of path: ".wav" or ".mp3". text may
+ * be plain text or SSML (<speak> with <break>, <prosody>, <emphasis>, <p>
+ * and <s>), up to 200000 characters. Blocks until the file is written, so
+ * call it off the main thread.
+ *
+ * @param handle  Handle from fae_core_init (runtime must be started).
+ * @param text    UTF-8 text or SSML to speak.
+ * @param path    UTF-8 path of the .wav or .mp3 file to write.
+ * @return 0 on success, -1 on failure.
+ */
+int32_t fae_core_synthesize_to_file(FaeCoreHandle handle, const char *text, const char *path);
+
 /**
  * Free a string returned by fae_core_send_command or fae_core_poll_event.
  *
@@ -136,7 +212,7 @@ void fae_string_free(char *s);
 /**
  * Linker dead-strip anchor — prevents the macOS linker from removing Rust
  * subsystems (ML models, audio, VAD, AEC) that are not directly reachable
- * from the 8 FFI entry points.
+ * from the 9 FFI entry points.
  *
  * Called internally by fae_core_init via black_box; no need to call directly.
  */
@@ -0,0 +1,479 @@
+//! Accessibility mode: screen-reader-friendly announcements.
+//!
+//! When accessibility mode is on, the host runtime follows each event it
+//! emits with an `accessibility.announcement` event carrying a short
+//! plain-text description ("You said: …", "Running web search.", "Approval
+//! needed: …"). A screen reader, or a blind user driving Fae from the shell
+//! through the stdio bridge, can read these directly instead of decoding the
+//! JSON payloads. [`AccessibilityVerbosity`] controls how much is announced.
+//!
+//! Spoken prompts also gain interaction hints (see [`with_hint`]) so the user
+//! knows what they can say next.
+//!
+//! Like offline mode, the switch is process-wide: it is set from
+//! `SpeechConfig::accessibility` at startup and can be changed at runtime
+//! through `config.patch`.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+pub use crate::config::{AccessibilityConfig, AccessibilityVerbosity};
+
+/// Event name for announcements.
+pub const ANNOUNCEMENT_EVENT: &str = "accessibility.announcement";
+
+/// Longest text (in characters) quoted from a transcript or tool output.
+const MAX_QUOTE_CHARS: usize = 300;
+
+/// 0 = off, otherwise `verbosity as u8 + 1`.
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+static SPOKEN_HINTS: AtomicBool = AtomicBool::new(false);
+
+/// Apply an accessibility configuration process-wide.
+pub fn apply(config: &AccessibilityConfig) {
+    let level = if config.enabled {
+        config.verbosity as u8 + 1
+    } else {
+        0
+    };
+    let previous = LEVEL.swap(level, Ordering::SeqCst);
+    SPOKEN_HINTS.store(config.enabled && config.spoken_hints, Ordering::SeqCst);
+    if previous != level {
+        tracing::info!(
+            enabled = config.enabled,
+            verbosity = ?config.verbosity,
+            "accessibility mode changed"
+        );
+    }
+}
+
+/// The active verbosity, or `None` when accessibility mode is off.
+pub fn verbosity() -> Option<AccessibilityVerbosity> {
+    match LEVEL.load(Ordering::SeqCst) {
+        0 => None,
+        1 => Some(AccessibilityVerbosity::Minimal),
+        2 => Some(AccessibilityVerbosity::Standard),
+        _ => Some(AccessibilityVerbosity::Verbose),
+    }
+}
+
+/// Whether spoken prompts should carry interaction hints.
+pub fn spoken_hints_enabled() -> bool {
+    SPOKEN_HINTS.load(Ordering::SeqCst)
+}
+
+/// Where a spoken prompt is used, for choosing an interaction hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionHint {
+    /// A tool approval prompt waiting for yes or no.
+    Approval,
+    /// A task handed to a background agent.
+    BackgroundTask,
+}
+
+impl InteractionHint {
+    fn text(self) -> &'static str {
+        match self {
+            Self::Approval => crate::i18n::text("hint.approval"),
+            Self::BackgroundTask => crate::i18n::text("hint.background_task"),
+        }
+    }
+}
+
+/// Append the interaction hint for `hint` to `spoken` when spoken hints are on.
+pub fn with_hint(spoken: &str, hint: InteractionHint) -> String {
+    if spoken_hints_enabled() {
+        format!("{spoken} {}", hint.text())
+    } else {
+        spoken.to_owned()
+    }
+}
+
+/// How urgently a screen reader should deliver an announcement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementPriority {
+    /// Read after whatever is currently being read.
+    Polite,
+    /// Interrupt: the user needs to respond or something failed.
+    Assertive,
+}
+
+/// A plain-text description of a runtime event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Announcement {
+    pub text: String,
+    pub priority: AnnouncementPriority,
+}
+
+impl Announcement {
+    fn polite(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            priority: AnnouncementPriority::Polite,
+        }
+    }
+
+    fn assertive(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            priority: AnnouncementPriority::Assertive,
+        }
+    }
+
+    /// Payload for an [`ANNOUNCEMENT_EVENT`] describing `source_event`.
+    pub fn to_payload(&self, source_event: &str) -> serde_json::Value {
+        serde_json::json!({
+            "text": self.text,
+            "priority": self.priority,
+            "source_event": source_event,
+        })
+    }
+}
+
+fn str_field<'a>(payload: &'a serde_json::Value, key: &str) -> &'a str {
+    payload.get(key).and_then(|v| v.as_str()).unwrap_or("")
+}
+
+fn bool_field(payload: &serde_json::Value, key: &str) -> Option<bool> {
+    payload.get(key).and_then(|v| v.as_bool())
+}
+
+fn u64_field(payload: &serde_json::Value, key: &str) -> Option<u64> {
+    payload.get(key).and_then(|v| v.as_u64())
+}
+
+/// Tool names as they are read aloud (`web_search` → `web search`).
+fn spoken_tool_name(name: &str) -> String {
+    name.replace(['_', '.'], " ")
+}
+
+/// Trim `text` to [`MAX_QUOTE_CHARS`], ending with an ellipsis when cut.
+fn quote(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(MAX_QUOTE_CHARS) {
+        Some((cut, _)) => format!("{}…", text[..cut].trim_end()),
+        None => text.to_owned(),
+    }
+}
+
+/// Describe a host event for a screen reader, in the active language.
+///
+/// Returns `None` for events that are not announced at `verbosity` (or at
+/// all, like audio levels and timings).
+pub fn describe_event(
+    event: &str,
+    payload: &serde_json::Value,
+    verbosity: AccessibilityVerbosity,
+) -> Option<Announcement> {
+    use crate::i18n::{format, plural, text};
+    use AccessibilityVerbosity::{Standard, Verbose};
+
+    let tool = || spoken_tool_name(str_field(payload, "name"));
+    let announcement = match event {
+        // ── Always announced ───────────────────────────────────────────
+        "pipeline.transcription" if bool_field(payload, "is_final") == Some(true) => {
+            let said = str_field(payload, "text");
+            if said.trim().is_empty() {
+                return None;
+            }
+            Announcement::polite(format("announce.you_said", &[("text", &quote(said))]))
+        }
+        "approval.requested" => {
+            let prompt = crate::personality::format_approval_prompt(
+                str_field(payload, "name"),
+                str_field(payload, "input_json"),
+            );
+            let mut announced = format(
+                "announce.approval_needed",
+                &[("tool", &tool()), ("prompt", &prompt)],
+            );
+            if verbosity >= Verbose
+                && let Some(preview) = payload.get("preview").and_then(|v| v.as_str())
+            {
+                announced.push(' ');
+                announced.push_str(&format(
+                    "announce.approval_details",
+                    &[("preview", &quote(preview))],
+                ));
+            }
+            Announcement::assertive(announced)
+        }
+        "approval.resolved" => {
+            let approved = bool_field(payload, "approved").unwrap_or(false);
+            let key = match (approved, str_field(payload, "source")) {
+                (false, "timeout") => "announce.approval_timed_out",
+                (true, _) => "announce.approved",
+                (false, _) => "announce.declined",
+            };
+            Announcement::polite(text(key))
+        }
+        "capability.requested" => Announcement::assertive(format(
+            "announce.permission_needed",
+            &[
+                ("capability", str_field(payload, "capability")),
+                ("reason", str_field(payload, "reason")),
+            ],
+        )),
+        "runtime.error" => Announcement::assertive(format(
+            "announce.error",
+            &[("error", &quote(str_field(payload, "error")))],
+        )),
+        "data.forget.declined" => Announcement::polite(text("announce.forget_declined")),
+        "data.forget.completed" => match bool_field(payload, "success") {
+            Some(true) => Announcement::polite(text("announce.forget_completed")),
+            _ => Announcement::assertive(text("announce.forget_failed")),
+        },
+        "pipeline.tool_result" if bool_field(payload, "success") == Some(false) => {
+            Announcement::assertive(format("announce.tool_failed", &[("tool", &tool())]))
+        }
+        "pipeline.control" => match str_field(payload, "action") {
+            "auto_restart_exhausted" => Announcement::assertive(text("announce.restart_exhausted")),
+            "auto_restart" if verbosity >= Standard => {
+                Announcement::polite(text("announce.restarting"))
+            }
+            "audio_device_changed" if verbosity >= Standard => Announcement::polite(format(
+                "announce.audio_device_changed",
+                &[("device", str_field(payload, "device_name"))],
+            )),
+            "audio_quality_changed" if bool_field(payload, "low_quality") == Some(true) => {
+                Announcement::polite(text("announce.audio_low_quality"))
+            }
+            "memory_pressure" if str_field(payload, "level") == "critical" => {
+                Announcement::assertive(text("announce.memory_critical"))
+            }
+            _ => return None,
+        },
+
+        // ── Standard ───────────────────────────────────────────────────
+        _ if verbosity < Standard => return None,
+        "pipeline.assistant_sentence" => {
+            let said = str_field(payload, "text");
+            if said.trim().is_empty() {
+                return None;
+            }
+            Announcement::polite(format("announce.assistant", &[("text", &quote(said))]))
+        }
+        "pipeline.tool_executing" => {
+            Announcement::polite(format("announce.tool_running", &[("tool", &tool())]))
+        }
+        "pipeline.tool_result" => match payload.get("output_text").and_then(|v| v.as_str()) {
+            Some(output) if verbosity >= Verbose && !output.trim().is_empty() => {
+                Announcement::polite(format(
+                    "announce.tool_finished_output",
+                    &[("tool", &tool()), ("output", &quote(output))],
+                ))
+            }
+            _ => Announcement::polite(format("announce.tool_finished", &[("tool", &tool())])),
+        },
+        "pipeline.tool_budget_exhausted" => {
+            let key = if str_field(payload, "window") == "minute" {
+                "announce.budget_minute"
+            } else {
+                "announce.budget_turn"
+            };
+            Announcement::polite(format(key, &[("tool", &tool())]))
+        }
+        "pipeline.answer_flagged" => Announcement::polite(format(
+            "announce.answer_flagged",
+            &[("reason", str_field(payload, "reason"))],
+        )),
+        "background_task.started" => Announcement::polite(format(
+            "announce.background_started",
+            &[("description", &quote(str_field(payload, "description")))],
+        )),
+        "background_task.completed" => {
+            if bool_field(payload, "success").unwrap_or(false) {
+                Announcement::polite(text("announce.background_finished"))
+            } else {
+                Announcement::assertive(text("announce.background_failed"))
+            }
+        }
+        "pipeline.mic_status" => match bool_field(payload, "active") {
+            Some(true) => Announcement::polite(text("announce.mic_on")),
+            _ => Announcement::assertive(text("announce.mic_silent")),
+        },
+        "pipeline.offline_mode_changed" => match bool_field(payload, "offline") {
+            Some(true) => Announcement::polite(text("announce.offline_on")),
+            _ => Announcement::polite(text("announce.offline_off")),
+        },
+        "pipeline.voice_command" => Announcement::polite(format(
+            "announce.voice_command",
+            &[("command", str_field(payload, "command"))],
+        )),
+        "pipeline.model_selected" => Announcement::polite(format(
+            "announce.model_selected",
+            &[("model", str_field(payload, "provider_model"))],
+        )),
+        "pipeline.provider_fallback" => Announcement::polite(text("announce.provider_fallback")),
+        "runtime.started" => Announcement::polite(text("announce.started")),
+        "runtime.stopped" => Announcement::polite(text("announce.stopped")),
+
+        // ── Verbose ────────────────────────────────────────────────────
+        _ if verbosity < Verbose => return None,
+        "pipeline.generating" => match bool_field(payload, "active") {
+            Some(true) => Announcement::polite(text("announce.thinking")),
+            _ => return None,
+        },
+        "pipeline.memory_recall" => match u64_field(payload, "hits") {
+            Some(0) | None => return None,
+            Some(n) => Announcement::polite(plural("announce.memory_recalled", n as usize, &[])),
+        },
+        "pipeline.memory_write" => Announcement::polite(text("announce.memory_updated")),
+        "runtime.progress" => match str_field(payload, "stage") {
+            "load_started" => Announcement::polite(format(
+                "announce.model_loading",
+                &[("model", str_field(payload, "model_name"))],
+            )),
+            "load_complete" => Announcement::polite(format(
+                "announce.model_loaded",
+                &[("model", str_field(payload, "model_name"))],
+            )),
+            "download_started" => Announcement::polite(format(
+                "announce.model_downloading",
+                &[("file", str_field(payload, "filename"))],
+            )),
+            "error" => Announcement::assertive(format(
+                "announce.model_failed",
+                &[("error", &quote(str_field(payload, "message")))],
+            )),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(announcement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn minimal_announces_transcripts_approvals_and_failures_only() {
+        let v = AccessibilityVerbosity::Minimal;
+        let said = describe_event(
+            "pipeline.transcription",
+            &json!({"text": "what's the weather", "is_final": true}),
+            v,
+        );
+        assert_eq!(
+            said.map(|a| a.text).as_deref(),
+            Some("You said: what's the weather")
+        );
+        assert!(
+            describe_event(
+                "pipeline.transcription",
+                &json!({"text": "what's", "is_final": false}),
+                v
+            )
+            .is_none()
+        );
+
+        let approval = describe_event(
+            "approval.requested",
+            &json!({"request_id": "1", "name": "bash", "input_json": "{\"command\":\"ls\"}"}),
+            v,
+        );
+        match approval {
+            Some(a) => {
+                assert_eq!(a.priority, AnnouncementPriority::Assertive);
+                assert!(a.text.starts_with("Approval needed for bash."));
+                assert!(a.text.contains("Say yes or no."));
+            }
+            None => unreachable!("approvals are always announced"),
+        }
+
+        let failed = describe_event(
+            "pipeline.tool_result",
+            &json!({"name": "web_search", "success": false}),
+            v,
+        );
+        assert_eq!(
+            failed.map(|a| a.text).as_deref(),
+            Some("web search failed.")
+        );
+        assert!(
+            describe_event("pipeline.tool_executing", &json!({"name": "web_search"}), v).is_none()
+        );
+    }
+
+    #[test]
+    fn higher_verbosity_adds_progress_and_detail() {
+        let executing = json!({"name": "web_search"});
+        assert_eq!(
+            describe_event(
+                "pipeline.tool_executing",
+                &executing,
+                AccessibilityVerbosity::Standard
+            )
+            .map(|a| a.text)
+            .as_deref(),
+            Some("Running web search.")
+        );
+
+        let result = json!({"name": "read", "success": true, "output_text": "fn main() {}"});
+        assert_eq!(
+            describe_event(
+                "pipeline.tool_result",
+                &result,
+                AccessibilityVerbosity::Standard
+            )
+            .map(|a| a.text)
+            .as_deref(),
+            Some("read finished.")
+        );
+        assert_eq!(
+            describe_event(
+                "pipeline.tool_result",
+                &result,
+                AccessibilityVerbosity::Verbose
+            )
+            .map(|a| a.text)
+            .as_deref(),
+            Some("read finished: fn main() {}")
+        );
+
+        let thinking = json!({"active": true});
+        assert!(
+            describe_event(
+                "pipeline.generating",
+                &thinking,
+                AccessibilityVerbosity::Standard
+            )
+            .is_none()
+        );
+        assert!(
+            describe_event(
+                "pipeline.generating",
+                &thinking,
+                AccessibilityVerbosity::Verbose
+            )
+            .is_some()
+        );
+        assert!(
+            describe_event(
+                "pipeline.audio_level",
+                &json!({"rms": 0.2}),
+                AccessibilityVerbosity::Verbose
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn long_text_is_truncated() {
+        let long = "word ".repeat(200);
+        let said = describe_event(
+            "pipeline.transcription",
+            &json!({"text": long, "is_final": true}),
+            AccessibilityVerbosity::Minimal,
+        );
+        match said {
+            Some(a) => {
+                assert!(a.text.ends_with('…'));
+                assert!(a.text.chars().count() < MAX_QUOTE_CHARS + 20);
+            }
+            None => unreachable!("final transcripts are announced"),
+        }
+    }
+}
@@ -3,40 +3,74 @@
 //! This module wires the pipeline-facing `generate_response` API to
 //! `fae_llm::agent::AgentLoop`, provider adapters, and tool registry.
 
-use crate::approval::{ToolApprovalRequest, ToolApprovalResponse};
+mod response_policy;
+
+use crate::approval::{DestructiveAction, ToolApprovalRequest, ToolApprovalResponse};
 use crate::canvas::registry::CanvasSessionRegistry;
+#[cfg(feature = "canvas")]
 use crate::canvas::tools::{CanvasExportTool, CanvasInteractTool, CanvasRenderTool};
-use crate::config::{AgentToolMode, LlmConfig};
+use crate::config::{AgentToolMode, LlmConfig, ReadBackConfig, VoiceResponseConfig};
 use crate::error::{Result, SpeechError};
 use crate::fae_llm::agent::{
-    AgentConfig as FaeAgentConfig, AgentLoop, AgentLoopResult, StopReason,
-    build_messages_from_result,
+    AccumulatedToolCall, AgentConfig as FaeAgentConfig, AgentLoop, AgentLoopResult,
+    BestOfCandidate, BestOfConfig, OutputSummarizer, PendingClarification, ProviderSummarizer,
+    RecentToolKeys, SpeculativeConfig, StopReason, ThinkingBudget, ToolCallHistory,
+    build_messages_from_result, run_best_of, run_speculative,
 };
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
 use crate::fae_llm::provider::{LlmEventStream, ProviderAdapter, ToolDefinition};
 use crate::fae_llm::providers::local::{LocalMistralrsAdapter, LocalMistralrsConfig};
-use crate::fae_llm::providers::message::{Message, Role};
+use crate::fae_llm::providers::message::{ImageAttachment, Message, Role};
+use crate::fae_llm::providers::pii_mask::PiiMaskingProvider;
+use crate::fae_llm::providers::vision::{
+    VisionRoutingProvider, remote_provider_from_config, vision_provider_from_config,
+};
 
 use crate::fae_llm::tools::{
-    BashTool, EditTool, PythonSkillTool, ReadTool, Tool, ToolRegistry, ToolResult, WriteTool,
+    ApprovalFuture, ArchiveTool, BashTool, CreateSkillTool, DomainApprover, EditTool,
+    FileOrganizeTool, ListArchiveTool, LspTool, NetworkGuard, PythonSkillTool, ReadTool, Tool,
+    ToolRegistry, ToolResult, UndoStore, UndoTool, WriteTool,
 };
-use crate::fae_llm::types::{ReasoningLevel, RequestOptions};
+use crate::fae_llm::types::{EndpointType, ReasoningLevel, RequestOptions};
+use crate::fae_llm::usage::UsageTracker;
 use crate::llm::LocalLlm;
 use crate::permissions::SharedPermissionStore;
 use crate::pipeline::messages::SentenceChunk;
 use crate::runtime::RuntimeEvent;
-use std::collections::HashSet;
+use response_policy::{ReplyTail, SentenceGate, VoiceResponsePolicy};
+use std::collections::{BTreeSet, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc, oneshot};
 
-const APPROVAL_TIMEOUT: Duration = Duration::from_secs(60);
 const INTERRUPT_POLL_INTERVAL: Duration = Duration::from_millis(25);
 
 static NEXT_APPROVAL_ID: AtomicU64 = AtomicU64::new(1);
 
+/// Allocate an approval request ID unique across tool and host-initiated
+/// approvals for the lifetime of the process.
+pub(crate) fn next_approval_id() -> u64 {
+    NEXT_APPROVAL_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Tool-call history shared by every agent loop in the process, so the
+/// voice engine and background agents draw from one per-minute budget.
+fn shared_tool_call_history() -> Arc<ToolCallHistory> {
+    static HISTORY: OnceLock<Arc<ToolCallHistory>> = OnceLock::new();
+    Arc::clone(HISTORY.get_or_init(|| Arc::new(ToolCallHistory::new())))
+}
+
+/// Keys of side-effecting tool calls shared by every agent loop in the
+/// process, so a retried run can't repeat its predecessor's calls.
+fn shared_recent_tool_keys() -> Arc<RecentToolKeys> {
+    static KEYS: OnceLock<Arc<RecentToolKeys>> = OnceLock::new();
+    Arc::clone(KEYS.get_or_init(|| Arc::new(RecentToolKeys::new())))
+}
+
+static NEXT_CONVERSATION_ID: AtomicU64 = AtomicU64::new(1);
+
 /// Maximum number of recent responses to track for duplicate detection.
 const RECENT_RESPONSE_WINDOW: usize = 5;
 
@@ -58,9 +92,13 @@ pub struct AgentChannels {
 
 pub struct FaeAgentLlm {
     provider: Arc<dyn ProviderAdapter>,
+    /// Local model that takes over when a remote route stalls mid-stream.
+    fallback_provider: Option<Arc<dyn ProviderAdapter>>,
     registry: Arc<ToolRegistry>,
     agent_config: FaeAgentConfig,
     runtime_tx: Option<broadcast::Sender<RuntimeEvent>>,
+    /// Summarizes oversized tool output; present when a local model is loaded.
+    output_summarizer: Option<Arc<dyn OutputSummarizer>>,
     history: Vec<Message>,
     max_history_messages: usize,
     context_size_tokens: usize,
@@ -72,6 +110,33 @@ pub struct FaeAgentLlm {
     recent_responses: std::collections::VecDeque<String>,
     /// Counter for consecutive duplicate detections (for varied fallbacks).
     consecutive_duplicates: usize,
+    /// Fingerprint of the history last handed to [`Self::prefill_task`].
+    prefilled_fingerprint: Option<u64>,
+    /// Spoken reply shaping; set for the voice engine only.
+    response_policy: Option<VoiceResponsePolicy>,
+    /// Tool call held back until the user picks one of its candidates.
+    pending_clarification: Option<PendingClarification>,
+    /// Reassembles the system prompt, e.g. after a skill was created.
+    system_prompt_builder: Arc<dyn Fn() -> String + Send + Sync>,
+    /// Identifies this engine's conversation in tool-call idempotency keys.
+    conversation_id: String,
+    /// Whether the model's thinking is sent to the host after each reply.
+    show_thinking: bool,
+    /// Tokens the latest turn used, for the session record.
+    last_turn_tokens: u64,
+    /// The model's thinking during the latest turn, for the session record.
+    last_turn_thinking: String,
+    /// Remote model that verifies spoken drafts (`[experimental.speculative]`)
+    /// or answers alongside the local one (`[experimental.best_of]`).
+    remote: Option<Arc<dyn ProviderAdapter>>,
+    speculative: SpeculativeConfig,
+    best_of: BestOfConfig,
+    /// Usage of every provider a best-of turn asked.
+    usage: UsageTracker,
+    /// Cuts off speech already queued, e.g. a draft being corrected.
+    speech_stop: Option<Arc<dyn Fn() + Send + Sync>>,
+    /// Images for the next user turn, e.g. attached to a typed message.
+    pending_images: Vec<ImageAttachment>,
 }
 
 impl FaeAgentLlm {
@@ -113,30 +178,43 @@ impl FaeAgentLlm {
         credential_manager: &dyn crate::credentials::CredentialManager,
         channels: AgentChannels,
     ) -> Result<Self> {
-        // Snapshot permission state for system prompt injection, then drop
-        // the guard so `shared_permissions` can be moved into `build_registry`.
-        let system_prompt = {
-            let perm_guard = channels
-                .shared_permissions
-                .as_ref()
-                .and_then(|sp| sp.lock().ok());
-            config.effective_system_prompt(perm_guard.as_deref(), None)
+        // Snapshot permission state for system prompt injection; the guard
+        // is dropped before each build returns.
+        let system_prompt_builder: Arc<dyn Fn() -> String + Send + Sync> = {
+            let config = config.clone();
+            let permissions = channels.shared_permissions.clone();
+            Arc::new(move || {
+                let perm_guard = permissions.as_ref().and_then(|sp| sp.lock().ok());
+                config.effective_system_prompt(perm_guard.as_deref(), None)
+            })
         };
+        let system_prompt = system_prompt_builder();
 
-        let provider = build_provider(config, preloaded_llm, credential_manager).await;
-        let registry = build_registry(config, channels);
+        let (provider, fallback_provider) =
+            build_provider(config, preloaded_llm, credential_manager).await;
+        let output_summarizer = build_output_summarizer(&provider, preloaded_llm);
+        let registry = build_registry(config, channels, runtime_tx.as_ref());
 
         let history = vec![Message::system(system_prompt)];
 
         let parallel_tool_calls = matches!(config.tool_mode, AgentToolMode::ReadOnly);
+        let fae_llm_config = read_fae_llm_config().unwrap_or_default();
+        let tools_config = &fae_llm_config.tools;
+        let remote = experimental_remote(&fae_llm_config, config);
 
         Ok(Self {
             provider,
+            fallback_provider,
             registry,
             agent_config: FaeAgentConfig::new()
                 .with_parallel_tool_calls(parallel_tool_calls)
-                .with_max_parallel_tool_calls(4),
+                .with_max_parallel_tool_calls(4)
+                .with_thinking_budget(thinking_budget(config))
+                .with_tool_output_limits(tools_config.output_limits())
+                .with_tool_limits(tools_config.execution_limits())
+                .with_sampling(model_sampling(&fae_llm_config, config)),
             runtime_tx,
+            output_summarizer,
             history,
             max_history_messages: config.max_history_messages,
             context_size_tokens: config.context_size_tokens,
@@ -144,9 +222,31 @@ impl FaeAgentLlm {
             tools_disabled: false,
             recent_responses: std::collections::VecDeque::with_capacity(RECENT_RESPONSE_WINDOW),
             consecutive_duplicates: 0,
+            prefilled_fingerprint: None,
+            response_policy: None,
+            pending_clarification: None,
+            system_prompt_builder,
+            conversation_id: format!(
+                "conversation-{}",
+                NEXT_CONVERSATION_ID.fetch_add(1, Ordering::Relaxed)
+            ),
+            show_thinking: config.thinking.show_in_ui,
+            last_turn_tokens: 0,
+            last_turn_thinking: String::new(),
+            remote,
+            speculative: fae_llm_config.experimental.speculative.clone(),
+            best_of: fae_llm_config.experimental.best_of.clone(),
+            usage: UsageTracker::new(),
+            speech_stop: None,
+            pending_images: Vec::new(),
         })
     }
 
+    /// Call `stop` to cut off queued speech when a spoken draft is corrected.
+    pub fn set_speech_stop(&mut self, stop: impl Fn() + Send + Sync + 'static) {
+        self.speech_stop = Some(Arc::new(stop));
+    }
+
     /// Disable tool schema advertisement for this engine.
     ///
     /// When tools are disabled, `generate_response()` always passes an empty
@@ -157,6 +257,15 @@ impl FaeAgentLlm {
         self.tools_disabled = true;
     }
 
+    /// Cap spoken reply length and shorten replies after interruptions.
+    ///
+    /// Long replies stop at the sentence limit with "want me to continue?";
+    /// a yes on the next turn speaks the rest. Only the voice engine does
+    /// this; text channels always get the whole reply.
+    pub fn shape_voice_responses(&mut self, config: VoiceResponseConfig) {
+        self.response_policy = Some(VoiceResponsePolicy::new(config));
+    }
+
     /// Temporarily change the reasoning level for the next generation.
     ///
     /// Used by the coordinator to enable thinking mode on the voice engine
@@ -179,12 +288,107 @@ impl FaeAgentLlm {
         }
     }
 
+    /// Task that warms the provider with the current history, so the next
+    /// turn's shared prefix (system prompt and past turns) is already
+    /// processed when the user finishes speaking.
+    ///
+    /// Returns `None` if the history has not changed since the last prefill.
+    /// Tool schemas are not included; they vary per turn.
+    pub fn prefill_task(&mut self) -> Option<impl Future<Output = ()> + Send + 'static> {
+        let fingerprint = history_fingerprint(&self.history);
+        if self.prefilled_fingerprint == Some(fingerprint) {
+            return None;
+        }
+        self.prefilled_fingerprint = Some(fingerprint);
+
+        let provider = Arc::clone(&self.provider);
+        let messages = self.history.clone();
+        let options = RequestOptions::new()
+            .with_stream(true)
+            .with_reasoning(self.agent_config.reasoning_level);
+        Some(async move {
+            let started = Instant::now();
+            match provider.warm_up(&messages, &options, &[]).await {
+                Ok(()) => tracing::debug!(
+                    messages = messages.len(),
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "prompt prefill complete"
+                ),
+                Err(e) => tracing::debug!(error = %e, "prompt prefill failed"),
+            }
+        })
+    }
+
+    /// Reassemble the system prompt, picking up newly active skills and
+    /// changed preferences.
+    pub fn reload_system_prompt(&mut self) {
+        let prompt = (self.system_prompt_builder)();
+        match self.history.first_mut() {
+            Some(first) if first.role == Role::System => *first = Message::system(prompt),
+            _ => self.history.insert(0, Message::system(prompt)),
+        }
+    }
+
     pub fn truncate_history(&mut self, keep_count: usize) {
         if self.history.len() > 1 + keep_count {
             self.history.truncate(1 + keep_count);
         }
     }
 
+    /// The conversation so far, starting with the system prompt.
+    pub fn history(&self) -> &[Message] {
+        &self.history
+    }
+
+    /// The latest turn: the last user message and everything after it.
+    pub fn last_turn(&self) -> &[Message] {
+        let start = self
+            .history
+            .iter()
+            .rposition(|m| m.role == Role::User)
+            .unwrap_or(self.history.len());
+        &self.history[start..]
+    }
+
+    /// Tokens the latest turn used.
+    pub fn last_turn_tokens(&self) -> u64 {
+        self.last_turn_tokens
+    }
+
+    /// The model's thinking during the latest turn; empty when it did not
+    /// think.
+    pub fn last_turn_thinking(&self) -> &str {
+        &self.last_turn_thinking
+    }
+
+    /// The provider answering this engine's turns.
+    pub fn provider(&self) -> Arc<dyn ProviderAdapter> {
+        Arc::clone(&self.provider)
+    }
+
+    /// Attach `images` to the next user turn. They stay in the history, and
+    /// so in the saved session, with that message.
+    pub fn attach_images(&mut self, images: Vec<ImageAttachment>) {
+        self.pending_images = images;
+    }
+
+    /// Continue a conversation handed off from another device: its messages
+    /// replace the current ones, after this engine's own system prompt.
+    pub fn resume_conversation(&mut self, messages: Vec<Message>) {
+        self.history.retain(|m| m.role == Role::System);
+        self.history
+            .extend(messages.into_iter().filter(|m| m.role != Role::System));
+        self.trim_history();
+    }
+
+    /// Forget the latest user message and everything after it, as if that
+    /// turn never happened. Used when a speculative turn is revised.
+    pub fn discard_last_turn(&mut self) {
+        if let Some(pos) = self.history.iter().rposition(|m| m.role == Role::User) {
+            self.history.truncate(pos);
+        }
+    }
+
     /// Generate a response from the LLM engine.
     ///
     /// # Arguments
@@ -201,11 +405,40 @@ impl FaeAgentLlm {
     ) -> Result<bool> {
         let interrupt_flag = interrupt;
         let user_message = extract_latest_user_message(&user_input);
-        let tool_allowlist = if self.tools_disabled {
+        let mut gate = None;
+        if let Some(policy) = self.response_policy.as_mut() {
+            // Still set if the user barged in on the previous reply.
+            if interrupt_flag.load(Ordering::Relaxed) {
+                policy.record_interruption(Instant::now());
+            }
+            if let Some(rest) = policy.take_continuation(user_message) {
+                self.last_turn_tokens = 0;
+                self.last_turn_thinking.clear();
+                let images = std::mem::take(&mut self.pending_images);
+                self.history
+                    .push(Message::user_with_images(user_message.to_owned(), images));
+                interrupt_flag.store(false, Ordering::Relaxed);
+                self.speak_continuation(rest, &tx).await;
+                return Ok(false);
+            }
+            gate = Some(SentenceGate::new(policy.sentence_limit(Instant::now())));
+        }
+        let mut tool_allowlist = if self.tools_disabled {
             Vec::new()
         } else {
             select_tool_allowlist(user_message)
         };
+        // An answer to a clarification question re-runs the held-back call
+        // with the picked candidate; anything else drops it.
+        let resumed_call = self
+            .pending_clarification
+            .take()
+            .and_then(|pending| pending.resolve(user_message));
+        if let Some(ref call) = resumed_call
+            && !tool_allowlist.contains(&call.function_name)
+        {
+            tool_allowlist.push(call.function_name.clone());
+        }
         tracing::debug!(
             user_message,
             tools = ?tool_allowlist,
@@ -217,7 +450,9 @@ impl FaeAgentLlm {
         // full augmented input (which includes memory recall, onboarding,
         // coding context). This prevents transient context from duplicating
         // across turns and inflating prefill token counts.
-        self.history.push(Message::user(user_message.to_owned()));
+        let images = std::mem::take(&mut self.pending_images);
+        self.history
+            .push(Message::user_with_images(user_message.to_owned(), images));
         self.trim_history();
         self.maybe_compact_history();
         interrupt_flag.store(false, Ordering::Relaxed);
@@ -227,30 +462,66 @@ impl FaeAgentLlm {
             Arc::clone(&self.provider),
             Arc::clone(&self.registry),
         )
+        .with_tool_call_history(shared_tool_call_history())
+        .with_idempotency(self.conversation_id.clone(), shared_recent_tool_keys())
+        .with_injection_audit(crate::fae_dirs::guardrail_audit_file())
         .restrict_tools_to(&tool_allowlist);
         if let Some(ref tx) = self.runtime_tx {
             agent = agent.with_runtime_tx(tx.clone());
         }
+        if let Some(ref summarizer) = self.output_summarizer {
+            agent = agent.with_output_summarizer(Arc::clone(summarizer));
+        }
+        if let Some(ref fallback) = self.fallback_provider {
+            agent = agent.with_fallback_provider(Arc::clone(fallback));
+        }
+        // Experimental: plain voice turns also go to a remote model, which
+        // verifies the local draft or competes with it.
+        let remote = match &self.remote {
+            Some(provider) if self.tools_disabled && resumed_call.is_none() => Some(
+                AgentLoop::new(
+                    self.agent_config.clone(),
+                    Arc::clone(provider),
+                    Arc::clone(&self.registry),
+                )
+                .restrict_tools_to(&[]),
+            ),
+            _ => None,
+        };
+        if let Some(call) = resumed_call {
+            agent = agent.with_resumed_call(call);
+        }
         let cancel = agent.cancellation_token();
+        let remote_cancel = remote.as_ref().map(AgentLoop::cancellation_token);
 
         // Create clause streaming channel for low-latency TTS pipelining.
         // Clauses are streamed during LLM generation so TTS can start
         // synthesizing before the full response is available.
         let (clause_tx, mut clause_rx) = mpsc::channel::<String>(16);
 
-        // Forwarding task: convert raw clause strings into SentenceChunks.
+        // Forwarding task: convert raw clause strings into SentenceChunks,
+        // holding back whatever exceeds the sentence budget.
         let chunk_tx = tx.clone();
         let fwd_handle = tokio::spawn(async move {
             while let Some(clause) = clause_rx.recv().await {
-                if !clause.is_empty() {
-                    let _ = chunk_tx
-                        .send(SentenceChunk {
-                            text: clause,
-                            is_final: false,
-                        })
-                        .await;
+                if clause.is_empty() {
+                    continue;
                 }
+                let clause = match gate.as_mut() {
+                    Some(gate) => match gate.admit(clause) {
+                        Some(clause) => clause,
+                        None => continue,
+                    },
+                    None => clause,
+                };
+                let _ = chunk_tx
+                    .send(SentenceChunk {
+                        text: clause,
+                        is_final: false,
+                    })
+                    .await;
             }
+            gate
         });
 
         // Build per-turn message list: history uses the raw user text, but
@@ -261,29 +532,92 @@ impl FaeAgentLlm {
         if let Some(last) = turn_messages.last_mut()
             && last.role == Role::User
         {
-            *last = Message::user(user_input);
+            let images = std::mem::take(&mut last.images);
+            *last = Message::user_with_images(user_input, images);
         }
-        let run_fut = agent.run_with_messages_streaming(turn_messages, clause_tx);
-        tokio::pin!(run_fut);
-
+        let speech_stop = self.speech_stop.clone();
+        let remote_name = self.best_of.provider.as_deref().unwrap_or("remote");
+        let (speculative, best_of) = (&self.speculative, &self.best_of);
+        let usage = &mut self.usage;
+        let judge = Arc::clone(&self.provider);
         let mut was_interrupted = false;
-        let mut tick = tokio::time::interval(INTERRUPT_POLL_INTERVAL);
-        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-
-        let run_result = loop {
-            tokio::select! {
-                _ = tick.tick() => {
-                    if interrupt_flag.load(Ordering::Relaxed) {
-                        was_interrupted = true;
-                        cancel.cancel();
+        let run_result = {
+            let run_fut = async {
+                let Some(remote) = &remote else {
+                    return agent
+                        .run_with_messages_streaming(turn_messages, clause_tx)
+                        .await;
+                };
+                if speculative.enabled {
+                    let stop_draft = || {
+                        if let Some(stop) = &speech_stop {
+                            stop();
+                        }
+                    };
+                    return run_speculative(
+                        &agent,
+                        remote,
+                        turn_messages,
+                        clause_tx,
+                        speculative,
+                        stop_draft,
+                    )
+                    .await
+                    .map(|outcome| outcome.verified.unwrap_or(outcome.draft));
+                }
+                let candidates = [
+                    BestOfCandidate {
+                        name: "local",
+                        agent: &agent,
+                    },
+                    BestOfCandidate {
+                        name: remote_name,
+                        agent: remote,
+                    },
+                ];
+                let outcome = run_best_of(
+                    &candidates,
+                    turn_messages,
+                    Some(judge.as_ref()),
+                    best_of,
+                    usage,
+                )
+                .await?;
+                tracing::debug!(
+                    winner = %outcome.winner,
+                    total_tokens = usage.total().total(),
+                    requests = usage.requests(),
+                    "best-of usage so far"
+                );
+                // Candidates do not stream; speak the winner once chosen.
+                let _ = clause_tx.send(outcome.result.final_text.clone()).await;
+                let mut result = outcome.result;
+                result.total_usage = outcome.usage;
+                Ok(result)
+            };
+            tokio::pin!(run_fut);
+
+            let mut tick = tokio::time::interval(INTERRUPT_POLL_INTERVAL);
+            tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    _ = tick.tick() => {
+                        if interrupt_flag.load(Ordering::Relaxed) {
+                            was_interrupted = true;
+                            cancel.cancel();
+                            if let Some(ref remote_cancel) = remote_cancel {
+                                remote_cancel.cancel();
+                            }
+                        }
                     }
+                    result = &mut run_fut => break result,
                 }
-                result = &mut run_fut => break result,
             }
         };
 
         // Wait for forwarding task to finish draining clauses.
-        let _ = fwd_handle.await;
+        let gate = fwd_handle.await.ok().flatten();
 
         let result = match run_result {
             Ok(r) => r,
@@ -308,14 +642,32 @@ impl FaeAgentLlm {
             return Ok(true);
         }
 
-        if let StopReason::Error(message) = &result.stop_reason {
+        let failure = match &result.stop_reason {
+            StopReason::Error(message) => Some(format!("agent error: {message}")),
+            StopReason::StreamStalled => Some("agent error: provider stream stalled".to_owned()),
+            _ => None,
+        };
+        if let Some(failure) = failure {
             let _ = tx
                 .send(SentenceChunk {
                     text: String::new(),
                     is_final: true,
                 })
                 .await;
-            return Err(SpeechError::Llm(format!("agent error: {message}")));
+            return Err(SpeechError::Llm(failure));
+        }
+        self.last_turn_tokens = result.total_usage.total();
+        self.last_turn_thinking = result.thinking();
+        if self.show_thinking
+            && !self.last_turn_thinking.is_empty()
+            && let Some(ref runtime_tx) = self.runtime_tx
+        {
+            let text = self.last_turn_thinking.clone();
+            let _ = runtime_tx.send(RuntimeEvent::AssistantThinking { text });
+        }
+        self.pending_clarification = result.pending_clarification();
+        if changed_system_prompt(&result) {
+            self.reload_system_prompt();
         }
 
         // Duplicate detection: if the model produced the same response as
@@ -343,6 +695,17 @@ impl FaeAgentLlm {
         }
         self.track_response(&result.final_text);
 
+        // A capped reply is remembered as what the user actually heard, so
+        // the model does not assume it already said the rest.
+        if let Some(gate) = gate
+            && let (heard, true) = self.close_reply(gate, &tx).await
+            && let Some(last) = self.history.last_mut()
+            && last.role == Role::Assistant
+            && last.tool_calls.is_empty()
+        {
+            *last = Message::assistant(heard);
+        }
+
         // All clause chunks were streamed during generation; send the
         // final marker so the TTS stage knows the response is complete.
         let _ = tx
@@ -355,6 +718,70 @@ impl FaeAgentLlm {
         Ok(false)
     }
 
+    /// Speak the held-back rest of the previous reply after the user asked
+    /// for more, capping it again if it is itself long.
+    async fn speak_continuation(&mut self, rest: Vec<String>, tx: &mpsc::Sender<SentenceChunk>) {
+        let limit = self
+            .response_policy
+            .as_mut()
+            .map_or(0, |policy| policy.sentence_limit(Instant::now()));
+        let mut gate = SentenceGate::new(limit);
+        for clause in rest {
+            if let Some(text) = gate.admit(clause) {
+                let _ = tx
+                    .send(SentenceChunk {
+                        text,
+                        is_final: false,
+                    })
+                    .await;
+            }
+        }
+        let (heard, _) = self.close_reply(gate, tx).await;
+        self.history.push(Message::assistant(heard));
+        self.trim_history();
+        let _ = tx
+            .send(SentenceChunk {
+                text: String::new(),
+                is_final: true,
+            })
+            .await;
+    }
+
+    /// Speak or offer what `gate` held back once its reply is complete.
+    ///
+    /// Returns the reply as the user heard it, and whether it was cut short.
+    async fn close_reply(
+        &mut self,
+        gate: SentenceGate,
+        tx: &mpsc::Sender<SentenceChunk>,
+    ) -> (String, bool) {
+        let mut heard = gate.spoken().to_owned();
+        let (text, capped) = match gate.finish() {
+            ReplyTail::None => return (heard, false),
+            ReplyTail::Speak(rest) => (rest, false),
+            ReplyTail::Offer(rest) => {
+                if let Some(policy) = self.response_policy.as_mut() {
+                    policy.offer_continuation(rest);
+                }
+                (
+                    crate::i18n::text("conversation.continue_prompt").to_owned(),
+                    true,
+                )
+            }
+        };
+        let _ = tx
+            .send(SentenceChunk {
+                text: text.clone(),
+                is_final: false,
+            })
+            .await;
+        if !heard.is_empty() {
+            heard.push(' ');
+        }
+        heard.push_str(&text);
+        (heard, capped)
+    }
+
     fn append_result_messages(&mut self, result: &AgentLoopResult) {
         for message in build_messages_from_result(result, None) {
             if message.role != Role::System {
@@ -363,13 +790,19 @@ impl FaeAgentLlm {
         }
     }
 
+    /// Drop the oldest messages once history exceeds its cap.
+    ///
+    /// Trims to three quarters of the cap rather than exactly to it, so the
+    /// next few turns only append and the local model's prefix cache stays
+    /// valid instead of shifting on every turn.
     fn trim_history(&mut self) {
         if self.max_history_messages == 0 {
             return;
         }
 
         if self.history.len() > 1 + self.max_history_messages {
-            let drain_end = self.history.len().saturating_sub(self.max_history_messages);
+            let keep = self.max_history_messages - self.max_history_messages / 4;
+            let drain_end = self.history.len().saturating_sub(keep);
             if drain_end > 1 {
                 self.history.drain(1..drain_end);
             }
@@ -482,6 +915,9 @@ fn select_tool_allowlist(user_text: &str) -> Vec<String> {
         allow.insert("create_calendar_event");
         allow.insert("update_calendar_event");
         allow.insert("delete_calendar_event");
+        allow.insert("calendar_free_busy");
+        allow.insert("propose_meeting_slots");
+        allow.insert("create_calendar_hold");
     }
 
     if contains_any(&lower, intent::REMINDERS_KEYWORDS) {
@@ -494,6 +930,7 @@ fn select_tool_allowlist(user_text: &str) -> Vec<String> {
     if contains_any(&lower, intent::NOTES_KEYWORDS) {
         allow.insert("list_notes");
         allow.insert("get_note");
+        allow.insert("search_notes");
         allow.insert("create_note");
         allow.insert("append_to_note");
     }
@@ -504,6 +941,24 @@ fn select_tool_allowlist(user_text: &str) -> Vec<String> {
         allow.insert("compose_mail");
     }
 
+    if contains_any(&lower, intent::MEDIA_KEYWORDS) {
+        allow.insert("now_playing");
+        allow.insert("media_control");
+        allow.insert("play_music");
+    }
+
+    if contains_any(&lower, intent::SYSTEM_SETTINGS_KEYWORDS) {
+        allow.insert("system_control");
+    }
+
+    if contains_any(&lower, intent::SYSTEM_INFO_KEYWORDS) {
+        allow.insert("system_info");
+    }
+
+    if contains_any(&lower, intent::NETWORK_DIAG_KEYWORDS) {
+        allow.insert("network_diagnostics");
+    }
+
     if contains_any(&lower, intent::CONTACTS_KEYWORDS) {
         allow.insert("search_contacts");
         allow.insert("get_contact");
@@ -527,6 +982,65 @@ fn select_tool_allowlist(user_text: &str) -> Vec<String> {
         allow.insert("read");
     }
 
+    if contains_any(&lower, intent::PICK_FILE_KEYWORDS) {
+        allow.insert("pick_file");
+        allow.insert("read");
+    }
+
+    if contains_any(&lower, intent::NOTIFY_KEYWORDS) {
+        allow.insert("post_notification");
+    }
+
+    if contains_any(&lower, intent::SHARE_KEYWORDS) {
+        allow.insert("share");
+    }
+
+    if contains_any(&lower, intent::CODE_NAVIGATION_KEYWORDS) {
+        allow.insert("lsp");
+        allow.insert("read");
+    }
+
+    if contains_any(&lower, intent::TODO_KEYWORDS) {
+        allow.insert("list_todos");
+        allow.insert("update_todo");
+    }
+
+    if contains_any(&lower, intent::PREFERENCE_KEYWORDS) {
+        allow.insert("get_preference");
+        allow.insert("set_preference");
+    }
+
+    if contains_any(&lower, intent::UNDO_KEYWORDS) {
+        allow.insert("undo");
+    }
+
+    if contains_any(&lower, intent::SKILL_AUTHORING_KEYWORDS) {
+        allow.insert("create_skill");
+    }
+
+    if contains_any(&lower, intent::FILE_ORGANIZE_KEYWORDS) {
+        allow.insert("file_organize");
+    }
+
+    if contains_any(&lower, intent::HTTP_REQUEST_KEYWORDS) {
+        allow.insert("http_request");
+    }
+
+    if contains_any(&lower, intent::ARCHIVE_KEYWORDS) {
+        allow.insert("list_archive");
+        allow.insert("archive");
+    }
+
+    if contains_any(&lower, intent::TRANSCRIBE_KEYWORDS) {
+        allow.insert("transcribe_file");
+    }
+
+    if contains_any(&lower, intent::SYNTHESIZE_KEYWORDS) {
+        allow.insert("synthesize_to_file");
+        allow.insert("fetch_url");
+        allow.insert("read");
+    }
+
     if contains_any(&lower, intent::X0X_KEYWORDS) {
         allow.insert("x0x");
     }
@@ -555,16 +1069,7 @@ fn select_tool_allowlist(user_text: &str) -> Vec<String> {
 pub fn select_tool_allowlist_for_prompt(prompt: &str) -> Vec<String> {
     let mut tools = select_tool_allowlist(prompt);
     // Remove scheduler tools — fired tasks should not create new tasks.
-    tools.retain(|t| {
-        !matches!(
-            t.as_str(),
-            "list_scheduled_tasks"
-                | "create_scheduled_task"
-                | "update_scheduled_task"
-                | "delete_scheduled_task"
-                | "trigger_scheduled_task"
-        )
-    });
+    tools.retain(|t| !is_scheduler_tool(t));
     // If nothing matched, default to web search (most scheduled tasks fetch info).
     if tools.is_empty() {
         tools.push("fetch_url".to_owned());
@@ -573,6 +1078,35 @@ pub fn select_tool_allowlist_for_prompt(prompt: &str) -> Vec<String> {
     tools
 }
 
+/// Select the tool allowlist for a scheduled task with an explicit tool list.
+///
+/// An empty `allowed` list falls back to [`select_tool_allowlist_for_prompt`].
+/// Scheduler management tools are always removed.
+pub fn select_tool_allowlist_for_scheduled_task(prompt: &str, allowed: &[String]) -> Vec<String> {
+    if allowed.is_empty() {
+        return select_tool_allowlist_for_prompt(prompt);
+    }
+    let mut tools: Vec<String> = allowed
+        .iter()
+        .filter(|t| !is_scheduler_tool(t))
+        .cloned()
+        .collect();
+    tools.sort();
+    tools.dedup();
+    tools
+}
+
+fn is_scheduler_tool(name: &str) -> bool {
+    matches!(
+        name,
+        "list_scheduled_tasks"
+            | "create_scheduled_task"
+            | "update_scheduled_task"
+            | "delete_scheduled_task"
+            | "trigger_scheduled_task"
+    )
+}
+
 /// Intent classification result from `classify_intent()`.
 ///
 /// Determines whether a user message requires background tool execution
@@ -698,6 +1232,7 @@ pub fn classify_intent_with_context(
             "list_calendars".to_owned(),
             "list_calendar_events".to_owned(),
             "create_calendar_event".to_owned(),
+            "create_calendar_hold".to_owned(),
         ]
     };
 
@@ -728,6 +1263,9 @@ pub struct BackgroundAgentTask {
     pub conversation_context: String,
     /// Tool names this agent should have access to.
     pub tool_allowlist: Vec<String>,
+    /// Held-back call to run first, now that the user has answered its
+    /// clarification question.
+    pub resumed_call: Option<AccumulatedToolCall>,
 }
 
 /// Result from a completed background agent task.
@@ -738,6 +1276,17 @@ pub struct BackgroundAgentResult {
     pub success: bool,
     /// Text to speak via TTS (the agent's final answer).
     pub spoken_summary: String,
+    /// Call waiting on the user's answer when `spoken_summary` asks which
+    /// of several matches they meant.
+    pub clarification: Option<PendingClarification>,
+}
+
+/// Thinking budget table for the model `config` runs.
+fn thinking_budget(config: &LlmConfig) -> ThinkingBudget {
+    config
+        .thinking
+        .budgets
+        .for_tier(crate::model_tier::tier_for_model(&config.model_id))
 }
 
 /// Select the reasoning level for a background agent task.
@@ -805,17 +1354,24 @@ pub async fn spawn_background_agent(
         .to_owned();
 
     let credential_manager = crate::credentials::create_manager();
-    let provider = build_provider(&config, preloaded_llm, credential_manager.as_ref()).await;
-    let registry = build_registry(&config, channels);
+    let (provider, fallback_provider) =
+        build_provider(&config, preloaded_llm, credential_manager.as_ref()).await;
+    let registry = build_registry(&config, channels, runtime_tx.as_ref());
 
     let parallel_tool_calls = matches!(config.tool_mode, AgentToolMode::ReadOnly);
+    let fae_llm_config = read_fae_llm_config().unwrap_or_default();
+    let tools_config = &fae_llm_config.tools;
     let agent_config = FaeAgentConfig::new()
         .with_parallel_tool_calls(parallel_tool_calls)
         .with_max_parallel_tool_calls(4)
-        .with_reasoning_level(reasoning_level);
+        .with_reasoning_level(reasoning_level)
+        .with_thinking_budget(thinking_budget(&config))
+        .with_tool_output_limits(tools_config.output_limits())
+        .with_tool_limits(tools_config.execution_limits())
+        .with_sampling(model_sampling(&fae_llm_config, &config));
 
     // Build the input prompt with conversation context.
-    let input = if task.conversation_context.is_empty() {
+    let mut input = if task.conversation_context.is_empty() {
         format!("User message:\n{}", task.user_message)
     } else {
         format!(
@@ -823,14 +1379,29 @@ pub async fn spawn_background_agent(
             task.conversation_context, task.user_message
         )
     };
+    if let Some(turn_ctx) = crate::turn_context::render() {
+        input = format!("{turn_ctx}\n\n{input}");
+    }
 
     let history = vec![Message::system(bg_system_prompt)];
 
     let mut agent = AgentLoop::new(agent_config, Arc::clone(&provider), Arc::clone(&registry))
+        .with_tool_call_history(shared_tool_call_history())
+        .with_idempotency(task.id.clone(), shared_recent_tool_keys())
+        .with_injection_audit(crate::fae_dirs::guardrail_audit_file())
         .restrict_tools_to(&task.tool_allowlist);
     if let Some(ref tx) = runtime_tx {
         agent = agent.with_runtime_tx(tx.clone());
     }
+    if let Some(summarizer) = build_output_summarizer(&provider, preloaded_llm) {
+        agent = agent.with_output_summarizer(summarizer);
+    }
+    if let Some(fallback) = fallback_provider {
+        agent = agent.with_fallback_provider(fallback);
+    }
+    if let Some(call) = task.resumed_call {
+        agent = agent.with_resumed_call(call);
+    }
 
     // Collect output text (no streaming to TTS — we batch the result).
     let (collect_tx, mut collect_rx) = mpsc::channel::<String>(32);
@@ -861,7 +1432,12 @@ pub async fn spawn_background_agent(
             // Prefer streamed text; fall back to result's final_text.
             // If both are empty the agent produced no narration — synthesise a
             // minimal fallback so the coordinator always has something to speak.
-            let spoken = if !collected_text.trim().is_empty() {
+            // A clarification question is spoken on its own, without the
+            // progress cue that preceded it.
+            let clarification = result.pending_clarification();
+            let spoken = if clarification.is_some() {
+                result.final_text.clone()
+            } else if !collected_text.trim().is_empty() {
                 collected_text
             } else if !result.final_text.trim().is_empty() {
                 result.final_text.trim().to_owned()
@@ -873,6 +1449,7 @@ pub async fn spawn_background_agent(
                 task_id: task.id,
                 success: true,
                 spoken_summary: spoken,
+                clarification,
             }
         }
         Err(e) => {
@@ -880,7 +1457,11 @@ pub async fn spawn_background_agent(
             BackgroundAgentResult {
                 task_id: task.id,
                 success: false,
-                spoken_summary: format!("Sorry, I couldn't complete that. {e}"),
+                spoken_summary: crate::i18n::format(
+                    "conversation.background_failed",
+                    &[("error", &e.to_string())],
+                ),
+                clarification: None,
             }
         }
     }
@@ -894,6 +1475,10 @@ impl ProviderAdapter for MissingLocalModelAdapter {
         "missing_provider_config"
     }
 
+    fn endpoint_type(&self) -> EndpointType {
+        EndpointType::Local
+    }
+
     async fn send(
         &self,
         _messages: &[Message],
@@ -908,7 +1493,76 @@ impl ProviderAdapter for MissingLocalModelAdapter {
     }
 }
 
+/// The `fae_llm` config file (providers, models and `[tools]`), if present
+/// and valid.
+fn read_fae_llm_config() -> Option<crate::fae_llm::config::FaeLlmConfig> {
+    crate::fae_llm::config::read_config(&crate::fae_dirs::llm_config_file()).ok()
+}
+
+/// The remote provider for speculative generation or best-of answering,
+/// when one of them is enabled.
+fn experimental_remote(
+    fae_llm_config: &crate::fae_llm::config::FaeLlmConfig,
+    config: &LlmConfig,
+) -> Option<Arc<dyn ProviderAdapter>> {
+    let experimental = &fae_llm_config.experimental;
+    let (feature, provider_id, model) = if experimental.speculative.enabled {
+        (
+            "speculative generation",
+            experimental.speculative.provider.as_deref()?,
+            experimental.speculative.model.as_deref(),
+        )
+    } else if experimental.best_of.enabled {
+        (
+            "best-of answering",
+            experimental.best_of.provider.as_deref()?,
+            experimental.best_of.model.as_deref(),
+        )
+    } else {
+        return None;
+    };
+    let Some(remote) = remote_provider_from_config(fae_llm_config, provider_id, model) else {
+        tracing::warn!(
+            provider = provider_id,
+            "{feature} needs an enabled remote chat provider; using the local model only"
+        );
+        return None;
+    };
+    tracing::info!(provider = provider_id, "{feature} enabled");
+    Some(PiiMaskingProvider::wrap(remote, config.remote_pii_masking))
+}
+
+/// Sampling overrides configured for the model in use.
+fn model_sampling(
+    fae_llm_config: &crate::fae_llm::config::FaeLlmConfig,
+    config: &LlmConfig,
+) -> crate::fae_llm::config::types::SamplingConfig {
+    fae_llm_config
+        .model(&config.model_id)
+        .map(|model| model.sampling.clone())
+        .unwrap_or_default()
+}
+
+/// The agent's provider, and the backend to fall back to when it routes
+/// turns to a remote model (image turns) and that model stalls.
 async fn build_provider(
+    config: &LlmConfig,
+    preloaded_llm: Option<&LocalLlm>,
+    manager: &dyn crate::credentials::CredentialManager,
+) -> (Arc<dyn ProviderAdapter>, Option<Arc<dyn ProviderAdapter>>) {
+    let provider = build_backend_provider(config, preloaded_llm, manager).await;
+    // No-op for local providers; personal data only needs masking when the
+    // request leaves the machine.
+    let provider = PiiMaskingProvider::wrap(provider, config.remote_pii_masking);
+    let vision = read_fae_llm_config()
+        .and_then(|llm_config| vision_provider_from_config(&llm_config))
+        .map(|vision| PiiMaskingProvider::wrap(vision, config.remote_pii_masking));
+    let routed = VisionRoutingProvider::wrap(Arc::clone(&provider), vision);
+    let fallback = (!Arc::ptr_eq(&routed, &provider)).then_some(provider);
+    (routed, fallback)
+}
+
+async fn build_backend_provider(
     config: &LlmConfig,
     preloaded_llm: Option<&LocalLlm>,
     _manager: &dyn crate::credentials::CredentialManager,
@@ -922,7 +1576,8 @@ async fn build_provider(
             LocalMistralrsConfig::new(local_llm.shared_model(), config.model_id.clone())
                 .with_temperature(config.temperature as f32)
                 .with_top_p(config.top_p as f32)
-                .with_max_tokens(config.max_tokens);
+                .with_max_tokens(config.max_tokens)
+                .with_vision(local_llm.vision_capable);
         if let Some(k) = config.top_k {
             provider_cfg = provider_cfg.with_top_k(k);
         }
@@ -934,6 +1589,36 @@ Ensure a GGUF or vision model is configured.";
     Arc::new(MissingLocalModelAdapter)
 }
 
+/// Summarizer for oversized tool output, backed by the local model when one
+/// is loaded.
+fn build_output_summarizer(
+    provider: &Arc<dyn ProviderAdapter>,
+    preloaded_llm: Option<&LocalLlm>,
+) -> Option<Arc<dyn OutputSummarizer>> {
+    if preloaded_llm.is_none() {
+        return None;
+    }
+    Some(Arc::new(ProviderSummarizer::new(Arc::clone(provider))))
+}
+
+/// Names of every tool the agent can offer in this build, as registered at
+/// full access. Used to check the tools a skill refers to.
+pub fn known_tool_names() -> Vec<String> {
+    let config = LlmConfig {
+        tool_mode: AgentToolMode::FullNoApproval,
+        ..LlmConfig::default()
+    };
+    let channels = AgentChannels {
+        canvas_registry: Some(Arc::new(Mutex::new(CanvasSessionRegistry::new()))),
+        ..AgentChannels::default()
+    };
+    build_registry(&config, channels, None)
+        .list_available()
+        .into_iter()
+        .map(str::to_owned)
+        .collect()
+}
+
 /// Build a tool registry from the config.
 ///
 /// `shared_permissions` is the live permission store to pass to all
@@ -943,7 +1628,13 @@ Ensure a GGUF or vision model is configured.";
 /// (pipeline coordinator) should pass the handler's `shared_permissions()` so
 /// that runtime grants are immediately visible to tools without a registry
 /// rebuild.
-fn build_registry(config: &LlmConfig, channels: AgentChannels) -> Arc<ToolRegistry> {
+///
+/// `runtime_tx`, when present, receives progress of long-running tools.
+fn build_registry(
+    config: &LlmConfig,
+    channels: AgentChannels,
+    runtime_tx: Option<&broadcast::Sender<RuntimeEvent>>,
+) -> Arc<ToolRegistry> {
     let AgentChannels {
         tool_approval_tx,
         canvas_registry,
@@ -958,51 +1649,120 @@ fn build_registry(config: &LlmConfig, channels: AgentChannels) -> Arc<ToolRegist
     };
     let mut registry = ToolRegistry::new(mode);
 
+    // Each network-capable tool applies its `[tools.<name>]` domain rules
+    // over the global policy. The guards share approval answers, so a domain
+    // approved for one tool is not asked about again for another.
+    let mut network = NetworkGuard::new(config.network.clone());
+    if let Some(tx) = &tool_approval_tx {
+        network = network.with_approver(Arc::new(ChannelDomainApprover {
+            approval_tx: tx.clone(),
+            timeout: config.approval_timeouts.for_tool("network_access"),
+        }));
+    }
+    let tools_config = read_fae_llm_config().map(|c| c.tools).unwrap_or_default();
+    let network_for = |tool_name: &str| {
+        Arc::new(network.with_policy(tools_config.network_policy(tool_name, &config.network)))
+    };
+    let bash = || BashTool::new().with_network_guard(network_for("bash"));
+    let skill_credentials: Arc<dyn crate::credentials::CredentialManager> =
+        Arc::from(crate::credentials::create_manager());
+    let python_skill = || {
+        PythonSkillTool::with_default_dir()
+            .with_network_guard(network_for("python_skill"))
+            .with_credential_manager(Arc::clone(&skill_credentials))
+            .with_grant_ledger(crate::fae_dirs::skill_credential_grants_file())
+    };
+
+    // write, edit and file_organize record into one undo history, reverted
+    // by the undo tool. The file tools keep to the scope of a narrowed
+    // Files grant.
+    let undo = Arc::new(UndoStore::default_location());
+    let file_permissions = shared_permissions
+        .clone()
+        .unwrap_or_else(crate::permissions::PermissionStore::default_shared);
+    let read = || ReadTool::new().with_permissions(Arc::clone(&file_permissions));
+    let write = || {
+        WriteTool::new()
+            .with_undo_store(Arc::clone(&undo))
+            .with_permissions(Arc::clone(&file_permissions))
+    };
+    let edit = || {
+        EditTool::new()
+            .with_undo_store(Arc::clone(&undo))
+            .with_permissions(Arc::clone(&file_permissions))
+    };
+    let file_organize = || {
+        FileOrganizeTool::new()
+            .with_undo_store(Arc::clone(&undo))
+            .with_permissions(Arc::clone(&file_permissions))
+    };
+    let undo_tool = || UndoTool::new(Arc::clone(&undo));
+
     // Helper: wrap a tool with approval gating and register it.
     let register_with_approval = |tool: Arc<dyn crate::fae_llm::tools::Tool>,
                                   reg: &mut ToolRegistry| {
+        let timeout = config.approval_timeouts.for_tool(tool.name());
         reg.register(Arc::new(ApprovalTool::new(
             tool,
             tool_approval_tx.clone(),
-            APPROVAL_TIMEOUT,
+            timeout,
         )));
     };
 
     match config.tool_mode {
         AgentToolMode::Off => {}
         AgentToolMode::ReadOnly => {
-            registry.register(Arc::new(ReadTool::new()));
+            registry.register(Arc::new(read()));
+            registry.register(Arc::new(LspTool::new()));
+            registry.register(Arc::new(ListArchiveTool::new()));
         }
         AgentToolMode::ReadWrite => {
-            registry.register(Arc::new(ReadTool::new()));
-            register_with_approval(Arc::new(WriteTool::new()), &mut registry);
-            register_with_approval(Arc::new(EditTool::new()), &mut registry);
+            registry.register(Arc::new(read()));
+            registry.register(Arc::new(LspTool::new()));
+            registry.register(Arc::new(ListArchiveTool::new()));
+            register_with_approval(Arc::new(write()), &mut registry);
+            register_with_approval(Arc::new(edit()), &mut registry);
+            register_with_approval(Arc::new(ArchiveTool::new()), &mut registry);
+            register_with_approval(Arc::new(undo_tool()), &mut registry);
         }
         AgentToolMode::Full => {
-            register_with_approval(Arc::new(BashTool::new()), &mut registry);
-            registry.register(Arc::new(ReadTool::new()));
-            register_with_approval(Arc::new(WriteTool::new()), &mut registry);
-            register_with_approval(Arc::new(EditTool::new()), &mut registry);
-            register_with_approval(Arc::new(PythonSkillTool::with_default_dir()), &mut registry);
+            register_with_approval(Arc::new(bash()), &mut registry);
+            registry.register(Arc::new(read()));
+            registry.register(Arc::new(LspTool::new()));
+            registry.register(Arc::new(ListArchiveTool::new()));
+            register_with_approval(Arc::new(write()), &mut registry);
+            register_with_approval(Arc::new(edit()), &mut registry);
+            register_with_approval(Arc::new(ArchiveTool::new()), &mut registry);
+            register_with_approval(Arc::new(file_organize()), &mut registry);
+            register_with_approval(Arc::new(undo_tool()), &mut registry);
+            register_with_approval(Arc::new(python_skill()), &mut registry);
             // Desktop automation (Full mode, with approval).
+            #[cfg(feature = "desktop")]
             if let Some(desktop_tool) = crate::fae_llm::tools::DesktopTool::try_new() {
                 register_with_approval(Arc::new(desktop_tool), &mut registry);
             }
         }
         AgentToolMode::FullNoApproval => {
             // No approval needed - register tools directly
-            registry.register(Arc::new(BashTool::new()));
-            registry.register(Arc::new(ReadTool::new()));
-            registry.register(Arc::new(WriteTool::new()));
-            registry.register(Arc::new(EditTool::new()));
-            registry.register(Arc::new(PythonSkillTool::with_default_dir()));
+            registry.register(Arc::new(bash()));
+            registry.register(Arc::new(read()));
+            registry.register(Arc::new(LspTool::new()));
+            registry.register(Arc::new(ListArchiveTool::new()));
+            registry.register(Arc::new(write()));
+            registry.register(Arc::new(edit()));
+            registry.register(Arc::new(ArchiveTool::new()));
+            registry.register(Arc::new(file_organize()));
+            registry.register(Arc::new(undo_tool()));
+            registry.register(Arc::new(python_skill()));
             // Desktop automation (no approval).
+            #[cfg(feature = "desktop")]
             if let Some(desktop_tool) = crate::fae_llm::tools::DesktopTool::try_new() {
                 registry.register(Arc::new(desktop_tool));
             }
         }
     }
 
+    #[cfg(feature = "canvas")]
     if !matches!(config.tool_mode, AgentToolMode::Off)
         && let Some(canvas_registry) = canvas_registry
     {
@@ -1010,12 +1770,82 @@ fn build_registry(config: &LlmConfig, channels: AgentChannels) -> Arc<ToolRegist
         registry.register(Arc::new(CanvasInteractTool::new(canvas_registry.clone())));
         registry.register(Arc::new(CanvasExportTool::new(canvas_registry)));
     }
+    #[cfg(not(feature = "canvas"))]
+    let _ = canvas_registry;
 
     // Web search tools (read-only, allowed in all non-Off modes).
     if !matches!(config.tool_mode, AgentToolMode::Off) {
         use crate::fae_llm::tools::{FetchUrlTool, WebSearchTool};
-        registry.register(Arc::new(WebSearchTool::new()));
-        registry.register(Arc::new(FetchUrlTool::new()));
+        registry.register(Arc::new(
+            WebSearchTool::new().with_network_guard(network_for("web_search")),
+        ));
+        registry.register(Arc::new(
+            FetchUrlTool::new().with_network_guard(network_for("fetch_url")),
+        ));
+    }
+
+    // Generic HTTP requests: GET and HEAD skip approval, other methods follow
+    // the scheduler's approval rules.
+    if !matches!(config.tool_mode, AgentToolMode::Off) {
+        use crate::fae_llm::tools::HttpRequestTool;
+        let http_request = HttpRequestTool::new().with_network_guard(network_for("http_request"));
+        if matches!(config.tool_mode, AgentToolMode::FullNoApproval) {
+            registry.register(Arc::new(http_request));
+        } else {
+            register_with_approval(Arc::new(http_request), &mut registry);
+        }
+    }
+
+    // System info (read-only, allowed in all non-Off modes).
+    if !matches!(config.tool_mode, AgentToolMode::Off) {
+        registry.register(Arc::new(crate::fae_llm::tools::SystemInfoTool::new()));
+    }
+
+    // Audio file transcription (read-only, allowed in all non-Off modes).
+    if !matches!(config.tool_mode, AgentToolMode::Off) {
+        let mut transcribe = crate::fae_llm::tools::TranscribeFileTool::new();
+        if let Some(tx) = runtime_tx {
+            let tx = tx.clone();
+            transcribe = transcribe.with_progress(Arc::new(move |fraction| {
+                let _ = tx.send(RuntimeEvent::ToolProgress {
+                    name: "transcribe_file".to_owned(),
+                    fraction,
+                });
+            }));
+        }
+        registry.register(Arc::new(transcribe));
+    }
+
+    // Saving speech to an audio file writes to disk, so it needs a
+    // write-capable mode and follows the write tool's approval rules.
+    if matches!(
+        config.tool_mode,
+        AgentToolMode::ReadWrite | AgentToolMode::Full | AgentToolMode::FullNoApproval
+    ) {
+        let mut synthesize = crate::fae_llm::tools::SynthesizeToFileTool::new();
+        if let Some(tx) = runtime_tx {
+            let tx = tx.clone();
+            synthesize = synthesize.with_progress(Arc::new(move |fraction| {
+                let _ = tx.send(RuntimeEvent::ToolProgress {
+                    name: "synthesize_to_file".to_owned(),
+                    fraction,
+                });
+            }));
+        }
+        if matches!(config.tool_mode, AgentToolMode::FullNoApproval) {
+            registry.register(Arc::new(synthesize));
+        } else {
+            register_with_approval(Arc::new(synthesize), &mut registry);
+        }
+    }
+
+    // Native UI through the host shell (non-Off modes). The user confirms
+    // picks and shares in the shell itself, so these skip approval.
+    if !matches!(config.tool_mode, AgentToolMode::Off) {
+        use crate::fae_llm::tools::{NotifyTool, PickFileTool, ShareTool};
+        registry.register(Arc::new(PickFileTool::new()));
+        registry.register(Arc::new(NotifyTool::new()));
+        registry.register(Arc::new(ShareTool::new()));
     }
 
     // x0x gossip network tool — gated by Network permission.
@@ -1058,6 +1888,30 @@ fn build_registry(config: &LlmConfig, channels: AgentChannels) -> Arc<ToolRegist
         }
     }
 
+    // Todo tools: list is direct; update follows the scheduler's approval rules.
+    if !matches!(config.tool_mode, AgentToolMode::Off) {
+        use crate::fae_llm::tools::apple::global_reminder_store;
+        use crate::fae_llm::tools::{ListTodosTool, UpdateTodoTool};
+        registry.register(Arc::new(ListTodosTool::new()));
+        let update = UpdateTodoTool::new().with_reminder_store(global_reminder_store());
+        if matches!(config.tool_mode, AgentToolMode::FullNoApproval) {
+            registry.register(Arc::new(update));
+        } else {
+            register_with_approval(Arc::new(update), &mut registry);
+        }
+    }
+
+    // Preference tools: get is direct; set follows the scheduler's approval rules.
+    if !matches!(config.tool_mode, AgentToolMode::Off) {
+        use crate::fae_llm::tools::{GetPreferenceTool, SetPreferenceTool};
+        registry.register(Arc::new(GetPreferenceTool::new()));
+        if matches!(config.tool_mode, AgentToolMode::FullNoApproval) {
+            registry.register(Arc::new(SetPreferenceTool::new()));
+        } else {
+            register_with_approval(Arc::new(SetPreferenceTool::new()), &mut registry);
+        }
+    }
+
     // Apple ecosystem tools — always registered in non-Off modes.
     // Each tool is wrapped with AvailabilityGatedTool so execution is blocked
     // at runtime when the required permission has not been granted.
@@ -1068,11 +1922,13 @@ fn build_registry(config: &LlmConfig, channels: AgentChannels) -> Arc<ToolRegist
     if !matches!(config.tool_mode, AgentToolMode::Off) {
         use crate::fae_llm::tools::apple::{
             AppendToNoteTool, AvailabilityGatedTool, ComposeMailTool, CreateContactTool,
-            CreateEventTool, CreateNoteTool, CreateReminderTool, DeleteEventTool, GetContactTool,
-            GetMailTool, GetNoteTool, ListCalendarsTool, ListEventsTool, ListNotesTool,
-            ListReminderListsTool, ListRemindersTool, SearchContactsTool, SearchMailTool,
-            SetReminderCompletedTool, UpdateEventTool, global_calendar_store, global_contact_store,
-            global_mail_store, global_note_store, global_reminder_store,
+            CreateEventTool, CreateHoldTool, CreateNoteTool, CreateReminderTool, DeleteEventTool,
+            FreeBusyTool, GetContactTool, GetMailTool, GetNoteTool, ListCalendarsTool,
+            ListEventsTool, ListNotesTool, ListReminderListsTool, ListRemindersTool,
+            MediaControlTool, NowPlayingTool, PlayMusicTool, ProposeSlotsTool, SearchContactsTool,
+            SearchMailTool, SearchNotesTool, SetReminderCompletedTool, UpdateEventTool,
+            global_calendar_store, global_contact_store, global_mail_store, global_media_player,
+            global_note_store, global_reminder_store,
         };
         use crate::permissions::PermissionStore;
 
@@ -1083,13 +1939,16 @@ fn build_registry(config: &LlmConfig, channels: AgentChannels) -> Arc<ToolRegist
         let reminders = global_reminder_store();
         let notes = global_note_store();
         let mail = global_mail_store();
+        let media = global_media_player();
 
         // Helper to wrap an AppleEcosystemTool with permission gating.
         // When a JIT request channel is available, the gate can emit a
         // `JitPermissionRequest` that triggers a native permission dialog.
+        // Permitted executions go to the ledger behind the monthly review.
         macro_rules! gated {
             ($tool:expr) => {{
-                let gate = AvailabilityGatedTool::new(Arc::new($tool), Arc::clone(&perms));
+                let gate = AvailabilityGatedTool::new(Arc::new($tool), Arc::clone(&perms))
+                    .with_usage_ledger(crate::fae_dirs::permission_usage_file());
                 let gate = if let Some(ref jit_tx) = jit_request_tx {
                     gate.with_jit_channel(jit_tx.clone())
                 } else {
@@ -1106,6 +1965,9 @@ fn build_registry(config: &LlmConfig, channels: AgentChannels) -> Arc<ToolRegist
         registry.register(gated!(ListEventsTool::new(Arc::clone(&calendars))));
         registry.register(gated!(CreateEventTool::new(Arc::clone(&calendars))));
         registry.register(gated!(UpdateEventTool::new(Arc::clone(&calendars))));
+        registry.register(gated!(FreeBusyTool::new(Arc::clone(&calendars))));
+        registry.register(gated!(ProposeSlotsTool::new(Arc::clone(&calendars))));
+        registry.register(gated!(CreateHoldTool::new(Arc::clone(&calendars))));
         registry.register(gated!(DeleteEventTool::new(calendars)));
         registry.register(gated!(ListReminderListsTool::new(Arc::clone(&reminders))));
         registry.register(gated!(ListRemindersTool::new(Arc::clone(&reminders))));
@@ -1113,21 +1975,92 @@ fn build_registry(config: &LlmConfig, channels: AgentChannels) -> Arc<ToolRegist
         registry.register(gated!(SetReminderCompletedTool::new(reminders)));
         registry.register(gated!(ListNotesTool::new(Arc::clone(&notes))));
         registry.register(gated!(GetNoteTool::new(Arc::clone(&notes))));
+        registry.register(gated!(SearchNotesTool::new(Arc::clone(&notes))));
         registry.register(gated!(CreateNoteTool::new(Arc::clone(&notes))));
         registry.register(gated!(AppendToNoteTool::new(notes)));
         registry.register(gated!(SearchMailTool::new(Arc::clone(&mail))));
         registry.register(gated!(GetMailTool::new(Arc::clone(&mail))));
         registry.register(gated!(ComposeMailTool::new(mail)));
+        registry.register(gated!(NowPlayingTool::new(Arc::clone(&media))));
+        registry.register(gated!(MediaControlTool::new(Arc::clone(&media))));
+        registry.register(gated!(PlayMusicTool::new(media)));
+
+        // System settings share the gate, under their own permission.
+        if let Some(system) = crate::fae_llm::tools::SystemControlTool::try_new() {
+            registry.register(gated!(system));
+        }
+        registry.register(gated!(
+            crate::fae_llm::tools::NetworkDiagTool::new()
+                .with_network_guard(network_for("network_diagnostics"))
+        ));
+    }
+
+    // Skill authoring goes last, so drafted skills are checked against every
+    // other tool. The approval prompt shows the drafted markdown.
+    if matches!(
+        config.tool_mode,
+        AgentToolMode::Full | AgentToolMode::FullNoApproval
+    ) {
+        let mut known_tools: BTreeSet<String> = registry
+            .list_available()
+            .into_iter()
+            .map(str::to_owned)
+            .collect();
+        known_tools.insert("create_skill".to_owned());
+        let create_skill = CreateSkillTool::with_default_dir().with_known_tools(known_tools);
+        if matches!(config.tool_mode, AgentToolMode::FullNoApproval) {
+            registry.register(Arc::new(create_skill));
+        } else {
+            register_with_approval(Arc::new(create_skill), &mut registry);
+        }
+    }
+
+    // Destructive calls are confirmed with a spoken read-back on top of
+    // whatever approval the tool already has.
+    for name in DestructiveAction::TOOLS {
+        if let Some(tool) = registry.get(name) {
+            registry.register(Arc::new(ReadBackTool::new(
+                tool,
+                tool_approval_tx.clone(),
+                config.read_back.clone(),
+                config.approval_timeouts.for_tool(name),
+            )));
+        }
     }
 
     Arc::new(registry)
 }
 
-fn estimate_history_tokens(messages: &[Message]) -> usize {
-    messages
+/// Tools whose successful calls change what goes into the system prompt.
+const PROMPT_CHANGING_TOOLS: &[&str] = &["create_skill", "set_preference"];
+
+/// Whether the run installed a skill or changed a preference.
+fn changed_system_prompt(result: &AgentLoopResult) -> bool {
+    result
+        .turns
         .iter()
-        .map(|message| {
-            let mut chars = match &message.content {
+        .flat_map(|turn| &turn.tool_calls)
+        .any(|call| {
+            call.result.success && PROMPT_CHANGING_TOOLS.contains(&call.function_name.as_str())
+        })
+}
+
+/// Cheap identity for a history snapshot, used to skip redundant prefills.
+fn history_fingerprint(messages: &[Message]) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    serde_json::to_string(messages)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+fn estimate_history_tokens(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .map(|message| {
+            let mut chars = match &message.content {
                 crate::fae_llm::providers::message::MessageContent::Text { text } => text.len(),
                 crate::fae_llm::providers::message::MessageContent::ToolResult {
                     content, ..
@@ -1183,7 +2116,95 @@ fn truncate_summary_text(text: &str) -> String {
     format!("{truncated}...")
 }
 
+/// Send an approval request and return a future for the user's answer.
+///
+/// The request is sent immediately; the future resolves once the user
+/// answers or `timeout` elapses, without polling.
+fn request_approval(
+    approval_tx: &mpsc::UnboundedSender<ToolApprovalRequest>,
+    name: String,
+    input_json: String,
+    preview: Option<String>,
+    read_back: Option<String>,
+    timeout: Duration,
+) -> ApprovalFuture {
+    let (respond_to, response_rx) = oneshot::channel::<ToolApprovalResponse>();
+    let request = ToolApprovalRequest::new(next_approval_id(), name, input_json, respond_to)
+        .with_preview(preview)
+        .with_read_back(read_back);
+    let sent = approval_tx.send(request).is_ok();
+
+    Box::pin(async move {
+        if !sent {
+            return Err(FaeLlmError::ToolExecutionError(
+                "tool approval handler is unavailable".to_string(),
+            ));
+        }
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(response)) => Ok(response.is_approved()),
+            Ok(Err(_)) => Err(FaeLlmError::ToolExecutionError(
+                "tool approval response channel closed".to_string(),
+            )),
+            Err(_) => {
+                tracing::error!("tool approval timed out after {:?}", timeout);
+                Err(FaeLlmError::ToolExecutionError(
+                    "tool approval timed out".to_string(),
+                ))
+            }
+        }
+    })
+}
+
+/// Wait for an approval from synchronous tool code.
+///
+/// Tools run on blocking threads, so this parks the thread on the runtime
+/// instead of spinning; outside a runtime a small one is started.
+fn block_on_approval(approval: ApprovalFuture) -> std::result::Result<bool, FaeLlmError> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(approval)),
+        Err(_) => tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .map_err(|e| {
+                FaeLlmError::ToolExecutionError(format!("cannot wait for tool approval: {e}"))
+            })?
+            .block_on(approval),
+    }
+}
+
+/// Asks about new network domains through the tool approval channel.
+///
+/// Requests are named `network_access` with `{"tool", "domain"}` input.
+struct ChannelDomainApprover {
+    approval_tx: mpsc::UnboundedSender<ToolApprovalRequest>,
+    timeout: Duration,
+}
+
+impl DomainApprover for ChannelDomainApprover {
+    fn approve(&self, tool_name: &str, host: &str) -> bool {
+        let input_json = serde_json::json!({ "tool": tool_name, "domain": host }).to_string();
+        tracing::info!("requesting network approval for {host} ({tool_name})");
+        match block_on_approval(request_approval(
+            &self.approval_tx,
+            "network_access".to_string(),
+            input_json,
+            None,
+            None,
+            self.timeout,
+        )) {
+            Ok(approved) => approved,
+            Err(e) => {
+                tracing::warn!("network approval for {host} failed: {e}");
+                false
+            }
+        }
+    }
+}
+
 /// Tool wrapper that gates execution behind UI approval.
+///
+/// The executor awaits [`Tool::request_approval`] asynchronously; direct
+/// [`Tool::execute`] calls wait on the calling thread instead.
 struct ApprovalTool {
     inner: Arc<dyn Tool>,
     approval_tx: Option<mpsc::UnboundedSender<ToolApprovalRequest>>,
@@ -1202,10 +2223,6 @@ impl ApprovalTool {
             timeout,
         }
     }
-
-    fn next_approval_id() -> u64 {
-        NEXT_APPROVAL_ID.fetch_add(1, Ordering::Relaxed)
-    }
 }
 
 impl Tool for ApprovalTool {
@@ -1221,8 +2238,77 @@ impl Tool for ApprovalTool {
         self.inner.schema()
     }
 
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        self.inner.output_schema()
+    }
+
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        self.inner.approval_preview(args)
+    }
+
+    fn read_back_args(&self, args: &serde_json::Value) -> serde_json::Value {
+        self.inner.read_back_args(args)
+    }
+
+    fn requires_approval(&self, args: &serde_json::Value) -> bool {
+        self.inner.requires_approval(args)
+    }
+
+    fn is_side_effecting(&self) -> bool {
+        self.inner.is_side_effecting()
+    }
+
     fn execute(&self, args: serde_json::Value) -> std::result::Result<ToolResult, FaeLlmError> {
-        let Some(approval_tx) = &self.approval_tx else {
+        if !self.inner.requires_approval(&args) {
+            return self.inner.execute(args);
+        }
+        // Without an approval channel there is nothing to wait for, and
+        // execute_approved must not be reached.
+        let Some(approval) = self.request_approval(&args) else {
+            return self.execute_approved(args);
+        };
+        match block_on_approval(approval) {
+            Ok(true) => self.execute_approved(args),
+            Ok(false) => {
+                tracing::warn!("tool denied by user: {}", self.inner.name());
+                Err(FaeLlmError::ToolExecutionError(
+                    "tool call denied by user".to_string(),
+                ))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn request_approval(&self, args: &serde_json::Value) -> Option<ApprovalFuture> {
+        if !self.inner.requires_approval(args) {
+            return None;
+        }
+        let approval_tx = self.approval_tx.as_ref()?;
+        let input_json = match serde_json::to_string(args) {
+            Ok(serialized) => serialized,
+            Err(e) => format!("{{\"_error\":\"failed to serialize tool input: {e}\"}}"),
+        };
+
+        tracing::info!(
+            "requesting tool approval for: {} (timeout {:?})",
+            self.inner.name(),
+            self.timeout
+        );
+        Some(request_approval(
+            approval_tx,
+            self.inner.name().to_string(),
+            input_json,
+            self.inner.approval_preview(args),
+            None,
+            self.timeout,
+        ))
+    }
+
+    fn execute_approved(
+        &self,
+        args: serde_json::Value,
+    ) -> std::result::Result<ToolResult, FaeLlmError> {
+        if self.approval_tx.is_none() {
             // Fail-closed: refuse to execute mutating tools when no approval
             // channel is wired.  This prevents channel-originated requests
             // (Discord, etc.) from silently bypassing interactive approval.
@@ -1234,77 +2320,138 @@ impl Tool for ApprovalTool {
                 "tool '{}' requires approval but no approval channel is available",
                 self.inner.name()
             )));
-        };
+        }
+        tracing::info!("tool approved, executing: {}", self.inner.name());
+        self.inner.execute(args)
+    }
 
-        let (respond_to, mut response_rx) = oneshot::channel::<ToolApprovalResponse>();
-        let input_json = match serde_json::to_string(&args) {
-            Ok(serialized) => serialized,
-            Err(e) => format!("{{\"_error\":\"failed to serialize tool input: {e}\"}}"),
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        self.inner.allowed_in_mode(mode)
+    }
+}
+
+/// Tool wrapper that confirms destructive calls with a spoken read-back.
+///
+/// Calls [`DestructiveAction::classify`] recognises, and the config covers,
+/// always ask — even in modes without approval — and the request carries a
+/// read-back of exactly what will happen for the voice prompt. Other calls
+/// go through the wrapped tool's own approval, if any.
+struct ReadBackTool {
+    inner: Arc<dyn Tool>,
+    approval_tx: Option<mpsc::UnboundedSender<ToolApprovalRequest>>,
+    config: ReadBackConfig,
+    timeout: Duration,
+}
+
+impl ReadBackTool {
+    fn new(
+        inner: Arc<dyn Tool>,
+        approval_tx: Option<mpsc::UnboundedSender<ToolApprovalRequest>>,
+        config: ReadBackConfig,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            inner,
+            approval_tx,
+            config,
+            timeout,
+        }
+    }
+
+    /// The read-back for a call with `args`, if it needs one.
+    fn read_back(&self, args: &serde_json::Value) -> Option<String> {
+        let action = DestructiveAction::classify(self.inner.name(), args)?;
+        self.config
+            .covers(action)
+            .then(|| crate::personality::format_read_back(action, &self.inner.read_back_args(args)))
+    }
+
+    fn request_read_back(&self, args: &serde_json::Value, read_back: String) -> ApprovalFuture {
+        let Some(approval_tx) = &self.approval_tx else {
+            // Fail closed, like ApprovalTool: nobody can hear the read-back.
+            let name = self.inner.name().to_owned();
+            return Box::pin(async move {
+                Err(FaeLlmError::ToolExecutionError(format!(
+                    "tool '{name}' needs a spoken confirmation but no approval channel is available"
+                )))
+            });
         };
-        let request = ToolApprovalRequest::new(
-            Self::next_approval_id(),
-            self.inner.name().to_string(),
-            input_json,
-            respond_to,
+        tracing::info!(
+            "requesting read-back confirmation for: {}",
+            self.inner.name()
         );
+        request_approval(
+            approval_tx,
+            self.inner.name().to_owned(),
+            serde_json::to_string(args).unwrap_or_default(),
+            self.inner.approval_preview(args),
+            Some(read_back),
+            self.timeout,
+        )
+    }
+}
 
-        tracing::info!("requesting tool approval for: {}", self.inner.name());
+impl Tool for ReadBackTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
 
-        if approval_tx.send(request).is_err() {
-            return Err(FaeLlmError::ToolExecutionError(
-                "tool approval handler is unavailable".to_string(),
-            ));
-        }
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
 
-        // Wait for user approval in a blocking fashion.
-        // We use `block_in_place` so the tokio runtime can migrate other tasks
-        // off this thread while we spin-wait, preventing worker thread starvation
-        // when this is called from inside a `tokio::spawn` task.
-        let approval_result = tokio::task::block_in_place(|| {
-            let start = Instant::now();
-            loop {
-                match response_rx.try_recv() {
-                    Ok(ToolApprovalResponse::Approved(true)) => {
-                        return Ok(true);
-                    }
-                    Ok(ToolApprovalResponse::Approved(false))
-                    | Ok(ToolApprovalResponse::Cancelled)
-                    | Ok(ToolApprovalResponse::Value(_)) => {
-                        return Ok(false);
-                    }
-                    Err(tokio::sync::oneshot::error::TryRecvError::Empty) => {
-                        if start.elapsed() >= self.timeout {
-                            tracing::error!("tool approval timed out after {:?}", self.timeout);
-                            return Err(FaeLlmError::ToolExecutionError(
-                                "tool approval timed out".to_string(),
-                            ));
-                        }
-                        std::thread::sleep(Duration::from_millis(25));
-                    }
-                    Err(tokio::sync::oneshot::error::TryRecvError::Closed) => {
-                        return Err(FaeLlmError::ToolExecutionError(
-                            "tool approval response channel closed".to_string(),
-                        ));
-                    }
-                }
-            }
-        });
+    fn schema(&self) -> serde_json::Value {
+        self.inner.schema()
+    }
 
-        match approval_result {
-            Ok(true) => {
-                tracing::info!("tool approved, executing: {}", self.inner.name());
-                self.inner.execute(args)
-            }
-            Ok(false) => {
-                tracing::warn!("tool denied by user: {}", self.inner.name());
-                Err(FaeLlmError::ToolExecutionError(
-                    "tool call denied by user".to_string(),
-                ))
-            }
-            Err(e) => Err(e),
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        self.inner.output_schema()
+    }
+
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        self.inner.approval_preview(args)
+    }
+
+    fn read_back_args(&self, args: &serde_json::Value) -> serde_json::Value {
+        self.inner.read_back_args(args)
+    }
+
+    fn requires_approval(&self, args: &serde_json::Value) -> bool {
+        self.read_back(args).is_some() || self.inner.requires_approval(args)
+    }
+
+    fn is_side_effecting(&self) -> bool {
+        self.inner.is_side_effecting()
+    }
+
+    fn execute(&self, args: serde_json::Value) -> std::result::Result<ToolResult, FaeLlmError> {
+        let Some(read_back) = self.read_back(&args) else {
+            return self.inner.execute(args);
+        };
+        if block_on_approval(self.request_read_back(&args, read_back))? {
+            self.inner.execute_approved(args)
+        } else {
+            tracing::warn!("tool denied by user after read-back: {}", self.inner.name());
+            Err(FaeLlmError::ToolExecutionError(
+                "tool call denied by user".to_string(),
+            ))
         }
     }
 
+    fn request_approval(&self, args: &serde_json::Value) -> Option<ApprovalFuture> {
+        match self.read_back(args) {
+            Some(read_back) => Some(self.request_read_back(args, read_back)),
+            None => self.inner.request_approval(args),
+        }
+    }
+
+    fn execute_approved(
+        &self,
+        args: serde_json::Value,
+    ) -> std::result::Result<ToolResult, FaeLlmError> {
+        self.inner.execute_approved(args)
+    }
+
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
         self.inner.allowed_in_mode(mode)
     }
@@ -1348,8 +2495,9 @@ mod tests {
         let config = LlmConfig::default();
         let manager = NoopCredentialManager;
 
-        let provider = build_provider(&config, None, &manager).await;
+        let (provider, fallback) = build_provider(&config, None, &manager).await;
         assert_eq!(provider.name(), "missing_provider_config");
+        assert!(fallback.is_none(), "no remote route to fall back from");
 
         let result = provider
             .send(&[Message::user("hello")], &RequestOptions::new(), &[])
@@ -1357,6 +2505,131 @@ mod tests {
         assert!(matches!(result, Err(FaeLlmError::ConfigValidationError(_))));
     }
 
+    #[tokio::test]
+    async fn prefill_task_skips_unchanged_history() {
+        let mut agent = FaeAgentLlm::new_with_channels(
+            &LlmConfig::default(),
+            None,
+            None,
+            &NoopCredentialManager,
+            AgentChannels::default(),
+        )
+        .await
+        .expect("agent");
+
+        agent.prefill_task().expect("initial prefill").await;
+        assert!(agent.prefill_task().is_none());
+
+        agent.inject_background_result("Timer set for ten minutes.");
+        assert!(agent.prefill_task().is_some());
+    }
+
+    #[tokio::test]
+    async fn trim_history_leaves_room_to_append() {
+        let config = LlmConfig {
+            max_history_messages: 8,
+            ..LlmConfig::default()
+        };
+        let mut agent = FaeAgentLlm::new_with_channels(
+            &config,
+            None,
+            None,
+            &NoopCredentialManager,
+            AgentChannels::default(),
+        )
+        .await
+        .expect("agent");
+
+        for i in 0..9 {
+            agent.inject_background_result(&format!("result {i}"));
+        }
+        // System prompt + 6 retained messages.
+        assert_eq!(agent.history.len(), 7);
+        assert_eq!(agent.history[0].role, Role::System);
+
+        let prefix = agent.history.clone();
+        agent.inject_background_result("result 9");
+        agent.inject_background_result("result 10");
+        assert_eq!(agent.history.len(), 9);
+        assert_eq!(agent.history[..7], prefix[..]);
+    }
+
+    #[tokio::test]
+    async fn reload_system_prompt_replaces_only_the_system_message() {
+        let mut agent = FaeAgentLlm::new_with_channels(
+            &LlmConfig::default(),
+            None,
+            None,
+            &NoopCredentialManager,
+            AgentChannels::default(),
+        )
+        .await
+        .expect("agent");
+        agent.inject_background_result("Timer set.");
+        agent.system_prompt_builder = Arc::new(|| "with new skill".to_owned());
+
+        agent.reload_system_prompt();
+
+        assert_eq!(agent.history.len(), 2);
+        assert_eq!(agent.history[0].role, Role::System);
+        assert_eq!(
+            agent.history[0].content,
+            Message::system("with new skill").content
+        );
+        assert_eq!(agent.history[1].role, Role::Assistant);
+    }
+
+    #[tokio::test]
+    async fn resume_conversation_keeps_own_system_prompt() {
+        let mut agent = FaeAgentLlm::new_with_channels(
+            &LlmConfig::default(),
+            None,
+            None,
+            &NoopCredentialManager,
+            AgentChannels::default(),
+        )
+        .await
+        .expect("agent");
+        agent.inject_background_result("Timer set.");
+        let prompt = agent.history()[0].content.clone();
+
+        agent.resume_conversation(vec![
+            Message::system("other device's prompt"),
+            Message::user("Plan my trip to Lisbon"),
+            Message::assistant("Sure — when are you going?"),
+        ]);
+
+        let roles: Vec<Role> = agent.history().iter().map(|m| m.role).collect();
+        assert_eq!(roles, vec![Role::System, Role::User, Role::Assistant]);
+        assert_eq!(agent.history()[0].content, prompt);
+    }
+
+    #[test]
+    fn full_mode_registers_create_skill_tool() {
+        for tool_mode in [AgentToolMode::Full, AgentToolMode::FullNoApproval] {
+            let config = LlmConfig {
+                tool_mode,
+                ..LlmConfig::default()
+            };
+            let registry = build_registry(&config, AgentChannels::default(), None);
+            assert!(registry.exists("create_skill"));
+        }
+        let config = LlmConfig {
+            tool_mode: AgentToolMode::ReadWrite,
+            ..LlmConfig::default()
+        };
+        let registry = build_registry(&config, AgentChannels::default(), None);
+        assert!(!registry.exists("create_skill"));
+    }
+
+    #[test]
+    fn known_tool_names_cover_full_access_tools() {
+        let names = known_tool_names();
+        for name in ["read", "bash", "web_search", "python_skill", "list_todos"] {
+            assert!(names.iter().any(|n| n == name), "missing {name}");
+        }
+    }
+
     #[test]
     fn full_mode_registers_python_skill_tool() {
         let config = LlmConfig {
@@ -1364,7 +2637,7 @@ mod tests {
             ..LlmConfig::default()
         };
 
-        let registry = build_registry(&config, AgentChannels::default());
+        let registry = build_registry(&config, AgentChannels::default(), None);
         assert!(
             registry.exists("python_skill"),
             "python_skill tool should be registered in full mode"
@@ -1401,6 +2674,20 @@ mod tests {
         assert!(tools.contains(&"list_calendar_events".to_string()));
     }
 
+    #[test]
+    fn select_tool_allowlist_adds_slot_tools_for_finding_time() {
+        let tools = select_tool_allowlist("Find me 30 minutes with Alex next week");
+        assert!(tools.contains(&"propose_meeting_slots".to_string()));
+        assert!(tools.contains(&"create_calendar_hold".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_note_search_for_described_notes() {
+        let tools = select_tool_allowlist("find that note about the boiler service");
+        assert!(tools.contains(&"search_notes".to_string()));
+        assert!(tools.contains(&"get_note".to_string()));
+    }
+
     #[test]
     fn select_tool_allowlist_multi_category_overlap() {
         // "search for meetings" should trigger both web and calendar tools.
@@ -1415,6 +2702,121 @@ mod tests {
         );
     }
 
+    #[test]
+    fn select_tool_allowlist_adds_lsp_for_code_navigation() {
+        let tools =
+            select_tool_allowlist("Where is this function used? Find usages of load_config");
+        assert!(tools.contains(&"lsp".to_string()));
+        assert!(tools.contains(&"read".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_todo_tools() {
+        let tools = select_tool_allowlist("What's on my to-do list this week?");
+        assert!(tools.contains(&"list_todos".to_string()));
+        assert!(tools.contains(&"update_todo".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_media_tools() {
+        let tools = select_tool_allowlist("Play some jazz");
+        assert!(tools.contains(&"play_music".to_string()));
+        let tools = select_tool_allowlist("What's playing right now?");
+        assert!(tools.contains(&"now_playing".to_string()));
+        assert!(tools.contains(&"media_control".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_system_control() {
+        let tools = select_tool_allowlist("Turn the volume down a bit");
+        assert!(tools.contains(&"system_control".to_string()));
+        let tools = select_tool_allowlist("switch on dark mode");
+        assert!(tools.contains(&"system_control".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_system_info() {
+        let tools = select_tool_allowlist("Why is my laptop fan going crazy?");
+        assert!(tools.contains(&"system_info".to_string()));
+        let tools = select_tool_allowlist("how much battery is left");
+        assert!(tools.contains(&"system_info".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_network_diagnostics() {
+        let tools = select_tool_allowlist("Is my internet down?");
+        assert!(tools.contains(&"network_diagnostics".to_string()));
+        let tools = select_tool_allowlist("add eggs to my shopping list");
+        assert!(!tools.contains(&"network_diagnostics".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_undo_for_revert_requests() {
+        let tools = select_tool_allowlist("Please undo the last two edits");
+        assert!(tools.contains(&"undo".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_create_skill_for_learning_requests() {
+        let tools = select_tool_allowlist(
+            "Learn this as a skill: whenever I ask about invoices, search my notes first",
+        );
+        assert!(tools.contains(&"create_skill".to_string()));
+        let tools = select_tool_allowlist("What's the weather like?");
+        assert!(!tools.contains(&"create_skill".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_preference_tools() {
+        let tools = select_tool_allowlist("My timezone is Europe/London, stop asking");
+        assert!(tools.contains(&"set_preference".to_string()));
+        assert!(tools.contains(&"get_preference".to_string()));
+        let tools = select_tool_allowlist("Please use metric units from now on");
+        assert!(tools.contains(&"set_preference".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_file_organize_for_cleanup_requests() {
+        let tools = select_tool_allowlist("Can you clean up my Downloads folder?");
+        assert!(tools.contains(&"file_organize".to_string()));
+        let tools = select_tool_allowlist("What's on my calendar today?");
+        assert!(!tools.contains(&"file_organize".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_archive_tools() {
+        let tools = select_tool_allowlist("Unzip the invoices.zip in my downloads");
+        assert!(tools.contains(&"list_archive".to_string()));
+        assert!(tools.contains(&"archive".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_transcribe_for_recordings() {
+        let tools = select_tool_allowlist("Transcribe the voice memo from this morning");
+        assert!(tools.contains(&"transcribe_file".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_synthesize_for_saved_audio() {
+        let tools = select_tool_allowlist("Read this article and save it as an MP3 for my run");
+        assert!(tools.contains(&"synthesize_to_file".to_string()));
+        assert!(tools.contains(&"fetch_url".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_http_request_for_api_calls() {
+        let tools = select_tool_allowlist("POST this payload to my webhook");
+        assert!(tools.contains(&"http_request".to_string()));
+    }
+
+    #[test]
+    fn select_tool_allowlist_adds_host_ui_tools() {
+        let tools = select_tool_allowlist("Summarise this document and share it with Sam");
+        assert!(tools.contains(&"pick_file".to_string()));
+        assert!(tools.contains(&"share".to_string()));
+        assert!(!tools.contains(&"post_notification".to_string()));
+    }
+
     #[test]
     fn select_tool_allowlist_case_insensitive() {
         let tools = select_tool_allowlist("CHECK MY CALENDAR");
@@ -1434,6 +2836,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scheduled_task_allowlist_prefers_explicit_tools() {
+        let allowed = vec![
+            "web_search".to_owned(),
+            "create_scheduled_task".to_owned(),
+            "fetch_url".to_owned(),
+        ];
+        assert_eq!(
+            select_tool_allowlist_for_scheduled_task("check my calendar", &allowed),
+            vec!["fetch_url".to_owned(), "web_search".to_owned()]
+        );
+        assert_eq!(
+            select_tool_allowlist_for_scheduled_task("check my calendar", &[]),
+            select_tool_allowlist_for_prompt("check my calendar")
+        );
+    }
+
     #[test]
     fn needs_deeper_reasoning_short_messages_false() {
         // Very short messages should never trigger thinking mode.
@@ -1499,6 +2918,7 @@ mod tests {
             user_message: "What time is it right now?".to_owned(),
             conversation_context: String::new(),
             tool_allowlist: vec!["bash".to_owned()],
+            resumed_call: None,
         };
         assert_eq!(
             select_background_reasoning_level(&task),
@@ -1514,10 +2934,97 @@ mod tests {
             user_message: "Search the web and compare options for my meeting plan".to_owned(),
             conversation_context: String::new(),
             tool_allowlist: vec!["web_search".to_owned(), "list_calendar_events".to_owned()],
+            resumed_call: None,
         };
         assert_eq!(
             select_background_reasoning_level(&task),
             ReasoningLevel::Medium
         );
     }
+
+    #[tokio::test]
+    async fn approval_tool_awaits_answer_without_blocking() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let tool = ApprovalTool::new(
+            Arc::new(ReadTool::new()),
+            Some(tx),
+            Duration::from_millis(50),
+        );
+        let args = serde_json::json!({ "file_path": "/tmp/approval-test.txt" });
+
+        let approval = tool.request_approval(&args).expect("approval required");
+        let request = rx.recv().await.expect("request sent");
+        assert_eq!(request.name, "read");
+        assert!(request.respond(true));
+        assert!(approval.await.unwrap());
+
+        let unanswered = tool.request_approval(&args).expect("approval required");
+        let err = unanswered.await.unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+
+        let ungated = ApprovalTool::new(Arc::new(ReadTool::new()), None, Duration::from_secs(1));
+        assert!(ungated.request_approval(&args).is_none());
+        assert!(ungated.execute(args).is_err());
+    }
+
+    #[tokio::test]
+    async fn read_back_tool_confirms_overwrites_but_not_new_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let existing = dir.path().join("notes.txt");
+        std::fs::write(&existing, "keep me").expect("write");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let tool = ReadBackTool::new(
+            Arc::new(WriteTool::new()),
+            Some(tx),
+            ReadBackConfig::default(),
+            Duration::from_secs(1),
+        );
+
+        let fresh = serde_json::json!({ "path": dir.path().join("new.txt"), "content": "x" });
+        assert!(tool.request_approval(&fresh).is_none());
+
+        let overwrite = serde_json::json!({ "path": existing, "content": "x" });
+        let approval = tool
+            .request_approval(&overwrite)
+            .expect("read-back required");
+        let request = rx.recv().await.expect("request sent");
+        assert!(
+            request
+                .read_back
+                .as_deref()
+                .is_some_and(|text| text.contains("overwrite"))
+        );
+        assert!(request.respond(false));
+        assert!(!approval.await.unwrap());
+
+        let opted_out = ReadBackTool::new(
+            Arc::new(WriteTool::new()),
+            None,
+            ReadBackConfig {
+                overwrite_files: false,
+                ..ReadBackConfig::default()
+            },
+            Duration::from_secs(1),
+        );
+        assert!(opted_out.request_approval(&overwrite).is_none());
+    }
+
+    #[test]
+    fn approval_tool_skips_calls_the_inner_tool_exempts() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let tool = ApprovalTool::new(
+            Arc::new(crate::fae_llm::tools::HttpRequestTool::new()),
+            Some(tx),
+            Duration::from_secs(1),
+        );
+        let url = "https://api.example.com/items";
+        assert!(
+            tool.request_approval(&serde_json::json!({ "url": url }))
+                .is_none()
+        );
+        assert!(
+            tool.request_approval(&serde_json::json!({ "url": url, "method": "DELETE" }))
+                .is_some()
+        );
+    }
 }
@@ -0,0 +1,268 @@
+//! Spoken reply shaping for the voice engine.
+//!
+//! The model is asked to be brief, but that is only a request. This policy
+//! enforces it on the streamed clauses: once a reply has used its sentence
+//! budget the remaining clauses are held back and Fae asks whether to
+//! continue. A "yes" on the next turn speaks the held text without another
+//! model call. When the user has cut Fae off repeatedly, the budget drops to
+//! [`VoiceVerbosity::Brief`] until the interruptions age out.
+
+use crate::config::{VoiceResponseConfig, VoiceVerbosity};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Held-back text this short is spoken instead of offered: asking
+/// "want me to continue?" costs more than just saying it.
+const SHORT_TAIL_CHARS: usize = 120;
+
+/// Replies accepted as "yes, continue" (after lowercasing and stripping
+/// punctuation).
+const CONTINUE_REPLIES: &[&str] = &[
+    "yes",
+    "yeah",
+    "yep",
+    "sure",
+    "please",
+    "yes please",
+    "ok",
+    "okay",
+    "continue",
+    "go on",
+    "keep going",
+    "carry on",
+    "go ahead",
+    "tell me more",
+    "more",
+    "ja",
+    "weiter",
+    "mach weiter",
+    "sí",
+    "si",
+    "sigue",
+    "continúa",
+    "oui",
+    "vas-y",
+];
+
+/// Per-engine reply policy: sentence budget, recent interruptions and the
+/// unspoken rest of the last capped reply.
+#[derive(Debug)]
+pub(crate) struct VoiceResponsePolicy {
+    config: VoiceResponseConfig,
+    interruptions: VecDeque<Instant>,
+    continuation: Option<Vec<String>>,
+}
+
+impl VoiceResponsePolicy {
+    pub(crate) fn new(config: VoiceResponseConfig) -> Self {
+        Self {
+            config,
+            interruptions: VecDeque::new(),
+            continuation: None,
+        }
+    }
+
+    /// Note that the user talked over the previous reply.
+    pub(crate) fn record_interruption(&mut self, at: Instant) {
+        self.interruptions.push_back(at);
+    }
+
+    /// Sentence budget for a reply starting at `now`.
+    pub(crate) fn sentence_limit(&mut self, now: Instant) -> usize {
+        let window = Duration::from_secs(u64::from(self.config.interruption_window_s));
+        while self
+            .interruptions
+            .front()
+            .is_some_and(|at| now.saturating_duration_since(*at) > window)
+        {
+            self.interruptions.pop_front();
+        }
+        let limit = self.config.sentence_limit();
+        let threshold = self.config.interruptions_before_brief;
+        if threshold > 0 && self.interruptions.len() >= threshold {
+            limit.min(VoiceVerbosity::Brief.sentence_limit())
+        } else {
+            limit
+        }
+    }
+
+    /// Remember the held-back clauses of a reply for a possible "yes".
+    pub(crate) fn offer_continuation(&mut self, rest: Vec<String>) {
+        self.continuation = Some(rest);
+    }
+
+    /// The held-back clauses if `user_message` accepts the offer to continue.
+    ///
+    /// Any other message drops the offer: the conversation has moved on.
+    pub(crate) fn take_continuation(&mut self, user_message: &str) -> Option<Vec<String>> {
+        let rest = self.continuation.take()?;
+        is_continue_reply(user_message).then_some(rest)
+    }
+}
+
+fn is_continue_reply(message: &str) -> bool {
+    let normalized = message
+        .trim()
+        .trim_matches(|c: char| c.is_ascii_punctuation() || c == '¿' || c == '¡')
+        .trim()
+        .to_lowercase();
+    let normalized = normalized
+        .strip_suffix(" please")
+        .unwrap_or(&normalized)
+        .trim_end_matches(|c: char| c == ',' || c.is_whitespace());
+    CONTINUE_REPLIES.contains(&normalized)
+}
+
+/// What to do with a reply's held-back text once it has finished.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ReplyTail {
+    /// Everything was spoken.
+    None,
+    /// Short enough to just say.
+    Speak(String),
+    /// Ask whether to continue, keeping these clauses for a "yes".
+    Offer(Vec<String>),
+}
+
+/// Sentence budget for one streamed reply.
+#[derive(Debug)]
+pub(crate) struct SentenceGate {
+    limit: usize,
+    sentences: usize,
+    spoken: String,
+    held: Vec<String>,
+}
+
+impl SentenceGate {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            sentences: 0,
+            spoken: String::new(),
+            held: Vec::new(),
+        }
+    }
+
+    /// Pass `clause` through if the budget allows; otherwise hold it back.
+    pub(crate) fn admit(&mut self, clause: String) -> Option<String> {
+        if self.limit > 0 && self.sentences >= self.limit {
+            self.held.push(clause);
+            return None;
+        }
+        if ends_sentence(&clause) {
+            self.sentences += 1;
+        }
+        if !self.spoken.is_empty() {
+            self.spoken.push(' ');
+        }
+        self.spoken.push_str(&clause);
+        Some(clause)
+    }
+
+    /// Text spoken so far.
+    pub(crate) fn spoken(&self) -> &str {
+        &self.spoken
+    }
+
+    /// Close the reply, deciding what happens to any held-back text.
+    pub(crate) fn finish(self) -> ReplyTail {
+        if self.held.is_empty() {
+            return ReplyTail::None;
+        }
+        let more_sentences = self.held.iter().filter(|c| ends_sentence(c)).count();
+        let rest_chars: usize = self.held.iter().map(|c| c.chars().count() + 1).sum();
+        if more_sentences <= 1 && rest_chars <= SHORT_TAIL_CHARS {
+            ReplyTail::Speak(self.held.join(" "))
+        } else {
+            ReplyTail::Offer(self.held)
+        }
+    }
+}
+
+/// Whether a streamed clause ends a sentence. A code block counts as one.
+fn ends_sentence(clause: &str) -> bool {
+    let clause = clause.trim_end_matches(['"', '\'', ')', '*', '_', '\u{201D}', '\u{2019}']);
+    clause.ends_with(['.', '!', '?', '\u{2026}']) || clause.ends_with("```")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    fn run(gate: &mut SentenceGate, clauses: &[&str]) -> Vec<String> {
+        clauses
+            .iter()
+            .filter_map(|c| gate.admit((*c).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn replies_stop_at_the_sentence_limit_and_offer_the_rest() {
+        let mut gate = SentenceGate::new(2);
+        let spoken = run(
+            &mut gate,
+            &[
+                "Paris is the capital,",
+                "and its largest city.",
+                "It sits on the Seine.",
+                "About two million people live there.",
+                "The wider region has over twelve million.",
+            ],
+        );
+        assert_eq!(spoken.len(), 3);
+        assert_eq!(
+            gate.spoken(),
+            "Paris is the capital, and its largest city. It sits on the Seine."
+        );
+        assert_eq!(
+            gate.finish(),
+            ReplyTail::Offer(vec![
+                "About two million people live there.".to_owned(),
+                "The wider region has over twelve million.".to_owned(),
+            ])
+        );
+    }
+
+    #[test]
+    fn a_short_tail_is_spoken_and_zero_means_unlimited() {
+        let mut gate = SentenceGate::new(1);
+        run(&mut gate, &["Yes.", "That's right."]);
+        assert_eq!(gate.finish(), ReplyTail::Speak("That's right.".to_owned()));
+
+        let mut gate = SentenceGate::new(0);
+        assert_eq!(run(&mut gate, &["One.", "Two.", "Three."]).len(), 3);
+        assert_eq!(gate.finish(), ReplyTail::None);
+    }
+
+    #[test]
+    fn repeated_interruptions_shorten_replies_until_they_age_out() {
+        let mut policy = VoiceResponsePolicy::new(VoiceResponseConfig {
+            verbosity: VoiceVerbosity::Detailed,
+            ..VoiceResponseConfig::default()
+        });
+        let start = Instant::now();
+        assert_eq!(policy.sentence_limit(start), 8);
+        policy.record_interruption(start);
+        assert_eq!(policy.sentence_limit(start), 8);
+        policy.record_interruption(start + Duration::from_secs(10));
+        assert_eq!(policy.sentence_limit(start + Duration::from_secs(20)), 2);
+        assert_eq!(policy.sentence_limit(start + Duration::from_secs(400)), 8);
+    }
+
+    #[test]
+    fn continuation_is_taken_only_on_a_yes() {
+        let mut policy = VoiceResponsePolicy::new(VoiceResponseConfig::default());
+        policy.offer_continuation(vec!["The rest.".to_owned()]);
+        assert_eq!(
+            policy.take_continuation("Yes, please!"),
+            Some(vec!["The rest.".to_owned()])
+        );
+        assert_eq!(policy.take_continuation("yes"), None);
+
+        policy.offer_continuation(vec!["The rest.".to_owned()]);
+        assert_eq!(policy.take_continuation("What time is it?"), None);
+        assert_eq!(policy.take_continuation("go on"), None);
+    }
+}
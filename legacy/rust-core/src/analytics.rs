@@ -0,0 +1,458 @@
+//! Conversation analytics: talk time, interruptions, latency and topics.
+//!
+//! The host runtime feeds every [`RuntimeEvent`] of a running pipeline into a
+//! [`ConversationAnalytics`] recorder. One conversation spans one pipeline
+//! run, from `runtime.start` to `runtime.stop`. The resulting
+//! [`ConversationStats`] are saved next to the conversation sessions (in
+//! `sessions/analytics/`, sealed when encryption at rest is on) after every
+//! assistant turn, and are served to the UI through the
+//! `conversation.analytics.get` and `conversation.analytics.list` commands.
+//!
+//! Only aggregate numbers and a handful of topic keywords are kept; no
+//! transcript text is stored.
+
+use crate::config::PrivacyConfig;
+use crate::error::{Result, SpeechError};
+use crate::pipeline::messages::ControlEvent;
+use crate::privacy::DataCipher;
+use crate::runtime::RuntimeEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Subdirectory of the sessions directory holding analytics files.
+pub const ANALYTICS_DIR_NAME: &str = "analytics";
+
+/// Number of topic keywords kept per conversation.
+const MAX_TOPICS: usize = 5;
+
+/// Replies arriving later than this after the user spoke are not counted as
+/// latency samples (they are proactive or scheduled speech, not answers).
+const MAX_LATENCY: Duration = Duration::from_secs(30);
+
+/// Shortest word considered as a topic keyword.
+const MIN_KEYWORD_CHARS: usize = 4;
+
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "again", "also", "been", "before", "being", "could", "does", "doing", "done",
+    "each", "even", "from", "going", "gonna", "good", "have", "having", "here", "into", "just",
+    "know", "like", "look", "make", "maybe", "more", "much", "need", "okay", "only", "other",
+    "over", "please", "really", "right", "said", "same", "should", "some", "sorry", "sure", "tell",
+    "than", "thank", "thanks", "that", "that's", "their", "them", "then", "there", "these", "they",
+    "thing", "things", "think", "this", "those", "through", "time", "very", "want", "well", "were",
+    "what", "what's", "when", "where", "which", "while", "will", "with", "would", "yeah", "your",
+    "you're",
+];
+
+/// Aggregate statistics for one conversation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationStats {
+    /// Conversation identifier (`conv_<uuid>`).
+    pub conversation_id: String,
+    /// Unix epoch seconds when the conversation started.
+    pub started_at: u64,
+    /// Unix epoch seconds of the last update.
+    pub updated_at: u64,
+    /// Final user transcriptions.
+    pub user_turns: u32,
+    /// Completed assistant replies.
+    pub assistant_turns: u32,
+    /// Total duration of user speech, in milliseconds.
+    pub user_talk_ms: u64,
+    /// Total duration of assistant speech playback, in milliseconds.
+    pub assistant_talk_ms: u64,
+    /// User share of the combined talk time (0.0–1.0), if anyone spoke.
+    pub user_talk_share: Option<f64>,
+    /// Assistant replies cut off by the user (barge-in).
+    pub interruptions: u32,
+    /// Mean time from the user's final transcription to the first sentence
+    /// of the reply, in milliseconds.
+    pub average_latency_ms: Option<u64>,
+    /// Most frequent keywords, most frequent first.
+    pub topics: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct KeywordCount {
+    count: u32,
+    first_seen: usize,
+}
+
+/// Accumulates [`ConversationStats`] from runtime events.
+#[derive(Debug)]
+pub struct ConversationAnalytics {
+    conversation_id: String,
+    started_at: u64,
+    user_turns: u32,
+    assistant_turns: u32,
+    user_talk: Duration,
+    assistant_talk: Duration,
+    interruptions: u32,
+    latency_total: Duration,
+    latency_samples: u32,
+    awaiting_reply_since: Option<Instant>,
+    speaking_since: Option<Instant>,
+    keywords: HashMap<String, KeywordCount>,
+}
+
+impl ConversationAnalytics {
+    /// Start a new conversation with a fresh identifier.
+    pub fn new() -> Self {
+        Self::with_id(format!("conv_{}", uuid::Uuid::new_v4()))
+    }
+
+    /// Start a conversation with the given identifier.
+    pub fn with_id(conversation_id: impl Into<String>) -> Self {
+        Self {
+            conversation_id: conversation_id.into(),
+            started_at: crate::time_util::now_epoch_secs(),
+            user_turns: 0,
+            assistant_turns: 0,
+            user_talk: Duration::ZERO,
+            assistant_talk: Duration::ZERO,
+            interruptions: 0,
+            latency_total: Duration::ZERO,
+            latency_samples: 0,
+            awaiting_reply_since: None,
+            speaking_since: None,
+            keywords: HashMap::new(),
+        }
+    }
+
+    /// The conversation identifier.
+    pub fn conversation_id(&self) -> &str {
+        &self.conversation_id
+    }
+
+    /// Update the statistics with an event observed at `now`.
+    ///
+    /// Returns `true` when the event completed an assistant turn, which is a
+    /// good moment to persist a snapshot.
+    pub fn observe(&mut self, event: &RuntimeEvent, now: Instant) -> bool {
+        match event {
+            RuntimeEvent::Transcription(t) if t.is_final && !t.text.trim().is_empty() => {
+                self.user_turns += 1;
+                if let Some(secs) = t.audio_duration_secs.filter(|s| s.is_finite() && *s > 0.0) {
+                    self.user_talk += Duration::from_secs_f32(secs);
+                }
+                self.count_keywords(&t.text);
+                self.awaiting_reply_since = Some(now);
+            }
+            RuntimeEvent::AssistantSentence(chunk) => {
+                if !chunk.text.trim().is_empty() {
+                    if let Some(asked) = self.awaiting_reply_since.take() {
+                        let latency = now.saturating_duration_since(asked);
+                        if latency <= MAX_LATENCY {
+                            self.latency_total += latency;
+                            self.latency_samples += 1;
+                        }
+                    }
+                    self.count_keywords(&chunk.text);
+                }
+                if chunk.is_final {
+                    self.assistant_turns += 1;
+                    return true;
+                }
+            }
+            RuntimeEvent::Control(ControlEvent::AssistantSpeechStart) => {
+                self.speaking_since.get_or_insert(now);
+            }
+            RuntimeEvent::Control(ControlEvent::AssistantSpeechEnd { interrupted }) => {
+                if let Some(since) = self.speaking_since.take() {
+                    self.assistant_talk += now.saturating_duration_since(since);
+                }
+                if *interrupted {
+                    self.interruptions += 1;
+                }
+            }
+            _ => {}
+        }
+        false
+    }
+
+    fn count_keywords(&mut self, text: &str) {
+        for word in text.split_whitespace() {
+            let word = word
+                .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .to_lowercase();
+            if word.chars().count() < MIN_KEYWORD_CHARS
+                || word.chars().all(|c| c.is_numeric())
+                || STOP_WORDS.contains(&word.as_str())
+            {
+                continue;
+            }
+            let first_seen = self.keywords.len();
+            self.keywords
+                .entry(word)
+                .or_insert(KeywordCount {
+                    count: 0,
+                    first_seen,
+                })
+                .count += 1;
+        }
+    }
+
+    /// Snapshot of the statistics so far.
+    pub fn stats(&self) -> ConversationStats {
+        let user_talk_ms = self.user_talk.as_millis() as u64;
+        let assistant_talk_ms = self.assistant_talk.as_millis() as u64;
+        let total_ms = user_talk_ms + assistant_talk_ms;
+
+        let mut keywords: Vec<(&String, &KeywordCount)> = self.keywords.iter().collect();
+        keywords.sort_by(|a, b| {
+            b.1.count
+                .cmp(&a.1.count)
+                .then(a.1.first_seen.cmp(&b.1.first_seen))
+        });
+
+        ConversationStats {
+            conversation_id: self.conversation_id.clone(),
+            started_at: self.started_at,
+            updated_at: crate::time_util::now_epoch_secs(),
+            user_turns: self.user_turns,
+            assistant_turns: self.assistant_turns,
+            user_talk_ms,
+            assistant_talk_ms,
+            user_talk_share: (total_ms > 0).then(|| user_talk_ms as f64 / total_ms as f64),
+            interruptions: self.interruptions,
+            average_latency_ms: (self.latency_samples > 0)
+                .then(|| (self.latency_total / self.latency_samples).as_millis() as u64),
+            topics: keywords
+                .into_iter()
+                .filter(|(_, k)| k.count > 1)
+                .take(MAX_TOPICS)
+                .map(|(word, _)| word.clone())
+                .collect(),
+        }
+    }
+}
+
+impl Default for ConversationAnalytics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Filesystem store for [`ConversationStats`], one JSON file per
+/// conversation. Writes are atomic (temp file + rename).
+#[derive(Debug, Clone)]
+pub struct AnalyticsStore {
+    dir: PathBuf,
+    cipher: Option<Arc<DataCipher>>,
+}
+
+impl AnalyticsStore {
+    /// Create a store in `dir`, creating the directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir, cipher: None })
+    }
+
+    /// Open the store under the sessions directory, sealing writes when
+    /// encryption at rest is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or the data key
+    /// cannot be loaded.
+    pub fn open(privacy: &PrivacyConfig) -> Result<Self> {
+        let store = Self::new(crate::fae_dirs::sessions_dir().join(ANALYTICS_DIR_NAME))?;
+        Ok(match crate::privacy::cipher_for(privacy)? {
+            Some(cipher) => store.with_cipher(Arc::new(cipher)),
+            None => store,
+        })
+    }
+
+    /// Seal files written by this store with `cipher`.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: Arc<DataCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    fn path(&self, conversation_id: &str) -> Result<PathBuf> {
+        let valid = !conversation_id.is_empty()
+            && conversation_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(SpeechError::Config(format!(
+                "invalid conversation id `{conversation_id}`"
+            )));
+        }
+        Ok(self.dir.join(format!("{conversation_id}.json")))
+    }
+
+    /// Save (or replace) the statistics for a conversation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be encoded, sealed or written.
+    pub fn save(&self, stats: &ConversationStats) -> Result<()> {
+        let path = self.path(&stats.conversation_id)?;
+        let mut bytes = serde_json::to_vec_pretty(stats)
+            .map_err(|e| SpeechError::Config(format!("failed to encode analytics: {e}")))?;
+        if let Some(cipher) = &self.cipher {
+            bytes = cipher.seal(&bytes)?;
+        }
+        let tmp = self.dir.join(format!(".{}.tmp", stats.conversation_id));
+        std::fs::write(&tmp, &bytes)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Load the statistics for a conversation, if saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the id is invalid or the file cannot be read,
+    /// opened or parsed.
+    pub fn load(&self, conversation_id: &str) -> Result<Option<ConversationStats>> {
+        let path = self.path(conversation_id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        self.read(&path).map(Some)
+    }
+
+    fn read(&self, path: &Path) -> Result<ConversationStats> {
+        let mut bytes = std::fs::read(path)?;
+        if DataCipher::is_sealed(&bytes) {
+            let cipher = self.cipher.as_ref().ok_or_else(|| {
+                SpeechError::Privacy(format!(
+                    "{} is encrypted but no key is configured",
+                    path.display()
+                ))
+            })?;
+            bytes = cipher.open(&bytes)?;
+        }
+        serde_json::from_slice(&bytes)
+            .map_err(|e| SpeechError::Config(format!("failed to parse {}: {e}", path.display())))
+    }
+
+    /// The most recent conversations, newest first. Unreadable files are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be listed.
+    pub fn list(&self, limit: usize) -> Result<Vec<ConversationStats>> {
+        let mut all = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let is_json = path.extension().and_then(|e| e.to_str()) == Some("json");
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+            if !is_json || hidden {
+                continue;
+            }
+            match self.read(&path) {
+                Ok(stats) => all.push(stats),
+                Err(e) => tracing::warn!("skipping analytics file: {e}"),
+            }
+        }
+        all.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        all.truncate(limit);
+        Ok(all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::pipeline::messages::{SentenceChunk, Transcription};
+
+    fn heard(text: &str, secs: f32) -> RuntimeEvent {
+        let now = Instant::now();
+        RuntimeEvent::Transcription(Transcription {
+            text: text.to_owned(),
+            is_final: true,
+            voiceprint: None,
+            audio_rms: None,
+            audio_duration_secs: Some(secs),
+            audio_captured_at: now,
+            transcribed_at: now,
+            speculation: None,
+        })
+    }
+
+    fn said(text: &str, is_final: bool) -> RuntimeEvent {
+        RuntimeEvent::AssistantSentence(SentenceChunk {
+            text: text.to_owned(),
+            is_final,
+        })
+    }
+
+    #[test]
+    fn stats_track_talk_time_interruptions_and_latency() {
+        let mut analytics = ConversationAnalytics::with_id("conv_test");
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+
+        analytics.observe(&heard("What's the weather in Paris?", 2.0), t0);
+        assert!(!analytics.observe(&said("It's sunny in Paris.", false), t0 + ms(400)));
+        analytics.observe(
+            &RuntimeEvent::Control(ControlEvent::AssistantSpeechStart),
+            t0 + ms(600),
+        );
+        assert!(analytics.observe(&said("", true), t0 + ms(700)));
+        analytics.observe(
+            &RuntimeEvent::Control(ControlEvent::AssistantSpeechEnd { interrupted: true }),
+            t0 + ms(2600),
+        );
+
+        analytics.observe(&heard("And the weather in Berlin?", 2.0), t0 + ms(5000));
+        analytics.observe(&said("Rainy in Berlin.", true), t0 + ms(5600));
+
+        let stats = analytics.stats();
+        assert_eq!(stats.conversation_id, "conv_test");
+        assert_eq!((stats.user_turns, stats.assistant_turns), (2, 2));
+        assert_eq!((stats.user_talk_ms, stats.assistant_talk_ms), (4000, 2000));
+        assert_eq!(stats.user_talk_share, Some(4000.0 / 6000.0));
+        assert_eq!(stats.interruptions, 1);
+        assert_eq!(stats.average_latency_ms, Some(500));
+        assert_eq!(stats.topics, vec!["weather", "paris", "berlin"]);
+    }
+
+    #[test]
+    fn late_replies_are_not_latency_samples() {
+        let mut analytics = ConversationAnalytics::with_id("conv_late");
+        let t0 = Instant::now();
+        analytics.observe(&heard("hmm", 1.0), t0);
+        analytics.observe(&said("Reminder: call mum.", true), t0 + MAX_LATENCY * 2);
+        assert_eq!(analytics.stats().average_latency_ms, None);
+    }
+
+    #[test]
+    fn store_round_trips_and_lists_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = AnalyticsStore::new(dir.path().join(ANALYTICS_DIR_NAME)).unwrap();
+
+        let mut older = ConversationAnalytics::with_id("conv_a").stats();
+        older.started_at = 100;
+        let mut newer = ConversationAnalytics::with_id("conv_b").stats();
+        newer.started_at = 200;
+        store.save(&older).unwrap();
+        store.save(&newer).unwrap();
+
+        assert_eq!(store.load("conv_a").unwrap(), Some(older));
+        assert_eq!(store.load("conv_missing").unwrap(), None);
+        assert!(store.load("../config").is_err());
+
+        let listed = store.list(10).unwrap();
+        let ids: Vec<&str> = listed.iter().map(|s| s.conversation_id.as_str()).collect();
+        assert_eq!(ids, vec!["conv_b", "conv_a"]);
+        assert_eq!(store.list(1).unwrap().len(), 1);
+    }
+}
@@ -3,8 +3,19 @@
 //! The agent tool harness can request high-risk operations (write/edit/bash/web).
 //! When an approval sender is wired up, those tools are gated behind an explicit
 //! user decision. If no approval handler is configured, tools run as-is.
+//!
+//! Requests nobody answers on the desktop can be forwarded to a channel
+//! (see [`RemoteApprovals`]) and confirmed from there with a short code.
+//!
+//! Destructive calls ([`DestructiveAction`]) always ask, whatever the tool
+//! mode, and carry a read-back: a spoken summary of exactly what is about to
+//! happen, so they can be confirmed by voice without looking at a dialog.
 
-use tokio::sync::oneshot;
+use crate::config::RemoteApprovalConfig;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
 
 /// UI response payload for interactive tool requests.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -34,6 +45,10 @@ pub struct ToolApprovalRequest {
     pub id: u64,
     pub name: String,
     pub input_json: String,
+    /// Rendered summary of the call (e.g. a diff), when the tool provides one.
+    pub preview: Option<String>,
+    /// Spoken read-back of a destructive call, used as the voice prompt.
+    pub read_back: Option<String>,
     respond_to: oneshot::Sender<ToolApprovalResponse>,
 }
 
@@ -48,10 +63,26 @@ impl ToolApprovalRequest {
             id,
             name,
             input_json,
+            preview: None,
+            read_back: None,
             respond_to,
         }
     }
 
+    /// Attach a rendered preview for the approval dialog.
+    #[must_use]
+    pub fn with_preview(mut self, preview: Option<String>) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Attach the spoken read-back of a destructive call.
+    #[must_use]
+    pub fn with_read_back(mut self, read_back: Option<String>) -> Self {
+        self.read_back = read_back;
+        self
+    }
+
     /// Respond to the approval request.
     ///
     /// Returns `true` if the response was delivered to the waiting tool runner.
@@ -77,3 +108,455 @@ impl ToolApprovalRequest {
             .is_ok()
     }
 }
+
+/// A kind of tool call that cannot be taken back, confirmed with a spoken
+/// read-back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestructiveAction {
+    /// Sending an email draft (`compose_mail` with `action: send`).
+    SendMail,
+    /// Deleting a calendar event (`delete_calendar_event`).
+    DeleteEvent,
+    /// Writing over a file that already exists (`write`).
+    OverwriteFile,
+}
+
+impl DestructiveAction {
+    /// Tools that can make destructive calls.
+    pub const TOOLS: &'static [&'static str] = &["compose_mail", "delete_calendar_event", "write"];
+
+    /// The destructive action a call to `tool_name` with `args` would take,
+    /// if any.
+    pub fn classify(tool_name: &str, args: &serde_json::Value) -> Option<Self> {
+        match tool_name {
+            "compose_mail" => (args.get("action").and_then(serde_json::Value::as_str)
+                == Some("send"))
+            .then_some(Self::SendMail),
+            "delete_calendar_event" => Some(Self::DeleteEvent),
+            "write" => {
+                let path = args.get("path").and_then(serde_json::Value::as_str)?;
+                std::path::Path::new(path)
+                    .is_file()
+                    .then_some(Self::OverwriteFile)
+            }
+            _ => None,
+        }
+    }
+
+    /// Stable name, as used in events.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SendMail => "send_mail",
+            Self::DeleteEvent => "delete_event",
+            Self::OverwriteFile => "overwrite_file",
+        }
+    }
+}
+
+/// Longest request detail included in a forwarded prompt, in characters.
+const REMOTE_DETAIL_MAX_CHARS: usize = 300;
+
+/// An approval prompt to deliver over a channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemotePrompt {
+    pub channel: String,
+    pub reply_target: String,
+    pub text: String,
+}
+
+/// A forwarded request resolved from a channel or by its code expiring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteDecision {
+    pub request_id: u64,
+    pub approved: bool,
+}
+
+#[derive(Debug)]
+struct ForwardedRequest {
+    request_id: u64,
+    channel: String,
+    target: String,
+    expires_at: Instant,
+}
+
+/// Forwards approval requests to a channel and resolves the replies.
+///
+/// The channels runtime installs an outbox for prompts; the host installs a
+/// sink for decisions, which it delivers to the pending
+/// [`ToolApprovalRequest`]. Each forwarded request gets a short code that
+/// must be quoted in the reply and expires after the configured TTL, after
+/// which the request is denied.
+#[derive(Debug)]
+pub struct RemoteApprovals {
+    forwarded: BTreeMap<String, ForwardedRequest>,
+    outbox: Option<mpsc::UnboundedSender<RemotePrompt>>,
+    decisions: Option<mpsc::UnboundedSender<RemoteDecision>>,
+}
+
+static REMOTE_APPROVALS: Mutex<RemoteApprovals> = Mutex::new(RemoteApprovals::new());
+
+/// The process-wide remote approval router.
+pub fn remote_approvals() -> MutexGuard<'static, RemoteApprovals> {
+    REMOTE_APPROVALS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl RemoteApprovals {
+    const fn new() -> Self {
+        Self {
+            forwarded: BTreeMap::new(),
+            outbox: None,
+            decisions: None,
+        }
+    }
+
+    /// Install (or remove) the channel outbox used to send prompts.
+    pub fn set_outbox(&mut self, outbox: Option<mpsc::UnboundedSender<RemotePrompt>>) {
+        self.outbox = outbox;
+    }
+
+    /// Install (or remove) the sink that receives decisions.
+    pub fn set_decisions(&mut self, decisions: Option<mpsc::UnboundedSender<RemoteDecision>>) {
+        self.decisions = decisions;
+    }
+
+    /// Whether `request_id` is waiting for a reply on a channel.
+    pub fn is_forwarded(&self, request_id: u64) -> bool {
+        self.forwarded.values().any(|f| f.request_id == request_id)
+    }
+
+    /// Send `request_id` to the configured channel target.
+    ///
+    /// Returns the code to quote in the reply, or `None` when forwarding is
+    /// disabled, unconfigured, or the channels runtime is not running.
+    pub fn forward(
+        &mut self,
+        request_id: u64,
+        tool: &str,
+        detail: &str,
+        config: &RemoteApprovalConfig,
+        now: Instant,
+    ) -> Option<String> {
+        if !config.enabled || config.channel.trim().is_empty() || config.target.trim().is_empty() {
+            return None;
+        }
+        let outbox = self.outbox.as_ref()?;
+        let token = loop {
+            let candidate = uuid::Uuid::new_v4().simple().to_string()[..6].to_ascii_uppercase();
+            if !self.forwarded.contains_key(&candidate) {
+                break candidate;
+            }
+        };
+        let detail: String = if detail.chars().count() > REMOTE_DETAIL_MAX_CHARS {
+            let cut: String = detail.chars().take(REMOTE_DETAIL_MAX_CHARS).collect();
+            format!("{cut}...")
+        } else {
+            detail.to_owned()
+        };
+        let secs = config.token_ttl_secs.to_string();
+        let text = crate::i18n::format(
+            "approval.remote.request",
+            &[
+                ("tool", tool),
+                ("detail", &detail),
+                ("token", &token),
+                ("secs", &secs),
+            ],
+        );
+        outbox
+            .send(RemotePrompt {
+                channel: config.channel.trim().to_owned(),
+                reply_target: config.target.trim().to_owned(),
+                text,
+            })
+            .ok()?;
+        self.forwarded.insert(
+            token.clone(),
+            ForwardedRequest {
+                request_id,
+                channel: config.channel.trim().to_owned(),
+                target: config.target.trim().to_owned(),
+                expires_at: now + Duration::from_secs(config.token_ttl_secs),
+            },
+        );
+        Some(token)
+    }
+
+    /// Resolve an inbound channel message if it is an approval reply.
+    ///
+    /// Accepts `approve <code>` / `deny <code>` (also `yes`/`no`). Returns
+    /// the text to send back, or `None` when the message is not a reply and
+    /// should go to the conversation as usual. Codes only resolve for the
+    /// channel and target they were sent to.
+    pub fn handle_reply(
+        &mut self,
+        channel: &str,
+        sender: &str,
+        reply_target: &str,
+        text: &str,
+        now: Instant,
+    ) -> Option<String> {
+        let (approved, token) = parse_remote_reply(text)?;
+        let unknown = || crate::i18n::format("approval.remote.unknown", &[("token", &token)]);
+        let Some(forwarded) = self.forwarded.get(&token) else {
+            return Some(unknown());
+        };
+        if forwarded.channel != channel
+            || (forwarded.target != sender && forwarded.target != reply_target)
+        {
+            return Some(unknown());
+        }
+        let forwarded = self.forwarded.remove(&token)?;
+        if now >= forwarded.expires_at {
+            self.decide(forwarded.request_id, false);
+            return Some(crate::i18n::format(
+                "approval.remote.expired",
+                &[("token", &token)],
+            ));
+        }
+        if !self.decide(forwarded.request_id, approved) {
+            return Some(unknown());
+        }
+        Some(
+            crate::i18n::text(if approved {
+                "approval.remote.approved"
+            } else {
+                "approval.remote.denied"
+            })
+            .to_owned(),
+        )
+    }
+
+    /// Deny `request_id` if its code is still outstanding.
+    ///
+    /// Returns whether a denial was issued; the channel target is told.
+    pub fn expire(&mut self, request_id: u64) -> bool {
+        let Some(token) = self.token_for(request_id) else {
+            return false;
+        };
+        let Some(forwarded) = self.forwarded.remove(&token) else {
+            return false;
+        };
+        if let Some(outbox) = &self.outbox {
+            let _ = outbox.send(RemotePrompt {
+                channel: forwarded.channel,
+                reply_target: forwarded.target,
+                text: crate::i18n::format("approval.remote.expired", &[("token", &token)]),
+            });
+        }
+        self.decide(request_id, false)
+    }
+
+    /// Drop the code for `request_id` after it was answered locally.
+    pub fn withdraw(&mut self, request_id: u64) {
+        self.forwarded.retain(|_, f| f.request_id != request_id);
+    }
+
+    fn token_for(&self, request_id: u64) -> Option<String> {
+        self.forwarded
+            .iter()
+            .find(|(_, f)| f.request_id == request_id)
+            .map(|(token, _)| token.clone())
+    }
+
+    fn decide(&self, request_id: u64, approved: bool) -> bool {
+        self.decisions.as_ref().is_some_and(|tx| {
+            tx.send(RemoteDecision {
+                request_id,
+                approved,
+            })
+            .is_ok()
+        })
+    }
+}
+
+/// Parse `approve <code>` / `deny <code>` into `(approved, CODE)`.
+fn parse_remote_reply(text: &str) -> Option<(bool, String)> {
+    let mut words = text.split_whitespace();
+    let verb = words.next()?.to_ascii_lowercase();
+    let token = words
+        .next()?
+        .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_ascii_uppercase();
+    if words.next().is_some() || token.is_empty() {
+        return None;
+    }
+    let approved = match verb.as_str() {
+        "approve" | "yes" | "allow" => true,
+        "deny" | "no" | "reject" => false,
+        _ => return None,
+    };
+    Some((approved, token))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn config() -> RemoteApprovalConfig {
+        RemoteApprovalConfig {
+            enabled: true,
+            channel: "discord".to_owned(),
+            target: "dm-42".to_owned(),
+            ..RemoteApprovalConfig::default()
+        }
+    }
+
+    fn router() -> (
+        RemoteApprovals,
+        mpsc::UnboundedReceiver<RemotePrompt>,
+        mpsc::UnboundedReceiver<RemoteDecision>,
+    ) {
+        let (outbox, prompts) = mpsc::unbounded_channel();
+        let (sink, decisions) = mpsc::unbounded_channel();
+        let mut router = RemoteApprovals::new();
+        router.set_outbox(Some(outbox));
+        router.set_decisions(Some(sink));
+        (router, prompts, decisions)
+    }
+
+    #[test]
+    fn destructive_calls_are_recognised() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let existing = dir.path().join("notes.txt");
+        std::fs::write(&existing, "keep me").expect("write");
+        let fresh = dir.path().join("new.txt");
+
+        assert_eq!(
+            DestructiveAction::classify("write", &serde_json::json!({"path": existing})),
+            Some(DestructiveAction::OverwriteFile)
+        );
+        assert_eq!(
+            DestructiveAction::classify("write", &serde_json::json!({"path": fresh})),
+            None
+        );
+        assert_eq!(
+            DestructiveAction::classify("compose_mail", &serde_json::json!({"action": "send"})),
+            Some(DestructiveAction::SendMail)
+        );
+        assert_eq!(
+            DestructiveAction::classify("compose_mail", &serde_json::json!({"action": "revise"})),
+            None
+        );
+        assert_eq!(
+            DestructiveAction::classify("read", &serde_json::json!({"path": existing})),
+            None
+        );
+        for tool in DestructiveAction::TOOLS {
+            assert!(
+                DestructiveAction::classify(tool, &serde_json::json!({"path": existing})).is_some()
+            );
+        }
+    }
+
+    #[test]
+    fn parses_replies() {
+        assert_eq!(
+            parse_remote_reply("Approve ab12cd"),
+            Some((true, "AB12CD".to_owned()))
+        );
+        assert_eq!(
+            parse_remote_reply("no AB12CD."),
+            Some((false, "AB12CD".to_owned()))
+        );
+        assert_eq!(parse_remote_reply("approve"), None);
+        assert_eq!(parse_remote_reply("what's the weather"), None);
+        assert_eq!(parse_remote_reply("yes please do it"), None);
+    }
+
+    #[test]
+    fn forwarded_request_is_resolved_by_reply() {
+        let (mut router, mut prompts, mut decisions) = router();
+        let now = Instant::now();
+        let token = router.forward(7, "bash", "ls -la", &config(), now).unwrap();
+        let prompt = prompts.try_recv().unwrap();
+        assert_eq!(prompt.channel, "discord");
+        assert_eq!(prompt.reply_target, "dm-42");
+        assert!(prompt.text.contains(&token));
+        assert!(router.is_forwarded(7));
+
+        // Another sender cannot answer.
+        assert!(
+            router
+                .handle_reply(
+                    "discord",
+                    "intruder",
+                    "dm-9",
+                    &format!("approve {token}"),
+                    now
+                )
+                .is_some()
+        );
+        assert!(decisions.try_recv().is_err());
+
+        let reply = router
+            .handle_reply("discord", "user", "dm-42", &format!("approve {token}"), now)
+            .unwrap();
+        assert!(!reply.is_empty());
+        assert_eq!(
+            decisions.try_recv().unwrap(),
+            RemoteDecision {
+                request_id: 7,
+                approved: true
+            }
+        );
+        assert!(!router.is_forwarded(7));
+        assert!(
+            router
+                .handle_reply("discord", "user", "dm-42", "hello there", now)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn expired_codes_deny() {
+        let (mut router, mut prompts, mut decisions) = router();
+        let now = Instant::now();
+        let token = router.forward(1, "bash", "rm x", &config(), now).unwrap();
+        let later = now + Duration::from_secs(config().token_ttl_secs + 1);
+        router.handle_reply("discord", "user", "dm-42", &format!("yes {token}"), later);
+        assert_eq!(
+            decisions.try_recv().unwrap(),
+            RemoteDecision {
+                request_id: 1,
+                approved: false
+            }
+        );
+
+        router.forward(2, "bash", "rm y", &config(), now).unwrap();
+        let _ = prompts.try_recv();
+        let _ = prompts.try_recv();
+        assert!(router.expire(2));
+        assert!(!decisions.try_recv().unwrap().approved);
+        assert!(
+            prompts.try_recv().is_ok(),
+            "target is told the code expired"
+        );
+        assert!(!router.expire(2));
+    }
+
+    #[test]
+    fn forwarding_needs_config_and_outbox() {
+        let (mut router, _prompts, _decisions) = router();
+        let now = Instant::now();
+        assert!(
+            router
+                .forward(1, "bash", "ls", &RemoteApprovalConfig::default(), now)
+                .is_none()
+        );
+        router.set_outbox(None);
+        assert!(router.forward(1, "bash", "ls", &config(), now).is_none());
+    }
+
+    #[test]
+    fn withdraw_drops_the_code() {
+        let (mut router, _prompts, mut decisions) = router();
+        let now = Instant::now();
+        let token = router.forward(3, "bash", "ls", &config(), now).unwrap();
+        router.withdraw(3);
+        assert!(!router.expire(3));
+        router.handle_reply("discord", "user", "dm-42", &format!("deny {token}"), now);
+        assert!(decisions.try_recv().is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Automatic gain control for microphone audio.
+//!
+//! Scales captured chunks so speech reaches the VAD and STT at a steady
+//! level. The gain only adapts on chunks loud enough to be speech, so room
+//! noise during pauses is not pumped up to speech level.
+
+use crate::config::AgcConfig;
+
+/// Lowest gain the controller will settle on.
+pub const MIN_GAIN: f32 = 0.25;
+
+/// Fraction of the distance to the desired gain covered per chunk.
+const ADAPT_RATE: f32 = 0.05;
+
+/// Chunks below this fraction of the target (after gain) are treated as
+/// non-speech and leave the gain unchanged.
+const SPEECH_GATE: f32 = 0.3;
+
+/// Root-mean-square level of `samples` (0 for an empty slice).
+pub fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = samples.iter().map(|s| s * s).sum();
+    (sum / samples.len() as f32).sqrt()
+}
+
+/// Gain controller state for one capture stream.
+#[derive(Debug, Clone)]
+pub struct Agc {
+    target_rms: f32,
+    max_gain: f32,
+    gain: f32,
+}
+
+impl Agc {
+    /// Create a controller starting at `config.initial_gain`.
+    pub fn new(config: &AgcConfig) -> Self {
+        let max_gain = config.max_gain.max(MIN_GAIN);
+        Self {
+            target_rms: config.target_rms.max(f32::EPSILON),
+            max_gain,
+            gain: config.initial_gain.clamp(MIN_GAIN, max_gain),
+        }
+    }
+
+    /// The gain currently applied.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Apply the gain to `samples` in place, then adapt it if the chunk
+    /// looks like speech. Output is clipped to \[-1, 1\].
+    pub fn process(&mut self, samples: &mut [f32]) {
+        let level = rms(samples);
+        let gain = self.gain;
+        for s in samples.iter_mut() {
+            *s = (*s * gain).clamp(-1.0, 1.0);
+        }
+        if level > 0.0 && level * gain >= self.target_rms * SPEECH_GATE {
+            let desired = (self.target_rms / level).clamp(MIN_GAIN, self.max_gain);
+            self.gain += (desired - self.gain) * ADAPT_RATE;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(initial_gain: f32) -> AgcConfig {
+        AgcConfig {
+            enabled: true,
+            target_rms: 0.05,
+            initial_gain,
+            max_gain: 10.0,
+        }
+    }
+
+    #[test]
+    fn quiet_speech_is_raised_towards_target() {
+        let mut agc = Agc::new(&config(1.0));
+        for _ in 0..200 {
+            let mut chunk = vec![0.02_f32, -0.02];
+            agc.process(&mut chunk);
+        }
+        assert!((agc.gain() - 2.5).abs() < 0.05, "gain {}", agc.gain());
+        let mut chunk = vec![0.02_f32, -0.02];
+        agc.process(&mut chunk);
+        assert!((rms(&chunk) - 0.05).abs() < 0.002);
+    }
+
+    #[test]
+    fn silence_leaves_gain_alone() {
+        let mut agc = Agc::new(&config(4.0));
+        for _ in 0..100 {
+            let mut chunk = vec![0.001_f32; 8];
+            agc.process(&mut chunk);
+        }
+        assert_eq!(agc.gain(), 4.0);
+    }
+}
@@ -0,0 +1,332 @@
+//! Microphone/speaker calibration for onboarding.
+//!
+//! Three measurements feed a [`CalibrationProfile`]:
+//!
+//! 1. **Ambient noise** — a few seconds of room tone give the noise floor.
+//! 2. **Read sentence** — the user's speaking level sets the AGC gain, and
+//!    the VAD threshold is placed between the (gained) noise floor and
+//!    speech.
+//! 3. **Echo test** — a test tone is played through the speakers while the
+//!    microphone records. If the tone is clearly audible at the microphone,
+//!    speaker output leaks back in and AEC is needed.
+//!
+//! Profiles are stored per input device in
+//! [`AudioConfig::calibrations`](crate::config::AudioConfig::calibrations)
+//! and applied by [`apply_profile`] when the pipeline starts.
+
+use crate::audio::agc::{MIN_GAIN, rms};
+use crate::config::{AgcConfig, AudioConfig, SpeechConfig};
+use crate::error::{Result, SpeechError};
+use crate::pipeline::messages::AudioChunk;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+pub use crate::config::CalibrationProfile;
+
+/// How long room tone is recorded for the noise floor.
+pub const AMBIENT_DURATION: Duration = Duration::from_secs(3);
+
+/// How long the user gets to read the calibration sentence.
+pub const SENTENCE_DURATION: Duration = Duration::from_secs(7);
+
+/// Frequency of the echo-test tone.
+pub const ECHO_TONE_HZ: f32 = 1000.0;
+
+/// Length of the echo-test tone.
+pub const ECHO_TONE_DURATION: Duration = Duration::from_millis(1500);
+
+/// Analysis frame length in milliseconds.
+const FRAME_MS: u32 = 20;
+
+/// Echo-test tone amplitude (about -14 dBFS).
+const ECHO_TONE_AMPLITUDE: f32 = 0.2;
+
+/// Tone level above ambient, in dB, at which speaker output is considered
+/// to reach the microphone.
+const ECHO_AEC_THRESHOLD_DB: f32 = 6.0;
+
+/// Minimum speech-to-noise ratio for a usable sentence recording.
+const MIN_SNR_DB: f32 = 6.0;
+
+/// VAD threshold as a multiple of the (gained) noise floor.
+const NOISE_MARGIN: f32 = 3.0;
+
+/// The VAD threshold never exceeds this fraction of the speech level.
+const MAX_THRESHOLD_OF_SPEECH: f32 = 0.5;
+
+/// The VAD threshold never drops below this level.
+const MIN_VAD_THRESHOLD: f32 = 0.002;
+
+/// Key under which the profile for `audio.input_device` is stored.
+pub fn device_key(audio: &AudioConfig) -> String {
+    audio
+        .input_device
+        .clone()
+        .unwrap_or_else(|| "default".to_owned())
+}
+
+/// The stored profile for the configured input device, if any.
+pub fn profile_for(audio: &AudioConfig) -> Option<&CalibrationProfile> {
+    audio.calibrations.get(&device_key(audio))
+}
+
+/// Override VAD, AGC and AEC settings in `config` with `profile`.
+pub fn apply_profile(profile: &CalibrationProfile, config: &mut SpeechConfig) {
+    config.vad.threshold = profile.vad_threshold;
+    config.agc.enabled = true;
+    config.agc.target_rms = profile.agc_target_rms;
+    config.agc.initial_gain = profile.input_gain;
+    config.aec.enabled = profile.aec_required;
+}
+
+fn frame_len(sample_rate: u32) -> usize {
+    (sample_rate * FRAME_MS / 1000).max(1) as usize
+}
+
+fn frame_levels(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+    let mut levels: Vec<f32> = samples.chunks(frame_len(sample_rate)).map(rms).collect();
+    levels.sort_by(f32::total_cmp);
+    levels
+}
+
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f32 * p).round() as usize;
+    sorted[index.min(sorted.len() - 1)]
+}
+
+/// Noise floor of a room-tone recording: the median frame level, so a
+/// cough or door slam does not raise it.
+pub fn noise_floor(samples: &[f32], sample_rate: u32) -> f32 {
+    percentile(&frame_levels(samples, sample_rate), 0.5)
+}
+
+/// Speaking level of a read-sentence recording: the median level of the
+/// frames clearly above the noise floor.
+///
+/// Returns `None` if too few frames contain speech.
+pub fn speech_level(samples: &[f32], sample_rate: u32, noise_floor: f32) -> Option<f32> {
+    let levels = frame_levels(samples, sample_rate);
+    let gate = (noise_floor * 2.0).max(MIN_VAD_THRESHOLD / 4.0);
+    let voiced: Vec<f32> = levels.into_iter().filter(|l| *l > gate).collect();
+    // At least half a second of speech.
+    if voiced.len() * (FRAME_MS as usize) < 500 {
+        return None;
+    }
+    Some(percentile(&voiced, 0.5))
+}
+
+/// Power of `samples` at `freq` (Goertzel), normalised by length.
+pub fn tone_power(samples: &[f32], sample_rate: u32, freq: f32) -> f32 {
+    if samples.is_empty() || sample_rate == 0 {
+        return 0.0;
+    }
+    let omega = 2.0 * std::f32::consts::PI * freq / sample_rate as f32;
+    let coeff = 2.0 * omega.cos();
+    let (mut s1, mut s2) = (0.0_f32, 0.0_f32);
+    for &x in samples {
+        let s0 = x + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s0;
+    }
+    let power = s1 * s1 + s2 * s2 - coeff * s1 * s2;
+    power / (samples.len() as f32 * samples.len() as f32)
+}
+
+/// Level of the echo-test tone in `during` relative to `ambient`, in dB.
+///
+/// Both recordings are cut to the same length so noise contributes equally.
+pub fn echo_return_db(ambient: &[f32], during: &[f32], sample_rate: u32) -> f32 {
+    let n = ambient.len().min(during.len());
+    let (ambient, during) = (&ambient[..n], &during[..n]);
+    let floor = tone_power(ambient, sample_rate, ECHO_TONE_HZ).max(1e-12);
+    let echo = tone_power(during, sample_rate, ECHO_TONE_HZ).max(1e-12);
+    10.0 * (echo / floor).log10()
+}
+
+/// The echo-test tone: a sine at [`ECHO_TONE_HZ`] with short fades.
+pub fn echo_test_tone(sample_rate: u32) -> Vec<f32> {
+    let n = (sample_rate as f32 * ECHO_TONE_DURATION.as_secs_f32()) as usize;
+    let fade = (sample_rate / 50).max(1) as usize;
+    (0..n)
+        .map(|i| {
+            let env = (i.min(n - 1 - i) as f32 / fade as f32).min(1.0);
+            let t = i as f32 / sample_rate as f32;
+            ECHO_TONE_AMPLITUDE * env * (2.0 * std::f32::consts::PI * ECHO_TONE_HZ * t).sin()
+        })
+        .collect()
+}
+
+fn to_db(ratio: f32) -> f32 {
+    20.0 * ratio.max(1e-9).log10()
+}
+
+/// Build a profile from the three measurements.
+///
+/// # Errors
+///
+/// Returns an error if speech was not sufficiently louder than the room.
+pub fn derive_profile(
+    noise_floor_rms: f32,
+    speech_rms: f32,
+    echo_return_db: f32,
+    agc: &AgcConfig,
+    output_device: Option<String>,
+) -> Result<CalibrationProfile> {
+    let snr_db = to_db(speech_rms / noise_floor_rms.max(1e-6));
+    if snr_db < MIN_SNR_DB {
+        return Err(SpeechError::Audio(format!(
+            "speech is only {snr_db:.1} dB above background noise; \
+             move closer to the microphone or somewhere quieter"
+        )));
+    }
+
+    let input_gain = (agc.target_rms / speech_rms).clamp(MIN_GAIN, agc.max_gain.max(MIN_GAIN));
+    let gained_noise = noise_floor_rms * input_gain;
+    let gained_speech = speech_rms * input_gain;
+    let vad_threshold = (gained_noise * NOISE_MARGIN)
+        .min(gained_speech * MAX_THRESHOLD_OF_SPEECH)
+        .max(MIN_VAD_THRESHOLD);
+
+    Ok(CalibrationProfile {
+        output_device,
+        noise_floor_rms,
+        speech_rms,
+        snr_db,
+        vad_threshold,
+        agc_target_rms: agc.target_rms,
+        input_gain,
+        echo_return_db,
+        aec_required: echo_return_db >= ECHO_AEC_THRESHOLD_DB,
+        calibrated_at: crate::time_util::now_epoch_secs(),
+    })
+}
+
+/// Record `duration` of microphone audio at the pipeline input rate.
+///
+/// # Errors
+///
+/// Returns an error if the input device cannot be opened.
+pub async fn record(audio: &AudioConfig, duration: Duration) -> Result<Vec<f32>> {
+    use crate::audio::capture::CpalCapture;
+
+    let capture = CpalCapture::new(audio)?;
+    let (tx, mut rx) = mpsc::channel::<AudioChunk>(64);
+    let cancel = CancellationToken::new();
+    let stop = cancel.clone();
+    let timer = tokio::spawn(async move {
+        tokio::time::sleep(duration).await;
+        stop.cancel();
+    });
+
+    let collect = async {
+        let mut samples = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            samples.extend_from_slice(&chunk.samples);
+        }
+        samples
+    };
+    let (result, samples) = tokio::join!(capture.run(tx, cancel), collect);
+    timer.abort();
+    result?;
+    Ok(samples)
+}
+
+/// Play the echo-test tone while recording, returning the recording.
+///
+/// # Errors
+///
+/// Returns an error if the input or output device cannot be opened.
+pub async fn record_echo_test(audio: &AudioConfig) -> Result<Vec<f32>> {
+    use crate::audio::playback::CpalPlayback;
+
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let mut playback = CpalPlayback::new(audio, event_tx)?;
+    let rate = audio.output_sample_rate;
+    let tone = echo_test_tone(rate);
+
+    // Start listening slightly before the tone and keep going briefly after
+    // it so device latency does not cut off the echo.
+    let recording = record(audio, ECHO_TONE_DURATION + Duration::from_millis(700));
+    let play = async {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        playback.enqueue(&tone, rate, true)
+    };
+    let (samples, played) = tokio::join!(recording, play);
+    played?;
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    const RATE: u32 = 16_000;
+
+    fn noise(n: usize, level: f32) -> Vec<f32> {
+        // Deterministic pseudo-noise with RMS close to `level`.
+        let mut state = 0x1234_5678_u32;
+        (0..n)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state as f32 / u32::MAX as f32 * 2.0 - 1.0) * level * 3f32.sqrt()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn levels_are_measured_per_frame() {
+        let room = noise(RATE as usize * 3, 0.002);
+        let floor = noise_floor(&room, RATE);
+        assert!((floor - 0.002).abs() < 0.0004, "floor {floor}");
+
+        // One second of speech-level audio inside two seconds of room tone.
+        let mut sentence = noise(RATE as usize, 0.002);
+        sentence.extend(noise(RATE as usize, 0.02));
+        let level = speech_level(&sentence, RATE, floor).unwrap();
+        assert!((level - 0.02).abs() < 0.004, "speech {level}");
+
+        assert_eq!(speech_level(&room, RATE, floor), None);
+    }
+
+    #[test]
+    fn profile_places_threshold_between_noise_and_speech() {
+        let agc = AgcConfig::default();
+        let profile = derive_profile(0.002, 0.02, 1.0, &agc, None).unwrap();
+        assert!((profile.input_gain - 2.5).abs() < 1e-4);
+        assert!((profile.vad_threshold - 0.015).abs() < 1e-4);
+        assert!(profile.vad_threshold < 0.05 * MAX_THRESHOLD_OF_SPEECH + 1e-6);
+        assert!((profile.snr_db - 20.0).abs() < 0.01);
+        assert!(!profile.aec_required);
+
+        assert!(derive_profile(0.01, 0.015, 0.0, &agc, None).is_err());
+
+        let mut config = SpeechConfig::default();
+        apply_profile(&profile, &mut config);
+        assert_eq!(config.vad.threshold, profile.vad_threshold);
+        assert!(config.agc.enabled);
+        assert!(!config.aec.enabled);
+    }
+
+    #[test]
+    fn echo_test_detects_tone_at_the_microphone() {
+        let ambient = noise(RATE as usize, 0.002);
+        let tone = echo_test_tone(RATE);
+        let leaked: Vec<f32> = ambient
+            .iter()
+            .zip(tone.iter().chain(std::iter::repeat(&0.0)))
+            .map(|(n, t)| n + t * 0.1)
+            .collect();
+        assert!(echo_return_db(&ambient, &leaked, RATE) > ECHO_AEC_THRESHOLD_DB);
+
+        let quiet = noise(RATE as usize, 0.002);
+        assert!(echo_return_db(&ambient, &quiet, RATE) < ECHO_AEC_THRESHOLD_DB);
+    }
+}
@@ -1,16 +1,20 @@
 //! Microphone audio capture using cpal.
 //!
-//! Captures audio at the device's native sample rate and downsamples
-//! to 16kHz mono for the speech processing pipeline.
-
-use crate::config::AudioConfig;
+//! Captures audio at the device's native sample rate and format (WASAPI
+//! shared-mode devices may deliver i16) and downsamples to 16kHz mono for
+//! the speech processing pipeline. The input stream is rebuilt in place if
+//! its callbacks stall (see [`crate::audio::watchdog`]).
+
+use crate::audio::agc::Agc;
+use crate::audio::watchdog;
+use crate::config::{AgcConfig, AudioConfig};
 use crate::error::{Result, SpeechError};
 use crate::pipeline::messages::AudioChunk;
 use cpal::StreamConfig;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
@@ -23,10 +27,16 @@ use tracing::{debug, error, info, warn};
 pub struct CpalCapture {
     device: cpal::Device,
     stream_config: StreamConfig,
+    /// Sample format the device delivers (f32, or i16 on some WASAPI devices).
+    sample_format: cpal::SampleFormat,
     /// The target sample rate for the pipeline (e.g., 16kHz).
     target_sample_rate: u32,
     /// Target chunk size at the pipeline sample rate (in frames/samples).
     target_chunk_frames: usize,
+    /// Gain control applied to each chunk before it is sent, if enabled.
+    agc: Option<Agc>,
+    /// Rebuild the stream after this long without callbacks (zero = never).
+    stall_timeout: Duration,
 }
 
 impl CpalCapture {
@@ -79,6 +89,7 @@ impl CpalCapture {
 
         let native_rate = default_config.sample_rate();
         let native_channels = default_config.channels();
+        let sample_format = default_config.sample_format();
 
         let stream_config = StreamConfig {
             channels: native_channels,
@@ -87,8 +98,8 @@ impl CpalCapture {
         };
 
         info!(
-            "native input config: {}Hz, {} channels",
-            native_rate, native_channels
+            "native input config: {}Hz, {} channels, {:?}",
+            native_rate, native_channels, sample_format
         );
 
         if native_rate != config.input_sample_rate {
@@ -101,23 +112,79 @@ impl CpalCapture {
         Ok(Self {
             device,
             stream_config,
+            sample_format,
             target_sample_rate: config.input_sample_rate,
             target_chunk_frames: config.buffer_size as usize,
+            agc: None,
+            stall_timeout: Duration::from_millis(u64::from(config.stall_timeout_ms)),
         })
     }
 
+    /// Apply automatic gain control to captured audio when `config` enables it.
+    #[must_use]
+    pub fn with_agc(mut self, config: &AgcConfig) -> Self {
+        self.agc = config.enabled.then(|| Agc::new(config));
+        self
+    }
+
     /// Run the capture loop, sending audio chunks to the provided channel.
     ///
-    /// Blocks until the cancellation token is triggered.
+    /// Blocks until the cancellation token is triggered. If the stream stops
+    /// delivering callbacks it is torn down and rebuilt without returning.
     ///
     /// # Errors
     ///
     /// Returns an error if the audio stream cannot be created.
     pub async fn run(&self, tx: mpsc::Sender<AudioChunk>, cancel: CancellationToken) -> Result<()> {
+        let mut stream = Some(self.start_stream(&tx)?);
+        watchdog::CAPTURE.arm();
+        info!(
+            "audio capture started: native {}Hz -> target {}Hz",
+            self.stream_config.sample_rate, self.target_sample_rate
+        );
+
+        let mut check = tokio::time::interval(watchdog::poll_interval(self.stall_timeout));
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                _ = check.tick(), if !self.stall_timeout.is_zero() => {
+                    let Some(silent) = watchdog::CAPTURE.stalled(self.stall_timeout) else {
+                        continue;
+                    };
+                    warn!(
+                        silent_ms = silent.as_millis() as u64,
+                        "audio input stream stalled, rebuilding"
+                    );
+                    // Tear the dead stream down before opening the device again.
+                    drop(stream.take());
+                    match self.start_stream(&tx) {
+                        Ok(rebuilt) => {
+                            stream = Some(rebuilt);
+                            watchdog::CAPTURE.record_rebuild(true);
+                            info!("audio input stream rebuilt");
+                        }
+                        Err(e) => {
+                            // Retried after another timeout.
+                            watchdog::CAPTURE.record_rebuild(false);
+                            warn!("audio input stream rebuild failed: {e}");
+                        }
+                    }
+                }
+            }
+        }
+
+        drop(stream);
+        info!("audio capture stopped");
+        Ok(())
+    }
+
+    /// Build and start an input stream that sends chunks to `tx`.
+    fn start_stream(&self, tx: &mpsc::Sender<AudioChunk>) -> Result<cpal::Stream> {
         let native_rate = self.stream_config.sample_rate;
         let native_channels = self.stream_config.channels;
         let target_rate = self.target_sample_rate;
         let chunk_len = self.target_chunk_frames.max(1);
+        let mut agc = self.agc.clone();
         let tx_clone = tx.clone();
         let mut pending: VecDeque<f32> = VecDeque::with_capacity(chunk_len.saturating_mul(4));
 
@@ -126,105 +193,115 @@ impl CpalCapture {
         let last_report_ms = AtomicU64::new(0);
         let tx_closed = AtomicBool::new(false);
 
-        let stream = self
-            .device
-            .build_input_stream(
-                &self.stream_config,
-                move |data: &[f32], _info: &cpal::InputCallbackInfo| {
-                    // Convert to mono if needed
-                    let mono = if native_channels > 1 {
-                        to_mono(data, native_channels)
-                    } else {
-                        data.to_vec()
-                    };
-
-                    // Downsample if native rate differs from target
-                    let samples = if native_rate != target_rate {
-                        downsample(&mono, native_rate, target_rate)
-                    } else {
-                        mono
-                    };
-
-                    pending.extend(samples.into_iter());
+        let mut on_samples = move |data: &[f32]| {
+            watchdog::CAPTURE.beat();
+
+            // Convert to mono if needed
+            let mono = if native_channels > 1 {
+                to_mono(data, native_channels)
+            } else {
+                data.to_vec()
+            };
+
+            // Downsample if native rate differs from target
+            let samples = if native_rate != target_rate {
+                downsample(&mono, native_rate, target_rate)
+            } else {
+                mono
+            };
+
+            pending.extend(samples.into_iter());
+
+            // Emit fixed-size chunks to make downstream timing consistent.
+            while pending.len() >= chunk_len {
+                if tx_closed.load(Ordering::Relaxed) {
+                    // Downstream pipeline has stopped; discard buffered samples.
+                    pending.clear();
+                    break;
+                }
 
-                    // Emit fixed-size chunks to make downstream timing consistent.
-                    while pending.len() >= chunk_len {
-                        if tx_closed.load(Ordering::Relaxed) {
-                            // Downstream pipeline has stopped; discard buffered samples.
-                            pending.clear();
-                            break;
-                        }
+                let mut out = Vec::with_capacity(chunk_len);
+                for _ in 0..chunk_len {
+                    if let Some(s) = pending.pop_front() {
+                        out.push(s);
+                    }
+                }
 
-                        let mut out = Vec::with_capacity(chunk_len);
-                        for _ in 0..chunk_len {
-                            if let Some(s) = pending.pop_front() {
-                                out.push(s);
-                            }
-                        }
+                if let Some(ref mut agc) = agc {
+                    agc.process(&mut out);
+                }
 
-                        let chunk = AudioChunk {
-                            samples: out,
-                            sample_rate: target_rate,
-                            captured_at: Instant::now(),
-                        };
-                        // Use try_send to avoid blocking the audio thread
-                        match tx_clone.try_send(chunk) {
-                            Ok(()) => {}
-                            Err(mpsc::error::TrySendError::Full(_chunk)) => {
-                                dropped_full.fetch_add(1, Ordering::Relaxed);
-                            }
-                            Err(mpsc::error::TrySendError::Closed(_chunk)) => {
-                                tx_closed.store(true, Ordering::Relaxed);
-                            }
-                        }
+                let chunk = AudioChunk {
+                    samples: out,
+                    sample_rate: target_rate,
+                    captured_at: Instant::now(),
+                };
+                // Use try_send to avoid blocking the audio thread
+                match tx_clone.try_send(chunk) {
+                    Ok(()) => {}
+                    Err(mpsc::error::TrySendError::Full(_chunk)) => {
+                        dropped_full.fetch_add(1, Ordering::Relaxed);
+                        crate::pipeline::queues::AUDIO.record_dropped(1);
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_chunk)) => {
+                        tx_closed.store(true, Ordering::Relaxed);
+                    }
+                }
 
-                        // Rate-limit logs to avoid spamming.
-                        let now_ms = SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .map(|d| d.as_millis() as u64)
-                            .unwrap_or(0);
-                        let last = last_report_ms.load(Ordering::Relaxed);
-                        if now_ms.saturating_sub(last) >= 2_000
-                            && last_report_ms
-                                .compare_exchange(
-                                    last,
-                                    now_ms,
-                                    Ordering::Relaxed,
-                                    Ordering::Relaxed,
-                                )
-                                .is_ok()
-                        {
-                            let n = dropped_full.swap(0, Ordering::Relaxed);
-                            if tx_closed.load(Ordering::Relaxed) {
-                                debug!("audio channel closed (pipeline stopped)");
-                            } else if n > 0 {
-                                debug!("audio channel full, dropped {n} chunks (last 2s)");
-                            }
-                        }
+                // Rate-limit logs to avoid spamming.
+                let now_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+                let last = last_report_ms.load(Ordering::Relaxed);
+                if now_ms.saturating_sub(last) >= 2_000
+                    && last_report_ms
+                        .compare_exchange(last, now_ms, Ordering::Relaxed, Ordering::Relaxed)
+                        .is_ok()
+                {
+                    let n = dropped_full.swap(0, Ordering::Relaxed);
+                    if tx_closed.load(Ordering::Relaxed) {
+                        debug!("audio channel closed (pipeline stopped)");
+                    } else if n > 0 {
+                        debug!("audio channel full, dropped {n} chunks (last 2s)");
                     }
+                }
+            }
+        };
+        let on_error = move |err: cpal::StreamError| {
+            error!("audio input stream error: {err}");
+        };
+
+        // WASAPI opens shared-mode streams in the device's mix format, which
+        // is not always f32; convert integer formats before processing.
+        let stream = match self.sample_format {
+            cpal::SampleFormat::F32 => self.device.build_input_stream(
+                &self.stream_config,
+                move |data: &[f32], _info: &cpal::InputCallbackInfo| on_samples(data),
+                on_error,
+                None,
+            ),
+            cpal::SampleFormat::I16 => self.device.build_input_stream(
+                &self.stream_config,
+                move |data: &[i16], _info: &cpal::InputCallbackInfo| {
+                    let samples: Vec<f32> = data.iter().map(|&s| f32::from(s) / 32_768.0).collect();
+                    on_samples(&samples);
                 },
-                move |err| {
-                    error!("audio input stream error: {err}");
-                },
+                on_error,
                 None,
-            )
-            .map_err(|e| SpeechError::Audio(format!("failed to build input stream: {e}")))?;
+            ),
+            other => {
+                return Err(SpeechError::Audio(format!(
+                    "unsupported input sample format: {other:?}"
+                )));
+            }
+        }
+        .map_err(|e| SpeechError::Audio(format!("failed to build input stream: {e}")))?;
 
         stream
             .play()
             .map_err(|e| SpeechError::Audio(format!("failed to start input stream: {e}")))?;
-
-        info!(
-            "audio capture started: native {}Hz -> target {}Hz",
-            native_rate, target_rate
-        );
-
-        // Hold the stream alive until cancelled
-        cancel.cancelled().await;
-
-        drop(stream);
-        info!("audio capture stopped");
-        Ok(())
+        Ok(stream)
     }
 
     /// List available input devices.
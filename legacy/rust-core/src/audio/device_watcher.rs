@@ -1,31 +1,112 @@
-//! Audio input device change detection.
+//! Audio device and format change detection.
 //!
-//! [`AudioDeviceWatcher`] polls the list of available CPAL input devices every
+//! [`AudioDeviceWatcher`] polls the default CPAL input and output devices every
 //! two seconds and emits a [`GateCommand::RestartAudio`] when the default
-//! device changes (or appears/disappears).
+//! input device changes (or appears/disappears), or when either device's
+//! stream format changes.
 //!
 //! This allows the pipeline to pick up newly plugged-in headphones or
 //! microphones without requiring a full pipeline restart.
 //!
+//! A format change on the same device is usually a Bluetooth headset
+//! switching profile: AirPods drop from A2DP to the hands-free profile (HFP)
+//! as soon as their microphone opens, and the stream built for the old
+//! sample rate captures garbage. Restarting the pipeline renegotiates both
+//! capture and playback against the new defaults. When the output falls to
+//! a hands-free rate the watcher can also tell the user, via
+//! [`ControlEvent::AudioQualityChanged`], that their headphones switched to
+//! low-quality mode.
+//!
 //! # Design
 //!
 //! The watcher runs as a background tokio task. It does not use OS-level
 //! audio change notifications because CPAL's cross-platform API does not
 //! expose them. Polling every 2 s is cheap and sufficient for the use case.
 
-use crate::pipeline::messages::GateCommand;
+use crate::pipeline::messages::{ControlEvent, GateCommand};
+use crate::runtime::RuntimeEvent;
 use cpal::traits::{DeviceTrait, HostTrait};
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
-/// Polls CPAL for input device changes and sends a [`GateCommand::RestartAudio`]
-/// when the default device changes.
+/// Highest output sample rate treated as a hands-free (low-quality) link.
+///
+/// HFP runs at 8 kHz (CVSD), 16 kHz (mSBC) or 24 kHz (AAC-ELD on recent
+/// AirPods); A2DP and wired outputs run at 44.1 kHz or more.
+const HANDS_FREE_MAX_RATE: u32 = 24_000;
+
+/// Default device and stream format on one side of the audio path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Endpoint {
+    name: String,
+    sample_rate: u32,
+    channels: u16,
+}
+
+/// Default input and output as seen by one poll.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct AudioSnapshot {
+    input: Option<Endpoint>,
+    output: Option<Endpoint>,
+}
+
+impl AudioSnapshot {
+    fn capture() -> Self {
+        let host = cpal::default_host();
+        let input = host.default_input_device().and_then(|dev| {
+            let config = dev.default_input_config().ok()?;
+            Some(Endpoint {
+                name: device_name(&dev)?,
+                sample_rate: config.sample_rate(),
+                channels: config.channels(),
+            })
+        });
+        let output = host.default_output_device().and_then(|dev| {
+            let config = dev.default_output_config().ok()?;
+            Some(Endpoint {
+                name: device_name(&dev)?,
+                sample_rate: config.sample_rate(),
+                channels: config.channels(),
+            })
+        });
+        Self { input, output }
+    }
+
+    fn input_name(&self) -> Option<String> {
+        self.input.as_ref().map(|e| e.name.clone())
+    }
+
+    /// Whether the output is running at a hands-free profile rate.
+    fn low_quality(&self) -> bool {
+        self.output
+            .as_ref()
+            .is_some_and(|e| e.sample_rate <= HANDS_FREE_MAX_RATE)
+    }
+
+    /// Why the pipeline must restart to move from `self` to `next`, if at all.
+    fn restart_reason(&self, next: &Self) -> Option<&'static str> {
+        if self.input_name() != next.input_name() {
+            Some("input device changed")
+        } else if self.output.as_ref().map(|e| &e.name) != next.output.as_ref().map(|e| &e.name) {
+            Some("output device changed")
+        } else if self.input != next.input || self.output != next.output {
+            // Same device, new format: a profile switch.
+            Some("audio format changed")
+        } else {
+            None
+        }
+    }
+}
+
+/// Polls CPAL for device and format changes and sends a
+/// [`GateCommand::RestartAudio`] when the audio path must be rebuilt.
 pub struct AudioDeviceWatcher {
     gate_tx: mpsc::UnboundedSender<GateCommand>,
     cancel: CancellationToken,
     poll_interval: Duration,
+    quality_tx: Option<broadcast::Sender<RuntimeEvent>>,
 }
 
 impl AudioDeviceWatcher {
@@ -37,9 +118,18 @@ impl AudioDeviceWatcher {
             gate_tx,
             cancel,
             poll_interval: Duration::from_secs(2),
+            quality_tx: None,
         }
     }
 
+    /// Emit [`ControlEvent::AudioQualityChanged`] on `runtime_tx` when the
+    /// output enters or leaves a hands-free profile.
+    #[must_use]
+    pub fn with_quality_warnings(mut self, runtime_tx: broadcast::Sender<RuntimeEvent>) -> Self {
+        self.quality_tx = Some(runtime_tx);
+        self
+    }
+
     /// Override the poll interval (useful for testing).
     #[cfg(test)]
     pub fn with_poll_interval(mut self, interval: Duration) -> Self {
@@ -56,8 +146,10 @@ impl AudioDeviceWatcher {
     /// tokio::spawn(watcher.run());
     /// ```
     pub async fn run(self) {
-        let mut last_device: Option<String> = current_default_device_name();
-        info!(device = ?last_device, "audio device watcher started");
+        let mut last = AudioSnapshot::capture();
+        info!(input = ?last.input, output = ?last.output, "audio device watcher started");
+        let mut low_quality = false;
+        self.report_quality(&last, &mut low_quality);
 
         loop {
             tokio::select! {
@@ -66,36 +158,58 @@ impl AudioDeviceWatcher {
                     break;
                 }
                 _ = tokio::time::sleep(self.poll_interval) => {
-                    let current = current_default_device_name();
-                    if current != last_device {
-                        let old = last_device.as_deref().unwrap_or("<none>");
-                        let new_name = current.as_deref().unwrap_or("<none>");
-                        info!(
-                            old_device = old,
-                            new_device = new_name,
-                            "audio input device changed — sending RestartAudio"
-                        );
-                        let cmd = GateCommand::RestartAudio {
-                            device_name: current.clone(),
-                        };
-                        if self.gate_tx.send(cmd).is_err() {
-                            // Pipeline no longer accepting commands — stop watching.
-                            warn!("audio device watcher: gate_tx closed, stopping");
-                            break;
-                        }
-                        last_device = current;
+                    let current = AudioSnapshot::capture();
+                    let Some(reason) = last.restart_reason(&current) else {
+                        continue;
+                    };
+                    info!(
+                        reason,
+                        old_input = ?last.input,
+                        new_input = ?current.input,
+                        old_output = ?last.output,
+                        new_output = ?current.output,
+                        "audio path changed — sending RestartAudio"
+                    );
+                    self.report_quality(&current, &mut low_quality);
+                    let cmd = GateCommand::RestartAudio {
+                        device_name: current.input_name(),
+                    };
+                    if self.gate_tx.send(cmd).is_err() {
+                        // Pipeline no longer accepting commands — stop watching.
+                        warn!("audio device watcher: gate_tx closed, stopping");
+                        break;
                     }
+                    last = current;
                 }
             }
         }
     }
+
+    /// Emit a quality event if `snapshot` crosses the hands-free threshold.
+    fn report_quality(&self, snapshot: &AudioSnapshot, low_quality: &mut bool) {
+        let now_low = snapshot.low_quality();
+        if now_low == *low_quality {
+            return;
+        }
+        *low_quality = now_low;
+        let Some(tx) = &self.quality_tx else {
+            return;
+        };
+        let output = snapshot.output.as_ref();
+        if now_low {
+            warn!(output = ?output, "audio output switched to a hands-free profile");
+        }
+        let _ = tx.send(RuntimeEvent::Control(ControlEvent::AudioQualityChanged {
+            device_name: output.map(|e| e.name.clone()),
+            sample_rate: output.map_or(0, |e| e.sample_rate),
+            low_quality: now_low,
+        }));
+    }
 }
 
-/// Return the name of the default CPAL input device, or `None` if unavailable.
-fn current_default_device_name() -> Option<String> {
-    let host = cpal::default_host();
-    host.default_input_device()
-        .and_then(|dev| dev.description().ok().map(|d| d.name().to_owned()))
+/// Display name of a CPAL device, or `None` if unavailable.
+fn device_name(device: &cpal::Device) -> Option<String> {
+    device.description().ok().map(|d| d.name().to_owned())
 }
 
 #[cfg(test)]
@@ -143,6 +257,63 @@ mod tests {
         assert!(result.is_ok(), "watcher task should finish");
     }
 
+    fn endpoint(name: &str, sample_rate: u32, channels: u16) -> Option<Endpoint> {
+        Some(Endpoint {
+            name: name.to_owned(),
+            sample_rate,
+            channels,
+        })
+    }
+
+    #[test]
+    fn profile_switch_on_the_same_device_restarts_audio() {
+        let a2dp = AudioSnapshot {
+            input: endpoint("AirPods Pro", 24_000, 1),
+            output: endpoint("AirPods Pro", 48_000, 2),
+        };
+        let hfp = AudioSnapshot {
+            input: endpoint("AirPods Pro", 16_000, 1),
+            output: endpoint("AirPods Pro", 16_000, 1),
+        };
+        assert_eq!(a2dp.restart_reason(&a2dp.clone()), None);
+        assert_eq!(a2dp.restart_reason(&hfp), Some("audio format changed"));
+        assert!(!a2dp.low_quality());
+        assert!(hfp.low_quality());
+
+        let built_in = AudioSnapshot {
+            input: endpoint("MacBook Pro Microphone", 48_000, 1),
+            ..hfp.clone()
+        };
+        assert_eq!(hfp.restart_reason(&built_in), Some("input device changed"));
+    }
+
+    #[test]
+    fn quality_events_are_sent_only_on_transitions() {
+        let (gate_tx, _gate_rx) = mpsc::unbounded_channel::<GateCommand>();
+        let (tx, mut rx) = broadcast::channel(8);
+        let watcher =
+            AudioDeviceWatcher::new(gate_tx, CancellationToken::new()).with_quality_warnings(tx);
+        let hfp = AudioSnapshot {
+            input: None,
+            output: endpoint("AirPods", 16_000, 1),
+        };
+        let mut low_quality = false;
+        watcher.report_quality(&hfp, &mut low_quality);
+        watcher.report_quality(&hfp, &mut low_quality);
+        watcher.report_quality(&AudioSnapshot::default(), &mut low_quality);
+
+        let mut events = Vec::new();
+        while let Ok(RuntimeEvent::Control(ControlEvent::AudioQualityChanged {
+            sample_rate,
+            low_quality,
+            ..
+        })) = rx.try_recv()
+        {
+            events.push((sample_rate, low_quality));
+        }
+        assert_eq!(events, vec![(16_000, true), (0, false)]);
+    }
+
     #[test]
     fn restart_audio_gate_command_has_device_name() {
         let cmd = GateCommand::RestartAudio {
@@ -1,7 +1,10 @@
 //! Audio capture, playback, and echo cancellation via cpal.
 
 pub mod aec;
+pub mod agc;
+pub mod calibration;
 pub mod capture;
 pub mod device_watcher;
 pub mod playback;
 pub mod tone;
+pub mod watchdog;
@@ -1,15 +1,18 @@
 //! Audio playback to system speakers via cpal.
 //!
 //! This implementation keeps a persistent output stream alive and plays audio
-//! from an internal queue so playback can be interrupted (barge-in).
+//! from an internal queue so playback can be interrupted (barge-in). If the
+//! stream's callbacks stall, [`CpalPlayback::rebuild_if_stalled`] replaces
+//! it and the queue carries over.
 
+use crate::audio::watchdog;
 use crate::config::AudioConfig;
 use crate::error::{Result, SpeechError};
 use cpal::StreamConfig;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::{error, info, warn};
 
@@ -33,10 +36,11 @@ struct SharedState {
 
 /// Audio playback to system speakers via cpal.
 pub struct CpalPlayback {
+    device: cpal::Device,
     stream_config: StreamConfig,
     shared: Arc<Mutex<SharedState>>,
     // Keep the stream alive for the life of this struct.
-    _stream: cpal::Stream,
+    stream: Option<cpal::Stream>,
     event_tx: UnboundedSender<PlaybackEvent>,
 }
 
@@ -155,15 +159,53 @@ impl CpalPlayback {
         stream
             .play()
             .map_err(|e| SpeechError::Audio(format!("failed to start output stream: {e}")))?;
+        watchdog::PLAYBACK.arm();
 
         Ok(Self {
+            device,
             stream_config,
             shared,
-            _stream: stream,
+            stream: Some(stream),
             event_tx,
         })
     }
 
+    /// Rebuild the output stream if its callbacks stopped for `timeout`.
+    ///
+    /// Queued audio is kept and resumes on the new stream. Returns whether
+    /// a stall was detected; a failed rebuild is retried on a later call.
+    pub fn rebuild_if_stalled(&mut self, timeout: Duration) -> bool {
+        let Some(silent) = watchdog::PLAYBACK.stalled(timeout) else {
+            return false;
+        };
+        warn!(
+            silent_ms = silent.as_millis() as u64,
+            "audio output stream stalled, rebuilding"
+        );
+        // Tear the dead stream down before opening the device again.
+        drop(self.stream.take());
+        let rebuilt = build_stream(
+            &self.device,
+            &self.stream_config,
+            Arc::clone(&self.shared),
+            self.event_tx.clone(),
+        )
+        .map_err(|e| e.to_string())
+        .and_then(|stream| stream.play().map(|()| stream).map_err(|e| e.to_string()));
+        match rebuilt {
+            Ok(stream) => {
+                self.stream = Some(stream);
+                watchdog::PLAYBACK.record_rebuild(true);
+                info!("audio output stream rebuilt");
+            }
+            Err(e) => {
+                watchdog::PLAYBACK.record_rebuild(false);
+                warn!("audio output stream rebuild failed: {e}");
+            }
+        }
+        true
+    }
+
     /// Enqueue audio samples for playback.
     ///
     /// If `is_final` is true, `PlaybackEvent::Finished` will be emitted when the queue drains.
@@ -264,6 +306,7 @@ fn build_stream(
     device.build_output_stream(
         stream_config,
         move |data: &mut [f32], _info: &cpal::OutputCallbackInfo| {
+            watchdog::PLAYBACK.beat();
             let mut drained = false;
             let mut should_finish = false;
             let mut level: Option<f32> = None;
@@ -324,7 +367,7 @@ fn build_stream(
     )
 }
 
-fn resample_linear(samples: &[f32], src_rate: u32, dst_rate: u32) -> Vec<f32> {
+pub(crate) fn resample_linear(samples: &[f32], src_rate: u32, dst_rate: u32) -> Vec<f32> {
     if src_rate == dst_rate || samples.is_empty() {
         return samples.to_vec();
     }
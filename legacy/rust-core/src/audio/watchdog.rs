@@ -0,0 +1,176 @@
+//! Stall detection for cpal streams.
+//!
+//! After sleep/wake (and occasionally after a device hiccup) a cpal stream
+//! can stay "playing" while its callback is never called again: the
+//! microphone goes deaf or speech never reaches the speakers, and only an
+//! app restart used to help. Each stream's callback beats a [`StreamWatch`];
+//! the owning stage polls [`StreamWatch::stalled`] and rebuilds the stream
+//! when no callback arrived within the configured timeout
+//! ([`AudioConfig::stall_timeout_ms`](crate::config::AudioConfig::stall_timeout_ms)).
+//!
+//! Like the queue metrics in [`crate::pipeline::queues`], each watched
+//! stream is a process-wide static so [`snapshot`] can report stall and
+//! rebuild counts in runtime status and diagnostics bundles.
+
+use serde::Serialize;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Process-wide reference point for heartbeat timestamps.
+fn epoch() -> Instant {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    *EPOCH.get_or_init(Instant::now)
+}
+
+fn now_ms() -> u64 {
+    epoch().elapsed().as_millis() as u64
+}
+
+/// Heartbeat and counters for one cpal stream.
+pub struct StreamWatch {
+    name: &'static str,
+    /// Milliseconds since [`epoch`] of the last callback.
+    last_beat_ms: AtomicU64,
+    stalls: AtomicU64,
+    rebuilds: AtomicU64,
+    failed_rebuilds: AtomicU64,
+    /// Length of the most recent stall, in milliseconds.
+    last_stall_ms: AtomicU64,
+}
+
+/// Point-in-time metrics for one watched stream.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreamStats {
+    pub name: &'static str,
+    /// Stalls detected since startup.
+    pub stalls: u64,
+    /// Streams rebuilt after a stall.
+    pub rebuilds: u64,
+    /// Rebuild attempts that failed (retried after another timeout).
+    pub failed_rebuilds: u64,
+    /// Silence before the most recent stall was detected, in milliseconds.
+    pub last_stall_ms: u64,
+}
+
+impl StreamWatch {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            last_beat_ms: AtomicU64::new(0),
+            stalls: AtomicU64::new(0),
+            rebuilds: AtomicU64::new(0),
+            failed_rebuilds: AtomicU64::new(0),
+            last_stall_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a callback. Lock-free, so it is safe to call from the audio thread.
+    pub fn beat(&self) {
+        self.last_beat_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    /// Start (or restart) the timeout, as if a callback had just arrived.
+    ///
+    /// Call when a stream is built so a slow first callback is not a stall.
+    pub fn arm(&self) {
+        self.beat();
+    }
+
+    /// If no callback arrived within `timeout`, record a stall and return
+    /// how long the stream has been silent. A zero `timeout` disables the check.
+    ///
+    /// The timeout is re-armed, so a stream that stays dead is reported (and
+    /// rebuilt) once per `timeout` rather than on every poll.
+    pub fn stalled(&self, timeout: Duration) -> Option<Duration> {
+        if timeout.is_zero() {
+            return None;
+        }
+        let now = now_ms();
+        let silent = now.saturating_sub(self.last_beat_ms.load(Ordering::Relaxed));
+        if silent < timeout.as_millis() as u64 {
+            return None;
+        }
+        self.last_beat_ms.store(now, Ordering::Relaxed);
+        self.stalls.fetch_add(1, Ordering::Relaxed);
+        self.last_stall_ms.store(silent, Ordering::Relaxed);
+        Some(Duration::from_millis(silent))
+    }
+
+    /// Count the outcome of a rebuild after a stall.
+    pub fn record_rebuild(&self, succeeded: bool) {
+        if succeeded {
+            self.rebuilds.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.failed_rebuilds.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The stream's name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Current metrics for this stream.
+    pub fn stats(&self) -> StreamStats {
+        StreamStats {
+            name: self.name,
+            stalls: self.stalls.load(Ordering::Relaxed),
+            rebuilds: self.rebuilds.load(Ordering::Relaxed),
+            failed_rebuilds: self.failed_rebuilds.load(Ordering::Relaxed),
+            last_stall_ms: self.last_stall_ms.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Microphone input stream.
+pub static CAPTURE: StreamWatch = StreamWatch::new("capture");
+/// Speaker output stream.
+pub static PLAYBACK: StreamWatch = StreamWatch::new("playback");
+
+/// All watched streams.
+pub static STREAMS: [&StreamWatch; 2] = [&CAPTURE, &PLAYBACK];
+
+/// Current metrics for every watched stream.
+pub fn snapshot() -> Vec<StreamStats> {
+    STREAMS.iter().map(|watch| watch.stats()).collect()
+}
+
+/// How often a stage checks its stream for a stall with `timeout`.
+pub fn poll_interval(timeout: Duration) -> Duration {
+    (timeout / 4).max(Duration::from_millis(100))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn silent_streams_stall_once_per_timeout() {
+        let watch = StreamWatch::new("test");
+        let timeout = Duration::from_millis(20);
+        watch.arm();
+        assert_eq!(watch.stalled(timeout), None);
+
+        std::thread::sleep(Duration::from_millis(30));
+        let silent = watch.stalled(timeout).expect("stream should be stalled");
+        assert!(silent >= timeout);
+        assert_eq!(watch.stalled(timeout), None, "timeout is re-armed");
+
+        watch.record_rebuild(true);
+        let stats = watch.stats();
+        assert_eq!(
+            (stats.stalls, stats.rebuilds, stats.failed_rebuilds),
+            (1, 1, 0)
+        );
+    }
+
+    #[test]
+    fn zero_timeout_disables_the_check() {
+        let watch = StreamWatch::new("test");
+        assert_eq!(watch.stalled(Duration::ZERO), None);
+        assert_eq!(watch.stats().stalls, 0);
+    }
+}
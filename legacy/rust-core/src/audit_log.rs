@@ -0,0 +1,59 @@
+//! Shared appender for the crate's JSONL audit logs and ledgers.
+//!
+//! Audit files can name tools, skills and permissions the user relied on,
+//! so they are created owner-only and every entry is synced before the
+//! caller moves on.
+
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+
+/// Append `entry` as one JSON line to the log at `path`, creating the file
+/// (mode `0600` on Unix) and its parent directories as needed.
+///
+/// # Errors
+///
+/// Returns an error if the entry cannot be serialized or the log cannot be
+/// opened, written or synced.
+pub(crate) fn append_audit_line<T: Serialize>(path: &Path, entry: &T) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    let mut line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn appends_lines_to_an_owner_only_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("audit.jsonl");
+
+        append_audit_line(&path, &serde_json::json!({"n": 1})).unwrap();
+        append_audit_line(&path, &serde_json::json!({"n": 2})).unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(raw, "{\"n\":1}\n{\"n\":2}\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
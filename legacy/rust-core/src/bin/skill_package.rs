@@ -25,13 +25,20 @@ fn run() -> fae::Result<()> {
             }
             install_package(Path::new(&args[2]))
         }
+        "test" => match args.len() {
+            3 => test_package(Path::new(&args[2]), false),
+            4 if args[3] == "--update" => test_package(Path::new(&args[2]), true),
+            _ => Err(fae::SpeechError::Config(
+                "test requires a package directory path and optional --update".to_owned(),
+            )),
+        },
         "list" => list_managed(),
         "help" | "--help" | "-h" => {
             print_usage();
             Ok(())
         }
         other => Err(fae::SpeechError::Config(format!(
-            "unknown subcommand `{other}` (use install|list)"
+            "unknown subcommand `{other}` (use install|test|list)"
         ))),
     }
 }
@@ -42,6 +49,44 @@ fn install_package(path: &Path) -> fae::Result<()> {
         "installed skill package: id={} name={} version={} state={:?}",
         info.id, info.name, info.version, info.state
     );
+    if let Some(reason) = &info.last_error {
+        println!("reason: {reason}");
+    }
+    Ok(())
+}
+
+fn test_package(path: &Path, update: bool) -> fae::Result<()> {
+    let outcomes = fae::skills::test_skill_package(path, update)?;
+    if outcomes.is_empty() {
+        println!(
+            "no skill tests found in {}",
+            path.join(fae::skills::test::SKILL_TESTS_DIR).display()
+        );
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for outcome in &outcomes {
+        if outcome.passed() {
+            println!("ok\t{}", outcome.name);
+        } else {
+            failed += 1;
+            println!("FAILED\t{}", outcome.name);
+            for failure in &outcome.failures {
+                println!("\t{failure}");
+            }
+        }
+    }
+    if update {
+        println!("updated {} golden transcript(s)", outcomes.len());
+    }
+
+    if failed > 0 {
+        return Err(fae::SpeechError::Config(format!(
+            "{failed} of {} skill test(s) failed",
+            outcomes.len()
+        )));
+    }
     Ok(())
 }
 
@@ -63,5 +108,5 @@ fn list_managed() -> fae::Result<()> {
 }
 
 fn print_usage() {
-    println!("usage: fae-skill-package <install <path>|list>");
+    println!("usage: fae-skill-package <install <path>|test <path> [--update]|list>");
 }
@@ -0,0 +1,57 @@
+//! Terminal front-end for running Fae on headless machines.
+//!
+//! Starts the same command handler and runtime event stream the native
+//! shells use, and drives them from a terminal UI, so Fae can be used over
+//! SSH. Build with `--features tui`.
+//!
+//! Tracing goes to `fae-tui.log` in the logs directory because the terminal
+//! itself is taken over by the UI.
+
+use fae::config::SpeechConfig;
+use fae::host::channel::command_channel_with_events;
+use fae::host::handler::FaeDeviceTransferHandler;
+use fae::ui::tui::run_tui;
+use tokio::sync::broadcast;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let log_dir = fae::fae_dirs::logs_dir();
+    std::fs::create_dir_all(&log_dir)?;
+    let (writer, _guard) =
+        tracing_appender::non_blocking(tracing_appender::rolling::never(&log_dir, "fae-tui.log"));
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    tracing::info!("fae-tui starting");
+
+    let (event_tx, _) = broadcast::channel(256);
+    let handle = tokio::runtime::Handle::current();
+    let handler =
+        match FaeDeviceTransferHandler::from_default_path(handle.clone(), event_tx.clone()) {
+            Ok(h) => h,
+            Err(e) => {
+                tracing::warn!("failed to load config, using defaults: {e}");
+                FaeDeviceTransferHandler::new(
+                    SpeechConfig::default(),
+                    SpeechConfig::default_config_path(),
+                    handle,
+                    event_tx.clone(),
+                )
+            }
+        };
+    let (client, server) = command_channel_with_events(32, event_tx, handler);
+    tokio::spawn(server.run());
+
+    run_tui(client)
+        .await
+        .map_err(|e| anyhow::anyhow!("fae-tui failed: {e}"))?;
+
+    tracing::info!("fae-tui shut down cleanly");
+    Ok(())
+}
@@ -137,6 +137,7 @@ impl CanvasBridge {
                 name,
                 success,
                 output_text,
+                ..
             } => {
                 let status = if *success { "success" } else { "failed" };
                 let text = format!("{name} \u{2192} {status}");
@@ -204,6 +205,10 @@ impl CanvasBridge {
             // Control events, audio levels, model selection, and voice command
             // events don't produce canvas messages (handled elsewhere).
             RuntimeEvent::Control(_)
+            | RuntimeEvent::AssistantTextDelta { .. }
+            | RuntimeEvent::AssistantTextDiscarded { .. }
+            | RuntimeEvent::AssistantThinking { .. }
+            | RuntimeEvent::ToolProgress { .. }
             | RuntimeEvent::AssistantAudioLevel { .. }
             | RuntimeEvent::AssistantViseme { .. }
             | RuntimeEvent::Transcription(_)
@@ -211,9 +216,21 @@ impl CanvasBridge {
             | RuntimeEvent::ModelSelected { .. }
             | RuntimeEvent::VoiceCommandDetected { .. }
             | RuntimeEvent::PermissionsChanged { .. }
+            | RuntimeEvent::DataForgetRequested
+            | RuntimeEvent::OfflineModeChanged { .. }
+            | RuntimeEvent::MeetingModeChanged { .. }
+            | RuntimeEvent::MeetingMinutesReady { .. }
+            | RuntimeEvent::ConversationHandedOff { .. }
+            | RuntimeEvent::CaptionSegment(_)
+            | RuntimeEvent::CaptionsEnded { .. }
+            | RuntimeEvent::ToolBudgetExhausted { .. }
+            | RuntimeEvent::ToolLimitExceeded { .. }
+            | RuntimeEvent::AnswerFlagged { .. }
+            | RuntimeEvent::PromptInjectionDetected { .. }
             | RuntimeEvent::ModelSwitchRequested { .. }
             | RuntimeEvent::ConversationCanvasVisibility { .. }
             | RuntimeEvent::ConversationVisibility { .. }
+            | RuntimeEvent::ConversationEnded { .. }
             | RuntimeEvent::ProviderFallback { .. }
             | RuntimeEvent::MicStatus { .. }
             | RuntimeEvent::IntelligenceExtraction { .. }
@@ -227,7 +244,9 @@ impl CanvasBridge {
             | RuntimeEvent::BackgroundTaskCompleted { .. }
             | RuntimeEvent::ApprovalResolved { .. }
             | RuntimeEvent::VoiceIdentityDecision { .. }
-            | RuntimeEvent::VoiceprintEnrollmentProgress { .. } => {}
+            | RuntimeEvent::VoiceprintEnrollmentProgress { .. }
+            | RuntimeEvent::Suspended
+            | RuntimeEvent::Resumed { .. } => {}
         }
     }
 
@@ -372,6 +391,7 @@ mod tests {
             audio_duration_secs: None,
             audio_captured_at: Instant::now(),
             transcribed_at: Instant::now(),
+            speculation: None,
         })
     }
 
@@ -435,6 +455,7 @@ mod tests {
             name: "search".into(),
             success: true,
             output_text: None,
+            structured: None,
         });
         assert_eq!(b.session().message_count(), 2);
 
@@ -451,6 +472,7 @@ mod tests {
             name: "fetch".into(),
             success: false,
             output_text: None,
+            structured: None,
         });
         let html = b.session().to_html();
         assert!(html.contains("fetch \u{2192} failed"));
@@ -539,6 +561,7 @@ mod tests {
             name: "weather".into(),
             success: true,
             output_text: None,
+            structured: None,
         });
 
         // Assistant responds
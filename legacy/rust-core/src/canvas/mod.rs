@@ -27,17 +27,37 @@
 //! bytes; a chart element is ~500–2000 bytes depending on data size.
 //! Full-scene snapshots for reconnection are proportional to the total
 //! element count.
+//!
+//! Everything here needs the `canvas` feature. Without it only an empty
+//! [`registry::CanvasSessionRegistry`] remains, so the pipeline and agent
+//! can keep their optional registry handles.
 
+#[cfg(feature = "canvas")]
 pub mod backend;
+#[cfg(feature = "canvas")]
 pub mod bridge;
+#[cfg(feature = "canvas")]
 pub mod registry;
+#[cfg(feature = "canvas")]
 pub mod remote;
+#[cfg(feature = "canvas")]
 pub mod render;
+#[cfg(feature = "canvas")]
 pub mod session;
+#[cfg(feature = "canvas")]
 pub mod tools;
+#[cfg(feature = "canvas")]
 pub mod types;
 
-#[cfg(test)]
+/// Stand-in registry for builds without the `canvas` feature.
+#[cfg(not(feature = "canvas"))]
+pub mod registry {
+    /// Registry that never holds a session.
+    #[derive(Debug, Default)]
+    pub struct CanvasSessionRegistry;
+}
+
+#[cfg(all(test, feature = "canvas"))]
 mod perf_tests {
     use super::session::CanvasSession;
     use super::types::{CanvasMessage, MessageRole};
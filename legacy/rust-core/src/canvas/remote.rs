@@ -626,6 +626,7 @@ async fn try_connect(
     use futures_util::{SinkExt, StreamExt};
     use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+    crate::offline::ensure_url_allowed("remote canvas", url).map_err(|e| e.to_string())?;
     let (ws_stream, _) = connect_async(url)
         .await
         .map_err(|e| format!("connect: {e}"))?;
@@ -45,6 +45,8 @@ impl CanvasExportTool {
         })?;
 
         let url = format!("{base_url}/api/export");
+        crate::offline::ensure_url_allowed("remote canvas export", &url)
+            .map_err(|e| e.to_string())?;
         let body = serde_json::json!({
             "session_id": params.session_id,
             "format": format_string(params.format),
@@ -0,0 +1,260 @@
+//! Live captions: time-aligned text for assistant speech.
+//!
+//! When captions are on, the playback stage emits a caption segment for each
+//! line of every sentence it queues, timed against the audio actually queued
+//! for playback, so a host UI can show what Fae is saying as she says it. In
+//! [`CaptionsMode::CaptionsOnly`] no speech is synthesized at all; segments
+//! are paced at a comfortable reading speed instead.
+//!
+//! Segment timestamps are milliseconds from the start of the utterance (one
+//! assistant reply), which is when its first segment is emitted. An
+//! utterance ends with a `captions_ended` event; when playback was cut off
+//! (barge-in) the host should remove any remaining lines immediately.
+//!
+//! Like accessibility mode, the switch is process-wide: it is set from
+//! `SpeechConfig::captions` at startup and can be changed at runtime through
+//! `config.patch` or the FFI.
+
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+pub use crate::config::{CaptionsConfig, CaptionsMode};
+
+/// Reading speed used to pace captions without audio, in characters per second.
+const READING_CHARS_PER_SEC: u64 = 15;
+
+/// Shortest time a caption stays up when paced by reading speed.
+const MIN_READING_DURATION: Duration = Duration::from_millis(1200);
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+static MAX_LINE_CHARS: AtomicUsize = AtomicUsize::new(42);
+
+/// Apply a caption configuration process-wide.
+pub fn apply(config: &CaptionsConfig) {
+    let mode = match config.mode {
+        CaptionsMode::Off => 0,
+        CaptionsMode::WithSpeech => 1,
+        CaptionsMode::CaptionsOnly => 2,
+    };
+    MAX_LINE_CHARS.store(config.max_line_chars.max(1), Ordering::SeqCst);
+    let previous = MODE.swap(mode, Ordering::SeqCst);
+    if previous != mode {
+        tracing::info!(mode = ?config.mode, "captions mode changed");
+    }
+}
+
+/// The active caption mode.
+pub fn mode() -> CaptionsMode {
+    match MODE.load(Ordering::SeqCst) {
+        0 => CaptionsMode::Off,
+        1 => CaptionsMode::WithSpeech,
+        _ => CaptionsMode::CaptionsOnly,
+    }
+}
+
+/// Whether caption events are emitted.
+pub fn enabled() -> bool {
+    mode() != CaptionsMode::Off
+}
+
+/// Whether speech synthesis is disabled in favour of captions.
+pub fn captions_only() -> bool {
+    mode() == CaptionsMode::CaptionsOnly
+}
+
+/// The configured longest caption line, in characters.
+pub fn max_line_chars() -> usize {
+    MAX_LINE_CHARS.load(Ordering::SeqCst)
+}
+
+/// One caption line, timed against the utterance it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CaptionSegment {
+    /// Identifies the utterance (assistant reply) this line belongs to.
+    pub utterance_id: u64,
+    /// Position of the line within the utterance, from 0.
+    pub index: u32,
+    /// Caption text.
+    pub text: String,
+    /// When the line starts being spoken, in ms from the utterance start.
+    pub start_ms: u64,
+    /// When the line has finished being spoken, in ms from the utterance start.
+    pub end_ms: u64,
+}
+
+/// Wrap `text` into lines of at most `max_chars` characters at word
+/// boundaries. A single word longer than `max_chars` gets a line of its own.
+pub fn split_lines(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_chars = 0;
+    for word in text.split_whitespace() {
+        let word_chars = word.chars().count();
+        if line_chars > 0 && line_chars + 1 + word_chars > max_chars {
+            lines.push(std::mem::take(&mut line));
+            line_chars = 0;
+        }
+        if line_chars > 0 {
+            line.push(' ');
+            line_chars += 1;
+        }
+        line.push_str(word);
+        line_chars += word_chars;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// How long `text` should stay on screen when there is no audio to time it.
+pub fn reading_duration(text: &str) -> Duration {
+    let chars = text.chars().count() as u64;
+    Duration::from_millis(chars * 1000 / READING_CHARS_PER_SEC).max(MIN_READING_DURATION)
+}
+
+#[derive(Debug)]
+struct Utterance {
+    id: u64,
+    started: Instant,
+    /// When everything queued so far will have finished playing.
+    queue_end: Instant,
+    next_index: u32,
+}
+
+/// Assigns caption timings as sentences are queued for playback.
+#[derive(Debug, Default)]
+pub struct CaptionTimeline {
+    next_utterance_id: u64,
+    current: Option<Utterance>,
+}
+
+impl CaptionTimeline {
+    /// Create a timeline with no active utterance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caption `text`, queued at `now` and taking `duration` to play once
+    /// everything queued before it has played.
+    ///
+    /// Starts a new utterance if none is active. The duration is shared
+    /// between the lines in proportion to their length.
+    pub fn schedule(
+        &mut self,
+        text: &str,
+        duration: Duration,
+        now: Instant,
+        max_line_chars: usize,
+    ) -> Vec<CaptionSegment> {
+        let lines = split_lines(text, max_line_chars);
+        if lines.is_empty() {
+            return Vec::new();
+        }
+        let utterance = self.current.get_or_insert_with(|| {
+            let id = self.next_utterance_id;
+            self.next_utterance_id += 1;
+            Utterance {
+                id,
+                started: now,
+                queue_end: now,
+                next_index: 0,
+            }
+        });
+
+        let start = utterance.queue_end.max(now);
+        utterance.queue_end = start + duration;
+        let base_ms = start.duration_since(utterance.started).as_millis() as u64;
+        let duration_ms = duration.as_millis() as u64;
+        let total_chars: u64 = lines.iter().map(|l| l.chars().count() as u64).sum();
+
+        let mut elapsed_chars = 0;
+        let mut segments = Vec::with_capacity(lines.len());
+        for text in lines {
+            let start_ms = base_ms + duration_ms * elapsed_chars / total_chars;
+            elapsed_chars += text.chars().count() as u64;
+            let end_ms = base_ms + duration_ms * elapsed_chars / total_chars;
+            segments.push(CaptionSegment {
+                utterance_id: utterance.id,
+                index: utterance.next_index,
+                text,
+                start_ms,
+                end_ms,
+            });
+            utterance.next_index += 1;
+        }
+        segments
+    }
+
+    /// End the active utterance, returning its id. The next scheduled
+    /// sentence starts a new utterance.
+    pub fn finish(&mut self) -> Option<u64> {
+        self.current.take().map(|u| u.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_wrap_at_word_boundaries() {
+        assert_eq!(
+            split_lines("The quick brown fox jumps over the lazy dog", 15),
+            vec!["The quick brown", "fox jumps over", "the lazy dog"]
+        );
+        assert_eq!(split_lines("  ", 15), Vec::<String>::new());
+        assert_eq!(
+            split_lines("a supercalifragilistic b", 5),
+            vec!["a", "supercalifragilistic", "b"]
+        );
+    }
+
+    #[test]
+    fn segments_follow_the_playback_queue() {
+        let mut timeline = CaptionTimeline::new();
+        let t0 = Instant::now();
+
+        let first = timeline.schedule("Hello there.", Duration::from_millis(1000), t0, 42);
+        assert_eq!(first.len(), 1);
+        assert_eq!((first[0].start_ms, first[0].end_ms), (0, 1000));
+
+        // Queued while the first sentence is still playing: starts after it.
+        let second = timeline.schedule(
+            "aaaa bbbb",
+            Duration::from_millis(900),
+            t0 + Duration::from_millis(200),
+            4,
+        );
+        assert_eq!(second.len(), 2);
+        assert_eq!((second[0].start_ms, second[0].end_ms), (1000, 1450));
+        assert_eq!((second[1].start_ms, second[1].end_ms), (1450, 1900));
+        assert_eq!(second[1].index, 2);
+        assert!(
+            second
+                .iter()
+                .all(|s| s.utterance_id == first[0].utterance_id)
+        );
+
+        // Queue ran dry: the next sentence starts when it is queued.
+        let third = timeline.schedule(
+            "Late.",
+            Duration::from_millis(500),
+            t0 + Duration::from_millis(3000),
+            42,
+        );
+        assert_eq!((third[0].start_ms, third[0].end_ms), (3000, 3500));
+
+        let id = timeline.finish();
+        assert_eq!(id, Some(first[0].utterance_id));
+        let next = timeline.schedule("Again.", Duration::from_millis(500), t0, 42);
+        assert_ne!(next[0].utterance_id, first[0].utterance_id);
+        assert_eq!((next[0].index, next[0].start_ms), (0, 0));
+    }
+
+    #[test]
+    fn reading_pace_has_a_floor() {
+        assert_eq!(reading_duration("Hi."), MIN_READING_DURATION);
+        assert_eq!(reading_duration(&"x".repeat(150)), Duration::from_secs(10));
+    }
+}
@@ -66,9 +66,11 @@ impl ChannelBrain {
                 .await?
         };
 
-        let mut response = collect_task.await.map_err(|e| {
+        let response = collect_task.await.map_err(|e| {
             SpeechError::Llm(format!("channel response collector task failed: {e}"))
         })?;
+        // Delivery tags only mean something to TTS.
+        let (mut response, _) = crate::tts::style::take_style_tags(&response);
 
         if interrupted && response.trim().is_empty() {
             response = "Request interrupted before completion.".to_owned();
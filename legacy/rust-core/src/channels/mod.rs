@@ -2,24 +2,40 @@
 //!
 //! Design goal: channel-specific adapters are pluggable. The manager owns
 //! routing, model invocation, and cross-channel policy checks.
+//!
+//! The runtime (adapters, brain, webhook gateway) is compiled only with the
+//! `channels` feature; configuration validation and history are always
+//! available.
 
+#[cfg(feature = "channels")]
 mod brain;
+#[cfg(feature = "channels")]
 mod gateway;
 pub mod history;
 pub mod rate_limit;
 pub mod skill_adapter;
 pub mod traits;
 
+#[cfg(feature = "channels")]
 use crate::channels::brain::ChannelBrain;
+#[cfg(feature = "channels")]
 use crate::channels::gateway::run_gateway;
-use crate::channels::history::{ChannelHistory, ChannelMessage, MessageDirection};
+use crate::channels::history::ChannelMessage;
+#[cfg(feature = "channels")]
+use crate::channels::history::{ChannelHistory, MessageDirection};
+#[cfg(feature = "channels")]
 use crate::channels::rate_limit::ChannelRateLimiters;
 use crate::channels::skill_adapter::ChannelSkillAdapter;
-use crate::channels::traits::{ChannelAdapter, ChannelInboundMessage, ChannelOutboundMessage};
+use crate::channels::traits::ChannelAdapter;
+#[cfg(feature = "channels")]
+use crate::channels::traits::{ChannelInboundMessage, ChannelOutboundMessage};
 use crate::config::SpeechConfig;
 use crate::skills::channel_templates::ChannelType;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+#[cfg(feature = "channels")]
+use std::sync::Mutex;
+#[cfg(feature = "channels")]
 use tokio::task::JoinSet;
 
 /// Runtime event emitted by channel manager.
@@ -76,7 +92,9 @@ impl ChannelRuntimeHandle {
 
 /// Launch channel runtime if channels are enabled and auto-start is on.
 ///
-/// Returns `None` when channels are disabled or auto-start is disabled.
+/// Returns `None` when channels are disabled, auto-start is disabled, or
+/// offline mode is on.
+#[cfg(feature = "channels")]
 pub fn start_runtime(
     config: SpeechConfig,
 ) -> Option<(
@@ -86,6 +104,10 @@ pub fn start_runtime(
     if !config.channels.enabled || !config.channels.auto_start {
         return None;
     }
+    if crate::offline::is_offline() {
+        tracing::info!("offline mode: channels runtime not started");
+        return None;
+    }
 
     let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
     let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
@@ -317,6 +339,7 @@ pub fn register_skill_channel(channel_type: ChannelType) -> (String, Arc<dyn Cha
     (id, Arc::new(adapter))
 }
 
+#[cfg(feature = "channels")]
 async fn run_runtime(
     config: SpeechConfig,
     event_tx: tokio::sync::mpsc::UnboundedSender<ChannelRuntimeEvent>,
@@ -416,7 +439,32 @@ async fn run_runtime(
         });
     }
 
-    while let Some(message) = inbound_rx.recv().await {
+    // Approval prompts forwarded from the desktop go out through the same
+    // adapters; replies to them are resolved before reaching the brain.
+    let (approval_tx, mut approval_rx) = tokio::sync::mpsc::unbounded_channel();
+    crate::approval::remote_approvals().set_outbox(Some(approval_tx));
+
+    loop {
+        let message = tokio::select! {
+            Some(prompt) = approval_rx.recv() => {
+                deliver_reply(
+                    &adapters,
+                    &rate_limiters,
+                    &history,
+                    &event_tx,
+                    &prompt.channel,
+                    prompt.reply_target,
+                    prompt.text,
+                )
+                .await?;
+                continue;
+            }
+            message = inbound_rx.recv() => match message {
+                Some(message) => message,
+                None => break,
+            },
+        };
+
         let _ = event_tx.send(ChannelRuntimeEvent::Inbound {
             channel: message.channel.clone(),
             sender: message.sender.clone(),
@@ -439,84 +487,122 @@ async fn run_runtime(
             });
         }
 
-        let prompt = format!(
-            "[channel:{}]\n[sender:{}]\n{}",
-            message.channel, message.sender, message.text
+        let approval_reply = crate::approval::remote_approvals().handle_reply(
+            &message.channel,
+            &message.sender,
+            &message.reply_target,
+            &message.text,
+            std::time::Instant::now(),
         );
-        let response = match brain.respond(prompt).await {
-            Ok(text) => text,
-            Err(err) => {
-                let error = format!("failed to generate channel response: {err}");
-                let _ = event_tx.send(ChannelRuntimeEvent::Error(error.clone()));
-                tracing::error!("{error}");
-                "I hit an internal error while processing that message.".to_owned()
+        let response = match approval_reply {
+            Some(reply) => reply,
+            None => {
+                let prompt = format!(
+                    "[channel:{}]\n[sender:{}]\n{}",
+                    message.channel, message.sender, message.text
+                );
+                match brain.respond(prompt).await {
+                    Ok(text) => text,
+                    Err(err) => {
+                        let error = format!("failed to generate channel response: {err}");
+                        let _ = event_tx.send(ChannelRuntimeEvent::Error(error.clone()));
+                        tracing::error!("{error}");
+                        crate::i18n::text("conversation.channel_error").to_owned()
+                    }
+                }
             }
         };
 
-        if let Some(adapter) = adapters.get(&message.channel) {
-            // Check rate limit before sending
-            let rate_limit_check = {
-                let mut limiters = rate_limiters
-                    .lock()
-                    .map_err(|_| anyhow::anyhow!("rate limiter lock poisoned"))?;
-                limiters.try_send(&message.channel)
-            };
+        deliver_reply(
+            &adapters,
+            &rate_limiters,
+            &history,
+            &event_tx,
+            &message.channel,
+            message.reply_target,
+            response,
+        )
+        .await?;
+    }
 
-            match rate_limit_check {
-                Ok(()) => {
-                    let send_result = adapter
-                        .send(ChannelOutboundMessage {
-                            reply_target: message.reply_target.clone(),
-                            text: response.clone(),
-                        })
-                        .await;
-                    match send_result {
-                        Ok(()) => {
-                            let _ = event_tx.send(ChannelRuntimeEvent::Outbound {
-                                channel: message.channel.clone(),
-                                reply_target: message.reply_target.clone(),
-                            });
-
-                            // Record outbound message
-                            let outbound_msg = ChannelMessage {
-                                id: String::new(),
-                                channel: message.channel.clone(),
-                                direction: MessageDirection::Outbound,
-                                sender: "fae".to_owned(),
-                                text: response,
-                                timestamp: chrono::Utc::now(),
-                                reply_target: message.reply_target,
-                            };
-                            if let Ok(mut hist) = history.lock() {
-                                hist.push(outbound_msg.clone());
-                                let _ = event_tx.send(ChannelRuntimeEvent::MessageRecorded {
-                                    message: outbound_msg,
-                                });
-                            }
-                        }
-                        Err(err) => {
-                            let warning =
-                                format!("failed to send {} response: {err}", adapter.id());
-                            let _ = event_tx.send(ChannelRuntimeEvent::Warning(warning.clone()));
-                            tracing::warn!("{warning}");
-                        }
-                    }
-                }
-                Err(err) => {
-                    let warning = format!("rate limit exceeded for {}: {}", message.channel, err);
-                    let _ = event_tx.send(ChannelRuntimeEvent::Warning(warning.clone()));
-                    tracing::warn!("{warning}");
-                }
+    crate::approval::remote_approvals().set_outbox(None);
+    workers.abort_all();
+    while workers.join_next().await.is_some() {}
+    Ok(())
+}
+
+/// Send `text` to `reply_target` on `channel`, subject to the outbound
+/// rate limit, and record it in the channel history.
+///
+/// Delivery problems are reported as warnings; only a poisoned rate limiter
+/// is an error.
+#[cfg(feature = "channels")]
+async fn deliver_reply(
+    adapters: &HashMap<String, Arc<dyn ChannelAdapter>>,
+    rate_limiters: &Mutex<ChannelRateLimiters>,
+    history: &Mutex<ChannelHistory>,
+    event_tx: &tokio::sync::mpsc::UnboundedSender<ChannelRuntimeEvent>,
+    channel: &str,
+    reply_target: String,
+    text: String,
+) -> anyhow::Result<()> {
+    let Some(adapter) = adapters.get(channel) else {
+        let warning = format!("no adapter found for channel `{channel}`");
+        let _ = event_tx.send(ChannelRuntimeEvent::Warning(warning.clone()));
+        tracing::warn!("{warning}");
+        return Ok(());
+    };
+
+    // Check rate limit before sending
+    let rate_limit_check = {
+        let mut limiters = rate_limiters
+            .lock()
+            .map_err(|_| anyhow::anyhow!("rate limiter lock poisoned"))?;
+        limiters.try_send(channel)
+    };
+    if let Err(err) = rate_limit_check {
+        let warning = format!("rate limit exceeded for {channel}: {err}");
+        let _ = event_tx.send(ChannelRuntimeEvent::Warning(warning.clone()));
+        tracing::warn!("{warning}");
+        return Ok(());
+    }
+
+    let send_result = adapter
+        .send(ChannelOutboundMessage {
+            reply_target: reply_target.clone(),
+            text: text.clone(),
+        })
+        .await;
+    match send_result {
+        Ok(()) => {
+            let _ = event_tx.send(ChannelRuntimeEvent::Outbound {
+                channel: channel.to_owned(),
+                reply_target: reply_target.clone(),
+            });
+
+            // Record outbound message
+            let outbound_msg = ChannelMessage {
+                id: String::new(),
+                channel: channel.to_owned(),
+                direction: MessageDirection::Outbound,
+                sender: "fae".to_owned(),
+                text,
+                timestamp: chrono::Utc::now(),
+                reply_target,
+            };
+            if let Ok(mut hist) = history.lock() {
+                hist.push(outbound_msg.clone());
+                let _ = event_tx.send(ChannelRuntimeEvent::MessageRecorded {
+                    message: outbound_msg,
+                });
             }
-        } else {
-            let warning = format!("no adapter found for channel `{}`", message.channel);
+        }
+        Err(err) => {
+            let warning = format!("failed to send {} response: {err}", adapter.id());
             let _ = event_tx.send(ChannelRuntimeEvent::Warning(warning.clone()));
             tracing::warn!("{warning}");
         }
     }
-
-    workers.abort_all();
-    while workers.join_next().await.is_some() {}
     Ok(())
 }
 
@@ -543,6 +629,7 @@ mod tests {
                 whatsapp: None,
                 rate_limits: Default::default(),
                 extensions: Vec::new(),
+                remote_approval: Default::default(),
             },
             ..Default::default()
         };
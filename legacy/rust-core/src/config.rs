@@ -1,8 +1,14 @@
 //! Configuration types for the speech-to-speech pipeline.
 
 use crate::credentials::CredentialRef;
+use crate::fae_llm::agent::ThinkingBudgets;
+use crate::fae_llm::config::LoraAdapterConfig;
+use crate::fae_llm::providers::PiiMaskingLevel;
+use crate::fae_llm::tools::NetworkPolicy;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Top-level configuration for the speech pipeline.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -12,6 +18,8 @@ pub struct SpeechConfig {
     pub audio: AudioConfig,
     /// Acoustic echo cancellation settings.
     pub aec: AecConfig,
+    /// Automatic gain control settings.
+    pub agc: AgcConfig,
     /// Voice activity detection settings.
     pub vad: VadConfig,
     /// Speech-to-text settings.
@@ -24,6 +32,8 @@ pub struct SpeechConfig {
     pub models: ModelConfig,
     /// Memory settings (persistent user identity + known people).
     pub memory: MemoryConfig,
+    /// Privacy settings (encryption at rest and data retention).
+    pub privacy: PrivacyConfig,
     /// Proactive intelligence settings.
     pub intelligence: IntelligenceConfig,
     /// Conversation gate settings (sleep phrases / always-on mode).
@@ -32,14 +42,36 @@ pub struct SpeechConfig {
     pub voice_identity: VoiceIdentityConfig,
     /// Barge-in (interrupt) behavior while the assistant is generating/speaking.
     pub barge_in: BargeInConfig,
+    /// Length limits for spoken replies and how they shorten after interruptions.
+    pub voice_response: VoiceResponseConfig,
     /// Wake word detection (MFCC+DTW keyword spotter).
     pub wakeword: WakewordConfig,
+    /// Hotword (name mention) sensitivity per environment.
+    pub hotword: HotwordConfig,
     /// Canvas visual output settings.
     pub canvas: CanvasConfig,
     /// External communication channel settings (Discord, WhatsApp, webhooks).
     pub channels: ChannelsConfig,
     /// UI theme settings (light/dark/auto).
     pub theme: ThemeConfig,
+    /// Screen-reader output and spoken interaction hints.
+    pub accessibility: AccessibilityConfig,
+    /// Live captions of assistant speech.
+    pub captions: CaptionsConfig,
+    /// Battery-aware performance profile switching.
+    pub power: PowerConfig,
+    /// Profanity masking and the content policy for spoken replies.
+    pub content_filter: ContentFilterConfig,
+    /// Daily journal of conversation summaries.
+    pub journal: JournalConfig,
+    /// Meeting transcription and minutes.
+    pub meeting: MeetingConfig,
+    /// Live transcript streaming for external captioning tools.
+    pub transcript_stream: TranscriptStreamConfig,
+    /// Date, time, locale and other context injected into every turn.
+    pub turn_context: TurnContextConfig,
+    /// End-to-end encrypted sync of settings and memory between devices.
+    pub sync: SyncConfig,
     /// System permission grants (microphone, contacts, calendar, etc.).
     #[serde(default)]
     pub permissions: crate::permissions::PermissionStore,
@@ -87,6 +119,18 @@ pub struct SpeechConfig {
     /// Python skill subprocess runtime settings.
     #[serde(default)]
     pub python_skills: PythonSkillsConfig,
+    /// Block all network egress: no web tools, channels, update checks, or
+    /// model downloads, and only local STT/LLM/TTS. See [`crate::offline`].
+    #[serde(default)]
+    pub offline_mode: bool,
+    /// Language for spoken system messages (e.g. `"de"`). Unset, or a
+    /// language without a catalog, uses English. See [`crate::i18n`].
+    #[serde(default)]
+    pub language: Option<String>,
+    /// A/B experiment on the system prompt or voice model, if one is
+    /// running. See [`crate::experiments`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<crate::experiments::PromptExperiment>,
 }
 
 /// A persisted security-scoped bookmark for App Sandbox file access.
@@ -121,6 +165,18 @@ pub struct AudioConfig {
     pub input_device: Option<String>,
     /// Output device name (None = system default).
     pub output_device: Option<String>,
+    /// Tell the user when the output drops to a low-quality link, such as a
+    /// Bluetooth headset switching to its hands-free profile.
+    pub warn_low_quality: bool,
+    /// Rebuild an audio stream when its callbacks stop for this long, in
+    /// milliseconds (0 = never). Catches streams left dead by sleep/wake.
+    pub stall_timeout_ms: u32,
+    /// Calibration profiles from onboarding, keyed by input device name
+    /// (`"default"` for the system default device).
+    ///
+    /// The profile for the active input device overrides the VAD threshold,
+    /// AGC gain and AEC switch when the pipeline starts.
+    pub calibrations: BTreeMap<String, CalibrationProfile>,
 }
 
 impl Default for AudioConfig {
@@ -132,6 +188,64 @@ impl Default for AudioConfig {
             buffer_size: 512,
             input_device: None,
             output_device: None,
+            warn_low_quality: true,
+            stall_timeout_ms: 2_000,
+            calibrations: BTreeMap::new(),
+        }
+    }
+}
+
+/// Microphone/speaker calibration measured during onboarding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationProfile {
+    /// Output device the echo test played through (None = system default).
+    pub output_device: Option<String>,
+    /// Ambient noise level (RMS) before gain.
+    pub noise_floor_rms: f32,
+    /// Level (RMS) of the user reading the calibration sentence, before gain.
+    pub speech_rms: f32,
+    /// Speech-to-noise ratio in dB.
+    pub snr_db: f32,
+    /// VAD start-of-speech threshold, applied after gain.
+    pub vad_threshold: f32,
+    /// Level the AGC steers speech towards.
+    pub agc_target_rms: f32,
+    /// Starting AGC gain that brings the user's speech to the target.
+    pub input_gain: f32,
+    /// How far the echo-test tone stood above ambient noise at the
+    /// microphone, in dB.
+    pub echo_return_db: f32,
+    /// Whether speaker output reaches the microphone and needs AEC.
+    pub aec_required: bool,
+    /// Unix timestamp (seconds) of the calibration.
+    pub calibrated_at: u64,
+}
+
+/// Automatic gain control configuration.
+///
+/// Scales microphone audio so speech arrives at a consistent level
+/// regardless of mic sensitivity and distance. Onboarding calibration
+/// enables it and seeds the gain for the calibrated device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgcConfig {
+    /// Whether gain control is applied to captured audio.
+    pub enabled: bool,
+    /// RMS level speech is steered towards.
+    pub target_rms: f32,
+    /// Gain applied before the controller has adapted.
+    pub initial_gain: f32,
+    /// Upper bound on the applied gain.
+    pub max_gain: f32,
+}
+
+impl Default for AgcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_rms: 0.05,
+            initial_gain: 1.0,
+            max_gain: 10.0,
         }
     }
 }
@@ -186,6 +300,14 @@ pub struct VadConfig {
     pub hysteresis_ratio: f32,
     /// Minimum silence duration in ms to end a speech segment.
     pub min_silence_duration_ms: u32,
+    /// Silence in ms after which a segment is provisionally ended.
+    ///
+    /// When set below `min_silence_duration_ms`, endpointing is two-stage:
+    /// the utterance is transcribed and answered speculatively after this
+    /// shorter pause, and the reply is only spoken once the full silence
+    /// confirms the user has finished. Speech resuming in between discards
+    /// the speculative turn. Needs the conversation gate. 0 disables.
+    pub provisional_silence_ms: u32,
     /// Padding added around detected speech in ms.
     pub speech_pad_ms: u32,
     /// Minimum speech duration in ms to consider valid.
@@ -211,6 +333,7 @@ impl Default for VadConfig {
             //   (typically 200-800ms) while keeping response latency
             //   around 1s from when the user stops speaking.
             min_silence_duration_ms: 1000,
+            provisional_silence_ms: 0,
             speech_pad_ms: 30,
             min_speech_duration_ms: 250,
             // 15s cap prevents runaway accumulation from ambient noise
@@ -229,6 +352,11 @@ pub struct SttConfig {
     pub model_id: String,
     /// Chunk size in samples for streaming transcription.
     pub chunk_size: usize,
+    /// Rewrite sound-alike spans toward known names and terms (see
+    /// [`crate::stt::bias`]).
+    pub contextual_biasing: bool,
+    /// Extra words and phrases to recognise, such as product names.
+    pub vocabulary: Vec<String>,
 }
 
 impl Default for SttConfig {
@@ -237,6 +365,8 @@ impl Default for SttConfig {
             // The ONNX-converted repo — the original NVIDIA repo only has .nemo format.
             model_id: "istupakov/parakeet-tdt-0.6b-v3-onnx".to_owned(),
             chunk_size: 2560, // 160ms at 16kHz
+            contextual_biasing: true,
+            vocabulary: Vec::new(),
         }
     }
 }
@@ -350,6 +480,11 @@ pub struct RuntimeConfig {
     /// Optional explicit path to a kernel signature manifest.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kernel_signature_manifest: Option<PathBuf>,
+    /// Load models one at a time and record the resident memory each adds.
+    ///
+    /// Slower startup, but `runtime.footprint` can then attribute RSS to each
+    /// model. Also disables lazy LLM loading.
+    pub memory_audit: bool,
 }
 
 /// Saved LLM settings captured before rescue mode overrides are applied.
@@ -381,6 +516,7 @@ impl Default for RuntimeConfig {
             rescue_saved_llm: None,
             kernel_signature_mode: KernelSignatureMode::Off,
             kernel_signature_manifest: None,
+            memory_audit: false,
         }
     }
 }
@@ -409,6 +545,149 @@ pub enum LlmMessageQueueDropPolicy {
     None,
 }
 
+/// How long a tool approval waits for an answer before it is denied,
+/// per class of tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApprovalTimeoutConfig {
+    /// Shell commands and Python skills (`bash`, `python_skill`).
+    pub shell_secs: u64,
+    /// File changes (`write`, `edit`, `undo`).
+    pub files_secs: u64,
+    /// Desktop automation and macro replay.
+    pub desktop_secs: u64,
+    /// Scheduled task and todo changes.
+    pub scheduler_secs: u64,
+    /// Requests to reach a new network domain.
+    pub network_secs: u64,
+    /// Any other approval-gated tool.
+    pub default_secs: u64,
+}
+
+impl Default for ApprovalTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            shell_secs: 60,
+            files_secs: 60,
+            desktop_secs: 60,
+            scheduler_secs: 60,
+            network_secs: 60,
+            default_secs: 60,
+        }
+    }
+}
+
+impl ApprovalTimeoutConfig {
+    /// Approval timeout for the tool named `tool_name`.
+    pub fn for_tool(&self, tool_name: &str) -> Duration {
+        let secs = match tool_name {
+            "bash" | "python_skill" => self.shell_secs,
+            "write" | "edit" | "undo" => self.files_secs,
+            "desktop" => self.desktop_secs,
+            "create_scheduled_task"
+            | "update_scheduled_task"
+            | "delete_scheduled_task"
+            | "trigger_scheduled_task"
+            | "update_todo" => self.scheduler_secs,
+            "network_access" => self.network_secs,
+            _ => self.default_secs,
+        };
+        Duration::from_secs(secs)
+    }
+}
+
+/// Spoken read-back before destructive tool calls, per kind of action.
+///
+/// A covered call always asks for confirmation, whatever the tool mode, and
+/// the voice prompt reads back what is about to happen (see
+/// [`crate::approval::DestructiveAction`]). Turning a kind off leaves it to
+/// the usual approval rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadBackConfig {
+    /// Sending email.
+    pub send_mail: bool,
+    /// Deleting calendar events.
+    pub delete_events: bool,
+    /// Overwriting existing files.
+    pub overwrite_files: bool,
+}
+
+impl Default for ReadBackConfig {
+    fn default() -> Self {
+        Self {
+            send_mail: true,
+            delete_events: true,
+            overwrite_files: true,
+        }
+    }
+}
+
+impl ReadBackConfig {
+    /// Whether `action` gets a read-back.
+    pub fn covers(&self, action: crate::approval::DestructiveAction) -> bool {
+        use crate::approval::DestructiveAction;
+
+        match action {
+            DestructiveAction::SendMail => self.send_mail,
+            DestructiveAction::DeleteEvent => self.delete_events,
+            DestructiveAction::OverwriteFile => self.overwrite_files,
+        }
+    }
+}
+
+/// Size limits for images sent to the model and stored with sessions.
+///
+/// See [`crate::llm::images`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageBudgetConfig {
+    /// Pixels per image (width × height) before downscaling; 0 = no limit.
+    pub max_pixels: u32,
+    /// Encoded bytes per image before recompression; 0 = no limit.
+    pub max_bytes: usize,
+    /// JPEG quality (40–100) tried first.
+    pub jpeg_quality: u8,
+    /// Images kept per turn; extras are dropped.
+    pub max_per_turn: usize,
+}
+
+impl Default for ImageBudgetConfig {
+    fn default() -> Self {
+        Self {
+            max_pixels: 1024 * 1024,
+            max_bytes: 512 * 1024,
+            jpeg_quality: 85,
+            max_per_turn: 4,
+        }
+    }
+}
+
+/// Thinking budgets and what happens to the model's thinking.
+///
+/// See [`crate::fae_llm::agent::thinking`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThinkingConfig {
+    /// Keep thinking transcripts in saved sessions. They are never spoken.
+    pub store: bool,
+    /// Send thinking transcripts to the host app so it can show them.
+    pub show_in_ui: bool,
+    /// Overrides of the built-in thinking token budgets, per model tier.
+    #[serde(skip_serializing_if = "ThinkingBudgets::is_empty")]
+    pub budgets: ThinkingBudgets,
+}
+
+impl Default for ThinkingConfig {
+    fn default() -> Self {
+        Self {
+            store: true,
+            show_in_ui: false,
+            budgets: ThinkingBudgets::default(),
+        }
+    }
+}
+
 /// Language model configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -472,6 +751,42 @@ pub struct LlmConfig {
     /// Whether explicit stop/sleep actions clear queued user inputs.
     #[serde(default = "default_llm_clear_queue_on_stop")]
     pub clear_queue_on_stop: bool,
+    /// Number of recent prompts mistralrs keeps in its prefix cache so later
+    /// turns reuse the KV cache of the shared conversation prefix.
+    ///
+    /// Set to 0 to disable prefix caching.
+    #[serde(default = "default_llm_prefix_cache_sequences")]
+    pub prefix_cache_sequences: usize,
+    /// Prefill the conversation prompt while the user is silent so the
+    /// next reply starts sooner.
+    #[serde(default = "default_llm_prefill_during_silence")]
+    pub prefill_during_silence: bool,
+    /// Load the local model in the background once the mic is open instead
+    /// of before it; a turn that arrives first waits for the model.
+    pub lazy_load: bool,
+    /// LoRA adapters applied on top of the local GGUF model.
+    ///
+    /// Lets users apply domain or personality fine-tunes without replacing
+    /// the base model. Ignored by the vision path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lora: Option<LoraAdapterConfig>,
+    /// Outbound network policy for web, shell, and Python skill tools.
+    ///
+    /// Domains needing approval are asked about through the tool approval
+    /// channel. Web tools enforce it; for shell commands and Python skills
+    /// it is advisory (see [`crate::fae_llm::tools::network_policy`]).
+    #[serde(skip_serializing_if = "NetworkPolicy::is_unrestricted")]
+    pub network: NetworkPolicy,
+    /// How long tool approvals wait for an answer, per class of tool.
+    pub approval_timeouts: ApprovalTimeoutConfig,
+    /// Which destructive actions are confirmed with a spoken read-back.
+    pub read_back: ReadBackConfig,
+    /// Privacy level for requests to remote providers.
+    ///
+    /// Emails, phone numbers, and (at `strict`) addresses and names in
+    /// utterances and memory snippets are replaced with placeholders that
+    /// are restored in the response. Local inference is never masked.
+    pub remote_pii_masking: PiiMaskingLevel,
     /// Legacy personality profile name (deprecated).
     ///
     /// Prompt assembly now uses: core prompt + SOUL.md + optional
@@ -488,6 +803,10 @@ pub struct LlmConfig {
     /// candidate. Defaults to 30 seconds.
     #[serde(default = "default_model_selection_timeout_secs")]
     pub model_selection_timeout_secs: u32,
+    /// Thinking token budgets and transcript handling.
+    pub thinking: ThinkingConfig,
+    /// Downscaling applied to images before the model sees them.
+    pub images: ImageBudgetConfig,
 }
 
 impl Default for LlmConfig {
@@ -517,10 +836,20 @@ impl Default for LlmConfig {
             message_queue_max_pending: default_llm_message_queue_max_pending(),
             message_queue_drop_policy: LlmMessageQueueDropPolicy::default(),
             clear_queue_on_stop: default_llm_clear_queue_on_stop(),
+            prefix_cache_sequences: default_llm_prefix_cache_sequences(),
+            prefill_during_silence: default_llm_prefill_during_silence(),
+            lazy_load: false,
+            lora: None,
+            network: NetworkPolicy::default(),
+            approval_timeouts: ApprovalTimeoutConfig::default(),
+            read_back: ReadBackConfig::default(),
+            remote_pii_masking: PiiMaskingLevel::default(),
             personality: "system".to_owned(),
             // User add-on prompt (optional). The fixed base prompt is always applied.
             system_prompt: String::new(),
             model_selection_timeout_secs: default_model_selection_timeout_secs(),
+            thinking: ThinkingConfig::default(),
+            images: ImageBudgetConfig::default(),
         }
     }
 }
@@ -546,59 +875,31 @@ pub fn recommended_context_size_tokens(total_memory_bytes: Option<u64>) -> usize
 /// Recommended local model selection based on total system RAM.
 ///
 /// Returns a tuple of `(model_id, gguf_file, tokenizer_id, enable_vision)`.
-/// Managed defaults are text-only GGUF for low-latency voice conversations.
+/// Managed defaults are text-only GGUF for low-latency voice conversations,
+/// drawn from the embedded [model catalog](crate::models::catalog).
 pub fn recommended_local_model(
     total_memory_bytes: Option<u64>,
     voice_model_preset: VoiceModelPreset,
 ) -> (&'static str, &'static str, &'static str, bool) {
-    const GIB: u64 = 1024 * 1024 * 1024;
-    match voice_model_preset {
-        VoiceModelPreset::Qwen3_8b => (
-            "unsloth/Qwen3-8B-GGUF",
-            "Qwen3-8B-Q4_K_M.gguf",
-            "Qwen/Qwen3-8B",
-            false,
-        ),
-        VoiceModelPreset::Qwen3_4b => (
-            "unsloth/Qwen3-4B-Instruct-2507-GGUF",
-            "Qwen3-4B-Instruct-2507-Q4_K_M.gguf",
-            "Qwen/Qwen3-4B-Instruct-2507",
-            false,
-        ),
-        VoiceModelPreset::Qwen3_1_7b => (
-            "unsloth/Qwen3-1.7B-GGUF",
-            "Qwen3-1.7B-Q4_K_M.gguf",
-            "Qwen/Qwen3-1.7B",
-            false,
-        ),
-        VoiceModelPreset::Qwen3_0_6b => (
-            "unsloth/Qwen3-0.6B-GGUF",
-            "Qwen3-0.6B-Q4_K_M.gguf",
-            "Qwen/Qwen3-0.6B",
-            false,
-        ),
-        VoiceModelPreset::Auto => match total_memory_bytes {
-            Some(bytes) if bytes >= 48 * GIB => (
-                "unsloth/Qwen3-8B-GGUF",
-                "Qwen3-8B-Q4_K_M.gguf",
-                "Qwen/Qwen3-8B",
-                false,
-            ),
-            Some(bytes) if bytes >= 32 * GIB => (
-                "unsloth/Qwen3-4B-Instruct-2507-GGUF",
-                "Qwen3-4B-Instruct-2507-Q4_K_M.gguf",
-                "Qwen/Qwen3-4B-Instruct-2507",
-                false,
-            ),
-            _ => (
-                "unsloth/Qwen3-1.7B-GGUF",
-                "Qwen3-1.7B-Q4_K_M.gguf",
-                "Qwen/Qwen3-1.7B",
-                false,
-            ),
-        },
-    }
-}
+    crate::models::catalog::embedded()
+        .local_llm_for(voice_model_preset, total_memory_bytes)
+        .map_or(FALLBACK_LOCAL_MODEL, |entry| {
+            (
+                entry.repo_id.as_str(),
+                entry.primary_file(),
+                entry.tokenizer_id.as_deref().unwrap_or(""),
+                entry.vision,
+            )
+        })
+}
+
+/// Used only if the embedded catalog has no entry for a preset.
+const FALLBACK_LOCAL_MODEL: (&str, &str, &str, bool) = (
+    "unsloth/Qwen3-1.7B-GGUF",
+    "Qwen3-1.7B-Q4_K_M.gguf",
+    "Qwen/Qwen3-1.7B",
+    false,
+);
 
 /// Apply RAM-based model selection to an `LlmConfig` **in place**.
 ///
@@ -637,16 +938,9 @@ pub fn apply_ram_model_selection(llm: &mut LlmConfig) {
 /// This guards RAM-based model selection so that user-customized configs
 /// are never silently overwritten.
 pub fn is_managed_default_model_id(model_id: &str) -> bool {
-    matches!(
-        model_id,
-        "unsloth/Qwen3-8B-GGUF"
-            | "unsloth/Qwen3-4B-Instruct-2507-GGUF"
-            | "MaziyarPanahi/Qwen3-4B-Instruct-GGUF"
-            | "unsloth/Qwen3-1.7B-GGUF"
-            | "unsloth/Qwen3-0.6B-GGUF"
-            | "Qwen/Qwen3-VL-4B-Instruct"
-            | "Qwen/Qwen3-VL-8B-Instruct"
-    )
+    crate::models::catalog::embedded()
+        .by_repo_id(model_id)
+        .is_some_and(|entry| entry.kind == crate::models::catalog::CatalogModelKind::Llm)
 }
 
 fn default_model_selection_timeout_secs() -> u32 {
@@ -661,6 +955,14 @@ fn default_llm_clear_queue_on_stop() -> bool {
     true
 }
 
+fn default_llm_prefix_cache_sequences() -> usize {
+    16
+}
+
+fn default_llm_prefill_during_silence() -> bool {
+    true
+}
+
 impl LlmConfig {
     /// Backward-compatible alias for the core prompt.
     ///
@@ -743,6 +1045,8 @@ pub enum TtsBackend {
     /// Kokoro-82M ONNX (fast, preset voices).
     #[default]
     Kokoro,
+    /// Local Chatterbox TTS server over HTTP (expressive, cloned voices).
+    Chatterbox,
 }
 
 /// Text-to-speech configuration.
@@ -761,6 +1065,10 @@ pub struct TtsConfig {
     pub speed: f32,
     /// Output sample rate in Hz (Kokoro always outputs 24 kHz).
     pub sample_rate: u32,
+    /// Base URL of the local Chatterbox server (used when `backend = "chatterbox"`).
+    pub chatterbox_url: String,
+    /// Chatterbox voice name (None = server default).
+    pub chatterbox_voice: Option<String>,
 }
 
 impl Default for TtsConfig {
@@ -771,6 +1079,8 @@ impl Default for TtsConfig {
             model_variant: "q8".to_owned(),
             speed: 1.1,
             sample_rate: 24_000,
+            chatterbox_url: "http://127.0.0.1:8004".to_owned(),
+            chatterbox_voice: None,
         }
     }
 }
@@ -804,6 +1114,18 @@ pub struct ConversationConfig {
     /// Set to 0 to disable the auto-idle timeout (companion mode — Fae stays
     /// present until told to sleep). Defaults to 0.
     pub idle_timeout_s: u32,
+    /// Minutes of silence after which the conversation is over: its history
+    /// is summarized into memory and the next turn starts a fresh session.
+    ///
+    /// Set to 0 to keep one conversation going for the whole run. Defaults
+    /// to 0.
+    pub session_timeout_min: u32,
+    /// Seconds of silence after one of Fae's replies before she asks, once,
+    /// whether the user is still there.
+    ///
+    /// Set to 0 to never ask. Only applies while a conversation is open, so
+    /// keep it shorter than `session_timeout_min`. Defaults to 0.
+    pub reengage_after_s: u32,
     /// Require direct address ("Fae ...") before forwarding speech when the
     /// assistant is not currently speaking.
     ///
@@ -841,6 +1163,8 @@ impl Default for ConversationConfig {
             sleep_phrases: default_sleep_phrases(),
             enabled: true,
             idle_timeout_s: 0,
+            session_timeout_min: 0,
+            reengage_after_s: 0,
             require_direct_address: false,
             direct_address_followup_s: 20,
         }
@@ -964,6 +1288,72 @@ impl Default for BargeInConfig {
     }
 }
 
+/// How long spoken replies run by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceVerbosity {
+    /// A sentence or two.
+    Brief,
+    /// A short paragraph.
+    #[default]
+    Normal,
+    /// Longer explanations before offering to continue.
+    Detailed,
+}
+
+impl VoiceVerbosity {
+    /// Spoken sentences per reply at this verbosity.
+    pub fn sentence_limit(self) -> usize {
+        match self {
+            Self::Brief => 2,
+            Self::Normal => 4,
+            Self::Detailed => 8,
+        }
+    }
+}
+
+/// Spoken reply shaping for the voice engine.
+///
+/// Replies longer than the sentence limit stop there and Fae asks whether to
+/// continue; the rest is spoken if the user says yes. After repeated
+/// interruptions replies drop to [`VoiceVerbosity::Brief`] for a while.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceResponseConfig {
+    /// Default reply length.
+    pub verbosity: VoiceVerbosity,
+    /// Hard cap on spoken sentences per reply, overriding the verbosity
+    /// default. Set to 0 to use the verbosity default.
+    pub max_sentences: usize,
+    /// Interruptions within `interruption_window_s` that switch replies to
+    /// brief. Set to 0 to never shorten replies after interruptions.
+    pub interruptions_before_brief: usize,
+    /// How long (seconds) an interruption counts as recent.
+    pub interruption_window_s: u32,
+}
+
+impl Default for VoiceResponseConfig {
+    fn default() -> Self {
+        Self {
+            verbosity: VoiceVerbosity::default(),
+            max_sentences: 0,
+            interruptions_before_brief: 2,
+            interruption_window_s: 300,
+        }
+    }
+}
+
+impl VoiceResponseConfig {
+    /// Sentence cap for replies when the user has not been interrupting.
+    pub fn sentence_limit(&self) -> usize {
+        if self.max_sentences > 0 {
+            self.max_sentences
+        } else {
+            self.verbosity.sentence_limit()
+        }
+    }
+}
+
 /// Wake word detection configuration (MFCC+DTW keyword spotter).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -1006,6 +1396,45 @@ impl Default for WakewordConfig {
     }
 }
 
+/// Hotword sensitivity configuration.
+///
+/// See [`crate::pipeline::hotword`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotwordConfig {
+    /// How readily a mention of Fae's name counts as addressing her
+    /// (0.0–1.0). A mention is accepted when its score reaches
+    /// `1 - sensitivity`.
+    ///   - 1.0: every mention (default)
+    ///   - 0.5: ignores misheard variants in the middle of a sentence
+    ///   - 0.0: only "Fae" at the start of an utterance
+    pub sensitivity: f32,
+    /// Active environment, selecting a sensitivity from `environments`.
+    pub environment: Option<String>,
+    /// Sensitivity per environment name, overriding `sensitivity`.
+    pub environments: BTreeMap<String, f32>,
+}
+
+impl Default for HotwordConfig {
+    fn default() -> Self {
+        Self {
+            sensitivity: 1.0,
+            environment: None,
+            environments: BTreeMap::new(),
+        }
+    }
+}
+
+impl HotwordConfig {
+    /// Sensitivity to use in `environment`.
+    pub fn sensitivity_for(&self, environment: &str) -> f32 {
+        self.environments
+            .get(environment)
+            .copied()
+            .unwrap_or(self.sensitivity)
+    }
+}
+
 /// Model management configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -1078,6 +1507,33 @@ impl Default for MemoryConfig {
     }
 }
 
+/// Privacy settings: encryption at rest and retention of persisted sessions.
+///
+/// Encryption covers conversation sessions and memory database backups
+/// only. The live memory database is not encrypted by Fae and relies on OS
+/// disk encryption (FileVault).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacyConfig {
+    /// Encrypt persisted sessions and memory backups (XChaCha20-Poly1305).
+    ///
+    /// Does not encrypt the live memory database; see
+    /// [`crate::privacy::ENCRYPTED_STORES`].
+    pub encrypt_at_rest: bool,
+    /// Keychain reference to the data encryption key.
+    ///
+    /// Created on first enable; never stored in plaintext.
+    pub encryption_key: CredentialRef,
+    /// Move sessions untouched for this many days into the archive (0 = never).
+    pub session_archive_after_days: u32,
+    /// Delete sessions, including archived ones, older than this many days
+    /// (0 = keep forever).
+    pub session_retention_days: u32,
+    /// Cap on total session storage in megabytes; the oldest sessions are
+    /// deleted first (0 = unlimited).
+    pub session_max_total_mb: u64,
+}
+
 /// Proactivity level for intelligence delivery.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -1119,6 +1575,16 @@ pub struct IntelligenceConfig {
     pub max_daily_research_tasks: u32,
     /// Minimum seconds between proactive deliveries.
     pub delivery_cooldown_secs: u64,
+    /// Capture todos ("remind me to…", "I need to…") from what the user says.
+    ///
+    /// Rule-based and local, so it runs even when `enabled` is off.
+    pub extract_todos: bool,
+    /// Mirror captured todos to Apple Reminders.
+    pub sync_todos_to_reminders: bool,
+    /// Record a reply as a correction when the user says it was wrong ("no,
+    /// that's wrong") and ask for the right answer. A thumbs-down in the app
+    /// is always recorded.
+    pub capture_corrections: bool,
 }
 
 impl Default for IntelligenceConfig {
@@ -1135,6 +1601,9 @@ impl Default for IntelligenceConfig {
             proactivity_level: ProactivityLevel::default(),
             max_daily_research_tasks: 3,
             delivery_cooldown_secs: 300,
+            extract_todos: true,
+            sync_todos_to_reminders: false,
+            capture_corrections: true,
         }
     }
 }
@@ -1172,12 +1641,357 @@ pub enum ThemeMode {
 pub struct ThemeConfig {
     /// Theme mode (auto/light/dark).
     pub mode: ThemeMode,
+    /// Conversation-state theme pack id; `None` uses the built-in pack.
+    /// See [`crate::theme::load_theme_packs`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pack: Option<String>,
 }
 
 impl Default for ThemeConfig {
     fn default() -> Self {
         Self {
             mode: ThemeMode::Auto,
+            pack: None,
+        }
+    }
+}
+
+/// How much the accessibility announcer says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessibilityVerbosity {
+    /// Only what needs a response: transcripts, approvals, errors.
+    Minimal,
+    /// Also assistant replies, tool progress and state changes.
+    #[default]
+    Standard,
+    /// Everything, including thinking state, memory and model loading.
+    Verbose,
+}
+
+/// Accessibility output configuration.
+///
+/// See [`crate::accessibility`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    /// Emit an `accessibility.announcement` event alongside runtime events.
+    pub enabled: bool,
+    /// Which events are announced.
+    pub verbosity: AccessibilityVerbosity,
+    /// Add interaction hints ("say yes or no…") to spoken prompts while
+    /// accessibility mode is on.
+    pub spoken_hints: bool,
+}
+
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            verbosity: AccessibilityVerbosity::Standard,
+            spoken_hints: true,
+        }
+    }
+}
+
+/// Whether assistant speech is captioned, and whether it is also spoken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionsMode {
+    /// No caption events.
+    #[default]
+    Off,
+    /// Speak replies and caption them in time with the audio.
+    WithSpeech,
+    /// Caption replies without synthesizing any speech.
+    CaptionsOnly,
+}
+
+/// Live caption configuration.
+///
+/// See [`crate::captions`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptionsConfig {
+    /// Caption mode.
+    pub mode: CaptionsMode,
+    /// Longest caption line in characters; longer sentences are split into
+    /// several segments.
+    pub max_line_chars: usize,
+}
+
+impl Default for CaptionsConfig {
+    fn default() -> Self {
+        Self {
+            mode: CaptionsMode::Off,
+            max_line_chars: 42,
+        }
+    }
+}
+
+/// How strictly replies are checked before they are spoken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentPolicyLevel {
+    /// Speak replies as written.
+    #[default]
+    Off,
+    /// Leave profanity out of spoken and displayed replies.
+    Clean,
+    /// Like `Clean`, and withhold sentences about adult topics.
+    Family,
+}
+
+/// Content filtering configuration.
+///
+/// See [`crate::content_filter`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentFilterConfig {
+    /// Mask profanity in transcripts before they reach the LLM and UI.
+    pub mask_transcripts: bool,
+    /// Policy applied to replies before they are spoken.
+    pub level: ContentPolicyLevel,
+    /// Per runtime profile overrides of `level`, keyed by profile name
+    /// (`standard`, `rescue`).
+    pub profiles: BTreeMap<String, ContentPolicyLevel>,
+}
+
+impl ContentFilterConfig {
+    /// The policy level for the runtime `profile`.
+    pub fn level_for(&self, profile: RuntimeProfile) -> ContentPolicyLevel {
+        self.profiles
+            .get(profile.as_str())
+            .copied()
+            .unwrap_or(self.level)
+    }
+}
+
+/// Where journal entries are written.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalDestination {
+    /// A Markdown file per day in the journal folder.
+    #[default]
+    Markdown,
+    /// An Apple Note per day, through the Notes bridge.
+    AppleNotes,
+}
+
+/// Conversation journal configuration.
+///
+/// See [`crate::journal`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalConfig {
+    /// Append a summary of each conversation to the day's journal note.
+    pub enabled: bool,
+    pub destination: JournalDestination,
+    /// Folder for Markdown notes; `journal/` in the data directory when unset.
+    pub folder: Option<PathBuf>,
+    /// Personal data masked out of entries.
+    pub pii_masking: PiiMaskingLevel,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            destination: JournalDestination::default(),
+            folder: None,
+            pii_masking: PiiMaskingLevel::Contact,
+        }
+    }
+}
+
+/// Meeting mode configuration.
+///
+/// See [`crate::meeting`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeetingConfig {
+    /// Input device captured during a meeting, such as a loopback or
+    /// aggregate device carrying system audio. The microphone when unset.
+    pub loopback_device: Option<String>,
+    /// Folder for transcripts and minutes; `meetings/` in the data directory
+    /// when unset.
+    pub folder: Option<PathBuf>,
+    /// Transcripts and minutes older than this many days are deleted when a
+    /// meeting starts. 0 keeps them.
+    pub retention_days: u32,
+    /// Disk budget (MiB) for the meetings folder. The oldest files are
+    /// deleted to stay under it, and a transcript stops being written to
+    /// disk when it would exceed it.
+    pub max_disk_mb: u64,
+    /// Voiceprint similarity at or above which a segment is attributed to an
+    /// existing speaker.
+    pub speaker_threshold: f32,
+    /// Most distinct speakers told apart; further voices join the closest.
+    pub max_speakers: usize,
+}
+
+impl Default for MeetingConfig {
+    fn default() -> Self {
+        Self {
+            loopback_device: None,
+            folder: None,
+            retention_days: 30,
+            max_disk_mb: 200,
+            speaker_threshold: 0.92,
+            max_speakers: 8,
+        }
+    }
+}
+
+/// Live transcript streaming configuration.
+///
+/// See [`crate::transcript_stream`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscriptStreamConfig {
+    /// File or named pipe that transcript lines are appended to. Streaming
+    /// is off when unset.
+    pub path: Option<PathBuf>,
+    /// Stream Fae's replies as well as what the user says.
+    pub include_assistant: bool,
+}
+
+impl Default for TranscriptStreamConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            include_assistant: true,
+        }
+    }
+}
+
+/// Per-turn context configuration.
+///
+/// See [`crate::turn_context`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TurnContextConfig {
+    /// Tell the model the current date, time, timezone and locale each turn.
+    pub enabled: bool,
+    /// Locale to report (e.g. `"en-GB"`); the system locale when unset.
+    pub locale: Option<String>,
+    /// Where the user is (e.g. `"Edinburgh"`); not reported when unset.
+    pub location: Option<String>,
+    /// Report today's busy times from the calendar, without event titles.
+    pub calendar_busy: bool,
+}
+
+impl Default for TurnContextConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            locale: None,
+            location: None,
+            calendar_busy: false,
+        }
+    }
+}
+
+/// Where synced data is stored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncBackendKind {
+    /// A folder that another service replicates, such as iCloud Drive.
+    #[default]
+    Folder,
+    /// A WebDAV collection.
+    WebDav,
+}
+
+/// Sync of config, skills, memories and preferences between the user's
+/// devices.
+///
+/// Everything is sealed with a key only the user's devices hold before it
+/// reaches the backend. See [`crate::sync`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Whether to sync.
+    pub enabled: bool,
+    /// Which backend to use.
+    pub backend: SyncBackendKind,
+    /// Folder for the `folder` backend.
+    pub folder: Option<PathBuf>,
+    /// Collection URL for the `webdav` backend. Must be `https` unless it
+    /// is on this machine.
+    pub webdav_url: Option<String>,
+    /// WebDAV user name.
+    pub webdav_username: Option<String>,
+    /// Keychain reference to the WebDAV password.
+    pub webdav_password: CredentialRef,
+    /// Keychain reference to the sync key. The same key is entered on each
+    /// device; it never leaves them.
+    pub key: CredentialRef,
+    /// Sync the config, apart from device-specific sections.
+    pub config: bool,
+    /// Sync user skills.
+    pub skills: bool,
+    /// Sync memories.
+    pub memories: bool,
+    /// Sync learned preferences.
+    pub preferences: bool,
+    /// Minutes between background syncs (0 = only on request).
+    pub interval_mins: u32,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: SyncBackendKind::Folder,
+            folder: None,
+            webdav_url: None,
+            webdav_username: None,
+            webdav_password: CredentialRef::None,
+            key: CredentialRef::None,
+            config: true,
+            skills: true,
+            memories: true,
+            preferences: true,
+            interval_mins: 15,
+        }
+    }
+}
+
+/// Battery-aware performance profile.
+///
+/// On battery (at or below `low_power_below_percent`) Fae switches to a
+/// low-power profile: a smaller local model, a lighter TTS model and less
+/// frequent background jobs. See [`crate::platform::power`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerConfig {
+    /// Whether to switch profiles with the power source.
+    pub enabled: bool,
+    /// Battery charge (percent) at or below which the low-power profile
+    /// applies while unplugged. 100 switches as soon as the charger is removed.
+    pub low_power_below_percent: u8,
+    /// How often (seconds) the power source is checked.
+    pub poll_interval_s: u32,
+    /// Local model used in low-power mode, if smaller than the one the
+    /// normal selection would load.
+    pub low_power_model: VoiceModelPreset,
+    /// Kokoro model variant used in low-power mode, if lighter than
+    /// [`TtsConfig::model_variant`].
+    pub low_power_tts_variant: String,
+    /// Background jobs run on every Nth scheduler tick in low-power mode.
+    pub background_interval_factor: u32,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            low_power_below_percent: 100,
+            poll_interval_s: 60,
+            low_power_model: VoiceModelPreset::Qwen3_1_7b,
+            low_power_tts_variant: "q4".to_owned(),
+            background_interval_factor: 4,
         }
     }
 }
@@ -1202,6 +2016,8 @@ pub struct ChannelsConfig {
     pub rate_limits: crate::channels::rate_limit::ChannelRateLimits,
     /// Future external adapters (plugin scaffolding).
     pub extensions: Vec<ChannelExtensionConfig>,
+    /// Forwarding of tool approvals to a channel when nobody answers locally.
+    pub remote_approval: RemoteApprovalConfig,
 }
 
 impl Default for ChannelsConfig {
@@ -1215,6 +2031,43 @@ impl Default for ChannelsConfig {
             whatsapp: None,
             rate_limits: crate::channels::rate_limit::ChannelRateLimits::default(),
             extensions: Vec::new(),
+            remote_approval: RemoteApprovalConfig::default(),
+        }
+    }
+}
+
+/// Remote confirmation of tool approvals over a channel.
+///
+/// A request that is still unanswered on the desktop after
+/// `forward_after_secs` is sent to `target` on `channel` with a short code;
+/// replying `approve <code>` or `deny <code>` resolves it. Unanswered codes
+/// expire after `token_ttl_secs` and the request is denied. Both delays
+/// together should stay under the agent's approval timeout for the tool
+/// (see [`ApprovalTimeoutConfig`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteApprovalConfig {
+    /// Whether unanswered approvals are forwarded.
+    pub enabled: bool,
+    /// Channel id to forward to (`discord` or `whatsapp`).
+    pub channel: String,
+    /// Reply target for the direct message (Discord DM channel or user ID,
+    /// WhatsApp number). Only replies from this target are accepted.
+    pub target: String,
+    /// Seconds to wait for a local answer before forwarding.
+    pub forward_after_secs: u64,
+    /// Seconds a forwarded code stays valid.
+    pub token_ttl_secs: u64,
+}
+
+impl Default for RemoteApprovalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channel: String::new(),
+            target: String::new(),
+            forward_after_secs: 15,
+            token_ttl_secs: 40,
         }
     }
 }
@@ -1572,6 +2425,21 @@ mod tests {
         assert_eq!(loaded.tts.backend, TtsBackend::Kokoro);
     }
 
+    #[test]
+    fn tts_backend_chatterbox_parses_from_toml() {
+        let loaded: SpeechConfig = toml::from_str(
+            r#"
+[tts]
+backend = "chatterbox"
+chatterbox_url = "http://localhost:9000"
+"#,
+        )
+        .unwrap();
+        assert_eq!(loaded.tts.backend, TtsBackend::Chatterbox);
+        assert_eq!(loaded.tts.chatterbox_url, "http://localhost:9000");
+        assert_eq!(loaded.tts.voice, "fae");
+    }
+
     #[test]
     fn recommended_context_size_tokens_scales_with_memory() {
         const GIB: u64 = 1024 * 1024 * 1024;
@@ -2220,6 +3088,28 @@ enabled = true
         assert!(cfg.runtime.kernel_signature_manifest.is_none());
     }
 
+    #[test]
+    fn content_filter_level_is_overridable_per_profile() {
+        let toml_str = r#"
+[content_filter]
+level = "clean"
+
+[content_filter.profiles]
+rescue = "family"
+"#;
+        let cfg: SpeechConfig = toml::from_str(toml_str).expect("parse content filter config");
+        let filter = &cfg.content_filter;
+        assert!(!filter.mask_transcripts);
+        assert_eq!(
+            filter.level_for(RuntimeProfile::Standard),
+            ContentPolicyLevel::Clean
+        );
+        assert_eq!(
+            filter.level_for(RuntimeProfile::Rescue),
+            ContentPolicyLevel::Family
+        );
+    }
+
     #[test]
     fn runtime_profile_section_parses_from_toml() {
         let toml_str = r#"
@@ -2289,4 +3179,57 @@ kernel_signature_manifest = "/tmp/kernel-signatures.toml"
             Some(std::path::PathBuf::from("/tmp/kernel-signatures.toml"))
         );
     }
+
+    #[test]
+    fn privacy_section_parses_from_toml() {
+        let toml_str = r#"
+[privacy]
+encrypt_at_rest = true
+session_retention_days = 90
+"#;
+        let cfg: SpeechConfig = toml::from_str(toml_str).expect("parse privacy config");
+        assert!(cfg.privacy.encrypt_at_rest);
+        assert_eq!(cfg.privacy.session_retention_days, 90);
+        assert_eq!(cfg.privacy.session_archive_after_days, 0);
+        assert_eq!(cfg.privacy.session_max_total_mb, 0);
+        assert!(!SpeechConfig::default().privacy.encrypt_at_rest);
+    }
+
+    #[test]
+    fn voice_response_section_parses_from_toml() {
+        let cfg: SpeechConfig = toml::from_str(
+            r#"
+[voice_response]
+verbosity = "brief"
+"#,
+        )
+        .expect("parse voice_response config");
+        assert_eq!(cfg.voice_response.verbosity, VoiceVerbosity::Brief);
+        assert_eq!(cfg.voice_response.sentence_limit(), 2);
+        assert_eq!(cfg.voice_response.interruptions_before_brief, 2);
+
+        let capped = VoiceResponseConfig {
+            max_sentences: 6,
+            ..VoiceResponseConfig::default()
+        };
+        assert_eq!(capped.sentence_limit(), 6);
+    }
+
+    #[test]
+    fn approval_timeouts_apply_per_tool_class() {
+        let cfg: SpeechConfig = toml::from_str(
+            r#"
+[llm.approval_timeouts]
+shell_secs = 120
+network_secs = 20
+"#,
+        )
+        .expect("parse approval timeouts");
+        let timeouts = &cfg.llm.approval_timeouts;
+        assert_eq!(timeouts.for_tool("bash"), Duration::from_secs(120));
+        assert_eq!(timeouts.for_tool("python_skill"), Duration::from_secs(120));
+        assert_eq!(timeouts.for_tool("network_access"), Duration::from_secs(20));
+        assert_eq!(timeouts.for_tool("write"), Duration::from_secs(60));
+        assert_eq!(timeouts.for_tool("compose_mail"), Duration::from_secs(60));
+    }
 }
@@ -0,0 +1,272 @@
+//! Profanity masking and the content policy for spoken replies.
+//!
+//! Two independent controls, both configured under `[content_filter]`:
+//!
+//! 1. **Transcript masking** — profanity in what the user said is masked
+//!    (`f***`) before the text reaches the LLM, memory, and the UI.
+//! 2. **Reply policy** — before a reply sentence is shown or spoken,
+//!    [`ReplyFilter`] drops profanity (`clean`) and, in `family` mode,
+//!    withholds sentences about adult topics, saying a short notice once in
+//!    their place.
+//!
+//! Matching is a word-list scan, so it is fast enough to run on every
+//! sentence; it is a courtesy filter, not a safety classifier.
+
+use crate::config::ContentPolicyLevel;
+
+/// Words masked or dropped as profanity (lowercase, several languages).
+const PROFANITY: &[&str] = &[
+    // en
+    "arse",
+    "arsehole",
+    "ass",
+    "asshole",
+    "bastard",
+    "bitch",
+    "bitches",
+    "bollocks",
+    "cock",
+    "crap",
+    "cunt",
+    "damn",
+    "dick",
+    "goddamn",
+    "piss",
+    "pissed",
+    "prick",
+    "slut",
+    "twat",
+    "wanker",
+    "whore",
+    // de
+    "arschloch",
+    "ficken",
+    "scheiss",
+    "scheiße",
+    "scheisse",
+    "wichser",
+    // es
+    "cabrón",
+    "cabron",
+    "gilipollas",
+    "joder",
+    "mierda",
+    "puta",
+    "puto",
+    // fr
+    "connard",
+    "connasse",
+    "enculé",
+    "merde",
+    "putain",
+    "salope",
+];
+
+/// Prefixes that make any word profane (`fucking`, `shithead`, ...).
+const PROFANE_PREFIXES: &[&str] = &["fuck", "motherfuck", "shit", "bullshit"];
+
+/// Punctuation that attaches to the word before it.
+const PUNCTUATION: [char; 6] = [',', '.', '!', '?', ';', ':'];
+
+/// Words that mark a sentence as unsuitable in family mode.
+const ADULT_TOPICS: &[&str] = &[
+    "cocaine",
+    "dismember",
+    "dismembered",
+    "erotic",
+    "genitals",
+    "gore",
+    "heroin",
+    "meth",
+    "methamphetamine",
+    "naked",
+    "nude",
+    "nudity",
+    "orgasm",
+    "porn",
+    "pornographic",
+    "pornography",
+    "sex",
+    "sexual",
+    "sexually",
+    "torture",
+    "tortured",
+];
+
+fn is_profane(word: &str) -> bool {
+    PROFANITY.contains(&word) || PROFANE_PREFIXES.iter().any(|p| word.starts_with(p))
+}
+
+fn is_adult(word: &str) -> bool {
+    ADULT_TOPICS.contains(&word)
+}
+
+/// Rewrite each word of `text` that `replace` returns `Some` for. `replace`
+/// gets the word lowercased and as written.
+fn replace_words(text: &str, replace: impl Fn(&str, &str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut word_start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        if c.is_alphabetic() {
+            word_start.get_or_insert(i);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            let word = &text[start..i];
+            match replace(&word.to_lowercase(), word) {
+                Some(replacement) => out.push_str(&replacement),
+                None => out.push_str(word),
+            }
+        }
+        if i < text.len() {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn words_lowercase(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Mask profanity in `text`, keeping each word's first letter (`f***`).
+///
+/// Returns `None` when there was nothing to mask.
+pub fn mask_profanity(text: &str) -> Option<String> {
+    if !words_lowercase(text).any(|w| is_profane(&w)) {
+        return None;
+    }
+    Some(replace_words(text, |lower, word| {
+        is_profane(lower).then(|| {
+            let mut chars = word.chars();
+            chars.next().into_iter().chain(chars.map(|_| '*')).collect()
+        })
+    }))
+}
+
+/// Remove profanity from `text`, tidying the spacing it leaves behind.
+pub fn strip_profanity(text: &str) -> String {
+    if !words_lowercase(text).any(|w| is_profane(&w)) {
+        return text.to_owned();
+    }
+    let stripped = replace_words(text, |lower, _| is_profane(lower).then(String::new));
+    let mut out = String::with_capacity(stripped.len());
+    for part in stripped.split(' ') {
+        // "Well, shit, no" leaves "Well, , no": drop the orphaned comma.
+        let part = if out.is_empty() || out.ends_with(PUNCTUATION) {
+            part.trim_start_matches(PUNCTUATION)
+        } else {
+            part
+        };
+        if part.is_empty() {
+            continue;
+        }
+        if !out.is_empty() && !part.starts_with(PUNCTUATION) {
+            out.push(' ');
+        }
+        out.push_str(part);
+    }
+    if text.ends_with(' ') {
+        out.push(' ');
+    }
+    out
+}
+
+/// Whether `text` touches a topic family mode withholds.
+pub fn mentions_adult_topic(text: &str) -> bool {
+    words_lowercase(text).any(|w| is_adult(&w))
+}
+
+/// Applies the reply policy to the sentences of one response at a time.
+#[derive(Debug, Clone)]
+pub struct ReplyFilter {
+    level: ContentPolicyLevel,
+    withheld: bool,
+}
+
+impl ReplyFilter {
+    /// A filter enforcing `level`.
+    pub fn new(level: ContentPolicyLevel) -> Self {
+        Self {
+            level,
+            withheld: false,
+        }
+    }
+
+    /// Filter one reply sentence. `is_final` marks the end of the response.
+    ///
+    /// A withheld sentence becomes the withheld notice the first time in a
+    /// response and empty after that.
+    pub fn filter(&mut self, text: &str, is_final: bool) -> String {
+        let filtered = match self.level {
+            ContentPolicyLevel::Off => text.to_owned(),
+            ContentPolicyLevel::Clean => strip_profanity(text),
+            ContentPolicyLevel::Family if mentions_adult_topic(text) => {
+                if std::mem::replace(&mut self.withheld, true) {
+                    String::new()
+                } else {
+                    crate::i18n::text("conversation.withheld").to_owned()
+                }
+            }
+            ContentPolicyLevel::Family => strip_profanity(text),
+        };
+        if is_final {
+            self.reset();
+        }
+        filtered
+    }
+
+    /// Forget what the current response withheld.
+    pub fn reset(&mut self) {
+        self.withheld = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn masks_profanity_in_transcripts() {
+        assert_eq!(
+            mask_profanity("This fucking printer, Scheiße!").as_deref(),
+            Some("This f****** printer, S******!")
+        );
+        assert_eq!(mask_profanity("Pass the salt, please."), None);
+        // Whole words only.
+        assert_eq!(mask_profanity("Scunthorpe class assessment"), None);
+    }
+
+    #[test]
+    fn clean_level_drops_profanity() {
+        let mut filter = ReplyFilter::new(ContentPolicyLevel::Clean);
+        assert_eq!(
+            filter.filter("Well, shit, that damn build failed. ", false),
+            "Well, that build failed. "
+        );
+        assert_eq!(filter.filter("All good.", true), "All good.");
+    }
+
+    #[test]
+    fn family_level_withholds_adult_topics_once_per_response() {
+        let mut filter = ReplyFilter::new(ContentPolicyLevel::Family);
+        let notice = crate::i18n::text("conversation.withheld");
+        assert_eq!(filter.filter("The film has nudity.", false), notice);
+        assert_eq!(filter.filter("And explicit sex scenes.", false), "");
+        assert_eq!(
+            filter.filter("It runs two hours.", true),
+            "It runs two hours."
+        );
+        assert_eq!(filter.filter("It is rated R for gore.", true), notice);
+
+        let mut off = ReplyFilter::new(ContentPolicyLevel::Off);
+        assert_eq!(off.filter("Damn, nudity.", true), "Damn, nudity.");
+    }
+}
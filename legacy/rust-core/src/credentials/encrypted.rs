@@ -10,6 +10,10 @@ use super::{CredentialError, CredentialManager, CredentialRef};
 /// Service name for all Fae credentials in the platform credential store.
 const SERVICE_NAME: &str = "fae-credentials";
 
+/// Largest secret Windows Credential Manager accepts (stored as UTF-16).
+#[cfg(target_os = "windows")]
+const MAX_WINDOWS_SECRET_BYTES: usize = 2_560;
+
 /// Credential manager using platform-specific encrypted storage via `keyring`.
 pub struct EncryptedCredentialManager;
 
@@ -29,6 +33,14 @@ impl Default for EncryptedCredentialManager {
 
 impl CredentialManager for EncryptedCredentialManager {
     fn store(&self, account: &str, value: &str) -> Result<CredentialRef, CredentialError> {
+        #[cfg(target_os = "windows")]
+        if value.encode_utf16().count() * 2 > MAX_WINDOWS_SECRET_BYTES {
+            return Err(CredentialError::StorageError(format!(
+                "credential '{account}' is too large for Windows Credential Manager \
+                 (max {MAX_WINDOWS_SECRET_BYTES} bytes)"
+            )));
+        }
+
         let entry = keyring::Entry::new(SERVICE_NAME, account).map_err(|e| {
             CredentialError::StorageError(format!("Failed to create keyring entry: {e}"))
         })?;
@@ -5,6 +5,7 @@
 //! backends:
 //!
 //! - **macOS**: Keychain Services (encrypted, OS-managed)
+//! - **Windows**: Windows Credential Manager via the `keyring` crate
 //! - **Other platforms**: Encrypted storage via `keyring` crate
 //!
 //! ## Usage
@@ -96,6 +97,7 @@ pub trait CredentialManager: Send + Sync {
 /// Create a platform-appropriate credential manager.
 ///
 /// - **macOS**: Returns a Keychain Services-backed manager
+/// - **Windows**: Returns a Windows Credential Manager-backed manager
 /// - **Other platforms**: Returns an encrypted storage manager via `keyring`
 ///
 /// # Example
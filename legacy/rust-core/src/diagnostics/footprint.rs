@@ -0,0 +1,165 @@
+//! Binary and memory footprint reporting.
+//!
+//! Records how much resident memory each model adds while it loads, so
+//! embedders can see what a component costs and decide whether to build
+//! without it. Attribution is only recorded in memory audit mode
+//! (`runtime.memory_audit`), where models load one at a time; concurrent
+//! loads would blur each other's growth.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// Optional subsystems and whether this build includes them.
+const FEATURES: &[(&str, bool)] = &[
+    ("vision", cfg!(feature = "vision")),
+    ("channels", cfg!(feature = "channels")),
+    ("canvas", cfg!(feature = "canvas")),
+    ("desktop", cfg!(feature = "desktop")),
+    ("tui", cfg!(feature = "tui")),
+];
+
+static LEDGER: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+
+fn ledger() -> MutexGuard<'static, BTreeMap<String, u64>> {
+    LEDGER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Resident memory attributed to one component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentFootprint {
+    /// Component name (`stt`, `tts`, `llm`).
+    pub component: String,
+    /// Resident bytes the process grew by while the component loaded.
+    pub resident_bytes: u64,
+}
+
+/// Snapshot returned by the `runtime.footprint` command.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FootprintReport {
+    /// Current resident set size of the process, if the platform reports it.
+    pub resident_bytes: Option<u64>,
+    /// Per-component attribution, empty unless memory audit mode ran.
+    pub components: Vec<ComponentFootprint>,
+    /// Resident bytes not attributed to any component.
+    pub unattributed_bytes: Option<u64>,
+    /// Optional subsystems compiled into this build.
+    pub features: BTreeMap<&'static str, bool>,
+}
+
+/// Current resident set size of this process in bytes.
+///
+/// Returns `None` on platforms without a supported source.
+pub fn resident_bytes() -> Option<u64> {
+    #[cfg(target_os = "macos")]
+    {
+        macos_resident_bytes()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        linux_resident_bytes()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn macos_resident_bytes() -> Option<u64> {
+    let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let written = unsafe {
+        libc::proc_pidinfo(
+            libc::getpid(),
+            libc::PROC_PIDTASKINFO,
+            0,
+            (&mut info as *mut libc::proc_taskinfo).cast(),
+            size,
+        )
+    };
+    (written == size).then_some(info.pti_resident_size)
+}
+
+#[cfg(target_os = "linux")]
+fn linux_resident_bytes() -> Option<u64> {
+    // statm fields are in pages: size resident shared text lib data dt.
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    u64::try_from(page_size)
+        .ok()
+        .map(|size| pages.saturating_mul(size))
+}
+
+/// Forget all recorded components, before an audited load starts.
+pub fn reset() {
+    ledger().clear();
+}
+
+/// Attribute the growth since `before` (from [`resident_bytes`]) to
+/// `component`.
+///
+/// Does nothing when the resident size is unknown. Memory that was released
+/// meanwhile counts as zero growth.
+pub fn record_since(component: &str, before: Option<u64>) {
+    let (Some(before), Some(after)) = (before, resident_bytes()) else {
+        return;
+    };
+    record(component, after.saturating_sub(before));
+}
+
+/// Attribute `bytes` of resident memory to `component`, replacing any
+/// earlier figure.
+pub fn record(component: &str, bytes: u64) {
+    ledger().insert(component.to_owned(), bytes);
+}
+
+/// Build a report from the recorded components and the current RSS.
+pub fn report() -> FootprintReport {
+    build_report(resident_bytes(), &ledger())
+}
+
+fn build_report(resident: Option<u64>, ledger: &BTreeMap<String, u64>) -> FootprintReport {
+    let components: Vec<ComponentFootprint> = ledger
+        .iter()
+        .map(|(component, &bytes)| ComponentFootprint {
+            component: component.clone(),
+            resident_bytes: bytes,
+        })
+        .collect();
+    let attributed: u64 = components.iter().map(|c| c.resident_bytes).sum();
+    FootprintReport {
+        resident_bytes: resident,
+        components,
+        unattributed_bytes: resident.map(|r| r.saturating_sub(attributed)),
+        features: FEATURES.iter().copied().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn report_attributes_recorded_components() {
+        let ledger = BTreeMap::from([("llm".to_owned(), 600), ("stt".to_owned(), 300)]);
+        let report = build_report(Some(1_000), &ledger);
+
+        assert_eq!(report.components.len(), 2);
+        assert_eq!(report.components[0].component, "llm");
+        assert_eq!(report.unattributed_bytes, Some(100));
+        assert_eq!(report.features.len(), FEATURES.len());
+
+        let unknown = build_report(None, &ledger);
+        assert_eq!(unknown.unattributed_bytes, None);
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    #[test]
+    fn resident_size_is_reported() {
+        assert!(resident_bytes().unwrap() > 0);
+    }
+}
@@ -1,4 +1,4 @@
-//! Diagnostic bundle creation and log rotation.
+//! Diagnostic bundle creation, log rotation, and memory footprint reporting.
 //!
 //! Creates a timestamped zip file in the diagnostics directory containing:
 //! - Log files
@@ -7,6 +7,7 @@
 //!
 //! Explicitly excludes: memory records, conversations, voice samples, API keys.
 
+pub mod footprint;
 pub mod log_rotation;
 
 use crate::error::{Result, SpeechError};
@@ -176,6 +177,58 @@ fn build_system_info() -> String {
         }
     }
 
+    // Pipeline queue depths (zero when the pipeline is not running)
+    info.push_str("\n=== Pipeline Queues ===\n");
+    for link in crate::pipeline::queues::snapshot() {
+        info.push_str(&format!(
+            "  {}: {}/{} queued, {} dropped, {} merged ({:?})\n",
+            link.name, link.depth, link.capacity, link.dropped, link.merged, link.policy
+        ));
+    }
+
+    // Audio stream stalls and rebuilds since startup
+    info.push_str("\n=== Audio Streams ===\n");
+    for stream in crate::audio::watchdog::snapshot() {
+        info.push_str(&format!(
+            "  {}: {} stalls, {} rebuilds, {} failed rebuilds (last stall {}ms)\n",
+            stream.name,
+            stream.stalls,
+            stream.rebuilds,
+            stream.failed_rebuilds,
+            stream.last_stall_ms
+        ));
+    }
+
+    // Tool calls over their timeout, CPU budget or output limit
+    info.push_str("\n=== Tool Limits ===\n");
+    let overruns = crate::fae_llm::agent::tool_limits::snapshot();
+    if overruns.is_empty() {
+        info.push_str("  (no overruns)\n");
+    }
+    for stats in overruns {
+        info.push_str(&format!(
+            "  {}: {} timeouts, {} over CPU budget, {} over output limit\n",
+            stats.tool_name, stats.timeouts, stats.cpu_time, stats.output
+        ));
+    }
+
+    // Hotword decisions since startup, per environment
+    info.push_str("\n=== Hotword ===\n");
+    let hotword = crate::pipeline::hotword::snapshot();
+    if hotword.is_empty() {
+        info.push_str("  (no name mentions yet)\n");
+    }
+    for stats in hotword {
+        info.push_str(&format!(
+            "  {}: {} accepted, {} rejected, {} false accepts, {} likely false rejects\n",
+            stats.environment,
+            stats.accepts,
+            stats.rejects,
+            stats.false_accepts,
+            stats.likely_false_rejects
+        ));
+    }
+
     info
 }
 
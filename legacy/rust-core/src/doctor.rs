@@ -1,7 +1,8 @@
 //! Doctor checks and repair actions.
 //!
-//! Doctor is a GUI-facing health subsystem that inspects scheduler, skills, and
-//! channel configuration and provides one-click repair actions.
+//! Doctor is a GUI-facing health subsystem that inspects scheduler, skills,
+//! channel, TTS engine, and network policy configuration and provides
+//! one-click repair actions.
 
 use crate::scheduler::{
     clear_persisted_state, load_persisted_snapshot, mark_persisted_task_due_now,
@@ -139,7 +140,16 @@ pub fn run_checks() -> Vec<DoctorFinding> {
         }
     }
 
-    findings.extend(findings_from_channel_config(&read_config_or_default()));
+    let config = read_config_or_default();
+    findings.extend(findings_from_channel_config(&config));
+    findings.extend(findings_from_tts_backend(
+        &config.tts,
+        crate::tts::preflight(config.tts.backend, &config.tts),
+    ));
+    let tools = crate::fae_llm::config::read_config(&crate::fae_dirs::llm_config_file())
+        .map(|c| c.tools)
+        .unwrap_or_default();
+    findings.extend(findings_from_network_policy(&config.llm, &tools));
 
     if findings.is_empty() {
         findings.push(
@@ -147,7 +157,7 @@ pub fn run_checks() -> Vec<DoctorFinding> {
                 "doctor-clean",
                 "No issues found",
                 DoctorSeverity::Info,
-                "Scheduler, managed skills, and channel settings look healthy.",
+                "Scheduler, managed skills, channel, and voice settings look healthy.",
             )
             .with_action("Gather diagnostics", DoctorActionKind::GatherDiagnostics),
         );
@@ -361,6 +371,63 @@ fn findings_from_channel_config(config: &crate::config::SpeechConfig) -> Vec<Doc
     findings
 }
 
+fn findings_from_tts_backend(
+    tts: &crate::config::TtsConfig,
+    preflight: crate::Result<()>,
+) -> Vec<DoctorFinding> {
+    let Err(err) = preflight else {
+        return Vec::new();
+    };
+    let (id, title, severity, summary) = match tts.backend {
+        crate::config::TtsBackend::Kokoro => (
+            "tts-kokoro-assets-missing",
+            "Kokoro voice model not downloaded",
+            DoctorSeverity::Warning,
+            "Kokoro will download its model on next start; speech is unavailable offline until then.",
+        ),
+        crate::config::TtsBackend::Chatterbox => (
+            "tts-chatterbox-unreachable",
+            "Chatterbox TTS server unreachable",
+            DoctorSeverity::Error,
+            "Fae cannot speak until the Chatterbox server is running or the TTS backend is switched to Kokoro.",
+        ),
+    };
+    vec![DoctorFinding::new(id, title, severity, summary).with_evidence(err.to_string())]
+}
+
+/// Network rules only bind the web tools; say so when rules are set and the
+/// shell and Python skill tools are on.
+fn findings_from_network_policy(
+    llm: &crate::config::LlmConfig,
+    tools: &crate::fae_llm::config::types::ToolsConfig,
+) -> Vec<DoctorFinding> {
+    use crate::config::AgentToolMode;
+    if !matches!(
+        llm.tool_mode,
+        AgentToolMode::Full | AgentToolMode::FullNoApproval
+    ) {
+        return Vec::new();
+    }
+    let restricted: Vec<&str> = ["bash", "python_skill"]
+        .into_iter()
+        .filter(|tool| !tools.network_policy(tool, &llm.network).is_unrestricted())
+        .collect();
+    if restricted.is_empty() {
+        return Vec::new();
+    }
+    vec![
+        DoctorFinding::new(
+            "network-policy-advisory",
+            "Network rules are advisory for shell commands and skills",
+            DoctorSeverity::Info,
+            "Web tools enforce the network rules. Shell commands and Python skills are only \
+             checked for URLs written in the command; a script that builds its URL at runtime \
+             can reach any host. They are not sandboxed.",
+        )
+        .with_evidence(format!("rules set for: {}", restricted.join(", "))),
+    ]
+}
+
 /// Applies a doctor action and returns a human-readable status message.
 pub fn apply_action(kind: &DoctorActionKind) -> crate::Result<String> {
     match kind {
@@ -446,6 +513,42 @@ mod tests {
         assert!(findings.iter().any(|f| f.id.contains("skill-quarantined")));
     }
 
+    #[test]
+    fn network_findings_say_shell_rules_are_advisory() {
+        let mut llm = crate::config::LlmConfig {
+            tool_mode: crate::config::AgentToolMode::Full,
+            ..Default::default()
+        };
+        let tools = crate::fae_llm::config::types::ToolsConfig::default();
+        assert!(findings_from_network_policy(&llm, &tools).is_empty());
+
+        llm.network.deny_domains = vec!["example.com".to_owned()];
+        let findings = findings_from_network_policy(&llm, &tools);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].id, "network-policy-advisory");
+        assert_eq!(findings[0].evidence, ["rules set for: bash, python_skill"]);
+
+        llm.tool_mode = crate::config::AgentToolMode::ReadOnly;
+        assert!(findings_from_network_policy(&llm, &tools).is_empty());
+    }
+
+    #[test]
+    fn tts_findings_report_unreachable_chatterbox() {
+        let tts = crate::config::TtsConfig {
+            backend: crate::config::TtsBackend::Chatterbox,
+            ..Default::default()
+        };
+        let findings = findings_from_tts_backend(
+            &tts,
+            Err(crate::SpeechError::Tts("connection refused".to_owned())),
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].id, "tts-chatterbox-unreachable");
+        assert_eq!(findings[0].severity, DoctorSeverity::Error);
+
+        assert!(findings_from_tts_backend(&tts, Ok(())).is_empty());
+    }
+
     #[test]
     fn clean_result_contains_info_card() {
         let mut builtin = crate::scheduler::ScheduledTask::new(
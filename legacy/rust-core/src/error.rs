@@ -54,6 +54,18 @@ pub enum SpeechError {
     /// Scheduler error (task execution, state persistence).
     #[error("scheduler error: {0}")]
     Scheduler(String),
+
+    /// Privacy error (encryption at rest, retention, data export).
+    #[error("privacy error: {0}")]
+    Privacy(String),
+
+    /// Settings and memory sync error (backend, sync key, merge).
+    #[error("sync error: {0}")]
+    Sync(String),
+
+    /// The user cancelled the operation (e.g. a model download).
+    #[error("cancelled: {0}")]
+    Cancelled(String),
 }
 
 /// Convenience result type.
@@ -0,0 +1,428 @@
+//! A/B experiments on the system prompt and voice model.
+//!
+//! An experiment configured under `[experiment]` in `config.toml` names two
+//! variants, `a` and `b`. Each pipeline run (one conversation, as in
+//! [`crate::analytics`]) is assigned to one of them by hashing the
+//! experiment and conversation ids, and the variant is applied to that run's
+//! LLM config: an extra system prompt add-on, a different voice model
+//! preset, or both.
+//!
+//! ```toml
+//! [experiment]
+//! id = "brief-replies"
+//!
+//! [experiment.a]
+//! name = "control"
+//!
+//! [experiment.b]
+//! name = "brief"
+//! system_prompt_add_on = "Answer in one or two sentences unless asked for more."
+//! ```
+//!
+//! The host records a [`TrialOutcome`] per conversation from its analytics
+//! (turns, latency, interruptions) and the corrections the user gave during
+//! it, in `experiments.json`. [`ExperimentReport`] summarises the outcomes
+//! per variant and is served by the `experiment.report` command.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::analytics::ConversationStats;
+use crate::config::{LlmConfig, VoiceModelPreset};
+
+/// Outcomes kept; the oldest are dropped on save.
+const MAX_OUTCOMES: usize = 1_000;
+
+/// Which variant a conversation ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Arm {
+    A,
+    B,
+}
+
+/// One side of an experiment.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptVariant {
+    /// Label used in the report.
+    pub name: String,
+    /// Text appended to the user's system prompt add-on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt_add_on: Option<String>,
+    /// Voice model to use instead of the configured one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice_model_preset: Option<VoiceModelPreset>,
+}
+
+impl PromptVariant {
+    /// Apply this variant to the LLM config of one pipeline run.
+    pub fn apply(&self, llm: &mut LlmConfig) {
+        if let Some(add_on) = self
+            .system_prompt_add_on
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let base = llm.system_prompt.trim();
+            llm.system_prompt = if base.is_empty() {
+                add_on.to_owned()
+            } else {
+                format!("{base}\n\n{add_on}")
+            };
+        }
+        if let Some(preset) = self.voice_model_preset {
+            llm.voice_model_preset = preset;
+            crate::config::apply_ram_model_selection(llm);
+        }
+    }
+}
+
+/// A running experiment.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptExperiment {
+    /// Identifies the experiment in recorded outcomes; change it when the
+    /// variants change.
+    pub id: String,
+    pub a: PromptVariant,
+    pub b: PromptVariant,
+}
+
+impl PromptExperiment {
+    /// Assign a conversation to a variant. The same conversation always
+    /// gets the same variant.
+    pub fn assign(&self, conversation_id: &str) -> Trial {
+        // FNV-1a: stable across builds, unlike `DefaultHasher`.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in self.id.bytes().chain([0]).chain(conversation_id.bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        let arm = if hash.is_multiple_of(2) {
+            Arm::A
+        } else {
+            Arm::B
+        };
+        Trial {
+            experiment_id: self.id.clone(),
+            conversation_id: conversation_id.to_owned(),
+            arm,
+        }
+    }
+
+    /// The variant for `arm`.
+    pub fn variant(&self, arm: Arm) -> &PromptVariant {
+        match arm {
+            Arm::A => &self.a,
+            Arm::B => &self.b,
+        }
+    }
+}
+
+/// A conversation's place in an experiment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trial {
+    pub experiment_id: String,
+    pub conversation_id: String,
+    pub arm: Arm,
+}
+
+/// Metrics of one conversation in an experiment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrialOutcome {
+    pub experiment_id: String,
+    pub conversation_id: String,
+    pub arm: Arm,
+    /// Unix seconds when the conversation started.
+    pub started_at: u64,
+    pub user_turns: u32,
+    pub assistant_turns: u32,
+    pub interruptions: u32,
+    pub average_latency_ms: Option<u64>,
+    /// Replies the user said were wrong.
+    pub corrections: u32,
+}
+
+impl TrialOutcome {
+    /// Outcome of `trial` from its conversation statistics.
+    pub fn new(trial: &Trial, stats: &ConversationStats, corrections: u32) -> Self {
+        Self {
+            experiment_id: trial.experiment_id.clone(),
+            conversation_id: trial.conversation_id.clone(),
+            arm: trial.arm,
+            started_at: stats.started_at,
+            user_turns: stats.user_turns,
+            assistant_turns: stats.assistant_turns,
+            interruptions: stats.interruptions,
+            average_latency_ms: stats.average_latency_ms,
+            corrections,
+        }
+    }
+}
+
+/// Persisted experiment outcomes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExperimentStore {
+    #[serde(default)]
+    outcomes: Vec<TrialOutcome>,
+}
+
+impl ExperimentStore {
+    /// Load the outcomes from `path`, returning an empty store on error.
+    pub fn load(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("failed to parse experiment outcomes: {e}");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!("failed to load experiment outcomes: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Save the outcomes to `path`, dropping the oldest beyond the limit.
+    pub fn save(&mut self, path: &Path) -> Result<(), String> {
+        if self.outcomes.len() > MAX_OUTCOMES {
+            let excess = self.outcomes.len() - MAX_OUTCOMES;
+            self.outcomes.drain(..excess);
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create experiments dir: {e}"))?;
+        }
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| format!("serialize error: {e}"))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("write error: {e}"))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("write error: {e}"))
+    }
+
+    /// Add an outcome, replacing an earlier one for the same conversation.
+    pub fn record(&mut self, outcome: TrialOutcome) {
+        match self
+            .outcomes
+            .iter_mut()
+            .find(|o| o.conversation_id == outcome.conversation_id)
+        {
+            Some(existing) => *existing = outcome,
+            None => self.outcomes.push(outcome),
+        }
+    }
+
+    /// Summarise the outcomes of `experiment`.
+    pub fn report(&self, experiment: &PromptExperiment) -> ExperimentReport {
+        let arms = [Arm::A, Arm::B]
+            .into_iter()
+            .map(|arm| {
+                let outcomes = self
+                    .outcomes
+                    .iter()
+                    .filter(|o| o.experiment_id == experiment.id && o.arm == arm);
+                ArmSummary::from_outcomes(arm, &experiment.variant(arm).name, outcomes)
+            })
+            .collect();
+        ExperimentReport {
+            experiment_id: experiment.id.clone(),
+            arms,
+        }
+    }
+}
+
+/// Aggregate metrics of one variant.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArmSummary {
+    pub arm: Arm,
+    pub name: String,
+    pub conversations: u32,
+    pub user_turns: u32,
+    pub assistant_turns: u32,
+    /// Mean reply latency, weighted by each conversation's replies.
+    pub average_latency_ms: Option<u64>,
+    pub interruptions_per_reply: Option<f64>,
+    pub corrections_per_reply: Option<f64>,
+}
+
+impl ArmSummary {
+    fn from_outcomes<'a>(
+        arm: Arm,
+        name: &str,
+        outcomes: impl Iterator<Item = &'a TrialOutcome>,
+    ) -> Self {
+        let mut summary = Self {
+            arm,
+            name: name.to_owned(),
+            conversations: 0,
+            user_turns: 0,
+            assistant_turns: 0,
+            average_latency_ms: None,
+            interruptions_per_reply: None,
+            corrections_per_reply: None,
+        };
+        let (mut interruptions, mut corrections) = (0u32, 0u32);
+        let (mut latency_total, mut latency_replies) = (0u64, 0u64);
+        for outcome in outcomes {
+            summary.conversations += 1;
+            summary.user_turns += outcome.user_turns;
+            summary.assistant_turns += outcome.assistant_turns;
+            interruptions += outcome.interruptions;
+            corrections += outcome.corrections;
+            if let Some(latency) = outcome.average_latency_ms {
+                let replies = u64::from(outcome.assistant_turns.max(1));
+                latency_total += latency * replies;
+                latency_replies += replies;
+            }
+        }
+        summary.average_latency_ms = latency_total.checked_div(latency_replies);
+        if summary.assistant_turns > 0 {
+            let replies = f64::from(summary.assistant_turns);
+            summary.interruptions_per_reply = Some(f64::from(interruptions) / replies);
+            summary.corrections_per_reply = Some(f64::from(corrections) / replies);
+        }
+        summary
+    }
+}
+
+/// Per-variant summary of an experiment.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExperimentReport {
+    pub experiment_id: String,
+    /// Variant `a`, then `b`.
+    pub arms: Vec<ArmSummary>,
+}
+
+/// Record the outcome of `trial` so far in the store at `path`, counting the
+/// corrections given since the conversation started.
+///
+/// Best effort: failures are logged.
+pub fn record_trial(path: &Path, trial: &Trial, stats: &ConversationStats) {
+    let corrections =
+        crate::intelligence::CorrectionStore::load(&crate::fae_dirs::corrections_file())
+            .corrections()
+            .iter()
+            .filter(|c| c.created_at >= stats.started_at)
+            .count();
+    let mut store = ExperimentStore::load(path);
+    store.record(TrialOutcome::new(
+        trial,
+        stats,
+        u32::try_from(corrections).unwrap_or(u32::MAX),
+    ));
+    if let Err(e) = store.save(path) {
+        warn!("failed to save experiment outcome: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn experiment() -> PromptExperiment {
+        PromptExperiment {
+            id: "brief-replies".to_owned(),
+            a: PromptVariant {
+                name: "control".to_owned(),
+                ..Default::default()
+            },
+            b: PromptVariant {
+                name: "brief".to_owned(),
+                system_prompt_add_on: Some("Answer briefly.".to_owned()),
+                voice_model_preset: None,
+            },
+        }
+    }
+
+    fn outcome(conversation_id: &str, arm: Arm, turns: u32, latency: u64) -> TrialOutcome {
+        TrialOutcome {
+            experiment_id: "brief-replies".to_owned(),
+            conversation_id: conversation_id.to_owned(),
+            arm,
+            started_at: 0,
+            user_turns: turns,
+            assistant_turns: turns,
+            interruptions: 1,
+            average_latency_ms: Some(latency),
+            corrections: 0,
+        }
+    }
+
+    #[test]
+    fn assignment_is_stable_and_uses_both_arms() {
+        let experiment = experiment();
+        let first = experiment.assign("conv_1");
+        assert_eq!(first, experiment.assign("conv_1"));
+        assert_eq!(first.experiment_id, "brief-replies");
+
+        let b_count = (0..200)
+            .filter(|i| experiment.assign(&format!("conv_{i}")).arm == Arm::B)
+            .count();
+        assert!((60..=140).contains(&b_count), "{b_count}");
+    }
+
+    #[test]
+    fn variant_appends_to_the_users_add_on() {
+        let mut llm = LlmConfig {
+            system_prompt: "Call me Sam.".to_owned(),
+            ..Default::default()
+        };
+        experiment().b.apply(&mut llm);
+        assert_eq!(llm.system_prompt, "Call me Sam.\n\nAnswer briefly.");
+
+        let mut llm = LlmConfig::default();
+        let before = llm.system_prompt.clone();
+        experiment().a.apply(&mut llm);
+        assert_eq!(llm.system_prompt, before);
+    }
+
+    #[test]
+    fn report_summarises_each_arm() {
+        let mut store = ExperimentStore::default();
+        store.record(outcome("conv_1", Arm::A, 2, 900));
+        store.record(outcome("conv_2", Arm::A, 6, 500));
+        store.record(outcome("conv_3", Arm::B, 4, 700));
+        let mut corrected = outcome("conv_3", Arm::B, 4, 300);
+        corrected.corrections = 2;
+        store.record(corrected);
+        let mut other = outcome("conv_4", Arm::B, 4, 100);
+        other.experiment_id = "older".to_owned();
+        store.record(other);
+
+        let report = store.report(&experiment());
+        assert_eq!(report.experiment_id, "brief-replies");
+        let [a, b] = report.arms.as_slice() else {
+            panic!("two arms expected");
+        };
+        assert_eq!((a.name.as_str(), a.conversations), ("control", 2));
+        assert_eq!(a.assistant_turns, 8);
+        assert_eq!(a.average_latency_ms, Some(600));
+        assert_eq!(a.interruptions_per_reply, Some(0.25));
+        assert_eq!(a.corrections_per_reply, Some(0.0));
+        assert_eq!((b.name.as_str(), b.conversations), ("brief", 1));
+        assert_eq!(b.average_latency_ms, Some(300));
+        assert_eq!(b.corrections_per_reply, Some(0.5));
+    }
+
+    #[test]
+    fn store_round_trips() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("experiments.json");
+        let mut store = ExperimentStore::default();
+        store.record(outcome("conv_1", Arm::B, 3, 400));
+        store.save(&path).expect("save");
+
+        let report = ExperimentStore::load(&path).report(&experiment());
+        assert_eq!(report.arms[0].conversations, 0);
+        assert_eq!(report.arms[0].average_latency_ms, None);
+        assert_eq!(report.arms[1].conversations, 1);
+    }
+}
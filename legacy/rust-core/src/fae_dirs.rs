@@ -7,11 +7,15 @@
 //!
 //! # Directory Layout
 //!
-//! | Purpose | macOS (sandbox) | Linux |
-//! |---------|----------------|-------|
-//! | App data | `~/Library/Application Support/fae/` | `~/.local/share/fae/` |
-//! | Config | `~/Library/Application Support/fae/` | `~/.config/fae/` |
-//! | Cache | `~/Library/Caches/fae/` | `~/.cache/fae/` |
+//! | Purpose | macOS (sandbox) | Linux | Windows |
+//! |---------|----------------|-------|---------|
+//! | App data | `~/Library/Application Support/fae/` | `~/.local/share/fae/` | `%LOCALAPPDATA%\fae\data\` |
+//! | Config | `~/Library/Application Support/fae/` | `~/.config/fae/` | `%APPDATA%\fae\` |
+//! | Cache | `~/Library/Caches/fae/` | `~/.cache/fae/` | `%LOCALAPPDATA%\fae\cache\` |
+//!
+//! On Windows only the config roams with the user profile: memory, logs and
+//! models are large and machine-specific, so they stay in `%LOCALAPPDATA%`.
+//! Without a home directory, paths fall back to the system temp directory.
 //!
 //! # Environment Overrides
 //!
@@ -27,16 +31,15 @@ use std::path::PathBuf;
 /// Used for persistent user data: memory records, SOUL.md, skills,
 /// voice samples, logs, and diagnostics.
 ///
-/// Resolves to `dirs::data_dir()/fae/` by default. Override with
+/// Resolves to `dirs::data_dir()/fae/` by default (`%LOCALAPPDATA%\fae\data\`
+/// on Windows). Override with
 /// the `FAE_DATA_DIR` environment variable.
 #[must_use]
 pub fn data_dir() -> PathBuf {
     if let Some(override_dir) = std::env::var_os("FAE_DATA_DIR") {
         return PathBuf::from(override_dir);
     }
-    dirs::data_dir()
-        .map(|d| d.join("fae"))
-        .unwrap_or_else(|| PathBuf::from("/tmp/fae-data"))
+    platform_data_dir().unwrap_or_else(|| std::env::temp_dir().join("fae-data"))
 }
 
 /// Application config directory.
@@ -50,25 +53,47 @@ pub fn config_dir() -> PathBuf {
     if let Some(override_dir) = std::env::var_os("FAE_CONFIG_DIR") {
         return PathBuf::from(override_dir);
     }
-    dirs::config_dir()
-        .map(|d| d.join("fae"))
-        .unwrap_or_else(|| PathBuf::from("/tmp/fae-config"))
+    platform_config_dir().unwrap_or_else(|| std::env::temp_dir().join("fae-config"))
 }
 
 /// Application cache directory.
 ///
 /// Used for downloaded model files and other expendable cached data.
 ///
-/// Resolves to `dirs::cache_dir()/fae/` by default. Override with
+/// Resolves to `dirs::cache_dir()/fae/` by default (`%LOCALAPPDATA%\fae\cache\`
+/// on Windows). Override with
 /// the `FAE_CACHE_DIR` environment variable.
 #[must_use]
 pub fn cache_dir() -> PathBuf {
     if let Some(override_dir) = std::env::var_os("FAE_CACHE_DIR") {
         return PathBuf::from(override_dir);
     }
-    dirs::cache_dir()
-        .map(|d| d.join("fae"))
-        .unwrap_or_else(|| PathBuf::from("/tmp/fae-cache"))
+    platform_cache_dir().unwrap_or_else(|| std::env::temp_dir().join("fae-cache"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn platform_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("fae"))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_data_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|d| d.join("fae").join("data"))
+}
+
+fn platform_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("fae"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn platform_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("fae"))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_cache_dir() -> Option<PathBuf> {
+    // `dirs::cache_dir()` is `%LOCALAPPDATA%` itself on Windows.
+    dirs::cache_dir().map(|d| d.join("fae").join("cache"))
 }
 
 /// Log file directory (`data_dir()/logs/`).
@@ -77,6 +102,14 @@ pub fn logs_dir() -> PathBuf {
     data_dir().join("logs")
 }
 
+/// Scheduler run logs directory (`logs_dir()/scheduler/`).
+///
+/// One file per recorded run, referenced from the run history.
+#[must_use]
+pub fn scheduler_runs_dir() -> PathBuf {
+    logs_dir().join("scheduler")
+}
+
 /// User skills directory (`data_dir()/skills/`).
 ///
 /// Override with `FAE_SKILLS_DIR` (checked by the skills module directly).
@@ -106,24 +139,153 @@ pub fn memory_dir() -> PathBuf {
     data_dir()
 }
 
+/// Conversation session store directory (`data_dir()/sessions/`).
+#[must_use]
+pub fn sessions_dir() -> PathBuf {
+    data_dir().join("sessions")
+}
+
 /// Main config file path (`config_dir()/config.toml`).
 #[must_use]
 pub fn config_file() -> PathBuf {
     config_dir().join("config.toml")
 }
 
+/// LLM provider config path (`config_dir()/fae_llm.toml`).
+#[must_use]
+pub fn llm_config_file() -> PathBuf {
+    config_dir().join("fae_llm.toml")
+}
+
 /// Scheduler state file path (`config_dir()/scheduler.json`).
 #[must_use]
 pub fn scheduler_file() -> PathBuf {
     config_dir().join("scheduler.json")
 }
 
+/// User job templates for the scheduler (`config_dir()/scheduler.templates.json`).
+#[must_use]
+pub fn scheduler_templates_file() -> PathBuf {
+    config_dir().join("scheduler.templates.json")
+}
+
+/// Pending provider batches of scheduled tasks (`config_dir()/scheduler.batches.json`).
+#[must_use]
+pub fn scheduler_batches_file() -> PathBuf {
+    config_dir().join("scheduler.batches.json")
+}
+
 /// Runtime profile audit log path (`config_dir()/runtime_audit.jsonl`).
 #[must_use]
 pub fn runtime_audit_file() -> PathBuf {
     config_dir().join("runtime_audit.jsonl")
 }
 
+/// Privacy audit log path (`config_dir()/privacy_audit.jsonl`).
+///
+/// Kept outside [`data_dir`] so the record of a wipe survives the wipe.
+#[must_use]
+pub fn privacy_audit_file() -> PathBuf {
+    config_dir().join("privacy_audit.jsonl")
+}
+
+/// Guardrail audit log path (`config_dir()/guardrail_audit.jsonl`).
+///
+/// Records prompt-injection detections in tool output, without the content.
+#[must_use]
+pub fn guardrail_audit_file() -> PathBuf {
+    config_dir().join("guardrail_audit.jsonl")
+}
+
+/// Permission usage ledger path (`config_dir()/permission_usage.jsonl`).
+///
+/// Records which permission-gated tools ran, for the monthly permission review.
+#[must_use]
+pub fn permission_usage_file() -> PathBuf {
+    config_dir().join("permission_usage.jsonl")
+}
+
+/// Skill credential grant ledger path (`config_dir()/skill_credential_grants.jsonl`).
+///
+/// Records which environment variables each Python skill launch received,
+/// without their values.
+#[must_use]
+pub fn skill_credential_grants_file() -> PathBuf {
+    config_dir().join("skill_credential_grants.jsonl")
+}
+
+/// Default conversation journal folder (`data_dir()/journal/`).
+#[must_use]
+pub fn journal_dir() -> PathBuf {
+    data_dir().join("journal")
+}
+
+/// Default meeting transcripts and minutes folder (`data_dir()/meetings/`).
+#[must_use]
+pub fn meetings_dir() -> PathBuf {
+    data_dir().join("meetings")
+}
+
+/// Todo list path (`data_dir()/todos.json`).
+#[must_use]
+pub fn todos_file() -> PathBuf {
+    data_dir().join("todos.json")
+}
+
+/// Unsent email drafts (`data_dir()/mail_drafts.json`).
+#[must_use]
+pub fn mail_drafts_file() -> PathBuf {
+    data_dir().join("mail_drafts.json")
+}
+
+/// User preferences path (`memory_dir()/preferences.json`).
+#[must_use]
+pub fn preferences_file() -> PathBuf {
+    memory_dir().join("preferences.json")
+}
+
+/// Experiment outcomes path (`data_dir()/experiments.json`).
+#[must_use]
+pub fn experiments_file() -> PathBuf {
+    data_dir().join("experiments.json")
+}
+
+/// Corrections path (`memory_dir()/corrections.json`).
+#[must_use]
+pub fn corrections_file() -> PathBuf {
+    memory_dir().join("corrections.json")
+}
+
+/// Undo history directory (`data_dir()/undo/`).
+///
+/// Holds the previous content of files changed by the write and edit tools.
+#[must_use]
+pub fn undo_dir() -> PathBuf {
+    data_dir().join("undo")
+}
+
+/// Recorded desktop macros directory (`data_dir()/desktop_macros/`).
+#[must_use]
+pub fn desktop_macros_dir() -> PathBuf {
+    data_dir().join("desktop_macros")
+}
+
+/// User theme packs directory (`config_dir()/themes/`).
+///
+/// Each `*.toml` file is a conversation-state theme pack.
+#[must_use]
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+/// Undo audit log path (`config_dir()/undo_audit.jsonl`).
+///
+/// Records which files were changed and reverted, without their content.
+#[must_use]
+pub fn undo_audit_file() -> PathBuf {
+    config_dir().join("undo_audit.jsonl")
+}
+
 /// Mutable-artifact mutation manifest path (`config_dir()/mutation_manifest.json`).
 #[must_use]
 pub fn mutation_manifest_file() -> PathBuf {
@@ -164,6 +326,12 @@ pub fn uv_cache_dir() -> PathBuf {
     cache_dir().join("uv")
 }
 
+/// Notes search index (`cache_dir()/notes_index.db`).
+#[must_use]
+pub fn notes_index_file() -> PathBuf {
+    cache_dir().join("notes_index.db")
+}
+
 /// Wakeword recordings directory (`data_dir()/wakeword/`).
 #[must_use]
 pub fn wakeword_dir() -> PathBuf {
@@ -269,6 +437,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn privacy_audit_file_is_under_config_dir() {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let path = privacy_audit_file();
+        assert!(path.starts_with(config_dir()));
+        assert!(path.ends_with("privacy_audit.jsonl"));
+    }
+
     #[test]
     fn mutation_manifest_file_ends_with_mutation_manifest_json() {
         let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
@@ -330,6 +506,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sessions_dir_is_subpath_of_data_dir() {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let sessions = sessions_dir();
+        let data = data_dir();
+        assert!(
+            sessions.starts_with(&data),
+            "sessions_dir ({}) should start with data_dir ({})",
+            sessions.display(),
+            data.display()
+        );
+    }
+
     #[test]
     fn hf_cache_dir_is_subpath_of_cache_dir() {
         let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
@@ -0,0 +1,442 @@
+//! Opt-in "best of N": run the same prompt on several agent loops at once.
+//!
+//! In latency mode the first candidate to complete wins and the rest are
+//! dropped. In quality mode every candidate runs to completion and a judge
+//! model picks the better answer; if judging fails, the first completed
+//! candidate (in the order given) wins.
+//!
+//! Each candidate runs its own tools, so candidates should be given
+//! read-only tool registries to avoid executing side effects twice.
+//!
+//! Enabled by `[experimental.best_of]` in the LLM config, which names the
+//! remote provider that answers alongside the local model; the voice
+//! engine runs it for turns without tools, with the local model as judge.
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use futures_util::stream::FuturesUnordered;
+use serde::{Deserialize, Serialize};
+
+use super::loop_engine::AgentLoop;
+use super::types::{AgentLoopResult, StopReason};
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::events::LlmEvent;
+use crate::fae_llm::provider::ProviderAdapter;
+use crate::fae_llm::providers::message::{Message, MessageContent, Role};
+use crate::fae_llm::types::{ReasoningLevel, RequestOptions};
+use crate::fae_llm::usage::{TokenUsage, UsageTracker};
+
+/// Default time allowed for the judge request.
+pub const DEFAULT_JUDGE_TIMEOUT_SECS: u64 = 20;
+
+const JUDGE_SYSTEM_PROMPT: &str = "You compare candidate answers to the same user request. \
+Pick the answer that is most correct, complete, and directly useful. Reply with exactly \
+one line: WINNER: <number>";
+
+/// How the winning candidate is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BestOfMode {
+    /// Use the first candidate that completes.
+    #[default]
+    Latency,
+    /// Wait for all candidates and let a judge model choose.
+    Quality,
+}
+
+/// Settings for best-of-N answering (`[experimental.best_of]`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BestOfConfig {
+    /// Answer each turn with both the local model and `provider`.
+    pub enabled: bool,
+    /// Remote provider ID that answers alongside the local model.
+    pub provider: Option<String>,
+    /// Model for `provider`; its first model when unset.
+    pub model: Option<String>,
+    /// How the winner is chosen.
+    pub mode: BestOfMode,
+    /// Timeout for the judge request in seconds (quality mode).
+    pub judge_timeout_secs: u64,
+}
+
+impl Default for BestOfConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: None,
+            model: None,
+            mode: BestOfMode::default(),
+            judge_timeout_secs: DEFAULT_JUDGE_TIMEOUT_SECS,
+        }
+    }
+}
+
+/// A named agent loop taking part in a best-of run.
+pub struct BestOfCandidate<'a> {
+    /// Provider name, used for usage tracking and reporting the winner.
+    pub name: &'a str,
+    /// The loop to run.
+    pub agent: &'a AgentLoop,
+}
+
+/// Result of a best-of run.
+#[derive(Debug, Clone)]
+pub struct BestOfOutcome {
+    /// The winning candidate's result.
+    pub result: AgentLoopResult,
+    /// Name of the winning candidate.
+    pub winner: String,
+    /// Whether a judge chose the winner.
+    pub judged: bool,
+    /// Usage of every candidate that finished, combined.
+    pub usage: TokenUsage,
+}
+
+/// Parse a judge reply of the form `WINNER: <n>` (1-based) into a
+/// candidate index below `count`.
+pub fn parse_judgement(reply: &str, count: usize) -> Option<usize> {
+    let line = reply
+        .lines()
+        .map(str::trim)
+        .find_map(|l| l.strip_prefix("WINNER:"))?;
+    let digits: String = line
+        .trim()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    let n: usize = digits.parse().ok()?;
+    (1..=count).contains(&n).then(|| n - 1)
+}
+
+/// Run `candidates` on `messages` and return the winning answer.
+///
+/// Usage of every candidate that returns is recorded in `usage`.
+///
+/// # Errors
+///
+/// Returns an error if no candidates are given, or if every candidate
+/// fails (the last failure is returned).
+pub async fn run_best_of(
+    candidates: &[BestOfCandidate<'_>],
+    messages: Vec<Message>,
+    judge: Option<&dyn ProviderAdapter>,
+    config: &BestOfConfig,
+    usage: &mut UsageTracker,
+) -> Result<BestOfOutcome, FaeLlmError> {
+    if candidates.is_empty() {
+        return Err(FaeLlmError::ConfigValidationError(
+            "best-of requires at least one candidate".into(),
+        ));
+    }
+
+    let mut pending: FuturesUnordered<_> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, candidate)| {
+            let messages = messages.clone();
+            async move { (index, candidate.agent.run_with_messages(messages).await) }
+        })
+        .collect();
+
+    let mut finished: Vec<(usize, AgentLoopResult)> = Vec::new();
+    let mut last_error = None;
+    let mut combined = TokenUsage::default();
+    while let Some((index, result)) = pending.next().await {
+        match result {
+            Ok(result) => {
+                usage.record(candidates[index].name, &result.total_usage);
+                combined.add(&result.total_usage);
+                let complete = result.stop_reason == StopReason::Complete;
+                finished.push((index, result));
+                if complete && config.mode == BestOfMode::Latency {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::warn!(candidate = candidates[index].name, error = %e, "Best-of candidate failed");
+                last_error = Some(e);
+            }
+        }
+    }
+    drop(pending);
+
+    finished.sort_by_key(|(index, _)| *index);
+    let complete: Vec<&(usize, AgentLoopResult)> = finished
+        .iter()
+        .filter(|(_, r)| r.stop_reason == StopReason::Complete)
+        .collect();
+
+    let (winner, judged) = match config.mode {
+        BestOfMode::Latency => (complete.first().map(|(index, _)| *index), false),
+        BestOfMode::Quality if complete.len() < 2 => {
+            (complete.first().map(|(index, _)| *index), false)
+        }
+        BestOfMode::Quality => {
+            let answers: Vec<&str> = complete
+                .iter()
+                .map(|(_, r)| r.final_text.as_str())
+                .collect();
+            let choice = match judge {
+                Some(judge) => match judge_answers(judge, &messages, &answers, config).await {
+                    Ok(choice) => choice,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Best-of judge failed; keeping first answer");
+                        None
+                    }
+                },
+                None => None,
+            };
+            match choice {
+                Some(i) => (Some(complete[i].0), true),
+                None => (Some(complete[0].0), false),
+            }
+        }
+    };
+
+    // Without a complete answer, fall back to the first result that returned.
+    let winner = winner.or_else(|| finished.first().map(|(index, _)| *index));
+    let Some(winner) = winner else {
+        return Err(last_error.unwrap_or_else(|| {
+            FaeLlmError::ProviderError("no best-of candidate returned".into())
+        }));
+    };
+    let result = finished
+        .into_iter()
+        .find_map(|(index, result)| (index == winner).then_some(result))
+        .ok_or_else(|| FaeLlmError::ProviderError("best-of winner missing".into()))?;
+
+    tracing::info!(
+        winner = candidates[winner].name,
+        judged,
+        mode = ?config.mode,
+        "Best-of answer selected"
+    );
+    Ok(BestOfOutcome {
+        result,
+        winner: candidates[winner].name.to_owned(),
+        judged,
+        usage: combined,
+    })
+}
+
+/// Ask `judge` which of `answers` best answers the conversation. Returns
+/// `Ok(None)` if the reply cannot be parsed.
+async fn judge_answers(
+    judge: &dyn ProviderAdapter,
+    conversation: &[Message],
+    answers: &[&str],
+    config: &BestOfConfig,
+) -> Result<Option<usize>, FaeLlmError> {
+    let request = conversation
+        .iter()
+        .rev()
+        .find_map(|m| match (&m.role, &m.content) {
+            (Role::User, MessageContent::Text { text }) => Some(text.as_str()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let mut prompt = format!("User request:\n{request}\n");
+    for (i, answer) in answers.iter().enumerate() {
+        prompt.push_str(&format!("\nAnswer {}:\n{answer}\n", i + 1));
+    }
+    let messages = vec![Message::system(JUDGE_SYSTEM_PROMPT), Message::user(prompt)];
+    let options = RequestOptions::new()
+        .with_stream(true)
+        .with_reasoning(ReasoningLevel::Off)
+        .with_temperature(0.0)
+        .with_max_tokens(16);
+
+    let collect = async {
+        let mut stream = judge.send(&messages, &options, &[]).await?;
+        let mut reply = String::new();
+        while let Some(event) = stream.next().await {
+            match event {
+                LlmEvent::TextDelta { text } => reply.push_str(&text),
+                LlmEvent::StreamError { error } => return Err(FaeLlmError::StreamError(error)),
+                LlmEvent::StreamEnd { .. } => break,
+                _ => {}
+            }
+        }
+        Ok(reply)
+    };
+    let reply = tokio::time::timeout(Duration::from_secs(config.judge_timeout_secs), collect)
+        .await
+        .map_err(|_| {
+            FaeLlmError::TimeoutError(format!(
+                "best-of judge timed out after {}s",
+                config.judge_timeout_secs
+            ))
+        })??;
+    let choice = parse_judgement(&reply, answers.len());
+    if choice.is_none() {
+        tracing::debug!(reply = %reply, "Unparseable judge reply");
+    }
+    Ok(choice)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::fae_llm::agent::types::AgentConfig;
+    use crate::fae_llm::config::types::ToolMode;
+    use crate::fae_llm::events::FinishReason;
+    use crate::fae_llm::provider::{LlmEventStream, ToolDefinition};
+    use crate::fae_llm::tools::registry::ToolRegistry;
+    use crate::fae_llm::types::ModelRef;
+
+    struct TextProvider {
+        text: Mutex<Option<&'static str>>,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl ProviderAdapter for TextProvider {
+        fn name(&self) -> &str {
+            "text"
+        }
+
+        async fn send(
+            &self,
+            _messages: &[Message],
+            _options: &RequestOptions,
+            _tools: &[ToolDefinition],
+        ) -> Result<LlmEventStream, FaeLlmError> {
+            tokio::time::sleep(self.delay).await;
+            let text = self.text.lock().unwrap().take().unwrap_or_default();
+            let events = vec![
+                LlmEvent::StreamStart {
+                    request_id: "req".into(),
+                    model: ModelRef::new("mock"),
+                },
+                LlmEvent::TextDelta { text: text.into() },
+                LlmEvent::StreamEnd {
+                    finish_reason: FinishReason::Stop,
+                },
+            ];
+            Ok(Box::pin(futures_util::stream::iter(events)))
+        }
+    }
+
+    fn provider(text: &'static str, delay_ms: u64) -> TextProvider {
+        TextProvider {
+            text: Mutex::new(Some(text)),
+            delay: Duration::from_millis(delay_ms),
+        }
+    }
+
+    fn agent(text: &'static str, delay_ms: u64) -> AgentLoop {
+        AgentLoop::new(
+            AgentConfig::new(),
+            Arc::new(provider(text, delay_ms)),
+            Arc::new(ToolRegistry::new(ToolMode::ReadOnly)),
+        )
+    }
+
+    #[test]
+    fn parse_judgement_forms() {
+        assert_eq!(parse_judgement("WINNER: 2", 2), Some(1));
+        assert_eq!(parse_judgement("Thinking...\n  WINNER: 1.", 2), Some(0));
+        assert_eq!(parse_judgement("WINNER: 3", 2), None);
+        assert_eq!(parse_judgement("WINNER: 0", 2), None);
+        assert_eq!(parse_judgement("the second one", 2), None);
+    }
+
+    #[tokio::test]
+    async fn latency_mode_takes_first_complete_answer() {
+        let slow = agent("slow answer", 200);
+        let fast = agent("fast answer", 0);
+        let mut usage = UsageTracker::new();
+        let outcome = run_best_of(
+            &[
+                BestOfCandidate {
+                    name: "slow",
+                    agent: &slow,
+                },
+                BestOfCandidate {
+                    name: "fast",
+                    agent: &fast,
+                },
+            ],
+            vec![Message::user("question")],
+            None,
+            &BestOfConfig::default(),
+            &mut usage,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.winner, "fast");
+        assert_eq!(outcome.result.final_text, "fast answer");
+        assert!(!outcome.judged);
+        assert_eq!(usage.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn quality_mode_uses_judge_choice() {
+        let first = agent("first answer", 0);
+        let second = agent("second answer", 0);
+        let judge = provider("WINNER: 2", 0);
+        let mut usage = UsageTracker::new();
+        let outcome = run_best_of(
+            &[
+                BestOfCandidate {
+                    name: "a",
+                    agent: &first,
+                },
+                BestOfCandidate {
+                    name: "b",
+                    agent: &second,
+                },
+            ],
+            vec![Message::user("question")],
+            Some(&judge),
+            &BestOfConfig {
+                mode: BestOfMode::Quality,
+                ..BestOfConfig::default()
+            },
+            &mut usage,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.winner, "b");
+        assert!(outcome.judged);
+        assert_eq!(usage.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn quality_mode_without_judge_keeps_first_candidate() {
+        let first = agent("first answer", 50);
+        let second = agent("second answer", 0);
+        let mut usage = UsageTracker::new();
+        let outcome = run_best_of(
+            &[
+                BestOfCandidate {
+                    name: "a",
+                    agent: &first,
+                },
+                BestOfCandidate {
+                    name: "b",
+                    agent: &second,
+                },
+            ],
+            vec![Message::user("question")],
+            None,
+            &BestOfConfig {
+                mode: BestOfMode::Quality,
+                ..BestOfConfig::default()
+            },
+            &mut usage,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.winner, "a");
+        assert!(!outcome.judged);
+    }
+}
@@ -1,17 +1,21 @@
 //! Tool executor with timeout and cancellation support.
 //!
 //! The [`ToolExecutor`] wraps a [`ToolRegistry`] and executes tool calls
-//! with per-tool timeouts and cancellation token propagation.
+//! with per-tool timeouts and CPU budgets, rate limits, duplicate
+//! suppression for side-effecting calls, and cancellation token propagation.
 
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use futures_util::stream::{self, StreamExt};
 use tokio_util::sync::CancellationToken;
 
 use super::accumulator::AccumulatedToolCall;
+use super::idempotency::{IdempotencyKey, RecentToolKeys};
+use super::rate_limit::{BudgetExhausted, ToolRateLimiter};
+use super::tool_limits::{self, OverrunKind, ToolLimits, ToolOverrun};
 use super::types::ExecutedToolCall;
-use super::validation::validate_tool_args;
+use super::validation::{validate_tool_args, validate_tool_output};
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
 use crate::fae_llm::observability::spans::*;
@@ -20,15 +24,33 @@ use crate::fae_llm::tools::registry::ToolRegistry;
 /// Executes tool calls with timeout and cancellation support.
 ///
 /// Wraps a [`ToolRegistry`] and adds:
-/// - Per-tool execution timeout
+/// - Asynchronous user approval ahead of execution
+/// - Per-tool execution timeout and CPU-time budget
 /// - Cancellation token checking between tool calls
 /// - Argument validation against tool schemas
+/// - Optional per-tool and global rate limits
+/// - Refusal of replayed side-effecting calls
+/// - Structured output validation against declared output schemas
 /// - Execution timing
 pub struct ToolExecutor {
     registry: Arc<ToolRegistry>,
     tool_timeout_secs: u64,
     parallel_tool_calls: bool,
     max_parallel_tool_calls: usize,
+    rate_limiter: Option<ToolRateLimiter>,
+    limits: ToolLimits,
+    idempotency: Option<Idempotency>,
+    /// Budget refusals not yet collected via [`Self::take_budget_exhaustions`].
+    exhausted: Mutex<Vec<BudgetExhausted>>,
+    /// Limit overruns not yet collected via [`Self::take_overruns`].
+    overruns: Mutex<Vec<ToolOverrun>>,
+}
+
+/// Where replayed side-effecting calls are looked up.
+struct Idempotency {
+    conversation_id: String,
+    recent: Arc<RecentToolKeys>,
+    window: Duration,
 }
 
 impl ToolExecutor {
@@ -44,6 +66,11 @@ impl ToolExecutor {
             tool_timeout_secs,
             parallel_tool_calls: false,
             max_parallel_tool_calls: 1,
+            rate_limiter: None,
+            limits: ToolLimits::default(),
+            idempotency: None,
+            exhausted: Mutex::new(Vec::new()),
+            overruns: Mutex::new(Vec::new()),
         }
     }
 
@@ -59,20 +86,89 @@ impl ToolExecutor {
             tool_timeout_secs,
             parallel_tool_calls,
             max_parallel_tool_calls: max_parallel_tool_calls.max(1),
+            rate_limiter: None,
+            limits: ToolLimits::default(),
+            idempotency: None,
+            exhausted: Mutex::new(Vec::new()),
+            overruns: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Enforce tool-call budgets with `limiter`.
+    pub fn with_rate_limiter(mut self, limiter: ToolRateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Apply per-tool timeouts, CPU budgets and output limits.
+    pub fn with_limits(mut self, limits: ToolLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Refuse side-effecting calls that replay one already run in
+    /// `conversation_id` within `window`, looking keys up in `recent`.
+    pub fn with_idempotency(
+        mut self,
+        conversation_id: impl Into<String>,
+        recent: Arc<RecentToolKeys>,
+        window: Duration,
+    ) -> Self {
+        self.idempotency = Some(Idempotency {
+            conversation_id: conversation_id.into(),
+            recent,
+            window,
+        });
+        self
+    }
+
+    /// Reset per-turn budgets at the start of a user turn.
+    pub fn begin_turn(&self) {
+        if let Some(ref limiter) = self.rate_limiter {
+            limiter.begin_turn();
         }
     }
 
+    /// Drain the budget refusals recorded since the last call.
+    pub fn take_budget_exhaustions(&self) -> Vec<BudgetExhausted> {
+        std::mem::take(&mut *self.exhausted.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Drain the limit overruns recorded since the last call.
+    pub fn take_overruns(&self) -> Vec<ToolOverrun> {
+        std::mem::take(&mut *self.overruns.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn record_overrun(&self, overrun: ToolOverrun) {
+        tracing::warn!(
+            tool_name = %overrun.tool_name,
+            kind = overrun.kind.as_str(),
+            limit = overrun.limit,
+            used = overrun.used,
+            "Tool exceeded its execution limit"
+        );
+        tool_limits::record(&overrun);
+        self.overruns
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(overrun);
+    }
+
     /// Execute a single tool call.
     ///
-    /// Validates arguments against the tool's schema, executes with timeout,
-    /// and returns the result with timing information.
+    /// Validates arguments against the tool's schema, awaits user approval
+    /// when the tool asks for it, executes with timeout, and returns the
+    /// result with timing information.
     ///
     /// # Errors
     ///
     /// Returns:
     /// - [`FaeLlmError::ToolValidationError`] when arguments fail schema validation.
-    /// - [`FaeLlmError::ToolExecutionError`] when execution fails, is cancelled, or the tool is unavailable.
-    /// - [`FaeLlmError::TimeoutError`] when execution exceeds the configured timeout.
+    /// - [`FaeLlmError::ToolExecutionError`] when execution fails, is cancelled, the tool is
+    ///   unavailable, a rate-limit budget is exhausted, the call replays a
+    ///   side-effecting call that already ran, or the tool uses more CPU time
+    ///   than its budget.
+    /// - [`FaeLlmError::TimeoutError`] when execution exceeds the tool's timeout.
     /// - The tool is not found in the registry
     /// - Execution times out
     /// - The operation is cancelled
@@ -126,14 +222,84 @@ impl ToolExecutor {
         let args = validate_tool_args(&call.function_name, &call.arguments_json, &tool.schema())?;
         tracing::debug!(tool_name = %call.function_name, "Arguments validated successfully");
 
-        // Execute with timeout
+        // Charge the call against rate-limit budgets
+        if let Some(ref limiter) = self.rate_limiter
+            && let Err(exhausted) = limiter.try_acquire(&call.function_name)
+        {
+            tracing::warn!(
+                tool_name = %call.function_name,
+                scope = exhausted.scope(),
+                window = exhausted.window.as_str(),
+                limit = exhausted.limit,
+                "Tool call refused: rate limit exceeded"
+            );
+            let message = exhausted.to_string();
+            self.exhausted
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(exhausted);
+            return Err(FaeLlmError::ToolExecutionError(message));
+        }
+
+        // Wait for the user's decision without holding a thread; the wait
+        // does not count against the execution timeout.
+        let approved = match tool.request_approval(&args) {
+            Some(approval) => {
+                tracing::info!(tool_name = %call.function_name, "Awaiting tool approval");
+                let approved = tokio::select! {
+                    _ = cancel.cancelled() => {
+                        tracing::warn!(tool_name = %call.function_name, "Tool execution cancelled while awaiting approval");
+                        return Err(FaeLlmError::ToolExecutionError(format!(
+                            "tool '{}': cancelled while awaiting approval",
+                            call.function_name
+                        )));
+                    }
+                    approved = approval => approved?,
+                };
+                if !approved {
+                    tracing::warn!(tool_name = %call.function_name, "Tool call denied by user");
+                    return Err(FaeLlmError::ToolExecutionError(format!(
+                        "tool '{}': call denied by user",
+                        call.function_name
+                    )));
+                }
+                true
+            }
+            None => false,
+        };
+
+        // Refuse a replay of a side-effecting call that already ran
+        let claimed = match self.idempotency {
+            Some(ref idempotency) if tool.is_side_effecting() => {
+                let key = IdempotencyKey::new(&idempotency.conversation_id, &call.call_id, &args);
+                if let Err(duplicate) =
+                    idempotency
+                        .recent
+                        .claim(&key, &call.function_name, idempotency.window)
+                {
+                    tracing::warn!(
+                        tool_name = %call.function_name,
+                        key = key.as_str(),
+                        age_secs = duplicate.age.as_secs(),
+                        "Tool call refused: duplicate of a call that already ran"
+                    );
+                    return Err(FaeLlmError::ToolExecutionError(duplicate.to_string()));
+                }
+                Some((Arc::clone(&idempotency.recent), key))
+            }
+            _ => None,
+        };
+
+        // Execute with timeout, measuring the CPU time of the tool's thread
+        let limit = self.limits.for_tool(&call.function_name);
+        let timeout_secs = limit.timeout_secs.unwrap_or(self.tool_timeout_secs);
         let start = Instant::now();
-        let timeout = tokio::time::Duration::from_secs(self.tool_timeout_secs);
+        let timeout = tokio::time::Duration::from_secs(timeout_secs);
 
         let tool_clone = Arc::clone(&tool);
         let args_clone = args.clone();
 
-        let result = tokio::select! {
+        let mut result = tokio::select! {
             _ = cancel.cancelled() => {
                 tracing::warn!(tool_name = %call.function_name, "Tool execution cancelled during execution");
                 return Err(FaeLlmError::ToolExecutionError(format!(
@@ -142,12 +308,39 @@ impl ToolExecutor {
                 )));
             }
             result = tokio::time::timeout(timeout, tokio::task::spawn_blocking(move || {
-                tool_clone.execute(args_clone)
+                let cpu_start = tool_limits::thread_cpu_time();
+                let result = if approved {
+                    tool_clone.execute_approved(args_clone)
+                } else {
+                    tool_clone.execute(args_clone)
+                };
+                let cpu_used = cpu_start
+                    .zip(tool_limits::thread_cpu_time())
+                    .map(|(start, end)| end.saturating_sub(start));
+                result.map(|tool_result| (tool_result, cpu_used))
             })) => {
                 match result {
-                    Ok(Ok(Ok(tool_result))) => tool_result,
+                    Ok(Ok(Ok((tool_result, cpu_used)))) => {
+                        if let (Some(budget_ms), Some(used)) = (limit.cpu_time_ms, cpu_used)
+                            && used.as_millis() > u128::from(budget_ms)
+                        {
+                            let overrun = ToolOverrun {
+                                tool_name: call.function_name.clone(),
+                                kind: OverrunKind::CpuTime,
+                                limit: budget_ms,
+                                used: u64::try_from(used.as_millis()).unwrap_or(u64::MAX),
+                            };
+                            let message = overrun.to_string();
+                            self.record_overrun(overrun);
+                            return Err(FaeLlmError::ToolExecutionError(message));
+                        }
+                        tool_result
+                    }
                     Ok(Ok(Err(e))) => {
                         tracing::error!(tool_name = %call.function_name, error = %e, "Tool execution failed");
+                        if let Some((recent, key)) = &claimed {
+                            recent.release(key);
+                        }
                         return Err(e);
                     }
                     Ok(Err(join_err)) => {
@@ -158,12 +351,16 @@ impl ToolExecutor {
                         )));
                     }
                     Err(_elapsed) => {
-                        tracing::error!(tool_name = %call.function_name, timeout_secs = self.tool_timeout_secs, "Tool execution timed out");
-                        return Err(FaeLlmError::TimeoutError(format!(
-                            "tool '{}': execution timed out after {}s",
-                            call.function_name,
-                            self.tool_timeout_secs
-                        )));
+                        tracing::error!(tool_name = %call.function_name, timeout_secs, "Tool execution timed out");
+                        let overrun = ToolOverrun {
+                            tool_name: call.function_name.clone(),
+                            kind: OverrunKind::Timeout,
+                            limit: timeout_secs.saturating_mul(1000),
+                            used: timeout_secs.saturating_mul(1000),
+                        };
+                        let message = overrun.to_string();
+                        self.record_overrun(overrun);
+                        return Err(FaeLlmError::TimeoutError(message));
                     }
                 }
             }
@@ -171,6 +368,39 @@ impl ToolExecutor {
 
         let duration_ms = start.elapsed().as_millis() as u64;
 
+        // A call that failed did nothing, so an identical one may run again.
+        if !result.success
+            && let Some((recent, key)) = &claimed
+        {
+            recent.release(key);
+        }
+
+        // Oversized output is still returned (and bounded before it reaches
+        // the model), but counts as an overrun.
+        if let Some(max_bytes) = limit.max_output_bytes
+            && result.content.len() > max_bytes
+        {
+            self.record_overrun(ToolOverrun {
+                tool_name: call.function_name.clone(),
+                kind: OverrunKind::Output,
+                limit: max_bytes as u64,
+                used: result.content.len() as u64,
+            });
+        }
+
+        // Structured output must match the declared schema; the text
+        // rendering is kept either way.
+        let invalid_structured = match (&result.structured, tool.output_schema()) {
+            (Some(value), Some(schema)) => {
+                validate_tool_output(&call.function_name, value, &schema).err()
+            }
+            _ => None,
+        };
+        if let Some(e) = invalid_structured {
+            tracing::warn!(tool_name = %call.function_name, error = %e, "Dropping structured tool output");
+            result.structured = None;
+        }
+
         tracing::info!(
             tool_name = %call.function_name,
             duration_ms = duration_ms,
@@ -618,4 +848,356 @@ mod tests {
             assert!(!msg.contains("blocked by current mode"));
         }
     }
+
+    #[tokio::test]
+    async fn execute_tool_refuses_calls_over_budget() {
+        use super::super::rate_limit::{ToolBudget, ToolCallHistory, ToolRateLimits};
+        let limits = ToolRateLimits::unlimited().with_tool_budget("echo", ToolBudget::new(1, 0));
+        let executor = ToolExecutor::new(make_registry(), 30).with_rate_limiter(
+            ToolRateLimiter::new(limits, Arc::new(ToolCallHistory::new())),
+        );
+        let cancel = CancellationToken::new();
+        let call = make_call("echo", r#"{"message": "hi"}"#);
+
+        assert!(executor.execute_tool(&call, &cancel).await.is_ok());
+        match executor.execute_tool(&call, &cancel).await {
+            Err(FaeLlmError::ToolExecutionError(msg)) => {
+                assert!(msg.contains("rate limit exceeded"));
+            }
+            _ => unreachable!("expected rate limit error"),
+        }
+        let exhausted = executor.take_budget_exhaustions();
+        assert_eq!(exhausted.len(), 1);
+        assert_eq!(exhausted[0].tool_name, "echo");
+        assert!(executor.take_budget_exhaustions().is_empty());
+
+        executor.begin_turn();
+        assert!(executor.execute_tool(&call, &cancel).await.is_ok());
+    }
+
+    /// Burns CPU until `cpu_ms` of thread CPU time has passed.
+    struct BusyTool {
+        cpu_ms: u64,
+    }
+
+    impl Tool for BusyTool {
+        fn name(&self) -> &str {
+            "busy"
+        }
+        fn description(&self) -> &str {
+            "A CPU-bound tool"
+        }
+        fn schema(&self) -> serde_json::Value {
+            serde_json::json!({
+                "type": "object",
+                "properties": {}
+            })
+        }
+        fn execute(&self, _args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+            let target = std::time::Duration::from_millis(self.cpu_ms);
+            let start = tool_limits::thread_cpu_time().unwrap_or_default();
+            let mut x = 0u64;
+            while tool_limits::thread_cpu_time().unwrap_or(target + start) - start < target {
+                x = std::hint::black_box(x.wrapping_add(1));
+            }
+            Ok(ToolResult::success(format!("{x}")))
+        }
+        fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_tool_applies_per_tool_timeout() {
+        use super::super::tool_limits::ToolLimit;
+        let limits = ToolLimits::default().with_tool_limit(
+            "slow",
+            ToolLimit {
+                timeout_secs: Some(1),
+                ..Default::default()
+            },
+        );
+        let executor = ToolExecutor::new(make_registry_with_slow(5000), 30).with_limits(limits);
+        let cancel = CancellationToken::new();
+
+        match executor
+            .execute_tool(&make_call("slow", r#"{}"#), &cancel)
+            .await
+        {
+            Err(FaeLlmError::TimeoutError(msg)) => assert!(msg.contains("after 1s"), "{msg}"),
+            _ => unreachable!("expected TimeoutError"),
+        }
+        let overruns = executor.take_overruns();
+        assert_eq!(overruns.len(), 1);
+        assert_eq!(overruns[0].kind, OverrunKind::Timeout);
+        assert_eq!(overruns[0].limit, 1000);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn execute_tool_enforces_cpu_budget() {
+        use super::super::tool_limits::ToolLimit;
+        let mut reg = ToolRegistry::new(ToolMode::Full);
+        reg.register(Arc::new(BusyTool { cpu_ms: 50 }));
+        let limit = |cpu_time_ms| ToolLimit {
+            cpu_time_ms: Some(cpu_time_ms),
+            ..Default::default()
+        };
+        let registry = Arc::new(reg);
+        let cancel = CancellationToken::new();
+        let call = make_call("busy", r#"{}"#);
+
+        let strict = ToolExecutor::new(Arc::clone(&registry), 30)
+            .with_limits(ToolLimits::default().with_tool_limit("busy", limit(10)));
+        match strict.execute_tool(&call, &cancel).await {
+            Err(FaeLlmError::ToolExecutionError(msg)) => {
+                assert!(msg.contains("CPU time budget exceeded"), "{msg}");
+            }
+            _ => unreachable!("expected CPU budget error"),
+        }
+        let overruns = strict.take_overruns();
+        assert_eq!(overruns[0].kind, OverrunKind::CpuTime);
+        assert!(overruns[0].used >= 50);
+
+        let lenient = ToolExecutor::new(registry, 30)
+            .with_limits(ToolLimits::default().with_tool_limit("busy", limit(10_000)));
+        assert!(lenient.execute_tool(&call, &cancel).await.is_ok());
+        assert!(lenient.take_overruns().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_tool_reports_oversized_output() {
+        use super::super::tool_limits::ToolLimit;
+        let limits = ToolLimits::default().with_tool_limit(
+            "echo",
+            ToolLimit {
+                max_output_bytes: Some(4),
+                ..Default::default()
+            },
+        );
+        let executor = ToolExecutor::new(make_registry(), 30).with_limits(limits);
+        let cancel = CancellationToken::new();
+
+        let executed = executor
+            .execute_tool(&make_call("echo", r#"{"message": "hello"}"#), &cancel)
+            .await;
+        assert_eq!(
+            executed.map(|e| e.result.content).ok().as_deref(),
+            Some("hello")
+        );
+        let overruns = executor.take_overruns();
+        assert_eq!(overruns.len(), 1);
+        assert_eq!(overruns[0].kind, OverrunKind::Output);
+        assert_eq!((overruns[0].limit, overruns[0].used), (4, 5));
+    }
+
+    /// Side-effecting tool that counts its runs and fails on "fail".
+    struct CreateTool {
+        runs: std::sync::atomic::AtomicUsize,
+    }
+
+    impl Tool for CreateTool {
+        fn name(&self) -> &str {
+            "create"
+        }
+        fn description(&self) -> &str {
+            "Creates something"
+        }
+        fn schema(&self) -> serde_json::Value {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" }
+                }
+            })
+        }
+        fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if args["title"] == "fail" {
+                return Ok(ToolResult::failure("calendar unavailable".to_string()));
+            }
+            Ok(ToolResult::success("created".to_string()))
+        }
+        fn is_side_effecting(&self) -> bool {
+            true
+        }
+        fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_tool_refuses_replayed_side_effecting_calls() {
+        let tool = Arc::new(CreateTool {
+            runs: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let mut reg = ToolRegistry::new(ToolMode::Full);
+        reg.register(Arc::clone(&tool) as Arc<dyn Tool>);
+        reg.register(Arc::new(EchoTool));
+        let registry = Arc::new(reg);
+        let recent = Arc::new(RecentToolKeys::new());
+        let window = Duration::from_secs(60);
+        let executor = |conversation: &str| {
+            ToolExecutor::new(Arc::clone(&registry), 30).with_idempotency(
+                conversation,
+                Arc::clone(&recent),
+                window,
+            )
+        };
+        let cancel = CancellationToken::new();
+        let create = |id: &str, title: &str| {
+            make_call_with_id(id, "create", &format!(r#"{{"title": "{title}"}}"#))
+        };
+
+        // A retried run replays the call; a new call or conversation runs.
+        let first = executor("c1");
+        assert!(
+            first
+                .execute_tool(&create("t1", "Dentist"), &cancel)
+                .await
+                .is_ok()
+        );
+        let retried = executor("c1");
+        match retried
+            .execute_tool(&create("t1", "Dentist"), &cancel)
+            .await
+        {
+            Err(FaeLlmError::ToolExecutionError(msg)) => {
+                assert!(msg.contains("duplicate call suppressed"), "{msg}");
+            }
+            _ => unreachable!("expected duplicate refusal"),
+        }
+        assert!(
+            retried
+                .execute_tool(&create("t2", "Dentist"), &cancel)
+                .await
+                .is_ok()
+        );
+        assert!(
+            executor("c2")
+                .execute_tool(&create("t1", "Dentist"), &cancel)
+                .await
+                .is_ok()
+        );
+        assert_eq!(tool.runs.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        // Failed calls may be retried; tools without side effects always run.
+        assert!(
+            first
+                .execute_tool(&create("t3", "fail"), &cancel)
+                .await
+                .is_ok()
+        );
+        assert!(
+            first
+                .execute_tool(&create("t3", "fail"), &cancel)
+                .await
+                .is_ok()
+        );
+        assert_eq!(tool.runs.load(std::sync::atomic::Ordering::SeqCst), 5);
+        let echo = make_call("echo", r#"{"message": "hi"}"#);
+        assert!(first.execute_tool(&echo, &cancel).await.is_ok());
+        assert!(first.execute_tool(&echo, &cancel).await.is_ok());
+    }
+
+    /// Needs approval; the decision arrives after `delay_ms`.
+    struct GatedTool {
+        approve: bool,
+        delay_ms: u64,
+    }
+
+    impl Tool for GatedTool {
+        fn name(&self) -> &str {
+            "gated"
+        }
+        fn description(&self) -> &str {
+            "A tool behind approval"
+        }
+        fn schema(&self) -> serde_json::Value {
+            serde_json::json!({
+                "type": "object",
+                "properties": {}
+            })
+        }
+        fn execute(&self, _args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+            Ok(ToolResult::failure("ran without approval".to_string()))
+        }
+        fn request_approval(
+            &self,
+            _args: &serde_json::Value,
+        ) -> Option<crate::fae_llm::tools::ApprovalFuture> {
+            let (approve, delay_ms) = (self.approve, self.delay_ms);
+            Some(Box::pin(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                Ok(approve)
+            }))
+        }
+        fn execute_approved(&self, _args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+            Ok(ToolResult::success("approved".to_string()))
+        }
+        fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+            true
+        }
+    }
+
+    fn make_gated_executor(approve: bool, delay_ms: u64) -> ToolExecutor {
+        let mut reg = ToolRegistry::new(ToolMode::Full);
+        reg.register(Arc::new(GatedTool { approve, delay_ms }));
+        ToolExecutor::new(Arc::new(reg), 1)
+    }
+
+    #[tokio::test]
+    async fn approval_wait_does_not_count_against_timeout() {
+        let executor = make_gated_executor(true, 1200);
+        let cancel = CancellationToken::new();
+
+        let result = executor
+            .execute_tool(&make_call("gated", "{}"), &cancel)
+            .await;
+        match result {
+            Ok(executed) => {
+                assert!(executed.result.success);
+                assert_eq!(executed.result.content, "approved");
+                assert!(executed.duration_ms < 1000);
+            }
+            Err(e) => unreachable!("expected approved execution, got {e}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn denied_approval_skips_execution() {
+        let executor = make_gated_executor(false, 0);
+        let cancel = CancellationToken::new();
+
+        match executor
+            .execute_tool(&make_call("gated", "{}"), &cancel)
+            .await
+        {
+            Err(FaeLlmError::ToolExecutionError(msg)) => {
+                assert!(msg.contains("denied by user"));
+            }
+            _ => unreachable!("expected denial"),
+        }
+    }
+
+    #[tokio::test]
+    async fn cancel_interrupts_pending_approval() {
+        let executor = make_gated_executor(true, 60_000);
+        let cancel = CancellationToken::new();
+        let cancel_clone = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            cancel_clone.cancel();
+        });
+
+        match executor
+            .execute_tool(&make_call("gated", "{}"), &cancel)
+            .await
+        {
+            Err(FaeLlmError::ToolExecutionError(msg)) => {
+                assert!(msg.contains("awaiting approval"));
+            }
+            _ => unreachable!("expected cancellation"),
+        }
+    }
 }
@@ -0,0 +1,169 @@
+//! Duplicate suppression for side-effecting tool calls.
+//!
+//! A stream that is retried after a network blip can replay tool calls the
+//! executor already ran, and a replayed `create_calendar_event` is a second
+//! event in the user's calendar. Before running a tool that reports
+//! [`Tool::is_side_effecting`](crate::fae_llm::tools::types::Tool::is_side_effecting),
+//! the executor derives an [`IdempotencyKey`] from the conversation, the
+//! call ID and a hash of the arguments, and claims it in a
+//! [`RecentToolKeys`] store. A key that was claimed within the window is a
+//! replay: the call is refused instead of run again.
+//!
+//! Keys are released when the tool reports a failure, so a call that did
+//! nothing can be retried. Share one store (via `Arc`) between agent loops
+//! so a retried run sees the keys of the run it replaces.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default time within which a replayed call is refused.
+pub const DEFAULT_IDEMPOTENCY_WINDOW_SECS: u64 = 600;
+
+/// Identity of a tool call: conversation, call ID and argument hash.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    /// Derive the key of the call `call_id` with `args` in `conversation_id`.
+    pub fn new(conversation_id: &str, call_id: &str, args: &serde_json::Value) -> Self {
+        let hash = blake3::hash(args.to_string().as_bytes());
+        Self(format!(
+            "{conversation_id}:{call_id}:{}",
+            &hash.to_hex()[..16]
+        ))
+    }
+
+    /// The key as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A call refused because an identical one already ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateToolCall {
+    pub tool_name: String,
+    /// How long ago the identical call ran.
+    pub age: Duration,
+}
+
+impl std::fmt::Display for DuplicateToolCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tool '{}': duplicate call suppressed; an identical call already ran {}s ago, \
+             so do not repeat it",
+            self.tool_name,
+            self.age.as_secs()
+        )
+    }
+}
+
+/// Keys of recently run side-effecting calls.
+#[derive(Debug, Default)]
+pub struct RecentToolKeys {
+    keys: Mutex<HashMap<IdempotencyKey, Instant>>,
+}
+
+impl RecentToolKeys {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Claim `key` for a call to `tool_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DuplicateToolCall`] when `key` was claimed less than
+    /// `window` ago.
+    pub fn claim(
+        &self,
+        key: &IdempotencyKey,
+        tool_name: &str,
+        window: Duration,
+    ) -> Result<(), DuplicateToolCall> {
+        self.claim_at(key, tool_name, window, Instant::now())
+    }
+
+    fn claim_at(
+        &self,
+        key: &IdempotencyKey,
+        tool_name: &str,
+        window: Duration,
+        now: Instant,
+    ) -> Result<(), DuplicateToolCall> {
+        let mut keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        keys.retain(|_, at| now.saturating_duration_since(*at) < window);
+        if let Some(at) = keys.get(key) {
+            return Err(DuplicateToolCall {
+                tool_name: tool_name.to_owned(),
+                age: now.saturating_duration_since(*at),
+            });
+        }
+        keys.insert(key.clone(), now);
+        Ok(())
+    }
+
+    /// Forget `key`, so an identical call may run again.
+    pub fn release(&self, key: &IdempotencyKey) {
+        self.keys
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn key_covers_conversation_call_and_arguments() {
+        let args = serde_json::json!({"title": "Dentist"});
+        let key = IdempotencyKey::new("voice-1", "call_0", &args);
+        assert_eq!(key, IdempotencyKey::new("voice-1", "call_0", &args));
+        assert!(key.as_str().starts_with("voice-1:call_0:"));
+        assert_ne!(key, IdempotencyKey::new("voice-2", "call_0", &args));
+        assert_ne!(key, IdempotencyKey::new("voice-1", "call_1", &args));
+        assert_ne!(
+            key,
+            IdempotencyKey::new("voice-1", "call_0", &serde_json::json!({"title": "Gym"}))
+        );
+    }
+
+    #[test]
+    fn replays_within_the_window_are_refused() {
+        let store = RecentToolKeys::new();
+        let key = IdempotencyKey::new("voice-1", "call_0", &serde_json::json!({}));
+        let window = Duration::from_secs(60);
+        let now = Instant::now();
+
+        assert!(store.claim_at(&key, "create_reminder", window, now).is_ok());
+        let duplicate = store
+            .claim_at(
+                &key,
+                "create_reminder",
+                window,
+                now + Duration::from_secs(5),
+            )
+            .expect_err("duplicate");
+        assert_eq!(duplicate.age, Duration::from_secs(5));
+        assert!(duplicate.to_string().contains("ran 5s ago"));
+
+        assert!(
+            store
+                .claim_at(&key, "create_reminder", window, now + window)
+                .is_ok()
+        );
+        store.release(&key);
+        assert!(
+            store
+                .claim_at(&key, "create_reminder", window, now + window)
+                .is_ok()
+        );
+    }
+}
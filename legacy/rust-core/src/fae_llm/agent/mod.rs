@@ -35,10 +35,29 @@
 //! - [`StopReason`] — Why the loop stopped
 //! - [`StreamAccumulator`] — Collects streaming events into structured data
 //! - [`ToolExecutor`] — Executes tools with timeout and cancellation
+//! - [`ToolRateLimits`] — Per-tool and global call budgets per turn and per minute
+//! - [`RecentToolKeys`] — Duplicate suppression for replayed side-effecting tool calls
+//! - [`ToolOutputLimits`] — Per-tool output bounds with middle-out compression
+//! - [`InjectionGuardConfig`] — Prompt-injection scanning of tool output
+//! - [`ThinkingBudgets`] — Thinking token budgets per reasoning level and model tier
+//! - [`ReflectionConfig`] — Optional critique pass over the final answer
+//! - [`run_speculative`] — Experimental spoken draft with authoritative verification
+//! - [`run_best_of`] — Same prompt on several providers; first or judged best answer wins
+//! - [`AssistantTextStream`] — Word-level markdown deltas of the reply for the host UI
 
 pub mod accumulator;
+pub mod best_of;
 pub mod executor;
+pub mod guardrails;
+pub mod idempotency;
 pub mod loop_engine;
+pub mod output_compress;
+pub mod rate_limit;
+pub mod reflection;
+pub mod speculative;
+pub mod text_stream;
+pub mod thinking;
+pub mod tool_limits;
 pub mod types;
 pub mod validation;
 
@@ -49,10 +68,32 @@ mod failure_tests;
 
 // Re-export key types for convenience
 pub use accumulator::{AccumulatedToolCall, AccumulatedTurn, StreamAccumulator};
+pub use best_of::{
+    BestOfCandidate, BestOfConfig, BestOfMode, BestOfOutcome, parse_judgement, run_best_of,
+};
 pub use executor::ToolExecutor;
+pub use guardrails::{
+    GuardedOutput, InjectionAuditEntry, InjectionGuardConfig, append_injection_audit,
+    guard_tool_output,
+};
+pub use idempotency::{DuplicateToolCall, IdempotencyKey, RecentToolKeys};
 pub use loop_engine::{AgentLoop, build_messages_from_result};
-pub use types::{AgentConfig, AgentLoopResult, ExecutedToolCall, StopReason, TurnResult};
-pub use validation::validate_tool_args;
+pub use output_compress::{
+    BoundedOutput, OutputSummarizer, ProviderSummarizer, ToolOutputLimit, ToolOutputLimits,
+    bound_tool_output,
+};
+pub use rate_limit::{
+    BudgetExhausted, BudgetWindow, ToolBudget, ToolCallHistory, ToolRateLimiter, ToolRateLimits,
+};
+pub use reflection::{Critique, ReflectionConfig, ReflectionVerdict, parse_critique};
+pub use speculative::{SpeculativeConfig, SpeculativeOutcome, draft_diverges, run_speculative};
+pub use text_stream::AssistantTextStream;
+pub use thinking::{ThinkingBudget, ThinkingBudgets};
+pub use tool_limits::{OverrunKind, OverrunStats, ToolLimit, ToolLimits, ToolOverrun};
+pub use types::{
+    AgentConfig, AgentLoopResult, ExecutedToolCall, PendingClarification, StopReason, TurnResult,
+};
+pub use validation::{validate_tool_args, validate_tool_output};
 
 #[cfg(test)]
 mod integration_tests {
@@ -373,6 +414,125 @@ mod integration_tests {
         assert_eq!(r.stop_reason, StopReason::MaxToolCalls);
     }
 
+    // ── Integration Test: Tool rate limit ────────────────────
+
+    #[tokio::test]
+    async fn integration_tool_budget_exhausted() {
+        let provider = Arc::new(MockProvider::new(vec![
+            MockProvider::tool_call("c1", "echo", r#"{"message":"one"}"#),
+            MockProvider::tool_call("c2", "echo", r#"{"message":"two"}"#),
+            MockProvider::text("Stopping there."),
+        ]));
+        let limits = ToolRateLimits::unlimited().with_tool_budget("echo", ToolBudget::new(1, 0));
+        let config = AgentConfig::new().with_tool_rate_limits(limits);
+        let (rtx, mut rrx) = tokio::sync::broadcast::channel(32);
+        let agent = AgentLoop::new(config, provider, registry_with_echo()).with_runtime_tx(rtx);
+
+        let result = agent.run("Echo twice").await;
+        assert!(result.is_ok());
+        let r = result.unwrap_or_else(|_| unreachable!());
+        assert!(r.turns[0].tool_calls[0].result.success);
+        let refused = &r.turns[1].tool_calls[0].result;
+        assert!(!refused.success);
+        assert!(
+            refused
+                .error
+                .as_deref()
+                .unwrap_or("")
+                .contains("rate limit exceeded")
+        );
+        assert_eq!(r.final_text, "Stopping there.");
+
+        let mut saw_event = false;
+        while let Ok(event) = rrx.try_recv() {
+            if let crate::runtime::RuntimeEvent::ToolBudgetExhausted {
+                name,
+                scope,
+                window,
+                limit,
+            } = event
+            {
+                assert_eq!(name, "echo");
+                assert_eq!(scope, "tool");
+                assert_eq!(window, "turn");
+                assert_eq!(limit, 1);
+                saw_event = true;
+            }
+        }
+        assert!(saw_event);
+    }
+
+    // ── Integration Test: Reflection ─────────────────────────
+
+    #[tokio::test]
+    async fn integration_reflection_revises_answer() {
+        let provider = Arc::new(MockProvider::new(vec![
+            MockProvider::tool_call("c1", "echo", r#"{"message":"42"}"#),
+            MockProvider::text("The tool said 41."),
+            MockProvider::text("VERDICT: REVISE\nThe tool said 42."),
+        ]));
+        let config = AgentConfig::new().with_reflection(true);
+        let agent = AgentLoop::new(config, provider, registry_with_echo());
+
+        let result = agent.run("What number?").await;
+        let r = result.unwrap_or_else(|_| unreachable!());
+        assert_eq!(r.final_text, "The tool said 42.");
+        assert_eq!(
+            r.reflection,
+            Some(ReflectionVerdict::Revised {
+                original: "The tool said 41.".into()
+            })
+        );
+        // Continuations see the revised answer.
+        let messages = build_messages_from_result(&r, None);
+        let last = messages.last().map(|m| format!("{:?}", m.content));
+        assert!(last.unwrap_or_default().contains("The tool said 42."));
+    }
+
+    #[tokio::test]
+    async fn integration_reflection_flag_emits_event() {
+        let provider = Arc::new(MockProvider::new(vec![
+            MockProvider::text("It will be sunny."),
+            MockProvider::text("VERDICT: FLAG\nNo weather tool was called."),
+        ]));
+        let config = AgentConfig::new().with_reflection(true);
+        let (rtx, mut rrx) = tokio::sync::broadcast::channel(32);
+        let agent = AgentLoop::new(config, provider, registry_with_echo()).with_runtime_tx(rtx);
+
+        let result = agent.run("Weather tomorrow?").await;
+        let r = result.unwrap_or_else(|_| unreachable!());
+        assert_eq!(r.final_text, "It will be sunny.");
+        assert_eq!(
+            r.reflection,
+            Some(ReflectionVerdict::Flagged {
+                reason: "No weather tool was called.".into()
+            })
+        );
+
+        let mut saw_event = false;
+        while let Ok(event) = rrx.try_recv() {
+            if let crate::runtime::RuntimeEvent::AnswerFlagged { reason } = event {
+                assert_eq!(reason, "No weather tool was called.");
+                saw_event = true;
+            }
+        }
+        assert!(saw_event);
+    }
+
+    #[tokio::test]
+    async fn integration_reflection_unparseable_keeps_answer() {
+        let provider = Arc::new(MockProvider::new(vec![
+            MockProvider::text("Hello."),
+            MockProvider::text("Seems fine."),
+        ]));
+        let config = AgentConfig::new().with_reflection(true);
+        let agent = AgentLoop::new(config, provider, registry_with_echo());
+
+        let r = agent.run("Hi").await.unwrap_or_else(|_| unreachable!());
+        assert_eq!(r.final_text, "Hello.");
+        assert_eq!(r.reflection, None);
+    }
+
     // ── Integration Test: Tool timeout ───────────────────────
 
     #[tokio::test]
@@ -640,6 +800,7 @@ mod integration_tests {
             final_text: "Final answer.".into(),
             total_usage: crate::fae_llm::usage::TokenUsage::default(),
             stop_reason: StopReason::Complete,
+            reflection: None,
         };
 
         let messages = build_messages_from_result(&result, Some("System prompt."));
@@ -0,0 +1,338 @@
+//! Context-aware bounding of tool output fed back to the model.
+//!
+//! Output over a tool's byte limit is cut middle-out: the head and tail are
+//! kept and the elided middle is annotated. When an [`OutputSummarizer`] is
+//! available (the local model), part of the budget is spent on a short
+//! summary of the elided text instead.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::events::LlmEvent;
+use crate::fae_llm::provider::ProviderAdapter;
+use crate::fae_llm::providers::message::Message;
+use crate::fae_llm::tools::types::{DEFAULT_MAX_BYTES, split_middle_out, truncate_output};
+use crate::fae_llm::types::{ReasoningLevel, RequestOptions};
+
+/// Default time allowed for summarizing elided output.
+pub const DEFAULT_SUMMARY_TIMEOUT_SECS: u64 = 20;
+
+/// Share of the byte budget reserved for the summary when summarizing.
+const SUMMARY_SHARE_PERCENT: usize = 20;
+/// Largest slice of elided text handed to the summarizer.
+const MAX_SUMMARIZER_INPUT_BYTES: usize = 32 * 1024;
+/// Rough bytes-per-token ratio used to cap summary generation length.
+const BYTES_PER_TOKEN: usize = 4;
+
+const SUMMARY_SYSTEM_PROMPT: &str = "You compress tool output for another assistant. \
+Summarize the text in a few short sentences: what it contains, notable values, errors, \
+and anything that looks important. Reply with the summary only.";
+
+/// Output limit for one tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolOutputLimit {
+    /// Maximum bytes of tool output sent back to the model.
+    pub max_bytes: usize,
+    /// Whether to summarize elided output when a summarizer is available.
+    pub summarize: bool,
+}
+
+impl Default for ToolOutputLimit {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_BYTES,
+            summarize: true,
+        }
+    }
+}
+
+/// Output limits for all tools.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolOutputLimits {
+    /// Limit for tools without their own entry.
+    pub default: ToolOutputLimit,
+    /// Limits for individual tools, keyed by tool name.
+    pub per_tool: HashMap<String, ToolOutputLimit>,
+}
+
+impl ToolOutputLimits {
+    /// Set the limit for a single tool.
+    #[must_use]
+    pub fn with_tool_limit(mut self, tool_name: impl Into<String>, limit: ToolOutputLimit) -> Self {
+        self.per_tool.insert(tool_name.into(), limit);
+        self
+    }
+
+    /// Limit for `tool_name`, falling back to the default.
+    pub fn for_tool(&self, tool_name: &str) -> ToolOutputLimit {
+        self.per_tool
+            .get(tool_name)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// Summarizes output elided by middle-out truncation.
+#[async_trait]
+pub trait OutputSummarizer: Send + Sync {
+    /// Summarize `text` produced by `tool_name` in roughly `max_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summary cannot be produced; callers fall back
+    /// to plain truncation.
+    async fn summarize(
+        &self,
+        tool_name: &str,
+        text: &str,
+        max_bytes: usize,
+    ) -> Result<String, FaeLlmError>;
+}
+
+/// [`OutputSummarizer`] backed by a provider adapter (normally the local
+/// model), called without tools or reasoning.
+pub struct ProviderSummarizer {
+    provider: Arc<dyn ProviderAdapter>,
+    timeout: Duration,
+}
+
+impl ProviderSummarizer {
+    /// Create a summarizer using `provider`.
+    pub fn new(provider: Arc<dyn ProviderAdapter>) -> Self {
+        Self {
+            provider,
+            timeout: Duration::from_secs(DEFAULT_SUMMARY_TIMEOUT_SECS),
+        }
+    }
+
+    /// Set the summary timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl OutputSummarizer for ProviderSummarizer {
+    async fn summarize(
+        &self,
+        tool_name: &str,
+        text: &str,
+        max_bytes: usize,
+    ) -> Result<String, FaeLlmError> {
+        let messages = vec![
+            Message::system(SUMMARY_SYSTEM_PROMPT),
+            Message::user(format!("Output of the `{tool_name}` tool:\n\n{text}")),
+        ];
+        let max_tokens = (max_bytes / BYTES_PER_TOKEN).clamp(32, 512) as u32;
+        let options = RequestOptions::new()
+            .with_stream(true)
+            .with_reasoning(ReasoningLevel::Off)
+            .with_temperature(0.0)
+            .with_max_tokens(max_tokens);
+
+        let collect = async {
+            let mut stream = self.provider.send(&messages, &options, &[]).await?;
+            let mut summary = String::new();
+            while let Some(event) = stream.next().await {
+                match event {
+                    LlmEvent::TextDelta { text } => summary.push_str(&text),
+                    LlmEvent::StreamError { error } => {
+                        return Err(FaeLlmError::StreamError(error));
+                    }
+                    LlmEvent::StreamEnd { .. } => break,
+                    _ => {}
+                }
+            }
+            Ok(summary)
+        };
+        tokio::time::timeout(self.timeout, collect)
+            .await
+            .map_err(|_| {
+                FaeLlmError::TimeoutError(format!(
+                    "summarizing '{tool_name}' output timed out after {}s",
+                    self.timeout.as_secs()
+                ))
+            })?
+    }
+}
+
+/// Tool output after bounding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundedOutput {
+    /// Content to send to the model.
+    pub content: String,
+    /// Whether anything was elided.
+    pub truncated: bool,
+    /// Whether the elided part was summarized.
+    pub summarized: bool,
+}
+
+/// Bound `content` to `limit`, summarizing the elided middle when allowed
+/// and a summarizer is available.
+///
+/// Summarization failures and cancellation fall back to plain middle-out
+/// truncation.
+pub async fn bound_tool_output(
+    content: &str,
+    tool_name: &str,
+    limit: ToolOutputLimit,
+    summarizer: Option<&dyn OutputSummarizer>,
+    cancel: &CancellationToken,
+) -> BoundedOutput {
+    if content.len() <= limit.max_bytes {
+        return BoundedOutput {
+            content: content.to_owned(),
+            truncated: false,
+            summarized: false,
+        };
+    }
+
+    if limit.summarize
+        && let Some(summarizer) = summarizer
+    {
+        let summary_budget = limit.max_bytes * SUMMARY_SHARE_PERCENT / 100;
+        let split = split_middle_out(content, limit.max_bytes - summary_budget);
+        let (input, _) = truncate_output(split.elided, MAX_SUMMARIZER_INPUT_BYTES);
+        let summary = tokio::select! {
+            _ = cancel.cancelled() => None,
+            result = summarizer.summarize(tool_name, &input, summary_budget) => match result {
+                Ok(summary) => Some(summary),
+                Err(e) => {
+                    tracing::warn!(tool_name, error = %e, "Tool output summary failed; truncating");
+                    None
+                }
+            },
+        };
+        if let Some(summary) = summary.filter(|s| !s.trim().is_empty()) {
+            let summary = clip(summary.trim(), summary_budget);
+            return BoundedOutput {
+                content: split.render(&split.marker(limit.max_bytes, Some(summary))),
+                truncated: true,
+                summarized: true,
+            };
+        }
+    }
+
+    let (content, truncated) = truncate_output(content, limit.max_bytes);
+    BoundedOutput {
+        content,
+        truncated,
+        summarized: false,
+    }
+}
+
+fn clip(s: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(s.len());
+    while end > 0 && !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    struct FixedSummarizer(Result<&'static str, ()>);
+
+    #[async_trait]
+    impl OutputSummarizer for FixedSummarizer {
+        async fn summarize(
+            &self,
+            _tool_name: &str,
+            text: &str,
+            _max_bytes: usize,
+        ) -> Result<String, FaeLlmError> {
+            assert!(!text.is_empty());
+            self.0
+                .map(str::to_owned)
+                .map_err(|()| FaeLlmError::ProviderError("model busy".into()))
+        }
+    }
+
+    fn limit(max_bytes: usize) -> ToolOutputLimit {
+        ToolOutputLimit {
+            max_bytes,
+            summarize: true,
+        }
+    }
+
+    fn long_output() -> String {
+        (0..200).map(|i| format!("row {i:03}\n")).collect()
+    }
+
+    #[tokio::test]
+    async fn short_output_is_unchanged() {
+        let out = bound_tool_output("ok", "bash", limit(10), None, &CancellationToken::new()).await;
+        assert_eq!(out.content, "ok");
+        assert!(!out.truncated);
+    }
+
+    #[tokio::test]
+    async fn summary_replaces_part_of_the_budget() {
+        let summarizer = FixedSummarizer(Ok("rows 010 to 190, no errors"));
+        let out = bound_tool_output(
+            &long_output(),
+            "bash",
+            limit(200),
+            Some(&summarizer),
+            &CancellationToken::new(),
+        )
+        .await;
+        assert!(out.truncated && out.summarized);
+        assert!(out.content.starts_with("row 000\n"));
+        assert!(out.content.ends_with("row 199\n"));
+        assert!(
+            out.content
+                .contains("summary of elided output: rows 010 to 190, no errors]")
+        );
+    }
+
+    #[tokio::test]
+    async fn summarizer_failure_falls_back_to_truncation() {
+        let out = bound_tool_output(
+            &long_output(),
+            "bash",
+            limit(200),
+            Some(&FixedSummarizer(Err(()))),
+            &CancellationToken::new(),
+        )
+        .await;
+        assert!(out.truncated);
+        assert!(!out.summarized);
+        assert!(out.content.contains("elided from the middle]"));
+    }
+
+    #[tokio::test]
+    async fn summarize_disabled_per_tool() {
+        let limits = ToolOutputLimits::default().with_tool_limit(
+            "read",
+            ToolOutputLimit {
+                max_bytes: 200,
+                summarize: false,
+            },
+        );
+        let out = bound_tool_output(
+            &long_output(),
+            "read",
+            limits.for_tool("read"),
+            Some(&FixedSummarizer(Ok("unused"))),
+            &CancellationToken::new(),
+        )
+        .await;
+        assert!(out.truncated);
+        assert!(!out.summarized);
+        assert_eq!(limits.for_tool("bash"), ToolOutputLimit::default());
+    }
+}
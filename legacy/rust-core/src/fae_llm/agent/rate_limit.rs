@@ -0,0 +1,391 @@
+//! Per-tool and global rate limits for tool execution.
+//!
+//! Budgets are counted per user turn (one agent run, across all of its
+//! provider round-trips) and per rolling minute. Minute windows live in a
+//! [`ToolCallHistory`] that can be shared between agent loops, so
+//! back-to-back runs and background agents draw from the same budget.
+//!
+//! A limit of `0` means "unlimited".
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Default global tool-call budget per user turn.
+pub const DEFAULT_GLOBAL_CALLS_PER_TURN: u32 = 24;
+/// Default global tool-call budget per rolling minute.
+pub const DEFAULT_GLOBAL_CALLS_PER_MINUTE: u32 = 60;
+
+const MINUTE: Duration = Duration::from_secs(60);
+
+/// Call budget for a single scope (one tool, or all tools).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolBudget {
+    /// Maximum calls per user turn (`0` = unlimited).
+    #[serde(default)]
+    pub per_turn: u32,
+    /// Maximum calls per rolling minute (`0` = unlimited).
+    #[serde(default)]
+    pub per_minute: u32,
+}
+
+impl ToolBudget {
+    /// A budget that never runs out.
+    pub const UNLIMITED: Self = Self::new(0, 0);
+
+    /// Create a budget with the given per-turn and per-minute limits.
+    pub const fn new(per_turn: u32, per_minute: u32) -> Self {
+        Self {
+            per_turn,
+            per_minute,
+        }
+    }
+}
+
+/// Rate-limit configuration for the tool executor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolRateLimits {
+    /// Budget shared by all tools.
+    pub global: ToolBudget,
+    /// Budgets for individual tools, keyed by tool name.
+    pub per_tool: HashMap<String, ToolBudget>,
+}
+
+impl Default for ToolRateLimits {
+    fn default() -> Self {
+        let per_tool = HashMap::from([
+            ("web_search".to_owned(), ToolBudget::new(6, 20)),
+            ("fetch_url".to_owned(), ToolBudget::new(8, 30)),
+        ]);
+        Self {
+            global: ToolBudget::new(
+                DEFAULT_GLOBAL_CALLS_PER_TURN,
+                DEFAULT_GLOBAL_CALLS_PER_MINUTE,
+            ),
+            per_tool,
+        }
+    }
+}
+
+impl ToolRateLimits {
+    /// Limits that never refuse a call.
+    pub fn unlimited() -> Self {
+        Self {
+            global: ToolBudget::UNLIMITED,
+            per_tool: HashMap::new(),
+        }
+    }
+
+    /// Set the global budget.
+    #[must_use]
+    pub fn with_global(mut self, budget: ToolBudget) -> Self {
+        self.global = budget;
+        self
+    }
+
+    /// Set the budget for a single tool.
+    #[must_use]
+    pub fn with_tool_budget(mut self, tool_name: impl Into<String>, budget: ToolBudget) -> Self {
+        self.per_tool.insert(tool_name.into(), budget);
+        self
+    }
+
+    /// Budget for `tool_name`, or [`ToolBudget::UNLIMITED`] if none is set.
+    pub fn budget_for(&self, tool_name: &str) -> ToolBudget {
+        self.per_tool
+            .get(tool_name)
+            .copied()
+            .unwrap_or(ToolBudget::UNLIMITED)
+    }
+}
+
+/// The window a budget is counted over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetWindow {
+    /// One user turn.
+    Turn,
+    /// A rolling 60-second window.
+    Minute,
+}
+
+impl BudgetWindow {
+    /// Stable string form for events and logs.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Turn => "turn",
+            Self::Minute => "minute",
+        }
+    }
+}
+
+/// A tool call refused because a budget ran out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetExhausted {
+    /// The tool that was refused.
+    pub tool_name: String,
+    /// `true` when the global budget ran out, `false` for the tool's own.
+    pub global: bool,
+    /// Window the exhausted budget is counted over.
+    pub window: BudgetWindow,
+    /// The limit that was reached.
+    pub limit: u32,
+}
+
+impl BudgetExhausted {
+    /// `"global"` or `"tool"`.
+    pub fn scope(&self) -> &'static str {
+        if self.global { "global" } else { "tool" }
+    }
+}
+
+impl std::fmt::Display for BudgetExhausted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let budget = if self.global {
+            "the shared tool budget".to_owned()
+        } else {
+            format!("the '{}' budget", self.tool_name)
+        };
+        let advice = match self.window {
+            BudgetWindow::Turn => "do not call it again this turn",
+            BudgetWindow::Minute => "wait before calling it again",
+        };
+        write!(
+            f,
+            "tool '{}': rate limit exceeded ({budget} of {} calls per {} is used up); \
+             {advice} and answer with the information you already have",
+            self.tool_name,
+            self.limit,
+            self.window.as_str()
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct MinuteWindows {
+    all: VecDeque<Instant>,
+    by_tool: HashMap<String, VecDeque<Instant>>,
+}
+
+/// Timestamps of recent tool calls, for per-minute budgets.
+///
+/// Share one instance (via `Arc`) between agent loops that should draw
+/// from the same per-minute budget.
+#[derive(Debug, Default)]
+pub struct ToolCallHistory {
+    windows: Mutex<MinuteWindows>,
+}
+
+impl ToolCallHistory {
+    /// Create an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Default)]
+struct TurnCounts {
+    total: u32,
+    by_tool: HashMap<String, u32>,
+}
+
+/// Enforces [`ToolRateLimits`] for one agent loop.
+///
+/// Per-turn counts are owned by the limiter and reset by
+/// [`begin_turn`](Self::begin_turn); per-minute counts come from the shared
+/// [`ToolCallHistory`].
+#[derive(Debug)]
+pub struct ToolRateLimiter {
+    limits: ToolRateLimits,
+    history: Arc<ToolCallHistory>,
+    turn: Mutex<TurnCounts>,
+}
+
+impl ToolRateLimiter {
+    /// Create a limiter backed by `history`.
+    pub fn new(limits: ToolRateLimits, history: Arc<ToolCallHistory>) -> Self {
+        Self {
+            limits,
+            history,
+            turn: Mutex::new(TurnCounts::default()),
+        }
+    }
+
+    /// The configured limits.
+    pub fn limits(&self) -> &ToolRateLimits {
+        &self.limits
+    }
+
+    /// Reset per-turn counts at the start of a user turn.
+    pub fn begin_turn(&self) {
+        let mut turn = self.turn.lock().unwrap_or_else(|e| e.into_inner());
+        *turn = TurnCounts::default();
+    }
+
+    /// Record a call to `tool_name` if every budget allows it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BudgetExhausted`] for the first budget that would be
+    /// exceeded. Refused calls are not counted.
+    pub fn try_acquire(&self, tool_name: &str) -> Result<(), BudgetExhausted> {
+        self.try_acquire_at(tool_name, Instant::now())
+    }
+
+    fn try_acquire_at(&self, tool_name: &str, now: Instant) -> Result<(), BudgetExhausted> {
+        let tool_budget = self.limits.budget_for(tool_name);
+        let global_budget = self.limits.global;
+        let exhausted = |global: bool, window: BudgetWindow, limit: u32| BudgetExhausted {
+            tool_name: tool_name.to_owned(),
+            global,
+            window,
+            limit,
+        };
+        let over = |count: usize, limit: u32| limit > 0 && count >= limit as usize;
+
+        let mut turn = self.turn.lock().unwrap_or_else(|e| e.into_inner());
+        let tool_turn = turn.by_tool.get(tool_name).copied().unwrap_or(0);
+        if over(tool_turn as usize, tool_budget.per_turn) {
+            return Err(exhausted(false, BudgetWindow::Turn, tool_budget.per_turn));
+        }
+        if over(turn.total as usize, global_budget.per_turn) {
+            return Err(exhausted(true, BudgetWindow::Turn, global_budget.per_turn));
+        }
+
+        let mut guard = self
+            .history
+            .windows
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let windows = &mut *guard;
+        prune(&mut windows.all, now);
+        let tool_window = windows.by_tool.entry(tool_name.to_owned()).or_default();
+        prune(tool_window, now);
+        if over(tool_window.len(), tool_budget.per_minute) {
+            return Err(exhausted(
+                false,
+                BudgetWindow::Minute,
+                tool_budget.per_minute,
+            ));
+        }
+        if over(windows.all.len(), global_budget.per_minute) {
+            return Err(exhausted(
+                true,
+                BudgetWindow::Minute,
+                global_budget.per_minute,
+            ));
+        }
+        tool_window.push_back(now);
+        windows.all.push_back(now);
+
+        turn.total += 1;
+        *turn.by_tool.entry(tool_name.to_owned()).or_insert(0) += 1;
+        Ok(())
+    }
+}
+
+fn prune(window: &mut VecDeque<Instant>, now: Instant) {
+    while window
+        .front()
+        .is_some_and(|&t| now.duration_since(t) >= MINUTE)
+    {
+        window.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn limiter(limits: ToolRateLimits) -> ToolRateLimiter {
+        ToolRateLimiter::new(limits, Arc::new(ToolCallHistory::new()))
+    }
+
+    #[test]
+    fn per_tool_turn_budget_resets_each_turn() {
+        let limiter = limiter(
+            ToolRateLimits::unlimited().with_tool_budget("web_search", ToolBudget::new(2, 0)),
+        );
+        assert!(limiter.try_acquire("web_search").is_ok());
+        assert!(limiter.try_acquire("web_search").is_ok());
+        let err = limiter.try_acquire("web_search").unwrap_err();
+        assert!(!err.global);
+        assert_eq!(err.window, BudgetWindow::Turn);
+        assert_eq!(err.limit, 2);
+        assert!(limiter.try_acquire("read").is_ok());
+
+        limiter.begin_turn();
+        assert!(limiter.try_acquire("web_search").is_ok());
+    }
+
+    #[test]
+    fn global_turn_budget_spans_tools() {
+        let limiter = limiter(ToolRateLimits::unlimited().with_global(ToolBudget::new(2, 0)));
+        assert!(limiter.try_acquire("read").is_ok());
+        assert!(limiter.try_acquire("web_search").is_ok());
+        let err = limiter.try_acquire("bash").unwrap_err();
+        assert!(err.global);
+        assert_eq!(err.scope(), "global");
+    }
+
+    #[test]
+    fn minute_budget_is_shared_and_rolls_over() {
+        let history = Arc::new(ToolCallHistory::new());
+        let limits =
+            ToolRateLimits::unlimited().with_tool_budget("web_search", ToolBudget::new(0, 2));
+        let first = ToolRateLimiter::new(limits.clone(), Arc::clone(&history));
+        let second = ToolRateLimiter::new(limits, Arc::clone(&history));
+
+        let start = Instant::now();
+        assert!(first.try_acquire_at("web_search", start).is_ok());
+        assert!(second.try_acquire_at("web_search", start).is_ok());
+        let err = second.try_acquire_at("web_search", start).unwrap_err();
+        assert_eq!(err.window, BudgetWindow::Minute);
+
+        let later = start + MINUTE;
+        assert!(second.try_acquire_at("web_search", later).is_ok());
+    }
+
+    #[test]
+    fn refused_calls_are_not_counted() {
+        let limiter = limiter(
+            ToolRateLimits::unlimited()
+                .with_global(ToolBudget::new(0, 2))
+                .with_tool_budget("web_search", ToolBudget::new(0, 1)),
+        );
+        let now = Instant::now();
+        assert!(limiter.try_acquire_at("web_search", now).is_ok());
+        assert!(limiter.try_acquire_at("web_search", now).is_err());
+        assert!(limiter.try_acquire_at("read", now).is_ok());
+        let err = limiter.try_acquire_at("read", now).unwrap_err();
+        assert!(err.global);
+    }
+
+    #[test]
+    fn error_message_tells_model_to_stop() {
+        let err = BudgetExhausted {
+            tool_name: "web_search".to_owned(),
+            global: false,
+            window: BudgetWindow::Turn,
+            limit: 6,
+        };
+        let text = err.to_string();
+        assert!(text.contains("rate limit exceeded"));
+        assert!(text.contains("6 calls per turn"));
+        assert!(text.contains("do not call it again this turn"));
+    }
+
+    #[test]
+    fn limits_deserialize_with_defaults() {
+        let limits: ToolRateLimits =
+            serde_json::from_str(r#"{"per_tool":{"web_search":{"per_turn":3}}}"#).unwrap();
+        assert_eq!(limits.budget_for("web_search"), ToolBudget::new(3, 0));
+        assert_eq!(limits.global, ToolRateLimits::default().global);
+        assert_eq!(limits.budget_for("read"), ToolBudget::UNLIMITED);
+    }
+}
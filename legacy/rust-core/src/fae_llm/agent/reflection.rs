@@ -0,0 +1,263 @@
+//! Optional self-review of the agent's final answer.
+//!
+//! When [`AgentConfig::reflection`](super::types::AgentConfig::reflection) is
+//! enabled, the loop runs one cheap critique pass over the final answer with
+//! the same (local) provider: no tools, no reasoning, temperature zero. The
+//! critic checks the answer against the configured criteria and the tool
+//! output it was based on, then accepts it, rewrites it, or flags a problem
+//! it cannot fix. Critique failures never fail the run.
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use super::types::TurnResult;
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::events::LlmEvent;
+use crate::fae_llm::provider::ProviderAdapter;
+use crate::fae_llm::providers::message::Message;
+use crate::fae_llm::tools::types::truncate_output;
+use crate::fae_llm::types::{ReasoningLevel, RequestOptions};
+
+/// Default time allowed for the critique pass.
+pub const DEFAULT_REFLECTION_TIMEOUT_SECS: u64 = 15;
+
+/// Criteria checked when none are configured.
+pub const DEFAULT_REFLECTION_CRITERIA: &[&str] = &[
+    "The answer addresses what the user actually asked.",
+    "Every fact attributed to a tool appears in that tool's output; nothing is invented.",
+    "The answer does not claim an action succeeded unless a tool result shows it did.",
+];
+
+/// Bytes of each tool result shown to the critic.
+const MAX_TOOL_EVIDENCE_BYTES: usize = 2048;
+/// Generation cap for the critique reply.
+const MAX_CRITIQUE_TOKENS: u32 = 512;
+
+const CRITIC_SYSTEM_PROMPT: &str = "You review an assistant's final answer before it is \
+delivered. Check it against each criterion using only the user request and tool results \
+shown. Reply in exactly one of these forms:\n\
+VERDICT: OK\n\
+VERDICT: REVISE, then the corrected answer on the following lines\n\
+VERDICT: FLAG, then one sentence describing a problem you cannot fix";
+
+/// Settings for the reflection pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReflectionConfig {
+    /// Whether to critique the final answer.
+    pub enabled: bool,
+    /// Criteria the answer is checked against.
+    pub criteria: Vec<String>,
+    /// Timeout for the critique request in seconds.
+    pub timeout_secs: u64,
+}
+
+impl Default for ReflectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            criteria: DEFAULT_REFLECTION_CRITERIA
+                .iter()
+                .map(|c| (*c).to_owned())
+                .collect(),
+            timeout_secs: DEFAULT_REFLECTION_TIMEOUT_SECS,
+        }
+    }
+}
+
+/// Outcome of the reflection pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum ReflectionVerdict {
+    /// The answer met every criterion.
+    Approved,
+    /// The critic rewrote the answer; the result holds the revision.
+    Revised {
+        /// The answer before revision.
+        original: String,
+    },
+    /// The critic found a problem it could not fix.
+    Flagged {
+        /// Short description of the problem.
+        reason: String,
+    },
+}
+
+/// A parsed critic reply, before it is applied to the run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Critique {
+    /// Keep the answer.
+    Ok,
+    /// Replace the answer with this text.
+    Revise(String),
+    /// Keep the answer but report this problem.
+    Flag(String),
+}
+
+/// Parse a critic reply. Returns `None` when the reply does not follow the
+/// expected format or a revision/flag has no body.
+pub fn parse_critique(reply: &str) -> Option<Critique> {
+    let reply = reply.trim();
+    let (first, rest) = reply.split_once('\n').unwrap_or((reply, ""));
+    let verdict = first.trim().strip_prefix("VERDICT:")?.trim();
+    let (keyword, inline) = verdict
+        .split_once(char::is_whitespace)
+        .unwrap_or((verdict, ""));
+    let body = format!("{} {}", inline.trim(), rest.trim());
+    let body = body.trim();
+    match keyword
+        .trim_end_matches(['.', ',', ':'])
+        .to_ascii_uppercase()
+        .as_str()
+    {
+        "OK" => Some(Critique::Ok),
+        "REVISE" if !body.is_empty() => Some(Critique::Revise(body.to_owned())),
+        "FLAG" if !body.is_empty() => Some(Critique::Flag(body.to_owned())),
+        _ => None,
+    }
+}
+
+/// Ask `provider` to critique `answer` to `request`, given the tool calls
+/// executed in `turns`.
+///
+/// Returns `Ok(None)` if cancelled or the reply cannot be parsed.
+///
+/// # Errors
+///
+/// Returns an error if the provider request fails or times out.
+pub async fn critique_answer(
+    provider: &dyn ProviderAdapter,
+    config: &ReflectionConfig,
+    request: &str,
+    answer: &str,
+    turns: &[TurnResult],
+    cancel: &CancellationToken,
+) -> Result<Option<Critique>, FaeLlmError> {
+    let messages = vec![
+        Message::system(CRITIC_SYSTEM_PROMPT),
+        Message::user(critique_prompt(&config.criteria, request, answer, turns)),
+    ];
+    let options = RequestOptions::new()
+        .with_stream(true)
+        .with_reasoning(ReasoningLevel::Off)
+        .with_temperature(0.0)
+        .with_max_tokens(MAX_CRITIQUE_TOKENS);
+
+    let collect = async {
+        let mut stream = provider.send(&messages, &options, &[]).await?;
+        let mut reply = String::new();
+        while let Some(event) = stream.next().await {
+            match event {
+                LlmEvent::TextDelta { text } => reply.push_str(&text),
+                LlmEvent::StreamError { error } => return Err(FaeLlmError::StreamError(error)),
+                LlmEvent::StreamEnd { .. } => break,
+                _ => {}
+            }
+        }
+        Ok(reply)
+    };
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let reply = tokio::select! {
+        _ = cancel.cancelled() => return Ok(None),
+        result = tokio::time::timeout(timeout, collect) => result.map_err(|_| {
+            FaeLlmError::TimeoutError(format!(
+                "answer critique timed out after {}s",
+                config.timeout_secs
+            ))
+        })??,
+    };
+    let critique = parse_critique(&reply);
+    if critique.is_none() {
+        tracing::debug!(reply = %reply, "Unparseable critique reply; keeping answer");
+    }
+    Ok(critique)
+}
+
+fn critique_prompt(
+    criteria: &[String],
+    request: &str,
+    answer: &str,
+    turns: &[TurnResult],
+) -> String {
+    let mut prompt = String::from("Criteria:\n");
+    for criterion in criteria {
+        prompt.push_str(&format!("- {criterion}\n"));
+    }
+    prompt.push_str(&format!("\nUser request:\n{request}\n\nTool results:\n"));
+    let mut any_tools = false;
+    for call in turns.iter().flat_map(|turn| &turn.tool_calls) {
+        any_tools = true;
+        let output = if call.result.success {
+            truncate_output(&call.result.content, MAX_TOOL_EVIDENCE_BYTES).0
+        } else {
+            format!(
+                "error: {}",
+                call.result.error.as_deref().unwrap_or("unknown")
+            )
+        };
+        prompt.push_str(&format!(
+            "- {}({}): {output}\n",
+            call.function_name, call.arguments
+        ));
+    }
+    if !any_tools {
+        prompt.push_str("(no tools were called)\n");
+    }
+    prompt.push_str(&format!("\nFinal answer:\n{answer}"));
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::agent::types::ExecutedToolCall;
+    use crate::fae_llm::events::FinishReason;
+    use crate::fae_llm::tools::types::ToolResult;
+
+    #[test]
+    fn parse_critique_forms() {
+        assert_eq!(parse_critique("VERDICT: OK"), Some(Critique::Ok));
+        assert_eq!(parse_critique("  VERDICT: ok.\n"), Some(Critique::Ok));
+        assert_eq!(
+            parse_critique("VERDICT: REVISE\nParis is in France."),
+            Some(Critique::Revise("Paris is in France.".into()))
+        );
+        assert_eq!(
+            parse_critique("VERDICT: FLAG: the weather tool returned nothing"),
+            Some(Critique::Flag("the weather tool returned nothing".into()))
+        );
+        assert_eq!(parse_critique("VERDICT: REVISE"), None);
+        assert_eq!(parse_critique("Looks fine to me."), None);
+    }
+
+    #[test]
+    fn prompt_includes_tool_evidence() {
+        let turns = vec![TurnResult {
+            text: String::new(),
+            thinking: String::new(),
+            tool_calls: vec![ExecutedToolCall {
+                call_id: "c1".into(),
+                function_name: "read".into(),
+                arguments: serde_json::json!({"path": "notes.txt"}),
+                result: ToolResult::success("buy milk".into()),
+                duration_ms: 1,
+            }],
+            finish_reason: FinishReason::ToolCalls,
+            usage: None,
+        }];
+        let prompt = critique_prompt(
+            &ReflectionConfig::default().criteria,
+            "What's in my notes?",
+            "Your notes say buy eggs.",
+            &turns,
+        );
+        assert!(prompt.contains("- read({\"path\":\"notes.txt\"}): buy milk"));
+        assert!(prompt.contains("nothing is invented"));
+        assert!(prompt.ends_with("Final answer:\nYour notes say buy eggs."));
+    }
+}
@@ -0,0 +1,297 @@
+//! Experimental speculative generation: speak a fast draft, verify it with a
+//! slower authoritative model, and correct when they disagree.
+//!
+//! The draft loop (normally the local model) streams clauses to speech as
+//! soon as they form while the verifier loop generates the answer that is
+//! kept in history. When both finish, the spoken draft is compared with the
+//! verified answer; if it diverges materially, the rest of the draft is cut
+//! off and a short correction followed by the verified answer is streamed.
+//!
+//! Both loops receive the same messages. The verifier never streams, so a
+//! slow verifier only delays the correction, never the first audio.
+//!
+//! Enabled by `[experimental.speculative]` in the LLM config, which names
+//! the remote verifier; the voice engine runs it for turns without tools.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tokio::sync::mpsc;
+
+use super::loop_engine::AgentLoop;
+use super::types::{AgentLoopResult, StopReason};
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::providers::message::Message;
+
+/// Default word-overlap below which a draft counts as divergent.
+pub const DEFAULT_DIVERGENCE_THRESHOLD: f32 = 0.3;
+
+/// Message catalog key of the phrase spoken before the verified answer
+/// when the draft was wrong.
+pub const CORRECTION_KEY: &str = "conversation.speculative_correction";
+
+/// Settings for speculative generation (`[experimental.speculative]`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeculativeConfig {
+    /// Speak a local draft while `provider` verifies it.
+    pub enabled: bool,
+    /// Remote provider ID that produces the verified answer.
+    pub provider: Option<String>,
+    /// Model for `provider`; its first model when unset.
+    pub model: Option<String>,
+    /// Word-overlap (Jaccard, 0.0–1.0) below which the draft is corrected.
+    pub divergence_threshold: f32,
+}
+
+impl Default for SpeculativeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: None,
+            model: None,
+            divergence_threshold: DEFAULT_DIVERGENCE_THRESHOLD,
+        }
+    }
+}
+
+/// Result of a speculative run.
+#[derive(Debug, Clone)]
+pub struct SpeculativeOutcome {
+    /// The draft loop's result; its text was spoken.
+    pub draft: AgentLoopResult,
+    /// The verifier loop's result, or `None` if it failed or did not complete.
+    pub verified: Option<AgentLoopResult>,
+    /// Whether a correction was streamed after the draft.
+    pub corrected: bool,
+    /// Text to record in history: the verified answer when available,
+    /// otherwise the draft.
+    pub final_text: String,
+}
+
+/// Whether `draft` differs materially from `verified`.
+///
+/// Answers diverge when they cite different numbers or when their word
+/// overlap falls below `threshold`. An empty draft never diverges.
+pub fn draft_diverges(draft: &str, verified: &str, threshold: f32) -> bool {
+    let draft_words = words(draft);
+    let verified_words = words(verified);
+    if draft_words.is_empty() || verified_words.is_empty() {
+        return false;
+    }
+
+    let numbers = |set: &HashSet<String>| -> HashSet<String> {
+        set.iter()
+            .filter(|w| w.chars().any(|c| c.is_ascii_digit()))
+            .cloned()
+            .collect()
+    };
+    if numbers(&draft_words) != numbers(&verified_words) {
+        return true;
+    }
+
+    let shared = draft_words.intersection(&verified_words).count();
+    let total = draft_words.union(&verified_words).count();
+    (shared as f32 / total as f32) < threshold
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '.')
+        .map(|w| w.trim_matches('.').to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Run `draft` and `verifier` concurrently on `messages`.
+///
+/// Draft clauses go to `clause_tx` as they are produced. If the verified
+/// answer diverges from the draft, `stop_draft` is called to cut off the
+/// draft still being spoken, then the localized correction phrase
+/// ([`CORRECTION_KEY`]) and the verified answer are sent. A failed or
+/// incomplete verification leaves the draft standing.
+///
+/// # Errors
+///
+/// Returns the draft loop's error if the draft request fails; the draft is
+/// what the user hears, so without it there is nothing to verify.
+pub async fn run_speculative(
+    draft: &AgentLoop,
+    verifier: &AgentLoop,
+    messages: Vec<Message>,
+    clause_tx: mpsc::Sender<String>,
+    config: &SpeculativeConfig,
+    stop_draft: impl FnOnce(),
+) -> Result<SpeculativeOutcome, FaeLlmError> {
+    let (draft_result, verified_result) = tokio::join!(
+        draft.run_with_messages_streaming(messages.clone(), clause_tx.clone()),
+        verifier.run_with_messages(messages),
+    );
+    let draft_result = draft_result?;
+
+    let verified = match verified_result {
+        Ok(result) if result.stop_reason == StopReason::Complete => Some(result),
+        Ok(result) => {
+            tracing::warn!(stop_reason = %result.stop_reason, "Speculative verify incomplete; keeping draft");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Speculative verify failed; keeping draft");
+            None
+        }
+    };
+
+    let Some(verified) = verified else {
+        return Ok(SpeculativeOutcome {
+            final_text: draft_result.final_text.clone(),
+            draft: draft_result,
+            verified: None,
+            corrected: false,
+        });
+    };
+
+    let corrected = draft_diverges(
+        &draft_result.final_text,
+        &verified.final_text,
+        config.divergence_threshold,
+    );
+    if corrected {
+        tracing::info!("Speculative draft diverged from verified answer; correcting");
+        stop_draft();
+        let correction = crate::i18n::text(CORRECTION_KEY).to_owned();
+        let _ = clause_tx.send(correction).await;
+        let _ = clause_tx.send(verified.final_text.clone()).await;
+    }
+
+    Ok(SpeculativeOutcome {
+        final_text: verified.final_text.clone(),
+        draft: draft_result,
+        verified: Some(verified),
+        corrected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::fae_llm::agent::types::AgentConfig;
+    use crate::fae_llm::config::types::ToolMode;
+    use crate::fae_llm::events::{FinishReason, LlmEvent};
+    use crate::fae_llm::provider::{LlmEventStream, ProviderAdapter, ToolDefinition};
+    use crate::fae_llm::tools::registry::ToolRegistry;
+    use crate::fae_llm::types::{ModelRef, RequestOptions};
+
+    struct TextProvider(Mutex<Option<&'static str>>);
+
+    #[async_trait]
+    impl ProviderAdapter for TextProvider {
+        fn name(&self) -> &str {
+            "text"
+        }
+
+        async fn send(
+            &self,
+            _messages: &[Message],
+            _options: &RequestOptions,
+            _tools: &[ToolDefinition],
+        ) -> Result<LlmEventStream, FaeLlmError> {
+            let text = self.0.lock().unwrap().take().unwrap_or_default();
+            let events = vec![
+                LlmEvent::StreamStart {
+                    request_id: "req".into(),
+                    model: ModelRef::new("mock"),
+                },
+                LlmEvent::TextDelta { text: text.into() },
+                LlmEvent::StreamEnd {
+                    finish_reason: FinishReason::Stop,
+                },
+            ];
+            Ok(Box::pin(futures_util::stream::iter(events)))
+        }
+    }
+
+    fn agent(text: &'static str) -> AgentLoop {
+        AgentLoop::new(
+            AgentConfig::new(),
+            Arc::new(TextProvider(Mutex::new(Some(text)))),
+            Arc::new(ToolRegistry::new(ToolMode::ReadOnly)),
+        )
+    }
+
+    /// The outcome, the clauses sent to speech, and whether the draft was
+    /// cut off.
+    async fn run(
+        draft: &'static str,
+        verified: &'static str,
+    ) -> (SpeculativeOutcome, Vec<String>, bool) {
+        let (tx, mut rx) = mpsc::channel(16);
+        let mut stopped = false;
+        let outcome = run_speculative(
+            &agent(draft),
+            &agent(verified),
+            vec![Message::user("question")],
+            tx,
+            &SpeculativeConfig::default(),
+            || stopped = true,
+        )
+        .await
+        .unwrap();
+        let mut spoken = Vec::new();
+        while let Ok(clause) = rx.try_recv() {
+            spoken.push(clause);
+        }
+        (outcome, spoken, stopped)
+    }
+
+    #[test]
+    fn divergence_checks_numbers_and_overlap() {
+        assert!(!draft_diverges(
+            "The meeting is at 3 pm tomorrow.",
+            "Your meeting is tomorrow at 3 pm.",
+            DEFAULT_DIVERGENCE_THRESHOLD
+        ));
+        assert!(draft_diverges(
+            "The meeting is at 3 pm tomorrow.",
+            "The meeting is at 4 pm tomorrow.",
+            DEFAULT_DIVERGENCE_THRESHOLD
+        ));
+        assert!(draft_diverges(
+            "Paris is the capital of France.",
+            "I could not find that in your notes.",
+            DEFAULT_DIVERGENCE_THRESHOLD
+        ));
+        assert!(!draft_diverges(
+            "",
+            "Anything.",
+            DEFAULT_DIVERGENCE_THRESHOLD
+        ));
+    }
+
+    #[tokio::test]
+    async fn matching_draft_is_not_corrected() {
+        let (outcome, spoken, stopped) = run("It is 12 degrees.", "It is 12 degrees today.").await;
+        assert!(!outcome.corrected);
+        assert!(!stopped);
+        assert_eq!(outcome.final_text, "It is 12 degrees today.");
+        assert_eq!(spoken, vec!["It is 12 degrees."]);
+    }
+
+    #[tokio::test]
+    async fn divergent_draft_is_corrected() {
+        let (outcome, spoken, stopped) = run("It is 12 degrees.", "It is 18 degrees.").await;
+        assert!(outcome.corrected);
+        assert!(stopped, "the rest of the draft is cut off");
+        assert_eq!(
+            spoken,
+            vec![
+                "It is 12 degrees.",
+                crate::i18n::text(CORRECTION_KEY),
+                "It is 18 degrees."
+            ]
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Word-level assistant text streaming to the host UI.
+//!
+//! Provider text deltas are arbitrary token fragments. [`AssistantTextStream`]
+//! regroups them into whole words (splitting only after whitespace) and
+//! emits them as [`RuntimeEvent::AssistantTextDelta`], so hosts can render
+//! the raw markdown progressively without ever showing half a word or a
+//! half-typed `**`. Delivery tags (`[whisper]`) are for TTS and are left out.
+
+use crate::runtime::RuntimeEvent;
+use tokio::sync::broadcast;
+
+/// Streams one assistant message to the runtime event channel.
+pub struct AssistantTextStream {
+    runtime_tx: broadcast::Sender<RuntimeEvent>,
+    message_id: String,
+    pending: String,
+    emitted: bool,
+}
+
+impl AssistantTextStream {
+    /// Start streaming the message `message_id`.
+    pub fn new(runtime_tx: broadcast::Sender<RuntimeEvent>, message_id: String) -> Self {
+        Self {
+            runtime_tx,
+            message_id,
+            pending: String::new(),
+            emitted: false,
+        }
+    }
+
+    /// Append a provider text delta, emitting every completed word.
+    pub fn push(&mut self, text: &str) {
+        self.pending.push_str(text);
+        let Some(split) = self
+            .pending
+            .char_indices()
+            .filter(|(_, c)| c.is_whitespace())
+            .map(|(i, c)| i + c.len_utf8())
+            .last()
+        else {
+            return;
+        };
+        let rest = self.pending.split_off(split);
+        let delta = std::mem::replace(&mut self.pending, rest);
+        self.send(delta, false);
+    }
+
+    /// End the message, emitting any partial word as the final delta.
+    ///
+    /// Nothing is sent for a message that never had text (a tool-only turn).
+    pub fn finish(&mut self) {
+        if !self.emitted && self.pending.is_empty() {
+            return;
+        }
+        let delta = std::mem::take(&mut self.pending);
+        self.send(delta, true);
+        self.emitted = false;
+    }
+
+    /// Drop everything streamed so far; the message restarts from empty.
+    pub fn discard(&mut self) {
+        self.pending.clear();
+        if std::mem::take(&mut self.emitted) {
+            let _ = self.runtime_tx.send(RuntimeEvent::AssistantTextDiscarded {
+                message_id: self.message_id.clone(),
+            });
+        }
+    }
+
+    fn send(&mut self, delta: String, is_final: bool) {
+        let (shown, _) = crate::tts::style::take_style_tags(&delta);
+        let delta = if shown == delta {
+            delta
+        } else if shown.is_empty() {
+            if !is_final {
+                return;
+            }
+            shown
+        } else {
+            // Keep the word separator the tag removal trimmed off.
+            let separator = &delta[delta.trim_end().len()..];
+            format!("{shown}{separator}")
+        };
+        self.emitted = true;
+        let _ = self.runtime_tx.send(RuntimeEvent::AssistantTextDelta {
+            message_id: self.message_id.clone(),
+            delta,
+            is_final,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deltas(rx: &mut broadcast::Receiver<RuntimeEvent>) -> Vec<(String, bool)> {
+        let mut out = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                RuntimeEvent::AssistantTextDelta {
+                    delta, is_final, ..
+                } => out.push((delta, is_final)),
+                RuntimeEvent::AssistantTextDiscarded { .. } => out.push(("<discard>".into(), true)),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn tokens_are_regrouped_into_words() {
+        let (tx, mut rx) = broadcast::channel(32);
+        let mut stream = AssistantTextStream::new(tx, "m1".to_owned());
+        for token in ["**Bo", "ld** te", "xt\n```", "rust\nfn", " main"] {
+            stream.push(token);
+        }
+        stream.finish();
+        assert_eq!(
+            deltas(&mut rx),
+            vec![
+                ("**Bold** ".to_owned(), false),
+                ("text\n".to_owned(), false),
+                ("```rust\n".to_owned(), false),
+                ("fn ".to_owned(), false),
+                ("main".to_owned(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn delivery_tags_are_left_out() {
+        let (tx, mut rx) = broadcast::channel(32);
+        let mut stream = AssistantTextStream::new(tx, "m3".to_owned());
+        for token in ["[whis", "per] It's", " a [slow] secret."] {
+            stream.push(token);
+        }
+        stream.finish();
+        assert_eq!(
+            deltas(&mut rx),
+            vec![("It's a ".to_owned(), false), ("secret.".to_owned(), true),]
+        );
+    }
+
+    #[test]
+    fn empty_messages_send_nothing_and_discards_are_announced() {
+        let (tx, mut rx) = broadcast::channel(32);
+        let mut stream = AssistantTextStream::new(tx, "m2".to_owned());
+        stream.finish();
+        stream.discard();
+        assert!(deltas(&mut rx).is_empty());
+
+        stream.push("Partial answer");
+        stream.discard();
+        stream.push("Retry.");
+        stream.finish();
+        assert_eq!(
+            deltas(&mut rx),
+            vec![
+                ("Partial ".to_owned(), false),
+                ("<discard>".to_owned(), true),
+                ("Retry.".to_owned(), true),
+            ]
+        );
+    }
+}
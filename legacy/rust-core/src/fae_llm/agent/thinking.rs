@@ -0,0 +1,137 @@
+//! Thinking token budgets per reasoning level and model tier.
+//!
+//! A [`ReasoningLevel`] says how hard the model should think; a
+//! [`ThinkingBudget`] says how many tokens each level may spend on it.
+//! [`ThinkingBudgets`] resolves the table for the model in use from its
+//! [`ModelTier`]: small local models get small budgets, since every thinking
+//! token delays the first spoken word, while flagship models get room to
+//! work. Any tier's table can be overridden in `config.toml`:
+//!
+//! ```toml
+//! [llm.thinking.budgets.small]
+//! medium = 1536
+//! high = 3072
+//! ```
+//!
+//! The resolved budget is sent with each request as
+//! [`RequestOptions::thinking_budget`](crate::fae_llm::types::RequestOptions::thinking_budget).
+//! Providers that take a token budget apply it; providers that only accept
+//! effort levels use the level.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fae_llm::types::ReasoningLevel;
+use crate::model_tier::ModelTier;
+
+/// Thinking tokens allowed at each reasoning level.
+///
+/// Unset levels send no budget, leaving the provider's own limit in place.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThinkingBudget {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimal: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high: Option<u32>,
+}
+
+impl ThinkingBudget {
+    /// The built-in table for models of `tier`.
+    pub fn for_tier(tier: ModelTier) -> Self {
+        let [minimal, low, medium, high] = match tier {
+            ModelTier::Flagship => [1_024, 4_096, 16_384, 32_768],
+            ModelTier::Strong => [1_024, 2_048, 8_192, 16_384],
+            ModelTier::Mid => [512, 1_024, 4_096, 8_192],
+            ModelTier::Small | ModelTier::Unknown => [256, 512, 1_024, 2_048],
+        };
+        Self {
+            minimal: Some(minimal),
+            low: Some(low),
+            medium: Some(medium),
+            high: Some(high),
+        }
+    }
+
+    /// Tokens allowed at `level`; `None` when thinking is off or unbudgeted.
+    pub fn tokens(&self, level: ReasoningLevel) -> Option<u32> {
+        match level {
+            ReasoningLevel::Off => None,
+            ReasoningLevel::Minimal => self.minimal,
+            ReasoningLevel::Low => self.low,
+            ReasoningLevel::Medium => self.medium,
+            ReasoningLevel::High => self.high,
+        }
+    }
+
+    /// Fill levels unset here from `fallback`.
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            minimal: self.minimal.or(fallback.minimal),
+            low: self.low.or(fallback.low),
+            medium: self.medium.or(fallback.medium),
+            high: self.high.or(fallback.high),
+        }
+    }
+}
+
+/// Per-tier overrides of the built-in thinking budgets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ThinkingBudgets {
+    /// Overrides keyed by model tier; unset levels keep the built-in value.
+    pub tiers: HashMap<ModelTier, ThinkingBudget>,
+}
+
+impl ThinkingBudgets {
+    /// Whether no tier is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.tiers.is_empty()
+    }
+
+    /// The budget table for models of `tier`.
+    pub fn for_tier(&self, tier: ModelTier) -> ThinkingBudget {
+        let builtin = ThinkingBudget::for_tier(tier);
+        self.tiers
+            .get(&tier)
+            .map_or(builtin, |overrides| overrides.or(builtin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn budgets_grow_with_level_and_tier() {
+        let small = ThinkingBudget::for_tier(ModelTier::Small);
+        assert_eq!(small.tokens(ReasoningLevel::Off), None);
+        assert_eq!(small.tokens(ReasoningLevel::Medium), Some(1_024));
+        assert!(
+            ThinkingBudget::for_tier(ModelTier::Flagship).tokens(ReasoningLevel::High)
+                > small.tokens(ReasoningLevel::High)
+        );
+        assert_eq!(ThinkingBudget::default().tokens(ReasoningLevel::High), None);
+    }
+
+    #[test]
+    fn tier_overrides_keep_unset_levels() {
+        let budgets: ThinkingBudgets =
+            toml::from_str("[small]\nhigh = 3072\n").expect("parse budgets");
+        let small = budgets.for_tier(ModelTier::Small);
+        assert_eq!(small.tokens(ReasoningLevel::High), Some(3_072));
+        assert_eq!(small.tokens(ReasoningLevel::Medium), Some(1_024));
+        assert_eq!(
+            budgets.for_tier(ModelTier::Mid),
+            ThinkingBudget::for_tier(ModelTier::Mid)
+        );
+    }
+}
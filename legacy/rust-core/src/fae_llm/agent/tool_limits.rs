@@ -0,0 +1,236 @@
+//! Per-tool execution limits: wall-clock timeout, CPU time and output size.
+//!
+//! The executor applies a tool's own timeout instead of the agent-wide
+//! [`AgentConfig::tool_timeout_secs`](super::AgentConfig::tool_timeout_secs)
+//! and measures the CPU time the tool's thread spent. A call that runs out
+//! of time or CPU fails with an error telling the model what happened; a
+//! call whose output is over its byte limit still succeeds (the output is
+//! bounded as usual, see [`super::output_compress`]). Every overrun is
+//! reported as a [`ToolOverrun`] and counted process-wide so [`snapshot`]
+//! can show them in runtime status and diagnostics bundles.
+//!
+//! CPU time is that of the thread running the tool; work done in child
+//! processes is only bounded by the timeout.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Execution limits for one tool. Unset fields use the agent-wide defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolLimit {
+    /// Wall-clock timeout in seconds.
+    pub timeout_secs: Option<u64>,
+    /// CPU time the tool may use, in milliseconds.
+    pub cpu_time_ms: Option<u64>,
+    /// Output size, in bytes, above which an overrun is reported.
+    pub max_output_bytes: Option<usize>,
+}
+
+/// Execution limits for all tools.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolLimits {
+    /// Limits for individual tools, keyed by tool name.
+    pub per_tool: HashMap<String, ToolLimit>,
+}
+
+impl ToolLimits {
+    /// Set the limits for a single tool.
+    #[must_use]
+    pub fn with_tool_limit(mut self, tool_name: impl Into<String>, limit: ToolLimit) -> Self {
+        self.per_tool.insert(tool_name.into(), limit);
+        self
+    }
+
+    /// Limits for `tool_name`; all unset without an entry.
+    pub fn for_tool(&self, tool_name: &str) -> ToolLimit {
+        self.per_tool.get(tool_name).copied().unwrap_or_default()
+    }
+}
+
+/// Which limit a tool call went over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrunKind {
+    /// Ran past its wall-clock timeout (limit and usage in milliseconds).
+    Timeout,
+    /// Used more CPU time than its budget (milliseconds).
+    CpuTime,
+    /// Produced more output than its limit (bytes).
+    Output,
+}
+
+impl OverrunKind {
+    /// Stable name used in events and diagnostics.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::CpuTime => "cpu_time",
+            Self::Output => "output",
+        }
+    }
+}
+
+/// A tool call that went over one of its limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolOverrun {
+    pub tool_name: String,
+    pub kind: OverrunKind,
+    /// The limit, in milliseconds or bytes depending on `kind`.
+    pub limit: u64,
+    /// What the call used, in the same unit; for a timeout, the limit.
+    pub used: u64,
+}
+
+impl std::fmt::Display for ToolOverrun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            OverrunKind::Timeout => write!(
+                f,
+                "tool '{}': execution timed out after {}s",
+                self.tool_name,
+                self.limit / 1000
+            ),
+            OverrunKind::CpuTime => write!(
+                f,
+                "tool '{}': CPU time budget exceeded ({} ms used of {} ms); \
+                 try a smaller request or answer with the information you already have",
+                self.tool_name, self.used, self.limit
+            ),
+            OverrunKind::Output => write!(
+                f,
+                "tool '{}': output limit exceeded ({} bytes of {} allowed)",
+                self.tool_name, self.used, self.limit
+            ),
+        }
+    }
+}
+
+/// Overrun counts for one tool since startup.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct OverrunStats {
+    pub tool_name: String,
+    pub timeouts: u64,
+    pub cpu_time: u64,
+    pub output: u64,
+}
+
+static OVERRUNS: LazyLock<Mutex<BTreeMap<String, OverrunStats>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Count `overrun` for diagnostics.
+pub fn record(overrun: &ToolOverrun) {
+    let mut overruns = OVERRUNS.lock().unwrap_or_else(|e| e.into_inner());
+    let stats = overruns
+        .entry(overrun.tool_name.clone())
+        .or_insert_with(|| OverrunStats {
+            tool_name: overrun.tool_name.clone(),
+            ..Default::default()
+        });
+    match overrun.kind {
+        OverrunKind::Timeout => stats.timeouts += 1,
+        OverrunKind::CpuTime => stats.cpu_time += 1,
+        OverrunKind::Output => stats.output += 1,
+    }
+}
+
+/// Overrun counts per tool since startup.
+pub fn snapshot() -> Vec<OverrunStats> {
+    OVERRUNS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect()
+}
+
+/// CPU time used so far by the calling thread, where the platform reports it.
+pub fn thread_cpu_time() -> Option<Duration> {
+    #[cfg(unix)]
+    {
+        let mut ts = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: `ts` is a valid, writable timespec.
+        let rc = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) };
+        if rc != 0 {
+            return None;
+        }
+        Some(Duration::new(
+            u64::try_from(ts.tv_sec).ok()?,
+            u32::try_from(ts.tv_nsec).ok()?,
+        ))
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn tools_without_an_entry_have_no_limits() {
+        let limits = ToolLimits::default().with_tool_limit(
+            "bash",
+            ToolLimit {
+                cpu_time_ms: Some(500),
+                ..Default::default()
+            },
+        );
+        assert_eq!(limits.for_tool("bash").cpu_time_ms, Some(500));
+        assert_eq!(limits.for_tool("read"), ToolLimit::default());
+    }
+
+    #[test]
+    fn overruns_are_counted_per_tool() {
+        let overrun = |tool: &str, kind| ToolOverrun {
+            tool_name: tool.to_owned(),
+            kind,
+            limit: 1_000,
+            used: 2_500,
+        };
+        record(&overrun("limits_test_a", OverrunKind::CpuTime));
+        record(&overrun("limits_test_a", OverrunKind::CpuTime));
+        record(&overrun("limits_test_a", OverrunKind::Timeout));
+        record(&overrun("limits_test_b", OverrunKind::Output));
+
+        let stats = snapshot();
+        let a = stats
+            .iter()
+            .find(|s| s.tool_name == "limits_test_a")
+            .expect("a");
+        assert_eq!((a.timeouts, a.cpu_time, a.output), (1, 2, 0));
+        let b = stats
+            .iter()
+            .find(|s| s.tool_name == "limits_test_b")
+            .expect("b");
+        assert_eq!(b.output, 1);
+
+        assert_eq!(
+            overrun("bash", OverrunKind::CpuTime).to_string(),
+            "tool 'bash': CPU time budget exceeded (2500 ms used of 1000 ms); \
+             try a smaller request or answer with the information you already have"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn thread_cpu_time_advances_with_work() {
+        let before = thread_cpu_time().expect("cpu time");
+        let mut x = 0u64;
+        for i in 0..5_000_000u64 {
+            x = std::hint::black_box(x.wrapping_add(i * i));
+        }
+        assert!(thread_cpu_time().expect("cpu time") > before, "{x}");
+    }
+}
@@ -7,8 +7,17 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+use super::accumulator::AccumulatedToolCall;
+use super::guardrails::InjectionGuardConfig;
+use super::idempotency::DEFAULT_IDEMPOTENCY_WINDOW_SECS;
+use super::output_compress::ToolOutputLimits;
+use super::rate_limit::ToolRateLimits;
+use super::reflection::{ReflectionConfig, ReflectionVerdict};
+use super::thinking::ThinkingBudget;
+use super::tool_limits::ToolLimits;
+use crate::fae_llm::config::types::SamplingConfig;
 use crate::fae_llm::events::FinishReason;
-use crate::fae_llm::tools::types::ToolResult;
+use crate::fae_llm::tools::types::{Clarification, ToolResult};
 use crate::fae_llm::types::ReasoningLevel;
 use crate::fae_llm::usage::TokenUsage;
 
@@ -24,6 +33,9 @@ pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 120;
 /// Default per-tool execution timeout in seconds.
 pub const DEFAULT_TOOL_TIMEOUT_SECS: u64 = 30;
 
+/// Default time without a stream event before a provider counts as stalled.
+pub const DEFAULT_STREAM_STALL_TIMEOUT_SECS: u64 = 30;
+
 /// Default maximum retry attempts for transient errors.
 pub const DEFAULT_MAX_RETRY_ATTEMPTS: u32 = 3;
 
@@ -332,6 +344,10 @@ pub struct AgentConfig {
     pub request_timeout_secs: u64,
     /// Timeout for each individual tool execution in seconds.
     pub tool_timeout_secs: u64,
+    /// Seconds without a stream event before the provider counts as stalled.
+    /// `0` disables stall detection.
+    #[serde(default = "default_stream_stall_timeout_secs")]
+    pub stream_stall_timeout_secs: u64,
     /// Optional system prompt prepended to every conversation.
     pub system_prompt: Option<String>,
     /// Retry policy for transient provider failures.
@@ -353,12 +369,54 @@ pub struct AgentConfig {
     /// it (background agent / complex reasoning path).
     #[serde(default)]
     pub reasoning_level: ReasoningLevel,
+    /// Thinking tokens allowed at each reasoning level, sent with every
+    /// request where thinking is on.
+    #[serde(default)]
+    pub thinking_budget: ThinkingBudget,
+    /// Whether sessions keep the model's thinking transcripts.
+    #[serde(default = "default_store_thinking")]
+    pub store_thinking: bool,
+    /// Per-tool and global tool-call budgets (per turn and per minute).
+    #[serde(default)]
+    pub tool_rate_limits: ToolRateLimits,
+    /// Per-tool limits on output fed back to the model.
+    #[serde(default)]
+    pub tool_output_limits: ToolOutputLimits,
+    /// Per-tool timeouts, CPU budgets and output limits enforced by the
+    /// executor; a tool's timeout overrides `tool_timeout_secs`.
+    #[serde(default)]
+    pub tool_limits: ToolLimits,
+    /// Seconds within which a replayed side-effecting tool call is refused.
+    /// `0` disables duplicate suppression.
+    #[serde(default = "default_idempotency_window_secs")]
+    pub idempotency_window_secs: u64,
+    /// Self-review of the final answer by a cheap critique pass.
+    #[serde(default)]
+    pub reflection: ReflectionConfig,
+    /// Sampling overrides for the model in use, applied to every request.
+    #[serde(default)]
+    pub sampling: SamplingConfig,
+    /// Prompt-injection scanning of tool output.
+    #[serde(default)]
+    pub injection_guard: InjectionGuardConfig,
 }
 
 fn default_max_parallel_tool_calls() -> usize {
     DEFAULT_MAX_PARALLEL_TOOL_CALLS
 }
 
+fn default_stream_stall_timeout_secs() -> u64 {
+    DEFAULT_STREAM_STALL_TIMEOUT_SECS
+}
+
+fn default_store_thinking() -> bool {
+    true
+}
+
+fn default_idempotency_window_secs() -> u64 {
+    DEFAULT_IDEMPOTENCY_WINDOW_SECS
+}
+
 impl Default for AgentConfig {
     fn default() -> Self {
         Self {
@@ -366,12 +424,22 @@ impl Default for AgentConfig {
             max_tool_calls_per_turn: DEFAULT_MAX_TOOL_CALLS_PER_TURN,
             request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
             tool_timeout_secs: DEFAULT_TOOL_TIMEOUT_SECS,
+            stream_stall_timeout_secs: DEFAULT_STREAM_STALL_TIMEOUT_SECS,
             system_prompt: None,
             retry_policy: RetryPolicy::default(),
             circuit_breaker: CircuitBreaker::default(),
             parallel_tool_calls: false,
             max_parallel_tool_calls: default_max_parallel_tool_calls(),
             reasoning_level: ReasoningLevel::Off,
+            thinking_budget: ThinkingBudget::default(),
+            store_thinking: default_store_thinking(),
+            tool_rate_limits: ToolRateLimits::default(),
+            tool_output_limits: ToolOutputLimits::default(),
+            tool_limits: ToolLimits::default(),
+            idempotency_window_secs: DEFAULT_IDEMPOTENCY_WINDOW_SECS,
+            reflection: ReflectionConfig::default(),
+            sampling: SamplingConfig::default(),
+            injection_guard: InjectionGuardConfig::default(),
         }
     }
 }
@@ -406,6 +474,12 @@ impl AgentConfig {
         self
     }
 
+    /// Set the stream stall timeout in seconds (`0` disables it).
+    pub fn with_stream_stall_timeout_secs(mut self, secs: u64) -> Self {
+        self.stream_stall_timeout_secs = secs;
+        self
+    }
+
     /// Set the system prompt.
     pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
         self.system_prompt = Some(prompt.into());
@@ -445,6 +519,71 @@ impl AgentConfig {
         self.reasoning_level = level;
         self
     }
+
+    /// Set the thinking tokens allowed at each reasoning level.
+    pub fn with_thinking_budget(mut self, budget: ThinkingBudget) -> Self {
+        self.thinking_budget = budget;
+        self
+    }
+
+    /// Set whether sessions keep the model's thinking transcripts.
+    pub fn with_store_thinking(mut self, store: bool) -> Self {
+        self.store_thinking = store;
+        self
+    }
+
+    /// Set the tool-call rate limits.
+    pub fn with_tool_rate_limits(mut self, limits: ToolRateLimits) -> Self {
+        self.tool_rate_limits = limits;
+        self
+    }
+
+    /// Set the per-tool output limits.
+    pub fn with_tool_output_limits(mut self, limits: ToolOutputLimits) -> Self {
+        self.tool_output_limits = limits;
+        self
+    }
+
+    /// Set the per-tool execution limits.
+    pub fn with_tool_limits(mut self, limits: ToolLimits) -> Self {
+        self.tool_limits = limits;
+        self
+    }
+
+    /// Set the window for refusing replayed side-effecting tool calls
+    /// (`0` disables it).
+    pub fn with_idempotency_window_secs(mut self, secs: u64) -> Self {
+        self.idempotency_window_secs = secs;
+        self
+    }
+
+    /// Enable or disable the critique pass over the final answer.
+    pub fn with_reflection(mut self, enabled: bool) -> Self {
+        self.reflection.enabled = enabled;
+        self
+    }
+
+    /// Set the criteria the critique pass checks the final answer against.
+    pub fn with_reflection_criteria<I, S>(mut self, criteria: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.reflection.criteria = criteria.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the prompt-injection guardrail settings.
+    pub fn with_injection_guard(mut self, guard: InjectionGuardConfig) -> Self {
+        self.injection_guard = guard;
+        self
+    }
+
+    /// Set the sampling overrides for the model in use.
+    pub fn with_sampling(mut self, sampling: SamplingConfig) -> Self {
+        self.sampling = sampling;
+        self
+    }
 }
 
 /// A tool call that was executed during the agent loop.
@@ -494,6 +633,10 @@ pub enum StopReason {
     MaxToolCalls,
     /// The loop was cancelled by the caller.
     Cancelled,
+    /// The provider stopped sending stream events and no fallback took over.
+    StreamStalled,
+    /// A tool call matched several things; the user was asked which one.
+    Clarification,
     /// An error occurred during the loop.
     Error(String),
 }
@@ -505,6 +648,8 @@ impl std::fmt::Display for StopReason {
             Self::MaxTurns => write!(f, "max_turns"),
             Self::MaxToolCalls => write!(f, "max_tool_calls"),
             Self::Cancelled => write!(f, "cancelled"),
+            Self::StreamStalled => write!(f, "stream_stalled"),
+            Self::Clarification => write!(f, "clarification"),
             Self::Error(msg) => write!(f, "error: {msg}"),
         }
     }
@@ -524,6 +669,70 @@ pub struct AgentLoopResult {
     pub total_usage: TokenUsage,
     /// Why the agent loop stopped.
     pub stop_reason: StopReason,
+    /// Verdict of the critique pass, if reflection ran.
+    pub reflection: Option<ReflectionVerdict>,
+}
+
+impl AgentLoopResult {
+    /// The model's thinking across all turns, one paragraph per turn that
+    /// thought; empty when it did not think.
+    pub fn thinking(&self) -> String {
+        self.turns
+            .iter()
+            .map(|turn| turn.thinking.trim())
+            .filter(|thinking| !thinking.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// The call waiting on the user's answer, when the loop stopped with
+    /// [`StopReason::Clarification`].
+    pub fn pending_clarification(&self) -> Option<PendingClarification> {
+        if self.stop_reason != StopReason::Clarification {
+            return None;
+        }
+        self.turns
+            .iter()
+            .rev()
+            .flat_map(|turn| &turn.tool_calls)
+            .find_map(|call| {
+                Some(PendingClarification {
+                    function_name: call.function_name.clone(),
+                    arguments: call.arguments.clone(),
+                    clarification: call.result.clarification.clone()?,
+                })
+            })
+    }
+}
+
+/// A tool call held back until the user says which candidate they meant.
+#[derive(Debug, Clone)]
+pub struct PendingClarification {
+    /// The tool that asked.
+    pub function_name: String,
+    /// The arguments of the held-back call.
+    pub arguments: serde_json::Value,
+    /// The question and its candidates.
+    pub clarification: Clarification,
+}
+
+impl PendingClarification {
+    /// The held-back call with the candidate picked by `answer` filled in,
+    /// or `None` when the answer picks none of them.
+    pub fn resolve(&self, answer: &str) -> Option<AccumulatedToolCall> {
+        let candidate = self.clarification.pick(answer)?;
+        let mut arguments = self.arguments.clone();
+        let object = arguments.as_object_mut()?;
+        object.insert(
+            self.clarification.argument.clone(),
+            serde_json::Value::String(candidate.value.clone()),
+        );
+        Some(AccumulatedToolCall {
+            call_id: format!("clarified_{}", candidate.value),
+            function_name: self.function_name.clone(),
+            arguments_json: arguments.to_string(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -728,7 +937,9 @@ mod tests {
         assert_eq!(StopReason::Complete.to_string(), "complete");
         assert_eq!(StopReason::MaxTurns.to_string(), "max_turns");
         assert_eq!(StopReason::MaxToolCalls.to_string(), "max_tool_calls");
+        assert_eq!(StopReason::StreamStalled.to_string(), "stream_stalled");
         assert_eq!(StopReason::Cancelled.to_string(), "cancelled");
+        assert_eq!(StopReason::Clarification.to_string(), "clarification");
         assert_eq!(
             StopReason::Error("timeout".into()).to_string(),
             "error: timeout"
@@ -750,6 +961,8 @@ mod tests {
             StopReason::MaxTurns,
             StopReason::MaxToolCalls,
             StopReason::Cancelled,
+            StopReason::StreamStalled,
+            StopReason::Clarification,
             StopReason::Error("something".into()),
         ];
         for reason in &reasons {
@@ -793,6 +1006,7 @@ mod tests {
             final_text: "Done.".into(),
             total_usage: TokenUsage::new(200, 100),
             stop_reason: StopReason::Complete,
+            reflection: None,
         };
         assert_eq!(result.turns.len(), 1);
         assert_eq!(result.final_text, "Done.");
@@ -829,6 +1043,7 @@ mod tests {
             final_text: "Here's the file.".into(),
             total_usage: total,
             stop_reason: StopReason::Complete,
+            reflection: None,
         };
         assert_eq!(result.turns.len(), 2);
         assert_eq!(result.total_usage.prompt_tokens, 250);
@@ -842,6 +1057,7 @@ mod tests {
             final_text: "test".into(),
             total_usage: TokenUsage::default(),
             stop_reason: StopReason::Complete,
+            reflection: None,
         };
         let cloned = result.clone();
         assert_eq!(cloned.final_text, "test");
@@ -854,12 +1070,67 @@ mod tests {
             final_text: "output".into(),
             total_usage: TokenUsage::default(),
             stop_reason: StopReason::Complete,
+            reflection: None,
         };
         let debug = format!("{result:?}");
         assert!(debug.contains("AgentLoopResult"));
         assert!(debug.contains("output"));
     }
 
+    #[test]
+    fn pending_clarification_resolves_to_the_picked_call() {
+        use crate::fae_llm::tools::types::{Clarification, ClarificationCandidate};
+
+        let candidate = |label: &str, value: &str| ClarificationCandidate {
+            label: label.into(),
+            value: value.into(),
+        };
+        let clarification = Clarification::new(
+            "identifier",
+            vec![
+                candidate("Team meeting, 2026-03-02 10:00", "evt-010"),
+                candidate("Client meeting, 2026-03-04 15:30", "evt-011"),
+            ],
+        );
+        let turn = TurnResult {
+            text: String::new(),
+            thinking: String::new(),
+            tool_calls: vec![ExecutedToolCall {
+                call_id: "call_1".into(),
+                function_name: "delete_calendar_event".into(),
+                arguments: serde_json::json!({"identifier": "meeting", "confirm": true}),
+                result: ToolResult::clarify(clarification),
+                duration_ms: 3,
+            }],
+            finish_reason: FinishReason::ToolCalls,
+            usage: None,
+        };
+        let mut result = AgentLoopResult {
+            turns: vec![turn],
+            final_text: String::new(),
+            total_usage: TokenUsage::default(),
+            stop_reason: StopReason::Complete,
+            reflection: None,
+        };
+        assert!(result.pending_clarification().is_none());
+
+        result.stop_reason = StopReason::Clarification;
+        let Some(pending) = result.pending_clarification() else {
+            unreachable!("the clarifying call is pending");
+        };
+        assert!(pending.resolve("hmm, no idea").is_none());
+        let Some(call) = pending.resolve("the client one") else {
+            unreachable!("the answer names a candidate");
+        };
+        assert_eq!(call.function_name, "delete_calendar_event");
+        let arguments: serde_json::Value =
+            serde_json::from_str(&call.arguments_json).unwrap_or_default();
+        assert_eq!(
+            arguments,
+            serde_json::json!({"identifier": "evt-011", "confirm": true})
+        );
+    }
+
     #[test]
     fn all_agent_types_are_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
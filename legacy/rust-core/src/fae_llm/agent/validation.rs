@@ -1,7 +1,8 @@
-//! Tool argument validation against JSON schemas.
+//! Tool argument and output validation against JSON schemas.
 //!
 //! Validates that tool call arguments from the LLM conform to the
-//! tool's declared JSON schema. Checks required fields, type constraints,
+//! tool's declared JSON schema, and that structured tool output conforms
+//! to the tool's output schema. Checks required fields, type constraints,
 //! and basic structural correctness.
 //!
 //! # Examples
@@ -93,6 +94,60 @@ pub fn validate_tool_args(
     Ok(value)
 }
 
+/// Validate a tool's structured output against its declared output schema.
+///
+/// Checks the top-level `"type"`, then `"required"` and `"properties"` of
+/// objects and the `"items"` schema of arrays, recursively. Like
+/// [`validate_tool_args`], extra object fields are allowed.
+///
+/// # Errors
+///
+/// Returns [`FaeLlmError::ToolValidationError`] naming the first offending
+/// path (e.g. `output[2].price`).
+pub fn validate_tool_output(
+    tool_name: &str,
+    value: &serde_json::Value,
+    schema: &serde_json::Value,
+) -> Result<(), FaeLlmError> {
+    validate_output_value(tool_name, "output", value, schema)
+}
+
+fn validate_output_value(
+    tool_name: &str,
+    path: &str,
+    value: &serde_json::Value,
+    schema: &serde_json::Value,
+) -> Result<(), FaeLlmError> {
+    validate_field_type(tool_name, path, value, schema)?;
+
+    if let Some(obj) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+            for field_name in required.iter().filter_map(|f| f.as_str()) {
+                if !obj.contains_key(field_name) {
+                    return Err(FaeLlmError::ToolValidationError(format!(
+                        "tool '{tool_name}': {path} missing required field '{field_name}'"
+                    )));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (key, val) in obj {
+                if let Some(prop_schema) = properties.get(key) {
+                    validate_output_value(tool_name, &format!("{path}.{key}"), val, prop_schema)?;
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate_output_value(tool_name, &format!("{path}[{i}]"), item, item_schema)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate that a single field's value matches its schema type.
 fn validate_field_type(
     tool_name: &str,
@@ -493,4 +548,51 @@ mod tests {
         assert_eq!(json_type_name(&serde_json::json!(42)), "integer");
         assert_eq!(json_type_name(&serde_json::json!(3.5)), "number");
     }
+
+    // ── Structured output ─────────────────────────────────────
+
+    fn table_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "rank": { "type": "integer" }
+                },
+                "required": ["title"]
+            }
+        })
+    }
+
+    #[test]
+    fn output_matching_schema_passes() {
+        let value = serde_json::json!([
+            { "title": "a", "rank": 1 },
+            { "title": "b", "extra": true }
+        ]);
+        assert!(validate_tool_output("search", &value, &table_schema()).is_ok());
+    }
+
+    #[test]
+    fn output_errors_name_the_offending_path() {
+        let wrong_type = serde_json::json!([{ "title": "a" }, { "title": "b", "rank": "2" }]);
+        match validate_tool_output("search", &wrong_type, &table_schema()) {
+            Err(FaeLlmError::ToolValidationError(msg)) => {
+                assert!(msg.contains("output[1].rank"));
+            }
+            _ => unreachable!("expected validation error"),
+        }
+
+        let missing = serde_json::json!([{ "rank": 1 }]);
+        match validate_tool_output("search", &missing, &table_schema()) {
+            Err(FaeLlmError::ToolValidationError(msg)) => {
+                assert!(msg.contains("output[0] missing required field 'title'"));
+            }
+            _ => unreachable!("expected validation error"),
+        }
+
+        let not_array = serde_json::json!({ "title": "a" });
+        assert!(validate_tool_output("search", &not_array, &table_schema()).is_err());
+    }
 }
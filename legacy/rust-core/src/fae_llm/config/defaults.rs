@@ -30,6 +30,7 @@ pub fn default_config() -> FaeLlmConfig {
             base_url: String::new(),
             api_key: SecretRef::None,
             models: Vec::new(),
+            azure: None,
         },
     );
 
@@ -41,6 +42,7 @@ pub fn default_config() -> FaeLlmConfig {
                 name: String::new(),
                 enabled: true,
                 options: std::collections::HashMap::new(),
+                ..ToolConfig::default()
             },
         );
     }
@@ -50,6 +52,8 @@ pub fn default_config() -> FaeLlmConfig {
     config.defaults = DefaultsConfig {
         default_provider: Some("local".to_string()),
         default_model: None,
+        vision_provider: None,
+        vision_model: None,
         tool_mode: ToolMode::ReadOnly,
         reasoning: crate::fae_llm::types::ReasoningLevel::Off,
     };
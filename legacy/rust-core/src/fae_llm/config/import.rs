@@ -0,0 +1,541 @@
+//! Import provider profiles from other LLM tools.
+//!
+//! Discovers endpoints the user already has set up — a running Ollama,
+//! LM Studio or llama.cpp server, OpenAI / Anthropic API keys in the
+//! environment, or the key Claude Code is configured with — so onboarding
+//! can offer them instead of asking for everything again. Nothing is written
+//! until the user confirms; [`apply_imports`] then adds the chosen profiles
+//! to a [`FaeLlmConfig`].
+//!
+//! Keys found in environment variables are imported as [`SecretRef::Env`]
+//! references, so the key itself never lands in Fae's config file. Keys that
+//! only exist inside another tool's config file have to be copied.
+
+use super::defaults::ensure_config_exists;
+use super::service::ConfigService;
+use super::types::{EndpointType, FaeLlmConfig, ProviderConfig, SecretRef};
+use crate::fae_llm::error::FaeLlmError;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const OLLAMA_DEFAULT_HOST: &str = "http://127.0.0.1:11434";
+const LM_STUDIO_BASE_URL: &str = "http://127.0.0.1:1234/v1";
+const LLAMA_CPP_DEFAULT_HOST: &str = "127.0.0.1";
+const LLAMA_CPP_DEFAULT_PORT: &str = "8080";
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+
+/// Where a discovered profile came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    Ollama,
+    LmStudio,
+    LlamaCpp,
+    OpenAiEnv,
+    AnthropicEnv,
+    ClaudeCode,
+}
+
+impl ImportSource {
+    /// Provider id the profile is imported under (made unique if taken).
+    pub fn provider_id(self) -> &'static str {
+        match self {
+            Self::Ollama => "ollama",
+            Self::LmStudio => "lmstudio",
+            Self::LlamaCpp => "llamacpp",
+            Self::OpenAiEnv => "openai",
+            Self::AnthropicEnv | Self::ClaudeCode => "anthropic",
+        }
+    }
+
+    /// Stable identifier used by hosts to select a discovered profile.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ollama => "ollama",
+            Self::LmStudio => "lm_studio",
+            Self::LlamaCpp => "llama_cpp",
+            Self::OpenAiEnv => "openai_env",
+            Self::AnthropicEnv => "anthropic_env",
+            Self::ClaudeCode => "claude_code",
+        }
+    }
+}
+
+/// A provider profile found on this machine.
+#[derive(Debug, Clone)]
+pub struct DiscoveredProvider {
+    pub source: ImportSource,
+    pub provider: ProviderConfig,
+    /// Shown to the user next to the option (e.g. where the key comes from).
+    pub note: Option<String>,
+}
+
+impl DiscoveredProvider {
+    /// A display summary that never contains key material.
+    pub fn summary(&self) -> serde_json::Value {
+        let key = match &self.provider.api_key {
+            SecretRef::None => serde_json::Value::Null,
+            SecretRef::Env { var } => serde_json::json!(format!("env:{var}")),
+            SecretRef::Literal { .. } => serde_json::json!("copied"),
+            SecretRef::Command { .. } => serde_json::json!("command"),
+            SecretRef::Keychain { .. } => serde_json::json!("keychain"),
+        };
+        serde_json::json!({
+            "source": self.source.as_str(),
+            "provider_id": self.source.provider_id(),
+            "endpoint_type": self.provider.endpoint_type,
+            "base_url": self.provider.base_url,
+            "models": self.provider.models,
+            "key": key,
+            "note": self.note,
+        })
+    }
+}
+
+/// What discovery looks at; [`ImportContext::detect`] fills it from the
+/// running system.
+#[derive(Debug, Clone, Default)]
+pub struct ImportContext {
+    /// The user's home directory.
+    pub home: Option<PathBuf>,
+    /// Environment variables.
+    pub env: HashMap<String, String>,
+    /// Output of `ollama list`, if the command ran.
+    pub ollama_list: Option<String>,
+}
+
+impl ImportContext {
+    /// Capture the current environment, home directory and Ollama models.
+    pub fn detect() -> Self {
+        let ollama_list = std::process::Command::new("ollama")
+            .arg("list")
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .and_then(|out| String::from_utf8(out.stdout).ok());
+        Self {
+            home: dirs::home_dir(),
+            env: std::env::vars().collect(),
+            ollama_list,
+        }
+    }
+
+    fn var(&self, name: &str) -> Option<&str> {
+        self.env
+            .get(name)
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn home_path(&self, relative: &str) -> Option<PathBuf> {
+        self.home.as_ref().map(|h| h.join(relative))
+    }
+
+    fn on_path(&self, program: &str) -> bool {
+        self.var("PATH")
+            .is_some_and(|path| std::env::split_paths(path).any(|dir| dir.join(program).is_file()))
+    }
+}
+
+/// Find importable provider profiles.
+pub fn discover(ctx: &ImportContext) -> Vec<DiscoveredProvider> {
+    [
+        discover_ollama(ctx),
+        discover_lm_studio(ctx),
+        discover_llama_cpp(ctx),
+        discover_openai_env(ctx),
+        discover_anthropic_env(ctx),
+        discover_claude_code(ctx),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn provider(endpoint_type: EndpointType, base_url: String, api_key: SecretRef) -> ProviderConfig {
+    ProviderConfig {
+        endpoint_type,
+        enabled: true,
+        base_url,
+        api_key,
+        models: Vec::new(),
+        azure: None,
+    }
+}
+
+/// Model names from `ollama list` output (first column, header skipped).
+pub fn parse_ollama_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn discover_ollama(ctx: &ImportContext) -> Option<DiscoveredProvider> {
+    let installed =
+        ctx.ollama_list.is_some() || ctx.home_path(".ollama/models").is_some_and(|p| p.is_dir());
+    if !installed {
+        return None;
+    }
+    let host = ctx.var("OLLAMA_HOST").map_or_else(
+        || OLLAMA_DEFAULT_HOST.to_owned(),
+        |h| {
+            let h = h.trim_end_matches('/');
+            if h.contains("://") {
+                h.to_owned()
+            } else {
+                format!("http://{}", h.replace("0.0.0.0", "127.0.0.1"))
+            }
+        },
+    );
+    let mut provider = provider(
+        EndpointType::OpenAiCompletions,
+        format!("{host}/v1"),
+        SecretRef::None,
+    );
+    provider.models = ctx
+        .ollama_list
+        .as_deref()
+        .map(parse_ollama_list)
+        .unwrap_or_default();
+    Some(DiscoveredProvider {
+        source: ImportSource::Ollama,
+        provider,
+        note: None,
+    })
+}
+
+/// Model directories (`publisher/model`) under an LM Studio models root.
+fn lm_studio_models(models_dir: &Path) -> Vec<String> {
+    let Ok(publishers) = std::fs::read_dir(models_dir) else {
+        return Vec::new();
+    };
+    let mut models: Vec<String> = publishers
+        .flatten()
+        .filter(|p| p.path().is_dir())
+        .flat_map(|publisher| {
+            let name = publisher.file_name().to_string_lossy().into_owned();
+            std::fs::read_dir(publisher.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|m| m.path().is_dir())
+                .map(move |m| format!("{name}/{}", m.file_name().to_string_lossy()))
+        })
+        .collect();
+    models.sort();
+    models
+}
+
+fn discover_lm_studio(ctx: &ImportContext) -> Option<DiscoveredProvider> {
+    let root = [".lmstudio", ".cache/lm-studio"]
+        .into_iter()
+        .filter_map(|dir| ctx.home_path(dir))
+        .find(|p| p.is_dir())?;
+    let mut provider = provider(
+        EndpointType::OpenAiCompletions,
+        LM_STUDIO_BASE_URL.to_owned(),
+        SecretRef::None,
+    );
+    provider.models = lm_studio_models(&root.join("models"));
+    Some(DiscoveredProvider {
+        source: ImportSource::LmStudio,
+        provider,
+        note: Some("Start the local server in LM Studio to use it".to_owned()),
+    })
+}
+
+fn discover_llama_cpp(ctx: &ImportContext) -> Option<DiscoveredProvider> {
+    let configured = ctx.var("LLAMA_ARG_PORT").is_some() || ctx.var("LLAMA_ARG_HOST").is_some();
+    if !configured && !ctx.on_path("llama-server") {
+        return None;
+    }
+    let host = ctx
+        .var("LLAMA_ARG_HOST")
+        .unwrap_or(LLAMA_CPP_DEFAULT_HOST)
+        .replace("0.0.0.0", "127.0.0.1");
+    let port = ctx.var("LLAMA_ARG_PORT").unwrap_or(LLAMA_CPP_DEFAULT_PORT);
+    let api_key = if ctx.var("LLAMA_API_KEY").is_some() {
+        SecretRef::Env {
+            var: "LLAMA_API_KEY".to_owned(),
+        }
+    } else {
+        SecretRef::None
+    };
+    let mut provider = provider(
+        EndpointType::OpenAiCompletions,
+        format!("http://{host}:{port}/v1"),
+        api_key,
+    );
+    if let Some(model) = ctx
+        .var("LLAMA_ARG_MODEL")
+        .and_then(|m| Path::new(m).file_stem())
+    {
+        provider.models = vec![model.to_string_lossy().into_owned()];
+    }
+    Some(DiscoveredProvider {
+        source: ImportSource::LlamaCpp,
+        provider,
+        note: None,
+    })
+}
+
+fn discover_openai_env(ctx: &ImportContext) -> Option<DiscoveredProvider> {
+    ctx.var("OPENAI_API_KEY")?;
+    let base_url = ctx
+        .var("OPENAI_BASE_URL")
+        .or_else(|| ctx.var("OPENAI_API_BASE"))
+        .unwrap_or(OPENAI_BASE_URL)
+        .trim_end_matches('/')
+        .to_owned();
+    Some(DiscoveredProvider {
+        source: ImportSource::OpenAiEnv,
+        provider: provider(
+            EndpointType::OpenAiCompletions,
+            base_url,
+            SecretRef::Env {
+                var: "OPENAI_API_KEY".to_owned(),
+            },
+        ),
+        note: None,
+    })
+}
+
+fn discover_anthropic_env(ctx: &ImportContext) -> Option<DiscoveredProvider> {
+    ctx.var("ANTHROPIC_API_KEY")?;
+    let base_url = ctx
+        .var("ANTHROPIC_BASE_URL")
+        .unwrap_or(ANTHROPIC_BASE_URL)
+        .trim_end_matches('/')
+        .to_owned();
+    Some(DiscoveredProvider {
+        source: ImportSource::AnthropicEnv,
+        provider: provider(
+            EndpointType::AnthropicMessages,
+            base_url,
+            SecretRef::Env {
+                var: "ANTHROPIC_API_KEY".to_owned(),
+            },
+        ),
+        note: None,
+    })
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let text = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// The Anthropic key Claude Code is configured with, from the `env` block
+/// of `~/.claude/settings.json` or the `primaryApiKey` in `~/.claude.json`.
+fn discover_claude_code(ctx: &ImportContext) -> Option<DiscoveredProvider> {
+    let settings = ctx
+        .home_path(".claude/settings.json")
+        .and_then(|p| read_json(&p));
+    let settings_env = settings.as_ref().and_then(|s| s.get("env"));
+    let setting = |name: &str| {
+        settings_env
+            .and_then(|env| env.get(name))
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_owned)
+    };
+
+    let key = setting("ANTHROPIC_API_KEY").or_else(|| {
+        ctx.home_path(".claude.json")
+            .and_then(|p| read_json(&p))
+            .and_then(|v| {
+                v.get("primaryApiKey")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_owned)
+            })
+            .filter(|k| !k.trim().is_empty())
+    })?;
+    // Same key as the environment already offers: nothing new to import.
+    if ctx.var("ANTHROPIC_API_KEY") == Some(key.trim()) {
+        return None;
+    }
+
+    let base_url = setting("ANTHROPIC_BASE_URL")
+        .unwrap_or_else(|| ANTHROPIC_BASE_URL.to_owned())
+        .trim_end_matches('/')
+        .to_owned();
+    let mut provider = provider(
+        EndpointType::AnthropicMessages,
+        base_url,
+        SecretRef::Literal { value: key },
+    );
+    if let Some(model) = setting("ANTHROPIC_MODEL") {
+        provider.models = vec![model];
+    }
+    Some(DiscoveredProvider {
+        source: ImportSource::ClaudeCode,
+        provider,
+        note: Some("The API key will be copied into Fae's config".to_owned()),
+    })
+}
+
+/// Add `chosen` profiles to `config`, returning the provider ids used.
+///
+/// A profile whose endpoint is already configured (same type and base URL)
+/// is skipped; otherwise it gets the source's provider id, suffixed with a
+/// number if that id is taken.
+pub fn apply_imports(config: &mut FaeLlmConfig, chosen: &[DiscoveredProvider]) -> Vec<String> {
+    let mut added = Vec::new();
+    for found in chosen {
+        let duplicate = config.providers.values().any(|p| {
+            p.endpoint_type == found.provider.endpoint_type
+                && p.base_url.trim_end_matches('/') == found.provider.base_url
+        });
+        if duplicate {
+            continue;
+        }
+        let base = found.source.provider_id();
+        let id = std::iter::once(base.to_owned())
+            .chain((2..).map(|n| format!("{base}-{n}")))
+            .find(|id| !config.providers.contains_key(id))
+            .unwrap_or_else(|| base.to_owned());
+        config.providers.insert(id.clone(), found.provider.clone());
+        added.push(id);
+    }
+    added
+}
+
+/// Import `chosen` into the config file at `path` (created with defaults if
+/// missing), returning the provider ids added.
+///
+/// # Errors
+/// Returns `FaeLlmError::ConfigError` if the file cannot be read, validated,
+/// or written.
+pub fn import_into_file(
+    path: &Path,
+    chosen: &[DiscoveredProvider],
+) -> Result<Vec<String>, FaeLlmError> {
+    ensure_config_exists(path)?;
+    let service = ConfigService::new(path.to_path_buf());
+    service.load()?;
+    let mut added = Vec::new();
+    service.update(|config| added = apply_imports(config, chosen))?;
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn ollama_models_and_env_keys_are_discovered() {
+        let ctx = ImportContext {
+            home: None,
+            env: env(&[
+                ("OLLAMA_HOST", "0.0.0.0:11500"),
+                ("OPENAI_API_KEY", "sk-test"),
+                ("LLAMA_ARG_PORT", "9000"),
+                ("LLAMA_ARG_MODEL", "/models/qwen3-4b-q4.gguf"),
+            ]),
+            ollama_list: Some(
+                "NAME            ID      SIZE    MODIFIED\n\
+                 llama3.2:latest a80c4f  2.0 GB  2 days ago\n\
+                 qwen3:4b        2bfd38  2.6 GB  5 weeks ago\n"
+                    .to_owned(),
+            ),
+        };
+        let found = discover(&ctx);
+        let sources: Vec<_> = found.iter().map(|d| d.source).collect();
+        assert_eq!(
+            sources,
+            vec![
+                ImportSource::Ollama,
+                ImportSource::LlamaCpp,
+                ImportSource::OpenAiEnv
+            ]
+        );
+
+        assert_eq!(found[0].provider.base_url, "http://127.0.0.1:11500/v1");
+        assert_eq!(
+            found[0].provider.models,
+            vec!["llama3.2:latest", "qwen3:4b"]
+        );
+        assert_eq!(found[1].provider.base_url, "http://127.0.0.1:9000/v1");
+        assert_eq!(found[1].provider.models, vec!["qwen3-4b-q4"]);
+
+        // Env keys stay references; the summary never shows key material.
+        assert!(matches!(
+            &found[2].provider.api_key,
+            SecretRef::Env { var } if var == "OPENAI_API_KEY"
+        ));
+        let summary = found[2].summary().to_string();
+        assert!(summary.contains("env:OPENAI_API_KEY"));
+        assert!(!summary.contains("sk-test"));
+    }
+
+    #[test]
+    fn claude_code_and_lm_studio_configs_are_read() {
+        let home = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(home.path().join(".claude")).unwrap();
+        std::fs::write(
+            home.path().join(".claude/settings.json"),
+            r#"{"env": {"ANTHROPIC_API_KEY": "sk-ant-from-settings", "ANTHROPIC_MODEL": "claude-sonnet-4"}}"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(home.path().join(".lmstudio/models/qwen/qwen3-8b")).unwrap();
+
+        let ctx = ImportContext {
+            home: Some(home.path().to_path_buf()),
+            ..ImportContext::default()
+        };
+        let found = discover(&ctx);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].source, ImportSource::LmStudio);
+        assert_eq!(found[0].provider.models, vec!["qwen/qwen3-8b"]);
+
+        let claude = &found[1];
+        assert_eq!(claude.source, ImportSource::ClaudeCode);
+        assert_eq!(
+            claude.provider.endpoint_type,
+            EndpointType::AnthropicMessages
+        );
+        assert_eq!(claude.provider.models, vec!["claude-sonnet-4"]);
+        assert!(!claude.summary().to_string().contains("sk-ant"));
+    }
+
+    #[test]
+    fn imports_skip_known_endpoints_and_avoid_id_clashes() {
+        let mut config = crate::fae_llm::config::default_config();
+        config.providers.insert(
+            "openai".to_owned(),
+            provider(
+                EndpointType::OpenAiCompletions,
+                "https://proxy.example/v1".to_owned(),
+                SecretRef::None,
+            ),
+        );
+        let ctx = ImportContext {
+            env: env(&[("OPENAI_API_KEY", "sk-test")]),
+            ..ImportContext::default()
+        };
+        let found = discover(&ctx);
+
+        assert_eq!(apply_imports(&mut config, &found), vec!["openai-2"]);
+        assert_eq!(
+            config.providers["openai-2"].base_url,
+            "https://api.openai.com/v1"
+        );
+        // Importing again adds nothing.
+        assert!(apply_imports(&mut config, &found).is_empty());
+    }
+}
@@ -17,6 +17,7 @@
 //! - **editor** — Round-trip TOML editing via `toml_edit` (`ConfigEditor`)
 //! - **service** — Thread-safe config cache with validation (`ConfigService`)
 //! - **defaults** — Default config generation for first-run
+//! - **import** — Provider profiles discovered from other tools (Ollama, LM Studio, ...)
 //!
 //! # Quick Start
 //!
@@ -30,17 +31,19 @@
 
 pub mod defaults;
 pub mod editor;
+pub mod import;
 pub mod persist;
 pub mod service;
 pub mod types;
 
 pub use defaults::{default_config, ensure_config_exists};
 pub use editor::ConfigEditor;
+pub use import::{DiscoveredProvider, ImportContext, ImportSource};
 pub use persist::{backup_config, read_config, write_config_atomic};
 pub use service::{ConfigService, ModelUpdate, ProviderUpdate, validate_config};
 pub use types::{
-    DefaultsConfig, FaeLlmConfig, ModelConfig, ModelTier, ProviderConfig, RuntimeConfig, SecretRef,
-    ToolConfig, ToolMode,
+    AzureOpenAiConfig, DefaultsConfig, FaeLlmConfig, LoraAdapterConfig, LoraMode, ModelConfig,
+    ModelTier, ProviderConfig, RuntimeConfig, SamplingConfig, SecretRef, ToolConfig, ToolMode,
 };
 
 #[cfg(test)]
@@ -208,6 +211,7 @@ mod integration_tests {
                 base_url: String::new(),
                 api_key: SecretRef::None,
                 models: Vec::new(),
+                azure: None,
             },
         );
         assert!(validate_config(&config).is_err());
@@ -377,6 +381,8 @@ tool_mode = "read_only"
                 display_name: "Test".to_string(),
                 tier: *tier,
                 max_tokens: 4096,
+                sampling: Default::default(),
+                lora: None,
             };
             let json = serde_json::to_string(&model).unwrap_or_default();
             let parsed: serde_json::Value = serde_json::from_str(&json).unwrap_or_default();
@@ -135,6 +135,7 @@ mod tests {
                     var: "OPENAI_API_KEY".to_string(),
                 },
                 models: vec!["gpt-4o".to_string()],
+                azure: None,
             },
         );
         config.runtime.request_timeout_secs = 60;
@@ -259,6 +259,12 @@ pub struct ModelUpdate {
 /// - Default provider references a valid provider (if set)
 /// - Default model references a valid model (if set)
 /// - All provider base_urls are non-empty
+/// - Azure settings are complete and only used with OpenAI endpoints
+/// - Per-model sampling parameters are in range
+/// - Per-model LoRA adapter references are complete
+/// - Enabled speculative generation names a known verifier provider
+/// - Enabled best-of answering names a known provider and is not combined
+///   with speculative generation
 ///
 /// # Errors
 /// Returns `FaeLlmError::ConfigError` if validation fails.
@@ -291,6 +297,78 @@ pub fn validate_config(config: &FaeLlmConfig) -> Result<(), FaeLlmError> {
                 "provider '{name}' has empty base_url"
             )));
         }
+        if let Some(azure) = &provider.azure {
+            if !matches!(
+                provider.endpoint_type,
+                crate::fae_llm::types::EndpointType::OpenAiCompletions
+                    | crate::fae_llm::types::EndpointType::OpenAiResponses
+            ) {
+                return Err(FaeLlmError::ConfigValidationError(format!(
+                    "provider '{name}' azure settings require an OpenAI endpoint type"
+                )));
+            }
+            azure.validate().map_err(|reason| {
+                FaeLlmError::ConfigValidationError(format!("provider '{name}' azure: {reason}"))
+            })?;
+        }
+    }
+
+    // Check per-model sampling parameters are in range.
+    for (name, model) in &config.models {
+        model.sampling.validate().map_err(|reason| {
+            FaeLlmError::ConfigValidationError(format!("model '{name}' sampling: {reason}"))
+        })?;
+        if let Some(lora) = &model.lora {
+            lora.validate().map_err(|reason| {
+                FaeLlmError::ConfigValidationError(format!("model '{name}' lora: {reason}"))
+            })?;
+        }
+    }
+
+    // Check speculative generation has a verifier to run.
+    let speculative = &config.experimental.speculative;
+    if !(0.0..=1.0).contains(&speculative.divergence_threshold) {
+        return Err(FaeLlmError::ConfigValidationError(
+            "experimental.speculative divergence_threshold must be between 0 and 1".into(),
+        ));
+    }
+    if speculative.enabled {
+        match speculative.provider.as_deref() {
+            Some(id) if config.providers.contains_key(id) => {}
+            Some(id) => {
+                return Err(FaeLlmError::ConfigValidationError(format!(
+                    "experimental.speculative provider '{id}' not found in providers"
+                )));
+            }
+            None => {
+                return Err(FaeLlmError::ConfigValidationError(
+                    "experimental.speculative is enabled without a provider".into(),
+                ));
+            }
+        }
+    }
+
+    // Check best-of answering has a second provider to ask.
+    let best_of = &config.experimental.best_of;
+    if best_of.enabled {
+        if speculative.enabled {
+            return Err(FaeLlmError::ConfigValidationError(
+                "experimental.best_of and experimental.speculative cannot both be enabled".into(),
+            ));
+        }
+        match best_of.provider.as_deref() {
+            Some(id) if config.providers.contains_key(id) => {}
+            Some(id) => {
+                return Err(FaeLlmError::ConfigValidationError(format!(
+                    "experimental.best_of provider '{id}' not found in providers"
+                )));
+            }
+            None => {
+                return Err(FaeLlmError::ConfigValidationError(
+                    "experimental.best_of is enabled without a provider".into(),
+                ));
+            }
+        }
     }
 
     // Check tool names only use the locked v1 set.
@@ -444,6 +522,8 @@ mod tests {
                     display_name: "Test Model".to_string(),
                     tier: crate::fae_llm::config::types::ModelTier::Balanced,
                     max_tokens: 4096,
+                    sampling: Default::default(),
+                    lora: None,
                 },
             );
         });
@@ -556,12 +636,48 @@ mod tests {
                 base_url: String::new(),
                 api_key: super::super::types::SecretRef::None,
                 models: Vec::new(),
+                azure: None,
             },
         );
         let result = validate_config(&config);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn validate_config_azure_requires_openai_endpoint_and_deployment() {
+        let azure = super::super::types::AzureOpenAiConfig {
+            deployment: "gpt-4o".to_string(),
+            api_version: super::super::types::DEFAULT_AZURE_API_VERSION.to_string(),
+        };
+        let mut provider = ProviderConfig {
+            endpoint_type: EndpointType::OpenAiCompletions,
+            enabled: true,
+            base_url: "https://contoso.openai.azure.com".to_string(),
+            api_key: super::super::types::SecretRef::None,
+            models: Vec::new(),
+            azure: Some(azure.clone()),
+        };
+        let mut config = default_config();
+        config
+            .providers
+            .insert("azure".to_string(), provider.clone());
+        assert!(validate_config(&config).is_ok());
+
+        provider.endpoint_type = EndpointType::AnthropicMessages;
+        config
+            .providers
+            .insert("azure".to_string(), provider.clone());
+        assert!(validate_config(&config).is_err());
+
+        provider.endpoint_type = EndpointType::OpenAiCompletions;
+        provider.azure = Some(super::super::types::AzureOpenAiConfig {
+            deployment: String::new(),
+            ..azure
+        });
+        config.providers.insert("azure".to_string(), provider);
+        assert!(validate_config(&config).is_err());
+    }
+
     #[test]
     fn validate_config_no_defaults() {
         let mut config = FaeLlmConfig::default();
@@ -573,6 +689,7 @@ mod tests {
                 base_url: "https://example.com".to_string(),
                 api_key: super::super::types::SecretRef::None,
                 models: Vec::new(),
+                azure: None,
             },
         );
         // No default_provider or default_model set — should be OK
@@ -588,4 +705,57 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result, Err(FaeLlmError::ConfigValidationError(_))));
     }
+
+    #[test]
+    fn validate_config_checks_speculative_verifier() {
+        let mut config = default_config();
+        config.experimental.speculative.enabled = true;
+        assert!(validate_config(&config).is_err(), "no verifier provider");
+
+        config.experimental.speculative.provider = Some("nonexistent".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.experimental.speculative.provider = Some("local".to_string());
+        assert!(validate_config(&config).is_ok());
+
+        config.experimental.speculative.divergence_threshold = 1.5;
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn validate_config_checks_best_of_provider() {
+        let mut config = default_config();
+        config.experimental.best_of.enabled = true;
+        assert!(validate_config(&config).is_err(), "no second provider");
+
+        config.experimental.best_of.provider = Some("local".to_string());
+        assert!(validate_config(&config).is_ok());
+
+        config.experimental.speculative.enabled = true;
+        config.experimental.speculative.provider = Some("local".to_string());
+        assert!(validate_config(&config).is_err(), "speculative is on");
+    }
+
+    #[test]
+    fn validate_config_rejects_bad_model_sampling() {
+        let mut config = default_config();
+        config.models.insert(
+            "m".to_string(),
+            crate::fae_llm::config::types::ModelConfig {
+                model_id: "m".to_string(),
+                display_name: "M".to_string(),
+                tier: crate::fae_llm::config::types::ModelTier::Fast,
+                max_tokens: 1024,
+                sampling: crate::fae_llm::config::types::SamplingConfig {
+                    min_p: Some(-0.1),
+                    ..Default::default()
+                },
+                lora: None,
+            },
+        );
+        let result = validate_config(&config);
+        assert!(
+            matches!(result, Err(FaeLlmError::ConfigValidationError(ref m)) if m.contains("min_p"))
+        );
+    }
 }
@@ -3,8 +3,13 @@
 //! Defines the TOML configuration structure including providers, models,
 //! defaults, runtime settings, and locked tool-mode behavior.
 
+use crate::fae_llm::agent::output_compress::{ToolOutputLimit, ToolOutputLimits};
+use crate::fae_llm::agent::best_of::BestOfConfig;
+use crate::fae_llm::agent::speculative::SpeculativeConfig;
+use crate::fae_llm::agent::tool_limits::{ToolLimit, ToolLimits};
+use crate::fae_llm::tools::network_policy::NetworkPolicy;
 pub use crate::fae_llm::types::EndpointType;
-use crate::fae_llm::types::ReasoningLevel;
+use crate::fae_llm::types::{ReasoningLevel, RequestOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -40,6 +45,10 @@ pub struct FaeLlmConfig {
     /// Runtime settings.
     #[serde(default)]
     pub runtime: RuntimeConfig,
+
+    /// Experimental features, all off by default.
+    #[serde(default)]
+    pub experimental: ExperimentalConfig,
 }
 
 fn default_config_version() -> u32 {
@@ -55,10 +64,21 @@ impl Default for FaeLlmConfig {
             tools: ToolsConfig::default(),
             defaults: DefaultsConfig::default(),
             runtime: RuntimeConfig::default(),
+            experimental: ExperimentalConfig::default(),
         }
     }
 }
 
+impl FaeLlmConfig {
+    /// The entry for `model_id`: keyed by it, or naming it as its
+    /// `model_id`.
+    pub fn model(&self, model_id: &str) -> Option<&ModelConfig> {
+        self.models
+            .get(model_id)
+            .or_else(|| self.models.values().find(|m| m.model_id == model_id))
+    }
+}
+
 /// Configuration for a single LLM provider.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderConfig {
@@ -79,6 +99,78 @@ pub struct ProviderConfig {
     /// Provider-advertised model IDs.
     #[serde(default)]
     pub models: Vec<String>,
+
+    /// Azure OpenAI deployment settings; set when `base_url` is an Azure
+    /// OpenAI resource (`https://<resource>.openai.azure.com`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub azure: Option<AzureOpenAiConfig>,
+}
+
+/// Azure OpenAI API version used when a provider entry does not set one.
+pub const DEFAULT_AZURE_API_VERSION: &str = "2024-10-21";
+
+fn default_azure_api_version() -> String {
+    DEFAULT_AZURE_API_VERSION.to_owned()
+}
+
+/// Azure OpenAI settings for an OpenAI-protocol provider.
+///
+/// Azure addresses models by deployment name in the URL path, takes the
+/// key in an `api-key` header instead of a bearer token, and requires an
+/// `api-version` query parameter on every request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AzureOpenAiConfig {
+    /// Deployment name chat requests are sent to.
+    pub deployment: String,
+    /// Value of the `api-version` query parameter.
+    #[serde(default = "default_azure_api_version")]
+    pub api_version: String,
+}
+
+impl AzureOpenAiConfig {
+    /// Header carrying the API key.
+    pub const API_KEY_HEADER: &'static str = "api-key";
+
+    /// Chat completions URL for the deployment under `base_url`.
+    pub fn chat_completions_url(&self, base_url: &str) -> String {
+        format!(
+            "{}/openai/deployments/{}/chat/completions?api-version={}",
+            azure_root(base_url),
+            self.deployment.trim(),
+            self.api_version.trim()
+        )
+    }
+
+    /// Model listing URL under `base_url`, used to validate keys.
+    pub fn models_url(&self, base_url: &str) -> String {
+        format!(
+            "{}/openai/models?api-version={}",
+            azure_root(base_url),
+            self.api_version.trim()
+        )
+    }
+
+    /// Check the settings are complete, returning a description of the
+    /// first problem.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.deployment.trim().is_empty() {
+            return Err("deployment must not be empty".into());
+        }
+        if self.deployment.contains(['/', '?', '#']) {
+            return Err("deployment must be a plain deployment name".into());
+        }
+        if self.api_version.trim().is_empty() {
+            return Err("api_version must not be empty".into());
+        }
+        Ok(())
+    }
+}
+
+/// The resource root of an Azure base URL, accepting URLs that already end
+/// in `/openai` as copied from the Azure portal.
+fn azure_root(base_url: &str) -> &str {
+    let base = base_url.trim().trim_end_matches('/');
+    base.strip_suffix("/openai").unwrap_or(base)
 }
 
 /// Configuration for a single model.
@@ -95,6 +187,142 @@ pub struct ModelConfig {
 
     /// Maximum generated tokens.
     pub max_tokens: usize,
+
+    /// Sampling overrides applied to every request for this model.
+    #[serde(default, skip_serializing_if = "SamplingConfig::is_empty")]
+    pub sampling: SamplingConfig,
+
+    /// LoRA adapters applied on top of the base weights (local models only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lora: Option<LoraAdapterConfig>,
+}
+
+/// How LoRA adapters are applied to a local model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoraMode {
+    /// Merge the adapters listed in the ordering file into the base weights.
+    #[default]
+    Merge,
+    /// Mix the adapters per token with a trained X-LoRA classifier.
+    Xlora,
+}
+
+/// LoRA adapter set for a local model.
+///
+/// The ordering file is a mistral.rs ordering JSON listing the adapters in
+/// the repo and the layers they target; it may name one or several.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoraAdapterConfig {
+    /// HuggingFace repo ID (or local directory) holding the adapter weights.
+    pub adapter_id: String,
+    /// Path to the adapter ordering JSON file.
+    pub ordering_file: String,
+    /// Merge the adapters or mix them with X-LoRA.
+    #[serde(default)]
+    pub mode: LoraMode,
+    /// X-LoRA only: scale adapters once, from the token at this index,
+    /// instead of per token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tgt_non_granular_index: Option<usize>,
+}
+
+impl LoraAdapterConfig {
+    /// Check the adapter reference is complete, returning a description of
+    /// the first problem.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.adapter_id.trim().is_empty() {
+            return Err("adapter_id must not be empty".into());
+        }
+        if self.ordering_file.trim().is_empty() {
+            return Err("ordering_file must not be empty".into());
+        }
+        if self.mode == LoraMode::Merge && self.tgt_non_granular_index.is_some() {
+            return Err("tgt_non_granular_index only applies to xlora mode".into());
+        }
+        Ok(())
+    }
+}
+
+/// Per-model sampling parameters.
+///
+/// Unset fields leave the request's own value (or the provider default) in
+/// place. Providers ignore parameters they do not support.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplingConfig {
+    /// Sampling temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling threshold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Top-k sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Min-p sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_p: Option<f64>,
+    /// Repetition penalty (1.0 = none).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repetition_penalty: Option<f64>,
+    /// Frequency penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    /// Presence penalty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
+    /// Stop sequences, added to any the request already has.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    /// Seed for reproducible sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+impl SamplingConfig {
+    /// Whether no parameter is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Apply the set parameters on top of `options`.
+    pub fn apply_to(&self, mut options: RequestOptions) -> RequestOptions {
+        options.temperature = self.temperature.or(options.temperature);
+        options.top_p = self.top_p.or(options.top_p);
+        options.top_k = self.top_k.or(options.top_k);
+        options.min_p = self.min_p.or(options.min_p);
+        options.repetition_penalty = self.repetition_penalty.or(options.repetition_penalty);
+        options.frequency_penalty = self.frequency_penalty.or(options.frequency_penalty);
+        options.presence_penalty = self.presence_penalty.or(options.presence_penalty);
+        for stop in &self.stop {
+            if !options.stop.contains(stop) {
+                options.stop.push(stop.clone());
+            }
+        }
+        options.seed = self.seed.or(options.seed);
+        options
+    }
+
+    /// Check parameter ranges, returning a description of the first problem.
+    pub fn validate(&self) -> Result<(), String> {
+        let in_range = |name: &str, value: Option<f64>, min: f64, max: f64| match value {
+            Some(v) if !(min..=max).contains(&v) => {
+                Err(format!("{name} must be between {min} and {max}, got {v}"))
+            }
+            _ => Ok(()),
+        };
+        in_range("temperature", self.temperature, 0.0, 2.0)?;
+        in_range("top_p", self.top_p, 0.0, 1.0)?;
+        in_range("min_p", self.min_p, 0.0, 1.0)?;
+        in_range("repetition_penalty", self.repetition_penalty, 0.0, 2.0)?;
+        in_range("frequency_penalty", self.frequency_penalty, -2.0, 2.0)?;
+        in_range("presence_penalty", self.presence_penalty, -2.0, 2.0)?;
+        if self.top_k == Some(0) {
+            return Err("top_k must be at least 1".into());
+        }
+        Ok(())
+    }
 }
 
 /// Model performance tier.
@@ -146,6 +374,35 @@ pub struct ToolConfig {
     #[serde(default = "default_true")]
     pub enabled: bool,
 
+    /// Maximum bytes of output fed back to the model (default 100 KB).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_bytes: Option<usize>,
+
+    /// Whether elided output is summarized by the local model (default true).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summarize_output: Option<bool>,
+
+    /// Domains this tool may contact; empty allows any domain not denied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_domains: Vec<String>,
+
+    /// Domains this tool may never contact.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_domains: Vec<String>,
+
+    /// Ask before contacting a domain not in `allow_domains` (default false).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ask_new_domains: Option<bool>,
+
+    /// Execution timeout in seconds, overriding the agent-wide timeout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
+    /// CPU time the tool may use per call, in milliseconds (unlimited by
+    /// default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_time_ms: Option<u64>,
+
     /// Tool-specific options (arbitrary key-value pairs).
     #[serde(default, flatten)]
     pub options: HashMap<String, toml::Value>,
@@ -156,11 +413,60 @@ impl Default for ToolConfig {
         Self {
             name: String::new(),
             enabled: true,
+            max_output_bytes: None,
+            summarize_output: None,
+            allow_domains: Vec::new(),
+            deny_domains: Vec::new(),
+            ask_new_domains: None,
+            timeout_secs: None,
+            cpu_time_ms: None,
             options: HashMap::new(),
         }
     }
 }
 
+impl ToolConfig {
+    /// Output limit for this tool, filling unset fields from `default`.
+    pub fn output_limit(&self, default: ToolOutputLimit) -> ToolOutputLimit {
+        ToolOutputLimit {
+            max_bytes: self.max_output_bytes.unwrap_or(default.max_bytes),
+            summarize: self.summarize_output.unwrap_or(default.summarize),
+        }
+    }
+
+    /// Execution limits enforced by the tool executor.
+    pub fn execution_limit(&self) -> ToolLimit {
+        ToolLimit {
+            timeout_secs: self.timeout_secs,
+            cpu_time_ms: self.cpu_time_ms,
+            max_output_bytes: self.max_output_bytes,
+        }
+    }
+
+    /// Outbound network policy for this tool, layered over `global`.
+    ///
+    /// Denied domains add to the global ones, which always apply. A
+    /// non-empty `allow_domains` replaces the global allow list, and a set
+    /// `ask_new_domains` overrides the global choice.
+    pub fn network_policy(&self, global: &NetworkPolicy) -> NetworkPolicy {
+        let mut deny_domains = global.deny_domains.clone();
+        for domain in &self.deny_domains {
+            if !deny_domains.contains(domain) {
+                deny_domains.push(domain.clone());
+            }
+        }
+        NetworkPolicy {
+            allow_domains: if self.allow_domains.is_empty() {
+                global.allow_domains.clone()
+            } else {
+                self.allow_domains.clone()
+            },
+            deny_domains,
+            ask_new_domains: self.ask_new_domains.unwrap_or(global.ask_new_domains),
+        }
+    }
+}
+
 fn default_true() -> bool {
     true
 }
@@ -213,15 +519,14 @@ impl ToolsConfig {
             .collect()
     }
 
-    /// Returns true if this config references only locked tool names.
+    /// Returns true if `enabled` names only locked tool names.
+    ///
+    /// Per-tool entries may name any tool: they only tune its limits and
+    /// network policy (e.g. `[tools.fetch_url]`).
     pub fn has_only_known_tool_names(&self) -> bool {
         self.enabled
             .iter()
             .all(|n| DEFAULT_TOOL_NAMES.contains(&n.as_str()))
-            && self
-                .entries
-                .keys()
-                .all(|n| DEFAULT_TOOL_NAMES.contains(&n.as_str()))
     }
 
     /// Legacy-compatible map-like insert.
@@ -258,6 +563,36 @@ impl ToolsConfig {
     pub fn keys(&self) -> impl Iterator<Item = &String> {
         self.entries.keys()
     }
+
+    /// Per-tool output limits for the agent loop.
+    pub fn output_limits(&self) -> ToolOutputLimits {
+        let default = ToolOutputLimit::default();
+        self.entries
+            .iter()
+            .filter(|(_, tool)| tool.max_output_bytes.is_some() || tool.summarize_output.is_some())
+            .fold(ToolOutputLimits::default(), |limits, (name, tool)| {
+                limits.with_tool_limit(name.clone(), tool.output_limit(default))
+            })
+    }
+
+    /// Per-tool execution limits for the agent loop.
+    pub fn execution_limits(&self) -> ToolLimits {
+        self.entries
+            .iter()
+            .map(|(name, tool)| (name, tool.execution_limit()))
+            .filter(|(_, limit)| *limit != ToolLimit::default())
+            .fold(ToolLimits::default(), |limits, (name, limit)| {
+                limits.with_tool_limit(name.clone(), limit)
+            })
+    }
+
+    /// Outbound network policy for `tool_name`: its entry layered over
+    /// `global`, or `global` itself without an entry.
+    pub fn network_policy(&self, tool_name: &str, global: &NetworkPolicy) -> NetworkPolicy {
+        self.entries
+            .get(tool_name)
+            .map_or_else(|| global.clone(), |tool| tool.network_policy(global))
+    }
 }
 
 impl std::ops::Index<&str> for ToolsConfig {
@@ -279,6 +614,15 @@ pub struct DefaultsConfig {
     #[serde(default, alias = "model_id")]
     pub default_model: Option<String>,
 
+    /// Remote provider ID for turns with images when the local model has
+    /// no vision support.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vision_provider: Option<String>,
+
+    /// Model ID used with `vision_provider`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vision_model: Option<String>,
+
     /// Default reasoning level.
     #[serde(default)]
     pub reasoning: ReasoningLevel,
@@ -293,12 +637,24 @@ impl Default for DefaultsConfig {
         Self {
             default_provider: None,
             default_model: None,
+            vision_provider: None,
+            vision_model: None,
             reasoning: ReasoningLevel::Off,
             tool_mode: ToolMode::ReadOnly,
         }
     }
 }
 
+/// Experimental features (`[experimental]`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExperimentalConfig {
+    /// Speak a local draft while a remote model verifies it.
+    pub speculative: SpeculativeConfig,
+    /// Answer with the local model and a remote one; keep the better answer.
+    pub best_of: BestOfConfig,
+}
+
 /// Local runtime mode (v1 locked to probe-only).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -447,6 +803,7 @@ mod tests {
                 name: "read".to_string(),
                 enabled: true,
                 options: HashMap::new(),
+                ..ToolConfig::default()
             },
         );
 
@@ -455,6 +812,218 @@ mod tests {
         assert!(tools["read"].enabled);
     }
 
+    #[test]
+    fn tools_config_output_limits_from_toml() {
+        let tools: ToolsConfig = toml::from_str(
+            r#"
+            mode = "full"
+
+            [bash]
+            max_output_bytes = 4096
+
+            [read]
+            summarize_output = false
+            custom = "kept"
+            "#,
+        )
+        .unwrap_or_else(|e| unreachable!("tools config should parse: {e}"));
+
+        let limits = tools.output_limits();
+        let bash = limits.for_tool("bash");
+        assert_eq!(bash.max_bytes, 4096);
+        assert!(bash.summarize);
+        let read = limits.for_tool("read");
+        assert_eq!(read.max_bytes, ToolOutputLimit::default().max_bytes);
+        assert!(!read.summarize);
+        assert!(tools["read"].options.contains_key("custom"));
+        assert_eq!(limits.for_tool("write"), ToolOutputLimit::default());
+    }
+
+    #[test]
+    fn tools_config_execution_limits_from_toml() {
+        let tools: ToolsConfig = toml::from_str(
+            r#"
+            [bash]
+            timeout_secs = 5
+            cpu_time_ms = 2000
+            max_output_bytes = 4096
+
+            [read]
+            summarize_output = false
+            "#,
+        )
+        .unwrap_or_else(|e| unreachable!("tools config should parse: {e}"));
+
+        let limits = tools.execution_limits();
+        let bash = limits.for_tool("bash");
+        assert_eq!(bash.timeout_secs, Some(5));
+        assert_eq!(bash.cpu_time_ms, Some(2000));
+        assert_eq!(bash.max_output_bytes, Some(4096));
+        assert!(!limits.per_tool.contains_key("read"));
+        assert!(!tools["bash"].options.contains_key("cpu_time_ms"));
+    }
+
+    #[test]
+    fn tools_config_network_policy_from_toml() {
+        let tools: ToolsConfig = toml::from_str(
+            r#"
+            [fetch_url]
+            allow_domains = ["example.com"]
+            deny_domains = ["ads.example.com"]
+            ask_new_domains = true
+            "#,
+        )
+        .unwrap_or_else(|e| unreachable!("tools config should parse: {e}"));
+
+        let unrestricted = NetworkPolicy::default();
+        let policy = tools.network_policy("fetch_url", &unrestricted);
+        assert_eq!(policy.allow_domains, vec!["example.com".to_string()]);
+        assert_eq!(policy.deny_domains, vec!["ads.example.com".to_string()]);
+        assert!(policy.ask_new_domains);
+        assert!(!tools["fetch_url"].options.contains_key("allow_domains"));
+        assert!(
+            tools
+                .network_policy("web_search", &unrestricted)
+                .is_unrestricted()
+        );
+
+        // Global denials still apply; the global allow list and ask setting
+        // are used where the tool sets none.
+        let global = NetworkPolicy {
+            allow_domains: vec!["wikipedia.org".into()],
+            deny_domains: vec!["tracker.test".into()],
+            ask_new_domains: false,
+        };
+        let policy = tools.network_policy("fetch_url", &global);
+        assert_eq!(
+            policy.deny_domains,
+            vec!["tracker.test".to_string(), "ads.example.com".to_string()]
+        );
+        assert_eq!(policy.allow_domains, vec!["example.com".to_string()]);
+        assert!(policy.ask_new_domains);
+        assert_eq!(tools.network_policy("web_search", &global), global);
+    }
+
+    #[test]
+    fn model_sampling_parses_and_overrides_set_fields() {
+        let model: ModelConfig = toml::from_str(
+            r#"
+model_id = "qwen3"
+display_name = "Qwen3"
+tier = "fast"
+max_tokens = 2048
+
+[sampling]
+top_k = 20
+min_p = 0.05
+stop = ["</answer>"]
+seed = 7
+"#,
+        )
+        .unwrap_or_else(|e| panic!("parse failed: {e}"));
+        assert_eq!(model.sampling.top_k, Some(20));
+
+        let options = model.sampling.apply_to(
+            RequestOptions::new()
+                .with_temperature(0.2)
+                .with_stop("</answer>"),
+        );
+        assert_eq!(options.temperature, Some(0.2));
+        assert_eq!(options.top_k, Some(20));
+        assert_eq!(options.min_p, Some(0.05));
+        assert_eq!(options.stop, vec!["</answer>".to_string()]);
+        assert_eq!(options.seed, Some(7));
+
+        let mut config = FaeLlmConfig::default();
+        config.models.insert("voice".to_string(), model.clone());
+        assert_eq!(
+            config.model("qwen3").and_then(|m| m.sampling.top_k),
+            Some(20)
+        );
+        assert!(config.model("voice").is_some());
+        assert!(config.model("other").is_none());
+
+        let plain = ModelConfig {
+            sampling: SamplingConfig::default(),
+            ..model
+        };
+        let json = serde_json::to_string(&plain).unwrap_or_default();
+        assert!(!json.contains("sampling"));
+    }
+
+    #[test]
+    fn lora_adapter_parses_and_validates() {
+        let model: ModelConfig = toml::from_str(
+            r#"
+model_id = "qwen3"
+display_name = "Qwen3"
+tier = "fast"
+max_tokens = 2048
+
+[lora]
+adapter_id = "me/fae-persona-lora"
+ordering_file = "/tmp/ordering.json"
+"#,
+        )
+        .unwrap_or_else(|e| panic!("parse failed: {e}"));
+        let lora = model.lora.unwrap_or_else(|| panic!("lora missing"));
+        assert_eq!(lora.mode, LoraMode::Merge);
+        assert!(lora.validate().is_ok());
+
+        let bad = LoraAdapterConfig {
+            tgt_non_granular_index: Some(1),
+            ..lora
+        };
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn azure_provider_parses_and_builds_deployment_urls() {
+        let provider: ProviderConfig = toml::from_str(
+            r#"
+endpoint_type = "openai_completions"
+base_url = "https://contoso.openai.azure.com/openai/"
+api_key = { type = "env", var = "AZURE_OPENAI_API_KEY" }
+
+[azure]
+deployment = "gpt-4o-mini"
+"#,
+        )
+        .unwrap_or_else(|e| panic!("parse failed: {e}"));
+        let azure = provider.azure.unwrap_or_else(|| panic!("azure missing"));
+        assert_eq!(azure.api_version, DEFAULT_AZURE_API_VERSION);
+        assert!(azure.validate().is_ok());
+        assert_eq!(
+            azure.chat_completions_url(&provider.base_url),
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o-mini/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(
+            azure.models_url(&provider.base_url),
+            "https://contoso.openai.azure.com/openai/models?api-version=2024-10-21"
+        );
+
+        let bad = AzureOpenAiConfig {
+            deployment: " ".into(),
+            ..azure
+        };
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn sampling_validate_rejects_out_of_range_values() {
+        assert!(SamplingConfig::default().validate().is_ok());
+        let bad = SamplingConfig {
+            top_p: Some(1.5),
+            ..SamplingConfig::default()
+        };
+        assert!(bad.validate().is_err_and(|e| e.contains("top_p")));
+        let zero_k = SamplingConfig {
+            top_k: Some(0),
+            ..SamplingConfig::default()
+        };
+        assert!(zero_k.validate().is_err());
+    }
+
     #[test]
     fn defaults_config_supports_reasoning_and_legacy_fields() {
         let defaults = DefaultsConfig::default();
@@ -46,9 +46,10 @@ pub use agent::{
     build_messages_from_result, validate_tool_args,
 };
 pub use config::{
-    ConfigEditor, ConfigService, DefaultsConfig, FaeLlmConfig, ModelConfig, ModelTier, ModelUpdate,
-    ProviderConfig, ProviderUpdate, RuntimeConfig, SecretRef, ToolConfig, ToolMode, backup_config,
-    default_config, ensure_config_exists, read_config, validate_config, write_config_atomic,
+    ConfigEditor, ConfigService, DefaultsConfig, FaeLlmConfig, LoraAdapterConfig, LoraMode,
+    ModelConfig, ModelTier, ModelUpdate, ProviderConfig, ProviderUpdate, RuntimeConfig,
+    SamplingConfig, SecretRef, ToolConfig, ToolMode, backup_config, default_config,
+    ensure_config_exists, read_config, validate_config, write_config_atomic,
 };
 pub use error::FaeLlmError;
 pub use events::{AssistantEvent, FinishReason, LlmEvent};
@@ -60,11 +61,12 @@ pub use provider::{
 pub use providers::message::{AssistantToolCall, Message, MessageContent, Role};
 pub use session::{
     ConversationContext, FsSessionStore, MemorySessionStore, Session, SessionId, SessionMeta,
-    SessionResumeError, SessionStore, validate_message_sequence, validate_session,
+    SessionResumeError, SessionSearchHit, SessionStore, search_sessions, validate_message_sequence,
+    validate_session,
 };
 pub use tools::{BashTool, EditTool, ReadTool, Tool, ToolRegistry, ToolResult, WriteTool};
 pub use types::{EndpointType, ModelRef, ReasoningLevel, RequestOptions};
-pub use usage::{CostEstimate, TokenPricing, TokenUsage};
+pub use usage::{CostEstimate, TokenPricing, TokenUsage, UsageTracker};
 
 #[cfg(test)]
 mod integration_tests {
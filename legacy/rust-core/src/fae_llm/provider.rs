@@ -27,6 +27,9 @@ pub struct ToolDefinition {
     pub description: String,
     /// JSON Schema describing the tool's parameters.
     pub parameters: serde_json::Value,
+    /// JSON Schema describing the tool's structured output, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
 }
 
 impl ToolDefinition {
@@ -40,8 +43,15 @@ impl ToolDefinition {
             name: name.into(),
             description: description.into(),
             parameters,
+            output_schema: None,
         }
     }
+
+    /// Declare the schema of the tool's structured output.
+    pub fn with_output_schema(mut self, output_schema: serde_json::Value) -> Self {
+        self.output_schema = Some(output_schema);
+        self
+    }
 }
 
 /// Provider-neutral context passed to the v1+ streaming contract.
@@ -88,6 +98,35 @@ pub trait ProviderAdapter: Send + Sync {
         EndpointType::OpenAiCompletions
     }
 
+    /// Whether tool result messages may carry a structured payload.
+    ///
+    /// Providers that return `false` receive only the text rendering.
+    fn supports_structured_tool_results(&self) -> bool {
+        false
+    }
+
+    /// Whether the model accepts images attached to user messages.
+    ///
+    /// Providers that return `false` ignore [`Message::images`].
+    fn supports_images(&self) -> bool {
+        false
+    }
+
+    /// Prepare for a request whose conversation starts with `messages`.
+    ///
+    /// Called while the user is silent so the next request starts faster:
+    /// local providers process the prompt prefix into their KV cache,
+    /// remote providers can open and keep alive their connection. The
+    /// default does nothing.
+    async fn warm_up(
+        &self,
+        _messages: &[Message],
+        _options: &RequestOptions,
+        _tools: &[ToolDefinition],
+    ) -> Result<(), FaeLlmError> {
+        Ok(())
+    }
+
     /// Legacy send contract used by the existing agent loop.
     async fn send(
         &self,
@@ -165,6 +204,14 @@ mod tests {
         assert!(tool.parameters.is_object());
     }
 
+    #[tokio::test]
+    async fn warm_up_default_is_a_no_op() {
+        let result = NoopProvider
+            .warm_up(&[Message::system("prompt")], &RequestOptions::new(), &[])
+            .await;
+        assert!(result.is_ok());
+    }
+
     #[tokio::test]
     async fn stream_default_forwards_to_send() {
         let provider = NoopProvider;
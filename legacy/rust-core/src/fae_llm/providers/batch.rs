@@ -0,0 +1,638 @@
+//! Provider batch APIs for latency-insensitive background jobs.
+//!
+//! OpenAI's Batch API and Anthropic's Message Batches accept a set of
+//! requests, process them within 24 hours, and bill them at roughly half
+//! the interactive price. [`BatchProvider`] hides the two protocols behind
+//! the same three steps: [`submit`](BatchProvider::submit) the requests,
+//! [`poll`](BatchProvider::poll) until the batch has ended, then
+//! [`collect`](BatchProvider::collect) one [`BatchResult`] per request.
+//!
+//! Batched requests are single-shot completions: no tools, no streaming.
+
+use crate::fae_llm::config::types::{FaeLlmConfig, ProviderConfig};
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::types::EndpointType;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+/// How long a single batch API call may take.
+const BATCH_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Anthropic API version header sent with batch requests.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Output tokens requested when the model config does not set a limit.
+const DEFAULT_BATCH_MAX_TOKENS: usize = 1024;
+
+/// Which batch protocol a handle belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchApi {
+    /// OpenAI Batch API (`/batches` over an uploaded JSONL file).
+    OpenAi,
+    /// Anthropic Message Batches (`/v1/messages/batches`).
+    Anthropic,
+}
+
+/// One completion request inside a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchRequest {
+    /// Caller-chosen id, unique within the batch, echoed on the result.
+    pub custom_id: String,
+    /// Optional system prompt.
+    pub system: Option<String>,
+    /// The user prompt.
+    pub prompt: String,
+}
+
+/// A submitted batch; persist it to poll and collect later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchHandle {
+    /// Protocol the batch was submitted with.
+    pub api: BatchApi,
+    /// Provider-assigned batch id.
+    pub id: String,
+}
+
+/// Processing state of a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchStatus {
+    /// Still queued, validating, or running.
+    InProgress,
+    /// Finished; results can be collected.
+    Completed,
+    /// Rejected or failed as a whole.
+    Failed(String),
+    /// Not finished within the completion window.
+    Expired,
+    /// Cancelled before it finished.
+    Cancelled,
+}
+
+/// The outcome of one request in a finished batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchResult {
+    /// The request's [`BatchRequest::custom_id`].
+    pub custom_id: String,
+    /// Response text, or why this request failed.
+    pub outcome: Result<String, String>,
+}
+
+/// A provider that can run requests through its batch API.
+#[async_trait]
+pub trait BatchProvider: Send + Sync {
+    /// Provider name for logs.
+    fn name(&self) -> &str;
+
+    /// Submit `requests` as one batch.
+    async fn submit(&self, requests: &[BatchRequest]) -> Result<BatchHandle, FaeLlmError>;
+
+    /// Check how far `handle` has progressed.
+    async fn poll(&self, handle: &BatchHandle) -> Result<BatchStatus, FaeLlmError>;
+
+    /// Fetch the results of a [`BatchStatus::Completed`] batch.
+    async fn collect(&self, handle: &BatchHandle) -> Result<Vec<BatchResult>, FaeLlmError>;
+}
+
+/// Build the batch provider for the configured default provider and model.
+///
+/// Returns `None` when no default provider is set, it is disabled, its
+/// endpoint has no batch API (local, Responses, custom, Azure), or no model
+/// can be determined.
+pub fn batch_provider_from_config(config: &FaeLlmConfig) -> Option<Box<dyn BatchProvider>> {
+    let provider_id = config.defaults.default_provider.as_deref()?;
+    let provider = config.providers.get(provider_id).filter(|p| p.enabled)?;
+    let model = config
+        .defaults
+        .default_model
+        .as_deref()
+        .and_then(|id| config.models.get(id));
+    let model_id = model
+        .map(|m| m.model_id.clone())
+        .or_else(|| config.defaults.default_model.clone())
+        .or_else(|| provider.models.first().cloned())?;
+    let max_tokens = model.map_or(DEFAULT_BATCH_MAX_TOKENS, |m| m.max_tokens);
+    batch_provider_for(provider_id, provider, &model_id, max_tokens)
+}
+
+/// Build the batch provider for `provider`, sending requests to `model`.
+///
+/// Returns `None` for endpoints without a batch API.
+pub fn batch_provider_for(
+    name: &str,
+    provider: &ProviderConfig,
+    model: &str,
+    max_tokens: usize,
+) -> Option<Box<dyn BatchProvider>> {
+    if provider.azure.is_some() {
+        return None;
+    }
+    let api = match provider.endpoint_type {
+        EndpointType::OpenAiCompletions => BatchApi::OpenAi,
+        EndpointType::AnthropicMessages => BatchApi::Anthropic,
+        EndpointType::OpenAiResponses | EndpointType::Local | EndpointType::Custom => return None,
+    };
+    Some(Box::new(HttpBatchProvider {
+        name: name.to_owned(),
+        api,
+        base_url: provider.base_url.trim().trim_end_matches('/').to_owned(),
+        api_key: provider.api_key.clone(),
+        model: model.to_owned(),
+        max_tokens,
+    }))
+}
+
+/// Batch provider speaking either protocol over HTTP.
+struct HttpBatchProvider {
+    name: String,
+    api: BatchApi,
+    base_url: String,
+    api_key: crate::fae_llm::config::SecretRef,
+    model: String,
+    max_tokens: usize,
+}
+
+impl HttpBatchProvider {
+    fn anthropic_batches_url(&self) -> String {
+        if self.base_url.ends_with("/v1") {
+            format!("{}/messages/batches", self.base_url)
+        } else {
+            format!("{}/v1/messages/batches", self.base_url)
+        }
+    }
+
+    fn client(&self) -> Result<reqwest::Client, FaeLlmError> {
+        reqwest::Client::builder()
+            .timeout(BATCH_HTTP_TIMEOUT)
+            .build()
+            .map_err(|e| FaeLlmError::RequestError(format!("HTTP client: {e}")))
+    }
+
+    fn authorize(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, FaeLlmError> {
+        let key = self
+            .api_key
+            .resolve()?
+            .filter(|k| !k.trim().is_empty())
+            .ok_or_else(|| {
+                FaeLlmError::SecretResolutionError("no API key configured".to_owned())
+            })?;
+        Ok(match self.api {
+            BatchApi::OpenAi => request.bearer_auth(key.trim()),
+            BatchApi::Anthropic => request
+                .header("x-api-key", key.trim())
+                .header("anthropic-version", ANTHROPIC_VERSION),
+        })
+    }
+
+    /// Send `request` to `url` and return the response body.
+    async fn send(
+        &self,
+        url: &str,
+        request: reqwest::RequestBuilder,
+    ) -> Result<String, FaeLlmError> {
+        crate::offline::ensure_url_allowed("batch request", url)
+            .map_err(|e| FaeLlmError::RequestError(e.to_string()))?;
+        let response = self.authorize(request)?.send().await.map_err(|e| {
+            if e.is_timeout() {
+                FaeLlmError::TimeoutError(format!("no response from {url}"))
+            } else {
+                FaeLlmError::RequestError(format!("cannot reach {url}: {e}"))
+            }
+        })?;
+        if !response.status().is_success() {
+            return Err(super::validate::classify_status(response.status().as_u16()));
+        }
+        response
+            .text()
+            .await
+            .map_err(|e| FaeLlmError::RequestError(format!("cannot read response: {e}")))
+    }
+
+    async fn get_json(&self, url: &str) -> Result<Value, FaeLlmError> {
+        let body = self.send(url, self.client()?.get(url)).await?;
+        parse_json(&body)
+    }
+}
+
+fn parse_json(body: &str) -> Result<Value, FaeLlmError> {
+    serde_json::from_str(body)
+        .map_err(|e| FaeLlmError::ProviderError(format!("malformed batch response: {e}")))
+}
+
+fn batch_id(body: &Value) -> Result<String, FaeLlmError> {
+    body.get("id")
+        .and_then(Value::as_str)
+        .map(str::to_owned)
+        .ok_or_else(|| FaeLlmError::ProviderError("batch response has no id".to_owned()))
+}
+
+#[async_trait]
+impl BatchProvider for HttpBatchProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn submit(&self, requests: &[BatchRequest]) -> Result<BatchHandle, FaeLlmError> {
+        let client = self.client()?;
+        let id = match self.api {
+            BatchApi::OpenAi => {
+                let jsonl = openai_batch_jsonl(requests, &self.model, self.max_tokens);
+                let (content_type, form) = multipart_form(&jsonl);
+                let files_url = format!("{}/files", self.base_url);
+                let upload = client
+                    .post(&files_url)
+                    .header("content-type", content_type)
+                    .body(form);
+                let file = parse_json(&self.send(&files_url, upload).await?)?;
+                let batches_url = format!("{}/batches", self.base_url);
+                let create = client.post(&batches_url).json(&json!({
+                    "input_file_id": batch_id(&file)?,
+                    "endpoint": "/v1/chat/completions",
+                    "completion_window": "24h",
+                }));
+                batch_id(&parse_json(&self.send(&batches_url, create).await?)?)?
+            }
+            BatchApi::Anthropic => {
+                let url = self.anthropic_batches_url();
+                let create = client.post(&url).json(&anthropic_batch_body(
+                    requests,
+                    &self.model,
+                    self.max_tokens,
+                ));
+                batch_id(&parse_json(&self.send(&url, create).await?)?)?
+            }
+        };
+        Ok(BatchHandle { api: self.api, id })
+    }
+
+    async fn poll(&self, handle: &BatchHandle) -> Result<BatchStatus, FaeLlmError> {
+        Ok(match handle.api {
+            BatchApi::OpenAi => openai_status(
+                &self
+                    .get_json(&format!("{}/batches/{}", self.base_url, handle.id))
+                    .await?,
+            ),
+            BatchApi::Anthropic => anthropic_status(
+                &self
+                    .get_json(&format!("{}/{}", self.anthropic_batches_url(), handle.id))
+                    .await?,
+            ),
+        })
+    }
+
+    async fn collect(&self, handle: &BatchHandle) -> Result<Vec<BatchResult>, FaeLlmError> {
+        let (batch, results_url) = match handle.api {
+            BatchApi::OpenAi => {
+                let batch = self
+                    .get_json(&format!("{}/batches/{}", self.base_url, handle.id))
+                    .await?;
+                let url = batch
+                    .get("output_file_id")
+                    .or_else(|| batch.get("error_file_id"))
+                    .and_then(Value::as_str)
+                    .map(|file| format!("{}/files/{file}/content", self.base_url));
+                (batch, url)
+            }
+            BatchApi::Anthropic => {
+                let batch = self
+                    .get_json(&format!("{}/{}", self.anthropic_batches_url(), handle.id))
+                    .await?;
+                let url = batch
+                    .get("results_url")
+                    .and_then(Value::as_str)
+                    .map(str::to_owned);
+                (batch, url)
+            }
+        };
+        let Some(url) = results_url else {
+            return Err(FaeLlmError::ProviderError(format!(
+                "batch {} has no results (status: {})",
+                handle.id,
+                batch_state(&batch)
+            )));
+        };
+        let jsonl = self.send(&url, self.client()?.get(&url)).await?;
+        Ok(jsonl
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .filter_map(|line| match handle.api {
+                BatchApi::OpenAi => openai_result(&line),
+                BatchApi::Anthropic => anthropic_result(&line),
+            })
+            .collect())
+    }
+}
+
+fn batch_state(batch: &Value) -> &str {
+    batch
+        .get("status")
+        .or_else(|| batch.get("processing_status"))
+        .and_then(Value::as_str)
+        .unwrap_or("unknown")
+}
+
+/// Chat messages for one request in OpenAI wire format.
+fn openai_messages(request: &BatchRequest) -> Vec<Value> {
+    let mut messages = Vec::with_capacity(2);
+    if let Some(system) = &request.system {
+        messages.push(json!({"role": "system", "content": system}));
+    }
+    messages.push(json!({"role": "user", "content": request.prompt}));
+    messages
+}
+
+/// The OpenAI batch input file: one chat/completions request per line.
+pub fn openai_batch_jsonl(requests: &[BatchRequest], model: &str, max_tokens: usize) -> String {
+    requests
+        .iter()
+        .map(|request| {
+            json!({
+                "custom_id": request.custom_id,
+                "method": "POST",
+                "url": "/v1/chat/completions",
+                "body": {
+                    "model": model,
+                    "messages": openai_messages(request),
+                    "max_tokens": max_tokens,
+                },
+            })
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `multipart/form-data` upload of `jsonl` as a batch input file.
+///
+/// Returns the content type (with boundary) and the body.
+fn multipart_form(jsonl: &str) -> (String, String) {
+    let boundary = format!("fae-batch-{}", uuid::Uuid::new_v4().simple());
+    let body = format!(
+        "--{boundary}\r\n\
+         Content-Disposition: form-data; name=\"purpose\"\r\n\r\n\
+         batch\r\n\
+         --{boundary}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"batch.jsonl\"\r\n\
+         Content-Type: application/jsonl\r\n\r\n\
+         {jsonl}\r\n\
+         --{boundary}--\r\n"
+    );
+    (format!("multipart/form-data; boundary={boundary}"), body)
+}
+
+/// Map an OpenAI batch object to a [`BatchStatus`].
+pub fn openai_status(batch: &Value) -> BatchStatus {
+    match batch_state(batch) {
+        "completed" => BatchStatus::Completed,
+        "expired" => BatchStatus::Expired,
+        "cancelling" | "cancelled" => BatchStatus::Cancelled,
+        "failed" => BatchStatus::Failed(
+            batch
+                .pointer("/errors/data/0/message")
+                .and_then(Value::as_str)
+                .unwrap_or("batch failed")
+                .to_owned(),
+        ),
+        // validating, in_progress, finalizing
+        _ => BatchStatus::InProgress,
+    }
+}
+
+/// Read one line of an OpenAI batch output or error file.
+pub fn openai_result(line: &Value) -> Option<BatchResult> {
+    let custom_id = line.get("custom_id")?.as_str()?.to_owned();
+    let error = line
+        .pointer("/error/message")
+        .or_else(|| line.pointer("/response/body/error/message"))
+        .and_then(Value::as_str);
+    let outcome = match error {
+        Some(error) => Err(error.to_owned()),
+        None => line
+            .pointer("/response/body/choices/0/message/content")
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+            .ok_or_else(|| "response has no message content".to_owned()),
+    };
+    Some(BatchResult { custom_id, outcome })
+}
+
+/// The Anthropic Message Batches create body.
+pub fn anthropic_batch_body(requests: &[BatchRequest], model: &str, max_tokens: usize) -> Value {
+    let requests: Vec<Value> = requests
+        .iter()
+        .map(|request| {
+            let mut params = json!({
+                "model": model,
+                "max_tokens": max_tokens,
+                "messages": [{"role": "user", "content": request.prompt}],
+            });
+            if let Some(system) = &request.system {
+                params["system"] = json!(system);
+            }
+            json!({"custom_id": request.custom_id, "params": params})
+        })
+        .collect();
+    json!({ "requests": requests })
+}
+
+/// Map an Anthropic message batch object to a [`BatchStatus`].
+///
+/// An ended batch is [`Completed`](BatchStatus::Completed) even when some
+/// requests errored; those surface as failed [`BatchResult`]s. It is only
+/// [`Expired`](BatchStatus::Expired) or [`Cancelled`](BatchStatus::Cancelled)
+/// when nothing succeeded.
+pub fn anthropic_status(batch: &Value) -> BatchStatus {
+    match batch_state(batch) {
+        "ended" => {
+            let count = |key: &str| {
+                batch
+                    .get("request_counts")
+                    .and_then(|c| c.get(key))
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0)
+            };
+            if count("succeeded") > 0 || count("errored") > 0 {
+                BatchStatus::Completed
+            } else if count("expired") > 0 {
+                BatchStatus::Expired
+            } else if count("canceled") > 0 {
+                BatchStatus::Cancelled
+            } else {
+                BatchStatus::Completed
+            }
+        }
+        "canceling" => BatchStatus::Cancelled,
+        _ => BatchStatus::InProgress,
+    }
+}
+
+/// Read one line of an Anthropic batch results file.
+pub fn anthropic_result(line: &Value) -> Option<BatchResult> {
+    let custom_id = line.get("custom_id")?.as_str()?.to_owned();
+    let result = line.get("result")?;
+    let outcome = match result.get("type").and_then(Value::as_str) {
+        Some("succeeded") => {
+            let text: String = result
+                .pointer("/message/content")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+                .filter_map(|block| block.get("text").and_then(Value::as_str))
+                .collect();
+            Ok(text)
+        }
+        Some("errored") => Err(result
+            .pointer("/error/error/message")
+            .and_then(Value::as_str)
+            .unwrap_or("request errored")
+            .to_owned()),
+        Some(other) => Err(format!("request {other}")),
+        None => Err("result has no type".to_owned()),
+    };
+    Some(BatchResult { custom_id, outcome })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fae_llm::config::SecretRef;
+
+    fn request(id: &str) -> BatchRequest {
+        BatchRequest {
+            custom_id: id.to_owned(),
+            system: Some("Be brief.".to_owned()),
+            prompt: "Summarize today.".to_owned(),
+        }
+    }
+
+    fn provider(endpoint_type: EndpointType) -> ProviderConfig {
+        ProviderConfig {
+            endpoint_type,
+            enabled: true,
+            base_url: "https://api.example.com/v1".to_owned(),
+            api_key: SecretRef::None,
+            models: vec!["m".to_owned()],
+            azure: None,
+        }
+    }
+
+    #[test]
+    fn only_openai_and_anthropic_endpoints_batch() {
+        assert!(
+            batch_provider_for("a", &provider(EndpointType::OpenAiCompletions), "m", 64).is_some()
+        );
+        assert!(
+            batch_provider_for("a", &provider(EndpointType::AnthropicMessages), "m", 64).is_some()
+        );
+        assert!(batch_provider_for("a", &provider(EndpointType::Local), "m", 64).is_none());
+        assert!(
+            batch_provider_for("a", &provider(EndpointType::OpenAiResponses), "m", 64).is_none()
+        );
+    }
+
+    #[test]
+    fn default_provider_selects_the_batch_api() {
+        let mut config = FaeLlmConfig::default();
+        assert!(batch_provider_from_config(&config).is_none());
+        config.providers.insert(
+            "anthropic".to_owned(),
+            provider(EndpointType::AnthropicMessages),
+        );
+        config.defaults.default_provider = Some("anthropic".to_owned());
+        let batch = batch_provider_from_config(&config);
+        assert_eq!(batch.as_ref().map(|b| b.name()), Some("anthropic"));
+    }
+
+    #[test]
+    fn openai_input_file_has_one_request_per_line() {
+        let jsonl = openai_batch_jsonl(&[request("a"), request("b")], "gpt-4o-mini", 256);
+        let lines: Vec<Value> = jsonl
+            .lines()
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["custom_id"], "b");
+        assert_eq!(lines[0]["url"], "/v1/chat/completions");
+        assert_eq!(lines[0]["body"]["messages"][0]["role"], "system");
+        assert_eq!(lines[0]["body"]["max_tokens"], 256);
+    }
+
+    #[test]
+    fn openai_statuses_and_results_are_mapped() {
+        assert_eq!(
+            openai_status(&json!({"status": "finalizing"})),
+            BatchStatus::InProgress
+        );
+        assert_eq!(
+            openai_status(&json!({"status": "completed"})),
+            BatchStatus::Completed
+        );
+        assert_eq!(
+            openai_status(
+                &json!({"status": "failed", "errors": {"data": [{"message": "bad line"}]}})
+            ),
+            BatchStatus::Failed("bad line".to_owned())
+        );
+
+        let ok = openai_result(&json!({
+            "custom_id": "a",
+            "response": {"status_code": 200, "body": {"choices": [{"message": {"content": "Done."}}]}},
+            "error": null
+        }));
+        assert_eq!(ok.map(|r| r.outcome), Some(Ok("Done.".to_owned())));
+        let failed = openai_result(&json!({"custom_id": "b", "error": {"message": "quota"}}));
+        assert_eq!(failed.map(|r| r.outcome), Some(Err("quota".to_owned())));
+    }
+
+    #[test]
+    fn anthropic_body_carries_system_prompt_in_params() {
+        let body = anthropic_batch_body(&[request("a")], "claude-haiku", 128);
+        assert_eq!(body["requests"][0]["custom_id"], "a");
+        assert_eq!(body["requests"][0]["params"]["system"], "Be brief.");
+        assert_eq!(
+            body["requests"][0]["params"]["messages"][0]["content"],
+            "Summarize today."
+        );
+    }
+
+    #[test]
+    fn anthropic_statuses_and_results_are_mapped() {
+        assert_eq!(
+            anthropic_status(&json!({"processing_status": "in_progress"})),
+            BatchStatus::InProgress
+        );
+        assert_eq!(
+            anthropic_status(
+                &json!({"processing_status": "ended", "request_counts": {"succeeded": 1}})
+            ),
+            BatchStatus::Completed
+        );
+        assert_eq!(
+            anthropic_status(
+                &json!({"processing_status": "ended", "request_counts": {"expired": 1}})
+            ),
+            BatchStatus::Expired
+        );
+
+        let ok = anthropic_result(&json!({
+            "custom_id": "a",
+            "result": {"type": "succeeded", "message": {"content": [
+                {"type": "text", "text": "All "},
+                {"type": "text", "text": "done."}
+            ]}}
+        }));
+        assert_eq!(ok.map(|r| r.outcome), Some(Ok("All done.".to_owned())));
+        let errored = anthropic_result(&json!({
+            "custom_id": "b",
+            "result": {"type": "errored", "error": {"type": "error", "error": {"type": "invalid_request_error", "message": "too long"}}}
+        }));
+        assert_eq!(errored.map(|r| r.outcome), Some(Err("too long".to_owned())));
+    }
+}
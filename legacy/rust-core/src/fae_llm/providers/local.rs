@@ -6,9 +6,15 @@
 //! **Streaming**: Events are yielded in real-time as tokens arrive from
 //! mistralrs, enabling TTS to begin speaking the first sentence while the
 //! model is still generating. This is critical for perceived latency.
+//!
+//! **Prefix reuse**: mistralrs keeps recently processed prompts in its prefix
+//! cache, so a turn that only appends to the previous conversation skips
+//! prefill for the shared part. The adapter tracks the previous request's
+//! messages to log how much of each prompt should hit that cache.
 
 use async_trait::async_trait;
-use std::sync::Arc;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
@@ -37,6 +43,9 @@ pub struct LocalMistralrsConfig {
     ///
     /// `None` means no top-k constraint (all tokens eligible).
     pub top_k: Option<usize>,
+    /// Whether the model accepts images. Images on messages sent to a
+    /// text-only model are dropped.
+    pub vision: bool,
 }
 
 impl LocalMistralrsConfig {
@@ -49,6 +58,7 @@ impl LocalMistralrsConfig {
             temperature: 0.7,
             top_p: 0.9,
             top_k: None,
+            vision: false,
         }
     }
 
@@ -75,6 +85,12 @@ impl LocalMistralrsConfig {
         self.top_k = Some(k);
         self
     }
+
+    /// Set whether the model accepts images.
+    pub fn with_vision(mut self, vision: bool) -> Self {
+        self.vision = vision;
+        self
+    }
 }
 
 /// Local mistralrs provider adapter.
@@ -87,12 +103,68 @@ impl LocalMistralrsConfig {
 /// waiting for the entire response to complete.
 pub struct LocalMistralrsAdapter {
     config: LocalMistralrsConfig,
+    prefix: Mutex<PrefixTracker>,
 }
 
 impl LocalMistralrsAdapter {
     /// Create a new adapter.
     pub fn new(config: LocalMistralrsConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            prefix: Mutex::new(PrefixTracker::default()),
+        }
+    }
+}
+
+/// Message fingerprints of the previous request.
+///
+/// mistralrs matches cached prefixes itself; this only estimates how many
+/// leading messages of a request it can serve from the cache.
+#[derive(Debug, Default)]
+struct PrefixTracker {
+    /// Hash of request-wide settings that change the rendered prompt
+    /// (thinking mode, tool set).
+    template_key: u64,
+    messages: Vec<u64>,
+}
+
+impl PrefixTracker {
+    /// Record a request and return how many of its leading messages match
+    /// the previous one.
+    fn observe(&mut self, template_key: u64, messages: Vec<u64>) -> usize {
+        let shared = if template_key == self.template_key {
+            shared_prefix_len(&self.messages, &messages)
+        } else {
+            0
+        };
+        self.template_key = template_key;
+        self.messages = messages;
+        shared
+    }
+}
+
+fn shared_prefix_len(previous: &[u64], next: &[u64]) -> usize {
+    previous
+        .iter()
+        .zip(next)
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
+fn fingerprint(value: &impl serde::Serialize) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    serde_json::to_string(value)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+fn message_chars(message: &Message) -> usize {
+    match &message.content {
+        crate::fae_llm::providers::message::MessageContent::Text { text } => text.len(),
+        crate::fae_llm::providers::message::MessageContent::ToolResult { content, .. } => {
+            content.len()
+        }
     }
 }
 
@@ -114,6 +186,33 @@ impl ProviderAdapter for LocalMistralrsAdapter {
         EndpointType::Local
     }
 
+    fn supports_images(&self) -> bool {
+        self.config.vision
+    }
+
+    /// Prefill the prompt with a one-token generation.
+    ///
+    /// mistralrs keeps the processed prefix in its prefix cache, so the next
+    /// request sharing these messages (and options that affect the prompt,
+    /// such as the reasoning level) skips most of its prefill.
+    async fn warm_up(
+        &self,
+        messages: &[Message],
+        options: &RequestOptions,
+        tools: &[crate::fae_llm::provider::ToolDefinition],
+    ) -> std::result::Result<(), FaeLlmError> {
+        use futures_util::StreamExt;
+
+        let options = options.clone().with_max_tokens(1);
+        let mut stream = self.send(messages, &options, tools).await?;
+        while let Some(event) = stream.next().await {
+            if let LlmEvent::StreamError { error } = event {
+                return Err(FaeLlmError::StreamError(error));
+            }
+        }
+        Ok(())
+    }
+
     async fn send(
         &self,
         messages: &[Message],
@@ -150,6 +249,7 @@ impl ProviderAdapter for LocalMistralrsAdapter {
                     crate::fae_llm::providers::message::MessageContent::ToolResult {
                         call_id,
                         content,
+                        ..
                     },
                 ) => {
                     request = request.add_tool_message(content, call_id);
@@ -220,25 +320,47 @@ impl ProviderAdapter for LocalMistralrsAdapter {
                         text = format!("{prefix}\n\n{text}");
                         injected_think_prefix = true;
                     }
+                    if self.config.vision && !msg.images.is_empty() {
+                        let images = msg
+                            .images
+                            .iter()
+                            .filter_map(|attachment| {
+                                image::load_from_memory(&attachment.bytes()?).ok()
+                            })
+                            .collect();
+                        request = request
+                            .add_image_message(mistral_role, &text, images, &self.config.model)
+                            .map_err(|e| {
+                                FaeLlmError::RequestError(format!(
+                                    "failed to add image message: {e}"
+                                ))
+                            })?;
+                        continue;
+                    }
                     request = request.add_message(mistral_role, &text);
                 }
             }
         }
 
-        // Log prompt size for performance diagnosis.
-        let approx_chars: usize = messages
-            .iter()
-            .map(|m| match &m.content {
-                crate::fae_llm::providers::message::MessageContent::Text { text } => text.len(),
-                crate::fae_llm::providers::message::MessageContent::ToolResult {
-                    content, ..
-                } => content.len(),
-            })
-            .sum();
+        // Log prompt size and expected prefix-cache reuse for performance
+        // diagnosis.
+        let approx_chars: usize = messages.iter().map(message_chars).sum();
+        let template_key = fingerprint(&(
+            thinking_enabled,
+            tools.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+        ));
+        let cached_messages = self
+            .prefix
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .observe(template_key, messages.iter().map(fingerprint).collect());
+        let cached_chars: usize = messages[..cached_messages].iter().map(message_chars).sum();
         tracing::info!(
             messages = messages.len(),
             approx_chars,
             approx_tokens = approx_chars / 4,
+            cached_messages,
+            cached_approx_tokens = cached_chars / 4,
             tools = tools.len(),
             "prompt size estimate"
         );
@@ -252,15 +374,43 @@ impl ProviderAdapter for LocalMistralrsAdapter {
             .max_tokens
             .map(|v| v as usize)
             .unwrap_or(self.config.max_tokens);
+        // mistralrs has no separate thinking limit: thinking tokens count
+        // against the generation length, so the budget is added on top and
+        // the answer keeps its own allowance.
+        let thinking_budget = options
+            .thinking_budget
+            .filter(|_| thinking_enabled)
+            .map_or(0, |tokens| tokens as usize);
+        let max_tokens = max_tokens + thinking_budget;
 
         request = request
             .set_sampler_temperature(temperature)
             .set_sampler_topp(top_p)
             .set_sampler_max_len(max_tokens)
             .enable_thinking(thinking_enabled);
-        if let Some(k) = self.config.top_k {
+        if let Some(k) = options.top_k.map(|k| k as usize).or(self.config.top_k) {
             request = request.set_sampler_topk(k);
         }
+        if let Some(min_p) = options.min_p {
+            request = request.set_sampler_minp(min_p);
+        }
+        if let Some(penalty) = options.frequency_penalty {
+            request = request.set_sampler_frequency_penalty(penalty as f32);
+        }
+        if let Some(penalty) = options.presence_penalty {
+            request = request.set_sampler_presence_penalty(penalty as f32);
+        }
+        if !options.stop.is_empty() {
+            request =
+                request.set_sampler_stop_toks(mistralrs::StopTokens::Seqs(options.stop.clone()));
+        }
+        if options.repetition_penalty.is_some() || options.seed.is_some() {
+            tracing::debug!(
+                repetition_penalty = ?options.repetition_penalty,
+                seed = ?options.seed,
+                "local backend does not support per-request repetition penalty or seed; ignoring"
+            );
+        }
 
         // Convert fae_llm tool definitions to mistralrs format
         let mistral_tools: Vec<mistralrs::Tool> = tools
@@ -356,6 +506,7 @@ impl ProviderAdapter for LocalMistralrsAdapter {
             let gen_start = Instant::now();
             let mut first_visible = false;
             let mut reasoning_events: usize = 0;
+            let mut in_thinking = false;
             let mut chunk_count: usize = 0;
             let mut first_chunk_time: Option<Instant> = None;
 
@@ -380,6 +531,26 @@ impl ProviderAdapter for LocalMistralrsAdapter {
                             let has_content =
                                 choice.delta.content.as_ref().is_some_and(|c| !c.is_empty());
 
+                            // Forward reasoning as thinking, never as reply text.
+                            if let Some(ref reasoning) = choice.delta.reasoning_content
+                                && !reasoning.is_empty()
+                            {
+                                let mut events = Vec::with_capacity(2);
+                                if !in_thinking {
+                                    in_thinking = true;
+                                    events.push(LlmEvent::ThinkingStart);
+                                }
+                                events.push(LlmEvent::ThinkingDelta {
+                                    text: reasoning.clone(),
+                                });
+                                for event in events {
+                                    event_count += 1;
+                                    if tx.send(event).await.is_err() {
+                                        return;
+                                    }
+                                }
+                            }
+
                             if has_reasoning && !has_content {
                                 reasoning_events += 1;
                                 if reasoning_events == 1 {
@@ -400,6 +571,13 @@ impl ProviderAdapter for LocalMistralrsAdapter {
                             if let Some(ref content) = choice.delta.content
                                 && !content.is_empty()
                             {
+                                if in_thinking {
+                                    in_thinking = false;
+                                    event_count += 1;
+                                    if tx.send(LlmEvent::ThinkingEnd).await.is_err() {
+                                        return;
+                                    }
+                                }
                                 if !first_visible {
                                     first_visible = true;
                                     let ttft = gen_start.elapsed();
@@ -555,6 +733,11 @@ impl ProviderAdapter for LocalMistralrsAdapter {
                 }
             }
 
+            if in_thinking {
+                event_count += 1;
+                let _ = tx.send(LlmEvent::ThinkingEnd).await;
+            }
+
             // Determine the correct finish reason
             let finish_reason = if has_tool_calls {
                 FinishReason::ToolCalls
@@ -598,3 +781,27 @@ impl ProviderAdapter for LocalMistralrsAdapter {
         Ok(Box::pin(ReceiverStream::new(rx)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_tracker_counts_shared_leading_messages() {
+        let mut tracker = PrefixTracker::default();
+        assert_eq!(tracker.observe(1, vec![10, 20]), 0);
+        // Next turn appends to the conversation.
+        assert_eq!(tracker.observe(1, vec![10, 20, 30, 40]), 2);
+        // Trimming the oldest turn shifts everything after the system prompt.
+        assert_eq!(tracker.observe(1, vec![10, 30, 40, 50]), 1);
+        // A different thinking mode or tool set renders a different prompt.
+        assert_eq!(tracker.observe(2, vec![10, 30, 40, 50]), 0);
+    }
+
+    #[test]
+    fn message_fingerprints_distinguish_content() {
+        let a = fingerprint(&Message::user("hello"));
+        assert_eq!(a, fingerprint(&Message::user("hello")));
+        assert_ne!(a, fingerprint(&Message::assistant("hello")));
+    }
+}
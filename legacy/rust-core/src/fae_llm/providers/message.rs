@@ -15,6 +15,9 @@
 //! assert_eq!(tool_result.role, Role::Tool);
 //! ```
 
+use std::path::PathBuf;
+
+use base64::Engine as _;
 use serde::{Deserialize, Serialize};
 
 /// The role of a message in a conversation.
@@ -45,7 +48,8 @@ impl std::fmt::Display for Role {
 /// The content of a message.
 ///
 /// Most messages contain plain text, but tool results include the
-/// call ID for correlation with the tool call that produced them.
+/// call ID for correlation with the tool call that produced them, and
+/// optionally a structured payload for providers that accept one.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum MessageContent {
@@ -60,6 +64,9 @@ pub enum MessageContent {
         call_id: String,
         /// The tool's output content.
         content: String,
+        /// Structured payload, sent only to providers that support it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        structured: Option<serde_json::Value>,
     },
 }
 
@@ -77,11 +84,94 @@ pub struct AssistantToolCall {
     pub arguments: String,
 }
 
+/// An image attached to a user message.
+///
+/// Either stored inline, base64-encoded so it persists with the session, or
+/// referenced by file path (a screenshot on disk) and read when a request is
+/// built. Images are already fitted to the size budget (see
+/// [`crate::llm::images`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageAttachment {
+    /// MIME type of the encoded image (e.g. `"image/jpeg"`).
+    pub media_type: String,
+    /// Base64-encoded image bytes; empty when `path` is set.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub data: String,
+    /// File holding the encoded image, read in place of `data`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageAttachment {
+    /// Attach a JPEG image.
+    pub fn jpeg(bytes: &[u8], width: u32, height: u32) -> Self {
+        Self {
+            media_type: "image/jpeg".to_owned(),
+            data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            path: None,
+            width,
+            height,
+        }
+    }
+
+    /// Attach the image stored at `path`.
+    pub fn file(
+        path: impl Into<PathBuf>,
+        media_type: impl Into<String>,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self {
+            media_type: media_type.into(),
+            data: String::new(),
+            path: Some(path.into()),
+            width,
+            height,
+        }
+    }
+
+    /// The encoded image bytes, or `None` if the file cannot be read or
+    /// `data` is not valid base64.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        match &self.path {
+            Some(path) => std::fs::read(path).ok(),
+            None => base64::engine::general_purpose::STANDARD
+                .decode(&self.data)
+                .ok(),
+        }
+    }
+
+    /// This image stored inline, so it outlives the file it was read from.
+    /// Unchanged when the file cannot be read.
+    pub fn into_inline(self) -> Self {
+        match self.path.as_ref().and_then(|path| std::fs::read(path).ok()) {
+            Some(bytes) => Self {
+                data: base64::engine::general_purpose::STANDARD.encode(bytes),
+                path: None,
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    /// The image base64-encoded, as remote providers expect it.
+    pub fn base64(&self) -> Option<String> {
+        match &self.path {
+            Some(_) => self
+                .bytes()
+                .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes)),
+            None => Some(self.data.clone()),
+        }
+    }
+}
+
 /// A message in an LLM conversation.
 ///
 /// Messages form the conversation history sent to the provider.
 /// Each message has a role and content, with optional tool calls
-/// for assistant messages.
+/// for assistant messages and optional images for user messages.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Message {
     /// Who sent this message.
@@ -91,6 +181,9 @@ pub struct Message {
     /// Tool calls made by the assistant (only for Assistant role).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tool_calls: Vec<AssistantToolCall>,
+    /// Images the user attached (only for User role).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImageAttachment>,
 }
 
 impl Message {
@@ -100,6 +193,7 @@ impl Message {
             role,
             content: MessageContent::Text { text: text.into() },
             tool_calls: Vec::new(),
+            images: Vec::new(),
         }
     }
 
@@ -113,6 +207,14 @@ impl Message {
         Self::text(Role::User, text)
     }
 
+    /// Create a user message with attached images.
+    pub fn user_with_images(text: impl Into<String>, images: Vec<ImageAttachment>) -> Self {
+        Self {
+            images,
+            ..Self::user(text)
+        }
+    }
+
     /// Create an assistant message.
     pub fn assistant(text: impl Into<String>) -> Self {
         Self::text(Role::Assistant, text)
@@ -129,6 +231,7 @@ impl Message {
                 text: text.unwrap_or_default(),
             },
             tool_calls,
+            images: Vec::new(),
         }
     }
 
@@ -139,8 +242,29 @@ impl Message {
             content: MessageContent::ToolResult {
                 call_id: call_id.into(),
                 content: content.into(),
+                structured: None,
+            },
+            tool_calls: Vec::new(),
+            images: Vec::new(),
+        }
+    }
+
+    /// Create a tool result message carrying a structured payload alongside
+    /// its text rendering.
+    pub fn structured_tool_result(
+        call_id: impl Into<String>,
+        content: impl Into<String>,
+        structured: serde_json::Value,
+    ) -> Self {
+        Self {
+            role: Role::Tool,
+            content: MessageContent::ToolResult {
+                call_id: call_id.into(),
+                content: content.into(),
+                structured: Some(structured),
             },
             tool_calls: Vec::new(),
+            images: Vec::new(),
         }
     }
 }
@@ -196,9 +320,12 @@ mod tests {
         let content = MessageContent::ToolResult {
             call_id: "call_1".into(),
             content: "output".into(),
+            structured: None,
         };
         match &content {
-            MessageContent::ToolResult { call_id, content } => {
+            MessageContent::ToolResult {
+                call_id, content, ..
+            } => {
                 assert_eq!(call_id, "call_1");
                 assert_eq!(content, "output");
             }
@@ -211,6 +338,7 @@ mod tests {
         let original = MessageContent::ToolResult {
             call_id: "tc_1".into(),
             content: "result data".into(),
+            structured: None,
         };
         let json = serde_json::to_string(&original).unwrap_or_default();
         let parsed: Result<MessageContent, _> = serde_json::from_str(&json);
@@ -270,14 +398,36 @@ mod tests {
         let msg = Message::tool_result("call_1", "file contents");
         assert_eq!(msg.role, Role::Tool);
         match &msg.content {
-            MessageContent::ToolResult { call_id, content } => {
+            MessageContent::ToolResult {
+                call_id,
+                content,
+                structured,
+            } => {
                 assert_eq!(call_id, "call_1");
                 assert_eq!(content, "file contents");
+                assert!(structured.is_none());
             }
             _ => unreachable!("expected ToolResult"),
         }
     }
 
+    #[test]
+    fn structured_tool_result_serde() {
+        let msg = Message::structured_tool_result(
+            "call_1",
+            "2 rows",
+            serde_json::json!([{ "id": 1 }, { "id": 2 }]),
+        );
+        let json = serde_json::to_string(&msg).unwrap_or_default();
+        assert!(json.contains("\"structured\""));
+        let parsed: Result<Message, _> = serde_json::from_str(&json);
+        assert_eq!(parsed.ok(), Some(msg));
+
+        // Text-only results keep the previous wire format.
+        let plain = serde_json::to_string(&Message::tool_result("c", "x")).unwrap_or_default();
+        assert!(!plain.contains("structured"));
+    }
+
     #[test]
     fn message_serde_round_trip() {
         let original = Message::user("test message");
@@ -310,6 +460,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn message_with_images_serde_round_trip() {
+        let image = ImageAttachment::jpeg(&[0xff, 0xd8, 0xff], 2, 1);
+        assert_eq!(image.bytes(), Some(vec![0xff, 0xd8, 0xff]));
+        let original = Message::user_with_images("what is this?", vec![image]);
+        let json = serde_json::to_string(&original).unwrap_or_default();
+        match serde_json::from_str::<Message>(&json) {
+            Ok(p) => assert_eq!(p, original),
+            Err(_) => unreachable!("deserialization succeeded"),
+        }
+
+        // Text-only messages keep the previous wire format.
+        let plain = serde_json::to_string(&Message::user("x")).unwrap_or_default();
+        assert!(!plain.contains("images"));
+    }
+
     #[test]
     fn message_clone() {
         let msg = Message::user("test");
@@ -6,10 +6,26 @@
 //!
 //! # Available providers
 //!
+//! - [`batch`] — OpenAI and Anthropic batch APIs for latency-insensitive jobs
 //! - [`message`] — Shared message types for all providers
 //! - [`local`] — Local mistralrs GGUF inference (embedded models)
+//! - [`openrouter`] — OpenRouter headers, priced model catalog, cheapest-route selection
+//! - [`pii_mask`] — Personal data masking wrapper for remote providers
+//! - [`profile`] — Wire-format differences of OpenAI-compatible providers (xAI, Mistral)
+//! - [`responses`] — OpenAI Responses API stream event normalization
+//! - [`validate`] — Live API key checks for remote providers
+//! - [`vision`] — Remote vision models for turns with images
 
+pub mod batch;
 pub mod local;
 pub mod message;
+pub mod openrouter;
+pub mod pii_mask;
+pub mod profile;
+pub mod responses;
+pub mod validate;
+pub mod vision;
 
 pub use local::{LocalMistralrsAdapter, LocalMistralrsConfig};
+pub use pii_mask::{PiiMasker, PiiMaskingLevel, PiiMaskingProvider};
+pub use vision::VisionRoutingProvider;
@@ -0,0 +1,303 @@
+//! OpenRouter support: attribution headers, the priced model catalog, and
+//! price-aware model selection.
+//!
+//! OpenRouter speaks the OpenAI chat/completions protocol, so it is
+//! configured as an [`EndpointType::OpenAiCompletions`] provider whose base
+//! URL points at [`OPENROUTER_BASE_URL`]. On top of that it accepts
+//! attribution headers identifying the calling app, and publishes a model
+//! catalog with per-token prices. The catalog is cached on disk so model
+//! selection can show prices without a request each time.
+
+use crate::fae_llm::config::types::ProviderConfig;
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::types::{EndpointType, RequestOptions};
+use crate::fae_llm::usage::{CostEstimate, TokenPricing, TokenUsage};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// OpenRouter's OpenAI-compatible API root.
+pub const OPENROUTER_BASE_URL: &str = "https://openrouter.ai/api/v1";
+
+/// Sent as `HTTP-Referer` so requests are attributed to Fae.
+const APP_REFERER: &str = "https://github.com/saorsa-labs/fae";
+
+/// Sent as `X-Title`.
+const APP_TITLE: &str = "Fae";
+
+/// How long a catalog fetch may take.
+const CATALOG_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Age after which the cached catalog is refreshed.
+pub const CATALOG_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Whether `base_url` points at OpenRouter.
+pub fn is_openrouter(base_url: &str) -> bool {
+    base_url
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .starts_with("openrouter.ai")
+}
+
+/// Whether `provider` is an OpenRouter provider.
+pub fn is_openrouter_provider(provider: &ProviderConfig) -> bool {
+    provider.endpoint_type == EndpointType::OpenAiCompletions && is_openrouter(&provider.base_url)
+}
+
+/// Add OpenRouter's app attribution headers to `options`.
+pub fn with_attribution(options: RequestOptions) -> RequestOptions {
+    options
+        .with_header("HTTP-Referer", APP_REFERER)
+        .with_header("X-Title", APP_TITLE)
+}
+
+/// One model in the OpenRouter catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenRouterModel {
+    /// Model id used in requests (e.g. `"anthropic/claude-sonnet-4"`).
+    pub id: String,
+    /// Display name.
+    pub name: String,
+    /// Context window in tokens, if published.
+    pub context_length: Option<u64>,
+    /// Prices in USD per 1M tokens.
+    pub pricing: TokenPricing,
+}
+
+impl OpenRouterModel {
+    /// Estimated cost of `usage` on this model.
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        CostEstimate::calculate(usage, &self.pricing).usd
+    }
+}
+
+#[derive(Deserialize)]
+struct CatalogResponse {
+    data: Vec<RawModel>,
+}
+
+#[derive(Deserialize)]
+struct RawModel {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    context_length: Option<u64>,
+    pricing: RawPricing,
+}
+
+/// Per-token USD prices, published as decimal strings.
+#[derive(Deserialize)]
+struct RawPricing {
+    prompt: String,
+    completion: String,
+}
+
+fn per_million(per_token: &str) -> Option<f64> {
+    per_token
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|p| p * 1_000_000.0)
+}
+
+/// Parse a `GET /models` response.
+///
+/// Models without a fixed price (routers such as `openrouter/auto` publish
+/// `-1`) are left out, since their cost cannot be estimated.
+///
+/// # Errors
+/// Returns `FaeLlmError::ProviderError` if the document is not a model list.
+pub fn parse_catalog(json: &str) -> Result<Vec<OpenRouterModel>, FaeLlmError> {
+    let response: CatalogResponse = serde_json::from_str(json)
+        .map_err(|e| FaeLlmError::ProviderError(format!("invalid OpenRouter model list: {e}")))?;
+    Ok(response
+        .data
+        .into_iter()
+        .filter_map(|raw| {
+            let pricing = TokenPricing::try_new(
+                per_million(&raw.pricing.prompt)?,
+                per_million(&raw.pricing.completion)?,
+            )
+            .ok()?;
+            Some(OpenRouterModel {
+                name: raw.name.unwrap_or_else(|| raw.id.clone()),
+                id: raw.id,
+                context_length: raw.context_length,
+                pricing,
+            })
+        })
+        .collect())
+}
+
+/// Path of the cached catalog (`cache_dir()/openrouter_models.json`).
+pub fn cached_catalog_path() -> PathBuf {
+    crate::fae_dirs::cache_dir().join("openrouter_models.json")
+}
+
+/// The cached catalog, if one has been fetched.
+pub fn cached_catalog() -> Option<Vec<OpenRouterModel>> {
+    read_cache(&cached_catalog_path())
+}
+
+fn read_cache(path: &Path) -> Option<Vec<OpenRouterModel>> {
+    let json = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Whether the cached catalog is missing or older than [`CATALOG_MAX_AGE`].
+pub fn catalog_is_stale() -> bool {
+    std::fs::metadata(cached_catalog_path())
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_none_or(|age| age > CATALOG_MAX_AGE)
+}
+
+/// Fetch the model catalog from `provider` and cache it.
+///
+/// The model list is public, so the key is sent only when one resolves.
+///
+/// # Errors
+/// Returns `FaeLlmError::RequestError` / `FaeLlmError::TimeoutError` when
+/// OpenRouter is unreachable (including offline mode), and
+/// `FaeLlmError::ProviderError` for HTTP failures or an invalid response.
+pub async fn refresh_catalog(
+    provider: &ProviderConfig,
+) -> Result<Vec<OpenRouterModel>, FaeLlmError> {
+    let url = format!("{}/models", provider.base_url.trim().trim_end_matches('/'));
+    crate::offline::ensure_url_allowed("OpenRouter model catalog", &url)
+        .map_err(|e| FaeLlmError::RequestError(e.to_string()))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(CATALOG_TIMEOUT)
+        .build()
+        .map_err(|e| FaeLlmError::RequestError(format!("HTTP client: {e}")))?;
+    let mut request = client
+        .get(&url)
+        .header("HTTP-Referer", APP_REFERER)
+        .header("X-Title", APP_TITLE);
+    if let Ok(Some(key)) = provider.api_key.resolve() {
+        request = request.bearer_auth(key.trim());
+    }
+    let response = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            FaeLlmError::TimeoutError(format!("no response from {url}"))
+        } else {
+            FaeLlmError::RequestError(format!("cannot reach {url}: {e}"))
+        }
+    })?;
+    if !response.status().is_success() {
+        return Err(FaeLlmError::ProviderError(format!(
+            "OpenRouter model list failed (HTTP {})",
+            response.status().as_u16()
+        )));
+    }
+    let body = response
+        .text()
+        .await
+        .map_err(|e| FaeLlmError::RequestError(format!("cannot read {url}: {e}")))?;
+    let models = parse_catalog(&body)?;
+    write_cache(&cached_catalog_path(), &models)
+        .map_err(|e| FaeLlmError::ProviderError(format!("cannot cache model list: {e}")))?;
+    Ok(models)
+}
+
+fn write_cache(path: &Path, models: &[OpenRouterModel]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(models)?)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// The cheapest of `candidates` for a request of roughly `usage`.
+///
+/// Candidates missing from the catalog, or whose context window is smaller
+/// than the request, are skipped. Ties keep the earlier candidate, so the
+/// configured order is the preference among equally priced models.
+pub fn cheapest_route<'a>(
+    catalog: &'a [OpenRouterModel],
+    candidates: &[String],
+    usage: &TokenUsage,
+) -> Option<&'a OpenRouterModel> {
+    let needed = usage.total();
+    candidates
+        .iter()
+        .filter_map(|id| catalog.iter().find(|m| &m.id == id))
+        .filter(|m| m.context_length.is_none_or(|ctx| ctx >= needed))
+        .fold(None, |best: Option<&OpenRouterModel>, m| match best {
+            Some(b) if b.cost(usage) <= m.cost(usage) => Some(b),
+            _ => Some(m),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    const CATALOG: &str = r#"{"data": [
+        {"id": "openrouter/auto", "name": "Auto Router", "context_length": 2000000,
+         "pricing": {"prompt": "-1", "completion": "-1"}},
+        {"id": "anthropic/claude-sonnet-4", "name": "Claude Sonnet 4", "context_length": 200000,
+         "pricing": {"prompt": "0.000003", "completion": "0.000015"}},
+        {"id": "qwen/qwen3-32b", "context_length": 40960,
+         "pricing": {"prompt": "0.0000001", "completion": "0.0000003", "image": "0"}}
+    ]}"#;
+
+    #[test]
+    fn catalog_prices_are_per_million_tokens() {
+        let models = parse_catalog(CATALOG).unwrap();
+        let ids: Vec<_> = models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["anthropic/claude-sonnet-4", "qwen/qwen3-32b"]);
+        assert!((models[0].pricing.input_per_1m - 3.0).abs() < 1e-9);
+        assert!((models[0].pricing.output_per_1m - 15.0).abs() < 1e-9);
+        assert_eq!(models[1].name, "qwen/qwen3-32b");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("openrouter_models.json");
+        write_cache(&path, &models).unwrap();
+        let cached = read_cache(&path).unwrap();
+        assert_eq!(cached.len(), 2);
+        assert_eq!(cached[1].id, "qwen/qwen3-32b");
+        assert!((cached[1].pricing.input_per_1m - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cheapest_route_respects_context_and_candidates() {
+        let models = parse_catalog(CATALOG).unwrap();
+        let candidates = vec![
+            "anthropic/claude-sonnet-4".to_owned(),
+            "qwen/qwen3-32b".to_owned(),
+            "missing/model".to_owned(),
+        ];
+        let short = TokenUsage::new(2_000, 500);
+        assert_eq!(
+            cheapest_route(&models, &candidates, &short).unwrap().id,
+            "qwen/qwen3-32b"
+        );
+        // Too long for Qwen's context window.
+        let long = TokenUsage::new(100_000, 1_000);
+        assert_eq!(
+            cheapest_route(&models, &candidates, &long).unwrap().id,
+            "anthropic/claude-sonnet-4"
+        );
+        assert!(cheapest_route(&models, &[], &short).is_none());
+    }
+
+    #[test]
+    fn openrouter_is_detected_and_attributed() {
+        assert!(is_openrouter(OPENROUTER_BASE_URL));
+        assert!(!is_openrouter("https://api.openai.com/v1"));
+        let options = with_attribution(RequestOptions::default());
+        assert_eq!(
+            options.headers.get("X-Title").map(String::as_str),
+            Some("Fae")
+        );
+        assert!(options.headers.contains_key("HTTP-Referer"));
+    }
+}
@@ -0,0 +1,699 @@
+//! PII masking for requests sent to remote providers.
+//!
+//! [`PiiMaskingProvider`] wraps another provider. Before a request leaves
+//! the machine it replaces emails, phone numbers, street addresses, and
+//! names in user and assistant messages (which carry utterances and recalled
+//! memory snippets) with placeholders such as `[EMAIL_1]`. The same value
+//! always gets the same placeholder within a request. Placeholders the model
+//! echoes back are restored in the streamed response, including tool call
+//! arguments.
+//!
+//! Detection is heuristic and errs towards masking: any run of two or three
+//! capitalised words counts as a name at [`PiiMaskingLevel::Strict`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use super::message::{Message, MessageContent, Role};
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::events::LlmEvent;
+use crate::fae_llm::provider::{LlmEventStream, ProviderAdapter, ToolDefinition};
+use crate::fae_llm::types::{EndpointType, RequestOptions};
+
+/// How much personal data is masked before a remote request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiMaskingLevel {
+    /// Nothing is masked.
+    #[default]
+    Off,
+    /// Emails and phone numbers.
+    Contact,
+    /// Emails, phone numbers, street addresses, and names.
+    Strict,
+}
+
+impl PiiMaskingLevel {
+    /// Whether `kind` is masked at this level.
+    pub fn masks(self, kind: PiiKind) -> bool {
+        match self {
+            Self::Off => false,
+            Self::Contact => matches!(kind, PiiKind::Email | PiiKind::Phone),
+            Self::Strict => true,
+        }
+    }
+}
+
+/// Category of detected personal data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PiiKind {
+    /// Email address.
+    Email,
+    /// Phone number.
+    Phone,
+    /// Street address.
+    Address,
+    /// Person name.
+    Name,
+}
+
+impl PiiKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Email => "EMAIL",
+            Self::Phone => "PHONE",
+            Self::Address => "ADDRESS",
+            Self::Name => "NAME",
+        }
+    }
+}
+
+/// A detected span of personal data (byte offsets into the text).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PiiSpan {
+    /// Start byte offset.
+    pub start: usize,
+    /// End byte offset (exclusive).
+    pub end: usize,
+    /// What was detected.
+    pub kind: PiiKind,
+}
+
+const STREET_SUFFIXES: &[&str] = &[
+    "street",
+    "st",
+    "avenue",
+    "ave",
+    "road",
+    "rd",
+    "lane",
+    "ln",
+    "drive",
+    "dr",
+    "boulevard",
+    "blvd",
+    "way",
+    "court",
+    "ct",
+    "place",
+    "pl",
+    "terrace",
+    "crescent",
+    "close",
+];
+
+const HONORIFICS: &[&str] = &["mr", "mrs", "ms", "miss", "dr", "prof"];
+
+/// Longest placeholder the response restorer waits for (e.g. `[ADDRESS_123]`).
+const MAX_PLACEHOLDER_LEN: usize = 16;
+
+/// Find personal data in `text` that `level` masks, in order and without
+/// overlaps.
+pub fn detect_pii(text: &str, level: PiiMaskingLevel) -> Vec<PiiSpan> {
+    let mut spans = Vec::new();
+    if level.masks(PiiKind::Email) {
+        spans.extend(detect_emails(text));
+    }
+    if level.masks(PiiKind::Phone) {
+        spans.extend(detect_phones(text));
+    }
+    let words = words(text);
+    if level.masks(PiiKind::Address) {
+        spans.extend(detect_addresses(&words));
+    }
+    if level.masks(PiiKind::Name) {
+        spans.extend(detect_names(&words));
+    }
+    spans.sort_by_key(|s| (s.start, std::cmp::Reverse(s.end)));
+    let mut kept: Vec<PiiSpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        if kept.last().is_none_or(|last| span.start >= last.end) {
+            kept.push(span);
+        }
+    }
+    kept
+}
+
+fn detect_emails(text: &str) -> Vec<PiiSpan> {
+    let bytes = text.as_bytes();
+    let local = |b: u8| b.is_ascii_alphanumeric() || b"._%+-".contains(&b);
+    let domain = |b: u8| b.is_ascii_alphanumeric() || b".-".contains(&b);
+    let mut spans = Vec::new();
+    for (at, _) in text.match_indices('@') {
+        let mut start = at;
+        while start > 0 && local(bytes[start - 1]) {
+            start -= 1;
+        }
+        let mut end = at + 1;
+        while end < bytes.len() && domain(bytes[end]) {
+            end += 1;
+        }
+        while end > at + 1 && bytes[end - 1] == b'.' {
+            end -= 1;
+        }
+        let host = &text[at + 1..end];
+        if start < at && host.contains('.') && !host.starts_with('.') {
+            spans.push(PiiSpan {
+                start,
+                end,
+                kind: PiiKind::Email,
+            });
+        }
+    }
+    spans
+}
+
+fn detect_phones(text: &str) -> Vec<PiiSpan> {
+    let bytes = text.as_bytes();
+    let phone_char = |b: u8| b.is_ascii_digit() || b" -.()".contains(&b);
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let starts = bytes[i] == b'+' || bytes[i] == b'(' || bytes[i].is_ascii_digit();
+        if !starts || (i > 0 && bytes[i - 1].is_ascii_alphanumeric()) {
+            i += 1;
+            continue;
+        }
+        let mut end = i + 1;
+        while end < bytes.len() && phone_char(bytes[end]) {
+            end += 1;
+        }
+        // Trailing separators belong to the surrounding text.
+        while end > i && !bytes[end - 1].is_ascii_digit() {
+            end -= 1;
+        }
+        let digits = bytes[i..end].iter().filter(|b| b.is_ascii_digit()).count();
+        let followed_by_word = end < bytes.len() && bytes[end].is_ascii_alphabetic();
+        let plausible = if bytes[i] == b'+' {
+            digits >= 7
+        } else {
+            digits >= 9
+        };
+        if plausible && digits <= 15 && !followed_by_word {
+            spans.push(PiiSpan {
+                start: i,
+                end,
+                kind: PiiKind::Phone,
+            });
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    spans
+}
+
+/// A whitespace-delimited word with trailing punctuation removed.
+#[derive(Debug, Clone, Copy)]
+struct Word<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    /// Whether punctuation after the word ends a phrase.
+    ends_phrase: bool,
+}
+
+fn words(text: &str) -> Vec<Word<'_>> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                let raw = &text[s..i];
+                let trimmed = raw.trim_end_matches(|c: char| ",.;:!?)\"'".contains(c));
+                if !trimmed.is_empty() {
+                    out.push(Word {
+                        text: trimmed,
+                        start: s,
+                        end: s + trimmed.len(),
+                        ends_phrase: trimmed.len() != raw.len(),
+                    });
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn detect_addresses(words: &[Word<'_>]) -> Vec<PiiSpan> {
+    let mut spans = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        let is_number = word.text.len() <= 5
+            && word.text.starts_with(|c: char| c.is_ascii_digit())
+            && word.text.chars().all(|c| c.is_ascii_alphanumeric());
+        if !is_number || word.ends_phrase {
+            continue;
+        }
+        for (offset, candidate) in words.iter().enumerate().skip(i + 1).take(4) {
+            let lower = candidate.text.to_ascii_lowercase();
+            if offset > i + 1 && STREET_SUFFIXES.contains(&lower.as_str()) {
+                spans.push(PiiSpan {
+                    start: word.start,
+                    end: candidate.end,
+                    kind: PiiKind::Address,
+                });
+                break;
+            }
+            if candidate.ends_phrase || !is_capitalised(candidate.text) {
+                break;
+            }
+        }
+    }
+    spans
+}
+
+fn is_capitalised(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(|c| c.is_uppercase())
+        && word.chars().count() >= 2
+        && chars.all(|c| c.is_lowercase() || c == '-' || c == '\'')
+}
+
+fn detect_names(words: &[Word<'_>]) -> Vec<PiiSpan> {
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        let honorific = HONORIFICS.contains(&words[i].text.to_ascii_lowercase().as_str());
+        let first = if honorific { i + 1 } else { i };
+        let mut end = first;
+        while end < words.len() && end - first < 3 && is_capitalised(words[end].text) {
+            end += 1;
+            if words[end - 1].ends_phrase {
+                break;
+            }
+        }
+        let count = end - first;
+        if count >= 2 || (honorific && count == 1) {
+            spans.push(PiiSpan {
+                start: words[first].start,
+                end: words[end - 1].end,
+                kind: PiiKind::Name,
+            });
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    spans
+}
+
+/// Reversible placeholder mapping for one request.
+#[derive(Debug, Clone, Default)]
+pub struct PiiMasker {
+    level: PiiMaskingLevel,
+    by_value: HashMap<String, String>,
+    by_placeholder: HashMap<String, String>,
+    counts: HashMap<PiiKind, usize>,
+}
+
+impl PiiMasker {
+    /// Create a masker for `level`.
+    pub fn new(level: PiiMaskingLevel) -> Self {
+        Self {
+            level,
+            ..Self::default()
+        }
+    }
+
+    /// Number of distinct values masked so far.
+    pub fn len(&self) -> usize {
+        self.by_value.len()
+    }
+
+    /// Whether nothing has been masked.
+    pub fn is_empty(&self) -> bool {
+        self.by_value.is_empty()
+    }
+
+    /// Replace personal data in `text` with placeholders.
+    pub fn mask(&mut self, text: &str) -> String {
+        let spans = detect_pii(text, self.level);
+        if spans.is_empty() {
+            return text.to_owned();
+        }
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for span in spans {
+            out.push_str(&text[last..span.start]);
+            out.push_str(&self.placeholder(&text[span.start..span.end], span.kind));
+            last = span.end;
+        }
+        out.push_str(&text[last..]);
+        out
+    }
+
+    fn placeholder(&mut self, value: &str, kind: PiiKind) -> String {
+        if let Some(existing) = self.by_value.get(value) {
+            return existing.clone();
+        }
+        let n = self.counts.entry(kind).or_insert(0);
+        *n += 1;
+        let placeholder = format!("[{}_{}]", kind.label(), n);
+        self.by_value.insert(value.to_owned(), placeholder.clone());
+        self.by_placeholder
+            .insert(placeholder.clone(), value.to_owned());
+        placeholder
+    }
+
+    /// Replace known placeholders in `text` with the original values.
+    pub fn restore(&self, text: &str) -> String {
+        if self.by_placeholder.is_empty() || !text.contains('[') {
+            return text.to_owned();
+        }
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(open) = rest.find('[') {
+            out.push_str(&rest[..open]);
+            let tail = &rest[open..];
+            let original = tail
+                .find(']')
+                .and_then(|close| self.by_placeholder.get(&tail[..=close]).map(|v| (v, close)));
+            match original {
+                Some((value, close)) => {
+                    out.push_str(value);
+                    rest = &tail[close + 1..];
+                }
+                None => {
+                    out.push('[');
+                    rest = &tail[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Mask user and assistant text and assistant tool call arguments.
+    ///
+    /// System prompts and tool results are passed through unchanged.
+    pub fn mask_messages(&mut self, messages: &[Message]) -> Vec<Message> {
+        messages
+            .iter()
+            .map(|message| {
+                let mut masked = message.clone();
+                if matches!(message.role, Role::User | Role::Assistant) {
+                    if let MessageContent::Text { text } = &mut masked.content {
+                        *text = self.mask(text);
+                    }
+                    for call in &mut masked.tool_calls {
+                        call.arguments = self.mask(&call.arguments);
+                    }
+                }
+                masked
+            })
+            .collect()
+    }
+}
+
+/// Restores placeholders in streamed text, holding back a trailing
+/// fragment that may be the start of a placeholder split across chunks.
+#[derive(Debug, Default)]
+struct StreamRestorer {
+    pending: String,
+}
+
+impl StreamRestorer {
+    fn push(&mut self, masker: &PiiMasker, text: &str) -> String {
+        self.pending.push_str(text);
+        let hold_from = self
+            .pending
+            .rfind('[')
+            .filter(|&open| {
+                !self.pending[open..].contains(']')
+                    && self.pending.len() - open < MAX_PLACEHOLDER_LEN
+            })
+            .unwrap_or(self.pending.len());
+        let ready: String = self.pending.drain(..hold_from).collect();
+        masker.restore(&ready)
+    }
+
+    fn flush(&mut self, masker: &PiiMasker) -> String {
+        let rest = std::mem::take(&mut self.pending);
+        masker.restore(&rest)
+    }
+}
+
+#[derive(Debug, Default)]
+struct RestoreState {
+    text: StreamRestorer,
+    thinking: StreamRestorer,
+    args: HashMap<String, StreamRestorer>,
+}
+
+impl RestoreState {
+    fn map(&mut self, masker: &PiiMasker, event: LlmEvent) -> Vec<LlmEvent> {
+        let mut out = Vec::with_capacity(2);
+        match event {
+            LlmEvent::TextDelta { text } => {
+                let text = self.text.push(masker, &text);
+                if !text.is_empty() {
+                    out.push(LlmEvent::TextDelta { text });
+                }
+            }
+            LlmEvent::ThinkingDelta { text } => {
+                let text = self.thinking.push(masker, &text);
+                if !text.is_empty() {
+                    out.push(LlmEvent::ThinkingDelta { text });
+                }
+            }
+            LlmEvent::ThinkingEnd => {
+                self.flush_thinking(masker, &mut out);
+                out.push(LlmEvent::ThinkingEnd);
+            }
+            LlmEvent::ToolCallArgsDelta {
+                call_id,
+                args_fragment,
+            } => {
+                let args_fragment = self
+                    .args
+                    .entry(call_id.clone())
+                    .or_default()
+                    .push(masker, &args_fragment);
+                if !args_fragment.is_empty() {
+                    out.push(LlmEvent::ToolCallArgsDelta {
+                        call_id,
+                        args_fragment,
+                    });
+                }
+            }
+            LlmEvent::ToolCallEnd { call_id } => {
+                if let Some(mut restorer) = self.args.remove(&call_id) {
+                    let args_fragment = restorer.flush(masker);
+                    if !args_fragment.is_empty() {
+                        out.push(LlmEvent::ToolCallArgsDelta {
+                            call_id: call_id.clone(),
+                            args_fragment,
+                        });
+                    }
+                }
+                out.push(LlmEvent::ToolCallEnd { call_id });
+            }
+            other => {
+                if matches!(
+                    other,
+                    LlmEvent::ToolCallStart { .. }
+                        | LlmEvent::StreamEnd { .. }
+                        | LlmEvent::StreamError { .. }
+                ) {
+                    self.flush_thinking(masker, &mut out);
+                    let text = self.text.flush(masker);
+                    if !text.is_empty() {
+                        out.push(LlmEvent::TextDelta { text });
+                    }
+                }
+                out.push(other);
+            }
+        }
+        out
+    }
+
+    fn flush_thinking(&mut self, masker: &PiiMasker, out: &mut Vec<LlmEvent>) {
+        let text = self.thinking.flush(masker);
+        if !text.is_empty() {
+            out.push(LlmEvent::ThinkingDelta { text });
+        }
+    }
+}
+
+/// Provider wrapper that masks personal data before requests and restores
+/// it in responses.
+pub struct PiiMaskingProvider {
+    inner: Arc<dyn ProviderAdapter>,
+    level: PiiMaskingLevel,
+}
+
+impl PiiMaskingProvider {
+    /// Wrap `inner`, masking at `level`.
+    pub fn new(inner: Arc<dyn ProviderAdapter>, level: PiiMaskingLevel) -> Self {
+        Self { inner, level }
+    }
+
+    /// Wrap `inner` when it is remote and `level` masks anything; otherwise
+    /// return it unchanged.
+    pub fn wrap(
+        inner: Arc<dyn ProviderAdapter>,
+        level: PiiMaskingLevel,
+    ) -> Arc<dyn ProviderAdapter> {
+        if level == PiiMaskingLevel::Off || inner.endpoint_type() == EndpointType::Local {
+            return inner;
+        }
+        Arc::new(Self::new(inner, level))
+    }
+}
+
+#[async_trait]
+impl ProviderAdapter for PiiMaskingProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn endpoint_type(&self) -> EndpointType {
+        self.inner.endpoint_type()
+    }
+
+    fn supports_structured_tool_results(&self) -> bool {
+        self.inner.supports_structured_tool_results()
+    }
+
+    fn supports_images(&self) -> bool {
+        self.inner.supports_images()
+    }
+
+    async fn warm_up(
+        &self,
+        messages: &[Message],
+        options: &RequestOptions,
+        tools: &[ToolDefinition],
+    ) -> Result<(), FaeLlmError> {
+        let masked = PiiMasker::new(self.level).mask_messages(messages);
+        self.inner.warm_up(&masked, options, tools).await
+    }
+
+    async fn send(
+        &self,
+        messages: &[Message],
+        options: &RequestOptions,
+        tools: &[ToolDefinition],
+    ) -> Result<LlmEventStream, FaeLlmError> {
+        let mut masker = PiiMasker::new(self.level);
+        let masked = masker.mask_messages(messages);
+        if masker.is_empty() {
+            return self.inner.send(messages, options, tools).await;
+        }
+        tracing::debug!(
+            provider = self.inner.name(),
+            masked = masker.len(),
+            "masked personal data before remote request"
+        );
+        let stream = self.inner.send(&masked, options, tools).await?;
+        let masker = Arc::new(masker);
+        let state = Arc::new(Mutex::new(RestoreState::default()));
+        let restored = stream.flat_map(move |event| {
+            let events = state
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .map(&masker, event);
+            futures_util::stream::iter(events)
+        });
+        Ok(Box::pin(restored))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::events::FinishReason;
+
+    fn kinds(text: &str, level: PiiMaskingLevel) -> Vec<(PiiKind, &str)> {
+        detect_pii(text, level)
+            .into_iter()
+            .map(|s| (s.kind, &text[s.start..s.end]))
+            .collect()
+    }
+
+    #[test]
+    fn detects_each_kind_at_strict_level() {
+        let text = "Email jane.doe@example.co.uk or call +44 20 7946 0958. \
+                    Send it to Jane Doe at 221B Baker Street, London.";
+        assert_eq!(
+            kinds(text, PiiMaskingLevel::Strict),
+            vec![
+                (PiiKind::Email, "jane.doe@example.co.uk"),
+                (PiiKind::Phone, "+44 20 7946 0958"),
+                (PiiKind::Name, "Jane Doe"),
+                (PiiKind::Address, "221B Baker Street"),
+            ]
+        );
+    }
+
+    #[test]
+    fn contact_level_skips_names_and_dates_are_not_phones() {
+        let text = "Dr Smith said the meeting on 2024-01-15 at 10:30 moved; ring 020 7946 0958";
+        assert_eq!(
+            kinds(text, PiiMaskingLevel::Contact),
+            vec![(PiiKind::Phone, "020 7946 0958")]
+        );
+        assert!(kinds(text, PiiMaskingLevel::Off).is_empty());
+    }
+
+    #[test]
+    fn masking_is_consistent_and_reversible() {
+        let mut masker = PiiMasker::new(PiiMaskingLevel::Contact);
+        let masked = masker.mask("a@b.io wrote to c@d.io, then a@b.io again");
+        assert_eq!(masked, "[EMAIL_1] wrote to [EMAIL_2], then [EMAIL_1] again");
+        assert_eq!(
+            masker.restore("Reply to [EMAIL_2] and [OTHER_1]"),
+            "Reply to c@d.io and [OTHER_1]"
+        );
+    }
+
+    #[test]
+    fn restorer_handles_placeholders_split_across_chunks() {
+        let mut masker = PiiMasker::new(PiiMaskingLevel::Contact);
+        masker.mask("me@home.org");
+        let mut state = RestoreState::default();
+        let mut text = String::new();
+        for chunk in ["Sure, mailing [EM", "AIL_1", "] now [", "x"] {
+            for event in state.map(&masker, LlmEvent::TextDelta { text: chunk.into() }) {
+                if let LlmEvent::TextDelta { text: t } = event {
+                    text.push_str(&t);
+                }
+            }
+        }
+        let end = state.map(
+            &masker,
+            LlmEvent::StreamEnd {
+                finish_reason: FinishReason::Stop,
+            },
+        );
+        if let LlmEvent::TextDelta { text: t } = &end[0] {
+            text.push_str(t);
+        }
+        assert_eq!(text, "Sure, mailing me@home.org now [x");
+        assert!(matches!(end.last(), Some(LlmEvent::StreamEnd { .. })));
+    }
+
+    #[test]
+    fn only_user_and_assistant_messages_are_masked() {
+        let mut masker = PiiMasker::new(PiiMaskingLevel::Contact);
+        let messages = vec![
+            Message::system("Support: help@fae.dev"),
+            Message::user("I'm bob@example.com"),
+        ];
+        let masked = masker.mask_messages(&messages);
+        assert_eq!(masked[0], messages[0]);
+        assert_eq!(masked[1], Message::user("I'm [EMAIL_1]"));
+    }
+}
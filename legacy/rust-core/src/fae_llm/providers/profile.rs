@@ -0,0 +1,377 @@
+//! Compatibility profiles for OpenAI-protocol providers.
+//!
+//! Many providers accept the OpenAI chat/completions wire format but differ
+//! in the details: request field names, accepted `tool_choice` and
+//! `reasoning_effort` values, tool-call id rules, extra finish reasons, and
+//! how reasoning tokens are counted in `usage`. A [`CompatibilityProfile`]
+//! records those differences so requests can be adjusted before sending and
+//! responses normalized on the way back, instead of fields being silently
+//! ignored or misread.
+//!
+//! Built-in profiles: [`OPENAI`] (the reference behaviour), [`XAI`] (Grok)
+//! and [`MISTRAL`] (La Plateforme). [`CompatibilityProfile::for_base_url`]
+//! picks one from a provider's base URL.
+
+use crate::fae_llm::events::FinishReason;
+use crate::fae_llm::usage::TokenUsage;
+use serde_json::Value;
+
+/// How a provider reports reasoning tokens in `usage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasoningAccounting {
+    /// `completion_tokens_details.reasoning_tokens` is part of
+    /// `completion_tokens` (OpenAI).
+    IncludedInCompletion,
+    /// `completion_tokens_details.reasoning_tokens` is reported on top of
+    /// `completion_tokens` (xAI).
+    SeparateFromCompletion,
+    /// Reasoning is not broken out; it is billed inside `completion_tokens`
+    /// (Mistral).
+    NotReported,
+}
+
+/// Which `reasoning_effort` values a provider accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasoningEffortSupport {
+    /// `minimal` / `low` / `medium` / `high`.
+    Full,
+    /// Only `low` and `high`; other levels are rounded to the nearest.
+    LowHigh,
+    /// The field is rejected; it is removed from requests.
+    Unsupported,
+}
+
+/// Constraints on tool-call ids sent back in the conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolCallIdFormat {
+    /// Any string.
+    Any,
+    /// Exactly nine ASCII letters or digits (Mistral).
+    Alphanumeric9,
+}
+
+/// Wire-format differences of one OpenAI-compatible provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompatibilityProfile {
+    /// Profile name.
+    pub name: &'static str,
+    /// Request field for the output token limit.
+    pub max_tokens_field: &'static str,
+    /// Request field for the sampling seed.
+    pub seed_field: &'static str,
+    /// `tool_choice` value that forces a tool call.
+    pub required_tool_choice: &'static str,
+    /// Accepted `reasoning_effort` values.
+    pub reasoning_effort: ReasoningEffortSupport,
+    /// How reasoning tokens appear in `usage`.
+    pub reasoning_accounting: ReasoningAccounting,
+    /// Constraints on tool-call ids.
+    pub tool_call_ids: ToolCallIdFormat,
+    /// Whether tool-call arguments arrive in fragments while streaming;
+    /// when `false` each call arrives whole in a single chunk.
+    pub streams_tool_call_arguments: bool,
+    /// Finish reasons beyond the OpenAI set, and what they mean.
+    pub extra_finish_reasons: &'static [(&'static str, FinishReason)],
+    /// Model-id prefixes of reasoning models that reject sampling
+    /// penalties, `stop` and `reasoning_effort`.
+    pub restricted_reasoning_models: &'static [&'static str],
+}
+
+/// OpenAI reference behaviour.
+pub const OPENAI: CompatibilityProfile = CompatibilityProfile {
+    name: "openai",
+    max_tokens_field: "max_completion_tokens",
+    seed_field: "seed",
+    required_tool_choice: "required",
+    reasoning_effort: ReasoningEffortSupport::Full,
+    reasoning_accounting: ReasoningAccounting::IncludedInCompletion,
+    tool_call_ids: ToolCallIdFormat::Any,
+    streams_tool_call_arguments: true,
+    extra_finish_reasons: &[],
+    restricted_reasoning_models: &[],
+};
+
+/// xAI Grok (`https://api.x.ai/v1`).
+pub const XAI: CompatibilityProfile = CompatibilityProfile {
+    name: "xai",
+    max_tokens_field: "max_tokens",
+    seed_field: "seed",
+    required_tool_choice: "required",
+    reasoning_effort: ReasoningEffortSupport::LowHigh,
+    reasoning_accounting: ReasoningAccounting::SeparateFromCompletion,
+    tool_call_ids: ToolCallIdFormat::Any,
+    streams_tool_call_arguments: false,
+    extra_finish_reasons: &[("end_turn", FinishReason::Stop)],
+    restricted_reasoning_models: &["grok-4"],
+};
+
+/// Mistral La Plateforme (`https://api.mistral.ai/v1`).
+pub const MISTRAL: CompatibilityProfile = CompatibilityProfile {
+    name: "mistral",
+    max_tokens_field: "max_tokens",
+    seed_field: "random_seed",
+    required_tool_choice: "any",
+    reasoning_effort: ReasoningEffortSupport::Unsupported,
+    reasoning_accounting: ReasoningAccounting::NotReported,
+    tool_call_ids: ToolCallIdFormat::Alphanumeric9,
+    streams_tool_call_arguments: false,
+    extra_finish_reasons: &[
+        ("model_length", FinishReason::Length),
+        ("error", FinishReason::Other),
+    ],
+    restricted_reasoning_models: &[],
+};
+
+/// All built-in profiles.
+pub const PROFILES: [&CompatibilityProfile; 3] = [&OPENAI, &XAI, &MISTRAL];
+
+impl CompatibilityProfile {
+    /// The profile for a provider at `base_url`, defaulting to [`OPENAI`].
+    pub fn for_base_url(base_url: &str) -> &'static Self {
+        let host = base_url
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .split(['/', ':'])
+            .next()
+            .unwrap_or_default();
+        match host {
+            "api.x.ai" => &XAI,
+            "api.mistral.ai" | "codestral.mistral.ai" => &MISTRAL,
+            _ => &OPENAI,
+        }
+    }
+
+    /// Look a profile up by name.
+    pub fn by_name(name: &str) -> Option<&'static Self> {
+        PROFILES.into_iter().find(|p| p.name == name)
+    }
+
+    /// Rewrite an OpenAI-shaped request body for this provider.
+    ///
+    /// Expects the OpenAI field names (`max_tokens`, `seed`,
+    /// `tool_choice: "required"`, `reasoning_effort`).
+    pub fn apply_to_request(&self, body: &mut Value) {
+        let Some(obj) = body.as_object_mut() else {
+            return;
+        };
+        rename_field(obj, "max_tokens", self.max_tokens_field);
+        rename_field(obj, "seed", self.seed_field);
+        if obj.get("tool_choice").and_then(Value::as_str) == Some("required") {
+            obj.insert(
+                "tool_choice".to_owned(),
+                Value::from(self.required_tool_choice),
+            );
+        }
+
+        let model = obj.get("model").and_then(Value::as_str).unwrap_or_default();
+        if self
+            .restricted_reasoning_models
+            .iter()
+            .any(|prefix| model.starts_with(prefix))
+        {
+            for field in [
+                "presence_penalty",
+                "frequency_penalty",
+                "stop",
+                "reasoning_effort",
+            ] {
+                obj.remove(field);
+            }
+            return;
+        }
+
+        match self.reasoning_effort {
+            ReasoningEffortSupport::Full => {}
+            ReasoningEffortSupport::Unsupported => {
+                obj.remove("reasoning_effort");
+            }
+            ReasoningEffortSupport::LowHigh => {
+                let rounded = match obj.get("reasoning_effort").and_then(Value::as_str) {
+                    Some("minimal" | "low") => "low",
+                    Some(_) => "high",
+                    None => return,
+                };
+                obj.insert("reasoning_effort".to_owned(), Value::from(rounded));
+            }
+        }
+    }
+
+    /// Map a raw `finish_reason` to a [`FinishReason`].
+    pub fn finish_reason(&self, raw: &str) -> FinishReason {
+        match raw {
+            "stop" => FinishReason::Stop,
+            "length" => FinishReason::Length,
+            "tool_calls" | "function_call" => FinishReason::ToolCalls,
+            "content_filter" => FinishReason::ContentFilter,
+            other => self
+                .extra_finish_reasons
+                .iter()
+                .find(|(name, _)| *name == other)
+                .map_or(FinishReason::Other, |(_, reason)| *reason),
+        }
+    }
+
+    /// Read a response's `usage` object into [`TokenUsage`].
+    ///
+    /// `completion_tokens` in the result never includes reasoning tokens,
+    /// matching how [`TokenUsage`] adds them on top.
+    pub fn token_usage(&self, usage: &Value) -> TokenUsage {
+        let count = |v: Option<&Value>| v.and_then(Value::as_u64).unwrap_or(0);
+        let prompt = count(usage.get("prompt_tokens"));
+        let completion = count(usage.get("completion_tokens"));
+        let reasoning = usage
+            .get("completion_tokens_details")
+            .and_then(|d| d.get("reasoning_tokens"))
+            .and_then(Value::as_u64);
+        match (self.reasoning_accounting, reasoning) {
+            (ReasoningAccounting::NotReported, _) | (_, None) => {
+                TokenUsage::new(prompt, completion)
+            }
+            (ReasoningAccounting::IncludedInCompletion, Some(r)) => {
+                TokenUsage::new(prompt, completion.saturating_sub(r)).with_reasoning_tokens(r)
+            }
+            (ReasoningAccounting::SeparateFromCompletion, Some(r)) => {
+                TokenUsage::new(prompt, completion).with_reasoning_tokens(r)
+            }
+        }
+    }
+
+    /// A tool-call id this provider accepts, derived from `id`.
+    ///
+    /// Ids that already fit are returned unchanged; others are mapped
+    /// deterministically, so a call and its result keep matching ids.
+    pub fn tool_call_id(&self, id: &str) -> String {
+        match self.tool_call_ids {
+            ToolCallIdFormat::Any => id.to_owned(),
+            ToolCallIdFormat::Alphanumeric9
+                if id.len() == 9 && id.bytes().all(|b| b.is_ascii_alphanumeric()) =>
+            {
+                id.to_owned()
+            }
+            ToolCallIdFormat::Alphanumeric9 => {
+                const ALPHABET: &[u8] =
+                    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+                // FNV-1a: stable across runs, unlike the std hasher.
+                let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+                for b in id.bytes() {
+                    hash = (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3);
+                }
+                (0..9)
+                    .map(|_| {
+                        let c = ALPHABET[(hash % ALPHABET.len() as u64) as usize];
+                        hash /= ALPHABET.len() as u64;
+                        c as char
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn rename_field(obj: &mut serde_json::Map<String, Value>, from: &str, to: &str) {
+    if from != to
+        && let Some(value) = obj.remove(from)
+    {
+        obj.insert(to.to_owned(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn profiles_are_chosen_by_host() {
+        assert_eq!(
+            CompatibilityProfile::for_base_url("https://api.x.ai/v1").name,
+            "xai"
+        );
+        assert_eq!(
+            CompatibilityProfile::for_base_url("https://api.mistral.ai/v1/").name,
+            "mistral"
+        );
+        assert_eq!(
+            CompatibilityProfile::for_base_url("http://127.0.0.1:8080/v1").name,
+            "openai"
+        );
+        assert_eq!(CompatibilityProfile::by_name("xai"), Some(&XAI));
+    }
+
+    #[test]
+    fn xai_requests_round_effort_and_strip_grok4_parameters() {
+        let mut body = json!({
+            "model": "grok-3-mini",
+            "max_tokens": 256,
+            "reasoning_effort": "medium",
+        });
+        XAI.apply_to_request(&mut body);
+        assert_eq!(body["reasoning_effort"], "high");
+        assert_eq!(body["max_tokens"], 256);
+
+        let mut body = json!({
+            "model": "grok-4-0709",
+            "presence_penalty": 0.5,
+            "stop": ["\n"],
+            "reasoning_effort": "low",
+        });
+        XAI.apply_to_request(&mut body);
+        assert_eq!(body, json!({"model": "grok-4-0709"}));
+    }
+
+    #[test]
+    fn mistral_requests_use_its_field_names() {
+        let mut body = json!({
+            "model": "magistral-medium-latest",
+            "seed": 7,
+            "tool_choice": "required",
+            "reasoning_effort": "high",
+        });
+        MISTRAL.apply_to_request(&mut body);
+        assert_eq!(
+            body,
+            json!({"model": "magistral-medium-latest", "random_seed": 7, "tool_choice": "any"})
+        );
+
+        let mut body = json!({"model": "o4-mini", "max_tokens": 100});
+        OPENAI.apply_to_request(&mut body);
+        assert_eq!(body["max_completion_tokens"], 100);
+    }
+
+    #[test]
+    fn provider_finish_reasons_are_mapped() {
+        assert_eq!(MISTRAL.finish_reason("model_length"), FinishReason::Length);
+        assert_eq!(MISTRAL.finish_reason("tool_calls"), FinishReason::ToolCalls);
+        assert_eq!(XAI.finish_reason("end_turn"), FinishReason::Stop);
+        assert_eq!(OPENAI.finish_reason("model_length"), FinishReason::Other);
+    }
+
+    #[test]
+    fn reasoning_tokens_are_counted_once() {
+        let usage = json!({
+            "prompt_tokens": 100,
+            "completion_tokens": 40,
+            "completion_tokens_details": {"reasoning_tokens": 30},
+        });
+        // OpenAI: 40 completion tokens, 30 of them reasoning.
+        let openai = OPENAI.token_usage(&usage);
+        assert_eq!((openai.completion_tokens, openai.total()), (10, 140));
+        // xAI: 40 answer tokens plus 30 reasoning tokens.
+        let xai = XAI.token_usage(&usage);
+        assert_eq!((xai.completion_tokens, xai.total()), (40, 170));
+        // Mistral does not break reasoning out.
+        let mistral = MISTRAL.token_usage(&json!({"prompt_tokens": 5, "completion_tokens": 9}));
+        assert_eq!((mistral.reasoning_tokens, mistral.total()), (None, 14));
+    }
+
+    #[test]
+    fn mistral_tool_call_ids_are_nine_alphanumerics() {
+        assert_eq!(MISTRAL.tool_call_id("D681PevKs"), "D681PevKs");
+        let mapped = MISTRAL.tool_call_id("call_abc123_xyz");
+        assert_eq!(mapped.len(), 9);
+        assert!(mapped.bytes().all(|b| b.is_ascii_alphanumeric()));
+        assert_eq!(mapped, MISTRAL.tool_call_id("call_abc123_xyz"));
+        assert_eq!(XAI.tool_call_id("call_abc123_xyz"), "call_abc123_xyz");
+    }
+}
@@ -0,0 +1,477 @@
+//! Normalization of OpenAI Responses API stream events.
+//!
+//! The Responses API streams typed server-sent events instead of
+//! chat/completions chunks. [`parse_responses_event`] maps each event to
+//! the shared [`LlmEvent`] model:
+//!
+//! | Responses event | LlmEvent |
+//! |-----------------|----------|
+//! | `response.output_text.delta`, `response.refusal.delta` | `TextDelta` |
+//! | `response.output_item.added` (reasoning) | `ThinkingStart` |
+//! | `response.reasoning_summary_text.delta`, `response.reasoning_text.delta` | `ThinkingDelta` |
+//! | `response.output_item.done` (reasoning) | `ThinkingEnd` |
+//! | `response.output_item.added` (function_call) | `ToolCallStart` |
+//! | `response.function_call_arguments.delta` | `ToolCallArgsDelta` |
+//! | `response.output_item.done` (function_call) | `ToolCallEnd` |
+//! | `response.completed`, `response.incomplete` | `StreamEnd` |
+//! | `response.failed`, `error` | `StreamError` |
+//!
+//! Annotations (`response.output_text.annotation.added`, e.g. web search
+//! citations) have no event of their own; they are collected on the
+//! [`ResponsesStreamState`] together with the final token usage.
+//!
+//! [`parse_responses_stream`] runs a whole `text/event-stream` body through
+//! the same mapping; the remote chat client in [`super::vision`] uses it for
+//! providers with a Responses endpoint.
+
+use crate::fae_llm::events::{FinishReason, LlmEvent};
+use crate::fae_llm::usage::TokenUsage;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A citation or file reference attached to the output text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Annotation {
+    /// Annotation type (`url_citation`, `file_citation`, ...).
+    pub kind: String,
+    /// Cited URL, for URL citations.
+    pub url: Option<String>,
+    /// Title of the cited source.
+    pub title: Option<String>,
+    /// Referenced file, for file citations.
+    pub file_id: Option<String>,
+}
+
+/// Per-stream state needed to normalize Responses events.
+#[derive(Debug, Default)]
+pub struct ResponsesStreamState {
+    /// Output item id → tool call id; argument deltas name the item.
+    call_ids: HashMap<String, String>,
+    /// Call ids that have received argument deltas.
+    args_streamed: Vec<String>,
+    saw_tool_call: bool,
+    /// Annotations on the output text, in arrival order.
+    pub annotations: Vec<Annotation>,
+    /// Token usage reported when the response finished.
+    pub usage: Option<TokenUsage>,
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// Map one Responses stream event (`event_type` plus its JSON `data`) to
+/// zero or more [`LlmEvent`]s.
+///
+/// Unknown and purely structural events (`response.created`,
+/// `response.content_part.added`, ...) produce nothing.
+pub fn parse_responses_event(
+    event_type: &str,
+    data: &Value,
+    state: &mut ResponsesStreamState,
+) -> Vec<LlmEvent> {
+    let delta = || str_field(data, "delta").unwrap_or_default().to_owned();
+    match event_type {
+        "response.output_text.delta" | "response.refusal.delta" => {
+            vec![LlmEvent::TextDelta { text: delta() }]
+        }
+        "response.reasoning_summary_text.delta" | "response.reasoning_text.delta" => {
+            vec![LlmEvent::ThinkingDelta { text: delta() }]
+        }
+        // Separate consecutive summary parts the way the API renders them.
+        "response.reasoning_summary_part.added"
+            if data
+                .get("summary_index")
+                .and_then(Value::as_u64)
+                .is_some_and(|i| i > 0) =>
+        {
+            vec![LlmEvent::ThinkingDelta {
+                text: "\n\n".to_owned(),
+            }]
+        }
+        "response.output_item.added" => output_item_added(data, state),
+        "response.function_call_arguments.delta" => {
+            let Some(call_id) = str_field(data, "item_id").and_then(|id| state.call_ids.get(id))
+            else {
+                return Vec::new();
+            };
+            if !state.args_streamed.contains(call_id) {
+                state.args_streamed.push(call_id.clone());
+            }
+            vec![LlmEvent::ToolCallArgsDelta {
+                call_id: call_id.clone(),
+                args_fragment: delta(),
+            }]
+        }
+        // Some models send the arguments only once, complete.
+        "response.function_call_arguments.done" => {
+            let Some(call_id) = str_field(data, "item_id").and_then(|id| state.call_ids.get(id))
+            else {
+                return Vec::new();
+            };
+            if state.args_streamed.contains(call_id) {
+                return Vec::new();
+            }
+            state.args_streamed.push(call_id.clone());
+            vec![LlmEvent::ToolCallArgsDelta {
+                call_id: call_id.clone(),
+                args_fragment: str_field(data, "arguments").unwrap_or_default().to_owned(),
+            }]
+        }
+        "response.output_item.done" => output_item_done(data, state),
+        "response.output_text.annotation.added" => {
+            if let Some(annotation) = data.get("annotation") {
+                let owned = |key: &str| str_field(annotation, key).map(str::to_owned);
+                state.annotations.push(Annotation {
+                    kind: owned("type").unwrap_or_default(),
+                    url: owned("url"),
+                    title: owned("title"),
+                    file_id: owned("file_id"),
+                });
+            }
+            Vec::new()
+        }
+        "response.completed" | "response.incomplete" => {
+            let response = data.get("response").unwrap_or(&Value::Null);
+            state.usage = response.get("usage").map(responses_usage);
+            let finish_reason = match response
+                .get("incomplete_details")
+                .and_then(|d| str_field(d, "reason"))
+            {
+                Some("max_output_tokens") => FinishReason::Length,
+                Some("content_filter") => FinishReason::ContentFilter,
+                Some(_) => FinishReason::Other,
+                None if state.saw_tool_call => FinishReason::ToolCalls,
+                None => FinishReason::Stop,
+            };
+            vec![LlmEvent::StreamEnd { finish_reason }]
+        }
+        "response.failed" => {
+            let error = data
+                .get("response")
+                .and_then(|r| r.get("error"))
+                .and_then(|e| str_field(e, "message"))
+                .unwrap_or("response failed");
+            vec![LlmEvent::StreamError {
+                error: error.to_owned(),
+            }]
+        }
+        "error" => vec![LlmEvent::StreamError {
+            error: str_field(data, "message")
+                .unwrap_or("stream error")
+                .to_owned(),
+        }],
+        _ => Vec::new(),
+    }
+}
+
+/// Map a complete Responses server-sent event stream to [`LlmEvent`]s.
+///
+/// Events are separated by blank lines; each names its type on an `event:`
+/// line (or in the data's `type` field) and carries JSON on its `data:`
+/// lines. Events whose data is not JSON are skipped.
+pub fn parse_responses_stream(body: &str, state: &mut ResponsesStreamState) -> Vec<LlmEvent> {
+    let body = body.replace("\r\n", "\n");
+    let mut events = Vec::new();
+    for frame in body.split("\n\n") {
+        let mut event_type = None;
+        let mut data = String::new();
+        for line in frame.lines() {
+            if let Some(rest) = line.strip_prefix("event:") {
+                event_type = Some(rest.trim());
+            } else if let Some(rest) = line.strip_prefix("data:") {
+                if !data.is_empty() {
+                    data.push('\n');
+                }
+                data.push_str(rest.trim_start());
+            }
+        }
+        if data.is_empty() || data == "[DONE]" {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(&data) else {
+            tracing::debug!(data = %data, "skipping malformed Responses event");
+            continue;
+        };
+        let event_type = event_type
+            .or_else(|| str_field(&value, "type"))
+            .unwrap_or_default();
+        events.extend(parse_responses_event(event_type, &value, state));
+    }
+    events
+}
+
+fn output_item_added(data: &Value, state: &mut ResponsesStreamState) -> Vec<LlmEvent> {
+    let item = data.get("item").unwrap_or(&Value::Null);
+    match str_field(item, "type") {
+        Some("reasoning") => vec![LlmEvent::ThinkingStart],
+        Some("function_call") => {
+            let item_id = str_field(item, "id").unwrap_or_default();
+            let call_id = str_field(item, "call_id").unwrap_or(item_id).to_owned();
+            state.call_ids.insert(item_id.to_owned(), call_id.clone());
+            state.saw_tool_call = true;
+            vec![LlmEvent::ToolCallStart {
+                call_id,
+                function_name: str_field(item, "name").unwrap_or_default().to_owned(),
+            }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn output_item_done(data: &Value, state: &mut ResponsesStreamState) -> Vec<LlmEvent> {
+    let item = data.get("item").unwrap_or(&Value::Null);
+    match str_field(item, "type") {
+        Some("reasoning") => vec![LlmEvent::ThinkingEnd],
+        Some("function_call") => {
+            let item_id = str_field(item, "id").unwrap_or_default();
+            let Some(call_id) = state.call_ids.get(item_id).cloned() else {
+                return Vec::new();
+            };
+            let mut events = Vec::new();
+            // Arguments that never streamed are carried on the finished item.
+            if !state.args_streamed.contains(&call_id) {
+                state.args_streamed.push(call_id.clone());
+                events.push(LlmEvent::ToolCallArgsDelta {
+                    call_id: call_id.clone(),
+                    args_fragment: str_field(item, "arguments").unwrap_or_default().to_owned(),
+                });
+            }
+            events.push(LlmEvent::ToolCallEnd { call_id });
+            events
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Read a Responses `usage` object. `output_tokens` includes reasoning
+/// tokens, which [`TokenUsage`] keeps separately.
+fn responses_usage(usage: &Value) -> TokenUsage {
+    let count = |key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+    let output = count("output_tokens");
+    match usage
+        .get("output_tokens_details")
+        .and_then(|d| d.get("reasoning_tokens"))
+        .and_then(Value::as_u64)
+    {
+        Some(reasoning) => TokenUsage::new(count("input_tokens"), output.saturating_sub(reasoning))
+            .with_reasoning_tokens(reasoning),
+        None => TokenUsage::new(count("input_tokens"), output),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(events: &[(&str, Value)], state: &mut ResponsesStreamState) -> Vec<LlmEvent> {
+        events
+            .iter()
+            .flat_map(|(ty, data)| parse_responses_event(ty, data, state))
+            .collect()
+    }
+
+    #[test]
+    fn function_calls_stream_with_their_call_id() {
+        let mut state = ResponsesStreamState::default();
+        let events = run(
+            &[
+                (
+                    "response.output_item.added",
+                    json!({"item": {"type": "function_call", "id": "fc_1", "call_id": "call_9", "name": "read", "arguments": ""}}),
+                ),
+                (
+                    "response.function_call_arguments.delta",
+                    json!({"item_id": "fc_1", "delta": "{\"path\":"}),
+                ),
+                (
+                    "response.function_call_arguments.delta",
+                    json!({"item_id": "fc_1", "delta": "\"a.txt\"}"}),
+                ),
+                (
+                    "response.function_call_arguments.done",
+                    json!({"item_id": "fc_1", "arguments": "{\"path\":\"a.txt\"}"}),
+                ),
+                (
+                    "response.output_item.done",
+                    json!({"item": {"type": "function_call", "id": "fc_1", "call_id": "call_9"}}),
+                ),
+                (
+                    "response.completed",
+                    json!({"response": {"status": "completed"}}),
+                ),
+            ],
+            &mut state,
+        );
+        assert_eq!(
+            events,
+            vec![
+                LlmEvent::ToolCallStart {
+                    call_id: "call_9".into(),
+                    function_name: "read".into()
+                },
+                LlmEvent::ToolCallArgsDelta {
+                    call_id: "call_9".into(),
+                    args_fragment: "{\"path\":".into()
+                },
+                LlmEvent::ToolCallArgsDelta {
+                    call_id: "call_9".into(),
+                    args_fragment: "\"a.txt\"}".into()
+                },
+                LlmEvent::ToolCallEnd {
+                    call_id: "call_9".into()
+                },
+                LlmEvent::StreamEnd {
+                    finish_reason: FinishReason::ToolCalls
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unstreamed_arguments_come_from_the_finished_item() {
+        let mut state = ResponsesStreamState::default();
+        let events = run(
+            &[
+                (
+                    "response.output_item.added",
+                    json!({"item": {"type": "function_call", "id": "fc_2", "call_id": "call_2", "name": "bash"}}),
+                ),
+                (
+                    "response.output_item.done",
+                    json!({"item": {"type": "function_call", "id": "fc_2", "arguments": "{\"command\":\"ls\"}"}}),
+                ),
+            ],
+            &mut state,
+        );
+        assert_eq!(
+            events[1],
+            LlmEvent::ToolCallArgsDelta {
+                call_id: "call_2".into(),
+                args_fragment: "{\"command\":\"ls\"}".into()
+            }
+        );
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn reasoning_summaries_become_thinking() {
+        let mut state = ResponsesStreamState::default();
+        let events = run(
+            &[
+                (
+                    "response.output_item.added",
+                    json!({"item": {"type": "reasoning", "id": "rs_1"}}),
+                ),
+                (
+                    "response.reasoning_summary_part.added",
+                    json!({"summary_index": 0}),
+                ),
+                (
+                    "response.reasoning_summary_text.delta",
+                    json!({"delta": "Checking the file."}),
+                ),
+                (
+                    "response.reasoning_summary_part.added",
+                    json!({"summary_index": 1}),
+                ),
+                (
+                    "response.reasoning_summary_text.delta",
+                    json!({"delta": "Done."}),
+                ),
+                (
+                    "response.output_item.done",
+                    json!({"item": {"type": "reasoning", "id": "rs_1"}}),
+                ),
+                ("response.output_text.delta", json!({"delta": "Hi"})),
+            ],
+            &mut state,
+        );
+        assert_eq!(
+            events,
+            vec![
+                LlmEvent::ThinkingStart,
+                LlmEvent::ThinkingDelta {
+                    text: "Checking the file.".into()
+                },
+                LlmEvent::ThinkingDelta {
+                    text: "\n\n".into()
+                },
+                LlmEvent::ThinkingDelta {
+                    text: "Done.".into()
+                },
+                LlmEvent::ThinkingEnd,
+                LlmEvent::TextDelta { text: "Hi".into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn event_stream_bodies_are_split_into_events() {
+        let body = "event: response.created\r\ndata: {\"type\":\"response.created\"}\r\n\r\n\
+                    event: response.output_text.delta\ndata: {\"delta\":\"Hel\"}\n\n\
+                    data: {\"type\":\"response.output_text.delta\",\"delta\":\"lo\"}\n\n\
+                    data: not json\n\n\
+                    event: response.completed\ndata: {\"response\":{\"usage\":{\"input_tokens\":3,\"output_tokens\":2}}}\n\n";
+        let mut state = ResponsesStreamState::default();
+        let events = parse_responses_stream(body, &mut state);
+        assert_eq!(
+            events,
+            vec![
+                LlmEvent::TextDelta { text: "Hel".into() },
+                LlmEvent::TextDelta { text: "lo".into() },
+                LlmEvent::StreamEnd {
+                    finish_reason: FinishReason::Stop
+                },
+            ]
+        );
+        assert_eq!(state.usage, Some(TokenUsage::new(3, 2)));
+    }
+
+    #[test]
+    fn annotations_usage_and_incomplete_responses_are_recorded() {
+        let mut state = ResponsesStreamState::default();
+        let events = run(
+            &[
+                (
+                    "response.output_text.annotation.added",
+                    json!({"annotation": {"type": "url_citation", "url": "https://example.com", "title": "Example"}}),
+                ),
+                (
+                    "response.incomplete",
+                    json!({"response": {
+                        "incomplete_details": {"reason": "max_output_tokens"},
+                        "usage": {"input_tokens": 12, "output_tokens": 50, "output_tokens_details": {"reasoning_tokens": 20}}
+                    }}),
+                ),
+            ],
+            &mut state,
+        );
+        assert_eq!(
+            events,
+            vec![LlmEvent::StreamEnd {
+                finish_reason: FinishReason::Length
+            }]
+        );
+        assert_eq!(
+            state.annotations[0].url.as_deref(),
+            Some("https://example.com")
+        );
+        let usage = state.usage.clone().unwrap_or_default();
+        assert_eq!((usage.completion_tokens, usage.total()), (30, 62));
+
+        let failed = parse_responses_event(
+            "response.failed",
+            &json!({"response": {"error": {"message": "server overloaded"}}}),
+            &mut state,
+        );
+        assert_eq!(
+            failed,
+            vec![LlmEvent::StreamError {
+                error: "server overloaded".into()
+            }]
+        );
+    }
+}
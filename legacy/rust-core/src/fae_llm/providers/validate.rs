@@ -0,0 +1,191 @@
+//! Live API key validation for remote providers.
+//!
+//! Used by first-run setup to confirm an entered key works before the user
+//! relies on it. The probe is the cheapest authenticated request each API
+//! offers — listing models — so validation never spends tokens.
+
+use crate::fae_llm::config::types::{AzureOpenAiConfig, ProviderConfig};
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::types::EndpointType;
+use std::time::Duration;
+
+/// How long a validation request may take.
+const VALIDATION_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Anthropic API version header sent with validation requests.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// The URL used to probe `provider`, accounting for Azure deployments.
+///
+/// Returns `None` for endpoints that have no key to validate.
+pub fn provider_probe_url(provider: &ProviderConfig) -> Option<String> {
+    match &provider.azure {
+        Some(azure) if provider.endpoint_type != EndpointType::Local => {
+            Some(azure.models_url(&provider.base_url))
+        }
+        _ => probe_url(provider.endpoint_type, &provider.base_url),
+    }
+}
+
+/// The model-listing URL used to probe `base_url`.
+///
+/// Returns `None` for endpoints that have no key to validate.
+pub fn probe_url(endpoint: EndpointType, base_url: &str) -> Option<String> {
+    let base = base_url.trim().trim_end_matches('/');
+    match endpoint {
+        EndpointType::Local => None,
+        EndpointType::AnthropicMessages if base.ends_with("/v1") => Some(format!("{base}/models")),
+        EndpointType::AnthropicMessages => Some(format!("{base}/v1/models")),
+        // OpenRouter's model list is public; the key endpoint requires auth.
+        EndpointType::OpenAiCompletions if super::openrouter::is_openrouter(base) => {
+            Some(format!("{base}/key"))
+        }
+        EndpointType::OpenAiCompletions | EndpointType::OpenAiResponses | EndpointType::Custom => {
+            Some(format!("{base}/models"))
+        }
+    }
+}
+
+/// Map a failed probe's HTTP status to an error.
+pub fn classify_status(status: u16) -> FaeLlmError {
+    match status {
+        401 | 403 => FaeLlmError::AuthError(format!("API key rejected (HTTP {status})")),
+        404 => FaeLlmError::ProviderConfigError(
+            "endpoint not found (HTTP 404); check the base URL".to_owned(),
+        ),
+        429 => FaeLlmError::ProviderError("rate limited or out of credit (HTTP 429)".to_owned()),
+        s if s >= 500 => FaeLlmError::ProviderError(format!("provider unavailable (HTTP {s})")),
+        s => FaeLlmError::ProviderError(format!("unexpected response (HTTP {s})")),
+    }
+}
+
+/// Validate `provider`'s key with a live request.
+///
+/// Returns the number of models the key can see (0 if the response does
+/// not list them). Endpoints without a key (local) succeed without a
+/// request.
+///
+/// # Errors
+///
+/// - [`FaeLlmError::SecretResolutionError`] if the key cannot be resolved
+///   or is empty.
+/// - [`FaeLlmError::AuthError`] if the provider rejects the key.
+/// - [`FaeLlmError::RequestError`] / [`FaeLlmError::TimeoutError`] if the
+///   provider is unreachable (including offline mode).
+/// - [`FaeLlmError::ProviderConfigError`] / [`FaeLlmError::ProviderError`]
+///   for a wrong base URL or other HTTP failures.
+pub async fn validate_provider(provider: &ProviderConfig) -> Result<usize, FaeLlmError> {
+    let Some(url) = provider_probe_url(provider) else {
+        return Ok(0);
+    };
+    let key = provider
+        .api_key
+        .resolve()?
+        .filter(|k| !k.trim().is_empty())
+        .ok_or_else(|| FaeLlmError::SecretResolutionError("no API key entered".to_owned()))?;
+    crate::offline::ensure_url_allowed("API key validation", &url)
+        .map_err(|e| FaeLlmError::RequestError(e.to_string()))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(VALIDATION_TIMEOUT)
+        .build()
+        .map_err(|e| FaeLlmError::RequestError(format!("HTTP client: {e}")))?;
+    let request = match provider.endpoint_type {
+        _ if provider.azure.is_some() => client
+            .get(&url)
+            .header(AzureOpenAiConfig::API_KEY_HEADER, key.trim()),
+        EndpointType::AnthropicMessages => client
+            .get(&url)
+            .header("x-api-key", key.trim())
+            .header("anthropic-version", ANTHROPIC_VERSION),
+        _ => client.get(&url).bearer_auth(key.trim()),
+    };
+
+    let response = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            FaeLlmError::TimeoutError(format!("no response from {url}"))
+        } else {
+            FaeLlmError::RequestError(format!("cannot reach {url}: {e}"))
+        }
+    })?;
+    let status = response.status().as_u16();
+    if !response.status().is_success() {
+        return Err(classify_status(status));
+    }
+
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    Ok(body
+        .get("data")
+        .and_then(serde_json::Value::as_array)
+        .map_or(0, Vec::len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_urls_follow_each_api() {
+        assert_eq!(
+            probe_url(
+                EndpointType::OpenAiCompletions,
+                "https://api.openai.com/v1/"
+            ),
+            Some("https://api.openai.com/v1/models".to_owned())
+        );
+        assert_eq!(
+            probe_url(EndpointType::AnthropicMessages, "https://api.anthropic.com"),
+            Some("https://api.anthropic.com/v1/models".to_owned())
+        );
+        assert_eq!(
+            probe_url(
+                EndpointType::AnthropicMessages,
+                "https://api.anthropic.com/v1"
+            ),
+            Some("https://api.anthropic.com/v1/models".to_owned())
+        );
+        assert_eq!(
+            probe_url(
+                EndpointType::OpenAiCompletions,
+                "https://openrouter.ai/api/v1"
+            ),
+            Some("https://openrouter.ai/api/v1/key".to_owned())
+        );
+        assert_eq!(
+            probe_url(EndpointType::Local, "http://localhost:8080"),
+            None
+        );
+    }
+
+    #[test]
+    fn azure_providers_probe_the_resource_model_list() {
+        let provider = ProviderConfig {
+            endpoint_type: EndpointType::OpenAiCompletions,
+            enabled: true,
+            base_url: "https://contoso.openai.azure.com".to_owned(),
+            api_key: crate::fae_llm::config::types::SecretRef::None,
+            models: Vec::new(),
+            azure: Some(AzureOpenAiConfig {
+                deployment: "gpt-4o".to_owned(),
+                api_version: "2024-10-21".to_owned(),
+            }),
+        };
+        assert_eq!(
+            provider_probe_url(&provider),
+            Some(
+                "https://contoso.openai.azure.com/openai/models?api-version=2024-10-21".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn statuses_map_to_error_classes() {
+        assert!(matches!(classify_status(401), FaeLlmError::AuthError(_)));
+        assert!(matches!(classify_status(403), FaeLlmError::AuthError(_)));
+        assert!(matches!(
+            classify_status(404),
+            FaeLlmError::ProviderConfigError(_)
+        ));
+        assert!(classify_status(503).is_retryable());
+    }
+}
@@ -0,0 +1,909 @@
+//! Remote vision models for turns with images.
+//!
+//! Most local voice models are text-only, so a camera capture or screenshot
+//! attached to a turn would be dropped before the model sees it. When
+//! `[defaults] vision_provider` names a remote provider (OpenAI-compatible
+//! chat completions, OpenAI Responses or Anthropic Messages),
+//! [`VisionRoutingProvider`] sends
+//! every turn whose latest user message carries images to that provider's
+//! `vision_model` instead; all other turns stay local.
+//!
+//! Images travel as content parts in each protocol's own shape: OpenAI gets
+//! `image_url` parts (`input_image` for Responses) with a `data:` URL,
+//! Anthropic gets `image` blocks with a base64 source. File-referenced
+//! images are read and inlined when the request is built.
+//!
+//! Requests are single-shot: the response arrives whole and is replayed as
+//! an event stream, which is fine for the short descriptions image turns
+//! produce. Responses endpoints always stream; their event stream is read
+//! whole and normalized by [`parse_responses_stream`]. [`remote_provider_from_config`] builds the same client for
+//! other remote roles, where turns may carry no images at all.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use super::message::{ImageAttachment, Message, MessageContent, Role};
+use super::profile::CompatibilityProfile;
+use super::responses::{ResponsesStreamState, parse_responses_stream};
+use crate::fae_llm::config::SecretRef;
+use crate::fae_llm::config::types::{AzureOpenAiConfig, FaeLlmConfig, ProviderConfig};
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::events::{FinishReason, LlmEvent};
+use crate::fae_llm::provider::{LlmEventStream, ProviderAdapter, ToolDefinition};
+use crate::fae_llm::types::{EndpointType, ModelRef, RequestOptions};
+
+/// How long a vision request may take unless the request sets a timeout.
+const VISION_HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Anthropic API version header sent with vision requests.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Output tokens requested when the model config does not set a limit.
+const DEFAULT_VISION_MAX_TOKENS: usize = 1024;
+
+/// Build the vision provider named by `[defaults] vision_provider`.
+///
+/// Returns `None` when no vision provider is set, it is disabled, its
+/// endpoint is not a chat API this module speaks, or no model can be
+/// determined.
+pub fn vision_provider_from_config(config: &FaeLlmConfig) -> Option<Arc<dyn ProviderAdapter>> {
+    let provider_id = config.defaults.vision_provider.as_deref()?;
+    remote_provider_from_config(config, provider_id, config.defaults.vision_model.as_deref())
+}
+
+/// Build a chat provider for the remote provider `provider_id`, sending
+/// requests to `model` (a `[models]` entry or a model ID), or to the
+/// provider's first model when `model` is `None`.
+///
+/// Text-only turns go through the same client, so this also serves other
+/// remote roles such as the verifier of speculative generation. Returns
+/// `None` under the same conditions as [`vision_provider_from_config`].
+pub fn remote_provider_from_config(
+    config: &FaeLlmConfig,
+    provider_id: &str,
+    model: Option<&str>,
+) -> Option<Arc<dyn ProviderAdapter>> {
+    let provider = config.providers.get(provider_id).filter(|p| p.enabled)?;
+    let entry = model.and_then(|id| config.model(id));
+    let model_id = entry
+        .map(|m| m.model_id.clone())
+        .or_else(|| model.map(str::to_owned))
+        .or_else(|| provider.models.first().cloned())?;
+    let max_tokens = entry.map_or(DEFAULT_VISION_MAX_TOKENS, |m| m.max_tokens);
+    vision_provider_for(provider_id, provider, &model_id, max_tokens)
+}
+
+/// Build a vision provider for `provider`, sending requests to `model`.
+///
+/// Returns `None` for local and custom endpoints, and for Azure Responses
+/// deployments.
+pub fn vision_provider_for(
+    name: &str,
+    provider: &ProviderConfig,
+    model: &str,
+    max_tokens: usize,
+) -> Option<Arc<dyn ProviderAdapter>> {
+    match provider.endpoint_type {
+        EndpointType::OpenAiCompletions | EndpointType::AnthropicMessages => {}
+        EndpointType::OpenAiResponses if provider.azure.is_none() => {}
+        EndpointType::OpenAiResponses | EndpointType::Local | EndpointType::Custom => return None,
+    }
+    Some(Arc::new(RemoteVisionProvider {
+        name: name.to_owned(),
+        endpoint_type: provider.endpoint_type,
+        base_url: provider.base_url.trim().trim_end_matches('/').to_owned(),
+        azure: provider.azure.clone(),
+        api_key: provider.api_key.clone(),
+        model: model.to_owned(),
+        max_tokens,
+    }))
+}
+
+/// Provider wrapper that sends image turns to a vision model.
+pub struct VisionRoutingProvider {
+    inner: Arc<dyn ProviderAdapter>,
+    vision: Arc<dyn ProviderAdapter>,
+}
+
+impl VisionRoutingProvider {
+    /// Route image turns of `inner` to `vision`.
+    pub fn new(inner: Arc<dyn ProviderAdapter>, vision: Arc<dyn ProviderAdapter>) -> Self {
+        Self { inner, vision }
+    }
+
+    /// Wrap `inner` when it cannot see images and a `vision` provider is
+    /// available; otherwise return it unchanged.
+    pub fn wrap(
+        inner: Arc<dyn ProviderAdapter>,
+        vision: Option<Arc<dyn ProviderAdapter>>,
+    ) -> Arc<dyn ProviderAdapter> {
+        match vision {
+            Some(vision) if !inner.supports_images() => Arc::new(Self::new(inner, vision)),
+            _ => inner,
+        }
+    }
+
+    fn target(&self, messages: &[Message]) -> &Arc<dyn ProviderAdapter> {
+        let image_turn = messages
+            .iter()
+            .rev()
+            .find(|m| m.role == Role::User)
+            .is_some_and(|m| !m.images.is_empty());
+        if image_turn {
+            &self.vision
+        } else {
+            &self.inner
+        }
+    }
+}
+
+#[async_trait]
+impl ProviderAdapter for VisionRoutingProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn endpoint_type(&self) -> EndpointType {
+        self.inner.endpoint_type()
+    }
+
+    fn supports_structured_tool_results(&self) -> bool {
+        self.inner.supports_structured_tool_results()
+    }
+
+    fn supports_images(&self) -> bool {
+        true
+    }
+
+    async fn warm_up(
+        &self,
+        messages: &[Message],
+        options: &RequestOptions,
+        tools: &[ToolDefinition],
+    ) -> Result<(), FaeLlmError> {
+        self.target(messages)
+            .warm_up(messages, options, tools)
+            .await
+    }
+
+    async fn send(
+        &self,
+        messages: &[Message],
+        options: &RequestOptions,
+        tools: &[ToolDefinition],
+    ) -> Result<LlmEventStream, FaeLlmError> {
+        let target = self.target(messages);
+        if !Arc::ptr_eq(target, &self.inner) {
+            tracing::info!(
+                provider = target.name(),
+                "sending image turn to remote vision model"
+            );
+        }
+        target.send(messages, options, tools).await
+    }
+}
+
+/// Chat provider for a remote vision model, over either protocol.
+struct RemoteVisionProvider {
+    name: String,
+    endpoint_type: EndpointType,
+    base_url: String,
+    azure: Option<AzureOpenAiConfig>,
+    api_key: SecretRef,
+    model: String,
+    max_tokens: usize,
+}
+
+impl RemoteVisionProvider {
+    fn url(&self) -> String {
+        match (&self.azure, self.endpoint_type) {
+            (Some(azure), _) => azure.chat_completions_url(&self.base_url),
+            (None, EndpointType::AnthropicMessages) if self.base_url.ends_with("/v1") => {
+                format!("{}/messages", self.base_url)
+            }
+            (None, EndpointType::AnthropicMessages) => format!("{}/v1/messages", self.base_url),
+            (None, EndpointType::OpenAiResponses) => format!("{}/responses", self.base_url),
+            (None, _) => format!("{}/chat/completions", self.base_url),
+        }
+    }
+
+    fn body(
+        &self,
+        messages: &[Message],
+        options: &RequestOptions,
+        tools: &[ToolDefinition],
+    ) -> Value {
+        let max_tokens = options.max_tokens.map_or(self.max_tokens, |n| n as usize);
+        let mut body = if self.endpoint_type == EndpointType::AnthropicMessages {
+            let (system, messages) = anthropic_messages(messages);
+            let mut body = json!({
+                "model": self.model,
+                "max_tokens": max_tokens,
+                "messages": messages,
+            });
+            if let Some(system) = system {
+                body["system"] = json!(system);
+            }
+            if !tools.is_empty() {
+                body["tools"] = json!(anthropic_tools(tools));
+            }
+            body
+        } else if self.endpoint_type == EndpointType::OpenAiResponses {
+            let (instructions, input) = responses_input(messages);
+            let mut body = json!({
+                "model": self.model,
+                "max_output_tokens": max_tokens,
+                "input": input,
+                "stream": true,
+                "store": false,
+            });
+            if let Some(instructions) = instructions {
+                body["instructions"] = json!(instructions);
+            }
+            if !tools.is_empty() {
+                body["tools"] = json!(responses_tools(tools));
+            }
+            body
+        } else {
+            let mut body = json!({
+                "model": self.model,
+                "max_tokens": max_tokens,
+                "messages": openai_messages(messages),
+            });
+            if !tools.is_empty() {
+                body["tools"] = json!(openai_tools(tools));
+            }
+            CompatibilityProfile::for_base_url(&self.base_url).apply_to_request(&mut body);
+            body
+        };
+        if let Some(temperature) = options.temperature {
+            body["temperature"] = json!(temperature);
+        }
+        body
+    }
+
+    fn stream_start(&self) -> LlmEvent {
+        LlmEvent::StreamStart {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            model: ModelRef::new(&self.model)
+                .with_provider(&self.name)
+                .with_endpoint_type(self.endpoint_type)
+                .with_base_url(&self.base_url),
+        }
+    }
+
+    fn authorize(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, FaeLlmError> {
+        let key = self
+            .api_key
+            .resolve()?
+            .filter(|k| !k.trim().is_empty())
+            .ok_or_else(|| {
+                FaeLlmError::SecretResolutionError("no API key configured".to_owned())
+            })?;
+        Ok(match (&self.azure, self.endpoint_type) {
+            (Some(_), _) => request.header(AzureOpenAiConfig::API_KEY_HEADER, key.trim()),
+            (None, EndpointType::AnthropicMessages) => request
+                .header("x-api-key", key.trim())
+                .header("anthropic-version", ANTHROPIC_VERSION),
+            (None, _) => request.bearer_auth(key.trim()),
+        })
+    }
+}
+
+#[async_trait]
+impl ProviderAdapter for RemoteVisionProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn endpoint_type(&self) -> EndpointType {
+        self.endpoint_type
+    }
+
+    fn supports_images(&self) -> bool {
+        true
+    }
+
+    async fn send(
+        &self,
+        messages: &[Message],
+        options: &RequestOptions,
+        tools: &[ToolDefinition],
+    ) -> Result<LlmEventStream, FaeLlmError> {
+        let url = self.url();
+        crate::offline::ensure_url_allowed("vision request", &url)
+            .map_err(|e| FaeLlmError::RequestError(e.to_string()))?;
+        let timeout = options
+            .timeout_ms
+            .map_or(VISION_HTTP_TIMEOUT, Duration::from_millis);
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| FaeLlmError::RequestError(format!("HTTP client: {e}")))?;
+        let mut request = client.post(&url).json(&self.body(messages, options, tools));
+        for (name, value) in &options.headers {
+            request = request.header(name, value);
+        }
+        let response = self.authorize(request)?.send().await.map_err(|e| {
+            if e.is_timeout() {
+                FaeLlmError::TimeoutError(format!("no response from {url}"))
+            } else {
+                FaeLlmError::RequestError(format!("cannot reach {url}: {e}"))
+            }
+        })?;
+        if !response.status().is_success() {
+            return Err(super::validate::classify_status(response.status().as_u16()));
+        }
+        if self.endpoint_type == EndpointType::OpenAiResponses {
+            let body = response.text().await.map_err(|e| {
+                FaeLlmError::ProviderError(format!("malformed Responses stream: {e}"))
+            })?;
+            let mut state = ResponsesStreamState::default();
+            let mut events = vec![self.stream_start()];
+            events.extend(parse_responses_stream(&body, &mut state));
+            if !events
+                .iter()
+                .any(|e| matches!(e, LlmEvent::StreamEnd { .. } | LlmEvent::StreamError { .. }))
+            {
+                events.push(LlmEvent::StreamError {
+                    error: "Responses stream ended before the response completed".to_owned(),
+                });
+            }
+            if !state.annotations.is_empty() {
+                tracing::debug!(annotations = ?state.annotations, "Responses answer cites sources");
+            }
+            return Ok(Box::pin(futures_util::stream::iter(events)));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| FaeLlmError::ProviderError(format!("malformed vision response: {e}")))?;
+        let mut events = vec![self.stream_start()];
+        events.extend(if self.endpoint_type == EndpointType::AnthropicMessages {
+            anthropic_response_events(&body)
+        } else {
+            openai_response_events(&body, CompatibilityProfile::for_base_url(&self.base_url))
+        }?);
+        Ok(Box::pin(futures_util::stream::iter(events)))
+    }
+}
+
+/// Base64 data of `images`, skipping (with a warning) any that cannot be read.
+fn image_data(images: &[ImageAttachment]) -> Vec<(&str, String)> {
+    images
+        .iter()
+        .filter_map(|image| match image.base64() {
+            Some(data) => Some((image.media_type.as_str(), data)),
+            None => {
+                tracing::warn!(path = ?image.path, "dropping unreadable image attachment");
+                None
+            }
+        })
+        .collect()
+}
+
+fn message_text(message: &Message) -> &str {
+    match &message.content {
+        MessageContent::Text { text } => text,
+        MessageContent::ToolResult { content, .. } => content,
+    }
+}
+
+/// `messages` in OpenAI chat completions wire format.
+///
+/// User messages with images become a content array of one `text` part and
+/// one `image_url` part per image.
+pub fn openai_messages(messages: &[Message]) -> Vec<Value> {
+    messages
+        .iter()
+        .map(|message| {
+            if let MessageContent::ToolResult {
+                call_id, content, ..
+            } = &message.content
+            {
+                return json!({"role": "tool", "tool_call_id": call_id, "content": content});
+            }
+            let text = message_text(message);
+            let mut wire = json!({"role": message.role.to_string(), "content": text});
+            let images = image_data(&message.images);
+            if message.role == Role::User && !images.is_empty() {
+                let mut parts = vec![json!({"type": "text", "text": text})];
+                parts.extend(images.into_iter().map(|(media_type, data)| {
+                    json!({
+                        "type": "image_url",
+                        "image_url": {"url": format!("data:{media_type};base64,{data}")},
+                    })
+                }));
+                wire["content"] = Value::Array(parts);
+            }
+            if !message.tool_calls.is_empty() {
+                if text.is_empty() {
+                    wire["content"] = Value::Null;
+                }
+                wire["tool_calls"] = message
+                    .tool_calls
+                    .iter()
+                    .map(|call| {
+                        json!({
+                            "id": call.call_id,
+                            "type": "function",
+                            "function": {"name": call.function_name, "arguments": call.arguments},
+                        })
+                    })
+                    .collect();
+            }
+            wire
+        })
+        .collect()
+}
+
+/// `tools` in OpenAI chat completions wire format.
+pub fn openai_tools(tools: &[ToolDefinition]) -> Vec<Value> {
+    tools
+        .iter()
+        .map(|tool| {
+            json!({
+                "type": "function",
+                "function": {
+                    "name": tool.name,
+                    "description": tool.description,
+                    "parameters": tool.parameters,
+                },
+            })
+        })
+        .collect()
+}
+
+/// `messages` as OpenAI Responses input items, with the system prompt
+/// split out as instructions.
+///
+/// User messages become `input_text` and `input_image` parts; assistant
+/// tool calls and tool results become `function_call` and
+/// `function_call_output` items.
+pub fn responses_input(messages: &[Message]) -> (Option<String>, Vec<Value>) {
+    let mut instructions = Vec::new();
+    let mut input = Vec::with_capacity(messages.len());
+    for message in messages {
+        let text = message_text(message);
+        match (&message.content, message.role) {
+            (MessageContent::ToolResult { call_id, .. }, _) => input.push(json!({
+                "type": "function_call_output",
+                "call_id": call_id,
+                "output": text,
+            })),
+            (_, Role::System) => instructions.push(text.to_owned()),
+            (_, Role::Assistant) => {
+                if !text.is_empty() {
+                    input.push(json!({"role": "assistant", "content": text}));
+                }
+                input.extend(message.tool_calls.iter().map(|call| {
+                    json!({
+                        "type": "function_call",
+                        "call_id": call.call_id,
+                        "name": call.function_name,
+                        "arguments": call.arguments,
+                    })
+                }));
+            }
+            (_, _) => {
+                let mut parts = vec![json!({"type": "input_text", "text": text})];
+                parts.extend(
+                    image_data(&message.images)
+                        .into_iter()
+                        .map(|(media_type, data)| {
+                            json!({
+                                "type": "input_image",
+                                "image_url": format!("data:{media_type};base64,{data}"),
+                            })
+                        }),
+                );
+                input.push(json!({"role": "user", "content": parts}));
+            }
+        }
+    }
+    let instructions = (!instructions.is_empty()).then(|| instructions.join("\n\n"));
+    (instructions, input)
+}
+
+/// `tools` in OpenAI Responses wire format.
+pub fn responses_tools(tools: &[ToolDefinition]) -> Vec<Value> {
+    tools
+        .iter()
+        .map(|tool| {
+            json!({
+                "type": "function",
+                "name": tool.name,
+                "description": tool.description,
+                "parameters": tool.parameters,
+            })
+        })
+        .collect()
+}
+
+/// `messages` in Anthropic Messages wire format, with the system prompt
+/// split out.
+///
+/// User messages with images become an `image` block per image followed by
+/// a `text` block. Consecutive tool results are merged into one user
+/// message, as the API requires.
+pub fn anthropic_messages(messages: &[Message]) -> (Option<String>, Vec<Value>) {
+    let mut system = Vec::new();
+    let mut wire: Vec<Value> = Vec::with_capacity(messages.len());
+    for message in messages {
+        let text = message_text(message);
+        match (&message.content, message.role) {
+            (MessageContent::ToolResult { call_id, .. }, _) => {
+                let block = json!({"type": "tool_result", "tool_use_id": call_id, "content": text});
+                let results = wire
+                    .last_mut()
+                    .filter(|last| last["role"] == "user")
+                    .and_then(|last| last["content"].as_array_mut())
+                    .filter(|blocks| blocks.iter().all(|b| b["type"] == "tool_result"));
+                let merged = match results {
+                    Some(results) => {
+                        results.push(block.clone());
+                        true
+                    }
+                    None => false,
+                };
+                if !merged {
+                    wire.push(json!({"role": "user", "content": [block]}));
+                }
+            }
+            (_, Role::System) => system.push(text.to_owned()),
+            (_, Role::Assistant) if !message.tool_calls.is_empty() => {
+                let mut blocks = Vec::with_capacity(message.tool_calls.len() + 1);
+                if !text.is_empty() {
+                    blocks.push(json!({"type": "text", "text": text}));
+                }
+                blocks.extend(message.tool_calls.iter().map(|call| {
+                    let input = serde_json::from_str::<Value>(&call.arguments)
+                        .ok()
+                        .filter(Value::is_object)
+                        .unwrap_or_else(|| json!({}));
+                    json!({
+                        "type": "tool_use",
+                        "id": call.call_id,
+                        "name": call.function_name,
+                        "input": input,
+                    })
+                }));
+                wire.push(json!({"role": "assistant", "content": blocks}));
+            }
+            (_, role) => {
+                let role = if role == Role::Assistant {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                let images = image_data(&message.images);
+                if images.is_empty() {
+                    wire.push(json!({"role": role, "content": text}));
+                    continue;
+                }
+                let mut blocks: Vec<Value> = images
+                    .into_iter()
+                    .map(|(media_type, data)| {
+                        json!({
+                            "type": "image",
+                            "source": {"type": "base64", "media_type": media_type, "data": data},
+                        })
+                    })
+                    .collect();
+                blocks.push(json!({"type": "text", "text": text}));
+                wire.push(json!({"role": role, "content": blocks}));
+            }
+        }
+    }
+    let system = (!system.is_empty()).then(|| system.join("\n\n"));
+    (system, wire)
+}
+
+/// `tools` in Anthropic Messages wire format.
+pub fn anthropic_tools(tools: &[ToolDefinition]) -> Vec<Value> {
+    tools
+        .iter()
+        .map(|tool| {
+            json!({
+                "name": tool.name,
+                "description": tool.description,
+                "input_schema": tool.parameters,
+            })
+        })
+        .collect()
+}
+
+fn tool_call_events(call_id: String, function_name: String, arguments: String) -> [LlmEvent; 3] {
+    [
+        LlmEvent::ToolCallStart {
+            call_id: call_id.clone(),
+            function_name,
+        },
+        LlmEvent::ToolCallArgsDelta {
+            call_id: call_id.clone(),
+            args_fragment: arguments,
+        },
+        LlmEvent::ToolCallEnd { call_id },
+    ]
+}
+
+/// Events for an OpenAI chat completions response.
+///
+/// # Errors
+///
+/// Returns an error when the response has no choice.
+pub fn openai_response_events(
+    body: &Value,
+    profile: &CompatibilityProfile,
+) -> Result<Vec<LlmEvent>, FaeLlmError> {
+    let choice = body
+        .pointer("/choices/0")
+        .ok_or_else(|| FaeLlmError::ProviderError("vision response has no choices".to_owned()))?;
+    let mut events = Vec::new();
+    if let Some(text) = choice
+        .pointer("/message/content")
+        .and_then(Value::as_str)
+        .filter(|t| !t.is_empty())
+    {
+        events.push(LlmEvent::TextDelta {
+            text: text.to_owned(),
+        });
+    }
+    for call in choice
+        .pointer("/message/tool_calls")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let field = |pointer: &str| {
+            call.pointer(pointer)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        events.extend(tool_call_events(
+            profile.tool_call_id(&field("/id")),
+            field("/function/name"),
+            field("/function/arguments"),
+        ));
+    }
+    let finish_reason = choice
+        .get("finish_reason")
+        .and_then(Value::as_str)
+        .map_or(FinishReason::Stop, |raw| profile.finish_reason(raw));
+    events.push(LlmEvent::StreamEnd { finish_reason });
+    Ok(events)
+}
+
+/// Events for an Anthropic Messages response.
+///
+/// # Errors
+///
+/// Returns an error when the response has no content.
+pub fn anthropic_response_events(body: &Value) -> Result<Vec<LlmEvent>, FaeLlmError> {
+    let blocks = body
+        .get("content")
+        .and_then(Value::as_array)
+        .ok_or_else(|| FaeLlmError::ProviderError("vision response has no content".to_owned()))?;
+    let mut events = Vec::new();
+    for block in blocks {
+        let field = |name: &str| {
+            block
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        match block.get("type").and_then(Value::as_str) {
+            Some("text") => events.push(LlmEvent::TextDelta {
+                text: field("text"),
+            }),
+            Some("thinking") => events.extend([
+                LlmEvent::ThinkingStart,
+                LlmEvent::ThinkingDelta {
+                    text: field("thinking"),
+                },
+                LlmEvent::ThinkingEnd,
+            ]),
+            Some("tool_use") => events.extend(tool_call_events(
+                field("id"),
+                field("name"),
+                block
+                    .get("input")
+                    .map_or_else(String::new, Value::to_string),
+            )),
+            _ => {}
+        }
+    }
+    let finish_reason = match body.get("stop_reason").and_then(Value::as_str) {
+        Some("end_turn" | "stop_sequence") | None => FinishReason::Stop,
+        Some("max_tokens") => FinishReason::Length,
+        Some("tool_use") => FinishReason::ToolCalls,
+        Some("refusal") => FinishReason::ContentFilter,
+        Some(_) => FinishReason::Other,
+    };
+    events.push(LlmEvent::StreamEnd { finish_reason });
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::providers::message::AssistantToolCall;
+
+    fn image_turn() -> Vec<Message> {
+        vec![
+            Message::system("Be brief."),
+            Message::user_with_images(
+                "What is this?",
+                vec![ImageAttachment::jpeg(&[0xff, 0xd8, 0xff], 2, 1)],
+            ),
+        ]
+    }
+
+    #[test]
+    fn images_become_provider_content_parts() {
+        let openai = openai_messages(&image_turn());
+        assert_eq!(openai[0], json!({"role": "system", "content": "Be brief."}));
+        assert_eq!(
+            openai[1]["content"],
+            json!([
+                {"type": "text", "text": "What is this?"},
+                {"type": "image_url", "image_url": {"url": "data:image/jpeg;base64,/9j/"}},
+            ])
+        );
+
+        let (instructions, responses) = responses_input(&image_turn());
+        assert_eq!(instructions.as_deref(), Some("Be brief."));
+        assert_eq!(
+            responses[0]["content"][1],
+            json!({"type": "input_image", "image_url": "data:image/jpeg;base64,/9j/"})
+        );
+
+        let (system, anthropic) = anthropic_messages(&image_turn());
+        assert_eq!(system.as_deref(), Some("Be brief."));
+        assert_eq!(
+            anthropic,
+            vec![json!({"role": "user", "content": [
+                {"type": "image", "source": {
+                    "type": "base64", "media_type": "image/jpeg", "data": "/9j/",
+                }},
+                {"type": "text", "text": "What is this?"},
+            ]})]
+        );
+
+        // File references are read and inlined; unreadable ones are dropped.
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("screen.png");
+        std::fs::write(&path, [0x89, 0x50, 0x4e]).expect("write");
+        let on_disk = Message::user_with_images(
+            "and this?",
+            vec![
+                ImageAttachment::file(&path, "image/png", 1, 1),
+                ImageAttachment::file(dir.path().join("gone.png"), "image/png", 1, 1),
+            ],
+        );
+        let parts = &openai_messages(&[on_disk])[0]["content"];
+        assert_eq!(parts.as_array().map(Vec::len), Some(2));
+        assert_eq!(parts[1]["image_url"]["url"], "data:image/png;base64,iVBO");
+    }
+
+    #[test]
+    fn tool_turns_use_each_protocols_shape() {
+        let call = AssistantToolCall {
+            call_id: "call_1".into(),
+            function_name: "read".into(),
+            arguments: r#"{"path":"a.txt"}"#.into(),
+        };
+        let messages = vec![
+            Message::assistant_with_tool_calls(None, vec![call.clone(), call]),
+            Message::tool_result("call_1", "first"),
+            Message::tool_result("call_1", "second"),
+        ];
+
+        let openai = openai_messages(&messages);
+        assert_eq!(openai[0]["content"], Value::Null);
+        assert_eq!(openai[0]["tool_calls"][0]["function"]["name"], "read");
+        assert_eq!(
+            openai[1],
+            json!({"role": "tool", "tool_call_id": "call_1", "content": "first"})
+        );
+
+        let (_, responses) = responses_input(&messages);
+        assert_eq!(responses[0]["type"], "function_call");
+        assert_eq!(
+            responses[2],
+            json!({"type": "function_call_output", "call_id": "call_1", "output": "first"})
+        );
+
+        let (_, anthropic) = anthropic_messages(&messages);
+        assert_eq!(anthropic.len(), 2, "tool results share one user message");
+        assert_eq!(
+            anthropic[0]["content"][0]["input"],
+            json!({"path": "a.txt"})
+        );
+        assert_eq!(anthropic[1]["content"][1]["content"], "second");
+
+        let events = anthropic_response_events(&json!({
+            "content": [
+                {"type": "text", "text": "Reading."},
+                {"type": "tool_use", "id": "toolu_1", "name": "read", "input": {"path": "b"}},
+            ],
+            "stop_reason": "tool_use",
+        }))
+        .expect("events");
+        assert_eq!(events.len(), 5);
+        assert_eq!(
+            events[4],
+            LlmEvent::StreamEnd {
+                finish_reason: FinishReason::ToolCalls
+            }
+        );
+    }
+
+    struct NamedProvider {
+        name: &'static str,
+        vision: bool,
+    }
+
+    #[async_trait]
+    impl ProviderAdapter for NamedProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn supports_images(&self) -> bool {
+            self.vision
+        }
+
+        async fn send(
+            &self,
+            _messages: &[Message],
+            _options: &RequestOptions,
+            _tools: &[ToolDefinition],
+        ) -> Result<LlmEventStream, FaeLlmError> {
+            let events = vec![LlmEvent::TextDelta {
+                text: self.name.to_owned(),
+            }];
+            Ok(Box::pin(futures_util::stream::iter(events)))
+        }
+    }
+
+    #[tokio::test]
+    async fn image_turns_go_to_the_vision_provider() {
+        use futures_util::StreamExt;
+
+        let provider =
+            |name, vision| -> Arc<dyn ProviderAdapter> { Arc::new(NamedProvider { name, vision }) };
+        let answer = |provider: Arc<dyn ProviderAdapter>, messages: Vec<Message>| async move {
+            let mut stream = provider
+                .send(&messages, &RequestOptions::new(), &[])
+                .await
+                .expect("send");
+            stream.next().await
+        };
+        let text = |text: &str| {
+            Some(LlmEvent::TextDelta {
+                text: text.to_owned(),
+            })
+        };
+        let vision = provider("remote", true);
+
+        let seeing = VisionRoutingProvider::wrap(provider("local", true), Some(vision.clone()));
+        assert_eq!(answer(seeing, image_turn()).await, text("local"));
+
+        let routed = VisionRoutingProvider::wrap(provider("local", false), Some(vision));
+        assert_eq!(answer(routed.clone(), image_turn()).await, text("remote"));
+        let mut follow_up = image_turn();
+        follow_up.extend([Message::assistant("A cat."), Message::user("Thanks")]);
+        assert_eq!(answer(routed, follow_up).await, text("local"));
+    }
+}
@@ -19,6 +19,7 @@
 
 use std::sync::Arc;
 
+use super::search::title_for_session;
 use super::store::SessionStore;
 use super::types::Session;
 use super::validation::validate_session;
@@ -26,7 +27,7 @@ use crate::fae_llm::agent::loop_engine::{AgentLoop, build_messages_from_result};
 use crate::fae_llm::agent::types::{AgentConfig, AgentLoopResult};
 use crate::fae_llm::error::FaeLlmError;
 use crate::fae_llm::provider::ProviderAdapter;
-use crate::fae_llm::providers::message::{Message, Role};
+use crate::fae_llm::providers::message::{ImageAttachment, Message, Role};
 use crate::fae_llm::tools::registry::ToolRegistry;
 
 /// Manages a conversation session with automatic persistence.
@@ -118,15 +119,35 @@ impl ConversationContext {
     /// 1. Appends the user message to the session
     /// 2. Runs the agent loop with the full message history
     /// 3. Appends response messages (assistant text, tool calls, tool results)
-    /// 4. Updates session metadata (turn count, timestamp, tokens)
+    /// 4. Updates session metadata (turn count, timestamp, tokens, and the
+    ///    title after the first turn) and, unless
+    ///    [`AgentConfig::store_thinking`] is off, records the model's thinking
     /// 5. Persists the updated session to the store
     ///
     /// # Errors
     ///
     /// Returns [`FaeLlmError`] if the agent loop or persistence fails.
     pub async fn send(&mut self, message: &str) -> Result<AgentLoopResult, FaeLlmError> {
+        self.send_with_images(message, Vec::new()).await
+    }
+
+    /// Send a user message with attached images.
+    ///
+    /// Behaves like [`send()`](Self::send); the images are stored on the
+    /// user message, so a resumed conversation still has them. Fit them to
+    /// the image budget first (see [`crate::llm::images`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FaeLlmError`] if the agent loop or persistence fails.
+    pub async fn send_with_images(
+        &mut self,
+        message: &str,
+        images: Vec<ImageAttachment>,
+    ) -> Result<AgentLoopResult, FaeLlmError> {
         // 1. Append user message
-        self.session.push_message(Message::user(message));
+        self.session
+            .push_message(Message::user_with_images(message, images));
 
         // 2. Run agent loop with full history
         let agent = AgentLoop::new(
@@ -148,13 +169,20 @@ impl ConversationContext {
             }
         }
 
-        // 4. Update metadata
+        // 4. Update metadata, keeping the turn's thinking if configured
         self.session.meta.turn_count = self.session.meta.turn_count.saturating_add(1);
+        if self.config.store_thinking {
+            self.session
+                .push_thinking(self.session.meta.turn_count, result.thinking());
+        }
         self.session.meta.total_tokens = self
             .session
             .meta
             .total_tokens
             .saturating_add(result.total_usage.total());
+        if self.session.meta.title.is_none() {
+            self.session.meta.title = title_for_session(&self.session);
+        }
         self.session.meta.touch();
 
         // 5. Persist
@@ -219,6 +247,24 @@ mod tests {
             ]
         }
 
+        fn thinking_then_text(thinking: &str, text: &str) -> Vec<LlmEvent> {
+            vec![
+                LlmEvent::StreamStart {
+                    request_id: "req-1".into(),
+                    model: ModelRef::new("mock"),
+                },
+                LlmEvent::ThinkingStart,
+                LlmEvent::ThinkingDelta {
+                    text: thinking.into(),
+                },
+                LlmEvent::ThinkingEnd,
+                LlmEvent::TextDelta { text: text.into() },
+                LlmEvent::StreamEnd {
+                    finish_reason: FinishReason::Stop,
+                },
+            ]
+        }
+
         fn tool_call(call_id: &str, fn_name: &str, args: &str) -> Vec<LlmEvent> {
             vec![
                 LlmEvent::StreamStart {
@@ -373,6 +419,133 @@ mod tests {
         assert_eq!(ctx.session().meta.turn_count, 1);
     }
 
+    #[tokio::test]
+    async fn context_send_titles_session_after_first_turn() {
+        let store: Arc<dyn SessionStore> = Arc::new(MemorySessionStore::new());
+        let provider: Arc<dyn ProviderAdapter> = Arc::new(MockProvider::new(vec![
+            MockProvider::text("Sure."),
+            MockProvider::text("Done."),
+        ]));
+        let config = AgentConfig::new();
+
+        let ctx =
+            ConversationContext::new(Arc::clone(&store), config, provider, empty_registry()).await;
+        let mut ctx = match ctx {
+            Ok(c) => c,
+            Err(_) => unreachable!("context creation succeeded"),
+        };
+        assert!(ctx.session().meta.title.is_none());
+
+        assert!(ctx.send("Hey Fae, help me with the tax form").await.is_ok());
+        assert_eq!(
+            ctx.session().meta.title.as_deref(),
+            Some("Help me with the tax form")
+        );
+
+        // Later turns keep the original title.
+        assert!(ctx.send("Now something else entirely").await.is_ok());
+        let loaded = match store.load(ctx.session_id()).await {
+            Ok(s) => s,
+            Err(_) => unreachable!("load succeeded"),
+        };
+        assert_eq!(
+            loaded.meta.title.as_deref(),
+            Some("Help me with the tax form")
+        );
+    }
+
+    #[tokio::test]
+    async fn context_send_keeps_thinking_out_of_messages() {
+        for store_thinking in [true, false] {
+            let store: Arc<dyn SessionStore> = Arc::new(MemorySessionStore::new());
+            let provider: Arc<dyn ProviderAdapter> =
+                Arc::new(MockProvider::new(vec![MockProvider::thinking_then_text(
+                    "They want the time.",
+                    "It is noon.",
+                )]));
+            let config = AgentConfig::new().with_store_thinking(store_thinking);
+
+            let ctx =
+                ConversationContext::new(Arc::clone(&store), config, provider, empty_registry())
+                    .await;
+            let mut ctx = match ctx {
+                Ok(c) => c,
+                Err(_) => unreachable!("context creation succeeded"),
+            };
+            assert!(ctx.send("What time is it?").await.is_ok());
+
+            let loaded = match store.load(ctx.session_id()).await {
+                Ok(s) => s,
+                Err(_) => unreachable!("load succeeded"),
+            };
+            assert!(
+                loaded
+                    .messages
+                    .iter()
+                    .all(|m| !format!("{:?}", m.content).contains("They want the time."))
+            );
+            if store_thinking {
+                assert_eq!(loaded.thinking.len(), 1);
+                assert_eq!(loaded.thinking[0].turn, 1);
+                assert_eq!(loaded.thinking[0].text, "They want the time.");
+            } else {
+                assert!(loaded.thinking.is_empty());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn context_resume_keeps_images() {
+        let store: Arc<dyn SessionStore> = Arc::new(MemorySessionStore::new());
+        let provider: Arc<dyn ProviderAdapter> = Arc::new(MockProvider::new(vec![
+            MockProvider::text("Two cats."),
+            MockProvider::text("The left one."),
+        ]));
+        let ctx = ConversationContext::new(
+            Arc::clone(&store),
+            AgentConfig::new(),
+            Arc::clone(&provider),
+            empty_registry(),
+        )
+        .await;
+        let mut ctx = match ctx {
+            Ok(c) => c,
+            Err(_) => unreachable!("context creation succeeded"),
+        };
+        let images = vec![
+            ImageAttachment::jpeg(&[1, 2, 3], 2, 2),
+            ImageAttachment::jpeg(&[4, 5, 6], 2, 2),
+        ];
+        assert!(
+            ctx.send_with_images("What's in these?", images.clone())
+                .await
+                .is_ok()
+        );
+
+        let resumed = ConversationContext::resume(
+            ctx.session_id(),
+            Arc::clone(&store),
+            AgentConfig::new(),
+            provider,
+            empty_registry(),
+        )
+        .await;
+        let mut resumed = match resumed {
+            Ok(c) => c,
+            Err(_) => unreachable!("resume succeeded"),
+        };
+        assert!(resumed.send("Which one is bigger?").await.is_ok());
+        let with_images: Vec<_> = resumed
+            .session()
+            .messages
+            .iter()
+            .filter(|m| !m.images.is_empty())
+            .collect();
+        assert_eq!(with_images.len(), 1);
+        assert_eq!(with_images[0].role, Role::User);
+        assert_eq!(with_images[0].images, images);
+    }
+
     #[tokio::test]
     async fn context_send_persists_session() {
         let store: Arc<dyn SessionStore> = Arc::new(MemorySessionStore::new());
@@ -4,6 +4,11 @@
 //! stored as `{data_dir}/{session_id}.json`. Writes are atomic (temp file
 //! + fsync + rename) to prevent corruption on crash.
 //!
+//! When a [`DataCipher`] is attached, session files are sealed at rest.
+//! Plaintext files written before encryption was enabled remain readable.
+//! Sessions moved to `{data_dir}/archive/` by retention remain loadable but
+//! are not listed.
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -13,12 +18,17 @@
 //! ```
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 
 use super::store::SessionStore;
 use super::types::{Session, SessionId, SessionMeta};
 use crate::fae_llm::error::FaeLlmError;
+use crate::privacy::DataCipher;
+
+/// Subdirectory holding archived sessions.
+pub const ARCHIVE_DIR_NAME: &str = "archive";
 
 /// Filesystem-backed session store.
 ///
@@ -27,6 +37,7 @@ use crate::fae_llm::error::FaeLlmError;
 #[derive(Debug, Clone)]
 pub struct FsSessionStore {
     data_dir: PathBuf,
+    cipher: Option<Arc<DataCipher>>,
 }
 
 impl FsSessionStore {
@@ -45,7 +56,17 @@ impl FsSessionStore {
                 data_dir.display()
             ))
         })?;
-        Ok(Self { data_dir })
+        Ok(Self {
+            data_dir,
+            cipher: None,
+        })
+    }
+
+    /// Seal session files written by this store with `cipher`.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: Arc<DataCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
     }
 
     /// Returns the path to a session file.
@@ -53,6 +74,20 @@ impl FsSessionStore {
         self.data_dir.join(format!("{id}.json"))
     }
 
+    /// Returns the path a session file has once archived.
+    fn archived_path(&self, id: &str) -> PathBuf {
+        self.data_dir
+            .join(ARCHIVE_DIR_NAME)
+            .join(format!("{id}.json"))
+    }
+
+    /// Live path if present, otherwise the archived path if present.
+    fn existing_path(&self, id: &str) -> Option<PathBuf> {
+        [self.session_path(id), self.archived_path(id)]
+            .into_iter()
+            .find(|p| p.exists())
+    }
+
     /// Returns the data directory path.
     pub fn data_dir(&self) -> &Path {
         &self.data_dir
@@ -60,13 +95,27 @@ impl FsSessionStore {
 
     /// Read and parse a session file from disk.
     fn read_session_file(&self, path: &Path) -> Result<Session, FaeLlmError> {
-        let content = std::fs::read_to_string(path).map_err(|e| {
+        let mut content = std::fs::read(path).map_err(|e| {
             FaeLlmError::SessionError(format!(
                 "failed to read session file {}: {e}",
                 path.display()
             ))
         })?;
-        serde_json::from_str(&content).map_err(|e| {
+        if DataCipher::is_sealed(&content) {
+            let cipher = self.cipher.as_ref().ok_or_else(|| {
+                FaeLlmError::SessionError(format!(
+                    "session file {} is encrypted but no key is configured",
+                    path.display()
+                ))
+            })?;
+            content = cipher.open(&content).map_err(|e| {
+                FaeLlmError::SessionError(format!(
+                    "failed to decrypt session file {}: {e}",
+                    path.display()
+                ))
+            })?;
+        }
+        serde_json::from_slice(&content).map_err(|e| {
             FaeLlmError::SessionError(format!(
                 "failed to parse session file {}: {e}",
                 path.display()
@@ -79,12 +128,17 @@ impl FsSessionStore {
     /// Writes to a temp file, fsyncs, then renames for crash safety.
     fn write_session_atomic(&self, session: &Session) -> Result<(), FaeLlmError> {
         let path = self.session_path(&session.meta.id);
-        let json = serde_json::to_string_pretty(session)
+        let mut json = serde_json::to_vec_pretty(session)
             .map_err(|e| FaeLlmError::SessionError(format!("failed to serialize session: {e}")))?;
+        if let Some(cipher) = &self.cipher {
+            json = cipher.seal(&json).map_err(|e| {
+                FaeLlmError::SessionError(format!("failed to encrypt session: {e}"))
+            })?;
+        }
 
         // Write to temp file in the same directory (for atomic rename)
         let tmp_path = self.data_dir.join(format!(".{}.tmp", session.meta.id));
-        std::fs::write(&tmp_path, json.as_bytes()).map_err(|e| {
+        std::fs::write(&tmp_path, &json).map_err(|e| {
             FaeLlmError::SessionError(format!(
                 "failed to write temp file {}: {e}",
                 tmp_path.display()
@@ -143,12 +197,11 @@ impl SessionStore for FsSessionStore {
     }
 
     async fn load(&self, id: &str) -> Result<Session, FaeLlmError> {
-        let path = self.session_path(id);
-        if !path.exists() {
+        let Some(path) = self.existing_path(id) else {
             return Err(FaeLlmError::SessionError(format!(
                 "session not found: {id}"
             )));
-        }
+        };
         self.read_session_file(&path)
     }
 
@@ -157,8 +210,7 @@ impl SessionStore for FsSessionStore {
     }
 
     async fn delete(&self, id: &str) -> Result<(), FaeLlmError> {
-        let path = self.session_path(id);
-        if path.exists() {
+        while let Some(path) = self.existing_path(id) {
             std::fs::remove_file(&path).map_err(|e| {
                 FaeLlmError::SessionError(format!(
                     "failed to delete session file {}: {e}",
@@ -205,7 +257,7 @@ impl SessionStore for FsSessionStore {
     }
 
     async fn exists(&self, id: &str) -> Result<bool, FaeLlmError> {
-        Ok(self.session_path(id).exists())
+        Ok(self.existing_path(id).is_some())
     }
 }
 
@@ -493,4 +545,62 @@ mod tests {
         assert!(store.is_ok());
         assert!(nested.exists());
     }
+
+    #[tokio::test]
+    async fn fs_store_with_cipher_seals_files_on_disk() {
+        let dir = tempfile::tempdir().unwrap_or_else(|_| unreachable!("tempdir succeeded"));
+        let cipher = Arc::new(DataCipher::from_key(&[5u8; 32]));
+        let store = FsSessionStore::new(dir.path())
+            .unwrap_or_else(|_| unreachable!("store succeeded"))
+            .with_cipher(Arc::clone(&cipher));
+
+        let mut session = Session::new("sealed", None, None, None);
+        session.push_message(Message::user("my tax id is 1234"));
+        assert!(store.save(&session).await.is_ok());
+
+        let raw = std::fs::read(store.session_path("sealed"))
+            .unwrap_or_else(|_| unreachable!("read succeeded"));
+        assert!(DataCipher::is_sealed(&raw));
+        assert!(!raw.windows(4).any(|w| w == b"1234"));
+
+        let loaded = store.load("sealed").await;
+        assert!(loaded.is_ok());
+
+        // Without the key the sealed file is unreadable.
+        let plain_store =
+            FsSessionStore::new(dir.path()).unwrap_or_else(|_| unreachable!("store succeeded"));
+        assert!(plain_store.load("sealed").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn fs_store_with_cipher_reads_legacy_plaintext() {
+        let (dir, plain_store) = temp_store();
+        let session = Session::new("legacy", None, None, None);
+        assert!(plain_store.save(&session).await.is_ok());
+
+        let store = FsSessionStore::new(dir.path())
+            .unwrap_or_else(|_| unreachable!("store succeeded"))
+            .with_cipher(Arc::new(DataCipher::from_key(&[6u8; 32])));
+        assert!(store.load("legacy").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn fs_store_loads_but_does_not_list_archived_sessions() {
+        let (dir, store) = temp_store();
+        let session = Session::new("old", None, None, None);
+        assert!(store.save(&session).await.is_ok());
+
+        let archive = dir.path().join(ARCHIVE_DIR_NAME);
+        std::fs::create_dir_all(&archive).unwrap_or_else(|_| unreachable!("mkdir succeeded"));
+        std::fs::rename(store.session_path("old"), archive.join("old.json"))
+            .unwrap_or_else(|_| unreachable!("rename succeeded"));
+
+        assert!(store.load("old").await.is_ok());
+        assert!(matches!(store.exists("old").await, Ok(true)));
+        let metas = store.list().await.unwrap_or_default();
+        assert!(metas.is_empty());
+
+        assert!(store.delete("old").await.is_ok());
+        assert!(matches!(store.exists("old").await, Ok(false)));
+    }
 }
@@ -10,17 +10,28 @@
 //! - [`fs_store`] — Filesystem-backed session store
 //! - [`validation`] — Session validation for safe resume
 //! - [`context`] — Conversation context with auto-persistence
+//! - [`recorder`] — Turn-by-turn recording of the live conversation
+//! - [`search`] — Session titles and hybrid search over past sessions
 
 pub mod context;
 pub mod fs_store;
+pub mod recorder;
+pub mod search;
 pub mod store;
 pub mod types;
 pub mod validation;
 
 pub use context::ConversationContext;
 pub use fs_store::FsSessionStore;
+pub use recorder::SessionRecorder;
+pub use search::{
+    SessionSearchHit, generate_title, search_sessions, search_sessions_by_embedding,
+    search_sessions_with,
+};
 pub use store::{MemorySessionStore, SessionStore};
-pub use types::{CURRENT_SCHEMA_VERSION, Session, SessionId, SessionMeta, SessionResumeError};
+pub use types::{
+    CURRENT_SCHEMA_VERSION, Session, SessionId, SessionMeta, SessionResumeError, ThinkingEntry,
+};
 pub use validation::{validate_message_sequence, validate_session};
 
 #[cfg(test)]
@@ -0,0 +1,443 @@
+//! Recording a live conversation into the session store, turn by turn.
+//!
+//! The voice engine keeps a trimmed, compacted history in memory. A
+//! [`SessionRecorder`] keeps the whole conversation as a [`Session`] and
+//! saves it after every turn, so past conversations can be searched and
+//! picked up again. A session is created with the first turn after
+//! [`finish`](SessionRecorder::finish) and titled after that turn from its
+//! first message. When a title model is set it writes a better title in the
+//! background, off the turn path, and the recorder saves it once it is
+//! ready. With an embedder set, each save also stores the session's search
+//! embedding. The model's thinking is kept apart from the messages when
+//! [`with_thinking`](SessionRecorder::with_thinking) is on.
+//! Images are saved inline with their messages, so a conversation continued
+//! later keeps its visual context even after a screenshot file is gone.
+
+use std::sync::{Arc, Mutex};
+
+use super::search::{model_title, title_for_session};
+use super::store::SessionStore;
+use super::types::{Session, SessionId};
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::provider::ProviderAdapter;
+use crate::fae_llm::providers::message::{ImageAttachment, Message, Role};
+
+/// Saves each turn of the current conversation to a [`SessionStore`].
+pub struct SessionRecorder {
+    store: Arc<dyn SessionStore>,
+    session: Option<Session>,
+    /// Earlier messages the next session starts with, e.g. a conversation
+    /// handed off from another device.
+    carried: Vec<Message>,
+    model: Option<String>,
+    provider_id: Option<String>,
+    /// Writes session titles; without it they come from the first message.
+    title_model: Option<Arc<dyn ProviderAdapter>>,
+    /// Whether thinking transcripts are saved with the session.
+    store_thinking: bool,
+    /// Embeds the session for search each time it is saved.
+    embedder: Option<fn(&Session) -> Option<Vec<f32>>>,
+    /// Held while saving, so a turn and a title write-back never overwrite
+    /// each other.
+    saving: Arc<tokio::sync::Mutex<()>>,
+    /// A model-written title saved in the background, not yet applied to
+    /// the session held here.
+    written_title: Arc<Mutex<Option<(SessionId, String)>>>,
+}
+
+impl SessionRecorder {
+    /// Record into `store`.
+    pub fn new(store: Arc<dyn SessionStore>) -> Self {
+        Self {
+            store,
+            session: None,
+            carried: Vec::new(),
+            model: None,
+            provider_id: None,
+            title_model: None,
+            store_thinking: false,
+            embedder: None,
+            saving: Arc::new(tokio::sync::Mutex::new(())),
+            written_title: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Have `provider` (normally the local model) write session titles in
+    /// the background.
+    pub fn with_title_model(mut self, provider: Arc<dyn ProviderAdapter>) -> Self {
+        self.title_model = Some(provider);
+        self
+    }
+
+    /// Save each turn's thinking transcript with the session
+    /// (`[llm.thinking] store`).
+    pub fn with_thinking(mut self, store: bool) -> Self {
+        self.store_thinking = store;
+        self
+    }
+
+    /// Save a search embedding from `embed` with each turn, keeping the
+    /// previous one when `embed` has none (e.g. the model is still loading).
+    pub fn with_embedder(mut self, embed: fn(&Session) -> Option<Vec<f32>>) -> Self {
+        self.embedder = Some(embed);
+        self
+    }
+
+    /// Note the model and provider on new sessions.
+    pub fn with_model(mut self, model: impl Into<String>, provider_id: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self.provider_id = Some(provider_id.into());
+        self
+    }
+
+    /// The session being recorded, once the conversation has a turn.
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
+    /// Append one turn — the user message and everything the assistant
+    /// added after it, plus the model's `thinking` if that is stored — and
+    /// save the session.
+    ///
+    /// System messages are skipped: the prompt is rebuilt every run.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FaeLlmError::SessionError`] if the session cannot be
+    /// created or saved.
+    pub async fn record_turn(
+        &mut self,
+        messages: &[Message],
+        tokens: u64,
+        thinking: &str,
+    ) -> Result<(), FaeLlmError> {
+        if messages.iter().all(|m| m.role == Role::System) {
+            return Ok(());
+        }
+        let mut session = match self.session.take() {
+            Some(session) => session,
+            None => self.start_session().await?,
+        };
+        for message in messages.iter().filter(|m| m.role != Role::System) {
+            let mut message = message.clone();
+            message.images = message
+                .images
+                .into_iter()
+                .map(ImageAttachment::into_inline)
+                .collect();
+            session.push_message(message);
+        }
+        session.meta.turn_count = session.meta.turn_count.saturating_add(1);
+        if self.store_thinking {
+            session.push_thinking(session.meta.turn_count, thinking);
+        }
+        session.meta.total_tokens = session.meta.total_tokens.saturating_add(tokens);
+        let _saving = self.saving.lock().await;
+        if let Some((_, title)) = self
+            .written_title
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take_if(|(id, _)| *id == session.meta.id)
+        {
+            session.meta.title = Some(title);
+        }
+        if session.meta.title.is_none() {
+            session.meta.title = title_for_session(&session);
+            self.spawn_model_title(&session);
+        }
+        if let Some(embedding) = self.embedder.and_then(|embed| embed(&session)) {
+            session.embedding = Some(embedding);
+        }
+        session.meta.touch();
+        let saved = self.store.save(&session).await;
+        self.session = Some(session);
+        saved
+    }
+
+    /// End the current conversation; the next turn starts a new session.
+    pub fn finish(&mut self) {
+        self.session = None;
+        self.carried.clear();
+    }
+
+    /// End the current conversation and start the next session with
+    /// `messages`, so a continued conversation is saved whole.
+    pub fn continue_from(&mut self, messages: Vec<Message>) {
+        self.finish();
+        self.carried = messages
+            .into_iter()
+            .filter(|m| m.role != Role::System)
+            .collect();
+    }
+
+    /// Have the title model title `session` and save the title once it is
+    /// written. The first-message title stays if the model fails.
+    fn spawn_model_title(&self, session: &Session) {
+        let Some(provider) = self.title_model.clone() else {
+            return;
+        };
+        let (store, saving, written_title) = (
+            Arc::clone(&self.store),
+            Arc::clone(&self.saving),
+            Arc::clone(&self.written_title),
+        );
+        let snapshot = session.clone();
+        tokio::spawn(async move {
+            let title = match model_title(provider.as_ref(), &snapshot).await {
+                Ok(title) => title,
+                Err(e) => {
+                    tracing::debug!(error = %e, "model title failed; keeping first message");
+                    return;
+                }
+            };
+            let _saving = saving.lock().await;
+            let id = snapshot.meta.id;
+            let saved = match store.load(&id).await {
+                Ok(mut latest) => {
+                    latest.meta.title = Some(title.clone());
+                    store.save(&latest).await
+                }
+                Err(e) => Err(e),
+            };
+            match saved {
+                Ok(()) => {
+                    *written_title.lock().unwrap_or_else(|e| e.into_inner()) = Some((id, title));
+                }
+                Err(e) => tracing::debug!(error = %e, "failed to save model title"),
+            }
+        });
+    }
+
+    async fn start_session(&mut self) -> Result<Session, FaeLlmError> {
+        let id = self.store.create(None).await?;
+        let mut session = self.store.load(&id).await?;
+        session.meta.model = self.model.clone();
+        session.meta.provider_id = self.provider_id.clone();
+        for message in self.carried.drain(..) {
+            session.push_message(message);
+        }
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::fae_llm::events::{FinishReason, LlmEvent};
+    use crate::fae_llm::provider::{LlmEventStream, ToolDefinition};
+    use crate::fae_llm::session::store::MemorySessionStore;
+    use crate::fae_llm::types::{ModelRef, RequestOptions};
+
+    /// Replies with a fixed title after a delay.
+    struct SlowTitleProvider;
+
+    #[async_trait]
+    impl ProviderAdapter for SlowTitleProvider {
+        fn name(&self) -> &str {
+            "slow-title"
+        }
+
+        async fn send(
+            &self,
+            _messages: &[Message],
+            _options: &RequestOptions,
+            _tools: &[ToolDefinition],
+        ) -> Result<LlmEventStream, FaeLlmError> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let events = vec![
+                LlmEvent::StreamStart {
+                    request_id: "req".into(),
+                    model: ModelRef::new("mock"),
+                },
+                LlmEvent::TextDelta {
+                    text: "Lisbon trip in May".into(),
+                },
+                LlmEvent::StreamEnd {
+                    finish_reason: FinishReason::Stop,
+                },
+            ];
+            Ok(Box::pin(futures_util::stream::iter(events)))
+        }
+    }
+
+    #[tokio::test]
+    async fn records_turns_into_one_titled_session_until_finished() {
+        let store = Arc::new(MemorySessionStore::new());
+        let mut recorder = SessionRecorder::new(store.clone()).with_model("qwen", "local");
+
+        recorder
+            .record_turn(
+                &[
+                    Message::user("Plan a trip to Lisbon"),
+                    Message::assistant("Sure, when?"),
+                ],
+                40,
+                "",
+            )
+            .await
+            .unwrap();
+        recorder
+            .record_turn(
+                &[Message::user("In May"), Message::assistant("Noted.")],
+                20,
+                "",
+            )
+            .await
+            .unwrap();
+
+        let id = recorder.session().unwrap().meta.id.clone();
+        let saved = store.load(&id).await.unwrap();
+        assert_eq!(saved.messages.len(), 4);
+        assert_eq!(saved.meta.turn_count, 2);
+        assert_eq!(saved.meta.total_tokens, 60);
+        assert_eq!(saved.meta.title.as_deref(), Some("Plan a trip to Lisbon"));
+        assert_eq!(saved.meta.model.as_deref(), Some("qwen"));
+
+        recorder.finish();
+        recorder
+            .record_turn(&[Message::user("What time is it?")], 0, "")
+            .await
+            .unwrap();
+        assert_ne!(recorder.session().unwrap().meta.id, id);
+        assert_eq!(store.list().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn model_title_is_written_in_the_background() {
+        let store = Arc::new(MemorySessionStore::new());
+        let mut recorder =
+            SessionRecorder::new(store.clone()).with_title_model(Arc::new(SlowTitleProvider));
+
+        recorder
+            .record_turn(&[Message::user("Plan a trip to Lisbon")], 0, "")
+            .await
+            .unwrap();
+        let id = recorder.session().unwrap().meta.id.clone();
+        let saved = store.load(&id).await.unwrap();
+        assert_eq!(saved.meta.title.as_deref(), Some("Plan a trip to Lisbon"));
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while store.load(&id).await.unwrap().meta.title.as_deref() != Some("Lisbon trip in May")
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("model title saved");
+
+        recorder
+            .record_turn(&[Message::user("In May")], 0, "")
+            .await
+            .unwrap();
+        let saved = store.load(&id).await.unwrap();
+        assert_eq!(saved.meta.title.as_deref(), Some("Lisbon trip in May"));
+        assert_eq!(saved.messages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn embedding_is_saved_with_each_turn() {
+        let store = Arc::new(MemorySessionStore::new());
+        let mut recorder = SessionRecorder::new(store.clone())
+            .with_embedder(|session| Some(vec![session.messages.len() as f32]));
+
+        recorder
+            .record_turn(&[Message::user("Hi"), Message::assistant("Hello")], 0, "")
+            .await
+            .unwrap();
+
+        let id = recorder.session().unwrap().meta.id.clone();
+        assert_eq!(store.load(&id).await.unwrap().embedding, Some(vec![2.0]));
+    }
+
+    #[tokio::test]
+    async fn continued_conversation_keeps_earlier_messages() {
+        let store = Arc::new(MemorySessionStore::new());
+        let mut recorder = SessionRecorder::new(store.clone());
+        recorder.continue_from(vec![
+            Message::system("other prompt"),
+            Message::user("Book a table"),
+            Message::assistant("For how many?"),
+        ]);
+
+        recorder
+            .record_turn(&[Message::user("Four"), Message::assistant("Done.")], 0, "")
+            .await
+            .unwrap();
+
+        let session = recorder.session().unwrap();
+        assert_eq!(session.messages.len(), 4);
+        assert_eq!(session.messages[0].role, Role::User);
+        assert_eq!(session.meta.title.as_deref(), Some("Book a table"));
+    }
+
+    #[tokio::test]
+    async fn thinking_is_saved_only_when_enabled() {
+        for store_thinking in [true, false] {
+            let store = Arc::new(MemorySessionStore::new());
+            let mut recorder = SessionRecorder::new(store.clone()).with_thinking(store_thinking);
+            let turn = [
+                Message::user("What time is it?"),
+                Message::assistant("Noon."),
+            ];
+
+            recorder
+                .record_turn(&turn, 0, "They want the time.")
+                .await
+                .unwrap();
+
+            let id = recorder.session().unwrap().meta.id.clone();
+            let saved = store.load(&id).await.unwrap();
+            assert_eq!(saved.messages.len(), 2);
+            if store_thinking {
+                assert_eq!(saved.thinking.len(), 1);
+                assert_eq!(saved.thinking[0].turn, 1);
+                assert_eq!(saved.thinking[0].text, "They want the time.");
+            } else {
+                assert!(saved.thinking.is_empty());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn images_survive_a_continued_conversation() {
+        let dir = tempfile::tempdir().unwrap();
+        let screenshot = dir.path().join("screen.png");
+        std::fs::write(&screenshot, [0x89, b'P', b'N', b'G']).unwrap();
+        let store = Arc::new(MemorySessionStore::new());
+        let mut recorder = SessionRecorder::new(store.clone());
+        let turn = [
+            Message::user_with_images(
+                "What is this?",
+                vec![ImageAttachment::file(&screenshot, "image/png", 1, 1)],
+            ),
+            Message::assistant("A receipt."),
+        ];
+        recorder.record_turn(&turn, 0, "").await.unwrap();
+        std::fs::remove_file(&screenshot).unwrap();
+
+        let id = recorder.session().unwrap().meta.id.clone();
+        let saved = store.load(&id).await.unwrap();
+        let image = &saved.messages[0].images[0];
+        assert_eq!(image.path, None);
+        assert_eq!(image.bytes(), Some(vec![0x89, b'P', b'N', b'G']));
+
+        recorder.continue_from(saved.messages.clone());
+        recorder
+            .record_turn(
+                &[Message::user("How much?"), Message::assistant("$12.")],
+                0,
+                "",
+            )
+            .await
+            .unwrap();
+        let continued = recorder.session().unwrap();
+        assert_ne!(continued.meta.id, id);
+        assert_eq!(continued.messages[0].images, saved.messages[0].images);
+    }
+}
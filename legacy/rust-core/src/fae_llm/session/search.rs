@@ -0,0 +1,601 @@
+//! Session titles and search over past conversations.
+//!
+//! Titles are written after the first turn so the session list reads like a
+//! history ("Help with the tax form") instead of opaque IDs. The local model
+//! writes them ([`model_title`]); [`generate_title`] is the fallback when no
+//! model is at hand, cutting the first user message down to a few words.
+//! [`search_sessions`] ranks stored sessions against a free-text query using
+//! a hybrid of keyword overlap and sentence-embedding similarity. Sessions
+//! are embedded once, when saved ([`embed_for_search`]), so a search only
+//! embeds the query.
+//!
+//! # Examples
+//!
+//! ```
+//! use fae::fae_llm::session::search::generate_title;
+//!
+//! let title = generate_title("hey fae, can you help me fill in the tax form?");
+//! assert_eq!(title, "Help me fill in the tax form");
+//! ```
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde::Serialize;
+
+use super::store::SessionStore;
+use super::types::{Session, SessionMeta};
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::events::LlmEvent;
+use crate::fae_llm::provider::ProviderAdapter;
+use crate::fae_llm::providers::message::{Message, MessageContent, Role};
+use crate::fae_llm::types::{ReasoningLevel, RequestOptions};
+use crate::memory::embedding::{cosine_similarity, shared_engine};
+
+/// Maximum number of words kept in a generated title.
+const TITLE_MAX_WORDS: usize = 8;
+
+/// Instruction for titling a conversation with a model.
+const TITLE_PROMPT: &str = "Write a title of at most eight words for the conversation \
+below. Reply with the title only, without quotes or punctuation at the end.";
+
+/// How long the model may take to write a title.
+const TITLE_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Characters of the first exchange shown to the model when titling.
+const TITLE_INPUT_MAX_CHARS: usize = 1_000;
+
+/// Maximum length (in characters) of a search result snippet.
+const SNIPPET_MAX_CHARS: usize = 120;
+
+/// Weight given to keyword overlap in the hybrid score; the remainder goes
+/// to embedding similarity.
+const KEYWORD_WEIGHT: f32 = 0.6;
+
+/// Hybrid scores below this are dropped from search results.
+const MIN_SCORE: f32 = 0.15;
+
+/// Leading conversational filler stripped before titling.
+const FILLER_PREFIXES: &[&str] = &[
+    "hey fae",
+    "hi fae",
+    "hello fae",
+    "ok fae",
+    "okay fae",
+    "fae",
+    "hey",
+    "hi",
+    "hello",
+    "please",
+    "can you",
+    "could you",
+    "would you",
+    "will you",
+    "i want you to",
+    "i need you to",
+    "i'd like you to",
+];
+
+/// A single ranked search hit.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSearchHit {
+    /// Metadata of the matching session.
+    pub meta: SessionMeta,
+    /// Hybrid relevance score in `[0.0, 1.0]`.
+    pub score: f32,
+    /// Short excerpt of the best-matching message, if any.
+    pub snippet: Option<String>,
+}
+
+/// Generate a short title from the first user message of a session.
+///
+/// Takes the first sentence, strips greetings and request filler
+/// ("hey fae, can you ..."), and keeps at most eight words. Returns
+/// `"Untitled conversation"` when nothing usable remains.
+pub fn generate_title(first_user_text: &str) -> String {
+    let first_line = first_user_text.lines().find(|l| !l.trim().is_empty());
+    let sentence = first_line
+        .unwrap_or("")
+        .split_terminator(['.', '?', '!'])
+        .next()
+        .unwrap_or("");
+
+    let mut rest = sentence.trim();
+    loop {
+        let stripped = strip_filler(rest);
+        if stripped.len() == rest.len() {
+            break;
+        }
+        rest = stripped;
+    }
+
+    let words: Vec<&str> = rest.split_whitespace().take(TITLE_MAX_WORDS).collect();
+    if words.is_empty() {
+        return "Untitled conversation".to_owned();
+    }
+
+    let joined = words.join(" ");
+    let joined = joined.trim_end_matches([',', ';', ':']);
+    let mut chars = joined.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Untitled conversation".to_owned(),
+    }
+}
+
+/// Title a session from its first user message, if it has one.
+pub fn title_for_session(session: &Session) -> Option<String> {
+    session
+        .messages
+        .iter()
+        .find(|m| m.role == Role::User)
+        .and_then(|m| match &m.content {
+            MessageContent::Text { text } if !text.trim().is_empty() => Some(generate_title(text)),
+            _ => None,
+        })
+}
+
+/// Title a session with `provider` (normally the local model) from its
+/// first user message and reply.
+///
+/// # Errors
+///
+/// Returns an error if the model fails, times out, or replies with nothing
+/// usable; callers fall back to [`title_for_session`].
+pub async fn model_title(
+    provider: &dyn ProviderAdapter,
+    session: &Session,
+) -> Result<String, FaeLlmError> {
+    let first_text = |role: Role| {
+        session
+            .messages
+            .iter()
+            .filter(|m| m.role == role)
+            .find_map(|m| match &m.content {
+                MessageContent::Text { text } if !text.trim().is_empty() => Some(text.as_str()),
+                _ => None,
+            })
+    };
+    let user = first_text(Role::User)
+        .ok_or_else(|| FaeLlmError::SessionError("session has no user message".into()))?;
+    let mut exchange: String = format!("User: {user}")
+        .chars()
+        .take(TITLE_INPUT_MAX_CHARS)
+        .collect();
+    if let Some(reply) = first_text(Role::Assistant) {
+        exchange.push_str("\nAssistant: ");
+        exchange.extend(reply.chars().take(TITLE_INPUT_MAX_CHARS));
+    }
+
+    let messages = vec![Message::system(TITLE_PROMPT), Message::user(exchange)];
+    let options = RequestOptions::new()
+        .with_stream(true)
+        .with_reasoning(ReasoningLevel::Off)
+        .with_temperature(0.0)
+        .with_max_tokens(32);
+    let collect = async {
+        let mut stream = provider.send(&messages, &options, &[]).await?;
+        let mut reply = String::new();
+        while let Some(event) = stream.next().await {
+            match event {
+                LlmEvent::TextDelta { text } => reply.push_str(&text),
+                LlmEvent::StreamError { error } => return Err(FaeLlmError::StreamError(error)),
+                LlmEvent::StreamEnd { .. } => break,
+                _ => {}
+            }
+        }
+        Ok(reply)
+    };
+    let reply = tokio::time::timeout(TITLE_TIMEOUT, collect)
+        .await
+        .map_err(|_| FaeLlmError::TimeoutError("writing the session title timed out".into()))??;
+    clean_model_title(&reply)
+        .ok_or_else(|| FaeLlmError::SessionError("model wrote an empty title".into()))
+}
+
+/// Tidy a model-written title: first line, no label, quotes or trailing
+/// punctuation, at most [`TITLE_MAX_WORDS`] words.
+fn clean_model_title(reply: &str) -> Option<String> {
+    let line = reply.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let line = line
+        .strip_prefix("Title:")
+        .or_else(|| line.strip_prefix("title:"))
+        .unwrap_or(line)
+        .trim_start_matches('#')
+        .trim()
+        .trim_matches(['"', '\'', '*', '`', '“', '”'])
+        .trim_end_matches(['.', '!', '?', ',', ';', ':']);
+    let words: Vec<&str> = line.split_whitespace().take(TITLE_MAX_WORDS).collect();
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+/// Embedding of `session` for search with the sentence embedder, or `None`
+/// while the model is not loaded (asking starts loading it).
+pub fn embed_for_search(session: &Session) -> Option<Vec<f32>> {
+    let document = session_document(session);
+    if document.is_empty() {
+        return None;
+    }
+    let engine = shared_engine()?;
+    let mut engine = engine.lock().ok()?;
+    engine.embed(&document).ok()
+}
+
+/// Search stored sessions, comparing the query's sentence embedding with the
+/// ones saved with each session.
+///
+/// Never waits for the embedding model: until it is loaded, and for
+/// sessions saved without an embedding, ranking falls back to the lexical
+/// embedding of skill discovery. See [`search_sessions_with`] for scoring
+/// details.
+///
+/// # Errors
+///
+/// Returns [`FaeLlmError::SessionError`] if the store cannot be listed.
+pub async fn search_sessions(
+    store: &dyn SessionStore,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SessionSearchHit>, FaeLlmError> {
+    let query_embedding =
+        shared_engine().and_then(|engine| engine.lock().ok().and_then(|mut e| e.embed(query).ok()));
+    match query_embedding {
+        Some(query_embedding) => {
+            search_sessions_by_embedding(store, query, limit, &query_embedding).await
+        }
+        None => {
+            search_sessions_with(
+                store,
+                query,
+                limit,
+                crate::skills::discovery::deterministic_embedding,
+            )
+            .await
+        }
+    }
+}
+
+/// Search stored sessions, ranking each by its saved embedding's similarity
+/// to `query_embedding`.
+///
+/// Sessions without a saved embedding of the same size are ranked with the
+/// lexical embedding instead. Nothing is embedded with a model.
+///
+/// # Errors
+///
+/// Returns [`FaeLlmError::SessionError`] if the store cannot be listed.
+pub async fn search_sessions_by_embedding(
+    store: &dyn SessionStore,
+    query: &str,
+    limit: usize,
+    query_embedding: &[f32],
+) -> Result<Vec<SessionSearchHit>, FaeLlmError> {
+    let lexical = crate::skills::discovery::deterministic_embedding;
+    let lexical_query = lexical(query);
+    rank_sessions(store, query, limit, |session, document| {
+        match &session.embedding {
+            Some(saved) if saved.len() == query_embedding.len() => {
+                cosine_similarity(query_embedding, saved)
+            }
+            _ => cosine_similarity(&lexical_query, &lexical(document)),
+        }
+    })
+    .await
+}
+
+/// Search stored sessions, ranking by keyword overlap and embedding similarity.
+///
+/// Each session is scored over its title and user/assistant text. The score
+/// blends the fraction of query terms present with the cosine similarity of
+/// `embed(query)` and `embed(document)`. Sessions that fail to load are
+/// skipped. Results are sorted by descending score, most recent first on ties.
+///
+/// # Errors
+///
+/// Returns [`FaeLlmError::SessionError`] if the store cannot be listed.
+pub async fn search_sessions_with<F>(
+    store: &dyn SessionStore,
+    query: &str,
+    limit: usize,
+    embed: F,
+) -> Result<Vec<SessionSearchHit>, FaeLlmError>
+where
+    F: Fn(&str) -> Vec<f32>,
+{
+    let query_embedding = embed(query);
+    rank_sessions(store, query, limit, |_, document| {
+        cosine_similarity(&query_embedding, &embed(document))
+    })
+    .await
+}
+
+/// Score every stored session, with `similarity(session, document)` as the
+/// semantic component.
+async fn rank_sessions<F>(
+    store: &dyn SessionStore,
+    query: &str,
+    limit: usize,
+    similarity: F,
+) -> Result<Vec<SessionSearchHit>, FaeLlmError>
+where
+    F: Fn(&Session, &str) -> f32,
+{
+    let terms = tokenize(query);
+    if terms.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut hits = Vec::new();
+    for meta in store.list().await? {
+        let Ok(session) = store.load(&meta.id).await else {
+            continue;
+        };
+        let document = session_document(&session);
+        if document.is_empty() {
+            continue;
+        }
+
+        let doc_terms = tokenize(&document);
+        let matched = terms.iter().filter(|t| doc_terms.contains(t)).count();
+        let keyword_score = matched as f32 / terms.len() as f32;
+        let semantic_score = similarity(&session, &document).max(0.0);
+        let score = KEYWORD_WEIGHT * keyword_score + (1.0 - KEYWORD_WEIGHT) * semantic_score;
+        if score < MIN_SCORE {
+            continue;
+        }
+
+        hits.push(SessionSearchHit {
+            snippet: best_snippet(&session, &terms),
+            meta: session.meta,
+            score,
+        });
+    }
+
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.meta.updated_at.cmp(&a.meta.updated_at))
+    });
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+fn strip_filler(text: &str) -> &str {
+    let lower = text.to_ascii_lowercase();
+    for prefix in FILLER_PREFIXES {
+        if let Some(after) = lower.strip_prefix(prefix)
+            && (after.is_empty() || after.starts_with([' ', ',', '!']))
+        {
+            return text[prefix.len()..].trim_start_matches([' ', ',', '!']);
+        }
+    }
+    text
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.len() > 2)
+        .map(str::to_lowercase)
+        .collect();
+    terms.sort_unstable();
+    terms.dedup();
+    terms
+}
+
+/// Searchable text for a session: title plus user and assistant messages.
+fn session_document(session: &Session) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    if let Some(title) = session.meta.title.as_deref() {
+        parts.push(title);
+    }
+    for message in &session.messages {
+        if !matches!(message.role, Role::User | Role::Assistant) {
+            continue;
+        }
+        if let MessageContent::Text { text } = &message.content
+            && !text.trim().is_empty()
+        {
+            parts.push(text);
+        }
+    }
+    parts.join("\n")
+}
+
+/// Excerpt of the first user/assistant message containing a query term.
+fn best_snippet(session: &Session, terms: &[String]) -> Option<String> {
+    session
+        .messages
+        .iter()
+        .filter(|m| matches!(m.role, Role::User | Role::Assistant))
+        .find_map(|m| match &m.content {
+            MessageContent::Text { text } => {
+                let lower = text.to_lowercase();
+                terms
+                    .iter()
+                    .filter_map(|t| lower.find(t.as_str()))
+                    .min()
+                    .map(|pos| excerpt(text, pos))
+            }
+            MessageContent::ToolResult { .. } => None,
+        })
+}
+
+/// Cut a window of at most [`SNIPPET_MAX_CHARS`] characters around `byte_pos`.
+fn excerpt(text: &str, byte_pos: usize) -> String {
+    let char_pos = text
+        .char_indices()
+        .take_while(|(i, _)| *i < byte_pos)
+        .count();
+    let start = char_pos.saturating_sub(SNIPPET_MAX_CHARS / 4);
+    let total = text.chars().count();
+    let window: String = text.chars().skip(start).take(SNIPPET_MAX_CHARS).collect();
+    let window = window.trim();
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    out.push_str(window);
+    if start + SNIPPET_MAX_CHARS < total {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::providers::message::Message;
+    use crate::fae_llm::session::store::MemorySessionStore;
+
+    /// Search without loading the embedding model.
+    async fn lexical_search(
+        store: &MemorySessionStore,
+        query: &str,
+        limit: usize,
+    ) -> Vec<SessionSearchHit> {
+        search_sessions_with(
+            store,
+            query,
+            limit,
+            crate::skills::discovery::deterministic_embedding,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn seed(store: &MemorySessionStore, user: &str, assistant: &str) -> String {
+        let id = store.create(None).await.unwrap();
+        let mut session = store.load(&id).await.unwrap();
+        session.push_message(Message::user(user));
+        session.push_message(Message::assistant(assistant));
+        session.meta.title = title_for_session(&session);
+        store.save(&session).await.unwrap();
+        id
+    }
+
+    #[test]
+    fn generate_title_strips_filler_and_capitalises() {
+        assert_eq!(
+            generate_title("Hey Fae, could you remind me to call mum tomorrow?"),
+            "Remind me to call mum tomorrow"
+        );
+        assert_eq!(generate_title("what's the weather"), "What's the weather");
+    }
+
+    #[test]
+    fn generate_title_limits_words_and_uses_first_sentence() {
+        let title = generate_title(
+            "Summarise the quarterly report for the board meeting on Friday afternoon please. Also more.",
+        );
+        assert_eq!(title.split_whitespace().count(), TITLE_MAX_WORDS);
+        assert!(title.starts_with("Summarise the quarterly report"));
+    }
+
+    #[test]
+    fn generate_title_falls_back_when_empty() {
+        assert_eq!(generate_title(""), "Untitled conversation");
+        assert_eq!(generate_title("hey fae!"), "Untitled conversation");
+    }
+
+    #[test]
+    fn title_for_session_uses_first_user_message() {
+        let mut session = Session::new("s", Some("system".into()), None, None);
+        session.push_message(Message::system("system"));
+        assert!(title_for_session(&session).is_none());
+        session.push_message(Message::user("Plan a trip to Lisbon"));
+        assert_eq!(
+            title_for_session(&session).as_deref(),
+            Some("Plan a trip to Lisbon")
+        );
+    }
+
+    #[test]
+    fn clean_model_title_strips_labels_and_quotes() {
+        assert_eq!(
+            clean_model_title("\nTitle: \"Planning a May trip to Lisbon.\"\nExtra").as_deref(),
+            Some("Planning a May trip to Lisbon")
+        );
+        assert_eq!(
+            clean_model_title("one two three four five six seven eight nine").as_deref(),
+            Some("one two three four five six seven eight")
+        );
+        assert!(clean_model_title("  \n\"\"").is_none());
+    }
+
+    #[test]
+    fn excerpt_marks_truncation() {
+        let text = format!("{} tax form {}", "a".repeat(100), "b".repeat(200));
+        let pos = text.find("tax").unwrap();
+        let out = excerpt(&text, pos);
+        assert!(out.starts_with('…'));
+        assert!(out.ends_with('…'));
+        assert!(out.contains("tax form"));
+    }
+
+    #[tokio::test]
+    async fn search_ranks_matching_session_first() {
+        let store = MemorySessionStore::new();
+        let tax = seed(
+            &store,
+            "Can you help me with the tax form?",
+            "Sure, which tax form are you filling in?",
+        )
+        .await;
+        seed(&store, "Play some jazz", "Playing jazz now.").await;
+
+        let hits = lexical_search(&store, "that conversation about the tax form", 5).await;
+        assert!(!hits.is_empty());
+        assert_eq!(hits[0].meta.id, tax);
+        assert_eq!(
+            hits[0].meta.title.as_deref(),
+            Some("Help me with the tax form")
+        );
+        assert!(hits[0].snippet.as_deref().unwrap().contains("tax form"));
+        assert!(hits.iter().all(|h| h.meta.id == tax));
+    }
+
+    #[tokio::test]
+    async fn search_respects_limit_and_empty_query() {
+        let store = MemorySessionStore::new();
+        for _ in 0..3 {
+            seed(&store, "garden watering schedule", "Water every morning.").await;
+        }
+        assert_eq!(lexical_search(&store, "garden", 2).await.len(), 2);
+        assert!(lexical_search(&store, "  ", 5).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_uses_saved_embeddings() {
+        let store = MemorySessionStore::new();
+        let holiday = seed(&store, "Budget for the holiday", "Let's plan it.").await;
+        let jazz = seed(&store, "Play some jazz", "Playing jazz now.").await;
+        for (id, embedding) in [(&holiday, vec![1.0, 0.0]), (&jazz, vec![0.0, 1.0])] {
+            let mut session = store.load(id).await.unwrap();
+            session.embedding = Some(embedding);
+            store.save(&session).await.unwrap();
+        }
+
+        let hits = search_sessions_by_embedding(&store, "vacation", 5, &[1.0, 0.0])
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].meta.id, holiday);
+    }
+
+    #[tokio::test]
+    async fn search_uses_custom_embedder() {
+        let store = MemorySessionStore::new();
+        let id = seed(&store, "Budget for the holiday", "Let's plan it.").await;
+        // A constant embedder makes every document maximally similar, so the
+        // semantic component alone clears the threshold.
+        let hits = search_sessions_with(&store, "vacation", 5, |_| vec![1.0, 0.0])
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].meta.id, id);
+    }
+}
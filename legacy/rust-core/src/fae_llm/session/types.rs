@@ -47,6 +47,9 @@ pub struct SessionMeta {
     pub provider_id: Option<String>,
     /// Schema version for forward compatibility.
     pub schema_version: u32,
+    /// Short human-readable title, generated from the first user turn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
 }
 
 impl SessionMeta {
@@ -71,6 +74,7 @@ impl SessionMeta {
             model,
             provider_id,
             schema_version: CURRENT_SCHEMA_VERSION,
+            title: None,
         }
     }
 
@@ -80,16 +84,34 @@ impl SessionMeta {
     }
 }
 
+/// The model's thinking during one turn of a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThinkingEntry {
+    /// The turn it belongs to, counting from 1 (see [`SessionMeta::turn_count`]).
+    pub turn: usize,
+    /// The thinking text.
+    pub text: String,
+}
+
 /// A persisted session: metadata plus the full message history.
 ///
 /// The `messages` field contains the complete conversation in the same
 /// [`Message`] format used by the agent loop, making resume seamless.
+/// Thinking transcripts are kept apart in `thinking`: they are never sent
+/// back to the model or spoken, only shown on request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     /// Session metadata (ID, timestamps, counts).
     pub meta: SessionMeta,
     /// The full message history (system, user, assistant, tool results).
     pub messages: Vec<Message>,
+    /// Thinking transcripts of the turns where the model thought.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thinking: Vec<ThinkingEntry>,
+    /// Sentence embedding of the conversation, written when it is saved so
+    /// search does not have to embed every session per query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
 }
 
 impl Session {
@@ -104,6 +126,8 @@ impl Session {
         Self {
             meta,
             messages: Vec::new(),
+            thinking: Vec::new(),
+            embedding: None,
         }
     }
 
@@ -113,6 +137,14 @@ impl Session {
         self.meta.touch();
     }
 
+    /// Record the model's thinking during `turn`; blank text is ignored.
+    pub fn push_thinking(&mut self, turn: usize, text: impl Into<String>) {
+        let text = text.into();
+        if !text.trim().is_empty() {
+            self.thinking.push(ThinkingEntry { turn, text });
+        }
+    }
+
     /// Replace the entire message list and touch the timestamp.
     pub fn set_messages(&mut self, messages: Vec<Message>) {
         self.messages = messages;
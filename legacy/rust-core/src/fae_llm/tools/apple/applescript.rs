@@ -2,7 +2,7 @@
 //!
 //! Each store uses `osascript` (JXA / JavaScript for Automation) to interact
 //! with the corresponding macOS application: Contacts, Calendar, Reminders,
-//! Notes, and Mail.
+//! Notes, Mail, and Music.
 //!
 //! This approach requires no Objective-C bindings or Swift bridge changes —
 //! `osascript` is available on all macOS systems and works under App Sandbox
@@ -22,6 +22,9 @@ use super::calendar::{
 };
 use super::contacts::{Contact, ContactQuery, ContactStore, ContactStoreError, NewContact};
 use super::mail::{Mail, MailQuery, MailStore, MailStoreError, NewMail};
+use super::media::{
+    MediaCommand, MediaPlayer, MediaPlayerError, NowPlaying, PlayRequest, PlaybackState, Track,
+};
 use super::notes::{NewNote, Note, NoteQuery, NoteStore, NoteStoreError};
 use super::reminders::{
     NewReminder, Reminder, ReminderList, ReminderQuery, ReminderStore, ReminderStoreError,
@@ -759,6 +762,13 @@ impl ReminderStore for ApplescriptReminderStore {
     }
 
     fn create_reminder(&self, reminder: &NewReminder) -> Result<Reminder, ReminderStoreError> {
+        // Reminders' scripting dictionary has no repeat rules; creating the
+        // reminder without one would quietly drop what the user asked for.
+        if reminder.recurrence.is_some() {
+            return Err(ReminderStoreError::InvalidInput(
+                "repeating reminders need the native Reminders integration".to_owned(),
+            ));
+        }
         let title = jxa_escape(&reminder.title);
         let list_line = if let Some(ref list_id) = reminder.list_id {
             let escaped = jxa_escape(list_id);
@@ -885,6 +895,7 @@ fn parse_single_reminder(v: &serde_json::Value) -> Result<Reminder, String> {
         priority: v["priority"].as_u64().unwrap_or(0) as u8,
         is_completed: v["is_completed"].as_bool().unwrap_or(false),
         completion_date: v["completion_date"].as_str().map(str::to_owned),
+        recurrence: None,
     })
 }
 
@@ -1258,6 +1269,145 @@ fn parse_single_mail(v: &serde_json::Value) -> Result<Mail, String> {
     })
 }
 
+// ─── MediaPlayer ─────────────────────────────────────────────────────────────
+
+/// Name of the playlist Fae fills with search matches before playing them.
+const FAE_MIX_PLAYLIST: &str = "Fae Mix";
+
+/// JXA expression reporting Music.app's current track as JSON, or `null`
+/// when nothing is loaded. Expects `app` to be bound to Music.
+const JXA_NOW_PLAYING: &str = r#"
+    (function() {
+        var state = app.playerState();
+        if (state === "stopped") { return JSON.stringify(null); }
+        var t = app.currentTrack();
+        var list = null;
+        try { list = app.currentPlaylist().name(); } catch (e) {}
+        return JSON.stringify({
+            title: t.name(),
+            artist: t.artist() || null,
+            album: t.album() || null,
+            genre: t.genre() || null,
+            playlist: list,
+            state: state
+        });
+    })()
+"#;
+
+/// AppleScript-backed media player using JXA to drive Music.app.
+pub struct ApplescriptMediaPlayer;
+
+impl MediaPlayer for ApplescriptMediaPlayer {
+    fn now_playing(&self) -> Result<Option<NowPlaying>, MediaPlayerError> {
+        let script = format!(
+            r#"
+            var app = Application("Music");
+            if (!app.running()) {{ JSON.stringify(null); }}
+            else {{ {JXA_NOW_PLAYING}; }}
+            "#
+        );
+        let value = run_jxa(&script).map_err(MediaPlayerError::Backend)?;
+        parse_now_playing(&value)
+            .map_err(|e| MediaPlayerError::Backend(format!("parse error: {e}")))
+    }
+
+    fn control(&self, command: MediaCommand) -> Result<Option<NowPlaying>, MediaPlayerError> {
+        let call = match command {
+            MediaCommand::Play => "app.play();",
+            MediaCommand::Pause => "app.pause();",
+            MediaCommand::Next => "app.nextTrack();",
+            MediaCommand::Previous => "app.previousTrack();",
+        };
+        // Only "play" may launch Music; the others are no-ops when it is closed.
+        let launch_guard = if command == MediaCommand::Play {
+            ""
+        } else {
+            "if (!app.running()) { JSON.stringify(null); } else"
+        };
+        let script = format!(
+            r#"
+            var app = Application("Music");
+            {launch_guard} {{
+                {call}
+                {JXA_NOW_PLAYING};
+            }}
+            "#
+        );
+        let value = run_jxa(&script).map_err(MediaPlayerError::Backend)?;
+        parse_now_playing(&value)
+            .map_err(|e| MediaPlayerError::Backend(format!("parse error: {e}")))
+    }
+
+    fn play(&self, request: &PlayRequest) -> Result<NowPlaying, MediaPlayerError> {
+        let query = jxa_escape(&request.query);
+        let mix = jxa_escape(FAE_MIX_PLAYLIST);
+        let script = format!(
+            r#"
+            var app = Application("Music");
+            var q = "{query}".toLowerCase();
+            var lists = app.userPlaylists().filter(function(p) {{
+                return p.name() !== "{mix}" && p.name().toLowerCase().indexOf(q) !== -1;
+            }});
+            if (lists.length > 0) {{
+                lists[0].play();
+            }} else {{
+                if ({playlist_only}) {{ throw new Error("not found"); }}
+                var tracks = app.libraryPlaylists()[0].tracks.whose({{_or: [
+                    {{name: {{_contains: "{query}"}}}},
+                    {{artist: {{_contains: "{query}"}}}},
+                    {{album: {{_contains: "{query}"}}}},
+                    {{genre: {{_contains: "{query}"}}}}
+                ]}})();
+                if (tracks.length === 0) {{ throw new Error("not found"); }}
+                var existing = app.userPlaylists.whose({{name: "{mix}"}})();
+                var mix = existing.length > 0
+                    ? existing[0]
+                    : app.make({{new: "userPlaylist", withProperties: {{name: "{mix}"}}}});
+                mix.tracks().forEach(function(t) {{ t.delete(); }});
+                tracks.slice(0, 100).forEach(function(t) {{ t.duplicate({{to: mix}}); }});
+                app.shuffleEnabled = true;
+                mix.play();
+            }}
+            {JXA_NOW_PLAYING};
+            "#,
+            playlist_only = request.playlist_only,
+        );
+
+        let value = run_jxa(&script).map_err(|e| {
+            if e.contains("not found") {
+                MediaPlayerError::NotFound
+            } else {
+                MediaPlayerError::Backend(e)
+            }
+        })?;
+        parse_now_playing(&value)
+            .map_err(|e| MediaPlayerError::Backend(format!("parse error: {e}")))?
+            .ok_or(MediaPlayerError::NotFound)
+    }
+}
+
+fn parse_now_playing(v: &serde_json::Value) -> Result<Option<NowPlaying>, String> {
+    if v.is_null() {
+        return Ok(None);
+    }
+    let state = match v["state"].as_str().unwrap_or_default() {
+        "playing" | "fast forwarding" | "rewinding" => PlaybackState::Playing,
+        "paused" => PlaybackState::Paused,
+        "stopped" => PlaybackState::Stopped,
+        other => return Err(format!("unknown player state: {other}")),
+    };
+    Ok(Some(NowPlaying {
+        track: Track {
+            title: v["title"].as_str().unwrap_or_default().to_owned(),
+            artist: v["artist"].as_str().map(str::to_owned),
+            album: v["album"].as_str().map(str::to_owned),
+            genre: v["genre"].as_str().map(str::to_owned),
+        },
+        playlist: v["playlist"].as_str().map(str::to_owned),
+        state,
+    }))
+}
+
 // ─── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -1367,6 +1517,28 @@ mod tests {
         assert!(!mail.is_read);
     }
 
+    #[test]
+    fn parse_now_playing_from_json() {
+        let json = serde_json::json!({
+            "title": "So What",
+            "artist": "Miles Davis",
+            "album": "Kind of Blue",
+            "genre": "Jazz",
+            "playlist": null,
+            "state": "paused"
+        });
+        let now = parse_now_playing(&json).unwrap().unwrap();
+        assert_eq!(now.track.title, "So What");
+        assert_eq!(now.track.artist.as_deref(), Some("Miles Davis"));
+        assert_eq!(now.state, PlaybackState::Paused);
+        assert!(now.playlist.is_none());
+        assert!(
+            parse_now_playing(&serde_json::Value::Null)
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn stores_are_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -1375,5 +1547,6 @@ mod tests {
         assert_send_sync::<ApplescriptReminderStore>();
         assert_send_sync::<ApplescriptNoteStore>();
         assert_send_sync::<ApplescriptMailStore>();
+        assert_send_sync::<ApplescriptMediaPlayer>();
     }
 }
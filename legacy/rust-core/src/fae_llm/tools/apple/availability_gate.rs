@@ -11,7 +11,8 @@
 //! that is shared with the command handler.  When the handler grants or revokes
 //! a permission at runtime the change is immediately visible to every
 //! `AvailabilityGatedTool` that shares the same handle, with no restart
-//! required.
+//! required.  Read-only grants admit only tools that work in read-only mode,
+//! and expired grants count as not granted.
 //!
 //! ## JIT permission requests
 //!
@@ -20,6 +21,7 @@
 //! the native dialog awaits user response.  On grant the tool execution proceeds
 //! immediately; on deny a graceful failure is returned to the LLM.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -28,7 +30,10 @@ use tokio::sync::mpsc;
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
 use crate::fae_llm::tools::types::{Tool, ToolResult};
-use crate::permissions::{JitPermissionRequest, SharedPermissionStore};
+use crate::permission_usage::record_permission_use;
+use crate::permissions::{
+    JitPermissionRequest, PermissionAccess, PermissionKind, SharedPermissionStore,
+};
 
 use super::trait_def::AppleEcosystemTool;
 
@@ -57,6 +62,8 @@ pub struct AvailabilityGatedTool {
     /// to `JIT_TIMEOUT` (20 s) for a response before falling back to the
     /// standard "permission not granted" failure.
     jit_request_tx: Option<mpsc::UnboundedSender<JitPermissionRequest>>,
+    /// Ledger that records each execution allowed by a permission.
+    usage_ledger: Option<PathBuf>,
 }
 
 impl AvailabilityGatedTool {
@@ -71,6 +78,7 @@ impl AvailabilityGatedTool {
             inner,
             permissions,
             jit_request_tx: None,
+            usage_ledger: None,
         }
     }
 
@@ -84,6 +92,28 @@ impl AvailabilityGatedTool {
         self.jit_request_tx = Some(tx);
         self
     }
+
+    /// Record each permitted execution in the usage ledger at `path`
+    /// (see [`crate::permission_usage`]).
+    #[must_use]
+    pub fn with_usage_ledger(mut self, path: PathBuf) -> Self {
+        self.usage_ledger = Some(path);
+        self
+    }
+
+    /// Run the inner tool, recording the use of `kind` in the ledger.
+    fn execute_permitted(
+        &self,
+        kind: PermissionKind,
+        args: serde_json::Value,
+    ) -> Result<ToolResult, FaeLlmError> {
+        if let Some(path) = &self.usage_ledger
+            && let Err(e) = record_permission_use(path, kind, self.inner.name())
+        {
+            tracing::warn!("cannot record {kind} use by {}: {e}", self.inner.name());
+        }
+        self.inner.execute(args)
+    }
 }
 
 impl Tool for AvailabilityGatedTool {
@@ -102,6 +132,18 @@ impl Tool for AvailabilityGatedTool {
         self.inner.schema()
     }
 
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        self.inner.output_schema()
+    }
+
+    fn read_back_args(&self, args: &serde_json::Value) -> serde_json::Value {
+        self.inner.read_back_args(args)
+    }
+
+    fn is_side_effecting(&self) -> bool {
+        self.inner.is_side_effecting()
+    }
+
     /// Delegates to the inner tool's mode check.
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
         self.inner.allowed_in_mode(mode)
@@ -123,6 +165,13 @@ impl Tool for AvailabilityGatedTool {
     /// - The JIT response channel times out.
     fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
         let kind = self.inner.required_permission();
+        // Tools usable in read-only mode only read; the rest need a grant
+        // that is not limited to reading.
+        let access = if self.inner.allowed_in_mode(ToolMode::ReadOnly) {
+            PermissionAccess::Read
+        } else {
+            PermissionAccess::Write
+        };
 
         let check_granted = || {
             self.permissions
@@ -132,7 +181,19 @@ impl Tool for AvailabilityGatedTool {
         };
 
         if check_granted() {
-            return self.inner.execute(args);
+            let in_scope = self
+                .permissions
+                .lock()
+                .map(|guard| guard.allows(kind, access))
+                .unwrap_or(false);
+            if !in_scope {
+                return Ok(ToolResult::failure(format!(
+                    "Permission {kind} is granted with a narrower scope than `{}` needs. \
+                     Please grant full {kind} access to use this tool.",
+                    self.inner.name()
+                )));
+            }
+            return self.execute_permitted(kind, args);
         }
 
         // Permission not granted — attempt JIT request if channel is available.
@@ -164,10 +225,10 @@ impl Tool for AvailabilityGatedTool {
                         // User granted — re-check the store (handler should have applied the
                         // grant) and proceed.
                         if check_granted() {
-                            return self.inner.execute(args);
+                            return self.execute_permitted(kind, args);
                         }
                         // Store not yet updated — proceed optimistically.
-                        return self.inner.execute(args);
+                        return self.execute_permitted(kind, args);
                     }
                     Ok(false) => {
                         return Ok(ToolResult::failure(format!(
@@ -354,4 +415,73 @@ mod tests {
             elapsed
         );
     }
+
+    /// Mutating counterpart of [`MockTool`], refused in read-only mode.
+    struct MockWriteTool;
+
+    impl Tool for MockWriteTool {
+        fn name(&self) -> &str {
+            "mock_create_contact"
+        }
+
+        fn description(&self) -> &str {
+            "A mock contact creation tool"
+        }
+
+        fn schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object", "properties": {} })
+        }
+
+        fn execute(&self, _args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+            Ok(ToolResult::success("created".to_owned()))
+        }
+
+        fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+            mode == ToolMode::Full
+        }
+    }
+
+    impl AppleEcosystemTool for MockWriteTool {
+        fn required_permission(&self) -> PermissionKind {
+            PermissionKind::Contacts
+        }
+    }
+
+    #[test]
+    fn read_only_grant_allows_reads_and_blocks_writes() {
+        use crate::permissions::PermissionScope;
+
+        let mut store = PermissionStore::default();
+        store.grant_scoped(PermissionKind::Contacts, PermissionScope::ReadOnly, None);
+        let shared = store.into_shared();
+        let reader = AvailabilityGatedTool::new(Arc::new(MockTool), Arc::clone(&shared));
+        let writer = AvailabilityGatedTool::new(Arc::new(MockWriteTool), Arc::clone(&shared));
+
+        assert!(reader.execute(serde_json::json!({})).unwrap().success);
+        let result = writer.execute(serde_json::json!({})).unwrap();
+        assert!(!result.success);
+        let err = result.error.unwrap();
+        assert!(err.contains("narrower scope"), "unexpected error: {err}");
+
+        shared.lock().unwrap().grant(PermissionKind::Contacts);
+        assert!(writer.execute(serde_json::json!({})).unwrap().success);
+    }
+
+    #[test]
+    fn permitted_executions_are_recorded_in_ledger() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = dir.path().join("usage.jsonl");
+        let shared = PermissionStore::default_shared();
+        let tool = AvailabilityGatedTool::new(Arc::new(MockTool), Arc::clone(&shared))
+            .with_usage_ledger(ledger.clone());
+
+        assert!(!tool.execute(serde_json::json!({})).unwrap().success);
+        shared.lock().unwrap().grant(PermissionKind::Contacts);
+        assert!(tool.execute(serde_json::json!({})).unwrap().success);
+
+        let entries = crate::permission_usage::read_permission_usage(&ledger).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, PermissionKind::Contacts);
+        assert_eq!(entries[0].tool, "mock_contacts");
+    }
 }
@@ -15,9 +15,11 @@
 use std::fmt;
 use std::sync::Arc;
 
+use chrono::{DateTime, Local, NaiveDateTime};
+
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
-use crate::fae_llm::tools::types::{Tool, ToolResult};
+use crate::fae_llm::tools::types::{Clarification, ClarificationCandidate, Tool, ToolResult};
 use crate::permissions::PermissionKind;
 
 use super::trait_def::AppleEcosystemTool;
@@ -111,6 +113,16 @@ impl CalendarEvent {
     }
 }
 
+/// Parse an event time: RFC 3339 with an offset (converted to local time),
+/// or a local `YYYY-MM-DDTHH:MM[:SS]`.
+pub fn parse_event_time(iso: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(iso)
+        .map(|t| t.with_timezone(&Local).naive_local())
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(iso, "%Y-%m-%dT%H:%M:%S").ok())
+        .or_else(|| NaiveDateTime::parse_from_str(iso, "%Y-%m-%dT%H:%M").ok())
+}
+
 /// Query parameters for listing events.
 #[derive(Debug, Clone)]
 pub struct EventQuery {
@@ -540,6 +552,10 @@ impl Tool for CreateEventTool {
         )))
     }
 
+    fn is_side_effecting(&self) -> bool {
+        true
+    }
+
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
         matches!(mode, ToolMode::Full)
     }
@@ -707,9 +723,13 @@ impl AppleEcosystemTool for UpdateEventTool {
 /// Requires `ToolMode::Full`, Calendar permission, and `confirm: true` to
 /// prevent accidental deletion.
 ///
+/// An `identifier` that is not an event identifier is matched against event
+/// titles. A single match is deleted; several matches delete nothing and
+/// return a [`Clarification`] so the user can say which one they mean.
+///
 /// # Arguments (JSON)
 ///
-/// - `identifier` (string, required)
+/// - `identifier` (string, required) — event identifier or title
 /// - `confirm` (boolean, required) — must be `true`
 pub struct DeleteEventTool {
     store: Arc<dyn CalendarStore>,
@@ -720,6 +740,42 @@ impl DeleteEventTool {
     pub fn new(store: Arc<dyn CalendarStore>) -> Self {
         Self { store }
     }
+
+    /// Events a title-like `identifier` could mean, or `None` when it is an
+    /// event identifier (or matches nothing) and should be used as given.
+    fn title_matches(&self, identifier: &str) -> Option<Vec<CalendarEvent>> {
+        let events = self
+            .store
+            .list_events(&EventQuery {
+                calendar_ids: Vec::new(),
+                start_after: None,
+                end_before: None,
+                limit: TITLE_MATCH_SCAN_LIMIT,
+            })
+            .ok()?;
+        if events.iter().any(|e| e.identifier == identifier) {
+            return None;
+        }
+        let wanted = identifier.to_lowercase();
+        let matches: Vec<CalendarEvent> = events
+            .into_iter()
+            .filter(|e| e.title.to_lowercase().contains(&wanted))
+            .collect();
+        (!matches.is_empty()).then_some(matches)
+    }
+}
+
+/// How many events are searched when deleting by title.
+const TITLE_MATCH_SCAN_LIMIT: usize = 500;
+
+/// How an event is told apart from others with the same title.
+fn event_label(event: &CalendarEvent) -> String {
+    let (date, time) = event.start.split_once('T').unwrap_or((&event.start, ""));
+    if event.is_all_day || time.len() < 5 {
+        format!("{}, {date}", event.title)
+    } else {
+        format!("{}, {date} {}", event.title, &time[..5])
+    }
 }
 
 impl Tool for DeleteEventTool {
@@ -739,7 +795,7 @@ impl Tool for DeleteEventTool {
             "properties": {
                 "identifier": {
                     "type": "string",
-                    "description": "Event identifier from list_calendar_events (required)"
+                    "description": "Event identifier from list_calendar_events, or the event title (required)"
                 },
                 "confirm": {
                     "type": "boolean",
@@ -769,6 +825,27 @@ impl Tool for DeleteEventTool {
             ));
         }
 
+        let mut identifier = identifier;
+        match self.title_matches(&identifier) {
+            Some(mut matches) if matches.len() == 1 => {
+                identifier = matches.remove(0).identifier;
+            }
+            Some(matches) => {
+                let candidates = matches
+                    .iter()
+                    .map(|event| ClarificationCandidate {
+                        label: event_label(event),
+                        value: event.identifier.clone(),
+                    })
+                    .collect();
+                return Ok(ToolResult::clarify(Clarification::new(
+                    "identifier",
+                    candidates,
+                )));
+            }
+            None => {}
+        }
+
         self.store.delete_event(&identifier).map_err(|e| match e {
             CalendarStoreError::NotFound => FaeLlmError::ToolExecutionError(format!(
                 "no event found with identifier \"{identifier}\""
@@ -1148,4 +1225,59 @@ mod tests {
         assert!(!result.success);
         assert!(result.error.as_deref().unwrap_or("").contains("identifier"));
     }
+
+    #[test]
+    fn delete_by_ambiguous_title_asks_which_one() {
+        let meeting = |id: &str, title: &str, start: &str| CalendarEvent {
+            identifier: id.to_owned(),
+            calendar_id: "cal-work".to_owned(),
+            title: title.to_owned(),
+            start: start.to_owned(),
+            end: start.to_owned(),
+            location: None,
+            notes: None,
+            is_all_day: false,
+            alarms: vec![],
+        };
+        let mut events = sample_events();
+        events.push(meeting("evt-010", "Team meeting", "2026-03-02T10:00:00"));
+        events.push(meeting("evt-011", "Client meeting", "2026-03-04T15:30:00"));
+        let store = Arc::new(MockCalendarStore::new(sample_calendars(), events));
+        let tool = DeleteEventTool::new(store.clone());
+
+        let result = tool
+            .execute(serde_json::json!({"identifier": "meeting", "confirm": true}))
+            .unwrap();
+        assert!(!result.success);
+        let clarification = result.clarification.unwrap();
+        assert_eq!(clarification.argument, "identifier");
+        let labels: Vec<&str> = clarification
+            .candidates
+            .iter()
+            .map(|c| c.label.as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Team meeting, 2026-03-02 10:00",
+                "Client meeting, 2026-03-04 15:30"
+            ]
+        );
+        let remaining = store
+            .list_events(&EventQuery {
+                calendar_ids: vec![],
+                start_after: None,
+                end_before: None,
+                limit: 10,
+            })
+            .unwrap();
+        assert_eq!(remaining.len(), 4, "nothing is deleted while ambiguous");
+
+        // A unique title is deleted directly.
+        let result = tool
+            .execute(serde_json::json!({"identifier": "client meeting", "confirm": true}))
+            .unwrap();
+        assert!(result.success, "content: {}", result.content);
+        assert!(result.content.contains("evt-011"));
+    }
 }
@@ -453,6 +453,10 @@ impl Tool for CreateContactTool {
         )))
     }
 
+    fn is_side_effecting(&self) -> bool {
+        true
+    }
+
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
         matches!(mode, ToolMode::Full)
     }
@@ -26,6 +26,7 @@ static CALENDAR_STORE: OnceLock<Arc<dyn CalendarStore>> = OnceLock::new();
 static REMINDER_STORE: OnceLock<Arc<dyn ReminderStore>> = OnceLock::new();
 static NOTE_STORE: OnceLock<Arc<dyn NoteStore>> = OnceLock::new();
 static MAIL_STORE: OnceLock<Arc<dyn MailStore>> = OnceLock::new();
+static MEDIA_PLAYER: OnceLock<Arc<dyn MediaPlayer>> = OnceLock::new();
 
 // ─── Registration functions ──────────────────────────────────────────────────
 
@@ -56,12 +57,18 @@ pub fn register_mail_store(store: Arc<dyn MailStore>) {
     let _ = MAIL_STORE.set(store);
 }
 
+/// Register the global media player implementation.
+pub fn register_media_player(player: Arc<dyn MediaPlayer>) {
+    let _ = MEDIA_PLAYER.set(player);
+}
+
 use super::calendar::{
     CalendarEvent, CalendarInfo, CalendarStore, CalendarStoreError, EventPatch, EventQuery,
     NewCalendarEvent,
 };
 use super::contacts::{Contact, ContactQuery, ContactStore, ContactStoreError, NewContact};
 use super::mail::{Mail, MailQuery, MailStore, MailStoreError, NewMail};
+use super::media::{MediaCommand, MediaPlayer, MediaPlayerError, NowPlaying, PlayRequest};
 use super::notes::{NewNote, Note, NoteQuery, NoteStore, NoteStoreError};
 use super::reminders::{
     NewReminder, Reminder, ReminderList, ReminderQuery, ReminderStore, ReminderStoreError,
@@ -345,6 +352,51 @@ pub fn global_mail_store() -> Arc<dyn MailStore> {
         .unwrap_or_else(|| Arc::new(UnregisteredMailStore))
 }
 
+// ─── UnregisteredMediaPlayer ──────────────────────────────────────────────────
+
+/// A no-op [`MediaPlayer`] used before a real implementation is registered.
+///
+/// All operations return [`MediaPlayerError::PermissionDenied`] with a
+/// diagnostic message.
+pub struct UnregisteredMediaPlayer;
+
+impl MediaPlayer for UnregisteredMediaPlayer {
+    fn now_playing(&self) -> Result<Option<NowPlaying>, MediaPlayerError> {
+        Err(MediaPlayerError::PermissionDenied(
+            "Apple Music player not initialized. \
+             The app must be running on macOS with Desktop Automation permission granted."
+                .to_owned(),
+        ))
+    }
+
+    fn control(&self, _command: MediaCommand) -> Result<Option<NowPlaying>, MediaPlayerError> {
+        Err(MediaPlayerError::PermissionDenied(
+            "Apple Music player not initialized. \
+             The app must be running on macOS with Desktop Automation permission granted."
+                .to_owned(),
+        ))
+    }
+
+    fn play(&self, _request: &PlayRequest) -> Result<NowPlaying, MediaPlayerError> {
+        Err(MediaPlayerError::PermissionDenied(
+            "Apple Music player not initialized. \
+             The app must be running on macOS with Desktop Automation permission granted."
+                .to_owned(),
+        ))
+    }
+}
+
+/// Returns the global media player.
+///
+/// Returns the registered implementation if `register_media_player()` was
+/// called, otherwise falls back to `UnregisteredMediaPlayer`.
+pub fn global_media_player() -> Arc<dyn MediaPlayer> {
+    MEDIA_PLAYER
+        .get()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(UnregisteredMediaPlayer))
+}
+
 // ─── Tests ────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -511,6 +563,7 @@ mod tests {
             notes: None,
             due_date: None,
             priority: None,
+            recurrence: None,
         };
         let err = store.create_reminder(&reminder);
         assert!(err.is_err());
@@ -637,4 +690,23 @@ mod tests {
         let result = store.list_messages(&query);
         assert!(result.is_err());
     }
+
+    // ── UnregisteredMediaPlayer ───────────────────────────────────────────────
+
+    #[test]
+    fn unregistered_media_player_returns_permission_denied() {
+        let player = UnregisteredMediaPlayer;
+        let err = player.control(MediaCommand::Pause);
+        assert!(err.err().unwrap().to_string().contains("not initialized"));
+        let err = player.play(&PlayRequest {
+            query: "jazz".to_owned(),
+            playlist_only: false,
+        });
+        assert!(err.err().unwrap().to_string().contains("not initialized"));
+    }
+
+    #[test]
+    fn global_media_player_returns_unregistered() {
+        assert!(global_media_player().now_playing().is_err());
+    }
 }
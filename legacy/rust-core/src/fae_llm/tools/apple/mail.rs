@@ -4,7 +4,8 @@
 //!
 //! - [`SearchMailTool`] — search inbox messages by query (read-only)
 //! - [`GetMailTool`] — read a full email message by identifier (read-only)
-//! - [`ComposeMailTool`] — compose and send a new email (write, Full mode)
+//! - [`ComposeMailTool`] — draft, revise and, on confirmation, send an email
+//!   (write, Full mode)
 //!
 //! The store trait is implemented by:
 //! - `UnregisteredMailStore` in [`super::ffi_bridge`] for production before the
@@ -21,6 +22,7 @@ use crate::fae_llm::error::FaeLlmError;
 use crate::fae_llm::tools::types::{Tool, ToolResult};
 use crate::permissions::PermissionKind;
 
+use super::mail_drafts::{MailDraft, MailDraftStore};
 use super::trait_def::AppleEcosystemTool;
 
 // ─── Domain types ─────────────────────────────────────────────────────────────
@@ -361,55 +363,205 @@ impl AppleEcosystemTool for GetMailTool {
 
 // ─── ComposeMailTool ──────────────────────────────────────────────────────────
 
-/// Write tool that composes and sends an email via the user's Mail app.
+/// Write tool that drafts, revises and sends email via the user's Mail app.
+///
+/// Email goes out in stages so nothing is sent before the user has heard it:
+/// `draft` saves a new draft, `revise` changes it as often as the user likes,
+/// and only `send` — the user's explicit confirmation — sends it. Drafts are
+/// kept in a [`MailDraftStore`] and survive restarts.
 ///
 /// Requires `ToolMode::Full` and the Mail permission.
 ///
 /// # Arguments (JSON)
 ///
-/// - `to` (string, required) — recipient address(es), comma-separated
-/// - `subject` (string, required) — message subject
-/// - `body` (string, required) — plain-text message body
+/// - `action` (string, optional) — `draft` (default), `revise`, `send`, `discard` or `list`
+/// - `draft_id` (string, optional) — draft to revise, send or discard; defaults
+///   to the most recently changed draft
+/// - `to` (string) — recipient address(es), comma-separated; required for `draft`
+/// - `subject` (string) — message subject; required for `draft`
+/// - `body` (string) — plain-text message body; required for `draft`
 /// - `cc` (string, optional) — CC recipients, comma-separated
 pub struct ComposeMailTool {
     store: Arc<dyn MailStore>,
+    drafts: Arc<MailDraftStore>,
 }
 
 impl ComposeMailTool {
-    /// Create a new `ComposeMailTool` backed by `store`.
+    /// Create a new `ComposeMailTool` backed by `store`, keeping drafts in
+    /// the default location.
     pub fn new(store: Arc<dyn MailStore>) -> Self {
-        Self { store }
+        Self {
+            store,
+            drafts: Arc::new(MailDraftStore::default_location()),
+        }
+    }
+
+    /// Keep drafts in `drafts` instead.
+    pub fn with_drafts(mut self, drafts: Arc<MailDraftStore>) -> Self {
+        self.drafts = drafts;
+        self
+    }
+
+    fn draft(&self, args: &serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let required = |name: &str| {
+            text_arg(args, name).ok_or_else(|| format!("{name} is required and cannot be empty"))
+        };
+        let (to, subject, body) = match (required("to"), required("subject"), required("body")) {
+            (Ok(to), Ok(subject), Ok(body)) => (to, subject, body),
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+                return Ok(ToolResult::failure(e));
+            }
+        };
+        let draft = MailDraft::new(NewMail {
+            to,
+            subject,
+            body,
+            cc: text_arg(args, "cc"),
+        });
+        self.drafts.save(&draft)?;
+        Ok(ToolResult::success(format!(
+            "Draft saved, not sent. Read it to the user and ask whether to change or send it.\n{}",
+            draft.format()
+        )))
+    }
+
+    fn revise(&self, args: &serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let Some(mut draft) = self.drafts.get(draft_id(args))? else {
+            return Ok(missing_draft(args));
+        };
+        let mut changed = false;
+        for (name, field) in [
+            ("to", &mut draft.to),
+            ("subject", &mut draft.subject),
+            ("body", &mut draft.body),
+        ] {
+            if let Some(value) = text_arg(args, name) {
+                *field = value;
+                changed = true;
+            }
+        }
+        if args.get("cc").is_some() {
+            // An empty cc clears it.
+            draft.cc = text_arg(args, "cc");
+            changed = true;
+        }
+        if !changed {
+            return Ok(ToolResult::failure(
+                "revise needs at least one of to, subject, body or cc".to_owned(),
+            ));
+        }
+        draft.updated_at = crate::time_util::now_epoch_secs();
+        self.drafts.save(&draft)?;
+        Ok(ToolResult::success(format!(
+            "Draft updated, not sent. Read the changes to the user and ask whether to send it.\n{}",
+            draft.format()
+        )))
+    }
+
+    fn send(&self, args: &serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let Some(draft) = self.drafts.get(draft_id(args))? else {
+            return Ok(missing_draft(args));
+        };
+        let sent = self
+            .store
+            .compose(&draft.to_new_mail())
+            .map_err(|e| FaeLlmError::ToolExecutionError(format!("failed to send email: {e}")))?;
+        // The mail is gone either way; a stale draft is only clutter.
+        if let Err(e) = self.drafts.remove(&draft.id) {
+            tracing::warn!("cannot remove sent draft {}: {e}", draft.id);
+        }
+        Ok(ToolResult::success(format!(
+            "Email sent successfully.\n{}",
+            sent.format_summary()
+        )))
+    }
+
+    fn discard(&self, args: &serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let Some(draft) = self.drafts.get(draft_id(args))? else {
+            return Ok(missing_draft(args));
+        };
+        self.drafts.remove(&draft.id)?;
+        Ok(ToolResult::success(format!(
+            "Draft {} discarded.",
+            draft.id
+        )))
+    }
+
+    fn list(&self) -> Result<ToolResult, FaeLlmError> {
+        let drafts = self.drafts.list()?;
+        if drafts.is_empty() {
+            return Ok(ToolResult::success("No saved drafts.".to_owned()));
+        }
+        let formatted: Vec<String> = drafts.iter().map(MailDraft::format).collect();
+        Ok(ToolResult::success(format!(
+            "{} saved draft(s), most recent first:\n\n{}",
+            drafts.len(),
+            formatted.join("\n\n")
+        )))
     }
 }
 
+/// Trimmed, non-empty string argument `name`.
+fn text_arg(args: &serde_json::Value, name: &str) -> Option<String> {
+    args.get(name)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+}
+
+fn draft_id(args: &serde_json::Value) -> Option<&str> {
+    args.get("draft_id")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn missing_draft(args: &serde_json::Value) -> ToolResult {
+    ToolResult::failure(match draft_id(args) {
+        Some(id) => format!("no draft with id {id}; use action list to see saved drafts"),
+        None => "there is no saved draft; create one with action draft first".to_owned(),
+    })
+}
+
 impl Tool for ComposeMailTool {
     fn name(&self) -> &str {
         "compose_mail"
     }
 
     fn description(&self) -> &str {
-        "Compose and send an email via the user's Mail app. \
-         Requires recipient address, subject, and body. \
-         Optionally add CC recipients. \
-         Returns a confirmation with the sent message details."
+        "Draft, revise and send email via the user's Mail app. Sending is staged: \
+         action \"draft\" (the default) saves a draft from to, subject, body and optional cc — \
+         read it to the user. Use \"revise\" to change a saved draft when the user asks \
+         (for \"make it friendlier\", rewrite the body). Only use \"send\" once the user \
+         explicitly confirms; it sends the saved draft exactly as it is. \"discard\" deletes a \
+         draft and \"list\" shows saved drafts. draft_id defaults to the most recent draft."
     }
 
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
-            "required": ["to", "subject", "body"],
             "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["draft", "revise", "send", "discard", "list"],
+                    "description": "What to do (default: draft)"
+                },
+                "draft_id": {
+                    "type": "string",
+                    "description": "Draft to revise, send or discard (default: the most recent draft)"
+                },
                 "to": {
                     "type": "string",
-                    "description": "Recipient email address(es), comma-separated for multiple"
+                    "description": "Recipient email address(es), comma-separated for multiple (required for draft)"
                 },
                 "subject": {
                     "type": "string",
-                    "description": "Email subject line (required)"
+                    "description": "Email subject line (required for draft)"
                 },
                 "body": {
                     "type": "string",
-                    "description": "Plain-text message body (required)"
+                    "description": "Plain-text message body (required for draft)"
                 },
                 "cc": {
                     "type": "string",
@@ -419,56 +571,39 @@ impl Tool for ComposeMailTool {
         })
     }
 
-    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
-        let to = match args.get("to").and_then(|v| v.as_str()) {
-            Some(t) if !t.trim().is_empty() => t.trim().to_owned(),
-            _ => {
-                return Ok(ToolResult::failure(
-                    "to is required and cannot be empty".to_owned(),
-                ));
-            }
-        };
-
-        let subject = match args.get("subject").and_then(|v| v.as_str()) {
-            Some(s) if !s.trim().is_empty() => s.trim().to_owned(),
-            _ => {
-                return Ok(ToolResult::failure(
-                    "subject is required and cannot be empty".to_owned(),
-                ));
-            }
-        };
-
-        let body = match args.get("body").and_then(|v| v.as_str()) {
-            Some(b) if !b.trim().is_empty() => b.trim().to_owned(),
-            _ => {
-                return Ok(ToolResult::failure(
-                    "body is required and cannot be empty".to_owned(),
-                ));
-            }
-        };
+    fn read_back_args(&self, args: &serde_json::Value) -> serde_json::Value {
+        match self.drafts.get(draft_id(args)) {
+            Ok(Some(draft)) => serde_json::json!({
+                "action": "send",
+                "draft_id": draft.id,
+                "to": draft.to,
+                "subject": draft.subject,
+                "body": draft.body,
+                "cc": draft.cc,
+            }),
+            _ => args.clone(),
+        }
+    }
 
-        let cc = args
-            .get("cc")
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        match args
+            .get("action")
             .and_then(|v| v.as_str())
-            .filter(|s| !s.trim().is_empty())
-            .map(str::to_owned);
-
-        let new_mail = NewMail {
-            to,
-            subject,
-            body,
-            cc,
-        };
-
-        let sent = self
-            .store
-            .compose(&new_mail)
-            .map_err(|e| FaeLlmError::ToolExecutionError(format!("failed to send email: {e}")))?;
+            .unwrap_or("draft")
+        {
+            "draft" => self.draft(&args),
+            "revise" => self.revise(&args),
+            "send" => self.send(&args),
+            "discard" => self.discard(&args),
+            "list" => self.list(),
+            other => Ok(ToolResult::failure(format!(
+                "unknown action '{other}'; use draft, revise, send, discard or list"
+            ))),
+        }
+    }
 
-        Ok(ToolResult::success(format!(
-            "Email sent successfully.\n{}",
-            sent.format_summary()
-        )))
+    fn is_side_effecting(&self) -> bool {
+        true
     }
 
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
@@ -538,9 +673,14 @@ mod tests {
         GetMailTool::new(store)
     }
 
-    fn make_compose_tool() -> ComposeMailTool {
+    fn make_compose_tool() -> (ComposeMailTool, tempfile::TempDir) {
         let store = Arc::new(MockMailStore::new(vec![]));
-        ComposeMailTool::new(store)
+        let dir = tempfile::tempdir().expect("tempdir");
+        let drafts = MailDraftStore::new(dir.path().join("mail_drafts.json"));
+        (
+            ComposeMailTool::new(store).with_drafts(Arc::new(drafts)),
+            dir,
+        )
     }
 
     // ── SearchMailTool ────────────────────────────────────────────────────────
@@ -689,8 +829,8 @@ mod tests {
     // ── ComposeMailTool ───────────────────────────────────────────────────────
 
     #[test]
-    fn compose_mail_minimal_succeeds() {
-        let tool = make_compose_tool();
+    fn compose_mail_minimal_saves_a_draft() {
+        let (tool, _dir) = make_compose_tool();
         let result = tool.execute(serde_json::json!({
             "to": "alice@example.com",
             "subject": "Hello",
@@ -701,13 +841,69 @@ mod tests {
             Err(_) => unreachable!("compose should succeed"),
         };
         assert!(result.success);
-        assert!(result.content.contains("sent successfully"));
+        assert!(result.content.contains("not sent"));
         assert!(result.content.contains("Hello"));
     }
 
+    #[test]
+    fn compose_mail_sends_the_revised_draft_only_on_send() {
+        let mail = Arc::new(MockMailStore::new(vec![]));
+        let dir = tempfile::tempdir().expect("tempdir");
+        let drafts = Arc::new(MailDraftStore::new(dir.path().join("mail_drafts.json")));
+        let tool = ComposeMailTool::new(Arc::clone(&mail) as Arc<dyn MailStore>)
+            .with_drafts(Arc::clone(&drafts));
+        let sent = || {
+            mail.list_messages(&MailQuery {
+                search: None,
+                mailbox: Some("Sent".to_owned()),
+                unread_only: false,
+                limit: 10,
+            })
+            .expect("list")
+        };
+
+        tool.execute(serde_json::json!({
+            "to": "alice@example.com",
+            "subject": "Friday",
+            "body": "Meeting moved to 3pm."
+        }))
+        .expect("draft");
+        let result = tool
+            .execute(serde_json::json!({
+                "action": "revise",
+                "body": "Hi Alice! Hope your week is going well. We moved Friday's meeting to 3pm."
+            }))
+            .expect("revise");
+        assert!(result.success);
+        assert!(sent().is_empty());
+
+        // A new tool over the same drafts file, as after a restart.
+        let restarted = ComposeMailTool::new(Arc::clone(&mail) as Arc<dyn MailStore>)
+            .with_drafts(Arc::new(MailDraftStore::new(drafts.path().to_path_buf())));
+        let send = serde_json::json!({"action": "send"});
+        let read_back = restarted.read_back_args(&send);
+        assert_eq!(read_back["to"], "alice@example.com");
+        assert!(
+            read_back["body"]
+                .as_str()
+                .unwrap_or("")
+                .starts_with("Hi Alice!")
+        );
+
+        let result = restarted.execute(send.clone()).expect("send");
+        assert!(result.content.contains("sent successfully"));
+        let sent = sent();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].body.starts_with("Hi Alice!"));
+        assert!(drafts.list().expect("list").is_empty());
+
+        let again = restarted.execute(send).expect("send again");
+        assert!(!again.success);
+    }
+
     #[test]
     fn compose_mail_with_cc_succeeds() {
-        let tool = make_compose_tool();
+        let (tool, _dir) = make_compose_tool();
         let result = tool.execute(serde_json::json!({
             "to": "alice@example.com",
             "subject": "Team update",
@@ -724,7 +920,7 @@ mod tests {
 
     #[test]
     fn compose_mail_empty_to_returns_failure() {
-        let tool = make_compose_tool();
+        let (tool, _dir) = make_compose_tool();
         let result = tool.execute(serde_json::json!({
             "to": "  ",
             "subject": "Hello",
@@ -740,7 +936,7 @@ mod tests {
 
     #[test]
     fn compose_mail_empty_subject_returns_failure() {
-        let tool = make_compose_tool();
+        let (tool, _dir) = make_compose_tool();
         let result = tool.execute(serde_json::json!({
             "to": "alice@example.com",
             "subject": "",
@@ -756,7 +952,7 @@ mod tests {
 
     #[test]
     fn compose_mail_empty_body_returns_failure() {
-        let tool = make_compose_tool();
+        let (tool, _dir) = make_compose_tool();
         let result = tool.execute(serde_json::json!({
             "to": "alice@example.com",
             "subject": "Hello",
@@ -772,14 +968,14 @@ mod tests {
 
     #[test]
     fn compose_mail_only_full_mode() {
-        let tool = make_compose_tool();
+        let (tool, _dir) = make_compose_tool();
         assert!(!tool.allowed_in_mode(ToolMode::ReadOnly));
         assert!(tool.allowed_in_mode(ToolMode::Full));
     }
 
     #[test]
     fn compose_mail_requires_mail_permission() {
-        let tool = make_compose_tool();
+        let (tool, _dir) = make_compose_tool();
         let mut store = PermissionStore::default();
         assert!(!tool.is_available(&store));
         store.grant(PermissionKind::Mail);
@@ -0,0 +1,202 @@
+//! Saved email drafts for [`ComposeMailTool`](super::mail::ComposeMailTool).
+//!
+//! Email goes out in stages: a draft is saved, revised as often as the user
+//! likes ("make it friendlier"), and only sent on an explicit confirmation.
+//! Drafts live in one JSON file under the data directory, so a draft started
+//! before a restart can still be revised or sent after it.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::mail::{MailStoreError, NewMail};
+
+/// Serializes read-modify-write cycles on the drafts file.
+static DRAFTS_LOCK: Mutex<()> = Mutex::new(());
+
+/// An unsent email.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MailDraft {
+    /// Short identifier the model refers to the draft by.
+    pub id: String,
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cc: Option<String>,
+    /// Unix epoch seconds of the last change.
+    pub updated_at: u64,
+}
+
+impl MailDraft {
+    /// A new draft with a fresh id.
+    pub fn new(mail: NewMail) -> Self {
+        let uuid = uuid::Uuid::new_v4().simple().to_string();
+        Self {
+            id: format!("draft-{}", &uuid[..8]),
+            to: mail.to,
+            subject: mail.subject,
+            body: mail.body,
+            cc: mail.cc,
+            updated_at: crate::time_util::now_epoch_secs(),
+        }
+    }
+
+    /// The email this draft would send.
+    pub fn to_new_mail(&self) -> NewMail {
+        NewMail {
+            to: self.to.clone(),
+            subject: self.subject.clone(),
+            body: self.body.clone(),
+            cc: self.cc.clone(),
+        }
+    }
+
+    /// Format the draft for the model to read back to the user.
+    pub fn format(&self) -> String {
+        let mut parts = vec![format!("[draft: {}]", self.id), format!("To: {}", self.to)];
+        if let Some(ref cc) = self.cc {
+            parts.push(format!("Cc: {cc}"));
+        }
+        parts.push(format!("Subject: {}", self.subject));
+        parts.push(String::new());
+        parts.push(self.body.clone());
+        parts.join("\n")
+    }
+}
+
+/// On-disk store of unsent drafts.
+#[derive(Debug, Clone)]
+pub struct MailDraftStore {
+    path: PathBuf,
+}
+
+impl MailDraftStore {
+    /// Keep drafts in the JSON file at `path`, created on first save.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// The store at `data_dir()/mail_drafts.json`.
+    pub fn default_location() -> Self {
+        Self::new(crate::fae_dirs::mail_drafts_file())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All drafts, most recently changed first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MailStoreError::Backend`] if the drafts file cannot be read.
+    pub fn list(&self) -> Result<Vec<MailDraft>, MailStoreError> {
+        let _guard = DRAFTS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut drafts = self.load()?;
+        // Newer drafts are appended, so reversing first breaks ties.
+        drafts.reverse();
+        drafts.sort_by_key(|d| std::cmp::Reverse(d.updated_at));
+        Ok(drafts)
+    }
+
+    /// Draft `id`, or the most recently changed draft when `id` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MailStoreError::Backend`] if the drafts file cannot be read.
+    pub fn get(&self, id: Option<&str>) -> Result<Option<MailDraft>, MailStoreError> {
+        let drafts = self.list()?;
+        Ok(match id {
+            Some(id) => drafts.into_iter().find(|d| d.id == id),
+            None => drafts.into_iter().next(),
+        })
+    }
+
+    /// Save `draft`, replacing any draft with the same id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MailStoreError::Backend`] if the drafts file cannot be written.
+    pub fn save(&self, draft: &MailDraft) -> Result<(), MailStoreError> {
+        let _guard = DRAFTS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut drafts = self.load()?;
+        match drafts.iter_mut().find(|d| d.id == draft.id) {
+            Some(existing) => *existing = draft.clone(),
+            None => drafts.push(draft.clone()),
+        }
+        self.store(&drafts)
+    }
+
+    /// Remove draft `id`, returning it if it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MailStoreError::Backend`] if the drafts file cannot be
+    /// read or written.
+    pub fn remove(&self, id: &str) -> Result<Option<MailDraft>, MailStoreError> {
+        let _guard = DRAFTS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut drafts = self.load()?;
+        let Some(index) = drafts.iter().position(|d| d.id == id) else {
+            return Ok(None);
+        };
+        let removed = drafts.remove(index);
+        self.store(&drafts)?;
+        Ok(Some(removed))
+    }
+
+    fn load(&self) -> Result<Vec<MailDraft>, MailStoreError> {
+        match std::fs::read(&self.path) {
+            Ok(data) => serde_json::from_slice(&data)
+                .map_err(|e| MailStoreError::Backend(format!("drafts file is malformed: {e}"))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(MailStoreError::Backend(format!("cannot read drafts: {e}"))),
+        }
+    }
+
+    fn store(&self, drafts: &[MailDraft]) -> Result<(), MailStoreError> {
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let json = serde_json::to_string_pretty(drafts).map_err(std::io::Error::other)?;
+            crate::fae_llm::tools::edit::write_atomic(&self.path, &json)
+        };
+        write().map_err(|e| MailStoreError::Backend(format!("cannot save drafts: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn drafts_survive_reopening_the_store() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("fae/mail_drafts.json");
+        let store = MailDraftStore::new(path.clone());
+        assert!(store.get(None).expect("get").is_none());
+
+        let mut draft = MailDraft::new(NewMail {
+            to: "alice@example.com".to_owned(),
+            subject: "Lunch".to_owned(),
+            body: "Lunch on Friday?".to_owned(),
+            cc: None,
+        });
+        store.save(&draft).expect("save");
+        draft.body = "Fancy lunch on Friday?".to_owned();
+        store.save(&draft).expect("revise");
+
+        let reopened = MailDraftStore::new(path);
+        let drafts = reopened.list().expect("list");
+        assert_eq!(drafts, vec![draft.clone()]);
+        assert_eq!(reopened.get(None).expect("get"), Some(draft.clone()));
+
+        assert_eq!(reopened.remove(&draft.id).expect("remove"), Some(draft));
+        assert!(reopened.list().expect("list").is_empty());
+        assert!(reopened.remove("draft-missing").expect("remove").is_none());
+    }
+}
@@ -0,0 +1,506 @@
+//! Media playback tools for Fae's Apple ecosystem integration.
+//!
+//! Provides three LLM tools backed by a [`MediaPlayer`] abstraction:
+//!
+//! - [`NowPlayingTool`] — report what is playing (read-only)
+//! - [`MediaControlTool`] — play, pause, skip, or go back (Full mode)
+//! - [`PlayMusicTool`] — play a named playlist or music matching a search,
+//!   such as a genre or artist (Full mode)
+//!
+//! The player trait is implemented by:
+//! - `ApplescriptMediaPlayer` in [`super::applescript`] for production
+//! - `UnregisteredMediaPlayer` in [`super::ffi_bridge`] before registration
+//! - `MockMediaPlayer` in [`super::mock_stores`] for unit tests
+//!
+//! Media control requires [`PermissionKind::DesktopAutomation`] because the
+//! production implementation uses AppleScript to drive Music.app.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::tools::types::{Tool, ToolResult};
+use crate::permissions::PermissionKind;
+
+use super::trait_def::AppleEcosystemTool;
+
+// ─── Domain types ─────────────────────────────────────────────────────────────
+
+/// A track in the user's music library.
+#[derive(Debug, Clone)]
+pub struct Track {
+    /// Track title.
+    pub title: String,
+    /// Performing artist, if known.
+    pub artist: Option<String>,
+    /// Album the track belongs to, if known.
+    pub album: Option<String>,
+    /// Genre, if tagged.
+    pub genre: Option<String>,
+}
+
+/// Whether the player is currently producing sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackState {
+    /// A track is playing.
+    Playing,
+    /// A track is loaded but paused.
+    Paused,
+    /// Nothing is loaded.
+    Stopped,
+}
+
+/// The player's current track and state.
+#[derive(Debug, Clone)]
+pub struct NowPlaying {
+    /// The current track.
+    pub track: Track,
+    /// Playlist the track is being played from, if any.
+    pub playlist: Option<String>,
+    /// Playing or paused.
+    pub state: PlaybackState,
+}
+
+impl NowPlaying {
+    /// Format as a one-line summary, e.g.
+    /// `Now playing: So What by Miles Davis from Kind of Blue`.
+    pub fn format_summary(&self) -> String {
+        let state = match self.state {
+            PlaybackState::Playing => "Now playing",
+            PlaybackState::Paused => "Paused",
+            PlaybackState::Stopped => "Stopped",
+        };
+        let mut summary = format!("{state}: {}", self.track.title);
+        if let Some(ref artist) = self.track.artist {
+            summary.push_str(&format!(" by {artist}"));
+        }
+        if let Some(ref album) = self.track.album {
+            summary.push_str(&format!(" from {album}"));
+        }
+        if let Some(ref playlist) = self.playlist {
+            summary.push_str(&format!(" (playlist: {playlist})"));
+        }
+        summary
+    }
+}
+
+/// A transport command for the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaCommand {
+    /// Start or resume playback.
+    Play,
+    /// Pause playback.
+    Pause,
+    /// Skip to the next track.
+    Next,
+    /// Go back to the previous track.
+    Previous,
+}
+
+impl MediaCommand {
+    /// Parse the `action` argument of [`MediaControlTool`].
+    pub fn parse(action: &str) -> Option<Self> {
+        match action.trim().to_ascii_lowercase().as_str() {
+            "play" | "resume" => Some(Self::Play),
+            "pause" | "stop" => Some(Self::Pause),
+            "next" | "skip" => Some(Self::Next),
+            "previous" | "back" => Some(Self::Previous),
+            _ => None,
+        }
+    }
+
+    fn confirmation(self) -> &'static str {
+        match self {
+            Self::Play => "Playback resumed.",
+            Self::Pause => "Playback paused.",
+            Self::Next => "Skipped to the next track.",
+            Self::Previous => "Went back to the previous track.",
+        }
+    }
+}
+
+/// What [`MediaPlayer::play`] should start.
+#[derive(Debug, Clone)]
+pub struct PlayRequest {
+    /// Playlist name, or words matched against track title, artist, album
+    /// and genre.
+    pub query: String,
+    /// Only match playlist names.
+    pub playlist_only: bool,
+}
+
+/// Error type for media player operations.
+#[derive(Debug, Clone)]
+pub enum MediaPlayerError {
+    /// macOS permission not granted or player not initialized.
+    PermissionDenied(String),
+    /// Nothing in the library matches the request.
+    NotFound,
+    /// Invalid input supplied by the caller.
+    InvalidInput(String),
+    /// Unexpected error from the underlying player.
+    Backend(String),
+}
+
+impl fmt::Display for MediaPlayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MediaPlayerError::PermissionDenied(msg) => write!(f, "permission denied: {msg}"),
+            MediaPlayerError::NotFound => write!(f, "no matching music found"),
+            MediaPlayerError::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+            MediaPlayerError::Backend(msg) => write!(f, "player error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for MediaPlayerError {}
+
+impl From<MediaPlayerError> for FaeLlmError {
+    fn from(e: MediaPlayerError) -> Self {
+        FaeLlmError::ToolExecutionError(e.to_string())
+    }
+}
+
+// ─── MediaPlayer trait ────────────────────────────────────────────────────────
+
+/// Abstraction over the system music player for testability.
+///
+/// The production implementation in [`super::applescript`] drives Music.app
+/// via AppleScript.  Tests use [`super::mock_stores::MockMediaPlayer`].
+pub trait MediaPlayer: Send + Sync {
+    /// The current track, or `None` when nothing is loaded.
+    fn now_playing(&self) -> Result<Option<NowPlaying>, MediaPlayerError>;
+
+    /// Apply a transport command and return the resulting state.
+    fn control(&self, command: MediaCommand) -> Result<Option<NowPlaying>, MediaPlayerError>;
+
+    /// Start playing whatever best matches `request`.
+    ///
+    /// Returns [`MediaPlayerError::NotFound`] when nothing matches.
+    fn play(&self, request: &PlayRequest) -> Result<NowPlaying, MediaPlayerError>;
+}
+
+// ─── NowPlayingTool ───────────────────────────────────────────────────────────
+
+/// Read-only tool that reports the current track.
+///
+/// # Arguments (JSON)
+///
+/// None.
+pub struct NowPlayingTool {
+    player: Arc<dyn MediaPlayer>,
+}
+
+impl NowPlayingTool {
+    /// Create a new `NowPlayingTool` backed by `player`.
+    pub fn new(player: Arc<dyn MediaPlayer>) -> Self {
+        Self { player }
+    }
+}
+
+impl Tool for NowPlayingTool {
+    fn name(&self) -> &str {
+        "now_playing"
+    }
+
+    fn description(&self) -> &str {
+        "Report what is playing in the user's Music app: track title, artist, \
+         album, and whether it is playing or paused."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn execute(&self, _args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let current = self.player.now_playing().map_err(|e| {
+            FaeLlmError::ToolExecutionError(format!("failed to read player state: {e}"))
+        })?;
+
+        Ok(ToolResult::success(match current {
+            Some(now) => now.format_summary(),
+            None => "Nothing is playing.".to_owned(),
+        }))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true
+    }
+}
+
+impl AppleEcosystemTool for NowPlayingTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::DesktopAutomation
+    }
+}
+
+// ─── MediaControlTool ─────────────────────────────────────────────────────────
+
+/// Tool that plays, pauses, skips, or goes back a track.
+///
+/// Requires `ToolMode::Full` and the DesktopAutomation permission.
+///
+/// # Arguments (JSON)
+///
+/// - `action` (string, required) — `play`, `pause`, `next`, or `previous`
+pub struct MediaControlTool {
+    player: Arc<dyn MediaPlayer>,
+}
+
+impl MediaControlTool {
+    /// Create a new `MediaControlTool` backed by `player`.
+    pub fn new(player: Arc<dyn MediaPlayer>) -> Self {
+        Self { player }
+    }
+}
+
+impl Tool for MediaControlTool {
+    fn name(&self) -> &str {
+        "media_control"
+    }
+
+    fn description(&self) -> &str {
+        "Control music playback in the user's Music app: play (resume), pause, \
+         skip to the next track, or go back to the previous one. \
+         Use play_music to start a specific playlist, artist, or genre."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["action"],
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["play", "pause", "next", "previous"],
+                    "description": "Transport command (required)"
+                }
+            }
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let Some(command) = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .and_then(MediaCommand::parse)
+        else {
+            return Ok(ToolResult::failure(
+                "action is required: one of play, pause, next, previous".to_owned(),
+            ));
+        };
+
+        let state = self.player.control(command).map_err(|e| {
+            FaeLlmError::ToolExecutionError(format!("failed to control playback: {e}"))
+        })?;
+
+        Ok(ToolResult::success(match state {
+            Some(now) => format!("{}\n{}", command.confirmation(), now.format_summary()),
+            None => command.confirmation().to_owned(),
+        }))
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        matches!(mode, ToolMode::Full)
+    }
+}
+
+impl AppleEcosystemTool for MediaControlTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::DesktopAutomation
+    }
+}
+
+// ─── PlayMusicTool ────────────────────────────────────────────────────────────
+
+/// Tool that starts a playlist or music matching a search.
+///
+/// Requires `ToolMode::Full` and the DesktopAutomation permission.
+///
+/// # Arguments (JSON)
+///
+/// - `query` (string, required) — playlist name, artist, album, title, or genre
+/// - `playlist_only` (boolean, optional) — only match playlist names
+pub struct PlayMusicTool {
+    player: Arc<dyn MediaPlayer>,
+}
+
+impl PlayMusicTool {
+    /// Create a new `PlayMusicTool` backed by `player`.
+    pub fn new(player: Arc<dyn MediaPlayer>) -> Self {
+        Self { player }
+    }
+}
+
+impl Tool for PlayMusicTool {
+    fn name(&self) -> &str {
+        "play_music"
+    }
+
+    fn description(&self) -> &str {
+        "Play music from the user's Music library. The query is matched against \
+         playlist names first, then track titles, artists, albums, and genres — \
+         so \"jazz\" plays a jazz playlist or jazz tracks. Set playlist_only when \
+         the user named a playlist."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["query"],
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Playlist name, artist, album, song title, or genre (required)"
+                },
+                "playlist_only": {
+                    "type": "boolean",
+                    "description": "Only match playlist names (default false)"
+                }
+            }
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let query = match args.get("query").and_then(|v| v.as_str()) {
+            Some(q) if !q.trim().is_empty() => q.trim().to_owned(),
+            _ => {
+                return Ok(ToolResult::failure(
+                    "query is required and cannot be empty".to_owned(),
+                ));
+            }
+        };
+        let playlist_only = args
+            .get("playlist_only")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let request = PlayRequest {
+            query,
+            playlist_only,
+        };
+        match self.player.play(&request) {
+            Ok(now) => Ok(ToolResult::success(now.format_summary())),
+            Err(MediaPlayerError::NotFound) => Ok(ToolResult::failure(format!(
+                "Nothing in the music library matches \"{}\".",
+                request.query
+            ))),
+            Err(e) => Err(FaeLlmError::ToolExecutionError(format!(
+                "failed to start playback: {e}"
+            ))),
+        }
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        matches!(mode, ToolMode::Full)
+    }
+}
+
+impl AppleEcosystemTool for PlayMusicTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::DesktopAutomation
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::tools::apple::mock_stores::MockMediaPlayer;
+    use crate::permissions::PermissionStore;
+
+    fn track(title: &str, artist: &str, genre: &str) -> Track {
+        Track {
+            title: title.to_owned(),
+            artist: Some(artist.to_owned()),
+            album: None,
+            genre: Some(genre.to_owned()),
+        }
+    }
+
+    fn make_player() -> Arc<MockMediaPlayer> {
+        Arc::new(MockMediaPlayer::new(vec![
+            (
+                "Morning Run".to_owned(),
+                vec![
+                    track("Eye of the Tiger", "Survivor", "Rock"),
+                    track("Lose Yourself", "Eminem", "Hip-Hop"),
+                ],
+            ),
+            (
+                "Library".to_owned(),
+                vec![
+                    track("So What", "Miles Davis", "Jazz"),
+                    track("Take Five", "Dave Brubeck", "Jazz"),
+                ],
+            ),
+        ]))
+    }
+
+    #[test]
+    fn play_music_matches_genre_then_controls_playback() {
+        let player = make_player();
+        let play = PlayMusicTool::new(player.clone());
+        let result = play.execute(serde_json::json!({"query": "polka"})).unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("polka"));
+
+        let result = play.execute(serde_json::json!({"query": "jazz"})).unwrap();
+        assert!(result.success);
+        assert_eq!(result.content, "Now playing: So What by Miles Davis");
+
+        let control = MediaControlTool::new(player.clone());
+        let result = control
+            .execute(serde_json::json!({"action": "next"}))
+            .unwrap();
+        assert!(result.content.contains("Take Five"));
+        let result = control
+            .execute(serde_json::json!({"action": "pause"}))
+            .unwrap();
+        assert!(result.content.starts_with("Playback paused."));
+
+        let now = NowPlayingTool::new(player).execute(serde_json::json!({}));
+        assert_eq!(now.unwrap().content, "Paused: Take Five by Dave Brubeck");
+    }
+
+    #[test]
+    fn play_music_playlist_only_ignores_tracks() {
+        let play = PlayMusicTool::new(make_player());
+        let result = play
+            .execute(serde_json::json!({"query": "morning run", "playlist_only": true}))
+            .unwrap();
+        assert!(result.success);
+        assert!(result.content.ends_with("(playlist: Morning Run)"));
+
+        let result = play
+            .execute(serde_json::json!({"query": "Survivor", "playlist_only": true}))
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn media_control_rejects_unknown_action() {
+        let control = MediaControlTool::new(make_player());
+        let result = control
+            .execute(serde_json::json!({"action": "louder"}))
+            .unwrap();
+        assert!(!result.success);
+        assert!(!control.allowed_in_mode(ToolMode::ReadOnly));
+    }
+
+    #[test]
+    fn media_tools_need_desktop_automation() {
+        let tool = NowPlayingTool::new(make_player());
+        let mut store = PermissionStore::default();
+        assert!(!tool.is_available(&store));
+        store.grant(PermissionKind::DesktopAutomation);
+        assert!(tool.is_available(&store));
+    }
+}
@@ -0,0 +1,879 @@
+//! Meeting scheduling on top of the calendar tools.
+//!
+//! "Find me 30 minutes with Alex next week" used to take a chain of calls —
+//! list the events, work out the gaps, create an event — that small models
+//! get wrong. These tools do the arithmetic in one structured call each:
+//!
+//! - [`FreeBusyTool`] — busy and free time over a range (read-only)
+//! - [`ProposeSlotsTool`] — candidate slots matching constraints (read-only)
+//! - [`CreateHoldTool`] — hold a slot on the calendar (write, Full mode)
+//!
+//! Times are local, in the ISO-8601 form the calendar tools use. Free time
+//! only counts within working hours (09:00–17:00 on weekdays unless the call
+//! says otherwise). All-day events are reported but don't block time: they
+//! are mostly birthdays and holidays.
+
+use std::sync::Arc;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::tools::types::{Tool, ToolResult};
+use crate::permissions::PermissionKind;
+
+use super::calendar::{
+    CalendarEvent, CalendarStore, EventQuery, NewCalendarEvent, parse_event_time,
+};
+use super::trait_def::AppleEcosystemTool;
+
+/// A stretch of local time, start inclusive and end exclusive.
+pub type TimeSpan = (NaiveDateTime, NaiveDateTime);
+
+/// Longest range the tools look at.
+const MAX_RANGE_DAYS: i64 = 31;
+
+/// Most events read for one range.
+const MAX_EVENTS: usize = 500;
+
+/// Proposed slots start on these boundaries.
+const SLOT_STEP_MINUTES: i64 = 30;
+
+/// Most slots one call proposes.
+const MAX_SLOTS: usize = 10;
+
+/// Working hours and the days they apply to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkingHours {
+    pub day_start: NaiveTime,
+    pub day_end: NaiveTime,
+    /// Skip Saturdays and Sundays.
+    pub weekdays_only: bool,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        Self {
+            day_start: NaiveTime::from_hms_opt(9, 0, 0).unwrap_or(NaiveTime::MIN),
+            day_end: NaiveTime::from_hms_opt(17, 0, 0).unwrap_or(NaiveTime::MIN),
+            weekdays_only: true,
+        }
+    }
+}
+
+impl WorkingHours {
+    /// The working-hours window of `date`, if it is a working day.
+    fn window(&self, date: NaiveDate) -> Option<TimeSpan> {
+        let weekend = date.weekday().number_from_monday() > 5;
+        (!(self.weekdays_only && weekend) && self.day_start < self.day_end)
+            .then(|| (date.and_time(self.day_start), date.and_time(self.day_end)))
+    }
+
+    fn describe(&self) -> String {
+        let days = if self.weekdays_only {
+            "weekdays"
+        } else {
+            "every day"
+        };
+        format!(
+            "{}–{}, {days}",
+            self.day_start.format("%H:%M"),
+            self.day_end.format("%H:%M")
+        )
+    }
+}
+
+/// Busy time within `range`, merged into non-overlapping blocks, and the
+/// number of all-day events, which are not counted as busy.
+pub fn busy_blocks(events: &[CalendarEvent], range: TimeSpan) -> (Vec<TimeSpan>, usize) {
+    let mut all_day = 0;
+    let mut busy = Vec::new();
+    for event in events {
+        let (Some(start), Some(end)) =
+            (parse_event_time(&event.start), parse_event_time(&event.end))
+        else {
+            continue;
+        };
+        if end <= range.0 || start >= range.1 {
+            continue;
+        }
+        if event.is_all_day {
+            all_day += 1;
+        } else {
+            busy.push((start.max(range.0), end.min(range.1)));
+        }
+    }
+    (merge(busy), all_day)
+}
+
+/// Sort `spans` and merge overlapping and back-to-back ones.
+fn merge(mut spans: Vec<TimeSpan>) -> Vec<TimeSpan> {
+    spans.sort();
+    let mut merged: Vec<TimeSpan> = Vec::new();
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Free time within `range` and working `hours`, around merged `busy`.
+pub fn free_blocks(busy: &[TimeSpan], range: TimeSpan, hours: &WorkingHours) -> Vec<TimeSpan> {
+    let mut free = Vec::new();
+    let mut date = range.0.date();
+    while date <= range.1.date() {
+        if let Some((day_start, day_end)) = hours.window(date) {
+            let mut cursor = day_start.max(range.0);
+            let day_end = day_end.min(range.1);
+            for &(start, end) in busy {
+                if end <= cursor || start >= day_end {
+                    continue;
+                }
+                if start > cursor {
+                    free.push((cursor, start));
+                }
+                cursor = cursor.max(end);
+            }
+            if cursor < day_end {
+                free.push((cursor, day_end));
+            }
+        }
+        let Some(next) = date.succ_opt() else {
+            break;
+        };
+        date = next;
+    }
+    free
+}
+
+/// Up to `count` slots of `duration` in the free time around `busy`, kept
+/// `buffer` away from other events.
+///
+/// Slots start on the half hour. The earliest slot of each day is taken
+/// first, so the choice is spread over the range rather than packed into
+/// its first morning.
+pub fn propose_slots(
+    busy: &[TimeSpan],
+    range: TimeSpan,
+    hours: &WorkingHours,
+    duration: Duration,
+    buffer: Duration,
+    count: usize,
+) -> Vec<TimeSpan> {
+    let padded = merge(
+        busy.iter()
+            .map(|&(start, end)| (start - buffer, end + buffer))
+            .collect(),
+    );
+    let mut candidates = Vec::new();
+    for (start, end) in free_blocks(&padded, range, hours) {
+        let mut slot = round_up(start);
+        while slot + duration <= end {
+            candidates.push((slot, slot + duration));
+            slot += Duration::minutes(SLOT_STEP_MINUTES);
+        }
+    }
+
+    let mut chosen: Vec<TimeSpan> = Vec::new();
+    for candidate in &candidates {
+        if chosen.len() < count
+            && chosen
+                .last()
+                .is_none_or(|c| c.0.date() != candidate.0.date())
+        {
+            chosen.push(*candidate);
+        }
+    }
+    for candidate in &candidates {
+        if chosen.len() >= count {
+            break;
+        }
+        if !chosen
+            .iter()
+            .any(|c| c.0 < candidate.1 && candidate.0 < c.1)
+        {
+            chosen.push(*candidate);
+        }
+    }
+    chosen.sort();
+    chosen
+}
+
+/// `time` rounded up to the next slot boundary.
+fn round_up(time: NaiveDateTime) -> NaiveDateTime {
+    let minutes = i64::from(time.hour() * 60 + time.minute());
+    let past = minutes % SLOT_STEP_MINUTES;
+    let on_minute = time
+        .with_second(0)
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(time);
+    if past == 0 && on_minute == time {
+        time
+    } else {
+        on_minute + Duration::minutes(SLOT_STEP_MINUTES - past)
+    }
+}
+
+/// A span as spoken: `Tue 3 Mar 14:00–14:30`.
+fn format_span((start, end): TimeSpan) -> String {
+    if start.date() == end.date() {
+        format!(
+            "{}–{}",
+            start.format("%a %-d %b %H:%M"),
+            end.format("%H:%M")
+        )
+    } else {
+        format!(
+            "{} – {}",
+            start.format("%a %-d %b %H:%M"),
+            end.format("%a %-d %b %H:%M")
+        )
+    }
+}
+
+fn format_iso(time: NaiveDateTime) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+/// A time argument: an ISO-8601 datetime, or a date meaning the start of
+/// that day (or, with `end_of_day`, the end of it).
+fn time_arg(
+    args: &serde_json::Value,
+    name: &str,
+    end_of_day: bool,
+) -> Result<Option<NaiveDateTime>, String> {
+    let Some(value) = args
+        .get(name)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    else {
+        return Ok(None);
+    };
+    if let Some(time) = parse_event_time(value) {
+        return Ok(Some(time));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        format!("{name} must be an ISO-8601 date or datetime (e.g. '2026-03-02T09:00:00')")
+    })?;
+    let date = if end_of_day {
+        date.succ_opt().unwrap_or(date)
+    } else {
+        date
+    };
+    Ok(Some(date.and_time(NaiveTime::MIN)))
+}
+
+fn minutes_arg(args: &serde_json::Value, name: &str) -> Option<i64> {
+    args.get(name).and_then(|v| v.as_i64())
+}
+
+/// The range to look at and the working hours within it.
+///
+/// The range is `start`..`end`, by default the next 7 days, and never
+/// reaches back before `now`.
+fn window_args(
+    args: &serde_json::Value,
+    now: NaiveDateTime,
+) -> Result<(TimeSpan, WorkingHours), String> {
+    let start = time_arg(args, "start", false)?.unwrap_or(now).max(now);
+    let end = time_arg(args, "end", true)?.unwrap_or(start + Duration::days(7));
+    if end <= start {
+        return Err("end must be after start, and the range must not be in the past".to_owned());
+    }
+    if end - start > Duration::days(MAX_RANGE_DAYS) {
+        return Err(format!("the range can be at most {MAX_RANGE_DAYS} days"));
+    }
+
+    let mut hours = WorkingHours::default();
+    for (name, field) in [
+        ("day_start", &mut hours.day_start),
+        ("day_end", &mut hours.day_end),
+    ] {
+        if let Some(value) = args.get(name).and_then(|v| v.as_str()) {
+            *field = NaiveTime::parse_from_str(value.trim(), "%H:%M")
+                .map_err(|_| format!("{name} must be a time like '09:00'"))?;
+        }
+    }
+    if hours.day_start >= hours.day_end {
+        return Err("day_start must be before day_end".to_owned());
+    }
+    if let Some(weekdays_only) = args.get("weekdays_only").and_then(|v| v.as_bool()) {
+        hours.weekdays_only = weekdays_only;
+    }
+    Ok(((start, end), hours))
+}
+
+fn calendar_ids_arg(args: &serde_json::Value) -> Vec<String> {
+    args.get("calendar_id")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+        .map(|id| vec![id.trim().to_owned()])
+        .unwrap_or_default()
+}
+
+/// Events that could overlap `range`. The store filters by start and end
+/// separately, so the query reaches a day beyond each side.
+fn events_around(
+    store: &dyn CalendarStore,
+    range: TimeSpan,
+    calendar_ids: Vec<String>,
+) -> Result<Vec<CalendarEvent>, FaeLlmError> {
+    store
+        .list_events(&EventQuery {
+            calendar_ids,
+            start_after: Some(format_iso(range.0 - Duration::days(1))),
+            end_before: Some(format_iso(range.1 + Duration::days(1))),
+            limit: MAX_EVENTS,
+        })
+        .map_err(|e| FaeLlmError::ToolExecutionError(format!("failed to list events: {e}")))
+}
+
+fn range_properties() -> serde_json::Value {
+    serde_json::json!({
+        "start": {
+            "type": "string",
+            "description": "Start of the range, ISO-8601 date or datetime (default: now)"
+        },
+        "end": {
+            "type": "string",
+            "description": "End of the range, ISO-8601 date (inclusive) or datetime (default: 7 days after start, at most 31)"
+        },
+        "day_start": {
+            "type": "string",
+            "description": "Start of working hours, HH:MM (default '09:00')"
+        },
+        "day_end": {
+            "type": "string",
+            "description": "End of working hours, HH:MM (default '17:00')"
+        },
+        "weekdays_only": {
+            "type": "boolean",
+            "description": "Skip weekends (default true)"
+        },
+        "calendar_id": {
+            "type": "string",
+            "description": "Only count events from this calendar (default: all calendars)"
+        }
+    })
+}
+
+// ─── FreeBusyTool ─────────────────────────────────────────────────────────────
+
+/// Read-only tool that reports busy and free time over a range.
+///
+/// # Arguments (JSON)
+///
+/// - `start`, `end` (string, optional) — the range; defaults to the next 7 days
+/// - `day_start`, `day_end` (string, optional) — working hours, default 09:00–17:00
+/// - `weekdays_only` (boolean, optional, default true)
+/// - `calendar_id` (string, optional) — only count this calendar's events
+pub struct FreeBusyTool {
+    store: Arc<dyn CalendarStore>,
+}
+
+impl FreeBusyTool {
+    /// Create a new `FreeBusyTool` backed by `store`.
+    pub fn new(store: Arc<dyn CalendarStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl Tool for FreeBusyTool {
+    fn name(&self) -> &str {
+        "calendar_free_busy"
+    }
+
+    fn description(&self) -> &str {
+        "Show when the user is busy and free over a date range (default: the next 7 days). \
+         Free time is counted within working hours, 09:00–17:00 on weekdays unless given. \
+         Use propose_meeting_slots instead to find a time for a meeting."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": range_properties()
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let (range, hours) = match window_args(&args, Local::now().naive_local()) {
+            Ok(window) => window,
+            Err(e) => return Ok(ToolResult::failure(e)),
+        };
+        let events = events_around(self.store.as_ref(), range, calendar_ids_arg(&args))?;
+        let (busy, all_day) = busy_blocks(&events, range);
+        let free = free_blocks(&busy, range, &hours);
+
+        let mut lines = vec![format!(
+            "Free/busy for {} (working hours {}):",
+            format_span(range),
+            hours.describe()
+        )];
+        lines.push(String::new());
+        if busy.is_empty() {
+            lines.push("Busy: nothing scheduled.".to_owned());
+        } else {
+            lines.push("Busy:".to_owned());
+            lines.extend(busy.iter().map(|&span| format!("- {}", format_span(span))));
+        }
+        lines.push(String::new());
+        if free.is_empty() {
+            lines.push("Free: no free time within working hours.".to_owned());
+        } else {
+            lines.push("Free:".to_owned());
+            lines.extend(free.iter().map(|&span| format!("- {}", format_span(span))));
+        }
+        if all_day > 0 {
+            lines.push(String::new());
+            lines.push(format!(
+                "{all_day} all-day event(s) in the range are not counted as busy."
+            ));
+        }
+        Ok(ToolResult::success(lines.join("\n")))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true // read-only
+    }
+}
+
+impl AppleEcosystemTool for FreeBusyTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::Calendar
+    }
+}
+
+// ─── ProposeSlotsTool ─────────────────────────────────────────────────────────
+
+/// Read-only tool that proposes meeting slots in the user's free time.
+///
+/// # Arguments (JSON)
+///
+/// - `duration_minutes` (integer, required) — meeting length
+/// - `count` (integer, optional) — how many slots to propose (default 3, max 10)
+/// - `buffer_minutes` (integer, optional) — gap to keep around other events
+/// - `start`, `end`, `day_start`, `day_end`, `weekdays_only`, `calendar_id` —
+///   as for [`FreeBusyTool`]
+pub struct ProposeSlotsTool {
+    store: Arc<dyn CalendarStore>,
+}
+
+impl ProposeSlotsTool {
+    /// Create a new `ProposeSlotsTool` backed by `store`.
+    pub fn new(store: Arc<dyn CalendarStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl Tool for ProposeSlotsTool {
+    fn name(&self) -> &str {
+        "propose_meeting_slots"
+    }
+
+    fn description(&self) -> &str {
+        "Find free slots for a meeting of a given length, e.g. 'find me 30 minutes next week'. \
+         Proposes a few candidate times spread over the range, within working hours \
+         (09:00–17:00 on weekdays unless given). Offer them to the user, then hold the \
+         chosen one with create_calendar_hold."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        let mut properties = range_properties();
+        if let Some(properties) = properties.as_object_mut() {
+            properties.insert(
+                "duration_minutes".to_owned(),
+                serde_json::json!({
+                    "type": "integer",
+                    "description": "Meeting length in minutes (required)",
+                    "minimum": 5,
+                    "maximum": 480
+                }),
+            );
+            properties.insert(
+                "count".to_owned(),
+                serde_json::json!({
+                    "type": "integer",
+                    "description": "How many slots to propose (default 3, max 10)",
+                    "minimum": 1,
+                    "maximum": MAX_SLOTS
+                }),
+            );
+            properties.insert(
+                "buffer_minutes".to_owned(),
+                serde_json::json!({
+                    "type": "integer",
+                    "description": "Minutes to keep free before and after other events (default 0)",
+                    "minimum": 0,
+                    "maximum": 120
+                }),
+            );
+        }
+        serde_json::json!({
+            "type": "object",
+            "required": ["duration_minutes"],
+            "properties": properties
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let minutes = match minutes_arg(&args, "duration_minutes") {
+            Some(m) if (5..=480).contains(&m) => m,
+            _ => {
+                return Ok(ToolResult::failure(
+                    "duration_minutes is required (5 to 480)".to_owned(),
+                ));
+            }
+        };
+        let count = args
+            .get("count")
+            .and_then(|v| v.as_u64())
+            .map_or(3, |n| (n as usize).clamp(1, MAX_SLOTS));
+        let buffer = minutes_arg(&args, "buffer_minutes").map_or(0, |m| m.clamp(0, 120));
+        let (range, hours) = match window_args(&args, Local::now().naive_local()) {
+            Ok(window) => window,
+            Err(e) => return Ok(ToolResult::failure(e)),
+        };
+
+        let events = events_around(self.store.as_ref(), range, calendar_ids_arg(&args))?;
+        let (busy, _) = busy_blocks(&events, range);
+        let slots = propose_slots(
+            &busy,
+            range,
+            &hours,
+            Duration::minutes(minutes),
+            Duration::minutes(buffer),
+            count,
+        );
+
+        if slots.is_empty() {
+            return Ok(ToolResult::success(format!(
+                "No free {minutes}-minute slot in {} within working hours {}. \
+                 Try a wider range or longer working hours.",
+                format_span(range),
+                hours.describe()
+            )));
+        }
+        let mut lines = vec![format!(
+            "{} free {minutes}-minute slot(s) in {}:",
+            slots.len(),
+            format_span(range)
+        )];
+        for (i, &slot) in slots.iter().enumerate() {
+            lines.push(format!(
+                "{}. {} (start: {})",
+                i + 1,
+                format_span(slot),
+                format_iso(slot.0)
+            ));
+        }
+        lines.push(String::new());
+        lines.push(
+            "Offer these to the user; hold the chosen one with create_calendar_hold.".to_owned(),
+        );
+        Ok(ToolResult::success(lines.join("\n")))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true // read-only
+    }
+}
+
+impl AppleEcosystemTool for ProposeSlotsTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::Calendar
+    }
+}
+
+// ─── CreateHoldTool ───────────────────────────────────────────────────────────
+
+/// Write tool that holds a slot on the calendar with a placeholder event.
+///
+/// The slot is checked again before the hold is created, so a time taken
+/// since it was proposed is not double-booked.
+///
+/// Requires `ToolMode::Full` and the Calendar permission.
+///
+/// # Arguments (JSON)
+///
+/// - `start` (string, required) — ISO-8601 datetime
+/// - `duration_minutes` (integer, optional, default 30)
+/// - `with` (string, optional) — who the meeting is with, used in the title
+/// - `title` (string, optional) — overrides the generated title
+/// - `calendar_id` (string, optional) — target calendar
+/// - `notes` (string, optional)
+pub struct CreateHoldTool {
+    store: Arc<dyn CalendarStore>,
+}
+
+impl CreateHoldTool {
+    /// Create a new `CreateHoldTool` backed by `store`.
+    pub fn new(store: Arc<dyn CalendarStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl Tool for CreateHoldTool {
+    fn name(&self) -> &str {
+        "create_calendar_hold"
+    }
+
+    fn description(&self) -> &str {
+        "Hold a time on the user's calendar for a meeting being arranged, usually one \
+         found with propose_meeting_slots. Fails if the time is no longer free. \
+         Use update_calendar_event later to turn the hold into the real meeting."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["start"],
+            "properties": {
+                "start": {
+                    "type": "string",
+                    "description": "Start time in ISO-8601 format (e.g. '2026-03-03T14:00:00')"
+                },
+                "duration_minutes": {
+                    "type": "integer",
+                    "description": "Length in minutes (default 30)",
+                    "minimum": 5,
+                    "maximum": 480
+                },
+                "with": {
+                    "type": "string",
+                    "description": "Who the meeting is with, e.g. 'Alex'"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Event title (default 'Hold: meeting with <with>')"
+                },
+                "calendar_id": {
+                    "type": "string",
+                    "description": "Target calendar identifier (from list_calendars). Uses default calendar if omitted."
+                },
+                "notes": {
+                    "type": "string",
+                    "description": "Notes for the hold"
+                }
+            }
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let start = match time_arg(&args, "start", false) {
+            Ok(Some(start)) => start,
+            Ok(None) | Err(_) => {
+                return Ok(ToolResult::failure(
+                    "start is required (ISO-8601 datetime, e.g. '2026-03-03T14:00:00')".to_owned(),
+                ));
+            }
+        };
+        let minutes = minutes_arg(&args, "duration_minutes").unwrap_or(30);
+        if !(5..=480).contains(&minutes) {
+            return Ok(ToolResult::failure(
+                "duration_minutes must be between 5 and 480".to_owned(),
+            ));
+        }
+        let slot = (start, start + Duration::minutes(minutes));
+        let text = |name: &str| {
+            args.get(name)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_owned)
+        };
+
+        let events = events_around(self.store.as_ref(), slot, Vec::new())?;
+        let conflicts: Vec<&CalendarEvent> = events
+            .iter()
+            .filter(|event| !event.is_all_day)
+            .filter(
+                |event| match (parse_event_time(&event.start), parse_event_time(&event.end)) {
+                    (Some(s), Some(e)) => s < slot.1 && slot.0 < e,
+                    _ => false,
+                },
+            )
+            .collect();
+        if !conflicts.is_empty() {
+            let titles: Vec<&str> = conflicts.iter().map(|e| e.title.as_str()).collect();
+            return Ok(ToolResult::failure(format!(
+                "{} is no longer free: it overlaps {}. Propose new slots.",
+                format_span(slot),
+                titles.join(", ")
+            )));
+        }
+
+        let title = text("title").unwrap_or_else(|| match text("with") {
+            Some(with) => format!("Hold: meeting with {with}"),
+            None => "Hold".to_owned(),
+        });
+        let hold = NewCalendarEvent {
+            title,
+            start: format_iso(slot.0),
+            end: Some(format_iso(slot.1)),
+            calendar_id: text("calendar_id"),
+            location: None,
+            notes: text("notes"),
+            is_all_day: false,
+            alarms: Vec::new(),
+        };
+        let created = self
+            .store
+            .create_event(&hold)
+            .map_err(|e| FaeLlmError::ToolExecutionError(format!("failed to create hold: {e}")))?;
+
+        Ok(ToolResult::success(format!(
+            "Held {}.\n{}",
+            format_span(slot),
+            created.format_summary()
+        )))
+    }
+
+    fn is_side_effecting(&self) -> bool {
+        true
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        matches!(mode, ToolMode::Full)
+    }
+}
+
+impl AppleEcosystemTool for CreateHoldTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::Calendar
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::tools::apple::mock_stores::MockCalendarStore;
+
+    fn at(iso: &str) -> NaiveDateTime {
+        parse_event_time(iso).expect("time")
+    }
+
+    fn event(id: &str, start: &str, end: &str) -> CalendarEvent {
+        CalendarEvent {
+            identifier: id.to_owned(),
+            calendar_id: "cal-work".to_owned(),
+            title: id.to_owned(),
+            start: start.to_owned(),
+            end: end.to_owned(),
+            location: None,
+            notes: None,
+            is_all_day: false,
+            alarms: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn free_time_is_working_hours_around_merged_events() {
+        // Mon 2 – Sun 8 March 2026.
+        let range = (at("2026-03-02T00:00:00"), at("2026-03-09T00:00:00"));
+        let mut holiday = event("holiday", "2026-03-03T00:00:00", "2026-03-04T00:00:00");
+        holiday.is_all_day = true;
+        let events = vec![
+            event("standup", "2026-03-02T09:00:00", "2026-03-02T09:30:00"),
+            event("review", "2026-03-02T09:30:00", "2026-03-02T11:00:00"),
+            event("lunch", "2026-03-02T12:15:00", "2026-03-02T13:00:00"),
+            event("last month", "2026-02-02T10:00:00", "2026-02-02T11:00:00"),
+            holiday,
+        ];
+        let (busy, all_day) = busy_blocks(&events, range);
+        assert_eq!(all_day, 1);
+        assert_eq!(
+            busy,
+            vec![
+                (at("2026-03-02T09:00:00"), at("2026-03-02T11:00:00")),
+                (at("2026-03-02T12:15:00"), at("2026-03-02T13:00:00")),
+            ]
+        );
+
+        let free = free_blocks(&busy, range, &WorkingHours::default());
+        assert_eq!(
+            free[0],
+            (at("2026-03-02T11:00:00"), at("2026-03-02T12:15:00"))
+        );
+        assert_eq!(
+            free[1],
+            (at("2026-03-02T13:00:00"), at("2026-03-02T17:00:00"))
+        );
+        // Tuesday to Friday whole, no weekend.
+        assert_eq!(free.len(), 6);
+        assert_eq!(free[5].1, at("2026-03-06T17:00:00"));
+    }
+
+    #[test]
+    fn proposals_spread_over_days_and_respect_buffers() {
+        let range = (at("2026-03-02T10:10:00"), at("2026-03-04T00:00:00"));
+        let busy = vec![(at("2026-03-02T11:00:00"), at("2026-03-02T16:00:00"))];
+        let hours = WorkingHours::default();
+
+        let slots = propose_slots(
+            &busy,
+            range,
+            &hours,
+            Duration::minutes(30),
+            Duration::zero(),
+            3,
+        );
+        assert_eq!(
+            slots,
+            vec![
+                (at("2026-03-02T10:30:00"), at("2026-03-02T11:00:00")),
+                (at("2026-03-02T16:00:00"), at("2026-03-02T16:30:00")),
+                (at("2026-03-03T09:00:00"), at("2026-03-03T09:30:00")),
+            ]
+        );
+
+        // A 15-minute buffer rules out the slots touching the busy block.
+        let slots = propose_slots(
+            &busy,
+            range,
+            &hours,
+            Duration::minutes(30),
+            Duration::minutes(15),
+            2,
+        );
+        assert_eq!(slots[0].0, at("2026-03-02T16:30:00"));
+        assert_eq!(slots[1].0, at("2026-03-03T09:00:00"));
+    }
+
+    #[test]
+    fn hold_refuses_a_taken_slot() {
+        let store = Arc::new(MockCalendarStore::new(
+            Vec::new(),
+            vec![event(
+                "Dentist",
+                "2099-03-03T14:00:00",
+                "2099-03-03T15:00:00",
+            )],
+        ));
+        let tool = CreateHoldTool::new(store);
+
+        let taken = tool
+            .execute(serde_json::json!({"start": "2099-03-03T14:30:00"}))
+            .expect("execute");
+        assert!(!taken.success);
+        assert!(taken.error.unwrap_or_default().contains("Dentist"));
+
+        let held = tool
+            .execute(serde_json::json!({
+                "start": "2099-03-03T15:00:00",
+                "duration_minutes": 30,
+                "with": "Alex"
+            }))
+            .expect("execute");
+        assert!(held.success);
+        assert!(held.content.contains("Hold: meeting with Alex"));
+        assert!(held.content.contains("2099-03-03T15:30:00"));
+    }
+}
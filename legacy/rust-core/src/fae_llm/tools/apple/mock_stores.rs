@@ -1,5 +1,5 @@
 //! In-memory mock implementations of [`ContactStore`], [`CalendarStore`],
-//! [`ReminderStore`], [`NoteStore`], and [`MailStore`].
+//! [`ReminderStore`], [`NoteStore`], [`MailStore`], and [`MediaPlayer`].
 //!
 //! These are used exclusively in tests to exercise the tool layer without
 //! requiring a macOS runtime or Apple framework access.
@@ -12,6 +12,9 @@ use super::calendar::{
 };
 use super::contacts::{Contact, ContactQuery, ContactStore, ContactStoreError, NewContact};
 use super::mail::{Mail, MailQuery, MailStore, MailStoreError, NewMail};
+use super::media::{
+    MediaCommand, MediaPlayer, MediaPlayerError, NowPlaying, PlayRequest, PlaybackState, Track,
+};
 use super::notes::{NewNote, Note, NoteQuery, NoteStore, NoteStoreError};
 use super::reminders::{
     NewReminder, Reminder, ReminderList, ReminderQuery, ReminderStore, ReminderStoreError,
@@ -369,6 +372,7 @@ impl ReminderStore for MockReminderStore {
             priority: reminder.priority.unwrap_or(0),
             is_completed: false,
             completion_date: None,
+            recurrence: reminder.recurrence.clone(),
         };
 
         reminders.push(new_reminder.clone());
@@ -622,3 +626,126 @@ impl MailStore for MockMailStore {
         Ok(new_mail)
     }
 }
+
+// ─── MockMediaPlayer ──────────────────────────────────────────────────────────
+
+/// An in-memory music player for unit testing.
+///
+/// Playlists are matched by name first; otherwise every track whose title,
+/// artist, album, or genre contains the query is queued in playlist order.
+pub struct MockMediaPlayer {
+    playlists: Vec<(String, Vec<Track>)>,
+    queue: Mutex<MockQueue>,
+}
+
+struct MockQueue {
+    tracks: Vec<Track>,
+    position: usize,
+    playlist: Option<String>,
+    state: PlaybackState,
+}
+
+impl MockMediaPlayer {
+    /// Create a stopped mock player with `playlists` of `(name, tracks)`.
+    pub fn new(playlists: Vec<(String, Vec<Track>)>) -> Self {
+        Self {
+            playlists,
+            queue: Mutex::new(MockQueue {
+                tracks: Vec::new(),
+                position: 0,
+                playlist: None,
+                state: PlaybackState::Stopped,
+            }),
+        }
+    }
+}
+
+impl MockQueue {
+    fn current(&self) -> Option<NowPlaying> {
+        if self.state == PlaybackState::Stopped {
+            return None;
+        }
+        Some(NowPlaying {
+            track: self.tracks.get(self.position)?.clone(),
+            playlist: self.playlist.clone(),
+            state: self.state,
+        })
+    }
+}
+
+impl MediaPlayer for MockMediaPlayer {
+    fn now_playing(&self) -> Result<Option<NowPlaying>, MediaPlayerError> {
+        let queue = self
+            .queue
+            .lock()
+            .map_err(|_| MediaPlayerError::Backend("mock lock poisoned".to_owned()))?;
+        Ok(queue.current())
+    }
+
+    fn control(&self, command: MediaCommand) -> Result<Option<NowPlaying>, MediaPlayerError> {
+        let mut queue = self
+            .queue
+            .lock()
+            .map_err(|_| MediaPlayerError::Backend("mock lock poisoned".to_owned()))?;
+        if queue.tracks.is_empty() {
+            return Ok(None);
+        }
+        match command {
+            MediaCommand::Play => queue.state = PlaybackState::Playing,
+            MediaCommand::Pause => {
+                if queue.state == PlaybackState::Playing {
+                    queue.state = PlaybackState::Paused;
+                }
+            }
+            MediaCommand::Next => {
+                queue.position = (queue.position + 1).min(queue.tracks.len() - 1);
+                queue.state = PlaybackState::Playing;
+            }
+            MediaCommand::Previous => {
+                queue.position = queue.position.saturating_sub(1);
+                queue.state = PlaybackState::Playing;
+            }
+        }
+        Ok(queue.current())
+    }
+
+    fn play(&self, request: &PlayRequest) -> Result<NowPlaying, MediaPlayerError> {
+        let query = request.query.to_ascii_lowercase();
+        let contains = |field: &str| field.to_ascii_lowercase().contains(&query);
+
+        let (tracks, playlist) = match self.playlists.iter().find(|(name, _)| contains(name)) {
+            Some((name, tracks)) => (tracks.clone(), Some(name.clone())),
+            None if request.playlist_only => return Err(MediaPlayerError::NotFound),
+            None => {
+                let tracks: Vec<Track> = self
+                    .playlists
+                    .iter()
+                    .flat_map(|(_, tracks)| tracks)
+                    .filter(|t| {
+                        contains(&t.title)
+                            || t.artist.as_deref().is_some_and(contains)
+                            || t.album.as_deref().is_some_and(contains)
+                            || t.genre.as_deref().is_some_and(contains)
+                    })
+                    .cloned()
+                    .collect();
+                (tracks, None)
+            }
+        };
+        if tracks.is_empty() {
+            return Err(MediaPlayerError::NotFound);
+        }
+
+        let mut queue = self
+            .queue
+            .lock()
+            .map_err(|_| MediaPlayerError::Backend("mock lock poisoned".to_owned()))?;
+        *queue = MockQueue {
+            tracks,
+            position: 0,
+            playlist,
+            state: PlaybackState::Playing,
+        };
+        queue.current().ok_or(MediaPlayerError::NotFound)
+    }
+}
@@ -3,16 +3,18 @@
 //! This module provides tools that give Fae's LLM access to native macOS data:
 //!
 //! - **Contacts** — search, read, and create contacts via `CNContactStore`
-//! - **Calendar** — list, create, update, and delete calendar events via `EventKit`
-//! - **Reminders** — list, create, and complete reminders via `EventKit`
-//! - **Notes** — list, read, create, and append to notes via AppleScript
-//! - **Mail** — search inbox, read messages, and compose email via AppleScript
+//! - **Calendar** — list, create, update, and delete calendar events, find free
+//!   time and hold meeting slots via `EventKit`
+//! - **Reminders** — list, create (optionally repeating), and complete reminders via `EventKit`
+//! - **Notes** — list, search, read, create, and append to notes via AppleScript
+//! - **Mail** — search inbox, read messages, and draft and send email via AppleScript
+//! - **Media** — report and control playback, and play music, in Music.app via AppleScript
 //!
 //! # Architecture
 //!
 //! All tools depend on store traits ([`ContactStore`], [`CalendarStore`],
-//! [`ReminderStore`], [`NoteStore`], [`MailStore`]) that abstract over the actual
-//! Apple-framework implementation.  The store implementations live in [`ffi_bridge`]
+//! [`ReminderStore`], [`NoteStore`], [`MailStore`], [`MediaPlayer`]) that abstract
+//! over the actual Apple-framework implementation.  The store implementations live in [`ffi_bridge`]
 //! (production, bridged through the Swift/C ABI) and in [`mock_stores`] (in-process
 //! mocks used for unit tests).
 //!
@@ -27,6 +29,7 @@
 //! [`ReminderStore`]: reminders::ReminderStore
 //! [`NoteStore`]: notes::NoteStore
 //! [`MailStore`]: mail::MailStore
+//! [`MediaPlayer`]: media::MediaPlayer
 //! [`PermissionStore`]: crate::permissions::PermissionStore
 
 pub mod applescript;
@@ -35,9 +38,14 @@ pub mod calendar;
 pub mod contacts;
 pub mod ffi_bridge;
 pub mod mail;
+pub mod mail_drafts;
+pub mod media;
+pub mod meeting_slots;
 pub mod mock_stores;
+pub mod note_search;
 pub mod notes;
 pub mod rate_limiter;
+pub mod recurrence;
 pub mod reminders;
 pub mod trait_def;
 
@@ -52,19 +60,27 @@ pub use contacts::{
     NewContact, SearchContactsTool,
 };
 pub use ffi_bridge::{
-    global_calendar_store, global_contact_store, global_mail_store, global_note_store,
-    global_reminder_store, register_calendar_store, register_contact_store, register_mail_store,
-    register_note_store, register_reminder_store,
+    global_calendar_store, global_contact_store, global_mail_store, global_media_player,
+    global_note_store, global_reminder_store, register_calendar_store, register_contact_store,
+    register_mail_store, register_media_player, register_note_store, register_reminder_store,
 };
 pub use mail::{
     ComposeMailTool, GetMailTool, Mail, MailQuery, MailStore, MailStoreError, NewMail,
     SearchMailTool,
 };
+pub use mail_drafts::{MailDraft, MailDraftStore};
+pub use media::{
+    MediaCommand, MediaControlTool, MediaPlayer, MediaPlayerError, NowPlaying, NowPlayingTool,
+    PlayMusicTool, PlayRequest, PlaybackState, Track,
+};
+pub use meeting_slots::{CreateHoldTool, FreeBusyTool, ProposeSlotsTool, WorkingHours};
+pub use note_search::{NoteIndex, NoteSearchHit, SearchNotesTool};
 pub use notes::{
     AppendToNoteTool, CreateNoteTool, GetNoteTool, ListNotesTool, NewNote, Note, NoteQuery,
     NoteStore, NoteStoreError,
 };
 pub use rate_limiter::AppleRateLimiter;
+pub use recurrence::{Frequency, Recurrence, parse_recurrence};
 pub use reminders::{
     CreateReminderTool, ListReminderListsTool, ListRemindersTool, NewReminder, Reminder,
     ReminderList, ReminderQuery, ReminderStore, ReminderStoreError, SetReminderCompletedTool,
@@ -0,0 +1,547 @@
+//! Search over the user's notes by words and by meaning.
+//!
+//! [`ListNotesTool`](super::notes::ListNotesTool) only matches a literal
+//! substring, so "find that note about the boiler service" misses a note
+//! titled "Heating — annual check". [`SearchNotesTool`] ranks every note
+//! against the query by keyword overlap with its full text, blended with
+//! embedding similarity, the same scoring session search uses.
+//!
+//! Note embeddings live in a local [`NoteIndex`] (a SQLite file in the cache
+//! directory), keyed by note identifier and a hash of the note's content.
+//! Each search lists the notes, embeds only those added or changed since
+//! the last one and drops the deleted ones, so searching thousands of notes
+//! costs only what changed.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use rusqlite::{Connection, params};
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::tools::types::{Tool, ToolResult};
+use crate::permissions::PermissionKind;
+
+use super::notes::{Note, NoteQuery, NoteStore, NoteStoreError};
+use super::trait_def::AppleEcosystemTool;
+
+/// Most notes read from the store per search.
+const MAX_INDEXED_NOTES: usize = 10_000;
+
+/// Weight given to keyword overlap in the score; the remainder goes to
+/// embedding similarity.
+const KEYWORD_WEIGHT: f32 = 0.6;
+
+/// Notes scoring below this are left out of the results.
+const MIN_SCORE: f32 = 0.15;
+
+/// Maximum length (in characters) of a result snippet.
+const SNIPPET_MAX_CHARS: usize = 120;
+
+/// Name of the built-in lexical embedding.
+const DEFAULT_EMBEDDER: &str = "lexical";
+
+const CREATE_NOTE_EMBEDDINGS_TABLE: &str = "\
+CREATE TABLE IF NOT EXISTS note_embeddings (
+    identifier   TEXT PRIMARY KEY,
+    content_hash TEXT NOT NULL,
+    embedding    BLOB NOT NULL
+)";
+
+type Embedder = Box<dyn Fn(&str) -> Vec<f32> + Send + Sync>;
+
+// ─── NoteIndex ────────────────────────────────────────────────────────────────
+
+/// A note that matched a search.
+#[derive(Debug, Clone)]
+pub struct NoteSearchHit {
+    pub note: Note,
+    /// Relevance in `0.0..=1.0`.
+    pub score: f32,
+    /// Excerpt around the first query term in the body.
+    pub snippet: String,
+}
+
+/// Embeddings of the user's notes, kept up to date as they are searched.
+pub struct NoteIndex {
+    path: PathBuf,
+    /// Opened on first use.
+    conn: Mutex<Option<Connection>>,
+    /// Part of every content hash, so switching embedders re-embeds all notes.
+    embedder: String,
+    embed: Embedder,
+}
+
+impl NoteIndex {
+    /// Keep the index in the SQLite file at `path`, created on first search.
+    /// Notes are embedded with the built-in lexical embedding.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            conn: Mutex::new(None),
+            embedder: DEFAULT_EMBEDDER.to_owned(),
+            embed: Box::new(crate::skills::discovery::deterministic_embedding),
+        }
+    }
+
+    /// The index at `cache_dir()/notes_index.db`.
+    pub fn default_location() -> Self {
+        Self::new(crate::fae_dirs::notes_index_file())
+    }
+
+    /// Embed notes with `embed` instead, e.g. a model-backed engine.
+    /// `name` identifies it; notes embedded under another name are
+    /// re-embedded on the next search.
+    pub fn with_embedder(
+        mut self,
+        name: impl Into<String>,
+        embed: impl Fn(&str) -> Vec<f32> + Send + Sync + 'static,
+    ) -> Self {
+        self.embedder = name.into();
+        self.embed = Box::new(embed);
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Notes in `store` matching `query`, best first.
+    ///
+    /// Brings the index up to date with the store first.
+    ///
+    /// # Errors
+    ///
+    /// Returns the store's error if the notes cannot be listed, or
+    /// [`NoteStoreError::Backend`] if the index cannot be read or written.
+    pub fn search(
+        &self,
+        store: &dyn NoteStore,
+        query: &str,
+        folder: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<NoteSearchHit>, NoteStoreError> {
+        let terms = tokenize(query);
+        if terms.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+        let notes = store.list_notes(&NoteQuery {
+            folder: None,
+            search: None,
+            limit: MAX_INDEXED_NOTES,
+        })?;
+        let embeddings = self.refresh(&notes, notes.len() < MAX_INDEXED_NOTES)?;
+        let query_embedding = (self.embed)(query);
+
+        let mut hits = Vec::new();
+        for note in notes {
+            if folder.is_some_and(|f| note.folder.as_deref() != Some(f)) {
+                continue;
+            }
+            let doc_terms = tokenize(&format!("{}\n{}", note.title, note.body));
+            let matched = terms.iter().filter(|t| doc_terms.contains(t)).count();
+            let keyword_score = matched as f32 / terms.len() as f32;
+            let semantic_score = embeddings
+                .get(&note.identifier)
+                .map(|e| crate::memory::embedding::cosine_similarity(&query_embedding, e))
+                .unwrap_or(0.0)
+                .max(0.0);
+            let score = KEYWORD_WEIGHT * keyword_score + (1.0 - KEYWORD_WEIGHT) * semantic_score;
+            if score < MIN_SCORE {
+                continue;
+            }
+            hits.push(NoteSearchHit {
+                snippet: snippet(&note.body, &terms),
+                note,
+                score,
+            });
+        }
+
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.note.modified_at.cmp(&a.note.modified_at))
+        });
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Number of notes in the index.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoteStoreError::Backend`] if the index cannot be read.
+    pub fn indexed_count(&self) -> Result<usize, NoteStoreError> {
+        self.with_conn(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM note_embeddings", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map(|n| n as usize)
+        })
+    }
+
+    /// Embed the notes that are new or changed and return every note's
+    /// embedding by identifier. When `complete` (the listing was not cut
+    /// short), notes missing from `notes` are dropped from the index.
+    fn refresh(
+        &self,
+        notes: &[Note],
+        complete: bool,
+    ) -> Result<HashMap<String, Vec<f32>>, NoteStoreError> {
+        self.with_conn(|conn| {
+            let mut stored = HashMap::new();
+            {
+                let mut stmt =
+                    conn.prepare("SELECT identifier, content_hash, embedding FROM note_embeddings")?;
+                let rows = stmt.query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        (row.get::<_, String>(1)?, row.get::<_, Vec<u8>>(2)?),
+                    ))
+                })?;
+                for row in rows {
+                    let (identifier, entry) = row?;
+                    stored.insert(identifier, entry);
+                }
+            }
+
+            let tx = conn.transaction()?;
+            let mut embeddings = HashMap::with_capacity(notes.len());
+            for note in notes {
+                let hash = self.content_hash(note);
+                let embedding = match stored.remove(&note.identifier) {
+                    Some((stored_hash, blob)) if stored_hash == hash => decode(&blob),
+                    _ => {
+                        let embedding = (self.embed)(&format!("{}\n{}", note.title, note.body));
+                        tx.execute(
+                            "INSERT OR REPLACE INTO note_embeddings (identifier, content_hash, embedding)
+                             VALUES (?1, ?2, ?3)",
+                            params![note.identifier, hash, encode(&embedding)],
+                        )?;
+                        embedding
+                    }
+                };
+                embeddings.insert(note.identifier.clone(), embedding);
+            }
+            if complete {
+                for identifier in stored.keys() {
+                    tx.execute(
+                        "DELETE FROM note_embeddings WHERE identifier = ?1",
+                        params![identifier],
+                    )?;
+                }
+            }
+            tx.commit()?;
+            Ok(embeddings)
+        })
+    }
+
+    fn content_hash(&self, note: &Note) -> String {
+        let mut hasher = blake3::Hasher::new();
+        for part in [&self.embedder, &note.title, &note.body] {
+            hasher.update(part.as_bytes());
+            hasher.update(&[0]);
+        }
+        hasher.finalize().to_hex().to_string()
+    }
+
+    /// Run `f` on the index connection, opening it first if needed.
+    fn with_conn<T>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>,
+    ) -> Result<T, NoteStoreError> {
+        let index_error =
+            |e: &dyn std::fmt::Display| NoteStoreError::Backend(format!("note search index: {e}"));
+        let mut guard = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        if guard.is_none() {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| index_error(&e))?;
+            }
+            let conn = Connection::open(&self.path).map_err(|e| index_error(&e))?;
+            conn.execute(CREATE_NOTE_EMBEDDINGS_TABLE, [])
+                .map_err(|e| index_error(&e))?;
+            *guard = Some(conn);
+        }
+        match guard.as_mut() {
+            Some(conn) => f(conn).map_err(|e| index_error(&e)),
+            None => Err(NoteStoreError::Backend(
+                "note search index is not open".to_owned(),
+            )),
+        }
+    }
+}
+
+fn encode(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn decode(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.len() > 2)
+        .map(str::to_lowercase)
+        .collect();
+    terms.sort_unstable();
+    terms.dedup();
+    terms
+}
+
+/// At most [`SNIPPET_MAX_CHARS`] characters of `body`, starting a little
+/// before the first query term in it.
+fn snippet(body: &str, terms: &[String]) -> String {
+    let lower = body.to_lowercase();
+    let byte_pos = terms
+        .iter()
+        .filter_map(|t| lower.find(t.as_str()))
+        .min()
+        .unwrap_or(0);
+    let char_pos = lower
+        .char_indices()
+        .take_while(|(i, _)| *i < byte_pos)
+        .count();
+    let start = char_pos.saturating_sub(SNIPPET_MAX_CHARS / 4);
+    let total = body.chars().count();
+    let window: String = body.chars().skip(start).take(SNIPPET_MAX_CHARS).collect();
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    out.push_str(window.trim());
+    if start + SNIPPET_MAX_CHARS < total {
+        out.push('…');
+    }
+    out
+}
+
+// ─── SearchNotesTool ──────────────────────────────────────────────────────────
+
+/// Read-only tool that finds notes by topic.
+///
+/// # Arguments (JSON)
+///
+/// - `query` (string, required) — what the note is about
+/// - `folder` (string, optional) — only search this folder
+/// - `limit` (integer, optional) — max results (default 5, max 20)
+pub struct SearchNotesTool {
+    store: Arc<dyn NoteStore>,
+    index: Arc<NoteIndex>,
+}
+
+impl SearchNotesTool {
+    /// Create a new `SearchNotesTool` backed by `store`, indexing into the
+    /// default location.
+    pub fn new(store: Arc<dyn NoteStore>) -> Self {
+        Self {
+            store,
+            index: Arc::new(NoteIndex::default_location()),
+        }
+    }
+
+    /// Keep the search index in `index` instead.
+    pub fn with_index(mut self, index: Arc<NoteIndex>) -> Self {
+        self.index = index;
+        self
+    }
+}
+
+impl Tool for SearchNotesTool {
+    fn name(&self) -> &str {
+        "search_notes"
+    }
+
+    fn description(&self) -> &str {
+        "Find notes in the user's Notes app by what they are about, searching the full \
+         text of every note and ranking by relevance. Use this when the user describes \
+         a note (\"that note about the boiler service\") rather than naming it. \
+         Pass the topic words as query. Use get_note to read a result in full."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["query"],
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "What the note is about (e.g. 'boiler service')"
+                },
+                "folder": {
+                    "type": "string",
+                    "description": "Only search notes in this folder"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of notes to return (default 5, max 20)",
+                    "minimum": 1,
+                    "maximum": 20
+                }
+            }
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let query = match args.get("query").and_then(|v| v.as_str()) {
+            Some(q) if !q.trim().is_empty() => q.trim().to_owned(),
+            _ => {
+                return Ok(ToolResult::failure(
+                    "query is required and cannot be empty".to_owned(),
+                ));
+            }
+        };
+        let folder = args
+            .get("folder")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty());
+        let limit = args
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|n| n.clamp(1, 20) as usize)
+            .unwrap_or(5);
+
+        let hits = self
+            .index
+            .search(self.store.as_ref(), &query, folder, limit)
+            .map_err(|e| FaeLlmError::ToolExecutionError(format!("failed to search notes: {e}")))?;
+
+        if hits.is_empty() {
+            return Ok(ToolResult::success(format!(
+                "No notes found about \"{query}\"."
+            )));
+        }
+
+        let mut lines = vec![format!("Found {} note(s):\n", hits.len())];
+        for hit in &hits {
+            let note = &hit.note;
+            lines.push(format!("Note: {} [id: {}]", note.title, note.identifier));
+            if let Some(ref folder) = note.folder {
+                lines.push(format!("  Folder: {folder}"));
+            }
+            if let Some(ref modified) = note.modified_at {
+                lines.push(format!("  Modified: {modified}"));
+            }
+            if !hit.snippet.is_empty() {
+                lines.push(format!("  Match: {}", hit.snippet));
+            }
+            lines.push(String::new());
+        }
+
+        Ok(ToolResult::success(lines.join("\n")))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true // read-only
+    }
+}
+
+impl AppleEcosystemTool for SearchNotesTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::DesktopAutomation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::fae_llm::tools::apple::mock_stores::MockNoteStore;
+
+    fn note(identifier: &str, title: &str, body: &str) -> Note {
+        Note {
+            identifier: identifier.to_owned(),
+            title: title.to_owned(),
+            body: body.to_owned(),
+            folder: Some("Notes".to_owned()),
+            created_at: None,
+            modified_at: None,
+        }
+    }
+
+    fn sample_store() -> Arc<MockNoteStore> {
+        Arc::new(MockNoteStore::new(vec![
+            note(
+                "note-1",
+                "Heating",
+                "Boiler service booked with Hughes Plumbing, annual check due in March.",
+            ),
+            note("note-2", "Groceries", "Milk, eggs, bread, coffee."),
+            note(
+                "note-3",
+                "Holiday ideas",
+                "Lisbon in spring, maybe a train to Porto.",
+            ),
+        ]))
+    }
+
+    #[test]
+    fn search_notes_finds_notes_by_topic() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let index = Arc::new(NoteIndex::new(dir.path().join("notes_index.db")));
+        let tool = SearchNotesTool::new(sample_store()).with_index(index);
+
+        let result = tool
+            .execute(serde_json::json!({"query": "boiler service"}))
+            .expect("search");
+        assert!(result.success);
+        assert!(result.content.starts_with("Found 1 note(s)"));
+        assert!(result.content.contains("[id: note-1]"));
+        assert!(result.content.contains("Match: Boiler service booked"));
+
+        let result = tool
+            .execute(serde_json::json!({"query": "quantum chromodynamics"}))
+            .expect("search");
+        assert!(result.content.contains("No notes found"));
+
+        let result = tool
+            .execute(serde_json::json!({"query": " "}))
+            .expect("search");
+        assert!(!result.success);
+        assert!(tool.allowed_in_mode(ToolMode::ReadOnly));
+    }
+
+    #[test]
+    fn index_embeds_only_new_and_changed_notes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let embedded = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&embedded);
+        let index = NoteIndex::new(dir.path().join("notes_index.db")).with_embedder(
+            "counting",
+            move |text| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                crate::skills::discovery::deterministic_embedding(text)
+            },
+        );
+        let store = sample_store();
+
+        index
+            .search(store.as_ref(), "boiler", None, 5)
+            .expect("search");
+        // Three notes plus the query.
+        assert_eq!(embedded.swap(0, Ordering::SeqCst), 4);
+        assert_eq!(index.indexed_count().expect("count"), 3);
+
+        index
+            .search(store.as_ref(), "boiler", None, 5)
+            .expect("search");
+        assert_eq!(embedded.swap(0, Ordering::SeqCst), 1);
+
+        store
+            .append_to_note("note-2", "Also oat milk.")
+            .expect("append");
+        let hits = index
+            .search(store.as_ref(), "oat milk", None, 5)
+            .expect("search");
+        assert_eq!(embedded.swap(0, Ordering::SeqCst), 2);
+        assert_eq!(hits[0].note.identifier, "note-2");
+    }
+}
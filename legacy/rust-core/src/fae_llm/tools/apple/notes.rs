@@ -12,6 +12,8 @@
 //!   Swift bridge registers a real implementation
 //! - `MockNoteStore` in [`super::mock_stores`] for unit tests
 //!
+//! Searching notes by topic is [`super::note_search::SearchNotesTool`].
+//!
 //! Notes access requires [`PermissionKind::DesktopAutomation`] because the
 //! production implementation uses AppleScript to bridge to Notes.app.
 
@@ -437,6 +439,10 @@ impl Tool for CreateNoteTool {
         )))
     }
 
+    fn is_side_effecting(&self) -> bool {
+        true
+    }
+
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
         matches!(mode, ToolMode::Full)
     }
@@ -529,6 +535,10 @@ impl Tool for AppendToNoteTool {
         )))
     }
 
+    fn is_side_effecting(&self) -> bool {
+        true
+    }
+
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
         matches!(mode, ToolMode::Full)
     }
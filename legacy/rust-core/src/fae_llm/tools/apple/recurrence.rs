@@ -0,0 +1,525 @@
+//! Natural-language repeat patterns for reminders.
+//!
+//! [`CreateReminderTool`](super::reminders::CreateReminderTool) takes the
+//! user's own words ("weekdays at 9", "every 2 weeks on Monday", "monthly
+//! on the 15th") and [`parse_recurrence`] turns them into a [`Recurrence`]
+//! here, in Rust, so a repeat rule never depends on the model getting an
+//! EventKit rule right. The rule has the shape of an `EKRecurrenceRule`:
+//! a frequency and interval, plus days of the week, a week of the month or
+//! a day of the month.
+//!
+//! Some phrases mean two things — "every second Tuesday" is every other
+//! Tuesday or the second Tuesday of each month, "at 5" is morning or
+//! evening. Those parse to several readings, which the tool puts to the
+//! user as a clarification. [`Recurrence::describe`] renders a rule in
+//! words that parse back to exactly that rule, so the user's pick can be
+//! passed straight back in.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/// Reminders without a time of day come due at 9 am.
+const DEFAULT_HOUR: u32 = 9;
+
+/// How far ahead [`Recurrence::next_after`] looks for an occurrence.
+const MAX_SCAN_DAYS: i64 = 400;
+
+/// Unit a reminder repeats in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A parsed repeat rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Repeat every `interval` units (2 = every other week).
+    pub interval: u32,
+    /// Days of the week it falls on; empty for any day. Monthly rules use
+    /// exactly one, together with [`Self::week_of_month`].
+    pub weekdays: Vec<Weekday>,
+    /// Which week of the month (1–5, or -1 for the last), for rules like
+    /// "the second Tuesday of every month".
+    pub week_of_month: Option<i8>,
+    /// Day of the month (1–31) for monthly rules.
+    pub day_of_month: Option<u8>,
+    /// Time of day it comes due, if the user gave one.
+    pub time: Option<NaiveTime>,
+}
+
+impl Recurrence {
+    fn every(frequency: Frequency, interval: u32) -> Self {
+        Self {
+            frequency,
+            interval,
+            weekdays: Vec::new(),
+            week_of_month: None,
+            day_of_month: None,
+            time: None,
+        }
+    }
+
+    /// The rule in words, e.g. "every 2 weeks on Monday at 9:00 am".
+    /// Parsing the result gives back exactly this rule.
+    pub fn describe(&self) -> String {
+        let unit = match self.frequency {
+            Frequency::Daily => "day",
+            Frequency::Weekly => "week",
+            Frequency::Monthly => "month",
+            Frequency::Yearly => "year",
+        };
+        let mut text = if self.interval > 1 {
+            format!("every {} {unit}s", self.interval)
+        } else {
+            format!("every {unit}")
+        };
+
+        if self.frequency == Frequency::Weekly && !self.weekdays.is_empty() {
+            let days = if self.weekdays == WORKDAYS {
+                "weekday".to_owned()
+            } else {
+                let names: Vec<String> = self.weekdays.iter().map(|d| day_name(*d)).collect();
+                join_words(&names)
+            };
+            text = if self.interval > 1 {
+                format!("{text} on {days}")
+            } else {
+                format!("every {days}")
+            };
+        }
+        if let (Some(week), Some(day)) = (self.week_of_month, self.weekdays.first()) {
+            text.push_str(&format!(" on the {} {}", week_name(week), day_name(*day)));
+        } else if let Some(day) = self.day_of_month {
+            text.push_str(&format!(" on the {}", day_ordinal(day)));
+        }
+        if let Some(time) = self.time {
+            text.push_str(&format!(" at {}", time.format("%-I:%M %P")));
+        }
+        text
+    }
+
+    /// The first time the rule comes due after `after`, or `None` if it
+    /// never does within about a year.
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let time = self
+            .time
+            .or_else(|| NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0))?;
+        (0..=MAX_SCAN_DAYS)
+            .map(|offset| after.date() + Duration::days(offset))
+            .filter(|date| self.falls_on(*date))
+            .map(|date| date.and_time(time))
+            .find(|due| *due > after)
+    }
+
+    /// Whether the rule can fall on `date`, ignoring the interval (the
+    /// first occurrence anchors it).
+    fn falls_on(&self, date: NaiveDate) -> bool {
+        if !self.weekdays.is_empty() && !self.weekdays.contains(&date.weekday()) {
+            return false;
+        }
+        if let Some(day) = self.day_of_month
+            && date.day() != u32::from(day)
+        {
+            return false;
+        }
+        match self.week_of_month {
+            Some(-1) => (date + Duration::days(7)).month() != date.month(),
+            Some(week) => (date.day() - 1) / 7 + 1 == week as u32,
+            None => true,
+        }
+    }
+}
+
+/// Monday to Friday.
+const WORKDAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+
+/// Parse a repeat pattern.
+///
+/// Returns every reading of `text`: one for a clear phrase, several when it
+/// is ambiguous.
+///
+/// # Errors
+///
+/// Returns a message for the model when `text` is not a repeat pattern
+/// this parser understands.
+pub fn parse_recurrence(text: &str) -> Result<Vec<Recurrence>, String> {
+    let not_understood = || {
+        format!(
+            "could not understand the repeat pattern \"{}\"; use phrasing like \"every day\", \
+             \"weekdays at 9\", \"every 2 weeks on Monday\", \"monthly on the 15th\" or \
+             \"the last Friday of every month\"",
+            text.trim()
+        )
+    };
+
+    let lowered = text.to_lowercase().replace([',', '.'], " ");
+    let words: Vec<&str> = lowered.split_whitespace().collect();
+    let (rule_words, time_words) = match words.iter().position(|w| *w == "at") {
+        Some(at) => (&words[..at], Some(&words[at + 1..])),
+        None => (&words[..], None),
+    };
+    let times = match time_words {
+        Some(words) => parse_time(&words.concat()).ok_or_else(not_understood)?,
+        None => vec![None],
+    };
+    let rules = parse_rule(rule_words).ok_or_else(not_understood)?;
+
+    let mut readings = Vec::new();
+    for rule in &rules {
+        for time in &times {
+            let mut reading = rule.clone();
+            reading.time = *time;
+            readings.push(reading);
+        }
+    }
+    Ok(readings)
+}
+
+/// One classified word of a repeat pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Word {
+    Unit(Frequency),
+    Days(&'static [Weekday]),
+    Count(u32),
+    /// "second", "15th", "last" (-1).
+    Ordinal(i32),
+}
+
+fn parse_rule(words: &[&str]) -> Option<Vec<Recurrence>> {
+    let mut classified = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        let Some(class) = classify(word) else {
+            if FILLER.contains(word) {
+                continue;
+            }
+            return None;
+        };
+        // An ordinal right before a unit is an interval ("every second
+        // week"), and a count must come right before what it counts.
+        let class = match (class, words.get(i + 1).and_then(|w| classify(w))) {
+            (Word::Ordinal(n), Some(Word::Unit(_))) if n > 0 => Word::Count(n as u32),
+            (Word::Count(_), Some(Word::Unit(_) | Word::Days(_))) => class,
+            (Word::Count(_), _) => return None,
+            _ => class,
+        };
+        classified.push(class);
+    }
+
+    let mut unit = None;
+    let mut count = None;
+    let mut ordinals = Vec::new();
+    let mut days: Vec<Weekday> = Vec::new();
+    for class in classified {
+        match class {
+            Word::Unit(f) if unit.is_none_or(|u| u == f) => unit = Some(f),
+            Word::Unit(_) => return None,
+            Word::Count(n) if count.is_none() && n > 0 => count = Some(n),
+            Word::Count(_) => return None,
+            Word::Ordinal(n) => ordinals.push(n),
+            Word::Days(list) => days.extend(list),
+        }
+    }
+    days.sort_by_key(|d| d.num_days_from_monday());
+    days.dedup();
+    let interval = count.unwrap_or(1);
+
+    let weekly = |interval| Recurrence {
+        weekdays: days.clone(),
+        ..Recurrence::every(Frequency::Weekly, interval)
+    };
+    let nth_weekday = |week: i32| {
+        (days.len() == 1 && (week == -1 || (1..=5).contains(&week))).then(|| Recurrence {
+            weekdays: days.clone(),
+            week_of_month: Some(week as i8),
+            ..Recurrence::every(Frequency::Monthly, interval)
+        })
+    };
+
+    let rule = match (unit, ordinals.as_slice()) {
+        // "every Tuesday", "every other Monday and Thursday".
+        (None | Some(Frequency::Weekly), []) if !days.is_empty() => weekly(interval),
+        // "every second Tuesday": every other Tuesday, or the second
+        // Tuesday of the month.
+        (None, [n]) if !days.is_empty() && count.is_none() && (2..=5).contains(n) => {
+            return Some(vec![weekly(*n as u32), nth_weekday(*n)?]);
+        }
+        // "every first Monday", "every last Friday".
+        (None, [n]) if !days.is_empty() && count.is_none() => nth_weekday(*n)?,
+        // "every 15th".
+        (None, [n]) if days.is_empty() && count.is_none() => month_day(*n, 1)?,
+        (Some(Frequency::Monthly), [n]) if !days.is_empty() => nth_weekday(*n)?,
+        (Some(Frequency::Monthly), [n]) => month_day(*n, interval)?,
+        (Some(frequency), []) if days.is_empty() => Recurrence::every(frequency, interval),
+        _ => return None,
+    };
+    Some(vec![rule])
+}
+
+fn month_day(day: i32, interval: u32) -> Option<Recurrence> {
+    (1..=31).contains(&day).then(|| Recurrence {
+        day_of_month: Some(day as u8),
+        ..Recurrence::every(Frequency::Monthly, interval)
+    })
+}
+
+/// Words that carry no meaning of their own in a repeat pattern.
+const FILLER: &[&str] = &[
+    "every",
+    "each",
+    "on",
+    "the",
+    "of",
+    "and",
+    "&",
+    "a",
+    "an",
+    "per",
+    "once",
+    "repeat",
+    "repeats",
+    "repeating",
+    "recurring",
+];
+
+fn classify(word: &str) -> Option<Word> {
+    const ALL_WORKDAYS: &[Weekday] = &WORKDAYS;
+    const WEEKEND: &[Weekday] = &[Weekday::Sat, Weekday::Sun];
+    let class = match word {
+        "day" | "days" | "daily" => Word::Unit(Frequency::Daily),
+        "week" | "weeks" | "weekly" => Word::Unit(Frequency::Weekly),
+        "month" | "months" | "monthly" => Word::Unit(Frequency::Monthly),
+        "year" | "years" | "yearly" | "annually" => Word::Unit(Frequency::Yearly),
+        "weekday" | "weekdays" | "workday" | "workdays" => Word::Days(ALL_WORKDAYS),
+        "weekend" | "weekends" => Word::Days(WEEKEND),
+        "other" => Word::Count(2),
+        "last" => Word::Ordinal(-1),
+        "first" => Word::Ordinal(1),
+        "second" => Word::Ordinal(2),
+        "third" => Word::Ordinal(3),
+        "fourth" => Word::Ordinal(4),
+        "fifth" => Word::Ordinal(5),
+        _ => {
+            if let Some(day) = weekday(word) {
+                return Some(Word::Days(day));
+            }
+            if let Some(n) = number_word(word) {
+                return Some(Word::Count(n));
+            }
+            let digits = ["st", "nd", "rd", "th"]
+                .iter()
+                .find_map(|suffix| word.strip_suffix(suffix))?;
+            return digits.parse().ok().map(Word::Ordinal);
+        }
+    };
+    Some(class)
+}
+
+fn weekday(word: &str) -> Option<&'static [Weekday]> {
+    let days: &'static [Weekday] = match word.strip_suffix('s').unwrap_or(word) {
+        "mon" | "monday" => &[Weekday::Mon],
+        "tue" | "tuesday" => &[Weekday::Tue],
+        "wed" | "wednesday" => &[Weekday::Wed],
+        "thu" | "thur" | "thursday" => &[Weekday::Thu],
+        "fri" | "friday" => &[Weekday::Fri],
+        "sat" | "saturday" => &[Weekday::Sat],
+        "sun" | "sunday" => &[Weekday::Sun],
+        _ => return None,
+    };
+    Some(days)
+}
+
+fn number_word(word: &str) -> Option<u32> {
+    const NUMBERS: [&str; 12] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+        "twelve",
+    ];
+    word.parse().ok().or_else(|| {
+        NUMBERS
+            .iter()
+            .position(|n| *n == word)
+            .map(|i| i as u32 + 1)
+    })
+}
+
+/// Readings of a time of day ("9", "9:30pm", "17:00", "noon"). A bare hour
+/// from 1 to 6 could be morning or evening and gives both.
+fn parse_time(text: &str) -> Option<Vec<Option<NaiveTime>>> {
+    let at = |hour: u32, minute: u32| NaiveTime::from_hms_opt(hour, minute, 0);
+    match text {
+        "noon" | "midday" => return Some(vec![at(12, 0)]),
+        "midnight" => return Some(vec![at(0, 0)]),
+        _ => {}
+    }
+    let (clock, meridiem) = if let Some(clock) = text.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = text.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (text, None)
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour, minute.parse().ok()?),
+        Some(_) => return None,
+        None => (clock, 0),
+    };
+    let padded = hour.starts_with('0');
+    let hour: u32 = hour.parse().ok()?;
+
+    let times = match meridiem {
+        Some(pm) if (1..=12).contains(&hour) => {
+            vec![at(hour % 12 + if pm { 12 } else { 0 }, minute)]
+        }
+        Some(_) => return None,
+        None if padded || hour == 0 || hour >= 12 => vec![at(hour, minute)],
+        None if hour <= 6 => vec![at(hour, minute), at(hour + 12, minute)],
+        None => vec![at(hour, minute)],
+    };
+    times.iter().all(Option::is_some).then_some(times)
+}
+
+fn day_name(day: Weekday) -> String {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+    .to_owned()
+}
+
+fn week_name(week: i8) -> &'static str {
+    match week {
+        1 => "first",
+        2 => "second",
+        3 => "third",
+        4 => "fourth",
+        5 => "fifth",
+        _ => "last",
+    }
+}
+
+fn day_ordinal(day: u8) -> String {
+    let suffix = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{day}{suffix}")
+}
+
+/// "Monday", "Monday and Thursday", "Monday, Wednesday and Friday".
+fn join_words(words: &[String]) -> String {
+    match words.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        _ => words.concat(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn one(text: &str) -> Recurrence {
+        let mut readings = parse_recurrence(text).expect("parse");
+        assert_eq!(readings.len(), 1, "{text} should have one reading");
+        readings.remove(0)
+    }
+
+    #[test]
+    fn phrases_parse_and_describe_round_trips() {
+        let weekdays = one("weekdays at 9");
+        assert_eq!(weekdays.weekdays, WORKDAYS);
+        assert_eq!(weekdays.time, NaiveTime::from_hms_opt(9, 0, 0));
+        assert_eq!(weekdays.describe(), "every weekday at 9:00 am");
+
+        let cases = [
+            ("every day", "every day"),
+            ("every other day", "every 2 days"),
+            ("every 2 weeks on Monday", "every 2 weeks on Monday"),
+            ("Mondays and Thursdays", "every Monday and Thursday"),
+            ("monthly on the 15th", "every month on the 15th"),
+            (
+                "the last Friday of every month",
+                "every month on the last Friday",
+            ),
+            (
+                "every first Monday at 6:30pm",
+                "every month on the first Monday at 6:30 pm",
+            ),
+            ("every second month", "every 2 months"),
+            ("yearly", "every year"),
+        ];
+        for (text, described) in cases {
+            let rule = one(text);
+            assert_eq!(rule.describe(), described, "{text}");
+            assert_eq!(one(described), rule, "{described}");
+        }
+
+        assert!(parse_recurrence("whenever it rains").is_err());
+        assert!(parse_recurrence("every day at 25").is_err());
+    }
+
+    #[test]
+    fn ambiguous_phrases_give_every_reading() {
+        let readings: Vec<String> = parse_recurrence("every second Tuesday")
+            .expect("parse")
+            .iter()
+            .map(Recurrence::describe)
+            .collect();
+        assert_eq!(
+            readings,
+            vec![
+                "every 2 weeks on Tuesday",
+                "every month on the second Tuesday"
+            ]
+        );
+
+        let readings: Vec<String> = parse_recurrence("daily at 5")
+            .expect("parse")
+            .iter()
+            .map(Recurrence::describe)
+            .collect();
+        assert_eq!(
+            readings,
+            vec!["every day at 5:00 am", "every day at 5:00 pm"]
+        );
+    }
+
+    #[test]
+    fn next_after_finds_the_first_occurrence() {
+        // Friday 2026-10-16, 10:00.
+        let now = NaiveDate::from_ymd_opt(2026, 10, 16)
+            .and_then(|d| d.and_hms_opt(10, 0, 0))
+            .expect("date");
+        let at = |y, m, d, h| NaiveDate::from_ymd_opt(y, m, d).and_then(|d| d.and_hms_opt(h, 0, 0));
+        assert_eq!(one("weekdays at 9").next_after(now), at(2026, 10, 19, 9));
+        assert_eq!(
+            one("every day at 17:00").next_after(now),
+            at(2026, 10, 16, 17)
+        );
+        assert_eq!(
+            one("the last Friday of every month").next_after(now),
+            at(2026, 10, 30, 9)
+        );
+        assert_eq!(
+            one("monthly on the 15th").next_after(now),
+            at(2026, 11, 15, 9)
+        );
+    }
+}
@@ -4,7 +4,7 @@
 //!
 //! - [`ListReminderListsTool`] — list all reminder lists (read-only)
 //! - [`ListRemindersTool`] — list reminders, optionally filtered by list (read-only)
-//! - [`CreateReminderTool`] — create a new reminder (write, Full mode)
+//! - [`CreateReminderTool`] — create a new reminder, optionally repeating (write, Full mode)
 //! - [`SetReminderCompletedTool`] — complete or uncomplete a reminder (write, Full mode)
 //!
 //! The store trait is implemented by:
@@ -17,9 +17,10 @@ use std::sync::Arc;
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
-use crate::fae_llm::tools::types::{Tool, ToolResult};
+use crate::fae_llm::tools::types::{Clarification, ClarificationCandidate, Tool, ToolResult};
 use crate::permissions::PermissionKind;
 
+use super::recurrence::{Recurrence, parse_recurrence};
 use super::trait_def::AppleEcosystemTool;
 
 // ─── Domain types ─────────────────────────────────────────────────────────────
@@ -54,6 +55,8 @@ pub struct Reminder {
     pub is_completed: bool,
     /// Completion date in ISO-8601 format, if completed.
     pub completion_date: Option<String>,
+    /// How the reminder repeats, if it does.
+    pub recurrence: Option<Recurrence>,
 }
 
 impl Reminder {
@@ -69,6 +72,9 @@ impl Reminder {
         if let Some(ref due) = self.due_date {
             parts.push(format!("  Due: {due}"));
         }
+        if let Some(ref recurrence) = self.recurrence {
+            parts.push(format!("  Repeats: {}", recurrence.describe()));
+        }
         if self.priority > 0 {
             let priority_label = match self.priority {
                 1..=3 => "high",
@@ -119,6 +125,8 @@ pub struct NewReminder {
     pub due_date: Option<String>,
     /// Optional: priority 0-9 (0 = none).
     pub priority: Option<u8>,
+    /// Optional: repeat rule.
+    pub recurrence: Option<Recurrence>,
 }
 
 /// Error type for reminder store operations.
@@ -366,6 +374,9 @@ impl AppleEcosystemTool for ListRemindersTool {
 /// - `notes` (string, optional)
 /// - `due_date` (string, optional) — ISO-8601 date/time
 /// - `priority` (integer, optional) — 0-9 (0 = none, 1 = highest)
+/// - `recurrence` (string, optional) — repeat pattern in the user's words,
+///   parsed by [`parse_recurrence`]; an ambiguous pattern returns a
+///   clarification instead of creating the reminder
 pub struct CreateReminderTool {
     store: Arc<dyn ReminderStore>,
 }
@@ -386,7 +397,10 @@ impl Tool for CreateReminderTool {
         "Create a new reminder in the user's Reminders app. \
          Requires at least a title. Due dates use ISO-8601 format \
          (e.g. '2026-03-01T09:00:00'). Priority ranges from 1 (highest) to 9 (lowest); \
-         0 means no priority."
+         0 means no priority. For a repeating reminder, pass the user's own words for \
+         the repeat pattern as recurrence (e.g. 'weekdays at 9', 'every second Tuesday'); \
+         do not convert it yourself. The first due date is worked out from the pattern \
+         when due_date is omitted."
     }
 
     fn schema(&self) -> serde_json::Value {
@@ -415,6 +429,10 @@ impl Tool for CreateReminderTool {
                     "description": "Priority level 0-9 (0=none, 1=highest, 9=lowest)",
                     "minimum": 0,
                     "maximum": 9
+                },
+                "recurrence": {
+                    "type": "string",
+                    "description": "How the reminder repeats, in the user's words (e.g. 'every day', 'weekdays at 9', 'every 2 weeks on Monday', 'monthly on the 15th')"
                 }
             }
         })
@@ -430,7 +448,32 @@ impl Tool for CreateReminderTool {
             }
         };
 
-        let new_reminder = NewReminder {
+        let recurrence = match args
+            .get("recurrence")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
+        {
+            Some(text) => match parse_recurrence(text) {
+                Ok(mut readings) if readings.len() == 1 => Some(readings.remove(0)),
+                Ok(readings) => {
+                    let candidates = readings
+                        .iter()
+                        .map(|reading| ClarificationCandidate {
+                            label: reading.describe(),
+                            value: reading.describe(),
+                        })
+                        .collect();
+                    return Ok(ToolResult::clarify(Clarification::new(
+                        "recurrence",
+                        candidates,
+                    )));
+                }
+                Err(message) => return Ok(ToolResult::failure(message)),
+            },
+            None => None,
+        };
+
+        let mut new_reminder = NewReminder {
             title,
             list_id: args
                 .get("list_id")
@@ -451,7 +494,16 @@ impl Tool for CreateReminderTool {
                 .get("priority")
                 .and_then(|v| v.as_u64())
                 .map(|n| n.min(9) as u8),
+            recurrence,
         };
+        if new_reminder.due_date.is_none()
+            && let Some(ref recurrence) = new_reminder.recurrence
+        {
+            let now = chrono::Local::now().naive_local();
+            new_reminder.due_date = recurrence
+                .next_after(now)
+                .map(|due| due.format("%Y-%m-%dT%H:%M:%S").to_string());
+        }
 
         let created = self.store.create_reminder(&new_reminder).map_err(|e| {
             FaeLlmError::ToolExecutionError(format!("failed to create reminder: {e}"))
@@ -463,6 +515,10 @@ impl Tool for CreateReminderTool {
         )))
     }
 
+    fn is_side_effecting(&self) -> bool {
+        true
+    }
+
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
         matches!(mode, ToolMode::Full)
     }
@@ -609,6 +665,7 @@ mod tests {
                 priority: 3,
                 is_completed: false,
                 completion_date: None,
+                recurrence: None,
             },
             Reminder {
                 identifier: "rem-002".to_owned(),
@@ -619,6 +676,7 @@ mod tests {
                 priority: 0,
                 is_completed: true,
                 completion_date: Some("2026-02-15T14:00:00".to_owned()),
+                recurrence: None,
             },
             Reminder {
                 identifier: "rem-003".to_owned(),
@@ -629,6 +687,7 @@ mod tests {
                 priority: 1,
                 is_completed: false,
                 completion_date: None,
+                recurrence: None,
             },
         ]
     }
@@ -806,6 +865,50 @@ mod tests {
         assert!(result.content.contains("Include Q4 data"));
     }
 
+    #[test]
+    fn create_reminder_parses_recurrence_and_asks_when_ambiguous() {
+        let tool = make_create_tool();
+        let result = tool
+            .execute(serde_json::json!({"title": "Stand-up", "recurrence": "weekdays at 9"}))
+            .expect("create");
+        assert!(result.success);
+        assert!(result.content.contains("Repeats: every weekday at 9:00 am"));
+        assert!(result.content.contains("T09:00:00"));
+
+        let result = tool
+            .execute(serde_json::json!({"title": "Bins", "recurrence": "every second Tuesday"}))
+            .expect("clarify");
+        let clarification = result.clarification.expect("clarification");
+        assert_eq!(clarification.argument, "recurrence");
+        let values: Vec<&str> = clarification
+            .candidates
+            .iter()
+            .map(|c| c.value.as_str())
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                "every 2 weeks on Tuesday",
+                "every month on the second Tuesday"
+            ]
+        );
+
+        let result = tool
+            .execute(serde_json::json!({"title": "Bins", "recurrence": values[1]}))
+            .expect("create");
+        assert!(result.success);
+        assert!(
+            result
+                .content
+                .contains("Repeats: every month on the second Tuesday")
+        );
+
+        let result = tool
+            .execute(serde_json::json!({"title": "Bins", "recurrence": "now and then"}))
+            .expect("failure");
+        assert!(!result.success);
+    }
+
     #[test]
     fn create_reminder_empty_title_returns_failure() {
         let tool = make_create_tool();
@@ -0,0 +1,852 @@
+//! Archive tools — inspect, create and extract zip and tar archives.
+//!
+//! [`ListArchiveTool`] only reads the archive index and is available in
+//! every mode. [`ArchiveTool`] creates and extracts archives in `Full` mode,
+//! behind approval. Extraction refuses the whole archive up front when any
+//! entry is absolute, climbs out with `..`, or is a link, and the entry count
+//! and unpacked size are capped both from the index and while bytes are
+//! written, so a lying header cannot fill the disk. Archives are always
+//! extracted into a new folder and a failed extraction removes it.
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use super::path_validation::{
+    resolve_workspace_root, validate_read_path_in_workspace, validate_write_path_in_workspace,
+};
+use super::types::{Tool, ToolResult};
+
+/// Most entries an archive may hold.
+pub const MAX_ENTRIES: usize = 10_000;
+/// Unpacked size limit for extracting or creating an archive.
+pub const DEFAULT_MAX_TOTAL_BYTES: u64 = 1024 * 1024 * 1024;
+/// Entries shown by a listing; the totals still cover the whole archive.
+const MAX_LISTED: usize = 200;
+
+/// Archive container, chosen from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    fn detect(path: &Path) -> Result<Self, FaeLlmError> {
+        Self::from_path(path).ok_or_else(|| {
+            FaeLlmError::ToolValidationError(
+                "unsupported archive type: use .zip, .tar, .tar.gz or .tgz".into(),
+            )
+        })
+    }
+}
+
+/// What an archive entry is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    File,
+    Dir,
+    /// Symlinks, hard links and devices; listed but never extracted.
+    Other,
+}
+
+/// One entry of an archive index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    size: u64,
+    kind: EntryKind,
+}
+
+impl Entry {
+    /// Why extracting this entry is refused, if it is.
+    fn problem(&self) -> Option<&'static str> {
+        if self.kind == EntryKind::Other {
+            Some("link or special file")
+        } else if safe_entry_path(&self.name).is_none() {
+            Some("unsafe path")
+        } else {
+            None
+        }
+    }
+}
+
+/// A checked archive index.
+struct Index {
+    entries: Vec<Entry>,
+    total_bytes: u64,
+}
+
+/// Tool that lists the contents of a zip or tar archive.
+///
+/// Arguments (JSON):
+/// - `path` (string, required) — archive to inspect
+///
+/// Available in all modes.
+pub struct ListArchiveTool {
+    workspace_root: PathBuf,
+}
+
+impl ListArchiveTool {
+    /// Create a new ListArchiveTool rooted at the working directory.
+    pub fn new() -> Self {
+        Self::with_workspace_root(resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+
+    /// Create a new ListArchiveTool rooted at a specific workspace path.
+    pub fn with_workspace_root(workspace_root: PathBuf) -> Self {
+        Self { workspace_root }
+    }
+}
+
+impl Default for ListArchiveTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for ListArchiveTool {
+    fn name(&self) -> &str {
+        "list_archive"
+    }
+
+    fn description(&self) -> &str {
+        "List the files inside a .zip, .tar, .tar.gz or .tgz archive without extracting it"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Archive to inspect"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let path = validate_read_path_in_workspace(str_arg(&args, "path")?, &self.workspace_root)?;
+        let format = ArchiveFormat::detect(&path)?;
+        let entries = match read_index(&path, format) {
+            Ok(entries) => entries,
+            Err(message) => return Ok(ToolResult::failure(message)),
+        };
+
+        let total: u64 = entries.iter().map(|e| e.size).sum();
+        let mut report = format!(
+            "{} entries, {} unpacked",
+            entries.len(),
+            format_bytes(total)
+        );
+        for entry in entries.iter().take(MAX_LISTED) {
+            let line = match entry.kind {
+                EntryKind::Dir => format!("\n{}/", entry.name.trim_end_matches('/')),
+                _ => format!("\n{} ({})", entry.name, format_bytes(entry.size)),
+            };
+            report.push_str(&line);
+            if let Some(problem) = entry.problem() {
+                report.push_str(&format!(" [{problem}, will not extract]"));
+            }
+        }
+        if entries.len() > MAX_LISTED {
+            report.push_str(&format!("\n… {} more", entries.len() - MAX_LISTED));
+        }
+        Ok(ToolResult::success(report))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true
+    }
+}
+
+/// Tool that creates and extracts zip and tar archives.
+///
+/// Arguments (JSON):
+/// - `action` (string, required) — `create` or `extract`
+/// - `path` (string, required) — archive to create or extract; the
+///   extension picks the format
+/// - `sources` (array of strings) — files and folders to pack (`create`)
+/// - `destination` (string) — new folder to extract into (`extract`)
+///
+/// Only available in `ToolMode::Full`.
+pub struct ArchiveTool {
+    workspace_root: PathBuf,
+    max_total_bytes: u64,
+}
+
+impl ArchiveTool {
+    /// Create a new ArchiveTool rooted at the working directory.
+    pub fn new() -> Self {
+        Self::with_workspace_root(resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+
+    /// Create a new ArchiveTool rooted at a specific workspace path.
+    pub fn with_workspace_root(workspace_root: PathBuf) -> Self {
+        Self {
+            workspace_root,
+            max_total_bytes: DEFAULT_MAX_TOTAL_BYTES,
+        }
+    }
+
+    /// Cap the unpacked size of extracted and created archives.
+    pub fn with_max_total_bytes(mut self, max_total_bytes: u64) -> Self {
+        self.max_total_bytes = max_total_bytes;
+        self
+    }
+
+    /// Read and check the index of the archive to extract.
+    fn extract_index(&self, archive: &Path, format: ArchiveFormat) -> Result<Index, String> {
+        let entries = read_index(archive, format)?;
+        if let Some((entry, problem)) = entries
+            .iter()
+            .find_map(|entry| entry.problem().map(|p| (entry, p)))
+        {
+            return Err(format!("refusing to extract: {} ({problem})", entry.name));
+        }
+        let total_bytes = entries.iter().map(|e| e.size).sum();
+        if total_bytes > self.max_total_bytes {
+            return Err(format!(
+                "archive unpacks to {}, over the {} limit",
+                format_bytes(total_bytes),
+                format_bytes(self.max_total_bytes)
+            ));
+        }
+        Ok(Index {
+            entries,
+            total_bytes,
+        })
+    }
+
+    /// Collect the files to pack as `(path on disk, name in archive)`.
+    fn create_inputs(&self, sources: &[PathBuf]) -> Result<(Vec<Input>, u64), String> {
+        let mut inputs = Vec::new();
+        let mut total_bytes = 0;
+        for source in sources {
+            let base = source.parent().unwrap_or(Path::new(""));
+            collect_inputs(source, base, &mut inputs, &mut total_bytes)?;
+            if inputs.len() > MAX_ENTRIES {
+                return Err(format!("more than {MAX_ENTRIES} files to pack"));
+            }
+            if total_bytes > self.max_total_bytes {
+                return Err(format!(
+                    "sources exceed the {} limit",
+                    format_bytes(self.max_total_bytes)
+                ));
+            }
+        }
+        Ok((inputs, total_bytes))
+    }
+
+    fn sources(&self, args: &serde_json::Value) -> Result<Vec<PathBuf>, FaeLlmError> {
+        let sources = args
+            .get("sources")
+            .and_then(|v| v.as_array())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| {
+                FaeLlmError::ToolValidationError("create needs a non-empty sources array".into())
+            })?;
+        sources
+            .iter()
+            .map(|s| {
+                let s = s.as_str().ok_or_else(|| {
+                    FaeLlmError::ToolValidationError("sources must be strings".into())
+                })?;
+                validate_read_path_in_workspace(s, &self.workspace_root)
+            })
+            .collect()
+    }
+
+    fn create(&self, args: &serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let output =
+            validate_write_path_in_workspace(str_arg(args, "path")?, &self.workspace_root)?;
+        let format = ArchiveFormat::detect(&output)?;
+        let sources = self.sources(args)?;
+        if std::fs::symlink_metadata(&output).is_ok() {
+            return Ok(ToolResult::failure(format!(
+                "{} already exists",
+                output.display()
+            )));
+        }
+        let (inputs, total_bytes) = match self.create_inputs(&sources) {
+            Ok(found) => found,
+            Err(message) => return Ok(ToolResult::failure(message)),
+        };
+
+        let result =
+            File::create_new(&output).and_then(|file| write_archive(file, format, &inputs));
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&output);
+            return Ok(ToolResult::failure(format!(
+                "failed to create {}: {e}",
+                output.display()
+            )));
+        }
+        Ok(ToolResult::success(format!(
+            "created {} with {} entries ({})",
+            output.display(),
+            inputs.len(),
+            format_bytes(total_bytes)
+        )))
+    }
+
+    fn extract(&self, args: &serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let archive =
+            validate_read_path_in_workspace(str_arg(args, "path")?, &self.workspace_root)?;
+        let format = ArchiveFormat::detect(&archive)?;
+        let destination =
+            validate_write_path_in_workspace(str_arg(args, "destination")?, &self.workspace_root)?;
+        if std::fs::symlink_metadata(&destination).is_ok() {
+            return Ok(ToolResult::failure(format!(
+                "{} already exists; extract into a new folder",
+                destination.display()
+            )));
+        }
+        let index = match self.extract_index(&archive, format) {
+            Ok(index) => index,
+            Err(message) => return Ok(ToolResult::failure(message)),
+        };
+
+        let result = std::fs::create_dir_all(&destination).and_then(|()| {
+            let mut out = Extractor {
+                root: &destination,
+                remaining: self.max_total_bytes,
+            };
+            match format {
+                ArchiveFormat::Zip => out.zip(&archive),
+                ArchiveFormat::Tar => out.tar(File::open(&archive)?),
+                ArchiveFormat::TarGz => {
+                    out.tar(flate2::read::GzDecoder::new(File::open(&archive)?))
+                }
+            }
+        });
+        if let Err(e) = result {
+            let _ = std::fs::remove_dir_all(&destination);
+            return Ok(ToolResult::failure(format!(
+                "failed to extract {}: {e}",
+                archive.display()
+            )));
+        }
+        Ok(ToolResult::success(format!(
+            "extracted {} entries ({}) into {}",
+            index.entries.len(),
+            format_bytes(index.total_bytes),
+            destination.display()
+        )))
+    }
+}
+
+impl Default for ArchiveTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for ArchiveTool {
+    fn name(&self) -> &str {
+        "archive"
+    }
+
+    fn description(&self) -> &str {
+        "Create a .zip, .tar, .tar.gz or .tgz archive from files and folders, or extract one \
+         into a new folder. Use list_archive to look inside first"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["create", "extract"]
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Archive to create or extract; the extension picks the format"
+                },
+                "sources": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Files and folders to pack (create)"
+                },
+                "destination": {
+                    "type": "string",
+                    "description": "New folder to extract into (extract)"
+                }
+            },
+            "required": ["action", "path"]
+        })
+    }
+
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        let path = str_arg(args, "path").ok()?;
+        match str_arg(args, "action").ok()? {
+            "create" => {
+                let sources = self.sources(args).ok()?;
+                Some(match self.create_inputs(&sources) {
+                    Ok((inputs, total)) => format!(
+                        "create {path} with {} entries ({})",
+                        inputs.len(),
+                        format_bytes(total)
+                    ),
+                    Err(e) => format!("create {path}\nwarning: {e}"),
+                })
+            }
+            "extract" => {
+                let destination = str_arg(args, "destination").unwrap_or("?");
+                let archive = validate_read_path_in_workspace(path, &self.workspace_root).ok()?;
+                let format = ArchiveFormat::from_path(&archive)?;
+                Some(match self.extract_index(&archive, format) {
+                    Ok(index) => format!(
+                        "extract {} entries ({}) from {path} into {destination}",
+                        index.entries.len(),
+                        format_bytes(index.total_bytes)
+                    ),
+                    Err(e) => format!("extract {path} into {destination}\nwarning: {e}"),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        match str_arg(&args, "action")? {
+            "create" => self.create(&args),
+            "extract" => self.extract(&args),
+            other => Err(FaeLlmError::ToolValidationError(format!(
+                "unknown action: {other} (use create or extract)"
+            ))),
+        }
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+fn str_arg<'a>(args: &'a serde_json::Value, name: &str) -> Result<&'a str, FaeLlmError> {
+    args.get(name).and_then(|v| v.as_str()).ok_or_else(|| {
+        FaeLlmError::ToolValidationError(format!("missing required argument: {name}"))
+    })
+}
+
+/// `name` as a relative path made only of normal components.
+fn safe_entry_path(name: &str) -> Option<PathBuf> {
+    if name.contains('\\') {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+/// Read the index of `archive`, failing past [`MAX_ENTRIES`] entries.
+fn read_index(archive: &Path, format: ArchiveFormat) -> Result<Vec<Entry>, String> {
+    let file = File::open(archive).map_err(|e| format!("cannot open archive: {e}"))?;
+    let entries = match format {
+        ArchiveFormat::Zip => zip_index(file),
+        ArchiveFormat::Tar => tar_index(file),
+        ArchiveFormat::TarGz => tar_index(flate2::read::GzDecoder::new(file)),
+    };
+    entries.map_err(|e| format!("cannot read archive: {e}"))
+}
+
+fn zip_index(file: File) -> std::io::Result<Vec<Entry>> {
+    let mut zip = zip::ZipArchive::new(file).map_err(std::io::Error::other)?;
+    if zip.len() > MAX_ENTRIES {
+        return Err(too_many_entries());
+    }
+    (0..zip.len())
+        .map(|i| {
+            let entry = zip.by_index_raw(i).map_err(std::io::Error::other)?;
+            let kind = if entry.is_dir() {
+                EntryKind::Dir
+            } else if entry.is_symlink() {
+                EntryKind::Other
+            } else {
+                EntryKind::File
+            };
+            Ok(Entry {
+                name: entry.name().to_owned(),
+                size: entry.size(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+fn tar_index(reader: impl Read) -> std::io::Result<Vec<Entry>> {
+    let mut tar = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for entry in tar.entries()? {
+        let entry = entry?;
+        if entries.len() == MAX_ENTRIES {
+            return Err(too_many_entries());
+        }
+        let kind = match entry.header().entry_type() {
+            tar::EntryType::Regular | tar::EntryType::Continuous => EntryKind::File,
+            tar::EntryType::Directory => EntryKind::Dir,
+            _ => EntryKind::Other,
+        };
+        entries.push(Entry {
+            name: String::from_utf8_lossy(&entry.path_bytes()).into_owned(),
+            size: entry.size(),
+            kind,
+        });
+    }
+    Ok(entries)
+}
+
+fn too_many_entries() -> std::io::Error {
+    std::io::Error::other(format!("more than {MAX_ENTRIES} entries"))
+}
+
+/// Writes entries under `root`, a folder it created, within a byte budget.
+struct Extractor<'a> {
+    root: &'a Path,
+    remaining: u64,
+}
+
+impl Extractor<'_> {
+    fn zip(&mut self, archive: &Path) -> std::io::Result<()> {
+        let mut zip = zip::ZipArchive::new(File::open(archive)?).map_err(std::io::Error::other)?;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i).map_err(std::io::Error::other)?;
+            let name = entry.name().to_owned();
+            let is_dir = entry.is_dir();
+            self.entry(&name, is_dir, &mut entry)?;
+        }
+        Ok(())
+    }
+
+    fn tar(&mut self, reader: impl Read) -> std::io::Result<()> {
+        let mut tar = tar::Archive::new(reader);
+        for entry in tar.entries()? {
+            let mut entry = entry?;
+            let name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+            let is_dir = match entry.header().entry_type() {
+                tar::EntryType::Directory => true,
+                tar::EntryType::Regular | tar::EntryType::Continuous => false,
+                _ => return Err(std::io::Error::other(format!("{name} is a link"))),
+            };
+            self.entry(&name, is_dir, &mut entry)?;
+        }
+        Ok(())
+    }
+
+    fn entry(&mut self, name: &str, is_dir: bool, reader: &mut impl Read) -> std::io::Result<()> {
+        let relative = safe_entry_path(name)
+            .ok_or_else(|| std::io::Error::other(format!("{name} has an unsafe path")))?;
+        let path = self.root.join(relative);
+        if is_dir {
+            return std::fs::create_dir_all(&path);
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.custom_flags(libc::O_NOFOLLOW);
+        }
+        let mut file = options.open(&path)?;
+        let written = std::io::copy(&mut reader.take(self.remaining + 1), &mut file)?;
+        if written > self.remaining {
+            return Err(std::io::Error::other("archive unpacks past the size limit"));
+        }
+        self.remaining -= written;
+        Ok(())
+    }
+}
+
+/// A file or folder to pack.
+struct Input {
+    path: PathBuf,
+    name: String,
+    is_dir: bool,
+}
+
+/// Walk `path`, naming entries relative to `base`. Symlinks are skipped.
+fn collect_inputs(
+    path: &Path,
+    base: &Path,
+    inputs: &mut Vec<Input>,
+    total_bytes: &mut u64,
+) -> Result<(), String> {
+    let meta = std::fs::symlink_metadata(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    if meta.file_type().is_symlink() {
+        return Ok(());
+    }
+    let name = path
+        .strip_prefix(base)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    inputs.push(Input {
+        path: path.to_path_buf(),
+        name,
+        is_dir: meta.is_dir(),
+    });
+    if inputs.len() > MAX_ENTRIES {
+        return Ok(());
+    }
+    if !meta.is_dir() {
+        *total_bytes += meta.len();
+        return Ok(());
+    }
+    let mut children = std::fs::read_dir(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    children.sort();
+    for child in children {
+        collect_inputs(&child, base, inputs, total_bytes)?;
+    }
+    Ok(())
+}
+
+fn write_archive(file: File, format: ArchiveFormat, inputs: &[Input]) -> std::io::Result<()> {
+    match format {
+        ArchiveFormat::Zip => {
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            let mut zip = zip::ZipWriter::new(file);
+            for input in inputs {
+                if input.is_dir {
+                    zip.add_directory(input.name.as_str(), options)
+                        .map_err(std::io::Error::other)?;
+                } else {
+                    zip.start_file(input.name.as_str(), options)
+                        .map_err(std::io::Error::other)?;
+                    std::io::copy(&mut File::open(&input.path)?, &mut zip)?;
+                }
+            }
+            zip.finish().map_err(std::io::Error::other)?.sync_all()
+        }
+        ArchiveFormat::Tar => write_tar(tar::Builder::new(file), inputs)?.sync_all(),
+        ArchiveFormat::TarGz => {
+            let gz = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            write_tar(tar::Builder::new(gz), inputs)?
+                .finish()?
+                .sync_all()
+        }
+    }
+}
+
+fn write_tar<W: Write>(mut tar: tar::Builder<W>, inputs: &[Input]) -> std::io::Result<W> {
+    for input in inputs {
+        if input.is_dir {
+            tar.append_dir(&input.name, &input.path)?;
+        } else {
+            tar.append_path_with_name(&input.path, &input.name)?;
+        }
+    }
+    tar.into_inner()
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    const GB: u64 = 1024 * MB;
+    match bytes {
+        0..KB => format!("{bytes} B"),
+        KB..MB => format!("{:.1} KB", bytes as f64 / KB as f64),
+        MB..GB => format!("{:.1} MB", bytes as f64 / MB as f64),
+        _ => format!("{:.1} GB", bytes as f64 / GB as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("project/src")).unwrap();
+        std::fs::write(dir.path().join("project/README.md"), "hello").unwrap();
+        std::fs::write(dir.path().join("project/src/main.rs"), "fn main() {}").unwrap();
+        dir
+    }
+
+    fn path(dir: &tempfile::TempDir, rel: &str) -> String {
+        dir.path().join(rel).to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn format_follows_the_extension() {
+        let format = |name: &str| ArchiveFormat::from_path(Path::new(name));
+        assert_eq!(format("a.ZIP"), Some(ArchiveFormat::Zip));
+        assert_eq!(format("a.tar"), Some(ArchiveFormat::Tar));
+        assert_eq!(format("a.tar.gz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(format("a.tgz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(format("a.rar"), None);
+    }
+
+    #[test]
+    fn unsafe_entry_paths_are_rejected() {
+        assert_eq!(
+            safe_entry_path("./docs/a.txt"),
+            Some(PathBuf::from("docs/a.txt"))
+        );
+        assert_eq!(safe_entry_path("../evil"), None);
+        assert_eq!(safe_entry_path("docs/../../evil"), None);
+        assert_eq!(safe_entry_path("/etc/passwd"), None);
+        assert_eq!(safe_entry_path("..\\evil"), None);
+        assert_eq!(safe_entry_path("."), None);
+    }
+
+    #[test]
+    fn create_list_and_extract_round_trip() {
+        for name in ["out.zip", "out.tar", "out.tar.gz"] {
+            let dir = workspace();
+            let tool = ArchiveTool::with_workspace_root(dir.path().to_path_buf());
+            let created = tool
+                .execute(serde_json::json!({
+                    "action": "create",
+                    "path": path(&dir, name),
+                    "sources": [path(&dir, "project")]
+                }))
+                .unwrap();
+            assert!(created.success, "{name}: {:?}", created.error);
+            assert!(created.content.contains("with 4 entries"), "{name}");
+
+            let listed = ListArchiveTool::with_workspace_root(dir.path().to_path_buf())
+                .execute(serde_json::json!({"path": path(&dir, name)}))
+                .unwrap();
+            assert!(
+                listed.content.starts_with("4 entries, 17 B unpacked"),
+                "{name}"
+            );
+            assert!(
+                listed.content.contains("project/src/main.rs (12 B)"),
+                "{name}"
+            );
+
+            let extracted = tool
+                .execute(serde_json::json!({
+                    "action": "extract",
+                    "path": path(&dir, name),
+                    "destination": path(&dir, "unpacked")
+                }))
+                .unwrap();
+            assert!(extracted.success, "{name}: {:?}", extracted.error);
+            assert_eq!(
+                std::fs::read_to_string(dir.path().join("unpacked/project/src/main.rs")).unwrap(),
+                "fn main() {}"
+            );
+        }
+    }
+
+    #[test]
+    fn traversal_entries_refuse_the_whole_archive() {
+        let dir = workspace();
+        let archive = dir.path().join("evil.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("fine.txt", options).unwrap();
+        zip.write_all(b"ok").unwrap();
+        zip.start_file("../escaped.txt", options).unwrap();
+        zip.write_all(b"evil").unwrap();
+        zip.finish().unwrap();
+
+        let listed = ListArchiveTool::with_workspace_root(dir.path().to_path_buf())
+            .execute(serde_json::json!({"path": path(&dir, "evil.zip")}))
+            .unwrap();
+        assert!(listed.content.contains("[unsafe path, will not extract]"));
+
+        let tool = ArchiveTool::with_workspace_root(dir.path().to_path_buf());
+        let result = tool
+            .execute(serde_json::json!({
+                "action": "extract",
+                "path": path(&dir, "evil.zip"),
+                "destination": path(&dir, "out")
+            }))
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("refusing to extract"));
+        assert!(!dir.path().join("out").exists());
+        assert!(!dir.path().join("escaped.txt").exists());
+    }
+
+    #[test]
+    fn size_limit_and_existing_targets_are_enforced() {
+        let dir = workspace();
+        let tool = ArchiveTool::with_workspace_root(dir.path().to_path_buf());
+        let create = |name: &str| {
+            serde_json::json!({
+                "action": "create",
+                "path": path(&dir, name),
+                "sources": [path(&dir, "project")]
+            })
+        };
+        assert!(tool.execute(create("a.zip")).unwrap().success);
+        assert!(!tool.execute(create("a.zip")).unwrap().success);
+        assert!(tool.execute(create("a.rar")).is_err());
+
+        let small =
+            ArchiveTool::with_workspace_root(dir.path().to_path_buf()).with_max_total_bytes(10);
+        assert!(!small.execute(create("b.zip")).unwrap().success);
+        let extract = serde_json::json!({
+            "action": "extract",
+            "path": path(&dir, "a.zip"),
+            "destination": path(&dir, "out")
+        });
+        let result = small.execute(extract.clone()).unwrap();
+        assert!(result.error.unwrap().contains("over the 10 B limit"));
+
+        std::fs::create_dir(dir.path().join("out")).unwrap();
+        let result = tool.execute(extract).unwrap();
+        assert!(result.error.unwrap().contains("already exists"));
+    }
+
+    #[test]
+    fn approval_preview_summarises_the_change() {
+        let dir = workspace();
+        let tool = ArchiveTool::with_workspace_root(dir.path().to_path_buf());
+        let preview = tool
+            .approval_preview(&serde_json::json!({
+                "action": "create",
+                "path": "out.zip",
+                "sources": [path(&dir, "project")]
+            }))
+            .unwrap();
+        assert_eq!(preview, "create out.zip with 4 entries (17 B)");
+    }
+
+    #[test]
+    fn listing_is_read_only_and_archiving_needs_full_mode() {
+        assert!(ListArchiveTool::new().allowed_in_mode(ToolMode::ReadOnly));
+        assert!(!ArchiveTool::new().allowed_in_mode(ToolMode::ReadOnly));
+        assert!(ArchiveTool::new().allowed_in_mode(ToolMode::Full));
+    }
+}
@@ -1,9 +1,12 @@
 //! Bash tool — executes shell commands with timeout and bounded output.
 
+use std::sync::Arc;
+
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
 use crate::fae_llm::tools::input_sanitize::sanitize_command_input;
 
+use super::network_policy::NetworkGuard;
 use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
 
 /// Default command timeout in seconds.
@@ -19,6 +22,7 @@ const DEFAULT_TIMEOUT_SECS: u64 = 30;
 pub struct BashTool {
     max_bytes: usize,
     timeout_secs: u64,
+    network: Option<Arc<NetworkGuard>>,
 }
 
 impl BashTool {
@@ -27,6 +31,7 @@ impl BashTool {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
             timeout_secs: DEFAULT_TIMEOUT_SECS,
+            network: None,
         }
     }
 
@@ -35,8 +40,19 @@ impl BashTool {
         Self {
             max_bytes,
             timeout_secs,
+            network: None,
         }
     }
+
+    /// Check URLs in each command against `guard` and expose the policy to
+    /// the shell through `FAE_NETWORK_ALLOW` / `FAE_NETWORK_DENY`.
+    ///
+    /// Advisory only: a command that builds its URL at runtime is not
+    /// checked, and the shell is not kept off the network.
+    pub fn with_network_guard(mut self, guard: Arc<NetworkGuard>) -> Self {
+        self.network = Some(guard);
+        self
+    }
 }
 
 impl Default for BashTool {
@@ -95,6 +111,10 @@ impl Tool for BashTool {
         }
         let command = sanitized.content;
 
+        if let Some(guard) = &self.network {
+            guard.check_text(self.name(), &command)?;
+        }
+
         let timeout_secs = args
             .get("timeout")
             .and_then(|v| v.as_u64())
@@ -118,6 +138,11 @@ impl Tool for BashTool {
             cmd.env("FAE_CONFIG_DIR", crate::fae_dirs::config_dir());
             cmd.env("FAE_CACHE_DIR", crate::fae_dirs::cache_dir());
         }
+        if let Some(guard) = &self.network {
+            for (key, value) in guard.policy().env_vars() {
+                cmd.env(key, value);
+            }
+        }
 
         let mut child = match cmd.spawn() {
             Ok(child) => child,
@@ -322,6 +347,28 @@ mod tests {
         assert!(result.content.contains("line3"));
     }
 
+    #[test]
+    fn bash_network_policy_blocks_urls_and_sets_env() {
+        let guard = NetworkGuard::new(crate::fae_llm::tools::NetworkPolicy {
+            deny_domains: vec!["blocked.test".into()],
+            ..Default::default()
+        });
+        let tool = BashTool::new().with_network_guard(Arc::new(guard));
+
+        let blocked = tool.execute(serde_json::json!({
+            "command": "echo https://api.blocked.test/data"
+        }));
+        assert!(blocked.is_err(), "denied domain should be rejected");
+
+        let result = match tool.execute(serde_json::json!({
+            "command": "printenv FAE_NETWORK_DENY"
+        })) {
+            Ok(r) => r,
+            Err(_) => unreachable!("printenv should succeed"),
+        };
+        assert_eq!(result.content.trim(), "blocked.test");
+    }
+
     #[test]
     fn bash_sandbox_env_vars_injected_when_sandboxed() {
         // Simulate sandbox by setting the sentinel env var.
@@ -0,0 +1,316 @@
+//! Skill authoring tool — turns "learn this as a skill: …" into a managed
+//! skill.
+//!
+//! The model drafts the skill from the conversation; [`CreateSkillTool`]
+//! renders it as skill markdown, shows that markdown in the approval prompt
+//! and installs it through [`crate::skills::install_skill_draft`], so the
+//! draft gets the same lint as a packaged skill. After a successful call the
+//! agent rebuilds its system prompt, and the skill applies from the next
+//! reply.
+//!
+//! # Arguments (JSON)
+//!
+//! ```json
+//! {
+//!   "name":         "Invoices",
+//!   "triggers":     ["invoice", "billing"],
+//!   "instructions": "Search my knowledge base before answering.",
+//!   "tools":        ["web_search"]
+//! }
+//! ```
+//!
+//! `tools` is optional.
+
+use std::collections::BTreeSet;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::skills::{ManagedSkillState, SkillDraft, SkillPaths};
+
+use super::types::{Tool, ToolResult};
+
+/// Longest skill name accepted, in characters.
+const MAX_NAME_CHARS: usize = 64;
+/// Most trigger phrases one skill may declare.
+const MAX_TRIGGERS: usize = 10;
+
+/// Tool that drafts and installs a managed skill.
+///
+/// # Mode gating
+///
+/// Only available in [`ToolMode::Full`] — not in `ReadOnly`.
+pub struct CreateSkillTool {
+    paths: SkillPaths,
+    /// Tool names a drafted skill may refer to; empty skips the check.
+    known_tools: BTreeSet<String>,
+}
+
+impl CreateSkillTool {
+    /// Create a tool that installs into `paths`.
+    pub fn new(paths: SkillPaths) -> Self {
+        Self {
+            paths,
+            known_tools: BTreeSet::new(),
+        }
+    }
+
+    /// Create a tool that installs into the default skills directory.
+    pub fn with_default_dir() -> Self {
+        Self::new(crate::skills::default_paths())
+    }
+
+    /// Check drafted skills against these tool names.
+    pub fn with_known_tools(mut self, known_tools: BTreeSet<String>) -> Self {
+        self.known_tools = known_tools;
+        self
+    }
+}
+
+impl Tool for CreateSkillTool {
+    fn name(&self) -> &str {
+        "create_skill"
+    }
+
+    fn description(&self) -> &str {
+        "Save standing instructions as a skill when the user asks you to learn or remember \
+         how to handle something. The skill is added to your instructions for every later \
+         conversation"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "maxLength": MAX_NAME_CHARS,
+                    "description": "Short title, e.g. \"Invoices\""
+                },
+                "triggers": {
+                    "type": "array",
+                    "minItems": 1,
+                    "maxItems": MAX_TRIGGERS,
+                    "items": {"type": "string"},
+                    "description": "Topics or phrases the skill applies to"
+                },
+                "instructions": {
+                    "type": "string",
+                    "description": "What to do, as markdown. Refer to tools as `name` tool"
+                },
+                "tools": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Tools the instructions rely on"
+                }
+            },
+            "required": ["name", "triggers", "instructions"]
+        })
+    }
+
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        Some(match parse_draft(args) {
+            Ok(draft) => format!("install skill `{}`:\n\n{}", draft.id, draft.content),
+            Err(e) => format!("skill will be rejected: {e}"),
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let draft = parse_draft(&args)?;
+        let info = match crate::skills::install_skill_draft_at(
+            &self.paths,
+            &draft,
+            &self.known_tools,
+            "skill.managed.create",
+        ) {
+            Ok(info) => info,
+            Err(e) => return Ok(ToolResult::failure(format!("cannot install skill: {e}"))),
+        };
+        if info.state != ManagedSkillState::Active {
+            return Ok(ToolResult::failure(format!(
+                "skill `{}` was saved but not activated: {}",
+                info.id,
+                info.last_error.as_deref().unwrap_or("unknown reason")
+            )));
+        }
+        Ok(ToolResult::success(format!(
+            "Installed skill `{}`. It applies from your next reply.",
+            info.id
+        )))
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        matches!(mode, ToolMode::Full)
+    }
+}
+
+/// Build the skill the arguments describe.
+fn parse_draft(args: &serde_json::Value) -> Result<SkillDraft, FaeLlmError> {
+    let invalid = |message: &str| FaeLlmError::ToolValidationError(message.to_owned());
+
+    let name = args
+        .get("name")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| invalid("missing `name`"))?;
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(invalid("`name` is too long"));
+    }
+    let id = skill_id(name).ok_or_else(|| invalid("`name` needs letters or digits"))?;
+
+    let triggers = string_list(args, "triggers")?;
+    if triggers.is_empty() {
+        return Err(invalid("`triggers` needs at least one phrase"));
+    }
+    if triggers.len() > MAX_TRIGGERS {
+        return Err(invalid("too many `triggers`"));
+    }
+
+    let instructions = args
+        .get("instructions")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .ok_or_else(|| invalid("missing `instructions`"))?;
+    let tools = string_list(args, "tools")?;
+
+    let content = format!(
+        "# {name}\n\nUse this skill when the user asks about: {}.\n\n{instructions}\n",
+        triggers.join(", ")
+    );
+    Ok(SkillDraft {
+        id,
+        name: name.to_owned(),
+        version: "0.1.0".to_owned(),
+        content,
+        triggers,
+        tools,
+    })
+}
+
+/// Non-empty trimmed strings from the array at `key`; missing is empty.
+fn string_list(args: &serde_json::Value, key: &str) -> Result<Vec<String>, FaeLlmError> {
+    let Some(value) = args.get(key) else {
+        return Ok(Vec::new());
+    };
+    let items = value
+        .as_array()
+        .ok_or_else(|| FaeLlmError::ToolValidationError(format!("`{key}` must be an array")))?;
+    items
+        .iter()
+        .map(|item| {
+            item.as_str().map(|s| s.trim().to_owned()).ok_or_else(|| {
+                FaeLlmError::ToolValidationError(format!("`{key}` must contain strings"))
+            })
+        })
+        .filter(|item| !matches!(item, Ok(s) if s.is_empty()))
+        .collect()
+}
+
+/// Skill id from a display name: lowercase ASCII words joined by `-`.
+fn skill_id(name: &str) -> Option<String> {
+    let id = name
+        .to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    (!id.is_empty()).then_some(id)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn temp_paths() -> (tempfile::TempDir, SkillPaths) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = SkillPaths::for_root(dir.path().to_path_buf());
+        (dir, paths)
+    }
+
+    fn tool(paths: SkillPaths) -> CreateSkillTool {
+        CreateSkillTool::new(paths)
+            .with_known_tools(["web_search", "read"].map(str::to_owned).into())
+    }
+
+    fn invoice_args() -> serde_json::Value {
+        serde_json::json!({
+            "name": "Invoices & Billing",
+            "triggers": ["invoices", " "],
+            "instructions": "Use the `web_search` tool before answering.",
+            "tools": ["read"]
+        })
+    }
+
+    #[test]
+    fn preview_shows_drafted_markdown() {
+        let (_dir, paths) = temp_paths();
+        let preview = tool(paths)
+            .approval_preview(&invoice_args())
+            .expect("preview");
+        assert_eq!(
+            preview,
+            "install skill `invoices-billing`:\n\n# Invoices & Billing\n\n\
+             Use this skill when the user asks about: invoices.\n\n\
+             Use the `web_search` tool before answering.\n"
+        );
+    }
+
+    #[test]
+    fn execute_installs_active_skill() {
+        let (_dir, paths) = temp_paths();
+        let result = tool(paths.clone())
+            .execute(invoice_args())
+            .expect("execute");
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.content.contains("`invoices-billing`"));
+
+        let content =
+            std::fs::read_to_string(paths.root.join("invoices-billing.md")).expect("skill file");
+        assert!(content.starts_with("# Invoices & Billing"));
+    }
+
+    #[test]
+    fn skill_failing_lint_is_reported_and_not_activated() {
+        let (_dir, paths) = temp_paths();
+        let args = serde_json::json!({
+            "name": "Invoices",
+            "triggers": ["invoices"],
+            "instructions": "Call the `knowledge_search` tool first.",
+        });
+        let result = tool(paths.clone()).execute(args).expect("execute");
+        assert!(!result.success);
+        let error = result.error.expect("error");
+        assert!(error.contains("unknown tool `knowledge_search`"), "{error}");
+        assert!(!paths.root.join("invoices.md").exists());
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        let (_dir, paths) = temp_paths();
+        let tool = tool(paths);
+        for args in [
+            serde_json::json!({"name": "!!", "triggers": ["x"], "instructions": "y"}),
+            serde_json::json!({"name": "Notes", "triggers": [], "instructions": "y"}),
+            serde_json::json!({"name": "Notes", "triggers": ["x"], "instructions": " "}),
+            serde_json::json!({"name": "Notes", "triggers": "x", "instructions": "y"}),
+        ] {
+            assert!(tool.execute(args.clone()).is_err(), "{args}");
+            assert!(
+                tool.approval_preview(&args)
+                    .expect("preview")
+                    .starts_with("skill will be rejected")
+            );
+        }
+    }
+
+    #[test]
+    fn only_allowed_in_full_mode() {
+        let (_dir, paths) = temp_paths();
+        let tool = CreateSkillTool::new(paths);
+        assert!(tool.allowed_in_mode(ToolMode::Full));
+        assert!(!tool.allowed_in_mode(ToolMode::ReadOnly));
+    }
+}
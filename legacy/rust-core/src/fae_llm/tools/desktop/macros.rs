@@ -0,0 +1,321 @@
+//! Recorded desktop macros.
+//!
+//! `record_start` makes [`DesktopTool`](super::DesktopTool) remember every
+//! action it runs successfully until `record_stop`, which saves the steps as
+//! a named [`DesktopMacro`] under `data_dir()/desktop_macros/`. Literal
+//! values can be turned into parameters when recording stops (`"params":
+//! {"file": "report.pdf"}` stores `{{file}}` in place of `report.pdf`), and
+//! `replay_macro` fills them in again.
+//!
+//! A replay is a single desktop tool call, so it is approved like any other
+//! call; the approval preview lists every step that will run.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fae_llm::error::FaeLlmError;
+use crate::time_util::now_epoch_secs;
+
+/// Most steps one macro may hold.
+pub const MAX_MACRO_STEPS: usize = 100;
+
+/// A named, replayable sequence of desktop actions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesktopMacro {
+    /// Normalized macro name (see [`normalize_name`]).
+    pub name: String,
+    /// Timestamp when recording stopped.
+    pub created_secs: u64,
+    /// Desktop tool arguments, one object per step, in order.
+    pub steps: Vec<serde_json::Value>,
+}
+
+impl DesktopMacro {
+    /// Parameter names referenced by `{{name}}` placeholders in the steps.
+    pub fn params(&self) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        for step in &self.steps {
+            visit_strings(step, &mut |s| {
+                let mut rest = s;
+                while let Some(start) = rest.find("{{") {
+                    let Some(len) = rest[start + 2..].find("}}") else {
+                        break;
+                    };
+                    names.insert(rest[start + 2..start + 2 + len].trim().to_owned());
+                    rest = &rest[start + 2 + len + 2..];
+                }
+            });
+        }
+        names
+    }
+
+    /// The steps with every `{{name}}` placeholder replaced from `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FaeLlmError::ToolValidationError`] naming any placeholder
+    /// without a value.
+    pub fn expand(
+        &self,
+        params: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Vec<serde_json::Value>, FaeLlmError> {
+        let missing: Vec<String> = self
+            .params()
+            .into_iter()
+            .filter(|name| !params.contains_key(name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(FaeLlmError::ToolValidationError(format!(
+                "macro '{}' needs params: {}",
+                self.name,
+                missing.join(", ")
+            )));
+        }
+        Ok(self
+            .steps
+            .iter()
+            .map(|step| {
+                map_strings(step, &|s| {
+                    params.iter().fold(s.to_owned(), |acc, (name, value)| {
+                        let value = match value {
+                            serde_json::Value::String(text) => text.clone(),
+                            other => other.to_string(),
+                        };
+                        acc.replace(&format!("{{{{{name}}}}}"), &value)
+                    })
+                })
+            })
+            .collect())
+    }
+
+    /// Replace literal `value`s in the steps with `{{name}}` placeholders.
+    pub fn parameterize(&mut self, params: &serde_json::Map<String, serde_json::Value>) {
+        for (name, value) in params {
+            let Some(value) = value.as_str().filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            let placeholder = format!("{{{{{name}}}}}");
+            for step in &mut self.steps {
+                *step = map_strings(step, &|s| s.replace(value, &placeholder));
+            }
+        }
+    }
+}
+
+/// Normalize a spoken or typed macro name into a file-safe slug.
+///
+/// "Screenshot and File" becomes `screenshot-and-file`.
+///
+/// # Errors
+///
+/// Returns [`FaeLlmError::ToolValidationError`] when nothing usable is left.
+pub fn normalize_name(name: &str) -> Result<String, FaeLlmError> {
+    let slug = name
+        .trim()
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() || slug.len() > 64 {
+        return Err(FaeLlmError::ToolValidationError(format!(
+            "invalid macro name: '{name}'"
+        )));
+    }
+    Ok(slug)
+}
+
+/// A macro being recorded.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    /// Normalized name it will be saved under.
+    pub name: String,
+    /// Steps recorded so far.
+    pub steps: Vec<serde_json::Value>,
+}
+
+impl Recording {
+    /// Finish the recording as a macro.
+    pub fn finish(self) -> DesktopMacro {
+        DesktopMacro {
+            name: self.name,
+            created_secs: now_epoch_secs(),
+            steps: self.steps,
+        }
+    }
+}
+
+/// On-disk store of desktop macros, one JSON file per macro.
+#[derive(Debug, Clone)]
+pub struct MacroStore {
+    dir: PathBuf,
+}
+
+impl MacroStore {
+    /// Create a store in `dir`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The store under `data_dir()/desktop_macros/`.
+    pub fn default_location() -> Self {
+        Self::new(crate::fae_dirs::desktop_macros_dir())
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+
+    /// Save `recorded`, replacing any macro with the same name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store directory or file cannot be written.
+    pub fn save(&self, recorded: &DesktopMacro) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(recorded).map_err(std::io::Error::other)?;
+        super::super::edit::write_atomic(&self.path(&recorded.name), &json)
+    }
+
+    /// Load the macro called `name`, or `None` if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(&self, name: &str) -> std::io::Result<Option<DesktopMacro>> {
+        match std::fs::read_to_string(self.path(name)) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(std::io::Error::other),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// All stored macros, sorted by name. Unreadable files are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store directory exists but cannot be listed.
+    pub fn list(&self) -> std::io::Result<Vec<DesktopMacro>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut macros: Vec<DesktopMacro> = entries
+            .flatten()
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+            .filter_map(|json| serde_json::from_str(&json).ok())
+            .collect();
+        macros.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(macros)
+    }
+}
+
+fn visit_strings(value: &serde_json::Value, f: &mut impl FnMut(&str)) {
+    match value {
+        serde_json::Value::String(s) => f(s),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| visit_strings(v, f)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| visit_strings(v, f)),
+        _ => {}
+    }
+}
+
+/// Copy `value` with `f` applied to every string except the `action` name.
+fn map_strings(value: &serde_json::Value, f: &impl Fn(&str) -> String) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => serde_json::Value::String(f(s)),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(|v| map_strings(v, f)).collect())
+        }
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let v = if k == "action" {
+                        v.clone()
+                    } else {
+                        map_strings(v, f)
+                    };
+                    (k.clone(), v)
+                })
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use serde_json::json;
+
+    fn routine() -> DesktopMacro {
+        DesktopMacro {
+            name: "screenshot-and-file".to_owned(),
+            created_secs: 0,
+            steps: vec![
+                json!({ "action": "screenshot", "app": "Preview" }),
+                json!({ "action": "hotkey", "keys": ["cmd", "s"] }),
+                json!({ "action": "type", "text": "report.pdf" }),
+                json!({ "action": "press", "key": "return" }),
+            ],
+        }
+    }
+
+    #[test]
+    fn names_are_normalized() {
+        assert_eq!(
+            normalize_name(" Screenshot and File ").unwrap(),
+            "screenshot-and-file"
+        );
+        assert_eq!(normalize_name("../etc/passwd").unwrap(), "etc-passwd");
+        assert!(normalize_name("  /  ").is_err());
+    }
+
+    #[test]
+    fn parameterized_steps_expand_with_new_values() {
+        let mut recorded = routine();
+        let params = json!({ "file": "report.pdf", "app": "Preview" });
+        recorded.parameterize(params.as_object().unwrap());
+        assert_eq!(
+            recorded.steps[2],
+            json!({ "action": "type", "text": "{{file}}" })
+        );
+        assert_eq!(
+            recorded.params().into_iter().collect::<Vec<_>>(),
+            ["app", "file"]
+        );
+
+        let values = json!({ "file": "invoice.pdf", "app": "Safari" });
+        let steps = recorded.expand(values.as_object().unwrap()).unwrap();
+        assert_eq!(steps[0], json!({ "action": "screenshot", "app": "Safari" }));
+        assert_eq!(steps[2], json!({ "action": "type", "text": "invoice.pdf" }));
+        assert_eq!(steps[3], json!({ "action": "press", "key": "return" }));
+    }
+
+    #[test]
+    fn missing_params_are_reported() {
+        let mut recorded = routine();
+        recorded.parameterize(json!({ "file": "report.pdf" }).as_object().unwrap());
+        let err = recorded.expand(&serde_json::Map::new()).unwrap_err();
+        assert!(err.to_string().contains("file"));
+    }
+
+    #[test]
+    fn store_round_trips_macros() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MacroStore::new(dir.path().join("desktop_macros"));
+        assert!(store.list().unwrap().is_empty());
+        assert_eq!(store.load("screenshot-and-file").unwrap(), None);
+
+        store.save(&routine()).unwrap();
+        assert_eq!(store.load("screenshot-and-file").unwrap(), Some(routine()));
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+}
@@ -5,6 +5,15 @@
 //!
 //! - **macOS**: [`PeekabooBackend`](peekaboo::PeekabooBackend) via the `peekaboo` CLI
 //! - **Linux/X11**: `XdotoolBackend` via `xdotool` + `scrot`
+//! - **Windows**: `PowerShellBackend` via `powershell.exe` (UI Automation,
+//!   `SendKeys`, `user32`)
+//!
+//! The `find_elements` action returns an accessibility tree snapshot
+//! ([`UiElement`]: role, label, bounds) so the model can locate controls
+//! instead of guessing coordinates.
+//!
+//! Successful actions can be recorded into named macros and replayed later
+//! with different parameters (see [`macros`]).
 //!
 //! Only available in `ToolMode::Full`.
 
@@ -14,10 +23,22 @@ pub mod peekaboo;
 #[cfg(target_os = "linux")]
 pub mod xdotool;
 
+// Script generation is tested on every platform.
+#[cfg(any(target_os = "windows", test))]
+pub mod powershell;
+
+pub mod macros;
+
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
 
 use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
+use macros::{DesktopMacro, MAX_MACRO_STEPS, MacroStore, Recording};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Most elements returned by one `find_elements` call.
+const MAX_ELEMENTS: usize = 150;
 
 // ── Common types ────────────────────────────────────────────────
 
@@ -53,6 +74,13 @@ pub enum DesktopAction {
     ListApps,
     /// Launch an application by name.
     LaunchApp { name: String },
+    /// Snapshot the accessibility tree, optionally scoped to an application
+    /// and filtered by role and label (case-insensitive substrings).
+    FindElements {
+        app: Option<String>,
+        role: Option<String>,
+        label: Option<String>,
+    },
     /// Raw platform-specific command passthrough.
     Raw { command: String },
 }
@@ -66,6 +94,103 @@ pub struct DesktopResult {
     pub screenshot_path: Option<String>,
 }
 
+/// Screen rectangle of a UI element, in screen points.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ElementBounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ElementBounds {
+    /// Centre point, where a coordinate click lands on the element.
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+}
+
+/// One node of an accessibility tree snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiElement {
+    /// Platform role (`AXButton`, `push button`, `Button`).
+    pub role: String,
+    /// Accessible name or title.
+    pub label: String,
+    pub bounds: ElementBounds,
+}
+
+/// Format a `find_elements` result: visible elements matching `role` and
+/// `label`, at most [`MAX_ELEMENTS`], each with its click centre.
+pub fn elements_result(
+    elements: Vec<UiElement>,
+    role: Option<&str>,
+    label: Option<&str>,
+) -> DesktopResult {
+    let matches = |value: &str, filter: Option<&str>| {
+        filter.is_none_or(|f| value.to_lowercase().contains(&f.to_lowercase()))
+    };
+    let found: Vec<UiElement> = elements
+        .into_iter()
+        .filter(|e| e.bounds.width > 0.0 && e.bounds.height > 0.0)
+        .filter(|e| matches(&e.role, role) && matches(&e.label, label))
+        .collect();
+    let listed: Vec<serde_json::Value> = found
+        .iter()
+        .take(MAX_ELEMENTS)
+        .map(|e| {
+            let (cx, cy) = e.bounds.center();
+            serde_json::json!({
+                "role": e.role,
+                "label": e.label,
+                "bounds": e.bounds,
+                "center": { "x": cx.round(), "y": cy.round() },
+            })
+        })
+        .collect();
+    let output = serde_json::json!({
+        "count": found.len(),
+        "truncated": found.len() > MAX_ELEMENTS,
+        "elements": listed,
+    });
+    DesktopResult {
+        output: output.to_string(),
+        screenshot_path: None,
+    }
+}
+
+/// Parse a JSON array of `{role, label, x, y, width, height}` objects, the
+/// shape printed by the AT-SPI and UI Automation helper scripts.
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "windows", test)),
+    allow(dead_code)
+)]
+fn parse_flat_elements(output: &str) -> Result<Vec<UiElement>, serde_json::Error> {
+    #[derive(Deserialize)]
+    struct Flat {
+        role: String,
+        label: String,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    }
+    let flat: Vec<Flat> = serde_json::from_str(output.trim())?;
+    Ok(flat
+        .into_iter()
+        .map(|f| UiElement {
+            role: f.role,
+            label: f.label,
+            bounds: ElementBounds {
+                x: f.x,
+                y: f.y,
+                width: f.width,
+                height: f.height,
+            },
+        })
+        .collect())
+}
+
 /// Platform backend trait for desktop automation.
 ///
 /// Implementations wrap a platform-specific CLI tool and translate
@@ -107,6 +232,14 @@ pub fn detect_backend() -> Option<Box<dyn DesktopBackend>> {
         }
     }
 
+    #[cfg(target_os = "windows")]
+    {
+        let ps = powershell::PowerShellBackend::new();
+        if ps.is_available() {
+            return Some(Box::new(ps));
+        }
+    }
+
     None
 }
 
@@ -129,10 +262,16 @@ pub fn install_instructions() -> &'static str {
          Install xdotool: sudo apt install xdotool scrot\n\
          (X11 session required; Wayland support is experimental.)"
     }
-    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    #[cfg(target_os = "windows")]
+    {
+        "No desktop automation backend found.\n\
+         Desktop automation on Windows needs Windows PowerShell (powershell.exe) on the PATH."
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
         "No desktop automation backend found.\n\
-         Desktop automation is currently supported on macOS (Peekaboo) and Linux (xdotool)."
+         Desktop automation is currently supported on macOS (Peekaboo), Linux (xdotool) \
+         and Windows (PowerShell)."
     }
 }
 
@@ -232,6 +371,19 @@ fn parse_action(args: &serde_json::Value) -> Result<DesktopAction, FaeLlmError>
                 name: name.to_string(),
             })
         }
+        "find_elements" => {
+            let field = |name: &str| {
+                args.get(name)
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.trim().is_empty())
+                    .map(String::from)
+            };
+            Ok(DesktopAction::FindElements {
+                app: field("app"),
+                role: field("role"),
+                label: field("label"),
+            })
+        }
         "raw" => {
             let command = args
                 .get("command")
@@ -247,7 +399,8 @@ fn parse_action(args: &serde_json::Value) -> Result<DesktopAction, FaeLlmError>
         }
         other => Err(FaeLlmError::ToolValidationError(format!(
             "unknown desktop action: '{other}'. Valid actions: screenshot, click, type, \
-             press, hotkey, scroll, list_windows, focus_window, list_apps, launch_app, raw"
+             press, hotkey, scroll, list_windows, focus_window, list_apps, launch_app, \
+             find_elements, raw, record_start, record_stop, replay_macro, list_macros"
         ))),
     }
 }
@@ -262,6 +415,8 @@ fn parse_action(args: &serde_json::Value) -> Result<DesktopAction, FaeLlmError>
 pub struct DesktopTool {
     backend: Box<dyn DesktopBackend>,
     max_bytes: usize,
+    macros: MacroStore,
+    recording: Mutex<Option<Recording>>,
 }
 
 impl DesktopTool {
@@ -272,6 +427,8 @@ impl DesktopTool {
         detect_backend().map(|backend| Self {
             backend,
             max_bytes: DEFAULT_MAX_BYTES,
+            macros: MacroStore::default_location(),
+            recording: Mutex::new(None),
         })
     }
 
@@ -281,17 +438,204 @@ impl DesktopTool {
         Self {
             backend,
             max_bytes: DEFAULT_MAX_BYTES,
+            macros: MacroStore::default_location(),
+            recording: Mutex::new(None),
+        }
+    }
+
+    /// Store macros in `store` instead of the default location.
+    #[cfg(test)]
+    pub fn with_macro_store(mut self, store: MacroStore) -> Self {
+        self.macros = store;
+        self
+    }
+
+    /// Run one action, folding the screenshot path into the output.
+    fn run(&self, action: &DesktopAction) -> Result<String, String> {
+        let result = self.backend.execute(action)?;
+        let mut output = result.output;
+        if let Some(path) = &result.screenshot_path {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            output.push_str(&format!("Screenshot saved: {path}"));
+        }
+        Ok(output)
+    }
+
+    /// Append `steps` to the macro being recorded, if any.
+    fn record(&self, steps: &[serde_json::Value]) {
+        let mut recording = self.recording.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(recording) = recording.as_mut() {
+            let room = MAX_MACRO_STEPS.saturating_sub(recording.steps.len());
+            recording.steps.extend(steps.iter().take(room).cloned());
+        }
+    }
+
+    fn load_macro(&self, args: &serde_json::Value) -> Result<DesktopMacro, FaeLlmError> {
+        let name = macro_name(args)?;
+        self.macros
+            .load(&name)
+            .map_err(|e| FaeLlmError::ToolExecutionError(format!("desktop macro '{name}': {e}")))?
+            .ok_or_else(|| {
+                FaeLlmError::ToolValidationError(format!("no desktop macro named '{name}'"))
+            })
+    }
+
+    /// The macro's steps with `params` filled in, each validated as an action.
+    fn replay_steps(
+        &self,
+        args: &serde_json::Value,
+    ) -> Result<(DesktopMacro, Vec<(serde_json::Value, DesktopAction)>), FaeLlmError> {
+        let recorded = self.load_macro(args)?;
+        let params = args
+            .get("params")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+        let steps = recorded
+            .expand(&params)?
+            .into_iter()
+            .map(|step| parse_action(&step).map(|action| (step, action)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((recorded, steps))
+    }
+
+    /// Handle the macro actions; `None` for ordinary desktop actions.
+    fn execute_macro_action(
+        &self,
+        args: &serde_json::Value,
+    ) -> Option<Result<ToolResult, FaeLlmError>> {
+        let result = match args.get("action").and_then(|v| v.as_str())? {
+            "record_start" => macro_name(args).map(|name| {
+                let mut recording = self.recording.lock().unwrap_or_else(|e| e.into_inner());
+                let replaced = recording.replace(Recording {
+                    name: name.clone(),
+                    steps: Vec::new(),
+                });
+                let mut output = format!("recording desktop macro '{name}'");
+                if let Some(old) = replaced {
+                    output.push_str(&format!(" (discarded unfinished recording '{}')", old.name));
+                }
+                ToolResult::success(output)
+            }),
+            "record_stop" => self.stop_recording(args),
+            "replay_macro" => self.replay(args),
+            "list_macros" => self
+                .macros
+                .list()
+                .map_err(|e| FaeLlmError::ToolExecutionError(format!("desktop macros: {e}")))
+                .map(|macros| {
+                    if macros.is_empty() {
+                        return ToolResult::success("no desktop macros recorded".to_owned());
+                    }
+                    let lines: Vec<String> = macros
+                        .iter()
+                        .map(|m| {
+                            let params: Vec<String> = m.params().into_iter().collect();
+                            format!(
+                                "{} ({} steps; params: {})",
+                                m.name,
+                                m.steps.len(),
+                                if params.is_empty() {
+                                    "none".to_owned()
+                                } else {
+                                    params.join(", ")
+                                }
+                            )
+                        })
+                        .collect();
+                    ToolResult::success(lines.join("\n"))
+                }),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    fn stop_recording(&self, args: &serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let Some(recording) = self
+            .recording
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        else {
+            return Err(FaeLlmError::ToolValidationError(
+                "no desktop macro is being recorded".into(),
+            ));
+        };
+        if recording.steps.is_empty() {
+            return Ok(ToolResult::failure(format!(
+                "macro '{}' has no steps and was not saved",
+                recording.name
+            )));
+        }
+        let mut recorded = recording.finish();
+        if let Some(params) = args.get("params").and_then(|v| v.as_object()) {
+            recorded.parameterize(params);
+        }
+        self.macros
+            .save(&recorded)
+            .map_err(|e| FaeLlmError::ToolExecutionError(format!("saving desktop macro: {e}")))?;
+        Ok(ToolResult::success(format!(
+            "saved desktop macro '{}' with {} steps",
+            recorded.name,
+            recorded.steps.len()
+        )))
+    }
+
+    fn replay(&self, args: &serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let (recorded, steps) = self.replay_steps(args)?;
+        let mut lines = Vec::with_capacity(steps.len());
+        for (index, (_, action)) in steps.iter().enumerate() {
+            match self.run(action) {
+                Ok(output) => lines.push(format!("{}. {output}", index + 1)),
+                Err(e) => {
+                    lines.push(format!("{}. failed: {e}", index + 1));
+                    return Ok(ToolResult::failure(format!(
+                        "macro '{}' stopped at step {} of {}\n{}",
+                        recorded.name,
+                        index + 1,
+                        steps.len(),
+                        lines.join("\n")
+                    )));
+                }
+            }
+        }
+        let expanded: Vec<serde_json::Value> = steps.into_iter().map(|(step, _)| step).collect();
+        self.record(&expanded);
+        let output = format!(
+            "replayed macro '{}' ({} steps)\n{}",
+            recorded.name,
+            expanded.len(),
+            lines.join("\n")
+        );
+        let (truncated, was_truncated) = truncate_output(&output, self.max_bytes);
+        if was_truncated {
+            Ok(ToolResult::success_truncated(truncated))
+        } else {
+            Ok(ToolResult::success(truncated))
         }
     }
 }
 
+/// The normalized `name` argument of a macro action.
+fn macro_name(args: &serde_json::Value) -> Result<String, FaeLlmError> {
+    let name = args.get("name").and_then(|v| v.as_str()).ok_or_else(|| {
+        FaeLlmError::ToolValidationError("macro actions require a 'name' argument".into())
+    })?;
+    macros::normalize_name(name)
+}
+
 impl Tool for DesktopTool {
     fn name(&self) -> &str {
         "desktop"
     }
 
     fn description(&self) -> &str {
-        "Control the desktop — screenshots, clicks, typing, window management"
+        "Control the desktop — screenshots, clicks, typing, window management. \
+         Use find_elements to locate buttons and fields before clicking. \
+         record_start/record_stop save the actions in between as a named macro; \
+         replay_macro runs it again with new params"
     }
 
     fn schema(&self) -> serde_json::Value {
@@ -304,12 +648,21 @@ impl Tool for DesktopTool {
                     "enum": [
                         "screenshot", "click", "type", "press", "hotkey",
                         "scroll", "list_windows", "focus_window",
-                        "list_apps", "launch_app", "raw"
+                        "list_apps", "launch_app", "find_elements", "raw",
+                        "record_start", "record_stop", "replay_macro", "list_macros"
                     ]
                 },
                 "app": {
                     "type": "string",
-                    "description": "Application name (for screenshot scope)"
+                    "description": "Application name (scope for screenshot and find_elements)"
+                },
+                "role": {
+                    "type": "string",
+                    "description": "Element role to match, e.g. 'button' (for find_elements)"
+                },
+                "label": {
+                    "type": "string",
+                    "description": "Element label text to match (for find_elements)"
                 },
                 "target": {
                     "type": "string",
@@ -350,7 +703,12 @@ impl Tool for DesktopTool {
                 },
                 "name": {
                     "type": "string",
-                    "description": "Application name (for launch_app)"
+                    "description": "Application name (for launch_app) or macro name (for record_start and replay_macro)"
+                },
+                "params": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": "Macro parameters. For record_stop: name -> recorded value to turn into a {{name}} placeholder. For replay_macro: name -> value to substitute"
                 },
                 "command": {
                     "type": "string",
@@ -361,18 +719,32 @@ impl Tool for DesktopTool {
         })
     }
 
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        if args.get("action").and_then(|v| v.as_str()) != Some("replay_macro") {
+            return None;
+        }
+        let (recorded, steps) = self.replay_steps(args).ok()?;
+        let lines: Vec<String> = steps
+            .iter()
+            .enumerate()
+            .map(|(index, (step, _))| format!("{}. {step}", index + 1))
+            .collect();
+        Some(format!(
+            "replay desktop macro '{}':\n{}",
+            recorded.name,
+            lines.join("\n")
+        ))
+    }
+
     fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        if let Some(result) = self.execute_macro_action(&args) {
+            return result;
+        }
         let action = parse_action(&args)?;
 
-        match self.backend.execute(&action) {
-            Ok(result) => {
-                let mut output = result.output;
-                if let Some(path) = &result.screenshot_path {
-                    if !output.is_empty() {
-                        output.push('\n');
-                    }
-                    output.push_str(&format!("Screenshot saved: {path}"));
-                }
+        match self.run(&action) {
+            Ok(output) => {
+                self.record(std::slice::from_ref(&args));
                 let (truncated, was_truncated) = truncate_output(&output, self.max_bytes);
                 if was_truncated {
                     Ok(ToolResult::success_truncated(truncated))
@@ -670,6 +1042,91 @@ mod tests {
         );
     }
 
+    fn macro_tool(dir: &tempfile::TempDir) -> DesktopTool {
+        DesktopTool::with_backend(Box::new(MockBackend::new(true)))
+            .with_macro_store(MacroStore::new(dir.path().join("desktop_macros")))
+    }
+
+    #[test]
+    fn recorded_macro_replays_with_new_params() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let tool = macro_tool(&dir);
+        let run = |args: serde_json::Value| tool.execute(args).expect("execute");
+
+        assert!(run(serde_json::json!({"action": "record_start", "name": "Save Report"})).success);
+        assert!(run(serde_json::json!({"action": "hotkey", "keys": ["cmd", "s"]})).success);
+        assert!(run(serde_json::json!({"action": "type", "text": "report.pdf"})).success);
+        // Rejected actions are not recorded.
+        assert!(tool.execute(serde_json::json!({"action": "type"})).is_err());
+        let saved = run(serde_json::json!({
+            "action": "record_stop",
+            "params": {"file": "report.pdf"}
+        }));
+        assert!(saved.success);
+        assert!(saved.content.contains("'save-report' with 2 steps"));
+
+        let listed = run(serde_json::json!({"action": "list_macros"}));
+        assert!(
+            listed
+                .content
+                .contains("save-report (2 steps; params: file)")
+        );
+
+        let replay = serde_json::json!({
+            "action": "replay_macro",
+            "name": "save report",
+            "params": {"file": "invoice.pdf"}
+        });
+        let preview = tool.approval_preview(&replay).expect("preview");
+        assert!(preview.contains("invoice.pdf"), "{preview}");
+        let result = run(replay);
+        assert!(result.success);
+        assert!(result.content.contains("invoice.pdf"), "{}", result.content);
+    }
+
+    #[test]
+    fn replay_requires_every_param_and_an_existing_macro() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let tool = macro_tool(&dir);
+        tool.execute(serde_json::json!({"action": "record_start", "name": "greet"}))
+            .expect("start");
+        tool.execute(serde_json::json!({"action": "type", "text": "hello"}))
+            .expect("type");
+        tool.execute(serde_json::json!({"action": "record_stop", "params": {"who": "hello"}}))
+            .expect("stop");
+
+        let missing = tool.execute(serde_json::json!({"action": "replay_macro", "name": "greet"}));
+        assert!(matches!(missing, Err(FaeLlmError::ToolValidationError(_))));
+        let unknown = tool.execute(serde_json::json!({"action": "replay_macro", "name": "nope"}));
+        assert!(matches!(unknown, Err(FaeLlmError::ToolValidationError(_))));
+        let stop = tool.execute(serde_json::json!({"action": "record_stop"}));
+        assert!(matches!(stop, Err(FaeLlmError::ToolValidationError(_))));
+    }
+
+    #[test]
+    fn failing_step_stops_the_replay() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = MacroStore::new(dir.path().join("desktop_macros"));
+        store
+            .save(&DesktopMacro {
+                name: "broken".to_owned(),
+                created_secs: 0,
+                steps: vec![serde_json::json!({"action": "press", "key": "return"})],
+            })
+            .expect("save");
+        let tool = DesktopTool::with_backend(Box::new(FailingBackend)).with_macro_store(store);
+        let result = tool
+            .execute(serde_json::json!({"action": "replay_macro", "name": "broken"}))
+            .expect("execute");
+        assert!(!result.success);
+        assert!(
+            result
+                .error
+                .unwrap_or_default()
+                .contains("stopped at step 1 of 1")
+        );
+    }
+
     #[test]
     fn tool_name_is_desktop() {
         let tool = DesktopTool::with_backend(Box::new(MockBackend::new(true)));
@@ -696,6 +1153,53 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn desktop_action_parsing_find_elements() {
+        let action = parse_action(&serde_json::json!({
+            "action": "find_elements",
+            "app": "Safari",
+            "role": "button",
+            "label": ""
+        }));
+        assert!(matches!(
+            action.as_ref().ok(),
+            Some(DesktopAction::FindElements { app: Some(app), role: Some(role), label: None })
+                if app == "Safari" && role == "button"
+        ));
+    }
+
+    #[test]
+    fn elements_are_filtered_and_given_click_centres() {
+        let element = |role: &str, label: &str, width: f64| UiElement {
+            role: role.to_owned(),
+            label: label.to_owned(),
+            bounds: ElementBounds {
+                x: 10.0,
+                y: 20.0,
+                width,
+                height: 30.0,
+            },
+        };
+        let result = elements_result(
+            vec![
+                element("AXButton", "Save", 100.0),
+                element("AXButton", "Cancel", 100.0),
+                element("AXTextField", "Save as", 100.0),
+                element("AXButton", "Save hidden", 0.0),
+            ],
+            Some("button"),
+            Some("save"),
+        );
+        let json: serde_json::Value = match serde_json::from_str(&result.output) {
+            Ok(v) => v,
+            Err(_) => unreachable!("output should be JSON"),
+        };
+        assert_eq!(json["count"], 1);
+        assert_eq!(json["elements"][0]["label"], "Save");
+        assert_eq!(json["elements"][0]["center"]["x"], 60.0);
+        assert_eq!(json["elements"][0]["center"]["y"], 35.0);
+    }
+
     #[test]
     fn install_instructions_not_empty() {
         let instructions = install_instructions();
@@ -6,7 +6,10 @@
 //!
 //! Requires: `brew install steipete/tap/peekaboo` + Accessibility permission.
 
-use super::{ClickTarget, DesktopAction, DesktopBackend, DesktopResult};
+use super::{
+    ClickTarget, DesktopAction, DesktopBackend, DesktopResult, ElementBounds, UiElement,
+    elements_result,
+};
 
 /// Default command timeout for Peekaboo invocations.
 const PEEKABOO_TIMEOUT_SECS: u64 = 30;
@@ -235,6 +238,18 @@ impl DesktopBackend for PeekabooBackend {
                 })
             }
 
+            DesktopAction::FindElements { app, role, label } => {
+                // `see` inspects the accessibility tree alongside the capture.
+                let mut args = vec!["see", "--format", "json"];
+                if let Some(name) = app {
+                    args.push("--app");
+                    args.push(name);
+                }
+                let output = self.run_command(Self::build_command(&args))?;
+                let elements = parse_see_elements(&output)?;
+                Ok(elements_result(elements, role.as_deref(), label.as_deref()))
+            }
+
             DesktopAction::Raw { command } => {
                 // Split the raw command into arguments for peekaboo.
                 let parts: Vec<&str> = command.split_whitespace().collect();
@@ -252,6 +267,52 @@ impl DesktopBackend for PeekabooBackend {
     }
 }
 
+/// Extract the UI elements from `peekaboo see --format json` output.
+///
+/// The element list sits under `ui_elements` (or `elements`), at the top
+/// level or inside `data`. An element's label is the first non-empty of
+/// `label`, `title`, `description` and `value`; its frame is `bounds` or
+/// `frame` as `{x, y, width, height}`.
+fn parse_see_elements(output: &str) -> Result<Vec<UiElement>, String> {
+    let json: serde_json::Value = serde_json::from_str(output.trim())
+        .map_err(|e| format!("peekaboo returned invalid JSON: {e}"))?;
+    let root = json.get("data").unwrap_or(&json);
+    let list = root
+        .get("ui_elements")
+        .or_else(|| root.get("elements"))
+        .and_then(|v| v.as_array())
+        .ok_or("peekaboo output has no ui_elements")?;
+
+    let elements = list
+        .iter()
+        .filter_map(|item| {
+            let text = |key: &str| {
+                item.get(key)
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+            };
+            let frame = item.get("bounds").or_else(|| item.get("frame"))?;
+            let num = |key: &str| frame.get(key).and_then(|v| v.as_f64());
+            Some(UiElement {
+                role: text("role").unwrap_or_default().to_owned(),
+                label: ["label", "title", "description", "value"]
+                    .into_iter()
+                    .find_map(text)
+                    .unwrap_or_default()
+                    .to_owned(),
+                bounds: ElementBounds {
+                    x: num("x")?,
+                    y: num("y")?,
+                    width: num("width")?,
+                    height: num("height")?,
+                },
+            })
+        })
+        .collect();
+    Ok(elements)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,4 +401,22 @@ mod tests {
         let args: Vec<&str> = cmd.get_args().filter_map(|a| a.to_str()).collect();
         assert_eq!(args, vec!["app", "list", "--format", "json"]);
     }
+
+    #[test]
+    fn see_output_elements_are_parsed() {
+        let output = r#"{"success": true, "data": {"ui_elements": [
+            {"id": "B1", "role": "AXButton", "title": "Save", "bounds": {"x": 10, "y": 20, "width": 80, "height": 24}},
+            {"id": "T1", "role": "AXTextField", "label": "", "value": "draft.txt", "frame": {"x": 0, "y": 0, "width": 200, "height": 22}},
+            {"id": "G1", "role": "AXGroup"}
+        ]}}"#;
+        let elements = match parse_see_elements(output) {
+            Ok(e) => e,
+            Err(e) => unreachable!("should parse: {e}"),
+        };
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0].label, "Save");
+        assert_eq!(elements[0].bounds.center(), (50.0, 32.0));
+        assert_eq!(elements[1].label, "draft.txt");
+        assert!(parse_see_elements("not json").is_err());
+    }
 }
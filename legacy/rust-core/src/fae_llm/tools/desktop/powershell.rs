@@ -0,0 +1,446 @@
+//! PowerShell backend for Windows desktop automation.
+//!
+//! Drives the desktop through `powershell.exe`, which ships with every
+//! Windows install: UI Automation for clicks on labelled elements,
+//! `SendKeys` for typing and key presses, `user32` for mouse input and
+//! `System.Drawing` for screenshots.
+//!
+//! Every argument is embedded as a single-quoted PowerShell literal (see
+//! [`ps_quote`]), so model-supplied text is never evaluated as script.
+
+use super::{
+    ClickTarget, DesktopAction, DesktopBackend, DesktopResult, elements_result, parse_flat_elements,
+};
+
+/// Default command timeout for PowerShell invocations.
+const POWERSHELL_TIMEOUT_SECS: u64 = 30;
+
+/// Mouse input via `user32.dll`, declared once per script.
+const USER32: &str = "Add-Type -Namespace Fae -Name User32 -MemberDefinition '\
+    [DllImport(\"user32.dll\")] public static extern bool SetCursorPos(int x, int y);\
+    [DllImport(\"user32.dll\")] public static extern void mouse_event(uint flags, uint dx, uint dy, int data, System.UIntPtr extra);'";
+
+/// One notch of the mouse wheel.
+const WHEEL_DELTA: i64 = 120;
+
+/// Windows desktop automation via PowerShell.
+pub struct PowerShellBackend {
+    timeout_secs: u64,
+}
+
+impl PowerShellBackend {
+    /// Create a new `PowerShellBackend` with default settings.
+    pub fn new() -> Self {
+        Self {
+            timeout_secs: POWERSHELL_TIMEOUT_SECS,
+        }
+    }
+
+    /// Build a `std::process::Command` that runs `script`.
+    fn build_command(script: &str) -> std::process::Command {
+        let mut cmd = std::process::Command::new("powershell");
+        cmd.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-ExecutionPolicy",
+            "Bypass",
+            "-Command",
+            script,
+        ]);
+        cmd.stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+        cmd
+    }
+
+    /// Run a script with timeout, returning stdout.
+    fn run_script(&self, script: &str) -> Result<String, String> {
+        let timeout = std::time::Duration::from_secs(self.timeout_secs);
+        let start = std::time::Instant::now();
+
+        let mut child = Self::build_command(script)
+            .spawn()
+            .map_err(|e| format!("failed to spawn powershell: {e}"))?;
+
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    let mut stdout = String::new();
+                    if let Some(mut pipe) = child.stdout.take() {
+                        std::io::Read::read_to_string(&mut pipe, &mut stdout).unwrap_or(0);
+                    }
+                    let mut stderr = String::new();
+                    if let Some(mut pipe) = child.stderr.take() {
+                        std::io::Read::read_to_string(&mut pipe, &mut stderr).unwrap_or(0);
+                    }
+
+                    if !status.success() {
+                        let code = status.code().unwrap_or(-1);
+                        let output = if stderr.is_empty() { stdout } else { stderr };
+                        return Err(format!("powershell exited with code {code}: {output}"));
+                    }
+
+                    return Ok(stdout);
+                }
+                Ok(None) => {
+                    if start.elapsed() > timeout {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        return Err(format!("powershell timed out after {}s", self.timeout_secs));
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                }
+                Err(e) => {
+                    return Err(format!("failed to check powershell status: {e}"));
+                }
+            }
+        }
+    }
+}
+
+impl Default for PowerShellBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Quote `value` as a single-quoted PowerShell string literal.
+fn ps_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Escape literal text for `SendKeys`, where `+^%~(){}[]` are special.
+fn sendkeys_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '+' | '^' | '%' | '~' | '(' | ')' | '{' | '}' | '[' | ']' => {
+                out.push('{');
+                out.push(c);
+                out.push('}');
+            }
+            '\n' => out.push_str("{ENTER}"),
+            '\t' => out.push_str("{TAB}"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// `SendKeys` code for a named key (`enter`, `f5`, `a`).
+fn sendkeys_key(key: &str) -> Result<String, String> {
+    let lower = key.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "enter" | "return" => "{ENTER}",
+        "tab" => "{TAB}",
+        "escape" | "esc" => "{ESC}",
+        "backspace" => "{BACKSPACE}",
+        "delete" | "del" => "{DELETE}",
+        "insert" => "{INSERT}",
+        "space" => " ",
+        "up" => "{UP}",
+        "down" => "{DOWN}",
+        "left" => "{LEFT}",
+        "right" => "{RIGHT}",
+        "home" => "{HOME}",
+        "end" => "{END}",
+        "pageup" | "page_up" => "{PGUP}",
+        "pagedown" | "page_down" => "{PGDN}",
+        _ => {
+            if let Some(n) = lower.strip_prefix('f')
+                && let Ok(n) = n.parse::<u8>()
+                && (1..=16).contains(&n)
+            {
+                return Ok(format!("{{F{n}}}"));
+            }
+            let mut chars = key.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(sendkeys_text(&c.to_string())),
+                _ => Err(format!("unsupported key: '{key}'")),
+            };
+        }
+    };
+    Ok(code.to_owned())
+}
+
+/// `SendKeys` code for a key combination such as `["ctrl", "shift", "s"]`.
+///
+/// `cmd` maps to Ctrl, the Windows equivalent of the macOS shortcuts the
+/// model usually knows. The Windows key cannot be sent through `SendKeys`.
+fn sendkeys_hotkey(keys: &[String]) -> Result<String, String> {
+    let (last, modifiers) = keys
+        .split_last()
+        .ok_or_else(|| "hotkey requires at least one key".to_owned())?;
+    let mut combo = String::new();
+    for modifier in modifiers {
+        combo.push(match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" | "cmd" | "command" => '^',
+            "shift" => '+',
+            "alt" | "option" => '%',
+            other => return Err(format!("unsupported modifier: '{other}'")),
+        });
+    }
+    combo.push_str(&sendkeys_key(last)?);
+    Ok(combo)
+}
+
+/// Script that clicks the left button at `(x, y)`.
+fn click_at(x: &str, y: &str) -> String {
+    format!(
+        "{USER32}; [Fae.User32]::SetCursorPos({x}, {y}) | Out-Null; \
+         [Fae.User32]::mouse_event(2, 0, 0, 0, [UIntPtr]::Zero); \
+         [Fae.User32]::mouse_event(4, 0, 0, 0, [UIntPtr]::Zero)"
+    )
+}
+
+/// Build the PowerShell script for `action`.
+fn script_for(action: &DesktopAction, screenshot_path: &str) -> Result<String, String> {
+    let script = match action {
+        DesktopAction::Screenshot { app: _ } => format!(
+            "Add-Type -AssemblyName System.Windows.Forms, System.Drawing; \
+             $b = [System.Windows.Forms.SystemInformation]::VirtualScreen; \
+             $bmp = New-Object System.Drawing.Bitmap $b.Width, $b.Height; \
+             $g = [System.Drawing.Graphics]::FromImage($bmp); \
+             $g.CopyFromScreen($b.Left, $b.Top, 0, 0, $bmp.Size); \
+             $bmp.Save({}, [System.Drawing.Imaging.ImageFormat]::Png)",
+            ps_quote(screenshot_path)
+        ),
+        DesktopAction::Click {
+            target: ClickTarget::Coordinates { x, y },
+        } => click_at(&(*x as i64).to_string(), &(*y as i64).to_string()),
+        DesktopAction::Click {
+            target: ClickTarget::Label(label),
+        } => format!(
+            "Add-Type -AssemblyName UIAutomationClient, UIAutomationTypes; \
+             $A = [System.Windows.Automation.AutomationElement]; \
+             $cond = New-Object System.Windows.Automation.PropertyCondition($A::NameProperty, {label}); \
+             $el = $A::RootElement.FindFirst([System.Windows.Automation.TreeScope]::Descendants, $cond); \
+             if (-not $el) {{ throw ('no element named ' + {label}) }}; \
+             $p = $null; \
+             if ($el.TryGetCurrentPattern([System.Windows.Automation.InvokePattern]::Pattern, [ref]$p)) {{ $p.Invoke() }} \
+             else {{ $r = $el.Current.BoundingRectangle; $x = [int]($r.X + $r.Width / 2); $y = [int]($r.Y + $r.Height / 2); {} }}",
+            click_at("$x", "$y"),
+            label = ps_quote(label),
+        ),
+        DesktopAction::Type { text } => format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             [System.Windows.Forms.SendKeys]::SendWait({})",
+            ps_quote(&sendkeys_text(text))
+        ),
+        DesktopAction::Press { key } => format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             [System.Windows.Forms.SendKeys]::SendWait({})",
+            ps_quote(&sendkeys_key(key)?)
+        ),
+        DesktopAction::Hotkey { keys } => format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             [System.Windows.Forms.SendKeys]::SendWait({})",
+            ps_quote(&sendkeys_hotkey(keys)?)
+        ),
+        DesktopAction::Scroll { direction, amount } => {
+            // MOUSEEVENTF_WHEEL / MOUSEEVENTF_HWHEEL; positive is up or right.
+            let notches = (*amount as i64).max(1);
+            let (flag, sign) = match direction.as_str() {
+                "up" => (0x0800, 1),
+                "left" => (0x1000, -1),
+                "right" => (0x1000, 1),
+                _ => (0x0800, -1),
+            };
+            format!(
+                "{USER32}; [Fae.User32]::mouse_event({flag}, 0, 0, {}, [UIntPtr]::Zero)",
+                sign * notches * WHEEL_DELTA
+            )
+        }
+        DesktopAction::ListWindows => "Get-Process | Where-Object { $_.MainWindowTitle } | \
+             Select-Object Id, ProcessName, MainWindowTitle | ConvertTo-Json"
+            .to_owned(),
+        DesktopAction::FocusWindow { title } => format!(
+            "if (-not (New-Object -ComObject WScript.Shell).AppActivate({title})) \
+             {{ throw ('no window found matching ' + {title}) }}",
+            title = ps_quote(title)
+        ),
+        DesktopAction::ListApps => "Get-Process | Where-Object { $_.MainWindowHandle -ne 0 } | \
+             Select-Object -ExpandProperty ProcessName | Sort-Object -Unique"
+            .to_owned(),
+        DesktopAction::LaunchApp { name } => format!("Start-Process {}", ps_quote(name)),
+        DesktopAction::FindElements { app, .. } => {
+            // Named, on-screen elements of the app's main windows (or the
+            // whole desktop), as the flat JSON array `parse_flat_elements` reads.
+            let roots = match app {
+                Some(name) => format!(
+                    "Get-Process -Name {} -ErrorAction SilentlyContinue | \
+                     Where-Object {{ $_.MainWindowHandle -ne 0 }} | \
+                     ForEach-Object {{ $A::FromHandle($_.MainWindowHandle) }}",
+                    ps_quote(name)
+                ),
+                None => "$A::RootElement".to_owned(),
+            };
+            format!(
+                "Add-Type -AssemblyName UIAutomationClient, UIAutomationTypes; \
+                 $A = [System.Windows.Automation.AutomationElement]; \
+                 $out = foreach ($r in @({roots})) {{ \
+                   $r.FindAll([System.Windows.Automation.TreeScope]::Descendants, \
+                     [System.Windows.Automation.Condition]::TrueCondition) | \
+                   Where-Object {{ $_.Current.Name -and -not $_.Current.BoundingRectangle.IsEmpty }} | \
+                   Select-Object -First 5000 | \
+                   ForEach-Object {{ $b = $_.Current.BoundingRectangle; [pscustomobject]@{{ \
+                     role = $_.Current.ControlType.ProgrammaticName -replace '^ControlType[.]', ''; \
+                     label = $_.Current.Name; x = $b.X; y = $b.Y; width = $b.Width; height = $b.Height }} }} }}; \
+                 ConvertTo-Json -InputObject @($out) -Compress"
+            )
+        }
+        DesktopAction::Raw { command } => {
+            if command.trim().is_empty() {
+                return Err("raw command cannot be empty".to_string());
+            }
+            command.clone()
+        }
+    };
+    Ok(script)
+}
+
+impl DesktopBackend for PowerShellBackend {
+    fn name(&self) -> &str {
+        "powershell"
+    }
+
+    fn is_available(&self) -> bool {
+        Self::build_command("exit 0")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    fn execute(&self, action: &DesktopAction) -> Result<DesktopResult, String> {
+        let screenshot_path = std::env::temp_dir()
+            .join(format!(
+                "fae_screenshot_{}.png",
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or(0)
+            ))
+            .to_string_lossy()
+            .into_owned();
+        let output = self.run_script(&script_for(action, &screenshot_path)?)?;
+
+        let output = match action {
+            DesktopAction::Screenshot { .. } => {
+                return Ok(DesktopResult {
+                    output: format!("Screenshot captured: {screenshot_path}"),
+                    screenshot_path: Some(screenshot_path),
+                });
+            }
+            DesktopAction::Click {
+                target: ClickTarget::Coordinates { x, y },
+            } => format!("Clicked at ({}, {})", *x as i64, *y as i64),
+            DesktopAction::Click {
+                target: ClickTarget::Label(label),
+            } => format!("Clicked '{label}'"),
+            DesktopAction::Type { text } => format!("Typed: {text}"),
+            DesktopAction::Press { key } => format!("Pressed: {key}"),
+            DesktopAction::Hotkey { keys } => format!("Hotkey: {}", keys.join("+")),
+            DesktopAction::Scroll { direction, amount } => {
+                format!("Scrolled {direction} {} notches", (*amount as i64).max(1))
+            }
+            DesktopAction::FindElements { role, label, .. } => {
+                let elements = parse_flat_elements(&output)
+                    .map_err(|e| format!("unexpected UI Automation output: {e}"))?;
+                return Ok(elements_result(elements, role.as_deref(), label.as_deref()));
+            }
+            DesktopAction::FocusWindow { title } => format!("Focused window: {title}"),
+            DesktopAction::LaunchApp { name } => format!("Launched: {name}"),
+            DesktopAction::ListWindows | DesktopAction::ListApps | DesktopAction::Raw { .. } => {
+                output
+            }
+        };
+        Ok(DesktopResult {
+            output,
+            screenshot_path: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_is_powershell() {
+        assert_eq!(PowerShellBackend::new().name(), "powershell");
+    }
+
+    #[test]
+    fn arguments_are_quoted_as_literals() {
+        assert_eq!(ps_quote("it's $(rm -r C:\\)"), "'it''s $(rm -r C:\\)'");
+        let script = script_for(
+            &DesktopAction::LaunchApp {
+                name: "notepad'; Remove-Item x; '".to_owned(),
+            },
+            "",
+        )
+        .unwrap_or_default();
+        assert_eq!(script, "Start-Process 'notepad''; Remove-Item x; '''");
+    }
+
+    #[test]
+    fn typed_text_escapes_sendkeys_syntax() {
+        assert_eq!(sendkeys_text("1+1 {ok}\n"), "1{+}1 {{}ok{}}{ENTER}");
+    }
+
+    #[test]
+    fn keys_and_hotkeys_map_to_sendkeys_codes() {
+        assert_eq!(sendkeys_key("Enter").as_deref(), Ok("{ENTER}"));
+        assert_eq!(sendkeys_key("f5").as_deref(), Ok("{F5}"));
+        assert_eq!(sendkeys_key("%").as_deref(), Ok("{%}"));
+        assert!(sendkeys_key("hyper").is_err());
+
+        let keys = |k: &[&str]| k.iter().map(|s| (*s).to_owned()).collect::<Vec<_>>();
+        assert_eq!(
+            sendkeys_hotkey(&keys(&["cmd", "shift", "s"])).as_deref(),
+            Ok("^+s")
+        );
+        assert_eq!(
+            sendkeys_hotkey(&keys(&["alt", "f4"])).as_deref(),
+            Ok("%{F4}")
+        );
+        assert!(sendkeys_hotkey(&keys(&["win", "d"])).is_err());
+    }
+
+    #[test]
+    fn scroll_uses_wheel_notches() {
+        let script = script_for(
+            &DesktopAction::Scroll {
+                direction: "down".to_owned(),
+                amount: 3.0,
+            },
+            "",
+        )
+        .unwrap_or_default();
+        assert!(script.contains("mouse_event(2048, 0, 0, -360,"), "{script}");
+    }
+
+    #[test]
+    fn empty_raw_commands_are_rejected() {
+        let raw = DesktopAction::Raw {
+            command: "  ".to_owned(),
+        };
+        assert!(script_for(&raw, "").is_err());
+    }
+
+    #[test]
+    fn find_elements_scopes_to_the_app_process() {
+        let script = script_for(
+            &DesktopAction::FindElements {
+                app: Some("notepad".to_owned()),
+                role: None,
+                label: None,
+            },
+            "",
+        )
+        .unwrap_or_default();
+        assert!(script.contains("Get-Process -Name 'notepad'"), "{script}");
+        assert!(script.contains("ConvertTo-Json"), "{script}");
+    }
+}
@@ -4,12 +4,51 @@
 //! desktop automation on Linux systems.
 //!
 //! Requires: `sudo apt install xdotool scrot` (X11 session).
+//!
+//! Element discovery (`find_elements`) walks the AT-SPI accessibility tree
+//! through its Python bindings: `sudo apt install python3-gi gir1.2-atspi-2.0`.
 
-use super::{ClickTarget, DesktopAction, DesktopBackend, DesktopResult};
+use super::{
+    ClickTarget, DesktopAction, DesktopBackend, DesktopResult, UiElement, elements_result,
+    parse_flat_elements,
+};
 
 /// Default command timeout for xdotool invocations.
 const XDOTOOL_TIMEOUT_SECS: u64 = 30;
 
+/// Walks the AT-SPI tree of every application (or those whose name contains
+/// `argv[1]`) and prints the showing, named elements as a JSON array.
+const ATSPI_DUMP_SCRIPT: &str = r#"
+import json, sys
+import gi
+gi.require_version('Atspi', '2.0')
+from gi.repository import Atspi
+
+app_filter = sys.argv[1].lower() if len(sys.argv) > 1 else ''
+out = []
+
+def walk(node, depth):
+    if node is None or depth > 40 or len(out) >= 5000:
+        return
+    try:
+        name = node.get_name() or ''
+        if name and node.get_state_set().contains(Atspi.StateType.SHOWING):
+            ext = node.get_extents(Atspi.CoordType.SCREEN)
+            out.append({'role': node.get_role_name(), 'label': name,
+                        'x': ext.x, 'y': ext.y, 'width': ext.width, 'height': ext.height})
+        for i in range(node.get_child_count()):
+            walk(node.get_child_at_index(i), depth + 1)
+    except Exception:
+        pass
+
+desktop = Atspi.get_desktop(0)
+for i in range(desktop.get_child_count()):
+    app = desktop.get_child_at_index(i)
+    if app is not None and app_filter in (app.get_name() or '').lower():
+        walk(app, 0)
+print(json.dumps(out))
+"#;
+
 /// Linux desktop automation via xdotool and scrot.
 pub struct XdotoolBackend {
     timeout_secs: u64,
@@ -139,6 +178,11 @@ fn parse_window_geometry(output: &str) -> Result<(i64, i64), String> {
     Ok((px + w / 2, py + h / 2))
 }
 
+/// Parse the JSON array printed by [`ATSPI_DUMP_SCRIPT`].
+fn parse_atspi_elements(output: &str) -> Result<Vec<UiElement>, String> {
+    parse_flat_elements(output).map_err(|e| format!("unexpected AT-SPI output: {e}"))
+}
+
 impl Default for XdotoolBackend {
     fn default() -> Self {
         Self::new()
@@ -310,6 +354,21 @@ impl DesktopBackend for XdotoolBackend {
                 })
             }
 
+            DesktopAction::FindElements { app, role, label } => {
+                let app_filter = app.as_deref().unwrap_or("");
+                let output = self
+                    .run_command("python3", &["-c", ATSPI_DUMP_SCRIPT, app_filter])
+                    .map_err(|e| {
+                        format!(
+                            "AT-SPI query failed ({e}). Install the bindings with \
+                             `sudo apt install python3-gi gir1.2-atspi-2.0` and make sure \
+                             accessibility is enabled for the session."
+                        )
+                    })?;
+                let elements = parse_atspi_elements(&output)?;
+                Ok(elements_result(elements, role.as_deref(), label.as_deref()))
+            }
+
             DesktopAction::Raw { command } => {
                 let parts: Vec<&str> = command.split_whitespace().collect();
                 if parts.is_empty() {
@@ -416,4 +475,23 @@ mod tests {
         let output = "Window 12345\n  Position: abc,def (screen: 0)\n  Geometry: 800x600\n";
         assert!(parse_window_geometry(output).is_err());
     }
+
+    // ── AT-SPI parsing tests ────────────────────────────────────
+
+    #[test]
+    fn parse_atspi_elements_valid() {
+        let output = r#"[{"role": "push button", "label": "OK", "x": 10, "y": 20, "width": 80, "height": 30}]"#;
+        let elements = match parse_atspi_elements(output) {
+            Ok(e) => e,
+            Err(_) => unreachable!("should parse AT-SPI output"),
+        };
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].role, "push button");
+        assert_eq!(elements[0].bounds.center(), (50.0, 35.0));
+    }
+
+    #[test]
+    fn parse_atspi_elements_rejects_garbage() {
+        assert!(parse_atspi_elements("Traceback (most recent call last):").is_err());
+    }
 }
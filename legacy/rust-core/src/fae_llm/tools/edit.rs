@@ -1,26 +1,61 @@
-//! Edit tool — deterministic text edits via find/replace.
+//! Edit tool — atomic multi-file edits from a unified diff.
+//!
+//! The model proposes a patch; the approval prompt shows it rendered (see
+//! [`Tool::approval_preview`]); on approval every hunk is checked against
+//! the current files before anything is written. Files are then replaced one
+//! at a time through a temp file and rename, and if any write fails the files
+//! already changed are restored from their original contents. Applied patches
+//! are recorded in the [`UndoStore`] when one is set.
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
+use crate::permissions::SharedPermissionStore;
 use std::fs::OpenOptions;
-use std::io::{Read as _, Seek as _, Write as _};
-use std::path::PathBuf;
-
-use super::path_validation::{resolve_workspace_root, validate_write_path_in_workspace};
+use std::io::{Read as _, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::patch::{FileChangeKind, FilePatch, apply_hunks, parse_patch, render_patch};
+use super::path_validation::{
+    check_file_scope, resolve_workspace_root, validate_write_path_in_workspace,
+};
 use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult};
+use super::undo::{UndoFile, UndoStore, record_change};
 
-/// Tool that performs deterministic text edits by replacing `old_string`
-/// with `new_string` in a file.
+/// Tool that applies a unified diff to one or more files atomically.
 ///
 /// Arguments (JSON):
-/// - `path` (string, required) — file path to edit
-/// - `old_string` (string, required) — exact text to find (must be unique in file)
-/// - `new_string` (string, required) — replacement text
+/// - `patch` (string, required) — unified diff; `--- /dev/null` creates a
+///   file and `+++ /dev/null` deletes one
+///
+/// Either every file in the patch changes or none does.
 ///
 /// Only available in `ToolMode::Full`.
 pub struct EditTool {
     max_bytes: usize,
     workspace_root: PathBuf,
+    undo: Option<Arc<UndoStore>>,
+    permissions: Option<SharedPermissionStore>,
+}
+
+/// One file change, checked and ready to write.
+struct PlannedChange {
+    path: PathBuf,
+    kind: FileChangeKind,
+    /// Content before the patch, `None` when the file is created.
+    original: Option<String>,
+    /// Content after the patch, `None` when the file is deleted.
+    updated: Option<String>,
+    added: usize,
+    removed: usize,
+}
+
+/// Why a patch cannot be applied.
+enum PlanError {
+    /// The arguments are invalid (bad patch, path outside the workspace).
+    Invalid(FaeLlmError),
+    /// The patch does not fit the files on disk.
+    Failed(String),
 }
 
 impl EditTool {
@@ -29,6 +64,8 @@ impl EditTool {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
             workspace_root: resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")),
+            undo: None,
+            permissions: None,
         }
     }
 
@@ -37,6 +74,8 @@ impl EditTool {
         Self {
             max_bytes,
             workspace_root: resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")),
+            undo: None,
+            permissions: None,
         }
     }
 
@@ -45,6 +84,8 @@ impl EditTool {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
             workspace_root,
+            undo: None,
+            permissions: None,
         }
     }
 
@@ -53,7 +94,99 @@ impl EditTool {
         Self {
             max_bytes,
             workspace_root,
+            undo: None,
+            permissions: None,
+        }
+    }
+
+    /// Record applied patches in `store` so they can be undone.
+    pub fn with_undo_store(mut self, store: Arc<UndoStore>) -> Self {
+        self.undo = Some(store);
+        self
+    }
+
+    /// Only change files the `Files` grant in `permissions` covers.
+    pub fn with_permissions(mut self, permissions: SharedPermissionStore) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Check every file patch against the disk and compute new contents.
+    fn plan(&self, patches: &[FilePatch]) -> Result<Vec<PlannedChange>, PlanError> {
+        let mut changes = Vec::with_capacity(patches.len());
+        for patch in patches {
+            let path = validate_write_path_in_workspace(patch.path(), &self.workspace_root)
+                .map_err(PlanError::Invalid)?;
+            let path = canonicalize_for_mutation(&path, &self.workspace_root)
+                .map_err(PlanError::Failed)?;
+            check_file_scope(self.permissions.as_ref(), &path, true).map_err(PlanError::Invalid)?;
+            if changes.iter().any(|c: &PlannedChange| c.path == path) {
+                return Err(PlanError::Failed(format!(
+                    "{} appears more than once in the patch",
+                    path.display()
+                )));
+            }
+
+            let kind = patch.kind();
+            let original = match kind {
+                FileChangeKind::Create => {
+                    if std::fs::symlink_metadata(&path).is_ok() {
+                        return Err(PlanError::Failed(format!(
+                            "{} already exists",
+                            path.display()
+                        )));
+                    }
+                    None
+                }
+                FileChangeKind::Modify | FileChangeKind::Delete => {
+                    Some(self.read_existing(&path).map_err(PlanError::Failed)?)
+                }
+            };
+
+            let patched = apply_hunks(original.as_deref().unwrap_or_default(), &patch.hunks)
+                .map_err(|e| PlanError::Failed(format!("{}: {e}", path.display())))?;
+            let updated = match kind {
+                FileChangeKind::Delete => None,
+                FileChangeKind::Create | FileChangeKind::Modify => {
+                    if patched.len() > self.max_bytes {
+                        return Err(PlanError::Failed(format!(
+                            "patched {} exceeds max size ({} bytes > {} bytes)",
+                            path.display(),
+                            patched.len(),
+                            self.max_bytes
+                        )));
+                    }
+                    Some(patched)
+                }
+            };
+
+            let (added, removed) = patch.line_counts();
+            changes.push(PlannedChange {
+                path,
+                kind,
+                original,
+                updated,
+                added,
+                removed,
+            });
         }
+        Ok(changes)
+    }
+
+    fn read_existing(&self, path: &Path) -> Result<String, String> {
+        let mut file = open_existing_rw_nofollow(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        if content.len() > self.max_bytes {
+            return Err(format!(
+                "file exceeds max size ({} bytes > {} bytes)",
+                content.len(),
+                self.max_bytes
+            ));
+        }
+        Ok(content)
     }
 }
 
@@ -63,146 +196,183 @@ impl Default for EditTool {
     }
 }
 
+fn patch_arg(args: &serde_json::Value) -> Result<&str, FaeLlmError> {
+    args.get("patch")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| FaeLlmError::ToolValidationError("missing required argument: patch".into()))
+}
+
 impl Tool for EditTool {
     fn name(&self) -> &str {
         "edit"
     }
 
     fn description(&self) -> &str {
-        "Edit a file by replacing an exact string match"
+        "Edit files by applying a unified diff; all files in the patch change or none do"
     }
 
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
             "properties": {
-                "path": {
+                "patch": {
                     "type": "string",
-                    "description": "File path to edit"
-                },
-                "old_string": {
-                    "type": "string",
-                    "description": "Exact text to find (must be unique in the file)"
-                },
-                "new_string": {
-                    "type": "string",
-                    "description": "Replacement text"
+                    "description": "Unified diff with `--- a/path` and `+++ b/path` headers and \
+                        `@@` hunks including a few lines of unchanged context. May cover \
+                        several files. Use `--- /dev/null` to create a file and \
+                        `+++ /dev/null` to delete one."
                 }
             },
-            "required": ["path", "old_string", "new_string"]
+            "required": ["patch"]
         })
     }
 
-    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
-        let path_str = args.get("path").and_then(|v| v.as_str()).ok_or_else(|| {
-            FaeLlmError::ToolValidationError("missing required argument: path".into())
-        })?;
-
-        let old_string = args
-            .get("old_string")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| {
-                FaeLlmError::ToolValidationError("missing required argument: old_string".into())
-            })?;
-
-        let new_string = args
-            .get("new_string")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| {
-                FaeLlmError::ToolValidationError("missing required argument: new_string".into())
-            })?;
-
-        let path = validate_write_path_in_workspace(path_str, &self.workspace_root)?;
-
-        let path = match canonicalize_for_mutation(&path, &self.workspace_root) {
-            Ok(path) => path,
-            Err(message) => return Ok(ToolResult::failure(message)),
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        let patches = match parse_patch(patch_arg(args).ok()?) {
+            Ok(patches) => patches,
+            Err(e) => return Some(format!("invalid patch: {e}")),
         };
-
-        let mut file = match open_existing_rw_nofollow(&path) {
-            Ok(file) => file,
-            Err(e) => {
-                return Ok(ToolResult::failure(format!(
-                    "failed to read {}: {e}",
-                    path.display()
-                )));
+        let mut preview = render_patch(&patches);
+        match self.plan(&patches) {
+            Ok(_) => {}
+            Err(PlanError::Invalid(e)) => {
+                preview.push_str(&format!("\nwarning: patch will be rejected: {e}"))
+            }
+            Err(PlanError::Failed(e)) => {
+                preview.push_str(&format!("\nwarning: patch does not apply: {e}"))
             }
+        }
+        Some(preview)
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let patches = parse_patch(patch_arg(&args)?)
+            .map_err(|e| FaeLlmError::ToolValidationError(format!("invalid patch: {e}")))?;
+
+        let changes = match self.plan(&patches) {
+            Ok(changes) => changes,
+            Err(PlanError::Invalid(e)) => return Err(e),
+            Err(PlanError::Failed(message)) => return Ok(ToolResult::failure(message)),
         };
 
-        // Read existing file from the opened descriptor so content and writeback
-        // apply to the same inode.
-        let mut content = String::new();
-        if let Err(e) = file.read_to_string(&mut content) {
-            return Ok(ToolResult::failure(format!(
-                "failed to read {}: {e}",
-                path.display()
-            )));
+        if let Err(message) = commit_changes(&changes, &write_atomic) {
+            return Ok(ToolResult::failure(message));
         }
+        record_change(
+            self.undo.as_ref(),
+            self.name(),
+            changes
+                .iter()
+                .map(|c| UndoFile {
+                    path: c.path.clone(),
+                    previous: c.original.clone(),
+                    moved_from: None,
+                })
+                .collect(),
+        );
 
-        // Check file size
-        if content.len() > self.max_bytes {
-            return Ok(ToolResult::failure(format!(
-                "file exceeds max size ({} bytes > {} bytes)",
-                content.len(),
-                self.max_bytes
-            )));
+        let mut summary = format!(
+            "applied patch to {} file{}:",
+            changes.len(),
+            if changes.len() == 1 { "" } else { "s" }
+        );
+        for change in &changes {
+            let verb = match change.kind {
+                FileChangeKind::Create => "created",
+                FileChangeKind::Modify => "modified",
+                FileChangeKind::Delete => "deleted",
+            };
+            summary.push_str(&format!(
+                "\n{verb} {} (+{} -{})",
+                change.path.display(),
+                change.added,
+                change.removed
+            ));
         }
+        Ok(ToolResult::success(summary))
+    }
 
-        // Check that old_string exists and is unique
-        let match_count = content.matches(old_string).count();
-        if match_count == 0 {
-            return Ok(ToolResult::failure(format!(
-                "old_string not found in {}",
-                path.display()
-            )));
-        }
-        if match_count > 1 {
-            return Ok(ToolResult::failure(format!(
-                "old_string has {match_count} matches in {} (must be unique)",
-                path.display()
-            )));
-        }
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
 
-        // Perform replacement
-        let new_content = content.replacen(old_string, new_string, 1);
+/// Apply `changes` in order, restoring earlier files if one fails.
+fn commit_changes(
+    changes: &[PlannedChange],
+    write: &dyn Fn(&Path, &str) -> std::io::Result<()>,
+) -> Result<(), String> {
+    for (done, change) in changes.iter().enumerate() {
+        let result = match &change.updated {
+            Some(content) => write(&change.path, content),
+            None => std::fs::remove_file(&change.path),
+        };
+        let Err(e) = result else {
+            continue;
+        };
 
-        // Rewrite in place through the same opened descriptor.
-        if let Err(e) = file.set_len(0) {
-            return Ok(ToolResult::failure(format!(
-                "failed to write {}: {e}",
-                path.display()
-            )));
-        }
-        if let Err(e) = file.rewind() {
-            return Ok(ToolResult::failure(format!(
-                "failed to write {}: {e}",
-                path.display()
-            )));
-        }
-        if let Err(e) = file.write_all(new_content.as_bytes()) {
-            return Ok(ToolResult::failure(format!(
-                "failed to write {}: {e}",
-                path.display()
-            )));
-        }
-        if let Err(e) = file.sync_all() {
-            return Ok(ToolResult::failure(format!(
-                "failed to write {}: {e}",
-                path.display()
-            )));
+        let mut unrestored = Vec::new();
+        for applied in changes[..done].iter().rev() {
+            let restored = match &applied.original {
+                Some(content) => write(&applied.path, content),
+                None => std::fs::remove_file(&applied.path),
+            };
+            if let Err(restore_err) = restored {
+                tracing::error!(
+                    "edit rollback failed for {}: {restore_err}",
+                    applied.path.display()
+                );
+                unrestored.push(applied.path.display().to_string());
+            }
         }
-
-        Ok(ToolResult::success(format!(
-            "edited {}: replaced {} bytes with {} bytes",
-            path.display(),
-            old_string.len(),
-            new_string.len()
-        )))
+        let outcome = if unrestored.is_empty() {
+            format!("rolled back {done} earlier file change(s)")
+        } else {
+            format!("rollback FAILED for: {}", unrestored.join(", "))
+        };
+        return Err(format!(
+            "failed to write {}: {e}; {outcome}",
+            change.path.display()
+        ));
     }
+    Ok(())
+}
 
-    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
-        mode == ToolMode::Full
+/// Replace `path` with `content` through a sibling temp file and rename, so
+/// readers never observe a half-written file.
+pub(super) fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| std::io::Error::other("path has no parent directory"))?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::other("path has no filename"))?;
+    let tmp = parent.join(format!(
+        ".{}.fae-edit-{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    let result = (|| {
+        let mut file = options.open(&tmp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        if let Ok(meta) = std::fs::symlink_metadata(path) {
+            file.set_permissions(meta.permissions())?;
+        }
+        std::fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
     }
+    result
 }
 
 fn canonicalize_for_mutation(
@@ -241,131 +411,138 @@ fn open_existing_rw_nofollow(path: &std::path::Path) -> std::io::Result<std::fs:
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
 
-    fn make_test_file(content: &str) -> (tempfile::TempDir, PathBuf) {
+    fn workspace_with(files: &[(&str, &str)]) -> tempfile::TempDir {
         let dir = tempfile::tempdir()
             .unwrap_or_else(|_| unreachable!("tempdir creation should not fail"));
-        let path = dir.path().join("test_edit.txt");
-        let mut file = std::fs::File::create(&path)
-            .unwrap_or_else(|_| unreachable!("file creation should not fail"));
-        let _ = file.write_all(content.as_bytes());
-        let _ = file.flush();
-        (dir, path)
+        for (name, content) in files {
+            std::fs::write(dir.path().join(name), content)
+                .unwrap_or_else(|_| unreachable!("file creation should not fail"));
+        }
+        dir
+    }
+
+    fn read(dir: &tempfile::TempDir, name: &str) -> String {
+        std::fs::read_to_string(dir.path().join(name)).unwrap_or_default()
+    }
+
+    fn run(tool: &EditTool, patch: &str) -> ToolResult {
+        match tool.execute(serde_json::json!({ "patch": patch })) {
+            Ok(r) => r,
+            Err(_) => unreachable!("should return ToolResult"),
+        }
     }
 
     #[test]
-    fn edit_replace_text() {
-        let (workspace, file) = make_test_file("hello world");
+    fn edit_applies_single_hunk() {
+        let workspace = workspace_with(&[("notes.txt", "line 1\nline 2\nline 3\n")]);
         let tool = EditTool::with_workspace_root(workspace.path().to_path_buf());
-        let result = tool.execute(serde_json::json!({
-            "path": file.to_str(),
-            "old_string": "world",
-            "new_string": "rust"
-        }));
-        let result = match result {
-            Ok(r) => r,
-            Err(_) => unreachable!("edit should succeed"),
-        };
+        let result = run(
+            &tool,
+            "--- a/notes.txt\n+++ b/notes.txt\n@@ -1,3 +1,3 @@\n line 1\n-line 2\n+replaced line\n line 3\n",
+        );
         assert!(result.success);
-        let content = std::fs::read_to_string(file).unwrap_or_default();
-        assert_eq!(content, "hello rust");
+        assert!(result.content.contains("modified"));
+        assert_eq!(
+            read(&workspace, "notes.txt"),
+            "line 1\nreplaced line\nline 3\n"
+        );
     }
 
     #[test]
-    fn edit_multiline_replacement() {
-        let (workspace, file) = make_test_file("line 1\nline 2\nline 3\n");
+    fn edit_applies_multiple_files_create_and_delete() {
+        let workspace = workspace_with(&[("a.txt", "alpha\n"), ("old.txt", "bye\n")]);
         let tool = EditTool::with_workspace_root(workspace.path().to_path_buf());
-        let result = tool.execute(serde_json::json!({
-            "path": file.to_str(),
-            "old_string": "line 2",
-            "new_string": "replaced line"
-        }));
-        let result = match result {
-            Ok(r) => r,
-            Err(_) => unreachable!("edit should succeed"),
-        };
-        assert!(result.success);
-        let content = std::fs::read_to_string(file).unwrap_or_default();
-        assert_eq!(content, "line 1\nreplaced line\nline 3\n");
+        let patch = "\
+--- a/a.txt
++++ b/a.txt
+@@ -1 +1 @@
+-alpha
++ALPHA
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1,2 @@
++fresh
++file
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+";
+        let result = run(&tool, patch);
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.content.starts_with("applied patch to 3 files"));
+        assert_eq!(read(&workspace, "a.txt"), "ALPHA\n");
+        assert_eq!(read(&workspace, "new.txt"), "fresh\nfile\n");
+        assert!(!workspace.path().join("old.txt").exists());
     }
 
     #[test]
-    fn edit_old_string_not_found() {
-        let (workspace, file) = make_test_file("hello world");
+    fn edit_mismatch_leaves_every_file_untouched() {
+        let workspace = workspace_with(&[("a.txt", "alpha\n"), ("b.txt", "beta\n")]);
         let tool = EditTool::with_workspace_root(workspace.path().to_path_buf());
-        let result = tool.execute(serde_json::json!({
-            "path": file.to_str(),
-            "old_string": "nonexistent",
-            "new_string": "replacement"
-        }));
-        let result = match result {
-            Ok(r) => r,
-            Err(_) => unreachable!("should return ToolResult"),
-        };
+        let patch = "--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-alpha\n+ALPHA\n\
+                     --- a/b.txt\n+++ b/b.txt\n@@ -1 +1 @@\n-gamma\n+GAMMA\n";
+        let result = run(&tool, patch);
         assert!(!result.success);
         assert!(
             result
                 .error
                 .as_ref()
-                .is_some_and(|e| e.contains("not found"))
+                .is_some_and(|e| e.contains("does not match"))
         );
+        assert_eq!(read(&workspace, "a.txt"), "alpha\n");
+        assert_eq!(read(&workspace, "b.txt"), "beta\n");
     }
 
     #[test]
-    fn edit_multiple_matches_rejected() {
-        let (workspace, file) = make_test_file("foo bar foo baz");
+    fn edit_rolls_back_when_a_write_fails() {
+        let workspace = workspace_with(&[("a.txt", "alpha\n"), ("b.txt", "beta\n")]);
         let tool = EditTool::with_workspace_root(workspace.path().to_path_buf());
-        let result = tool.execute(serde_json::json!({
-            "path": file.to_str(),
-            "old_string": "foo",
-            "new_string": "qux"
-        }));
-        let result = match result {
-            Ok(r) => r,
-            Err(_) => unreachable!("should return ToolResult"),
+        let patches = match parse_patch(
+            "--- /dev/null\n+++ b/c.txt\n@@ -0,0 +1 @@\n+new\n\
+             --- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-alpha\n+ALPHA\n\
+             --- a/b.txt\n+++ b/b.txt\n@@ -1 +1 @@\n-beta\n+BETA\n",
+        ) {
+            Ok(p) => p,
+            Err(_) => unreachable!("patch should parse"),
         };
-        assert!(!result.success);
+        let changes = match tool.plan(&patches) {
+            Ok(c) => c,
+            Err(_) => unreachable!("patch should apply"),
+        };
+
+        let failing_write = |path: &Path, content: &str| {
+            if path.ends_with("b.txt") {
+                return Err(std::io::Error::other("disk full"));
+            }
+            write_atomic(path, content)
+        };
+        let err = commit_changes(&changes, &failing_write).err();
         assert!(
-            result
-                .error
-                .as_ref()
-                .is_some_and(|e| e.contains("2 matches"))
+            err.as_ref()
+                .is_some_and(|e| e.contains("disk full") && e.contains("rolled back 2"))
         );
+        assert_eq!(read(&workspace, "a.txt"), "alpha\n");
+        assert_eq!(read(&workspace, "b.txt"), "beta\n");
+        assert!(!workspace.path().join("c.txt").exists());
     }
 
     #[test]
-    fn edit_nonexistent_file() {
-        let workspace = tempfile::tempdir()
-            .unwrap_or_else(|_| unreachable!("tempdir creation should not fail"));
+    fn edit_create_rejects_existing_file() {
+        let workspace = workspace_with(&[("a.txt", "alpha\n")]);
         let tool = EditTool::with_workspace_root(workspace.path().to_path_buf());
-        let missing = workspace.path().join("missing.txt");
-        let result = tool.execute(serde_json::json!({
-            "path": missing.to_str(),
-            "old_string": "x",
-            "new_string": "y"
-        }));
-        let result = match result {
-            Ok(r) => r,
-            Err(_) => unreachable!("should return ToolResult"),
-        };
+        let result = run(&tool, "--- /dev/null\n+++ b/a.txt\n@@ -0,0 +1 @@\n+new\n");
         assert!(!result.success);
+        assert_eq!(read(&workspace, "a.txt"), "alpha\n");
     }
 
     #[test]
     fn edit_file_exceeds_max_size() {
         let large = "x".repeat(200);
-        let (workspace, file) = make_test_file(&large);
+        let workspace = workspace_with(&[("big.txt", &large)]);
         let tool = EditTool::with_config(100, workspace.path().to_path_buf());
-        let result = tool.execute(serde_json::json!({
-            "path": file.to_str(),
-            "old_string": "x",
-            "new_string": "y"
-        }));
-        let result = match result {
-            Ok(r) => r,
-            Err(_) => unreachable!("should return ToolResult"),
-        };
+        let result = run(&tool, "--- a/big.txt\n+++ b/big.txt\n@@ -1 +1 @@\n-x\n+y\n");
         assert!(!result.success);
         assert!(
             result
@@ -376,16 +553,35 @@ mod tests {
     }
 
     #[test]
-    fn edit_missing_arguments() {
+    fn edit_missing_or_malformed_patch() {
         let tool = EditTool::new();
         assert!(tool.execute(serde_json::json!({})).is_err());
-        assert!(tool.execute(serde_json::json!({"path": "x"})).is_err());
         assert!(
-            tool.execute(serde_json::json!({"path": "x", "old_string": "y"}))
+            tool.execute(serde_json::json!({"patch": "replace foo with bar"}))
                 .is_err()
         );
     }
 
+    #[test]
+    fn edit_preview_renders_diff_and_warns_on_mismatch() {
+        let workspace = workspace_with(&[("a.txt", "alpha\n")]);
+        let tool = EditTool::with_workspace_root(workspace.path().to_path_buf());
+
+        let good = serde_json::json!({
+            "patch": "--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-alpha\n+ALPHA\n"
+        });
+        let preview = tool.approval_preview(&good).unwrap_or_default();
+        assert!(preview.starts_with("modify a.txt (+1 -1)\n"));
+        assert!(preview.contains("-alpha\n+ALPHA\n"));
+        assert!(!preview.contains("warning"));
+
+        let stale = serde_json::json!({
+            "patch": "--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-beta\n+BETA\n"
+        });
+        let preview = tool.approval_preview(&stale).unwrap_or_default();
+        assert!(preview.contains("warning: patch does not apply"));
+    }
+
     #[test]
     fn edit_only_allowed_in_full_mode() {
         let tool = EditTool::new();
@@ -395,13 +591,10 @@ mod tests {
 
     #[test]
     fn edit_path_traversal_rejected() {
-        let workspace = tempfile::tempdir()
-            .unwrap_or_else(|_| unreachable!("tempdir creation should not fail"));
+        let workspace = workspace_with(&[]);
         let tool = EditTool::with_workspace_root(workspace.path().to_path_buf());
         let result = tool.execute(serde_json::json!({
-            "path": "../../../etc/passwd",
-            "old_string": "x",
-            "new_string": "y"
+            "patch": "--- a/../../../etc/passwd\n+++ b/../../../etc/passwd\n@@ -1 +1 @@\n-x\n+y\n"
         }));
         assert!(result.is_err());
     }
@@ -409,19 +602,16 @@ mod tests {
     #[cfg(unix)]
     #[test]
     fn edit_rejects_symlink_target() {
-        let workspace = tempfile::tempdir()
-            .unwrap_or_else(|_| unreachable!("tempdir creation should not fail"));
-        let real = workspace.path().join("real.txt");
-        std::fs::write(&real, "hello world").unwrap_or_default();
+        let workspace = workspace_with(&[("real.txt", "hello world\n")]);
         let link = workspace.path().join("link.txt");
-        std::os::unix::fs::symlink(&real, &link).unwrap_or_else(|_| unreachable!());
+        std::os::unix::fs::symlink(workspace.path().join("real.txt"), &link)
+            .unwrap_or_else(|_| unreachable!());
 
         let tool = EditTool::with_workspace_root(workspace.path().to_path_buf());
         let result = tool.execute(serde_json::json!({
-            "path": link.to_str(),
-            "old_string": "world",
-            "new_string": "rust"
+            "patch": "--- a/link.txt\n+++ b/link.txt\n@@ -1 +1 @@\n-hello world\n+hello rust\n"
         }));
         assert!(result.is_err(), "symlink edits should be rejected");
+        assert_eq!(read(&workspace, "real.txt"), "hello world\n");
     }
 }
@@ -1,13 +1,17 @@
 //! Fetch URL tool — downloads a web page and extracts readable text content.
 //!
-//! Wraps the [`fae_search`] crate's async `fetch_page_content` API behind the
+//! Wraps the [`fae_search`] crate's async `fetch_page_content_checked` API behind the
 //! synchronous [`Tool`] trait interface using `tokio::runtime::Handle::current().block_on()`.
+//! With a [`NetworkGuard`] set, the URL and every redirect target are checked
+//! against it, so an allowed host cannot bounce the fetch to a denied one.
 
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
 
+use super::network_policy::NetworkGuard;
 use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
 
 const DEFAULT_TIMEOUT_SECS: u64 = 15;
@@ -24,6 +28,7 @@ const DEFAULT_TIMEOUT_SECS: u64 = 15;
 /// - `url` (string, required) — the URL to fetch
 pub struct FetchUrlTool {
     max_bytes: usize,
+    network: Option<Arc<NetworkGuard>>,
 }
 
 impl FetchUrlTool {
@@ -31,8 +36,15 @@ impl FetchUrlTool {
     pub fn new() -> Self {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
+            network: None,
         }
     }
+
+    /// Check each URL and redirect target against `guard` before fetching it.
+    pub fn with_network_guard(mut self, guard: Arc<NetworkGuard>) -> Self {
+        self.network = Some(guard);
+        self
+    }
 }
 
 impl Default for FetchUrlTool {
@@ -81,6 +93,29 @@ impl Tool for FetchUrlTool {
             ));
         }
 
+        crate::offline::ensure_online("fetching web pages")
+            .map_err(|e| FaeLlmError::ToolExecutionError(e.to_string()))?;
+        if let Some(guard) = &self.network {
+            guard.check_url(self.name(), url)?;
+        }
+
+        // A refused redirect fails the fetch; keep the guard's error so it is
+        // reported the same way as a refused first URL.
+        let refused: Arc<Mutex<Option<FaeLlmError>>> = Arc::new(Mutex::new(None));
+        let check: Arc<fae_search::UrlCheck> = match &self.network {
+            Some(guard) => {
+                let (guard, refused) = (Arc::clone(guard), Arc::clone(&refused));
+                Arc::new(move |target: &str| {
+                    guard.check_url("fetch_url", target).map_err(|e| {
+                        let reason = e.to_string();
+                        *refused.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+                        reason
+                    })
+                })
+            }
+            None => Arc::new(|_: &str| Ok(())),
+        };
+
         // Bridge sync Tool::execute to async fae_search::fetch_page_content.
         // Apply an explicit per-tool timeout so behavior is bounded even when
         // outer executor-level timeouts are absent or larger.
@@ -88,7 +123,7 @@ impl Tool for FetchUrlTool {
         let page_result = match tokio::runtime::Handle::try_current() {
             Ok(handle) => handle.block_on(tokio::time::timeout(
                 timeout,
-                fae_search::fetch_page_content(url),
+                fae_search::fetch_page_content_checked(url, check),
             )),
             Err(_) => {
                 let rt = tokio::runtime::Builder::new_current_thread()
@@ -101,11 +136,14 @@ impl Tool for FetchUrlTool {
                     })?;
                 rt.block_on(tokio::time::timeout(
                     timeout,
-                    fae_search::fetch_page_content(url),
+                    fae_search::fetch_page_content_checked(url, check),
                 ))
             }
         };
 
+        if let Some(e) = refused.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            return Err(e);
+        }
         let page = match page_result {
             Ok(Ok(page)) => page,
             Ok(Err(e)) => {
@@ -142,6 +180,8 @@ impl Tool for FetchUrlTool {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
     use super::*;
 
     #[test]
@@ -206,6 +246,53 @@ mod tests {
         assert!(err.to_string().contains("http"));
     }
 
+    #[test]
+    fn denied_domain_is_blocked_before_fetching() {
+        let guard = NetworkGuard::new(crate::fae_llm::tools::NetworkPolicy {
+            deny_domains: vec!["blocked.test".into()],
+            ..Default::default()
+        });
+        let tool = FetchUrlTool::new().with_network_guard(Arc::new(guard));
+        let result = tool.execute(serde_json::json!({"url": "https://www.blocked.test/page"}));
+        let err = match result {
+            Err(e) => e,
+            Ok(_) => unreachable!("denied domain should not be fetched"),
+        };
+        assert!(err.to_string().contains("blocked by policy"));
+    }
+
+    #[test]
+    fn redirect_to_denied_domain_is_blocked() {
+        use std::io::{Read as _, Write as _};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/page", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            stream
+                .write_all(
+                    b"HTTP/1.1 302 Found\r\nlocation: https://www.blocked.test/page\r\n\
+                      content-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .unwrap();
+        });
+        let guard = NetworkGuard::new(crate::fae_llm::tools::NetworkPolicy {
+            deny_domains: vec!["blocked.test".into()],
+            ..Default::default()
+        });
+        let tool = FetchUrlTool::new().with_network_guard(Arc::new(guard));
+
+        let result = tool.execute(serde_json::json!({ "url": url }));
+        server.join().unwrap();
+        let err = match result {
+            Err(e) => e,
+            Ok(_) => unreachable!("redirect to a denied domain should not be followed"),
+        };
+        assert!(err.to_string().contains("blocked by policy"));
+    }
+
     #[test]
     fn allowed_in_both_modes() {
         let tool = FetchUrlTool::new();
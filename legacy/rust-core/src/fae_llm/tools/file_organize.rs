@@ -0,0 +1,689 @@
+//! File organize tool — batch move, rename and copy inside the user's folders.
+//!
+//! The model describes the whole batch declaratively. Every operation is
+//! checked against the disk before anything changes, and the resulting plan
+//! is what the approval prompt shows (see [`Tool::approval_preview`]), so the
+//! user always approves a dry run. Paths must stay inside the allowed roots
+//! (Downloads, Desktop, Documents, Pictures, Movies and Music by default) and
+//! existing files are never overwritten. Completed operations are recorded in
+//! the [`UndoStore`] when one is set; undoing moves files back and deletes
+//! copies.
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::permissions::SharedPermissionStore;
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::path_validation::{check_file_scope, is_path_safe, is_system_path};
+use super::types::{Tool, ToolResult};
+use super::undo::{UndoFile, UndoStore, record_change};
+
+/// Most operations one call may carry.
+pub const MAX_OPERATIONS: usize = 200;
+
+/// Tool that moves, renames and copies files in bulk.
+///
+/// Arguments (JSON):
+/// - `operations` (array, required) — `{op, from, to}` objects where `op`
+///   is `move`, `rename` or `copy`; `from` and `to` are absolute or
+///   `~/` paths, except that `to` is a bare file name for `rename`. Moving
+///   or copying onto an existing folder puts the file inside it.
+/// - `dry_run` (boolean, optional) — return the plan without changing
+///   anything
+///
+/// Only available in `ToolMode::Full`.
+pub struct FileOrganizeTool {
+    roots: Vec<PathBuf>,
+    undo: Option<Arc<UndoStore>>,
+    permissions: Option<SharedPermissionStore>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpKind {
+    Move,
+    Rename,
+    Copy,
+}
+
+impl OpKind {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "move" | "mv" => Some(Self::Move),
+            "rename" => Some(Self::Rename),
+            "copy" | "cp" => Some(Self::Copy),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Move => "move",
+            Self::Rename => "rename",
+            Self::Copy => "copy",
+        }
+    }
+}
+
+/// One operation, checked and ready to run.
+#[derive(Debug)]
+struct PlannedOp {
+    kind: OpKind,
+    from: PathBuf,
+    to: PathBuf,
+}
+
+/// Why a batch cannot run.
+enum PlanError {
+    /// The arguments are invalid (bad operation, path outside the roots).
+    Invalid(FaeLlmError),
+    /// The batch does not fit the files on disk.
+    Failed(String),
+}
+
+fn invalid(message: impl Into<String>) -> PlanError {
+    PlanError::Invalid(FaeLlmError::ToolValidationError(message.into()))
+}
+
+impl FileOrganizeTool {
+    /// Create a tool confined to the user's standard folders.
+    pub fn new() -> Self {
+        Self::with_roots(default_roots())
+    }
+
+    /// Create a tool confined to `roots`; roots that do not exist are dropped.
+    pub fn with_roots(roots: Vec<PathBuf>) -> Self {
+        let roots = roots
+            .iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .filter(|root| !is_system_path(root))
+            .collect();
+        Self {
+            roots,
+            undo: None,
+            permissions: None,
+        }
+    }
+
+    /// Record completed operations in `store` so they can be undone.
+    pub fn with_undo_store(mut self, store: Arc<UndoStore>) -> Self {
+        self.undo = Some(store);
+        self
+    }
+
+    /// Only touch files the `Files` grant in `permissions` covers.
+    pub fn with_permissions(mut self, permissions: SharedPermissionStore) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Check every operation against the disk and the other operations.
+    fn plan(&self, args: &serde_json::Value) -> Result<Vec<PlannedOp>, PlanError> {
+        let operations = args
+            .get("operations")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| invalid("missing required argument: operations"))?;
+        if operations.is_empty() {
+            return Err(invalid("operations is empty"));
+        }
+        if operations.len() > MAX_OPERATIONS {
+            return Err(invalid(format!(
+                "at most {MAX_OPERATIONS} operations per call"
+            )));
+        }
+
+        let mut plan: Vec<PlannedOp> = Vec::with_capacity(operations.len());
+        let mut targets = HashSet::new();
+        let mut moved = HashSet::new();
+        for (i, operation) in operations.iter().enumerate() {
+            let field = |name: &str| {
+                operation
+                    .get(name)
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| invalid(format!("operation {}: missing {name}", i + 1)))
+            };
+            let kind = OpKind::parse(field("op")?).ok_or_else(|| {
+                invalid(format!(
+                    "operation {}: op must be move, rename or copy",
+                    i + 1
+                ))
+            })?;
+            let from = self.source(field("from")?)?;
+            let to = self.target(kind, &from, field("to")?)?;
+            let permissions = self.permissions.as_ref();
+            check_file_scope(permissions, &from, kind != OpKind::Copy)
+                .and_then(|()| check_file_scope(permissions, &to, true))
+                .map_err(PlanError::Invalid)?;
+
+            if moved.contains(&from) {
+                return Err(PlanError::Failed(format!(
+                    "{} is moved by an earlier operation",
+                    display_path(&from)
+                )));
+            }
+            if !targets.insert(to.clone()) {
+                return Err(PlanError::Failed(format!(
+                    "more than one operation targets {}",
+                    display_path(&to)
+                )));
+            }
+            if kind != OpKind::Copy {
+                moved.insert(from.clone());
+            }
+            plan.push(PlannedOp { kind, from, to });
+        }
+        Ok(plan)
+    }
+
+    /// Resolve an existing file or folder to move or copy.
+    fn source(&self, raw: &str) -> Result<PathBuf, PlanError> {
+        let path = absolute(raw)?;
+        let meta = std::fs::symlink_metadata(&path)
+            .map_err(|_| PlanError::Failed(format!("{} does not exist", display_path(&path))))?;
+        if meta.file_type().is_symlink() {
+            return Err(PlanError::Failed(format!(
+                "{} is a symlink",
+                display_path(&path)
+            )));
+        }
+        let canonical = path
+            .canonicalize()
+            .map_err(|e| PlanError::Failed(format!("cannot resolve {raw}: {e}")))?;
+        if self.roots.contains(&canonical) {
+            return Err(invalid(format!(
+                "{} is an allowed folder itself and cannot be moved",
+                display_path(&canonical)
+            )));
+        }
+        self.confine(&canonical)?;
+        Ok(canonical)
+    }
+
+    /// Resolve where `from` ends up; the result never exists yet.
+    fn target(&self, kind: OpKind, from: &Path, raw: &str) -> Result<PathBuf, PlanError> {
+        let file_name = from
+            .file_name()
+            .ok_or_else(|| invalid(format!("{} has no file name", from.display())))?;
+        let mut path = match kind {
+            OpKind::Rename => {
+                if raw.is_empty() || raw.contains('/') || raw.contains('\\') || raw == "." {
+                    return Err(invalid("rename takes a bare file name as `to`"));
+                }
+                if !is_path_safe(raw) {
+                    return Err(invalid("path contains directory traversal"));
+                }
+                from.with_file_name(raw)
+            }
+            OpKind::Move | OpKind::Copy => absolute(raw)?,
+        };
+        if kind != OpKind::Rename
+            && std::fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_dir())
+        {
+            path = path.join(file_name);
+        }
+        if std::fs::symlink_metadata(&path).is_ok() {
+            return Err(PlanError::Failed(format!(
+                "{} already exists",
+                display_path(&path)
+            )));
+        }
+
+        // Re-anchor on the nearest existing folder so a symlinked parent
+        // cannot lead outside the roots.
+        let parent = path
+            .parent()
+            .ok_or_else(|| invalid("path has no parent directory"))?;
+        let ancestor = parent
+            .ancestors()
+            .find(|p| p.exists())
+            .ok_or_else(|| invalid("path parent does not exist"))?;
+        let missing = parent.strip_prefix(ancestor).unwrap_or(Path::new(""));
+        let name = path
+            .file_name()
+            .ok_or_else(|| invalid("path has no file name"))?;
+        let canonical = ancestor
+            .canonicalize()
+            .map_err(|e| PlanError::Failed(format!("cannot resolve {}: {e}", parent.display())))?
+            .join(missing)
+            .join(name);
+        self.confine(&canonical)?;
+
+        if kind == OpKind::Copy && !from.is_file() {
+            return Err(PlanError::Failed(format!(
+                "{} is a folder; only files can be copied",
+                display_path(from)
+            )));
+        }
+        if canonical.starts_with(from) {
+            return Err(PlanError::Failed(format!(
+                "cannot move {} into itself",
+                display_path(from)
+            )));
+        }
+        Ok(canonical)
+    }
+
+    fn confine(&self, canonical: &Path) -> Result<(), PlanError> {
+        if self.roots.iter().any(|root| canonical.starts_with(root)) {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "{} is outside the allowed folders",
+                display_path(canonical)
+            )))
+        }
+    }
+}
+
+impl Default for FileOrganizeTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for FileOrganizeTool {
+    fn name(&self) -> &str {
+        "file_organize"
+    }
+
+    fn description(&self) -> &str {
+        "Move, rename or copy many files at once inside the user's Downloads, Desktop, \
+         Documents, Pictures, Movies and Music folders. Never overwrites; can be undone"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "operations": {
+                    "type": "array",
+                    "minItems": 1,
+                    "maxItems": MAX_OPERATIONS,
+                    "description": "Operations, run in order against the files as they are \
+                        before the batch",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "op": {"type": "string", "enum": ["move", "rename", "copy"]},
+                            "from": {
+                                "type": "string",
+                                "description": "Absolute or ~/ path of the file or folder"
+                            },
+                            "to": {
+                                "type": "string",
+                                "description": "Destination path (missing folders are \
+                                    created; an existing folder receives the file), or the \
+                                    new file name for rename"
+                            }
+                        },
+                        "required": ["op", "from", "to"]
+                    }
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Only show what would happen"
+                }
+            },
+            "required": ["operations"]
+        })
+    }
+
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        if args.get("dry_run").and_then(|v| v.as_bool()) == Some(true) {
+            return None;
+        }
+        Some(match self.plan(args) {
+            Ok(plan) => format!("dry run:\n{}", render_plan(&plan)),
+            Err(PlanError::Invalid(e)) => format!("batch will be rejected: {e}"),
+            Err(PlanError::Failed(e)) => format!("batch cannot run: {e}"),
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let plan = match self.plan(&args) {
+            Ok(plan) => plan,
+            Err(PlanError::Invalid(e)) => return Err(e),
+            Err(PlanError::Failed(message)) => return Ok(ToolResult::failure(message)),
+        };
+        if args.get("dry_run").and_then(|v| v.as_bool()) == Some(true) {
+            return Ok(ToolResult::success(format!(
+                "dry run, nothing changed:\n{}",
+                render_plan(&plan)
+            )));
+        }
+
+        let mut done = Vec::with_capacity(plan.len());
+        let mut failure = None;
+        for op in &plan {
+            match run(op) {
+                Ok(()) => done.push(op),
+                Err(e) => {
+                    failure = Some(format!(
+                        "failed to {} {}: {e}",
+                        op.kind.as_str(),
+                        display_path(&op.from)
+                    ));
+                    break;
+                }
+            }
+        }
+        if !done.is_empty() {
+            record_change(
+                self.undo.as_ref(),
+                self.name(),
+                done.iter()
+                    .map(|op| UndoFile {
+                        path: op.to.clone(),
+                        previous: None,
+                        moved_from: (op.kind != OpKind::Copy).then(|| op.from.clone()),
+                    })
+                    .collect(),
+            );
+        }
+
+        let summary = format!(
+            "completed {} of {} operation{}:\n{}",
+            done.len(),
+            plan.len(),
+            if plan.len() == 1 { "" } else { "s" },
+            done.iter()
+                .map(|op| render_op(op))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        match failure {
+            None => Ok(ToolResult::success(summary.trim_end().to_owned())),
+            Some(e) => Ok(ToolResult::failure(format!("{e}\n{}", summary.trim_end()))),
+        }
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+/// The user's standard folders.
+fn default_roots() -> Vec<PathBuf> {
+    [
+        dirs::download_dir(),
+        dirs::desktop_dir(),
+        dirs::document_dir(),
+        dirs::picture_dir(),
+        dirs::video_dir(),
+        dirs::audio_dir(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Expand `~/` and require an absolute path without `..`.
+fn absolute(raw: &str) -> Result<PathBuf, PlanError> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err(invalid("path is empty"));
+    }
+    if !is_path_safe(raw) {
+        return Err(invalid("path contains directory traversal"));
+    }
+    let path = match raw.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .ok_or_else(|| invalid("no home directory"))?
+            .join(rest),
+        None => PathBuf::from(raw),
+    };
+    if path.is_absolute() {
+        Ok(path)
+    } else {
+        Err(invalid(format!("{raw} must be absolute or start with ~/")))
+    }
+}
+
+fn run(op: &PlannedOp) -> std::io::Result<()> {
+    if let Some(parent) = op.to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    match op.kind {
+        OpKind::Copy => copy_new(&op.from, &op.to),
+        OpKind::Move | OpKind::Rename => {
+            if std::fs::symlink_metadata(&op.to).is_ok() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("{} appeared", op.to.display()),
+                ));
+            }
+            match std::fs::rename(&op.from, &op.to) {
+                Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices && op.from.is_file() => {
+                    copy_new(&op.from, &op.to)?;
+                    std::fs::remove_file(&op.from)
+                }
+                other => other,
+            }
+        }
+    }
+}
+
+/// Copy a file to a path that must not exist yet.
+fn copy_new(from: &Path, to: &Path) -> std::io::Result<()> {
+    let mut source = std::fs::File::open(from)?;
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    let mut dest = options.open(to)?;
+    let result = (|| {
+        std::io::copy(&mut source, &mut dest)?;
+        dest.set_permissions(source.metadata()?.permissions())?;
+        dest.sync_all()
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(to);
+    }
+    result
+}
+
+fn render_op(op: &PlannedOp) -> String {
+    format!(
+        "{} {} -> {}",
+        op.kind.as_str(),
+        display_path(&op.from),
+        display_path(&op.to)
+    )
+}
+
+fn render_plan(plan: &[PlannedOp]) -> String {
+    plan.iter().map(render_op).collect::<Vec<_>>().join("\n")
+}
+
+/// `path` with the home directory shortened to `~`.
+fn display_path(path: &Path) -> String {
+    if let Some(home) = dirs::home_dir()
+        && let Ok(rest) = path.strip_prefix(&home)
+    {
+        return format!("~/{}", rest.display());
+    }
+    path.display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn setup() -> (tempfile::TempDir, FileOrganizeTool) {
+        let dir = tempfile::tempdir().unwrap();
+        for folder in ["Downloads", "Documents"] {
+            std::fs::create_dir(dir.path().join(folder)).unwrap();
+        }
+        let tool = FileOrganizeTool::with_roots(vec![
+            dir.path().join("Downloads"),
+            dir.path().join("Documents"),
+        ]);
+        (dir, tool)
+    }
+
+    fn path(dir: &tempfile::TempDir, rel: &str) -> String {
+        dir.path().join(rel).to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn batch_moves_renames_and_copies() {
+        let (dir, tool) = setup();
+        for name in ["a.pdf", "b.jpg", "c.txt"] {
+            std::fs::write(dir.path().join("Downloads").join(name), name).unwrap();
+        }
+        let args = serde_json::json!({"operations": [
+            {"op": "copy", "from": path(&dir, "Downloads/a.pdf"),
+             "to": path(&dir, "Downloads/a copy.pdf")},
+            {"op": "move", "from": path(&dir, "Downloads/a.pdf"), "to": path(&dir, "Documents")},
+            {"op": "move", "from": path(&dir, "Downloads/b.jpg"),
+             "to": path(&dir, "Downloads/Images/b.jpg")},
+            {"op": "rename", "from": path(&dir, "Downloads/c.txt"), "to": "notes.txt"},
+        ]});
+
+        let result = tool.execute(args).unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.content.starts_with("completed 4 of 4 operations"));
+        let read = |rel: &str| std::fs::read_to_string(dir.path().join(rel)).unwrap();
+        assert_eq!(read("Documents/a.pdf"), "a.pdf");
+        assert_eq!(read("Downloads/Images/b.jpg"), "b.jpg");
+        assert_eq!(read("Downloads/notes.txt"), "c.txt");
+        assert_eq!(read("Downloads/a copy.pdf"), "a.pdf");
+        assert!(!dir.path().join("Downloads/a.pdf").exists());
+
+        let again = serde_json::json!({"operations": [
+            {"op": "move", "from": path(&dir, "Documents/a.pdf"), "to": path(&dir, "Downloads")},
+            {"op": "copy", "from": path(&dir, "Documents/a.pdf"), "to": path(&dir, "Downloads/x")},
+        ]});
+        let result = tool.execute(again).unwrap();
+        assert!(
+            result
+                .error
+                .unwrap()
+                .contains("moved by an earlier operation")
+        );
+    }
+
+    #[test]
+    fn dry_run_and_preview_change_nothing() {
+        let (dir, tool) = setup();
+        std::fs::write(dir.path().join("Downloads/a.pdf"), "a").unwrap();
+        let ops = serde_json::json!([
+            {"op": "move", "from": path(&dir, "Downloads/a.pdf"), "to": path(&dir, "Documents")}
+        ]);
+
+        let preview = tool
+            .approval_preview(&serde_json::json!({"operations": ops}))
+            .unwrap();
+        assert!(preview.starts_with("dry run:\nmove "));
+        assert!(preview.contains("Documents/a.pdf"));
+
+        let result = tool
+            .execute(serde_json::json!({"operations": ops, "dry_run": true}))
+            .unwrap();
+        assert!(result.success);
+        assert!(result.content.starts_with("dry run, nothing changed:"));
+        assert!(dir.path().join("Downloads/a.pdf").exists());
+        assert!(!dir.path().join("Documents/a.pdf").exists());
+    }
+
+    #[test]
+    fn paths_outside_roots_and_traversal_are_rejected() {
+        let (dir, tool) = setup();
+        std::fs::write(dir.path().join("secret.txt"), "s").unwrap();
+        std::fs::write(dir.path().join("Downloads/a.pdf"), "a").unwrap();
+        let op = |from: String, to: String| serde_json::json!({"operations": [{"op": "move", "from": from, "to": to}]});
+
+        assert!(
+            tool.execute(op(path(&dir, "secret.txt"), path(&dir, "Documents")))
+                .is_err()
+        );
+        assert!(
+            tool.execute(op(path(&dir, "Downloads/a.pdf"), path(&dir, "a.pdf")))
+                .is_err()
+        );
+        assert!(
+            tool.execute(op(
+                path(&dir, "Downloads/a.pdf"),
+                path(&dir, "Documents/../a.pdf")
+            ))
+            .is_err()
+        );
+        assert!(
+            tool.execute(op(path(&dir, "Downloads"), path(&dir, "Documents")))
+                .is_err()
+        );
+        let rename = serde_json::json!({"operations": [
+            {"op": "rename", "from": path(&dir, "Downloads/a.pdf"), "to": "../a.pdf"}
+        ]});
+        assert!(tool.execute(rename).is_err());
+        assert!(dir.path().join("Downloads/a.pdf").exists());
+    }
+
+    #[test]
+    fn conflicts_fail_before_anything_changes() {
+        let (dir, tool) = setup();
+        for name in ["a.pdf", "b.pdf"] {
+            std::fs::write(dir.path().join("Downloads").join(name), name).unwrap();
+        }
+        std::fs::write(dir.path().join("Documents/b.pdf"), "old").unwrap();
+
+        let existing = serde_json::json!({"operations": [
+            {"op": "move", "from": path(&dir, "Downloads/a.pdf"), "to": path(&dir, "Documents")},
+            {"op": "move", "from": path(&dir, "Downloads/b.pdf"), "to": path(&dir, "Documents")},
+        ]});
+        let result = tool.execute(existing).unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("already exists"));
+        assert!(dir.path().join("Downloads/a.pdf").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("Documents/b.pdf")).unwrap(),
+            "old"
+        );
+
+        let duplicate = serde_json::json!({"operations": [
+            {"op": "copy", "from": path(&dir, "Downloads/a.pdf"),
+             "to": path(&dir, "Documents/x.pdf")},
+            {"op": "copy", "from": path(&dir, "Downloads/b.pdf"),
+             "to": path(&dir, "Documents/x.pdf")},
+        ]});
+        let result = tool.execute(duplicate).unwrap();
+        assert!(result.error.unwrap().contains("more than one operation"));
+    }
+
+    #[test]
+    fn undo_restores_the_batch() {
+        let (dir, tool) = setup();
+        let store = Arc::new(UndoStore::new(dir.path().join("undo")));
+        let tool = tool.with_undo_store(Arc::clone(&store));
+        std::fs::write(dir.path().join("Downloads/a.pdf"), "a").unwrap();
+        std::fs::write(dir.path().join("Downloads/c.txt"), "c").unwrap();
+        let args = serde_json::json!({"operations": [
+            {"op": "move", "from": path(&dir, "Downloads/a.pdf"),
+             "to": path(&dir, "Documents/Papers/a.pdf")},
+            {"op": "copy", "from": path(&dir, "Downloads/c.txt"), "to": path(&dir, "Documents")},
+        ]});
+        assert!(tool.execute(args).unwrap().success);
+        assert_eq!(store.history(1).unwrap()[0].files.len(), 2);
+
+        let outcomes = store.undo(1).unwrap();
+        assert_eq!(outcomes[0].error, None);
+        assert!(dir.path().join("Downloads/a.pdf").exists());
+        assert!(!dir.path().join("Documents/Papers/a.pdf").exists());
+        assert!(dir.path().join("Downloads/c.txt").exists());
+        assert!(!dir.path().join("Documents/c.txt").exists());
+    }
+
+    #[test]
+    fn only_allowed_in_full_mode() {
+        let tool = FileOrganizeTool::new();
+        assert!(!tool.allowed_in_mode(ToolMode::ReadOnly));
+        assert!(tool.allowed_in_mode(ToolMode::Full));
+    }
+}
@@ -0,0 +1,293 @@
+//! Native UI tools backed by the host bridge.
+//!
+//! These tools ask the native shell to show a file picker, post a
+//! notification, or open the share sheet through
+//! [`crate::host::bridge::ask_host`]. The user confirms every pick and share
+//! in the shell's own UI, so no approval prompt is added on top.
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::host::bridge::{HostRequest, HostResponse, ask_host};
+use std::path::PathBuf;
+
+use super::types::{Tool, ToolResult};
+
+fn optional_str(args: &serde_json::Value, key: &str) -> Option<String> {
+    args.get(key)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+fn required_str(args: &serde_json::Value, key: &str) -> Result<String, FaeLlmError> {
+    optional_str(args, key).ok_or_else(|| {
+        FaeLlmError::ToolValidationError(format!("missing required argument: {key}"))
+    })
+}
+
+/// Ask the shell and turn anything but the expected answer into a failure.
+fn ask(
+    request: HostRequest,
+    on_answer: impl FnOnce(HostResponse) -> Option<ToolResult>,
+) -> ToolResult {
+    match ask_host(request) {
+        Ok(HostResponse::Cancelled) => ToolResult::failure("The user cancelled.".to_owned()),
+        Ok(HostResponse::Failed { error }) => ToolResult::failure(error),
+        Ok(response) => on_answer(response.clone()).unwrap_or_else(|| {
+            ToolResult::failure(format!(
+                "unexpected answer from the native shell: {response:?}"
+            ))
+        }),
+        Err(e) => ToolResult::failure(format!("{e}")),
+    }
+}
+
+/// Tool that lets the user choose a file or folder.
+///
+/// # Arguments (JSON)
+///
+/// - `prompt` (string, required) — what the user is asked to choose
+/// - `directory` (bool, optional) — choose a folder instead of a file
+/// - `allowed_types` (array of strings, optional) — file extensions to offer
+pub struct PickFileTool;
+
+impl PickFileTool {
+    /// Create a new `PickFileTool`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PickFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for PickFileTool {
+    fn name(&self) -> &str {
+        "pick_file"
+    }
+
+    fn description(&self) -> &str {
+        "Ask the user to choose a file or folder in a native picker and return its path. \
+         Use this instead of guessing where a file is."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "prompt": {
+                    "type": "string",
+                    "description": "What the user should choose, e.g. 'Choose the invoice to read'"
+                },
+                "directory": {
+                    "type": "boolean",
+                    "description": "Choose a folder instead of a file"
+                },
+                "allowed_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "File extensions to offer, e.g. [\"pdf\"]"
+                }
+            },
+            "required": ["prompt"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let prompt = required_str(&args, "prompt")?;
+        let directory = args
+            .get("directory")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let allowed_types = args
+            .get("allowed_types")
+            .and_then(|v| v.as_array())
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(|t| t.as_str())
+                    .map(|t| t.trim().trim_start_matches('.').to_owned())
+                    .filter(|t| !t.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let request = HostRequest::PickFile {
+            prompt,
+            directory,
+            allowed_types,
+        };
+        Ok(ask(request, |response| match response {
+            HostResponse::Picked { path, .. } => {
+                Some(ToolResult::success(path.display().to_string()))
+            }
+            _ => None,
+        }))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true
+    }
+}
+
+/// Tool that posts a native notification.
+///
+/// # Arguments (JSON)
+///
+/// - `title` (string, required)
+/// - `body` (string, required)
+pub struct NotifyTool;
+
+impl NotifyTool {
+    /// Create a new `NotifyTool`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for NotifyTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for NotifyTool {
+    fn name(&self) -> &str {
+        "post_notification"
+    }
+
+    fn description(&self) -> &str {
+        "Post a native notification with a title and body."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string", "description": "Notification title" },
+                "body": { "type": "string", "description": "Notification text" }
+            },
+            "required": ["title", "body"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let title = required_str(&args, "title")?;
+        let body = required_str(&args, "body")?;
+        Ok(ask(HostRequest::Notify { title, body }, |response| {
+            (response == HostResponse::Done)
+                .then(|| ToolResult::success("Notification posted.".to_owned()))
+        }))
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+/// Tool that opens the share sheet.
+///
+/// # Arguments (JSON)
+///
+/// At least one of:
+/// - `text` (string) — text to share
+/// - `url` (string) — link to share
+/// - `path` (string) — absolute path of a file to share
+pub struct ShareTool;
+
+impl ShareTool {
+    /// Create a new `ShareTool`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ShareTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for ShareTool {
+    fn name(&self) -> &str {
+        "share"
+    }
+
+    fn description(&self) -> &str {
+        "Open the share sheet so the user can send text, a link, or a file to another app \
+         or person."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "text": { "type": "string", "description": "Text to share" },
+                "url": { "type": "string", "description": "Link to share" },
+                "path": { "type": "string", "description": "Absolute path of a file to share" }
+            }
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let text = optional_str(&args, "text");
+        let url = optional_str(&args, "url");
+        let path = optional_str(&args, "path").map(PathBuf::from);
+        if text.is_none() && url.is_none() && path.is_none() {
+            return Err(FaeLlmError::ToolValidationError(
+                "share needs text, url, or path".into(),
+            ));
+        }
+        if let Some(path) = &path
+            && !path.is_absolute()
+        {
+            return Err(FaeLlmError::ToolValidationError(format!(
+                "share path must be absolute: {}",
+                path.display()
+            )));
+        }
+
+        Ok(ask(HostRequest::Share { text, url, path }, |response| {
+            (response == HostResponse::Done).then(|| ToolResult::success("Shared.".to_owned()))
+        }))
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn mode_gating() {
+        assert!(PickFileTool::new().allowed_in_mode(ToolMode::ReadOnly));
+        assert!(!NotifyTool::new().allowed_in_mode(ToolMode::ReadOnly));
+        assert!(!ShareTool::new().allowed_in_mode(ToolMode::ReadOnly));
+        assert!(ShareTool::new().allowed_in_mode(ToolMode::Full));
+    }
+
+    #[test]
+    fn validates_arguments_before_asking_the_shell() {
+        assert!(PickFileTool::new().execute(serde_json::json!({})).is_err());
+        assert!(
+            NotifyTool::new()
+                .execute(serde_json::json!({"title": "Hi"}))
+                .is_err()
+        );
+        assert!(ShareTool::new().execute(serde_json::json!({})).is_err());
+        assert!(
+            ShareTool::new()
+                .execute(serde_json::json!({"path": "notes.txt"}))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,507 @@
+//! HTTP request tool — calls REST endpoints with any method, headers and body.
+//!
+//! Unlike [`FetchUrlTool`](super::FetchUrlTool), which extracts readable
+//! text from web pages, this tool returns the raw response (status, headers
+//! and body) for talking to the user's own services. Every host is checked
+//! against the [`NetworkGuard`], redirects are not followed so a response
+//! cannot bounce the call to an unchecked host, and only `GET` and `HEAD`
+//! calls skip approval (see [`Tool::requires_approval`]).
+
+use std::io::Read as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+
+use super::network_policy::NetworkGuard;
+use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
+
+/// Methods the tool sends.
+const METHODS: &[&str] = &["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+/// Methods that only read and run without approval.
+const SAFE_METHODS: &[&str] = &["GET", "HEAD"];
+/// Headers the HTTP client manages itself.
+const RESERVED_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "transfer-encoding",
+    "connection",
+    "upgrade",
+];
+/// Headers whose values are hidden in approval prompts.
+const SECRET_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+    "api-key",
+];
+/// Response headers included in the result.
+const REPORTED_HEADERS: &[&str] = &[
+    "content-type",
+    "content-length",
+    "location",
+    "etag",
+    "last-modified",
+    "retry-after",
+];
+
+const DEFAULT_TIMEOUT_SECS: u64 = 20;
+/// Largest request body accepted.
+const MAX_REQUEST_BYTES: usize = 256 * 1024;
+/// Response bytes read before the rest is dropped.
+const MAX_RESPONSE_BYTES: u64 = 1024 * 1024;
+
+/// Tool that sends an HTTP request and returns the response.
+///
+/// Arguments (JSON):
+/// - `url` (string, required) — `http://` or `https://` URL
+/// - `method` (string, optional) — `GET` (default), `HEAD`, `POST`, `PUT`,
+///   `PATCH`, `DELETE` or `OPTIONS`
+/// - `headers` (object, optional) — header names to string values
+/// - `json` (any, optional) — JSON body, sent as `application/json`
+/// - `body` (string, optional) — raw body; not together with `json`
+///
+/// Only available in `ToolMode::Full`.
+pub struct HttpRequestTool {
+    max_bytes: usize,
+    timeout: Duration,
+    network: Option<Arc<NetworkGuard>>,
+}
+
+/// A checked request, ready to send.
+#[derive(Debug)]
+struct Request {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+impl HttpRequestTool {
+    /// Create a new `HttpRequestTool` with the default limits.
+    pub fn new() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_BYTES,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            network: None,
+        }
+    }
+
+    /// Check each URL against `guard` before sending.
+    pub fn with_network_guard(mut self, guard: Arc<NetworkGuard>) -> Self {
+        self.network = Some(guard);
+        self
+    }
+
+    fn send(&self, request: &Request) -> Result<ureq::Response, String> {
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(Duration::from_secs(5))
+            .timeout(self.timeout)
+            .redirects(0)
+            .build();
+        let mut call = agent.request(&request.method, &request.url);
+        for (name, value) in &request.headers {
+            call = call.set(name, value);
+        }
+        let result = match &request.body {
+            Some(body) => call.send_string(body),
+            None => call.call(),
+        };
+        match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => Ok(response),
+            Err(e) => Err(format!("{} {} failed: {e}", request.method, request.url)),
+        }
+    }
+}
+
+impl Default for HttpRequestTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for HttpRequestTool {
+    fn name(&self) -> &str {
+        "http_request"
+    }
+
+    fn description(&self) -> &str {
+        "Send an HTTP request (GET, POST, PUT, PATCH, DELETE, ...) with headers and a JSON or \
+         text body, and return the status, headers and response body. For reading web pages \
+         use fetch_url instead"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "URL starting with http:// or https://"
+                },
+                "method": {
+                    "type": "string",
+                    "enum": METHODS,
+                    "description": "HTTP method (default GET)"
+                },
+                "headers": {
+                    "type": "object",
+                    "additionalProperties": {"type": "string"},
+                    "description": "Request headers"
+                },
+                "json": {
+                    "description": "JSON request body, sent as application/json"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Raw request body; do not combine with json"
+                }
+            },
+            "required": ["url"]
+        })
+    }
+
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "status": {"type": "integer"},
+                "headers": {
+                    "type": "object",
+                    "additionalProperties": {"type": "string"}
+                },
+                "json": {"description": "Response body, when it is JSON"}
+            },
+            "required": ["status", "headers"]
+        }))
+    }
+
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        let request = match parse_request(args) {
+            Ok(request) => request,
+            Err(e) => return Some(format!("request will be rejected: {e}")),
+        };
+        let mut preview = format!("{} {}", request.method, request.url);
+        for (name, value) in &request.headers {
+            let shown = if SECRET_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                "<hidden>"
+            } else {
+                value.as_str()
+            };
+            preview.push_str(&format!("\n{name}: {shown}"));
+        }
+        if let Some(body) = &request.body {
+            let (body, _) = truncate_output(body, 2048);
+            preview.push_str(&format!("\n\n{body}"));
+        }
+        Some(preview)
+    }
+
+    fn requires_approval(&self, args: &serde_json::Value) -> bool {
+        parse_request(args).map_or(true, |r| !SAFE_METHODS.contains(&r.method.as_str()))
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let request = parse_request(&args)?;
+        crate::offline::ensure_online("sending HTTP requests")
+            .map_err(|e| FaeLlmError::ToolExecutionError(e.to_string()))?;
+        if let Some(guard) = &self.network {
+            guard.check_url(self.name(), &request.url)?;
+        }
+
+        let response = match self.send(&request) {
+            Ok(response) => response,
+            Err(message) => return Ok(ToolResult::failure(message)),
+        };
+        let status = response.status();
+        let mut text = format!("HTTP {status} {}", response.status_text());
+        let mut headers = serde_json::Map::new();
+        for name in REPORTED_HEADERS {
+            if let Some(value) = response.header(name) {
+                text.push_str(&format!("\n{name}: {value}"));
+                headers.insert((*name).to_owned(), value.into());
+            }
+        }
+
+        let mut raw = Vec::new();
+        if let Err(e) = response
+            .into_reader()
+            .take(MAX_RESPONSE_BYTES)
+            .read_to_end(&mut raw)
+        {
+            return Ok(ToolResult::failure(format!(
+                "reading the response from {} failed: {e}",
+                request.url
+            )));
+        }
+        let body = String::from_utf8_lossy(&raw);
+        let json = serde_json::from_slice::<serde_json::Value>(&raw).ok();
+        if !body.is_empty() {
+            text.push_str("\n\n");
+            match &json {
+                Some(value) => text.push_str(
+                    &serde_json::to_string_pretty(value).unwrap_or_else(|_| body.to_string()),
+                ),
+                None => text.push_str(&body),
+            }
+        }
+
+        let (text, truncated) = truncate_output(&text, self.max_bytes);
+        let mut structured = serde_json::json!({"status": status, "headers": headers});
+        if let Some(json) = json {
+            structured["json"] = json;
+        }
+        let result = if truncated {
+            ToolResult::success_truncated(text)
+        } else {
+            ToolResult::success(text)
+        };
+        Ok(result.with_structured(structured))
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+fn parse_request(args: &serde_json::Value) -> Result<Request, FaeLlmError> {
+    let invalid = |message: String| FaeLlmError::ToolValidationError(message);
+
+    let url = args
+        .get("url")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .ok_or_else(|| invalid("missing required argument: url".into()))?;
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(invalid("url must start with http:// or https://".into()));
+    }
+
+    let method = match args.get("method").and_then(|v| v.as_str()) {
+        Some(m) => m.trim().to_ascii_uppercase(),
+        None => "GET".to_owned(),
+    };
+    if !METHODS.contains(&method.as_str()) {
+        return Err(invalid(format!(
+            "unsupported method {method}: use {}",
+            METHODS.join(", ")
+        )));
+    }
+
+    let mut headers = Vec::new();
+    if let Some(map) = args.get("headers").filter(|v| !v.is_null()) {
+        let map = map
+            .as_object()
+            .ok_or_else(|| invalid("headers must be an object".into()))?;
+        for (name, value) in map {
+            let value = value
+                .as_str()
+                .ok_or_else(|| invalid(format!("header {name} must be a string")))?;
+            if name.is_empty() || !name.bytes().all(is_token_byte) {
+                return Err(invalid(format!("invalid header name: {name}")));
+            }
+            if value.contains(['\r', '\n']) {
+                return Err(invalid(format!("header {name} contains a line break")));
+            }
+            if RESERVED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                return Err(invalid(format!("header {name} is set automatically")));
+            }
+            headers.push((name.clone(), value.to_owned()));
+        }
+    }
+
+    let text = args.get("body").filter(|v| !v.is_null());
+    let json = args.get("json").filter(|v| !v.is_null());
+    let body = match (text, json) {
+        (Some(_), Some(_)) => return Err(invalid("pass either body or json, not both".into())),
+        (Some(text), None) => Some(
+            text.as_str()
+                .ok_or_else(|| invalid("body must be a string".into()))?
+                .to_owned(),
+        ),
+        (None, Some(json)) => {
+            if !headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            {
+                headers.push(("Content-Type".to_owned(), "application/json".to_owned()));
+            }
+            Some(json.to_string())
+        }
+        (None, None) => None,
+    };
+    if body.as_ref().is_some_and(|b| b.len() > MAX_REQUEST_BYTES) {
+        return Err(invalid(format!(
+            "request body exceeds {MAX_REQUEST_BYTES} bytes"
+        )));
+    }
+    if body.is_some() && SAFE_METHODS.contains(&method.as_str()) {
+        return Err(invalid(format!("{method} requests cannot carry a body")));
+    }
+
+    Ok(Request {
+        method,
+        url: url.to_owned(),
+        headers,
+        body,
+    })
+}
+
+/// Characters allowed in an HTTP header name (RFC 9110 `token`).
+fn is_token_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use std::io::{BufRead as _, BufReader, Write as _};
+    use std::net::TcpListener;
+
+    /// Serve one request, answering with `response`; returns the URL and a
+    /// handle yielding the raw request head and body.
+    fn serve_once(response: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/items", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+            (&stream).write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn post_sends_json_and_captures_the_response() {
+        let (url, server) = serve_once(
+            "HTTP/1.1 201 Created\r\ncontent-type: application/json\r\n\
+             content-length: 15\r\nconnection: close\r\n\r\n{\"id\":7,\"ok\":1}",
+        );
+        let result = HttpRequestTool::new()
+            .execute(serde_json::json!({
+                "url": url,
+                "method": "post",
+                "headers": {"Authorization": "Bearer secret"},
+                "json": {"name": "milk"}
+            }))
+            .unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /items HTTP/1.1\r\n"));
+        assert!(request.contains("Authorization: Bearer secret\r\n"));
+        assert!(request.contains("Content-Type: application/json\r\n"));
+        assert!(request.ends_with("{\"name\":\"milk\"}"));
+
+        assert!(result.success);
+        assert!(
+            result
+                .content
+                .starts_with("HTTP 201 Created\ncontent-type: application/json")
+        );
+        let structured = result.structured.unwrap();
+        assert_eq!(structured["status"], 201);
+        assert_eq!(structured["json"]["id"], 7);
+    }
+
+    #[test]
+    fn error_statuses_are_reported_not_raised() {
+        let (url, server) = serve_once(
+            "HTTP/1.1 404 Not Found\r\ncontent-length: 4\r\nconnection: close\r\n\r\nnope",
+        );
+        let result = HttpRequestTool::new()
+            .execute(serde_json::json!({"url": url}))
+            .unwrap();
+        server.join().unwrap();
+        assert!(result.success);
+        assert_eq!(
+            result.content,
+            "HTTP 404 Not Found\ncontent-length: 4\n\nnope"
+        );
+    }
+
+    #[test]
+    fn invalid_requests_are_rejected() {
+        let tool = HttpRequestTool::new();
+        let rejected = |args: serde_json::Value| tool.execute(args).is_err();
+        assert!(rejected(serde_json::json!({})));
+        assert!(rejected(serde_json::json!({"url": "ftp://example.com"})));
+        assert!(rejected(
+            serde_json::json!({"url": "https://example.com", "method": "TRACE"})
+        ));
+        assert!(rejected(serde_json::json!({
+            "url": "https://example.com",
+            "headers": {"X-Test": "a\r\nInjected: yes"}
+        })));
+        assert!(rejected(serde_json::json!({
+            "url": "https://example.com",
+            "headers": {"Host": "other.example"}
+        })));
+        assert!(rejected(serde_json::json!({
+            "url": "https://example.com", "method": "POST", "body": "a", "json": {}
+        })));
+        assert!(rejected(
+            serde_json::json!({"url": "https://example.com", "body": "a"})
+        ));
+    }
+
+    #[test]
+    fn only_non_get_methods_need_approval() {
+        let tool = HttpRequestTool::new();
+        let url = "https://api.example.com/items";
+        assert!(!tool.requires_approval(&serde_json::json!({"url": url})));
+        assert!(!tool.requires_approval(&serde_json::json!({"url": url, "method": "head"})));
+        assert!(tool.requires_approval(&serde_json::json!({"url": url, "method": "DELETE"})));
+        assert!(tool.requires_approval(&serde_json::json!({"url": "bad", "method": "GET"})));
+    }
+
+    #[test]
+    fn approval_preview_hides_secrets() {
+        let preview = HttpRequestTool::new()
+            .approval_preview(&serde_json::json!({
+                "url": "https://api.example.com/items",
+                "method": "PUT",
+                "headers": {"Authorization": "Bearer secret", "X-Trace": "1"},
+                "json": {"done": true}
+            }))
+            .unwrap();
+        assert_eq!(
+            preview,
+            "PUT https://api.example.com/items\nAuthorization: <hidden>\nX-Trace: 1\n\
+             Content-Type: application/json\n\n{\"done\":true}"
+        );
+    }
+
+    #[test]
+    fn denied_domain_is_blocked_before_sending() {
+        let guard = NetworkGuard::new(crate::fae_llm::tools::NetworkPolicy {
+            deny_domains: vec!["blocked.test".into()],
+            ..Default::default()
+        });
+        let tool = HttpRequestTool::new().with_network_guard(Arc::new(guard));
+        let err = tool
+            .execute(serde_json::json!({"url": "https://api.blocked.test/v1", "method": "POST"}))
+            .unwrap_err();
+        assert!(err.to_string().contains("blocked by policy"));
+        assert!(!tool.allowed_in_mode(ToolMode::ReadOnly));
+    }
+}
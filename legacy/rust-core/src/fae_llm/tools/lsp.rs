@@ -0,0 +1,917 @@
+//! LSP tool — code navigation backed by a language server.
+//!
+//! The language server for a file's language is started on first use,
+//! rooted at the workspace, and kept running for later queries. Supported
+//! queries are go-to-definition, find-references, diagnostics, and document
+//! symbols. Servers are looked up on `PATH`; a missing server is reported as
+//! a tool failure naming the command that would have been run.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::{Mutex, mpsc};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+
+use super::path_validation::{resolve_workspace_root, validate_read_path_in_workspace};
+use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
+
+/// Default time to wait for a language server reply, in seconds.
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Maximum number of locations or diagnostics listed in one result.
+const MAX_ITEMS: usize = 100;
+
+/// A language server and the file extensions it handles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LspServerSpec {
+    /// LSP language identifier sent when opening a document (e.g. `"rust"`).
+    pub language_id: String,
+    /// File extensions handled, without the leading dot.
+    pub extensions: Vec<String>,
+    /// Executable to run.
+    pub command: String,
+    /// Arguments passed to the executable.
+    pub args: Vec<String>,
+}
+
+impl LspServerSpec {
+    /// Create a server spec.
+    pub fn new(language_id: &str, command: &str, args: &[&str], extensions: &[&str]) -> Self {
+        Self {
+            language_id: language_id.to_owned(),
+            extensions: extensions.iter().map(|e| (*e).to_owned()).collect(),
+            command: command.to_owned(),
+            args: args.iter().map(|a| (*a).to_owned()).collect(),
+        }
+    }
+
+    /// Language identifier for a file with extension `ext`.
+    fn language_id_for(&self, ext: &str) -> String {
+        let id = match ext {
+            "tsx" => "typescriptreact",
+            "js" | "mjs" | "cjs" => "javascript",
+            "jsx" => "javascriptreact",
+            "c" | "h" => "c",
+            _ => self.language_id.as_str(),
+        };
+        id.to_owned()
+    }
+}
+
+/// Language servers used when none are configured.
+pub fn default_servers() -> Vec<LspServerSpec> {
+    vec![
+        LspServerSpec::new("rust", "rust-analyzer", &[], &["rs"]),
+        LspServerSpec::new("python", "pyright-langserver", &["--stdio"], &["py"]),
+        LspServerSpec::new(
+            "typescript",
+            "typescript-language-server",
+            &["--stdio"],
+            &["ts", "tsx", "js", "jsx", "mjs", "cjs"],
+        ),
+        LspServerSpec::new("go", "gopls", &[], &["go"]),
+        LspServerSpec::new("cpp", "clangd", &[], &["c", "h", "cc", "cpp", "hpp"]),
+        LspServerSpec::new("swift", "sourcekit-lsp", &[], &["swift"]),
+    ]
+}
+
+/// Query supported by [`LspTool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LspAction {
+    Definition,
+    References,
+    Diagnostics,
+    Symbols,
+}
+
+impl LspAction {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "definition" => Some(Self::Definition),
+            "references" => Some(Self::References),
+            "diagnostics" => Some(Self::Diagnostics),
+            "symbols" => Some(Self::Symbols),
+            _ => None,
+        }
+    }
+
+    fn needs_position(self) -> bool {
+        matches!(self, Self::Definition | Self::References)
+    }
+}
+
+/// Tool that answers code navigation queries through a language server.
+///
+/// Arguments (JSON):
+/// - `action` (string, required) — `definition`, `references`,
+///   `diagnostics`, or `symbols`
+/// - `path` (string, required) — file to query
+/// - `line` (integer) — 1-based line; required for `definition` and
+///   `references`
+/// - `symbol` (string, optional) — identifier on that line to query
+/// - `column` (integer, optional) — 1-based column, when `symbol` is not given
+///
+/// Available in both modes: queries never modify files.
+pub struct LspTool {
+    workspace_root: PathBuf,
+    servers: Vec<LspServerSpec>,
+    timeout: Duration,
+    max_bytes: usize,
+    sessions: Mutex<HashMap<String, LspSession>>,
+}
+
+impl LspTool {
+    /// Create a new LspTool rooted at the current working directory.
+    pub fn new() -> Self {
+        Self::with_workspace_root(resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+
+    /// Create a new LspTool rooted at a specific workspace path.
+    pub fn with_workspace_root(workspace_root: PathBuf) -> Self {
+        Self {
+            workspace_root,
+            servers: default_servers(),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            max_bytes: DEFAULT_MAX_BYTES,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replace the language server list.
+    pub fn with_servers(mut self, servers: Vec<LspServerSpec>) -> Self {
+        self.servers = servers;
+        self
+    }
+
+    /// Set how long to wait for each language server reply.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn server_for(&self, path: &Path) -> Option<&LspServerSpec> {
+        let ext = path.extension()?.to_str()?;
+        self.servers
+            .iter()
+            .find(|s| s.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    }
+
+    fn root(&self) -> PathBuf {
+        self.workspace_root
+            .canonicalize()
+            .unwrap_or_else(|_| self.workspace_root.clone())
+    }
+
+    fn query(
+        &self,
+        action: LspAction,
+        spec: &LspServerSpec,
+        path: &Path,
+        text: &str,
+        position: Option<(u32, u32)>,
+    ) -> Result<String, String> {
+        let root = self.root();
+        let uri = file_uri(path)?;
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        if sessions
+            .get_mut(&spec.command)
+            .is_some_and(|s| !s.is_alive())
+        {
+            sessions.remove(&spec.command);
+        }
+        let session = match sessions.entry(spec.command.clone()) {
+            std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+            std::collections::hash_map::Entry::Vacant(e) => {
+                e.insert(LspSession::start(spec, &root, self.timeout)?)
+            }
+        };
+        session.open_document(&uri, &spec.language_id_for(ext), text)?;
+
+        let document = serde_json::json!({ "uri": uri });
+        let at = |(line, character): (u32, u32)| {
+            serde_json::json!({
+                "textDocument": document,
+                "position": { "line": line, "character": character },
+            })
+        };
+        let lines = match (action, position) {
+            (LspAction::Definition, Some(pos)) => {
+                let result = session.request("textDocument/definition", at(pos), self.timeout)?;
+                format_locations(&result, &root)
+            }
+            (LspAction::References, Some(pos)) => {
+                let mut params = at(pos);
+                params["context"] = serde_json::json!({ "includeDeclaration": true });
+                let result = session.request("textDocument/references", params, self.timeout)?;
+                format_locations(&result, &root)
+            }
+            (LspAction::Symbols, _) => {
+                let result = session.request(
+                    "textDocument/documentSymbol",
+                    serde_json::json!({ "textDocument": document }),
+                    self.timeout,
+                )?;
+                let mut out = Vec::new();
+                format_symbols(&result, 0, &mut out);
+                out
+            }
+            (LspAction::Diagnostics, _) => {
+                let diagnostics = session.wait_for_diagnostics(&uri, self.timeout)?;
+                format_diagnostics(&diagnostics)
+            }
+            (_, None) => return Err("this action needs a position".into()),
+        };
+
+        Ok(if lines.is_empty() {
+            match action {
+                LspAction::Definition => "no definition found".to_owned(),
+                LspAction::References => "no references found".to_owned(),
+                LspAction::Diagnostics => "no diagnostics".to_owned(),
+                LspAction::Symbols => "no symbols found".to_owned(),
+            }
+        } else {
+            lines.join("\n")
+        })
+    }
+}
+
+impl Default for LspTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for LspTool {
+    fn name(&self) -> &str {
+        "lsp"
+    }
+
+    fn description(&self) -> &str {
+        "Query a language server about code: find a symbol's definition or references, \
+         list diagnostics, or list the symbols in a file"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["definition", "references", "diagnostics", "symbols"],
+                    "description": "Query to run"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Source file to query"
+                },
+                "line": {
+                    "type": "integer",
+                    "description": "1-based line of the symbol (definition and references)"
+                },
+                "symbol": {
+                    "type": "string",
+                    "description": "Identifier on that line to query"
+                },
+                "column": {
+                    "type": "integer",
+                    "description": "1-based column, when symbol is not given"
+                }
+            },
+            "required": ["action", "path"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let action_str = args.get("action").and_then(|v| v.as_str()).ok_or_else(|| {
+            FaeLlmError::ToolValidationError("missing required argument: action".into())
+        })?;
+        let action = LspAction::parse(action_str).ok_or_else(|| {
+            FaeLlmError::ToolValidationError(format!("unknown action: {action_str}"))
+        })?;
+        let path_str = args.get("path").and_then(|v| v.as_str()).ok_or_else(|| {
+            FaeLlmError::ToolValidationError("missing required argument: path".into())
+        })?;
+        let path = validate_read_path_in_workspace(path_str, &self.workspace_root)?;
+
+        let Some(spec) = self.server_for(&path) else {
+            return Ok(ToolResult::failure(format!(
+                "no language server configured for {}",
+                path.display()
+            )));
+        };
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(t) => t,
+            Err(e) => {
+                return Ok(ToolResult::failure(format!(
+                    "failed to read {}: {e}",
+                    path.display()
+                )));
+            }
+        };
+
+        let position = if action.needs_position() {
+            let line = args
+                .get("line")
+                .and_then(|v| v.as_u64())
+                .filter(|l| *l >= 1)
+                .ok_or_else(|| {
+                    FaeLlmError::ToolValidationError(format!("{action_str} needs a 1-based `line`"))
+                })?;
+            let symbol = args.get("symbol").and_then(|v| v.as_str());
+            let column = args.get("column").and_then(|v| v.as_u64());
+            match lsp_position(&text, line as usize, symbol, column.map(|c| c as usize)) {
+                Ok(pos) => Some(pos),
+                Err(e) => return Err(FaeLlmError::ToolValidationError(e)),
+            }
+        } else {
+            None
+        };
+
+        match self.query(action, spec, &path, &text, position) {
+            Ok(output) => {
+                let (truncated, was_truncated) = truncate_output(&output, self.max_bytes);
+                if was_truncated {
+                    Ok(ToolResult::success_truncated(truncated))
+                } else {
+                    Ok(ToolResult::success(truncated))
+                }
+            }
+            Err(e) => Ok(ToolResult::failure(e)),
+        }
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true // queries are read-only
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Language server session
+// ---------------------------------------------------------------------------
+
+/// A running language server speaking JSON-RPC over stdio.
+struct LspSession {
+    child: Child,
+    stdin: ChildStdin,
+    incoming: mpsc::Receiver<Value>,
+    next_id: i64,
+    /// Open documents: URI → (version, text).
+    documents: HashMap<String, (i64, String)>,
+    /// Latest published diagnostics per URI.
+    diagnostics: HashMap<String, Vec<Value>>,
+}
+
+impl LspSession {
+    fn start(spec: &LspServerSpec, root: &Path, timeout: Duration) -> Result<Self, String> {
+        let mut child = Command::new(&spec.command)
+            .args(&spec.args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("failed to start language server `{}`: {e}", spec.command))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "language server stdin unavailable".to_owned())?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "language server stdout unavailable".to_owned())?;
+
+        let (tx, incoming) = mpsc::channel();
+        std::thread::Builder::new()
+            .name(format!("lsp-{}", spec.language_id))
+            .spawn(move || {
+                let mut reader = BufReader::new(stdout);
+                while let Ok(Some(message)) = read_message(&mut reader) {
+                    if tx.send(message).is_err() {
+                        break;
+                    }
+                }
+            })
+            .map_err(|e| format!("failed to start language server reader: {e}"))?;
+
+        let mut session = Self {
+            child,
+            stdin,
+            incoming,
+            next_id: 1,
+            documents: HashMap::new(),
+            diagnostics: HashMap::new(),
+        };
+        let root_uri = file_uri(root)?;
+        let root_name = root.file_name().map_or_else(
+            || "workspace".to_owned(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        session.request(
+            "initialize",
+            serde_json::json!({
+                "processId": std::process::id(),
+                "rootUri": root_uri,
+                "workspaceFolders": [{ "uri": root_uri, "name": root_name }],
+                "capabilities": {
+                    "textDocument": {
+                        "definition": { "linkSupport": true },
+                        "references": {},
+                        "documentSymbol": { "hierarchicalDocumentSymbolSupport": true },
+                        "publishDiagnostics": {}
+                    },
+                    "workspace": { "workspaceFolders": true, "configuration": true }
+                }
+            }),
+            timeout,
+        )?;
+        session.notify("initialized", serde_json::json!({}))?;
+        tracing::info!(server = %spec.command, root = %root.display(), "language server started");
+        Ok(session)
+    }
+
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    fn send(&mut self, message: &Value) -> Result<(), String> {
+        write_message(&mut self.stdin, message)
+            .map_err(|e| format!("language server write failed: {e}"))
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), String> {
+        self.send(&serde_json::json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+
+    fn request(&mut self, method: &str, params: Value, timeout: Duration) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let Some(message) = self.pump(deadline)? else {
+                return Err(format!("language server did not answer {method} in time"));
+            };
+            if message.get("method").is_none()
+                && message.get("id").and_then(Value::as_i64) == Some(id)
+            {
+                if let Some(error) = message.get("error") {
+                    let text = error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown error");
+                    return Err(format!("{method} failed: {text}"));
+                }
+                return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+            }
+        }
+    }
+
+    /// Receive the next message before `deadline`, recording diagnostics and
+    /// answering server-to-client requests along the way.
+    fn pump(&mut self, deadline: Instant) -> Result<Option<Value>, String> {
+        let wait = deadline.saturating_duration_since(Instant::now());
+        let message = match self.incoming.recv_timeout(wait) {
+            Ok(m) => m,
+            Err(mpsc::RecvTimeoutError::Timeout) => return Ok(None),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err("language server exited".into());
+            }
+        };
+        match (
+            message.get("method").and_then(Value::as_str),
+            message.get("id"),
+        ) {
+            (Some("textDocument/publishDiagnostics"), None) => {
+                if let Some(uri) = message["params"]["uri"].as_str() {
+                    let list = message["params"]["diagnostics"]
+                        .as_array()
+                        .cloned()
+                        .unwrap_or_default();
+                    self.diagnostics.insert(uri.to_owned(), list);
+                }
+            }
+            (Some(method), Some(id)) => {
+                // Servers ask for configuration and progress tokens; answer
+                // with defaults so they do not stall.
+                let result = if method == "workspace/configuration" {
+                    let items = message["params"]["items"].as_array().map_or(0, Vec::len);
+                    Value::Array(vec![Value::Null; items])
+                } else {
+                    Value::Null
+                };
+                self.send(&serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }))?;
+            }
+            _ => {}
+        }
+        Ok(Some(message))
+    }
+
+    /// Open `uri`, or send its new text if it changed since it was opened.
+    fn open_document(&mut self, uri: &str, language_id: &str, text: &str) -> Result<(), String> {
+        match self.documents.get(uri) {
+            Some((_, current)) if current == text => Ok(()),
+            Some((version, _)) => {
+                let version = version + 1;
+                self.documents
+                    .insert(uri.to_owned(), (version, text.to_owned()));
+                self.diagnostics.remove(uri);
+                self.notify(
+                    "textDocument/didChange",
+                    serde_json::json!({
+                        "textDocument": { "uri": uri, "version": version },
+                        "contentChanges": [{ "text": text }],
+                    }),
+                )
+            }
+            None => {
+                self.documents.insert(uri.to_owned(), (1, text.to_owned()));
+                self.notify(
+                    "textDocument/didOpen",
+                    serde_json::json!({
+                        "textDocument": {
+                            "uri": uri,
+                            "languageId": language_id,
+                            "version": 1,
+                            "text": text,
+                        }
+                    }),
+                )
+            }
+        }
+    }
+
+    /// Diagnostics for `uri`, waiting for the server to publish them.
+    fn wait_for_diagnostics(&mut self, uri: &str, timeout: Duration) -> Result<Vec<Value>, String> {
+        let deadline = Instant::now() + timeout;
+        while !self.diagnostics.contains_key(uri) {
+            if self.pump(deadline)?.is_none() {
+                return Err("language server did not publish diagnostics in time".into());
+            }
+        }
+        Ok(self.diagnostics.get(uri).cloned().unwrap_or_default())
+    }
+}
+
+impl Drop for LspSession {
+    fn drop(&mut self) {
+        let _ = self.notify("exit", Value::Null);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Protocol helpers
+// ---------------------------------------------------------------------------
+
+/// Write one `Content-Length`-framed JSON-RPC message.
+fn write_message(writer: &mut impl Write, message: &Value) -> std::io::Result<()> {
+    let body = serde_json::to_string(message).map_err(std::io::Error::other)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    writer.flush()
+}
+
+/// Read one `Content-Length`-framed JSON-RPC message. `None` at end of stream.
+fn read_message(reader: &mut impl BufRead) -> std::io::Result<Option<Value>> {
+    let mut length = None;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let line = header.trim_end();
+        if line.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let mut body = vec![0u8; length.unwrap_or(0)];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn file_uri(path: &Path) -> Result<String, String> {
+    url::Url::from_file_path(path)
+        .map(|u| u.to_string())
+        .map_err(|()| format!("cannot build a file URI for {}", path.display()))
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    url::Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+/// LSP position (0-based line, UTF-16 column) for a 1-based `line`.
+///
+/// The column comes from `symbol` (its first occurrence on the line), then
+/// `column` (1-based, in characters), then the first non-blank character.
+fn lsp_position(
+    text: &str,
+    line: usize,
+    symbol: Option<&str>,
+    column: Option<usize>,
+) -> Result<(u32, u32), String> {
+    let line_text = text
+        .lines()
+        .nth(line.saturating_sub(1))
+        .ok_or_else(|| format!("line {line} is past the end of the file"))?;
+    let char_col = match (symbol.filter(|s| !s.is_empty()), column) {
+        (Some(sym), _) => {
+            let byte = line_text
+                .find(sym)
+                .ok_or_else(|| format!("`{sym}` does not appear on line {line}"))?;
+            line_text[..byte].chars().count()
+        }
+        (None, Some(col)) => col.saturating_sub(1),
+        (None, None) => line_text.chars().take_while(|c| c.is_whitespace()).count(),
+    };
+    let utf16: usize = line_text.chars().take(char_col).map(char::len_utf16).sum();
+    Ok((
+        u32::try_from(line - 1).unwrap_or(u32::MAX),
+        u32::try_from(utf16).unwrap_or(u32::MAX),
+    ))
+}
+
+/// Path shown to the model: relative to the workspace when inside it.
+fn display_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn range_start(range: &Value) -> (u64, u64) {
+    (
+        range["start"]["line"].as_u64().unwrap_or(0),
+        range["start"]["character"].as_u64().unwrap_or(0),
+    )
+}
+
+/// `path:line:col: source line` for each location in a definition or
+/// references result (`Location`, `Location[]`, or `LocationLink[]`).
+fn format_locations(result: &Value, root: &Path) -> Vec<String> {
+    let items = match result {
+        Value::Array(items) => items.clone(),
+        Value::Null => Vec::new(),
+        single => vec![single.clone()],
+    };
+    let mut sources: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    let mut out = Vec::new();
+    for item in items.iter().take(MAX_ITEMS) {
+        let uri = item
+            .get("uri")
+            .or_else(|| item.get("targetUri"))
+            .and_then(Value::as_str);
+        let range = item
+            .get("range")
+            .or_else(|| item.get("targetSelectionRange"))
+            .or_else(|| item.get("targetRange"));
+        let (Some(uri), Some(range)) = (uri, range) else {
+            continue;
+        };
+        let (line, col) = range_start(range);
+        let Some(path) = uri_to_path(uri) else {
+            out.push(format!("{uri}:{}:{}", line + 1, col + 1));
+            continue;
+        };
+        let source = sources.entry(path.clone()).or_insert_with(|| {
+            std::fs::read_to_string(&path)
+                .map(|t| t.lines().map(str::to_owned).collect())
+                .unwrap_or_default()
+        });
+        let snippet = source
+            .get(usize::try_from(line).unwrap_or(usize::MAX))
+            .map(|l| l.trim())
+            .unwrap_or("");
+        out.push(format!(
+            "{}:{}:{}: {snippet}",
+            display_path(&path, root),
+            line + 1,
+            col + 1
+        ));
+    }
+    if items.len() > MAX_ITEMS {
+        out.push(format!("... {} more", items.len() - MAX_ITEMS));
+    }
+    out
+}
+
+fn symbol_kind_name(kind: u64) -> &'static str {
+    match kind {
+        1 => "file",
+        2 => "module",
+        3 => "namespace",
+        4 => "package",
+        5 => "class",
+        6 => "method",
+        7 => "property",
+        8 => "field",
+        9 => "constructor",
+        10 => "enum",
+        11 => "interface",
+        12 => "function",
+        13 => "variable",
+        14 => "constant",
+        15 => "string",
+        16 => "number",
+        17 => "boolean",
+        18 => "array",
+        19 => "object",
+        20 => "key",
+        21 => "null",
+        22 => "enum member",
+        23 => "struct",
+        24 => "event",
+        25 => "operator",
+        26 => "type parameter",
+        _ => "symbol",
+    }
+}
+
+/// Indented outline of a document symbol result (`DocumentSymbol[]` or
+/// `SymbolInformation[]`).
+fn format_symbols(result: &Value, depth: usize, out: &mut Vec<String>) {
+    let Some(items) = result.as_array() else {
+        return;
+    };
+    for item in items {
+        let name = item["name"].as_str().unwrap_or("?");
+        let kind = symbol_kind_name(item["kind"].as_u64().unwrap_or(0));
+        let range = item
+            .get("selectionRange")
+            .or_else(|| item.get("range"))
+            .or_else(|| item.get("location").map(|l| &l["range"]));
+        let line = range.map_or(0, |r| range_start(r).0) + 1;
+        out.push(format!("{}{kind} {name} (line {line})", "  ".repeat(depth)));
+        if let Some(children) = item.get("children") {
+            format_symbols(children, depth + 1, out);
+        }
+    }
+}
+
+/// `line:col severity: message [source]` for each diagnostic.
+fn format_diagnostics(diagnostics: &[Value]) -> Vec<String> {
+    let mut out: Vec<String> = diagnostics
+        .iter()
+        .take(MAX_ITEMS)
+        .map(|d| {
+            let (line, col) = range_start(&d["range"]);
+            let severity = match d["severity"].as_u64() {
+                Some(1) => "error",
+                Some(2) => "warning",
+                Some(3) => "info",
+                Some(4) => "hint",
+                _ => "note",
+            };
+            let message = d["message"].as_str().unwrap_or("").replace('\n', " ");
+            match d["source"].as_str() {
+                Some(source) => {
+                    format!("{}:{} {severity}: {message} [{source}]", line + 1, col + 1)
+                }
+                None => format!("{}:{} {severity}: {message}", line + 1, col + 1),
+            }
+        })
+        .collect();
+    if diagnostics.len() > MAX_ITEMS {
+        out.push(format!("... {} more", diagnostics.len() - MAX_ITEMS));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn messages_round_trip_through_framing() {
+        let message = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": "ok ✓" });
+        let mut buf = Vec::new();
+        write_message(&mut buf, &message).unwrap();
+        write_message(&mut buf, &message).unwrap();
+        assert!(buf.starts_with(b"Content-Length: "));
+
+        let mut reader = std::io::Cursor::new(buf);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message.clone()));
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn position_uses_symbol_column_or_first_non_blank() {
+        let text = "fn main() {\n    let café = helper(1);\n}\n";
+        assert_eq!(
+            lsp_position(text, 2, Some("helper"), None).unwrap(),
+            (1, 15)
+        );
+        assert_eq!(lsp_position(text, 2, None, Some(9)).unwrap(), (1, 8));
+        assert_eq!(lsp_position(text, 2, None, None).unwrap(), (1, 4));
+        assert!(lsp_position(text, 2, Some("missing"), None).is_err());
+        assert!(lsp_position(text, 9, None, None).is_err());
+    }
+
+    #[test]
+    fn locations_show_relative_path_and_source_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let file = root.join("lib.rs");
+        std::fs::write(&file, "// header\npub fn helper() {}\n").unwrap();
+        let uri = file_uri(&file).unwrap();
+        let range = serde_json::json!({
+            "start": { "line": 1, "character": 7 },
+            "end": { "line": 1, "character": 13 }
+        });
+
+        let location = serde_json::json!({ "uri": uri, "range": range });
+        assert_eq!(
+            format_locations(&location, &root),
+            vec!["lib.rs:2:8: pub fn helper() {}".to_owned()]
+        );
+        let link = serde_json::json!([{ "targetUri": uri, "targetSelectionRange": range }]);
+        assert_eq!(format_locations(&link, &root).len(), 1);
+        assert!(format_locations(&Value::Null, &root).is_empty());
+    }
+
+    #[test]
+    fn symbols_and_diagnostics_are_listed() {
+        let range = |line: u64| serde_json::json!({ "start": { "line": line, "character": 0 }, "end": { "line": line, "character": 1 } });
+        let symbols = serde_json::json!([{
+            "name": "Config", "kind": 23, "range": range(0), "selectionRange": range(0),
+            "children": [{ "name": "load", "kind": 6, "range": range(4), "selectionRange": range(4) }]
+        }]);
+        let mut out = Vec::new();
+        format_symbols(&symbols, 0, &mut out);
+        assert_eq!(
+            out,
+            vec!["struct Config (line 1)", "  method load (line 5)"]
+        );
+
+        let diagnostics = vec![serde_json::json!({
+            "range": range(2), "severity": 1, "message": "mismatched types", "source": "rustc"
+        })];
+        assert_eq!(
+            format_diagnostics(&diagnostics),
+            vec!["3:1 error: mismatched types [rustc]"]
+        );
+    }
+
+    #[test]
+    fn unsupported_file_or_missing_server_fails_cleanly() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "hello\n").unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        let tool = LspTool::with_workspace_root(dir.path().to_path_buf()).with_servers(vec![
+            LspServerSpec::new("rust", "fae-test-missing-language-server", &[], &["rs"]),
+        ]);
+
+        let result = tool
+            .execute(serde_json::json!({ "action": "symbols", "path": "notes.txt" }))
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("no language server"));
+
+        let result = tool
+            .execute(serde_json::json!({ "action": "symbols", "path": "main.rs" }))
+            .unwrap();
+        assert!(!result.success);
+        assert!(
+            result
+                .error
+                .unwrap()
+                .contains("failed to start language server")
+        );
+
+        assert!(
+            tool.execute(serde_json::json!({ "action": "definition", "path": "main.rs" }))
+                .is_err()
+        );
+        assert!(
+            tool.execute(serde_json::json!({ "action": "rename", "path": "main.rs" }))
+                .is_err()
+        );
+    }
+}
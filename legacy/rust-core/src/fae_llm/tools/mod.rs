@@ -6,27 +6,73 @@
 //! # Tools
 //!
 //! - **read** — Read file contents with pagination
+//! - **list_archive** / **archive** — Inspect zip/tar archives, or create and
+//!   extract them with traversal protection and size limits
+//! - **transcribe_file** — Transcribe a voice memo or other recording, with
+//!   optional timestamps
+//! - **synthesize_to_file** — Save text or SSML as a WAV or MP3 file in the
+//!   current voice
 //! - **bash** — Execute shell commands with timeout
-//! - **edit** — Deterministic text edits (find/replace)
+//! - **edit** — Atomic multi-file edits from a unified diff, previewed in
+//!   the approval prompt
 //! - **write** — Create or overwrite files
 //! - **web_search** — Search the web via embedded multi-engine scraper
 //! - **fetch_url** — Fetch and extract web page content
-//! - **desktop** — Desktop automation (screenshots, clicks, typing, windows)
+//! - **http_request** — Call REST endpoints with any method, headers and body;
+//!   only GET and HEAD skip approval
+//! - **list_todos** / **update_todo** — The todo list captured from conversations
+//! - **get_preference** / **set_preference** — Units, locale, time zone and
+//!   other standing user preferences
+//! - **file_organize** — Batch move, rename and copy within the user's
+//!   folders, previewed as a dry run in the approval prompt
+//! - **undo** — Revert recent write/edit/file_organize changes from the undo
+//!   history
+//! - **lsp** — Code navigation through a language server (definition,
+//!   references, diagnostics, symbols)
+//! - **desktop** — Desktop automation (screenshots, clicks, typing, windows;
+//!   `desktop` feature)
+//! - **network_diagnostics** — Ping, DNS lookup, captive portal check and
+//!   download speed estimate
+//! - **system_info** — CPU load, memory, disk, top processes, battery and
+//!   uptime (read-only)
+//! - **system_control** — Volume, brightness, dark mode, do-not-disturb and
+//!   Wi-Fi through platform backends
+//! - **create_skill** — Draft a skill from the conversation and install it
+//!   after the user approves the markdown
 //! - **apple** — Apple ecosystem tools (Contacts, Calendar) — macOS only
+//! - **pick_file** / **post_notification** / **share** — Native file picker,
+//!   notifications and share sheet through the host bridge
 //!
 //! # Mode Gating
 //!
 //! Tools respect [`ToolMode`](crate::fae_llm::config::types::ToolMode):
-//! - `ReadOnly` — Only read-only tools are available (read, web_search, fetch_url)
+//! - `ReadOnly` — Only read-only tools are available (read, lsp, web_search, fetch_url)
 //! - `Full` — All tools are available
+//!
+//! # Network Policy
+//!
+//! Tools that reach remote hosts (web_search, fetch_url, http_request, bash,
+//! python_skill)
+//! consult an optional [`NetworkGuard`] before connecting.
 
 pub mod apple;
+pub mod archive;
 pub mod bash;
+pub mod create_skill;
+#[cfg(feature = "desktop")]
 pub mod desktop;
 pub mod edit;
 pub mod fetch_url;
+pub mod file_organize;
+pub mod host_ui;
+pub mod http_request;
 pub mod input_sanitize;
+pub mod lsp;
+pub mod network_diag;
+pub mod network_policy;
+pub mod patch;
 pub mod path_validation;
+pub mod preferences;
 pub mod python_skill;
 pub mod read;
 pub mod registry;
@@ -36,18 +82,34 @@ pub mod scheduler_delete;
 pub mod scheduler_list;
 pub mod scheduler_trigger;
 pub mod scheduler_update;
+pub mod synthesize;
+pub mod system_control;
+pub mod system_info;
+pub mod todo;
 pub mod tool_timeouts;
+pub mod transcribe;
 pub mod types;
+pub mod undo;
 pub mod web_search;
 pub mod write;
 pub mod x0x;
 
+pub use archive::{ArchiveTool, ListArchiveTool};
 pub use bash::BashTool;
+pub use create_skill::CreateSkillTool;
+#[cfg(feature = "desktop")]
 pub use desktop::DesktopTool;
 pub use edit::EditTool;
 pub use fetch_url::FetchUrlTool;
+pub use file_organize::FileOrganizeTool;
+pub use host_ui::{NotifyTool, PickFileTool, ShareTool};
+pub use http_request::HttpRequestTool;
 pub use input_sanitize::{SanitizedInput, sanitize_command_input, sanitize_content_input};
+pub use lsp::{LspServerSpec, LspTool};
+pub use network_diag::NetworkDiagTool;
+pub use network_policy::{DomainApprover, NetworkDecision, NetworkGuard, NetworkPolicy};
 pub use path_validation::{validate_read_path, validate_write_path};
+pub use preferences::{GetPreferenceTool, SetPreferenceTool};
 pub use python_skill::PythonSkillTool;
 pub use read::ReadTool;
 pub use registry::ToolRegistry;
@@ -57,7 +119,15 @@ pub use scheduler_delete::SchedulerDeleteTool;
 pub use scheduler_list::SchedulerListTool;
 pub use scheduler_trigger::SchedulerTriggerTool;
 pub use scheduler_update::SchedulerUpdateTool;
-pub use types::{Tool, ToolResult, truncate_output};
+pub use synthesize::SynthesizeToFileTool;
+pub use system_control::SystemControlTool;
+pub use system_info::SystemInfoTool;
+pub use todo::{ListTodosTool, UpdateTodoTool};
+pub use transcribe::TranscribeFileTool;
+pub use types::{
+    ApprovalFuture, Clarification, ClarificationCandidate, Tool, ToolResult, truncate_output,
+};
+pub use undo::{UndoStore, UndoTool};
 pub use web_search::WebSearchTool;
 pub use write::WriteTool;
 pub use x0x::X0xTool;
@@ -249,9 +319,9 @@ mod integration_tests {
             None => unreachable!("edit tool should be available"),
         };
         let result = edit_tool.execute(serde_json::json!({
-            "path": path_str,
-            "old_string": "foo bar",
-            "new_string": "hello rust"
+            "patch": format!(
+                "--- {path_str}\n+++ {path_str}\n@@ -1,3 +1,3 @@\n hello world\n-foo bar\n+hello rust\n baz qux\n\\ No newline at end of file\n"
+            )
         }));
         let result = match result {
             Ok(r) => r,
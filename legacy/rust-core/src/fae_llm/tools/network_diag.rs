@@ -0,0 +1,529 @@
+//! Network diagnostics tool — ping, DNS lookup, captive portal check and a
+//! rough download speed estimate.
+//!
+//! Lets Fae answer "is my internet down?" from measurements. Every action is
+//! a fixed, structured probe: the model names a host at most, never a
+//! command line. Probes respect offline mode and the shared
+//! [`NetworkGuard`], and the tool is gated by
+//! [`PermissionKind::Network`].
+
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::permissions::PermissionKind;
+
+use super::apple::AppleEcosystemTool;
+use super::network_policy::NetworkGuard;
+use super::types::{Tool, ToolResult};
+
+/// Echo requests sent when `count` is not given.
+const DEFAULT_PING_COUNT: u64 = 4;
+/// Most echo requests one call may send.
+const MAX_PING_COUNT: u64 = 10;
+/// Deadline for one ping run.
+const PING_DEADLINE_SECS: u64 = 10;
+
+/// How long a DNS lookup may take.
+const DNS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Returns exactly `Success` unless a captive portal intercepts the request.
+const CAPTIVE_PROBE_HOST: &str = "captive.apple.com";
+const CAPTIVE_PROBE_URL: &str = "http://captive.apple.com/hotspot-detect.html";
+
+const SPEED_HOST: &str = "speed.cloudflare.com";
+/// Download used for the speed estimate (10 MB).
+const SPEED_BYTES: u64 = 10_000_000;
+/// The speed estimate stops reading after this long.
+const SPEED_MAX_DURATION: Duration = Duration::from_secs(8);
+
+// ── Results ─────────────────────────────────────────────────────
+
+/// Summary of one `ping` run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PingSummary {
+    pub transmitted: u32,
+    pub received: u32,
+    /// Average round trip, when any reply arrived.
+    pub average_ms: Option<f64>,
+}
+
+impl PingSummary {
+    /// Lost packets as a whole percentage.
+    pub fn loss_percent(&self) -> u32 {
+        if self.transmitted == 0 {
+            return 100;
+        }
+        let lost = self.transmitted.saturating_sub(self.received);
+        (f64::from(lost) / f64::from(self.transmitted) * 100.0).round() as u32
+    }
+}
+
+/// Outcome of the captive portal probe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptivePortal {
+    /// The probe came back untouched: the internet is reachable.
+    None,
+    /// Something answered in place of the probe server.
+    Detected { redirect: Option<String> },
+}
+
+// ── Parsing ─────────────────────────────────────────────────────
+
+/// Whether `host` is a plain host name or IP address, safe to pass to
+/// `ping` as a single argument.
+fn valid_host(host: &str) -> bool {
+    !host.is_empty()
+        && host.len() <= 253
+        && !host.starts_with('-')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':'))
+}
+
+/// Parse the summary lines `ping` prints on macOS and Linux:
+///
+/// ```text
+/// 4 packets transmitted, 4 packets received, 0.0% packet loss
+/// round-trip min/avg/max/stddev = 10.1/12.3/15.2/1.1 ms
+/// ```
+///
+/// Linux prints `4 received` and `rtt min/avg/max/mdev` instead.
+fn parse_ping(output: &str) -> Option<PingSummary> {
+    let counts = output.lines().find(|l| l.contains("packets transmitted"))?;
+    let mut parts = counts.split(',').map(str::trim);
+    let transmitted = parts.next()?.split_whitespace().next()?.parse().ok()?;
+    let received = parts
+        .find(|p| p.ends_with("received"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+    let average_ms = output
+        .lines()
+        .find(|l| l.contains("min/avg/max"))
+        .and_then(|l| l.split('=').nth(1)?.trim().split('/').nth(1)?.parse().ok());
+    Some(PingSummary {
+        transmitted,
+        received,
+        average_ms,
+    })
+}
+
+/// Classify the probe response: anything but `Success` from the probe
+/// server means a portal (or proxy) answered for it.
+fn classify_portal(status: u16, body: &str, location: Option<&str>) -> CaptivePortal {
+    if status == 200 && body.contains("Success") {
+        CaptivePortal::None
+    } else {
+        CaptivePortal::Detected {
+            redirect: location.map(str::to_owned),
+        }
+    }
+}
+
+/// Megabits per second for `bytes` received in `elapsed`.
+fn megabits_per_second(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0.0;
+    }
+    bytes as f64 * 8.0 / secs / 1_000_000.0
+}
+
+fn http_agent(timeout: Duration) -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(5))
+        .timeout_read(timeout)
+        .redirects(0)
+        .build()
+}
+
+// ── NetworkDiagTool ─────────────────────────────────────────────
+
+/// Runs structured network probes: `ping`, `dns`, `captive_portal` and
+/// `speed`.
+///
+/// Probes change nothing, so the tool is allowed in all tool modes; it still
+/// needs the Network permission.
+pub struct NetworkDiagTool {
+    network: Option<Arc<NetworkGuard>>,
+}
+
+impl NetworkDiagTool {
+    /// Create a new `NetworkDiagTool`.
+    pub fn new() -> Self {
+        Self { network: None }
+    }
+
+    /// Only probe hosts `guard` allows.
+    pub fn with_network_guard(mut self, guard: Arc<NetworkGuard>) -> Self {
+        self.network = Some(guard);
+        self
+    }
+
+    fn check_host(&self, host: &str) -> Result<(), FaeLlmError> {
+        crate::offline::ensure_online("network diagnostics")
+            .map_err(|e| FaeLlmError::ToolExecutionError(e.to_string()))?;
+        match &self.network {
+            Some(guard) => guard.check_host(self.name(), host),
+            None => Ok(()),
+        }
+    }
+
+    fn ping(&self, host: &str, count: u64) -> Result<ToolResult, FaeLlmError> {
+        self.check_host(host)?;
+        let count = count.to_string();
+        let deadline = PING_DEADLINE_SECS.to_string();
+        // macOS spells the overall deadline `-t`, Linux `-w`.
+        let deadline_flag = if cfg!(target_os = "macos") {
+            "-t"
+        } else {
+            "-w"
+        };
+        let output = std::process::Command::new("ping")
+            .args(["-c", &count, deadline_flag, &deadline, host])
+            .output()
+            .map_err(|e| FaeLlmError::ToolExecutionError(format!("failed to run ping: {e}")))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some(summary) = parse_ping(&stdout) else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(ToolResult::failure(format!(
+                "ping {host} failed: {}",
+                stderr.trim()
+            )));
+        };
+        let text = match summary.average_ms {
+            Some(avg) if summary.received > 0 => format!(
+                "{host}: {}/{} replies, {}% loss, average {avg:.1} ms",
+                summary.received,
+                summary.transmitted,
+                summary.loss_percent()
+            ),
+            _ => format!(
+                "{host}: no replies to {} pings (100% loss)",
+                summary.transmitted
+            ),
+        };
+        Ok(
+            ToolResult::success(text).with_structured(serde_json::json!({
+                "host": host,
+                "transmitted": summary.transmitted,
+                "received": summary.received,
+                "loss_percent": summary.loss_percent(),
+                "average_ms": summary.average_ms,
+            })),
+        )
+    }
+
+    fn dns(&self, host: &str) -> Result<ToolResult, FaeLlmError> {
+        self.check_host(host)?;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let lookup = host.to_owned();
+        let started = Instant::now();
+        std::thread::spawn(move || {
+            let _ = tx.send((lookup.as_str(), 0).to_socket_addrs().map(|addrs| {
+                let mut ips: Vec<String> = addrs.map(|a| a.ip().to_string()).collect();
+                ips.dedup();
+                ips
+            }));
+        });
+        let ips = match rx.recv_timeout(DNS_TIMEOUT) {
+            Ok(Ok(ips)) if !ips.is_empty() => ips,
+            Ok(Ok(_)) => return Ok(ToolResult::failure(format!("{host} has no addresses"))),
+            Ok(Err(e)) => return Ok(ToolResult::failure(format!("cannot resolve {host}: {e}"))),
+            Err(_) => {
+                return Ok(ToolResult::failure(format!(
+                    "DNS lookup for {host} timed out after {}s",
+                    DNS_TIMEOUT.as_secs()
+                )));
+            }
+        };
+        let elapsed_ms = started.elapsed().as_millis();
+        Ok(ToolResult::success(format!(
+            "{host} resolves to {} ({elapsed_ms} ms)",
+            ips.join(", ")
+        ))
+        .with_structured(serde_json::json!({
+            "host": host,
+            "addresses": ips,
+            "elapsed_ms": elapsed_ms,
+        })))
+    }
+
+    fn captive_portal(&self) -> Result<ToolResult, FaeLlmError> {
+        self.check_host(CAPTIVE_PROBE_HOST)?;
+        let response = match http_agent(Duration::from_secs(5))
+            .get(CAPTIVE_PROBE_URL)
+            .call()
+        {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => {
+                return Ok(ToolResult::failure(format!(
+                    "cannot reach {CAPTIVE_PROBE_HOST}: {e}"
+                )));
+            }
+        };
+        let status = response.status();
+        let location = response.header("Location").map(str::to_owned);
+        let body = response.into_string().unwrap_or_default();
+        let (text, detected) = match classify_portal(status, &body, location.as_deref()) {
+            CaptivePortal::None => (
+                "No captive portal: the internet is reachable.".to_owned(),
+                false,
+            ),
+            CaptivePortal::Detected { redirect } => (
+                match redirect {
+                    Some(to) => format!(
+                        "A captive portal is intercepting traffic (redirects to {to}). \
+                         Sign in through a browser."
+                    ),
+                    None => "A captive portal or proxy is intercepting traffic. \
+                             Sign in through a browser."
+                        .to_owned(),
+                },
+                true,
+            ),
+        };
+        Ok(
+            ToolResult::success(text).with_structured(serde_json::json!({
+                "captive_portal": detected,
+                "status": status,
+                "redirect": location,
+            })),
+        )
+    }
+
+    fn speed(&self) -> Result<ToolResult, FaeLlmError> {
+        self.check_host(SPEED_HOST)?;
+        let url = format!("https://{SPEED_HOST}/__down?bytes={SPEED_BYTES}");
+        let started = Instant::now();
+        let response = match http_agent(SPEED_MAX_DURATION).get(&url).call() {
+            Ok(response) => response,
+            Err(e) => return Ok(ToolResult::failure(format!("speed test failed: {e}"))),
+        };
+        let mut reader = response.into_reader();
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut received: u64 = 0;
+        while received < SPEED_BYTES && started.elapsed() < SPEED_MAX_DURATION {
+            match std::io::Read::read(&mut reader, &mut buf) {
+                Ok(0) => break,
+                Ok(n) => received += n as u64,
+                Err(e) if received == 0 => {
+                    return Ok(ToolResult::failure(format!("speed test failed: {e}")));
+                }
+                Err(_) => break,
+            }
+        }
+        let elapsed = started.elapsed();
+        let mbps = megabits_per_second(received, elapsed);
+        Ok(ToolResult::success(format!(
+            "Download speed: about {mbps:.1} Mbit/s ({:.1} MB in {:.1} s).",
+            received as f64 / 1_000_000.0,
+            elapsed.as_secs_f64()
+        ))
+        .with_structured(serde_json::json!({
+            "megabits_per_second": mbps,
+            "bytes": received,
+            "elapsed_ms": elapsed.as_millis(),
+        })))
+    }
+}
+
+impl Default for NetworkDiagTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The validated `host` argument.
+fn host_arg(args: &serde_json::Value, action: &str) -> Result<String, FaeLlmError> {
+    let host = args
+        .get("host")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .ok_or_else(|| {
+            FaeLlmError::ToolValidationError(format!("{action} requires 'host' argument"))
+        })?;
+    if !valid_host(host) {
+        return Err(FaeLlmError::ToolValidationError(format!(
+            "'{host}' is not a host name or IP address"
+        )));
+    }
+    Ok(host.to_owned())
+}
+
+impl Tool for NetworkDiagTool {
+    fn name(&self) -> &str {
+        "network_diagnostics"
+    }
+
+    fn description(&self) -> &str {
+        "Troubleshoot the internet connection: ping a host, resolve a name in DNS, \
+         check for a captive portal, or estimate download speed"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "description": "Probe to run",
+                    "enum": ["ping", "dns", "captive_portal", "speed"]
+                },
+                "host": {
+                    "type": "string",
+                    "description": "Host name or IP address (for ping and dns)"
+                },
+                "count": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_PING_COUNT,
+                    "description": "Echo requests to send (for ping, default 4)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let action = args.get("action").and_then(|v| v.as_str()).ok_or_else(|| {
+            FaeLlmError::ToolValidationError("missing required argument: action (string)".into())
+        })?;
+        match action {
+            "ping" => {
+                let host = host_arg(&args, action)?;
+                let count = match args.get("count") {
+                    None => DEFAULT_PING_COUNT,
+                    Some(v) => v
+                        .as_u64()
+                        .filter(|n| (1..=MAX_PING_COUNT).contains(n))
+                        .ok_or_else(|| {
+                            FaeLlmError::ToolValidationError(format!(
+                                "count must be an integer 1-{MAX_PING_COUNT}"
+                            ))
+                        })?,
+                };
+                self.ping(&host, count)
+            }
+            "dns" => {
+                let host = host_arg(&args, action)?;
+                self.dns(&host)
+            }
+            "captive_portal" => self.captive_portal(),
+            "speed" => self.speed(),
+            other => Err(FaeLlmError::ToolValidationError(format!(
+                "unknown action: '{other}'. Valid actions: ping, dns, captive_portal, speed"
+            ))),
+        }
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true // probes only read the network's state
+    }
+}
+
+impl AppleEcosystemTool for NetworkDiagTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::Network
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::tools::network_policy::NetworkPolicy;
+
+    #[test]
+    fn ping_summaries_parse_on_macos_and_linux() {
+        let macos = "PING example.com (93.184.216.34): 56 data bytes\n\
+                     --- example.com ping statistics ---\n\
+                     4 packets transmitted, 3 packets received, 25.0% packet loss\n\
+                     round-trip min/avg/max/stddev = 10.100/12.300/15.200/1.100 ms\n";
+        let summary = parse_ping(macos).unwrap();
+        assert_eq!((summary.transmitted, summary.received), (4, 3));
+        assert_eq!(summary.average_ms, Some(12.3));
+        assert_eq!(summary.loss_percent(), 25);
+
+        let linux = "--- example.com ping statistics ---\n\
+                     4 packets transmitted, 4 received, 0% packet loss, time 3004ms\n\
+                     rtt min/avg/max/mdev = 9.8/11.0/13.1/1.2 ms\n";
+        let summary = parse_ping(linux).unwrap();
+        assert_eq!((summary.received, summary.average_ms), (4, Some(11.0)));
+
+        let lost = "4 packets transmitted, 0 received, 100% packet loss, time 3060ms\n";
+        let summary = parse_ping(lost).unwrap();
+        assert_eq!((summary.received, summary.average_ms), (0, None));
+        assert_eq!(summary.loss_percent(), 100);
+        assert_eq!(parse_ping("ping: unknown host"), None);
+    }
+
+    #[test]
+    fn only_plain_hosts_are_accepted() {
+        assert!(valid_host("example.com"));
+        assert!(valid_host("192.168.1.1"));
+        assert!(valid_host("2606:4700::1111"));
+        assert!(!valid_host("-f"));
+        assert!(!valid_host("example.com; rm -rf /"));
+        assert!(!valid_host(""));
+
+        let tool = NetworkDiagTool::new();
+        for args in [
+            serde_json::json!({ "action": "ping" }),
+            serde_json::json!({ "action": "ping", "host": "--flood" }),
+            serde_json::json!({ "action": "ping", "host": "a.test", "count": 50 }),
+            serde_json::json!({ "action": "traceroute", "host": "a.test" }),
+        ] {
+            assert!(matches!(
+                tool.execute(args),
+                Err(FaeLlmError::ToolValidationError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn portal_is_detected_unless_the_probe_succeeds() {
+        let ok = "<HTML><HEAD><TITLE>Success</TITLE></HEAD><BODY>Success</BODY></HTML>";
+        assert_eq!(classify_portal(200, ok, None), CaptivePortal::None);
+        assert_eq!(
+            classify_portal(302, "", Some("https://login.hotel.test/")),
+            CaptivePortal::Detected {
+                redirect: Some("https://login.hotel.test/".to_owned())
+            }
+        );
+        assert_eq!(
+            classify_portal(200, "<html>Welcome</html>", None),
+            CaptivePortal::Detected { redirect: None }
+        );
+    }
+
+    #[test]
+    fn speed_is_reported_in_megabits() {
+        let mbps = megabits_per_second(10_000_000, Duration::from_secs(2));
+        assert!((mbps - 40.0).abs() < f64::EPSILON);
+        assert_eq!(megabits_per_second(1, Duration::ZERO), 0.0);
+    }
+
+    #[test]
+    fn denied_hosts_are_not_probed() {
+        let guard = NetworkGuard::new(NetworkPolicy {
+            deny_domains: vec!["blocked.test".to_owned()],
+            ..NetworkPolicy::default()
+        });
+        let tool = NetworkDiagTool::new().with_network_guard(Arc::new(guard));
+        let result = tool.execute(serde_json::json!({ "action": "dns", "host": "blocked.test" }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn needs_the_network_permission_in_every_mode() {
+        let tool = NetworkDiagTool::new();
+        assert_eq!(tool.required_permission(), PermissionKind::Network);
+        assert!(tool.allowed_in_mode(ToolMode::ReadOnly));
+    }
+}
@@ -0,0 +1,380 @@
+//! Outbound network policy for tools that contact remote hosts.
+//!
+//! A [`NetworkPolicy`] holds per-domain allow and deny lists and an optional
+//! "ask before contacting new domains" mode. A [`NetworkGuard`] applies the
+//! policy for a set of tools, asks a [`DomainApprover`] about domains the
+//! policy leaves open, and remembers the answers for its lifetime.
+//!
+//! Domain patterns match the domain itself and all of its subdomains
+//! (`example.com` matches `docs.example.com`); `*` matches every domain.
+//! Deny entries always win over allow entries. While offline mode is on,
+//! every host other than this machine is denied regardless of the policy.
+//!
+//! The web tools make their requests in-process and check every host they
+//! contact, redirects included. For `bash` and `python_skill` the policy is
+//! advisory only: URLs written out in the command or parameters are checked
+//! and the lists are exported to the child as `FAE_NETWORK_ALLOW` /
+//! `FAE_NETWORK_DENY`, but nothing makes the child honour them, and a script
+//! that builds its URL at runtime reaches any host. This is not a sandbox.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fae_llm::error::FaeLlmError;
+
+/// Environment variable listing allowed domains for child processes.
+pub const NETWORK_ALLOW_ENV: &str = "FAE_NETWORK_ALLOW";
+/// Environment variable listing denied domains for child processes.
+pub const NETWORK_DENY_ENV: &str = "FAE_NETWORK_DENY";
+
+/// Per-domain allow/deny rules for outbound requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkPolicy {
+    /// Domains that may be contacted. Empty means any domain not denied.
+    pub allow_domains: Vec<String>,
+    /// Domains that may never be contacted.
+    pub deny_domains: Vec<String>,
+    /// Ask before contacting a domain not covered by `allow_domains`.
+    pub ask_new_domains: bool,
+}
+
+/// Outcome of checking a domain against a [`NetworkPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkDecision {
+    /// The domain may be contacted.
+    Allow,
+    /// The domain must not be contacted.
+    Deny,
+    /// The user has to decide.
+    Ask,
+}
+
+impl NetworkPolicy {
+    /// Whether the policy places no restriction on any domain.
+    pub fn is_unrestricted(&self) -> bool {
+        self.allow_domains.is_empty() && self.deny_domains.is_empty() && !self.ask_new_domains
+    }
+
+    /// Decide whether `host` may be contacted.
+    pub fn decide(&self, host: &str) -> NetworkDecision {
+        let host = normalize_host(host);
+        if self.deny_domains.iter().any(|p| domain_matches(&host, p)) {
+            return NetworkDecision::Deny;
+        }
+        if self.allow_domains.iter().any(|p| domain_matches(&host, p)) {
+            return NetworkDecision::Allow;
+        }
+        match (self.ask_new_domains, self.allow_domains.is_empty()) {
+            (true, _) => NetworkDecision::Ask,
+            (false, true) => NetworkDecision::Allow,
+            (false, false) => NetworkDecision::Deny,
+        }
+    }
+
+    /// Environment variables describing the policy to child processes.
+    ///
+    /// Informational: a child process is free to ignore them. Empty when
+    /// the policy is unrestricted.
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars = Vec::new();
+        if !self.allow_domains.is_empty() {
+            vars.push((NETWORK_ALLOW_ENV, self.allow_domains.join(",")));
+        }
+        if !self.deny_domains.is_empty() {
+            vars.push((NETWORK_DENY_ENV, self.deny_domains.join(",")));
+        }
+        vars
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Whether `host` is `pattern` or one of its subdomains.
+///
+/// Leading `*.` or `.` in the pattern is ignored; `*` matches everything.
+pub fn domain_matches(host: &str, pattern: &str) -> bool {
+    let pattern = normalize_host(pattern);
+    if pattern == "*" {
+        return true;
+    }
+    let pattern = pattern.trim_start_matches("*.").trim_start_matches('.');
+    if pattern.is_empty() {
+        return false;
+    }
+    let host = normalize_host(host);
+    host == pattern
+        || host
+            .strip_suffix(pattern)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Host component of an absolute URL.
+pub fn host_of(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url).ok()?;
+    parsed.host_str().map(normalize_host)
+}
+
+/// Hosts of every `http://` or `https://` URL appearing in `text`.
+///
+/// Best effort: URLs without a scheme are not detected.
+pub fn hosts_in_text(text: &str) -> Vec<String> {
+    let mut hosts: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("http") {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '<' | '>' | ')'))
+            .unwrap_or(candidate.len());
+        let token = &candidate[..end];
+        if (token.starts_with("http://") || token.starts_with("https://"))
+            && let Some(host) = host_of(token)
+            && !hosts.contains(&host)
+        {
+            hosts.push(host);
+        }
+        rest = &candidate[end.max(4)..];
+    }
+    hosts
+}
+
+/// Decides whether a tool may contact a domain the policy leaves open.
+pub trait DomainApprover: Send + Sync {
+    /// Ask whether `tool_name` may contact `host`. Blocks until answered.
+    fn approve(&self, tool_name: &str, host: &str) -> bool;
+}
+
+/// Applies a [`NetworkPolicy`] and remembers approval answers.
+///
+/// Share one guard between tools with `Arc`, or derive per-tool guards with
+/// [`with_policy`](Self::with_policy), so a domain approved for one tool is
+/// not asked about again for another.
+pub struct NetworkGuard {
+    policy: NetworkPolicy,
+    approver: Option<Arc<dyn DomainApprover>>,
+    answers: Arc<Mutex<HashMap<String, bool>>>,
+}
+
+impl std::fmt::Debug for NetworkGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetworkGuard")
+            .field("policy", &self.policy)
+            .field("has_approver", &self.approver.is_some())
+            .finish()
+    }
+}
+
+impl NetworkGuard {
+    /// Create a guard for `policy` with no approver.
+    ///
+    /// Domains that need approval are denied until an approver is set.
+    pub fn new(policy: NetworkPolicy) -> Self {
+        Self {
+            policy,
+            approver: None,
+            answers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// A guard applying `policy` that shares this guard's approver and
+    /// remembered answers.
+    pub fn with_policy(&self, policy: NetworkPolicy) -> Self {
+        Self {
+            policy,
+            approver: self.approver.clone(),
+            answers: Arc::clone(&self.answers),
+        }
+    }
+
+    /// Ask `approver` about domains the policy leaves open.
+    pub fn with_approver(mut self, approver: Arc<dyn DomainApprover>) -> Self {
+        self.approver = Some(approver);
+        self
+    }
+
+    /// The policy this guard applies.
+    pub fn policy(&self) -> &NetworkPolicy {
+        &self.policy
+    }
+
+    /// Check that `tool_name` may contact `host`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FaeLlmError::ToolExecutionError`] if offline mode is on and
+    /// the host is not loopback, the policy denies the domain, or approval
+    /// is refused or unavailable.
+    pub fn check_host(&self, tool_name: &str, host: &str) -> Result<(), FaeLlmError> {
+        let host = normalize_host(host);
+        if crate::offline::is_offline() && !crate::offline::is_loopback_host(&host) {
+            return Err(FaeLlmError::ToolExecutionError(format!(
+                "offline mode is on; network access to {host} is disabled"
+            )));
+        }
+        let allowed = match self.policy.decide(&host) {
+            NetworkDecision::Allow => true,
+            NetworkDecision::Deny => {
+                return Err(FaeLlmError::ToolExecutionError(format!(
+                    "network access to {host} is blocked by policy"
+                )));
+            }
+            NetworkDecision::Ask => self.ask(tool_name, &host)?,
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(FaeLlmError::ToolExecutionError(format!(
+                "network access to {host} was not approved"
+            )))
+        }
+    }
+
+    /// Check the host of `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL has no host or the host is not allowed.
+    pub fn check_url(&self, tool_name: &str, url: &str) -> Result<(), FaeLlmError> {
+        let host = host_of(url).ok_or_else(|| {
+            FaeLlmError::ToolValidationError(format!("cannot determine host of {url}"))
+        })?;
+        self.check_host(tool_name, &host)
+    }
+
+    /// Check every URL host found in `text` (a command line or arguments).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first denial.
+    pub fn check_text(&self, tool_name: &str, text: &str) -> Result<(), FaeLlmError> {
+        if self.policy.is_unrestricted() && !crate::offline::is_offline() {
+            return Ok(());
+        }
+        for host in hosts_in_text(text) {
+            self.check_host(tool_name, &host)?;
+        }
+        Ok(())
+    }
+
+    fn ask(&self, tool_name: &str, host: &str) -> Result<bool, FaeLlmError> {
+        if let Some(&answer) = self
+            .answers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(host)
+        {
+            return Ok(answer);
+        }
+        let Some(approver) = &self.approver else {
+            return Err(FaeLlmError::ToolExecutionError(format!(
+                "network access to {host} needs approval but no approval channel is available"
+            )));
+        };
+        let answer = approver.approve(tool_name, host);
+        tracing::info!(
+            tool_name,
+            host,
+            approved = answer,
+            "network domain decision"
+        );
+        self.answers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(host.to_owned(), answer);
+        Ok(answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingApprover {
+        answer: bool,
+        asked: AtomicUsize,
+    }
+
+    impl DomainApprover for CountingApprover {
+        fn approve(&self, _tool_name: &str, _host: &str) -> bool {
+            self.asked.fetch_add(1, Ordering::SeqCst);
+            self.answer
+        }
+    }
+
+    fn policy(allow: &[&str], deny: &[&str], ask: bool) -> NetworkPolicy {
+        NetworkPolicy {
+            allow_domains: allow.iter().map(|d| (*d).to_owned()).collect(),
+            deny_domains: deny.iter().map(|d| (*d).to_owned()).collect(),
+            ask_new_domains: ask,
+        }
+    }
+
+    #[test]
+    fn domains_match_themselves_and_subdomains() {
+        assert!(domain_matches("example.com", "example.com"));
+        assert!(domain_matches("Docs.Example.com.", "*.example.com"));
+        assert!(!domain_matches("badexample.com", "example.com"));
+        assert!(domain_matches("anything.org", "*"));
+    }
+
+    #[test]
+    fn deny_wins_and_allow_list_restricts() {
+        let p = policy(&["example.com"], &["secret.example.com"], false);
+        assert_eq!(p.decide("www.example.com"), NetworkDecision::Allow);
+        assert_eq!(p.decide("secret.example.com"), NetworkDecision::Deny);
+        assert_eq!(p.decide("other.org"), NetworkDecision::Deny);
+        assert_eq!(
+            policy(&[], &["evil.test"], false).decide("other.org"),
+            NetworkDecision::Allow
+        );
+        assert_eq!(
+            policy(&["example.com"], &[], true).decide("other.org"),
+            NetworkDecision::Ask
+        );
+    }
+
+    #[test]
+    fn guard_asks_once_per_domain() {
+        let approver = Arc::new(CountingApprover {
+            answer: true,
+            asked: AtomicUsize::new(0),
+        });
+        let guard = NetworkGuard::new(policy(&[], &[], true)).with_approver(approver.clone());
+        guard.check_url("fetch_url", "https://news.test/a").unwrap();
+        guard.check_url("fetch_url", "https://news.test/b").unwrap();
+        assert_eq!(approver.asked.load(Ordering::SeqCst), 1);
+
+        // A per-tool guard reuses the answer but applies its own policy.
+        let bash = guard.with_policy(policy(&[], &["blocked.test"], true));
+        bash.check_host("bash", "news.test").unwrap();
+        assert!(bash.check_host("bash", "blocked.test").is_err());
+        assert_eq!(approver.asked.load(Ordering::SeqCst), 1);
+
+        let no_approver = NetworkGuard::new(policy(&[], &[], true));
+        assert!(no_approver.check_host("fetch_url", "news.test").is_err());
+    }
+
+    #[test]
+    fn text_checks_every_url_host() {
+        assert_eq!(
+            hosts_in_text(
+                "curl -s 'https://a.test/x' && wget http://b.test:8080/y https://a.test/z"
+            ),
+            vec!["a.test".to_owned(), "b.test".to_owned()]
+        );
+        let guard = NetworkGuard::new(policy(&["a.test"], &[], false));
+        assert!(guard.check_text("bash", "curl https://a.test/").is_ok());
+        assert!(
+            guard
+                .check_text("bash", "curl https://a.test/ https://b.test/")
+                .is_err()
+        );
+    }
+}
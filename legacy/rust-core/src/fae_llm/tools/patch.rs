@@ -0,0 +1,434 @@
+//! Unified diff parsing and application for the edit tool.
+//!
+//! Parsing is lenient about what models tend to get wrong: hunk line counts
+//! in `@@` headers are ignored (hunks end at the next header), `a/` and `b/`
+//! prefixes are stripped, and git extended headers (`diff --git`, `index`,
+//! `new file mode`) are skipped. Application is strict about content: every
+//! context and removed line must match the file, although a hunk may be found
+//! at a different line than its header says.
+
+/// One line of a hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HunkLine {
+    /// Unchanged line (` ` prefix).
+    Context(String),
+    /// Line removed from the old file (`-` prefix).
+    Remove(String),
+    /// Line added in the new file (`+` prefix).
+    Add(String),
+}
+
+/// One `@@` section of a file patch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// 1-based start line in the old file, as written in the header.
+    pub old_start: usize,
+    /// 1-based start line in the new file, as written in the header.
+    pub new_start: usize,
+    /// Body lines in order.
+    pub lines: Vec<HunkLine>,
+    /// Whether a `\ No newline at end of file` marker followed an added or
+    /// context line, i.e. the new file ends without a newline.
+    pub no_newline_at_end: bool,
+}
+
+impl Hunk {
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|l| match l {
+                HunkLine::Context(s) | HunkLine::Remove(s) => Some(s.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// What a file patch does to its file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChangeKind {
+    /// The file does not exist yet (`--- /dev/null`).
+    Create,
+    /// The file exists and is changed in place.
+    Modify,
+    /// The file is removed (`+++ /dev/null`).
+    Delete,
+}
+
+/// All hunks for one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePatch {
+    /// Path from the `---` header, `None` for `/dev/null`.
+    pub old_path: Option<String>,
+    /// Path from the `+++` header, `None` for `/dev/null`.
+    pub new_path: Option<String>,
+    /// Hunks in file order.
+    pub hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// What the patch does to the file.
+    pub fn kind(&self) -> FileChangeKind {
+        match (&self.old_path, &self.new_path) {
+            (None, _) => FileChangeKind::Create,
+            (_, None) => FileChangeKind::Delete,
+            _ => FileChangeKind::Modify,
+        }
+    }
+
+    /// The path the patch applies to.
+    pub fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Number of added and removed lines.
+    pub fn line_counts(&self) -> (usize, usize) {
+        let mut added = 0;
+        let mut removed = 0;
+        for line in self.hunks.iter().flat_map(|h| &h.lines) {
+            match line {
+                HunkLine::Add(_) => added += 1,
+                HunkLine::Remove(_) => removed += 1,
+                HunkLine::Context(_) => {}
+            }
+        }
+        (added, removed)
+    }
+}
+
+/// Parse a unified diff covering one or more files.
+///
+/// # Errors
+///
+/// Returns a message describing the first malformed header or hunk, or an
+/// error if the patch contains no file sections, renames a file, or touches
+/// the same file twice.
+pub fn parse_patch(text: &str) -> Result<Vec<FilePatch>, String> {
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    let mut patches: Vec<FilePatch> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        if !(line.starts_with("--- ") && lines.get(i + 1).is_some_and(|n| n.starts_with("+++ "))) {
+            if line.starts_with("@@") {
+                return Err(format!(
+                    "hunk at line {} has no `---`/`+++` file header",
+                    i + 1
+                ));
+            }
+            // Prose, `diff --git`, `index`, mode lines: skip.
+            i += 1;
+            continue;
+        }
+
+        let old_path = parse_header_path(&line[4..]);
+        let new_path = parse_header_path(&lines[i + 1][4..]);
+        if old_path.is_none() && new_path.is_none() {
+            return Err(format!("file header at line {} names no file", i + 1));
+        }
+        if let (Some(old), Some(new)) = (&old_path, &new_path)
+            && old != new
+        {
+            return Err(format!(
+                "renaming {old} to {new} is not supported; delete and create instead"
+            ));
+        }
+        i += 2;
+
+        let mut hunks = Vec::new();
+        while let Some(header) = lines.get(i).filter(|l| l.starts_with("@@")) {
+            let (old_start, new_start) = parse_hunk_header(header)
+                .ok_or_else(|| format!("malformed hunk header at line {}: {header}", i + 1))?;
+            i += 1;
+            let mut hunk = Hunk {
+                old_start,
+                new_start,
+                lines: Vec::new(),
+                no_newline_at_end: false,
+            };
+            while let Some(&body) = lines.get(i) {
+                if body.starts_with("@@")
+                    || body.starts_with("diff ")
+                    || (body.starts_with("--- ")
+                        && lines.get(i + 1).is_some_and(|n| n.starts_with("+++ ")))
+                {
+                    break;
+                }
+                if body.starts_with('\\') {
+                    if !matches!(hunk.lines.last(), Some(HunkLine::Remove(_))) {
+                        hunk.no_newline_at_end = true;
+                    }
+                } else if let Some(rest) = body.strip_prefix('+') {
+                    hunk.lines.push(HunkLine::Add(rest.to_owned()));
+                } else if let Some(rest) = body.strip_prefix('-') {
+                    hunk.lines.push(HunkLine::Remove(rest.to_owned()));
+                } else if let Some(rest) = body.strip_prefix(' ') {
+                    hunk.lines.push(HunkLine::Context(rest.to_owned()));
+                } else if body.is_empty() {
+                    // Editors and models often strip the space from blank
+                    // context lines.
+                    hunk.lines.push(HunkLine::Context(String::new()));
+                } else {
+                    return Err(format!(
+                        "unexpected line {} in hunk (expected ' ', '+' or '-' prefix): {body}",
+                        i + 1
+                    ));
+                }
+                i += 1;
+            }
+            hunks.push(hunk);
+        }
+
+        let patch = FilePatch {
+            old_path,
+            new_path,
+            hunks,
+        };
+        if patch.hunks.is_empty() && patch.kind() != FileChangeKind::Delete {
+            return Err(format!("{} has no hunks", patch.path()));
+        }
+        if patches.iter().any(|p| p.path() == patch.path()) {
+            return Err(format!(
+                "{} appears more than once; merge its hunks",
+                patch.path()
+            ));
+        }
+        patches.push(patch);
+    }
+
+    if patches.is_empty() {
+        return Err("patch contains no `---`/`+++` file sections".to_owned());
+    }
+    Ok(patches)
+}
+
+fn parse_header_path(raw: &str) -> Option<String> {
+    // Drop a trailing timestamp (`--- file\t2024-01-01 ...`).
+    let raw = raw.split('\t').next().unwrap_or_default().trim();
+    if raw == "/dev/null" {
+        return None;
+    }
+    let path = raw
+        .strip_prefix("a/")
+        .or_else(|| raw.strip_prefix("b/"))
+        .unwrap_or(raw);
+    Some(path.to_owned())
+}
+
+/// Parse `@@ -a[,b] +c[,d] @@` into `(a, c)`.
+fn parse_hunk_header(header: &str) -> Option<(usize, usize)> {
+    let inner = header.strip_prefix("@@")?;
+    let end = inner.find("@@")?;
+    let mut parts = inner[..end].split_whitespace();
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+    let start = |range: &str| range.split(',').next()?.parse::<usize>().ok();
+    Some((start(old)?, start(new)?))
+}
+
+/// Apply `hunks` to `original` and return the new content.
+///
+/// Hunks must be in file order and may not overlap. Each hunk is placed at
+/// the occurrence of its old lines closest to the line in its header.
+///
+/// # Errors
+///
+/// Returns a message naming the first hunk whose context or removed lines
+/// are not found in the file.
+pub fn apply_hunks(original: &str, hunks: &[Hunk]) -> Result<String, String> {
+    let crlf = original.contains("\r\n");
+    let mut trailing_newline = original.is_empty() || original.ends_with('\n');
+    let lines: Vec<&str> = original
+        .lines()
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .collect();
+
+    let mut out: Vec<&str> = Vec::with_capacity(lines.len());
+    let mut cursor = 0;
+    for (n, hunk) in hunks.iter().enumerate() {
+        let old = hunk.old_lines();
+        let pos = if old.is_empty() {
+            // Pure insertion: `@@ -N,0` inserts after line N.
+            hunk.old_start.clamp(cursor, lines.len())
+        } else {
+            find_hunk(&lines, &old, hunk.old_start.saturating_sub(1), cursor).ok_or_else(|| {
+                format!(
+                    "hunk {} (@@ -{} +{} @@) does not match the file",
+                    n + 1,
+                    hunk.old_start,
+                    hunk.new_start
+                )
+            })?
+        };
+        out.extend_from_slice(&lines[cursor..pos]);
+        let mut at = pos;
+        for line in &hunk.lines {
+            match line {
+                HunkLine::Context(_) => {
+                    out.push(lines[at]);
+                    at += 1;
+                }
+                HunkLine::Remove(_) => at += 1,
+                HunkLine::Add(s) => out.push(s),
+            }
+        }
+        cursor = at;
+        if cursor == lines.len() {
+            trailing_newline = !hunk.no_newline_at_end;
+        }
+    }
+    out.extend_from_slice(&lines[cursor..]);
+
+    if out.is_empty() {
+        return Ok(String::new());
+    }
+    let eol = if crlf { "\r\n" } else { "\n" };
+    let mut content = out.join(eol);
+    if trailing_newline {
+        content.push_str(eol);
+    }
+    Ok(content)
+}
+
+/// Start index of the occurrence of `old` at or after `from` closest to
+/// `expected`.
+fn find_hunk(lines: &[&str], old: &[&str], expected: usize, from: usize) -> Option<usize> {
+    if old.len() > lines.len() {
+        return None;
+    }
+    (from..=lines.len() - old.len())
+        .filter(|&p| lines[p..p + old.len()] == *old)
+        .min_by_key(|&p| p.abs_diff(expected))
+}
+
+/// Render `patches` for an approval prompt: a summary line per file followed
+/// by its hunks with recomputed line counts.
+pub fn render_patch(patches: &[FilePatch]) -> String {
+    let mut out = String::new();
+    for patch in patches {
+        let (added, removed) = patch.line_counts();
+        let verb = match patch.kind() {
+            FileChangeKind::Create => "create",
+            FileChangeKind::Modify => "modify",
+            FileChangeKind::Delete => "delete",
+        };
+        out.push_str(&format!("{verb} {} (+{added} -{removed})\n", patch.path()));
+        for hunk in &patch.hunks {
+            let old_count = hunk.old_lines().len();
+            let new_count = hunk
+                .lines
+                .iter()
+                .filter(|l| !matches!(l, HunkLine::Remove(_)))
+                .count();
+            out.push_str(&format!(
+                "@@ -{},{old_count} +{},{new_count} @@\n",
+                hunk.old_start, hunk.new_start
+            ));
+            for line in &hunk.lines {
+                let (prefix, text) = match line {
+                    HunkLine::Context(s) => (' ', s),
+                    HunkLine::Remove(s) => ('-', s),
+                    HunkLine::Add(s) => ('+', s),
+                };
+                out.push(prefix);
+                out.push_str(text);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    const TWO_FILES: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    println!(\"hi\");
++    println!(\"hello\");
+ }
+--- /dev/null
++++ b/NOTES.md
+@@ -0,0 +1,2 @@
++# Notes
++first
+";
+
+    #[test]
+    fn parses_multi_file_patch() {
+        let patches = parse_patch(TWO_FILES).unwrap();
+        assert_eq!(patches.len(), 2);
+        assert_eq!(patches[0].path(), "src/lib.rs");
+        assert_eq!(patches[0].kind(), FileChangeKind::Modify);
+        assert_eq!(patches[0].line_counts(), (1, 1));
+        assert_eq!(patches[1].path(), "NOTES.md");
+        assert_eq!(patches[1].kind(), FileChangeKind::Create);
+        assert_eq!(patches[1].line_counts(), (2, 0));
+    }
+
+    #[test]
+    fn rejects_malformed_patches() {
+        assert!(parse_patch("just some text").is_err());
+        assert!(parse_patch("@@ -1 +1 @@\n-a\n+b\n").is_err());
+        assert!(parse_patch("--- a/x\n+++ b/y\n@@ -1 +1 @@\n-a\n+b\n").is_err());
+        assert!(parse_patch("--- a/x\n+++ b/x\n@@ -1 +1 @@\n?a\n").is_err());
+        let twice =
+            "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n--- a/x\n+++ b/x\n@@ -3 +3 @@\n-c\n+d\n";
+        assert!(parse_patch(twice).is_err());
+    }
+
+    #[test]
+    fn applies_hunks_with_offset_and_blank_context() {
+        let original = "a\nb\n\nc\nd\ne\n";
+        // Header says line 1 but the block is at line 3; the blank context
+        // line has lost its leading space.
+        let patch = "--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n b\n\n-c\n+C\n";
+        let patches = parse_patch(patch).unwrap();
+        assert_eq!(
+            apply_hunks(original, &patches[0].hunks).unwrap(),
+            "a\nb\n\nC\nd\ne\n"
+        );
+    }
+
+    #[test]
+    fn mismatched_context_fails() {
+        let patches = parse_patch("--- a/f\n+++ b/f\n@@ -1 +1 @@\n-missing\n+x\n").unwrap();
+        let err = apply_hunks("a\nb\n", &patches[0].hunks).unwrap_err();
+        assert!(err.contains("hunk 1"));
+    }
+
+    #[test]
+    fn preserves_crlf_and_missing_final_newline() {
+        let patches = parse_patch("--- a/f\n+++ b/f\n@@ -1,2 +1,2 @@\n a\n-b\n+B\n").unwrap();
+        assert_eq!(
+            apply_hunks("a\r\nb\r\n", &patches[0].hunks).unwrap(),
+            "a\r\nB\r\n"
+        );
+
+        let patches = parse_patch(
+            "--- a/f\n+++ b/f\n@@ -1,2 +1,2 @@\n a\n-b\n+B\n\\ No newline at end of file\n",
+        )
+        .unwrap();
+        assert_eq!(apply_hunks("a\nb\n", &patches[0].hunks).unwrap(), "a\nB");
+    }
+
+    #[test]
+    fn renders_recomputed_counts() {
+        let patches = parse_patch(TWO_FILES).unwrap();
+        let rendered = render_patch(&patches);
+        assert!(rendered.starts_with("modify src/lib.rs (+1 -1)\n@@ -1,3 +1,3 @@\n"));
+        assert!(rendered.contains("create NOTES.md (+2 -0)\n@@ -0,0 +1,2 @@\n+# Notes\n"));
+    }
+}
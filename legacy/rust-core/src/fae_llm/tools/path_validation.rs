@@ -4,6 +4,7 @@
 //! sensitive system directories.
 
 use crate::fae_llm::error::FaeLlmError;
+use crate::permissions::SharedPermissionStore;
 use std::path::{Component, Path, PathBuf};
 
 /// System directories that tools must never write to.
@@ -161,6 +162,32 @@ pub fn resolve_workspace_root() -> Result<PathBuf, FaeLlmError> {
     })
 }
 
+/// Check a resolved path against the scope of the `Files` grant.
+///
+/// File tools call this after resolving each path they read (`write` false)
+/// or change (`write` true). Without a permission store nothing is checked.
+pub fn check_file_scope(
+    permissions: Option<&SharedPermissionStore>,
+    path: &Path,
+    write: bool,
+) -> Result<(), FaeLlmError> {
+    let Some(permissions) = permissions else {
+        return Ok(());
+    };
+    let allowed = permissions
+        .lock()
+        .map(|store| store.allows_file(path, write))
+        .unwrap_or(false);
+    if allowed {
+        return Ok(());
+    }
+    let access = if write { "change" } else { "read" };
+    Err(FaeLlmError::ToolValidationError(format!(
+        "the Files permission does not allow fae to {access} {}",
+        path.display()
+    )))
+}
+
 /// Validate a path is safe for reading within the workspace root.
 ///
 /// Returns a canonical absolute path.
@@ -0,0 +1,276 @@
+//! User preference tools.
+//!
+//! `get_preference` reads the preferences in
+//! [`crate::memory::preferences`]; `set_preference` records one the user
+//! states ("I use metric", "my time zone is Europe/London", "Siobhan is said
+//! shi-VAWN"). Set preferences are part of the system prompt, which the agent
+//! rebuilds after a successful `set_preference` call.
+
+use std::path::PathBuf;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::memory::preferences::{PreferenceKey, UserPreferences};
+
+use super::types::{Tool, ToolResult};
+
+fn key_names() -> Vec<&'static str> {
+    PreferenceKey::ALL.iter().map(|key| key.as_str()).collect()
+}
+
+fn parse_key(raw: &str) -> Result<PreferenceKey, FaeLlmError> {
+    PreferenceKey::parse(raw).ok_or_else(|| {
+        FaeLlmError::ToolValidationError(format!(
+            "unknown preference \"{raw}\"; expected one of {}",
+            key_names().join(", ")
+        ))
+    })
+}
+
+/// Tool that reads the user's preferences.
+///
+/// This is a **read-only** tool — allowed in all tool modes.
+///
+/// # Arguments (JSON)
+///
+/// - `key` (string, optional) — one preference; all when omitted
+pub struct GetPreferenceTool {
+    path: PathBuf,
+}
+
+impl GetPreferenceTool {
+    /// Create a tool over the default preference store.
+    pub fn new() -> Self {
+        Self::with_path(crate::fae_dirs::preferences_file())
+    }
+
+    /// Create a tool over the preference store at `path`.
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Default for GetPreferenceTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for GetPreferenceTool {
+    fn name(&self) -> &str {
+        "get_preference"
+    }
+
+    fn description(&self) -> &str {
+        "Look up the user's saved preferences: units, locale, time zone, calendar, home city, \
+         name pronunciations and reply length."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "key": {
+                    "type": "string",
+                    "enum": key_names(),
+                    "description": "Preference to read (all when omitted)"
+                }
+            }
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let keys = match args.get("key").and_then(|v| v.as_str()) {
+            Some(raw) => vec![parse_key(raw)?],
+            None => PreferenceKey::ALL.to_vec(),
+        };
+        let prefs = UserPreferences::load(&self.path);
+        let lines: Vec<String> = keys.iter().flat_map(|&key| prefs.describe(key)).collect();
+        if lines.is_empty() {
+            return Ok(ToolResult::success(match keys.as_slice() {
+                [key] => format!("No {} preference is set.", key.as_str()),
+                _ => "No preferences are set.".to_owned(),
+            }));
+        }
+        Ok(ToolResult::success(lines.join("\n")))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true // read-only
+    }
+}
+
+/// Tool that saves or clears one user preference.
+///
+/// # Arguments (JSON)
+///
+/// - `key` (string, required) — which preference
+/// - `value` (string or null) — new value; null or empty clears it
+/// - `name` (string) — the person's name, for `pronunciation`
+pub struct SetPreferenceTool {
+    path: PathBuf,
+}
+
+impl SetPreferenceTool {
+    /// Create a tool over the default preference store.
+    pub fn new() -> Self {
+        Self::with_path(crate::fae_dirs::preferences_file())
+    }
+
+    /// Create a tool over the preference store at `path`.
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Default for SetPreferenceTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for SetPreferenceTool {
+    fn name(&self) -> &str {
+        "set_preference"
+    }
+
+    fn description(&self) -> &str {
+        "Save a preference the user states so you never need to ask again: units (metric or \
+         imperial), locale (e.g. en-GB), timezone (IANA, e.g. Europe/London), calendar, \
+         home_city, pronunciation of a name, or verbosity (brief, normal or detailed)."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "key": {
+                    "type": "string",
+                    "enum": key_names(),
+                    "description": "Preference to set"
+                },
+                "value": {
+                    "type": ["string", "null"],
+                    "description": "New value; null clears the preference"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Whose name is pronounced (for pronunciation)"
+                }
+            },
+            "required": ["key", "value"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let raw_key = args.get("key").and_then(|v| v.as_str()).ok_or_else(|| {
+            FaeLlmError::ToolValidationError("missing required argument: key".to_owned())
+        })?;
+        let key = parse_key(raw_key)?;
+        let value = args.get("value").and_then(|v| v.as_str());
+        let name = args.get("name").and_then(|v| v.as_str());
+
+        let mut prefs = UserPreferences::load(&self.path);
+        prefs
+            .set(key, name, value)
+            .map_err(FaeLlmError::ToolValidationError)?;
+        if let Err(e) = prefs.save(&self.path) {
+            return Ok(ToolResult::failure(format!(
+                "failed to save preferences: {e}"
+            )));
+        }
+
+        let cleared = value.is_none_or(|v| v.trim().is_empty());
+        Ok(ToolResult::success(if cleared {
+            format!("Cleared the {} preference.", key.as_str())
+        } else {
+            format!("Saved. {}", prefs.describe(key).join("; "))
+        }))
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn set_then_get_round_trips() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("preferences.json");
+        let set = SetPreferenceTool::with_path(path.clone());
+        let get = GetPreferenceTool::with_path(path);
+
+        let result = set
+            .execute(json!({"key": "timezone", "value": "Europe/London"}))
+            .expect("set");
+        assert!(result.success);
+        assert_eq!(result.content, "Saved. Time zone: Europe/London");
+
+        set.execute(json!({"key": "pronunciation", "name": "Siobhan", "value": "shi-VAWN"}))
+            .expect("set pronunciation");
+
+        let one = get.execute(json!({"key": "timezone"})).expect("get");
+        assert_eq!(one.content, "Time zone: Europe/London");
+        let all = get.execute(json!({})).expect("get all");
+        assert_eq!(
+            all.content,
+            "Time zone: Europe/London\nPronunciation: \"Siobhan\" is said \"shi-VAWN\""
+        );
+    }
+
+    #[test]
+    fn set_null_clears_and_get_reports_unset() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("preferences.json");
+        let set = SetPreferenceTool::with_path(path.clone());
+        let get = GetPreferenceTool::with_path(path);
+
+        set.execute(json!({"key": "units", "value": "metric"}))
+            .expect("set");
+        let cleared = set
+            .execute(json!({"key": "units", "value": null}))
+            .expect("clear");
+        assert_eq!(cleared.content, "Cleared the units preference.");
+
+        let result = get.execute(json!({"key": "units"})).expect("get");
+        assert_eq!(result.content, "No units preference is set.");
+        let result = get.execute(json!({})).expect("get all");
+        assert_eq!(result.content, "No preferences are set.");
+    }
+
+    #[test]
+    fn invalid_keys_and_values_are_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("preferences.json");
+        let set = SetPreferenceTool::with_path(path.clone());
+
+        assert!(
+            set.execute(json!({"key": "shoe_size", "value": "9"}))
+                .is_err()
+        );
+        assert!(
+            set.execute(json!({"key": "units", "value": "cubits"}))
+                .is_err()
+        );
+        assert!(!path.exists());
+        assert!(
+            GetPreferenceTool::with_path(path)
+                .execute(json!({"key": "shoe_size"}))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn set_is_full_mode_only() {
+        assert!(SetPreferenceTool::new().allowed_in_mode(ToolMode::Full));
+        assert!(!SetPreferenceTool::new().allowed_in_mode(ToolMode::ReadOnly));
+        assert!(GetPreferenceTool::new().allowed_in_mode(ToolMode::ReadOnly));
+    }
+}
@@ -18,20 +18,40 @@
 //!
 //! `params` is optional. `skill_name` and `method` are required.
 //!
+//! # Credentials
+//!
+//! Credentials a skill declares in its manifest are resolved from the
+//! credential manager when its process starts and passed to it as environment
+//! variables for that process only. Values are never written to disk; each
+//! grant is appended to the grant ledger by environment variable name.
+//!
+//! # Network policy
+//!
+//! With a network guard set, URLs in `params` are checked and the policy is
+//! exported to the skill as `FAE_NETWORK_ALLOW` / `FAE_NETWORK_DENY`. This is
+//! advisory: the skill process is not sandboxed and can reach any host.
+//!
 //! # Returns
 //!
 //! The raw JSON value returned by the skill in the `result` field of its
 //! JSON-RPC 2.0 response, serialized to a compact string.
 
+use crate::credentials::CredentialManager;
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
+use crate::skills::SkillPaths;
+use crate::skills::credential_mediation::{
+    CredentialMediationError, record_credential_grant, retrieve_skill_credentials,
+};
 use crate::skills::error::PythonSkillError;
+use crate::skills::python_lifecycle::load_python_registry;
 use crate::skills::python_runner::{PythonSkillRunner, SkillProcessConfig};
 
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex, OnceLock};
 
+use super::network_policy::NetworkGuard;
 use super::types::{Tool, ToolResult};
 
 /// Global shared runner map.  Both `PythonSkillTool` and the scheduler's
@@ -61,6 +81,12 @@ pub struct PythonSkillTool {
     uv_path: PathBuf,
     /// Live runner instances keyed by skill name (shared global map).
     runners: SharedRunnerMap,
+    /// Outbound network policy applied to call parameters and skill processes.
+    network: Option<Arc<NetworkGuard>>,
+    /// Source of the credentials skills declare in their manifests.
+    credentials: Option<Arc<dyn CredentialManager>>,
+    /// Ledger recording which credentials each skill launch received.
+    grant_ledger: Option<PathBuf>,
 }
 
 impl PythonSkillTool {
@@ -70,9 +96,72 @@ impl PythonSkillTool {
             skills_dir,
             uv_path,
             runners: global_runner_map(),
+            network: None,
+            credentials: None,
+            grant_ledger: None,
         }
     }
 
+    /// Check URLs in call parameters against `guard` and pass the policy to
+    /// newly started skills through `FAE_NETWORK_ALLOW` / `FAE_NETWORK_DENY`.
+    pub fn with_network_guard(mut self, guard: Arc<NetworkGuard>) -> Self {
+        self.network = Some(guard);
+        self
+    }
+
+    /// Resolve the credentials a skill declares from `manager` when its
+    /// process starts.
+    pub fn with_credential_manager(mut self, manager: Arc<dyn CredentialManager>) -> Self {
+        self.credentials = Some(manager);
+        self
+    }
+
+    /// Append each credential grant to the ledger at `path`.
+    pub fn with_grant_ledger(mut self, path: PathBuf) -> Self {
+        self.grant_ledger = Some(path);
+        self
+    }
+
+    /// Resolve the credentials declared by `skill_name` into `env`.
+    ///
+    /// Returns a user-facing message when a declared credential cannot be
+    /// provided.
+    fn inject_credentials(
+        &self,
+        skill_name: &str,
+        env: &mut HashMap<String, String>,
+    ) -> Result<(), String> {
+        let registry = load_python_registry(&SkillPaths::for_root(self.skills_dir.clone()))
+            .map_err(|e| format!("skill {skill_name} error: {e}"))?;
+        let Some(schema) = registry
+            .get(skill_name)
+            .map(|record| record.credentials.as_slice())
+            .filter(|schema| !schema.is_empty())
+        else {
+            return Ok(());
+        };
+        let Some(manager) = &self.credentials else {
+            return Err(format!(
+                "skill {skill_name} needs credentials but no credential store is available"
+            ));
+        };
+        let collection = retrieve_skill_credentials(skill_name, schema, manager.as_ref()).map_err(
+            |e| match e {
+                CredentialMediationError::MissingRequired { name } => format!(
+                    "skill {skill_name} needs credential `{name}`; store it before using the skill"
+                ),
+                other => format!("skill {skill_name} credentials unavailable: {other}"),
+            },
+        )?;
+        collection.inject_into(env);
+        if let Some(ledger) = &self.grant_ledger
+            && let Err(e) = record_credential_grant(ledger, &collection)
+        {
+            tracing::warn!("failed to record credential grant for {skill_name}: {e}");
+        }
+        Ok(())
+    }
+
     /// Create a tool using defaults (skills dir from [`fae_dirs`] and
     /// `"uv"` for PATH lookup).
     ///
@@ -88,6 +177,7 @@ impl std::fmt::Debug for PythonSkillTool {
         f.debug_struct("PythonSkillTool")
             .field("skills_dir", &self.skills_dir)
             .field("uv_path", &self.uv_path)
+            .field("credentials", &self.credentials.is_some())
             .finish()
     }
 }
@@ -161,6 +251,10 @@ impl Tool for PythonSkillTool {
             )));
         }
 
+        if let (Some(guard), Some(params)) = (&self.network, &params) {
+            guard.check_text(self.name(), &params.to_string())?;
+        }
+
         let script_path = self.skills_dir.join(format!("{skill_name}.py"));
 
         // Check if the skill script exists.
@@ -178,8 +272,16 @@ impl Tool for PythonSkillTool {
             .map_err(|_| FaeLlmError::ToolExecutionError("runner lock poisoned".into()))?;
 
         if !runners.contains_key(skill_name) {
-            let config =
+            let mut config =
                 SkillProcessConfig::new(skill_name, script_path).with_uv_path(self.uv_path.clone());
+            if let Some(guard) = &self.network {
+                for (key, value) in guard.policy().env_vars() {
+                    config.env_overrides.insert(key.to_owned(), value);
+                }
+            }
+            if let Err(message) = self.inject_credentials(skill_name, &mut config.env_overrides) {
+                return Ok(ToolResult::failure(message));
+            }
             runners.insert(skill_name.to_owned(), PythonSkillRunner::new(config));
         }
 
@@ -322,6 +424,102 @@ mod tests {
         assert!(result.error.unwrap().contains("skill not found"));
     }
 
+    #[test]
+    fn denied_url_in_params_is_rejected() {
+        let guard = NetworkGuard::new(crate::fae_llm::tools::NetworkPolicy {
+            allow_domains: vec!["api.example.com".into()],
+            ..Default::default()
+        });
+        let tool = PythonSkillTool::new(
+            std::path::PathBuf::from("/tmp/nonexistent-skills-dir"),
+            std::path::PathBuf::from("uv"),
+        )
+        .with_network_guard(Arc::new(guard));
+        let args = serde_json::json!({
+            "skill_name": "my-skill",
+            "method": "post",
+            "params": {"url": "https://elsewhere.test/hook"}
+        });
+        let err = tool.execute(args).unwrap_err();
+        assert!(err.to_string().contains("elsewhere.test"));
+    }
+
+    fn install_skill_with_credential(root: &std::path::Path, id: &str) {
+        let package = root.join("package");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(
+            package.join("manifest.toml"),
+            format!(
+                "id = \"{id}\"\nname = \"Weather\"\n\n[[credentials]]\n\
+                 name = \"api_key\"\nenv_var = \"WEATHER_API_KEY\"\n\
+                 description = \"Weather API key\"\n"
+            ),
+        )
+        .unwrap();
+        std::fs::write(package.join("skill.py"), "print('hi')\n").unwrap();
+        crate::skills::python_lifecycle::install_python_skill_at(
+            &SkillPaths::for_root(root.to_path_buf()),
+            &package,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn missing_declared_credential_returns_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        install_skill_with_credential(dir.path(), "weather-missing-cred");
+        let manager = crate::skills::credential_mediation::tests::MockCredentialManager::new();
+        let tool = PythonSkillTool::new(dir.path().to_path_buf(), std::path::PathBuf::from("uv"))
+            .with_credential_manager(Arc::new(manager));
+
+        let args = serde_json::json!({"skill_name": "weather-missing-cred", "method": "ping"});
+        let result = tool.execute(args).unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("`api_key`"));
+    }
+
+    #[test]
+    fn declared_credentials_are_injected_and_granted() {
+        let dir = tempfile::tempdir().unwrap();
+        install_skill_with_credential(dir.path(), "weather");
+        let manager = crate::skills::credential_mediation::tests::MockCredentialManager::new();
+        crate::credentials::CredentialManager::store(&manager, "weather.api_key", "sk-test")
+            .unwrap();
+        let ledger = dir.path().join("grants.jsonl");
+        let tool = PythonSkillTool::new(dir.path().to_path_buf(), std::path::PathBuf::from("uv"))
+            .with_credential_manager(Arc::new(manager))
+            .with_grant_ledger(ledger.clone());
+
+        let mut env = HashMap::new();
+        tool.inject_credentials("weather", &mut env).unwrap();
+        assert_eq!(
+            env.get("WEATHER_API_KEY").map(String::as_str),
+            Some("sk-test")
+        );
+
+        let grants =
+            crate::skills::credential_mediation::read_credential_grants(&ledger, "weather")
+                .unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].env_vars, vec!["WEATHER_API_KEY".to_owned()]);
+        assert!(
+            !std::fs::read_to_string(&ledger)
+                .unwrap()
+                .contains("sk-test")
+        );
+    }
+
+    #[test]
+    fn skill_without_declared_credentials_needs_no_manager() {
+        let tool = PythonSkillTool::new(
+            std::path::PathBuf::from("/tmp/nonexistent-skills-dir"),
+            std::path::PathBuf::from("uv"),
+        );
+        let mut env = HashMap::new();
+        tool.inject_credentials("my-skill", &mut env).unwrap();
+        assert!(env.is_empty());
+    }
+
     #[test]
     fn script_path_matches_lifecycle_flat_layout() {
         // The python_lifecycle installs scripts at `{skills_root}/{id}.py` (flat).
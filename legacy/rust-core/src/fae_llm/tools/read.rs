@@ -2,9 +2,12 @@
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
+use crate::permissions::SharedPermissionStore;
 use std::path::PathBuf;
 
-use super::path_validation::{resolve_workspace_root, validate_read_path_in_workspace};
+use super::path_validation::{
+    check_file_scope, resolve_workspace_root, validate_read_path_in_workspace,
+};
 use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
 
 /// Tool that reads file contents with optional line-based pagination.
@@ -16,6 +19,7 @@ use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
 pub struct ReadTool {
     max_bytes: usize,
     workspace_root: PathBuf,
+    permissions: Option<SharedPermissionStore>,
 }
 
 impl ReadTool {
@@ -24,6 +28,7 @@ impl ReadTool {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
             workspace_root: resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")),
+            permissions: None,
         }
     }
 
@@ -32,6 +37,7 @@ impl ReadTool {
         Self {
             max_bytes,
             workspace_root: resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")),
+            permissions: None,
         }
     }
 
@@ -40,6 +46,7 @@ impl ReadTool {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
             workspace_root,
+            permissions: None,
         }
     }
 
@@ -48,8 +55,15 @@ impl ReadTool {
         Self {
             max_bytes,
             workspace_root,
+            permissions: None,
         }
     }
+
+    /// Only read files the `Files` grant in `permissions` covers.
+    pub fn with_permissions(mut self, permissions: SharedPermissionStore) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
 }
 
 impl Default for ReadTool {
@@ -94,6 +108,7 @@ impl Tool for ReadTool {
         })?;
 
         let path = validate_read_path_in_workspace(path_str, &self.workspace_root)?;
+        check_file_scope(self.permissions.as_ref(), &path, false)?;
 
         let offset = args
             .get("offset")
@@ -66,11 +66,14 @@ impl ToolRegistry {
             .values()
             .filter(|t| t.allowed_in_mode(self.mode))
             .map(|t| {
-                let entry = serde_json::json!({
+                let mut entry = serde_json::json!({
                     "name": t.name(),
                     "description": t.description(),
                     "parameters": t.schema(),
                 });
+                if let Some(output_schema) = t.output_schema() {
+                    entry["output_schema"] = output_schema;
+                }
                 (t.name().to_string(), entry)
             })
             .collect();
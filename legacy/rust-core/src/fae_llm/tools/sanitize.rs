@@ -158,6 +158,6 @@ mod tests {
         let input = "hello ".repeat(200);
         let out = sanitize_tool_output(&input, 100);
         assert!(out.truncated);
-        assert!(out.content.contains("[output truncated at 100 bytes]"));
+        assert!(out.content.contains("[output truncated at 100 bytes:"));
     }
 }
@@ -1,12 +1,14 @@
 //! Create scheduled task tool.
 //!
 //! Mutation tool that creates or updates a user-defined scheduled task.
-//! Supports interval, daily, and weekly schedules.
+//! Supports interval, daily, and weekly schedules, and tasks instantiated
+//! from a [`JobTemplate`](crate::scheduler::JobTemplate).
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
 use crate::scheduler;
 use crate::scheduler::tasks::{Schedule, ScheduledTask, Weekday};
+use crate::scheduler::templates;
 
 use super::types::{Tool, ToolResult};
 
@@ -24,6 +26,13 @@ use super::types::{Tool, ToolResult};
 ///   - `{"type": "weekly", "weekdays": ["mon","fri"], "hour": 9, "min": 0}` — run on selected weekdays
 /// - `id` (string, optional) — task ID; auto-generated from name if omitted
 /// - `payload` (any, optional) — opaque data stored with the task
+/// - `exclusive` (boolean, optional) — never run alongside another exclusive task
+/// - `template` (string, optional) — job template to instantiate; `name` and
+///   `schedule` then default to the template's and `payload` is ignored
+/// - `params` (object, optional) — values for the template's placeholders
+///
+/// The task is saved even when its schedule conflicts with quiet hours or
+/// another exclusive task; the conflicts are reported as warnings.
 pub struct SchedulerCreateTool;
 
 impl SchedulerCreateTool {
@@ -45,7 +54,7 @@ impl Tool for SchedulerCreateTool {
     }
 
     fn description(&self) -> &str {
-        "Create or update a user-defined scheduled task. Supports interval, daily, and weekly schedules."
+        "Create or update a user-defined scheduled task. Supports interval, daily, and weekly schedules, or instantiating a job template with parameters."
     }
 
     fn schema(&self) -> serde_json::Value {
@@ -66,9 +75,24 @@ impl Tool for SchedulerCreateTool {
                 },
                 "payload": {
                     "description": "Optional data to store with the task"
+                },
+                "exclusive": {
+                    "type": "boolean",
+                    "description": "Never run at the same time as another exclusive task (default false)"
+                },
+                "template": {
+                    "type": "string",
+                    "description": format!(
+                        "Optional job template to instantiate instead of writing the task from scratch. Available: {}",
+                        template_catalog()
+                    )
+                },
+                "params": {
+                    "type": "object",
+                    "description": "Values for the template's parameters"
                 }
             },
-            "required": ["name", "schedule"]
+            "description": "Give 'name' and 'schedule', or a 'template' (name and schedule then default to the template's)."
         })
     }
 
@@ -79,9 +103,38 @@ impl Tool for SchedulerCreateTool {
             )));
         }
 
-        let name = args.get("name").and_then(|v| v.as_str()).ok_or_else(|| {
-            FaeLlmError::ToolValidationError("missing required argument: name".into())
-        })?;
+        let schedule = args.get("schedule").map(parse_schedule).transpose()?;
+        let name = args.get("name").and_then(|v| v.as_str()).map(String::from);
+
+        let (name, schedule, payload) = match args.get("template").and_then(|v| v.as_str()) {
+            Some(template_id) => {
+                let values = args
+                    .get("params")
+                    .and_then(|v| v.as_object())
+                    .cloned()
+                    .unwrap_or_default();
+                let instance = templates::find_template(template_id)
+                    .and_then(|template| template.instantiate(&values, schedule))
+                    .map_err(|e| FaeLlmError::ToolValidationError(e.to_string()))?;
+                let payload = instance.trigger.to_json().map_err(|e| {
+                    FaeLlmError::ToolExecutionError(format!("failed to build payload: {e}"))
+                })?;
+                (
+                    name.unwrap_or(instance.name),
+                    instance.schedule,
+                    Some(payload),
+                )
+            }
+            None => {
+                let name = name.ok_or_else(|| {
+                    FaeLlmError::ToolValidationError("missing required argument: name".into())
+                })?;
+                let schedule = schedule.ok_or_else(|| {
+                    FaeLlmError::ToolValidationError("missing required argument: schedule".into())
+                })?;
+                (name, schedule, args.get("payload").cloned())
+            }
+        };
 
         if name.trim().is_empty() {
             return Err(FaeLlmError::ToolValidationError(
@@ -89,29 +142,41 @@ impl Tool for SchedulerCreateTool {
             ));
         }
 
-        let schedule_obj = args.get("schedule").ok_or_else(|| {
-            FaeLlmError::ToolValidationError("missing required argument: schedule".into())
-        })?;
-
-        let schedule = parse_schedule(schedule_obj)?;
-
         let id = args
             .get("id")
             .and_then(|v| v.as_str())
             .map(String::from)
-            .unwrap_or_else(|| slug_from_name(name));
-
-        let payload = args.get("payload").cloned();
+            .unwrap_or_else(|| slug_from_name(&name));
 
-        let mut task = ScheduledTask::user_task(&id, name, schedule);
+        let mut task = ScheduledTask::user_task(&id, &name, schedule);
         task.payload = payload;
+        task.exclusive = args
+            .get("exclusive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let existing = scheduler::load_persisted_snapshot()
+            .map(|snapshot| snapshot.tasks)
+            .unwrap_or_default();
+        let conflicts = scheduler::detect_conflicts(
+            &task,
+            &existing,
+            Some(scheduler::QuietHours::from_config()),
+            crate::time_util::now_epoch_secs(),
+        );
 
         scheduler::upsert_persisted_user_task(task)
             .map_err(|e| FaeLlmError::ToolExecutionError(format!("failed to save task: {e}")))?;
 
-        Ok(ToolResult::success(format!(
-            "Task '{name}' (id: {id}) created successfully."
-        )))
+        let mut message = format!("Task '{name}' (id: {id}) created successfully.");
+        for conflict in &conflicts {
+            message.push_str(&format!("\nWarning: this task {conflict}."));
+        }
+        Ok(ToolResult::success(message))
+    }
+
+    fn is_side_effecting(&self) -> bool {
+        true
     }
 
     fn allowed_in_mode(&self, mode: ToolMode) -> bool {
@@ -119,6 +184,29 @@ impl Tool for SchedulerCreateTool {
     }
 }
 
+/// One-line summaries of the available job templates for the schema.
+fn template_catalog() -> String {
+    let templates = templates::load_templates(&crate::fae_dirs::scheduler_templates_file())
+        .unwrap_or_else(|_| templates::builtin_templates());
+    templates
+        .iter()
+        .map(|t| {
+            let params: Vec<&str> = t.params.iter().map(|p| p.name.as_str()).collect();
+            if params.is_empty() {
+                format!("'{}' ({})", t.id, t.description)
+            } else {
+                format!(
+                    "'{}' ({}; params: {})",
+                    t.id,
+                    t.description,
+                    params.join(", ")
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Parse a `Schedule` from a JSON value.
 fn parse_schedule(obj: &serde_json::Value) -> Result<Schedule, FaeLlmError> {
     let schedule_type = obj.get("type").and_then(|v| v.as_str()).ok_or_else(|| {
@@ -348,11 +436,14 @@ mod tests {
         let tool = SchedulerCreateTool::new();
         let schema = tool.schema();
         assert!(schema.is_object());
-        let required = schema.get("required").and_then(|v| v.as_array());
-        assert!(required.is_some());
-        let required = required.unwrap();
-        assert!(required.contains(&serde_json::json!("name")));
-        assert!(required.contains(&serde_json::json!("schedule")));
+        let properties = schema.get("properties").and_then(|v| v.as_object());
+        assert!(properties.is_some());
+        let properties = properties.unwrap();
+        assert!(properties.contains_key("name"));
+        assert!(properties.contains_key("schedule"));
+        assert!(properties.contains_key("template"));
+        let template = properties["template"]["description"].as_str().unwrap();
+        assert!(template.contains("news_briefing"), "{template}");
     }
 
     #[test]
@@ -380,4 +471,18 @@ mod tests {
         }));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn execute_rejects_unknown_template() {
+        let tool = SchedulerCreateTool::new();
+        let result = tool.execute(serde_json::json!({ "template": "no-such-template" }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn execute_rejects_template_without_params() {
+        let tool = SchedulerCreateTool::new();
+        let result = tool.execute(serde_json::json!({ "template": "news_briefing" }));
+        assert!(result.is_err());
+    }
 }
@@ -1,7 +1,7 @@
 //! List scheduled tasks tool.
 //!
 //! Read-only tool that lists all scheduled tasks with their status, schedule,
-//! and run history. Supports filtering by kind and enabled state.
+//! and last run outcome. Supports filtering by kind and enabled state.
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
@@ -100,9 +100,24 @@ impl Tool for SchedulerListTool {
             } else {
                 String::new()
             };
+            let exclusive = if task.exclusive { ", exclusive" } else { "" };
+            let last_outcome = snapshot
+                .history
+                .iter()
+                .rev()
+                .find(|run| run.task_id == task.id)
+                .map(|run| {
+                    let log = run
+                        .log_path
+                        .as_deref()
+                        .map(|path| format!(" (log: {path})"))
+                        .unwrap_or_default();
+                    format!("\n  Last outcome: {:?}{log}", run.outcome)
+                })
+                .unwrap_or_default();
 
             lines.push(format!(
-                "- [{id}] {name} ({kind})\n  Schedule: {schedule_desc}\n  Status: {status} | Last run: {last_run}{failure_info}",
+                "- [{id}] {name} ({kind}{exclusive})\n  Schedule: {schedule_desc}\n  Status: {status} | Last run: {last_run}{failure_info}{last_outcome}",
                 id = task.id,
                 name = task.name,
             ));
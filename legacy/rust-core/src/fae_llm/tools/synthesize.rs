@@ -0,0 +1,265 @@
+//! Synthesize tool — saves text as spoken audio.
+//!
+//! [`SynthesizeToFileTool`] reads a text aloud with the current voice and
+//! writes the result as a WAV or MP3 file (see [`crate::tts::export`]), for
+//! listening later: "read this article and save it for my run". The text may
+//! be SSML for pauses and pacing. It writes files, so it needs
+//! `ToolMode::Full`; progress is reported like
+//! [`TranscribeFileTool`](super::TranscribeFileTool)'s.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::config::{SpeechConfig, TtsConfig};
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::tts::TtsEngine;
+use crate::tts::export::{AudioFileFormat, MAX_TEXT_CHARS, synthesize_to_file};
+
+use super::archive::format_bytes;
+use super::path_validation::{resolve_workspace_root, validate_write_path_in_workspace};
+use super::transcribe::{ProgressCallback, format_timestamp};
+use super::types::{Tool, ToolResult};
+
+/// Tool that renders text to an audio file.
+///
+/// Arguments (JSON):
+/// - `text` (string, required) — plain text, or SSML in `<speak>…</speak>`
+/// - `path` (string, required) — `.wav` or `.mp3` file to write
+pub struct SynthesizeToFileTool {
+    workspace_root: PathBuf,
+    config: TtsConfig,
+    /// Created from `config` on first use, then kept for later calls.
+    engine: Mutex<Option<Box<dyn TtsEngine>>>,
+    progress: Option<ProgressCallback>,
+}
+
+impl SynthesizeToFileTool {
+    /// Create a new SynthesizeToFileTool using the voice from the user's
+    /// config.
+    pub fn new() -> Self {
+        let config = SpeechConfig::from_file(&crate::fae_dirs::config_file()).unwrap_or_default();
+        Self::with_config(config.tts)
+    }
+
+    /// Create a new SynthesizeToFileTool speaking with `config`.
+    pub fn with_config(config: TtsConfig) -> Self {
+        Self {
+            workspace_root: resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")),
+            config,
+            engine: Mutex::new(None),
+            progress: None,
+        }
+    }
+
+    /// Create a new SynthesizeToFileTool speaking with `engine`.
+    pub fn with_engine(engine: Box<dyn TtsEngine>) -> Self {
+        Self {
+            engine: Mutex::new(Some(engine)),
+            ..Self::with_config(TtsConfig::default())
+        }
+    }
+
+    /// Set the workspace root paths are resolved against.
+    pub fn with_workspace_root(mut self, workspace_root: PathBuf) -> Self {
+        self.workspace_root = workspace_root;
+        self
+    }
+
+    /// Report progress through `progress`.
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+}
+
+impl Default for SynthesizeToFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for SynthesizeToFileTool {
+    fn name(&self) -> &str {
+        "synthesize_to_file"
+    }
+
+    fn description(&self) -> &str {
+        "Read text aloud with the current voice and save it as a .wav or .mp3 file to \
+         listen to later. Accepts plain text or SSML (<speak>, <break>, <prosody>, <emphasis>)"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "text": {
+                    "type": "string",
+                    "description": "Text to speak: plain text, or SSML wrapped in <speak>"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Audio file to write (.wav or .mp3)"
+                }
+            },
+            "required": ["text", "path"]
+        })
+    }
+
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        let text = args.get("text")?.as_str()?;
+        let path = args.get("path")?.as_str()?;
+        Some(format!(
+            "save {} characters of speech to {path}",
+            text.chars().count()
+        ))
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let text = args.get("text").and_then(|v| v.as_str()).ok_or_else(|| {
+            FaeLlmError::ToolValidationError("missing required argument: text".into())
+        })?;
+        let path_str = args.get("path").and_then(|v| v.as_str()).ok_or_else(|| {
+            FaeLlmError::ToolValidationError("missing required argument: path".into())
+        })?;
+        if text.trim().is_empty() {
+            return Err(FaeLlmError::ToolValidationError("text is empty".into()));
+        }
+        let chars = text.chars().count();
+        if chars > MAX_TEXT_CHARS {
+            return Err(FaeLlmError::ToolValidationError(format!(
+                "text is {chars} characters; the limit is {MAX_TEXT_CHARS}"
+            )));
+        }
+        let path = validate_write_path_in_workspace(path_str, &self.workspace_root)?;
+        if AudioFileFormat::from_path(&path).is_none() {
+            return Err(FaeLlmError::ToolValidationError(
+                "unsupported audio type: use .wav or .mp3".into(),
+            ));
+        }
+
+        let mut engine = self
+            .engine
+            .lock()
+            .map_err(|_| FaeLlmError::ToolExecutionError("speech engine lock poisoned".into()))?;
+        let mut progress = |fraction: f32| {
+            if let Some(progress) = &self.progress {
+                progress(fraction);
+            }
+        };
+        let result = block_on(async {
+            let mut tts = match engine.take() {
+                Some(tts) => tts,
+                None => crate::tts::create_engine(&self.config).await?,
+            };
+            let result = synthesize_to_file(tts.as_mut(), text, &path, &mut progress).await;
+            *engine = Some(tts);
+            result
+        })?;
+
+        match result {
+            Ok(export) => Ok(ToolResult::success(format!(
+                "Saved {} of audio to {} ({}).",
+                format_timestamp(export.duration_secs),
+                export.path.display(),
+                format_bytes(export.bytes)
+            ))),
+            Err(e) => Ok(ToolResult::failure(format!(
+                "failed to save speech to {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+/// Run `future` to completion from the synchronous tool interface.
+fn block_on<F: std::future::Future>(future: F) -> Result<F::Output, FaeLlmError> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => Ok(handle.block_on(future)),
+        Err(_) => tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map(|rt| rt.block_on(future))
+            .map_err(|e| {
+                FaeLlmError::ToolExecutionError(format!(
+                    "failed to create runtime for synthesize_to_file: {e}"
+                ))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::error::Result;
+    use crate::tts::TtsCapabilities;
+
+    /// Speaks every character as 100 samples of silence at 1 kHz.
+    struct SilentEngine;
+
+    #[async_trait::async_trait]
+    impl TtsEngine for SilentEngine {
+        fn id(&self) -> &'static str {
+            "silent"
+        }
+
+        async fn synthesize(&mut self, text: &str) -> Result<Vec<f32>> {
+            Ok(vec![0.0; text.chars().count() * 100])
+        }
+
+        fn sample_rate(&self) -> u32 {
+            1_000
+        }
+
+        fn capabilities(&self) -> TtsCapabilities {
+            TtsCapabilities::default()
+        }
+    }
+
+    #[test]
+    fn saves_speech_and_reports_progress() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        let tool = SynthesizeToFileTool::with_engine(Box::new(SilentEngine))
+            .with_workspace_root(dir.path().to_path_buf())
+            .with_progress(Arc::new(move |f| sink.lock().unwrap().push(f)));
+
+        let result = tool
+            .execute(serde_json::json!({
+                "text": "<speak>Warm up.<break time=\"3s\"/>Go!</speak>",
+                "path": "run.wav"
+            }))
+            .expect("execute");
+        assert!(result.success, "{}", result.content);
+        // 11 characters at 0.1 s each, plus the 3 s break.
+        assert!(result.content.starts_with("Saved 0:04 of audio to "));
+        assert!(dir.path().join("run.wav").is_file());
+        assert_eq!(reported.lock().unwrap().last().copied(), Some(1.0));
+        assert!(!tool.allowed_in_mode(ToolMode::ReadOnly));
+    }
+
+    #[test]
+    fn rejects_other_formats_and_long_text() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let tool = SynthesizeToFileTool::with_engine(Box::new(SilentEngine))
+            .with_workspace_root(dir.path().to_path_buf());
+        assert!(
+            tool.execute(serde_json::json!({"text": "Hi.", "path": "run.ogg"}))
+                .is_err()
+        );
+        let long = "a".repeat(MAX_TEXT_CHARS + 1);
+        assert!(
+            tool.execute(serde_json::json!({"text": long, "path": "run.mp3"}))
+                .is_err()
+        );
+    }
+}
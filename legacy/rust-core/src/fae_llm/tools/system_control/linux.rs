@@ -0,0 +1,125 @@
+//! Linux system settings backend.
+//!
+//! - Volume through `pactl` on the default sink (PulseAudio or PipeWire).
+//! - Brightness through `brightnessctl`: `sudo apt install brightnessctl`.
+//! - Dark mode and do-not-disturb through GNOME `gsettings`
+//!   (`color-scheme` and notification banners).
+//! - Wi-Fi through NetworkManager's `nmcli`.
+
+use super::{
+    SettingValue, SystemSetting, SystemSettingsBackend, binary_exists, first_percent, run_command,
+};
+
+const INTERFACE_SCHEMA: &str = "org.gnome.desktop.interface";
+const NOTIFICATIONS_SCHEMA: &str = "org.gnome.desktop.notifications";
+
+/// Linux system settings via desktop command-line tools.
+pub struct LinuxSystemSettings;
+
+impl LinuxSystemSettings {
+    /// Create a new `LinuxSystemSettings`.
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn gsettings_get(schema: &str, key: &str) -> Result<String, String> {
+        run_command("gsettings", &["get", schema, key]).map(|out| unquote(&out))
+    }
+
+    fn gsettings_set(schema: &str, key: &str, value: &str) -> Result<(), String> {
+        run_command("gsettings", &["set", schema, key, value]).map(drop)
+    }
+}
+
+impl Default for LinuxSystemSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemSettingsBackend for LinuxSystemSettings {
+    fn name(&self) -> &str {
+        "linux"
+    }
+
+    fn is_available(&self) -> bool {
+        ["pactl", "brightnessctl", "gsettings", "nmcli"]
+            .into_iter()
+            .any(binary_exists)
+    }
+
+    fn get(&self, setting: SystemSetting) -> Result<SettingValue, String> {
+        match setting {
+            SystemSetting::Volume => {
+                let out = run_command("pactl", &["get-sink-volume", "@DEFAULT_SINK@"])?;
+                first_percent(&out)
+                    .map(SettingValue::Level)
+                    .ok_or_else(|| format!("unexpected pactl output: {}", out.trim()))
+            }
+            SystemSetting::Brightness => {
+                let out = run_command("brightnessctl", &["-m", "info"])?;
+                first_percent(&out)
+                    .map(SettingValue::Level)
+                    .ok_or_else(|| format!("unexpected brightnessctl output: {}", out.trim()))
+            }
+            SystemSetting::DarkMode => {
+                let scheme = Self::gsettings_get(INTERFACE_SCHEMA, "color-scheme")?;
+                Ok(SettingValue::Switch(scheme == "prefer-dark"))
+            }
+            SystemSetting::DoNotDisturb => {
+                let banners = Self::gsettings_get(NOTIFICATIONS_SCHEMA, "show-banners")?;
+                Ok(SettingValue::Switch(banners == "false"))
+            }
+            SystemSetting::Wifi => {
+                let out = run_command("nmcli", &["radio", "wifi"])?;
+                match out.trim() {
+                    "enabled" => Ok(SettingValue::Switch(true)),
+                    "disabled" => Ok(SettingValue::Switch(false)),
+                    other => Err(format!("unexpected nmcli output: {other}")),
+                }
+            }
+        }
+    }
+
+    fn set(&self, setting: SystemSetting, value: SettingValue) -> Result<(), String> {
+        match (setting, value) {
+            (SystemSetting::Volume, SettingValue::Level(level)) => run_command(
+                "pactl",
+                &["set-sink-volume", "@DEFAULT_SINK@", &format!("{level}%")],
+            )
+            .map(drop),
+            (SystemSetting::Brightness, SettingValue::Level(level)) => {
+                run_command("brightnessctl", &["set", &format!("{level}%")]).map(drop)
+            }
+            (SystemSetting::DarkMode, SettingValue::Switch(on)) => {
+                let scheme = if on { "prefer-dark" } else { "default" };
+                Self::gsettings_set(INTERFACE_SCHEMA, "color-scheme", scheme)
+            }
+            (SystemSetting::DoNotDisturb, SettingValue::Switch(on)) => {
+                let banners = if on { "false" } else { "true" };
+                Self::gsettings_set(NOTIFICATIONS_SCHEMA, "show-banners", banners)
+            }
+            (SystemSetting::Wifi, SettingValue::Switch(on)) => {
+                let state = if on { "on" } else { "off" };
+                run_command("nmcli", &["radio", "wifi", state]).map(drop)
+            }
+            (setting, value) => Err(format!("{} cannot be set to {value:?}", setting.as_str())),
+        }
+    }
+}
+
+/// `'prefer-dark'\n` → `prefer-dark`, the GVariant text `gsettings` prints.
+fn unquote(output: &str) -> String {
+    output.trim().trim_matches('\'').to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gsettings_values_are_unquoted() {
+        assert_eq!(unquote("'prefer-dark'\n"), "prefer-dark");
+        assert_eq!(unquote("false\n"), "false");
+    }
+}
@@ -0,0 +1,230 @@
+//! macOS system settings backend.
+//!
+//! - Volume and dark mode through `osascript` (Standard Additions and
+//!   System Events appearance preferences).
+//! - Wi-Fi through `networksetup` on the Wi-Fi hardware port.
+//! - Brightness through the `brightness` CLI: `brew install brightness`.
+//! - Do-not-disturb is read from the Focus assertion database and changed by
+//!   running two user shortcuts, [`FOCUS_ON_SHORTCUT`] and
+//!   [`FOCUS_OFF_SHORTCUT`], each a single "Set Focus" action, because macOS
+//!   has no command-line switch for Focus.
+
+use super::{SettingValue, SystemSetting, SystemSettingsBackend, binary_exists, run_command};
+
+/// Shortcut run to turn do-not-disturb on.
+pub const FOCUS_ON_SHORTCUT: &str = "Fae Focus On";
+/// Shortcut run to turn do-not-disturb off.
+pub const FOCUS_OFF_SHORTCUT: &str = "Fae Focus Off";
+
+/// Focus assertions, relative to the home directory.
+const FOCUS_ASSERTIONS: &str = "Library/DoNotDisturb/DB/Assertions.json";
+
+/// macOS system settings via built-in command-line tools.
+pub struct MacSystemSettings;
+
+impl MacSystemSettings {
+    /// Create a new `MacSystemSettings`.
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn osascript(script: &str) -> Result<String, String> {
+        run_command("osascript", &["-e", script]).map(|out| out.trim().to_owned())
+    }
+
+    /// The device name of the Wi-Fi interface, usually `en0`.
+    fn wifi_device() -> Result<String, String> {
+        let ports = run_command("networksetup", &["-listallhardwareports"])?;
+        parse_wifi_device(&ports).ok_or_else(|| "no Wi-Fi interface found".to_owned())
+    }
+
+    fn brightness_cli() -> Result<(), String> {
+        if binary_exists("brightness") {
+            Ok(())
+        } else {
+            Err("brightness control needs the brightness CLI: brew install brightness".to_owned())
+        }
+    }
+
+    fn focus_active() -> Result<bool, String> {
+        let path = dirs::home_dir()
+            .ok_or("no home directory")?
+            .join(FOCUS_ASSERTIONS);
+        let json = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read Focus state at {}: {e}", path.display()))?;
+        parse_focus_assertions(&json)
+    }
+}
+
+impl Default for MacSystemSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemSettingsBackend for MacSystemSettings {
+    fn name(&self) -> &str {
+        "macos"
+    }
+
+    fn is_available(&self) -> bool {
+        binary_exists("osascript")
+    }
+
+    fn get(&self, setting: SystemSetting) -> Result<SettingValue, String> {
+        match setting {
+            SystemSetting::Volume => {
+                let out = Self::osascript("output volume of (get volume settings)")?;
+                out.parse::<u8>()
+                    .map(|level| SettingValue::Level(level.min(100)))
+                    .map_err(|_| format!("unexpected volume output: {out}"))
+            }
+            SystemSetting::Brightness => {
+                Self::brightness_cli()?;
+                let out = run_command("brightness", &["-l"])?;
+                parse_brightness_list(&out)
+                    .map(SettingValue::Level)
+                    .ok_or_else(|| "no built-in display reported a brightness".to_owned())
+            }
+            SystemSetting::DarkMode => {
+                let out = Self::osascript(
+                    "tell application \"System Events\" to tell appearance preferences \
+                     to get dark mode",
+                )?;
+                Ok(SettingValue::Switch(out == "true"))
+            }
+            SystemSetting::DoNotDisturb => Self::focus_active().map(SettingValue::Switch),
+            SystemSetting::Wifi => {
+                let device = Self::wifi_device()?;
+                let out = run_command("networksetup", &["-getairportpower", &device])?;
+                parse_airport_power(&out)
+                    .map(SettingValue::Switch)
+                    .ok_or_else(|| format!("unexpected Wi-Fi power output: {}", out.trim()))
+            }
+        }
+    }
+
+    fn set(&self, setting: SystemSetting, value: SettingValue) -> Result<(), String> {
+        match (setting, value) {
+            (SystemSetting::Volume, SettingValue::Level(level)) => {
+                let muted = if level == 0 { "with" } else { "without" };
+                Self::osascript(&format!(
+                    "set volume output volume {level} {muted} output muted"
+                ))
+                .map(drop)
+            }
+            (SystemSetting::Brightness, SettingValue::Level(level)) => {
+                Self::brightness_cli()?;
+                let fraction = format!("{:.2}", f64::from(level) / 100.0);
+                run_command("brightness", &[&fraction]).map(drop)
+            }
+            (SystemSetting::DarkMode, SettingValue::Switch(on)) => Self::osascript(&format!(
+                "tell application \"System Events\" to tell appearance preferences \
+                 to set dark mode to {on}"
+            ))
+            .map(drop),
+            (SystemSetting::DoNotDisturb, SettingValue::Switch(on)) => {
+                let shortcut = if on {
+                    FOCUS_ON_SHORTCUT
+                } else {
+                    FOCUS_OFF_SHORTCUT
+                };
+                run_command("shortcuts", &["run", shortcut]).map_err(|e| {
+                    format!("{e} (create a shortcut named \"{shortcut}\" with a Set Focus action)")
+                })?;
+                Ok(())
+            }
+            (SystemSetting::Wifi, SettingValue::Switch(on)) => {
+                let device = Self::wifi_device()?;
+                let state = if on { "on" } else { "off" };
+                run_command("networksetup", &["-setairportpower", &device, state]).map(drop)
+            }
+            (setting, value) => Err(format!("{} cannot be set to {value:?}", setting.as_str())),
+        }
+    }
+}
+
+/// Device of the `Wi-Fi` (or older `AirPort`) port in
+/// `networksetup -listallhardwareports` output.
+fn parse_wifi_device(output: &str) -> Option<String> {
+    let mut lines = output.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        let port = line.strip_prefix("Hardware Port:").map(str::trim);
+        if matches!(port, Some("Wi-Fi" | "AirPort")) {
+            return lines
+                .next()?
+                .strip_prefix("Device:")
+                .map(|d| d.trim().to_owned());
+        }
+    }
+    None
+}
+
+/// `Wi-Fi Power (en0): On` → `true`.
+fn parse_airport_power(output: &str) -> Option<bool> {
+    match output.trim().rsplit(':').next()?.trim() {
+        "On" => Some(true),
+        "Off" => Some(false),
+        _ => None,
+    }
+}
+
+/// First `display N: brightness 0.687500` line of `brightness -l`, as a
+/// percentage.
+fn parse_brightness_list(output: &str) -> Option<u8> {
+    output.lines().find_map(|line| {
+        let value = line.split("brightness").nth(1)?.trim();
+        let fraction = value.parse::<f64>().ok()?;
+        Some((fraction.clamp(0.0, 1.0) * 100.0).round() as u8)
+    })
+}
+
+/// Whether the Focus assertion database holds an active assertion.
+fn parse_focus_assertions(json: &str) -> Result<bool, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("unexpected Focus state: {e}"))?;
+    Ok(value
+        .pointer("/data/0/storeAssertionRecords")
+        .and_then(|records| records.as_array())
+        .is_some_and(|records| !records.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn wifi_device_is_found_among_hardware_ports() {
+        let ports = "\nHardware Port: Ethernet\nDevice: en1\nEthernet Address: aa\n\n\
+                     Hardware Port: Wi-Fi\nDevice: en0\nEthernet Address: bb\n";
+        assert_eq!(parse_wifi_device(ports).as_deref(), Some("en0"));
+        assert_eq!(
+            parse_wifi_device("Hardware Port: Ethernet\nDevice: en1\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn airport_power_and_brightness_are_parsed() {
+        assert_eq!(parse_airport_power("Wi-Fi Power (en0): On\n"), Some(true));
+        assert_eq!(parse_airport_power("Wi-Fi Power (en0): Off"), Some(false));
+        assert_eq!(parse_airport_power("error"), None);
+
+        let list = "display 0: main, active, awake, online, built-in, ID 0x4280a80\n\
+                    display 0: brightness 0.687500\n";
+        assert_eq!(parse_brightness_list(list), Some(69));
+        assert_eq!(parse_brightness_list("display 0: main"), None);
+    }
+
+    #[test]
+    fn focus_is_active_while_an_assertion_is_stored() {
+        let active = r#"{"data":[{"storeAssertionRecords":[{"assertionDetails":{}}]}]}"#;
+        let idle = r#"{"data":[{"storeAssertionRecords":[]}]}"#;
+        assert!(parse_focus_assertions(active).unwrap());
+        assert!(!parse_focus_assertions(idle).unwrap());
+        assert!(!parse_focus_assertions(r#"{"data":[{}]}"#).unwrap());
+        assert!(parse_focus_assertions("not json").is_err());
+    }
+}
@@ -0,0 +1,615 @@
+//! System settings tool — volume, brightness, dark mode, do-not-disturb, Wi-Fi.
+//!
+//! [`SystemControlTool`] exposes a fixed set of safe toggles as structured
+//! actions, so "turn the volume down" needs neither the `desktop` tool nor a
+//! shell. Each setting is read and changed through a platform backend:
+//!
+//! - **macOS**: [`MacSystemSettings`](macos::MacSystemSettings) via
+//!   `osascript`, `networksetup`, `shortcuts` and the `brightness` CLI
+//! - **Linux**: [`LinuxSystemSettings`](linux::LinuxSystemSettings) via
+//!   `pactl`, `brightnessctl`, `gsettings` and `nmcli`
+//!
+//! There is no raw passthrough: the model picks a [`SystemSetting`] and an
+//! action, and the backend builds the command itself.
+//!
+//! Requires [`PermissionKind::SystemSettings`] and `ToolMode::Full`.
+
+// Output parsing is tested on every platform.
+#[cfg(any(target_os = "linux", test))]
+pub mod linux;
+#[cfg(any(target_os = "macos", test))]
+pub mod macos;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::permissions::PermissionKind;
+
+use super::apple::AppleEcosystemTool;
+use super::types::{Tool, ToolResult};
+
+/// Timeout for one settings command.
+const COMMAND_TIMEOUT_SECS: u64 = 10;
+
+/// Percentage points moved by `up` / `down` when no `step` is given.
+const DEFAULT_STEP: u8 = 10;
+
+// ── Common types ────────────────────────────────────────────────
+
+/// A system setting the tool can read or change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemSetting {
+    /// Output volume, 0–100.
+    Volume,
+    /// Built-in display brightness, 0–100.
+    Brightness,
+    /// Dark appearance on or off.
+    DarkMode,
+    /// Do-not-disturb / Focus on or off.
+    DoNotDisturb,
+    /// Wi-Fi radio on or off.
+    Wifi,
+}
+
+impl SystemSetting {
+    /// Every setting, in schema order.
+    pub const ALL: [SystemSetting; 5] = [
+        SystemSetting::Volume,
+        SystemSetting::Brightness,
+        SystemSetting::DarkMode,
+        SystemSetting::DoNotDisturb,
+        SystemSetting::Wifi,
+    ];
+
+    /// Parse the schema name of a setting.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == name)
+    }
+
+    /// Schema name (`"dark_mode"`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SystemSetting::Volume => "volume",
+            SystemSetting::Brightness => "brightness",
+            SystemSetting::DarkMode => "dark_mode",
+            SystemSetting::DoNotDisturb => "do_not_disturb",
+            SystemSetting::Wifi => "wifi",
+        }
+    }
+
+    /// `true` for percentage settings, `false` for on/off switches.
+    pub fn is_level(self) -> bool {
+        matches!(self, SystemSetting::Volume | SystemSetting::Brightness)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SystemSetting::Volume => "Volume",
+            SystemSetting::Brightness => "Brightness",
+            SystemSetting::DarkMode => "Dark mode",
+            SystemSetting::DoNotDisturb => "Do not disturb",
+            SystemSetting::Wifi => "Wi-Fi",
+        }
+    }
+}
+
+/// Value of a setting: a percentage for levels, a state for switches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingValue {
+    Level(u8),
+    Switch(bool),
+}
+
+impl SettingValue {
+    fn describe(self) -> String {
+        match self {
+            SettingValue::Level(level) => format!("{level}%"),
+            SettingValue::Switch(true) => "on".to_owned(),
+            SettingValue::Switch(false) => "off".to_owned(),
+        }
+    }
+}
+
+/// Platform backend trait for system settings.
+///
+/// Implementations only ever receive the values [`SystemControlTool`] has
+/// validated: levels in 0–100, switches for switch settings.
+pub trait SystemSettingsBackend: Send + Sync {
+    /// Human-readable name of the backend (e.g. "macos").
+    fn name(&self) -> &str;
+
+    /// Returns `true` if the backend can run on this machine.
+    fn is_available(&self) -> bool;
+
+    /// Read the current value of `setting`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error string on failure.
+    fn get(&self, setting: SystemSetting) -> Result<SettingValue, String>;
+
+    /// Change `setting` to `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error string on failure.
+    fn set(&self, setting: SystemSetting, value: SettingValue) -> Result<(), String>;
+}
+
+// ── Backend auto-detection ──────────────────────────────────────
+
+/// Detect and return the settings backend for the current OS.
+///
+/// Returns `None` if this platform has no backend.
+pub fn detect_backend() -> Option<Box<dyn SystemSettingsBackend>> {
+    #[cfg(target_os = "macos")]
+    {
+        let mac = macos::MacSystemSettings::new();
+        if mac.is_available() {
+            return Some(Box::new(mac));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let linux = linux::LinuxSystemSettings::new();
+        if linux.is_available() {
+            return Some(Box::new(linux));
+        }
+    }
+
+    None
+}
+
+/// Run `program` with a timeout, returning stdout.
+#[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(dead_code))]
+fn run_command(program: &str, args: &[&str]) -> Result<String, String> {
+    let timeout = std::time::Duration::from_secs(COMMAND_TIMEOUT_SECS);
+    let start = std::time::Instant::now();
+
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to spawn {program}: {e}"))?;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                let mut stdout = String::new();
+                if let Some(mut pipe) = child.stdout.take() {
+                    std::io::Read::read_to_string(&mut pipe, &mut stdout).unwrap_or(0);
+                }
+                let mut stderr = String::new();
+                if let Some(mut pipe) = child.stderr.take() {
+                    std::io::Read::read_to_string(&mut pipe, &mut stderr).unwrap_or(0);
+                }
+                if !status.success() {
+                    let code = status.code().unwrap_or(-1);
+                    let output = if stderr.is_empty() { stdout } else { stderr };
+                    return Err(format!(
+                        "{program} exited with code {code}: {}",
+                        output.trim()
+                    ));
+                }
+                return Ok(stdout);
+            }
+            Ok(None) => {
+                if start.elapsed() > timeout {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!("{program} timed out after {COMMAND_TIMEOUT_SECS}s"));
+                }
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            Err(e) => return Err(format!("failed to check {program} status: {e}")),
+        }
+    }
+}
+
+/// Check if a binary is in PATH.
+#[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(dead_code))]
+fn binary_exists(name: &str) -> bool {
+    std::process::Command::new("which")
+        .arg(name)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// First `NN%` in `output`, the shape `pactl` and `brightnessctl` print.
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn first_percent(output: &str) -> Option<u8> {
+    output
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '/')
+        .find_map(|word| word.strip_suffix('%')?.parse::<u8>().ok())
+        .map(|level| level.min(100))
+}
+
+// ── Action parsing ──────────────────────────────────────────────
+
+/// What the tool was asked to do with a setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    /// Report the current value.
+    Get,
+    /// Set a level to an exact percentage.
+    Set(u8),
+    /// Move a level up (positive) or down (negative) by percentage points.
+    Step(i16),
+    /// Turn a switch on or off.
+    Switch(bool),
+    /// Flip a switch.
+    Toggle,
+}
+
+/// Parse and validate the `setting` / `action` arguments.
+fn parse_request(args: &serde_json::Value) -> Result<(SystemSetting, Change), FaeLlmError> {
+    let name = args
+        .get("setting")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            FaeLlmError::ToolValidationError("missing required argument: setting (string)".into())
+        })?;
+    let setting = SystemSetting::parse(name).ok_or_else(|| {
+        FaeLlmError::ToolValidationError(format!(
+            "unknown setting: '{name}'. Valid settings: volume, brightness, dark_mode, \
+             do_not_disturb, wifi"
+        ))
+    })?;
+    let action = args.get("action").and_then(|v| v.as_str()).unwrap_or("get");
+    let step = || -> Result<i16, FaeLlmError> {
+        match args.get("step") {
+            None => Ok(i16::from(DEFAULT_STEP)),
+            Some(v) => v
+                .as_u64()
+                .filter(|n| (1..=100).contains(n))
+                .map(|n| n as i16)
+                .ok_or_else(|| {
+                    FaeLlmError::ToolValidationError("step must be an integer 1-100".into())
+                }),
+        }
+    };
+    let change = match action {
+        "get" => Change::Get,
+        "set" => {
+            let level = args
+                .get("level")
+                .and_then(|v| v.as_u64())
+                .filter(|n| *n <= 100)
+                .ok_or_else(|| {
+                    FaeLlmError::ToolValidationError(
+                        "set requires 'level' as an integer 0-100".into(),
+                    )
+                })?;
+            Change::Set(level as u8)
+        }
+        "up" => Change::Step(step()?),
+        "down" => Change::Step(-step()?),
+        "on" => Change::Switch(true),
+        "off" => Change::Switch(false),
+        "toggle" => Change::Toggle,
+        other => {
+            return Err(FaeLlmError::ToolValidationError(format!(
+                "unknown action: '{other}'. Valid actions: get, set, up, down, on, off, toggle"
+            )));
+        }
+    };
+    let fits = match change {
+        Change::Get => true,
+        Change::Set(_) | Change::Step(_) => setting.is_level(),
+        Change::Switch(_) | Change::Toggle => !setting.is_level(),
+    };
+    if !fits {
+        let valid = if setting.is_level() {
+            "get, set, up, down"
+        } else {
+            "get, on, off, toggle"
+        };
+        return Err(FaeLlmError::ToolValidationError(format!(
+            "{} does not support '{action}'. Valid actions: {valid}",
+            setting.as_str()
+        )));
+    }
+    Ok((setting, change))
+}
+
+// ── SystemControlTool ───────────────────────────────────────────
+
+/// Reads and changes a fixed set of system settings through a platform
+/// backend.
+///
+/// Only available in `ToolMode::Full`. If no backend is detected at
+/// construction time, [`SystemControlTool::try_new`] returns `None` so the
+/// tool is silently excluded from the registry.
+pub struct SystemControlTool {
+    backend: Box<dyn SystemSettingsBackend>,
+}
+
+impl SystemControlTool {
+    /// Attempt to create a `SystemControlTool` with the auto-detected backend.
+    ///
+    /// Returns `None` if no backend is available on this platform.
+    pub fn try_new() -> Option<Self> {
+        detect_backend().map(|backend| Self { backend })
+    }
+
+    /// Create a `SystemControlTool` with an explicit backend (for testing).
+    #[cfg(test)]
+    pub fn with_backend(backend: Box<dyn SystemSettingsBackend>) -> Self {
+        Self { backend }
+    }
+
+    fn apply(&self, setting: SystemSetting, change: Change) -> Result<String, String> {
+        let label = setting.label();
+        let current = || self.backend.get(setting);
+        match change {
+            Change::Get => Ok(format!("{label} is {}.", current()?.describe())),
+            Change::Set(level) => {
+                self.backend.set(setting, SettingValue::Level(level))?;
+                Ok(format!("{label} set to {level}%."))
+            }
+            Change::Step(delta) => {
+                let SettingValue::Level(from) = current()? else {
+                    return Err(format!("{label} did not report a level"));
+                };
+                let to = (i16::from(from) + delta).clamp(0, 100) as u8;
+                if to == from {
+                    return Ok(format!("{label} is already at {from}%."));
+                }
+                self.backend.set(setting, SettingValue::Level(to))?;
+                let verb = if to > from { "raised" } else { "lowered" };
+                Ok(format!("{label} {verb} from {from}% to {to}%."))
+            }
+            Change::Switch(on) => {
+                self.backend.set(setting, SettingValue::Switch(on))?;
+                Ok(format!(
+                    "{label} turned {}.",
+                    SettingValue::Switch(on).describe()
+                ))
+            }
+            Change::Toggle => {
+                let SettingValue::Switch(on) = current()? else {
+                    return Err(format!("{label} did not report an on/off state"));
+                };
+                self.backend.set(setting, SettingValue::Switch(!on))?;
+                Ok(format!(
+                    "{label} turned {}.",
+                    SettingValue::Switch(!on).describe()
+                ))
+            }
+        }
+    }
+}
+
+impl Tool for SystemControlTool {
+    fn name(&self) -> &str {
+        "system_control"
+    }
+
+    fn description(&self) -> &str {
+        "Read or change system settings: output volume and display brightness \
+         (percent; get, set, up, down) and dark mode, do-not-disturb and Wi-Fi \
+         (get, on, off, toggle)"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "setting": {
+                    "type": "string",
+                    "description": "Setting to read or change",
+                    "enum": ["volume", "brightness", "dark_mode", "do_not_disturb", "wifi"]
+                },
+                "action": {
+                    "type": "string",
+                    "description": "get (default) reports the current value. volume and brightness take set, up and down; dark_mode, do_not_disturb and wifi take on, off and toggle",
+                    "enum": ["get", "set", "up", "down", "on", "off", "toggle"]
+                },
+                "level": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 100,
+                    "description": "Target percentage (for set)"
+                },
+                "step": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": 100,
+                    "description": "Percentage points to move (for up/down, default 10)"
+                }
+            },
+            "required": ["setting"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let (setting, change) = parse_request(&args)?;
+        match self.apply(setting, change) {
+            Ok(output) => Ok(ToolResult::success(output)),
+            Err(e) => Ok(ToolResult::failure(format!(
+                "{} ({}): {e}",
+                setting.as_str(),
+                self.backend.name()
+            ))),
+        }
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+impl AppleEcosystemTool for SystemControlTool {
+    fn required_permission(&self) -> PermissionKind {
+        PermissionKind::SystemSettings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// In-memory backend recording every `set`.
+    struct MockSettings {
+        values: Mutex<HashMap<&'static str, SettingValue>>,
+    }
+
+    impl MockSettings {
+        fn new() -> Self {
+            let values = HashMap::from([
+                ("volume", SettingValue::Level(40)),
+                ("brightness", SettingValue::Level(95)),
+                ("dark_mode", SettingValue::Switch(false)),
+                ("wifi", SettingValue::Switch(true)),
+            ]);
+            Self {
+                values: Mutex::new(values),
+            }
+        }
+    }
+
+    impl SystemSettingsBackend for MockSettings {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn get(&self, setting: SystemSetting) -> Result<SettingValue, String> {
+            self.values
+                .lock()
+                .unwrap()
+                .get(setting.as_str())
+                .copied()
+                .ok_or_else(|| "not supported here".to_owned())
+        }
+
+        fn set(&self, setting: SystemSetting, value: SettingValue) -> Result<(), String> {
+            self.values.lock().unwrap().insert(setting.as_str(), value);
+            Ok(())
+        }
+    }
+
+    fn tool() -> SystemControlTool {
+        SystemControlTool::with_backend(Box::new(MockSettings::new()))
+    }
+
+    fn run(tool: &SystemControlTool, args: serde_json::Value) -> ToolResult {
+        tool.execute(args).unwrap()
+    }
+
+    #[test]
+    fn levels_are_read_set_and_stepped_within_bounds() {
+        let tool = tool();
+        let got = run(&tool, serde_json::json!({ "setting": "volume" }));
+        assert_eq!(got.content, "Volume is 40%.");
+
+        let down = run(
+            &tool,
+            serde_json::json!({ "setting": "volume", "action": "down" }),
+        );
+        assert_eq!(down.content, "Volume lowered from 40% to 30%.");
+
+        let up = run(
+            &tool,
+            serde_json::json!({ "setting": "brightness", "action": "up", "step": 20 }),
+        );
+        assert_eq!(up.content, "Brightness raised from 95% to 100%.");
+
+        let set = run(
+            &tool,
+            serde_json::json!({ "setting": "volume", "action": "set", "level": 0 }),
+        );
+        assert_eq!(set.content, "Volume set to 0%.");
+        let again = run(
+            &tool,
+            serde_json::json!({ "setting": "volume", "action": "down" }),
+        );
+        assert_eq!(again.content, "Volume is already at 0%.");
+    }
+
+    #[test]
+    fn switches_turn_on_off_and_toggle() {
+        let tool = tool();
+        let on = run(
+            &tool,
+            serde_json::json!({ "setting": "dark_mode", "action": "on" }),
+        );
+        assert_eq!(on.content, "Dark mode turned on.");
+
+        let toggled = run(
+            &tool,
+            serde_json::json!({ "setting": "wifi", "action": "toggle" }),
+        );
+        assert_eq!(toggled.content, "Wi-Fi turned off.");
+        let got = run(&tool, serde_json::json!({ "setting": "wifi" }));
+        assert_eq!(got.content, "Wi-Fi is off.");
+    }
+
+    #[test]
+    fn mismatched_or_out_of_range_requests_are_rejected() {
+        let tool = tool();
+        for args in [
+            serde_json::json!({}),
+            serde_json::json!({ "setting": "bluetooth" }),
+            serde_json::json!({ "setting": "wifi", "action": "up" }),
+            serde_json::json!({ "setting": "volume", "action": "toggle" }),
+            serde_json::json!({ "setting": "volume", "action": "set", "level": 150 }),
+            serde_json::json!({ "setting": "volume", "action": "down", "step": 0 }),
+            serde_json::json!({ "setting": "volume", "action": "mute" }),
+        ] {
+            assert!(
+                matches!(
+                    tool.execute(args.clone()),
+                    Err(FaeLlmError::ToolValidationError(_))
+                ),
+                "{args}"
+            );
+        }
+    }
+
+    #[test]
+    fn backend_failure_returns_tool_failure() {
+        let tool = tool();
+        let result = run(
+            &tool,
+            serde_json::json!({ "setting": "do_not_disturb", "action": "toggle" }),
+        );
+        assert!(!result.success);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("do_not_disturb (mock): not supported here")
+        );
+    }
+
+    #[test]
+    fn needs_full_mode_and_the_system_settings_permission() {
+        let tool = tool();
+        assert!(tool.allowed_in_mode(ToolMode::Full));
+        assert!(!tool.allowed_in_mode(ToolMode::ReadOnly));
+        assert_eq!(tool.required_permission(), PermissionKind::SystemSettings);
+    }
+
+    #[test]
+    fn first_percent_finds_the_level() {
+        assert_eq!(
+            first_percent("Volume: front-left: 26214 /  40% / -23.88 dB"),
+            Some(40)
+        );
+        assert_eq!(
+            first_percent("intel_backlight,backlight,400,40%,1000"),
+            Some(40)
+        );
+        assert_eq!(first_percent("no level"), None);
+    }
+}
@@ -0,0 +1,593 @@
+//! System info tool — CPU load, memory, disk, top processes, battery, uptime.
+//!
+//! Answers "why is my laptop fan going crazy" from measurements instead of
+//! guesses. Everything is read with the same best-effort sources as
+//! [`system_profile`](crate::system_profile): `ps`, `sysctl` and `vm_stat` on
+//! macOS, `/proc` on Linux, `getloadavg` and `statvfs` on both, and the
+//! battery reader from [`power`](crate::platform::power). A source that is
+//! missing leaves its section out rather than failing the call.
+
+use serde::Serialize;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+
+use super::types::{Tool, ToolResult};
+
+/// Processes listed when `top_processes` is not given.
+const DEFAULT_TOP_PROCESSES: usize = 5;
+
+/// Most processes one call may list.
+const MAX_TOP_PROCESSES: usize = 20;
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+// ── Snapshot ────────────────────────────────────────────────────
+
+/// CPU load, relative to the number of cores.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CpuLoad {
+    /// Logical cores available to processes.
+    pub cores: usize,
+    /// 1, 5 and 15 minute load averages.
+    pub load_average: Option<[f64; 3]>,
+}
+
+/// Physical memory in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MemoryUsage {
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+}
+
+/// Space on the filesystem holding the home directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DiskUsage {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+/// Battery charge and power source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BatteryInfo {
+    pub percent: Option<u8>,
+    pub on_battery: bool,
+}
+
+/// One process, as reported by `ps`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_percent: f64,
+    pub memory_percent: f64,
+}
+
+/// Everything the tool reports, collected in one pass.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SystemSnapshot {
+    pub cpu: CpuLoad,
+    pub memory: Option<MemoryUsage>,
+    pub disk: Option<DiskUsage>,
+    pub battery: Option<BatteryInfo>,
+    pub uptime_secs: Option<u64>,
+    /// Busiest processes first.
+    pub top_processes: Vec<ProcessUsage>,
+}
+
+impl SystemSnapshot {
+    /// Read the current state of this machine, listing `top` processes.
+    pub fn collect(top: usize) -> Self {
+        let mut top_processes = read_processes().unwrap_or_default();
+        top_processes.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+        top_processes.truncate(top);
+        Self {
+            cpu: CpuLoad {
+                cores: std::thread::available_parallelism().map_or(1, |n| n.get()),
+                load_average: load_average(),
+            },
+            memory: read_memory(),
+            disk: dirs::home_dir().and_then(|home| disk_usage(&home)),
+            battery: crate::platform::power::read_battery().map(|b| BatteryInfo {
+                percent: b.percent,
+                on_battery: b.on_battery,
+            }),
+            uptime_secs: read_uptime(),
+            top_processes,
+        }
+    }
+
+    /// Plain-text summary, one section per line.
+    pub fn render(&self) -> String {
+        let mut lines = Vec::new();
+        let cores = self.cpu.cores;
+        lines.push(match self.cpu.load_average {
+            Some([one, five, fifteen]) => format!(
+                "CPU: load {one:.2} / {five:.2} / {fifteen:.2} (1/5/15 min) on {cores} cores"
+            ),
+            None => format!("CPU: {cores} cores"),
+        });
+        if let Some(memory) = self.memory {
+            lines.push(format!(
+                "Memory: {:.1} GB of {:.1} GB used ({}%)",
+                memory.used_bytes as f64 / GIB,
+                memory.total_bytes as f64 / GIB,
+                percent_of(memory.used_bytes, memory.total_bytes)
+            ));
+        }
+        if let Some(disk) = self.disk {
+            lines.push(format!(
+                "Disk: {:.1} GB free of {:.1} GB",
+                disk.available_bytes as f64 / GIB,
+                disk.total_bytes as f64 / GIB
+            ));
+        }
+        if let Some(battery) = self.battery {
+            let source = if battery.on_battery {
+                "on battery"
+            } else {
+                "plugged in"
+            };
+            lines.push(match battery.percent {
+                Some(percent) => format!("Battery: {percent}%, {source}"),
+                None => format!("Battery: {source}"),
+            });
+        }
+        if let Some(secs) = self.uptime_secs {
+            lines.push(format!("Uptime: {}", format_uptime(secs)));
+        }
+        if !self.top_processes.is_empty() {
+            lines.push("Top processes by CPU:".to_owned());
+            for p in &self.top_processes {
+                lines.push(format!(
+                    "- {} (pid {}): {:.1}% CPU, {:.1}% memory",
+                    p.name, p.pid, p.cpu_percent, p.memory_percent
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+fn percent_of(part: u64, whole: u64) -> u64 {
+    if whole == 0 {
+        0
+    } else {
+        (part as f64 / whole as f64 * 100.0).round() as u64
+    }
+}
+
+/// `3 d 4 h 12 min`, dropping leading zero units.
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    if days > 0 {
+        format!("{days} d {hours} h {minutes} min")
+    } else if hours > 0 {
+        format!("{hours} h {minutes} min")
+    } else {
+        format!("{minutes} min")
+    }
+}
+
+// ── Sources ─────────────────────────────────────────────────────
+
+fn run_cmd(program: &str, args: &[&str]) -> Option<String> {
+    let out = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    String::from_utf8(out.stdout).ok()
+}
+
+#[cfg(unix)]
+fn load_average() -> Option<[f64; 3]> {
+    let mut loads = [0.0f64; 3];
+    let n = unsafe { libc::getloadavg(loads.as_mut_ptr(), 3) };
+    (n == 3).then_some(loads)
+}
+
+#[cfg(not(unix))]
+fn load_average() -> Option<[f64; 3]> {
+    None
+}
+
+#[cfg(unix)]
+fn disk_usage(path: &std::path::Path) -> Option<DiskUsage> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    // The field widths differ between macOS and Linux (see
+    // `startup::available_disk_space`).
+    let blocks: u64 = stat.f_blocks as _;
+    let bavail: u64 = stat.f_bavail as _;
+    let frsize: u64 = stat.f_frsize as _;
+    Some(DiskUsage {
+        total_bytes: blocks.wrapping_mul(frsize),
+        available_bytes: bavail.wrapping_mul(frsize),
+    })
+}
+
+#[cfg(not(unix))]
+fn disk_usage(_path: &std::path::Path) -> Option<DiskUsage> {
+    None
+}
+
+/// Every process with its CPU and memory share, unsorted.
+///
+/// `ps` reports a decaying recent average on macOS and the lifetime average
+/// on Linux.
+fn read_processes() -> Option<Vec<ProcessUsage>> {
+    if !cfg!(unix) {
+        return None;
+    }
+    run_cmd("ps", &["-A", "-o", "pid=,pcpu=,pmem=,comm="]).map(|out| parse_ps(&out))
+}
+
+/// Parse `ps -o pid=,pcpu=,pmem=,comm=` rows; the command may contain spaces
+/// and, on macOS, is a full path.
+fn parse_ps(output: &str) -> Vec<ProcessUsage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let cpu_percent = fields.next()?.parse().ok()?;
+            let memory_percent = fields.next()?.parse().ok()?;
+            let command = fields.collect::<Vec<_>>().join(" ");
+            let name = command.rsplit('/').next().unwrap_or_default().to_owned();
+            (!name.is_empty()).then_some(ProcessUsage {
+                pid,
+                name,
+                cpu_percent,
+                memory_percent,
+            })
+        })
+        .collect()
+}
+
+fn read_memory() -> Option<MemoryUsage> {
+    if cfg!(target_os = "macos") {
+        let total = run_cmd("sysctl", &["-n", "hw.memsize"])?
+            .trim()
+            .parse()
+            .ok()?;
+        return parse_vm_stat(&run_cmd("vm_stat", &[])?, total);
+    }
+    if cfg!(target_os = "linux") {
+        return parse_meminfo(&std::fs::read_to_string("/proc/meminfo").ok()?);
+    }
+    None
+}
+
+/// `MemTotal` minus `MemAvailable` from `/proc/meminfo`.
+fn parse_meminfo(content: &str) -> Option<MemoryUsage> {
+    let field = |name: &str| {
+        content.lines().find_map(|line| {
+            let kb: u64 = line
+                .strip_prefix(name)?
+                .split_whitespace()
+                .next()?
+                .parse()
+                .ok()?;
+            Some(kb.saturating_mul(1024))
+        })
+    };
+    let total_bytes = field("MemTotal:")?;
+    let available = field("MemAvailable:")?;
+    Some(MemoryUsage {
+        total_bytes,
+        used_bytes: total_bytes.saturating_sub(available),
+    })
+}
+
+/// Used memory from `vm_stat`: everything except free, inactive and
+/// speculative pages.
+fn parse_vm_stat(output: &str, total_bytes: u64) -> Option<MemoryUsage> {
+    let page_size: u64 = output
+        .lines()
+        .next()?
+        .split("page size of ")
+        .nth(1)?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+    let pages = |name: &str| -> u64 {
+        output
+            .lines()
+            .find_map(|line| {
+                line.strip_prefix(name)?
+                    .trim()
+                    .trim_end_matches('.')
+                    .parse()
+                    .ok()
+            })
+            .unwrap_or(0)
+    };
+    let reclaimable = pages("Pages free:") + pages("Pages inactive:") + pages("Pages speculative:");
+    Some(MemoryUsage {
+        total_bytes,
+        used_bytes: total_bytes.saturating_sub(reclaimable.saturating_mul(page_size)),
+    })
+}
+
+fn read_uptime() -> Option<u64> {
+    if cfg!(target_os = "macos") {
+        let boot = parse_boottime(&run_cmd("sysctl", &["-n", "kern.boottime"])?)?;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        return Some(now.saturating_sub(boot));
+    }
+    if cfg!(target_os = "linux") {
+        let content = std::fs::read_to_string("/proc/uptime").ok()?;
+        let secs: f64 = content.split_whitespace().next()?.parse().ok()?;
+        return Some(secs as u64);
+    }
+    None
+}
+
+/// Boot time in Unix seconds from `{ sec = 1700000000, usec = 0 } Tue ...`.
+fn parse_boottime(output: &str) -> Option<u64> {
+    output
+        .split("sec = ")
+        .nth(1)?
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+// ── SystemInfoTool ──────────────────────────────────────────────
+
+/// Reports CPU load, memory, disk, top processes, battery and uptime.
+///
+/// This is a **read-only** tool — allowed in all tool modes.
+///
+/// # Arguments (JSON)
+///
+/// - `top_processes` (integer, optional) — processes to list, busiest by CPU
+///   first (default 5, at most 20)
+pub struct SystemInfoTool;
+
+impl SystemInfoTool {
+    /// Create a new `SystemInfoTool`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SystemInfoTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for SystemInfoTool {
+    fn name(&self) -> &str {
+        "system_info"
+    }
+
+    fn description(&self) -> &str {
+        "Report this computer's CPU load, memory and disk usage, busiest processes, \
+         battery and uptime. Use it before explaining slowness, fan noise or battery drain."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "top_processes": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": MAX_TOP_PROCESSES,
+                    "description": "Processes to list, busiest by CPU first (default 5)"
+                }
+            }
+        })
+    }
+
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "cpu": {
+                    "type": "object",
+                    "properties": {
+                        "cores": { "type": "integer" },
+                        "load_average": {
+                            "type": ["array", "null"],
+                            "items": { "type": "number" }
+                        }
+                    },
+                    "required": ["cores"]
+                },
+                "memory": {
+                    "type": ["object", "null"],
+                    "properties": {
+                        "total_bytes": { "type": "integer" },
+                        "used_bytes": { "type": "integer" }
+                    }
+                },
+                "disk": {
+                    "type": ["object", "null"],
+                    "properties": {
+                        "total_bytes": { "type": "integer" },
+                        "available_bytes": { "type": "integer" }
+                    }
+                },
+                "battery": {
+                    "type": ["object", "null"],
+                    "properties": {
+                        "percent": { "type": ["integer", "null"] },
+                        "on_battery": { "type": "boolean" }
+                    }
+                },
+                "uptime_secs": { "type": ["integer", "null"] },
+                "top_processes": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "pid": { "type": "integer" },
+                            "name": { "type": "string" },
+                            "cpu_percent": { "type": "number" },
+                            "memory_percent": { "type": "number" }
+                        },
+                        "required": ["pid", "name", "cpu_percent", "memory_percent"]
+                    }
+                }
+            },
+            "required": ["cpu", "top_processes"]
+        }))
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let top = match args.get("top_processes") {
+            None | Some(serde_json::Value::Null) => DEFAULT_TOP_PROCESSES,
+            Some(v) => v
+                .as_u64()
+                .map(|n| n as usize)
+                .filter(|n| *n <= MAX_TOP_PROCESSES)
+                .ok_or_else(|| {
+                    FaeLlmError::ToolValidationError(format!(
+                        "top_processes must be an integer 0-{MAX_TOP_PROCESSES}"
+                    ))
+                })?,
+        };
+        let snapshot = SystemSnapshot::collect(top);
+        let structured = serde_json::to_value(&snapshot)
+            .map_err(|e| FaeLlmError::ToolExecutionError(format!("system info: {e}")))?;
+        Ok(ToolResult::success(snapshot.render()).with_structured(structured))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true // read-only
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn ps_rows_keep_spaced_names_and_strip_paths() {
+        let out = "    1   0.0  0.1 /sbin/launchd\n\
+                   4242  85.3  3.2 /Applications/Google Chrome.app/Contents/MacOS/Google Chrome Helper\n\
+                   junk line\n\
+                   77  12.0  0.5 cargo\n";
+        let rows = parse_ps(out);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].pid, 4242);
+        assert_eq!(rows[1].name, "Google Chrome Helper");
+        assert_eq!(rows[1].cpu_percent, 85.3);
+        assert_eq!(rows[2].name, "cargo");
+    }
+
+    #[test]
+    fn memory_is_parsed_from_meminfo_and_vm_stat() {
+        let meminfo =
+            "MemTotal:       16000000 kB\nMemFree:  1000 kB\nMemAvailable:    4000000 kB\n";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            Some(MemoryUsage {
+                total_bytes: 16_000_000 * 1024,
+                used_bytes: 12_000_000 * 1024,
+            })
+        );
+
+        let vm_stat = "Mach Virtual Memory Statistics: (page size of 16384 bytes)\n\
+                       Pages free:                               10000.\n\
+                       Pages active:                            400000.\n\
+                       Pages inactive:                           20000.\n\
+                       Pages speculative:                         5000.\n";
+        let total = 16 * 1024 * 1024 * 1024;
+        assert_eq!(
+            parse_vm_stat(vm_stat, total).map(|m| m.used_bytes),
+            Some(total - 35_000 * 16_384)
+        );
+        assert_eq!(parse_vm_stat("no header", total), None);
+    }
+
+    #[test]
+    fn boottime_and_uptime_are_formatted() {
+        assert_eq!(
+            parse_boottime("{ sec = 1700000000, usec = 12 } Tue Nov 14 22:13:20 2023"),
+            Some(1_700_000_000)
+        );
+        assert_eq!(format_uptime(42 * 60), "42 min");
+        assert_eq!(
+            format_uptime(3 * 86_400 + 4 * 3_600 + 12 * 60),
+            "3 d 4 h 12 min"
+        );
+    }
+
+    #[test]
+    fn snapshot_renders_each_known_section() {
+        let snapshot = SystemSnapshot {
+            cpu: CpuLoad {
+                cores: 8,
+                load_average: Some([6.5, 4.25, 2.0]),
+            },
+            memory: Some(MemoryUsage {
+                total_bytes: 16 * GIB as u64,
+                used_bytes: 12 * GIB as u64,
+            }),
+            disk: None,
+            battery: Some(BatteryInfo {
+                percent: Some(41),
+                on_battery: true,
+            }),
+            uptime_secs: Some(7_200),
+            top_processes: vec![ProcessUsage {
+                pid: 4242,
+                name: "Google Chrome Helper".into(),
+                cpu_percent: 85.3,
+                memory_percent: 3.2,
+            }],
+        };
+        assert_eq!(
+            snapshot.render(),
+            "CPU: load 6.50 / 4.25 / 2.00 (1/5/15 min) on 8 cores\n\
+             Memory: 12.0 GB of 16.0 GB used (75%)\n\
+             Battery: 41%, on battery\n\
+             Uptime: 2 h 0 min\n\
+             Top processes by CPU:\n\
+             - Google Chrome Helper (pid 4242): 85.3% CPU, 3.2% memory"
+        );
+    }
+
+    #[test]
+    fn execute_reports_a_structured_snapshot() {
+        let tool = SystemInfoTool::new();
+        let result = tool
+            .execute(serde_json::json!({ "top_processes": 3 }))
+            .unwrap();
+        assert!(result.success);
+        assert!(result.content.starts_with("CPU:"));
+        let structured = result.structured.unwrap();
+        assert!(structured["top_processes"].as_array().unwrap().len() <= 3);
+        let schema = tool.output_schema().unwrap();
+        assert!(
+            crate::fae_llm::agent::validate_tool_output("system_info", &structured, &schema)
+                .is_ok()
+        );
+
+        assert!(matches!(
+            tool.execute(serde_json::json!({ "top_processes": 500 })),
+            Err(FaeLlmError::ToolValidationError(_))
+        ));
+        assert!(tool.allowed_in_mode(ToolMode::ReadOnly));
+    }
+}
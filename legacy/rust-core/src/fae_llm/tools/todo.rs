@@ -0,0 +1,323 @@
+//! Todo list tools.
+//!
+//! `list_todos` reads the todo list that the intelligence layer fills from
+//! conversations; `update_todo` adds, completes and removes items. Completing
+//! an item that was mirrored to Apple Reminders completes the reminder too.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::tools::apple::ReminderStore;
+use crate::intelligence::todos::{TodoSource, TodoStore};
+
+use super::types::{Tool, ToolResult};
+
+fn today() -> chrono::NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+/// Tool that lists open todos, soonest due first.
+///
+/// This is a **read-only** tool — allowed in all tool modes.
+///
+/// # Arguments (JSON)
+///
+/// - `include_completed` (bool, optional) — also list finished items
+pub struct ListTodosTool {
+    path: PathBuf,
+}
+
+impl ListTodosTool {
+    /// Create a tool over the default todo list.
+    pub fn new() -> Self {
+        Self::with_path(crate::fae_dirs::todos_file())
+    }
+
+    /// Create a tool over the todo list at `path`.
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Default for ListTodosTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for ListTodosTool {
+    fn name(&self) -> &str {
+        "list_todos"
+    }
+
+    fn description(&self) -> &str {
+        "List the user's todo list (things they said they need to do), soonest due first."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "include_completed": {
+                    "type": "boolean",
+                    "description": "Also list completed items (default: false)"
+                }
+            }
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let include_completed = args
+            .get("include_completed")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let store = TodoStore::load(&self.path);
+        let today = today();
+
+        let mut lines: Vec<String> = store
+            .open_by_due()
+            .iter()
+            .map(|i| i.describe(today))
+            .collect();
+        if include_completed {
+            lines.extend(
+                store
+                    .items
+                    .iter()
+                    .filter(|i| !i.is_open())
+                    .map(|i| i.describe(today)),
+            );
+        }
+        if lines.is_empty() {
+            return Ok(ToolResult::success("The todo list is empty.".to_owned()));
+        }
+        Ok(ToolResult::success(lines.join("\n")))
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true // read-only
+    }
+}
+
+/// Tool that adds, completes or removes todos.
+///
+/// # Arguments (JSON)
+///
+/// - `action` (string, required) — `"add"`, `"complete"` or `"remove"`
+/// - `text` (string) — item text for `add`
+/// - `due` (string, optional) — due date `YYYY-MM-DD` for `add`
+/// - `item` (string) — id (`3`) or unique phrase for `complete` / `remove`
+pub struct UpdateTodoTool {
+    path: PathBuf,
+    reminders: Option<Arc<dyn ReminderStore>>,
+}
+
+impl UpdateTodoTool {
+    /// Create a tool over the default todo list.
+    pub fn new() -> Self {
+        Self::with_path(crate::fae_dirs::todos_file())
+    }
+
+    /// Create a tool over the todo list at `path`.
+    pub fn with_path(path: PathBuf) -> Self {
+        Self {
+            path,
+            reminders: None,
+        }
+    }
+
+    /// Complete mirrored Apple Reminders items through `store`.
+    pub fn with_reminder_store(mut self, store: Arc<dyn ReminderStore>) -> Self {
+        self.reminders = Some(store);
+        self
+    }
+}
+
+impl Default for UpdateTodoTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn required_str<'a>(args: &'a serde_json::Value, key: &str) -> Result<&'a str, FaeLlmError> {
+    args.get(key)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| {
+            FaeLlmError::ToolValidationError(format!("missing required argument: {key}"))
+        })
+}
+
+impl Tool for UpdateTodoTool {
+    fn name(&self) -> &str {
+        "update_todo"
+    }
+
+    fn description(&self) -> &str {
+        "Add an item to the user's todo list, mark one done, or remove one."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["add", "complete", "remove"],
+                    "description": "What to do"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Item text (for add)"
+                },
+                "due": {
+                    "type": "string",
+                    "description": "Due date as YYYY-MM-DD (optional, for add)"
+                },
+                "item": {
+                    "type": "string",
+                    "description": "Item id (e.g. \"3\") or a phrase from its text (for complete/remove)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let action = required_str(&args, "action")?;
+        let mut store = TodoStore::load(&self.path);
+
+        let message = match action {
+            "add" => {
+                let text = required_str(&args, "text")?;
+                let due = match args.get("due").and_then(|v| v.as_str()) {
+                    Some(raw) => Some(
+                        chrono::NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d").map_err(
+                            |_| {
+                                FaeLlmError::ToolValidationError(format!(
+                                    "due must be YYYY-MM-DD, got \"{raw}\""
+                                ))
+                            },
+                        )?,
+                    ),
+                    None => None,
+                };
+                match store.add(text, due, TodoSource::Manual) {
+                    Some(id) => format!("Added #{id} {text}."),
+                    None => {
+                        return Ok(ToolResult::success(format!(
+                            "\"{text}\" is already on the list."
+                        )));
+                    }
+                }
+            }
+            "complete" => {
+                let id = match store.find_open(required_str(&args, "item")?) {
+                    Ok(id) => id,
+                    Err(message) => return Ok(ToolResult::failure(message)),
+                };
+                let Some(item) = store.complete(id) else {
+                    return Ok(ToolResult::failure(format!("no open todo #{id}")));
+                };
+                let text = item.text.clone();
+                if let (Some(reminders), Some(reminder_id)) = (&self.reminders, &item.reminder_id)
+                    && let Err(e) = reminders.set_completed(reminder_id, true)
+                {
+                    tracing::warn!("failed to complete mirrored reminder for todo #{id}: {e}");
+                }
+                format!("Marked #{id} {text} as done.")
+            }
+            "remove" => {
+                let id = match store.find_open(required_str(&args, "item")?) {
+                    Ok(id) => id,
+                    Err(message) => return Ok(ToolResult::failure(message)),
+                };
+                match store.remove(id) {
+                    Some(item) => format!("Removed #{id} {}.", item.text),
+                    None => return Ok(ToolResult::failure(format!("no todo #{id}"))),
+                }
+            }
+            other => {
+                return Err(FaeLlmError::ToolValidationError(format!(
+                    "unknown action \"{other}\"; expected add, complete or remove"
+                )));
+            }
+        };
+
+        if let Err(e) = store.save(&self.path) {
+            return Ok(ToolResult::failure(format!(
+                "failed to save todo list: {e}"
+            )));
+        }
+        Ok(ToolResult::success(message))
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn add_complete_and_list_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("todos.json");
+        let update = UpdateTodoTool::with_path(path.clone());
+        let list = ListTodosTool::with_path(path);
+
+        let added = update
+            .execute(
+                serde_json::json!({"action": "add", "text": "Book flights", "due": "2030-01-02"}),
+            )
+            .unwrap();
+        assert_eq!(added.content, "Added #1 Book flights.");
+        update
+            .execute(serde_json::json!({"action": "add", "text": "Buy stamps"}))
+            .unwrap();
+
+        let listed = list.execute(serde_json::json!({})).unwrap();
+        assert!(listed.content.starts_with("#1 Book flights (due "));
+        assert!(listed.content.ends_with("#2 Buy stamps"));
+
+        let done = update
+            .execute(serde_json::json!({"action": "complete", "item": "stamps"}))
+            .unwrap();
+        assert!(done.success);
+        let listed = list.execute(serde_json::json!({})).unwrap();
+        assert!(!listed.content.contains("Buy stamps"));
+        let all = list
+            .execute(serde_json::json!({"include_completed": true}))
+            .unwrap();
+        assert!(all.content.contains("#2 Buy stamps [done]"));
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let update = UpdateTodoTool::with_path(dir.path().join("todos.json"));
+        assert!(update.execute(serde_json::json!({})).is_err());
+        assert!(
+            update
+                .execute(serde_json::json!({"action": "add"}))
+                .is_err()
+        );
+        assert!(
+            update
+                .execute(serde_json::json!({"action": "add", "text": "x", "due": "friday"}))
+                .is_err()
+        );
+        let missing = update
+            .execute(serde_json::json!({"action": "complete", "item": "nothing"}))
+            .unwrap();
+        assert!(!missing.success);
+        assert!(!update.allowed_in_mode(ToolMode::ReadOnly));
+    }
+}
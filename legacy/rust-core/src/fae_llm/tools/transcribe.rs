@@ -0,0 +1,287 @@
+//! Transcribe tool — turns a recorded audio file into text.
+//!
+//! [`TranscribeFileTool`] runs a WAV, M4A or MP3 file through the speech
+//! model (see [`crate::stt::file`]) and returns the transcript, optionally
+//! with a timestamp per sentence. It only reads the file, so it is available
+//! in every mode. A recording takes a few seconds per minute of audio; the
+//! fraction done is reported through [`with_progress`](TranscribeFileTool::with_progress)
+//! after each window.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::stt::file::{FileTranscriber, FileTranscript};
+
+use super::path_validation::{resolve_workspace_root, validate_read_path_in_workspace};
+use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
+
+/// File extensions accepted for transcription.
+const AUDIO_EXTENSIONS: [&str; 4] = ["wav", "m4a", "mp3", "aac"];
+
+/// Speech-to-text over whole files.
+pub trait AudioTranscriber: Send + Sync {
+    /// Transcribe `path`, calling `progress` with the fraction done.
+    fn transcribe(
+        &self,
+        path: &Path,
+        progress: &mut dyn FnMut(f32),
+    ) -> crate::error::Result<FileTranscript>;
+}
+
+impl AudioTranscriber for FileTranscriber {
+    fn transcribe(
+        &self,
+        path: &Path,
+        progress: &mut dyn FnMut(f32),
+    ) -> crate::error::Result<FileTranscript> {
+        FileTranscriber::transcribe(self, path, progress)
+    }
+}
+
+/// Callback receiving the fraction of the recording transcribed so far.
+pub type ProgressCallback = Arc<dyn Fn(f32) + Send + Sync>;
+
+/// Tool that transcribes an audio recording.
+///
+/// Arguments (JSON):
+/// - `path` (string, required) — `.wav`, `.m4a`, `.mp3` or `.aac` file
+/// - `timestamps` (boolean, optional) — prefix each sentence with its start
+///   time (default false)
+pub struct TranscribeFileTool {
+    workspace_root: PathBuf,
+    transcriber: Arc<dyn AudioTranscriber>,
+    progress: Option<ProgressCallback>,
+    max_bytes: usize,
+}
+
+impl TranscribeFileTool {
+    /// Create a new TranscribeFileTool using the speech model from the
+    /// user's config, loaded on first use.
+    pub fn new() -> Self {
+        Self::with_transcriber(Arc::new(FileTranscriber::from_user_config()))
+    }
+
+    /// Create a new TranscribeFileTool backed by `transcriber`.
+    pub fn with_transcriber(transcriber: Arc<dyn AudioTranscriber>) -> Self {
+        Self {
+            workspace_root: resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")),
+            transcriber,
+            progress: None,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+
+    /// Set the workspace root paths are resolved against.
+    pub fn with_workspace_root(mut self, workspace_root: PathBuf) -> Self {
+        self.workspace_root = workspace_root;
+        self
+    }
+
+    /// Report progress through `progress`.
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+}
+
+impl Default for TranscribeFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for TranscribeFileTool {
+    fn name(&self) -> &str {
+        "transcribe_file"
+    }
+
+    fn description(&self) -> &str {
+        "Transcribe an audio recording (.wav, .m4a, .mp3) such as a voice memo to text, \
+         optionally with timestamps"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Audio file to transcribe"
+                },
+                "timestamps": {
+                    "type": "boolean",
+                    "description": "Prefix each sentence with its start time (default false)"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        let path_str = args.get("path").and_then(|v| v.as_str()).ok_or_else(|| {
+            FaeLlmError::ToolValidationError("missing required argument: path".into())
+        })?;
+        let timestamps = args
+            .get("timestamps")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let path = validate_read_path_in_workspace(path_str, &self.workspace_root)?;
+        let supported = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+        if !supported {
+            return Err(FaeLlmError::ToolValidationError(
+                "unsupported audio type: use .wav, .m4a, .mp3 or .aac".into(),
+            ));
+        }
+
+        let mut progress = |fraction: f32| {
+            if let Some(progress) = &self.progress {
+                progress(fraction);
+            }
+        };
+        let transcript = match self.transcriber.transcribe(&path, &mut progress) {
+            Ok(transcript) => transcript,
+            Err(e) => {
+                return Ok(ToolResult::failure(format!(
+                    "failed to transcribe {}: {e}",
+                    path.display()
+                )));
+            }
+        };
+
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        let output = render_transcript(&name, &transcript, timestamps);
+        let (output, truncated) = truncate_output(&output, self.max_bytes);
+        if truncated {
+            Ok(ToolResult::success_truncated(output))
+        } else {
+            Ok(ToolResult::success(output))
+        }
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true
+    }
+}
+
+/// The transcript of `name` as tool output.
+fn render_transcript(name: &str, transcript: &FileTranscript, timestamps: bool) -> String {
+    if transcript.text.trim().is_empty() {
+        return format!("No speech found in {name}.");
+    }
+    let header = format!(
+        "Transcript of {name} ({}):",
+        format_timestamp(transcript.duration_secs)
+    );
+    if !timestamps {
+        return format!("{header}\n{}", transcript.text);
+    }
+    let lines: Vec<String> = transcript
+        .segments
+        .iter()
+        .map(|s| format!("[{}] {}", format_timestamp(s.start_secs), s.text))
+        .collect();
+    format!("{header}\n{}", lines.join("\n"))
+}
+
+/// `secs` as `m:ss`, or `h:mm:ss` from an hour on.
+pub(crate) fn format_timestamp(secs: f32) -> String {
+    let total = secs.max(0.0) as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::stt::file::TimedSegment;
+
+    struct FixedTranscriber;
+
+    impl AudioTranscriber for FixedTranscriber {
+        fn transcribe(
+            &self,
+            _path: &Path,
+            progress: &mut dyn FnMut(f32),
+        ) -> crate::error::Result<FileTranscript> {
+            progress(0.5);
+            progress(1.0);
+            Ok(FileTranscript {
+                text: "Buy milk. Call the plumber.".to_owned(),
+                segments: vec![
+                    TimedSegment {
+                        start_secs: 0.4,
+                        end_secs: 1.6,
+                        text: "Buy milk.".to_owned(),
+                    },
+                    TimedSegment {
+                        start_secs: 65.2,
+                        end_secs: 67.0,
+                        text: "Call the plumber.".to_owned(),
+                    },
+                ],
+                duration_secs: 68.0,
+            })
+        }
+    }
+
+    #[test]
+    fn transcribes_with_timestamps_and_progress() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("memo.m4a"), b"audio").expect("write");
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        let tool = TranscribeFileTool::with_transcriber(Arc::new(FixedTranscriber))
+            .with_workspace_root(dir.path().to_path_buf())
+            .with_progress(Arc::new(move |f| sink.lock().unwrap().push(f)));
+
+        let result = tool
+            .execute(serde_json::json!({"path": "memo.m4a"}))
+            .expect("execute");
+        assert!(result.success);
+        assert_eq!(
+            result.content,
+            "Transcript of memo.m4a (1:08):\nBuy milk. Call the plumber."
+        );
+        assert_eq!(*reported.lock().unwrap(), vec![0.5, 1.0]);
+
+        let result = tool
+            .execute(serde_json::json!({"path": "memo.m4a", "timestamps": true}))
+            .expect("execute");
+        assert!(
+            result
+                .content
+                .ends_with("\n[0:00] Buy milk.\n[1:05] Call the plumber.")
+        );
+        assert!(tool.allowed_in_mode(ToolMode::ReadOnly));
+    }
+
+    #[test]
+    fn rejects_non_audio_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("notes.txt"), b"text").expect("write");
+        let tool = TranscribeFileTool::with_transcriber(Arc::new(FixedTranscriber))
+            .with_workspace_root(dir.path().to_path_buf());
+        assert!(
+            tool.execute(serde_json::json!({"path": "notes.txt"}))
+                .is_err()
+        );
+        assert_eq!(format_timestamp(3_725.0), "1:02:05");
+    }
+}
@@ -1,18 +1,28 @@
 //! Core tool types for the fae_llm tool system.
 //!
 //! Defines the [`Tool`] trait that all tools implement and [`ToolResult`]
-//! for capturing bounded execution output.
+//! for capturing bounded execution output, with an optional structured
+//! payload described by [`Tool::output_schema`]. A tool that cannot tell
+//! which of several things a call means returns a [`Clarification`] instead
+//! of guessing.
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
+use std::future::Future;
+use std::pin::Pin;
 
 /// Default maximum output size (100 KB).
 pub const DEFAULT_MAX_BYTES: usize = 100 * 1024;
 
+/// A pending user decision on a tool call; resolves to whether it was approved.
+pub type ApprovalFuture = Pin<Box<dyn Future<Output = Result<bool, FaeLlmError>> + Send>>;
+
 /// Result of a tool execution.
 ///
 /// Contains the output content (bounded to `max_bytes`), success/error status,
-/// and a flag indicating whether output was truncated.
+/// a flag indicating whether output was truncated, and an optional structured
+/// payload. `content` is always the text rendering; `structured` is passed
+/// only to providers that accept structured tool results.
 #[derive(Debug, Clone)]
 pub struct ToolResult {
     /// Whether the tool execution succeeded.
@@ -23,6 +33,11 @@ pub struct ToolResult {
     pub error: Option<String>,
     /// Whether the output was truncated to fit within max_bytes.
     pub truncated: bool,
+    /// Structured payload matching the tool's [`Tool::output_schema`].
+    pub structured: Option<serde_json::Value>,
+    /// Set when the call was not run because the user must first pick
+    /// between several matches.
+    pub clarification: Option<Clarification>,
 }
 
 impl ToolResult {
@@ -33,6 +48,8 @@ impl ToolResult {
             content,
             error: None,
             truncated: false,
+            structured: None,
+            clarification: None,
         }
     }
 
@@ -43,6 +60,8 @@ impl ToolResult {
             content: String::new(),
             error: Some(error),
             truncated: false,
+            structured: None,
+            clarification: None,
         }
     }
 
@@ -53,11 +72,303 @@ impl ToolResult {
             content,
             error: None,
             truncated: true,
+            structured: None,
+            clarification: None,
+        }
+    }
+
+    /// Attach a structured payload alongside the text rendering.
+    #[must_use]
+    pub fn with_structured(mut self, value: serde_json::Value) -> Self {
+        self.structured = Some(value);
+        self
+    }
+
+    /// A call that was not run because it matched several things; the
+    /// agent loop stops and asks the user to pick one.
+    pub fn clarify(clarification: Clarification) -> Self {
+        Self {
+            success: false,
+            content: String::new(),
+            error: Some(clarification.render()),
+            truncated: false,
+            structured: None,
+            clarification: Some(clarification),
+        }
+    }
+}
+
+/// Ordinal words, per language, for picking a candidate by position.
+const ORDINALS: &[&[&str]] = &[
+    &[
+        "first",
+        "1st",
+        "erste",
+        "ersten",
+        "primero",
+        "primera",
+        "premier",
+        "première",
+    ],
+    &[
+        "second",
+        "2nd",
+        "zweite",
+        "zweiten",
+        "segundo",
+        "segunda",
+        "deuxième",
+    ],
+    &[
+        "third",
+        "3rd",
+        "dritte",
+        "dritten",
+        "tercero",
+        "tercera",
+        "troisième",
+    ],
+    &[
+        "fourth",
+        "4th",
+        "vierte",
+        "vierten",
+        "cuarto",
+        "cuarta",
+        "quatrième",
+    ],
+    &[
+        "fifth",
+        "5th",
+        "fünfte",
+        "fünften",
+        "quinto",
+        "quinta",
+        "cinquième",
+    ],
+];
+
+/// Words for the last candidate.
+const LAST: &[&str] = &[
+    "last",
+    "letzte",
+    "letzten",
+    "último",
+    "última",
+    "dernier",
+    "dernière",
+];
+
+/// One of the things an ambiguous call could mean.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClarificationCandidate {
+    /// How the candidate is described to the user.
+    pub label: String,
+    /// Value of [`Clarification::argument`] that selects it.
+    pub value: String,
+}
+
+/// A call a tool held back because its arguments matched several things,
+/// such as three events titled "meeting".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clarification {
+    /// Argument the user's pick fills in when the call is made again.
+    pub argument: String,
+    /// What the user can choose between.
+    pub candidates: Vec<ClarificationCandidate>,
+}
+
+impl Clarification {
+    /// Ask the user to choose which candidate the call fills into
+    /// `argument`.
+    pub fn new(argument: impl Into<String>, candidates: Vec<ClarificationCandidate>) -> Self {
+        Self {
+            argument: argument.into(),
+            candidates,
+        }
+    }
+
+    /// The spoken question listing the candidates.
+    pub fn question(&self) -> String {
+        let labels: Vec<&str> = self.candidates.iter().map(|c| c.label.as_str()).collect();
+        let options = match labels.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!(
+                "{} {} {last}",
+                rest.join(", "),
+                crate::i18n::text("clarify.or")
+            ),
+            _ => labels.join(""),
+        };
+        crate::i18n::format("clarify.which", &[("options", &options)])
+    }
+
+    /// What the model sees as the tool result.
+    pub fn render(&self) -> String {
+        let mut text = format!(
+            "Nothing was done: this matches {} items. The user is being asked which one they mean; \
+             once they answer, call the tool again with `{}` set to the chosen value.",
+            self.candidates.len(),
+            self.argument
+        );
+        for candidate in &self.candidates {
+            text.push_str(&format!("\n- {}: {}", candidate.label, candidate.value));
+        }
+        text
+    }
+
+    /// The candidate the user's `answer` picks, by position ("the second
+    /// one") or by the words of its label ("the client meeting").
+    pub fn pick(&self, answer: &str) -> Option<&ClarificationCandidate> {
+        let words: Vec<String> = answer
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+
+        // A bare number is a position only in a short answer ("number 2"),
+        // not inside a time or date.
+        let numbers_are_positions = words.len() <= 2;
+        for word in &words {
+            if LAST.contains(&word.as_str()) {
+                return self.candidates.last();
+            }
+            let position = ORDINALS
+                .iter()
+                .position(|forms| forms.contains(&word.as_str()))
+                .or_else(|| {
+                    if !numbers_are_positions {
+                        return None;
+                    }
+                    word.parse::<usize>().ok()?.checked_sub(1)
+                });
+            if let Some(candidate) = position.and_then(|i| self.candidates.get(i)) {
+                return Some(candidate);
+            }
+        }
+
+        // Otherwise the candidate whose label shares the most words with the
+        // answer, if exactly one does.
+        let scores: Vec<usize> = self
+            .candidates
+            .iter()
+            .map(|candidate| {
+                let label = candidate.label.to_lowercase();
+                let label_words: Vec<&str> = label
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|w| w.len() > 2 || w.chars().all(|c| c.is_ascii_digit()))
+                    .collect();
+                words
+                    .iter()
+                    .filter(|w| label_words.contains(&w.as_str()))
+                    .count()
+            })
+            .collect();
+        let best = scores.iter().copied().max().filter(|&best| best > 0)?;
+        if scores.iter().filter(|&&score| score == best).count() > 1 {
+            return None;
+        }
+        let index = scores.iter().position(|&score| score == best)?;
+        self.candidates.get(index)
+    }
+}
+
+/// Share of the byte budget kept from the start of truncated output; the
+/// rest is kept from the end.
+const HEAD_SHARE_PERCENT: usize = 60;
+
+/// A string split for middle-out truncation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MiddleOut<'a> {
+    /// Kept prefix.
+    pub head: &'a str,
+    /// Elided middle.
+    pub elided: &'a str,
+    /// Kept suffix.
+    pub tail: &'a str,
+}
+
+impl MiddleOut<'_> {
+    /// Marker placed between head and tail, with an optional summary of the
+    /// elided text.
+    pub fn marker(&self, max_bytes: usize, summary: Option<&str>) -> String {
+        let lines = self.elided.lines().count();
+        let mut marker = format!(
+            "[output truncated at {max_bytes} bytes: {} bytes ({lines} lines) elided from the middle",
+            self.elided.len()
+        );
+        if let Some(summary) = summary.map(str::trim).filter(|s| !s.is_empty()) {
+            marker.push_str("; summary of elided output: ");
+            marker.push_str(summary);
         }
+        marker.push(']');
+        marker
+    }
+
+    /// Join head, marker, and tail.
+    pub fn render(&self, marker: &str) -> String {
+        let mut out = String::with_capacity(self.head.len() + marker.len() + self.tail.len() + 4);
+        out.push_str(self.head);
+        if !self.head.is_empty() {
+            out.push_str("\n\n");
+        }
+        out.push_str(marker);
+        if !self.tail.is_empty() {
+            out.push_str("\n\n");
+            out.push_str(self.tail);
+        }
+        out
     }
 }
 
-/// Truncate a string to at most `max_bytes`, respecting UTF-8 boundaries.
+/// Split `s` so that head and tail together fit in `max_bytes`.
+///
+/// Cuts snap to line boundaries when one falls in the outer half of the head
+/// or tail budget, and always to UTF-8 character boundaries.
+pub(crate) fn split_middle_out(s: &str, max_bytes: usize) -> MiddleOut<'_> {
+    let head_budget = max_bytes * HEAD_SHARE_PERCENT / 100;
+    let tail_budget = max_bytes - head_budget;
+
+    let mut head_end = floor_char_boundary(s, head_budget.min(s.len()));
+    if let Some(nl) = s[..head_end].rfind('\n')
+        && nl + 1 >= head_end / 2
+    {
+        head_end = nl + 1;
+    }
+
+    let mut tail_start = ceil_char_boundary(s, s.len().saturating_sub(tail_budget).max(head_end));
+    if let Some(nl) = s[tail_start..].find('\n')
+        && nl < (s.len() - tail_start) / 2
+    {
+        tail_start += nl + 1;
+    }
+
+    MiddleOut {
+        head: &s[..head_end],
+        elided: &s[head_end..tail_start],
+        tail: &s[tail_start..],
+    }
+}
+
+fn floor_char_boundary(s: &str, mut idx: usize) -> usize {
+    while idx > 0 && !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+fn ceil_char_boundary(s: &str, mut idx: usize) -> usize {
+    while idx < s.len() && !s.is_char_boundary(idx) {
+        idx += 1;
+    }
+    idx
+}
+
+/// Bound a string to `max_bytes` of original content using middle-out
+/// truncation.
+///
+/// Keeps the start and end of the output (where commands usually print
+/// headers, errors, and final status), drops the middle, and annotates how
+/// much was elided. Respects UTF-8 boundaries.
 ///
 /// Returns `(truncated_string, was_truncated)`.
 pub fn truncate_output(s: &str, max_bytes: usize) -> (String, bool) {
@@ -65,17 +376,8 @@ pub fn truncate_output(s: &str, max_bytes: usize) -> (String, bool) {
         return (s.to_string(), false);
     }
 
-    // Find the last valid UTF-8 char boundary at or before max_bytes
-    let mut end = max_bytes;
-    while end > 0 && !s.is_char_boundary(end) {
-        end -= 1;
-    }
-
-    let truncated = &s[..end];
-    (
-        format!("{truncated}\n\n[output truncated at {max_bytes} bytes]"),
-        true,
-    )
+    let split = split_middle_out(s, max_bytes);
+    (split.render(&split.marker(max_bytes, None)), true)
 }
 
 /// Core trait for LLM tools.
@@ -93,6 +395,28 @@ pub trait Tool: Send + Sync {
     /// Returns the JSON Schema for the tool's arguments.
     fn schema(&self) -> serde_json::Value;
 
+    /// Returns the JSON Schema for [`ToolResult::structured`], if the tool
+    /// produces structured output.
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Human-readable summary of what a call with `args` would do, shown in
+    /// approval prompts alongside the raw arguments.
+    ///
+    /// Returns `None` when the arguments speak for themselves.
+    fn approval_preview(&self, _args: &serde_json::Value) -> Option<String> {
+        None
+    }
+
+    /// The arguments a spoken read-back of a call with `args` describes.
+    ///
+    /// Tools whose calls act on saved state, such as sending a stored draft,
+    /// fill that state in so the user hears what will actually happen.
+    fn read_back_args(&self, args: &serde_json::Value) -> serde_json::Value {
+        args.clone()
+    }
+
     /// Execute the tool with the given JSON arguments.
     ///
     /// # Errors
@@ -100,6 +424,40 @@ pub trait Tool: Send + Sync {
     /// Returns `FaeLlmError` for validation/execution failures.
     fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError>;
 
+    /// Whether a call with `args` needs approval when the tool is registered
+    /// behind an approval gate, so a tool can exempt its harmless calls.
+    fn requires_approval(&self, _args: &serde_json::Value) -> bool {
+        true
+    }
+
+    /// Ask the user to approve a call with `args` before it runs.
+    ///
+    /// Returns `None` for tools that need no approval. The executor awaits
+    /// the returned future without holding a thread or counting it against
+    /// the tool timeout, then runs the call with [`Tool::execute_approved`].
+    fn request_approval(&self, _args: &serde_json::Value) -> Option<ApprovalFuture> {
+        None
+    }
+
+    /// Execute a call the user already approved through
+    /// [`Tool::request_approval`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `FaeLlmError` for validation/execution failures.
+    fn execute_approved(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        self.execute(args)
+    }
+
+    /// Whether a call changes something outside Fae that must not happen
+    /// twice, such as creating an event or sending a message.
+    ///
+    /// The executor refuses a replay of such a call (same conversation,
+    /// call ID and arguments) within the idempotency window.
+    fn is_side_effecting(&self) -> bool {
+        false
+    }
+
     /// Whether this tool is allowed in the given mode.
     ///
     /// Read-only tools (like `read`) return true for both modes.
@@ -138,6 +496,55 @@ mod tests {
         assert!(result.truncated);
     }
 
+    #[test]
+    fn tool_result_with_structured() {
+        let result = ToolResult::success("1 row".to_string())
+            .with_structured(serde_json::json!([{ "id": 1 }]));
+        assert_eq!(result.content, "1 row");
+        assert_eq!(result.structured, Some(serde_json::json!([{ "id": 1 }])));
+        assert!(ToolResult::failure("x".to_string()).structured.is_none());
+    }
+
+    #[test]
+    fn clarification_picks_by_position_or_label() {
+        let candidate = |label: &str, value: &str| ClarificationCandidate {
+            label: label.to_owned(),
+            value: value.to_owned(),
+        };
+        let clarification = Clarification::new(
+            "identifier",
+            vec![
+                candidate("Team meeting, 2026-03-01 09:00", "evt-1"),
+                candidate("Client meeting, 2026-03-03 14:00", "evt-2"),
+                candidate("Budget meeting, 2026-03-06 10:00", "evt-3"),
+            ],
+        );
+        let pick = |answer: &str| clarification.pick(answer).map(|c| c.value.as_str());
+        assert_eq!(pick("The second one"), Some("evt-2"));
+        assert_eq!(pick("number 3"), Some("evt-3"));
+        assert_eq!(pick("the last"), Some("evt-3"));
+        assert_eq!(pick("the client meeting please"), Some("evt-2"));
+        assert_eq!(pick("the one at 14:00"), Some("evt-2"));
+        assert_eq!(pick("the one on 2026-03-06"), Some("evt-3"));
+        // Every label says "meeting": no single match.
+        assert_eq!(pick("the meeting"), None);
+        assert_eq!(pick("never mind"), None);
+
+        let result = ToolResult::clarify(clarification.clone());
+        assert!(!result.success);
+        assert!(
+            result
+                .error
+                .unwrap()
+                .contains("- Client meeting, 2026-03-03 14:00: evt-2")
+        );
+        assert_eq!(
+            clarification.question(),
+            "Which one do you mean: Team meeting, 2026-03-01 09:00, \
+             Client meeting, 2026-03-03 14:00 or Budget meeting, 2026-03-06 10:00?"
+        );
+    }
+
     #[test]
     fn truncate_output_short_string() {
         let (output, truncated) = truncate_output("hello", 100);
@@ -154,12 +561,14 @@ mod tests {
 
     #[test]
     fn truncate_output_truncates_long_string() {
-        let input = "a".repeat(200);
+        let input = format!("{}{}{}", "h".repeat(100), "m".repeat(100), "t".repeat(100));
         let (output, truncated) = truncate_output(&input, 100);
         assert!(truncated);
-        assert!(output.contains("[output truncated at 100 bytes]"));
-        // The actual content before the truncation message should be 100 bytes
-        assert!(output.starts_with(&"a".repeat(100)));
+        assert!(output.contains("[output truncated at 100 bytes: 200 bytes"));
+        // Head and tail together use the 100-byte budget.
+        assert!(output.starts_with(&"h".repeat(60)));
+        assert!(output.ends_with(&"t".repeat(40)));
+        assert!(!output.contains("mm"));
     }
 
     #[test]
@@ -168,8 +577,31 @@ mod tests {
         let input = "ééééé"; // 10 bytes total
         let (output, truncated) = truncate_output(input, 5);
         assert!(truncated);
-        // Should truncate to 4 bytes (2 chars) since byte 5 is mid-char
-        assert!(output.starts_with("éé"));
+        // Head budget of 3 bytes falls mid-char, so only one char is kept
+        assert!(output.starts_with("é\n"));
+        assert!(output.ends_with("é"));
+    }
+
+    #[test]
+    fn truncate_output_snaps_to_line_boundaries() {
+        let input: String = (0..50).map(|i| format!("line {i:02}\n")).collect();
+        let (output, truncated) = truncate_output(&input, 100);
+        assert!(truncated);
+        assert!(output.starts_with("line 00\n"));
+        assert!(output.ends_with("line 49\n"));
+        for line in output.lines().filter(|l| l.starts_with("line")) {
+            assert_eq!(line.len(), "line 00".len());
+        }
+        assert!(output.contains("lines) elided from the middle]"));
+    }
+
+    #[test]
+    fn middle_out_marker_includes_summary() {
+        let split = split_middle_out("aaaa\nbbbb\ncccc\n", 10);
+        assert_eq!(split.elided.len() + split.head.len() + split.tail.len(), 15);
+        let marker = split.marker(10, Some("  mostly b  "));
+        assert!(marker.ends_with("; summary of elided output: mostly b]"));
+        assert!(!split.marker(10, Some(" ")).contains("summary"));
     }
 
     #[test]
@@ -183,7 +615,7 @@ mod tests {
     fn truncate_output_zero_max() {
         let (output, truncated) = truncate_output("hello", 0);
         assert!(truncated);
-        assert!(output.contains("[output truncated at 0 bytes]"));
+        assert!(output.contains("[output truncated at 0 bytes: 5 bytes"));
     }
 
     // ── Trait bounds ──────────────────────────────────────────
@@ -0,0 +1,686 @@
+//! Undo history for file-modifying tools.
+//!
+//! `write` and `edit` record the previous content of every file they change,
+//! and `file_organize` the original location of every file it moves, one
+//! batch per tool call. Batches are stored as JSON files under
+//! `data_dir()/undo/`, and the oldest are pruned once the store exceeds its
+//! batch count or byte budget. [`UndoTool`] and the "undo that change" voice
+//! command revert the most recent batches. Every recorded and reverted batch
+//! is appended to the undo audit log, which the `undo.history` host command
+//! reads back.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit_log::append_audit_line;
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::time_util::now_epoch_secs;
+
+use super::edit::write_atomic;
+use super::types::{Tool, ToolResult};
+
+/// Batches kept by default.
+pub const DEFAULT_MAX_BATCHES: usize = 50;
+/// Total size of stored batches kept by default.
+pub const DEFAULT_MAX_STORE_BYTES: u64 = 32 * 1024 * 1024;
+/// Most batches one undo call may revert.
+pub const MAX_UNDO_COUNT: usize = 10;
+
+/// Serializes access to undo stores so concurrent tool calls and the voice
+/// command never interleave a record with an undo.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Previous state of one changed file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UndoFile {
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// Content before the change, `None` when the change created the file.
+    pub previous: Option<String>,
+    /// Where the file was moved from; reverting moves it back instead of
+    /// restoring `previous`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moved_from: Option<PathBuf>,
+}
+
+/// Files changed by one tool call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UndoBatch {
+    /// Increasing batch identifier.
+    pub id: u64,
+    /// Timestamp when the change was made.
+    pub timestamp_secs: u64,
+    /// Tool that made the change.
+    pub tool: String,
+    /// Changed files in the order they were written.
+    pub files: Vec<UndoFile>,
+}
+
+impl UndoBatch {
+    /// Paths of the changed files, for display.
+    pub fn paths(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .map(|f| match &f.moved_from {
+                Some(from) => format!("{} -> {}", from.display(), f.path.display()),
+                None => f.path.display().to_string(),
+            })
+            .collect()
+    }
+}
+
+/// What happened to a batch, as recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UndoAuditAction {
+    /// A tool changed files and the previous content was stored.
+    Recorded,
+    /// The change was reverted.
+    Undone,
+    /// Reverting the change failed.
+    UndoFailed,
+}
+
+/// One line of the undo audit log. Never contains file content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UndoAuditEntry {
+    /// Timestamp when the entry was written.
+    pub timestamp_secs: u64,
+    /// What happened.
+    pub action: UndoAuditAction,
+    /// Batch identifier.
+    pub batch_id: u64,
+    /// Tool that made the original change.
+    pub tool: String,
+    /// Files in the batch.
+    pub paths: Vec<String>,
+    /// Failure reason for [`UndoAuditAction::UndoFailed`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of reverting one batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoOutcome {
+    /// The batch that was reverted (or not).
+    pub batch: UndoBatch,
+    /// Why reverting failed; `None` on success.
+    pub error: Option<String>,
+}
+
+/// Bounded on-disk store of previous file contents.
+#[derive(Debug, Clone)]
+pub struct UndoStore {
+    dir: PathBuf,
+    audit_path: Option<PathBuf>,
+    max_batches: usize,
+    max_bytes: u64,
+}
+
+impl UndoStore {
+    /// Create a store in `dir` with default limits and no audit log.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            audit_path: None,
+            max_batches: DEFAULT_MAX_BATCHES,
+            max_bytes: DEFAULT_MAX_STORE_BYTES,
+        }
+    }
+
+    /// The store under `data_dir()/undo/`, audited to `undo_audit.jsonl`.
+    pub fn default_location() -> Self {
+        Self::new(crate::fae_dirs::undo_dir()).with_audit_log(crate::fae_dirs::undo_audit_file())
+    }
+
+    /// Append record and undo events to `path`.
+    pub fn with_audit_log(mut self, path: PathBuf) -> Self {
+        self.audit_path = Some(path);
+        self
+    }
+
+    /// Keep at most `max_batches` batches and `max_bytes` of stored content.
+    ///
+    /// The newest batch is always kept, even if it alone exceeds `max_bytes`.
+    pub fn with_limits(mut self, max_batches: usize, max_bytes: u64) -> Self {
+        self.max_batches = max_batches.max(1);
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Store the previous state of `files` changed by `tool`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch cannot be written.
+    pub fn record(&self, tool: &str, files: Vec<UndoFile>) -> std::io::Result<u64> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        std::fs::create_dir_all(&self.dir)?;
+        let existing = self.list()?;
+        let id = existing.last().map_or(1, |(id, _, _)| id + 1);
+        let batch = UndoBatch {
+            id,
+            timestamp_secs: now_epoch_secs(),
+            tool: tool.to_owned(),
+            files,
+        };
+        let json = serde_json::to_string(&batch).map_err(std::io::Error::other)?;
+        write_atomic(&self.batch_path(id), &json)?;
+        self.audit(UndoAuditAction::Recorded, &batch, None);
+        self.prune()?;
+        Ok(id)
+    }
+
+    /// The most recent `limit` batches, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read.
+    pub fn history(&self, limit: usize) -> std::io::Result<Vec<UndoBatch>> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        self.list()?
+            .iter()
+            .rev()
+            .take(limit)
+            .map(|(_, path, _)| read_batch(path))
+            .collect()
+    }
+
+    /// Revert the most recent `count` batches, newest first.
+    ///
+    /// Stops at the first batch that cannot be fully reverted, since older
+    /// changes may depend on it; that batch stays in the store so it can be
+    /// retried.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read.
+    pub fn undo(&self, count: usize) -> std::io::Result<Vec<UndoOutcome>> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut outcomes = Vec::new();
+        for (_, path, _) in self.list()?.iter().rev().take(count) {
+            let batch = read_batch(path)?;
+            let error = revert(&batch).err();
+            match &error {
+                None => {
+                    std::fs::remove_file(path)?;
+                    self.audit(UndoAuditAction::Undone, &batch, None);
+                }
+                Some(e) => self.audit(UndoAuditAction::UndoFailed, &batch, Some(e.clone())),
+            }
+            let failed = error.is_some();
+            outcomes.push(UndoOutcome { batch, error });
+            if failed {
+                break;
+            }
+        }
+        Ok(outcomes)
+    }
+
+    fn batch_path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{id:010}.json"))
+    }
+
+    /// Stored batches as `(id, path, size)`, oldest first.
+    fn list(&self) -> std::io::Result<Vec<(u64, PathBuf, u64)>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut batches = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            let Some(id) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".json"))
+                .and_then(|n| n.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            batches.push((id, path, entry.metadata()?.len()));
+        }
+        batches.sort_by_key(|(id, _, _)| *id);
+        Ok(batches)
+    }
+
+    fn prune(&self) -> std::io::Result<()> {
+        let batches = self.list()?;
+        let mut count = batches.len();
+        let mut total: u64 = batches.iter().map(|(_, _, size)| size).sum();
+        for (id, path, size) in &batches {
+            if count <= 1 || (count <= self.max_batches && total <= self.max_bytes) {
+                break;
+            }
+            std::fs::remove_file(path)?;
+            tracing::debug!(batch_id = id, "pruned undo batch");
+            count -= 1;
+            total -= size;
+        }
+        Ok(())
+    }
+
+    fn audit(&self, action: UndoAuditAction, batch: &UndoBatch, error: Option<String>) {
+        let Some(path) = &self.audit_path else {
+            return;
+        };
+        let entry = UndoAuditEntry {
+            timestamp_secs: now_epoch_secs(),
+            action,
+            batch_id: batch.id,
+            tool: batch.tool.clone(),
+            paths: batch.paths(),
+            error,
+        };
+        if let Err(e) = append_undo_audit(path, &entry) {
+            tracing::warn!("failed to write undo audit log: {e}");
+        }
+    }
+}
+
+fn read_batch(path: &Path) -> std::io::Result<UndoBatch> {
+    let raw = std::fs::read_to_string(path)?;
+    serde_json::from_str(&raw).map_err(std::io::Error::other)
+}
+
+/// Restore every file in `batch`, last written first.
+fn revert(batch: &UndoBatch) -> Result<(), String> {
+    for file in batch.files.iter().rev() {
+        if std::fs::symlink_metadata(&file.path).is_ok_and(|m| m.file_type().is_symlink()) {
+            return Err(format!("{} is now a symlink", file.path.display()));
+        }
+        if let Some(from) = &file.moved_from {
+            move_back(&file.path, from)?;
+            continue;
+        }
+        let result = match &file.previous {
+            Some(content) => write_atomic(&file.path, content),
+            None => match std::fs::remove_file(&file.path) {
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                other => other,
+            },
+        };
+        result.map_err(|e| format!("failed to restore {}: {e}", file.path.display()))?;
+    }
+    Ok(())
+}
+
+/// Move `path` back to `from`, refusing to replace whatever is there now.
+fn move_back(path: &Path, from: &Path) -> Result<(), String> {
+    if std::fs::symlink_metadata(from).is_ok() {
+        return Err(format!("{} exists again", from.display()));
+    }
+    if let Some(parent) = from.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to recreate {}: {e}", parent.display()))?;
+    }
+    std::fs::rename(path, from).map_err(|e| {
+        format!(
+            "failed to move {} back to {}: {e}",
+            path.display(),
+            from.display()
+        )
+    })
+}
+
+/// Append `entry` to the undo audit log at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the log cannot be opened or written.
+pub fn append_undo_audit(path: &Path, entry: &UndoAuditEntry) -> std::io::Result<()> {
+    append_audit_line(path, entry)
+}
+
+/// The last `limit` entries of the undo audit log at `path`, oldest first.
+/// Malformed lines are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the log exists but cannot be read.
+pub fn read_undo_audit(path: &Path, limit: usize) -> std::io::Result<Vec<UndoAuditEntry>> {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let entries: Vec<UndoAuditEntry> = raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    Ok(entries.into_iter().skip(skip).collect())
+}
+
+/// Record `files` in `store` if one is set, logging instead of failing: the
+/// change itself already succeeded.
+pub(super) fn record_change(store: Option<&Arc<UndoStore>>, tool: &str, files: Vec<UndoFile>) {
+    if let Some(store) = store
+        && let Err(e) = store.record(tool, files)
+    {
+        tracing::warn!("failed to record {tool} change for undo: {e}");
+    }
+}
+
+/// Tool that reverts recent `write`, `edit` and `file_organize` changes.
+///
+/// Arguments (JSON):
+/// - `count` (integer, optional) — number of changes to revert, newest
+///   first (default 1, max [`MAX_UNDO_COUNT`])
+/// - `list` (boolean, optional) — list recent changes instead of reverting
+///
+/// Only available in `ToolMode::Full`.
+pub struct UndoTool {
+    store: Arc<UndoStore>,
+}
+
+impl UndoTool {
+    /// Create an undo tool over `store`.
+    pub fn new(store: Arc<UndoStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl Tool for UndoTool {
+    fn name(&self) -> &str {
+        "undo"
+    }
+
+    fn description(&self) -> &str {
+        "Revert the most recent file changes made by the write, edit and file_organize tools, or list them"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "count": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_UNDO_COUNT,
+                    "description": "Number of changes to revert, newest first (default 1)"
+                },
+                "list": {
+                    "type": "boolean",
+                    "description": "List recent changes without reverting anything"
+                }
+            }
+        })
+    }
+
+    fn approval_preview(&self, args: &serde_json::Value) -> Option<String> {
+        if args.get("list").and_then(|v| v.as_bool()) == Some(true) {
+            return None;
+        }
+        let count = undo_count(args).ok()?;
+        let batches = self.store.history(count).ok()?;
+        if batches.is_empty() {
+            return Some("nothing to undo".to_owned());
+        }
+        Some(format!("revert:\n{}", format_batches(&batches)))
+    }
+
+    fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        if args.get("list").and_then(|v| v.as_bool()) == Some(true) {
+            let batches = self
+                .store
+                .history(MAX_UNDO_COUNT)
+                .map_err(|e| FaeLlmError::ToolExecutionError(format!("undo history: {e}")))?;
+            if batches.is_empty() {
+                return Ok(ToolResult::success("no changes to undo".to_owned()));
+            }
+            return Ok(ToolResult::success(format_batches(&batches)));
+        }
+
+        let count = undo_count(&args)?;
+        let outcomes = self
+            .store
+            .undo(count)
+            .map_err(|e| FaeLlmError::ToolExecutionError(format!("undo failed: {e}")))?;
+        if outcomes.is_empty() {
+            return Ok(ToolResult::success("no changes to undo".to_owned()));
+        }
+
+        let mut report = String::new();
+        let mut failure = None;
+        for outcome in &outcomes {
+            match &outcome.error {
+                None => report.push_str(&format!(
+                    "reverted {} change: {}\n",
+                    outcome.batch.tool,
+                    outcome.batch.paths().join(", ")
+                )),
+                Some(e) => failure = Some(e.clone()),
+            }
+        }
+        match failure {
+            None => Ok(ToolResult::success(report.trim_end().to_owned())),
+            Some(e) => Ok(ToolResult::failure(format!("{report}stopped: {e}"))),
+        }
+    }
+
+    fn allowed_in_mode(&self, mode: ToolMode) -> bool {
+        mode == ToolMode::Full
+    }
+}
+
+fn undo_count(args: &serde_json::Value) -> Result<usize, FaeLlmError> {
+    match args.get("count") {
+        None | Some(serde_json::Value::Null) => Ok(1),
+        Some(v) => match v.as_u64() {
+            Some(n) if (1..=MAX_UNDO_COUNT as u64).contains(&n) => Ok(n as usize),
+            _ => Err(FaeLlmError::ToolValidationError(format!(
+                "count must be an integer from 1 to {MAX_UNDO_COUNT}"
+            ))),
+        },
+    }
+}
+
+fn format_batches(batches: &[UndoBatch]) -> String {
+    let now = now_epoch_secs();
+    batches
+        .iter()
+        .map(|b| {
+            format!(
+                "#{} {} ({}): {}",
+                b.id,
+                b.tool,
+                format_age(now.saturating_sub(b.timestamp_secs)),
+                b.paths().join(", ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_age(secs: u64) -> String {
+    match secs {
+        0..60 => "just now".to_owned(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn store(dir: &tempfile::TempDir) -> UndoStore {
+        UndoStore::new(dir.path().join("undo")).with_audit_log(dir.path().join("undo_audit.jsonl"))
+    }
+
+    #[test]
+    fn undo_restores_newest_batches_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        let created = dir.path().join("new.txt");
+        let store = store(&dir);
+
+        std::fs::write(&file, "v2").unwrap();
+        store
+            .record(
+                "write",
+                vec![UndoFile {
+                    path: file.clone(),
+                    previous: Some("v1".to_owned()),
+                    moved_from: None,
+                }],
+            )
+            .unwrap();
+        std::fs::write(&file, "v3").unwrap();
+        std::fs::write(&created, "fresh").unwrap();
+        store
+            .record(
+                "edit",
+                vec![
+                    UndoFile {
+                        path: file.clone(),
+                        previous: Some("v2".to_owned()),
+                        moved_from: None,
+                    },
+                    UndoFile {
+                        path: created.clone(),
+                        previous: None,
+                        moved_from: None,
+                    },
+                ],
+            )
+            .unwrap();
+
+        let outcomes = store.undo(1).unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].batch.tool, "edit");
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2");
+        assert!(!created.exists());
+
+        store.undo(5).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v1");
+        assert!(store.history(10).unwrap().is_empty());
+        assert!(store.undo(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn store_prunes_oldest_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&dir).with_limits(2, u64::MAX);
+        for i in 0..4 {
+            store
+                .record(
+                    "write",
+                    vec![UndoFile {
+                        path: dir.path().join(format!("f{i}")),
+                        previous: None,
+                        moved_from: None,
+                    }],
+                )
+                .unwrap();
+        }
+        let ids: Vec<u64> = store.history(10).unwrap().iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec![4, 3]);
+    }
+
+    #[test]
+    fn audit_log_records_history_without_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("secret.txt");
+        std::fs::write(&file, "new").unwrap();
+        let store = store(&dir);
+        store
+            .record(
+                "write",
+                vec![UndoFile {
+                    path: file,
+                    previous: Some("password123".to_owned()),
+                    moved_from: None,
+                }],
+            )
+            .unwrap();
+        store.undo(1).unwrap();
+
+        let audit = dir.path().join("undo_audit.jsonl");
+        let actions: Vec<UndoAuditAction> = read_undo_audit(&audit, 10)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect();
+        assert_eq!(
+            actions,
+            vec![UndoAuditAction::Recorded, UndoAuditAction::Undone]
+        );
+        let last = read_undo_audit(&audit, 1).unwrap();
+        assert_eq!(last[0].action, UndoAuditAction::Undone);
+        let raw = std::fs::read_to_string(&audit).unwrap();
+        assert!(!raw.contains("password123"));
+    }
+
+    #[test]
+    fn undo_moves_files_back_and_refuses_to_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let origin = dir.path().join("report.pdf");
+        let moved = dir.path().join("Documents").join("report.pdf");
+        let store = store(&dir);
+
+        std::fs::create_dir(dir.path().join("Documents")).unwrap();
+        std::fs::write(&moved, "pdf").unwrap();
+        let batch = vec![UndoFile {
+            path: moved.clone(),
+            previous: None,
+            moved_from: Some(origin.clone()),
+        }];
+        store.record("file_organize", batch.clone()).unwrap();
+        assert_eq!(
+            store.history(1).unwrap()[0].paths(),
+            vec![format!("{} -> {}", origin.display(), moved.display())]
+        );
+
+        std::fs::write(&origin, "new download").unwrap();
+        let outcomes = store.undo(1).unwrap();
+        assert!(
+            outcomes[0]
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("exists again")
+        );
+        assert!(moved.exists());
+
+        std::fs::remove_file(&origin).unwrap();
+        let outcomes = store.undo(1).unwrap();
+        assert_eq!(outcomes[0].error, None);
+        assert_eq!(std::fs::read_to_string(&origin).unwrap(), "pdf");
+        assert!(!moved.exists());
+    }
+
+    #[test]
+    fn tool_validates_count_and_lists_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = UndoTool::new(Arc::new(store(&dir)));
+        assert!(tool.execute(serde_json::json!({"count": 0})).is_err());
+        assert!(tool.execute(serde_json::json!({"count": 99})).is_err());
+
+        let empty = tool.execute(serde_json::json!({})).unwrap();
+        assert_eq!(empty.content, "no changes to undo");
+
+        tool.store
+            .record(
+                "edit",
+                vec![UndoFile {
+                    path: dir.path().join("a.rs"),
+                    previous: None,
+                    moved_from: None,
+                }],
+            )
+            .unwrap();
+        let listed = tool.execute(serde_json::json!({"list": true})).unwrap();
+        assert!(listed.content.starts_with("#1 edit (just now): "));
+        assert!(!tool.allowed_in_mode(ToolMode::ReadOnly));
+    }
+}
@@ -3,11 +3,13 @@
 //! Wraps the [`fae_search`] crate's async search API behind the synchronous
 //! [`Tool`] trait interface using `tokio::runtime::Handle::current().block_on()`.
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
 
+use super::network_policy::{NetworkDecision, NetworkGuard, host_of};
 use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult, truncate_output};
 
 const DEFAULT_TIMEOUT_SECS: u64 = 15;
@@ -25,6 +27,7 @@ const DEFAULT_TIMEOUT_SECS: u64 = 15;
 /// - `max_results` (integer, optional) — maximum results to return (default 5)
 pub struct WebSearchTool {
     max_bytes: usize,
+    network: Option<Arc<NetworkGuard>>,
 }
 
 impl WebSearchTool {
@@ -32,8 +35,26 @@ impl WebSearchTool {
     pub fn new() -> Self {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
+            network: None,
         }
     }
+
+    /// Only query engines `guard` allows, and drop results on denied domains.
+    pub fn with_network_guard(mut self, guard: Arc<NetworkGuard>) -> Self {
+        self.network = Some(guard);
+        self
+    }
+}
+
+/// Host each search engine is queried on.
+fn engine_host(engine: fae_search::SearchEngine) -> &'static str {
+    match engine {
+        fae_search::SearchEngine::DuckDuckGo => "html.duckduckgo.com",
+        fae_search::SearchEngine::Brave => "search.brave.com",
+        fae_search::SearchEngine::Google => "www.google.com",
+        fae_search::SearchEngine::Bing => "www.bing.com",
+        fae_search::SearchEngine::Startpage => "www.startpage.com",
+    }
 }
 
 impl Default for WebSearchTool {
@@ -68,6 +89,21 @@ impl Tool for WebSearchTool {
         })
     }
 
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "url": { "type": "string" },
+                    "snippet": { "type": "string" }
+                },
+                "required": ["title", "url"]
+            }
+        }))
+    }
+
     fn execute(&self, args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
         let query = args.get("query").and_then(|v| v.as_str()).ok_or_else(|| {
             FaeLlmError::ToolValidationError("missing required argument: query".into())
@@ -78,6 +114,8 @@ impl Tool for WebSearchTool {
                 "query must not be empty".into(),
             ));
         }
+        crate::offline::ensure_online("web search")
+            .map_err(|e| FaeLlmError::ToolExecutionError(e.to_string()))?;
 
         let max_results = args
             .get("max_results")
@@ -85,10 +123,20 @@ impl Tool for WebSearchTool {
             .map(|v| v as usize)
             .unwrap_or(5);
 
-        let config = fae_search::SearchConfig {
+        let mut config = fae_search::SearchConfig {
             max_results,
             ..Default::default()
         };
+        if let Some(guard) = &self.network {
+            config
+                .engines
+                .retain(|engine| guard.check_host(self.name(), engine_host(*engine)).is_ok());
+            if config.engines.is_empty() {
+                return Err(FaeLlmError::ToolExecutionError(
+                    "network policy blocks every search engine".into(),
+                ));
+            }
+        }
 
         // Bridge sync Tool::execute to async fae_search::search.
         // Apply an explicit per-tool timeout so behavior is bounded even when
@@ -115,7 +163,7 @@ impl Tool for WebSearchTool {
             }
         };
 
-        let results = match results_result {
+        let mut results = match results_result {
             Ok(Ok(results)) => results,
             Ok(Err(e)) => {
                 return Ok(ToolResult::failure(format!(
@@ -130,6 +178,13 @@ impl Tool for WebSearchTool {
             }
         };
 
+        if let Some(guard) = &self.network {
+            results.retain(|result| {
+                host_of(&result.url)
+                    .is_none_or(|host| guard.policy().decide(&host) != NetworkDecision::Deny)
+            });
+        }
+
         if results.is_empty() {
             return Ok(ToolResult::success(format!(
                 "No results found for \"{query}\"."
@@ -148,12 +203,24 @@ impl Tool for WebSearchTool {
             ));
         }
 
+        let structured: Vec<serde_json::Value> = results
+            .iter()
+            .map(|result| {
+                serde_json::json!({
+                    "title": result.title,
+                    "url": result.url,
+                    "snippet": result.snippet,
+                })
+            })
+            .collect();
+
         let (truncated_output, was_truncated) = truncate_output(&output, self.max_bytes);
-        if was_truncated {
-            Ok(ToolResult::success_truncated(truncated_output))
+        let result = if was_truncated {
+            ToolResult::success_truncated(truncated_output)
         } else {
-            Ok(ToolResult::success(truncated_output))
-        }
+            ToolResult::success(truncated_output)
+        };
+        Ok(result.with_structured(serde_json::Value::Array(structured)))
     }
 
     fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
@@ -216,6 +283,20 @@ mod tests {
         assert!(err.to_string().contains("empty"));
     }
 
+    #[test]
+    fn output_schema_describes_result_rows() {
+        let tool = WebSearchTool::new();
+        let schema = tool.output_schema();
+        let items = schema.as_ref().and_then(|s| s.get("items"));
+        assert_eq!(
+            items.and_then(|i| i.get("required")),
+            Some(&serde_json::json!(["title", "url"]))
+        );
+        let row = serde_json::json!([{ "title": "t", "url": "https://x", "snippet": "s" }]);
+        let schema = schema.unwrap_or_default();
+        assert!(crate::fae_llm::agent::validate_tool_output("web_search", &row, &schema).is_ok());
+    }
+
     #[test]
     fn allowed_in_both_modes() {
         let tool = WebSearchTool::new();
@@ -240,6 +321,21 @@ mod tests {
     // Integration tests with mock engines are covered in Phase 2.2/3.1.
     // The block_on bridge is validated by the tokio::test below.
 
+    #[test]
+    fn policy_blocking_all_engines_fails_without_searching() {
+        let guard = NetworkGuard::new(crate::fae_llm::tools::NetworkPolicy {
+            allow_domains: vec!["example.com".into()],
+            ..Default::default()
+        });
+        let tool = WebSearchTool::new().with_network_guard(Arc::new(guard));
+        let result = tool.execute(serde_json::json!({"query": "rust"}));
+        let err = match result {
+            Err(e) => e,
+            Ok(_) => unreachable!("no engine is allowed"),
+        };
+        assert!(err.to_string().contains("every search engine"));
+    }
+
     #[tokio::test]
     async fn execute_in_tokio_context_missing_query() {
         // Verify the tool can detect validation errors even within an async context.
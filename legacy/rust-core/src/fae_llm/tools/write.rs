@@ -2,12 +2,17 @@
 
 use crate::fae_llm::config::types::ToolMode;
 use crate::fae_llm::error::FaeLlmError;
+use crate::permissions::SharedPermissionStore;
 use std::fs::OpenOptions;
 use std::io::Write as _;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use super::path_validation::{resolve_workspace_root, validate_write_path_in_workspace};
+use super::path_validation::{
+    check_file_scope, resolve_workspace_root, validate_write_path_in_workspace,
+};
 use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult};
+use super::undo::{UndoFile, UndoStore, record_change};
 
 /// Tool that creates or overwrites files.
 ///
@@ -15,10 +20,13 @@ use super::types::{DEFAULT_MAX_BYTES, Tool, ToolResult};
 /// - `path` (string, required) — file path to write
 /// - `content` (string, required) — content to write
 ///
-/// Only available in `ToolMode::Full`.
+/// Only available in `ToolMode::Full`. When an [`UndoStore`] is set, the
+/// previous content is recorded so the write can be undone.
 pub struct WriteTool {
     max_bytes: usize,
     workspace_root: PathBuf,
+    undo: Option<Arc<UndoStore>>,
+    permissions: Option<SharedPermissionStore>,
 }
 
 impl WriteTool {
@@ -27,6 +35,8 @@ impl WriteTool {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
             workspace_root: resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")),
+            undo: None,
+            permissions: None,
         }
     }
 
@@ -35,6 +45,8 @@ impl WriteTool {
         Self {
             max_bytes,
             workspace_root: resolve_workspace_root().unwrap_or_else(|_| PathBuf::from(".")),
+            undo: None,
+            permissions: None,
         }
     }
 
@@ -43,6 +55,8 @@ impl WriteTool {
         Self {
             max_bytes: DEFAULT_MAX_BYTES,
             workspace_root,
+            undo: None,
+            permissions: None,
         }
     }
 
@@ -51,8 +65,22 @@ impl WriteTool {
         Self {
             max_bytes,
             workspace_root,
+            undo: None,
+            permissions: None,
         }
     }
+
+    /// Record the previous content of written files in `store`.
+    pub fn with_undo_store(mut self, store: Arc<UndoStore>) -> Self {
+        self.undo = Some(store);
+        self
+    }
+
+    /// Only write files the `Files` grant in `permissions` covers.
+    pub fn with_permissions(mut self, permissions: SharedPermissionStore) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
 }
 
 impl Default for WriteTool {
@@ -100,6 +128,7 @@ impl Tool for WriteTool {
             })?;
 
         let path = validate_write_path_in_workspace(path_str, &self.workspace_root)?;
+        check_file_scope(self.permissions.as_ref(), &path, true)?;
 
         // Check content size
         if content.len() > self.max_bytes {
@@ -128,6 +157,20 @@ impl Tool for WriteTool {
             Err(message) => return Ok(ToolResult::failure(message)),
         };
 
+        // Snapshot for undo: `Some(None)` means the file is new, `None` that
+        // the old content could not be read as text and cannot be restored.
+        let previous = self
+            .undo
+            .as_ref()
+            .and_then(|_| match std::fs::read_to_string(&path) {
+                Ok(old) => Some(Some(old)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Some(None),
+                Err(e) => {
+                    tracing::warn!("not recording {} for undo: {e}", path.display());
+                    None
+                }
+            });
+
         if let Err(e) = write_all_nofollow(&path, content.as_bytes()) {
             return Ok(ToolResult::failure(format!(
                 "failed to write {}: {e}",
@@ -135,6 +178,18 @@ impl Tool for WriteTool {
             )));
         }
 
+        if let Some(previous) = previous {
+            record_change(
+                self.undo.as_ref(),
+                self.name(),
+                vec![UndoFile {
+                    path: path.clone(),
+                    previous,
+                    moved_from: None,
+                }],
+            );
+        }
+
         Ok(ToolResult::success(format!(
             "wrote {} bytes to {}",
             content.len(),
@@ -364,4 +419,38 @@ mod tests {
         }));
         assert!(result.is_err(), "symlink writes should be rejected");
     }
+
+    #[test]
+    fn write_stays_inside_a_path_scoped_files_grant() {
+        use crate::permissions::{PermissionKind, PermissionScope, PermissionStore};
+
+        let dir = temp_dir();
+        let allowed = dir.path().join("allowed");
+        std::fs::create_dir(&allowed).unwrap_or_default();
+        let permissions = PermissionStore::default_shared();
+        if let Ok(mut store) = permissions.lock() {
+            store.grant_scoped(
+                PermissionKind::Files,
+                PermissionScope::Paths(vec![allowed.clone()]),
+                None,
+            );
+        }
+        let tool =
+            WriteTool::with_workspace_root(dir.path().to_path_buf()).with_permissions(permissions);
+
+        let inside = tool.execute(serde_json::json!({
+            "path": allowed.join("a.txt").to_str(),
+            "content": "ok"
+        }));
+        assert!(inside.is_ok_and(|r| r.success));
+        let outside = tool.execute(serde_json::json!({
+            "path": dir.path().join("b.txt").to_str(),
+            "content": "no"
+        }));
+        assert!(
+            outside.is_err(),
+            "writes outside the granted folders are refused"
+        );
+        assert!(!dir.path().join("b.txt").exists());
+    }
 }
@@ -185,7 +185,10 @@ impl Tool for X0xTool {
 // ---------------------------------------------------------------------------
 
 /// Build a reqwest client with timeout.
+///
+/// Fails in offline mode: x0xd relays everything to the x0x network.
 fn build_client() -> Result<reqwest::Client, String> {
+    crate::offline::ensure_online("the x0x network").map_err(|e| e.to_string())?;
     reqwest::Client::builder()
         .timeout(REQUEST_TIMEOUT)
         .build()
@@ -164,6 +164,10 @@ pub struct RequestOptions {
     /// Reasoning mode/effort.
     #[serde(default, alias = "reasoning_level")]
     pub reasoning: Option<ReasoningLevel>,
+    /// Thinking tokens the model may spend before answering, for providers
+    /// that take a budget rather than (or as well as) an effort level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<u32>,
     /// End-to-end timeout in milliseconds.
     #[serde(default)]
     pub timeout_ms: Option<u64>,
@@ -173,6 +177,27 @@ pub struct RequestOptions {
     /// Optional nucleus sampling threshold.
     #[serde(default)]
     pub top_p: Option<f64>,
+    /// Sample only from the `k` most probable tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Drop tokens less likely than this fraction of the top token's probability.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_p: Option<f64>,
+    /// Multiplicative penalty for tokens already generated (1.0 = none).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repetition_penalty: Option<f64>,
+    /// Penalty proportional to how often a token has appeared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    /// Flat penalty for any token that has appeared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
+    /// Sequences that end generation when produced.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    /// Seed for reproducible sampling, where the provider supports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
     /// Whether to request streaming responses.
     #[serde(default = "default_stream")]
     pub stream: bool,
@@ -188,9 +213,17 @@ impl Default for RequestOptions {
             temperature: Some(0.7),
             max_tokens: Some(2048),
             reasoning: Some(ReasoningLevel::Off),
+            thinking_budget: None,
             timeout_ms: None,
             headers: HashMap::new(),
             top_p: Some(0.9),
+            top_k: None,
+            min_p: None,
+            repetition_penalty: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop: Vec::new(),
+            seed: None,
             stream: true,
         }
     }
@@ -220,12 +253,60 @@ impl RequestOptions {
         self
     }
 
+    /// Set top-k sampling.
+    pub fn with_top_k(mut self, top_k: u32) -> Self {
+        self.top_k = Some(top_k);
+        self
+    }
+
+    /// Set min-p sampling.
+    pub fn with_min_p(mut self, min_p: f64) -> Self {
+        self.min_p = Some(min_p);
+        self
+    }
+
+    /// Set the repetition penalty.
+    pub fn with_repetition_penalty(mut self, penalty: f64) -> Self {
+        self.repetition_penalty = Some(penalty);
+        self
+    }
+
+    /// Set the frequency penalty.
+    pub fn with_frequency_penalty(mut self, penalty: f64) -> Self {
+        self.frequency_penalty = Some(penalty);
+        self
+    }
+
+    /// Set the presence penalty.
+    pub fn with_presence_penalty(mut self, penalty: f64) -> Self {
+        self.presence_penalty = Some(penalty);
+        self
+    }
+
+    /// Add a stop sequence.
+    pub fn with_stop(mut self, sequence: impl Into<String>) -> Self {
+        self.stop.push(sequence.into());
+        self
+    }
+
+    /// Set the sampling seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     /// Set reasoning level.
     pub fn with_reasoning(mut self, level: ReasoningLevel) -> Self {
         self.reasoning = Some(level);
         self
     }
 
+    /// Set the thinking token budget; `None` leaves the provider's limit.
+    pub fn with_thinking_budget(mut self, tokens: Option<u32>) -> Self {
+        self.thinking_budget = tokens;
+        self
+    }
+
     /// Set timeout in milliseconds.
     pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
         self.timeout_ms = Some(timeout_ms);
@@ -296,6 +377,30 @@ mod tests {
         assert!(!opts.stream);
     }
 
+    #[test]
+    fn request_options_sampling_fields_round_trip() {
+        let opts = RequestOptions::new()
+            .with_top_k(40)
+            .with_min_p(0.05)
+            .with_repetition_penalty(1.1)
+            .with_frequency_penalty(0.2)
+            .with_presence_penalty(0.1)
+            .with_stop("\nUser:")
+            .with_seed(42);
+        let json = serde_json::to_string(&opts).unwrap_or_default();
+        let parsed: RequestOptions = serde_json::from_str(&json).unwrap_or_default();
+        assert_eq!(parsed.top_k, Some(40));
+        assert_eq!(parsed.min_p, Some(0.05));
+        assert_eq!(parsed.repetition_penalty, Some(1.1));
+        assert_eq!(parsed.frequency_penalty, Some(0.2));
+        assert_eq!(parsed.presence_penalty, Some(0.1));
+        assert_eq!(parsed.stop, vec!["\nUser:".to_string()]);
+        assert_eq!(parsed.seed, Some(42));
+
+        let plain = serde_json::to_string(&RequestOptions::new()).unwrap_or_default();
+        assert!(!plain.contains("seed") && !plain.contains("stop"));
+    }
+
     #[test]
     fn request_options_accepts_legacy_reasoning_level_key() {
         let json = r#"{"reasoning_level":"low","max_tokens":128}"#;
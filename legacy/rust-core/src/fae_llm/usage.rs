@@ -18,6 +18,7 @@
 
 use super::error::FaeLlmError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::ops::AddAssign;
 
 /// Token counts for a single LLM request/response.
@@ -187,6 +188,51 @@ impl CostEstimate {
     }
 }
 
+/// Running token totals per provider.
+///
+/// Used when one answer draws on several providers (e.g. best-of-N), so
+/// the combined cost of the answer can be reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageTracker {
+    by_provider: HashMap<String, TokenUsage>,
+    requests: u64,
+}
+
+impl UsageTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one request's usage against `provider`.
+    pub fn record(&mut self, provider: &str, usage: &TokenUsage) {
+        self.by_provider
+            .entry(provider.to_owned())
+            .or_default()
+            .add(usage);
+        self.requests = self.requests.saturating_add(1);
+    }
+
+    /// Usage recorded for `provider`, if any.
+    pub fn for_provider(&self, provider: &str) -> Option<&TokenUsage> {
+        self.by_provider.get(provider)
+    }
+
+    /// Combined usage across all providers.
+    pub fn total(&self) -> TokenUsage {
+        let mut total = TokenUsage::default();
+        for usage in self.by_provider.values() {
+            total.add(usage);
+        }
+        total
+    }
+
+    /// Number of requests recorded.
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -468,4 +514,28 @@ mod tests {
         assert_eq!(total.reasoning_tokens, Some(50));
         assert_eq!(total.total(), 3350);
     }
+
+    // ── UsageTracker ──────────────────────────────────────────
+
+    #[test]
+    fn usage_tracker_combines_providers() {
+        let mut tracker = UsageTracker::new();
+        tracker.record("local", &TokenUsage::new(100, 20));
+        tracker.record(
+            "remote",
+            &TokenUsage::new(100, 40).with_reasoning_tokens(10),
+        );
+        tracker.record("local", &TokenUsage::new(50, 5));
+
+        assert_eq!(tracker.requests(), 3);
+        assert_eq!(
+            tracker.for_provider("local"),
+            Some(&TokenUsage::new(150, 25))
+        );
+        let total = tracker.total();
+        assert_eq!(total.prompt_tokens, 250);
+        assert_eq!(total.completion_tokens, 65);
+        assert_eq!(total.reasoning_tokens, Some(10));
+        assert!(tracker.for_provider("other").is_none());
+    }
 }
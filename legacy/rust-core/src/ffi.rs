@@ -26,7 +26,7 @@ use std::hint::black_box;
 use std::sync::{Mutex, Once};
 
 use crate::host::channel::{HostCommandServer, command_channel_with_events};
-use crate::host::contract::{CommandEnvelope, EventEnvelope};
+use crate::host::contract::{CommandEnvelope, CommandName, EventEnvelope};
 use crate::host::handler::FaeDeviceTransferHandler;
 use tokio::sync::broadcast;
 
@@ -554,6 +554,264 @@ pub unsafe extern "C" fn fae_core_inject_audio(
     }
 }
 
+/// Turn offline mode on (`offline != 0`) or off.
+///
+/// Equivalent to a `config.patch` command for `offline_mode`: the switch
+/// applies immediately, is saved to config, and a
+/// `pipeline.offline_mode_changed` event is emitted.
+///
+/// Returns 0 on success, -1 on failure (null handle, runtime not started, or
+/// the config could not be saved).
+///
+/// # Safety
+///
+/// `handle` must be a valid handle from `fae_core_init`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fae_core_set_offline_mode(handle: *mut c_void, offline: i32) -> i32 {
+    // SAFETY: handle is from fae_core_init.
+    let rt = match unsafe { borrow_runtime(handle) } {
+        Some(r) => r,
+        None => return -1,
+    };
+
+    match rt.started.lock() {
+        Ok(started) if *started => {}
+        _ => return -1,
+    }
+
+    let envelope = CommandEnvelope::new(
+        uuid::Uuid::new_v4().to_string(),
+        CommandName::ConfigPatch,
+        serde_json::json!({"key": "offline_mode", "value": offline != 0}),
+    );
+    let response = rt.tokio_rt.block_on(rt.client.send(envelope));
+
+    rt.tokio_rt.block_on(tokio::task::yield_now());
+    rt.drain_events();
+
+    match response {
+        Ok(resp) if resp.ok => 0,
+        _ => -1,
+    }
+}
+
+/// Start (`active != 0`) or end meeting mode.
+///
+/// Equivalent to the `meeting.start` / `meeting.stop` commands. Starting
+/// requires `consent != 0`, confirming the user agreed to everyone in the
+/// meeting being transcribed; ending it emits
+/// `pipeline.meeting_minutes_ready` once the minutes are written.
+///
+/// Returns 0 on success, -1 on failure (null handle, runtime not started, no
+/// consent, or a meeting already running).
+///
+/// # Safety
+///
+/// `handle` must be a valid handle from `fae_core_init`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fae_core_set_meeting_mode(
+    handle: *mut c_void,
+    active: i32,
+    consent: i32,
+) -> i32 {
+    // SAFETY: handle is from fae_core_init.
+    let rt = match unsafe { borrow_runtime(handle) } {
+        Some(r) => r,
+        None => return -1,
+    };
+
+    match rt.started.lock() {
+        Ok(started) if *started => {}
+        _ => return -1,
+    }
+
+    let envelope = if active != 0 {
+        CommandEnvelope::new(
+            uuid::Uuid::new_v4().to_string(),
+            CommandName::MeetingStart,
+            serde_json::json!({"consent": consent != 0}),
+        )
+    } else {
+        CommandEnvelope::new(
+            uuid::Uuid::new_v4().to_string(),
+            CommandName::MeetingStop,
+            serde_json::json!({}),
+        )
+    };
+    let response = rt.tokio_rt.block_on(rt.client.send(envelope));
+
+    rt.tokio_rt.block_on(tokio::task::yield_now());
+    rt.drain_events();
+
+    match response {
+        Ok(resp) if resp.ok => 0,
+        _ => -1,
+    }
+}
+
+/// Set the accessibility announcement level.
+///
+/// `level` is 0 (off), 1 (minimal), 2 (standard) or 3 (verbose). While on,
+/// every event is followed by an `accessibility.announcement` event with
+/// screen-reader-friendly text. Equivalent to `config.patch` commands for
+/// `accessibility.verbosity` and `accessibility.enabled`; the setting is saved
+/// to config.
+///
+/// Returns 0 on success, -1 on failure (null handle, runtime not started,
+/// level out of range, or the config could not be saved).
+///
+/// # Safety
+///
+/// `handle` must be a valid handle from `fae_core_init`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fae_core_set_accessibility(handle: *mut c_void, level: i32) -> i32 {
+    // SAFETY: handle is from fae_core_init.
+    let rt = match unsafe { borrow_runtime(handle) } {
+        Some(r) => r,
+        None => return -1,
+    };
+
+    match rt.started.lock() {
+        Ok(started) if *started => {}
+        _ => return -1,
+    }
+
+    let verbosity = match level {
+        0 => None,
+        1 => Some("minimal"),
+        2 => Some("standard"),
+        3 => Some("verbose"),
+        _ => return -1,
+    };
+    let mut patches = Vec::new();
+    if let Some(verbosity) = verbosity {
+        patches.push(serde_json::json!({"key": "accessibility.verbosity", "value": verbosity}));
+    }
+    patches.push(serde_json::json!({"key": "accessibility.enabled", "value": verbosity.is_some()}));
+
+    let mut result = 0;
+    for payload in patches {
+        let envelope = CommandEnvelope::new(
+            uuid::Uuid::new_v4().to_string(),
+            CommandName::ConfigPatch,
+            payload,
+        );
+        match rt.tokio_rt.block_on(rt.client.send(envelope)) {
+            Ok(resp) if resp.ok => {}
+            _ => {
+                result = -1;
+                break;
+            }
+        }
+    }
+
+    rt.tokio_rt.block_on(tokio::task::yield_now());
+    rt.drain_events();
+    result
+}
+
+/// Set the live captions mode.
+///
+/// `mode` is 0 (off), 1 (captions alongside speech) or 2 (captions only, no
+/// speech synthesis). While on, assistant speech is mirrored as
+/// `pipeline.caption` events with `start_ms`/`end_ms` timings relative to the
+/// utterance start. Equivalent to a `config.patch` of `captions.mode`; the
+/// setting is saved to config.
+///
+/// Returns 0 on success, -1 on failure (null handle, runtime not started,
+/// mode out of range, or the config could not be saved).
+///
+/// # Safety
+///
+/// `handle` must be a valid handle from `fae_core_init`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fae_core_set_captions(handle: *mut c_void, mode: i32) -> i32 {
+    // SAFETY: handle is from fae_core_init.
+    let rt = match unsafe { borrow_runtime(handle) } {
+        Some(r) => r,
+        None => return -1,
+    };
+
+    match rt.started.lock() {
+        Ok(started) if *started => {}
+        _ => return -1,
+    }
+
+    let mode = match mode {
+        0 => "off",
+        1 => "with_speech",
+        2 => "captions_only",
+        _ => return -1,
+    };
+    let envelope = CommandEnvelope::new(
+        uuid::Uuid::new_v4().to_string(),
+        CommandName::ConfigPatch,
+        serde_json::json!({"key": "captions.mode", "value": mode}),
+    );
+    let response = rt.tokio_rt.block_on(rt.client.send(envelope));
+
+    rt.tokio_rt.block_on(tokio::task::yield_now());
+    rt.drain_events();
+
+    match response {
+        Ok(resp) if resp.ok => 0,
+        _ => -1,
+    }
+}
+
+/// Read `text` aloud with the current voice and save it to `path`.
+///
+/// The file type follows the extension: `.wav` or `.mp3`. `text` may be
+/// plain text or SSML (`<speak>` with `<break>`, `<prosody>`, `<emphasis>`,
+/// `<p>` and `<s>`), up to 200 000 characters. Equivalent to the
+/// `speech.synthesize_to_file` command; blocks until the file is written, so
+/// call it off the main thread.
+///
+/// Returns 0 on success, -1 on failure (null handle or arguments, runtime not
+/// started, unsupported file type, text too long, or synthesis failed).
+///
+/// # Safety
+///
+/// `handle` must be a valid handle from `fae_core_init`. `text` and `path`
+/// must be null or valid null-terminated UTF-8 C strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fae_core_synthesize_to_file(
+    handle: *mut c_void,
+    text: *const c_char,
+    path: *const c_char,
+) -> i32 {
+    // SAFETY: handle is from fae_core_init.
+    let rt = match unsafe { borrow_runtime(handle) } {
+        Some(r) => r,
+        None => return -1,
+    };
+    // SAFETY: caller guarantees text and path are null or valid C strings.
+    let (Some(text), Some(path)) = (unsafe { cstr_to_str(text) }, unsafe { cstr_to_str(path) })
+    else {
+        return -1;
+    };
+
+    match rt.started.lock() {
+        Ok(started) if *started => {}
+        _ => return -1,
+    }
+
+    let envelope = CommandEnvelope::new(
+        uuid::Uuid::new_v4().to_string(),
+        CommandName::SpeechSynthesizeToFile,
+        serde_json::json!({"text": text, "path": path}),
+    );
+    let response = rt.tokio_rt.block_on(rt.client.send(envelope));
+
+    rt.tokio_rt.block_on(tokio::task::yield_now());
+    rt.drain_events();
+
+    match response {
+        Ok(resp) if resp.ok => 0,
+        _ => -1,
+    }
+}
+
 /// Free a string returned by `fae_core_send_command` or `fae_core_poll_event`.
 ///
 /// Passing null is a safe no-op.
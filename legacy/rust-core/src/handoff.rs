@@ -0,0 +1,420 @@
+//! Conversation handoff: continue a conversation on another Fae.
+//!
+//! The LLM stage publishes the live conversation after every turn
+//! ([`publish`]) along with the ids of the memories it wrote
+//! ([`note_memory`]). `handoff.offer` seals a snapshot — the messages and
+//! memory references, never models or the memory store — with a one-time
+//! key and serves it once on the local network until [`OFFER_TTL`] passes.
+//! The offer is a pairing URI, shown as a QR code:
+//!
+//! ```text
+//! fae-handoff://192.168.1.20:53124/<token>#<key>
+//! ```
+//!
+//! The receiving Fae (`handoff.accept`) connects, presents the token, and
+//! opens the bundle with the key from the URI fragment, which never crosses
+//! the network. The bundle is queued ([`take_incoming`]) and the LLM stage
+//! swaps it in as the current conversation, keeping its own system prompt.
+//!
+//! Like meeting mode, the state is process-wide.
+
+use std::io::{BufRead as _, BufReader, Read as _, Write as _};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use rand::RngCore as _;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::fae_llm::providers::message::{Message, Role};
+use crate::privacy::cipher::DataCipher;
+
+/// Bundle format version; bundles from other versions are refused.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// How long an offer can be accepted.
+pub const OFFER_TTL: Duration = Duration::from_secs(120);
+
+/// Scheme of pairing URIs.
+pub const URI_SCHEME: &str = "fae-handoff";
+
+/// Largest sealed bundle accepted.
+const MAX_BUNDLE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Most memory references carried; older ones are dropped first.
+const MAX_MEMORY_IDS: usize = 200;
+
+/// Timeout for connecting and for each read or write of a transfer.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the offer listener checks for expiry while idle.
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+
+static CONVERSATION: Mutex<Conversation> = Mutex::new(Conversation {
+    messages: Vec::new(),
+    memory_ids: Vec::new(),
+});
+static OFFER_GENERATION: AtomicU64 = AtomicU64::new(0);
+static INCOMING: Mutex<Option<HandoffBundle>> = Mutex::new(None);
+static RECEIVED: LazyLock<watch::Sender<u64>> = LazyLock::new(|| watch::Sender::new(0));
+
+/// The live conversation, as last published by the LLM stage.
+struct Conversation {
+    messages: Vec<Message>,
+    memory_ids: Vec<String>,
+}
+
+/// A conversation in transit between two Fae instances.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffBundle {
+    pub version: u32,
+    /// Name of the device the conversation came from.
+    pub source: String,
+    /// Unix epoch seconds when the bundle was made.
+    pub created_at: u64,
+    /// The conversation without its system prompt.
+    pub messages: Vec<Message>,
+    /// Ids of the memories written during the conversation.
+    pub memory_ids: Vec<String>,
+}
+
+/// An open offer to hand the conversation off.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HandoffOffer {
+    /// Pairing URI for the receiving device, usually shown as a QR code.
+    pub uri: String,
+    /// Unix epoch seconds after which the offer is gone.
+    pub expires_at: u64,
+    pub messages: usize,
+    pub memory_ids: usize,
+}
+
+/// A conversation received from another device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HandoffReceipt {
+    pub source: String,
+    pub messages: usize,
+    pub memory_ids: usize,
+}
+
+/// Record the live conversation. System messages are left out: each Fae
+/// keeps its own prompt.
+pub fn publish(history: &[Message]) {
+    if let Ok(mut conversation) = CONVERSATION.lock() {
+        conversation.messages = history
+            .iter()
+            .filter(|m| m.role != Role::System)
+            .cloned()
+            .collect();
+        if conversation.messages.is_empty() {
+            conversation.memory_ids.clear();
+        }
+    }
+}
+
+/// Record that the conversation wrote memory `id`.
+pub fn note_memory(id: &str) {
+    if let Ok(mut conversation) = CONVERSATION.lock() {
+        let ids = &mut conversation.memory_ids;
+        ids.retain(|existing| existing != id);
+        ids.push(id.to_owned());
+        if ids.len() > MAX_MEMORY_IDS {
+            ids.drain(..ids.len() - MAX_MEMORY_IDS);
+        }
+    }
+}
+
+/// Snapshot the live conversation for handing off.
+pub fn bundle(source: &str) -> Result<HandoffBundle, String> {
+    let conversation = CONVERSATION
+        .lock()
+        .map_err(|_| "handoff state lock poisoned".to_owned())?;
+    if conversation.messages.is_empty() {
+        return Err("there is no conversation to hand off".to_owned());
+    }
+    Ok(HandoffBundle {
+        version: BUNDLE_VERSION,
+        source: source.to_owned(),
+        created_at: epoch_secs(SystemTime::now()),
+        messages: conversation.messages.clone(),
+        memory_ids: conversation.memory_ids.clone(),
+    })
+}
+
+/// Offer the live conversation to another device, replacing any open offer.
+///
+/// The listener is bound to `host`, or to this machine's local network
+/// address when `None`.
+pub fn offer(source: &str, host: Option<IpAddr>) -> Result<HandoffOffer, String> {
+    offer_bundle(bundle(source)?, host)
+}
+
+fn offer_bundle(bundle: HandoffBundle, host: Option<IpAddr>) -> Result<HandoffOffer, String> {
+    let plain = serde_json::to_vec(&bundle)
+        .map_err(|e| format!("failed to encode the conversation: {e}"))?;
+    let mut key = [0u8; 32];
+    let mut token = [0u8; 16];
+    rand::rngs::OsRng.fill_bytes(&mut key);
+    rand::rngs::OsRng.fill_bytes(&mut token);
+    let sealed = DataCipher::from_key(&key)
+        .seal(&plain)
+        .map_err(|e| format!("failed to seal the conversation: {e}"))?;
+
+    let host = host.unwrap_or_else(local_ip);
+    let listener = TcpListener::bind((host, 0))
+        .and_then(|l| l.set_nonblocking(true).map(|()| l))
+        .map_err(|e| format!("failed to listen for the other device: {e}"))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|e| format!("failed to listen for the other device: {e}"))?;
+    let uri = format!(
+        "{URI_SCHEME}://{addr}/{}#{}",
+        URL_SAFE_NO_PAD.encode(token),
+        URL_SAFE_NO_PAD.encode(key)
+    );
+    key.fill(0);
+
+    let generation = OFFER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let token = URL_SAFE_NO_PAD.encode(token);
+    let deadline = Instant::now() + OFFER_TTL;
+    std::thread::Builder::new()
+        .name("fae-handoff-offer".to_owned())
+        .spawn(move || serve(&listener, &token, &sealed, deadline, generation))
+        .map_err(|e| format!("failed to start the handoff listener: {e}"))?;
+    info!(%addr, messages = bundle.messages.len(), "conversation handoff offered");
+
+    Ok(HandoffOffer {
+        uri,
+        expires_at: epoch_secs(SystemTime::now() + OFFER_TTL),
+        messages: bundle.messages.len(),
+        memory_ids: bundle.memory_ids.len(),
+    })
+}
+
+/// Withdraw the open offer, if any.
+pub fn cancel_offer() {
+    OFFER_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Serve `sealed` to the first connection presenting `token`, until the
+/// deadline passes or a newer offer replaces this one.
+fn serve(listener: &TcpListener, token: &str, sealed: &[u8], deadline: Instant, generation: u64) {
+    while Instant::now() < deadline && OFFER_GENERATION.load(Ordering::SeqCst) == generation {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL);
+                continue;
+            }
+            Err(e) => {
+                warn!("handoff listener failed: {e}");
+                return;
+            }
+        };
+        match send_bundle(stream, token, sealed) {
+            Ok(true) => {
+                info!("conversation handed off");
+                return;
+            }
+            Ok(false) => warn!("handoff connection presented the wrong token"),
+            Err(e) => warn!("handoff transfer failed: {e}"),
+        }
+    }
+}
+
+/// Send `sealed` if the peer presents `token`. Returns whether it did.
+fn send_bundle(stream: TcpStream, token: &str, sealed: &[u8]) -> std::io::Result<bool> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new((&stream).take(128)).read_line(&mut line)?;
+    if line.trim_end() != token {
+        return Ok(false);
+    }
+    let mut stream = stream;
+    stream.write_all(&(sealed.len() as u32).to_be_bytes())?;
+    stream.write_all(sealed)?;
+    stream.flush()?;
+    Ok(true)
+}
+
+/// Fetch the conversation offered at pairing `uri` and queue it to continue
+/// here.
+pub fn accept(uri: &str) -> Result<HandoffReceipt, String> {
+    let (addr, token, mut key) = parse_uri(uri)?;
+    let sealed = fetch(addr, &token).map_err(|e| format!("failed to reach {addr}: {e}"))?;
+    let opened = DataCipher::from_key(&key).open(&sealed);
+    key.fill(0);
+    let plain = opened.map_err(|_| "the conversation could not be opened".to_owned())?;
+    let mut bundle: HandoffBundle = serde_json::from_slice(&plain)
+        .map_err(|e| format!("the conversation is malformed: {e}"))?;
+    if bundle.version != BUNDLE_VERSION {
+        return Err(format!(
+            "unsupported handoff version {} (expected {BUNDLE_VERSION})",
+            bundle.version
+        ));
+    }
+    bundle.messages.retain(|m| m.role != Role::System);
+
+    let receipt = HandoffReceipt {
+        source: bundle.source.clone(),
+        messages: bundle.messages.len(),
+        memory_ids: bundle.memory_ids.len(),
+    };
+    *INCOMING
+        .lock()
+        .map_err(|_| "handoff state lock poisoned".to_owned())? = Some(bundle);
+    RECEIVED.send_modify(|count| *count += 1);
+    info!(
+        source = receipt.source,
+        messages = receipt.messages,
+        "conversation handoff received"
+    );
+    Ok(receipt)
+}
+
+fn fetch(addr: SocketAddr, token: &str) -> std::io::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect_timeout(&addr, IO_TIMEOUT)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    stream.write_all(format!("{token}\n").as_bytes())?;
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_BUNDLE_BYTES {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("conversation is {len} bytes; the limit is {MAX_BUNDLE_BYTES}"),
+        ));
+    }
+    let mut sealed = vec![0u8; len];
+    stream.read_exact(&mut sealed)?;
+    Ok(sealed)
+}
+
+/// Split a pairing URI into address, token and key.
+fn parse_uri(uri: &str) -> Result<(SocketAddr, String, [u8; 32]), String> {
+    let invalid = || format!("not a {URI_SCHEME} pairing link");
+    let rest = uri
+        .trim()
+        .strip_prefix(URI_SCHEME)
+        .and_then(|r| r.strip_prefix("://"))
+        .ok_or_else(invalid)?;
+    let (rest, key) = rest.split_once('#').ok_or_else(invalid)?;
+    let (addr, token) = rest.split_once('/').ok_or_else(invalid)?;
+    let addr: SocketAddr = addr.parse().map_err(|_| invalid())?;
+    let key: [u8; 32] = URL_SAFE_NO_PAD
+        .decode(key)
+        .ok()
+        .and_then(|k| k.try_into().ok())
+        .ok_or_else(invalid)?;
+    if token.is_empty() {
+        return Err(invalid());
+    }
+    Ok((addr, token.to_owned(), key))
+}
+
+/// Watch for received conversations; the value counts arrivals.
+pub fn subscribe() -> watch::Receiver<u64> {
+    RECEIVED.subscribe()
+}
+
+/// Take the conversation last received, to continue it.
+pub fn take_incoming() -> Option<HandoffBundle> {
+    INCOMING.lock().ok()?.take()
+}
+
+/// This machine's address on the local network. Connecting a UDP socket
+/// picks the outgoing interface without sending anything.
+fn local_ip() -> IpAddr {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9))?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+}
+
+fn epoch_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn sample_bundle() -> HandoffBundle {
+        HandoffBundle {
+            version: BUNDLE_VERSION,
+            source: "Desktop".to_owned(),
+            created_at: 1_790_000_000,
+            messages: vec![
+                Message::system("desktop prompt"),
+                Message::user("Plan my trip to Lisbon"),
+                Message::assistant("Sure — when are you going?"),
+            ],
+            memory_ids: vec!["mem-1".to_owned()],
+        }
+    }
+
+    #[test]
+    fn offered_conversation_is_received_once() {
+        let mut received = subscribe();
+        let offer =
+            offer_bundle(sample_bundle(), Some(IpAddr::V4(Ipv4Addr::LOCALHOST))).expect("offer");
+        assert!(offer.uri.starts_with("fae-handoff://127.0.0.1:"));
+        assert_eq!(offer.messages, 3);
+
+        let receipt = accept(&offer.uri).expect("accept");
+        assert_eq!(
+            receipt,
+            HandoffReceipt {
+                source: "Desktop".to_owned(),
+                messages: 2,
+                memory_ids: 1,
+            }
+        );
+        assert!(received.has_changed().expect("watch"));
+        received.mark_unchanged();
+        let bundle = take_incoming().expect("incoming");
+        assert!(bundle.messages.iter().all(|m| m.role != Role::System));
+        assert_eq!(bundle.memory_ids, vec!["mem-1"]);
+        assert!(take_incoming().is_none());
+
+        // The offer is served once.
+        assert!(accept(&offer.uri).is_err());
+
+        // A bundle sealed with another key does not open.
+        let offer =
+            offer_bundle(sample_bundle(), Some(IpAddr::V4(Ipv4Addr::LOCALHOST))).expect("offer");
+        let (head, _) = offer.uri.split_once('#').expect("fragment");
+        let forged = format!("{head}#{}", URL_SAFE_NO_PAD.encode([7u8; 32]));
+        assert!(accept(&forged).is_err());
+        assert!(take_incoming().is_none());
+    }
+
+    #[test]
+    fn pairing_links_are_parsed_strictly() {
+        let key = URL_SAFE_NO_PAD.encode([1u8; 32]);
+        let (addr, token, parsed) =
+            parse_uri(&format!("fae-handoff://10.0.0.5:4242/abc#{key}")).expect("parse");
+        assert_eq!(addr.to_string(), "10.0.0.5:4242");
+        assert_eq!(token, "abc");
+        assert_eq!(parsed, [1u8; 32]);
+
+        assert!(parse_uri("https://127.0.0.1:80/token#key").is_err());
+        assert!(parse_uri(&format!("fae-handoff://127.0.0.1:80/#{key}")).is_err());
+        assert!(parse_uri("fae-handoff://127.0.0.1:80/abc#short").is_err());
+    }
+}
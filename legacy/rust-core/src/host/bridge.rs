@@ -0,0 +1,244 @@
+//! Requests from the core to the native shell.
+//!
+//! Tools ask the shell for native UI — a file picker, a notification, the
+//! share sheet — through the process-wide [`host_bridge`]. While the runtime
+//! runs, the command handler forwards each request as a `host.request`
+//! event and completes it when the matching `host.respond` command arrives.
+
+use crate::error::{Result, SpeechError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, SyncSender, sync_channel};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// How long the user has to pick a file or finish sharing.
+const INTERACTIVE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long the shell has to post a notification.
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A native UI affordance the core asks the shell for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HostRequest {
+    /// Let the user choose a file or folder.
+    PickFile {
+        /// Message shown in the picker.
+        prompt: String,
+        /// Choose a folder instead of a file.
+        #[serde(default)]
+        directory: bool,
+        /// File extensions to offer, e.g. `pdf`; empty allows any.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allowed_types: Vec<String>,
+    },
+    /// Post a native notification.
+    Notify { title: String, body: String },
+    /// Open the share sheet with text, a link, or a file.
+    Share {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<PathBuf>,
+    },
+}
+
+impl HostRequest {
+    /// How long to wait for the shell's answer.
+    pub fn timeout(&self) -> Duration {
+        match self {
+            Self::Notify { .. } => NOTIFY_TIMEOUT,
+            Self::PickFile { .. } | Self::Share { .. } => INTERACTIVE_TIMEOUT,
+        }
+    }
+}
+
+/// The shell's answer to a [`HostRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum HostResponse {
+    /// The user picked `path`.
+    Picked {
+        path: PathBuf,
+        /// Base64 security-scoped bookmark for reopening `path` after a
+        /// restart, when the shell is sandboxed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bookmark: Option<String>,
+    },
+    /// The notification was posted or the content shared.
+    Done,
+    /// The user dismissed the picker or share sheet.
+    Cancelled,
+    /// The shell could not carry out the request.
+    Failed { error: String },
+}
+
+/// Routes [`HostRequest`]s to the shell and their answers back.
+pub struct HostBridge {
+    next_id: u64,
+    pending: BTreeMap<u64, SyncSender<HostResponse>>,
+    outbox: Option<mpsc::UnboundedSender<(u64, HostRequest)>>,
+}
+
+static HOST_BRIDGE: Mutex<HostBridge> = Mutex::new(HostBridge::new());
+
+/// The process-wide host bridge.
+pub fn host_bridge() -> MutexGuard<'static, HostBridge> {
+    HOST_BRIDGE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl HostBridge {
+    const fn new() -> Self {
+        Self {
+            next_id: 1,
+            pending: BTreeMap::new(),
+            outbox: None,
+        }
+    }
+
+    /// Attach the channel the command handler drains into `host.request`
+    /// events, or detach it. Detaching fails every pending request.
+    pub fn set_outbox(&mut self, outbox: Option<mpsc::UnboundedSender<(u64, HostRequest)>>) {
+        if outbox.is_none() {
+            self.pending.clear();
+        }
+        self.outbox = outbox;
+    }
+
+    /// Whether a shell is attached to answer requests.
+    pub fn is_attached(&self) -> bool {
+        self.outbox.as_ref().is_some_and(|tx| !tx.is_closed())
+    }
+
+    /// Hand `request` to the shell; the receiver yields its answer.
+    ///
+    /// Returns `None` when no shell is attached.
+    pub fn submit(&mut self, request: HostRequest) -> Option<(u64, Receiver<HostResponse>)> {
+        let outbox = self.outbox.as_ref()?;
+        let id = self.next_id;
+        self.next_id += 1;
+        outbox.send((id, request)).ok()?;
+        let (tx, rx) = sync_channel(1);
+        self.pending.insert(id, tx);
+        Some((id, rx))
+    }
+
+    /// Deliver the shell's answer to request `id`.
+    ///
+    /// Returns `false` when `id` is not pending (already answered or timed out).
+    pub fn resolve(&mut self, id: u64, response: HostResponse) -> bool {
+        self.pending
+            .remove(&id)
+            .is_some_and(|tx| tx.try_send(response).is_ok())
+    }
+
+    /// Stop waiting for request `id`.
+    pub fn withdraw(&mut self, id: u64) {
+        self.pending.remove(&id);
+    }
+}
+
+/// Send `request` to the shell and block until it answers.
+///
+/// Call from tool execution, not from async code.
+///
+/// # Errors
+///
+/// Returns an error when no shell is attached, the shell detaches, or it
+/// does not answer within [`HostRequest::timeout`].
+pub fn ask_host(request: HostRequest) -> Result<HostResponse> {
+    let timeout = request.timeout();
+    let Some((id, rx)) = host_bridge().submit(request) else {
+        return Err(SpeechError::Pipeline(
+            "no native shell is connected".to_owned(),
+        ));
+    };
+    match rx.recv_timeout(timeout) {
+        Ok(response) => Ok(response),
+        Err(RecvTimeoutError::Timeout) => {
+            host_bridge().withdraw(id);
+            Err(SpeechError::Pipeline(format!(
+                "native shell did not answer within {}s",
+                timeout.as_secs()
+            )))
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(SpeechError::Pipeline(
+            "native shell disconnected before answering".to_owned(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn request_and_response_wire_format() {
+        let request = HostRequest::PickFile {
+            prompt: "Choose a report".to_owned(),
+            directory: false,
+            allowed_types: vec!["pdf".to_owned()],
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["kind"], "pick_file");
+        assert_eq!(json["allowed_types"][0], "pdf");
+
+        let picked: HostResponse = serde_json::from_value(serde_json::json!({
+            "request_id": "7",
+            "status": "picked",
+            "path": "/Users/me/report.pdf"
+        }))
+        .unwrap();
+        assert_eq!(
+            picked,
+            HostResponse::Picked {
+                path: PathBuf::from("/Users/me/report.pdf"),
+                bookmark: None,
+            }
+        );
+    }
+
+    #[test]
+    fn submitted_requests_reach_the_outbox_and_resolve_once() {
+        let mut bridge = HostBridge::new();
+        let notify = HostRequest::Notify {
+            title: "Done".to_owned(),
+            body: "Backup finished".to_owned(),
+        };
+        assert!(bridge.submit(notify.clone()).is_none());
+
+        let (tx, mut outbox) = mpsc::unbounded_channel();
+        bridge.set_outbox(Some(tx));
+        let (id, rx) = bridge.submit(notify.clone()).unwrap();
+        assert_eq!(outbox.try_recv().unwrap(), (id, notify));
+
+        assert!(bridge.resolve(id, HostResponse::Done));
+        assert_eq!(rx.try_recv().unwrap(), HostResponse::Done);
+        assert!(!bridge.resolve(id, HostResponse::Done));
+    }
+
+    #[test]
+    fn detaching_fails_pending_requests() {
+        let mut bridge = HostBridge::new();
+        let (tx, _outbox) = mpsc::unbounded_channel();
+        bridge.set_outbox(Some(tx));
+        let (_, rx) = bridge
+            .submit(HostRequest::Share {
+                text: Some("hello".to_owned()),
+                url: None,
+                path: None,
+            })
+            .unwrap();
+
+        bridge.set_outbox(None);
+        assert!(!bridge.is_attached());
+        assert!(rx.recv_timeout(Duration::from_millis(10)).is_err());
+    }
+}
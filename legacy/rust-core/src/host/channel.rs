@@ -1,9 +1,13 @@
 //! Host command channel and router for native shell integrations.
 
 use crate::error::{Result, SpeechError};
+use crate::fae_llm::config::types::ProviderConfig;
+use crate::host::bridge::HostResponse;
 use crate::host::contract::{CommandEnvelope, CommandName, EventEnvelope, ResponseEnvelope};
 use crate::onboarding::OnboardingPhase;
+use crate::platform::lifecycle::PowerEvent;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tokio::sync::{broadcast, mpsc, oneshot};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -63,10 +67,23 @@ pub trait DeviceTransferHandler: Send + Sync + 'static {
     fn grant_capability(&self, _capability: &str, _scope: Option<&str>) -> Result<()> {
         Ok(())
     }
+    /// Grant a capability that lapses after `expires_in_secs`.
+    fn grant_capability_for(
+        &self,
+        capability: &str,
+        scope: Option<&str>,
+        _expires_in_secs: u64,
+    ) -> Result<()> {
+        self.grant_capability(capability, scope)
+    }
     /// Deny (revoke) a previously granted capability, persisting to config.
     fn deny_capability(&self, _capability: &str, _scope: Option<&str>) -> Result<()> {
         Ok(())
     }
+    /// List every capability grant with its scope, timestamps, and expiry.
+    fn list_capabilities(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({"grants": []}))
+    }
     /// Query the current onboarding state (onboarded flag + current phase).
     fn query_onboarding_state(&self) -> Result<serde_json::Value> {
         Ok(serde_json::json!({"onboarded": false, "phase": "welcome"}))
@@ -122,11 +139,50 @@ pub trait DeviceTransferHandler: Send + Sync + 'static {
     fn onboarding_voiceprint_reset(&self) -> Result<()> {
         Ok(())
     }
+    /// Query the audio calibration session and stored profile.
+    fn query_onboarding_calibration_state(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({"active": false, "calibrated": false}))
+    }
+    /// Start (or restart) audio calibration for the active input device.
+    fn onboarding_calibration_start(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({"active": true, "step": "ambient_noise"}))
+    }
+    /// Record the current calibration step in the background.
+    fn onboarding_calibration_measure(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({"accepted": true}))
+    }
+    /// Save the calibration profile for the active input device.
+    fn onboarding_calibration_finish(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({"accepted": true}))
+    }
+    /// Abandon calibration and remove the active device's profile.
+    fn onboarding_calibration_reset(&self) -> Result<()> {
+        Ok(())
+    }
+    /// Start the first-run setup check in the background.
+    fn onboarding_setup_check(
+        &self,
+        _providers: BTreeMap<String, ProviderConfig>,
+        _load_model: bool,
+    ) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({"accepted": true}))
+    }
+    /// Find provider profiles that can be imported from other tools.
+    fn onboarding_llm_import_discover(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({"providers": []}))
+    }
+    /// Import the discovered profiles from `sources`, returning the added
+    /// provider ids.
+    fn onboarding_llm_import_apply(&self, _sources: &[String]) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
     /// Reload custom skills from `~/.fae/skills/`.
     fn reload_skills(&self) -> Result<()> {
         Ok(())
     }
-    fn request_conversation_inject_text(&self, _text: &str) -> Result<()> {
+    /// Queue typed text, with any attached images (encoded bytes), as the
+    /// next user turn.
+    fn request_conversation_inject_text(&self, _text: &str, _images: &[Vec<u8>]) -> Result<()> {
         Ok(())
     }
     /// Inject raw PCM audio from a companion device into the pipeline.
@@ -151,6 +207,10 @@ pub trait DeviceTransferHandler: Send + Sync + 'static {
     fn request_runtime_stop(&self) -> Result<()> {
         Ok(())
     }
+    /// Suspend the runtime before the system sleeps, or resume it on wake.
+    fn request_system_lifecycle(&self, _event: PowerEvent) -> Result<()> {
+        Ok(())
+    }
     fn query_runtime_status(&self) -> Result<serde_json::Value> {
         Ok(serde_json::json!({"status": "unknown"}))
     }
@@ -162,6 +222,10 @@ pub trait DeviceTransferHandler: Send + Sync + 'static {
     ) -> Result<()> {
         Ok(())
     }
+    /// Deliver the shell's answer to a `host.request` event.
+    fn respond_host_request(&self, _request_id: &str, _response: HostResponse) -> Result<()> {
+        Ok(())
+    }
     fn query_scheduler_list(&self) -> Result<serde_json::Value> {
         Ok(serde_json::json!({"tasks": []}))
     }
@@ -177,6 +241,10 @@ pub trait DeviceTransferHandler: Send + Sync + 'static {
     fn request_scheduler_trigger_now(&self, _id: &str) -> Result<()> {
         Ok(())
     }
+    /// Carry out the action the user picked on a `scheduler.prompt` event.
+    fn respond_scheduler_prompt(&self, _action: &str) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({"handled": false}))
+    }
     fn query_config_get(&self, _key: Option<&str>) -> Result<serde_json::Value> {
         Ok(serde_json::json!({}))
     }
@@ -256,6 +324,127 @@ pub trait DeviceTransferHandler: Send + Sync + 'static {
     ) -> Result<Vec<crate::skills::discovery::SkillSearchResult>> {
         Ok(Vec::new())
     }
+    /// Ask the user to confirm a personal data export and/or wipe.
+    ///
+    /// Returns immediately with the pending approval request ID; the work
+    /// runs after the user approves and is reported via `data.forget.*` events.
+    fn request_data_forget(&self, _export: bool, _wipe: bool) -> Result<serde_json::Value> {
+        Err(crate::SpeechError::Privacy(
+            "data.forget: not implemented".to_owned(),
+        ))
+    }
+    /// Search past conversation sessions by title and content.
+    fn conversation_sessions_search(
+        &self,
+        _query: &str,
+        _limit: usize,
+    ) -> Result<Vec<crate::fae_llm::session::SessionSearchHit>> {
+        Ok(Vec::new())
+    }
+    /// Thinking transcripts saved with session `session_id`.
+    fn conversation_session_thinking(
+        &self,
+        _session_id: &str,
+    ) -> Result<Vec<crate::fae_llm::session::ThinkingEntry>> {
+        Ok(Vec::new())
+    }
+    /// Statistics for a conversation, or the current one when `conversation_id`
+    /// is `None`.
+    fn conversation_analytics(
+        &self,
+        _conversation_id: Option<&str>,
+    ) -> Result<Option<crate::analytics::ConversationStats>> {
+        Ok(None)
+    }
+    /// Statistics for the most recent conversations, newest first.
+    fn conversation_analytics_list(
+        &self,
+        _limit: usize,
+    ) -> Result<Vec<crate::analytics::ConversationStats>> {
+        Ok(Vec::new())
+    }
+    /// Record a thumbs-down on the last reply as a correction, with the right
+    /// answer when one is given.
+    ///
+    /// Returns whether there was a reply to record.
+    fn record_conversation_feedback(&self, _right_answer: Option<&str>) -> Result<bool> {
+        Ok(false)
+    }
+    /// Write every recorded correction to `path` as JSON Lines.
+    ///
+    /// Returns the number of corrections written.
+    fn export_corrections(&self, _path: &std::path::Path) -> Result<usize> {
+        Ok(0)
+    }
+    /// Per-variant summary of an experiment, or of the configured one when
+    /// `experiment_id` is `None`.
+    fn experiment_report(
+        &self,
+        _experiment_id: Option<&str>,
+    ) -> Result<Option<crate::experiments::ExperimentReport>> {
+        Ok(None)
+    }
+    /// Start meeting mode, which the user has consented to.
+    ///
+    /// Returns the meeting id, or `None` when meetings are not supported.
+    fn start_meeting(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+    /// End meeting mode. Returns the id of the meeting that ended, or `None`
+    /// when none was running.
+    fn stop_meeting(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+    /// Speak `text` (plain or SSML) with the current voice into the `.wav`
+    /// or `.mp3` file at `path`.
+    ///
+    /// Returns the written file, or `None` when speech synthesis is not
+    /// supported.
+    fn synthesize_to_file(
+        &self,
+        _text: &str,
+        _path: &std::path::Path,
+    ) -> Result<Option<crate::tts::export::AudioExport>> {
+        Ok(None)
+    }
+    /// Capabilities of the configured TTS engine, or `None` when speech
+    /// synthesis is not supported.
+    fn tts_capabilities(&self) -> Result<Option<crate::tts::TtsCapabilities>> {
+        Ok(None)
+    }
+    /// Offer the current conversation to another device, naming this one
+    /// `device`.
+    ///
+    /// Returns the offer, or `None` when handoff is not supported.
+    fn offer_handoff(&self, _device: &str) -> Result<Option<crate::handoff::HandoffOffer>> {
+        Ok(None)
+    }
+    /// Fetch the conversation offered at pairing `uri` and continue it here.
+    ///
+    /// Returns what was received, or `None` when handoff is not supported.
+    fn accept_handoff(&self, _uri: &str) -> Result<Option<crate::handoff::HandoffReceipt>> {
+        Ok(None)
+    }
+    /// Current state of sync with the user's other devices.
+    ///
+    /// Returns `None` when sync is not supported.
+    fn sync_status(&self) -> Result<Option<crate::sync::SyncStatus>> {
+        Ok(None)
+    }
+    /// Sync with the user's other devices now.
+    ///
+    /// Returns what the sync did, or `None` when sync is not supported.
+    fn sync_now(&self) -> Result<Option<crate::sync::SyncReport>> {
+        Ok(None)
+    }
+    /// Store the sync key entered from another device, or generate one when
+    /// `key` is `None`.
+    ///
+    /// Returns the response payload (with the generated key), or `None` when
+    /// sync is not supported.
+    fn set_sync_key(&self, _key: Option<&str>) -> Result<Option<serde_json::Value>> {
+        Ok(None)
+    }
     /// Generate a Python skill from a plain-English intent.
     ///
     /// Returns a JSON value representing either a proposal or an existing match.
@@ -420,6 +609,7 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
             CommandName::CapabilityRequest => self.handle_capability_request(envelope),
             CommandName::CapabilityGrant => self.handle_capability_grant(envelope),
             CommandName::CapabilityDeny => self.handle_capability_deny(envelope),
+            CommandName::CapabilityList => self.handle_capability_list(envelope),
             CommandName::OnboardingGetState => self.handle_onboarding_get_state(envelope),
             CommandName::OnboardingAdvance => self.handle_onboarding_advance(envelope),
             CommandName::OnboardingComplete => self.handle_onboarding_complete(envelope),
@@ -436,6 +626,29 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
             CommandName::OnboardingVoiceprintReset => {
                 self.handle_onboarding_voiceprint_reset(envelope)
             }
+            CommandName::OnboardingCalibrationGetState => {
+                self.handle_onboarding_calibration_get_state(envelope)
+            }
+            CommandName::OnboardingCalibrationStart => {
+                self.handle_onboarding_calibration_start(envelope)
+            }
+            CommandName::OnboardingCalibrationMeasure => {
+                self.handle_onboarding_calibration_measure(envelope)
+            }
+            CommandName::OnboardingCalibrationFinish => {
+                self.handle_onboarding_calibration_finish(envelope)
+            }
+            CommandName::OnboardingCalibrationReset => {
+                self.handle_onboarding_calibration_reset(envelope)
+            }
+            CommandName::OnboardingSetupCheck => self.handle_onboarding_setup_check(envelope),
+            CommandName::OnboardingLlmImportDiscover => {
+                let payload = self.handler.onboarding_llm_import_discover()?;
+                Ok(ResponseEnvelope::ok(envelope.request_id.clone(), payload))
+            }
+            CommandName::OnboardingLlmImportApply => {
+                self.handle_onboarding_llm_import_apply(envelope)
+            }
             CommandName::OnboardingSetContactInfo => {
                 self.handle_onboarding_set_contact_info(envelope)
             }
@@ -463,6 +676,7 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
             CommandName::SkillHealthStatusCmd => self.handle_skill_health_status(envelope),
             CommandName::SkillChannelInstall => self.handle_skill_channel_install(envelope),
             CommandName::SkillChannelList => self.handle_skill_channel_list(envelope),
+            CommandName::SttVocabularySet => self.handle_stt_vocabulary_set(envelope),
             CommandName::ConversationInjectText => self.handle_conversation_inject_text(envelope),
             CommandName::ConversationInjectAudio => self.handle_conversation_inject_audio(envelope),
             CommandName::ConversationGateSet => self.handle_conversation_gate_set(envelope),
@@ -470,18 +684,50 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
             CommandName::ConversationLinkDetected => {
                 self.handle_conversation_link_detected(envelope)
             }
+            CommandName::ConversationSessionsSearch => {
+                self.handle_conversation_sessions_search(envelope)
+            }
+            CommandName::ConversationSessionsThinking => {
+                self.handle_conversation_sessions_thinking(envelope)
+            }
+            CommandName::ConversationAnalyticsGet => {
+                self.handle_conversation_analytics_get(envelope)
+            }
+            CommandName::ConversationAnalyticsList => {
+                self.handle_conversation_analytics_list(envelope)
+            }
+            CommandName::ConversationFeedback => self.handle_conversation_feedback(envelope),
+            CommandName::ConversationCorrectionsExport => {
+                self.handle_conversation_corrections_export(envelope)
+            }
+            CommandName::ExperimentReport => self.handle_experiment_report(envelope),
+            CommandName::UndoHistory => self.handle_undo_history(envelope),
+            CommandName::MeetingStart => self.handle_meeting_start(envelope),
+            CommandName::MeetingStop => self.handle_meeting_stop(envelope),
+            CommandName::SpeechSynthesizeToFile => self.handle_speech_synthesize_to_file(envelope),
+            CommandName::SpeechCapabilities => self.handle_speech_capabilities(envelope),
+            CommandName::HandoffOffer => self.handle_handoff_offer(envelope),
+            CommandName::HandoffAccept => self.handle_handoff_accept(envelope),
+            CommandName::SyncStatus => self.handle_sync_status(envelope),
+            CommandName::SyncNow => self.handle_sync_now(envelope),
+            CommandName::SyncSetKey => self.handle_sync_set_key(envelope),
             CommandName::RuntimeStart => self.handle_runtime_start(envelope),
             CommandName::RuntimeStop => self.handle_runtime_stop(envelope),
             CommandName::RuntimeStatus => self.handle_runtime_status(envelope),
+            CommandName::RuntimeFootprint => self.handle_runtime_footprint(envelope),
+            CommandName::SystemLifecycle => self.handle_system_lifecycle(envelope),
             CommandName::ApprovalRespond => self.handle_approval_respond(envelope),
+            CommandName::HostRespond => self.handle_host_respond(envelope),
             CommandName::SchedulerList => self.handle_scheduler_list(envelope),
             CommandName::SchedulerCreate => self.handle_scheduler_create(envelope),
             CommandName::SchedulerUpdate => self.handle_scheduler_update(envelope),
             CommandName::SchedulerDelete => self.handle_scheduler_delete(envelope),
             CommandName::SchedulerTriggerNow => self.handle_scheduler_trigger_now(envelope),
+            CommandName::SchedulerPromptRespond => self.handle_scheduler_prompt_respond(envelope),
             CommandName::ConfigGet => self.handle_config_get(envelope),
             CommandName::ConfigPatch => self.handle_config_patch(envelope),
             CommandName::DataDeleteAll => self.handle_data_delete_all(envelope),
+            CommandName::DataForget => self.handle_data_forget(envelope),
         }
     }
 
@@ -656,15 +902,25 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
 
     fn handle_capability_grant(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
         let grant = parse_capability_action(&envelope.payload, "capability.grant")?;
-        self.handler
-            .grant_capability(&grant.capability, grant.scope.as_deref())?;
+        let expires_in_secs = parse_optional_expiry(&envelope.payload)?;
+        match expires_in_secs {
+            Some(secs) => self.handler.grant_capability_for(
+                &grant.capability,
+                grant.scope.as_deref(),
+                secs,
+            )?,
+            None => self
+                .handler
+                .grant_capability(&grant.capability, grant.scope.as_deref())?,
+        }
 
         self.emit_event(
             "capability.granted",
             serde_json::json!({
                 "request_id": envelope.request_id,
                 "capability": grant.capability,
-                "scope": grant.scope
+                "scope": grant.scope,
+                "expires_in_secs": expires_in_secs
             }),
         );
 
@@ -673,7 +929,8 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
             serde_json::json!({
                 "accepted": true,
                 "capability": grant.capability,
-                "scope": grant.scope
+                "scope": grant.scope,
+                "expires_in_secs": expires_in_secs
             }),
         ))
     }
@@ -702,6 +959,11 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
         ))
     }
 
+    fn handle_capability_list(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let grants = self.handler.list_capabilities()?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), grants))
+    }
+
     fn handle_onboarding_get_state(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
         let state = self.handler.query_onboarding_state()?;
         Ok(ResponseEnvelope::ok(envelope.request_id.clone(), state))
@@ -880,6 +1142,121 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
         ))
     }
 
+    fn handle_onboarding_calibration_get_state(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let state = self.handler.query_onboarding_calibration_state()?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), state))
+    }
+
+    fn handle_onboarding_calibration_start(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let payload = self.handler.onboarding_calibration_start()?;
+
+        self.emit_event(
+            "onboarding.calibration.started",
+            serde_json::json!({
+                "request_id": envelope.request_id
+            }),
+        );
+
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), payload))
+    }
+
+    fn handle_onboarding_calibration_measure(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let payload = self.handler.onboarding_calibration_measure()?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), payload))
+    }
+
+    fn handle_onboarding_calibration_finish(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let payload = self.handler.onboarding_calibration_finish()?;
+
+        self.emit_event(
+            "onboarding.calibration.completed",
+            serde_json::json!({
+                "request_id": envelope.request_id
+            }),
+        );
+
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), payload))
+    }
+
+    fn handle_onboarding_calibration_reset(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        self.handler.onboarding_calibration_reset()?;
+
+        self.emit_event(
+            "onboarding.calibration.reset",
+            serde_json::json!({
+                "request_id": envelope.request_id
+            }),
+        );
+
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true}),
+        ))
+    }
+
+    fn handle_onboarding_setup_check(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let providers = match envelope.payload.get("providers") {
+            None | Some(serde_json::Value::Null) => BTreeMap::new(),
+            Some(raw) => serde_json::from_value(raw.clone()).map_err(|e| {
+                SpeechError::Config(format!("onboarding.setup.check: invalid providers: {e}"))
+            })?,
+        };
+        let load_model = envelope
+            .payload
+            .get("load_model")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+
+        let payload = self.handler.onboarding_setup_check(providers, load_model)?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), payload))
+    }
+
+    fn handle_onboarding_llm_import_apply(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let sources: Vec<String> = envelope
+            .payload
+            .get("sources")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                SpeechError::Config(format!("onboarding.llm_import.apply: invalid sources: {e}"))
+            })?
+            .ok_or_else(|| {
+                SpeechError::Config("onboarding.llm_import.apply: missing sources".to_owned())
+            })?;
+
+        let added = self.handler.onboarding_llm_import_apply(&sources)?;
+        self.emit_event(
+            "onboarding.llm_import.applied",
+            serde_json::json!({"added": added}),
+        );
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true, "added": added}),
+        ))
+    }
+
     fn handle_skills_reload(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
         self.handler.reload_skills()?;
 
@@ -1291,7 +1668,9 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
         envelope: &CommandEnvelope,
     ) -> Result<ResponseEnvelope> {
         let text = parse_conversation_text(&envelope.payload)?;
-        self.handler.request_conversation_inject_text(&text)?;
+        let images = parse_conversation_images(&envelope.payload)?;
+        self.handler
+            .request_conversation_inject_text(&text, &images)?;
 
         self.emit_event(
             "conversation.text_injected",
@@ -1366,85 +1745,420 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
         ))
     }
 
-    fn handle_conversation_gate_set(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
-        let active = parse_gate_active(&envelope.payload)?;
-        self.handler.request_conversation_gate_set(active)?;
+    fn handle_conversation_sessions_search(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let query = envelope
+            .payload
+            .get("query")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
 
-        self.emit_event(
-            "conversation.gate_set",
-            serde_json::json!({
-                "request_id": envelope.request_id,
-                "active": active
-            }),
-        );
+        let limit = envelope
+            .payload
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(10) as usize;
+
+        if query.trim().is_empty() {
+            return Ok(ResponseEnvelope::ok(
+                envelope.request_id.clone(),
+                serde_json::json!({"results": []}),
+            ));
+        }
+
+        let hits = self.handler.conversation_sessions_search(query, limit)?;
+
+        let results_json: Vec<serde_json::Value> = hits
+            .iter()
+            .map(|hit| {
+                serde_json::json!({
+                    "session_id": hit.meta.id,
+                    "title": hit.meta.title,
+                    "created_at": hit.meta.created_at,
+                    "updated_at": hit.meta.updated_at,
+                    "turn_count": hit.meta.turn_count,
+                    "score": hit.score,
+                    "snippet": hit.snippet,
+                })
+            })
+            .collect();
 
         Ok(ResponseEnvelope::ok(
             envelope.request_id.clone(),
-            serde_json::json!({
-                "accepted": true,
-                "active": active
-            }),
+            serde_json::json!({"results": results_json}),
         ))
     }
 
-    fn handle_conversation_engage(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
-        self.handler.request_conversation_engage()?;
+    fn handle_conversation_sessions_thinking(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let session_id = envelope
+            .payload
+            .get("session_id")
+            .and_then(|v| v.as_str())
+            .filter(|id| !id.trim().is_empty())
+            .ok_or_else(|| {
+                SpeechError::Pipeline(
+                    "conversation.sessions.thinking requires payload.session_id".to_owned(),
+                )
+            })?;
+        let thinking = self.handler.conversation_session_thinking(session_id)?;
         Ok(ResponseEnvelope::ok(
             envelope.request_id.clone(),
-            serde_json::json!({"accepted": true}),
+            serde_json::json!({"session_id": session_id, "thinking": thinking}),
         ))
     }
 
-    fn handle_runtime_start(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
-        // The handler emits lifecycle events (runtime.starting, runtime.started)
-        // directly — no additional event emission needed here.
-        self.handler.request_runtime_start()?;
+    fn handle_conversation_analytics_get(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let conversation_id = envelope
+            .payload
+            .get("conversation_id")
+            .and_then(|v| v.as_str());
+        let stats = self.handler.conversation_analytics(conversation_id)?;
         Ok(ResponseEnvelope::ok(
             envelope.request_id.clone(),
-            serde_json::json!({"accepted": true}),
+            serde_json::json!({"analytics": stats}),
         ))
     }
 
-    fn handle_runtime_stop(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
-        // The handler emits runtime.stopped directly.
-        self.handler.request_runtime_stop()?;
+    fn handle_conversation_analytics_list(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let limit = envelope
+            .payload
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(20) as usize;
+        let conversations = self.handler.conversation_analytics_list(limit)?;
         Ok(ResponseEnvelope::ok(
             envelope.request_id.clone(),
-            serde_json::json!({"accepted": true}),
+            serde_json::json!({"conversations": conversations}),
         ))
     }
 
-    fn handle_runtime_status(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
-        let status = self.handler.query_runtime_status()?;
-        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), status))
+    fn handle_conversation_feedback(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let thumbs_down = parse_feedback_rating(&envelope.payload)?;
+        // Only a thumbs-down is a correction; a thumbs-up needs no action.
+        let recorded = thumbs_down
+            && self.handler.record_conversation_feedback(
+                envelope.payload.get("correction").and_then(|v| v.as_str()),
+            )?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"recorded": recorded}),
+        ))
     }
 
-    fn handle_approval_respond(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
-        let (req_id, approved, reason) = parse_approval_respond(&envelope.payload)?;
-        self.handler
-            .request_approval_respond(&req_id, approved, reason.as_deref())?;
-        self.emit_event(
-            "approval.responded",
-            serde_json::json!({
-                "request_id": envelope.request_id,
-                "approval_request_id": req_id,
-                "approved": approved
-            }),
-        );
+    fn handle_conversation_corrections_export(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let path = envelope
+            .payload
+            .get("path")
+            .and_then(|v| v.as_str())
+            .filter(|p| !p.trim().is_empty())
+            .ok_or_else(|| {
+                SpeechError::Pipeline(
+                    "conversation.corrections.export requires payload.path".to_owned(),
+                )
+            })?;
+        let exported = self
+            .handler
+            .export_corrections(std::path::Path::new(path))?;
         Ok(ResponseEnvelope::ok(
             envelope.request_id.clone(),
-            serde_json::json!({"accepted": true, "approved": approved}),
+            serde_json::json!({"exported": exported, "path": path}),
         ))
     }
 
-    fn handle_scheduler_list(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
-        let list = self.handler.query_scheduler_list()?;
-        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), list))
+    fn handle_experiment_report(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let experiment_id = envelope
+            .payload
+            .get("experiment_id")
+            .and_then(|v| v.as_str());
+        let report = self.handler.experiment_report(experiment_id)?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"report": report}),
+        ))
     }
 
-    fn handle_scheduler_create(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
-        let result = self.handler.request_scheduler_create(&envelope.payload)?;
-        self.emit_event(
+    fn handle_undo_history(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let limit = envelope
+            .payload
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+            .unwrap_or(DEFAULT_UNDO_HISTORY_LIMIT);
+        let entries = crate::fae_llm::tools::undo::read_undo_audit(
+            &crate::fae_dirs::undo_audit_file(),
+            limit,
+        )?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"entries": entries}),
+        ))
+    }
+
+    fn handle_meeting_start(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let consent = envelope
+            .payload
+            .get("consent")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if !consent {
+            return Err(SpeechError::Pipeline(
+                "meeting.start requires payload.consent = true".to_owned(),
+            ));
+        }
+        let meeting_id = self.handler.start_meeting()?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"meeting_id": meeting_id}),
+        ))
+    }
+
+    fn handle_meeting_stop(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let meeting_id = self.handler.stop_meeting()?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"meeting_id": meeting_id}),
+        ))
+    }
+
+    fn handle_speech_synthesize_to_file(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let field = |name: &str| {
+            envelope
+                .payload
+                .get(name)
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.trim().is_empty())
+                .ok_or_else(|| {
+                    SpeechError::Pipeline(format!(
+                        "speech.synthesize_to_file requires payload.{name}"
+                    ))
+                })
+        };
+        let text = field("text")?;
+        let path = field("path")?;
+        let export = self
+            .handler
+            .synthesize_to_file(text, std::path::Path::new(path))?
+            .ok_or_else(|| SpeechError::Tts("speech synthesis is not available".to_owned()))?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::to_value(export).unwrap_or_default(),
+        ))
+    }
+
+    fn handle_speech_capabilities(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let capabilities = self.handler.tts_capabilities()?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"capabilities": capabilities}),
+        ))
+    }
+
+    fn handle_handoff_offer(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let device = envelope
+            .payload
+            .get("device")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or("Fae");
+        let offer = self
+            .handler
+            .offer_handoff(device)?
+            .ok_or_else(|| SpeechError::Pipeline("handoff is not available".to_owned()))?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::to_value(offer).unwrap_or_default(),
+        ))
+    }
+
+    fn handle_handoff_accept(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let uri = envelope
+            .payload
+            .get("uri")
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.trim().is_empty())
+            .ok_or_else(|| {
+                SpeechError::Pipeline("handoff.accept requires payload.uri".to_owned())
+            })?;
+        let receipt = self
+            .handler
+            .accept_handoff(uri)?
+            .ok_or_else(|| SpeechError::Pipeline("handoff is not available".to_owned()))?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::to_value(receipt).unwrap_or_default(),
+        ))
+    }
+
+    fn handle_sync_status(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let status = self
+            .handler
+            .sync_status()?
+            .ok_or_else(|| SpeechError::Sync("sync is not available".to_owned()))?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::to_value(status).unwrap_or_default(),
+        ))
+    }
+
+    fn handle_sync_now(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let report = self
+            .handler
+            .sync_now()?
+            .ok_or_else(|| SpeechError::Sync("sync is not available".to_owned()))?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::to_value(report).unwrap_or_default(),
+        ))
+    }
+
+    fn handle_sync_set_key(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let key = match envelope.payload.get("key") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(value) => Some(value.as_str().filter(|v| !v.trim().is_empty()).ok_or_else(
+                || SpeechError::Sync("sync.set_key payload.key must be a string".to_owned()),
+            )?),
+        };
+        let response = self
+            .handler
+            .set_sync_key(key)?
+            .ok_or_else(|| SpeechError::Sync("sync is not available".to_owned()))?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), response))
+    }
+
+    fn handle_conversation_gate_set(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let active = parse_gate_active(&envelope.payload)?;
+        self.handler.request_conversation_gate_set(active)?;
+
+        self.emit_event(
+            "conversation.gate_set",
+            serde_json::json!({
+                "request_id": envelope.request_id,
+                "active": active
+            }),
+        );
+
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({
+                "accepted": true,
+                "active": active
+            }),
+        ))
+    }
+
+    fn handle_conversation_engage(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        self.handler.request_conversation_engage()?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true}),
+        ))
+    }
+
+    fn handle_runtime_start(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        // The handler emits lifecycle events (runtime.starting, runtime.started)
+        // directly — no additional event emission needed here.
+        self.handler.request_runtime_start()?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true}),
+        ))
+    }
+
+    fn handle_runtime_stop(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        // The handler emits runtime.stopped directly.
+        self.handler.request_runtime_stop()?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true}),
+        ))
+    }
+
+    fn handle_runtime_status(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let status = self.handler.query_runtime_status()?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), status))
+    }
+
+    fn handle_runtime_footprint(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let report = crate::diagnostics::footprint::report();
+        let payload = serde_json::to_value(&report).unwrap_or(serde_json::Value::Null);
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), payload))
+    }
+
+    fn handle_stt_vocabulary_set(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let (source, terms) = parse_stt_vocabulary(&envelope.payload)?;
+        let mut vocabulary = crate::stt::bias::vocabulary();
+        vocabulary.set(source, &terms);
+        let count = vocabulary.counts().get(&source).copied().unwrap_or(0);
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true, "source": source, "count": count}),
+        ))
+    }
+
+    fn handle_system_lifecycle(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let event = parse_power_event(&envelope.payload)?;
+        self.handler.request_system_lifecycle(event)?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true}),
+        ))
+    }
+
+    fn handle_approval_respond(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let (req_id, approved, reason) = parse_approval_respond(&envelope.payload)?;
+        self.handler
+            .request_approval_respond(&req_id, approved, reason.as_deref())?;
+        self.emit_event(
+            "approval.responded",
+            serde_json::json!({
+                "request_id": envelope.request_id,
+                "approval_request_id": req_id,
+                "approved": approved
+            }),
+        );
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true, "approved": approved}),
+        ))
+    }
+
+    fn handle_host_respond(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let req_id = parse_non_empty_field(&envelope.payload, "request_id", "host.respond")?;
+        let response: HostResponse = serde_json::from_value(envelope.payload.clone())
+            .map_err(|e| SpeechError::Pipeline(format!("host.respond: invalid response: {e}")))?;
+        self.handler.respond_host_request(&req_id, response)?;
+        Ok(ResponseEnvelope::ok(
+            envelope.request_id.clone(),
+            serde_json::json!({"accepted": true, "request_id": req_id}),
+        ))
+    }
+
+    fn handle_scheduler_list(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let list = self.handler.query_scheduler_list()?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), list))
+    }
+
+    fn handle_scheduler_create(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let result = self.handler.request_scheduler_create(&envelope.payload)?;
+        self.emit_event(
             "scheduler.created",
             serde_json::json!({
                 "request_id": envelope.request_id,
@@ -1497,6 +2211,16 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
         ))
     }
 
+    fn handle_scheduler_prompt_respond(
+        &self,
+        envelope: &CommandEnvelope,
+    ) -> Result<ResponseEnvelope> {
+        let action =
+            parse_non_empty_field(&envelope.payload, "action", "scheduler.prompt.respond")?;
+        let result = self.handler.respond_scheduler_prompt(&action)?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), result))
+    }
+
     fn handle_config_get(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
         let key = envelope
             .payload
@@ -1539,6 +2263,26 @@ impl<H: DeviceTransferHandler> HostCommandServer<H> {
         ))
     }
 
+    fn handle_data_forget(&self, envelope: &CommandEnvelope) -> Result<ResponseEnvelope> {
+        let export = envelope
+            .payload
+            .get("export")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let wipe = envelope
+            .payload
+            .get("wipe")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+        if !export && !wipe {
+            return Err(SpeechError::Pipeline(
+                "data.forget requires payload.export or payload.wipe".to_owned(),
+            ));
+        }
+        let result = self.handler.request_data_forget(export, wipe)?;
+        Ok(ResponseEnvelope::ok(envelope.request_id.clone(), result))
+    }
+
     fn emit_event(&self, event: &str, payload: serde_json::Value) {
         let envelope =
             EventEnvelope::new(uuid::Uuid::new_v4().to_string(), event.to_owned(), payload);
@@ -1553,9 +2297,18 @@ fn command_allowed_in_rescue_mode(command: CommandName) -> bool {
             | CommandName::HostVersion
             | CommandName::ConversationInjectText
             | CommandName::ConversationInjectAudio
+            | CommandName::ConversationSessionsSearch
+            | CommandName::ConversationSessionsThinking
+            | CommandName::ConversationAnalyticsGet
+            | CommandName::ConversationAnalyticsList
+            | CommandName::ConversationCorrectionsExport
+            | CommandName::ExperimentReport
+            | CommandName::UndoHistory
             | CommandName::RuntimeStart
             | CommandName::RuntimeStop
             | CommandName::RuntimeStatus
+            | CommandName::RuntimeFootprint
+            | CommandName::SystemLifecycle
             | CommandName::ApprovalRespond
             | CommandName::SchedulerList
             | CommandName::SchedulerTriggerNow
@@ -1773,6 +2526,18 @@ fn parse_non_empty_field(
     Ok(value.to_owned())
 }
 
+fn parse_optional_expiry(payload: &serde_json::Value) -> Result<Option<u64>> {
+    match payload.get("expires_in_secs") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => match value.as_u64() {
+            Some(secs) if secs > 0 => Ok(Some(secs)),
+            _ => Err(SpeechError::Pipeline(
+                "capability.grant payload.expires_in_secs must be a positive integer".to_owned(),
+            )),
+        },
+    }
+}
+
 fn parse_optional_scope(payload: &serde_json::Value, command: &str) -> Result<Option<String>> {
     match payload.get("scope") {
         None | Some(serde_json::Value::Null) => Ok(None),
@@ -1808,6 +2573,82 @@ fn parse_conversation_text(payload: &serde_json::Value) -> Result<String> {
     Ok(text.to_owned())
 }
 
+/// Encoded images attached to `conversation.inject_text`, each given as
+/// `{ "path": "..." }` or `{ "data": "<base64>" }`.
+fn parse_conversation_images(payload: &serde_json::Value) -> Result<Vec<Vec<u8>>> {
+    use base64::Engine as _;
+
+    let Some(images) = payload.get("images").and_then(serde_json::Value::as_array) else {
+        return Ok(Vec::new());
+    };
+    images
+        .iter()
+        .map(|image| {
+            if let Some(path) = image.get("path").and_then(serde_json::Value::as_str) {
+                std::fs::read(path).map_err(|e| {
+                    SpeechError::Pipeline(format!(
+                        "conversation.inject_text: cannot read image {path}: {e}"
+                    ))
+                })
+            } else if let Some(data) = image.get("data").and_then(serde_json::Value::as_str) {
+                base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .map_err(|e| {
+                        SpeechError::Pipeline(format!(
+                            "conversation.inject_text: invalid image data: {e}"
+                        ))
+                    })
+            } else {
+                Err(SpeechError::Pipeline(
+                    "conversation.inject_text: each image needs a path or data".to_owned(),
+                ))
+            }
+        })
+        .collect()
+}
+
+fn parse_stt_vocabulary(
+    payload: &serde_json::Value,
+) -> Result<(
+    crate::stt::bias::BiasSource,
+    Vec<crate::stt::bias::BiasTerm>,
+)> {
+    use crate::stt::bias::{BiasSource, BiasTerm};
+
+    let raw_source = payload
+        .get("source")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default();
+    let source = match BiasSource::parse(raw_source) {
+        // Memory terms are refreshed by the runtime itself.
+        Some(BiasSource::Memory) | None => {
+            return Err(SpeechError::Pipeline(format!(
+                "stt.vocabulary_set: unsupported source `{raw_source}`"
+            )));
+        }
+        Some(source) => source,
+    };
+
+    let Some(raw_terms) = payload.get("terms").and_then(serde_json::Value::as_array) else {
+        return Err(SpeechError::Pipeline(
+            "stt.vocabulary_set requires payload.terms".to_owned(),
+        ));
+    };
+    let terms = raw_terms
+        .iter()
+        .map(|term| match term.as_str() {
+            Some(phrase) => Ok(BiasTerm::new(phrase)),
+            None => serde_json::from_value(term.clone()).map_err(|e| {
+                SpeechError::Pipeline(format!("stt.vocabulary_set: invalid term: {e}"))
+            }),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((source, terms))
+}
+
+/// Entries `undo.history` returns when the payload sets no limit.
+const DEFAULT_UNDO_HISTORY_LIMIT: usize = 50;
+
 /// Allowed URL schemes for link-detected events.
 const ALLOWED_LINK_SCHEMES: &[&str] = &["http://", "https://", "mailto:"];
 
@@ -1845,6 +2686,29 @@ fn parse_gate_active(payload: &serde_json::Value) -> Result<bool> {
     Ok(active)
 }
 
+/// Whether the feedback is a thumbs-down (`false` for a thumbs-up).
+fn parse_feedback_rating(payload: &serde_json::Value) -> Result<bool> {
+    match payload.get("rating").and_then(serde_json::Value::as_str) {
+        Some("down") => Ok(true),
+        Some("up") => Ok(false),
+        _ => Err(SpeechError::Pipeline(
+            "conversation.feedback requires payload.rating (\"up\" or \"down\")".to_owned(),
+        )),
+    }
+}
+
+fn parse_power_event(payload: &serde_json::Value) -> Result<PowerEvent> {
+    payload
+        .get("state")
+        .and_then(serde_json::Value::as_str)
+        .and_then(PowerEvent::parse)
+        .ok_or_else(|| {
+            SpeechError::Pipeline(
+                "system.lifecycle requires payload.state (\"sleep\" or \"wake\")".to_owned(),
+            )
+        })
+}
+
 fn parse_approval_respond(payload: &serde_json::Value) -> Result<(String, bool, Option<String>)> {
     let req_id = parse_non_empty_field(payload, "request_id", "approval.respond")?;
     let Some(approved) = payload.get("approved").and_then(serde_json::Value::as_bool) else {
@@ -1912,7 +2776,7 @@ mod tests {
         fn request_go_home(&self) -> Result<()> {
             Ok(())
         }
-        fn request_conversation_inject_text(&self, _text: &str) -> Result<()> {
+        fn request_conversation_inject_text(&self, _text: &str, _images: &[Vec<u8>]) -> Result<()> {
             self.inject_called.store(true, Ordering::SeqCst);
             Ok(())
         }
@@ -1966,6 +2830,62 @@ mod tests {
         assert_eq!(resp.payload["text"], "Hello Fae");
     }
 
+    #[test]
+    fn conversation_inject_text_reads_attached_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("screen.png");
+        std::fs::write(&path, [1, 2, 3]).unwrap();
+        let payload = serde_json::json!({
+            "text": "What's this?",
+            "images": [{"path": path}, {"data": "BAUG"}],
+        });
+        assert_eq!(
+            parse_conversation_images(&payload).unwrap(),
+            vec![vec![1, 2, 3], vec![4, 5, 6]]
+        );
+
+        let bad = serde_json::json!({"text": "x", "images": [{"url": "https://a/b.png"}]});
+        assert!(parse_conversation_images(&bad).is_err());
+        assert!(
+            parse_conversation_images(&serde_json::json!({"text": "x"}))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn runtime_footprint_reports_compiled_features() {
+        let server = make_server();
+        let envelope = make_envelope(CommandName::RuntimeFootprint, serde_json::json!({}));
+        let resp = server.route(&envelope).unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.payload["features"]["canvas"], cfg!(feature = "canvas"));
+        assert!(resp.payload["components"].is_array());
+    }
+
+    #[test]
+    fn stt_vocabulary_set_replaces_source_terms() {
+        let server = make_server();
+        let envelope = make_envelope(
+            CommandName::SttVocabularySet,
+            serde_json::json!({
+                "source": "project",
+                "terms": ["ratatui", {"phrase": "tokio select", "boost": 2.0}, "  "]
+            }),
+        );
+        let resp = server.route(&envelope).unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.payload["source"], "project");
+        assert_eq!(resp.payload["count"], 2);
+
+        let memory = make_envelope(
+            CommandName::SttVocabularySet,
+            serde_json::json!({"source": "memory", "terms": []}),
+        );
+        let resp = server.route(&memory);
+        assert!(resp.is_err() || !resp.unwrap().ok);
+    }
+
     #[test]
     fn conversation_inject_text_empty_returns_error() {
         let server = make_server();
@@ -2006,6 +2926,266 @@ mod tests {
         assert!(resp.is_err());
     }
 
+    #[test]
+    fn conversation_sessions_search_returns_results_array() {
+        let server = make_server();
+        let envelope = make_envelope(
+            CommandName::ConversationSessionsSearch,
+            serde_json::json!({"query": "tax form", "limit": 3}),
+        );
+        let resp = server.route(&envelope).unwrap();
+        assert!(resp.ok);
+        assert!(resp.payload["results"].is_array());
+    }
+
+    #[test]
+    fn conversation_sessions_search_empty_query_returns_no_results() {
+        let server = make_server();
+        let envelope = make_envelope(
+            CommandName::ConversationSessionsSearch,
+            serde_json::json!({"query": "  "}),
+        );
+        let resp = server.route(&envelope).unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.payload["results"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn conversation_sessions_thinking_requires_a_session_id() {
+        let server = make_server();
+        let without = make_envelope(
+            CommandName::ConversationSessionsThinking,
+            serde_json::json!({}),
+        );
+        assert!(server.route(&without).is_err());
+
+        let with = make_envelope(
+            CommandName::ConversationSessionsThinking,
+            serde_json::json!({"session_id": "abc"}),
+        );
+        let resp = server.route(&with).unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.payload["thinking"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn conversation_analytics_commands_return_empty_defaults() {
+        let server = make_server();
+        let get = make_envelope(CommandName::ConversationAnalyticsGet, serde_json::json!({}));
+        let resp = server.route(&get).unwrap();
+        assert!(resp.ok);
+        assert!(resp.payload["analytics"].is_null());
+
+        let list = make_envelope(
+            CommandName::ConversationAnalyticsList,
+            serde_json::json!({"limit": 5}),
+        );
+        let resp = server.route(&list).unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.payload["conversations"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn conversation_feedback_validates_rating_and_export_path() {
+        let server = make_server();
+        let down = make_envelope(
+            CommandName::ConversationFeedback,
+            serde_json::json!({"rating": "down", "correction": "Canberra"}),
+        );
+        let resp = server.route(&down).unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.payload["recorded"], false);
+
+        let bad = make_envelope(
+            CommandName::ConversationFeedback,
+            serde_json::json!({"rating": "meh"}),
+        );
+        assert!(server.route(&bad).is_err());
+
+        let export = make_envelope(
+            CommandName::ConversationCorrectionsExport,
+            serde_json::json!({}),
+        );
+        assert!(server.route(&export).is_err());
+        let export = make_envelope(
+            CommandName::ConversationCorrectionsExport,
+            serde_json::json!({"path": "/tmp/corrections.jsonl"}),
+        );
+        let resp = server.route(&export).unwrap();
+        assert_eq!(resp.payload["exported"], 0);
+    }
+
+    #[test]
+    fn experiment_report_returns_null_without_an_experiment() {
+        let server = make_server();
+        let envelope = make_envelope(CommandName::ExperimentReport, serde_json::json!({}));
+        let resp = server.route(&envelope).unwrap();
+        assert!(resp.ok);
+        assert!(resp.payload["report"].is_null());
+    }
+
+    #[test]
+    fn meeting_start_requires_consent() {
+        let server = make_server();
+        let without = make_envelope(CommandName::MeetingStart, serde_json::json!({}));
+        assert!(server.route(&without).is_err());
+
+        let with = make_envelope(
+            CommandName::MeetingStart,
+            serde_json::json!({"consent": true}),
+        );
+        let resp = server.route(&with).unwrap();
+        assert!(resp.ok);
+        assert!(resp.payload["meeting_id"].is_null());
+
+        let stop = make_envelope(CommandName::MeetingStop, serde_json::json!({}));
+        assert!(server.route(&stop).unwrap().payload["meeting_id"].is_null());
+    }
+
+    #[test]
+    fn speech_synthesize_to_file_requires_text_and_path() {
+        let server = make_server();
+        let without_path = make_envelope(
+            CommandName::SpeechSynthesizeToFile,
+            serde_json::json!({"text": "Hello."}),
+        );
+        assert!(server.route(&without_path).is_err());
+
+        // The test handler has no speech engine.
+        let full = make_envelope(
+            CommandName::SpeechSynthesizeToFile,
+            serde_json::json!({"text": "Hello.", "path": "/tmp/hello.wav"}),
+        );
+        assert!(server.route(&full).is_err());
+    }
+
+    #[test]
+    fn speech_capabilities_is_null_without_an_engine() {
+        let server = make_server();
+        let envelope = make_envelope(CommandName::SpeechCapabilities, serde_json::json!({}));
+        let resp = server.route(&envelope).unwrap();
+        assert!(resp.ok);
+        assert!(resp.payload["capabilities"].is_null());
+    }
+
+    #[test]
+    fn handoff_accept_requires_uri() {
+        let server = make_server();
+        let without = make_envelope(CommandName::HandoffAccept, serde_json::json!({}));
+        assert!(server.route(&without).is_err());
+
+        // The test handler does not support handoff.
+        let with = make_envelope(
+            CommandName::HandoffAccept,
+            serde_json::json!({"uri": "fae-handoff://127.0.0.1:1/token#key"}),
+        );
+        assert!(server.route(&with).is_err());
+        let offer = make_envelope(CommandName::HandoffOffer, serde_json::json!({}));
+        assert!(server.route(&offer).is_err());
+    }
+
+    #[test]
+    fn sync_set_key_rejects_a_non_string_key() {
+        let server = make_server();
+        let bad = make_envelope(CommandName::SyncSetKey, serde_json::json!({"key": 42}));
+        assert!(server.route(&bad).is_err());
+
+        // The test handler does not support sync.
+        let generate = make_envelope(CommandName::SyncSetKey, serde_json::json!({}));
+        assert!(server.route(&generate).is_err());
+        let now = make_envelope(CommandName::SyncNow, serde_json::json!({}));
+        assert!(server.route(&now).is_err());
+    }
+
+    #[test]
+    fn onboarding_calibration_commands_route() {
+        let server = make_server();
+        let state = make_envelope(
+            CommandName::OnboardingCalibrationGetState,
+            serde_json::json!({}),
+        );
+        let resp = server.route(&state).unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.payload["active"], false);
+
+        for command in [
+            CommandName::OnboardingCalibrationStart,
+            CommandName::OnboardingCalibrationMeasure,
+            CommandName::OnboardingCalibrationFinish,
+            CommandName::OnboardingCalibrationReset,
+        ] {
+            let resp = server
+                .route(&make_envelope(command, serde_json::json!({})))
+                .unwrap();
+            assert!(resp.ok, "{} should succeed", command.as_str());
+        }
+    }
+
+    #[test]
+    fn onboarding_setup_check_validates_provider_payload() {
+        let server = make_server();
+        let ok = make_envelope(
+            CommandName::OnboardingSetupCheck,
+            serde_json::json!({
+                "providers": {
+                    "openai": {
+                        "endpoint_type": "openai",
+                        "base_url": "https://api.openai.com/v1",
+                        "api_key": {"type": "literal", "value": "sk-test"}
+                    }
+                },
+                "load_model": false
+            }),
+        );
+        let resp = server.route(&ok).unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.payload["accepted"], true);
+
+        let bad = make_envelope(
+            CommandName::OnboardingSetupCheck,
+            serde_json::json!({"providers": {"openai": {"base_url": 42}}}),
+        );
+        assert!(server.route(&bad).is_err());
+    }
+
+    #[test]
+    fn onboarding_llm_import_requires_sources() {
+        let server = make_server();
+        let discover = make_envelope(
+            CommandName::OnboardingLlmImportDiscover,
+            serde_json::json!({}),
+        );
+        let resp = server.route(&discover).unwrap();
+        assert!(resp.payload["providers"].is_array());
+
+        let apply = make_envelope(
+            CommandName::OnboardingLlmImportApply,
+            serde_json::json!({"sources": ["ollama"]}),
+        );
+        let resp = server.route(&apply).unwrap();
+        assert_eq!(resp.payload["accepted"], true);
+
+        let missing = make_envelope(CommandName::OnboardingLlmImportApply, serde_json::json!({}));
+        assert!(server.route(&missing).is_err());
+    }
+
+    #[test]
+    fn host_respond_requires_a_typed_status() {
+        let server = make_server();
+        let picked = make_envelope(
+            CommandName::HostRespond,
+            serde_json::json!({"request_id": "3", "status": "picked", "path": "/tmp/a.pdf"}),
+        );
+        let resp = server.route(&picked).unwrap();
+        assert_eq!(resp.payload["request_id"], "3");
+
+        let untyped = make_envelope(
+            CommandName::HostRespond,
+            serde_json::json!({"request_id": "3", "path": "/tmp/a.pdf"}),
+        );
+        assert!(server.route(&untyped).is_err());
+    }
+
     #[test]
     fn conversation_link_detected_accepted() {
         let server = make_server();
@@ -2041,6 +3221,23 @@ mod tests {
         assert!(resp.is_err());
     }
 
+    #[test]
+    fn data_forget_requires_export_or_wipe() {
+        let server = make_server();
+        let envelope = make_envelope(
+            CommandName::DataForget,
+            serde_json::json!({"export": false, "wipe": false}),
+        );
+        assert!(server.route(&envelope).is_err());
+    }
+
+    #[test]
+    fn rescue_mode_blocks_data_forget() {
+        let server = make_rescue_server();
+        let envelope = make_envelope(CommandName::DataForget, serde_json::json!({}));
+        assert!(server.route(&envelope).is_err());
+    }
+
     #[test]
     fn conversation_link_detected_missing_field_returns_error() {
         let server = make_server();
@@ -2098,6 +3295,10 @@ mod tests {
             (CommandName::RuntimeStart, serde_json::json!({})),
             (CommandName::RuntimeStop, serde_json::json!({})),
             (CommandName::RuntimeStatus, serde_json::json!({})),
+            (
+                CommandName::ConversationSessionsSearch,
+                serde_json::json!({"query": "tax form"}),
+            ),
             (
                 CommandName::ApprovalRespond,
                 serde_json::json!({"request_id": "1", "approved": true}),
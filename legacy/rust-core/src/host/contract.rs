@@ -18,6 +18,19 @@ pub enum CommandName {
     RuntimeStop,
     #[serde(rename = "runtime.status")]
     RuntimeStatus,
+    /// Report resident memory per loaded component and the optional
+    /// subsystems compiled into this build.
+    #[serde(rename = "runtime.footprint")]
+    RuntimeFootprint,
+    /// Forward an OS power notification so the runtime can suspend before
+    /// sleep and resume on wake.
+    ///
+    /// Payload: `{ "state": "sleep" | "wake" }`
+    #[serde(rename = "system.lifecycle")]
+    SystemLifecycle,
+    /// Send typed text as the user's next turn, optionally with images.
+    ///
+    /// Payload: `{ "text": "...", "images": [{ "path": "..." } | { "data": "<base64>" }] }`
     #[serde(rename = "conversation.inject_text")]
     ConversationInjectText,
     #[serde(rename = "conversation.gate_set")]
@@ -26,6 +39,8 @@ pub enum CommandName {
     ConversationEngage,
     #[serde(rename = "approval.respond")]
     ApprovalRespond,
+    #[serde(rename = "host.respond")]
+    HostRespond,
     #[serde(rename = "scheduler.list")]
     SchedulerList,
     #[serde(rename = "scheduler.create")]
@@ -36,6 +51,8 @@ pub enum CommandName {
     SchedulerDelete,
     #[serde(rename = "scheduler.trigger_now")]
     SchedulerTriggerNow,
+    #[serde(rename = "scheduler.prompt.respond")]
+    SchedulerPromptRespond,
     #[serde(rename = "device.move")]
     DeviceMove,
     #[serde(rename = "device.go_home")]
@@ -56,6 +73,8 @@ pub enum CommandName {
     CapabilityGrant,
     #[serde(rename = "capability.deny")]
     CapabilityDeny,
+    #[serde(rename = "capability.list")]
+    CapabilityList,
     #[serde(rename = "onboarding.get_state")]
     OnboardingGetState,
     #[serde(rename = "onboarding.advance")]
@@ -72,6 +91,38 @@ pub enum CommandName {
     OnboardingVoiceprintFinalize,
     #[serde(rename = "onboarding.voiceprint.reset")]
     OnboardingVoiceprintReset,
+    #[serde(rename = "onboarding.calibration.get_state")]
+    OnboardingCalibrationGetState,
+    #[serde(rename = "onboarding.calibration.start")]
+    OnboardingCalibrationStart,
+    /// Record the current calibration step in the background.
+    ///
+    /// Results arrive as `onboarding.calibration.progress` or
+    /// `onboarding.calibration.failed` events.
+    #[serde(rename = "onboarding.calibration.measure")]
+    OnboardingCalibrationMeasure,
+    #[serde(rename = "onboarding.calibration.finish")]
+    OnboardingCalibrationFinish,
+    #[serde(rename = "onboarding.calibration.reset")]
+    OnboardingCalibrationReset,
+    /// Validate entered provider API keys and smoke-test the local model.
+    ///
+    /// Payload: `{ "providers": { "<name>": <ProviderConfig> }, "load_model": true }`.
+    /// Checks report as `onboarding.setup.progress` events and finish with
+    /// `onboarding.setup.completed`.
+    #[serde(rename = "onboarding.setup.check")]
+    OnboardingSetupCheck,
+    /// List provider profiles found in other tools' configs (Ollama,
+    /// LM Studio, llama.cpp, API key env vars, Claude Code).
+    ///
+    /// Response: `{ "providers": [<summary>] }`; key material is never included.
+    #[serde(rename = "onboarding.llm_import.discover")]
+    OnboardingLlmImportDiscover,
+    /// Write the confirmed profiles into the LLM provider config.
+    ///
+    /// Payload: `{ "sources": ["ollama", "openai_env"] }`.
+    #[serde(rename = "onboarding.llm_import.apply")]
+    OnboardingLlmImportApply,
     /// Inject raw PCM audio from a companion device into the pipeline.
     ///
     /// Payload: `{ "sample_rate": 16000, "samples_b64": "<base64 f32 LE>" }`
@@ -79,6 +130,100 @@ pub enum CommandName {
     ConversationInjectAudio,
     #[serde(rename = "conversation.link_detected")]
     ConversationLinkDetected,
+    /// Search past conversation sessions by title and content.
+    ///
+    /// Payload: `{ "query": "...", "limit": 10 }`
+    #[serde(rename = "conversation.sessions.search")]
+    ConversationSessionsSearch,
+    /// The model's thinking transcripts saved with a past session, one per
+    /// turn where it thought (`[llm.thinking] store`).
+    ///
+    /// Payload: `{ "session_id": "..." }`
+    #[serde(rename = "conversation.sessions.thinking")]
+    ConversationSessionsThinking,
+    /// Talk-time, interruption, latency and topic statistics for one
+    /// conversation (the current one when no id is given).
+    ///
+    /// Payload: `{ "conversation_id": "conv_..." }`
+    #[serde(rename = "conversation.analytics.get")]
+    ConversationAnalyticsGet,
+    /// Statistics for the most recent conversations, newest first.
+    ///
+    /// Payload: `{ "limit": 20 }`
+    #[serde(rename = "conversation.analytics.list")]
+    ConversationAnalyticsList,
+    /// Thumbs-up or thumbs-down on the last reply. A thumbs-down records it
+    /// as a correction, with the right answer when the user typed one.
+    ///
+    /// Payload: `{ "rating": "down", "correction": "..." }`
+    #[serde(rename = "conversation.feedback")]
+    ConversationFeedback,
+    /// Write every recorded correction to a JSON Lines file for offline
+    /// analysis.
+    ///
+    /// Payload: `{ "path": "/path/to/corrections.jsonl" }`
+    #[serde(rename = "conversation.corrections.export")]
+    ConversationCorrectionsExport,
+    /// Per-variant summary of the prompt experiment (the configured one when
+    /// no id is given).
+    ///
+    /// Payload: `{ "experiment_id": "..." }`
+    #[serde(rename = "experiment.report")]
+    ExperimentReport,
+    /// Recent undo audit entries: file changes recorded for undo and the
+    /// ones reverted, without file content.
+    ///
+    /// Payload: `{ "limit": 50 }` (optional).
+    #[serde(rename = "undo.history")]
+    UndoHistory,
+    /// Start meeting mode: transcribe everyone without replying. `consent`
+    /// must be `true`, confirming the user agreed to the meeting being
+    /// transcribed.
+    ///
+    /// Payload: `{ "consent": true }`
+    #[serde(rename = "meeting.start")]
+    MeetingStart,
+    /// End meeting mode; the minutes follow as a
+    /// `pipeline.meeting_minutes_ready` event.
+    #[serde(rename = "meeting.stop")]
+    MeetingStop,
+    /// Read a text aloud with the current voice and save it as a `.wav` or
+    /// `.mp3` file. The text may be SSML. Responds once the file is written.
+    ///
+    /// Payload: `{ "text": "...", "path": "/path/to/article.mp3" }`
+    #[serde(rename = "speech.synthesize_to_file")]
+    SpeechSynthesizeToFile,
+    /// Languages, voices and streaming support of the configured TTS engine
+    /// (`tts.backend`).
+    #[serde(rename = "speech.capabilities")]
+    SpeechCapabilities,
+    /// Offer the current conversation to another device. Responds with a
+    /// one-time pairing URI (shown as a QR code) that expires after two
+    /// minutes. `device` names this device to the receiver.
+    ///
+    /// Payload: `{ "device": "Desktop" }`
+    #[serde(rename = "handoff.offer")]
+    HandoffOffer,
+    /// Continue the conversation offered at a pairing URI from another
+    /// device; a `pipeline.conversation_handed_off` event follows once it
+    /// replaces the current one.
+    ///
+    /// Payload: `{ "uri": "fae-handoff://..." }`
+    #[serde(rename = "handoff.accept")]
+    HandoffAccept,
+    /// Report the state of sync with the user's other devices.
+    #[serde(rename = "sync.status")]
+    SyncStatus,
+    /// Sync settings, skills, memories and preferences with the user's
+    /// other devices now. Responds with what was sent and received.
+    #[serde(rename = "sync.now")]
+    SyncNow,
+    /// Set the sync key. Without `key`, generates one and responds with it
+    /// for entering on the other devices.
+    ///
+    /// Payload: `{ "key": "..." }` (optional)
+    #[serde(rename = "sync.set_key")]
+    SyncSetKey,
     #[serde(rename = "config.get")]
     ConfigGet,
     #[serde(rename = "config.patch")]
@@ -91,6 +236,9 @@ pub enum CommandName {
     SkillsReload,
     #[serde(rename = "data.delete_all")]
     DataDeleteAll,
+    /// Export and/or securely wipe personal data after user confirmation.
+    #[serde(rename = "data.forget")]
+    DataForget,
     /// Start (or restart) a named Python skill daemon process.
     #[serde(rename = "skill.python.start")]
     SkillPythonStart,
@@ -161,6 +309,12 @@ pub enum CommandName {
     /// List available and installed channel skill types.
     #[serde(rename = "skill.channel.list")]
     SkillChannelList,
+    /// Replace the speech recognition vocabulary from one source.
+    ///
+    /// Payload: `{ "source": "contacts" | "project" | "custom",
+    /// "terms": ["Siobhan", { "phrase": "ratatui", "boost": 2.0 }] }`
+    #[serde(rename = "stt.vocabulary_set")]
+    SttVocabularySet,
 }
 
 impl CommandName {
@@ -173,15 +327,19 @@ impl CommandName {
             Self::RuntimeStart => "runtime.start",
             Self::RuntimeStop => "runtime.stop",
             Self::RuntimeStatus => "runtime.status",
+            Self::RuntimeFootprint => "runtime.footprint",
+            Self::SystemLifecycle => "system.lifecycle",
             Self::ConversationInjectText => "conversation.inject_text",
             Self::ConversationGateSet => "conversation.gate_set",
             Self::ConversationEngage => "conversation.engage",
             Self::ApprovalRespond => "approval.respond",
+            Self::HostRespond => "host.respond",
             Self::SchedulerList => "scheduler.list",
             Self::SchedulerCreate => "scheduler.create",
             Self::SchedulerUpdate => "scheduler.update",
             Self::SchedulerDelete => "scheduler.delete",
             Self::SchedulerTriggerNow => "scheduler.trigger_now",
+            Self::SchedulerPromptRespond => "scheduler.prompt.respond",
             Self::DeviceMove => "device.move",
             Self::DeviceGoHome => "device.go_home",
             Self::OrbPaletteSet => "orb.palette.set",
@@ -192,6 +350,7 @@ impl CommandName {
             Self::CapabilityRequest => "capability.request",
             Self::CapabilityGrant => "capability.grant",
             Self::CapabilityDeny => "capability.deny",
+            Self::CapabilityList => "capability.list",
             Self::OnboardingGetState => "onboarding.get_state",
             Self::OnboardingAdvance => "onboarding.advance",
             Self::OnboardingComplete => "onboarding.complete",
@@ -200,14 +359,40 @@ impl CommandName {
             Self::OnboardingVoiceprintStartEnrollment => "onboarding.voiceprint.start_enrollment",
             Self::OnboardingVoiceprintFinalize => "onboarding.voiceprint.finalize",
             Self::OnboardingVoiceprintReset => "onboarding.voiceprint.reset",
+            Self::OnboardingCalibrationGetState => "onboarding.calibration.get_state",
+            Self::OnboardingCalibrationStart => "onboarding.calibration.start",
+            Self::OnboardingCalibrationMeasure => "onboarding.calibration.measure",
+            Self::OnboardingCalibrationFinish => "onboarding.calibration.finish",
+            Self::OnboardingCalibrationReset => "onboarding.calibration.reset",
+            Self::OnboardingSetupCheck => "onboarding.setup.check",
+            Self::OnboardingLlmImportDiscover => "onboarding.llm_import.discover",
+            Self::OnboardingLlmImportApply => "onboarding.llm_import.apply",
             Self::ConversationInjectAudio => "conversation.inject_audio",
             Self::ConversationLinkDetected => "conversation.link_detected",
+            Self::ConversationSessionsSearch => "conversation.sessions.search",
+            Self::ConversationSessionsThinking => "conversation.sessions.thinking",
+            Self::ConversationAnalyticsGet => "conversation.analytics.get",
+            Self::ConversationAnalyticsList => "conversation.analytics.list",
+            Self::ConversationFeedback => "conversation.feedback",
+            Self::ConversationCorrectionsExport => "conversation.corrections.export",
+            Self::ExperimentReport => "experiment.report",
+            Self::UndoHistory => "undo.history",
+            Self::MeetingStart => "meeting.start",
+            Self::MeetingStop => "meeting.stop",
+            Self::SpeechSynthesizeToFile => "speech.synthesize_to_file",
+            Self::SpeechCapabilities => "speech.capabilities",
+            Self::HandoffOffer => "handoff.offer",
+            Self::HandoffAccept => "handoff.accept",
+            Self::SyncStatus => "sync.status",
+            Self::SyncNow => "sync.now",
+            Self::SyncSetKey => "sync.set_key",
             Self::ConfigGet => "config.get",
             Self::ConfigPatch => "config.patch",
             Self::OnboardingSetContactInfo => "onboarding.set_contact_info",
             Self::OnboardingSetFamilyInfo => "onboarding.set_family_info",
             Self::SkillsReload => "skills.reload",
             Self::DataDeleteAll => "data.delete_all",
+            Self::DataForget => "data.forget",
             Self::SkillPythonStart => "skill.python.start",
             Self::SkillPythonStop => "skill.python.stop",
             Self::SkillPythonList => "skill.python.list",
@@ -226,6 +411,7 @@ impl CommandName {
             Self::SkillHealthStatusCmd => "skill.health.status",
             Self::SkillChannelInstall => "skill.channel.install",
             Self::SkillChannelList => "skill.channel.list",
+            Self::SttVocabularySet => "stt.vocabulary_set",
         }
     }
 
@@ -238,15 +424,19 @@ impl CommandName {
             "runtime.start" => Some(Self::RuntimeStart),
             "runtime.stop" => Some(Self::RuntimeStop),
             "runtime.status" => Some(Self::RuntimeStatus),
+            "runtime.footprint" => Some(Self::RuntimeFootprint),
+            "system.lifecycle" => Some(Self::SystemLifecycle),
             "conversation.inject_text" => Some(Self::ConversationInjectText),
             "conversation.gate_set" => Some(Self::ConversationGateSet),
             "conversation.engage" => Some(Self::ConversationEngage),
             "approval.respond" => Some(Self::ApprovalRespond),
+            "host.respond" => Some(Self::HostRespond),
             "scheduler.list" => Some(Self::SchedulerList),
             "scheduler.create" => Some(Self::SchedulerCreate),
             "scheduler.update" => Some(Self::SchedulerUpdate),
             "scheduler.delete" => Some(Self::SchedulerDelete),
             "scheduler.trigger_now" => Some(Self::SchedulerTriggerNow),
+            "scheduler.prompt.respond" => Some(Self::SchedulerPromptRespond),
             "device.move" => Some(Self::DeviceMove),
             "device.go_home" => Some(Self::DeviceGoHome),
             "orb.palette.set" => Some(Self::OrbPaletteSet),
@@ -257,6 +447,7 @@ impl CommandName {
             "capability.request" => Some(Self::CapabilityRequest),
             "capability.grant" => Some(Self::CapabilityGrant),
             "capability.deny" => Some(Self::CapabilityDeny),
+            "capability.list" => Some(Self::CapabilityList),
             "onboarding.get_state" => Some(Self::OnboardingGetState),
             "onboarding.advance" => Some(Self::OnboardingAdvance),
             "onboarding.complete" => Some(Self::OnboardingComplete),
@@ -267,14 +458,40 @@ impl CommandName {
             }
             "onboarding.voiceprint.finalize" => Some(Self::OnboardingVoiceprintFinalize),
             "onboarding.voiceprint.reset" => Some(Self::OnboardingVoiceprintReset),
+            "onboarding.calibration.get_state" => Some(Self::OnboardingCalibrationGetState),
+            "onboarding.calibration.start" => Some(Self::OnboardingCalibrationStart),
+            "onboarding.calibration.measure" => Some(Self::OnboardingCalibrationMeasure),
+            "onboarding.calibration.finish" => Some(Self::OnboardingCalibrationFinish),
+            "onboarding.calibration.reset" => Some(Self::OnboardingCalibrationReset),
+            "onboarding.setup.check" => Some(Self::OnboardingSetupCheck),
+            "onboarding.llm_import.discover" => Some(Self::OnboardingLlmImportDiscover),
+            "onboarding.llm_import.apply" => Some(Self::OnboardingLlmImportApply),
             "conversation.inject_audio" => Some(Self::ConversationInjectAudio),
             "conversation.link_detected" => Some(Self::ConversationLinkDetected),
+            "conversation.sessions.search" => Some(Self::ConversationSessionsSearch),
+            "conversation.sessions.thinking" => Some(Self::ConversationSessionsThinking),
+            "conversation.analytics.get" => Some(Self::ConversationAnalyticsGet),
+            "conversation.analytics.list" => Some(Self::ConversationAnalyticsList),
+            "conversation.feedback" => Some(Self::ConversationFeedback),
+            "conversation.corrections.export" => Some(Self::ConversationCorrectionsExport),
+            "experiment.report" => Some(Self::ExperimentReport),
+            "undo.history" => Some(Self::UndoHistory),
+            "meeting.start" => Some(Self::MeetingStart),
+            "meeting.stop" => Some(Self::MeetingStop),
+            "speech.synthesize_to_file" => Some(Self::SpeechSynthesizeToFile),
+            "speech.capabilities" => Some(Self::SpeechCapabilities),
+            "handoff.offer" => Some(Self::HandoffOffer),
+            "handoff.accept" => Some(Self::HandoffAccept),
+            "sync.status" => Some(Self::SyncStatus),
+            "sync.now" => Some(Self::SyncNow),
+            "sync.set_key" => Some(Self::SyncSetKey),
             "config.get" => Some(Self::ConfigGet),
             "config.patch" => Some(Self::ConfigPatch),
             "onboarding.set_contact_info" => Some(Self::OnboardingSetContactInfo),
             "onboarding.set_family_info" => Some(Self::OnboardingSetFamilyInfo),
             "skills.reload" => Some(Self::SkillsReload),
             "data.delete_all" => Some(Self::DataDeleteAll),
+            "data.forget" => Some(Self::DataForget),
             "skill.python.start" => Some(Self::SkillPythonStart),
             "skill.python.stop" => Some(Self::SkillPythonStop),
             "skill.python.list" => Some(Self::SkillPythonList),
@@ -293,6 +510,7 @@ impl CommandName {
             "skill.health.status" => Some(Self::SkillHealthStatusCmd),
             "skill.channel.install" => Some(Self::SkillChannelInstall),
             "skill.channel.list" => Some(Self::SkillChannelList),
+            "stt.vocabulary_set" => Some(Self::SttVocabularySet),
             _ => None,
         }
     }
@@ -387,6 +605,11 @@ pub struct EventEnvelope {
     pub event_id: String,
     pub event: String,
     pub payload: serde_json::Value,
+    /// Versioned record of the [`RuntimeEvent`](crate::runtime::RuntimeEvent)
+    /// behind this event, from `RuntimeEvent::to_json`. Absent for events
+    /// the host raises itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime_event: Option<serde_json::Value>,
 }
 
 impl EventEnvelope {
@@ -402,8 +625,16 @@ impl EventEnvelope {
             event_id: event_id.into(),
             event: event.into(),
             payload,
+            runtime_event: None,
         }
     }
+
+    /// Attach the versioned runtime event record this event was mapped from.
+    #[must_use]
+    pub fn with_runtime_event(mut self, record: serde_json::Value) -> Self {
+        self.runtime_event = Some(record);
+        self
+    }
 }
 
 /// Contract validation error categories.
@@ -449,15 +680,19 @@ mod tests {
         CommandName::RuntimeStart,
         CommandName::RuntimeStop,
         CommandName::RuntimeStatus,
+        CommandName::RuntimeFootprint,
+        CommandName::SystemLifecycle,
         CommandName::ConversationInjectText,
         CommandName::ConversationGateSet,
         CommandName::ConversationEngage,
         CommandName::ApprovalRespond,
+        CommandName::HostRespond,
         CommandName::SchedulerList,
         CommandName::SchedulerCreate,
         CommandName::SchedulerUpdate,
         CommandName::SchedulerDelete,
         CommandName::SchedulerTriggerNow,
+        CommandName::SchedulerPromptRespond,
         CommandName::DeviceMove,
         CommandName::DeviceGoHome,
         CommandName::OrbPaletteSet,
@@ -468,6 +703,7 @@ mod tests {
         CommandName::CapabilityRequest,
         CommandName::CapabilityGrant,
         CommandName::CapabilityDeny,
+        CommandName::CapabilityList,
         CommandName::OnboardingGetState,
         CommandName::OnboardingAdvance,
         CommandName::OnboardingComplete,
@@ -476,14 +712,40 @@ mod tests {
         CommandName::OnboardingVoiceprintStartEnrollment,
         CommandName::OnboardingVoiceprintFinalize,
         CommandName::OnboardingVoiceprintReset,
+        CommandName::OnboardingCalibrationGetState,
+        CommandName::OnboardingCalibrationStart,
+        CommandName::OnboardingCalibrationMeasure,
+        CommandName::OnboardingCalibrationFinish,
+        CommandName::OnboardingCalibrationReset,
+        CommandName::OnboardingSetupCheck,
+        CommandName::OnboardingLlmImportDiscover,
+        CommandName::OnboardingLlmImportApply,
         CommandName::ConversationInjectAudio,
         CommandName::ConversationLinkDetected,
+        CommandName::ConversationSessionsSearch,
+        CommandName::ConversationSessionsThinking,
+        CommandName::ConversationAnalyticsGet,
+        CommandName::ConversationAnalyticsList,
+        CommandName::ConversationFeedback,
+        CommandName::ConversationCorrectionsExport,
+        CommandName::ExperimentReport,
+        CommandName::UndoHistory,
+        CommandName::MeetingStart,
+        CommandName::MeetingStop,
+        CommandName::SpeechSynthesizeToFile,
+        CommandName::SpeechCapabilities,
+        CommandName::HandoffOffer,
+        CommandName::HandoffAccept,
+        CommandName::SyncStatus,
+        CommandName::SyncNow,
+        CommandName::SyncSetKey,
         CommandName::ConfigGet,
         CommandName::ConfigPatch,
         CommandName::OnboardingSetContactInfo,
         CommandName::OnboardingSetFamilyInfo,
         CommandName::SkillsReload,
         CommandName::DataDeleteAll,
+        CommandName::DataForget,
         CommandName::SkillPythonStart,
         CommandName::SkillPythonStop,
         CommandName::SkillPythonList,
@@ -502,6 +764,7 @@ mod tests {
         CommandName::SkillHealthStatusCmd,
         CommandName::SkillChannelInstall,
         CommandName::SkillChannelList,
+        CommandName::SttVocabularySet,
     ];
 
     #[test]
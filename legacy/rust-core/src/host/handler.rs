@@ -1,27 +1,36 @@
 //! Production host command handler for the embedded Fae runtime.
 
+use crate::analytics::{AnalyticsStore, ConversationAnalytics, ConversationStats};
 use crate::approval::ToolApprovalRequest;
 use crate::config::{
-    AgentToolMode, LlmBackend, RuntimeConfig, RuntimeProfile, RuntimeRescueSavedLlmConfig,
-    SpeechConfig, VoiceIdentityMode, VoiceModelPreset,
+    AccessibilityVerbosity, AgentToolMode, CaptionsMode, LlmBackend, RuntimeConfig, RuntimeProfile,
+    RuntimeRescueSavedLlmConfig, SpeechConfig, VoiceIdentityMode, VoiceModelPreset,
 };
 use crate::error::{Result, SpeechError};
+use crate::experiments::{ExperimentReport, ExperimentStore, PromptExperiment, Trial};
+use crate::fae_llm::config::import::{self, ImportContext};
+use crate::fae_llm::config::types::ProviderConfig;
+use crate::fae_llm::providers::openrouter;
+use crate::host::bridge::{HostRequest, HostResponse, host_bridge};
 use crate::host::channel::{DeviceTarget, DeviceTransferHandler};
 use crate::host::contract::EventEnvelope;
-use crate::host::runtime_events::{map_runtime_event, progress_event_to_json};
-use crate::onboarding::OnboardingPhase;
-use crate::permissions::{PermissionKind, SharedPermissionStore};
+use crate::host::runtime_events::{
+    overall_progress_to_json, progress_event_to_json, runtime_event_envelope,
+};
+use crate::onboarding::{CalibrationSession, CalibrationStep, OnboardingPhase};
+use crate::permissions::{PermissionKind, PermissionScope, SharedPermissionStore};
 use crate::pipeline::coordinator::PipelineCoordinator;
 use crate::pipeline::messages::{AudioChunk, GateCommand, TextInjection};
-use crate::progress::ProgressEvent;
+use crate::platform::lifecycle::{LifecycleState, PowerEvent, Transition};
+use crate::progress::{ProgressCallback, ProgressEvent, ProgressTree};
 use crate::runtime::RuntimeEvent;
 use crate::runtime_audit::{RuntimeAuditEntry, RuntimeAuditSource};
 use crate::startup::initialize_models_with_progress;
 use crate::time_util::now_epoch_secs;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc};
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
@@ -142,6 +151,8 @@ pub struct FaeDeviceTransferHandler {
     device_watcher_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
     /// Handle for the memory pressure monitor task.
     memory_pressure_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Handle for the battery-aware power profile monitor task.
+    power_monitor_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
     /// Handle for the x0x network message listener task.
     x0x_listener_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
     /// Current pipeline operating mode (updated on degraded mode transitions).
@@ -155,6 +166,22 @@ pub struct FaeDeviceTransferHandler {
     scheduler_llm: Arc<Mutex<Option<Arc<crate::llm::LocalLlm>>>>,
     /// Handle for the background scheduler task.
     scheduler_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Analytics for the current (or most recent) pipeline run.
+    conversation_analytics: Arc<Mutex<Option<ConversationAnalytics>>>,
+    /// The latest user message and reply, for thumbs-down feedback.
+    last_turn: Arc<Mutex<LastTurn>>,
+    /// Prompt experiment variant of the current (or most recent) pipeline
+    /// run, if an experiment is configured.
+    experiment_trial: Mutex<Option<Trial>>,
+    /// Onboarding audio calibration in progress, shared with the task
+    /// recording the current step.
+    calibration: Arc<Mutex<Option<CalibrationSession>>>,
+    /// Whether the runtime is suspended for system sleep.
+    lifecycle: Mutex<LifecycleState>,
+    /// Set when a suspend stopped a running pipeline, so wake restarts it.
+    resume_on_wake: std::sync::atomic::AtomicBool,
+    /// Handle for the task that notices an unannounced sleep.
+    wake_watcher_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
 }
 
 impl std::fmt::Debug for FaeDeviceTransferHandler {
@@ -172,11 +199,11 @@ impl std::fmt::Debug for FaeDeviceTransferHandler {
 fn register_apple_stores() {
     use crate::fae_llm::tools::apple::applescript::{
         ApplescriptCalendarStore, ApplescriptContactStore, ApplescriptMailStore,
-        ApplescriptNoteStore, ApplescriptReminderStore,
+        ApplescriptMediaPlayer, ApplescriptNoteStore, ApplescriptReminderStore,
     };
     use crate::fae_llm::tools::apple::{
-        register_calendar_store, register_contact_store, register_mail_store, register_note_store,
-        register_reminder_store,
+        register_calendar_store, register_contact_store, register_mail_store,
+        register_media_player, register_note_store, register_reminder_store,
     };
 
     register_contact_store(Arc::new(ApplescriptContactStore));
@@ -184,6 +211,7 @@ fn register_apple_stores() {
     register_reminder_store(Arc::new(ApplescriptReminderStore));
     register_note_store(Arc::new(ApplescriptNoteStore));
     register_mail_store(Arc::new(ApplescriptMailStore));
+    register_media_player(Arc::new(ApplescriptMediaPlayer));
     info!("registered AppleScript-backed Apple ecosystem stores");
 }
 
@@ -204,6 +232,22 @@ impl FaeDeviceTransferHandler {
         // handles permission checks at execution time.
         register_apple_stores();
 
+        if config.offline_mode {
+            crate::offline::set_offline(true);
+        }
+        if config.accessibility.enabled {
+            crate::accessibility::apply(&config.accessibility);
+        }
+        if config.captions.mode != CaptionsMode::Off {
+            crate::captions::apply(&config.captions);
+        }
+        if config.theme.pack.is_some() {
+            crate::theme::apply(&config.theme);
+        }
+        if config.language.is_some() {
+            crate::i18n::set_language(config.language.as_deref());
+        }
+
         Self {
             config: Mutex::new(config),
             config_path,
@@ -229,11 +273,19 @@ impl FaeDeviceTransferHandler {
             clean_exit_flag: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             device_watcher_handle: Mutex::new(None),
             memory_pressure_handle: Mutex::new(None),
+            power_monitor_handle: Mutex::new(None),
             x0x_listener_handle: Mutex::new(None),
             pipeline_mode: Mutex::new(crate::pipeline::coordinator::PipelineMode::Conversation),
             skill_discovery_cache: Mutex::new(SkillDiscoveryCacheState::default()),
             scheduler_llm: Arc::new(Mutex::new(None)),
+            conversation_analytics: Arc::new(Mutex::new(None)),
+            last_turn: Arc::new(Mutex::new(LastTurn::default())),
+            experiment_trial: Mutex::new(None),
+            calibration: Arc::new(Mutex::new(None)),
             scheduler_handle: Mutex::new(None),
+            lifecycle: Mutex::new(LifecycleState::default()),
+            resume_on_wake: std::sync::atomic::AtomicBool::new(false),
+            wake_watcher_handle: Mutex::new(None),
         }
     }
 
@@ -276,7 +328,13 @@ impl FaeDeviceTransferHandler {
     fn emit_event(&self, event: &str, payload: serde_json::Value) {
         let envelope =
             EventEnvelope::new(uuid::Uuid::new_v4().to_string(), event.to_owned(), payload);
-        let _ = self.event_tx.send(envelope);
+        send_event(&self.event_tx, envelope);
+    }
+
+    /// Emit a [`RuntimeEvent`] raised by the handler itself rather than the
+    /// pipeline (which may not be running).
+    fn emit_runtime_event(&self, event: &RuntimeEvent) {
+        send_event(&self.event_tx, runtime_event_envelope(event));
     }
 
     /// Best-effort mutation-manifest sync.
@@ -308,7 +366,10 @@ impl FaeDeviceTransferHandler {
 
     /// Save the current config to disk.
     fn save_config(&self) -> Result<()> {
-        let guard = self.lock_config()?;
+        let mut guard = self.lock_config()?;
+        // Offline mode can also be switched by voice, which bypasses this
+        // copy of the config; the process-wide flag is authoritative.
+        guard.offline_mode = crate::offline::is_offline();
         guard.save_to_file(&self.config_path)
     }
 
@@ -320,6 +381,56 @@ impl FaeDeviceTransferHandler {
             .map_err(|e| SpeechError::Config(format!("config lock poisoned: {e}")))
     }
 
+    /// Statistics of the current (or most recent) conversation, if anything
+    /// was said in it.
+    fn current_analytics(&self) -> Option<ConversationStats> {
+        let guard = self.conversation_analytics.lock().ok()?;
+        let stats = guard.as_ref()?.stats();
+        (stats.user_turns > 0 || stats.assistant_turns > 0).then_some(stats)
+    }
+
+    /// Fetch the OpenRouter model catalog in the background, using the
+    /// configured OpenRouter provider (and its key) when there is one.
+    ///
+    /// Emits `models.openrouter.updated` once the cache has been written.
+    fn refresh_openrouter_catalog(&self) {
+        let provider = crate::fae_llm::config::read_config(&crate::fae_dirs::llm_config_file())
+            .ok()
+            .and_then(|cfg| {
+                cfg.providers
+                    .into_values()
+                    .find(openrouter::is_openrouter_provider)
+            })
+            .unwrap_or_else(|| ProviderConfig {
+                endpoint_type: crate::fae_llm::types::EndpointType::OpenAiCompletions,
+                enabled: true,
+                base_url: openrouter::OPENROUTER_BASE_URL.to_owned(),
+                api_key: crate::fae_llm::config::SecretRef::None,
+                models: Vec::new(),
+                azure: None,
+            });
+        let event_tx = self.event_tx.clone();
+        self.tokio_handle.spawn(async move {
+            match openrouter::refresh_catalog(&provider).await {
+                Ok(models) => {
+                    let envelope = EventEnvelope::new(
+                        uuid::Uuid::new_v4().to_string(),
+                        "models.openrouter.updated".to_owned(),
+                        serde_json::json!({"count": models.len()}),
+                    );
+                    send_event(&event_tx, envelope);
+                }
+                Err(e) => warn!(error = %e, "OpenRouter model catalog refresh failed"),
+            }
+        });
+    }
+
+    fn lock_calibration(&self) -> Result<std::sync::MutexGuard<'_, Option<CalibrationSession>>> {
+        self.calibration
+            .lock()
+            .map_err(|e| SpeechError::Audio(format!("calibration lock poisoned: {e}")))
+    }
+
     /// Parse a capability string to a `PermissionKind`.
     fn parse_permission(capability: &str) -> Result<PermissionKind> {
         capability.parse::<PermissionKind>().map_err(|_| {
@@ -334,14 +445,37 @@ impl FaeDeviceTransferHandler {
         })
     }
 
-    fn modify_capability(&self, capability: &str, scope: Option<&str>, grant: bool) -> Result<()> {
+    /// Parse a capability scope string; no scope means full access.
+    fn parse_scope(scope: Option<&str>) -> Result<PermissionScope> {
+        scope.map_or(Ok(PermissionScope::Full), |raw| {
+            raw.parse::<PermissionScope>().map_err(|_| {
+                SpeechError::Pipeline(format!(
+                    "invalid capability scope `{raw}`; expected full, read_only, or \
+                     comma-separated absolute paths"
+                ))
+            })
+        })
+    }
+
+    fn modify_capability(
+        &self,
+        capability: &str,
+        scope: Option<&str>,
+        grant: bool,
+        ttl: Option<Duration>,
+    ) -> Result<()> {
         let kind = Self::parse_permission(capability)?;
         let action = if grant { "grant" } else { "deny" };
-        info!(%kind, ?scope, "capability.{action} — persisting");
+        info!(%kind, ?scope, ?ttl, "capability.{action} — persisting");
+        let scope = if grant {
+            Self::parse_scope(scope)?
+        } else {
+            PermissionScope::Full
+        };
 
         let mut guard = self.lock_config()?;
         if grant {
-            guard.permissions.grant(kind);
+            guard.permissions.grant_scoped(kind, scope.clone(), ttl);
         } else {
             guard.permissions.deny(kind);
         }
@@ -351,7 +485,7 @@ impl FaeDeviceTransferHandler {
 
         if let Ok(mut perms) = self.shared_permissions.lock() {
             if grant {
-                perms.grant(kind);
+                perms.grant_scoped(kind, scope, ttl);
             } else {
                 perms.deny(kind);
             }
@@ -734,6 +868,130 @@ impl FaeDeviceTransferHandler {
         })
     }
 
+    /// Apply a `config.patch` for a `privacy.*` key.
+    ///
+    /// Enabling `privacy.encrypt_at_rest` creates the keychain data key on
+    /// first use so sealed writes never happen without a recoverable key. It
+    /// seals sessions and memory backups, not the live memory database.
+    fn patch_privacy_config(&self, key: &str, value: &serde_json::Value) -> Result<()> {
+        let mut guard = self.lock_config()?;
+
+        match key {
+            "privacy.encrypt_at_rest" => {
+                let Some(enabled) = value.as_bool() else {
+                    return Ok(());
+                };
+                if enabled {
+                    let manager = crate::credentials::create_manager();
+                    crate::privacy::ensure_data_key(&mut guard.privacy, manager.as_ref())?;
+                }
+                guard.privacy.encrypt_at_rest = enabled;
+            }
+            "privacy.session_archive_after_days" => {
+                if let Some(days) = value.as_u64() {
+                    guard.privacy.session_archive_after_days =
+                        u32::try_from(days).unwrap_or(u32::MAX);
+                }
+            }
+            "privacy.session_retention_days" => {
+                if let Some(days) = value.as_u64() {
+                    guard.privacy.session_retention_days = u32::try_from(days).unwrap_or(u32::MAX);
+                }
+            }
+            "privacy.session_max_total_mb" => {
+                if let Some(mb) = value.as_u64() {
+                    guard.privacy.session_max_total_mb = mb;
+                }
+            }
+            _ => {
+                warn!(key, "config.patch: unknown privacy key, ignored");
+                return Ok(());
+            }
+        }
+
+        drop(guard);
+        self.save_config()?;
+        info!(key, "config.patch applied");
+        Ok(())
+    }
+
+    /// Apply a `config.patch` for a `sync.*` key.
+    ///
+    /// `sync.webdav_password` is stored in the keychain, never in config.
+    fn patch_sync_config(&self, key: &str, value: &serde_json::Value) -> Result<()> {
+        use crate::config::SyncBackendKind;
+
+        let mut guard = self.lock_config()?;
+        let sync = &mut guard.sync;
+        match key {
+            "sync.enabled" => {
+                if let Some(v) = value.as_bool() {
+                    sync.enabled = v;
+                }
+            }
+            "sync.backend" => {
+                if let Ok(kind) = serde_json::from_value::<SyncBackendKind>(value.clone()) {
+                    sync.backend = kind;
+                }
+            }
+            "sync.folder" => {
+                sync.folder = value
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(PathBuf::from);
+            }
+            "sync.webdav_url" => {
+                sync.webdav_url = value
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_owned);
+            }
+            "sync.webdav_username" => {
+                sync.webdav_username = value
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_owned);
+            }
+            "sync.webdav_password" => {
+                let Some(password) = value.as_str() else {
+                    return Ok(());
+                };
+                let manager = crate::credentials::create_manager();
+                sync.webdav_password = manager
+                    .store("fae.sync.webdav_password", password)
+                    .map_err(|e| SpeechError::Sync(format!("cannot store WebDAV password: {e}")))?;
+            }
+            "sync.config" | "sync.skills" | "sync.memories" | "sync.preferences" => {
+                let Some(v) = value.as_bool() else {
+                    return Ok(());
+                };
+                match key {
+                    "sync.config" => sync.config = v,
+                    "sync.skills" => sync.skills = v,
+                    "sync.memories" => sync.memories = v,
+                    _ => sync.preferences = v,
+                }
+            }
+            "sync.interval_mins" => {
+                if let Some(mins) = value.as_u64() {
+                    sync.interval_mins = u32::try_from(mins).unwrap_or(u32::MAX);
+                }
+            }
+            _ => {
+                warn!(key, "config.patch: unknown sync key, ignored");
+                return Ok(());
+            }
+        }
+
+        drop(guard);
+        self.save_config()?;
+        info!(key, "config.patch applied");
+        Ok(())
+    }
+
     /// Apply a `config.patch` for a nested channel key (Discord or WhatsApp).
     fn patch_channel_config(&self, key: &str, value: &serde_json::Value) -> Result<()> {
         use crate::config::{DiscordChannelConfig, WhatsAppChannelConfig};
@@ -909,11 +1167,34 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
     }
 
     fn grant_capability(&self, capability: &str, scope: Option<&str>) -> Result<()> {
-        self.modify_capability(capability, scope, true)
+        self.modify_capability(capability, scope, true, None)
+    }
+
+    fn grant_capability_for(
+        &self,
+        capability: &str,
+        scope: Option<&str>,
+        expires_in_secs: u64,
+    ) -> Result<()> {
+        self.modify_capability(
+            capability,
+            scope,
+            true,
+            Some(Duration::from_secs(expires_in_secs)),
+        )
     }
 
     fn deny_capability(&self, capability: &str, scope: Option<&str>) -> Result<()> {
-        self.modify_capability(capability, scope, false)
+        self.modify_capability(capability, scope, false, None)
+    }
+
+    fn list_capabilities(&self) -> Result<serde_json::Value> {
+        let grants = self
+            .shared_permissions
+            .lock()
+            .map(|perms| perms.list())
+            .unwrap_or_default();
+        Ok(serde_json::json!({ "grants": grants }))
     }
 
     fn query_onboarding_state(&self) -> Result<serde_json::Value> {
@@ -1197,6 +1478,210 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         Ok(())
     }
 
+    fn query_onboarding_calibration_state(&self) -> Result<serde_json::Value> {
+        let audio = self.lock_config()?.audio.clone();
+        let profile = crate::audio::calibration::profile_for(&audio).cloned();
+        let mut state = match self.lock_calibration()?.as_ref() {
+            Some(session) => session.status(),
+            None => serde_json::json!({
+                "active": false,
+                "device": crate::audio::calibration::device_key(&audio),
+            }),
+        };
+        state["calibrated"] = serde_json::json!(profile.is_some());
+        state["profile"] = serde_json::to_value(profile).unwrap_or(serde_json::Value::Null);
+        Ok(state)
+    }
+
+    fn onboarding_calibration_start(&self) -> Result<serde_json::Value> {
+        let audio = self.lock_config()?.audio.clone();
+        let device = crate::audio::calibration::device_key(&audio);
+        info!(device, "onboarding.calibration.start");
+
+        let session = CalibrationSession::new(device, audio.input_sample_rate);
+        let status = session.status();
+        *self.lock_calibration()? = Some(session);
+        Ok(status)
+    }
+
+    fn onboarding_calibration_measure(&self) -> Result<serde_json::Value> {
+        let audio = self.lock_config()?.audio.clone();
+        let step = self
+            .lock_calibration()?
+            .as_mut()
+            .ok_or_else(|| {
+                SpeechError::Audio(
+                    "onboarding.calibration.measure: no calibration in progress".to_owned(),
+                )
+            })?
+            .begin_measurement()?;
+        info!(step = step.as_str(), "onboarding.calibration.measure");
+
+        let session = Arc::clone(&self.calibration);
+        let event_tx = self.event_tx.clone();
+        self.tokio_handle.spawn(async move {
+            use crate::audio::calibration;
+
+            let recorded = match step {
+                CalibrationStep::AmbientNoise => {
+                    calibration::record(&audio, calibration::AMBIENT_DURATION).await
+                }
+                CalibrationStep::ReadSentence => {
+                    calibration::record(&audio, calibration::SENTENCE_DURATION).await
+                }
+                CalibrationStep::EchoTest => calibration::record_echo_test(&audio).await,
+                CalibrationStep::Complete => Ok(Vec::new()),
+            };
+
+            let Ok(mut guard) = session.lock() else {
+                return;
+            };
+            // The session was restarted or reset while recording.
+            let Some(session) = guard.as_mut().filter(|s| s.is_measuring()) else {
+                return;
+            };
+            let (event, payload) = match recorded.and_then(|samples| session.record(&samples)) {
+                Ok(()) => ("onboarding.calibration.progress", session.status()),
+                Err(e) => {
+                    warn!(step = step.as_str(), "calibration step failed: {e}");
+                    session.abort_measurement();
+                    let mut status = session.status();
+                    status["error"] = serde_json::json!(e.to_string());
+                    ("onboarding.calibration.failed", status)
+                }
+            };
+            drop(guard);
+
+            let envelope =
+                EventEnvelope::new(uuid::Uuid::new_v4().to_string(), event.to_owned(), payload);
+            send_event(&event_tx, envelope);
+        });
+
+        Ok(serde_json::json!({
+            "accepted": true,
+            "step": step.as_str(),
+            "duration_ms": step.duration().as_millis() as u64,
+        }))
+    }
+
+    fn onboarding_calibration_finish(&self) -> Result<serde_json::Value> {
+        info!("onboarding.calibration.finish");
+
+        let (agc, output_device) = {
+            let guard = self.lock_config()?;
+            (guard.agc.clone(), guard.audio.output_device.clone())
+        };
+        let (device, profile) = {
+            let guard = self.lock_calibration()?;
+            let session = guard.as_ref().ok_or_else(|| {
+                SpeechError::Audio(
+                    "onboarding.calibration.finish: no calibration in progress".to_owned(),
+                )
+            })?;
+            (
+                session.device().to_owned(),
+                session.profile(&agc, output_device)?,
+            )
+        };
+
+        {
+            let mut guard = self.lock_config()?;
+            guard
+                .audio
+                .calibrations
+                .insert(device.clone(), profile.clone());
+        }
+        self.save_config()?;
+        *self.lock_calibration()? = None;
+
+        info!(
+            device,
+            vad_threshold = profile.vad_threshold,
+            input_gain = profile.input_gain,
+            aec_required = profile.aec_required,
+            "onboarding.calibration.finish persisted to config"
+        );
+        Ok(serde_json::json!({
+            "accepted": true,
+            "device": device,
+            "profile": profile,
+            "applies_on_restart": self.pipeline_state() == PipelineState::Running,
+        }))
+    }
+
+    fn onboarding_calibration_reset(&self) -> Result<()> {
+        info!("onboarding.calibration.reset");
+
+        *self.lock_calibration()? = None;
+        let removed = {
+            let mut guard = self.lock_config()?;
+            let device = crate::audio::calibration::device_key(&guard.audio);
+            guard.audio.calibrations.remove(&device).is_some()
+        };
+        if removed {
+            self.save_config()?;
+        }
+        Ok(())
+    }
+
+    fn onboarding_setup_check(
+        &self,
+        providers: BTreeMap<String, ProviderConfig>,
+        load_model: bool,
+    ) -> Result<serde_json::Value> {
+        let config = self.lock_config()?.clone();
+        // A running pipeline already holds the model; loading a second copy
+        // would only measure (and risk) double the memory.
+        let load_model = load_model && self.pipeline_state() != PipelineState::Running;
+        info!(
+            providers = providers.len(),
+            load_model, "onboarding.setup.check"
+        );
+
+        let event_tx = self.event_tx.clone();
+        let provider_count = providers.len();
+        self.tokio_handle.spawn(async move {
+            let callback = progress_events(event_tx.clone(), "onboarding.setup.progress");
+
+            let report =
+                crate::startup::first_run_check(&config, &providers, load_model, Some(&callback))
+                    .await;
+            let envelope = EventEnvelope::new(
+                uuid::Uuid::new_v4().to_string(),
+                "onboarding.setup.completed".to_owned(),
+                serde_json::json!({
+                    "ready": report.ready(),
+                    "checks": report.checks,
+                }),
+            );
+            send_event(&event_tx, envelope);
+        });
+
+        Ok(serde_json::json!({
+            "accepted": true,
+            "providers": provider_count,
+            "load_model": load_model,
+        }))
+    }
+
+    fn onboarding_llm_import_discover(&self) -> Result<serde_json::Value> {
+        let found = import::discover(&ImportContext::detect());
+        info!(found = found.len(), "onboarding.llm_import.discover");
+        let providers: Vec<_> = found.iter().map(|d| d.summary()).collect();
+        Ok(serde_json::json!({ "providers": providers }))
+    }
+
+    fn onboarding_llm_import_apply(&self, sources: &[String]) -> Result<Vec<String>> {
+        let chosen: Vec<_> = import::discover(&ImportContext::detect())
+            .into_iter()
+            .filter(|d| sources.iter().any(|s| s == d.source.as_str()))
+            .collect();
+        let added = import::import_into_file(&crate::fae_dirs::llm_config_file(), &chosen)
+            .map_err(|e| SpeechError::Config(format!("LLM provider import failed: {e}")))?;
+        info!(?added, "onboarding.llm_import.apply");
+        Ok(added)
+    }
+
     fn reload_skills(&self) -> Result<()> {
         info!("skills.reload — re-scanning custom skills directory");
         self.invalidate_skill_discovery_cache();
@@ -1413,68 +1898,346 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             .map_err(|e| SpeechError::Config(format!("skill.credential.clear failed: {e}")))
     }
 
-    fn skill_discovery_search(
+    fn conversation_sessions_search(
         &self,
         query: &str,
         limit: usize,
-    ) -> Result<Vec<crate::skills::discovery::SkillSearchResult>> {
-        info!(query, limit, "skill.discovery.search");
+    ) -> Result<Vec<crate::fae_llm::session::SessionSearchHit>> {
+        info!(query, limit, "conversation.sessions.search");
 
-        let trimmed_query = query.trim();
+        let trimmed_query = query.trim().to_owned();
         if trimmed_query.is_empty() || limit == 0 {
             return Ok(Vec::new());
         }
 
-        let (python_skills_dir, memory_root) = {
-            let config = self
-                .config
-                .lock()
-                .map_err(|e| SpeechError::Config(format!("config lock poisoned: {e}")))?;
-            (
-                config.python_skills.skills_dir.clone(),
-                config.memory.root_dir.clone(),
-            )
-        };
-
-        let skills_dir = crate::skills::skills_dir();
-        let signature = Self::compute_skill_discovery_signature(&skills_dir, &python_skills_dir);
-        let db_path = Self::skill_discovery_db_path(&memory_root);
-        let index = crate::skills::discovery::SkillDiscoveryIndex::open(&db_path)
-            .map_err(|e| SpeechError::Config(format!("skill discovery index init failed: {e}")))?;
-
-        let should_rebuild = self
-            .skill_discovery_cache
-            .lock()
-            .map(|cache| cache.signature.as_deref() != Some(signature.as_str()))
-            .unwrap_or(true);
+        let privacy = self.lock_config()?.privacy.clone();
+        let store = crate::privacy::open_session_store(&privacy)?;
+
+        // Routing runs inside the runtime, so drive the async search on a
+        // helper thread rather than blocking a worker.
+        let handle = self.tokio_handle.clone();
+        std::thread::Builder::new()
+            .name("fae-session-search".to_owned())
+            .spawn(move || {
+                handle.block_on(crate::fae_llm::session::search_sessions(
+                    &store,
+                    &trimmed_query,
+                    limit,
+                ))
+            })
+            .map_err(|e| SpeechError::Config(format!("session search thread failed: {e}")))?
+            .join()
+            .map_err(|_| SpeechError::Config("session search thread panicked".to_owned()))?
+            .map_err(|e| SpeechError::Config(format!("session search failed: {e}")))
+    }
 
-        if should_rebuild {
-            crate::skills::discovery::rebuild_skill_index_deterministic(
-                &index,
-                &skills_dir,
-                &python_skills_dir,
-            )
-            .map_err(|e| SpeechError::Config(format!("skill discovery rebuild failed: {e}")))?;
+    fn conversation_session_thinking(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<crate::fae_llm::session::ThinkingEntry>> {
+        use crate::fae_llm::session::SessionStore as _;
+
+        let privacy = self.lock_config()?.privacy.clone();
+        let store = crate::privacy::open_session_store(&privacy)?;
+        let id = session_id.to_owned();
+
+        // Same helper thread as the session search above.
+        let handle = self.tokio_handle.clone();
+        let session = std::thread::Builder::new()
+            .name("fae-session-thinking".to_owned())
+            .spawn(move || handle.block_on(store.load(&id)))
+            .map_err(|e| SpeechError::Config(format!("session load thread failed: {e}")))?
+            .join()
+            .map_err(|_| SpeechError::Config("session load thread panicked".to_owned()))?
+            .map_err(|e| SpeechError::Config(format!("session load failed: {e}")))?;
+        Ok(session.thinking)
+    }
+
+    fn conversation_analytics(
+        &self,
+        conversation_id: Option<&str>,
+    ) -> Result<Option<ConversationStats>> {
+        let current = self.current_analytics();
+        let id = match conversation_id {
+            None => return Ok(current),
+            Some(id) => id,
+        };
+        if let Some(stats) = current.filter(|s| s.conversation_id == id) {
+            return Ok(Some(stats));
+        }
+        let privacy = self.lock_config()?.privacy.clone();
+        AnalyticsStore::open(&privacy)?.load(id)
+    }
+
+    fn conversation_analytics_list(&self, limit: usize) -> Result<Vec<ConversationStats>> {
+        let privacy = self.lock_config()?.privacy.clone();
+        let mut list = AnalyticsStore::open(&privacy)?.list(limit)?;
+        // The live conversation may be ahead of its last saved snapshot.
+        if let Some(current) = self.current_analytics() {
+            list.retain(|s| s.conversation_id != current.conversation_id);
+            list.insert(0, current);
+            list.truncate(limit);
+        }
+        Ok(list)
+    }
 
-            if let Ok(mut cache) = self.skill_discovery_cache.lock() {
-                cache.signature = Some(signature);
-                #[cfg(test)]
-                {
-                    cache.rebuild_count = cache.rebuild_count.saturating_add(1);
-                }
+    fn record_conversation_feedback(&self, right_answer: Option<&str>) -> Result<bool> {
+        let (asked, replied) = {
+            let turn = self
+                .last_turn
+                .lock()
+                .map_err(|e| SpeechError::Pipeline(format!("last turn lock poisoned: {e}")))?;
+            if turn.assistant_text.is_empty() {
+                return Ok(false);
             }
-        }
+            (turn.user_text.clone(), turn.assistant_text.clone())
+        };
+        Ok(crate::intelligence::record_correction(
+            &crate::fae_dirs::corrections_file(),
+            crate::intelligence::FeedbackSource::ThumbsDown,
+            &asked,
+            &replied,
+            right_answer,
+        )
+        .is_some())
+    }
+
+    fn experiment_report(&self, experiment_id: Option<&str>) -> Result<Option<ExperimentReport>> {
+        let configured = self.lock_config()?.experiment.clone();
+        let experiment = match (configured, experiment_id) {
+            (Some(experiment), None) => experiment,
+            (Some(experiment), Some(id)) if experiment.id == id => experiment,
+            // Past experiments report by id; their variant names are gone.
+            (_, Some(id)) => PromptExperiment {
+                id: id.to_owned(),
+                ..Default::default()
+            },
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(
+            ExperimentStore::load(&crate::fae_dirs::experiments_file()).report(&experiment),
+        ))
+    }
 
-        let query_embedding = crate::skills::discovery::deterministic_embedding(trimmed_query);
-        index
-            .search(&query_embedding, limit)
-            .map_err(|e| SpeechError::Config(format!("skill discovery search failed: {e}")))
+    fn start_meeting(&self) -> Result<Option<String>> {
+        // Transcripts flow through the pipeline; without it nothing is heard.
+        if self.pipeline_state() != PipelineState::Running {
+            return Err(SpeechError::Pipeline(
+                "meeting mode needs the runtime to be running".to_owned(),
+            ));
+        }
+        let config = self.lock_config()?.meeting.clone();
+        crate::meeting::start(&config, true)
+            .map(Some)
+            .map_err(SpeechError::Pipeline)
     }
 
-    fn skill_generate(&self, intent: &str, confirm: bool) -> Result<serde_json::Value> {
-        info!(intent, confirm, "skill.generate");
+    fn stop_meeting(&self) -> Result<Option<String>> {
+        Ok(crate::meeting::stop())
+    }
 
-        let pipeline = crate::skills::skill_generator::SkillGeneratorPipeline::with_defaults();
+    fn synthesize_to_file(
+        &self,
+        text: &str,
+        path: &Path,
+    ) -> Result<Option<crate::tts::export::AudioExport>> {
+        let tts = self.lock_config()?.tts.clone();
+        let text = text.to_owned();
+        let path = path.to_path_buf();
+
+        // Routing runs inside the runtime, so synthesize on a helper thread
+        // rather than blocking a worker. The engine is separate from the
+        // pipeline's, so speech in progress is not interrupted.
+        let handle = self.tokio_handle.clone();
+        std::thread::Builder::new()
+            .name("fae-synthesize-to-file".to_owned())
+            .spawn(move || {
+                handle.block_on(async {
+                    let mut engine = crate::tts::create_engine(&tts).await?;
+                    crate::tts::export::synthesize_to_file(
+                        engine.as_mut(),
+                        &text,
+                        &path,
+                        &mut |_| {},
+                    )
+                    .await
+                })
+            })
+            .map_err(|e| SpeechError::Tts(format!("speech export thread failed: {e}")))?
+            .join()
+            .map_err(|_| SpeechError::Tts("speech export thread panicked".to_owned()))?
+            .map(Some)
+    }
+
+    fn tts_capabilities(&self) -> Result<Option<crate::tts::TtsCapabilities>> {
+        let guard = self.lock_config()?;
+        Ok(Some(crate::tts::capabilities(&guard.tts)))
+    }
+
+    fn offer_handoff(&self, device: &str) -> Result<Option<crate::handoff::HandoffOffer>> {
+        crate::handoff::offer(device, None)
+            .map(Some)
+            .map_err(SpeechError::Pipeline)
+    }
+
+    fn accept_handoff(&self, uri: &str) -> Result<Option<crate::handoff::HandoffReceipt>> {
+        // The LLM stage picks the conversation up; without it nothing would.
+        if self.pipeline_state() != PipelineState::Running {
+            return Err(SpeechError::Pipeline(
+                "handoff needs the runtime to be running".to_owned(),
+            ));
+        }
+        crate::handoff::accept(uri)
+            .map(Some)
+            .map_err(SpeechError::Pipeline)
+    }
+
+    fn sync_status(&self) -> Result<Option<crate::sync::SyncStatus>> {
+        let guard = self.lock_config()?;
+        let paths = crate::sync::SyncPaths::for_memory_root(&guard.memory.root_dir);
+        Ok(Some(crate::sync::status(&guard.sync, &paths)))
+    }
+
+    fn sync_now(&self) -> Result<Option<crate::sync::SyncReport>> {
+        // Sync a copy so the config lock is not held across network I/O.
+        let mut config = self.lock_config()?.clone();
+        let paths = crate::sync::SyncPaths::for_memory_root(&config.memory.root_dir);
+        let report = crate::sync::sync_now(&mut config, true, &paths)?;
+        if report.config_changed {
+            // Take only the synced sections; anything else may have changed
+            // meanwhile.
+            let shared = crate::sync::items::shared_config(&config)?;
+            crate::sync::items::apply_shared_config(&mut *self.lock_config()?, &shared)?;
+            self.save_config()?;
+            info!("sync: shared config updated from another device");
+        }
+        Ok(Some(report))
+    }
+
+    fn set_sync_key(&self, key: Option<&str>) -> Result<Option<serde_json::Value>> {
+        let manager = crate::credentials::create_manager();
+        let mut guard = self.lock_config()?;
+        let response = match key {
+            Some(key) => {
+                crate::sync::set_key(&mut guard.sync, manager.as_ref(), key)?;
+                serde_json::json!({ "key_set": true })
+            }
+            None => {
+                let key = crate::sync::create_key(&mut guard.sync, manager.as_ref())?;
+                serde_json::json!({ "key_set": true, "key": key })
+            }
+        };
+        drop(guard);
+        self.save_config()?;
+        Ok(Some(response))
+    }
+
+    fn export_corrections(&self, path: &Path) -> Result<usize> {
+        crate::intelligence::CorrectionStore::load(&crate::fae_dirs::corrections_file())
+            .export_jsonl(path)
+            .map_err(SpeechError::Memory)
+    }
+
+    fn request_data_forget(&self, export: bool, wipe: bool) -> Result<serde_json::Value> {
+        let Some(action) = crate::privacy::PrivacyAction::from_flags(export, wipe) else {
+            return Err(SpeechError::Privacy(
+                "data.forget requires export or wipe".to_owned(),
+            ));
+        };
+        let approval_tx = self
+            .tool_approval_tx
+            .lock()
+            .ok()
+            .and_then(|guard| guard.as_ref().cloned())
+            .ok_or_else(|| {
+                SpeechError::Privacy(
+                    "data.forget needs a running pipeline to confirm the request".to_owned(),
+                )
+            })?;
+        let config = self.lock_config()?;
+        let request = DataForgetRequest {
+            action,
+            source: crate::privacy::PrivacyRequestSource::HostCommand,
+            data_dir: config.memory.root_dir.clone(),
+            cache_dir: crate::fae_dirs::cache_dir(),
+            privacy: config.privacy.clone(),
+        };
+        drop(config);
+
+        let id = spawn_data_forget(&self.tokio_handle, &approval_tx, &self.event_tx, request)?;
+        info!(
+            approval_request_id = id,
+            ?action,
+            "data.forget awaiting confirmation"
+        );
+        Ok(serde_json::json!({
+            "accepted": true,
+            "approval_request_id": id.to_string(),
+        }))
+    }
+
+    fn skill_discovery_search(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::skills::discovery::SkillSearchResult>> {
+        info!(query, limit, "skill.discovery.search");
+
+        let trimmed_query = query.trim();
+        if trimmed_query.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let (python_skills_dir, memory_root) = {
+            let config = self
+                .config
+                .lock()
+                .map_err(|e| SpeechError::Config(format!("config lock poisoned: {e}")))?;
+            (
+                config.python_skills.skills_dir.clone(),
+                config.memory.root_dir.clone(),
+            )
+        };
+
+        let skills_dir = crate::skills::skills_dir();
+        let signature = Self::compute_skill_discovery_signature(&skills_dir, &python_skills_dir);
+        let db_path = Self::skill_discovery_db_path(&memory_root);
+        let index = crate::skills::discovery::SkillDiscoveryIndex::open(&db_path)
+            .map_err(|e| SpeechError::Config(format!("skill discovery index init failed: {e}")))?;
+
+        let should_rebuild = self
+            .skill_discovery_cache
+            .lock()
+            .map(|cache| cache.signature.as_deref() != Some(signature.as_str()))
+            .unwrap_or(true);
+
+        if should_rebuild {
+            crate::skills::discovery::rebuild_skill_index_deterministic(
+                &index,
+                &skills_dir,
+                &python_skills_dir,
+            )
+            .map_err(|e| SpeechError::Config(format!("skill discovery rebuild failed: {e}")))?;
+
+            if let Ok(mut cache) = self.skill_discovery_cache.lock() {
+                cache.signature = Some(signature);
+                #[cfg(test)]
+                {
+                    cache.rebuild_count = cache.rebuild_count.saturating_add(1);
+                }
+            }
+        }
+
+        let query_embedding = crate::skills::discovery::deterministic_embedding(trimmed_query);
+        index
+            .search(&query_embedding, limit)
+            .map_err(|e| SpeechError::Config(format!("skill discovery search failed: {e}")))
+    }
+
+    fn skill_generate(&self, intent: &str, confirm: bool) -> Result<serde_json::Value> {
+        info!(intent, confirm, "skill.generate");
+
+        let pipeline = crate::skills::skill_generator::SkillGeneratorPipeline::with_defaults();
         let staging_dir = crate::fae_dirs::data_dir().join("staging").join(format!(
             "gen-{}",
             std::time::SystemTime::now()
@@ -1704,8 +2467,18 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         }))
     }
 
-    fn request_conversation_inject_text(&self, text: &str) -> Result<()> {
-        info!(text, "conversation.inject_text requested");
+    fn request_conversation_inject_text(&self, text: &str, images: &[Vec<u8>]) -> Result<()> {
+        info!(
+            text,
+            images = images.len(),
+            "conversation.inject_text requested"
+        );
+        let images = if images.is_empty() {
+            Vec::new()
+        } else {
+            let budget = self.lock_config()?.llm.images.clone();
+            crate::llm::images::fit_attachments(images, &budget)?
+        };
         let guard = self
             .text_injection_tx
             .lock()
@@ -1713,6 +2486,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         if let Some(tx) = guard.as_ref() {
             tx.send(TextInjection {
                 text: text.to_owned(),
+                images,
                 fork_at_keep_count: None,
             })
             .map_err(|e| SpeechError::Pipeline(format!("text injection send failed: {e}")))?;
@@ -1809,7 +2583,12 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         // stores them in `pending_approvals` until `approval.respond` arrives.
         let (approval_tx, mut approval_rx) = mpsc::unbounded_channel::<ToolApprovalRequest>();
         let coordinator_approval_tx = approval_tx.clone();
+        // The event bridge turns a spoken "forget everything about me" into
+        // a confirmed data.forget request through the same approval channel.
+        let forget_approval_tx = approval_tx.clone();
         let (runtime_event_tx, mut runtime_event_rx) = broadcast::channel::<RuntimeEvent>(64);
+        let device_watcher_events = runtime_event_tx.clone();
+        let wake_watcher_events = runtime_event_tx.clone();
 
         // Voice approval channels: the approval bridge forwards metadata to the
         // coordinator so it can speak the prompt; the coordinator sends back
@@ -1842,12 +2621,48 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         // Clone what the async tasks need. The handler trait methods are sync
         // (&self) so we capture clones of Arc/Sender values for move into
         // async blocks.
-        let config = self.lock_config().map(|g| g.clone())?;
+        let mut config = self.lock_config().map(|g| g.clone())?;
+        let warn_low_quality_audio = config.audio.warn_low_quality;
         let scheduler_llm = Arc::clone(&self.scheduler_llm);
         let event_tx = self.event_tx.clone();
         let event_tx_bridge = self.event_tx.clone();
         let event_tx_approval = self.event_tx.clone();
+        let forget_handle = self.tokio_handle.clone();
+        let forget_data_dir = config.memory.root_dir.clone();
+        let forget_privacy = config.privacy.clone();
+        let offline_config_path = self.config_path.clone();
+        // Each pipeline run is one conversation for analytics purposes.
+        let analytics = Arc::clone(&self.conversation_analytics);
+        let conversation = ConversationAnalytics::new();
+        // ...and one trial of the prompt experiment, if one is running.
+        let trial = config
+            .experiment
+            .as_ref()
+            .map(|experiment| experiment.assign(conversation.conversation_id()));
+        if let (Some(experiment), Some(trial)) = (config.experiment.clone(), &trial) {
+            let variant = experiment.variant(trial.arm);
+            info!(
+                experiment = %trial.experiment_id,
+                variant = %variant.name,
+                "prompt experiment variant assigned"
+            );
+            variant.apply(&mut config.llm);
+        }
+        if let Ok(mut guard) = analytics.lock() {
+            *guard = Some(conversation);
+        }
+        if let Ok(mut guard) = self.experiment_trial.lock() {
+            *guard = trial.clone();
+        }
+        let analytics_store = AnalyticsStore::open(&config.privacy)
+            .inspect_err(|e| warn!("conversation analytics will not be saved: {e}"))
+            .ok();
+        let last_turn = Arc::clone(&self.last_turn);
+        let transcript_stream =
+            crate::transcript_stream::TranscriptStream::start(&config.transcript_stream);
         let pending_approvals_clone = Arc::clone(&self.pending_approvals);
+        let remote_approval = config.channels.remote_approval.clone();
+        let remote_handle = self.tokio_handle.clone();
         let cancel_token = token.clone();
         // Pass the live shared permission store so that JIT grants applied
         // while the pipeline runs are immediately visible to the tool gate.
@@ -1886,7 +2701,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                                 "tool_name": req.tool_name,
                             }),
                         );
-                        let _ = jit_event_tx.send(envelope);
+                        send_event(&jit_event_tx, envelope);
 
                         // Wait for the grant/deny to propagate through the
                         // shared permission store, then resolve the oneshot.
@@ -1925,23 +2740,21 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         let clean_exit_flag = Arc::clone(&self.clean_exit_flag);
         let clean_exit_for_pipeline = Arc::clone(&self.clean_exit_flag);
 
+        let progress_cancel = token.child_token();
+
         // Spawn the async startup + pipeline task.
         let pipeline_jh = self.tokio_handle.spawn(async move {
             // ── Task 3: Model loading ────────────────────────────
-            let progress_tx = event_tx.clone();
-            let callback: crate::progress::ProgressCallback =
-                Box::new(move |evt: ProgressEvent| {
-                    let payload = progress_event_to_json(&evt);
-                    let envelope = EventEnvelope::new(
-                        uuid::Uuid::new_v4().to_string(),
-                        "runtime.progress".to_owned(),
-                        payload,
-                    );
-                    let _ = progress_tx.send(envelope);
-                });
+            // Stopping the runtime cancels downloads and loads in progress.
+            let callback =
+                progress_events(event_tx.clone(), "runtime.progress").with_cancel(progress_cancel);
 
             let models = match initialize_models_with_progress(&config, Some(&callback)).await {
                 Ok(m) => m,
+                Err(SpeechError::Cancelled(task)) => {
+                    info!(task, "model initialization cancelled");
+                    return;
+                }
                 Err(e) => {
                     warn!("model initialization failed: {e}");
                     let envelope = EventEnvelope::new(
@@ -1949,7 +2762,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                         "runtime.error".to_owned(),
                         serde_json::json!({"error": format!("{e}")}),
                     );
-                    let _ = event_tx.send(envelope);
+                    send_event(&event_tx, envelope);
                     return;
                 }
             };
@@ -1961,6 +2774,16 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             {
                 *guard = Some(Arc::new(llm.shallow_clone()));
             }
+            if let Some(mut pending) = models.llm_loading.clone() {
+                let scheduler_llm = Arc::clone(&scheduler_llm);
+                tokio::spawn(async move {
+                    if let Ok(llm) = pending.wait().await
+                        && let Ok(mut guard) = scheduler_llm.lock()
+                    {
+                        *guard = Some(llm);
+                    }
+                });
+            }
 
             // ── Task 4: Create and spawn PipelineCoordinator ─────
             let coordinator = PipelineCoordinator::with_models(config, models)
@@ -1984,7 +2807,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                             "runtime.error".to_owned(),
                             serde_json::json!({"error": format!("{e}")}),
                         );
-                        let _ = event_tx.send(envelope);
+                        send_event(&event_tx, envelope);
                     }
                 }
                 _ = cancel_token.cancelled() => {
@@ -2015,13 +2838,48 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                                 if matches!(re, RuntimeEvent::AssistantGenerating { active: false }) {
                                     let _ = gate_tx_for_bridge.send(GateCommand::Engage);
                                 }
-                                let (name, payload) = map_runtime_event(&re);
-                                let envelope = EventEnvelope::new(
-                                    uuid::Uuid::new_v4().to_string(),
-                                    name,
-                                    payload,
+                                if matches!(re, RuntimeEvent::DataForgetRequested) {
+                                    let request = DataForgetRequest {
+                                        action: crate::privacy::PrivacyAction::Wipe,
+                                        source: crate::privacy::PrivacyRequestSource::Voice,
+                                        data_dir: forget_data_dir.clone(),
+                                        cache_dir: crate::fae_dirs::cache_dir(),
+                                        privacy: forget_privacy.clone(),
+                                    };
+                                    if let Err(e) = spawn_data_forget(
+                                        &forget_handle,
+                                        &forget_approval_tx,
+                                        &event_tx_bridge,
+                                        request,
+                                    ) {
+                                        warn!("voice data.forget request failed: {e}");
+                                    }
+                                }
+                                if let RuntimeEvent::OfflineModeChanged { offline } = re {
+                                    persist_offline_mode(&offline_config_path, offline);
+                                }
+                                record_analytics(
+                                    &analytics,
+                                    analytics_store.as_ref(),
+                                    trial.as_ref(),
+                                    &re,
                                 );
-                                let _ = event_tx_bridge.send(envelope);
+                                track_last_turn(&last_turn, &re);
+                                if let Some(stream) = &transcript_stream {
+                                    stream.observe(&re);
+                                }
+                                send_event(&event_tx_bridge, runtime_event_envelope(&re));
+                                let theme_update = crate::theme::engine().observe(&re);
+                                if let Some(update) = theme_update {
+                                    send_event(
+                                        &event_tx_bridge,
+                                        EventEnvelope::new(
+                                            uuid::Uuid::new_v4().to_string(),
+                                            crate::theme::STATE_CHANGED_EVENT,
+                                            update.to_payload(),
+                                        ),
+                                    );
+                                }
                             }
                             Err(broadcast::error::RecvError::Lagged(n)) => {
                                 warn!("event bridge lagged, skipped {n} events");
@@ -2051,6 +2909,11 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                                 let id = req.id;
                                 let name = req.name.clone();
                                 let input_json = req.input_json.clone();
+                                let read_back = req.read_back.clone();
+                                let remote_detail = read_back
+                                    .clone()
+                                    .or_else(|| req.preview.clone())
+                                    .unwrap_or_else(|| input_json.clone());
                                 // Emit event before storing so the UI sees
                                 // the request immediately.
                                 let envelope = EventEnvelope::new(
@@ -2060,20 +2923,32 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                                         "request_id": id.to_string(),
                                         "name": name,
                                         "input_json": input_json,
+                                        "preview": req.preview,
+                                        "read_back": read_back,
                                     }),
                                 );
-                                let _ = event_tx_approval.send(envelope);
+                                send_event(&event_tx_approval, envelope);
                                 // Forward to the pipeline coordinator for voice prompting.
                                 let _ = approval_notification_tx.send(
                                     crate::pipeline::messages::ApprovalNotification {
                                         request_id: id,
-                                        tool_name: name,
+                                        tool_name: name.clone(),
                                         input_json,
+                                        read_back,
                                     },
                                 );
                                 if let Ok(mut map) = pending_approvals_clone.lock() {
                                     map.insert(id, req);
                                 }
+                                if remote_approval.enabled {
+                                    remote_handle.spawn(forward_approval_remotely(
+                                        id,
+                                        name,
+                                        remote_detail,
+                                        remote_approval.clone(),
+                                        Arc::clone(&pending_approvals_clone),
+                                    ));
+                                }
                             }
                             None => break, // sender dropped
                         }
@@ -2098,6 +2973,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                     response = approval_response_rx.recv() => {
                         match response {
                             Some((request_id, approved)) => {
+                                crate::approval::remote_approvals().withdraw(request_id);
                                 let req = pending_approvals_for_response
                                     .lock()
                                     .ok()
@@ -2127,7 +3003,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                                         "speaker_verified": serde_json::Value::Null,
                                     }),
                                 );
-                                let _ = event_tx_resolution.send(envelope);
+                                send_event(&event_tx_resolution, envelope);
                             }
                             None => break,
                         }
@@ -2139,6 +3015,86 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             *guard = Some(approval_response_jh);
         }
 
+        // ── Remote approval drain ────────────────────────────────
+        // Delivers approvals confirmed (or expired) over a channel; see
+        // `crate::approval::RemoteApprovals`.
+        let (remote_decision_tx, mut remote_decision_rx) =
+            mpsc::unbounded_channel::<crate::approval::RemoteDecision>();
+        crate::approval::remote_approvals().set_decisions(Some(remote_decision_tx));
+        let remote_decision_token = token.child_token();
+        let pending_approvals_for_remote = Arc::clone(&self.pending_approvals);
+        let event_tx_remote = self.event_tx.clone();
+        drop(self.tokio_handle.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = remote_decision_token.cancelled() => break,
+                    decision = remote_decision_rx.recv() => {
+                        let Some(decision) = decision else { break };
+                        let req = pending_approvals_for_remote
+                            .lock()
+                            .ok()
+                            .and_then(|mut map| map.remove(&decision.request_id));
+                        let Some(req) = req else {
+                            warn!(
+                                request_id = decision.request_id,
+                                "remote approval: no pending request (already resolved?)"
+                            );
+                            continue;
+                        };
+                        if !req.respond(decision.approved) {
+                            warn!(
+                                request_id = decision.request_id,
+                                "remote approval: tool already timed out"
+                            );
+                        }
+                        send_event(
+                            &event_tx_remote,
+                            EventEnvelope::new(
+                                uuid::Uuid::new_v4().to_string(),
+                                "approval.resolved".to_owned(),
+                                serde_json::json!({
+                                    "request_id": decision.request_id.to_string(),
+                                    "approved": decision.approved,
+                                    "source": "channel",
+                                    "speaker_verified": serde_json::Value::Null,
+                                }),
+                            ),
+                        );
+                    }
+                }
+            }
+        }));
+
+        // ── Host request bridge ──────────────────────────────────
+        // Forwards file picker, notification and share sheet requests from
+        // tools as `host.request` events; `host.respond` completes them.
+        let (host_request_tx, mut host_request_rx) =
+            mpsc::unbounded_channel::<(u64, HostRequest)>();
+        host_bridge().set_outbox(Some(host_request_tx));
+        let host_request_token = token.child_token();
+        let event_tx_host = self.event_tx.clone();
+        drop(self.tokio_handle.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = host_request_token.cancelled() => break,
+                    request = host_request_rx.recv() => {
+                        let Some((id, request)) = request else { break };
+                        let mut payload = serde_json::to_value(&request)
+                            .unwrap_or_else(|_| serde_json::json!({}));
+                        payload["request_id"] = serde_json::json!(id.to_string());
+                        send_event(
+                            &event_tx_host,
+                            EventEnvelope::new(
+                                uuid::Uuid::new_v4().to_string(),
+                                "host.request".to_owned(),
+                                payload,
+                            ),
+                        );
+                    }
+                }
+            }
+        }));
+
         // ── x0x network listener ──────────────────────────────────
         // Connects to the local x0xd SSE stream and delivers trusted messages
         // to the conversation pipeline via TextInjection.
@@ -2214,7 +3170,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                         "max_attempts": MAX_RESTART_ATTEMPTS,
                     }),
                 );
-                let _ = event_tx_watcher.send(envelope);
+                send_event(&event_tx_watcher, envelope);
                 return;
             }
 
@@ -2250,7 +3206,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                     "uptime_secs": uptime.as_secs(),
                 }),
             );
-            let _ = event_tx_watcher.send(envelope);
+            send_event(&event_tx_watcher, envelope);
         });
 
         if let Ok(mut guard) = self.restart_watcher_handle.lock() {
@@ -2258,23 +3214,44 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         }
 
         // ── Audio device hot-swap watcher ────────────────────────
-        // Polls CPAL every 2 s for the default input device. On change,
-        // sends GateCommand::RestartAudio through the gate channel so the
-        // pipeline can cancel and re-initialize with the new device.
+        // Polls CPAL every 2 s for the default devices and their formats. On
+        // change, sends GateCommand::RestartAudio through the gate channel so
+        // the pipeline can cancel and re-initialize with the new device or
+        // format (e.g. a headset switching to its hands-free profile).
         let device_watcher_token = token.child_token();
         if let Ok(gate_guard) = self.gate_cmd_tx.lock()
             && let Some(gate_tx) = gate_guard.as_ref()
         {
-            let watcher = crate::audio::device_watcher::AudioDeviceWatcher::new(
+            let mut watcher = crate::audio::device_watcher::AudioDeviceWatcher::new(
                 gate_tx.clone(),
                 device_watcher_token,
             );
+            if warn_low_quality_audio {
+                watcher = watcher.with_quality_warnings(device_watcher_events);
+            }
             let device_jh = self.tokio_handle.spawn(async move { watcher.run().await });
             if let Ok(mut guard) = self.device_watcher_handle.lock() {
                 *guard = Some(device_jh);
             }
         }
 
+        // ── Wake watcher ─────────────────────────────────────────
+        // Catches a sleep the host did not announce via system.lifecycle
+        // and restarts audio so the devices are probed again.
+        if let Ok(gate_guard) = self.gate_cmd_tx.lock()
+            && let Some(gate_tx) = gate_guard.as_ref()
+        {
+            let watcher = crate::platform::lifecycle::WakeWatcher::new(
+                gate_tx.clone(),
+                wake_watcher_events,
+                token.child_token(),
+            );
+            let wake_jh = self.tokio_handle.spawn(watcher.run());
+            if let Ok(mut guard) = self.wake_watcher_handle.lock() {
+                *guard = Some(wake_jh);
+            }
+        }
+
         // ── Memory pressure monitor ──────────────────────────────
         // Polls available system RAM every 30 s.  Emits a `pipeline.control`
         // event whenever the pressure level transitions (Normal → Warning →
@@ -2310,7 +3287,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                                         "available_mb": ev.available_mb,
                                     }),
                                 );
-                                let _ = event_tx_pressure.send(envelope);
+                                send_event(&event_tx_pressure, envelope);
                             }
                             Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
                             Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
@@ -2328,6 +3305,53 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             *guard = Some(mp_monitor_jh);
         }
 
+        // ── Power profile monitor ────────────────────────────────
+        // Switches to the low-power profile on battery and back on AC, and
+        // tells the host so it can show the current profile.
+        let power_config = self
+            .lock_config()
+            .map(|g| g.power.clone())
+            .unwrap_or_default();
+        if power_config.enabled {
+            let power_token = token.child_token();
+            let event_tx_power = self.event_tx.clone();
+            let (power_tx, mut power_rx) =
+                tokio::sync::broadcast::channel::<crate::platform::power::PowerProfileEvent>(4);
+            let monitor = crate::platform::power::PowerMonitor::new(
+                power_config,
+                power_tx,
+                power_token.clone(),
+            );
+            let power_jh = self.tokio_handle.spawn(monitor.run());
+            drop(self.tokio_handle.spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = power_token.cancelled() => break,
+                        evt = power_rx.recv() => match evt {
+                            Ok(ev) => {
+                                let envelope = crate::host::contract::EventEnvelope::new(
+                                    uuid::Uuid::new_v4().to_string(),
+                                    "pipeline.control".to_owned(),
+                                    serde_json::json!({
+                                        "action": "power_profile",
+                                        "profile": ev.profile.as_str(),
+                                        "on_battery": ev.battery.is_some_and(|b| b.on_battery),
+                                        "battery_percent": ev.battery.and_then(|b| b.percent),
+                                    }),
+                                );
+                                send_event(&event_tx_power, envelope);
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                }
+            }));
+            if let Ok(mut guard) = self.power_monitor_handle.lock() {
+                *guard = Some(power_jh);
+            }
+        }
+
         if let Ok(mut guard) = self.pipeline_state.lock() {
             *guard = PipelineState::Running;
         }
@@ -2364,12 +3388,37 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             let event_tx = self.event_tx.clone();
             drop(self.tokio_handle.spawn(async move {
                 while let Some(result) = sched_rx.recv().await {
-                    if let crate::scheduler::tasks::TaskResult::Error(msg) = result {
-                        let _ = event_tx.send(EventEnvelope::new(
-                            uuid::Uuid::new_v4().to_string(),
-                            "runtime.error".to_owned(),
-                            serde_json::json!({"source": "scheduler", "error": msg}),
-                        ));
+                    match result {
+                        crate::scheduler::tasks::TaskResult::Error(msg) => {
+                            send_event(
+                                &event_tx,
+                                EventEnvelope::new(
+                                    uuid::Uuid::new_v4().to_string(),
+                                    "runtime.error".to_owned(),
+                                    serde_json::json!({"source": "scheduler", "error": msg}),
+                                ),
+                            );
+                        }
+                        crate::scheduler::tasks::TaskResult::NeedsUserAction(prompt) => {
+                            let actions: Vec<serde_json::Value> = prompt
+                                .actions
+                                .iter()
+                                .map(|a| serde_json::json!({"id": a.id, "label": a.label}))
+                                .collect();
+                            send_event(
+                                &event_tx,
+                                EventEnvelope::new(
+                                    uuid::Uuid::new_v4().to_string(),
+                                    "scheduler.prompt".to_owned(),
+                                    serde_json::json!({
+                                        "title": prompt.title,
+                                        "message": prompt.message,
+                                        "actions": actions,
+                                    }),
+                                ),
+                            );
+                        }
+                        _ => {}
                     }
                 }
             }));
@@ -2451,6 +3500,13 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             jh.abort();
         }
 
+        // Abort wake watcher task
+        if let Ok(mut guard) = self.wake_watcher_handle.lock()
+            && let Some(jh) = guard.take()
+        {
+            jh.abort();
+        }
+
         // Abort memory pressure monitor task
         if let Ok(mut guard) = self.memory_pressure_handle.lock()
             && let Some(jh) = guard.take()
@@ -2458,6 +3514,13 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             jh.abort();
         }
 
+        // Abort power profile monitor task
+        if let Ok(mut guard) = self.power_monitor_handle.lock()
+            && let Some(jh) = guard.take()
+        {
+            jh.abort();
+        }
+
         // Abort x0x listener task
         if let Ok(mut guard) = self.x0x_listener_handle.lock()
             && let Some(jh) = guard.take()
@@ -2490,8 +3553,41 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
 
         // Clear any pending approval requests that will never be answered.
         if let Ok(mut map) = self.pending_approvals.lock() {
+            for id in map.keys() {
+                crate::approval::remote_approvals().withdraw(*id);
+            }
             map.clear();
         }
+        crate::approval::remote_approvals().set_decisions(None);
+        host_bridge().set_outbox(None);
+        // The transcript is on disk; minutes need the pipeline that just stopped.
+        if let Some(id) = crate::meeting::stop() {
+            let _ = crate::meeting::take_finished();
+            warn!(
+                id,
+                "runtime stopped during a meeting — no minutes were written"
+            );
+        }
+        // The conversation went with the pipeline; nothing is left to offer.
+        crate::handoff::cancel_offer();
+        crate::handoff::publish(&[]);
+        let theme_update = crate::theme::engine().set_state(crate::theme::ConversationState::Idle);
+        if let Some(update) = theme_update {
+            self.emit_event(crate::theme::STATE_CHANGED_EVENT, update.to_payload());
+        }
+
+        if let Some(stats) = self.current_analytics() {
+            let privacy = self.lock_config()?.privacy.clone();
+            save_analytics(AnalyticsStore::open(&privacy).ok().as_ref(), &stats);
+            let trial = self.experiment_trial.lock().ok().and_then(|t| t.clone());
+            if let Some(trial) = trial {
+                crate::experiments::record_trial(
+                    &crate::fae_dirs::experiments_file(),
+                    &trial,
+                    &stats,
+                );
+            }
+        }
 
         if let Ok(mut guard) = self.pipeline_state.lock() {
             *guard = PipelineState::Stopped;
@@ -2501,6 +3597,50 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         Ok(())
     }
 
+    fn request_system_lifecycle(&self, event: PowerEvent) -> Result<()> {
+        let transition = self
+            .lifecycle
+            .lock()
+            .map_err(|e| SpeechError::Pipeline(format!("lifecycle lock poisoned: {e}")))?
+            .apply(event, std::time::SystemTime::now());
+        match transition {
+            None => {}
+            Some(Transition::Suspend) => {
+                info!("system going to sleep — suspending runtime");
+                crate::scheduler::priority::set_suspended(true);
+                let running = matches!(
+                    self.pipeline_state(),
+                    PipelineState::Running | PipelineState::Starting
+                );
+                self.resume_on_wake
+                    .store(running, std::sync::atomic::Ordering::SeqCst);
+                if running {
+                    // A clean stop ends capture and saves conversation state.
+                    self.request_runtime_stop()?;
+                }
+                self.emit_runtime_event(&RuntimeEvent::Suspended);
+            }
+            Some(Transition::Resume { slept_for }) => {
+                info!(
+                    slept_secs = slept_for.as_secs(),
+                    "system woke — resuming runtime"
+                );
+                crate::scheduler::priority::set_suspended(false);
+                self.emit_runtime_event(&RuntimeEvent::Resumed {
+                    slept_secs: slept_for.as_secs(),
+                });
+                if self
+                    .resume_on_wake
+                    .swap(false, std::sync::atomic::Ordering::SeqCst)
+                {
+                    // Starting afresh re-probes the audio devices.
+                    self.request_runtime_start()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn query_runtime_status(&self) -> Result<serde_json::Value> {
         info!("runtime.status queried");
         let state = self.pipeline_state();
@@ -2535,6 +3675,11 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             result["pipeline_mode"] = serde_json::json!(mode.to_string());
         }
 
+        result["queues"] = serde_json::json!(crate::pipeline::queues::snapshot());
+        result["audio_streams"] = serde_json::json!(crate::audio::watchdog::snapshot());
+        result["hotword"] = serde_json::json!(crate::pipeline::hotword::snapshot());
+        result["tool_overruns"] = serde_json::json!(crate::fae_llm::agent::tool_limits::snapshot());
+
         let runtime_config = self
             .config
             .lock()
@@ -2628,6 +3773,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                 ))
             })?;
 
+        crate::approval::remote_approvals().withdraw(numeric_id);
         let delivered = req.respond(approved);
         if !delivered {
             warn!(
@@ -2639,6 +3785,21 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         Ok(())
     }
 
+    fn respond_host_request(&self, request_id: &str, response: HostResponse) -> Result<()> {
+        info!(request_id, ?response, "host.respond received");
+        let numeric_id = request_id.parse::<u64>().map_err(|_| {
+            SpeechError::Pipeline(format!(
+                "host.respond: request_id `{request_id}` is not a valid numeric ID"
+            ))
+        })?;
+        if !host_bridge().resolve(numeric_id, response) {
+            return Err(SpeechError::Pipeline(format!(
+                "host.respond: no pending request with id `{request_id}`"
+            )));
+        }
+        Ok(())
+    }
+
     fn query_scheduler_list(&self) -> Result<serde_json::Value> {
         info!("scheduler.list queried");
         // If the state file is corrupt or missing, return an empty list so the
@@ -2655,7 +3816,24 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             .iter()
             .filter_map(|t| serde_json::to_value(t).ok())
             .collect();
-        Ok(serde_json::json!({"tasks": tasks_json}))
+        let quiet =
+            crate::scheduler::QuietHours::from_intelligence(&self.lock_config()?.intelligence);
+        let now = crate::time_util::now_epoch_secs();
+        let conflicts: serde_json::Map<String, serde_json::Value> = snapshot
+            .tasks
+            .iter()
+            .filter(|t| t.kind == crate::scheduler::tasks::TaskKind::User)
+            .filter_map(|t| {
+                let found =
+                    crate::scheduler::detect_conflicts(t, &snapshot.tasks, Some(quiet), now);
+                (!found.is_empty()).then(|| (t.id.clone(), conflicts_json(&found)))
+            })
+            .collect();
+        Ok(serde_json::json!({
+            "tasks": tasks_json,
+            "history": snapshot.history,
+            "conflicts": conflicts,
+        }))
     }
 
     fn request_scheduler_create(&self, spec: &serde_json::Value) -> Result<serde_json::Value> {
@@ -2665,6 +3843,17 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                 SpeechError::Scheduler(format!("scheduler.create: invalid task spec: {e}"))
             })?;
         let id = task.id.clone();
+        let existing = crate::scheduler::load_persisted_snapshot()
+            .map(|snapshot| snapshot.tasks)
+            .unwrap_or_default();
+        let conflicts = crate::scheduler::detect_conflicts(
+            &task,
+            &existing,
+            Some(crate::scheduler::QuietHours::from_intelligence(
+                &self.lock_config()?.intelligence,
+            )),
+            crate::time_util::now_epoch_secs(),
+        );
         // If state is corrupt, recover by clearing it first then re-upsert.
         if let Err(e) = crate::scheduler::upsert_persisted_user_task(task.clone()) {
             warn!("scheduler.create: state corrupt ({e}), attempting recovery");
@@ -2672,7 +3861,7 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             crate::scheduler::upsert_persisted_user_task(task)?;
         }
         info!(id, "scheduler.create persisted");
-        Ok(serde_json::json!({"id": id}))
+        Ok(serde_json::json!({"id": id, "conflicts": conflicts_json(&conflicts)}))
     }
 
     fn request_scheduler_update(&self, id: &str, spec: &serde_json::Value) -> Result<()> {
@@ -2743,6 +3932,20 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
         Ok(())
     }
 
+    fn respond_scheduler_prompt(&self, action: &str) -> Result<serde_json::Value> {
+        info!(action, "scheduler.prompt.respond");
+        let Some(kinds) = crate::permission_usage::parse_revoke_action(action) else {
+            return Ok(serde_json::json!({"handled": false, "action": action}));
+        };
+        let mut revoked = Vec::new();
+        for kind in kinds {
+            let name = kind.to_string();
+            self.deny_capability(&name, None)?;
+            revoked.push(name);
+        }
+        Ok(serde_json::json!({"handled": true, "revoked": revoked}))
+    }
+
     fn query_config_get(&self, key: Option<&str>) -> Result<serde_json::Value> {
         info!(?key, "config.get queried");
         let guard = self.lock_config()?;
@@ -2758,6 +3961,49 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                 Ok(serde_json::json!({"permissions": granted}))
             }
             Some("onboarded") => Ok(serde_json::json!({"onboarded": guard.onboarded})),
+            Some("offline_mode") => Ok(serde_json::json!({
+                "offline_mode": crate::offline::is_offline()
+            })),
+            Some("accessibility") => Ok(serde_json::json!({
+                "accessibility": {
+                    "enabled": guard.accessibility.enabled,
+                    "verbosity": guard.accessibility.verbosity,
+                    "spoken_hints": guard.accessibility.spoken_hints
+                }
+            })),
+            Some("captions") => Ok(serde_json::json!({
+                "captions": {
+                    "mode": guard.captions.mode,
+                    "max_line_chars": guard.captions.max_line_chars
+                }
+            })),
+            Some("hotword") => Ok(serde_json::json!({
+                "hotword": {
+                    "sensitivity": guard.hotword.sensitivity,
+                    "environment": guard.hotword.environment,
+                    "environments": guard.hotword.environments,
+                    "stats": crate::pipeline::hotword::snapshot()
+                }
+            })),
+            Some("theme") => {
+                let packs: Vec<serde_json::Value> =
+                    crate::theme::load_theme_packs(&crate::fae_dirs::themes_dir())
+                        .iter()
+                        .map(|p| serde_json::json!({"id": p.id, "name": p.name}))
+                        .collect();
+                Ok(serde_json::json!({
+                    "theme": {
+                        "mode": guard.theme.mode,
+                        "pack": guard.theme.pack,
+                        "packs": packs,
+                        "current": crate::theme::engine().current().to_payload()
+                    }
+                }))
+            }
+            Some("language") => Ok(serde_json::json!({
+                "language": guard.language,
+                "active": crate::i18n::locale().code()
+            })),
             Some("runtime.profile") => Ok(serde_json::json!({
                 "runtime": {
                     "profile": guard.runtime.profile.as_str()
@@ -2768,6 +4014,48 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                     "voice_model_preset": guard.llm.voice_model_preset
                 }
             })),
+            Some("models.catalog") => {
+                let catalog = crate::models::catalog::active();
+                let ram = crate::system_profile::detect_total_memory_bytes();
+                let models: Vec<serde_json::Value> = catalog
+                    .models
+                    .iter()
+                    .filter(|entry| !entry.deprecated)
+                    .map(|entry| {
+                        let mut value = serde_json::to_value(entry).unwrap_or_default();
+                        value["fits_memory"] = serde_json::json!(entry.fits_memory(ram));
+                        value
+                    })
+                    .collect();
+                Ok(serde_json::json!({
+                    "models": {
+                        "catalog": {
+                            "revision": catalog.revision,
+                            "models": models
+                        }
+                    }
+                }))
+            }
+            Some("models.openrouter") => {
+                if openrouter::catalog_is_stale() {
+                    self.refresh_openrouter_catalog();
+                }
+                let mut models = openrouter::cached_catalog().unwrap_or_default();
+                models.sort_by(|a, b| {
+                    let cost = |m: &openrouter::OpenRouterModel| {
+                        m.pricing.input_per_1m + m.pricing.output_per_1m
+                    };
+                    cost(a).total_cmp(&cost(b))
+                });
+                Ok(serde_json::json!({
+                    "models": {
+                        "openrouter": {
+                            "models": models,
+                            "stale": openrouter::catalog_is_stale()
+                        }
+                    }
+                }))
+            }
             Some("voice_identity") => {
                 let mode = match guard.voice_identity.mode {
                     VoiceIdentityMode::Assist => "assist",
@@ -2802,6 +4090,15 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                     "approval_requires_match": guard.voice_identity.approval_requires_match
                 }
             })),
+            Some("privacy") => Ok(serde_json::json!({
+                "privacy": {
+                    "encrypt_at_rest": guard.privacy.encrypt_at_rest,
+                    "encrypted_stores": crate::privacy::ENCRYPTED_STORES,
+                    "session_archive_after_days": guard.privacy.session_archive_after_days,
+                    "session_retention_days": guard.privacy.session_retention_days,
+                    "session_max_total_mb": guard.privacy.session_max_total_mb
+                }
+            })),
             _ => Ok(serde_json::json!({})),
         }
     }
@@ -2833,6 +4130,20 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                     info!(onboarded = v, "config.patch applied: onboarded");
                 }
             }
+            "offline_mode" => {
+                if let Some(v) = value.as_bool() {
+                    let mut guard = self.lock_config()?;
+                    guard.offline_mode = v;
+                    drop(guard);
+                    crate::offline::set_offline(v);
+                    self.save_config()?;
+                    self.emit_event(
+                        "pipeline.offline_mode_changed",
+                        serde_json::json!({"offline": v}),
+                    );
+                    info!(offline = v, "config.patch applied: offline_mode");
+                }
+            }
             "tool_mode" => {
                 if let Some(s) = value.as_str() {
                     match serde_json::from_value::<AgentToolMode>(serde_json::Value::String(
@@ -2901,16 +4212,139 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                     }
                 }
             }
-            "voice_identity.approval_requires_match" => {
-                if let Some(v) = value.as_bool() {
+            "voice_identity.approval_requires_match" => {
+                if let Some(v) = value.as_bool() {
+                    let mut guard = self.lock_config()?;
+                    guard.voice_identity.approval_requires_match = v;
+                    drop(guard);
+                    self.save_config()?;
+                    info!(
+                        approval_requires_match = v,
+                        "config.patch applied: voice_identity.approval_requires_match"
+                    );
+                }
+            }
+            "accessibility.enabled" | "accessibility.spoken_hints" => {
+                if let Some(v) = value.as_bool() {
+                    let mut guard = self.lock_config()?;
+                    if key == "accessibility.enabled" {
+                        guard.accessibility.enabled = v;
+                    } else {
+                        guard.accessibility.spoken_hints = v;
+                    }
+                    crate::accessibility::apply(&guard.accessibility);
+                    drop(guard);
+                    self.save_config()?;
+                    info!(key, value = v, "config.patch applied");
+                }
+            }
+            "accessibility.verbosity" => {
+                if let Some(s) = value.as_str() {
+                    match serde_json::from_value::<AccessibilityVerbosity>(
+                        serde_json::Value::String(s.to_owned()),
+                    ) {
+                        Ok(verbosity) => {
+                            let mut guard = self.lock_config()?;
+                            guard.accessibility.verbosity = verbosity;
+                            crate::accessibility::apply(&guard.accessibility);
+                            drop(guard);
+                            self.save_config()?;
+                            info!(?verbosity, "config.patch applied: accessibility.verbosity");
+                        }
+                        Err(_) => {
+                            warn!(
+                                key,
+                                value = s,
+                                "config.patch: invalid accessibility.verbosity"
+                            );
+                        }
+                    }
+                }
+            }
+            "captions.mode" => {
+                if let Some(s) = value.as_str() {
+                    match serde_json::from_value::<CaptionsMode>(serde_json::Value::String(
+                        s.to_owned(),
+                    )) {
+                        Ok(mode) => {
+                            let mut guard = self.lock_config()?;
+                            guard.captions.mode = mode;
+                            crate::captions::apply(&guard.captions);
+                            drop(guard);
+                            self.save_config()?;
+                            info!(?mode, "config.patch applied: captions.mode");
+                        }
+                        Err(_) => {
+                            warn!(key, value = s, "config.patch: invalid captions.mode");
+                        }
+                    }
+                }
+            }
+            "captions.max_line_chars" => {
+                if let Some(v) = value.as_u64().filter(|v| *v > 0) {
+                    let mut guard = self.lock_config()?;
+                    guard.captions.max_line_chars = v as usize;
+                    crate::captions::apply(&guard.captions);
+                    drop(guard);
+                    self.save_config()?;
+                    info!(value = v, "config.patch applied: captions.max_line_chars");
+                }
+            }
+            "hotword.environment" => {
+                if value.is_null() || value.is_string() {
+                    let environment = value
+                        .as_str()
+                        .map(str::trim)
+                        .filter(|e| !e.is_empty())
+                        .map(str::to_owned);
+                    let mut guard = self.lock_config()?;
+                    guard.hotword.environment = environment.clone();
+                    crate::pipeline::hotword::apply(&guard.hotword);
+                    drop(guard);
+                    self.save_config()?;
+                    info!(?environment, "config.patch applied: hotword.environment");
+                }
+            }
+            "hotword.sensitivity" => {
+                if let Some(v) = value.as_f64().filter(|v| (0.0..=1.0).contains(v)) {
+                    let mut guard = self.lock_config()?;
+                    guard.hotword.sensitivity = v as f32;
+                    crate::pipeline::hotword::apply(&guard.hotword);
+                    drop(guard);
+                    self.save_config()?;
+                    info!(value = v, "config.patch applied: hotword.sensitivity");
+                }
+            }
+            "theme.pack" => {
+                if value.is_null() || value.is_string() {
+                    let pack = value
+                        .as_str()
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_owned);
                     let mut guard = self.lock_config()?;
-                    guard.voice_identity.approval_requires_match = v;
+                    guard.theme.pack = pack;
+                    crate::theme::apply(&guard.theme);
                     drop(guard);
                     self.save_config()?;
-                    info!(
-                        approval_requires_match = v,
-                        "config.patch applied: voice_identity.approval_requires_match"
-                    );
+                    let current = crate::theme::engine().current();
+                    info!(pack = %current.pack, "config.patch applied: theme.pack");
+                    self.emit_event(crate::theme::STATE_CHANGED_EVENT, current.to_payload());
+                }
+            }
+            "language" => {
+                if value.is_null() || value.is_string() {
+                    let language = value
+                        .as_str()
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_owned);
+                    let active = crate::i18n::set_language(language.as_deref());
+                    let mut guard = self.lock_config()?;
+                    guard.language = language;
+                    drop(guard);
+                    self.save_config()?;
+                    info!(language = active.code(), "config.patch applied: language");
                 }
             }
             "runtime.profile" => {
@@ -2948,6 +4382,22 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
                     }
                 }
             }
+            "llm.read_back.send_mail"
+            | "llm.read_back.delete_events"
+            | "llm.read_back.overwrite_files" => {
+                if let Some(v) = value.as_bool() {
+                    let mut guard = self.lock_config()?;
+                    let read_back = &mut guard.llm.read_back;
+                    match key {
+                        "llm.read_back.send_mail" => read_back.send_mail = v,
+                        "llm.read_back.delete_events" => read_back.delete_events = v,
+                        _ => read_back.overwrite_files = v,
+                    }
+                    drop(guard);
+                    self.save_config()?;
+                    info!(key, enabled = v, "config.patch applied");
+                }
+            }
             "channels.enabled" => {
                 if let Some(v) = value.as_bool() {
                     let mut guard = self.lock_config()?;
@@ -2960,6 +4410,12 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
             k if k.starts_with("channels.discord.") || k.starts_with("channels.whatsapp.") => {
                 self.patch_channel_config(key, value)?;
             }
+            k if k.starts_with("privacy.") => {
+                self.patch_privacy_config(key, value)?;
+            }
+            k if k.starts_with("sync.") => {
+                self.patch_sync_config(key, value)?;
+            }
             _ => {
                 warn!(key, "config.patch: unknown key, ignored");
             }
@@ -2975,11 +4431,352 @@ impl DeviceTransferHandler for FaeDeviceTransferHandler {
     }
 }
 
+/// Forward an approval request to the configured channel if it is still
+/// unanswered after the grace period, then deny it when the code expires.
+async fn forward_approval_remotely(
+    request_id: u64,
+    tool: String,
+    detail: String,
+    config: crate::config::RemoteApprovalConfig,
+    pending: Arc<Mutex<HashMap<u64, ToolApprovalRequest>>>,
+) {
+    tokio::time::sleep(std::time::Duration::from_secs(config.forward_after_secs)).await;
+    let still_pending = pending
+        .lock()
+        .map(|map| map.contains_key(&request_id))
+        .unwrap_or(false);
+    if !still_pending {
+        return;
+    }
+    let forwarded = crate::approval::remote_approvals()
+        .forward(request_id, &tool, &detail, &config, Instant::now())
+        .is_some();
+    if !forwarded {
+        warn!(
+            request_id,
+            channel = %config.channel,
+            "remote approval: could not forward (is the channels runtime running?)"
+        );
+        return;
+    }
+    info!(request_id, channel = %config.channel, "approval forwarded to channel");
+    tokio::time::sleep(std::time::Duration::from_secs(config.token_ttl_secs)).await;
+    if crate::approval::remote_approvals().expire(request_id) {
+        info!(request_id, "remote approval expired; denied");
+    }
+}
+
+/// How long a `data.forget` confirmation stays open before it is declined.
+const DATA_FORGET_APPROVAL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// A personal data export/wipe waiting for confirmation.
+struct DataForgetRequest {
+    action: crate::privacy::PrivacyAction,
+    source: crate::privacy::PrivacyRequestSource,
+    data_dir: PathBuf,
+    cache_dir: PathBuf,
+    privacy: crate::config::PrivacyConfig,
+}
+
+/// Send `envelope` to the host, followed by a screen-reader announcement of
+/// it when accessibility mode is on (see [`crate::accessibility`]).
+fn send_event(event_tx: &broadcast::Sender<EventEnvelope>, envelope: EventEnvelope) {
+    let announcement = crate::accessibility::verbosity()
+        .and_then(|verbosity| {
+            crate::accessibility::describe_event(&envelope.event, &envelope.payload, verbosity)
+        })
+        .map(|announcement| announcement.to_payload(&envelope.event));
+    let theme_update = if envelope.event == "runtime.error" {
+        crate::theme::engine().set_state(crate::theme::ConversationState::Error)
+    } else {
+        None
+    };
+    let _ = event_tx.send(envelope);
+    if let Some(payload) = announcement {
+        let _ = event_tx.send(EventEnvelope::new(
+            uuid::Uuid::new_v4().to_string(),
+            crate::accessibility::ANNOUNCEMENT_EVENT,
+            payload,
+        ));
+    }
+    if let Some(update) = theme_update {
+        send_event(
+            event_tx,
+            EventEnvelope::new(
+                uuid::Uuid::new_v4().to_string(),
+                crate::theme::STATE_CHANGED_EVENT,
+                update.to_payload(),
+            ),
+        );
+    }
+}
+
+/// Progress callback that sends each event as `event_name`, followed by the
+/// weighted overall progress whenever a task moves.
+fn progress_events(
+    event_tx: broadcast::Sender<EventEnvelope>,
+    event_name: &'static str,
+) -> ProgressCallback {
+    let tree = Mutex::new(ProgressTree::new());
+    ProgressCallback::new(move |evt: ProgressEvent| {
+        send_event(
+            &event_tx,
+            EventEnvelope::new(
+                uuid::Uuid::new_v4().to_string(),
+                event_name,
+                progress_event_to_json(&evt),
+            ),
+        );
+        let overall = tree.lock().unwrap_or_else(|e| e.into_inner()).observe(&evt);
+        if let Some(overall) = overall {
+            send_event(
+                &event_tx,
+                EventEnvelope::new(
+                    uuid::Uuid::new_v4().to_string(),
+                    event_name,
+                    overall_progress_to_json(&overall),
+                ),
+            );
+        }
+    })
+}
+
+/// Write an offline mode change made by voice to the config file.
+fn persist_offline_mode(config_path: &std::path::Path, offline: bool) {
+    let result = SpeechConfig::from_file(config_path).and_then(|mut config| {
+        config.offline_mode = offline;
+        config.save_to_file(config_path)
+    });
+    if let Err(e) = result {
+        warn!("failed to persist offline mode: {e}");
+    }
+}
+
+/// Schedule conflicts as host JSON: the conflict fields plus a readable `message`.
+fn conflicts_json(conflicts: &[crate::scheduler::ScheduleConflict]) -> serde_json::Value {
+    conflicts
+        .iter()
+        .map(|conflict| {
+            let mut value = serde_json::to_value(conflict).unwrap_or_default();
+            if let Some(object) = value.as_object_mut() {
+                object.insert("message".to_owned(), conflict.to_string().into());
+            }
+            value
+        })
+        .collect()
+}
+
+/// Feed a runtime event into the conversation analytics, saving a snapshot
+/// (and the experiment outcome so far) whenever an assistant turn completes.
+fn record_analytics(
+    analytics: &Mutex<Option<ConversationAnalytics>>,
+    store: Option<&AnalyticsStore>,
+    trial: Option<&Trial>,
+    event: &RuntimeEvent,
+) {
+    let snapshot = analytics.lock().ok().and_then(|mut guard| {
+        let recorder = guard.as_mut()?;
+        recorder
+            .observe(event, Instant::now())
+            .then(|| recorder.stats())
+    });
+    if let Some(stats) = snapshot {
+        save_analytics(store, &stats);
+        if let Some(trial) = trial {
+            crate::experiments::record_trial(&crate::fae_dirs::experiments_file(), trial, &stats);
+        }
+    }
+}
+
+fn save_analytics(store: Option<&AnalyticsStore>, stats: &ConversationStats) {
+    if let Some(store) = store
+        && let Err(e) = store.save(stats)
+    {
+        warn!("failed to save conversation analytics: {e}");
+    }
+}
+
+/// The latest user message and the reply to it.
+#[derive(Debug, Default)]
+struct LastTurn {
+    user_text: String,
+    assistant_text: String,
+}
+
+/// Keep [`LastTurn`] up to date from runtime events.
+fn track_last_turn(last_turn: &Mutex<LastTurn>, event: &RuntimeEvent) {
+    let Ok(mut turn) = last_turn.lock() else {
+        return;
+    };
+    match event {
+        RuntimeEvent::Transcription(t) if t.is_final && !t.text.trim().is_empty() => {
+            turn.user_text = t.text.trim().to_owned();
+            turn.assistant_text.clear();
+        }
+        RuntimeEvent::AssistantSentence(chunk) if !chunk.text.trim().is_empty() => {
+            if !turn.assistant_text.is_empty() {
+                turn.assistant_text.push(' ');
+            }
+            turn.assistant_text.push_str(chunk.text.trim());
+        }
+        _ => {}
+    }
+}
+
+/// Ask for confirmation through the approval channel, then run the request.
+///
+/// The approval bridge shows the request in the UI and the coordinator
+/// speaks it, exactly like a tool approval. Every outcome is written to the
+/// privacy audit log and reported as a `data.forget.completed` or
+/// `data.forget.declined` event. Returns the approval request ID.
+fn spawn_data_forget(
+    tokio_handle: &tokio::runtime::Handle,
+    approval_tx: &mpsc::UnboundedSender<ToolApprovalRequest>,
+    event_tx: &broadcast::Sender<EventEnvelope>,
+    request: DataForgetRequest,
+) -> Result<u64> {
+    use crate::privacy::{PrivacyAuditEntry, PrivacyOutcome};
+
+    let id = crate::agent::next_approval_id();
+    let (respond_to, response_rx) = tokio::sync::oneshot::channel();
+    let input_json = serde_json::json!({
+        "export": request.action.exports(),
+        "wipe": request.action.wipes(),
+    })
+    .to_string();
+    approval_tx
+        .send(ToolApprovalRequest::new(
+            id,
+            crate::privacy::FORGET_APPROVAL_NAME.to_owned(),
+            input_json,
+            respond_to,
+        ))
+        .map_err(|_| SpeechError::Privacy("approval channel is closed".to_owned()))?;
+
+    let event_tx = event_tx.clone();
+    tokio_handle.spawn(async move {
+        let approved = matches!(
+            tokio::time::timeout(DATA_FORGET_APPROVAL_TIMEOUT, response_rx).await,
+            Ok(Ok(response)) if response.is_approved()
+        );
+        let (action, source) = (request.action, request.source);
+        let audit_path = crate::fae_dirs::privacy_audit_file();
+
+        if !approved {
+            info!(approval_request_id = id, "data.forget declined");
+            let entry = PrivacyAuditEntry::new(action, source, PrivacyOutcome::Declined, None);
+            if let Err(e) = crate::privacy::append_privacy_audit(&audit_path, &entry) {
+                warn!("failed to record privacy audit entry: {e}");
+            }
+            send_event(
+                &event_tx,
+                EventEnvelope::new(
+                    uuid::Uuid::new_v4().to_string(),
+                    "data.forget.declined".to_owned(),
+                    serde_json::json!({"approval_request_id": id.to_string()}),
+                ),
+            );
+            return;
+        }
+
+        let result = tokio::task::spawn_blocking(move || run_data_forget(&request))
+            .await
+            .unwrap_or_else(|e| Err(SpeechError::Privacy(format!("data.forget panicked: {e}"))));
+        let (outcome, detail, payload) = match result {
+            Ok((export_path, report)) => {
+                let failed = report.as_ref().is_some_and(|r| !r.failures.is_empty());
+                let detail = report.as_ref().map(crate::privacy::WipeReport::summary);
+                let payload = serde_json::json!({
+                    "approval_request_id": id.to_string(),
+                    "success": !failed,
+                    "export_path": export_path.map(|p| p.display().to_string()),
+                    "wipe": report,
+                    // The live memory database stays open until restart.
+                    "restart_required": action.wipes(),
+                });
+                let outcome = if failed {
+                    PrivacyOutcome::Failed
+                } else {
+                    PrivacyOutcome::Completed
+                };
+                (outcome, detail, payload)
+            }
+            Err(e) => {
+                warn!("data.forget failed: {e}");
+                let payload = serde_json::json!({
+                    "approval_request_id": id.to_string(),
+                    "success": false,
+                    "error": e.to_string(),
+                });
+                (PrivacyOutcome::Failed, Some(e.to_string()), payload)
+            }
+        };
+
+        let entry = PrivacyAuditEntry::new(action, source, outcome, detail);
+        if let Err(e) = crate::privacy::append_privacy_audit(&audit_path, &entry) {
+            warn!("failed to record privacy audit entry: {e}");
+        }
+        send_event(
+            &event_tx,
+            EventEnvelope::new(
+                uuid::Uuid::new_v4().to_string(),
+                "data.forget.completed".to_owned(),
+                payload,
+            ),
+        );
+    });
+    Ok(id)
+}
+
+/// Export and/or wipe personal data once the user has confirmed.
+///
+/// Channel history is held by the channels runtime rather than the host, so
+/// it is not part of host-initiated exports.
+fn run_data_forget(
+    request: &DataForgetRequest,
+) -> Result<(Option<PathBuf>, Option<crate::privacy::WipeReport>)> {
+    use crate::privacy::data_rights;
+
+    let export_path = if request.action.exports() {
+        let cipher = crate::privacy::cipher_for(&request.privacy)?;
+        let destination = data_rights::exports_dir(&request.data_dir).join(format!(
+            "fae-data-export-{}.zip",
+            crate::diagnostics::chrono_timestamp()
+        ));
+        Some(data_rights::export_personal_data(
+            &request.data_dir,
+            &request.cache_dir,
+            cipher.as_ref(),
+            &[],
+            &destination,
+        )?)
+    } else {
+        None
+    };
+
+    let report = if request.action.wipes() {
+        let keep: Vec<PathBuf> = export_path.iter().cloned().collect();
+        let mut report =
+            data_rights::wipe_personal_data(&request.data_dir, &request.cache_dir, &keep);
+        let manager = crate::credentials::create_manager();
+        if let Err(e) = data_rights::shred_data_key(&request.privacy, manager.as_ref()) {
+            report.failures.push(e.to_string());
+        }
+        info!(summary = %report.summary(), "personal data wiped");
+        Some(report)
+    } else {
+        None
+    };
+
+    Ok((export_path, report))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used, clippy::expect_used)]
 
     use super::*;
+    use crate::host::runtime_events::map_runtime_event;
     use std::sync::Arc;
 
     fn temp_handler() -> (
@@ -3071,6 +4868,52 @@ mod tests {
         assert!(loaded.permissions.is_granted(PermissionKind::Mail));
     }
 
+    #[test]
+    fn scoped_expiring_grant_is_listed() {
+        let (handler, dir, _rt) = temp_handler();
+
+        handler
+            .grant_capability_for("calendar", Some("read_only"), 3600)
+            .unwrap();
+        assert!(handler.grant_capability("files", Some("session")).is_err());
+
+        let loaded = SpeechConfig::from_file(&dir.path().join("config.toml")).unwrap();
+        let grant = &loaded.permissions.grants()[0];
+        assert_eq!(grant.scope, PermissionScope::ReadOnly);
+        assert!(grant.expires_at.is_some());
+
+        let listed = handler.list_capabilities().unwrap();
+        let grants = listed["grants"].as_array().unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0]["kind"], "calendar");
+        assert_eq!(grants[0]["scope"], "read_only");
+        assert_eq!(grants[0]["active"], true);
+        assert!(grants[0]["granted_at"].is_u64());
+    }
+
+    #[test]
+    fn permission_review_prompt_revokes_unused() {
+        let (handler, _dir, _rt) = temp_handler();
+        handler.grant_capability("contacts", None).unwrap();
+        handler.grant_capability("mail", None).unwrap();
+
+        let result = handler
+            .respond_scheduler_prompt("revoke_permissions:contacts")
+            .unwrap();
+        assert_eq!(result["handled"], true);
+        assert_eq!(result["revoked"][0], "contacts");
+
+        let guard = handler.config.lock().unwrap();
+        assert!(!guard.permissions.is_granted(PermissionKind::Contacts));
+        assert!(guard.permissions.is_granted(PermissionKind::Mail));
+        drop(guard);
+
+        let ack = handler
+            .respond_scheduler_prompt("acknowledge_scheduler_prompt")
+            .unwrap();
+        assert_eq!(ack["handled"], false);
+    }
+
     #[test]
     fn complete_onboarding_saves_to_disk() {
         let (handler, dir, _rt) = temp_handler();
@@ -3241,6 +5084,17 @@ mod tests {
         assert_eq!(loaded.llm.model_id, "unsloth/Qwen3-1.7B-GGUF");
     }
 
+    #[test]
+    fn config_get_models_catalog_lists_offered_models() {
+        let (handler, _dir, _rt) = temp_handler();
+        let result = handler.query_config_get(Some("models.catalog")).unwrap();
+        let models = result["models"]["catalog"]["models"].as_array().unwrap();
+        assert!(models.iter().any(|m| m["kind"] == "stt"));
+        assert!(models.iter().any(|m| m["kind"] == "tts"));
+        assert!(models.iter().all(|m| m["fits_memory"].is_boolean()));
+        assert!(models.iter().all(|m| m["deprecated"] == false));
+    }
+
     #[test]
     fn config_get_voice_model_preset_returns_current_value() {
         let (handler, _dir, _rt) = temp_handler();
@@ -3285,6 +5139,192 @@ mod tests {
         assert!(!loaded.voice_identity.approval_requires_match);
     }
 
+    #[test]
+    fn config_patch_privacy_retention_persists() {
+        let (handler, dir, _rt) = temp_handler();
+        let path = dir.path().join("config.toml");
+
+        handler
+            .request_config_patch("privacy.session_archive_after_days", &serde_json::json!(30))
+            .unwrap();
+        handler
+            .request_config_patch("privacy.session_retention_days", &serde_json::json!(365))
+            .unwrap();
+
+        let loaded = SpeechConfig::from_file(&path).unwrap();
+        assert_eq!(loaded.privacy.session_archive_after_days, 30);
+        assert_eq!(loaded.privacy.session_retention_days, 365);
+        assert!(!loaded.privacy.encrypt_at_rest);
+
+        let result = handler.query_config_get(Some("privacy")).unwrap();
+        assert_eq!(result["privacy"]["session_retention_days"], 365);
+        assert_eq!(
+            result["privacy"]["encrypted_stores"],
+            serde_json::json!(["sessions", "memory_backups"])
+        );
+    }
+
+    #[test]
+    fn config_patch_offline_mode_persists_and_switches_guard() {
+        let (handler, dir, _rt) = temp_handler();
+        let path = dir.path().join("config.toml");
+
+        handler
+            .request_config_patch("offline_mode", &serde_json::json!(true))
+            .unwrap();
+        assert!(crate::offline::is_offline());
+        assert!(SpeechConfig::from_file(&path).unwrap().offline_mode);
+        let result = handler.query_config_get(Some("offline_mode")).unwrap();
+        assert_eq!(result["offline_mode"], true);
+
+        handler
+            .request_config_patch("offline_mode", &serde_json::json!(false))
+            .unwrap();
+        assert!(!crate::offline::is_offline());
+        assert!(!SpeechConfig::from_file(&path).unwrap().offline_mode);
+    }
+
+    #[test]
+    fn config_patch_captions_mode_persists_and_applies() {
+        let (handler, dir, _rt) = temp_handler();
+        let path = dir.path().join("config.toml");
+
+        handler
+            .request_config_patch("captions.mode", &serde_json::json!("captions_only"))
+            .unwrap();
+        assert!(crate::captions::captions_only());
+        assert_eq!(
+            SpeechConfig::from_file(&path).unwrap().captions.mode,
+            CaptionsMode::CaptionsOnly
+        );
+        let result = handler.query_config_get(Some("captions")).unwrap();
+        assert_eq!(result["captions"]["mode"], "captions_only");
+
+        handler
+            .request_config_patch("captions.mode", &serde_json::json!("loud"))
+            .unwrap();
+        assert!(crate::captions::captions_only());
+
+        handler
+            .request_config_patch("captions.mode", &serde_json::json!("off"))
+            .unwrap();
+        assert!(!crate::captions::enabled());
+    }
+
+    #[test]
+    fn config_patch_hotword_environment_persists() {
+        let (handler, dir, _rt) = temp_handler();
+        let path = dir.path().join("config.toml");
+
+        handler
+            .request_config_patch("hotword.sensitivity", &serde_json::json!(0.5))
+            .unwrap();
+        handler
+            .request_config_patch("hotword.environment", &serde_json::json!("kitchen"))
+            .unwrap();
+        handler
+            .request_config_patch("hotword.sensitivity", &serde_json::json!(1.5))
+            .unwrap();
+
+        let loaded = SpeechConfig::from_file(&path).unwrap();
+        assert!((loaded.hotword.sensitivity - 0.5).abs() < f32::EPSILON);
+        assert_eq!(loaded.hotword.environment.as_deref(), Some("kitchen"));
+        let result = handler.query_config_get(Some("hotword")).unwrap();
+        assert_eq!(result["hotword"]["environment"], "kitchen");
+        assert!(result["hotword"]["stats"].is_array());
+
+        handler
+            .request_config_patch("hotword.environment", &serde_json::Value::Null)
+            .unwrap();
+        assert!(
+            SpeechConfig::from_file(&path)
+                .unwrap()
+                .hotword
+                .environment
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn config_patch_theme_pack_persists_and_lists_packs() {
+        let (handler, dir, _rt) = temp_handler();
+        let path = dir.path().join("config.toml");
+
+        handler
+            .request_config_patch("theme.pack", &serde_json::json!("no-such-pack"))
+            .unwrap();
+        assert_eq!(
+            SpeechConfig::from_file(&path)
+                .unwrap()
+                .theme
+                .pack
+                .as_deref(),
+            Some("no-such-pack")
+        );
+        let result = handler.query_config_get(Some("theme")).unwrap();
+        let theme = &result["theme"];
+        assert_eq!(theme["pack"], "no-such-pack");
+        assert_eq!(theme["packs"][0]["id"], crate::theme::DEFAULT_PACK_ID);
+        assert_eq!(theme["current"]["pack"], crate::theme::DEFAULT_PACK_ID);
+
+        handler
+            .request_config_patch("theme.pack", &serde_json::Value::Null)
+            .unwrap();
+        assert!(SpeechConfig::from_file(&path).unwrap().theme.pack.is_none());
+    }
+
+    #[test]
+    fn accessibility_mode_follows_events_with_announcements() {
+        let (handler, mut event_rx, dir, _rt) = temp_handler_with_events();
+        handler
+            .request_config_patch("accessibility.verbosity", &serde_json::json!("minimal"))
+            .unwrap();
+        handler
+            .request_config_patch("accessibility.enabled", &serde_json::json!(true))
+            .unwrap();
+        let saved = SpeechConfig::from_file(&dir.path().join("config.toml")).unwrap();
+        assert!(saved.accessibility.enabled);
+        assert_eq!(
+            saved.accessibility.verbosity,
+            AccessibilityVerbosity::Minimal
+        );
+
+        handler.emit_event(
+            "runtime.error",
+            serde_json::json!({"error": "model missing"}),
+        );
+        let mut announcements = Vec::new();
+        while let Ok(evt) = event_rx.try_recv() {
+            if evt.event == crate::accessibility::ANNOUNCEMENT_EVENT {
+                announcements.push(evt.payload);
+            }
+        }
+        handler
+            .request_config_patch("accessibility.enabled", &serde_json::json!(false))
+            .unwrap();
+
+        let error = announcements
+            .iter()
+            .find(|p| p["source_event"] == "runtime.error");
+        match error {
+            Some(p) => {
+                assert_eq!(p["text"], "Error: model missing");
+                assert_eq!(p["priority"], "assertive");
+            }
+            None => unreachable!("runtime.error should be announced"),
+        }
+        let result = handler.query_config_get(Some("accessibility")).unwrap();
+        assert_eq!(result["accessibility"]["enabled"], false);
+        assert_eq!(result["accessibility"]["verbosity"], "minimal");
+    }
+
+    #[test]
+    fn data_forget_requires_running_pipeline() {
+        let (handler, _dir, _rt) = temp_handler();
+        let err = handler.request_data_forget(false, true).unwrap_err();
+        assert!(err.to_string().contains("running pipeline"));
+    }
+
     #[test]
     fn config_get_voice_identity_returns_current_values() {
         let (handler, _dir, _rt) = temp_handler();
@@ -3632,6 +5672,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sleep_and_wake_emit_suspended_and_resumed_once() {
+        let (handler, mut event_rx, _dir, _rt) = temp_handler_with_events();
+
+        handler.request_system_lifecycle(PowerEvent::Sleep).unwrap();
+        handler.request_system_lifecycle(PowerEvent::Sleep).unwrap();
+        assert!(crate::scheduler::priority::suspended());
+        handler.request_system_lifecycle(PowerEvent::Wake).unwrap();
+        handler.request_system_lifecycle(PowerEvent::Wake).unwrap();
+        assert!(!crate::scheduler::priority::suspended());
+
+        let mut events = Vec::new();
+        while let Ok(evt) = event_rx.try_recv() {
+            events.push(evt.event);
+        }
+        assert_eq!(events, vec!["runtime.suspended", "runtime.resumed"]);
+        // The pipeline was not running, so waking does not start it.
+        assert_eq!(handler.pipeline_state(), PipelineState::Stopped);
+    }
+
     #[test]
     #[ignore = "requires ML model download — run locally with cached models"]
     fn runtime_start_stop_start_full_lifecycle() {
@@ -3729,6 +5789,19 @@ mod tests {
         assert_eq!(status["restart_count"], 0);
     }
 
+    #[test]
+    fn runtime_status_includes_queue_depths() {
+        let (handler, _dir, _rt) = temp_handler();
+        let status = handler.query_runtime_status().unwrap();
+        let queues = status["queues"].as_array().expect("queues array");
+        assert_eq!(queues.len(), crate::pipeline::queues::LINKS.len());
+        assert_eq!(queues[0]["name"], "audio");
+        assert_eq!(queues[0]["policy"], "drop_newest");
+        let streams = status["audio_streams"].as_array().expect("streams array");
+        assert_eq!(streams.len(), crate::audio::watchdog::STREAMS.len());
+        assert_eq!(streams[0]["name"], "capture");
+    }
+
     #[test]
     fn runtime_status_includes_rescue_health() {
         let (handler, _dir, _rt) = temp_handler();
@@ -4281,6 +6354,28 @@ mod tests {
         assert_eq!(payload["visible"], true);
     }
 
+    #[test]
+    fn runtime_event_envelopes_carry_the_versioned_record() {
+        let event = RuntimeEvent::ConversationCanvasVisibility { visible: true };
+        let envelope = runtime_event_envelope(&event);
+        assert_eq!(envelope.event, "pipeline.canvas_visibility");
+        let record = envelope.runtime_event.clone().unwrap();
+        assert_eq!(
+            record["schema_version"],
+            crate::runtime::RUNTIME_EVENT_SCHEMA_VERSION
+        );
+        assert!(matches!(
+            RuntimeEvent::from_json(&record).unwrap(),
+            Some(RuntimeEvent::ConversationCanvasVisibility { visible: true })
+        ));
+
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["runtime_event"], record);
+        let plain = EventEnvelope::new("e", "runtime.error", serde_json::json!({}));
+        let json = serde_json::to_value(&plain).unwrap();
+        assert!(json.get("runtime_event").is_none());
+    }
+
     #[test]
     fn scheduler_error_payload_shape_is_runtime_error_with_source() {
         let payload = serde_json::json!({"source": "scheduler", "error": "boom"});
@@ -1,5 +1,6 @@
 //! Host-facing contracts and latency harnesses for native app integration.
 
+pub mod bridge;
 pub mod channel;
 pub mod contract;
 pub mod handler;
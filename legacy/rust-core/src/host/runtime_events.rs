@@ -3,7 +3,8 @@
 //! Extracted from `handler.rs` — these free functions convert internal
 //! event types into FFI-compatible JSON payloads for the Swift host.
 
-use crate::progress::ProgressEvent;
+use crate::host::contract::EventEnvelope;
+use crate::progress::{OverallProgress, ProgressEvent};
 use crate::runtime::RuntimeEvent;
 
 /// Convert a [`ProgressEvent`] to a JSON payload for the FFI event bus.
@@ -53,6 +54,10 @@ pub(crate) fn progress_event_to_json(evt: &ProgressEvent) -> serde_json::Value {
             "model_name": model_name,
             "duration_secs": duration_secs,
         }),
+        ProgressEvent::ComponentReady { component } => serde_json::json!({
+            "stage": "component_ready",
+            "component": component,
+        }),
         ProgressEvent::AggregateProgress {
             bytes_downloaded,
             total_bytes,
@@ -75,6 +80,76 @@ pub(crate) fn progress_event_to_json(evt: &ProgressEvent) -> serde_json::Value {
             "stage": "error",
             "message": message,
         }),
+        ProgressEvent::CheckStarted { check } => serde_json::json!({
+            "stage": "check_started",
+            "check": check,
+        }),
+        ProgressEvent::CheckPassed { check, detail } => serde_json::json!({
+            "stage": "check_passed",
+            "check": check,
+            "detail": detail,
+        }),
+        ProgressEvent::CheckFailed {
+            check,
+            message,
+            action,
+        } => serde_json::json!({
+            "stage": "check_failed",
+            "check": check,
+            "message": message,
+            "action": action,
+        }),
+        ProgressEvent::TaskStarted {
+            id,
+            parent,
+            label,
+            weight,
+        } => serde_json::json!({
+            "stage": "task_started",
+            "task_id": id,
+            "parent_id": parent,
+            "label": label,
+            "weight": weight,
+        }),
+        ProgressEvent::TaskProgress {
+            id,
+            completed,
+            total,
+        } => serde_json::json!({
+            "stage": "task_progress",
+            "task_id": id,
+            "completed": completed,
+            "total": total,
+        }),
+        ProgressEvent::TaskFinished { id } => serde_json::json!({
+            "stage": "task_finished",
+            "task_id": id,
+        }),
+    }
+}
+
+/// Convert weighted [`OverallProgress`] to a progress payload, for the one
+/// bar onboarding shows.
+pub(crate) fn overall_progress_to_json(overall: &OverallProgress) -> serde_json::Value {
+    serde_json::json!({
+        "stage": "overall_progress",
+        "fraction": overall.fraction,
+        "label": overall.label,
+    })
+}
+
+/// Wrap a [`RuntimeEvent`] for the FFI event bus: its event name and payload
+/// from [`map_runtime_event`], plus its versioned record from
+/// [`RuntimeEvent::to_json`].
+pub(crate) fn runtime_event_envelope(event: &RuntimeEvent) -> EventEnvelope {
+    let (name, payload) = map_runtime_event(event);
+    let envelope = EventEnvelope::new(uuid::Uuid::new_v4().to_string(), name, payload);
+    match event.to_json() {
+        Ok(record) => envelope.with_runtime_event(record),
+        Err(e) => {
+            tracing::warn!(error = %e, "sending runtime event without its versioned record");
+            envelope
+        }
     }
 }
 
@@ -89,6 +164,20 @@ pub(crate) fn map_runtime_event(event: &RuntimeEvent) -> (String, serde_json::Va
                 "device_name": device_name,
             }),
         ),
+        RuntimeEvent::Control(ControlEvent::AudioQualityChanged {
+            device_name,
+            sample_rate,
+            low_quality,
+        }) => (
+            "pipeline.control".to_owned(),
+            serde_json::json!({
+                "action": "audio_quality_changed",
+                "device_name": device_name,
+                "sample_rate": sample_rate,
+                "low_quality": low_quality,
+                "message": low_quality.then(|| crate::i18n::text("announce.audio_low_quality")),
+            }),
+        ),
         RuntimeEvent::Control(ControlEvent::DegradedMode { mode }) => (
             "pipeline.control".to_owned(),
             serde_json::json!({
@@ -108,6 +197,22 @@ pub(crate) fn map_runtime_event(event: &RuntimeEvent) -> (String, serde_json::Va
             "pipeline.assistant_sentence".to_owned(),
             serde_json::json!({"text": s.text, "is_final": s.is_final}),
         ),
+        RuntimeEvent::AssistantTextDelta {
+            message_id,
+            delta,
+            is_final,
+        } => (
+            "pipeline.assistant_text_delta".to_owned(),
+            serde_json::json!({"message_id": message_id, "delta": delta, "is_final": is_final}),
+        ),
+        RuntimeEvent::AssistantTextDiscarded { message_id } => (
+            "pipeline.assistant_text_discarded".to_owned(),
+            serde_json::json!({"message_id": message_id}),
+        ),
+        RuntimeEvent::AssistantThinking { text } => (
+            "pipeline.assistant_thinking".to_owned(),
+            serde_json::json!({"text": text}),
+        ),
         RuntimeEvent::AssistantGenerating { active } => (
             "pipeline.generating".to_owned(),
             serde_json::json!({"active": active}),
@@ -116,6 +221,10 @@ pub(crate) fn map_runtime_event(event: &RuntimeEvent) -> (String, serde_json::Va
             "pipeline.tool_executing".to_owned(),
             serde_json::json!({"name": name}),
         ),
+        RuntimeEvent::ToolProgress { name, fraction } => (
+            "pipeline.tool_progress".to_owned(),
+            serde_json::json!({"name": name, "fraction": fraction}),
+        ),
         RuntimeEvent::ToolCall {
             id,
             name,
@@ -129,6 +238,7 @@ pub(crate) fn map_runtime_event(event: &RuntimeEvent) -> (String, serde_json::Va
             name,
             success,
             output_text,
+            structured,
         } => (
             "pipeline.tool_result".to_owned(),
             serde_json::json!({
@@ -136,8 +246,49 @@ pub(crate) fn map_runtime_event(event: &RuntimeEvent) -> (String, serde_json::Va
                 "name": name,
                 "success": success,
                 "output_text": output_text,
+                "structured": structured,
+            }),
+        ),
+        RuntimeEvent::ToolBudgetExhausted {
+            name,
+            scope,
+            window,
+            limit,
+        } => (
+            "pipeline.tool_budget_exhausted".to_owned(),
+            serde_json::json!({
+                "name": name,
+                "scope": scope,
+                "window": window,
+                "limit": limit,
             }),
         ),
+        RuntimeEvent::ToolLimitExceeded {
+            name,
+            kind,
+            limit,
+            used,
+        } => (
+            "pipeline.tool_limit_exceeded".to_owned(),
+            serde_json::json!({
+                "name": name,
+                "kind": kind,
+                "limit": limit,
+                "used": used,
+            }),
+        ),
+        RuntimeEvent::PromptInjectionDetected {
+            tool,
+            patterns,
+            stripped,
+        } => (
+            "pipeline.prompt_injection_detected".to_owned(),
+            serde_json::json!({"tool": tool, "patterns": patterns, "stripped": stripped}),
+        ),
+        RuntimeEvent::AnswerFlagged { reason } => (
+            "pipeline.answer_flagged".to_owned(),
+            serde_json::json!({"reason": reason}),
+        ),
         RuntimeEvent::AssistantAudioLevel { rms } => (
             "pipeline.audio_level".to_owned(),
             serde_json::json!({"rms": rms}),
@@ -184,6 +335,59 @@ pub(crate) fn map_runtime_event(event: &RuntimeEvent) -> (String, serde_json::Va
             "pipeline.permissions_changed".to_owned(),
             serde_json::json!({"granted": granted}),
         ),
+        RuntimeEvent::DataForgetRequested => (
+            "pipeline.data_forget_requested".to_owned(),
+            serde_json::json!({}),
+        ),
+        RuntimeEvent::CaptionSegment(segment) => (
+            "pipeline.caption".to_owned(),
+            serde_json::json!({
+                "utterance_id": segment.utterance_id,
+                "index": segment.index,
+                "text": segment.text,
+                "start_ms": segment.start_ms,
+                "end_ms": segment.end_ms,
+            }),
+        ),
+        RuntimeEvent::CaptionsEnded {
+            utterance_id,
+            interrupted,
+        } => (
+            "pipeline.captions_ended".to_owned(),
+            serde_json::json!({"utterance_id": utterance_id, "interrupted": interrupted}),
+        ),
+        RuntimeEvent::OfflineModeChanged { offline } => (
+            "pipeline.offline_mode_changed".to_owned(),
+            serde_json::json!({"offline": offline}),
+        ),
+        RuntimeEvent::MeetingModeChanged { active } => (
+            "pipeline.meeting_mode_changed".to_owned(),
+            serde_json::json!({"active": active}),
+        ),
+        RuntimeEvent::MeetingMinutesReady {
+            meeting_id,
+            path,
+            action_items,
+        } => (
+            "pipeline.meeting_minutes_ready".to_owned(),
+            serde_json::json!({
+                "meeting_id": meeting_id,
+                "path": path,
+                "action_items": action_items,
+            }),
+        ),
+        RuntimeEvent::ConversationHandedOff {
+            source,
+            messages,
+            memory_ids,
+        } => (
+            "pipeline.conversation_handed_off".to_owned(),
+            serde_json::json!({
+                "source": source,
+                "messages": messages,
+                "memory_ids": memory_ids,
+            }),
+        ),
         RuntimeEvent::ModelSwitchRequested { target } => (
             "pipeline.model_switch_requested".to_owned(),
             serde_json::json!({"target": target}),
@@ -198,6 +402,10 @@ pub(crate) fn map_runtime_event(event: &RuntimeEvent) -> (String, serde_json::Va
                 serde_json::json!({"entries": items}),
             )
         }
+        RuntimeEvent::ConversationEnded { turns, summarized } => (
+            "pipeline.conversation_ended".to_owned(),
+            serde_json::json!({"turns": turns, "summarized": summarized}),
+        ),
         RuntimeEvent::MicStatus { active } => (
             "pipeline.mic_status".to_owned(),
             serde_json::json!({"active": active}),
@@ -298,5 +506,13 @@ pub(crate) fn map_runtime_event(event: &RuntimeEvent) -> (String, serde_json::Va
                 "enrolled": enrolled,
             }),
         ),
+        RuntimeEvent::Suspended => (
+            "runtime.suspended".to_owned(),
+            serde_json::json!({"status": "suspended"}),
+        ),
+        RuntimeEvent::Resumed { slept_secs } => (
+            "runtime.resumed".to_owned(),
+            serde_json::json!({"status": "resumed", "slept_secs": slept_secs}),
+        ),
     }
 }
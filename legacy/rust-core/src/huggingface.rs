@@ -95,12 +95,14 @@ struct GgufWire {
     eos_token: Option<String>,
 }
 
-fn http_agent() -> ureq::Agent {
-    ureq::AgentBuilder::new()
+fn http_agent() -> Result<ureq::Agent, HfApiError> {
+    crate::offline::ensure_online("Hugging Face API access")
+        .map_err(|e| HfApiError::Http(e.to_string()))?;
+    Ok(ureq::AgentBuilder::new()
         .timeout_connect(Duration::from_secs(10))
         .timeout_read(Duration::from_secs(20))
         .timeout_write(Duration::from_secs(20))
-        .build()
+        .build())
 }
 
 fn parse_json<T: for<'de> Deserialize<'de>>(body: &str) -> Result<T, HfApiError> {
@@ -114,7 +116,7 @@ pub fn search_models(
     pipeline_tag: Option<&str>,
     limit: usize,
 ) -> Result<Vec<ModelSearchItem>, HfApiError> {
-    let agent = http_agent();
+    let agent = http_agent()?;
 
     let mut url = format!(
         "https://huggingface.co/api/models?search={}&limit={}",
@@ -158,7 +160,7 @@ pub fn search_models(
 
 /// Fetch detailed model info including siblings (filenames) and GGUF metadata when available.
 pub fn get_model_info(model_id: &str) -> Result<ModelInfo, HfApiError> {
-    let agent = http_agent();
+    let agent = http_agent()?;
     let url = format!("https://huggingface.co/api/models/{model_id}");
 
     let resp = agent
@@ -266,7 +268,7 @@ fn first_paragraph(markdown: &str) -> Option<String> {
 
 /// Best-effort README snippet (first paragraph).
 pub fn readme_snippet(model_id: &str) -> Result<Option<String>, HfApiError> {
-    let agent = http_agent();
+    let agent = http_agent()?;
     let urls = [
         format!("https://huggingface.co/{model_id}/raw/main/README.md"),
         format!("https://huggingface.co/{model_id}/resolve/main/README.md"),
@@ -330,7 +332,7 @@ pub fn gguf_file_size_bytes(
     model_id: &str,
     gguf_filename: &str,
 ) -> Result<Option<u64>, HfApiError> {
-    let agent = http_agent();
+    let agent = http_agent()?;
     let url = format!("https://huggingface.co/{model_id}/resolve/main/{gguf_filename}");
 
     let resp = head_follow_location(&agent, &url, 3)?;
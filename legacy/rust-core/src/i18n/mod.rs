@@ -0,0 +1,327 @@
+//! Localization of user-facing text generated in Rust.
+//!
+//! Canned spoken replies (acknowledgments, approval prompts, voice command
+//! responses, error apologies) and accessibility announcements are looked up
+//! by key in per-language message catalogs instead of being hard-coded in
+//! English. Catalogs are TOML files compiled into the binary (`en.toml`,
+//! `de.toml`, …); nested tables form dotted keys (`approval.prompt.bash`).
+//!
+//! - A value is either a string or, for phrases rotated to avoid repetition,
+//!   a list of strings (see [`phrase`]).
+//! - `{name}` placeholders are filled in by [`format`].
+//! - Counted messages use `.one` / `.other` sub-keys (see [`plural`]).
+//! - A key missing from the active catalog falls back to English.
+//!
+//! The active language is process-wide, set from `SpeechConfig::language` at
+//! startup and changeable through `config.patch`.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// A language with a bundled message catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    English,
+    German,
+    Spanish,
+    French,
+}
+
+impl Locale {
+    /// Every bundled locale, English (the fallback) first.
+    pub const ALL: [Locale; 4] = [
+        Locale::English,
+        Locale::German,
+        Locale::Spanish,
+        Locale::French,
+    ];
+
+    /// ISO 639-1 code (`"en"`, `"de"`, …).
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::Spanish => "es",
+            Self::French => "fr",
+        }
+    }
+
+    /// Parse a language tag such as `"de"`, `"de-AT"` or `"fr_CA"`.
+    ///
+    /// Only the primary subtag is considered. Returns `None` for languages
+    /// without a catalog.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        Self::ALL.into_iter().find(|l| l.code() == primary)
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Self::English => include_str!("en.toml"),
+            Self::German => include_str!("de.toml"),
+            Self::Spanish => include_str!("es.toml"),
+            Self::French => include_str!("fr.toml"),
+        }
+    }
+
+    fn index(self) -> u8 {
+        match self {
+            Self::English => 0,
+            Self::German => 1,
+            Self::Spanish => 2,
+            Self::French => 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Entry {
+    Text(String),
+    Phrases(Vec<String>),
+}
+
+type Catalog = HashMap<String, Entry>;
+
+static ACTIVE: AtomicU8 = AtomicU8::new(0);
+static CATALOGS: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+
+fn catalogs() -> &'static HashMap<Locale, Catalog> {
+    CATALOGS.get_or_init(|| {
+        Locale::ALL
+            .into_iter()
+            .map(|locale| (locale, parse_catalog(locale)))
+            .collect()
+    })
+}
+
+fn parse_catalog(locale: Locale) -> Catalog {
+    let mut catalog = Catalog::new();
+    match toml::from_str::<toml::Table>(locale.source()) {
+        Ok(table) => flatten("", &table, &mut catalog),
+        // Catalogs are compiled in and covered by tests; a broken one falls
+        // back to English key by key.
+        Err(e) => tracing::error!(locale = locale.code(), "invalid message catalog: {e}"),
+    }
+    catalog
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut Catalog) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            toml::Value::String(s) => {
+                out.insert(key, Entry::Text(s.clone()));
+            }
+            toml::Value::Array(items) => {
+                let phrases = items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_owned))
+                    .collect();
+                out.insert(key, Entry::Phrases(phrases));
+            }
+            toml::Value::Table(inner) => flatten(&key, inner, out),
+            _ => {}
+        }
+    }
+}
+
+/// Switch the active language.
+///
+/// `None`, an empty tag, or a language without a catalog selects English.
+/// Returns the locale now in use.
+pub fn set_language(tag: Option<&str>) -> Locale {
+    let requested = tag.map(str::trim).filter(|t| !t.is_empty());
+    let locale = match requested {
+        Some(t) => Locale::from_tag(t).unwrap_or_else(|| {
+            tracing::warn!(
+                language = t,
+                "no message catalog for language; using English"
+            );
+            Locale::English
+        }),
+        None => Locale::English,
+    };
+    let previous = ACTIVE.swap(locale.index(), Ordering::SeqCst);
+    if previous != locale.index() {
+        tracing::info!(language = locale.code(), "spoken message language changed");
+    }
+    locale
+}
+
+/// The active locale.
+pub fn locale() -> Locale {
+    let index = ACTIVE.load(Ordering::SeqCst);
+    Locale::ALL
+        .into_iter()
+        .find(|l| l.index() == index)
+        .unwrap_or(Locale::English)
+}
+
+fn lookup(locale: Locale, key: &str) -> Option<&'static Entry> {
+    let catalogs = catalogs();
+    catalogs
+        .get(&locale)
+        .and_then(|c| c.get(key))
+        .or_else(|| catalogs.get(&Locale::English).and_then(|c| c.get(key)))
+}
+
+/// Message `key` in `locale`, falling back to English, then to the key itself.
+pub fn text_in(locale: Locale, key: &'static str) -> &'static str {
+    match lookup(locale, key) {
+        Some(Entry::Text(s)) => s,
+        Some(Entry::Phrases(list)) => list.first().map_or(key, String::as_str),
+        None => {
+            tracing::warn!(key, "missing message");
+            key
+        }
+    }
+}
+
+/// Message `key` in the active language.
+pub fn text(key: &'static str) -> &'static str {
+    text_in(locale(), key)
+}
+
+/// Fill `{name}` placeholders in `template` from `args`.
+fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    let mut out = template.to_owned();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{name}}}"), value);
+    }
+    out
+}
+
+/// Message `key` in `locale` with `{name}` placeholders filled from `args`.
+pub fn format_in(locale: Locale, key: &'static str, args: &[(&str, &str)]) -> String {
+    fill(text_in(locale, key), args)
+}
+
+/// Message `key` in the active language with placeholders filled from `args`.
+pub fn format(key: &'static str, args: &[(&str, &str)]) -> String {
+    format_in(locale(), key, args)
+}
+
+/// Counted message in `locale`: `key.one` when `count` is 1, otherwise
+/// `key.other`, with `{count}` and `args` filled in.
+pub fn plural_in(locale: Locale, key: &'static str, count: usize, args: &[(&str, &str)]) -> String {
+    let form = if count == 1 { "one" } else { "other" };
+    let count_text = count.to_string();
+    let mut all_args = vec![("count", count_text.as_str())];
+    all_args.extend_from_slice(args);
+    match lookup(locale, &format!("{key}.{form}")) {
+        Some(Entry::Text(s)) => fill(s, &all_args),
+        _ => {
+            tracing::warn!(key, form, "missing plural message");
+            key.to_owned()
+        }
+    }
+}
+
+/// Counted message in the active language (see [`plural_in`]).
+pub fn plural(key: &'static str, count: usize, args: &[(&str, &str)]) -> String {
+    plural_in(locale(), key, count, args)
+}
+
+/// Pick from the phrase list `key`, rotating with `counter` so the same
+/// phrase is not used twice in a row.
+pub fn phrase(key: &'static str, counter: u64) -> &'static str {
+    match lookup(locale(), key) {
+        Some(Entry::Phrases(list)) if !list.is_empty() => &list[(counter as usize) % list.len()],
+        Some(Entry::Text(s)) => s,
+        _ => {
+            tracing::warn!(key, "missing phrase list");
+            ""
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    fn placeholders(s: &str) -> Vec<&str> {
+        let mut out: Vec<&str> = s
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        out.sort_unstable();
+        out
+    }
+
+    #[test]
+    fn every_catalog_parses_and_matches_english() {
+        let english = &catalogs()[&Locale::English];
+        assert!(!english.is_empty());
+        for locale in Locale::ALL {
+            let catalog = &catalogs()[&locale];
+            assert_eq!(
+                toml::from_str::<toml::Table>(locale.source())
+                    .err()
+                    .map(|e| e.to_string()),
+                None,
+                "{} catalog must parse",
+                locale.code()
+            );
+            for (key, entry) in catalog {
+                let english_entry = english
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{}: unknown key {key}", locale.code()));
+                match (entry, english_entry) {
+                    (Entry::Text(t), Entry::Text(e)) => assert_eq!(
+                        placeholders(t),
+                        placeholders(e),
+                        "{}: placeholders differ for {key}",
+                        locale.code()
+                    ),
+                    (Entry::Phrases(p), Entry::Phrases(_)) => {
+                        assert!(!p.is_empty(), "{}: empty list {key}", locale.code());
+                    }
+                    _ => panic!("{}: {key} has a different shape", locale.code()),
+                }
+            }
+            for key in english.keys() {
+                assert!(
+                    catalog.contains_key(key),
+                    "{}: missing translation for {key}",
+                    locale.code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tags_resolve_to_bundled_locales() {
+        assert_eq!(Locale::from_tag("de"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("fr_CA"), Some(Locale::French));
+        assert_eq!(Locale::from_tag(" ES-mx "), Some(Locale::Spanish));
+        assert_eq!(Locale::from_tag("ja"), None);
+        assert_eq!(Locale::from_tag(""), None);
+    }
+
+    #[test]
+    fn lookups_fill_placeholders_and_fall_back() {
+        assert_eq!(
+            format_in(Locale::English, "voice.current_model", &[("model", "qwen")]),
+            "I'm currently using qwen."
+        );
+        assert_eq!(
+            format_in(Locale::German, "voice.current_model", &[("model", "qwen")]),
+            "Ich verwende gerade qwen."
+        );
+        assert_eq!(text_in(Locale::French, "no.such.key"), "no.such.key");
+        assert_eq!(fill("{a} and {b}", &[("a", "x"), ("b", "y")]), "x and y");
+    }
+}
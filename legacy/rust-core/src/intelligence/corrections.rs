@@ -0,0 +1,465 @@
+//! Corrections the user has given, kept so Fae doesn't repeat a mistake.
+//!
+//! A correction is captured when the user rejects the previous reply — by
+//! voice ("no, that's wrong", "that's not right") or with a thumbs-down in
+//! the app. It records the turn that was wrong and, when the user gives it,
+//! the right answer. If the user only says the reply was wrong, Fae asks what
+//! was right and the next thing the user says fills it in.
+//!
+//! Corrections are persisted in `corrections.json`. The most recent ones are
+//! part of the system prompt, and the whole list can be exported as JSON
+//! Lines for offline analysis.
+//!
+//! Detection is rule-based and only looks at the start of the utterance, so
+//! "what's wrong with my car" is not feedback.
+
+use std::io::Write as _;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::time_util::now_epoch_secs;
+
+/// Corrections kept; the oldest are dropped on save.
+const MAX_CORRECTIONS: usize = 200;
+
+/// Corrections included in the system prompt, newest first.
+const PROMPT_CORRECTIONS: usize = 8;
+
+/// Longest quote of a turn in the system prompt, in characters.
+const MAX_PROMPT_QUOTE_CHARS: usize = 160;
+
+/// How long after the feedback the user's next words count as the answer.
+const ANSWER_WINDOW_SECS: u64 = 5 * 60;
+
+/// Leading words dropped before looking for a feedback phrase.
+const LEAD_INS: &[&str] = &["no", "nope", "nah", "sorry", "um", "hmm", "actually"];
+
+/// Phrases that reject the previous reply.
+const NEGATIVE_PHRASES: &[&str] = &[
+    "that's wrong",
+    "that is wrong",
+    "that's not right",
+    "that is not right",
+    "that isn't right",
+    "that's not correct",
+    "that is not correct",
+    "that isn't correct",
+    "that's incorrect",
+    "that is incorrect",
+    "that's not true",
+    "that is not true",
+    "you're wrong",
+    "you are wrong",
+    "you got that wrong",
+    "you got it wrong",
+    "wrong answer",
+];
+
+/// Words that introduce the right answer after the feedback phrase.
+const ANSWER_PREFIXES: &[&str] = &[
+    "the right answer is ",
+    "the correct answer is ",
+    "the answer is ",
+    "it should be ",
+    "it's actually ",
+    "it is actually ",
+    "it's ",
+    "it is ",
+    "actually ",
+    "because ",
+];
+
+/// Note prepended to the model input when the user said a reply was wrong
+/// without saying what was right.
+pub const ASK_FOR_CORRECTION_NOTE: &str = "[The user says your previous reply was wrong. \
+     Acknowledge it in one short sentence and ask what the right answer is.]";
+
+/// How the user flagged the reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackSource {
+    /// Said so in conversation.
+    Voice,
+    /// Thumbs-down in the app.
+    ThumbsDown,
+}
+
+/// One reply the user said was wrong.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Correction {
+    pub id: u64,
+    /// Unix seconds when the feedback was given.
+    pub created_at: u64,
+    pub source: FeedbackSource,
+    /// What the user asked.
+    pub user_text: String,
+    /// The reply that was wrong.
+    pub assistant_text: String,
+    /// What the user said was right, if they said.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub right_answer: Option<String>,
+}
+
+/// Negative feedback found in an utterance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeFeedback {
+    /// The right answer, when the user gave it in the same breath.
+    pub right_answer: Option<String>,
+}
+
+/// The persisted list of corrections.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorrectionStore {
+    #[serde(default)]
+    next_id: u64,
+    #[serde(default)]
+    corrections: Vec<Correction>,
+}
+
+impl CorrectionStore {
+    /// Load the list from `path`, returning an empty list on error.
+    pub fn load(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("failed to parse corrections: {e}");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!("failed to load corrections: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Save the list to `path`, dropping the oldest corrections beyond the
+    /// limit.
+    pub fn save(&mut self, path: &Path) -> Result<(), String> {
+        if self.corrections.len() > MAX_CORRECTIONS {
+            let excess = self.corrections.len() - MAX_CORRECTIONS;
+            self.corrections.drain(..excess);
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create corrections dir: {e}"))?;
+        }
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| format!("serialize error: {e}"))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("write error: {e}"))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("write error: {e}"))
+    }
+
+    /// All corrections, oldest first.
+    pub fn corrections(&self) -> &[Correction] {
+        &self.corrections
+    }
+
+    /// Record that the reply to `user_text` was wrong. Returns its id.
+    pub fn add(
+        &mut self,
+        source: FeedbackSource,
+        user_text: &str,
+        assistant_text: &str,
+        right_answer: Option<&str>,
+    ) -> u64 {
+        self.next_id = self.next_id.max(1);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.corrections.push(Correction {
+            id,
+            created_at: now_epoch_secs(),
+            source,
+            user_text: user_text.trim().to_owned(),
+            assistant_text: assistant_text.trim().to_owned(),
+            right_answer: non_empty(right_answer),
+        });
+        id
+    }
+
+    /// Fill in the right answer of the newest correction, if it has none
+    /// and was given within the last few minutes.
+    ///
+    /// Returns whether a correction was updated.
+    pub fn answer_latest(&mut self, right_answer: &str, now: u64) -> bool {
+        let Some(answer) = non_empty(Some(right_answer)) else {
+            return false;
+        };
+        match self.corrections.last_mut() {
+            Some(latest)
+                if latest.right_answer.is_none()
+                    && now.saturating_sub(latest.created_at) <= ANSWER_WINDOW_SECS =>
+            {
+                latest.right_answer = Some(answer);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// System prompt section listing the most recent corrections, or `None`
+    /// when there are none.
+    pub fn prompt_section(&self) -> Option<String> {
+        if self.corrections.is_empty() {
+            return None;
+        }
+        let mut section = String::from(
+            "Past corrections (the user said these replies were wrong; don't repeat them):",
+        );
+        for correction in self.corrections.iter().rev().take(PROMPT_CORRECTIONS) {
+            section.push_str(&format!(
+                "\n- Asked \"{}\", you said \"{}\"",
+                quote(&correction.user_text),
+                quote(&correction.assistant_text)
+            ));
+            if let Some(answer) = &correction.right_answer {
+                section.push_str(&format!("; right answer: \"{}\"", quote(answer)));
+            }
+        }
+        Some(section)
+    }
+
+    /// Write every correction to `path` as JSON Lines, oldest first.
+    ///
+    /// Returns the number of corrections written.
+    pub fn export_jsonl(&self, path: &Path) -> Result<usize, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create export dir: {e}"))?;
+        }
+        let mut file =
+            std::fs::File::create(path).map_err(|e| format!("failed to create export: {e}"))?;
+        for correction in &self.corrections {
+            let line =
+                serde_json::to_string(correction).map_err(|e| format!("serialize error: {e}"))?;
+            writeln!(file, "{line}").map_err(|e| format!("write error: {e}"))?;
+        }
+        Ok(self.corrections.len())
+    }
+}
+
+/// Check whether `user_text` says the previous reply was wrong.
+pub fn detect_negative_feedback(user_text: &str) -> Option<NegativeFeedback> {
+    // ASCII lowercasing keeps byte offsets, so the answer keeps its case.
+    let text = user_text.trim().replace('\u{2019}', "'");
+    let lower = text.to_ascii_lowercase();
+
+    let mut start = 0;
+    loop {
+        let rest = lower[start..].trim_start_matches(|c: char| !c.is_alphanumeric());
+        start = lower.len() - rest.len();
+        let Some(word) = LEAD_INS.iter().find(|word| {
+            rest.strip_prefix(**word)
+                .is_some_and(|after| after.is_empty() || !after.starts_with(char::is_alphanumeric))
+        }) else {
+            break;
+        };
+        start += word.len();
+    }
+
+    let phrase = NEGATIVE_PHRASES
+        .iter()
+        .find(|phrase| lower[start..].starts_with(**phrase))?;
+    let end = start + phrase.len();
+    if lower[end..].starts_with(char::is_alphanumeric) {
+        return None;
+    }
+
+    let rest_lower = lower[end..].trim_start_matches(|c: char| !c.is_alphanumeric());
+    let mut answer_start = lower.len() - rest_lower.len();
+    if let Some(prefix) = ANSWER_PREFIXES
+        .iter()
+        .find(|prefix| rest_lower.starts_with(**prefix))
+    {
+        answer_start += prefix.len();
+    }
+    let answer = text[answer_start..]
+        .trim()
+        .trim_end_matches(['.', '!'])
+        .trim();
+    Some(NegativeFeedback {
+        right_answer: non_empty(Some(answer)),
+    })
+}
+
+/// Record that the reply to `user_text` was wrong in the list at `path`.
+///
+/// Best effort: failures are logged. Returns the new correction's id.
+pub fn record_correction(
+    path: &Path,
+    source: FeedbackSource,
+    user_text: &str,
+    assistant_text: &str,
+    right_answer: Option<&str>,
+) -> Option<u64> {
+    let mut store = CorrectionStore::load(path);
+    let id = store.add(source, user_text, assistant_text, right_answer);
+    if let Err(e) = store.save(path) {
+        warn!("failed to save correction: {e}");
+        return None;
+    }
+    info!(id, ?source, "captured correction");
+    Some(id)
+}
+
+/// Use `user_text` as the right answer to the correction Fae just asked
+/// about, in the list at `path`.
+///
+/// Best effort: failures are logged. Returns whether a correction was
+/// updated.
+pub fn answer_correction(path: &Path, user_text: &str) -> bool {
+    let mut store = CorrectionStore::load(path);
+    if !store.answer_latest(user_text, now_epoch_secs()) {
+        return false;
+    }
+    if let Err(e) = store.save(path) {
+        warn!("failed to save correction: {e}");
+        return false;
+    }
+    true
+}
+
+fn non_empty(text: Option<&str>) -> Option<String> {
+    text.map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_owned)
+}
+
+/// `text` on one line, cut to [`MAX_PROMPT_QUOTE_CHARS`].
+fn quote(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= MAX_PROMPT_QUOTE_CHARS {
+        return line;
+    }
+    let cut: String = line.chars().take(MAX_PROMPT_QUOTE_CHARS).collect();
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn answer_of(text: &str) -> Option<Option<String>> {
+        detect_negative_feedback(text).map(|f| f.right_answer)
+    }
+
+    #[test]
+    fn detects_feedback_with_and_without_an_answer() {
+        assert_eq!(answer_of("No, that's wrong."), Some(None));
+        assert_eq!(answer_of("Nope — that\u{2019}s not right"), Some(None));
+        assert_eq!(answer_of("you got it wrong!"), Some(None));
+        assert_eq!(
+            answer_of("No, that's wrong, it's Canberra."),
+            Some(Some("Canberra".to_owned()))
+        );
+        assert_eq!(
+            answer_of("That is incorrect. The answer is 42"),
+            Some(Some("42".to_owned()))
+        );
+        assert_eq!(
+            answer_of("that's not right, Paris is the capital of France"),
+            Some(Some("Paris is the capital of France".to_owned()))
+        );
+    }
+
+    #[test]
+    fn ignores_ordinary_sentences() {
+        for text in [
+            "What's wrong with my car?",
+            "Tell me why that's wrong in chess",
+            "nothing's wrong",
+            "that's wronged me",
+            "no worries, thanks",
+            "",
+        ] {
+            assert_eq!(answer_of(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn store_round_trips_and_caps_history() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("corrections.json");
+
+        let id = record_correction(
+            &path,
+            FeedbackSource::Voice,
+            "capital of Australia?",
+            "Sydney.",
+            None,
+        )
+        .expect("recorded");
+        assert!(answer_correction(&path, " Canberra "));
+        assert!(!answer_correction(&path, "something else"));
+
+        let store = CorrectionStore::load(&path);
+        let correction = &store.corrections()[0];
+        assert_eq!(correction.id, id);
+        assert_eq!(correction.right_answer.as_deref(), Some("Canberra"));
+
+        let mut store = CorrectionStore::default();
+        for i in 0..MAX_CORRECTIONS + 5 {
+            store.add(FeedbackSource::ThumbsDown, &format!("q{i}"), "a", None);
+        }
+        store.save(&path).expect("save");
+        let store = CorrectionStore::load(&path);
+        assert_eq!(store.corrections().len(), MAX_CORRECTIONS);
+        assert_eq!(store.corrections()[0].user_text, "q5");
+    }
+
+    #[test]
+    fn late_answers_are_not_attached() {
+        let mut store = CorrectionStore::default();
+        store.add(FeedbackSource::Voice, "q", "a", None);
+        let created = store.corrections()[0].created_at;
+        assert!(!store.answer_latest("late", created + ANSWER_WINDOW_SECS + 1));
+        assert!(store.answer_latest("on time", created + 10));
+    }
+
+    #[test]
+    fn prompt_section_lists_newest_first() {
+        let mut store = CorrectionStore::default();
+        assert_eq!(store.prompt_section(), None);
+        store.add(
+            FeedbackSource::Voice,
+            "capital of Australia?",
+            "Sydney.",
+            Some("Canberra"),
+        );
+        store.add(FeedbackSource::ThumbsDown, "2 + 2?", "5", None);
+        assert_eq!(
+            store.prompt_section().expect("section"),
+            "Past corrections (the user said these replies were wrong; don't repeat them):\n\
+             - Asked \"2 + 2?\", you said \"5\"\n\
+             - Asked \"capital of Australia?\", you said \"Sydney.\"; right answer: \"Canberra\""
+        );
+    }
+
+    #[test]
+    fn export_writes_one_json_object_per_line() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("export").join("corrections.jsonl");
+        let mut store = CorrectionStore::default();
+        store.add(FeedbackSource::Voice, "q1", "a1", Some("r1"));
+        store.add(FeedbackSource::ThumbsDown, "q2", "a2", None);
+
+        assert_eq!(store.export_jsonl(&path).expect("export"), 2);
+        let contents = std::fs::read_to_string(&path).expect("read");
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["source"], "voice");
+        assert_eq!(lines[0]["right_answer"], "r1");
+        assert_eq!(lines[1]["source"], "thumbs_down");
+        assert!(lines[1].get("right_answer").is_none());
+    }
+}
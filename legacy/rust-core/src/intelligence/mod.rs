@@ -16,15 +16,19 @@
 //! - **Briefing** (`briefing.rs`): Morning briefing builder and delivery
 //! - **Research** (`research.rs`): Background research scheduling
 //! - **Skill Proposals** (`skill_proposals.rs`): Adaptive skill detection
+//! - **Todos** (`todos.rs`): Rule-based todo capture and the persisted todo list
+//! - **Corrections** (`corrections.rs`): Replies the user said were wrong, and the right answers
 
 pub mod actions;
 pub mod briefing;
+pub mod corrections;
 pub mod extraction;
 pub mod extractor;
 pub mod noise;
 pub mod research;
 pub mod skill_proposals;
 pub mod store;
+pub mod todos;
 pub mod types;
 
 pub use actions::execute_actions;
@@ -32,6 +36,10 @@ pub use briefing::{
     Briefing, BriefingCategory, BriefingItem, BriefingPriority, build_briefing,
     format_briefing_for_prompt, is_briefing_trigger,
 };
+pub use corrections::{
+    Correction, CorrectionStore, FeedbackSource, answer_correction, detect_negative_feedback,
+    record_correction,
+};
 pub use extraction::parse_extraction_response;
 pub use extractor::IntelligenceExtractor;
 pub use noise::{DeliveryBlock, NoiseController};
@@ -45,6 +53,7 @@ pub use skill_proposals::{
     load_skill_opportunity_policy,
 };
 pub use store::{IntelligenceStore, RelationshipMeta};
+pub use todos::{TodoItem, TodoSource, TodoStore, capture_todos, extract_todos};
 pub use types::{
     ActionResult, ExtractionResult, IntelligenceAction, IntelligenceItem, IntelligenceKind,
 };
@@ -0,0 +1,637 @@
+//! Todo list extracted from conversations.
+//!
+//! After each turn the user's words are scanned for actionable commitments
+//! ("remind me to…", "I need to…", "add … to my todo list"). Each one becomes
+//! a [`TodoItem`] with an optional due date parsed from phrases such as
+//! "tomorrow" or "by Friday", persisted in `todos.json`. Items can optionally
+//! be mirrored to Apple Reminders, and the `list_todos` / `update_todo` tools
+//! make the list queryable by voice.
+//!
+//! Extraction is rule-based so it runs on every turn without an LLM call; it
+//! prefers missing a todo over inventing one.
+
+use std::ops::Range;
+use std::path::Path;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::fae_llm::tools::apple::reminders::{NewReminder, ReminderStore, ReminderStoreError};
+use crate::time_util::now_epoch_secs;
+
+/// Completed items kept for history; older ones are dropped on save.
+const MAX_COMPLETED_ITEMS: usize = 100;
+
+/// Phrases that introduce a commitment; the todo text follows.
+const COMMITMENT_PREFIXES: &[&str] = &[
+    "remind me to ",
+    "don't let me forget to ",
+    "do not let me forget to ",
+    "i need to remember to ",
+    "i have to remember to ",
+    "i must remember to ",
+    "i need to ",
+    "i have to ",
+    "i've got to ",
+    "i must ",
+];
+
+/// Verbs after "I need to" that express a wish for information rather than
+/// a task ("I need to know the time").
+const NON_TASK_VERBS: &[&str] = &[
+    "know",
+    "understand",
+    "find out",
+    "figure out",
+    "think",
+    "ask you",
+    "tell you",
+    "hear",
+    "see if",
+    "be ",
+    "go to sleep",
+    "sleep",
+    "relax",
+];
+
+/// Where a todo came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoSource {
+    /// Extracted from something the user said.
+    Conversation,
+    /// Added explicitly through the `update_todo` tool.
+    Manual,
+}
+
+/// One entry in the todo list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoItem {
+    /// Short numeric identifier, easy to say aloud.
+    pub id: u64,
+    /// What needs doing.
+    pub text: String,
+    /// Due date, if one was mentioned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    /// Timestamp when the item was added.
+    pub created_at: u64,
+    /// Timestamp when the item was completed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<u64>,
+    /// Where the item came from.
+    pub source: TodoSource,
+    /// Identifier of the mirrored Apple Reminders item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reminder_id: Option<String>,
+}
+
+impl TodoItem {
+    /// Whether the item is still open.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.completed_at.is_none()
+    }
+
+    /// One-line description, with the due date relative to `today`.
+    #[must_use]
+    pub fn describe(&self, today: NaiveDate) -> String {
+        let mut line = format!("#{} {}", self.id, self.text);
+        if let Some(due) = self.due {
+            line.push_str(&format!(" ({})", describe_due(due, today)));
+        }
+        if !self.is_open() {
+            line.push_str(" [done]");
+        }
+        line
+    }
+}
+
+fn describe_due(due: NaiveDate, today: NaiveDate) -> String {
+    match (due - today).num_days() {
+        d if d < 0 => format!("overdue since {}", due.format("%a %-d %b")),
+        0 => "due today".to_owned(),
+        1 => "due tomorrow".to_owned(),
+        2..=6 => format!("due {}", due.format("%A")),
+        _ => format!("due {}", due.format("%a %-d %b")),
+    }
+}
+
+/// A commitment found in user text, before it is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedTodo {
+    /// What needs doing.
+    pub text: String,
+    /// Due date, if one was mentioned.
+    pub due: Option<NaiveDate>,
+}
+
+/// Persisted todo list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TodoStore {
+    /// All items, open and completed.
+    #[serde(default)]
+    pub items: Vec<TodoItem>,
+    /// Next identifier to assign.
+    #[serde(default)]
+    pub next_id: u64,
+}
+
+impl TodoStore {
+    /// Load the list from `path`, returning an empty list on error.
+    pub fn load(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("failed to parse todo list: {e}");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!("failed to load todo list: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Save the list to `path`, dropping the oldest completed items beyond
+    /// the history limit.
+    pub fn save(&mut self, path: &Path) -> Result<(), String> {
+        let completed = self.items.iter().filter(|i| !i.is_open()).count();
+        if completed > MAX_COMPLETED_ITEMS {
+            let mut excess = completed - MAX_COMPLETED_ITEMS;
+            self.items.retain(|i| {
+                if excess > 0 && !i.is_open() {
+                    excess -= 1;
+                    return false;
+                }
+                true
+            });
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create todo dir: {e}"))?;
+        }
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| format!("serialize error: {e}"))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("write error: {e}"))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("write error: {e}"))
+    }
+
+    /// Add an item unless an open item with the same text exists.
+    ///
+    /// Returns the new item's identifier, or `None` if it was a duplicate.
+    pub fn add(&mut self, text: &str, due: Option<NaiveDate>, source: TodoSource) -> Option<u64> {
+        let key = normalize(text);
+        if key.is_empty() || self.open().any(|i| normalize(&i.text) == key) {
+            return None;
+        }
+        self.next_id = self.next_id.max(1);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.items.push(TodoItem {
+            id,
+            text: text.trim().to_owned(),
+            due,
+            created_at: now_epoch_secs(),
+            completed_at: None,
+            source,
+            reminder_id: None,
+        });
+        Some(id)
+    }
+
+    /// Open items in insertion order.
+    pub fn open(&self) -> impl Iterator<Item = &TodoItem> {
+        self.items.iter().filter(|i| i.is_open())
+    }
+
+    /// Open items, soonest due first; undated items last.
+    #[must_use]
+    pub fn open_by_due(&self) -> Vec<&TodoItem> {
+        let mut items: Vec<&TodoItem> = self.open().collect();
+        items.sort_by_key(|i| (i.due.is_none(), i.due, i.id));
+        items
+    }
+
+    /// Find the open item matching `selector`: its id (`3` or `#3`) or a
+    /// phrase contained in exactly one open item.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when nothing or more than one item matches.
+    pub fn find_open(&self, selector: &str) -> Result<u64, String> {
+        let selector = selector.trim();
+        if let Ok(id) = selector.trim_start_matches('#').parse::<u64>() {
+            return self
+                .open()
+                .find(|i| i.id == id)
+                .map(|i| i.id)
+                .ok_or_else(|| format!("no open todo #{id}"));
+        }
+        let needle = normalize(selector);
+        let matches: Vec<&TodoItem> = self
+            .open()
+            .filter(|i| normalize(&i.text).contains(&needle))
+            .collect();
+        match matches.as_slice() {
+            [] => Err(format!("no open todo matches \"{selector}\"")),
+            [one] => Ok(one.id),
+            many => Err(format!(
+                "\"{selector}\" matches {} todos; use an id: {}",
+                many.len(),
+                many.iter()
+                    .map(|i| format!("#{}", i.id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// Mark item `id` as done.
+    pub fn complete(&mut self, id: u64) -> Option<&TodoItem> {
+        let item = self.items.iter_mut().find(|i| i.id == id && i.is_open())?;
+        item.completed_at = Some(now_epoch_secs());
+        Some(item)
+    }
+
+    /// Delete item `id`.
+    pub fn remove(&mut self, id: u64) -> Option<TodoItem> {
+        let pos = self.items.iter().position(|i| i.id == id)?;
+        Some(self.items.remove(pos))
+    }
+
+    /// Mutable access to item `id`.
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut TodoItem> {
+        self.items.iter_mut().find(|i| i.id == id)
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c.is_ascii_punctuation())
+        .to_lowercase()
+}
+
+/// Find commitments in `user_text`, resolving relative due dates against
+/// `today`.
+#[must_use]
+pub fn extract_todos(user_text: &str, today: NaiveDate) -> Vec<ExtractedTodo> {
+    let mut todos = Vec::new();
+    for sentence in user_text.split_inclusive(['.', '!', '?', '\n']) {
+        let sentence = sentence.trim();
+        // Questions ("do I need to…?") are not commitments.
+        if sentence.ends_with('?') {
+            continue;
+        }
+        let lower = sentence.to_lowercase();
+        let Some(range) = commitment_range(&lower) else {
+            continue;
+        };
+        // Keep the user's casing when lowercasing did not shift byte offsets.
+        let source = if lower.len() == sentence.len() {
+            sentence
+        } else {
+            lower.as_str()
+        };
+        let Some(found) = source.get(range) else {
+            continue;
+        };
+        let (text, due) = split_due(found.trim_end_matches(['.', '!']), today);
+        let text = clean_todo_text(&text);
+        if text.split_whitespace().count() >= 2 || (!text.is_empty() && due.is_some()) {
+            todos.push(ExtractedTodo { text, due });
+        }
+    }
+    todos
+}
+
+/// Byte range of the todo text within a lowercased sentence.
+fn commitment_range(lower: &str) -> Option<Range<usize>> {
+    // "add X to my todo list" / "put X on my to-do list".
+    for verb in ["add ", "put "] {
+        if let Some(pos) = find_word(lower, verb.trim_end()) {
+            let start = pos + verb.len();
+            let after = lower.get(start..)?;
+            for suffix in [
+                " to my todo list",
+                " to my to-do list",
+                " to my to do list",
+                " on my todo list",
+                " on my to-do list",
+                " on my to do list",
+            ] {
+                if let Some(end) = after.find(suffix) {
+                    return Some(start..start + end);
+                }
+            }
+        }
+    }
+
+    for prefix in COMMITMENT_PREFIXES {
+        let Some(pos) = lower.find(prefix) else {
+            continue;
+        };
+        // Only at the start of the sentence or after a clause boundary, so
+        // "what do I need to…" or "you need to…" do not match.
+        let before = lower[..pos].trim_end();
+        let last_word = before.rsplit(' ').next().unwrap_or_default();
+        let at_boundary = before.is_empty()
+            || before.ends_with(',')
+            || matches!(
+                last_word,
+                "and" | "also" | "oh" | "so" | "but" | "fae" | "please" | "ok" | "okay"
+            );
+        if !at_boundary {
+            continue;
+        }
+        let start = pos + prefix.len();
+        let rest = &lower[start..];
+        if !prefix.starts_with("remind") && NON_TASK_VERBS.iter().any(|v| rest.starts_with(v)) {
+            return None;
+        }
+        return Some(start..lower.len());
+    }
+    None
+}
+
+/// Split a trailing or leading due-date phrase from `text`.
+fn split_due(text: &str, today: NaiveDate) -> (String, Option<NaiveDate>) {
+    let lower = text.to_lowercase();
+    let mut phrases: Vec<(String, NaiveDate)> = vec![
+        ("today".to_owned(), today),
+        ("tonight".to_owned(), today),
+        ("this evening".to_owned(), today),
+        ("tomorrow".to_owned(), today + Duration::days(1)),
+        ("next week".to_owned(), next_weekday(today, Weekday::Mon)),
+        (
+            "this weekend".to_owned(),
+            upcoming_weekday(today, Weekday::Sat),
+        ),
+        (
+            "end of the week".to_owned(),
+            upcoming_weekday(today, Weekday::Fri),
+        ),
+    ];
+    for day in [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ] {
+        let name = weekday_name(day);
+        phrases.push((format!("next {name}"), next_weekday(today, day)));
+        phrases.push((name.to_owned(), upcoming_weekday(today, day)));
+    }
+
+    for (phrase, date) in phrases {
+        let Some(pos) = find_word(&lower, &phrase) else {
+            continue;
+        };
+        let mut start = pos;
+        for lead in ["by the ", "by ", "on ", "before ", "until ", "the "] {
+            if lower[..start].ends_with(lead) {
+                start -= lead.len();
+                break;
+            }
+        }
+        let remaining = format!("{} {}", &text[..start], &text[pos + phrase.len()..]);
+        return (remaining, Some(date));
+    }
+    (text.to_owned(), None)
+}
+
+/// Byte offset of `phrase` in `haystack` as a whole word.
+fn find_word(haystack: &str, phrase: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(offset) = haystack[from..].find(phrase) {
+        let pos = from + offset;
+        let end = pos + phrase.len();
+        let before_ok = haystack[..pos]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric());
+        let after_ok = haystack[end..]
+            .chars()
+            .next()
+            .is_none_or(|c| !c.is_alphanumeric());
+        if before_ok && after_ok {
+            return Some(pos);
+        }
+        from = end;
+    }
+    None
+}
+
+fn clean_todo_text(text: &str) -> String {
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    while words
+        .last()
+        .is_some_and(|w| matches!(w.to_lowercase().as_str(), "please" | "for" | "by" | "on"))
+    {
+        words.pop();
+    }
+    let joined = words
+        .join(" ")
+        .trim_matches(|c: char| c == ',' || c == '.' || c == ';')
+        .to_owned();
+    let mut chars = joined.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// The next `day` on or after tomorrow.
+fn upcoming_weekday(today: NaiveDate, day: Weekday) -> NaiveDate {
+    let ahead = (day.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    today + Duration::days(if ahead == 0 { 7 } else { i64::from(ahead) })
+}
+
+/// `day` in the following calendar week.
+fn next_weekday(today: NaiveDate, day: Weekday) -> NaiveDate {
+    let monday_next_week =
+        today + Duration::days(i64::from(7 - today.weekday().num_days_from_monday()));
+    monday_next_week + Duration::days(i64::from(day.num_days_from_monday()))
+}
+
+/// Mirror `item` to Apple Reminders and remember the reminder identifier.
+///
+/// # Errors
+///
+/// Returns the reminder store error, e.g. when permission is not granted.
+pub fn sync_to_reminders(
+    item: &mut TodoItem,
+    reminders: &dyn ReminderStore,
+) -> Result<(), ReminderStoreError> {
+    if item.reminder_id.is_some() {
+        return Ok(());
+    }
+    let reminder = reminders.create_reminder(&NewReminder {
+        title: item.text.clone(),
+        list_id: None,
+        notes: Some("Added by Fae from your todo list.".to_owned()),
+        due_date: item.due.map(|d| format!("{d}T09:00:00")),
+        priority: None,
+        recurrence: None,
+    })?;
+    item.reminder_id = Some(reminder.identifier);
+    Ok(())
+}
+
+/// Extract todos from a user turn and add them to the list at `path`.
+///
+/// Best effort: failures are logged. Returns the texts of the items added.
+pub fn capture_todos(
+    user_text: &str,
+    path: &Path,
+    reminders: Option<&dyn ReminderStore>,
+) -> Vec<String> {
+    let today = chrono::Local::now().date_naive();
+    let extracted = extract_todos(user_text, today);
+    if extracted.is_empty() {
+        return Vec::new();
+    }
+
+    let mut store = TodoStore::load(path);
+    let mut added = Vec::new();
+    for todo in extracted {
+        let Some(id) = store.add(&todo.text, todo.due, TodoSource::Conversation) else {
+            debug!(text = %todo.text, "todo already on the list");
+            continue;
+        };
+        if let (Some(reminders), Some(item)) = (reminders, store.get_mut(id))
+            && let Err(e) = sync_to_reminders(item, reminders)
+        {
+            warn!("failed to mirror todo #{id} to Reminders: {e}");
+        }
+        added.push(todo.text);
+    }
+    if !added.is_empty() {
+        if let Err(e) = store.save(path) {
+            warn!("failed to save todo list: {e}");
+            return Vec::new();
+        }
+        info!(count = added.len(), "captured todos from conversation");
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    /// A Wednesday.
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 11).unwrap()
+    }
+
+    fn date(m: u32, d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2026, m, d)
+    }
+
+    #[test]
+    fn extracts_commitments_with_due_dates() {
+        let todos = extract_todos(
+            "Remind me to call the dentist tomorrow. Also I need to renew my passport by Friday!",
+            today(),
+        );
+        assert_eq!(
+            todos,
+            vec![
+                ExtractedTodo {
+                    text: "Call the dentist".to_owned(),
+                    due: date(3, 12),
+                },
+                ExtractedTodo {
+                    text: "Renew my passport".to_owned(),
+                    due: date(3, 13),
+                },
+            ]
+        );
+
+        let todos = extract_todos("Add oat milk to my to-do list", today());
+        assert_eq!(todos[0].text, "Oat milk");
+        assert_eq!(todos[0].due, None);
+
+        let todos = extract_todos("I have to send the report next Monday.", today());
+        assert_eq!(todos[0].due, date(3, 16));
+    }
+
+    #[test]
+    fn ignores_questions_and_information_requests() {
+        assert!(extract_todos("Do I need to bring an umbrella today?", today()).is_empty());
+        assert!(extract_todos("I need to know what time it is.", today()).is_empty());
+        assert!(extract_todos("What do I need to pack.", today()).is_empty());
+        assert!(extract_todos("You need to speak up.", today()).is_empty());
+    }
+
+    #[test]
+    fn store_dedups_and_finds_items() {
+        let mut store = TodoStore::default();
+        let id = store
+            .add("Call the dentist", date(3, 12), TodoSource::Conversation)
+            .unwrap();
+        assert!(
+            store
+                .add("call the dentist.", None, TodoSource::Manual)
+                .is_none()
+        );
+        store
+            .add("Renew passport", None, TodoSource::Manual)
+            .unwrap();
+
+        assert_eq!(store.find_open("dentist"), Ok(id));
+        assert_eq!(store.find_open("#1"), Ok(id));
+        assert!(store.find_open("e").is_err());
+
+        store.complete(id).unwrap();
+        assert!(store.find_open("dentist").is_err());
+        assert_eq!(store.open_by_due().len(), 1);
+        assert_eq!(
+            store.items[0].describe(today()),
+            "#1 Call the dentist (due tomorrow) [done]"
+        );
+    }
+
+    #[test]
+    fn capture_persists_and_mirrors_to_reminders() {
+        use crate::fae_llm::tools::apple::mock_stores::MockReminderStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("todos.json");
+        let reminders = MockReminderStore::new(Vec::new(), Vec::new());
+
+        let added = capture_todos("Remind me to water the plants.", &path, Some(&reminders));
+        assert_eq!(added, vec!["Water the plants".to_owned()]);
+        // Saying it again does not duplicate it.
+        assert!(capture_todos("Remind me to water the plants.", &path, None).is_empty());
+
+        let store = TodoStore::load(&path);
+        assert_eq!(store.items.len(), 1);
+        assert!(store.items[0].reminder_id.is_some());
+    }
+}
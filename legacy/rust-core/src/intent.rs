@@ -35,6 +35,10 @@ pub(crate) const CALENDAR_KEYWORDS: &[&str] = &[
     "when is my",
     "book a meeting",
     "block time",
+    "find time",
+    "minutes with",
+    "when am i free",
+    "my availability",
 ];
 
 pub(crate) const REMINDERS_KEYWORDS: &[&str] = &[
@@ -55,10 +59,81 @@ pub(crate) const NOTES_KEYWORDS: &[&str] = &[
     "create a note",
     "save a note",
     "notes app",
+    "note about",
+    "notes about",
+    "that note",
 ];
 
 pub(crate) const MAIL_KEYWORDS: &[&str] = &["mail", "email", "inbox"];
 
+pub(crate) const MEDIA_KEYWORDS: &[&str] = &[
+    "music",
+    "song",
+    "playlist",
+    "play some",
+    "play my",
+    "what's playing",
+    "what is playing",
+    "next track",
+    "previous track",
+    "skip this",
+    "pause playback",
+    "resume playback",
+];
+
+pub(crate) const SYSTEM_SETTINGS_KEYWORDS: &[&str] = &[
+    "volume",
+    "louder",
+    "quieter",
+    "brightness",
+    "brighter",
+    "dimmer",
+    "dark mode",
+    "light mode",
+    "do not disturb",
+    "do-not-disturb",
+    "focus mode",
+    "wifi",
+    "wi-fi",
+];
+
+pub(crate) const SYSTEM_INFO_KEYWORDS: &[&str] = &[
+    "system info",
+    "cpu",
+    "memory usage",
+    "disk space",
+    "disk usage",
+    "battery",
+    "uptime",
+    "the fan",
+    "laptop fan",
+    "fan noise",
+    "overheating",
+    "running hot",
+    "running slow",
+    "so slow",
+    "activity monitor",
+    "top processes",
+];
+
+pub(crate) const NETWORK_DIAG_KEYWORDS: &[&str] = &[
+    "internet down",
+    "internet is down",
+    "internet working",
+    "no internet",
+    "internet slow",
+    "internet is slow",
+    "slow internet",
+    "internet speed",
+    "speed test",
+    "can't connect",
+    "cannot connect",
+    "connection problem",
+    "captive portal",
+    "ping the",
+    "dns",
+];
+
 pub(crate) const CONTACTS_KEYWORDS: &[&str] =
     &["contact", "contacts", "phone number", "address book"];
 
@@ -159,6 +234,189 @@ pub(crate) const FILE_KEYWORDS: &[&str] = &[
     "in this project",
 ];
 
+/// Keywords asking the user to point at a file, answered by `pick_file`.
+pub(crate) const PICK_FILE_KEYWORDS: &[&str] = &[
+    "this file",
+    "a file",
+    "my file",
+    "that document",
+    "this document",
+    "which folder",
+    "a folder",
+    "choose a file",
+    "pick a file",
+    "select a file",
+];
+
+/// Keywords asking for a native notification or the share sheet.
+pub(crate) const NOTIFY_KEYWORDS: &[&str] = &["notify me", "notification", "send me an alert"];
+
+pub(crate) const SHARE_KEYWORDS: &[&str] =
+    &["share this", "share it", "share that", "send this to"];
+
+/// Keywords indicating a code navigation question answered by the `lsp` tool.
+pub(crate) const CODE_NAVIGATION_KEYWORDS: &[&str] = &[
+    "where is this function",
+    "where is the function",
+    "where is it defined",
+    "defined",
+    "definition of",
+    "references to",
+    "usages of",
+    "find usages",
+    "call sites",
+    "callers of",
+    "compile errors",
+    "compiler errors",
+    "type errors",
+    "diagnostics",
+    "symbols in",
+];
+
+/// Keywords about the user's todo list.
+pub(crate) const TODO_KEYWORDS: &[&str] = &[
+    "todo",
+    "to-do",
+    "to do list",
+    "my tasks",
+    "what do i need to do",
+    "what do i have to do",
+    "what's left to do",
+    "mark it done",
+    "mark as done",
+    "cross off",
+];
+
+/// Keywords stating or asking about standing preferences, handled by
+/// `get_preference` / `set_preference`.
+pub(crate) const PREFERENCE_KEYWORDS: &[&str] = &[
+    "prefer",
+    "timezone",
+    "time zone",
+    "metric",
+    "imperial",
+    "celsius",
+    "fahrenheit",
+    "locale",
+    "i live in",
+    "my home city",
+    "my hometown",
+    "pronounce",
+    "pronunciation",
+    "is said",
+    "default calendar",
+    "shorter answers",
+    "shorter replies",
+    "more detail",
+    "less detail",
+    "be brief",
+    "more concise",
+];
+
+/// Keywords asking to revert file changes made by the write/edit/file_organize tools.
+pub(crate) const UNDO_KEYWORDS: &[&str] = &[
+    "undo",
+    "revert that",
+    "revert the last",
+    "roll back the",
+    "rollback the",
+    "put it back",
+    "change it back",
+];
+
+/// Keywords asking Fae to keep standing instructions, handled by `create_skill`.
+pub(crate) const SKILL_AUTHORING_KEYWORDS: &[&str] = &[
+    "as a skill",
+    "new skill",
+    "create a skill",
+    "make a skill",
+    "learn this",
+    "learn that",
+    "learn how to",
+    "teach you",
+    "whenever i ask",
+    "from now on",
+];
+
+/// Keywords asking to tidy folders in bulk, handled by `file_organize`.
+pub(crate) const FILE_ORGANIZE_KEYWORDS: &[&str] = &[
+    "clean up my downloads",
+    "clean up my desktop",
+    "tidy up my",
+    "organize my files",
+    "organise my files",
+    "organize my downloads",
+    "organise my downloads",
+    "sort my files",
+    "move these files",
+    "rename these files",
+    "rename all the",
+    "into a folder",
+    "into folders",
+];
+
+/// Keywords about calling REST services, handled by `http_request`.
+pub(crate) const HTTP_REQUEST_KEYWORDS: &[&str] = &[
+    "api call",
+    "call the api",
+    "call my api",
+    "endpoint",
+    "webhook",
+    "rest api",
+    "http request",
+    "post request",
+    "get request",
+    "put request",
+    "delete request",
+    "post this",
+    "curl",
+];
+
+/// Keywords about zip and tar archives, handled by `list_archive` and `archive`.
+pub(crate) const ARCHIVE_KEYWORDS: &[&str] = &[
+    "unzip",
+    "zip file",
+    "zip up",
+    "zip it",
+    "zip this",
+    "zip that",
+    ".zip",
+    "tarball",
+    ".tar",
+    ".tgz",
+    "archive",
+    "compress",
+    "decompress",
+];
+
+/// Keywords about recordings to turn into text, handled by `transcribe_file`.
+pub(crate) const TRANSCRIBE_KEYWORDS: &[&str] = &[
+    "transcribe",
+    "transcript",
+    "transcription",
+    "voice memo",
+    "voice note",
+    "recording",
+    ".m4a",
+    ".mp3",
+    ".wav",
+];
+
+/// Keywords about saving text as audio, handled by `synthesize_to_file`.
+pub(crate) const SYNTHESIZE_KEYWORDS: &[&str] = &[
+    "audio file",
+    "as audio",
+    "as an mp3",
+    "as mp3",
+    "as a wav",
+    "audiobook",
+    "listen to later",
+    "listen to it later",
+    "for my run",
+    "for my commute",
+    "text to speech",
+];
+
 pub(crate) const X0X_KEYWORDS: &[&str] = &[
     "x0x",
     "x0x network",
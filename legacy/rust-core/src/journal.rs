@@ -0,0 +1,215 @@
+//! Conversation journal: a short summary of each conversation, appended to
+//! a daily note.
+//!
+//! Opt-in through `[journal]` in `config.toml`. When a conversation ends
+//! after silence, the coordinator builds a digest of its turns and hands it
+//! to [`record_conversation`], which masks personal data at the configured
+//! `pii_masking` level and appends the entry to today's note — a Markdown
+//! file (`2026-10-16.md`) in the journal folder, or an Apple Note titled
+//! "Fae Journal 2026-10-16" through the Notes bridge.
+//!
+//! Saying "off the record" or "don't journal this" keeps the current
+//! conversation out of the journal (see [`is_opt_out`]).
+
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDate, NaiveTime};
+use tracing::{info, warn};
+
+use crate::config::{JournalConfig, JournalDestination};
+use crate::fae_llm::providers::PiiMasker;
+use crate::fae_llm::tools::apple::notes::{NewNote, NoteQuery, NoteStore};
+
+/// Phrases that keep the current conversation out of the journal.
+const OPT_OUT_PHRASES: &[&str] = &[
+    "off the record",
+    "don't journal this",
+    "do not journal this",
+    "don't put this in the journal",
+    "don't put this in my journal",
+    "don't add this to the journal",
+    "don't add this to my journal",
+    "keep this out of the journal",
+    "keep this out of my journal",
+    "leave this out of the journal",
+    "leave this out of my journal",
+];
+
+/// Whether `user_text` asks to keep this conversation out of the journal.
+pub fn is_opt_out(user_text: &str) -> bool {
+    let text = user_text.to_lowercase().replace('\u{2019}', "'");
+    OPT_OUT_PHRASES.iter().any(|phrase| text.contains(phrase))
+}
+
+/// Title of the daily note for `date`.
+pub fn note_title(date: NaiveDate) -> String {
+    format!("Fae Journal {}", date.format("%Y-%m-%d"))
+}
+
+/// Journal entry for a conversation of `turns` turns that ended at `time`,
+/// with personal data masked.
+pub fn format_entry(config: &JournalConfig, time: NaiveTime, turns: usize, digest: &str) -> String {
+    let noun = if turns == 1 { "turn" } else { "turns" };
+    let body = PiiMasker::new(config.pii_masking).mask(digest.trim());
+    format!("## {} · {turns} {noun}\n\n{body}\n", time.format("%H:%M"))
+}
+
+/// Append `entry` to the Markdown note for `date` in `folder`, creating the
+/// note with a heading if needed. Returns the note's path.
+pub fn append_markdown(folder: &Path, date: NaiveDate, entry: &str) -> Result<PathBuf, String> {
+    use std::io::Write as _;
+
+    std::fs::create_dir_all(folder).map_err(|e| format!("failed to create journal folder: {e}"))?;
+    let path = folder.join(format!("{}.md", date.format("%Y-%m-%d")));
+    let is_new = !path.exists();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("failed to open {}: {e}", path.display()))?;
+    let text = if is_new {
+        format!("# {}\n\n{entry}", note_title(date))
+    } else {
+        format!("\n{entry}")
+    };
+    file.write_all(text.as_bytes())
+        .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+/// Append `entry` to the Apple Note for `date`, creating the note if needed.
+pub fn append_apple_note(
+    store: &dyn NoteStore,
+    date: NaiveDate,
+    entry: &str,
+) -> Result<(), String> {
+    let title = note_title(date);
+    let query = NoteQuery {
+        folder: None,
+        search: Some(title.clone()),
+        limit: 10,
+    };
+    let existing = store
+        .list_notes(&query)
+        .map_err(|e| format!("failed to look up journal note: {e}"))?
+        .into_iter()
+        .find(|note| note.title == title);
+    match existing {
+        Some(note) => store.append_to_note(&note.identifier, entry).map(|_| ()),
+        None => store
+            .create_note(&NewNote {
+                title,
+                body: entry.to_owned(),
+                folder: None,
+            })
+            .map(|_| ()),
+    }
+    .map_err(|e| format!("failed to write journal note: {e}"))
+}
+
+/// Write the journal entry for a conversation that just ended.
+///
+/// Best effort: failures are logged. `notes` is the Notes bridge, used when
+/// the destination is Apple Notes.
+pub fn record_conversation(
+    config: &JournalConfig,
+    turns: usize,
+    digest: &str,
+    notes: Option<&dyn NoteStore>,
+) {
+    let now = Local::now();
+    let entry = format_entry(config, now.time(), turns, digest);
+    let result = match config.destination {
+        JournalDestination::Markdown => {
+            let folder = config
+                .folder
+                .clone()
+                .unwrap_or_else(crate::fae_dirs::journal_dir);
+            append_markdown(&folder, now.date_naive(), &entry).map(|_| ())
+        }
+        JournalDestination::AppleNotes => match notes {
+            Some(store) => append_apple_note(store, now.date_naive(), &entry),
+            None => Err("Apple Notes access is not granted".to_owned()),
+        },
+    };
+    match result {
+        Ok(()) => info!(turns, destination = ?config.destination, "journal entry written"),
+        Err(e) => warn!("failed to write journal entry: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::providers::PiiMaskingLevel;
+    use crate::fae_llm::tools::apple::mock_stores::MockNoteStore;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 16).expect("date")
+    }
+
+    fn config() -> JournalConfig {
+        JournalConfig {
+            enabled: true,
+            pii_masking: PiiMaskingLevel::Contact,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn opt_out_phrases_are_detected() {
+        assert!(is_opt_out("This is off the record, okay?"));
+        assert!(is_opt_out("Don\u{2019}t journal this."));
+        assert!(!is_opt_out("What did I write in my journal yesterday?"));
+    }
+
+    #[test]
+    fn entries_are_masked() {
+        let time = NaiveTime::from_hms_opt(14, 32, 0).expect("time");
+        let entry = format_entry(
+            &config(),
+            time,
+            1,
+            "- Email sam@example.com about Friday → Done.",
+        );
+        assert!(
+            entry.starts_with("## 14:32 · 1 turn\n\n- Email "),
+            "{entry}"
+        );
+        assert!(!entry.contains("sam@example.com"), "{entry}");
+    }
+
+    #[test]
+    fn markdown_note_gets_a_heading_once() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let folder = dir.path().join("journal");
+        let path =
+            append_markdown(&folder, date(), "## 09:00 · 1 turn\n\nfirst\n").expect("append");
+        append_markdown(&folder, date(), "## 10:00 · 2 turns\n\nsecond\n").expect("append");
+
+        assert_eq!(path, folder.join("2026-10-16.md"));
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read"),
+            "# Fae Journal 2026-10-16\n\n## 09:00 · 1 turn\n\nfirst\n\n## 10:00 · 2 turns\n\nsecond\n"
+        );
+    }
+
+    #[test]
+    fn apple_note_is_created_then_appended() {
+        let store = MockNoteStore::new(Vec::new());
+        append_apple_note(&store, date(), "first\n").expect("create");
+        append_apple_note(&store, date(), "second\n").expect("append");
+
+        let notes = store
+            .list_notes(&NoteQuery {
+                folder: None,
+                search: Some(note_title(date())),
+                limit: 10,
+            })
+            .expect("list");
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].body, "first\n\nsecond\n");
+    }
+}
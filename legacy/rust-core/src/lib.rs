@@ -23,39 +23,52 @@ compile_error!(
      This is a one-time ~700 MB download."
 );
 
+pub mod accessibility;
 pub mod agent;
+pub mod analytics;
 pub mod approval;
 pub mod audio;
+pub(crate) mod audit_log;
 
 // C ABI surface for embedding in native shells (Swift, Obj-C, etc.).
 pub mod canvas;
+pub mod captions;
 pub mod channels;
 pub mod config;
+pub mod content_filter;
 pub mod credentials;
 pub mod diagnostics;
 pub mod doctor;
 pub mod error;
+pub mod experiments;
 pub mod fae_dirs;
 pub mod fae_llm;
 pub mod ffi;
+pub mod handoff;
 pub mod host;
 pub mod huggingface;
+pub mod i18n;
 pub mod intelligence;
 pub(crate) mod intent;
+pub mod journal;
 pub mod kernel_signature;
 pub mod linker_anchor;
 pub mod llm;
+pub mod meeting;
 pub mod memory;
 pub mod memory_pressure;
 pub mod model_integrity;
 pub mod model_tier;
 pub mod models;
 pub mod mutation_manifest;
+pub mod offline;
 pub mod onboarding;
+pub mod permission_usage;
 pub mod permissions;
 pub mod personality;
 pub mod pipeline;
 pub mod platform;
+pub mod privacy;
 pub mod progress;
 pub mod runtime;
 pub mod runtime_audit;
@@ -65,10 +78,13 @@ pub mod skills;
 pub mod soul_version;
 pub mod startup;
 pub mod stt;
+pub mod sync;
 pub mod system_profile;
 pub mod theme;
 pub(crate) mod time_util;
+pub mod transcript_stream;
 pub mod tts;
+pub mod turn_context;
 pub mod ui;
 pub mod update;
 pub mod vad;
@@ -86,6 +102,6 @@ pub use error::{Result, SpeechError};
 pub use permissions::{PermissionKind, PermissionStore};
 pub use pipeline::coordinator::{PipelineCoordinator, PipelineMode};
 pub use pipeline::messages::GateCommand;
-pub use progress::{ProgressCallback, ProgressEvent};
+pub use progress::{ProgressCallback, ProgressEvent, ProgressTree};
 pub use runtime::RuntimeEvent;
 pub use startup::InitializedModels;
@@ -0,0 +1,220 @@
+//! Image downscaling to a pixel and byte budget.
+//!
+//! A camera capture or screenshot is far larger than a vision encoder
+//! needs: a 12-megapixel photo costs thousands of image tokens locally,
+//! megabytes per request remotely, and is stored with the session. Every
+//! image is fitted to `[llm.images]` before the model sees it:
+//!
+//! 1. Scaled down (never up), keeping its aspect ratio, to at most
+//!    `max_pixels` pixels.
+//! 2. Encoded as JPEG at `jpeg_quality`. While the result is over
+//!    `max_bytes`, the quality is lowered in steps down to
+//!    [`MIN_JPEG_QUALITY`], then the image is scaled down further.
+//!
+//! The resulting JPEG is what every consumer uses: the local encoder gets it
+//! decoded, remote providers and saved sessions get the bytes.
+
+use std::io::Cursor;
+
+use image::DynamicImage;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+
+use crate::config::ImageBudgetConfig;
+use crate::error::{Result, SpeechError};
+use crate::fae_llm::providers::message::ImageAttachment;
+
+/// Lowest JPEG quality used to meet the byte budget.
+pub const MIN_JPEG_QUALITY: u8 = 40;
+
+/// Quality reduction per re-encode.
+const QUALITY_STEP: u8 = 15;
+
+/// Images are not shrunk below this many pixels on their longer side.
+const MIN_SIDE: u32 = 64;
+
+/// An image fitted to the budget, as JPEG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetedImage {
+    pub jpeg: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl BudgetedImage {
+    /// Decode the JPEG for the local vision encoder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes do not decode.
+    pub fn decode(&self) -> Result<DynamicImage> {
+        image::load_from_memory(&self.jpeg)
+            .map_err(|e| SpeechError::Llm(format!("failed to decode image: {e}")))
+    }
+
+    /// The image as a message attachment.
+    pub fn to_attachment(&self) -> ImageAttachment {
+        ImageAttachment::jpeg(&self.jpeg, self.width, self.height)
+    }
+}
+
+/// Fit `image` to `budget`.
+///
+/// When the byte budget cannot be met even at [`MIN_JPEG_QUALITY`] and the
+/// smallest size, the smallest encoding is returned.
+///
+/// # Errors
+///
+/// Returns an error if JPEG encoding fails.
+pub fn fit_to_budget(image: &DynamicImage, budget: &ImageBudgetConfig) -> Result<BudgetedImage> {
+    let (mut width, mut height) =
+        scaled_dimensions(image.width(), image.height(), budget.max_pixels);
+    let start_quality = budget.jpeg_quality.clamp(MIN_JPEG_QUALITY, 100);
+    loop {
+        let scaled = if (width, height) == (image.width(), image.height()) {
+            image.clone()
+        } else {
+            image.resize_exact(width, height, FilterType::Triangle)
+        };
+        let mut quality = start_quality;
+        let jpeg = loop {
+            let jpeg = encode_jpeg(&scaled, quality)?;
+            if budget.max_bytes == 0 || jpeg.len() <= budget.max_bytes {
+                return Ok(BudgetedImage {
+                    jpeg,
+                    width,
+                    height,
+                });
+            }
+            if quality == MIN_JPEG_QUALITY {
+                break jpeg;
+            }
+            quality = quality.saturating_sub(QUALITY_STEP).max(MIN_JPEG_QUALITY);
+        };
+        if width.max(height) <= MIN_SIDE {
+            return Ok(BudgetedImage {
+                jpeg,
+                width,
+                height,
+            });
+        }
+        width = (width * 3 / 4).max(1);
+        height = (height * 3 / 4).max(1);
+    }
+}
+
+/// Decode encoded images (PNG, JPEG, ...) attached to a turn and fit each
+/// to `budget`, keeping at most `max_per_turn`.
+///
+/// # Errors
+///
+/// Returns an error if an image does not decode or cannot be re-encoded.
+pub fn fit_attachments(
+    encoded: &[Vec<u8>],
+    budget: &ImageBudgetConfig,
+) -> Result<Vec<ImageAttachment>> {
+    encoded
+        .iter()
+        .take(budget.max_per_turn)
+        .map(|bytes| {
+            let image = image::load_from_memory(bytes)
+                .map_err(|e| SpeechError::Llm(format!("failed to decode image: {e}")))?;
+            Ok(fit_to_budget(&image, budget)?.to_attachment())
+        })
+        .collect()
+}
+
+/// Dimensions of a `width` × `height` image scaled down to at most
+/// `max_pixels` pixels; `0` means no limit.
+pub fn scaled_dimensions(width: u32, height: u32, max_pixels: u32) -> (u32, u32) {
+    let pixels = u64::from(width) * u64::from(height);
+    if max_pixels == 0 || pixels <= u64::from(max_pixels) {
+        return (width, height);
+    }
+    let scale = (f64::from(max_pixels) / pixels as f64).sqrt();
+    let scaled = |side: u32| ((f64::from(side) * scale).floor() as u32).max(1);
+    (scaled(width), scaled(height))
+}
+
+fn encode_jpeg(image: &DynamicImage, quality: u8) -> Result<Vec<u8>> {
+    let mut jpeg = Cursor::new(Vec::new());
+    JpegEncoder::new_with_quality(&mut jpeg, quality)
+        .encode_image(&image.to_rgb8())
+        .map_err(|e| SpeechError::Llm(format!("failed to encode image: {e}")))?;
+    Ok(jpeg.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn noise(width: u32, height: u32) -> DynamicImage {
+        let mut seed = 0x2545_f491_u32;
+        DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |_, _| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            let [r, g, b, _] = seed.to_le_bytes();
+            image::Rgb([r, g, b])
+        }))
+    }
+
+    #[test]
+    fn large_images_are_scaled_to_the_pixel_budget() {
+        assert_eq!(scaled_dimensions(4000, 3000, 1_200_000), (1264, 948));
+        assert_eq!(scaled_dimensions(640, 480, 1_200_000), (640, 480));
+        assert_eq!(scaled_dimensions(4000, 3000, 0), (4000, 3000));
+
+        let budget = ImageBudgetConfig {
+            max_pixels: 100 * 75,
+            max_bytes: 0,
+            ..Default::default()
+        };
+        let fitted = fit_to_budget(&noise(400, 300), &budget).expect("fit");
+        assert_eq!((fitted.width, fitted.height), (100, 75));
+        let decoded = fitted.decode().expect("decode");
+        assert_eq!((decoded.width(), decoded.height()), (100, 75));
+    }
+
+    #[test]
+    fn byte_budget_lowers_quality_then_size() {
+        let image = noise(320, 240);
+        let unlimited = ImageBudgetConfig {
+            max_bytes: 0,
+            ..Default::default()
+        };
+        let full = fit_to_budget(&image, &unlimited).expect("fit");
+
+        let budget = ImageBudgetConfig {
+            max_bytes: full.jpeg.len() / 4,
+            ..Default::default()
+        };
+        let fitted = fit_to_budget(&image, &budget).expect("fit");
+        assert!(fitted.jpeg.len() <= budget.max_bytes);
+        assert!(fitted.width < 320, "noise only fits after shrinking");
+    }
+
+    #[test]
+    fn attachments_are_fitted_and_capped_per_turn() {
+        let png = {
+            let mut png = Cursor::new(Vec::new());
+            noise(200, 100)
+                .write_to(&mut png, image::ImageFormat::Png)
+                .unwrap();
+            png.into_inner()
+        };
+        let budget = ImageBudgetConfig {
+            max_pixels: 50 * 25,
+            max_per_turn: 2,
+            ..Default::default()
+        };
+        let attachments = fit_attachments(&[png.clone(), png.clone(), png], &budget).unwrap();
+        assert_eq!(attachments.len(), 2);
+        assert_eq!(attachments[0].media_type, "image/jpeg");
+        assert_eq!((attachments[0].width, attachments[0].height), (50, 25));
+
+        assert!(fit_attachments(&[b"not an image".to_vec()], &budget).is_err());
+    }
+}
@@ -4,15 +4,19 @@
 //! with Metal GPU acceleration on Apple Silicon.
 
 pub mod fallback;
+pub mod images;
 
 use crate::config::LlmConfig;
 use crate::error::{Result, SpeechError};
+use crate::fae_llm::config::{LoraAdapterConfig, LoraMode};
 use crate::pipeline::messages::SentenceChunk;
 use image::DynamicImage;
 use mistralrs::{
-    GgufModelBuilder, IsqType, MemoryGpuConfig, Model, PagedAttentionMetaBuilder, RequestBuilder,
-    Response, TextMessageRole, VisionMessages, VisionModelBuilder,
+    GgufLoraModelBuilder, GgufModelBuilder, GgufXLoraModelBuilder, MemoryGpuConfig, Model,
+    PagedAttentionMetaBuilder, RequestBuilder, Response, TextMessageRole, VisionMessages,
 };
+#[cfg(feature = "vision")]
+use mistralrs::{IsqType, VisionModelBuilder};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
@@ -41,12 +45,13 @@ pub enum HistoryEntry {
         /// The message content.
         content: String,
     },
-    /// A user message with an attached camera image.
+    /// A user message with one or more attached images.
     ImageCapture {
-        /// The user's text accompanying the image.
+        /// The user's text accompanying the images.
         text: String,
-        /// The captured image (decoded, ready for the vision encoder).
-        image: DynamicImage,
+        /// The images, fitted to the image budget and decoded for the
+        /// vision encoder.
+        images: Vec<DynamicImage>,
     },
 }
 
@@ -156,15 +161,20 @@ impl LocalLlm {
     ///
     /// Downloads full-precision HF weights on first run, then applies ISQ (Q4K)
     /// in memory. Subsequent starts use the HF cache.
+    #[cfg(feature = "vision")]
     async fn load_vision_model(config: &LlmConfig) -> Result<Arc<Model>> {
         info!("loading vision LLM: {} (ISQ Q4K)", config.model_id);
 
         let context_size = effective_context_size_tokens(config);
         info!("local LLM context_size_tokens={context_size}");
+        if config.lora.is_some() {
+            warn!("LoRA adapters are only supported on GGUF models; ignoring for vision model");
+        }
 
         let model = VisionModelBuilder::new(&config.model_id)
             .with_isq(IsqType::Q4K)
             .with_logging()
+            .with_prefix_cache_n(prefix_cache_n(config))
             .with_paged_attn(|| {
                 PagedAttentionMetaBuilder::default()
                     .with_gpu_memory(MemoryGpuConfig::ContextSize(context_size))
@@ -179,6 +189,14 @@ impl LocalLlm {
         Ok(Arc::new(model))
     }
 
+    /// Builds without the `vision` feature always fall back to GGUF.
+    #[cfg(not(feature = "vision"))]
+    async fn load_vision_model(_config: &LlmConfig) -> Result<Arc<Model>> {
+        Err(SpeechError::Llm(
+            "vision support is not compiled in".to_owned(),
+        ))
+    }
+
     /// Load a text-only GGUF model via `GgufModelBuilder`.
     async fn load_gguf_model(config: &LlmConfig) -> Result<Arc<Model>> {
         info!(
@@ -186,8 +204,9 @@ impl LocalLlm {
             config.model_id, config.gguf_file
         );
 
-        let mut builder =
-            GgufModelBuilder::new(&config.model_id, vec![&config.gguf_file]).with_logging();
+        let mut builder = GgufModelBuilder::new(&config.model_id, vec![&config.gguf_file])
+            .with_logging()
+            .with_prefix_cache_n(prefix_cache_n(config));
 
         if !config.tokenizer_id.is_empty() {
             builder = builder.with_tok_model_id(&config.tokenizer_id);
@@ -196,16 +215,49 @@ impl LocalLlm {
         let context_size = effective_context_size_tokens(config);
         info!("local LLM context_size_tokens={context_size}");
 
-        let model = builder
+        let builder = builder
             .with_paged_attn(|| {
                 PagedAttentionMetaBuilder::default()
                     .with_gpu_memory(MemoryGpuConfig::ContextSize(context_size))
                     .build()
             })
-            .map_err(|e| SpeechError::Llm(format!("paged attention config failed: {e}")))?
-            .build()
-            .await
-            .map_err(|e| SpeechError::Llm(format!("GGUF model build failed: {e}")))?;
+            .map_err(|e| SpeechError::Llm(format!("paged attention config failed: {e}")))?;
+
+        let model = match &config.lora {
+            None => builder.build().await,
+            Some(lora) => {
+                let ordering = load_lora_ordering(lora)?;
+                info!(
+                    adapter_id = lora.adapter_id,
+                    mode = ?lora.mode,
+                    "applying LoRA adapters"
+                );
+                match lora.mode {
+                    LoraMode::Merge => {
+                        GgufLoraModelBuilder::from_gguf_model_builder(
+                            builder,
+                            &lora.adapter_id,
+                            ordering,
+                        )
+                        .build()
+                        .await
+                    }
+                    LoraMode::Xlora => {
+                        let mut xlora = GgufXLoraModelBuilder::from_gguf_model_builder(
+                            builder,
+                            &lora.adapter_id,
+                            ordering,
+                            false,
+                        );
+                        if let Some(index) = lora.tgt_non_granular_index {
+                            xlora = xlora.tgt_non_granular_index(index);
+                        }
+                        xlora.build().await
+                    }
+                }
+            }
+        }
+        .map_err(|e| SpeechError::Llm(format!("GGUF model build failed: {e}")))?;
 
         info!("GGUF LLM loaded successfully");
         Ok(Arc::new(model))
@@ -281,14 +333,34 @@ impl LocalLlm {
         })
     }
 
+    /// Fit a turn's images to the configured budget, dropping any beyond
+    /// the per-turn limit.
+    fn fit_images(&self, mut images: Vec<DynamicImage>) -> Result<Vec<DynamicImage>> {
+        let budget = &self.config.images;
+        if images.len() > budget.max_per_turn {
+            warn!(
+                "dropping {} of {} images over the per-turn limit",
+                images.len() - budget.max_per_turn,
+                images.len()
+            );
+            images.truncate(budget.max_per_turn);
+        }
+        images
+            .iter()
+            .map(|image| images::fit_to_budget(image, budget)?.decode())
+            .collect()
+    }
+
     /// Generate a response to the given user input, streaming sentences to the channel.
     ///
     /// Tokens are accumulated into sentences (split on `.`, `!`, `?`, `\n`).
     /// Each complete sentence is sent to the TTS stage immediately for
     /// low-latency speech output.
     ///
-    /// When `image` is `Some`, the user message is treated as an image-carrying
-    /// turn and the image is sent to the vision encoder alongside the text.
+    /// When `images` is not empty, the user message is treated as an
+    /// image-carrying turn: up to `[llm.images] max_per_turn` images are
+    /// fitted to the image budget and sent to the vision encoder alongside
+    /// the text.
     ///
     /// The `interrupt` flag is checked every chunk. If set, generation stops
     /// early and the partial response is saved. Returns `true` if interrupted.
@@ -299,15 +371,16 @@ impl LocalLlm {
     pub async fn generate_response(
         &mut self,
         user_input: String,
-        image: Option<DynamicImage>,
+        images: Vec<DynamicImage>,
         tx: mpsc::Sender<SentenceChunk>,
         interrupt: Arc<AtomicBool>,
     ) -> Result<bool> {
-        // Add user message to history (with optional image).
-        if let Some(img) = image {
+        // Add user message to history (with any images).
+        if !images.is_empty() {
+            let images = self.fit_images(images)?;
             self.history.push(HistoryEntry::ImageCapture {
                 text: user_input.clone(),
-                image: img,
+                images,
             });
         } else {
             self.history.push(HistoryEntry::Text {
@@ -331,14 +404,9 @@ impl LocalLlm {
                 HistoryEntry::Text { role, content } => {
                     messages = messages.add_message(role.clone(), content);
                 }
-                HistoryEntry::ImageCapture { text, image } => {
+                HistoryEntry::ImageCapture { text, images } => {
                     messages = messages
-                        .add_image_message(
-                            TextMessageRole::User,
-                            text,
-                            vec![image.clone()],
-                            &self.model,
-                        )
+                        .add_image_message(TextMessageRole::User, text, images.clone(), &self.model)
                         .map_err(|e| {
                             SpeechError::Llm(format!("failed to add image message: {e}"))
                         })?;
@@ -621,6 +689,29 @@ fn should_abort_reasoning_only(
         && elapsed >= REASONING_ONLY_DURATION_LIMIT
 }
 
+/// Prefix cache capacity for the model builders; `None` disables caching.
+/// Read and parse the adapter ordering file for `lora`.
+fn load_lora_ordering(lora: &LoraAdapterConfig) -> Result<mistralrs::Ordering> {
+    lora.validate()
+        .map_err(|reason| SpeechError::Llm(format!("invalid LoRA config: {reason}")))?;
+    let raw = std::fs::read_to_string(&lora.ordering_file).map_err(|e| {
+        SpeechError::Llm(format!(
+            "failed to read LoRA ordering file {}: {e}",
+            lora.ordering_file
+        ))
+    })?;
+    serde_json::from_str(&raw).map_err(|e| {
+        SpeechError::Llm(format!(
+            "invalid LoRA ordering file {}: {e}",
+            lora.ordering_file
+        ))
+    })
+}
+
+fn prefix_cache_n(config: &LlmConfig) -> Option<usize> {
+    (config.prefix_cache_sequences > 0).then_some(config.prefix_cache_sequences)
+}
+
 pub(crate) fn effective_context_size_tokens(config: &LlmConfig) -> usize {
     if config.context_size_tokens < MIN_CONTEXT_SIZE_TOKENS {
         warn!(
@@ -659,12 +750,28 @@ const CLAUSE_MIN_LEN: usize = 20;
 /// when the buffer exceeds [`CLAUSE_MIN_LEN`] characters, also splits on
 /// clause punctuation (`, ; : — –`) to enable lower-latency streaming.
 ///
+/// A fenced code block (```` ``` ````) is never split: text before it is
+/// chunked as usual, then the buffer is held until the closing fence so the
+/// whole block reaches speech rendering as one chunk.
+///
 /// Returns the byte index of the last byte of the boundary character, or `None`.
 ///
 /// Callers use `text[..=pos]` and `text[pos + 1..]`, so we must return the
 /// last byte of the (possibly multi-byte) punctuation character to ensure
 /// both slices land on valid UTF-8 char boundaries.
 pub(crate) fn find_clause_boundary(text: &str) -> Option<usize> {
+    if let Some(fence) = text.find("```") {
+        if let Some(pos) = find_prose_boundary(&text[..fence]) {
+            return Some(pos);
+        }
+        let body = fence + 3;
+        return text[body..].find("```").map(|close| body + close + 2);
+    }
+    find_prose_boundary(text)
+}
+
+/// [`find_clause_boundary`] for text without code fences.
+fn find_prose_boundary(text: &str) -> Option<usize> {
     // Sentence boundaries take priority.
     if let Some(pos) = find_sentence_boundary(text) {
         return Some(pos);
@@ -706,6 +813,35 @@ mod tests {
         assert_eq!(effective_context_size_tokens(&cfg), 65_536);
     }
 
+    #[test]
+    fn prefix_cache_disabled_by_zero() {
+        let cfg = LlmConfig {
+            prefix_cache_sequences: 0,
+            ..Default::default()
+        };
+        assert_eq!(prefix_cache_n(&cfg), None);
+        assert_eq!(prefix_cache_n(&LlmConfig::default()), Some(16));
+    }
+
+    #[test]
+    fn lora_ordering_errors_name_the_file() {
+        let lora = LoraAdapterConfig {
+            adapter_id: "me/adapter".to_owned(),
+            ordering_file: "/nonexistent/fae-ordering.json".to_owned(),
+            mode: LoraMode::Merge,
+            tgt_non_granular_index: None,
+        };
+        let err = load_lora_ordering(&lora).err().map(|e| e.to_string());
+        assert!(err.is_some_and(|e| e.contains("/nonexistent/fae-ordering.json")));
+
+        let incomplete = LoraAdapterConfig {
+            adapter_id: String::new(),
+            ..lora
+        };
+        let err = load_lora_ordering(&incomplete).err().map(|e| e.to_string());
+        assert!(err.is_some_and(|e| e.contains("adapter_id")));
+    }
+
     #[test]
     fn effective_context_size_clamps_small_values() {
         let cfg = LlmConfig {
@@ -790,15 +926,30 @@ mod tests {
         let img = image::DynamicImage::new_rgb8(1, 1);
         let entry = HistoryEntry::ImageCapture {
             text: "what is this".to_owned(),
-            image: img,
+            images: vec![img.clone(), img],
         };
         match entry {
-            HistoryEntry::ImageCapture { text, image } => {
+            HistoryEntry::ImageCapture { text, images } => {
                 assert_eq!(text, "what is this");
-                assert_eq!(image.width(), 1);
-                assert_eq!(image.height(), 1);
+                assert_eq!(images.len(), 2);
+                assert_eq!(images[0].width(), 1);
+                assert_eq!(images[0].height(), 1);
             }
             HistoryEntry::Text { .. } => panic!("expected ImageCapture variant"),
         }
     }
+
+    #[test]
+    fn clause_boundary_keeps_code_fences_whole() {
+        let text = "Run this:\n```sh\nls -la.\n";
+        // Prose before the fence is still chunked.
+        assert_eq!(find_clause_boundary(text), Some(text.find('\n').unwrap()));
+        // An open fence holds the buffer, sentence punctuation inside or not.
+        assert_eq!(find_clause_boundary("```sh\nls -la.\n"), None);
+        let block = "```sh\nls -la.\n```\nDone.";
+        assert_eq!(
+            find_clause_boundary(block),
+            Some(block.rfind("```").unwrap() + 2)
+        );
+    }
 }
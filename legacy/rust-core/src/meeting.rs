@@ -0,0 +1,542 @@
+//! Meeting mode: transcribe a meeting without replying, then write minutes.
+//!
+//! A meeting starts by voice ("start meeting mode") or from the host
+//! (`meeting.start` with `consent: true`, or the FFI toggle) once the user
+//! has agreed to everyone in the meeting being transcribed. While it runs,
+//! Fae does not speak: the STT stage hands every transcribed segment to
+//! [`record`], which labels it with a speaker by clustering voiceprints
+//! ([`SpeakerClusters`]) and appends it to the transcript on disk. With
+//! `meeting.loopback_device` set, capture switches to that device for the
+//! meeting so the far side of a call (system audio) is transcribed too.
+//!
+//! When the meeting stops ("end meeting", `meeting.stop`), the LLM stage
+//! takes the transcript ([`take_finished`]) and has a background agent write
+//! minutes with action items, saved beside the transcript. Old transcripts
+//! and minutes are pruned by `retention_days` and `max_disk_mb` each time a
+//! meeting starts.
+//!
+//! Like offline mode, the switch is process-wide.
+
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{DateTime, Local};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::config::MeetingConfig;
+
+/// Longest transcript (in characters) handed to the agent for minutes;
+/// longer ones keep their start and end.
+const MAX_PROMPT_TRANSCRIPT_CHARS: usize = 24_000;
+
+static ACTIVE: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::Sender::new(false));
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static FINISHED: Mutex<Option<MeetingTranscript>> = Mutex::new(None);
+
+/// One transcribed segment of a meeting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeetingSegment {
+    /// Milliseconds from the start of the meeting.
+    pub offset_ms: u64,
+    /// 1-based speaker label.
+    pub speaker: usize,
+    pub text: String,
+}
+
+impl MeetingSegment {
+    /// Transcript line: `[mm:ss] Speaker N: text`.
+    fn line(&self) -> String {
+        let secs = self.offset_ms / 1000;
+        format!(
+            "[{:02}:{:02}] Speaker {}: {}\n",
+            secs / 60,
+            secs % 60,
+            self.speaker,
+            self.text
+        )
+    }
+}
+
+/// Transcript of a meeting.
+#[derive(Debug, Clone)]
+pub struct MeetingTranscript {
+    pub id: String,
+    pub started_at: DateTime<Local>,
+    /// Transcript file; the minutes are saved beside it.
+    pub path: PathBuf,
+    pub segments: Vec<MeetingSegment>,
+}
+
+impl MeetingTranscript {
+    fn new(folder: &Path, started_at: DateTime<Local>) -> Self {
+        let id = format!("meeting-{}", started_at.format("%Y%m%d-%H%M%S"));
+        Self {
+            path: folder.join(format!("{id}.md")),
+            id,
+            started_at,
+            segments: Vec::new(),
+        }
+    }
+
+    fn header(&self) -> String {
+        format!("# Meeting {}\n\n", self.started_at.format("%Y-%m-%d %H:%M"))
+    }
+
+    /// The transcript, one `[mm:ss] Speaker N: text` line per segment.
+    pub fn to_text(&self) -> String {
+        self.segments.iter().map(MeetingSegment::line).collect()
+    }
+
+    /// Number of distinct speakers heard.
+    pub fn speaker_count(&self) -> usize {
+        self.segments.iter().map(|s| s.speaker).max().unwrap_or(0)
+    }
+
+    /// Request asking the agent to write minutes for this meeting.
+    pub fn minutes_prompt(&self) -> String {
+        let mut transcript = self.to_text();
+        if transcript.chars().count() > MAX_PROMPT_TRANSCRIPT_CHARS {
+            let half = MAX_PROMPT_TRANSCRIPT_CHARS / 2;
+            let head: String = transcript.chars().take(half).collect();
+            let tail: String = {
+                let mut tail: Vec<char> = transcript.chars().rev().take(half).collect();
+                tail.reverse();
+                tail.into_iter().collect()
+            };
+            transcript = format!("{head}\n[… middle of the meeting omitted …]\n{tail}");
+        }
+        format!(
+            "Write minutes for the meeting transcribed below ({}, {} speakers told apart by \
+             voice). Use Markdown with three sections: \"## Summary\" (a few sentences), \
+             \"## Decisions\", and \"## Action items\" with one \"- [ ] owner: task\" line per \
+             item, adding the due date when one was mentioned. Use \"Speaker N\" when the owner's \
+             name is not said. Only include what the transcript supports.\n\nTranscript:\n{}",
+            self.started_at.format("%Y-%m-%d %H:%M"),
+            self.speaker_count(),
+            transcript.trim_end()
+        )
+    }
+
+    /// Where the minutes are saved.
+    pub fn minutes_path(&self) -> PathBuf {
+        self.path.with_file_name(format!("{}-minutes.md", self.id))
+    }
+
+    /// Save `minutes` beside the transcript. Returns their path.
+    pub fn save_minutes(&self, minutes: &str) -> Result<PathBuf, String> {
+        let path = self.minutes_path();
+        let text = format!(
+            "# Minutes: meeting {}\n\n{}\n",
+            self.started_at.format("%Y-%m-%d %H:%M"),
+            minutes.trim()
+        );
+        std::fs::write(&path, text)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Number of action items (`- [ ] …` lines) in minutes.
+pub fn count_action_items(minutes: &str) -> usize {
+    minutes
+        .lines()
+        .filter(|line| line.trim_start().starts_with("- [ ]"))
+        .count()
+}
+
+/// Online speaker clustering over voiceprints.
+///
+/// A segment joins the most similar known speaker when the similarity is at
+/// least `threshold`; otherwise it starts a new speaker, up to
+/// `max_speakers`, after which it joins the closest one. Each speaker's
+/// centroid is the normalized mean of its segments' voiceprints.
+#[derive(Debug, Clone)]
+pub struct SpeakerClusters {
+    threshold: f32,
+    max_speakers: usize,
+    /// Normalized centroid and segment count per speaker.
+    speakers: Vec<(Vec<f32>, usize)>,
+}
+
+impl SpeakerClusters {
+    pub fn new(threshold: f32, max_speakers: usize) -> Self {
+        Self {
+            threshold,
+            max_speakers: max_speakers.max(1),
+            speakers: Vec::new(),
+        }
+    }
+
+    /// 1-based label of the speaker of a segment with voiceprint `print`.
+    pub fn assign(&mut self, print: &[f32]) -> usize {
+        let closest = self
+            .speakers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (centroid, _))| {
+                crate::voiceprint::similarity(centroid, print).map(|s| (i, s))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let index = match closest {
+            Some((i, similarity))
+                if similarity >= self.threshold || self.speakers.len() >= self.max_speakers =>
+            {
+                let (centroid, count) = &mut self.speakers[i];
+                let weight = *count as f32;
+                for (c, p) in centroid.iter_mut().zip(print) {
+                    *c = *c * weight + p;
+                }
+                normalize(centroid);
+                *count += 1;
+                i
+            }
+            _ => {
+                let mut centroid = print.to_vec();
+                normalize(&mut centroid);
+                self.speakers.push((centroid, 1));
+                self.speakers.len() - 1
+            }
+        };
+        index + 1
+    }
+}
+
+fn normalize(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// A running meeting.
+struct Session {
+    transcript: MeetingTranscript,
+    speakers: SpeakerClusters,
+    started: Instant,
+    /// Speaker of the previous segment, for segments too short to tell.
+    last_speaker: usize,
+    /// Bytes the transcript file may still grow by.
+    disk_budget: u64,
+    /// The disk budget ran out; later segments are kept in memory only.
+    over_budget: bool,
+}
+
+impl Session {
+    fn record(&mut self, samples: &[f32], sample_rate: u32, text: &str) {
+        let speaker = match crate::voiceprint::compute_voiceprint(samples, sample_rate) {
+            Ok(print) => self.speakers.assign(&print),
+            Err(_) => self.last_speaker,
+        };
+        self.last_speaker = speaker;
+        let audio = Duration::from_secs_f64(samples.len() as f64 / f64::from(sample_rate.max(1)));
+        let segment = MeetingSegment {
+            offset_ms: self.started.elapsed().saturating_sub(audio).as_millis() as u64,
+            speaker,
+            text: text.to_owned(),
+        };
+        let line = segment.line();
+        self.transcript.segments.push(segment);
+
+        if self.over_budget {
+            return;
+        }
+        let len = line.len() as u64;
+        if len > self.disk_budget {
+            warn!(
+                id = %self.transcript.id,
+                "meeting disk budget reached — transcript is no longer written to disk"
+            );
+            self.over_budget = true;
+            return;
+        }
+        let written = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&self.transcript.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+        match written {
+            Ok(()) => self.disk_budget -= len,
+            Err(e) => warn!("failed to append to meeting transcript: {e}"),
+        }
+    }
+}
+
+/// Watch the meeting switch: `true` while a meeting is running.
+pub fn subscribe() -> watch::Receiver<bool> {
+    ACTIVE.subscribe()
+}
+
+/// Whether a meeting is running.
+pub fn is_active() -> bool {
+    *ACTIVE.borrow()
+}
+
+/// Start a meeting and return its id.
+///
+/// `consent` records that the user agreed to everyone in the meeting being
+/// transcribed; nothing starts without it.
+pub fn start(config: &MeetingConfig, consent: bool) -> Result<String, String> {
+    if !consent {
+        return Err("meeting mode needs consent to transcribe the meeting".to_owned());
+    }
+    let mut session = SESSION
+        .lock()
+        .map_err(|_| "meeting state lock poisoned".to_owned())?;
+    if let Some(running) = session.as_ref() {
+        return Err(format!(
+            "meeting {} is already running",
+            running.transcript.id
+        ));
+    }
+
+    let folder = config
+        .folder
+        .clone()
+        .unwrap_or_else(crate::fae_dirs::meetings_dir);
+    std::fs::create_dir_all(&folder)
+        .map_err(|e| format!("failed to create meetings folder: {e}"))?;
+    let pruned = prune(&folder, config, SystemTime::now());
+    let transcript = MeetingTranscript::new(&folder, Local::now());
+    let header = transcript.header();
+    std::fs::write(&transcript.path, &header)
+        .map_err(|e| format!("failed to create {}: {e}", transcript.path.display()))?;
+    let used = meeting_files(&folder).iter().map(|f| f.2).sum::<u64>();
+
+    let id = transcript.id.clone();
+    info!(id, pruned, "meeting started");
+    *session = Some(Session {
+        transcript,
+        speakers: SpeakerClusters::new(config.speaker_threshold, config.max_speakers),
+        started: Instant::now(),
+        last_speaker: 1,
+        disk_budget: config
+            .max_disk_mb
+            .saturating_mul(1024 * 1024)
+            .saturating_sub(used),
+        over_budget: false,
+    });
+    ACTIVE.send_replace(true);
+    Ok(id)
+}
+
+/// Add a transcribed segment to the running meeting. `samples` is the
+/// segment's audio, used to tell speakers apart. Does nothing when no
+/// meeting is running.
+pub fn record(samples: &[f32], sample_rate: u32, text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    if let Ok(mut session) = SESSION.lock()
+        && let Some(session) = session.as_mut()
+    {
+        session.record(samples, sample_rate, text);
+    }
+}
+
+/// Stop the running meeting and return its id, or `None` when no meeting
+/// was running. The transcript is kept for [`take_finished`].
+pub fn stop() -> Option<String> {
+    let session = SESSION.lock().ok()?.take()?;
+    let transcript = session.transcript;
+    let id = transcript.id.clone();
+    info!(id, segments = transcript.segments.len(), "meeting stopped");
+    if let Ok(mut finished) = FINISHED.lock() {
+        *finished = Some(transcript);
+    }
+    ACTIVE.send_replace(false);
+    Some(id)
+}
+
+/// Take the transcript of the meeting that last stopped, to write minutes.
+pub fn take_finished() -> Option<MeetingTranscript> {
+    FINISHED.lock().ok()?.take()
+}
+
+/// Meeting files in `folder`, oldest first: path, modification time, size.
+fn meeting_files(folder: &Path) -> Vec<(PathBuf, SystemTime, u64)> {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    let mut files: Vec<_> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("meeting-") && name.ends_with(".md")
+        })
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            let modified = meta.modified().ok()?;
+            meta.is_file().then(|| (entry.path(), modified, meta.len()))
+        })
+        .collect();
+    files.sort_by_key(|(_, modified, _)| *modified);
+    files
+}
+
+/// Delete meeting files in `folder` older than `retention_days`, then the
+/// oldest ones until the rest fit in `max_disk_mb`. Returns how many were
+/// deleted.
+pub fn prune(folder: &Path, config: &MeetingConfig, now: SystemTime) -> usize {
+    let files = meeting_files(folder);
+    let max_age = Duration::from_secs(u64::from(config.retention_days) * 86_400);
+    let budget = config.max_disk_mb.saturating_mul(1024 * 1024);
+    let mut total: u64 = files.iter().map(|f| f.2).sum();
+    let mut removed = 0;
+    for (path, modified, len) in files {
+        let expired = config.retention_days > 0
+            && now.duration_since(modified).is_ok_and(|age| age > max_age);
+        if !expired && total <= budget {
+            break;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                total -= len;
+                removed += 1;
+            }
+            Err(e) => warn!("failed to delete {}: {e}", path.display()),
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn print(direction: usize) -> Vec<f32> {
+        let mut v = vec![0.05; crate::voiceprint::VOICEPRINT_DIMS];
+        v[direction] = 1.0;
+        normalize(&mut v);
+        v
+    }
+
+    fn transcript(folder: &Path) -> MeetingTranscript {
+        let started_at = chrono::NaiveDate::from_ymd_opt(2026, 10, 16)
+            .and_then(|d| d.and_hms_opt(9, 30, 0))
+            .and_then(|t| t.and_local_timezone(Local).single())
+            .expect("time");
+        MeetingTranscript::new(folder, started_at)
+    }
+
+    #[test]
+    fn speakers_are_clustered_by_voiceprint() {
+        let mut clusters = SpeakerClusters::new(0.9, 2);
+        assert_eq!(clusters.assign(&print(0)), 1);
+        assert_eq!(clusters.assign(&print(1)), 2);
+        assert_eq!(clusters.assign(&print(0)), 1);
+        // A third voice joins the closest once the limit is reached.
+        assert_eq!(clusters.assign(&print(2)), 1);
+    }
+
+    #[test]
+    fn transcript_lines_and_minutes_prompt() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut meeting = transcript(dir.path());
+        meeting.segments = vec![
+            MeetingSegment {
+                offset_ms: 4_200,
+                speaker: 1,
+                text: "Let's ship on Friday.".to_owned(),
+            },
+            MeetingSegment {
+                offset_ms: 65_000,
+                speaker: 2,
+                text: "I'll update the docs.".to_owned(),
+            },
+        ];
+
+        assert_eq!(meeting.id, "meeting-20261016-093000");
+        assert_eq!(
+            meeting.to_text(),
+            "[00:04] Speaker 1: Let's ship on Friday.\n[01:05] Speaker 2: I'll update the docs.\n"
+        );
+        let prompt = meeting.minutes_prompt();
+        assert!(prompt.contains("2 speakers"), "{prompt}");
+        assert!(
+            prompt.ends_with("Speaker 2: I'll update the docs."),
+            "{prompt}"
+        );
+
+        let minutes =
+            "## Summary\nShip Friday.\n\n## Action items\n- [ ] Speaker 2: update the docs\n";
+        assert_eq!(count_action_items(minutes), 1);
+        let path = meeting.save_minutes(minutes).expect("save");
+        assert_eq!(path, dir.path().join("meeting-20261016-093000-minutes.md"));
+        assert!(
+            std::fs::read_to_string(path)
+                .expect("read")
+                .starts_with("# Minutes: meeting 2026-10-16 09:30\n\n## Summary")
+        );
+    }
+
+    #[test]
+    fn session_stops_writing_at_the_disk_budget() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let meeting = transcript(dir.path());
+        std::fs::write(&meeting.path, meeting.header()).expect("header");
+        let path = meeting.path.clone();
+        let mut session = Session {
+            transcript: meeting,
+            speakers: SpeakerClusters::new(0.9, 4),
+            started: Instant::now(),
+            last_speaker: 1,
+            disk_budget: 40,
+            over_budget: false,
+        };
+
+        session.record(&[], 16_000, "Hello everyone.");
+        session.record(&[], 16_000, "This line no longer fits on disk.");
+
+        assert_eq!(session.transcript.segments.len(), 2);
+        assert!(session.over_budget);
+        assert_eq!(
+            std::fs::read_to_string(path).expect("read"),
+            "# Meeting 2026-10-16 09:30\n\n[00:00] Speaker 1: Hello everyone.\n"
+        );
+    }
+
+    #[test]
+    fn prune_removes_expired_then_oldest_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let now = SystemTime::now();
+        let day = Duration::from_secs(86_400);
+        for (name, age) in [
+            ("meeting-old.md", day * 40),
+            ("meeting-mid.md", day * 2),
+            ("meeting-new.md", Duration::ZERO),
+            ("notes.md", day * 40),
+        ] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, "x").expect("write");
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|f| f.set_modified(now - age))
+                .expect("mtime");
+        }
+
+        let config = MeetingConfig::default();
+        assert_eq!(prune(dir.path(), &config, now), 1);
+        assert!(!dir.path().join("meeting-old.md").exists());
+        assert!(dir.path().join("meeting-mid.md").exists());
+
+        let no_room = MeetingConfig {
+            max_disk_mb: 0,
+            ..MeetingConfig::default()
+        };
+        assert_eq!(prune(dir.path(), &no_room, now), 2);
+        assert!(dir.path().join("notes.md").exists());
+    }
+
+    #[test]
+    fn start_requires_consent() {
+        assert!(start(&MeetingConfig::default(), false).is_err());
+        assert!(!is_active());
+    }
+}
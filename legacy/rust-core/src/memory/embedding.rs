@@ -15,7 +15,8 @@ use ort::session::{Session, SessionInputValue, SessionInputs};
 use ort::value::Tensor;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tracing::info;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
 
 /// HuggingFace repo for the all-MiniLM-L6-v2 ONNX model.
 const REPO_ID: &str = "sentence-transformers/all-MiniLM-L6-v2";
@@ -236,6 +237,12 @@ impl EmbeddingEngine {
     ///
     /// Returns an error if the download fails.
     pub fn download_model() -> Result<(PathBuf, PathBuf)> {
+        if crate::offline::is_offline() {
+            return Ok((
+                crate::models::ModelManager::cached_file(REPO_ID, MODEL_FILE)?,
+                crate::models::ModelManager::cached_file(REPO_ID, TOKENIZER_FILE)?,
+            ));
+        }
         info!("downloading embedding model: {REPO_ID}");
         let api = hf_hub::api::sync::Api::new()
             .map_err(|e| SpeechError::Model(format!("HF Hub API init failed: {e}")))?;
@@ -272,6 +279,48 @@ impl EmbeddingEngine {
     }
 }
 
+/// The process-wide engine, if it has been loaded.
+///
+/// Never waits for the model: the first call starts loading it (downloading
+/// it if needed) on a background thread and returns `None` until it is
+/// ready. After a failed load, e.g. offline before the model was first
+/// downloaded, a later call tries again.
+pub fn shared_engine() -> Option<Arc<Mutex<EmbeddingEngine>>> {
+    enum Slot {
+        Empty,
+        Loading,
+        Ready(Arc<Mutex<EmbeddingEngine>>),
+    }
+    static ENGINE: Mutex<Slot> = Mutex::new(Slot::Empty);
+    let slot = || ENGINE.lock().unwrap_or_else(|e| e.into_inner());
+
+    {
+        let mut slot = slot();
+        match &*slot {
+            Slot::Ready(engine) => return Some(Arc::clone(engine)),
+            Slot::Loading => return None,
+            Slot::Empty => *slot = Slot::Loading,
+        }
+    }
+    let spawned = std::thread::Builder::new()
+        .name("fae-embedding-load".into())
+        .spawn(move || {
+            let loaded = EmbeddingEngine::download_and_load();
+            *slot() = match loaded {
+                Ok(engine) => Slot::Ready(Arc::new(Mutex::new(engine))),
+                Err(e) => {
+                    warn!("embedding model unavailable: {e}");
+                    Slot::Empty
+                }
+            };
+        });
+    if let Err(e) = spawned {
+        warn!("cannot start loading the embedding model: {e}");
+        *slot() = Slot::Empty;
+    }
+    None
+}
+
 // ---------------------------------------------------------------------------
 // Internal helpers
 // ---------------------------------------------------------------------------
@@ -1539,6 +1539,38 @@ checklist:\n\
         Ok(report)
     }
 
+    /// Store the summary of a conversation that has ended as one episode,
+    /// tagged `conversation_summary`, so later recall can find it.
+    pub fn capture_conversation_summary(
+        &self,
+        session_id: &str,
+        summary: &str,
+    ) -> Result<MemoryCaptureReport> {
+        if !self.config.enabled || !self.config.auto_capture || summary.trim().is_empty() {
+            return Ok(MemoryCaptureReport::default());
+        }
+
+        self.ensure_ready()?;
+
+        let text = truncate_record_text(summary.trim());
+        let episode = self.insert_and_embed(
+            MemoryKind::Episode,
+            &text,
+            0.6,
+            Some(session_id),
+            &["conversation_summary".to_owned()],
+        )?;
+        let mut report = MemoryCaptureReport {
+            episodes_written: 1,
+            ..MemoryCaptureReport::default()
+        };
+        report.writes.push(MemoryWriteSummary {
+            op: "insert_conversation_summary".to_owned(),
+            target_id: Some(episode.id),
+        });
+        Ok(report)
+    }
+
     fn is_duplicate_memory(&self, text: &str) -> Result<bool> {
         let hits = self.repo.search(text, 3, false)?;
         // Only consider durable records (profile/fact) as duplicates, not episodes.
@@ -2567,6 +2599,35 @@ mod tests {
         let _ = std::fs::remove_dir_all(root);
     }
 
+    #[test]
+    fn orchestrator_stores_conversation_summary_as_tagged_episode() {
+        let root = test_root("orchestrator-conversation-summary");
+        let cfg = test_cfg(&root);
+        let orchestrator = MemoryOrchestrator::new(&cfg).expect("orchestrator init");
+
+        let report = orchestrator
+            .capture_conversation_summary("session-1", "Talked about the garden.")
+            .expect("capture summary");
+        assert_eq!(report.episodes_written, 1);
+        let empty = orchestrator
+            .capture_conversation_summary("session-2", "  ")
+            .expect("skip empty summary");
+        assert_eq!(empty.episodes_written, 0);
+
+        let sqlite_repo = SqliteMemoryRepository::new(&root).expect("sqlite repo");
+        let records = sqlite_repo
+            .list_records_filtered(true)
+            .expect("list records");
+        let summary = records
+            .iter()
+            .find(|record| record.source_turn_id.as_deref() == Some("session-1"))
+            .expect("summary record");
+        assert_eq!(summary.kind, MemoryKind::Episode);
+        assert_eq!(summary.tags, vec!["conversation_summary".to_owned()]);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
     #[test]
     fn migration_from_older_manifest_version_is_supported() {
         let root = test_root("migration");
@@ -4,6 +4,7 @@
 //! - `types`: Shared types, constants, enums, and helpers (backend-agnostic).
 //! - `jsonl`: JSONL-backed `MemoryRepository`, `MemoryOrchestrator`, and legacy
 //!   markdown identity store.
+//! - `preferences`: Typed user preferences injected into the system prompt.
 //!
 //! Active modules:
 //! - `schema`: SQLite DDL definitions.
@@ -13,6 +14,7 @@ pub(crate) mod backup;
 pub mod embedding;
 pub mod jsonl;
 pub(crate) mod migrate;
+pub mod preferences;
 pub(crate) mod schema;
 pub mod sqlite;
 pub mod types;
@@ -0,0 +1,460 @@
+//! Typed user preferences.
+//!
+//! Settings the user states once and expects Fae to keep: units, locale,
+//! time zone, preferred calendar, home city, how to say people's names and
+//! how long replies should be. They are persisted in `preferences.json`, set
+//! and read through the `set_preference` / `get_preference` tools, and every
+//! preference that is set goes into the system prompt so the model does not
+//! ask for it again.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Most name pronunciations kept.
+pub const MAX_PRONUNCIATIONS: usize = 50;
+/// Longest free-text preference value, in characters.
+const MAX_VALUE_CHARS: usize = 120;
+
+/// Measurement system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Units {
+    Metric,
+    Imperial,
+}
+
+/// How long replies should be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verbosity {
+    Brief,
+    Normal,
+    Detailed,
+}
+
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Metric => "metric",
+            Self::Imperial => "imperial",
+        })
+    }
+}
+
+impl fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Brief => "brief",
+            Self::Normal => "normal",
+            Self::Detailed => "detailed",
+        })
+    }
+}
+
+/// One preference, as named in the tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferenceKey {
+    Units,
+    Locale,
+    Timezone,
+    Calendar,
+    HomeCity,
+    /// Keyed further by the name being pronounced.
+    Pronunciation,
+    Verbosity,
+}
+
+impl PreferenceKey {
+    /// Every key, in display order.
+    pub const ALL: [Self; 7] = [
+        Self::Units,
+        Self::Locale,
+        Self::Timezone,
+        Self::Calendar,
+        Self::HomeCity,
+        Self::Pronunciation,
+        Self::Verbosity,
+    ];
+
+    /// Name used in tool arguments.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Units => "units",
+            Self::Locale => "locale",
+            Self::Timezone => "timezone",
+            Self::Calendar => "calendar",
+            Self::HomeCity => "home_city",
+            Self::Pronunciation => "pronunciation",
+            Self::Verbosity => "verbosity",
+        }
+    }
+
+    /// Parse a tool argument name.
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|key| key.as_str() == raw.trim())
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Units => "Units",
+            Self::Locale => "Locale",
+            Self::Timezone => "Time zone",
+            Self::Calendar => "Preferred calendar",
+            Self::HomeCity => "Home city",
+            Self::Pronunciation => "Pronunciation",
+            Self::Verbosity => "Reply length",
+        }
+    }
+}
+
+/// Persisted user preferences; unset fields are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserPreferences {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<Units>,
+    /// BCP 47 language tag, e.g. `en-GB`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// IANA time zone name, e.g. `Europe/London`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Calendar new events go to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home_city: Option<String>,
+    /// Name to how it is said, e.g. `Siobhan` → `shi-VAWN`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pronunciations: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+}
+
+impl UserPreferences {
+    /// Load preferences from `path`, returning none set on error.
+    pub fn load(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("failed to parse preferences: {e}");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!("failed to load preferences: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Save preferences to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create preferences dir: {e}"))?;
+        }
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| format!("serialize error: {e}"))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("write error: {e}"))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("write error: {e}"))
+    }
+
+    /// Set `key` to `value`, or clear it when `value` is `None`.
+    ///
+    /// `name` is the person's name for [`PreferenceKey::Pronunciation`] and
+    /// ignored otherwise.
+    pub fn set(
+        &mut self,
+        key: PreferenceKey,
+        name: Option<&str>,
+        value: Option<&str>,
+    ) -> Result<(), String> {
+        let value = value.map(str::trim).filter(|v| !v.is_empty());
+        match key {
+            PreferenceKey::Units => {
+                self.units = value
+                    .map(|v| match v.to_ascii_lowercase().as_str() {
+                        "metric" | "celsius" => Ok(Units::Metric),
+                        "imperial" | "fahrenheit" => Ok(Units::Imperial),
+                        _ => Err(format!("units must be metric or imperial, got \"{v}\"")),
+                    })
+                    .transpose()?;
+            }
+            PreferenceKey::Verbosity => {
+                self.verbosity = value
+                    .map(|v| match v.to_ascii_lowercase().as_str() {
+                        "brief" | "short" => Ok(Verbosity::Brief),
+                        "normal" => Ok(Verbosity::Normal),
+                        "detailed" | "long" => Ok(Verbosity::Detailed),
+                        _ => Err(format!(
+                            "verbosity must be brief, normal or detailed, got \"{v}\""
+                        )),
+                    })
+                    .transpose()?;
+            }
+            PreferenceKey::Locale => {
+                self.locale = value.map(validate_locale).transpose()?;
+            }
+            PreferenceKey::Timezone => {
+                self.timezone = value.map(validate_timezone).transpose()?;
+            }
+            PreferenceKey::Calendar => self.calendar = value.map(free_text).transpose()?,
+            PreferenceKey::HomeCity => self.home_city = value.map(free_text).transpose()?,
+            PreferenceKey::Pronunciation => {
+                let name = name
+                    .map(str::trim)
+                    .filter(|n| !n.is_empty())
+                    .ok_or("pronunciation needs the name it is for")?;
+                let name = free_text(name)?;
+                match value {
+                    Some(value) => {
+                        if !self.pronunciations.contains_key(&name)
+                            && self.pronunciations.len() >= MAX_PRONUNCIATIONS
+                        {
+                            return Err(format!(
+                                "at most {MAX_PRONUNCIATIONS} pronunciations can be kept"
+                            ));
+                        }
+                        self.pronunciations.insert(name, free_text(value)?);
+                    }
+                    None => {
+                        self.pronunciations.remove(&name);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// One line per set value of `key`, e.g. `Units: metric`.
+    #[must_use]
+    pub fn describe(&self, key: PreferenceKey) -> Vec<String> {
+        let label = key.label();
+        let single = match key {
+            PreferenceKey::Units => self.units.map(|u| u.to_string()),
+            PreferenceKey::Locale => self.locale.clone(),
+            PreferenceKey::Timezone => self.timezone.clone(),
+            PreferenceKey::Calendar => self.calendar.clone(),
+            PreferenceKey::HomeCity => self.home_city.clone(),
+            PreferenceKey::Verbosity => self.verbosity.map(|v| v.to_string()),
+            PreferenceKey::Pronunciation => {
+                return self
+                    .pronunciations
+                    .iter()
+                    .map(|(name, said)| format!("{label}: \"{name}\" is said \"{said}\""))
+                    .collect();
+            }
+        };
+        single
+            .map(|value| format!("{label}: {value}"))
+            .into_iter()
+            .collect()
+    }
+
+    /// Whether no preference is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// System prompt section listing every set preference, or `None` when
+    /// there are none.
+    #[must_use]
+    pub fn prompt_section(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut section = String::from(
+            "User preferences (already known \u{2014} follow them and do not ask for them again):",
+        );
+        for key in PreferenceKey::ALL {
+            for line in self.describe(key) {
+                section.push_str("\n- ");
+                section.push_str(&line);
+            }
+        }
+        match self.verbosity {
+            Some(Verbosity::Brief) => section.push_str("\nKeep replies to a sentence or two."),
+            Some(Verbosity::Detailed) => {
+                section.push_str("\nGive fuller explanations when they help.");
+            }
+            Some(Verbosity::Normal) | None => {}
+        }
+        Some(section)
+    }
+}
+
+fn free_text(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.chars().count() > MAX_VALUE_CHARS {
+        return Err(format!("value is longer than {MAX_VALUE_CHARS} characters"));
+    }
+    if value.chars().any(char::is_control) {
+        return Err("value contains control characters".to_owned());
+    }
+    Ok(value.to_owned())
+}
+
+fn validate_locale(value: &str) -> Result<String, String> {
+    let valid = value.len() <= 35
+        && value.split(['-', '_']).all(|part| {
+            !part.is_empty() && part.len() <= 8 && part.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+    if valid {
+        Ok(value.replace('_', "-"))
+    } else {
+        Err(format!(
+            "locale must be a language tag like en-GB, got \"{value}\""
+        ))
+    }
+}
+
+fn validate_timezone(value: &str) -> Result<String, String> {
+    let valid = value.len() <= 64
+        && value.split('/').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+'))
+        });
+    if valid {
+        Ok(value.to_owned())
+    } else {
+        Err(format!(
+            "timezone must be an IANA name like Europe/London, got \"{value}\""
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn set_parses_and_clears_values() {
+        let mut prefs = UserPreferences::default();
+        prefs
+            .set(PreferenceKey::Units, None, Some("Fahrenheit"))
+            .unwrap();
+        prefs
+            .set(PreferenceKey::Locale, None, Some("en_GB"))
+            .unwrap();
+        prefs
+            .set(
+                PreferenceKey::Timezone,
+                None,
+                Some("America/Argentina/Buenos_Aires"),
+            )
+            .unwrap();
+        assert_eq!(prefs.units, Some(Units::Imperial));
+        assert_eq!(prefs.locale.as_deref(), Some("en-GB"));
+        assert_eq!(
+            prefs.timezone.as_deref(),
+            Some("America/Argentina/Buenos_Aires")
+        );
+
+        prefs.set(PreferenceKey::Units, None, None).unwrap();
+        prefs.set(PreferenceKey::Locale, None, Some(" ")).unwrap();
+        assert_eq!(prefs.units, None);
+        assert_eq!(prefs.locale, None);
+    }
+
+    #[test]
+    fn set_rejects_invalid_values() {
+        let mut prefs = UserPreferences::default();
+        assert!(
+            prefs
+                .set(PreferenceKey::Units, None, Some("furlongs"))
+                .is_err()
+        );
+        assert!(
+            prefs
+                .set(PreferenceKey::Verbosity, None, Some("chatty"))
+                .is_err()
+        );
+        assert!(
+            prefs
+                .set(PreferenceKey::Timezone, None, Some("London time"))
+                .is_err()
+        );
+        assert!(
+            prefs
+                .set(PreferenceKey::Locale, None, Some("en--GB"))
+                .is_err()
+        );
+        assert!(
+            prefs
+                .set(PreferenceKey::Pronunciation, None, Some("shi-VAWN"))
+                .is_err()
+        );
+        assert!(prefs.is_empty());
+    }
+
+    #[test]
+    fn pronunciations_are_keyed_by_name() {
+        let mut prefs = UserPreferences::default();
+        prefs
+            .set(
+                PreferenceKey::Pronunciation,
+                Some("Siobhan"),
+                Some("shi-VAWN"),
+            )
+            .unwrap();
+        prefs
+            .set(PreferenceKey::Pronunciation, Some("Niamh"), Some("NEEV"))
+            .unwrap();
+        prefs
+            .set(PreferenceKey::Pronunciation, Some("Niamh"), None)
+            .unwrap();
+        assert_eq!(
+            prefs.describe(PreferenceKey::Pronunciation),
+            vec!["Pronunciation: \"Siobhan\" is said \"shi-VAWN\"".to_owned()]
+        );
+    }
+
+    #[test]
+    fn prompt_section_lists_set_preferences_only() {
+        let mut prefs = UserPreferences::default();
+        assert_eq!(prefs.prompt_section(), None);
+
+        prefs
+            .set(PreferenceKey::Timezone, None, Some("Europe/London"))
+            .unwrap();
+        prefs
+            .set(PreferenceKey::HomeCity, None, Some("Edinburgh"))
+            .unwrap();
+        prefs
+            .set(PreferenceKey::Verbosity, None, Some("brief"))
+            .unwrap();
+        let section = prefs.prompt_section().expect("section");
+        assert!(section.contains("\n- Time zone: Europe/London"));
+        assert!(section.contains("\n- Home city: Edinburgh"));
+        assert!(section.contains("\n- Reply length: brief"));
+        assert!(section.ends_with("Keep replies to a sentence or two."));
+        assert!(!section.contains("Units"));
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("nested").join("preferences.json");
+        assert!(UserPreferences::load(&path).is_empty());
+
+        let mut prefs = UserPreferences::default();
+        prefs
+            .set(PreferenceKey::Calendar, None, Some("Family"))
+            .unwrap();
+        prefs.save(&path).expect("save");
+        assert_eq!(UserPreferences::load(&path), prefs);
+    }
+}
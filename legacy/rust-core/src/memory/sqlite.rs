@@ -333,6 +333,68 @@ impl SqliteMemoryRepository {
         Ok(())
     }
 
+    /// Store a record received from another device (see [`crate::sync`]).
+    ///
+    /// Inserts it, or replaces the local copy when the incoming one was
+    /// updated later. Returns whether anything changed. The record's
+    /// embedding, if any, is rebuilt by the next reindex.
+    pub fn upsert_synced_record(&self, record: &MemoryRecord) -> Result<bool, SqliteMemoryError> {
+        let conn = self.lock()?;
+        let tags_json = serde_json::to_string(&record.tags).unwrap_or_else(|_| "[]".to_owned());
+        let metadata_json = record
+            .metadata
+            .as_ref()
+            .and_then(|m| serde_json::to_string(m).ok());
+
+        let rows = conn
+            .execute(
+                "INSERT INTO memory_records \
+                 (id, kind, status, text, confidence, source_turn_id, tags, supersedes, \
+                  created_at, updated_at, importance_score, stale_after_secs, metadata) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13) \
+                 ON CONFLICT(id) DO UPDATE SET \
+                  kind = excluded.kind, status = excluded.status, text = excluded.text, \
+                  confidence = excluded.confidence, source_turn_id = excluded.source_turn_id, \
+                  tags = excluded.tags, supersedes = excluded.supersedes, \
+                  updated_at = excluded.updated_at, \
+                  importance_score = excluded.importance_score, \
+                  stale_after_secs = excluded.stale_after_secs, metadata = excluded.metadata \
+                 WHERE excluded.updated_at > memory_records.updated_at",
+                params![
+                    record.id,
+                    kind_to_str(record.kind),
+                    status_to_str(record.status),
+                    record.text,
+                    record.confidence,
+                    record.source_turn_id,
+                    tags_json,
+                    record.supersedes,
+                    record.created_at,
+                    record.updated_at,
+                    record.importance_score,
+                    record.stale_after_secs,
+                    metadata_json,
+                ],
+            )
+            .map_err(SqliteMemoryError::Sqlite)?;
+        if rows == 0 {
+            return Ok(false);
+        }
+
+        conn.execute(
+            "INSERT INTO memory_audit (id, op, target_id, note, at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                new_id("audit"),
+                "patch",
+                record.id,
+                "synced from another device",
+                now_epoch_secs(),
+            ],
+        )
+        .map_err(SqliteMemoryError::Sqlite)?;
+        Ok(true)
+    }
+
     /// Insert a pre-existing audit entry verbatim (for JSONL→SQLite migration).
     pub fn insert_audit_raw(&self, entry: &MemoryAuditEntry) -> Result<(), SqliteMemoryError> {
         let conn = self.lock()?;
@@ -1453,6 +1515,41 @@ mod tests {
         assert_eq!(matches.len(), 1);
     }
 
+    #[test]
+    fn sqlite_upsert_synced_record_keeps_the_newer_copy() {
+        let (_dir, repo) = test_repo();
+
+        let mut record = MemoryRecord {
+            id: "synced-id".to_owned(),
+            kind: MemoryKind::Fact,
+            status: MemoryStatus::Active,
+            text: "likes tea".to_owned(),
+            confidence: 0.5,
+            source_turn_id: None,
+            tags: vec![],
+            supersedes: None,
+            created_at: 100,
+            updated_at: 200,
+            importance_score: None,
+            stale_after_secs: None,
+            metadata: None,
+        };
+        assert!(repo.upsert_synced_record(&record).expect("insert"));
+
+        record.text = "likes green tea".to_owned();
+        record.updated_at = 300;
+        assert!(repo.upsert_synced_record(&record).expect("newer"));
+
+        record.text = "likes coffee".to_owned();
+        record.updated_at = 250;
+        assert!(!repo.upsert_synced_record(&record).expect("older"));
+
+        let records = repo.list_records().expect("list");
+        let found = records.iter().find(|r| r.id == "synced-id").expect("find");
+        assert_eq!(found.text, "likes green tea");
+        assert_eq!(found.updated_at, 300);
+    }
+
     #[test]
     fn sqlite_insert_audit_raw_preserves_fields() {
         let (_dir, repo) = test_repo();
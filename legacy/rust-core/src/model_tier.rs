@@ -33,6 +33,8 @@
 
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 // ---------------------------------------------------------------------------
 // ModelTier enum
 // ---------------------------------------------------------------------------
@@ -52,7 +54,8 @@ use std::fmt;
 /// assert!(ModelTier::Flagship < ModelTier::Strong);
 /// assert_eq!(ModelTier::Flagship.rank(), 0);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ModelTier {
     /// Top-tier flagship models (Claude Opus, GPT-4o, O-series reasoning).
     Flagship,
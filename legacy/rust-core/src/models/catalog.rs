@@ -0,0 +1,332 @@
+//! Curated catalog of known-good STT, LLM, and TTS models.
+//!
+//! A versioned catalog is embedded in the binary and can be refreshed from a
+//! remote copy, which is cached on disk and used when its revision is newer.
+//! Managed defaults (see [`crate::config::recommended_local_model`]) always
+//! come from the embedded catalog so a refresh cannot change what loads at
+//! startup; refreshed entries extend the choices offered during onboarding
+//! and model selection.
+
+use crate::config::VoiceModelPreset;
+use crate::error::{Result, SpeechError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Catalog schema version this build understands.
+pub const CATALOG_SCHEMA_VERSION: u32 = 1;
+
+/// Default location of the published catalog.
+pub const DEFAULT_CATALOG_URL: &str = "https://raw.githubusercontent.com/saorsa-labs/fae/main/legacy/rust-core/src/models/catalog.json";
+
+const EMBEDDED_CATALOG_JSON: &str = include_str!("catalog.json");
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+/// What a catalog model is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogModelKind {
+    /// Speech-to-text.
+    Stt,
+    /// Language model.
+    Llm,
+    /// Text-to-speech.
+    Tts,
+}
+
+/// One model in the catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    /// Stable catalog identifier (e.g. `"qwen3-1.7b"`).
+    pub id: String,
+    /// What the model is used for.
+    pub kind: CatalogModelKind,
+    /// Human-readable name.
+    pub display_name: String,
+    /// HuggingFace repo the model downloads from.
+    pub repo_id: String,
+    /// Files fetched from the repo; the first is the weights file.
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Tokenizer repo, when it differs from `repo_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer_id: Option<String>,
+    /// Quantization of the downloaded weights.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<String>,
+    /// Approximate download size in bytes.
+    pub size_bytes: u64,
+    /// Minimum system RAM in GiB for comfortable use.
+    pub min_ram_gib: u32,
+    /// SPDX license identifier.
+    pub license: String,
+    /// Whether the model accepts images.
+    #[serde(default)]
+    pub vision: bool,
+    /// Voice model preset that forces this model, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<VoiceModelPreset>,
+    /// Whether the `auto` preset may pick this model by RAM.
+    #[serde(default)]
+    pub auto_select: bool,
+    /// Kept only so older configs are still recognised; not offered.
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+impl CatalogEntry {
+    /// Whether a machine with `total_memory_bytes` meets the RAM requirement.
+    /// Unknown memory only fits models with no requirement.
+    pub fn fits_memory(&self, total_memory_bytes: Option<u64>) -> bool {
+        let required = u64::from(self.min_ram_gib) * GIB;
+        match total_memory_bytes {
+            Some(bytes) => bytes >= required,
+            None => required == 0,
+        }
+    }
+
+    /// Weights file within the repo (empty for repo-directory models).
+    pub fn primary_file(&self) -> &str {
+        self.files.first().map_or("", String::as_str)
+    }
+}
+
+/// A versioned list of catalog entries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelCatalog {
+    /// Schema version; catalogs with another schema are rejected.
+    pub schema_version: u32,
+    /// Content revision; a higher revision supersedes a lower one.
+    pub revision: u32,
+    /// Catalog entries.
+    pub models: Vec<CatalogEntry>,
+}
+
+impl ModelCatalog {
+    /// Parse and check a catalog document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid, the schema version is not
+    /// supported, or entry IDs are duplicated.
+    pub fn parse(json: &str) -> Result<Self> {
+        let catalog: Self = serde_json::from_str(json)
+            .map_err(|e| SpeechError::Model(format!("invalid model catalog: {e}")))?;
+        if catalog.schema_version != CATALOG_SCHEMA_VERSION {
+            return Err(SpeechError::Model(format!(
+                "unsupported model catalog schema {} (expected {CATALOG_SCHEMA_VERSION})",
+                catalog.schema_version
+            )));
+        }
+        let mut ids = std::collections::HashSet::new();
+        for entry in &catalog.models {
+            if !ids.insert(entry.id.as_str()) {
+                return Err(SpeechError::Model(format!(
+                    "duplicate model catalog id '{}'",
+                    entry.id
+                )));
+            }
+        }
+        Ok(catalog)
+    }
+
+    /// Entries of `kind` that are offered to users (not deprecated).
+    pub fn offered(&self, kind: CatalogModelKind) -> impl Iterator<Item = &CatalogEntry> {
+        self.models
+            .iter()
+            .filter(move |e| e.kind == kind && !e.deprecated)
+    }
+
+    /// Look up an entry by catalog ID.
+    pub fn get(&self, id: &str) -> Option<&CatalogEntry> {
+        self.models.iter().find(|e| e.id == id)
+    }
+
+    /// Look up an entry by HuggingFace repo ID, including deprecated ones.
+    pub fn by_repo_id(&self, repo_id: &str) -> Option<&CatalogEntry> {
+        self.models.iter().find(|e| e.repo_id == repo_id)
+    }
+
+    /// The local LLM for `preset`.
+    ///
+    /// A forced preset maps to its entry regardless of RAM. `Auto` picks the
+    /// auto-selectable entry with the highest RAM requirement the machine
+    /// meets.
+    pub fn local_llm_for(
+        &self,
+        preset: VoiceModelPreset,
+        total_memory_bytes: Option<u64>,
+    ) -> Option<&CatalogEntry> {
+        let llms = || self.offered(CatalogModelKind::Llm);
+        match preset {
+            VoiceModelPreset::Auto => llms()
+                .filter(|e| e.auto_select && e.fits_memory(total_memory_bytes))
+                .max_by_key(|e| e.min_ram_gib),
+            forced => llms().find(|e| e.preset == Some(forced)),
+        }
+    }
+}
+
+/// The catalog compiled into this build.
+pub fn embedded() -> &'static ModelCatalog {
+    static EMBEDDED: OnceLock<ModelCatalog> = OnceLock::new();
+    EMBEDDED.get_or_init(|| {
+        ModelCatalog::parse(EMBEDDED_CATALOG_JSON).unwrap_or_else(|e| {
+            warn!("embedded model catalog is invalid: {e}");
+            ModelCatalog {
+                schema_version: CATALOG_SCHEMA_VERSION,
+                revision: 0,
+                models: Vec::new(),
+            }
+        })
+    })
+}
+
+/// Path of the cached remote catalog (`cache_dir()/model_catalog.json`).
+pub fn cached_catalog_path() -> PathBuf {
+    crate::fae_dirs::cache_dir().join("model_catalog.json")
+}
+
+/// The catalog to show users: the cached remote copy when it parses and is
+/// newer than the embedded one, otherwise the embedded catalog.
+pub fn active() -> ModelCatalog {
+    active_from(&cached_catalog_path())
+}
+
+fn active_from(cache_path: &Path) -> ModelCatalog {
+    let embedded = embedded();
+    let cached =
+        std::fs::read_to_string(cache_path).ok().and_then(|json| {
+            match ModelCatalog::parse(&json) {
+                Ok(catalog) => Some(catalog),
+                Err(e) => {
+                    warn!("ignoring cached model catalog: {e}");
+                    None
+                }
+            }
+        });
+    match cached {
+        Some(catalog) if catalog.revision > embedded.revision => catalog,
+        _ => embedded.clone(),
+    }
+}
+
+/// Fetch the catalog at `url` and cache it if it is newer than what is
+/// already active.
+///
+/// Returns the active catalog after the refresh.
+///
+/// # Errors
+///
+/// Returns an error if offline mode is on, the download fails, the
+/// document is invalid, or the cache cannot be written.
+pub fn refresh(url: &str) -> Result<ModelCatalog> {
+    crate::offline::ensure_online("model catalog refresh")
+        .map_err(|e| SpeechError::Model(e.to_string()))?;
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(10))
+        .timeout_read(Duration::from_secs(20))
+        .build();
+    let body = agent
+        .get(url)
+        .set("User-Agent", "fae/0.1 (model-catalog)")
+        .call()
+        .map_err(|e| SpeechError::Model(format!("model catalog request failed: {e}")))?
+        .into_string()
+        .map_err(|e| SpeechError::Model(format!("cannot read model catalog response: {e}")))?;
+    store_if_newer(&body, &cached_catalog_path())
+}
+
+fn store_if_newer(json: &str, cache_path: &Path) -> Result<ModelCatalog> {
+    let fetched = ModelCatalog::parse(json)?;
+    let current = active_from(cache_path);
+    if fetched.revision <= current.revision {
+        info!(
+            revision = fetched.revision,
+            active = current.revision,
+            "model catalog already up to date"
+        );
+        return Ok(current);
+    }
+    if let Some(parent) = cache_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = cache_path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, cache_path)?;
+    info!(revision = fetched.revision, "model catalog refreshed");
+    Ok(fetched)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn embedded_catalog_covers_every_preset() {
+        let catalog = embedded();
+        assert!(catalog.revision > 0);
+        for preset in [
+            VoiceModelPreset::Qwen3_8b,
+            VoiceModelPreset::Qwen3_4b,
+            VoiceModelPreset::Qwen3_1_7b,
+            VoiceModelPreset::Qwen3_0_6b,
+        ] {
+            let entry = catalog.local_llm_for(preset, None).unwrap();
+            assert!(!entry.primary_file().is_empty(), "{preset:?}");
+        }
+        assert!(catalog.offered(CatalogModelKind::Stt).count() >= 1);
+        assert!(catalog.offered(CatalogModelKind::Tts).count() >= 1);
+    }
+
+    #[test]
+    fn auto_selection_picks_largest_fitting_model() {
+        let catalog = embedded();
+        let pick = |gib: u64| {
+            catalog
+                .local_llm_for(VoiceModelPreset::Auto, Some(gib * GIB))
+                .map(|e| e.id.as_str())
+        };
+        assert_eq!(pick(64), Some("qwen3-8b"));
+        assert_eq!(pick(32), Some("qwen3-4b-instruct-2507"));
+        assert_eq!(pick(8), Some("qwen3-1.7b"));
+        assert_eq!(
+            catalog
+                .local_llm_for(VoiceModelPreset::Auto, None)
+                .map(|e| e.id.as_str()),
+            Some("qwen3-1.7b")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_schema_and_duplicates() {
+        assert!(ModelCatalog::parse(r#"{"schema_version":2,"revision":1,"models":[]}"#).is_err());
+        let entry = r#"{"id":"a","kind":"tts","display_name":"A","repo_id":"r","size_bytes":1,"min_ram_gib":0,"license":"mit"}"#;
+        let dup = format!(r#"{{"schema_version":1,"revision":1,"models":[{entry},{entry}]}}"#);
+        assert!(ModelCatalog::parse(&dup).is_err());
+    }
+
+    #[test]
+    fn newer_remote_catalog_is_cached_and_preferred() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model_catalog.json");
+
+        let mut newer = embedded().clone();
+        newer.revision += 1;
+        newer.models.retain(|e| e.kind != CatalogModelKind::Tts);
+        let json = serde_json::to_string(&newer).unwrap();
+
+        let active = store_if_newer(&json, &path).unwrap();
+        assert_eq!(active.revision, newer.revision);
+        assert_eq!(active_from(&path), newer);
+
+        let stale = serde_json::to_string(embedded()).unwrap();
+        let active = store_if_newer(&stale, &path).unwrap();
+        assert_eq!(active.revision, newer.revision);
+    }
+}
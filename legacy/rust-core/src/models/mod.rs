@@ -1,8 +1,10 @@
 //! Model downloading, caching, and management via hf-hub.
 
+pub mod catalog;
+
 use crate::config::ModelConfig;
 use crate::error::{Result, SpeechError};
-use crate::progress::{ProgressCallback, ProgressEvent};
+use crate::progress::{ProgressCallback, ProgressEvent, download_task_id};
 use indicatif::{ProgressBar, ProgressStyle};
 use std::io::Read;
 use std::path::PathBuf;
@@ -34,6 +36,9 @@ impl ModelManager {
     ///
     /// Returns an error if the model cannot be downloaded.
     pub fn get_model_path(&self, repo_id: &str, filename: &str) -> Result<PathBuf> {
+        if crate::offline::is_offline() {
+            return Self::cached_file(repo_id, filename);
+        }
         let api = hf_hub::api::sync::Api::new()
             .map_err(|e| SpeechError::Model(format!("failed to create HF API: {e}")))?;
 
@@ -55,6 +60,9 @@ impl ModelManager {
     ///
     /// Returns an error if the repo directory cannot be determined.
     pub fn get_repo_dir(&self, repo_id: &str) -> Result<PathBuf> {
+        if crate::offline::is_offline() {
+            return cached_repo_dir(repo_id).ok_or_else(|| offline_model_error(repo_id, None));
+        }
         let api = hf_hub::api::sync::Api::new()
             .map_err(|e| SpeechError::Model(format!("failed to create HF API: {e}")))?;
 
@@ -112,16 +120,19 @@ impl ModelManager {
         if let Some(path) = cache.model(repo_id.to_owned()).get(filename) {
             println!("  {repo_id}/{filename}  [cached]");
             if let Some(cb) = callback {
-                cb(ProgressEvent::Cached {
+                cb.emit(ProgressEvent::Cached {
                     repo_id: repo_id.to_owned(),
                     filename: filename.to_owned(),
                 });
             }
             return Ok(path);
         }
+        crate::offline::ensure_online("model download")
+            .map_err(|e| SpeechError::Model(e.to_string()))?;
+        let task = download_task(repo_id, filename, callback)?;
 
         if let Some(cb) = callback {
-            cb(ProgressEvent::DownloadStarted {
+            cb.emit(ProgressEvent::DownloadStarted {
                 repo_id: repo_id.to_owned(),
                 filename: filename.to_owned(),
                 total_bytes: None,
@@ -142,11 +153,14 @@ impl ModelManager {
         })?;
 
         if let Some(cb) = callback {
-            cb(ProgressEvent::DownloadComplete {
+            cb.emit(ProgressEvent::DownloadComplete {
                 repo_id: repo_id.to_owned(),
                 filename: filename.to_owned(),
             });
         }
+        if let Some(task) = task {
+            task.finish();
+        }
 
         Ok(path)
     }
@@ -190,16 +204,19 @@ impl ModelManager {
         if dest.exists() {
             println!("  {filename}  [cached]");
             if let Some(cb) = callback {
-                cb(ProgressEvent::Cached {
+                cb.emit(ProgressEvent::Cached {
                     repo_id: url.to_owned(),
                     filename: filename.to_owned(),
                 });
             }
             return Ok(dest);
         }
+        crate::offline::ensure_online("model download")
+            .map_err(|e| SpeechError::Model(e.to_string()))?;
+        let task = download_task(url, filename, callback)?;
 
         if let Some(cb) = callback {
-            cb(ProgressEvent::DownloadStarted {
+            cb.emit(ProgressEvent::DownloadStarted {
                 repo_id: url.to_owned(),
                 filename: filename.to_owned(),
                 total_bytes: None,
@@ -243,24 +260,33 @@ impl ModelManager {
             pb.inc(n as u64);
             bytes_downloaded += n as u64;
             if let Some(cb) = callback {
-                cb(ProgressEvent::DownloadProgress {
+                cb.emit(ProgressEvent::DownloadProgress {
                     repo_id: url.to_owned(),
                     filename: filename.to_owned(),
                     bytes_downloaded,
                     total_bytes,
                 });
             }
+            if let Some(task) = &task {
+                task.check_cancelled()?;
+                if let Some(total) = total_bytes {
+                    task.progress(bytes_downloaded, total);
+                }
+            }
         }
         pb.finish();
 
         std::fs::rename(&tmp, &dest)?;
 
         if let Some(cb) = callback {
-            cb(ProgressEvent::DownloadComplete {
+            cb.emit(ProgressEvent::DownloadComplete {
                 repo_id: url.to_owned(),
                 filename: filename.to_owned(),
             });
         }
+        if let Some(task) = task {
+            task.finish();
+        }
 
         Ok(dest)
     }
@@ -297,6 +323,38 @@ impl ModelManager {
     pub fn cache_dir(&self) -> &PathBuf {
         &self.cache_dir
     }
+
+    /// Resolve a file from the local hf-hub cache without touching the
+    /// network. Used in offline mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is not cached.
+    pub fn cached_file(repo_id: &str, filename: &str) -> Result<PathBuf> {
+        hf_hub::Cache::default()
+            .model(repo_id.to_owned())
+            .get(filename)
+            .ok_or_else(|| offline_model_error(repo_id, Some(filename)))
+    }
+}
+
+/// Snapshot directory of a cached HuggingFace repo, if one is on disk.
+fn cached_repo_dir(repo_id: &str) -> Option<PathBuf> {
+    let cache = hf_hub::Cache::default();
+    let repo = hf_hub::Repo::model(repo_id.to_owned());
+    let repo_path = cache.path().join(repo.folder_name());
+    let commit = std::fs::read_to_string(repo_path.join("refs").join(repo.revision())).ok()?;
+    let dir = repo_path.join("snapshots").join(commit.trim());
+    dir.is_dir().then_some(dir)
+}
+
+fn offline_model_error(repo_id: &str, filename: Option<&str>) -> SpeechError {
+    let what = match filename {
+        Some(f) => format!("downloading {repo_id}/{f}"),
+        None => format!("downloading {repo_id}"),
+    };
+    let reason = crate::offline::OfflineError { what };
+    SpeechError::Model(format!("{reason} and it is not cached"))
 }
 
 /// Query the size of a single file from HuggingFace Hub using a HEAD request.
@@ -305,6 +363,9 @@ impl ModelManager {
 /// `content-length`. This avoids downloading the file just to check its size.
 fn query_single_file_size(repo_id: &str, filename: &str) -> Option<u64> {
     // HF Hub file URL pattern: https://huggingface.co/{repo_id}/resolve/main/{filename}
+    if crate::offline::is_offline() {
+        return None;
+    }
     let url = format!("https://huggingface.co/{repo_id}/resolve/main/{filename}");
 
     // Use a HEAD request to get content-length without downloading
@@ -317,6 +378,24 @@ fn query_single_file_size(repo_id: &str, filename: &str) -> Option<u64> {
         .and_then(|v| v.parse::<u64>().ok())
 }
 
+/// Announce downloading `filename` as a subtask of `callback`, failing if
+/// the download was cancelled before it started.
+fn download_task(
+    repo_id: &str,
+    filename: &str,
+    callback: Option<&ProgressCallback>,
+) -> Result<Option<ProgressCallback>> {
+    let Some(cb) = callback else {
+        return Ok(None);
+    };
+    cb.check_cancelled()?;
+    Ok(Some(cb.subtask(
+        &download_task_id(repo_id, filename),
+        filename,
+        1.0,
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
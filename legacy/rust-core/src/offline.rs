@@ -0,0 +1,124 @@
+//! Offline mode: a process-wide switch that blocks network egress.
+//!
+//! When offline mode is on, every module that talks to the network calls
+//! [`ensure_online`] (or [`ensure_url_allowed`] for configurable endpoints)
+//! before opening a connection and fails with an [`OfflineError`] instead.
+//! Loopback endpoints stay reachable so a locally hosted TTS server or daemon
+//! keeps working.
+//!
+//! The switch is set from `SpeechConfig::offline_mode` at startup and can be
+//! flipped at runtime through `config.patch`, a voice command, or the FFI.
+
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// A network operation was refused because offline mode is on.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("offline mode is on; {what} is disabled")]
+pub struct OfflineError {
+    /// What was blocked (e.g. `"model download"`).
+    pub what: String,
+}
+
+/// Whether offline mode is on.
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::SeqCst)
+}
+
+/// Turn offline mode on or off. Returns the previous setting.
+pub fn set_offline(offline: bool) -> bool {
+    let previous = OFFLINE.swap(offline, Ordering::SeqCst);
+    if previous != offline {
+        tracing::info!(offline, "offline mode changed");
+    }
+    previous
+}
+
+/// Fail if offline mode is on.
+///
+/// # Errors
+///
+/// Returns [`OfflineError`] naming `what` when offline mode is on.
+pub fn ensure_online(what: &str) -> Result<(), OfflineError> {
+    check(is_offline(), what)
+}
+
+/// Fail if offline mode is on and `url` does not point at this machine.
+///
+/// # Errors
+///
+/// Returns [`OfflineError`] naming `what` when offline mode is on and the
+/// URL's host is not a loopback address or `localhost`.
+pub fn ensure_url_allowed(what: &str, url: &str) -> Result<(), OfflineError> {
+    check(is_offline() && !is_loopback_url(url), what)
+}
+
+fn check(blocked: bool, what: &str) -> Result<(), OfflineError> {
+    if blocked {
+        tracing::debug!(what, "blocked by offline mode");
+        return Err(OfflineError {
+            what: what.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Whether `url` points at `localhost` or a loopback address.
+pub fn is_loopback_url(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+    match parsed.host() {
+        Some(url::Host::Domain(host)) => is_loopback_host(host),
+        Some(url::Host::Ipv4(ip)) => IpAddr::V4(ip).is_loopback(),
+        Some(url::Host::Ipv6(ip)) => IpAddr::V6(ip).is_loopback(),
+        None => false,
+    }
+}
+
+/// Whether `host` (a name or IP address) is `localhost` or a loopback
+/// address.
+pub fn is_loopback_host(host: &str) -> bool {
+    let host = host
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    match host.parse::<IpAddr>() {
+        Ok(ip) => ip.is_loopback(),
+        Err(_) => host == "localhost" || host.ends_with(".localhost"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loopback_urls_are_recognised() {
+        assert!(is_loopback_url("http://localhost:8080/tts"));
+        assert!(is_loopback_url("http://127.0.0.1:12700"));
+        assert!(is_loopback_url("ws://[::1]:9000/canvas"));
+        assert!(!is_loopback_url("https://huggingface.co/api/models"));
+        assert!(!is_loopback_url("http://192.168.1.10:8080"));
+        assert!(!is_loopback_url("not a url"));
+
+        assert!(is_loopback_host("LOCALHOST."));
+        assert!(is_loopback_host("127.0.0.2"));
+        assert!(is_loopback_host("[::1]"));
+        assert!(!is_loopback_host("pypi.org"));
+    }
+
+    #[test]
+    fn blocked_check_names_the_operation() {
+        assert!(check(false, "web search").is_ok());
+        let err = check(true, "model download").err();
+        assert_eq!(
+            err.map(|e| e.to_string()).as_deref(),
+            Some("offline mode is on; model download is disabled")
+        );
+    }
+}
@@ -9,9 +9,24 @@
 //! The current phase is persisted in [`crate::config::SpeechConfig`] and
 //! exposed to the Swift shell via the `onboarding.get_state`,
 //! `onboarding.advance`, and `onboarding.complete` host commands.
+//!
+//! Alongside the phases, the shell can run an audio calibration
+//! ([`CalibrationSession`]) through the `onboarding.calibration.*` commands:
+//!
+//! ```text
+//! AmbientNoise → ReadSentence → EchoTest → Complete
+//! ```
+//!
+//! Each step is one recording; `onboarding.calibration.finish` turns the
+//! measurements into a [`crate::config::CalibrationProfile`] for the active
+//! input device.
 
+use crate::audio::calibration;
+use crate::config::{AgcConfig, CalibrationProfile};
+use crate::error::{Result, SpeechError};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::time::Duration;
 
 /// The four phases of the Fae onboarding experience.
 ///
@@ -106,6 +121,209 @@ impl fmt::Display for OnboardingPhase {
     }
 }
 
+/// A step of the audio calibration flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationStep {
+    /// Record room tone to find the noise floor.
+    AmbientNoise,
+    /// Record the user reading the calibration sentence.
+    ReadSentence,
+    /// Play a test tone and listen for it at the microphone.
+    EchoTest,
+    /// All measurements taken; the profile can be saved.
+    Complete,
+}
+
+impl CalibrationStep {
+    /// Canonical wire-format string for this step.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AmbientNoise => "ambient_noise",
+            Self::ReadSentence => "read_sentence",
+            Self::EchoTest => "echo_test",
+            Self::Complete => "complete",
+        }
+    }
+
+    /// How long the step records for (zero once complete).
+    #[must_use]
+    pub fn duration(self) -> Duration {
+        match self {
+            Self::AmbientNoise => calibration::AMBIENT_DURATION,
+            Self::ReadSentence => calibration::SENTENCE_DURATION,
+            Self::EchoTest => calibration::ECHO_TONE_DURATION + Duration::from_millis(700),
+            Self::Complete => Duration::ZERO,
+        }
+    }
+
+    /// What to tell the user before this step, in the active language.
+    #[must_use]
+    pub fn instruction(self) -> &'static str {
+        crate::i18n::text(match self {
+            Self::AmbientNoise => "calibration.ambient",
+            Self::ReadSentence => "calibration.read",
+            Self::EchoTest => "calibration.echo",
+            Self::Complete => "calibration.complete",
+        })
+    }
+}
+
+/// An in-progress audio calibration for one input device.
+///
+/// The session only holds measurements; recording is done by the caller,
+/// which brackets each recording with [`begin_measurement`] and
+/// [`record`] (or [`abort_measurement`] if recording failed).
+///
+/// [`begin_measurement`]: CalibrationSession::begin_measurement
+/// [`record`]: CalibrationSession::record
+/// [`abort_measurement`]: CalibrationSession::abort_measurement
+#[derive(Debug, Clone)]
+pub struct CalibrationSession {
+    device: String,
+    sample_rate: u32,
+    step: CalibrationStep,
+    measuring: bool,
+    /// Room tone, kept as the baseline for the echo test.
+    ambient: Vec<f32>,
+    noise_floor: Option<f32>,
+    speech_level: Option<f32>,
+    echo_return_db: Option<f32>,
+}
+
+impl CalibrationSession {
+    /// Start calibrating `device`, recorded at `sample_rate`.
+    pub fn new(device: impl Into<String>, sample_rate: u32) -> Self {
+        Self {
+            device: device.into(),
+            sample_rate,
+            step: CalibrationStep::AmbientNoise,
+            measuring: false,
+            ambient: Vec::new(),
+            noise_floor: None,
+            speech_level: None,
+            echo_return_db: None,
+        }
+    }
+
+    /// The input device being calibrated.
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    /// The step the next measurement is for.
+    pub fn step(&self) -> CalibrationStep {
+        self.step
+    }
+
+    /// Whether a recording is in progress.
+    pub fn is_measuring(&self) -> bool {
+        self.measuring
+    }
+
+    /// Mark the current step as being recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a recording is already running or every step is
+    /// done.
+    pub fn begin_measurement(&mut self) -> Result<CalibrationStep> {
+        if self.measuring {
+            return Err(SpeechError::Audio(
+                "calibration measurement already in progress".to_owned(),
+            ));
+        }
+        if self.step == CalibrationStep::Complete {
+            return Err(SpeechError::Audio(
+                "calibration has no steps left to measure".to_owned(),
+            ));
+        }
+        self.measuring = true;
+        Ok(self.step)
+    }
+
+    /// Give up on the current recording; the step can be retried.
+    pub fn abort_measurement(&mut self) {
+        self.measuring = false;
+    }
+
+    /// Feed the recording for the current step and move to the next step.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, staying on the step, if the sentence recording
+    /// holds too little speech.
+    pub fn record(&mut self, samples: &[f32]) -> Result<()> {
+        self.measuring = false;
+        match self.step {
+            CalibrationStep::AmbientNoise => {
+                self.noise_floor = Some(calibration::noise_floor(samples, self.sample_rate));
+                self.ambient = samples.to_vec();
+                self.step = CalibrationStep::ReadSentence;
+            }
+            CalibrationStep::ReadSentence => {
+                let floor = self.noise_floor.unwrap_or(0.0);
+                let level = calibration::speech_level(samples, self.sample_rate, floor)
+                    .ok_or_else(|| {
+                        SpeechError::Audio(crate::i18n::text("calibration.no_speech").to_owned())
+                    })?;
+                self.speech_level = Some(level);
+                self.step = CalibrationStep::EchoTest;
+            }
+            CalibrationStep::EchoTest => {
+                self.echo_return_db = Some(calibration::echo_return_db(
+                    &self.ambient,
+                    samples,
+                    self.sample_rate,
+                ));
+                self.step = CalibrationStep::Complete;
+            }
+            CalibrationStep::Complete => {}
+        }
+        Ok(())
+    }
+
+    /// Build the calibration profile from the completed measurements.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if steps are missing or speech was too quiet
+    /// relative to the room.
+    pub fn profile(
+        &self,
+        agc: &AgcConfig,
+        output_device: Option<String>,
+    ) -> Result<CalibrationProfile> {
+        match (self.noise_floor, self.speech_level, self.echo_return_db) {
+            (Some(noise), Some(speech), Some(echo)) => {
+                calibration::derive_profile(noise, speech, echo, agc, output_device)
+            }
+            _ => Err(SpeechError::Audio(format!(
+                "calibration is not finished (next step: {})",
+                self.step.as_str()
+            ))),
+        }
+    }
+
+    /// Snapshot for the host: current step, instructions and measurements.
+    pub fn status(&self) -> serde_json::Value {
+        let sentence = (self.step == CalibrationStep::ReadSentence)
+            .then(|| crate::i18n::text("calibration.sentence"));
+        serde_json::json!({
+            "active": true,
+            "device": self.device,
+            "step": self.step.as_str(),
+            "measuring": self.measuring,
+            "duration_ms": self.step.duration().as_millis() as u64,
+            "instruction": self.step.instruction(),
+            "sentence": sentence,
+            "noise_floor_rms": self.noise_floor,
+            "speech_rms": self.speech_level,
+            "echo_return_db": self.echo_return_db,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +386,43 @@ mod tests {
             assert_eq!(parsed, phase);
         }
     }
+
+    #[test]
+    fn calibration_session_walks_through_steps() {
+        let rate = 16_000;
+        let mut session = CalibrationSession::new("default", rate);
+        assert_eq!(session.step(), CalibrationStep::AmbientNoise);
+        assert!(session.profile(&AgcConfig::default(), None).is_err());
+
+        let room: Vec<f32> = (0..rate * 2)
+            .map(|i| if i % 2 == 0 { 0.002 } else { -0.002 })
+            .collect();
+        assert_eq!(
+            session.begin_measurement().ok(),
+            Some(CalibrationStep::AmbientNoise)
+        );
+        assert!(session.begin_measurement().is_err(), "already measuring");
+        session.record(&room).expect("ambient");
+        assert_eq!(session.step(), CalibrationStep::ReadSentence);
+
+        // Silence instead of speech: the step has to be repeated.
+        session.begin_measurement().expect("begin sentence");
+        assert!(session.record(&room).is_err());
+        assert_eq!(session.step(), CalibrationStep::ReadSentence);
+        assert!(!session.is_measuring());
+
+        let speech: Vec<f32> = room.iter().map(|s| s * 10.0).collect();
+        session.begin_measurement().expect("begin sentence");
+        session.record(&speech).expect("sentence");
+        session.begin_measurement().expect("begin echo");
+        session.record(&room).expect("echo");
+        assert_eq!(session.step(), CalibrationStep::Complete);
+        assert!(session.begin_measurement().is_err());
+
+        let profile = session
+            .profile(&AgcConfig::default(), None)
+            .expect("profile");
+        assert!((profile.speech_rms - 0.02).abs() < 1e-4);
+        assert!(!profile.aec_required);
+    }
 }
@@ -0,0 +1,318 @@
+//! Ledger of permission-gated tool executions and the periodic review built
+//! from it.
+//!
+//! Every time a gated tool runs under a granted permission an entry is
+//! appended to [`crate::fae_dirs::permission_usage_file`]. The monthly
+//! `permission_review` scheduler task compares the ledger with the current
+//! grants and asks the user whether to revoke permissions nothing used.
+
+use crate::audit_log::append_audit_line;
+use crate::error::{Result, SpeechError};
+use crate::permissions::{PermissionKind, PermissionStore};
+use crate::scheduler::tasks::{PromptAction, TaskResult, UserPrompt};
+use crate::time_util::now_epoch_secs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// How far back a review looks.
+pub const REVIEW_WINDOW_SECS: u64 = 30 * 24 * 3600;
+
+/// How long ledger entries are kept.
+const LEDGER_RETENTION_SECS: u64 = 3 * REVIEW_WINDOW_SECS;
+
+/// Prompt action prefix for revoking permissions; followed by a
+/// comma-separated list of permission kinds.
+pub const REVOKE_ACTION_PREFIX: &str = "revoke_permissions:";
+
+/// One permission-gated tool execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionUsageEntry {
+    pub timestamp_secs: u64,
+    pub kind: PermissionKind,
+    /// Tool that ran, e.g. `list_calendar_events`.
+    pub tool: String,
+}
+
+/// Append a use of `kind` by `tool` to the ledger at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the ledger cannot be opened or written.
+pub fn record_permission_use(path: &Path, kind: PermissionKind, tool: &str) -> Result<()> {
+    let entry = PermissionUsageEntry {
+        timestamp_secs: now_epoch_secs(),
+        kind,
+        tool: tool.to_owned(),
+    };
+    Ok(append_audit_line(path, &entry)?)
+}
+
+/// Read all ledger entries from `path`, oldest first. Malformed lines are
+/// skipped.
+///
+/// # Errors
+///
+/// Returns an error if the ledger exists but cannot be read.
+pub fn read_permission_usage(path: &Path) -> Result<Vec<PermissionUsageEntry>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(entry) = serde_json::from_str(&line?) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// Drop ledger entries older than `cutoff_secs`.
+fn prune_permission_usage(
+    path: &Path,
+    entries: &[PermissionUsageEntry],
+    cutoff_secs: u64,
+) -> Result<()> {
+    if entries.iter().all(|e| e.timestamp_secs >= cutoff_secs) {
+        return Ok(());
+    }
+    let mut kept = String::new();
+    for entry in entries.iter().filter(|e| e.timestamp_secs >= cutoff_secs) {
+        let line = serde_json::to_string(entry).map_err(|e| {
+            SpeechError::Config(format!("permission ledger serialization failed: {e}"))
+        })?;
+        kept.push_str(&line);
+        kept.push('\n');
+    }
+    std::fs::write(path, kept)?;
+    Ok(())
+}
+
+/// Use of one granted permission during the review window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionUse {
+    pub kind: PermissionKind,
+    pub count: usize,
+    pub last_used_secs: u64,
+}
+
+/// Which granted permissions were used since `since_secs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionReview {
+    pub since_secs: u64,
+    pub used: Vec<PermissionUse>,
+    pub unused: Vec<PermissionKind>,
+}
+
+impl PermissionReview {
+    /// Compare the current grants with ledger entries since `since_secs`.
+    ///
+    /// The microphone is left out: Fae cannot work without it.
+    pub fn build(
+        store: &PermissionStore,
+        entries: &[PermissionUsageEntry],
+        since_secs: u64,
+    ) -> Self {
+        let mut uses: BTreeMap<String, PermissionUse> = BTreeMap::new();
+        for entry in entries.iter().filter(|e| e.timestamp_secs >= since_secs) {
+            let usage = uses.entry(entry.kind.to_string()).or_insert(PermissionUse {
+                kind: entry.kind,
+                count: 0,
+                last_used_secs: 0,
+            });
+            usage.count += 1;
+            usage.last_used_secs = usage.last_used_secs.max(entry.timestamp_secs);
+        }
+
+        let mut used = Vec::new();
+        let mut unused = Vec::new();
+        for kind in store.all_granted() {
+            if kind == PermissionKind::Microphone {
+                continue;
+            }
+            match uses.remove(&kind.to_string()) {
+                Some(usage) => used.push(usage),
+                None => unused.push(kind),
+            }
+        }
+        Self {
+            since_secs,
+            used,
+            unused,
+        }
+    }
+
+    /// The review as a scheduler prompt, or `None` when nothing is granted.
+    pub fn prompt(&self) -> Option<UserPrompt> {
+        if self.used.is_empty() && self.unused.is_empty() {
+            return None;
+        }
+
+        let mut lines =
+            vec!["Here is how your permissions were used in the last month.".to_owned()];
+        for usage in &self.used {
+            let times = if usage.count == 1 { "time" } else { "times" };
+            lines.push(format!("- {}: used {} {times}", usage.kind, usage.count));
+        }
+        let mut actions = Vec::new();
+        if !self.unused.is_empty() {
+            let names: Vec<String> = self.unused.iter().map(ToString::to_string).collect();
+            lines.push(format!("Not used: {}.", names.join(", ")));
+            lines.push(
+                "You can revoke what you no longer need and grant it again later.".to_owned(),
+            );
+            actions.push(PromptAction {
+                label: "Revoke unused".to_owned(),
+                id: format!("{REVOKE_ACTION_PREFIX}{}", names.join(",")),
+            });
+        }
+        actions.push(PromptAction {
+            label: "Keep all".to_owned(),
+            id: "acknowledge_scheduler_prompt".to_owned(),
+        });
+
+        Some(UserPrompt {
+            title: "Permission review".to_owned(),
+            message: lines.join("\n"),
+            actions,
+        })
+    }
+}
+
+/// Permissions named by a revoke prompt action, or `None` for other actions.
+///
+/// Unknown kinds are skipped.
+pub fn parse_revoke_action(action: &str) -> Option<Vec<PermissionKind>> {
+    let kinds = action.strip_prefix(REVOKE_ACTION_PREFIX)?;
+    Some(
+        kinds
+            .split(',')
+            .filter_map(|kind| kind.trim().parse().ok())
+            .collect(),
+    )
+}
+
+/// Build the monthly permission review from the ledger at `path`, pruning
+/// entries past retention.
+pub fn run_permission_review(store: &PermissionStore, path: &Path) -> TaskResult {
+    let entries = match read_permission_usage(path) {
+        Ok(entries) => entries,
+        Err(e) => return TaskResult::Error(format!("cannot read permission ledger: {e}")),
+    };
+    let now = now_epoch_secs();
+    if let Err(e) =
+        prune_permission_usage(path, &entries, now.saturating_sub(LEDGER_RETENTION_SECS))
+    {
+        tracing::warn!("cannot prune permission ledger: {e}");
+    }
+
+    let review = PermissionReview::build(store, &entries, now.saturating_sub(REVIEW_WINDOW_SECS));
+    match review.prompt() {
+        Some(prompt) => TaskResult::NeedsUserAction(prompt),
+        None => TaskResult::Success("no permissions granted; nothing to review".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn entry(kind: PermissionKind, tool: &str, timestamp_secs: u64) -> PermissionUsageEntry {
+        PermissionUsageEntry {
+            timestamp_secs,
+            kind,
+            tool: tool.to_owned(),
+        }
+    }
+
+    #[test]
+    fn ledger_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.jsonl");
+        assert!(read_permission_usage(&path).unwrap().is_empty());
+
+        record_permission_use(&path, PermissionKind::Calendar, "list_calendar_events").unwrap();
+        record_permission_use(&path, PermissionKind::Mail, "search_mail").unwrap();
+
+        let entries = read_permission_usage(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, PermissionKind::Calendar);
+        assert_eq!(entries[1].tool, "search_mail");
+    }
+
+    #[test]
+    fn review_splits_used_and_unused_grants() {
+        let mut store = PermissionStore::default();
+        store.grant(PermissionKind::Microphone);
+        store.grant(PermissionKind::Calendar);
+        store.grant(PermissionKind::Contacts);
+        store.grant(PermissionKind::Mail);
+
+        let entries = vec![
+            entry(PermissionKind::Calendar, "list_calendar_events", 100),
+            entry(PermissionKind::Calendar, "create_calendar_event", 300),
+            entry(PermissionKind::Mail, "search_mail", 10),
+            entry(PermissionKind::Camera, "camera", 200),
+        ];
+        let review = PermissionReview::build(&store, &entries, 50);
+
+        assert_eq!(
+            review.used,
+            vec![PermissionUse {
+                kind: PermissionKind::Calendar,
+                count: 2,
+                last_used_secs: 300,
+            }]
+        );
+        assert_eq!(
+            review.unused,
+            vec![PermissionKind::Contacts, PermissionKind::Mail]
+        );
+
+        let prompt = review.prompt().unwrap();
+        assert!(prompt.message.contains("calendar: used 2 times"));
+        assert!(prompt.message.contains("Not used: contacts, mail."));
+        let revoke = &prompt.actions[0].id;
+        assert_eq!(
+            parse_revoke_action(revoke).unwrap(),
+            vec![PermissionKind::Contacts, PermissionKind::Mail]
+        );
+        assert!(parse_revoke_action("acknowledge_scheduler_prompt").is_none());
+    }
+
+    #[test]
+    fn review_without_grants_has_no_prompt() {
+        let dir = tempfile::tempdir().unwrap();
+        let result =
+            run_permission_review(&PermissionStore::default(), &dir.path().join("usage.jsonl"));
+        assert!(matches!(result, TaskResult::Success(_)));
+    }
+
+    #[test]
+    fn review_prunes_entries_past_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.jsonl");
+        let old = entry(PermissionKind::Mail, "search_mail", 1);
+        let recent = entry(PermissionKind::Mail, "get_mail", now_epoch_secs());
+        let lines = format!(
+            "{}\n{}\n",
+            serde_json::to_string(&old).unwrap(),
+            serde_json::to_string(&recent).unwrap()
+        );
+        std::fs::write(&path, lines).unwrap();
+
+        let mut store = PermissionStore::default();
+        store.grant(PermissionKind::Mail);
+        let result = run_permission_review(&store, &path);
+        let TaskResult::NeedsUserAction(prompt) = result else {
+            unreachable!("expected a review prompt");
+        };
+        assert!(prompt.message.contains("mail: used 1 time"));
+        assert_eq!(read_permission_usage(&path).unwrap(), vec![recent]);
+    }
+}
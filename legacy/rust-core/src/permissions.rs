@@ -12,11 +12,20 @@
 //! the tool availability gate and the command handler that processes
 //! `capability.grant` commands).  Use [`PermissionStore::into_shared`] to
 //! convert a store into a shareable handle.
+//!
+//! ## Scopes and expiry
+//!
+//! A grant can be narrowed with a [`PermissionScope`] (read-only, or limited
+//! to filesystem paths) and can expire ("allow for 1 hour").  Expired grants
+//! stay in the store so [`PermissionStore::list`] can show when they lapsed,
+//! but no longer count as granted.
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::oneshot;
 
 /// A system capability that Fae can request access to.
@@ -43,8 +52,10 @@ pub enum PermissionKind {
     Camera,
     /// Desktop automation (AppleScript, accessibility).
     DesktopAutomation,
-    /// Network access for x0x gossip mesh operations.
+    /// Network access for x0x gossip mesh operations and network diagnostics.
     Network,
+    /// Safe system toggles (volume, brightness, dark mode, Wi-Fi).
+    SystemSettings,
 }
 
 impl PermissionKind {
@@ -62,6 +73,7 @@ impl PermissionKind {
             PermissionKind::Camera,
             PermissionKind::DesktopAutomation,
             PermissionKind::Network,
+            PermissionKind::SystemSettings,
         ]
     }
 }
@@ -80,6 +92,7 @@ impl fmt::Display for PermissionKind {
             PermissionKind::Camera => "camera",
             PermissionKind::DesktopAutomation => "desktop_automation",
             PermissionKind::Network => "network",
+            PermissionKind::SystemSettings => "system_settings",
         };
         f.write_str(s)
     }
@@ -101,6 +114,7 @@ impl FromStr for PermissionKind {
             "camera" => Ok(PermissionKind::Camera),
             "desktop_automation" | "desktopautomation" => Ok(PermissionKind::DesktopAutomation),
             "network" => Ok(PermissionKind::Network),
+            "system_settings" | "systemsettings" => Ok(PermissionKind::SystemSettings),
             _ => Err(PermissionParseError(s.to_owned())),
         }
     }
@@ -156,8 +170,98 @@ impl fmt::Display for PermissionParseError {
 
 impl std::error::Error for PermissionParseError {}
 
+/// How much of a permission a grant covers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionScope {
+    /// Everything the permission allows.
+    #[default]
+    Full,
+    /// Reading only; tools that change data are refused.
+    ReadOnly,
+    /// Only files under these absolute paths.
+    Paths(Vec<PathBuf>),
+}
+
+impl PermissionScope {
+    fn is_full(&self) -> bool {
+        matches!(self, Self::Full)
+    }
+
+    /// Whether this scope covers `access`.
+    pub fn allows(&self, access: PermissionAccess<'_>) -> bool {
+        match (self, access) {
+            (Self::Full, _) => true,
+            (Self::ReadOnly, access) => !access.is_write(),
+            (Self::Paths(roots), PermissionAccess::Path { path, .. }) => roots.iter().any(|root| {
+                path.starts_with(root)
+                    || root.canonicalize().is_ok_and(|root| path.starts_with(root))
+            }),
+            (Self::Paths(_), _) => false,
+        }
+    }
+}
+
+impl fmt::Display for PermissionScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => f.write_str("full"),
+            Self::ReadOnly => f.write_str("read_only"),
+            Self::Paths(roots) => {
+                let roots: Vec<String> = roots.iter().map(|p| p.display().to_string()).collect();
+                f.write_str(&roots.join(","))
+            }
+        }
+    }
+}
+
+impl FromStr for PermissionScope {
+    type Err = PermissionParseError;
+
+    /// Parse `full`/`write`, `read`/`read_only`, or a comma-separated list
+    /// of absolute paths.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "full" | "write" | "read_write" => return Ok(Self::Full),
+            "read" | "read_only" | "readonly" => return Ok(Self::ReadOnly),
+            _ => {}
+        }
+        let roots: Vec<PathBuf> = s
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .collect();
+        if roots.is_empty() || roots.iter().any(|p| !p.is_absolute()) {
+            return Err(PermissionParseError(s.to_owned()));
+        }
+        Ok(Self::Paths(roots))
+    }
+}
+
+/// What a caller wants to do under a permission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionAccess<'a> {
+    /// Read data.
+    Read,
+    /// Create, change, or delete data.
+    Write,
+    /// Read or write a specific file.
+    Path { path: &'a Path, write: bool },
+}
+
+impl PermissionAccess<'_> {
+    fn is_write(self) -> bool {
+        match self {
+            Self::Read => false,
+            Self::Write => true,
+            Self::Path { write, .. } => write,
+        }
+    }
+}
+
 /// A single permission grant record.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PermissionGrant {
     /// Which permission this grant covers.
     pub kind: PermissionKind,
@@ -165,6 +269,31 @@ pub struct PermissionGrant {
     pub granted: bool,
     /// Epoch seconds when the grant was last updated.
     pub granted_at: Option<u64>,
+    /// What the grant covers.
+    #[serde(default, skip_serializing_if = "PermissionScope::is_full")]
+    pub scope: PermissionScope,
+    /// Epoch seconds after which the grant no longer applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl PermissionGrant {
+    /// Whether the grant applies at epoch second `now`.
+    pub fn is_active(&self, now: u64) -> bool {
+        self.granted && self.expires_at.is_none_or(|at| now < at)
+    }
+}
+
+/// Snapshot of one grant for settings and diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GrantStatus {
+    pub kind: PermissionKind,
+    pub granted: bool,
+    /// Granted and not expired.
+    pub active: bool,
+    pub scope: PermissionScope,
+    pub granted_at: Option<u64>,
+    pub expires_at: Option<u64>,
 }
 
 /// Persistent store of permission grants.
@@ -203,28 +332,72 @@ impl PermissionStore {
 }
 
 impl PermissionStore {
-    /// Check whether a specific permission is currently granted.
+    /// Check whether a specific permission is currently granted, in any
+    /// scope, and has not expired.
     pub fn is_granted(&self, kind: PermissionKind) -> bool {
+        self.active_grant(kind).is_some()
+    }
+
+    /// Check whether a current grant for `kind` covers `access`.
+    pub fn allows(&self, kind: PermissionKind, access: PermissionAccess<'_>) -> bool {
+        self.active_grant(kind)
+            .is_some_and(|g| g.scope.allows(access))
+    }
+
+    /// Check whether the file tools may touch `path`.
+    ///
+    /// The file tools are governed by the tool mode and need no `Files`
+    /// grant. A narrowed `Files` grant (read-only, or a list of folders)
+    /// confines them to its scope, and to nothing once it expires.
+    pub fn allows_file(&self, path: &Path, write: bool) -> bool {
+        let Some(grant) = self.grants.iter().find(|g| g.kind == PermissionKind::Files) else {
+            return true;
+        };
+        if grant.scope.is_full() {
+            return true;
+        }
+        grant.is_active(epoch_seconds())
+            && grant.scope.allows(PermissionAccess::Path { path, write })
+    }
+
+    fn active_grant(&self, kind: PermissionKind) -> Option<&PermissionGrant> {
+        let now = epoch_seconds();
         self.grants
             .iter()
             .find(|g| g.kind == kind)
-            .is_some_and(|g| g.granted)
+            .filter(|g| g.is_active(now))
     }
 
     /// Grant a permission, updating the timestamp.
     ///
     /// If the permission already exists in the store, it is updated in place.
-    /// Otherwise a new record is appended.
+    /// Otherwise a new record is appended.  The grant is unscoped and does
+    /// not expire.
     pub fn grant(&mut self, kind: PermissionKind) {
+        self.grant_scoped(kind, PermissionScope::Full, None);
+    }
+
+    /// Grant a permission limited to `scope`, optionally for `ttl` only.
+    pub fn grant_scoped(
+        &mut self,
+        kind: PermissionKind,
+        scope: PermissionScope,
+        ttl: Option<Duration>,
+    ) {
         let now = epoch_seconds();
+        let expires_at = ttl.map(|ttl| now.saturating_add(ttl.as_secs()));
         if let Some(existing) = self.grants.iter_mut().find(|g| g.kind == kind) {
             existing.granted = true;
             existing.granted_at = Some(now);
+            existing.scope = scope;
+            existing.expires_at = expires_at;
         } else {
             self.grants.push(PermissionGrant {
                 kind,
                 granted: true,
                 granted_at: Some(now),
+                scope,
+                expires_at,
             });
         }
     }
@@ -241,18 +414,38 @@ impl PermissionStore {
                 kind,
                 granted: false,
                 granted_at: None,
+                scope: PermissionScope::Full,
+                expires_at: None,
             });
         }
     }
 
     /// Return all currently granted permission kinds.
     pub fn all_granted(&self) -> Vec<PermissionKind> {
+        let now = epoch_seconds();
         self.grants
             .iter()
-            .filter(|g| g.granted)
+            .filter(|g| g.is_active(now))
             .map(|g| g.kind)
             .collect()
     }
+
+    /// Every grant record with its scope, timestamps, and whether it
+    /// currently applies.
+    pub fn list(&self) -> Vec<GrantStatus> {
+        let now = epoch_seconds();
+        self.grants
+            .iter()
+            .map(|g| GrantStatus {
+                kind: g.kind,
+                granted: g.granted,
+                active: g.is_active(now),
+                scope: g.scope.clone(),
+                granted_at: g.granted_at,
+                expires_at: g.expires_at,
+            })
+            .collect()
+    }
 }
 
 /// Current epoch time in seconds (returns 0 on clock error).
@@ -411,4 +604,113 @@ mod tests {
         assert!(guard.is_granted(PermissionKind::Reminders));
         assert!(!guard.is_granted(PermissionKind::Mail));
     }
+
+    // ── Scopes and expiry ────────────────────────────────────────────────
+
+    #[test]
+    fn read_only_scope_refuses_writes() {
+        let mut store = PermissionStore::default();
+        store.grant_scoped(PermissionKind::Calendar, PermissionScope::ReadOnly, None);
+
+        assert!(store.is_granted(PermissionKind::Calendar));
+        assert!(store.allows(PermissionKind::Calendar, PermissionAccess::Read));
+        assert!(!store.allows(PermissionKind::Calendar, PermissionAccess::Write));
+
+        store.grant(PermissionKind::Calendar);
+        assert!(store.allows(PermissionKind::Calendar, PermissionAccess::Write));
+    }
+
+    #[test]
+    fn path_scope_limits_files_to_roots() {
+        let mut store = PermissionStore::default();
+        let scope: PermissionScope = "/Users/me/Documents, /tmp/fae".parse().unwrap();
+        store.grant_scoped(PermissionKind::Files, scope, None);
+
+        let inside = PermissionAccess::Path {
+            path: Path::new("/Users/me/Documents/notes.txt"),
+            write: true,
+        };
+        let outside = PermissionAccess::Path {
+            path: Path::new("/Users/me/Documents-old/notes.txt"),
+            write: false,
+        };
+        assert!(store.allows(PermissionKind::Files, inside));
+        assert!(!store.allows(PermissionKind::Files, outside));
+        assert!(!store.allows(PermissionKind::Files, PermissionAccess::Read));
+    }
+
+    #[test]
+    fn file_tools_follow_a_narrowed_files_grant() {
+        let mut store = PermissionStore::default();
+        let notes = Path::new("/Users/me/Documents/notes.txt");
+        assert!(
+            store.allows_file(notes, true),
+            "no Files grant: tool mode decides"
+        );
+
+        store.grant_scoped(PermissionKind::Files, PermissionScope::ReadOnly, None);
+        assert!(store.allows_file(notes, false));
+        assert!(!store.allows_file(notes, true));
+
+        let scope: PermissionScope = "/Users/me/Documents".parse().unwrap();
+        store.grant_scoped(PermissionKind::Files, scope.clone(), None);
+        assert!(store.allows_file(notes, true));
+        assert!(!store.allows_file(Path::new("/Users/me/.ssh/id_ed25519"), false));
+
+        store.grant_scoped(PermissionKind::Files, scope, Some(Duration::ZERO));
+        assert!(
+            !store.allows_file(notes, false),
+            "expired scope allows nothing"
+        );
+    }
+
+    #[test]
+    fn scope_parsing() {
+        assert_eq!(
+            "read".parse::<PermissionScope>().unwrap(),
+            PermissionScope::ReadOnly
+        );
+        assert_eq!(
+            "FULL".parse::<PermissionScope>().unwrap(),
+            PermissionScope::Full
+        );
+        assert!("session".parse::<PermissionScope>().is_err());
+        assert!("relative/dir".parse::<PermissionScope>().is_err());
+    }
+
+    #[test]
+    fn expired_grant_no_longer_applies_but_is_listed() {
+        let mut store = PermissionStore::default();
+        store.grant_scoped(
+            PermissionKind::Location,
+            PermissionScope::Full,
+            Some(Duration::from_secs(3600)),
+        );
+        assert!(store.is_granted(PermissionKind::Location));
+
+        store.grants[0].expires_at = Some(epoch_seconds() - 1);
+        assert!(!store.is_granted(PermissionKind::Location));
+        assert!(store.all_granted().is_empty());
+
+        let listed = store.list();
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].granted);
+        assert!(!listed[0].active);
+        assert!(listed[0].granted_at.is_some());
+    }
+
+    #[test]
+    fn scoped_grant_roundtrips_through_toml() {
+        let mut store = PermissionStore::default();
+        store.grant(PermissionKind::Mail);
+        store.grant_scoped(
+            PermissionKind::Files,
+            PermissionScope::Paths(vec![PathBuf::from("/tmp")]),
+            Some(Duration::from_secs(60)),
+        );
+
+        let raw = toml::to_string(&store).unwrap();
+        let parsed: PermissionStore = toml::from_str(&raw).unwrap();
+        assert_eq!(parsed.grants, store.grants);
+    }
 }
@@ -159,81 +159,47 @@ Example — user says \"tell me robotics news every morning\":\n\
 After creating the task, confirm with a short spoken response like:\n\
 Done. I will check for robotics news every morning at 8 AM.";
 
-/// Canned acknowledgment phrases for when a background tool task is spawned.
+/// Acknowledgment phrases for when a background tool task is spawned.
 ///
 /// Rotated to avoid repetition. Spoken immediately via TTS while the
-/// background agent works asynchronously.
-pub const TOOL_ACKNOWLEDGMENTS: &[&str] = &[
-    "Checking that now.",
-    "On it.",
-    "Let me look into that.",
-    "One moment.",
-    "Working on that.",
-    "Give me a second.",
-    "Looking that up.",
-    "Let me see.",
-];
+/// background agent works asynchronously. This is an [`crate::i18n`] phrase
+/// list key; pass it to [`next_acknowledgment`].
+pub const TOOL_ACKNOWLEDGMENTS: &str = "ack.tool";
 
 /// Acknowledgment phrases for when Fae needs to engage deeper thinking.
 ///
 /// Used when the voice pipeline detects a complex question that benefits
 /// from reasoning mode. Spoken before the model starts its internal
 /// deliberation so the user knows Fae is working.
-pub const THINKING_ACKNOWLEDGMENTS: &[&str] = &[
-    "Let me think about that.",
-    "Thinking.",
-    "Give me a moment to work that out.",
-    "That's a good question, let me reason through it.",
-    "Let me consider that carefully.",
-    "Hmm, let me think.",
-    "Working through that now.",
-    "Hold on, I need to think this through.",
-];
-
-/// Pick the next acknowledgment phrase, rotating through the list.
+pub const THINKING_ACKNOWLEDGMENTS: &str = "ack.thinking";
+
+/// Pick the next acknowledgment phrase in the active language, rotating
+/// through the list.
 ///
 /// Uses the `counter` value (typically an `AtomicU64`) to cycle through
 /// phrases so Fae never repeats the same one back-to-back.
-pub fn next_acknowledgment<'a>(phrases: &'a [&'a str], counter: u64) -> &'a str {
-    if phrases.is_empty() {
-        return "";
-    }
-    phrases[(counter as usize) % phrases.len()]
+pub fn next_acknowledgment(phrases: &'static str, counter: u64) -> &'static str {
+    crate::i18n::phrase(phrases, counter)
 }
 
 // ---------------------------------------------------------------------------
 // Approval prompt generation
 // ---------------------------------------------------------------------------
 
-/// Canned acknowledgment phrases after approval is granted.
-pub const APPROVAL_GRANTED: &[&str] = &[
-    "Got it, running that now.",
-    "On it.",
-    "Alright, going ahead.",
-    "Okay, executing that.",
-];
-
-/// Canned acknowledgment phrases after approval is denied.
-pub const APPROVAL_DENIED: &[&str] = &[
-    "Understood, I won't do that.",
-    "Okay, skipping that.",
-    "Alright, cancelled.",
-    "Got it, I'll leave that alone.",
-];
-
-/// Canned phrases for approval timeout.
-pub const APPROVAL_TIMEOUT: &[&str] = &[
-    "I'll skip that for now.",
-    "No response, so I'll move on.",
-    "Timed out waiting, I won't run that.",
-];
-
-/// Canned phrases for ambiguous responses during approval.
-pub const APPROVAL_AMBIGUOUS: &[&str] = &[
-    "Was that a yes or no?",
-    "Sorry, I didn't catch that. Yes or no?",
-    "I need a clear yes or no.",
-];
+/// Acknowledgment phrases after approval is granted.
+pub const APPROVAL_GRANTED: &str = "approval.granted";
+
+/// Acknowledgment phrases after approval is denied.
+pub const APPROVAL_DENIED: &str = "approval.denied";
+
+/// Phrases for approval timeout.
+pub const APPROVAL_TIMEOUT: &str = "approval.timeout";
+
+/// Phrases for ambiguous responses during approval.
+pub const APPROVAL_AMBIGUOUS: &str = "approval.ambiguous";
+
+/// Spoken while a turn is suspended on an approval the user has not given.
+pub const APPROVAL_WAITING: &str = "approval.waiting";
 
 /// Format a spoken approval prompt for a tool execution request.
 ///
@@ -251,16 +217,98 @@ pub const APPROVAL_AMBIGUOUS: &[&str] = &[
 /// ```
 #[must_use]
 pub fn format_approval_prompt(tool_name: &str, input_json: &str) -> String {
+    use crate::i18n::{format, text};
+
     let detail = extract_approval_detail(tool_name, input_json);
     match tool_name {
-        "bash" => format!("I'd like to run a command: {detail}. Say yes or no."),
-        "write" => format!("I'd like to create the file {detail}. Say yes or no."),
-        "edit" => format!("I'd like to edit {detail}. Say yes or no."),
+        "bash" => format("approval.prompt.bash", &[("detail", &detail)]),
+        "write" => format("approval.prompt.write", &[("detail", &detail)]),
+        "edit" => format("approval.prompt.edit", &[("detail", &detail)]),
         "desktop" | "desktop_automation" => {
-            "I'd like to use desktop automation. Say yes or no.".to_owned()
+            let parsed = serde_json::from_str::<serde_json::Value>(input_json).ok();
+            let macro_name = parsed
+                .as_ref()
+                .filter(|v| {
+                    v.get("action").and_then(serde_json::Value::as_str) == Some("replay_macro")
+                })
+                .and_then(|v| v.get("name").and_then(serde_json::Value::as_str));
+            match macro_name {
+                Some(name) => format(
+                    "approval.prompt.desktop_macro",
+                    &[("detail", &truncate_for_speech(name, 60))],
+                ),
+                None => text("approval.prompt.desktop").to_owned(),
+            }
         }
-        "python_skill" => "I'd like to run a Python skill. Say yes or no.".to_owned(),
-        _ => format!("I'd like to use the {tool_name} tool. Say yes or no."),
+        "python_skill" => text("approval.prompt.python_skill").to_owned(),
+        "data.forget" => {
+            let wipe = serde_json::from_str::<serde_json::Value>(input_json)
+                .ok()
+                .and_then(|v| v.get("wipe").and_then(serde_json::Value::as_bool))
+                .unwrap_or(true);
+            if wipe {
+                text("approval.prompt.forget_wipe").to_owned()
+            } else {
+                text("approval.prompt.forget_export").to_owned()
+            }
+        }
+        _ => format("approval.prompt.tool", &[("tool", tool_name)]),
+    }
+}
+
+/// Format the spoken read-back for a destructive call: exactly what is about
+/// to happen, ending with "Say yes or no." like other approval prompts.
+///
+/// # Examples
+///
+/// ```
+/// use fae::approval::DestructiveAction;
+/// use fae::personality::format_read_back;
+///
+/// let args = serde_json::json!({"to": "bob@example.com", "subject": "Lunch", "body": "Noon?"});
+/// let read_back = format_read_back(DestructiveAction::SendMail, &args);
+/// assert!(read_back.contains("bob@example.com"));
+/// assert!(read_back.contains("Noon?"));
+/// ```
+#[must_use]
+pub fn format_read_back(
+    action: crate::approval::DestructiveAction,
+    args: &serde_json::Value,
+) -> String {
+    use crate::approval::DestructiveAction;
+    use crate::i18n::format;
+
+    let field = |name: &str, max_chars: usize| {
+        let value = args
+            .get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        truncate_for_speech(value, max_chars)
+    };
+    match action {
+        DestructiveAction::SendMail => {
+            let mut body = field("body", 200);
+            if !body.ends_with(['.', '!', '?']) {
+                body.push('.');
+            }
+            format(
+                "approval.read_back.send_mail",
+                &[
+                    ("to", &field("to", 80)),
+                    ("subject", &field("subject", 80)),
+                    ("body", &body),
+                ],
+            )
+        }
+        DestructiveAction::DeleteEvent => format(
+            "approval.read_back.delete_event",
+            &[("event", &field("identifier", 80))],
+        ),
+        DestructiveAction::OverwriteFile => format(
+            "approval.read_back.overwrite_file",
+            &[("path", &field("path", 80))],
+        ),
     }
 }
 
@@ -277,7 +325,7 @@ fn extract_approval_detail(tool_name: &str, input_json: &str) -> String {
             let cmd = v
                 .get("command")
                 .and_then(serde_json::Value::as_str)
-                .unwrap_or("a shell command");
+                .unwrap_or_else(|| crate::i18n::text("approval.detail.command"));
             truncate_for_speech(cmd, 60)
         }
         ("write", Some(ref v)) => {
@@ -285,16 +333,25 @@ fn extract_approval_detail(tool_name: &str, input_json: &str) -> String {
                 .get("file_path")
                 .or_else(|| v.get("path"))
                 .and_then(serde_json::Value::as_str)
-                .unwrap_or("a file");
+                .unwrap_or_else(|| crate::i18n::text("approval.detail.file"));
             truncate_for_speech(path, 80)
         }
         ("edit", Some(ref v)) => {
-            let path = v
-                .get("file_path")
-                .or_else(|| v.get("path"))
+            let paths: Vec<String> = v
+                .get("patch")
                 .and_then(serde_json::Value::as_str)
-                .unwrap_or("a file");
-            truncate_for_speech(path, 80)
+                .and_then(|p| crate::fae_llm::tools::patch::parse_patch(p).ok())
+                .map(|patches| patches.iter().map(|p| p.path().to_owned()).collect())
+                .unwrap_or_default();
+            match paths.as_slice() {
+                [] => crate::i18n::text("approval.detail.file").to_owned(),
+                [one] => truncate_for_speech(one, 80),
+                [first, rest @ ..] => crate::i18n::plural(
+                    "approval.detail.more_files",
+                    rest.len(),
+                    &[("first", &truncate_for_speech(first, 60))],
+                ),
+            }
         }
         _ => tool_name.to_owned(),
     }
@@ -326,8 +383,12 @@ Vision understanding:\n\
 /// so the model knows it can process image inputs.
 /// When `user_name` is `Some`, a user-context section is added so the LLM can
 /// address the user by name.
-/// When `voice_optimized` is `true`, skills and permission fragments are omitted
-/// to minimize prefill latency for voice conversations.
+/// Preferences the user has saved (see [`crate::memory::preferences`]) and
+/// their recent corrections (see [`crate::intelligence::corrections`]) are
+/// always included.
+/// When `voice_optimized` is `true`, built-in skills and permission fragments
+/// are omitted to minimize prefill latency for voice conversations; the user's
+/// installed skills are kept.
 #[must_use]
 pub fn assemble_prompt(
     _personality_name: &str,
@@ -348,6 +409,9 @@ pub fn assemble_prompt(
         parts.push(CORE_PROMPT.trim().to_owned());
     }
 
+    // Replies are spoken, so every prompt teaches the delivery tags.
+    parts.push(crate::tts::style::prompt_section());
+
     let soul = load_soul();
 
     if vision_capable {
@@ -369,37 +433,54 @@ pub fn assemble_prompt(
         }
     }
 
-    // Skip skills and capability fragments in voice-optimized mode to reduce
-    // prefill latency. The tool gating layer already handles which tools are
-    // available — we don't need the LLM to "know about" every skill schema.
-    if !voice_optimized {
-        let skills = crate::skills::load_all_skills();
-        let skills_trimmed = skills.trim();
-        if !skills_trimmed.is_empty() {
-            parts.push(skills_trimmed.to_owned());
-        }
+    // Standing preferences are short and save the model asking for them.
+    let preferences =
+        crate::memory::preferences::UserPreferences::load(&crate::fae_dirs::preferences_file());
+    if let Some(section) = preferences.prompt_section() {
+        parts.push(section);
+    }
 
-        if let Some(store) = permissions {
-            let builtin_skills = crate::skills::builtins::builtin_skills();
-            let active = builtin_skills.active_prompt_fragments(store);
-            if !active.trim().is_empty() {
-                parts.push(format!(
-                    "# Active capabilities\n\n\
-                     The following capabilities are enabled:\n\n{active}"
-                ));
-            }
-            let unavailable = builtin_skills.unavailable(store);
-            if !unavailable.is_empty() {
-                let names: Vec<&str> = unavailable.iter().map(|s| s.name()).collect();
-                parts.push(format!(
-                    "# Capabilities requiring permission\n\n\
-                     These capabilities are available but not yet granted: {}. \
-                     If the user asks about these, try using the tool \u{2014} the system will \
-                     prompt them to grant access. If declined, suggest they can enable \
-                     it later in settings.",
-                    names.join(", ")
-                ));
-            }
+    // So are recent corrections, which keep a known mistake from recurring.
+    let corrections =
+        crate::intelligence::CorrectionStore::load(&crate::fae_dirs::corrections_file());
+    if let Some(section) = corrections.prompt_section() {
+        parts.push(section);
+    }
+
+    // Skip built-in skills and capability fragments in voice-optimized mode
+    // to reduce prefill latency. The tool gating layer already handles which
+    // tools are available — we don't need the LLM to "know about" every skill
+    // schema. The user's own skills are standing instructions and stay in.
+    let skills = if voice_optimized {
+        crate::skills::load_custom_skills()
+    } else {
+        crate::skills::load_all_skills()
+    };
+    let skills_trimmed = skills.trim();
+    if !skills_trimmed.is_empty() {
+        parts.push(skills_trimmed.to_owned());
+    }
+
+    if !voice_optimized && let Some(store) = permissions {
+        let builtin_skills = crate::skills::builtins::builtin_skills();
+        let active = builtin_skills.active_prompt_fragments(store);
+        if !active.trim().is_empty() {
+            parts.push(format!(
+                "# Active capabilities\n\n\
+                 The following capabilities are enabled:\n\n{active}"
+            ));
+        }
+        let unavailable = builtin_skills.unavailable(store);
+        if !unavailable.is_empty() {
+            let names: Vec<&str> = unavailable.iter().map(|s| s.name()).collect();
+            parts.push(format!(
+                "# Capabilities requiring permission\n\n\
+                 These capabilities are available but not yet granted: {}. \
+                 If the user asks about these, try using the tool \u{2014} the system will \
+                 prompt them to grant access. If declined, suggest they can enable \
+                 it later in settings.",
+                names.join(", ")
+            ));
         }
     }
 
@@ -416,6 +497,55 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn edit_approval_prompt_names_patched_files() {
+        let patch = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-a\n+b\n\
+                     --- /dev/null\n+++ b/NOTES.md\n@@ -0,0 +1 @@\n+n\n";
+        let input = serde_json::json!({ "patch": patch }).to_string();
+        assert_eq!(
+            format_approval_prompt("edit", &input),
+            "I'd like to edit src/lib.rs and 1 other file. Say yes or no."
+        );
+    }
+
+    #[test]
+    fn data_forget_approval_prompt_names_what_is_erased() {
+        let wipe = format_approval_prompt("data.forget", r#"{"export":false,"wipe":true}"#);
+        assert!(wipe.contains("permanently erase"));
+        assert!(wipe.ends_with("Say yes or no."));
+        let export = format_approval_prompt("data.forget", r#"{"export":true,"wipe":false}"#);
+        assert!(export.contains("export"));
+    }
+
+    #[test]
+    fn read_back_says_what_will_happen() {
+        use crate::approval::DestructiveAction;
+
+        let mail = serde_json::json!({
+            "to": "bob@example.com",
+            "subject": "Friday",
+            "body": "Running late, start without me.",
+        });
+        assert_eq!(
+            format_read_back(DestructiveAction::SendMail, &mail),
+            "I'm about to email bob@example.com with the subject \"Friday\", saying: \
+             Running late, start without me. Should I send it? Say yes or no."
+        );
+        let event = serde_json::json!({"identifier": "Dentist", "confirm": true});
+        assert!(format_read_back(DestructiveAction::DeleteEvent, &event).contains("Dentist"));
+    }
+
+    #[test]
+    fn desktop_macro_replay_prompt_names_the_macro() {
+        let replay = r#"{"action":"replay_macro","name":"screenshot-and-file"}"#;
+        assert_eq!(
+            format_approval_prompt("desktop", replay),
+            "I'd like to replay your desktop macro screenshot-and-file. Say yes or no."
+        );
+        let click = format_approval_prompt("desktop", r#"{"action":"click","target":"OK"}"#);
+        assert!(click.contains("desktop automation"));
+    }
+
     #[test]
     fn core_prompt_nonempty() {
         assert!(!CORE_PROMPT.trim().is_empty());
@@ -3,8 +3,11 @@
 //! Extracted from `coordinator.rs` — these are pure helpers for managing
 //! the in-memory conversation history during a pipeline run.
 
+use std::time::{Duration, Instant};
+
 use tracing::warn;
 
+use crate::config::ConversationConfig;
 use crate::memory::MemoryOrchestrator;
 use crate::runtime::{ConversationSnapshotEntry, ConversationSnapshotEntryRole, RuntimeEvent};
 use tokio::sync::broadcast;
@@ -75,6 +78,177 @@ pub(crate) fn build_background_context(turns: &[ConversationTurn], max_turns: us
     ctx
 }
 
+/// Summarize an ended conversation for memory: how long it was and the gist
+/// of its latest turns.
+pub(crate) fn summarize_conversation(turns: &[ConversationTurn]) -> String {
+    const MAX_TURNS: usize = 8;
+    const MAX_CHARS: usize = 160;
+
+    fn clip(text: &str) -> String {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.chars().count() <= MAX_CHARS {
+            return text;
+        }
+        let clipped: String = text.chars().take(MAX_CHARS).collect();
+        format!("{clipped}...")
+    }
+
+    let noun = if turns.len() == 1 { "turn" } else { "turns" };
+    let mut summary = format!("Conversation of {} {noun}.", turns.len());
+    if turns.len() > MAX_TURNS {
+        summary.push_str(" Latest:");
+    }
+    for turn in &turns[turns.len().saturating_sub(MAX_TURNS)..] {
+        if !turn.user_text.trim().is_empty() {
+            summary.push_str(&format!("\n- User: {}", clip(&turn.user_text)));
+        }
+        if !turn.assistant_text.trim().is_empty() {
+            summary.push_str(&format!("\n- Fae: {}", clip(&turn.assistant_text)));
+        }
+    }
+    summary
+}
+
+/// Digest of an ended conversation for the journal: what the user said in
+/// each exchange and the first sentence of the reply.
+pub(crate) fn journal_digest(turns: &[ConversationTurn]) -> String {
+    const MAX_TURNS: usize = 10;
+    const MAX_CHARS: usize = 120;
+
+    fn clip(text: &str) -> String {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.chars().count() <= MAX_CHARS {
+            return text;
+        }
+        let clipped: String = text.chars().take(MAX_CHARS).collect();
+        format!("{}…", clipped.trim_end())
+    }
+
+    let exchanges: Vec<&ConversationTurn> = turns
+        .iter()
+        .filter(|t| !t.user_text.trim().is_empty() && !t.user_text.starts_with("[background task"))
+        .collect();
+    let mut lines = Vec::new();
+    for turn in &exchanges[exchanges.len().saturating_sub(MAX_TURNS)..] {
+        let reply = turn.assistant_text.trim();
+        let first_sentence = reply
+            .find(['.', '!', '?'])
+            .map_or(reply, |end| &reply[..=end]);
+        if first_sentence.is_empty() {
+            lines.push(format!("- {}", clip(&turn.user_text)));
+        } else {
+            lines.push(format!(
+                "- {} → {}",
+                clip(&turn.user_text),
+                clip(first_sentence)
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// What the conversation lifecycle calls for once the user has been quiet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LifecycleAction {
+    /// Ask once whether the user is still there.
+    Reengage,
+    /// The conversation is over.
+    End,
+}
+
+/// Times the silence in the current conversation against
+/// `conversation.reengage_after_s` and `conversation.session_timeout_min`.
+#[derive(Debug)]
+pub(crate) struct ConversationLifecycle {
+    reengage_after: Option<Duration>,
+    end_after: Option<Duration>,
+    /// Start of the current silence; `None` while no conversation is open.
+    quiet_since: Option<Instant>,
+    /// Whether this silence already had its re-engagement prompt.
+    reengaged: bool,
+}
+
+impl ConversationLifecycle {
+    pub(crate) fn new(config: &ConversationConfig) -> Self {
+        let nonzero = |d: Duration| (!d.is_zero()).then_some(d);
+        Self {
+            reengage_after: nonzero(Duration::from_secs(u64::from(config.reengage_after_s))),
+            end_after: nonzero(Duration::from_secs(
+                u64::from(config.session_timeout_min) * 60,
+            )),
+            quiet_since: None,
+            reengaged: false,
+        }
+    }
+
+    /// Whether a conversation is open.
+    pub(crate) fn is_open(&self) -> bool {
+        self.quiet_since.is_some()
+    }
+
+    /// Something was said: open the conversation if needed and start timing
+    /// the silence again from `now`.
+    pub(crate) fn activity(&mut self, now: Instant) {
+        self.quiet_since = Some(now);
+        self.reengaged = false;
+    }
+
+    /// The next action due and when, if any.
+    pub(crate) fn next(&self) -> Option<(Instant, LifecycleAction)> {
+        let since = self.quiet_since?;
+        if let Some(after) = self.reengage_after
+            && !self.reengaged
+            && self.end_after.is_none_or(|end| after < end)
+        {
+            return Some((since + after, LifecycleAction::Reengage));
+        }
+        self.end_after
+            .map(|end| (since + end, LifecycleAction::End))
+    }
+
+    /// Record that `action` was carried out.
+    pub(crate) fn fired(&mut self, action: LifecycleAction) {
+        match action {
+            LifecycleAction::Reengage => self.reengaged = true,
+            LifecycleAction::End => {
+                self.quiet_since = None;
+                self.reengaged = false;
+            }
+        }
+    }
+}
+
+/// Store an ended conversation's summary in memory and emit runtime events.
+///
+/// Returns whether a summary was written.
+pub(crate) fn capture_conversation_summary(
+    memory_orchestrator: Option<&MemoryOrchestrator>,
+    runtime_tx: Option<&broadcast::Sender<RuntimeEvent>>,
+    session_id: &str,
+    summary: &str,
+) -> bool {
+    let Some(memory) = memory_orchestrator else {
+        return false;
+    };
+    match memory.capture_conversation_summary(session_id, summary) {
+        Ok(report) => {
+            if let Some(rt) = runtime_tx {
+                for write in &report.writes {
+                    let _ = rt.send(RuntimeEvent::MemoryWrite {
+                        op: write.op.clone(),
+                        target_id: write.target_id.clone(),
+                    });
+                }
+            }
+            report.episodes_written > 0
+        }
+        Err(e) => {
+            warn!("conversation summary capture failed: {e}");
+            false
+        }
+    }
+}
+
 /// Capture a completed turn into the memory system and emit runtime events.
 pub(crate) fn capture_memory_turn(
     memory_orchestrator: Option<&MemoryOrchestrator>,
@@ -86,6 +260,10 @@ pub(crate) fn capture_memory_turn(
     if let Some(memory) = memory_orchestrator {
         match memory.capture_turn(turn_id, user_text, assistant_text) {
             Ok(report) => {
+                // A handed-off conversation carries the memories it wrote.
+                for id in report.writes.iter().filter_map(|w| w.target_id.as_deref()) {
+                    crate::handoff::note_memory(id);
+                }
                 if let Some(rt) = runtime_tx {
                     for write in &report.writes {
                         let _ = rt.send(RuntimeEvent::MemoryWrite {
@@ -105,3 +283,78 @@ pub(crate) fn capture_memory_turn(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn turn(user: &str, assistant: &str) -> ConversationTurn {
+        ConversationTurn {
+            user_text: user.to_owned(),
+            assistant_text: assistant.to_owned(),
+        }
+    }
+
+    #[test]
+    fn lifecycle_reengages_once_then_ends() {
+        let config = ConversationConfig {
+            reengage_after_s: 60,
+            session_timeout_min: 5,
+            ..ConversationConfig::default()
+        };
+        let mut lifecycle = ConversationLifecycle::new(&config);
+        assert_eq!(lifecycle.next(), None);
+
+        let start = Instant::now();
+        lifecycle.activity(start);
+        assert_eq!(
+            lifecycle.next(),
+            Some((start + Duration::from_secs(60), LifecycleAction::Reengage))
+        );
+        lifecycle.fired(LifecycleAction::Reengage);
+        // The prompt does not restart the silence.
+        assert_eq!(
+            lifecycle.next(),
+            Some((start + Duration::from_secs(300), LifecycleAction::End))
+        );
+        lifecycle.fired(LifecycleAction::End);
+        assert!(!lifecycle.is_open());
+        assert_eq!(lifecycle.next(), None);
+
+        // Both off by default.
+        let mut off = ConversationLifecycle::new(&ConversationConfig::default());
+        off.activity(start);
+        assert_eq!(off.next(), None);
+    }
+
+    #[test]
+    fn journal_digest_pairs_requests_with_first_sentences() {
+        let turns = vec![
+            turn("What's the weather?", "Sunny and mild. Highs of 21."),
+            turn("[background task 3]", "Your export finished."),
+            turn("Thanks", ""),
+        ];
+        assert_eq!(
+            journal_digest(&turns),
+            "- What's the weather? → Sunny and mild.\n- Thanks"
+        );
+        assert_eq!(journal_digest(&[]), "");
+    }
+
+    #[test]
+    fn summary_keeps_the_latest_turns() {
+        let mut turns = vec![turn("What's the weather?", "Sunny and mild.")];
+        assert_eq!(
+            summarize_conversation(&turns),
+            "Conversation of 1 turn.\n- User: What's the weather?\n- Fae: Sunny and mild."
+        );
+
+        turns.extend((0..9).map(|i| turn(&format!("question {i}"), "")));
+        let summary = summarize_conversation(&turns);
+        assert!(summary.starts_with("Conversation of 10 turns. Latest:"));
+        assert!(!summary.contains("weather") && !summary.contains("question 0"));
+        assert!(summary.ends_with("- User: question 8"));
+    }
+}
@@ -3,13 +3,16 @@
 use crate::approval::ToolApprovalRequest;
 use crate::audio::aec::{AecProcessor, ReferenceBuffer, ReferenceHandle};
 use crate::canvas::registry::CanvasSessionRegistry;
-use crate::config::{SpeechConfig, VoiceIdentityMode};
+use crate::config::{ContentPolicyLevel, SpeechConfig, VoiceIdentityMode};
+use crate::content_filter::ReplyFilter;
 use crate::error::Result;
 use crate::memory::{MemoryOrchestrator, MemoryStore};
 use crate::pipeline::conversation::{
-    ConversationTurn, append_conversation_turn, build_background_context,
-    build_conversation_snapshot_entries, capture_memory_turn,
+    ConversationLifecycle, ConversationTurn, LifecycleAction, append_conversation_turn,
+    build_background_context, build_conversation_snapshot_entries, capture_conversation_summary,
+    capture_memory_turn, journal_digest, summarize_conversation,
 };
+use crate::pipeline::endpointing::{Endpoint, Speculation, Speculator, speculate};
 use crate::pipeline::input_queue::{
     LlmInputQueue, QueuedLlmInput, clear_pending_inputs, enqueue_pending_input,
 };
@@ -17,6 +20,7 @@ use crate::pipeline::messages::{
     AudioChunk, ControlEvent, GateCommand, SentenceChunk, SpeechSegment, SynthesizedAudio,
     TextInjection, Transcription,
 };
+use crate::pipeline::queues;
 use crate::pipeline::voice_approval::{
     ApprovalContext, PendingVoiceApproval, resolve_and_advance_approval, start_voice_approval,
 };
@@ -25,7 +29,7 @@ use crate::pipeline::voice_identity::{
     load_approval_voice_profile,
 };
 use crate::runtime::RuntimeEvent;
-use crate::startup::InitializedModels;
+use crate::startup::{InitializedModels, PendingLlm};
 use crate::time_util::now_epoch_secs;
 use crate::tts::kokoro::strip_non_speech_chars;
 use std::io::Write;
@@ -44,6 +48,9 @@ const TRANSCRIPTION_CHANNEL_SIZE: usize = 8;
 const SENTENCE_CHANNEL_SIZE: usize = 8;
 const SYNTH_CHANNEL_SIZE: usize = 16;
 
+/// Poll interval while a prompt prefill waits for Fae to stop speaking.
+const PREFILL_SILENCE_POLL: Duration = Duration::from_millis(100);
+
 /// Commands sent to the playback stage (e.g., barge-in stop).
 enum PlaybackCommand {
     Stop,
@@ -296,6 +303,18 @@ impl PipelineCoordinator {
     pub async fn run(mut self) -> Result<()> {
         info!("initializing speech pipeline (mode: {:?})", self.mode);
 
+        // Onboarding calibration for the active microphone overrides the
+        // VAD threshold, AGC and AEC defaults.
+        if let Some(profile) = crate::audio::calibration::profile_for(&self.config.audio).cloned() {
+            info!(
+                vad_threshold = profile.vad_threshold,
+                input_gain = profile.input_gain,
+                aec = profile.aec_required,
+                "applying audio calibration profile"
+            );
+            crate::audio::calibration::apply_profile(&profile, &mut self.config);
+        }
+
         // Ensure persistent memory roots exist early.
         let memory_root = self.config.memory.root_dir.clone();
         let store = MemoryStore::new(&memory_root);
@@ -305,19 +324,19 @@ impl PipelineCoordinator {
         let onboarding_seg_rx: Option<mpsc::Receiver<SpeechSegment>> = None;
 
         // Split pre-loaded models (if any) into per-stage pieces.
-        let (preloaded_stt, preloaded_llm, preloaded_tts) = match self.models.take() {
-            Some(m) => (Some(m.stt), m.llm, m.tts),
-            None => (None, None, None),
+        let (preloaded_stt, preloaded_llm, pending_llm, preloaded_tts) = match self.models.take() {
+            Some(m) => (Some(m.stt), m.llm, m.llm_loading, m.tts),
+            None => (None, None, None, None),
         };
 
         let text_injection_rx = self.text_injection_rx.take();
         let audio_injection_rx = self.audio_injection_rx.take();
 
         // Create channels between stages
-        let (audio_tx, audio_rx) = mpsc::channel::<AudioChunk>(AUDIO_CHANNEL_SIZE);
-        let (speech_tx, speech_rx) = mpsc::channel::<SpeechSegment>(SPEECH_CHANNEL_SIZE);
+        let (audio_tx, audio_rx) = queues::AUDIO.channel::<AudioChunk>(AUDIO_CHANNEL_SIZE);
+        let (speech_tx, speech_rx) = queues::SPEECH.channel::<SpeechSegment>(SPEECH_CHANNEL_SIZE);
         let (transcription_tx, transcription_rx) =
-            mpsc::channel::<Transcription>(TRANSCRIPTION_CHANNEL_SIZE);
+            queues::TRANSCRIPTION.channel::<Transcription>(TRANSCRIPTION_CHANNEL_SIZE);
         let (control_tx, control_rx) = mpsc::unbounded_channel::<ControlEvent>();
 
         let cancel = self.cancel.clone();
@@ -341,12 +360,22 @@ impl PipelineCoordinator {
         // Stage 1: Audio capture (always)
         let capture_handle = {
             let config = self.config.audio.clone();
+            let agc = self.config.agc.clone();
+            let loopback_device = self.config.meeting.loopback_device.clone();
             let cancel = cancel.clone();
             let rt_tx = runtime_tx.clone();
             // Clone audio_tx before move so the companion injection task can share it.
             let capture_audio_tx = audio_tx.clone();
             tokio::spawn(async move {
-                run_capture_stage(config, capture_audio_tx, rt_tx, cancel).await;
+                run_capture_stage(
+                    config,
+                    agc,
+                    loopback_device,
+                    capture_audio_tx,
+                    rt_tx,
+                    cancel,
+                )
+                .await;
             })
         };
 
@@ -383,7 +412,8 @@ impl PipelineCoordinator {
 
         // AEC stage: sits between capture and VAD when enabled.
         let (vad_audio_rx, aec_handle) = if aec_enabled {
-            let (aec_out_tx, aec_out_rx) = mpsc::channel::<AudioChunk>(AUDIO_CHANNEL_SIZE);
+            let (aec_out_tx, aec_out_rx) =
+                queues::AEC_AUDIO.channel::<AudioChunk>(AUDIO_CHANNEL_SIZE);
             let aec_config = self.config.aec.clone();
             let cancel = cancel.clone();
             let handle = tokio::spawn(async move {
@@ -411,6 +441,9 @@ impl PipelineCoordinator {
                 aec_enabled,
                 runtime_tx: runtime_tx.clone(),
                 awaiting_approval: Arc::clone(&awaiting_approval),
+                // Speculative turns are settled by the conversation gate.
+                speculative_endpoints: self.mode == PipelineMode::Conversation
+                    && self.config.conversation.enabled,
             };
             tokio::spawn(async move {
                 run_vad_stage(
@@ -452,8 +485,9 @@ impl PipelineCoordinator {
                 let (llm_sentence_tx, llm_sentence_rx) =
                     mpsc::channel::<SentenceChunk>(SENTENCE_CHANNEL_SIZE);
                 let (tts_sentence_tx, tts_sentence_rx) =
-                    mpsc::channel::<SentenceChunk>(SENTENCE_CHANNEL_SIZE);
-                let (synth_tx, synth_rx) = mpsc::channel::<SynthesizedAudio>(SYNTH_CHANNEL_SIZE);
+                    queues::SENTENCE.channel::<SentenceChunk>(SENTENCE_CHANNEL_SIZE);
+                let (synth_tx, synth_rx) =
+                    queues::SYNTH.channel::<SynthesizedAudio>(SYNTH_CHANNEL_SIZE);
 
                 // Shared interrupt flag between gate and LLM
                 let interrupt = Arc::new(AtomicBool::new(false));
@@ -545,6 +579,10 @@ impl PipelineCoordinator {
                 let sentence_forward_handle = {
                     let runtime_tx = runtime_tx.clone();
                     let canvas_reg = canvas_registry.clone();
+                    let content_level = self
+                        .config
+                        .content_filter
+                        .level_for(self.config.runtime.profile);
                     tokio::spawn(async move {
                         forward_sentences(
                             llm_sentence_rx,
@@ -552,6 +590,7 @@ impl PipelineCoordinator {
                             runtime_tx,
                             canvas_reg,
                             console_output,
+                            content_level,
                         )
                         .await;
                     })
@@ -565,6 +604,7 @@ impl PipelineCoordinator {
                     let assistant_speaking = Arc::clone(&assistant_speaking);
                     let assistant_generating = Arc::clone(&assistant_generating);
                     let awaiting_approval_for_llm = Arc::clone(&awaiting_approval);
+                    let gate_active = Arc::clone(&self.gate_active);
                     let playback_cmd_tx = playback_cmd_tx.clone();
                     let runtime_tx = runtime_tx.clone();
                     let tool_approval_tx = tool_approval_tx.clone();
@@ -600,6 +640,7 @@ impl PipelineCoordinator {
                                 voice_command_rx: Some(voice_cmd_rx),
                                 queue_cmd_rx: Some(llm_queue_cmd_rx),
                                 awaiting_approval: awaiting_approval_for_llm,
+                                gate_active,
                                 approval_notification_rx,
                                 approval_response_tx,
                                 jit_request_tx: jit_request_tx_for_llm,
@@ -607,6 +648,7 @@ impl PipelineCoordinator {
                             run_llm_stage(
                                 config,
                                 preloaded_llm,
+                                pending_llm,
                                 llm_rx,
                                 llm_sentence_tx,
                                 ctl,
@@ -882,28 +924,57 @@ impl PipelineCoordinator {
 
 async fn run_capture_stage(
     config: crate::config::AudioConfig,
+    agc: crate::config::AgcConfig,
+    loopback_device: Option<String>,
     tx: mpsc::Sender<AudioChunk>,
     runtime_tx: Option<broadcast::Sender<RuntimeEvent>>,
     cancel: CancellationToken,
 ) {
     use crate::audio::capture::CpalCapture;
 
-    match CpalCapture::new(&config) {
-        Ok(capture) => {
-            // NOTE: MicStatus { active: true } is NOT emitted here.
-            // The VAD stage validates actual audio flow before confirming
-            // mic health (macOS TCC can silently provide zero-amplitude audio).
-            if let Err(e) = capture.run(tx, cancel).await {
-                error!("capture stage error: {e}");
-                if let Some(ref rt) = runtime_tx {
-                    let _ = rt.send(RuntimeEvent::MicStatus { active: false });
+    // During a meeting, capture switches to the loopback device (when one is
+    // configured) so system audio is transcribed, and back afterwards.
+    let mut meeting_rx = crate::meeting::subscribe();
+    loop {
+        let mut config = config.clone();
+        let in_meeting = *meeting_rx.borrow_and_update();
+        if in_meeting && let Some(device) = &loopback_device {
+            info!(device, "meeting mode — capturing from loopback device");
+            config.input_device = Some(device.clone());
+        }
+
+        let capture_cancel = cancel.child_token();
+        let capture = async {
+            match CpalCapture::new(&config).map(|c| c.with_agc(&agc)) {
+                Ok(capture) => {
+                    // NOTE: MicStatus { active: true } is NOT emitted here.
+                    // The VAD stage validates actual audio flow before confirming
+                    // mic health (macOS TCC can silently provide zero-amplitude audio).
+                    if let Err(e) = capture.run(tx.clone(), capture_cancel.clone()).await {
+                        error!("capture stage error: {e}");
+                        if let Some(ref rt) = runtime_tx {
+                            let _ = rt.send(RuntimeEvent::MicStatus { active: false });
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!("failed to init capture: {e}");
+                    if let Some(ref rt) = runtime_tx {
+                        let _ = rt.send(RuntimeEvent::MicStatus { active: false });
+                    }
                 }
             }
-        }
-        Err(e) => {
-            error!("failed to init capture: {e}");
-            if let Some(ref rt) = runtime_tx {
-                let _ = rt.send(RuntimeEvent::MicStatus { active: false });
+        };
+        tokio::pin!(capture);
+
+        tokio::select! {
+            () = &mut capture => return,
+            changed = meeting_rx.changed(), if loopback_device.is_some() => {
+                capture_cancel.cancel();
+                capture.await;
+                if changed.is_err() || cancel.is_cancelled() {
+                    return;
+                }
             }
         }
     }
@@ -972,6 +1043,9 @@ struct VadStageState {
     /// In this mode the short-utterance guard is bypassed for segments >= 0.15s
     /// so that short "yes"/"no" responses can pass through after the echo tail.
     awaiting_approval: Arc<AtomicBool>,
+    /// Whether provisional endpoints may start speculative turns
+    /// (see [`super::endpointing`]).
+    speculative_endpoints: bool,
 }
 
 async fn run_vad_stage(
@@ -993,6 +1067,14 @@ async fn run_vad_stage(
             return;
         }
     };
+    if !state.speculative_endpoints {
+        vad.set_provisional_silence_ms(0);
+    }
+    // The open provisional endpoint, if any, and whether the utterance being
+    // heard has been answered speculatively. A speculative reply is held
+    // back, so assistant generation during it is not echo.
+    let mut speculator: Option<Speculator> = None;
+    let mut speculative_utterance = false;
 
     let confirm_samples = ms_to_samples(config.audio.input_sample_rate, config.barge_in.confirm_ms);
     let mut pending: Option<PendingBargeIn> = None;
@@ -1072,9 +1154,9 @@ async fn run_vad_stage(
                                 }
 
                                 // Update echo tail: detect the transition from suppressing→not.
-                                let actively_suppressing =
-                                    state.assistant_speaking.load(Ordering::Relaxed)
-                                    || state.assistant_generating.load(Ordering::Relaxed);
+                                let actively_suppressing = !speculative_utterance
+                                    && (state.assistant_speaking.load(Ordering::Relaxed)
+                                        || state.assistant_generating.load(Ordering::Relaxed));
                                 if was_suppressing && !actively_suppressing {
                                     let now = std::time::Instant::now();
                                     suppress_until = Some(now + echo_tail);
@@ -1086,6 +1168,8 @@ async fn run_vad_stage(
                                     // tail expires.
                                     vad.reset();
                                     pending = None;
+                                    speculator = None;
+                                    speculative_utterance = false;
                                 }
                                 was_suppressing = actively_suppressing;
 
@@ -1140,12 +1224,36 @@ async fn run_vad_stage(
                                 if let Some((captured_at, rms)) = emit
                                     && allow_event
                                 {
+                                    crate::scheduler::priority::touch_interactive();
                                     let _ = control_tx.send(ControlEvent::UserSpeechStart {
                                         captured_at,
                                         rms,
                                     });
                                 }
+                                if out.resumed && speculator.take().is_some() {
+                                    info!("speech resumed — revising provisional endpoint");
+                                }
+                                if let Some(mut segment) = out.provisional
+                                    && allow_event
+                                    && !state.awaiting_approval.load(Ordering::Relaxed)
+                                {
+                                    let (open, speculation) = speculate();
+                                    segment.speculation = Some(speculation);
+                                    info!(
+                                        "provisional endpoint after {:.1}s of speech",
+                                        segment.samples.len() as f32 / segment.sample_rate as f32
+                                    );
+                                    speculator = Some(open);
+                                    speculative_utterance = true;
+                                    if tx.send(segment).await.is_err() {
+                                        break;
+                                    }
+                                }
                                 if let Some(segment) = out.segment {
+                                    // Dropping a speculator unconfirmed revises it, so
+                                    // any rejected segment below is never answered.
+                                    let speculated = speculator.take().filter(|_| out.confirmed);
+                                    speculative_utterance = false;
                                     let duration_s =
                                         segment.samples.len() as f32 / segment.sample_rate as f32;
 
@@ -1249,6 +1357,13 @@ async fn run_vad_stage(
                                         let _ = tap.try_send(segment.clone());
                                     }
 
+                                    // The provisional segment already carries this
+                                    // audio: confirming it releases the reply.
+                                    if let Some(speculated) = speculated {
+                                        info!("provisional endpoint confirmed");
+                                        speculated.confirm();
+                                        continue;
+                                    }
                                     if tx.send(segment).await.is_err() {
                                         break;
                                     }
@@ -1274,6 +1389,12 @@ async fn run_stt_stage(
 ) {
     use crate::stt::ParakeetStt;
 
+    // Each pipeline run is a new conversation: pick up people learned since
+    // the last one.
+    if config.stt.contextual_biasing {
+        crate::stt::bias::refresh_from_memory(&config);
+    }
+
     let mut stt = match preloaded {
         Some(s) => s,
         None => match ParakeetStt::new(&config.stt, &config.models) {
@@ -1291,6 +1412,14 @@ async fn run_stt_stage(
             segment = rx.recv() => {
                 match segment {
                     Some(segment) => {
+                        // The user kept talking: the full segment follows.
+                        if segment
+                            .speculation
+                            .as_ref()
+                            .is_some_and(|s| s.endpoint() == Endpoint::Revised)
+                        {
+                            continue;
+                        }
                         // Compute audio metrics before transcription for quality filtering.
                         let duration_secs = segment.samples.len() as f32
                             / segment.sample_rate as f32;
@@ -1330,6 +1459,34 @@ async fn run_stt_stage(
                                 ) {
                                     transcription.text = fixed;
                                 }
+                                if config.content_filter.mask_transcripts
+                                    && let Some(masked) =
+                                        crate::content_filter::mask_profanity(&transcription.text)
+                                {
+                                    transcription.text = masked;
+                                }
+
+                                // Meetings are transcribed, not answered: only a
+                                // request to end the meeting goes on downstream.
+                                if crate::meeting::is_active()
+                                    && !matches!(
+                                        crate::voice_command::parse_voice_command(&transcription.text),
+                                        Some(crate::voice_command::VoiceCommand::StopMeeting)
+                                    )
+                                {
+                                    if !super::endpointing::settle(&mut transcription).await {
+                                        continue;
+                                    }
+                                    crate::meeting::record(
+                                        &segment.samples,
+                                        segment.sample_rate,
+                                        &transcription.text,
+                                    );
+                                    if let Some(rt) = &runtime_tx {
+                                        let _ = rt.send(RuntimeEvent::Transcription(transcription));
+                                    }
+                                    continue;
+                                }
 
                                 if let Some(rt) = &runtime_tx {
                                     let _ = rt.send(RuntimeEvent::Transcription(transcription.clone()));
@@ -1385,7 +1542,7 @@ async fn run_identity_gate(
     if !has_primary {
         let _ = speak(
             &tts_tx,
-            "Hello, I am Fae. We can get to know each other naturally as we chat.",
+            crate::i18n::text("conversation.greeting"),
             cancel.clone(),
         )
         .await;
@@ -1396,6 +1553,7 @@ async fn run_identity_gate(
             () = cancel.cancelled() => break,
             msg = rx.recv() => {
                 let Some(t) = msg else { break };
+                let t = queues::coalesce_partials(t, &mut rx);
 
                 let lower_raw = t.text.to_lowercase();
                 let has_direct_address = find_name_mention(&lower_raw).is_some();
@@ -1535,7 +1693,16 @@ async fn run_voice_command_filter(
         tokio::select! {
             () = cancel.cancelled() => break,
             msg = rx.recv() => {
-                let Some(t) = msg else { break };
+                let Some(mut t) = msg else { break };
+
+                // Commands act at once: wait for a provisional endpoint to
+                // settle before treating one as final.
+                if t.speculation.is_some()
+                    && parse_voice_command(&t.text).is_some()
+                    && !super::endpointing::settle(&mut t).await
+                {
+                    continue;
+                }
 
                 // Only inspect final transcriptions for commands.
                 if t.is_final
@@ -1671,6 +1838,8 @@ struct LlmStageControl {
     queue_cmd_rx: Option<mpsc::UnboundedReceiver<LlmQueueCommand>>,
     /// Shared flag: when true, the coordinator is awaiting a voice approval response.
     awaiting_approval: Arc<AtomicBool>,
+    /// Shared flag: whether the conversation gate is active (Fae is not asleep).
+    gate_active: Arc<AtomicBool>,
     /// Receiver for approval notifications from the handler bridge.
     approval_notification_rx:
         Option<mpsc::UnboundedReceiver<super::messages::ApprovalNotification>>,
@@ -1680,9 +1849,57 @@ struct LlmStageControl {
     jit_request_tx: Option<mpsc::UnboundedSender<crate::permissions::JitPermissionRequest>>,
 }
 
+/// Wait for a lazily loaded local model, saying "one moment" once if the
+/// user finishes a sentence before it is ready.
+///
+/// Queued transcriptions stay in the LLM channel and are answered once the
+/// model is ready.
+async fn wait_for_lazy_llm(
+    mut pending: PendingLlm,
+    tx: &mpsc::Sender<SentenceChunk>,
+    runtime_tx: Option<&broadcast::Sender<RuntimeEvent>>,
+    cancel: &CancellationToken,
+) -> Option<crate::llm::LocalLlm> {
+    let mut events = runtime_tx.map(broadcast::Sender::subscribe);
+    let mut told_user = false;
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => return None,
+            loaded = pending.wait() => {
+                return match loaded {
+                    Ok(llm) => Some(llm.shallow_clone()),
+                    Err(e) => {
+                        error!("lazy LLM load failed: {e}");
+                        None
+                    }
+                };
+            }
+            event = async {
+                match events.as_mut() {
+                    Some(rx) => rx.recv().await,
+                    None => std::future::pending().await,
+                }
+            } => match event {
+                Ok(RuntimeEvent::Transcription(t)) if t.is_final && !told_user => {
+                    told_user = true;
+                    let _ = tx
+                        .send(SentenceChunk {
+                            text: crate::i18n::text("conversation.warming_up").to_owned(),
+                            is_final: true,
+                        })
+                        .await;
+                }
+                Err(broadcast::error::RecvError::Closed) => events = None,
+                _ => {}
+            },
+        }
+    }
+}
+
 async fn run_llm_stage(
     mut config: SpeechConfig,
     preloaded: Option<crate::llm::LocalLlm>,
+    pending: Option<PendingLlm>,
     mut rx: mpsc::Receiver<Transcription>,
     tx: mpsc::Sender<SentenceChunk>,
     ctl: LlmStageControl,
@@ -1693,11 +1910,20 @@ async fn run_llm_stage(
     if let Err(e) = crate::personality::ensure_prompt_assets() {
         warn!("failed to ensure prompt assets: {e}");
     }
+    crate::turn_context::apply(&config.turn_context, config.language.as_deref());
+
+    let preloaded = match (preloaded, pending) {
+        (None, Some(pending)) => {
+            wait_for_lazy_llm(pending, &tx, ctl.runtime_tx.as_ref(), &ctl.cancel).await
+        }
+        (preloaded, _) => preloaded,
+    };
 
     // Apply RAM-based model selection so config.llm.model_id matches the
     // actually-loaded model (startup may have selected a different model
     // than what was persisted in config.toml).
     crate::config::apply_ram_model_selection(&mut config.llm);
+    crate::platform::power::apply_to_models(&mut config);
 
     let credential_manager = crate::credentials::create_manager();
     let voice_channels = crate::agent::AgentChannels {
@@ -1720,6 +1946,12 @@ async fn run_llm_stage(
             // Voice engine: disable tools. Tool-intent routing is handled
             // at the coordinator level by spawning background agents.
             agent.disable_tools();
+            agent.shape_voice_responses(config.voice_response.clone());
+            // A corrected speculative draft cuts off what is still queued.
+            let stop_tx = ctl.playback_cmd_tx.clone();
+            agent.set_speech_stop(move || {
+                let _ = stop_tx.send(PlaybackCommand::Stop);
+            });
             Box::new(agent)
         }
         Err(e) => {
@@ -1728,6 +1960,26 @@ async fn run_llm_stage(
         }
     };
 
+    // Every conversation is saved to the session store, turn by turn.
+    // The engine's own model titles them in the background after the first
+    // turn, so the reply path never waits on it.
+    let mut session_recorder = match crate::privacy::open_session_store(&config.privacy) {
+        Ok(store) => {
+            let provider = engine.provider();
+            Some(
+                crate::fae_llm::session::SessionRecorder::new(Arc::new(store))
+                    .with_model(config.llm.model_id.clone(), provider.name())
+                    .with_title_model(provider)
+                    .with_embedder(crate::fae_llm::session::search::embed_for_search)
+                    .with_thinking(config.llm.thinking.store),
+            )
+        }
+        Err(e) => {
+            warn!("conversations will not be saved: {e}");
+            None
+        }
+    };
+
     // Stash dependencies for spawning background agents.
     // Background agents share the same model weights via `Arc<Model>`.
     let bg_preloaded = preloaded.as_ref().map(crate::llm::LocalLlm::shallow_clone);
@@ -1801,6 +2053,7 @@ async fn run_llm_stage(
         voice_command_rx,
         queue_cmd_rx,
         awaiting_approval,
+        gate_active,
         approval_notification_rx: mut approval_notif_rx,
         approval_response_tx,
         jit_request_tx: _,
@@ -1812,6 +2065,8 @@ async fn run_llm_stage(
     let mut pending_inputs = LlmInputQueue::new(&config.llm);
     let mut transcription_channel_closed = false;
     let mut conversation_turns: Vec<ConversationTurn> = Vec::new();
+    // Silence timing for re-engagement and the end of the conversation.
+    let mut lifecycle = ConversationLifecycle::new(&config.conversation);
 
     let cancel = cancel;
     let mut turn_counter: u64 = 0;
@@ -1831,6 +2086,16 @@ async fn run_llm_stage(
 
     // Channel for receiving results from background agent tasks.
     let (bg_result_tx, mut bg_result_rx) = mpsc::channel::<crate::agent::BackgroundAgentResult>(4);
+    // Background call waiting for the user to say which match they meant.
+    let mut pending_clarification: Option<crate::fae_llm::agent::PendingClarification> = None;
+    // Fae asked what the right answer was after "no, that's wrong".
+    let mut awaiting_correction = false;
+    // The user asked to keep the current conversation out of the journal.
+    let mut journal_opted_out = false;
+    // Meeting mode switches; minutes are written when a meeting ends.
+    let mut meeting_rx = crate::meeting::subscribe();
+    // Conversations handed off from another device.
+    let mut handoff_rx = crate::handoff::subscribe();
 
     'outer: loop {
         if cancel.is_cancelled() {
@@ -1847,6 +2112,25 @@ async fn run_llm_stage(
         let next_input = if let Some(queued) = pending_inputs.dequeue_next() {
             queued
         } else {
+            // Keep the conversation ready to hand off to another device.
+            crate::handoff::publish(engine.history());
+            // Idle: prefill the prompt once Fae stops speaking so the next
+            // reply skips most of its prompt processing.
+            if config.llm.prefill_during_silence
+                && let Some(prefill) = engine.prefill_task()
+            {
+                let speaking = Arc::clone(&assistant_speaking);
+                tokio::spawn(async move {
+                    while speaking.load(Ordering::Relaxed) {
+                        tokio::time::sleep(PREFILL_SILENCE_POLL).await;
+                    }
+                    prefill.await;
+                });
+            }
+            // The silence counts from the end of Fae's reply.
+            if lifecycle.is_open() {
+                lifecycle.activity(Instant::now());
+            }
             loop {
                 let recv_injection = async {
                     match text_injection_rx.as_mut() {
@@ -1898,6 +2182,17 @@ async fn run_llm_stage(
                     }
                 };
 
+                let lifecycle_due = lifecycle.next();
+                let lifecycle_timer = async move {
+                    match lifecycle_due {
+                        Some((at, action)) => {
+                            tokio::time::sleep_until(at.into()).await;
+                            action
+                        }
+                        None => std::future::pending().await,
+                    }
+                };
+
                 enum Input {
                     Transcription(Option<Transcription>),
                     TextInjection(Option<TextInjection>),
@@ -1906,6 +2201,9 @@ async fn run_llm_stage(
                     BackgroundResult(crate::agent::BackgroundAgentResult),
                     ApprovalNotification(Option<super::messages::ApprovalNotification>),
                     ApprovalTimeout(&'static str),
+                    Lifecycle(LifecycleAction),
+                    MeetingChanged,
+                    HandoffReceived,
                 }
 
                 let input = tokio::select! {
@@ -1917,16 +2215,23 @@ async fn run_llm_stage(
                     Some(result) = bg_result_rx.recv() => Input::BackgroundResult(result),
                     notif = recv_approval_notif => Input::ApprovalNotification(notif),
                     action = approval_timeout => Input::ApprovalTimeout(action),
+                    action = lifecycle_timer => Input::Lifecycle(action),
+                    Ok(()) = meeting_rx.changed() => Input::MeetingChanged,
+                    Ok(()) = handoff_rx.changed() => Input::HandoffReceived,
                 };
 
                 match input {
-                    Input::Transcription(Some(transcription)) => {
+                    Input::Transcription(Some(mut transcription)) => {
                         if transcription.text.trim().is_empty() {
                             continue;
                         }
                         // If awaiting approval, intercept the transcription
-                        // for the approval parser instead of the LLM.
+                        // for the approval parser instead of the LLM. An
+                        // answer only counts once the user has finished.
                         if pending_voice_approval.is_some() {
+                            if !super::endpointing::settle(&mut transcription).await {
+                                continue;
+                            }
                             use crate::voice_command::{
                                 ApprovalVoiceResponse, parse_approval_response,
                             };
@@ -2055,7 +2360,7 @@ async fn run_llm_stage(
                         break QueuedLlmInput::TextInjection(injection);
                     }
                     Input::VoiceCommand(Some(cmd)) => {
-                        let response = handle_voice_command(&cmd);
+                        let response = handle_voice_command(&cmd, &config);
                         // Emit permissions changed event for GUI
                         use crate::voice_command::VoiceCommand;
                         match &cmd {
@@ -2074,6 +2379,8 @@ async fn run_llm_stage(
                             _ => {}
                         }
                         emit_panel_visibility_events(&cmd, &runtime_tx);
+                        emit_data_forget_request(&cmd, &runtime_tx);
+                        apply_offline_mode_command(&cmd, &runtime_tx);
                         if !response.is_empty() {
                             let _ = tx
                                 .send(SentenceChunk {
@@ -2119,6 +2426,7 @@ async fn run_llm_stage(
                             success = result.success,
                             "background agent task completed"
                         );
+                        pending_clarification = result.clarification;
                         if let Some(rt) = &runtime_tx {
                             let _ = rt.send(RuntimeEvent::BackgroundTaskCompleted {
                                 task_id: result.task_id.clone(),
@@ -2160,6 +2468,94 @@ async fn run_llm_stage(
                         }
                         continue;
                     }
+                    Input::MeetingChanged => {
+                        let active = crate::meeting::is_active();
+                        if let Some(rt) = &runtime_tx {
+                            let _ = rt.send(RuntimeEvent::MeetingModeChanged { active });
+                        }
+                        if active {
+                            continue;
+                        }
+                        let Some(transcript) = crate::meeting::take_finished() else {
+                            continue;
+                        };
+                        if transcript.segments.is_empty() {
+                            let _ = tx
+                                .send(SentenceChunk {
+                                    text: crate::i18n::text("meeting.empty").to_owned(),
+                                    is_final: true,
+                                })
+                                .await;
+                            continue;
+                        }
+
+                        // A background agent writes the minutes; the result
+                        // comes back like any other background task.
+                        let task_id = format!("minutes-{}", transcript.id);
+                        let description = "Write meeting minutes".to_owned();
+                        let task = crate::agent::BackgroundAgentTask {
+                            id: task_id.clone(),
+                            description: description.clone(),
+                            user_message: transcript.minutes_prompt(),
+                            conversation_context: String::new(),
+                            tool_allowlist: Vec::new(),
+                            resumed_call: None,
+                        };
+                        if let Some(rt) = &runtime_tx {
+                            let _ = rt.send(RuntimeEvent::BackgroundTaskStarted {
+                                task_id,
+                                description,
+                            });
+                        }
+                        let bg_tx = bg_result_tx.clone();
+                        let bg_cfg = bg_config.clone();
+                        let bg_model = bg_preloaded
+                            .as_ref()
+                            .map(crate::llm::LocalLlm::shallow_clone);
+                        let bg_channels = crate::agent::AgentChannels {
+                            tool_approval_tx: bg_tool_approval_tx.clone(),
+                            canvas_registry: bg_canvas_registry.clone(),
+                            shared_permissions: bg_shared_permissions.clone(),
+                            jit_request_tx: bg_jit_request_tx.clone(),
+                        };
+                        let bg_runtime = runtime_tx.clone();
+                        tokio::spawn(async move {
+                            let mut result = crate::agent::spawn_background_agent(
+                                task,
+                                bg_cfg.llm,
+                                bg_model.as_ref(),
+                                bg_runtime.clone(),
+                                bg_channels,
+                            )
+                            .await;
+                            result.spoken_summary =
+                                save_meeting_minutes(&transcript, &result, bg_runtime.as_ref());
+                            let _ = bg_tx.send(result).await;
+                        });
+                    }
+                    Input::HandoffReceived => {
+                        let Some(bundle) = crate::handoff::take_incoming() else {
+                            continue;
+                        };
+                        info!(
+                            source = bundle.source,
+                            messages = bundle.messages.len(),
+                            "continuing conversation handed off from another device"
+                        );
+                        if let Some(rt) = &runtime_tx {
+                            let _ = rt.send(RuntimeEvent::ConversationHandedOff {
+                                source: bundle.source.clone(),
+                                messages: bundle.messages.len(),
+                                memory_ids: bundle.memory_ids.clone(),
+                            });
+                        }
+                        engine.resume_conversation(bundle.messages);
+                        if let Some(recorder) = session_recorder.as_mut() {
+                            recorder.continue_from(engine.history().to_vec());
+                        }
+                        crate::handoff::publish(engine.history());
+                        continue;
+                    }
                     Input::ApprovalNotification(Some(notif)) => {
                         // Refresh enrolled profile at approval start so newly
                         // completed onboarding enrollment applies immediately.
@@ -2219,6 +2615,87 @@ async fn run_llm_stage(
                         }
                         continue;
                     }
+                    Input::Lifecycle(action) => {
+                        lifecycle.fired(action);
+                        if assistant_speaking.load(Ordering::Relaxed)
+                            || assistant_generating.load(Ordering::Relaxed)
+                            || pending_voice_approval.is_some()
+                        {
+                            // Still busy: the silence has not started yet.
+                            lifecycle.activity(Instant::now());
+                            continue;
+                        }
+                        match action {
+                            LifecycleAction::Reengage => {
+                                // Asleep means the user asked for quiet, as
+                                // does a meeting.
+                                let asleep = (config.conversation.enabled
+                                    && !gate_active.load(Ordering::Relaxed))
+                                    || crate::meeting::is_active();
+                                if !asleep {
+                                    info!("conversation quiet — asking if the user is still there");
+                                    let _ = tx
+                                        .send(SentenceChunk {
+                                            text: crate::i18n::text("conversation.still_there")
+                                                .to_owned(),
+                                            is_final: true,
+                                        })
+                                        .await;
+                                }
+                            }
+                            LifecycleAction::End => {
+                                let turns = conversation_turns.len();
+                                let session_id = format!(
+                                    "session-{}-{turn_counter}",
+                                    std::time::SystemTime::now()
+                                        .duration_since(std::time::UNIX_EPOCH)
+                                        .unwrap_or_default()
+                                        .as_millis()
+                                );
+                                let summarized = turns > 0
+                                    && capture_conversation_summary(
+                                        memory_orchestrator.as_ref(),
+                                        runtime_tx.as_ref(),
+                                        &session_id,
+                                        &summarize_conversation(&conversation_turns),
+                                    );
+                                if config.journal.enabled && turns > 0 && !journal_opted_out {
+                                    let journal = config.journal.clone();
+                                    let digest = journal_digest(&conversation_turns);
+                                    let kind =
+                                        crate::permissions::PermissionKind::DesktopAutomation;
+                                    let notes_allowed = bg_shared_permissions.as_ref().map_or_else(
+                                        || config.permissions.is_granted(kind),
+                                        |p| p.lock().is_ok_and(|store| store.is_granted(kind)),
+                                    );
+                                    tokio::task::spawn_blocking(move || {
+                                        let notes = notes_allowed
+                                            .then(crate::fae_llm::tools::apple::global_note_store);
+                                        crate::journal::record_conversation(
+                                            &journal,
+                                            turns,
+                                            &digest,
+                                            notes.as_deref(),
+                                        );
+                                    });
+                                }
+                                journal_opted_out = false;
+                                conversation_turns.clear();
+                                engine.truncate_history(0);
+                                if let Some(recorder) = session_recorder.as_mut() {
+                                    recorder.finish();
+                                }
+                                info!(turns, summarized, "conversation ended after silence");
+                                if let Some(rt) = &runtime_tx {
+                                    let _ = rt.send(RuntimeEvent::ConversationEnded {
+                                        turns,
+                                        summarized,
+                                    });
+                                }
+                            }
+                        }
+                        continue;
+                    }
                 }
             }
         };
@@ -2233,6 +2710,23 @@ async fn run_llm_stage(
             runtime_tx: runtime_tx.as_ref(),
             console_output,
         };
+        // A transcription cut at a provisional endpoint is answered ahead of
+        // time; its reply is held until the endpoint is confirmed.
+        let mut next_input = next_input;
+        let mut speculation = match &mut next_input {
+            QueuedLlmInput::Transcription(t) => {
+                match t.speculation.as_ref().map(Speculation::endpoint) {
+                    Some(Endpoint::Revised) => continue,
+                    Some(Endpoint::Confirmed) => {
+                        t.speculation = None;
+                        t.is_final = true;
+                    }
+                    _ => {}
+                }
+                t.speculation.clone()
+            }
+            QueuedLlmInput::TextInjection(_) => None,
+        };
         let Some(user_text) = prepare_user_text(next_input, &mut engine, &user_ctx) else {
             continue;
         };
@@ -2246,6 +2740,71 @@ async fn run_llm_stage(
                 .as_millis(),
             turn_counter
         );
+        lifecycle.activity(Instant::now());
+
+        let last_assistant_text = conversation_turns
+            .last()
+            .map(|t| t.assistant_text.as_str())
+            .unwrap_or("");
+        if config.journal.enabled && crate::journal::is_opt_out(&user_text) {
+            journal_opted_out = true;
+        }
+        // "No, that's wrong" records the previous turn as a correction; when
+        // the user didn't say what was right, Fae asks and the next turn
+        // supplies it.
+        let mut ask_for_correction = false;
+        if config.intelligence.capture_corrections {
+            let feedback = crate::intelligence::detect_negative_feedback(&user_text);
+            if let (Some(feedback), Some(previous)) = (feedback, conversation_turns.last()) {
+                ask_for_correction = feedback.right_answer.is_none();
+                let asked = previous.user_text.clone();
+                let replied = previous.assistant_text.clone();
+                tokio::task::spawn_blocking(move || {
+                    crate::intelligence::record_correction(
+                        &crate::fae_dirs::corrections_file(),
+                        crate::intelligence::FeedbackSource::Voice,
+                        &asked,
+                        &replied,
+                        feedback.right_answer.as_deref(),
+                    );
+                });
+            } else if awaiting_correction {
+                let text = user_text.clone();
+                tokio::task::spawn_blocking(move || {
+                    crate::intelligence::answer_correction(
+                        &crate::fae_dirs::corrections_file(),
+                        &text,
+                    );
+                });
+            }
+            awaiting_correction = ask_for_correction;
+        }
+        let mut intent =
+            crate::agent::classify_intent_with_context(&user_text, last_assistant_text);
+        // An answer to a clarification question re-runs the held-back call
+        // with the picked candidate.
+        let resumed_call = pending_clarification
+            .as_ref()
+            .and_then(|pending| pending.resolve(&user_text));
+        if let Some(ref call) = resumed_call {
+            intent.needs_tools = true;
+            intent.tool_allowlist = vec![call.function_name.clone()];
+        }
+
+        // Only a plain reply runs ahead of a provisional endpoint. Turns that
+        // act straight away (tools, canvas, a spoken thinking cue) wait for
+        // the user to finish.
+        if (intent.needs_tools
+            || intent.needs_thinking
+            || is_hide_conversation_request(&user_text)
+            || is_show_conversation_request(&user_text))
+            && let Some(mut pending) = speculation.take()
+            && !pending.settled().await
+        {
+            info!("speculative turn revised before it could act");
+            continue;
+        }
+        pending_clarification = None;
 
         if is_hide_conversation_request(&user_text) {
             let assistant_text = "Okay, I've hidden the conversation canvas.".to_owned();
@@ -2323,12 +2882,6 @@ async fn run_llm_stage(
         // `classify_intent_with_context` also upgrades short confirmations
         // ("yes", "go ahead") to background-agent routing when Fae's previous
         // response mentioned a tool-backed action she offered to take.
-        let last_assistant_text = conversation_turns
-            .last()
-            .map(|t| t.assistant_text.as_str())
-            .unwrap_or("");
-        let intent =
-            crate::agent::classify_intent_with_context(&user_text, last_assistant_text);
         if intent.needs_tools {
             info!(
                 tools = ?intent.tool_allowlist,
@@ -2345,21 +2898,24 @@ async fn run_llm_stage(
             interrupt.store(false, Ordering::Relaxed);
 
             // 1. Send canned acknowledgment immediately via TTS.
-            let ack = crate::personality::next_acknowledgment(
-                crate::personality::TOOL_ACKNOWLEDGMENTS,
-                ack_counter,
+            let ack = crate::accessibility::with_hint(
+                crate::personality::next_acknowledgment(
+                    crate::personality::TOOL_ACKNOWLEDGMENTS,
+                    ack_counter,
+                ),
+                crate::accessibility::InteractionHint::BackgroundTask,
             );
             ack_counter += 1;
             // Emit AssistantSentence so the conversation panel shows the ack bubble.
             if let Some(rt) = &runtime_tx {
                 let _ = rt.send(RuntimeEvent::AssistantSentence(SentenceChunk {
-                    text: ack.to_owned(),
+                    text: ack.clone(),
                     is_final: true,
                 }));
             }
             let _ = tx
                 .send(SentenceChunk {
-                    text: ack.to_owned(),
+                    text: ack.clone(),
                     is_final: true,
                 })
                 .await;
@@ -2375,6 +2931,7 @@ async fn run_llm_stage(
                 user_message: user_text.clone(),
                 conversation_context: context,
                 tool_allowlist: intent.tool_allowlist,
+                resumed_call,
             };
 
             if let Some(rt) = &runtime_tx {
@@ -2411,13 +2968,13 @@ async fn run_llm_stage(
             });
 
             // 4. Record the ack in conversation history.
-            append_conversation_turn(&mut conversation_turns, user_text.clone(), ack.to_owned());
+            append_conversation_turn(&mut conversation_turns, user_text.clone(), ack.clone());
             capture_memory_turn(
                 memory_orchestrator.as_ref(),
                 runtime_tx.as_ref(),
                 &turn_id,
                 &user_text,
-                ack,
+                &ack,
             );
             continue;
         }
@@ -2444,6 +3001,12 @@ async fn run_llm_stage(
         // ── End thinking mode routing ────────────────────────────────────
 
         let mut llm_input = format!("User message:\n{user_text}");
+        if ask_for_correction {
+            llm_input = format!(
+                "{}\n\n{llm_input}",
+                crate::intelligence::corrections::ASK_FOR_CORRECTION_NOTE
+            );
+        }
         if let Some(memory) = &memory_orchestrator {
             if let Ok(Some(memory_ctx)) = memory.recall_context(&user_text) {
                 if let Some(rt) = &runtime_tx {
@@ -2478,37 +3041,29 @@ async fn run_llm_stage(
                 build_local_coding_assistants_context(local_coding_assistants, permission);
             llm_input = format!("{local_coding_ctx}\n\n{llm_input}");
         }
+        if let Some(turn_ctx) = crate::turn_context::render() {
+            llm_input = format!("{turn_ctx}\n\n{llm_input}");
+        }
 
         let llm_start = Instant::now();
+        // Background jobs give way until this turn has been answered.
+        let _interactive_turn = crate::scheduler::priority::begin_interactive();
         assistant_generating.store(true, Ordering::Relaxed);
         if let Some(rt) = &runtime_tx {
             let _ = rt.send(RuntimeEvent::AssistantGenerating { active: true });
         }
         // Send a brief thinking tone so the user gets audio feedback that Fae
-        // heard them and is processing their request.
-        let _ = playback_cmd_tx.send(PlaybackCommand::ThinkingTone);
-        // Proxy channel captures assistant text for memory while forwarding to TTS.
+        // heard them and is processing their request. A speculative turn
+        // stays silent: the user may not have finished.
+        if speculation.is_none() {
+            let _ = playback_cmd_tx.send(PlaybackCommand::ThinkingTone);
+        }
+        // Proxy channel captures assistant text for memory while forwarding to
+        // TTS, holding a speculative reply until its endpoint settles.
         let (proxy_tx, mut proxy_rx) = mpsc::channel::<SentenceChunk>(SENTENCE_CHANNEL_SIZE);
         let final_tx = tx.clone();
         let forward_handle = tokio::spawn(async move {
-            let mut assistant_text = String::new();
-            while let Some(chunk) = proxy_rx.recv().await {
-                let is_final = chunk.is_final;
-                let text = chunk.text.trim();
-                if !text.is_empty() {
-                    if !assistant_text.is_empty() {
-                        assistant_text.push(' ');
-                    }
-                    assistant_text.push_str(text);
-                }
-                final_tx.send(chunk).await.map_err(|e| {
-                    crate::error::SpeechError::Channel(format!("LLM output channel closed: {e}"))
-                })?;
-                if is_final {
-                    break;
-                }
-            }
-            Ok::<String, crate::error::SpeechError>(assistant_text)
+            super::endpointing::forward_reply(&mut proxy_rx, &final_tx, speculation).await
         });
 
         let mut pending_bg_results: Vec<crate::agent::BackgroundAgentResult> = Vec::new();
@@ -2570,7 +3125,9 @@ async fn run_llm_stage(
                     if let Some(cmd) = cmd {
                         // Emit panel visibility events for the GUI.
                         emit_panel_visibility_events(&cmd, &runtime_tx);
-                        let response = handle_voice_command(&cmd);
+                        emit_data_forget_request(&cmd, &runtime_tx);
+                        apply_offline_mode_command(&cmd, &runtime_tx);
+                        let response = handle_voice_command(&cmd, &config);
                         if !response.is_empty() {
                             let _ = tx.send(SentenceChunk { text: response, is_final: true }).await;
                         }
@@ -2658,13 +3215,21 @@ async fn run_llm_stage(
                     }
                 } => {
                     // Queue approval notifications during generation — will be
-                    // processed after the current response finishes.
+                    // processed after the current response finishes. The turn
+                    // is suspended on the approval, so say why it went quiet.
                     if let Some(notif) = notif {
                         info!(
                             request_id = notif.request_id,
                             "queuing approval notification during generation"
                         );
                         approval_queue.push(notif);
+                        let _ = tx
+                            .send(SentenceChunk {
+                                text: crate::i18n::text(crate::personality::APPROVAL_WAITING)
+                                    .to_owned(),
+                                is_final: false,
+                            })
+                            .await;
                     } else {
                         approval_notif_rx = None;
                     }
@@ -2681,18 +3246,21 @@ async fn run_llm_stage(
             engine.set_reasoning_level(crate::fae_llm::types::ReasoningLevel::Off);
         }
 
-        let assistant_text = match forward_handle.await {
-            Ok(Ok(text)) => text,
+        // `None`: the speculative reply was dropped because the user kept
+        // talking.
+        let reply = match forward_handle.await {
+            Ok(Ok(reply)) => reply,
             Ok(Err(e)) => {
                 error!("failed to forward LLM chunks: {e}");
-                String::new()
+                Some(String::new())
             }
             Err(e) => {
                 error!("failed to join LLM forwarding task: {e}");
-                String::new()
+                Some(String::new())
             }
         };
 
+        let generated = gen_result.is_ok();
         match gen_result {
             Ok(interrupted) => {
                 let llm_duration = llm_start.elapsed();
@@ -2723,12 +3291,14 @@ async fn run_llm_stage(
                 }
                 error!("LLM error: {e}");
                 // Report the error to the user via TTS instead of silently dropping it.
-                let _ = tx
-                    .send(SentenceChunk {
-                        text: "Sorry, something went wrong with that request.".to_owned(),
-                        is_final: true,
-                    })
-                    .await;
+                if reply.is_some() {
+                    let _ = tx
+                        .send(SentenceChunk {
+                            text: crate::i18n::text("conversation.request_failed").to_owned(),
+                            is_final: true,
+                        })
+                        .await;
+                }
                 assistant_generating.store(false, Ordering::Relaxed);
                 if let Some(rt) = &runtime_tx {
                     let _ = rt.send(RuntimeEvent::AssistantGenerating { active: false });
@@ -2736,18 +3306,36 @@ async fn run_llm_stage(
             }
         }
 
-        append_conversation_turn(
-            &mut conversation_turns,
-            user_text.clone(),
-            assistant_text.clone(),
-        );
-        capture_memory_turn(
-            memory_orchestrator.as_ref(),
-            runtime_tx.as_ref(),
-            &turn_id,
-            &user_text,
-            &assistant_text,
-        );
+        if let Some(assistant_text) = reply {
+            append_conversation_turn(
+                &mut conversation_turns,
+                user_text.clone(),
+                assistant_text.clone(),
+            );
+            capture_memory_turn(
+                memory_orchestrator.as_ref(),
+                runtime_tx.as_ref(),
+                &turn_id,
+                &user_text,
+                &assistant_text,
+            );
+            if generated
+                && let Some(recorder) = session_recorder.as_mut()
+                && let Err(e) = recorder
+                    .record_turn(
+                        engine.last_turn(),
+                        engine.last_turn_tokens(),
+                        engine.last_turn_thinking(),
+                    )
+                    .await
+            {
+                warn!("failed to save conversation turn: {e}");
+            }
+        } else {
+            // The full utterance follows as its own turn.
+            info!("speculative turn dropped — user kept talking");
+            engine.discard_last_turn();
+        }
 
         // Process any background agent results that arrived during generation.
         for bg_result in pending_bg_results {
@@ -2804,6 +3392,20 @@ async fn run_llm_stage(
             tokio::spawn(crate::intelligence::run_background_extraction(params));
         }
 
+        // Todo capture from the user's words (non-blocking, file I/O).
+        if config.intelligence.extract_todos {
+            let text = user_text.clone();
+            let sync = config.intelligence.sync_todos_to_reminders;
+            tokio::task::spawn_blocking(move || {
+                let reminders = sync.then(crate::fae_llm::tools::apple::global_reminder_store);
+                crate::intelligence::capture_todos(
+                    &text,
+                    &crate::fae_dirs::todos_file(),
+                    reminders.as_deref(),
+                );
+            });
+        }
+
         if transcription_channel_closed && pending_inputs.is_empty() {
             break;
         }
@@ -2906,6 +3508,7 @@ fn prepare_user_text(
                     audio_duration_secs: None,
                     audio_captured_at: now,
                     transcribed_at: now,
+                    speculation: None,
                 }));
             }
 
@@ -2914,6 +3517,7 @@ fn prepare_user_text(
                 print!("[{}] ", ctx.name);
                 let _ = std::io::stdout().flush();
             }
+            engine.attach_images(injection.images);
             Some(injection.text)
         }
     }
@@ -2945,80 +3549,191 @@ fn emit_panel_visibility_events(
     }
 }
 
+/// Ask the host runtime to confirm a personal data wipe.
+///
+/// Called from both the normal and interrupted-generation code paths so a
+/// spoken "forget everything about me" is never dropped.
+fn emit_data_forget_request(
+    cmd: &crate::voice_command::VoiceCommand,
+    runtime_tx: &Option<broadcast::Sender<RuntimeEvent>>,
+) {
+    if matches!(cmd, crate::voice_command::VoiceCommand::ForgetEverything)
+        && let Some(rt) = runtime_tx
+    {
+        let _ = rt.send(RuntimeEvent::DataForgetRequested);
+    }
+}
+
+/// Switch offline mode for "go offline" / "go online" and tell the host so
+/// it can persist the setting.
+fn apply_offline_mode_command(
+    cmd: &crate::voice_command::VoiceCommand,
+    runtime_tx: &Option<broadcast::Sender<RuntimeEvent>>,
+) {
+    use crate::voice_command::VoiceCommand;
+
+    let offline = match cmd {
+        VoiceCommand::GoOffline => true,
+        VoiceCommand::GoOnline => false,
+        _ => return,
+    };
+    crate::offline::set_offline(offline);
+    if let Some(rt) = runtime_tx {
+        let _ = rt.send(RuntimeEvent::OfflineModeChanged { offline });
+    }
+}
+
 /// Handle a voice command.
 ///
 /// Returns a human-readable response string for TTS.
-fn handle_voice_command(cmd: &crate::voice_command::VoiceCommand) -> String {
+fn handle_voice_command(cmd: &crate::voice_command::VoiceCommand, config: &SpeechConfig) -> String {
+    use crate::i18n::text;
     use crate::voice_command::VoiceCommand;
 
     match cmd {
-        VoiceCommand::SwitchModel { .. } => {
-            "Voice model switching is not currently available.".to_owned()
-        }
+        VoiceCommand::SwitchModel { .. } => text("voice.model_switching_unavailable").to_owned(),
         VoiceCommand::ListModels | VoiceCommand::CurrentModel => {
-            "Voice model info is not currently available.".to_owned()
+            text("voice.model_info_unavailable").to_owned()
         }
         VoiceCommand::Help => crate::voice_command::help_response(),
-        VoiceCommand::ShowConversation => "Opening conversation.".to_owned(),
-        VoiceCommand::HideConversation => "Closing conversation.".to_owned(),
-        VoiceCommand::ShowCanvas => "Opening canvas.".to_owned(),
-        VoiceCommand::HideCanvas => "Closing canvas.".to_owned(),
-        VoiceCommand::GrantPermissions => {
-            "Permissions granted. I can now use tools without asking.".to_owned()
+        VoiceCommand::ShowConversation => text("voice.show_conversation").to_owned(),
+        VoiceCommand::HideConversation => text("voice.hide_conversation").to_owned(),
+        VoiceCommand::ShowCanvas => text("voice.show_canvas").to_owned(),
+        VoiceCommand::HideCanvas => text("voice.hide_canvas").to_owned(),
+        VoiceCommand::GrantPermissions => text("voice.permissions_granted").to_owned(),
+        VoiceCommand::RevokePermissions => text("voice.permissions_revoked").to_owned(),
+        // The confirmation prompt follows through the approval channel.
+        VoiceCommand::ForgetEverything => String::new(),
+        VoiceCommand::GoOffline => text("voice.offline_on").to_owned(),
+        VoiceCommand::GoOnline => text("voice.offline_off").to_owned(),
+        VoiceCommand::UndoChange => undo_last_change(),
+        // Asking aloud is the user's consent to transcribe the meeting.
+        VoiceCommand::StartMeeting => match crate::meeting::start(&config.meeting, true) {
+            Ok(_) => text("meeting.started").to_owned(),
+            Err(e) => {
+                warn!("failed to start meeting mode: {e}");
+                text("meeting.start_failed").to_owned()
+            }
+        },
+        // The minutes follow once the LLM stage sees the meeting end.
+        VoiceCommand::StopMeeting => match crate::meeting::stop() {
+            Some(_) => text("meeting.stopped").to_owned(),
+            None => text("meeting.not_active").to_owned(),
+        },
+    }
+}
+
+/// Save the minutes a background agent wrote for a finished meeting, tell
+/// the host, and return what to say about them.
+fn save_meeting_minutes(
+    transcript: &crate::meeting::MeetingTranscript,
+    result: &crate::agent::BackgroundAgentResult,
+    runtime_tx: Option<&broadcast::Sender<RuntimeEvent>>,
+) -> String {
+    let minutes = result.spoken_summary.trim();
+    if !result.success || minutes.is_empty() {
+        warn!(id = %transcript.id, "meeting minutes were not written");
+        return crate::i18n::text("meeting.minutes_failed").to_owned();
+    }
+    match transcript.save_minutes(minutes) {
+        Ok(path) => {
+            let action_items = crate::meeting::count_action_items(minutes);
+            info!(id = %transcript.id, action_items, "meeting minutes saved");
+            if let Some(rt) = runtime_tx {
+                let _ = rt.send(RuntimeEvent::MeetingMinutesReady {
+                    meeting_id: transcript.id.clone(),
+                    path: path.display().to_string(),
+                    action_items,
+                });
+            }
+            crate::i18n::plural("meeting.minutes_ready", action_items, &[])
         }
-        VoiceCommand::RevokePermissions => {
-            "Permissions revoked. I'll ask before using any tools.".to_owned()
+        Err(e) => {
+            warn!("failed to save meeting minutes: {e}");
+            crate::i18n::text("meeting.minutes_failed").to_owned()
         }
     }
 }
 
-/// Internal TTS engine wrapper.
-struct TtsEngine(Box<crate::tts::KokoroTts>);
-
-impl TtsEngine {
-    /// Synthesise text to f32 audio samples.
-    async fn synthesize(&mut self, text: &str) -> crate::error::Result<Vec<f32>> {
-        self.0.synthesize(text).await
+/// Revert the most recent file-changing tool call for "undo that change".
+fn undo_last_change() -> String {
+    let store = crate::fae_llm::tools::UndoStore::default_location();
+    let outcome = match store.undo(1) {
+        Ok(mut outcomes) => outcomes.pop(),
+        Err(e) => {
+            warn!("voice undo failed: {e}");
+            return crate::i18n::text("undo.history_unreadable").to_owned();
+        }
+    };
+    let Some(outcome) = outcome else {
+        return crate::i18n::text("undo.nothing").to_owned();
+    };
+    if let Some(e) = outcome.error {
+        warn!("voice undo of batch {} failed: {e}", outcome.batch.id);
+        return crate::i18n::text("undo.failed").to_owned();
+    }
+    let files = &outcome.batch.files;
+    match files.as_slice() {
+        [one] => {
+            let name = one.path.file_name().map_or_else(
+                || one.path.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+            crate::i18n::format("undo.reverted_file", &[("name", &name)])
+        }
+        _ => crate::i18n::format(
+            "undo.reverted_files",
+            &[("count", &files.len().to_string())],
+        ),
     }
 }
 
 async fn run_tts_stage(
     config: SpeechConfig,
-    preloaded: Option<crate::tts::KokoroTts>,
+    preloaded: Option<Box<dyn crate::tts::TtsEngine>>,
     mut rx: mpsc::Receiver<SentenceChunk>,
     tx: mpsc::Sender<SynthesizedAudio>,
     interrupt: Arc<AtomicBool>,
     cancel: CancellationToken,
     runtime_tx: Option<broadcast::Sender<RuntimeEvent>>,
 ) {
-    let mut engine = {
-        let tts = match preloaded {
-            Some(t) => t,
-            None => match crate::tts::KokoroTts::new(&config.tts) {
-                Ok(t) => t,
-                Err(e) => {
-                    error!("failed to init Kokoro TTS: {e}");
-                    return;
-                }
-            },
-        };
-        TtsEngine(Box::new(tts))
+    let mut engine = match preloaded {
+        Some(engine) => engine,
+        None => match crate::tts::create_engine(&config.tts).await {
+            Ok(engine) => engine,
+            Err(e) => {
+                error!("failed to init {:?} TTS: {e}", config.tts.backend);
+                return;
+            }
+        },
     };
+    // Delivery set by the model's style tags; lasts until the reply ends.
+    let mut style = crate::tts::SpeechStyle::default();
 
     loop {
         tokio::select! {
             () = cancel.cancelled() => break,
             sentence = rx.recv() => {
                 match sentence {
-                    Some(sentence) => {
+                    Some(mut sentence) => {
+                        let (text, tags) = crate::tts::style::take_style_tags(&sentence.text);
+                        sentence.text = text;
+                        for tag in tags {
+                            style.apply(tag);
+                        }
+                        let sentence_style = style;
+                        if sentence.is_final {
+                            style = crate::tts::SpeechStyle::default();
+                        }
                         // If an interrupt was requested (barge-in), drop any pending synthesis
                         // and only forward a final marker to unblock downstream state.
                         if interrupt.load(Ordering::Relaxed) {
                             if sentence.is_final {
                                 let synth = SynthesizedAudio {
                                     samples: Vec::new(),
-                                    sample_rate: config.tts.sample_rate,
+                                    sample_rate: engine.sample_rate(),
                                     is_final: true,
+                                    text: String::new(),
                                 };
                                 if tx.send(synth).await.is_err() {
                                     break;
@@ -3036,8 +3751,9 @@ async fn run_tts_stage(
                             if sentence.is_final || sentence.text.is_empty() {
                                 let synth = SynthesizedAudio {
                                     samples: Vec::new(),
-                                    sample_rate: config.tts.sample_rate,
+                                    sample_rate: engine.sample_rate(),
                                     is_final: true,
+                                    text: String::new(),
                                 };
                                 if tx.send(synth).await.is_err() {
                                     break;
@@ -3045,8 +3761,22 @@ async fn run_tts_stage(
                             }
                             continue;
                         }
+                        if crate::captions::captions_only() {
+                            // Speech is off: hand the text straight to the
+                            // playback stage, which paces the captions.
+                            let synth = SynthesizedAudio {
+                                samples: Vec::new(),
+                                sample_rate: engine.sample_rate(),
+                                is_final: sentence.is_final,
+                                text: sentence.text,
+                            };
+                            if tx.send(synth).await.is_err() {
+                                break;
+                            }
+                            continue;
+                        }
                         let tts_start = Instant::now();
-                        match engine.synthesize(&clean_text).await {
+                        match engine.synthesize_styled(&clean_text, &sentence_style).await {
                             Ok(audio) => {
                                 let tts_duration = tts_start.elapsed();
                                 info!(
@@ -3065,8 +3795,9 @@ async fn run_tts_stage(
                                     if sentence.is_final {
                                         let synth = SynthesizedAudio {
                                             samples: Vec::new(),
-                                            sample_rate: config.tts.sample_rate,
+                                            sample_rate: engine.sample_rate(),
                                             is_final: true,
+                                            text: String::new(),
                                         };
                                         let _ = tx.send(synth).await;
                                     }
@@ -3074,8 +3805,9 @@ async fn run_tts_stage(
                                 }
                                 let synth = SynthesizedAudio {
                                     samples: audio,
-                                    sample_rate: config.tts.sample_rate,
+                                    sample_rate: engine.sample_rate(),
                                     is_final: sentence.is_final,
+                                    text: sentence.text,
                                 };
                                 if tx.send(synth).await.is_err() {
                                     break;
@@ -3129,14 +3861,27 @@ async fn run_playback_stage(
     // keep assistant_speaking=true so the VAD echo suppression covers the gap
     // between TTS chunks.
     let mut received_final_chunk = true;
+    let mut captions = crate::captions::CaptionTimeline::new();
+    let stall_timeout = Duration::from_millis(u64::from(config.stall_timeout_ms));
+    let mut stall_check =
+        tokio::time::interval(crate::audio::watchdog::poll_interval(stall_timeout));
 
     loop {
         tokio::select! {
             () = cancel.cancelled() => break,
+            _ = stall_check.tick(), if !stall_timeout.is_zero() => {
+                playback.rebuild_if_stalled(stall_timeout);
+            }
             cmd = cmd_rx.recv() => {
                 match cmd {
                     Some(PlaybackCommand::Stop) => {
                         playback.stop();
+                        // Audio synthesized before the barge-in is stale.
+                        let flushed = queues::SYNTH.flush(&mut rx);
+                        if flushed > 0 {
+                            info!(flushed, "discarded queued speech after stop");
+                        }
+                        end_captions(&mut captions, runtime_tx.as_ref(), true);
                         received_final_chunk = true;
                         assistant_speaking.store(false, Ordering::Relaxed);
                         if let Some(ref r) = aec_ref {
@@ -3176,11 +3921,14 @@ async fn run_playback_stage(
                         }
                     }
                     Some(PlaybackEvent::Stopped) => {
+                        end_captions(&mut captions, runtime_tx.as_ref(), true);
                         received_final_chunk = true;
                         assistant_speaking.store(false, Ordering::Relaxed);
                         let _ = control_tx.send(ControlEvent::AssistantSpeechEnd { interrupted: true });
                     }
                     Some(PlaybackEvent::Level { rms }) => {
+                        // Fae is audibly speaking.
+                        crate::scheduler::priority::touch_interactive();
                         if let Some(rt) = &runtime_tx {
                             let _ = rt.send(RuntimeEvent::AssistantAudioLevel { rms });
                         }
@@ -3191,6 +3939,10 @@ async fn run_playback_stage(
             audio = rx.recv() => {
                 match audio {
                     Some(audio) => {
+                        caption_audio(&mut captions, &audio, runtime_tx.as_ref());
+                        if audio.is_final {
+                            end_captions(&mut captions, runtime_tx.as_ref(), false);
+                        }
                         if audio.samples.is_empty() && audio.is_final {
                             // End-of-response marker from TTS. Use mark_end() so
                             // Finished fires only after the queue actually drains
@@ -3226,6 +3978,48 @@ async fn run_playback_stage(
     }
 }
 
+/// Emit caption segments for a chunk queued for playback, timed by its audio
+/// or, without audio (captions-only mode), by reading speed.
+fn caption_audio(
+    timeline: &mut crate::captions::CaptionTimeline,
+    audio: &SynthesizedAudio,
+    runtime_tx: Option<&broadcast::Sender<RuntimeEvent>>,
+) {
+    let Some(rt) = runtime_tx else { return };
+    if audio.text.is_empty() || !crate::captions::enabled() {
+        return;
+    }
+    let duration = if audio.samples.is_empty() {
+        crate::captions::reading_duration(&audio.text)
+    } else {
+        Duration::from_secs_f64(audio.samples.len() as f64 / f64::from(audio.sample_rate.max(1)))
+    };
+    for segment in timeline.schedule(
+        &audio.text,
+        duration,
+        Instant::now(),
+        crate::captions::max_line_chars(),
+    ) {
+        let _ = rt.send(RuntimeEvent::CaptionSegment(segment));
+    }
+}
+
+/// Close the captioned utterance, if any.
+fn end_captions(
+    timeline: &mut crate::captions::CaptionTimeline,
+    runtime_tx: Option<&broadcast::Sender<RuntimeEvent>>,
+    interrupted: bool,
+) {
+    if let Some(utterance_id) = timeline.finish()
+        && let Some(rt) = runtime_tx
+    {
+        let _ = rt.send(RuntimeEvent::CaptionsEnded {
+            utterance_id,
+            interrupted,
+        });
+    }
+}
+
 /// Conversation gate state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum GateState {
@@ -3303,6 +4097,9 @@ struct ConversationGateControl {
 ///   - Optionally auto-returns to Idle after `idle_timeout_s` of inactivity.
 ///     When `idle_timeout_s == 0` (companion mode), Fae stays present until
 ///     explicitly paused by `GateCommand::Sleep`.
+///   - A speculative transcription from a provisional endpoint is forwarded
+///     straight away; if the user resumes speaking before the endpoint is
+///     confirmed, the gate stops that turn (see [`super::endpointing`]).
 async fn run_conversation_gate(
     config: SpeechConfig,
     mut stt_rx: mpsc::Receiver<Transcription>,
@@ -3333,6 +4130,13 @@ async fn run_conversation_gate(
     // Fae finishes speaking — without this, a 25-second response consumes
     // most of the 30-second window and the user's follow-up gets dropped.
     let mut prev_assistant_active = false;
+    // The speculative turn forwarded last, until its endpoint settles.
+    let mut in_flight: Option<Speculation> = None;
+    hotword::apply(&config.hotword);
+    let mut hotword_summary = tokio::time::interval_at(
+        tokio::time::Instant::now() + hotword::SUMMARY_INTERVAL,
+        hotword::SUMMARY_INTERVAL,
+    );
 
     info!("conversation gate active (always-on)");
 
@@ -3414,12 +4218,36 @@ async fn run_conversation_gate(
                     info!("conversation idle timeout, returning to idle");
                 }
             }
+            _ = hotword_summary.tick() => hotword::log_summary(),
+            // Stop the speculative turn if the user kept talking.
+            confirmed = async {
+                match in_flight.as_mut() {
+                    Some(speculation) => speculation.settled().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                in_flight = None;
+                if !confirmed && ctl.assistant_generating.load(Ordering::Relaxed) {
+                    ctl.interrupt.store(true, Ordering::Relaxed);
+                    info!("gate: speech resumed — stopping speculative turn");
+                }
+            }
             transcription = stt_rx.recv() => {
                 match transcription {
-                    Some(t) => {
+                    Some(mut t) => {
                         if t.text.is_empty() {
                             continue;
                         }
+                        match t.speculation.as_ref().map(Speculation::endpoint) {
+                            // Superseded by the full segment.
+                            Some(Endpoint::Revised) => continue,
+                            Some(Endpoint::Confirmed) => {
+                                t.speculation = None;
+                                t.is_final = true;
+                            }
+                            _ => {}
+                        }
+                        let speculation = t.speculation.clone();
 
                         // Use lowercase string for stable byte offsets back into `t.text`
                         // when extracting a query around the name mention.
@@ -3454,9 +4282,35 @@ async fn run_conversation_gate(
                                 }
                                 prev_assistant_active = assistant_active;
 
+                                // "That wasn't for you": stop whatever the
+                                // last accepted mention started and count it
+                                // as a false accept. Waits for the full
+                                // segment so a speculative one isn't counted
+                                // twice.
+                                if hotword::is_dismissal(&t.text) {
+                                    if speculation.is_some() {
+                                        continue;
+                                    }
+                                    ctl.interrupt.store(true, Ordering::Relaxed);
+                                    let _ = ctl.playback_cmd_tx.send(PlaybackCommand::Stop);
+                                    if let Some(tx) = &ctl.llm_queue_cmd_tx {
+                                        let _ = tx.send(LlmQueueCommand::ClearQueuedInputs);
+                                    }
+                                    engaged_until = None;
+                                    let counted = hotword::dismiss();
+                                    info!(counted, "gate: turn dismissed as not meant for Fae");
+                                    continue;
+                                }
+
                                 // Name-gated barge-in: saying "Fae, stop that"
                                 // should interrupt even during assistant speech.
-                                let name_match = find_name_mention(&lower_raw);
+                                // Mentions scoring below the hotword
+                                // sensitivity are treated as no mention.
+                                let name_match = find_name_mention(&lower_raw).filter(
+                                    |&(pos, matched_len)| {
+                                        hotword::judge(hotword::score(&lower_raw, pos, matched_len))
+                                    },
+                                );
 
                                 if let Some((pos, matched_len)) = name_match {
                                     ctl.interrupt.store(true, Ordering::Relaxed);
@@ -3484,6 +4338,9 @@ async fn run_conversation_gate(
                                     if llm_tx.send(forwarded).await.is_err() {
                                         break;
                                     }
+                                    if speculation.is_some() {
+                                        in_flight = speculation;
+                                    }
                                     let now = Instant::now();
                                     if require_direct_address && !direct_address_followup.is_zero() {
                                         engaged_until = Some(now + direct_address_followup);
@@ -3533,6 +4390,9 @@ async fn run_conversation_gate(
                                 if llm_tx.send(t).await.is_err() {
                                     break;
                                 }
+                                if speculation.is_some() {
+                                    in_flight = speculation;
+                                }
                                 let now = Instant::now();
                                 if require_direct_address && !direct_address_followup.is_zero() {
                                     engaged_until = Some(now + direct_address_followup);
@@ -3548,6 +4408,7 @@ async fn run_conversation_gate(
     }
 }
 
+use super::hotword;
 use super::name_detection::{
     canonicalize_wake_word_transcription, extract_query_around_name, find_name_mention,
 };
@@ -3620,22 +4481,41 @@ async fn forward_sentences(
     runtime_tx: Option<broadcast::Sender<RuntimeEvent>>,
     canvas_registry: Option<Arc<Mutex<CanvasSessionRegistry>>>,
     console_output: bool,
+    content_level: ContentPolicyLevel,
 ) {
     /// Send a chunk to both the runtime event stream and TTS.
+    ///
+    /// The runtime event carries the raw markdown; TTS gets the speechified
+    /// rendering, and nothing at all for a chunk that says nothing aloud
+    /// (a rule line, an empty code fence) unless it ends the response.
+    /// Both see the text only after the content policy has filtered it.
     async fn emit(
         chunk: &SentenceChunk,
         runtime_tx: &Option<broadcast::Sender<RuntimeEvent>>,
         tx: &mpsc::Sender<SentenceChunk>,
         console_output: bool,
+        policy: &mut ReplyFilter,
     ) {
+        let text = policy.filter(&chunk.text, chunk.is_final);
+        // Style tags are for the TTS stage only.
+        let (shown, _) = crate::tts::style::take_style_tags(&text);
         if let Some(rt) = runtime_tx {
-            let _ = rt.send(RuntimeEvent::AssistantSentence(chunk.clone()));
+            let _ = rt.send(RuntimeEvent::AssistantSentence(SentenceChunk {
+                text: shown.clone(),
+                is_final: chunk.is_final,
+            }));
         }
-        if console_output && !chunk.text.is_empty() {
-            print!("{}", chunk.text);
+        if console_output && !shown.is_empty() {
+            print!("{shown}");
             let _ = std::io::stdout().flush();
         }
-        let _ = tx.send(chunk.clone()).await;
+        let spoken = SentenceChunk {
+            text: super::speechify::speechify(&text),
+            is_final: chunk.is_final,
+        };
+        if !spoken.text.is_empty() || spoken.is_final || chunk.text.is_empty() {
+            let _ = tx.send(spoken).await;
+        }
     }
 
     /// How many characters of preamble to buffer before deciding this is
@@ -3664,6 +4544,7 @@ async fn forward_sentences(
     let mut pending = String::new(); // buffered text while Deciding
     let mut json_buf = String::new(); // JSON accumulator in Json mode
     let mut decide_started: Option<Instant> = None;
+    let mut policy = ReplyFilter::new(content_level);
 
     while let Some(chunk) = rx.recv().await {
         match mode {
@@ -3671,7 +4552,7 @@ async fn forward_sentences(
                 json_buf.push_str(&chunk.text);
             }
             Mode::Speech => {
-                emit(&chunk, &runtime_tx, &tx, console_output).await;
+                emit(&chunk, &runtime_tx, &tx, console_output, &mut policy).await;
             }
             Mode::Deciding => {
                 pending.push_str(&chunk.text);
@@ -3704,6 +4585,7 @@ async fn forward_sentences(
                             &runtime_tx,
                             &tx,
                             console_output,
+                            &mut policy,
                         )
                         .await;
                     }
@@ -3754,7 +4636,10 @@ async fn forward_sentences(
                         attempted_tool.map(|tool| {
                             // Convert snake_case tool name to readable words.
                             let readable = tool.replace('_', " ");
-                            format!("I tried to {readable}, but I don't have the right tools available for that right now.")
+                            crate::i18n::format(
+                                "conversation.tool_unavailable",
+                                &[("action", &readable)],
+                            )
                         })
                     };
 
@@ -3767,6 +4652,7 @@ async fn forward_sentences(
                             &runtime_tx,
                             &tx,
                             console_output,
+                            &mut policy,
                         )
                         .await;
                     }
@@ -3792,6 +4678,7 @@ async fn forward_sentences(
                             &runtime_tx,
                             &tx,
                             console_output,
+                            &mut policy,
                         )
                         .await;
                     } else if !pending.is_empty() {
@@ -3803,6 +4690,7 @@ async fn forward_sentences(
                             &runtime_tx,
                             &tx,
                             console_output,
+                            &mut policy,
                         )
                         .await;
                     }
@@ -3814,6 +4702,7 @@ async fn forward_sentences(
 
             // Reset for the next response.
             mode = Mode::Deciding;
+            policy.reset();
             pending.clear();
             json_buf.clear();
             decide_started = None;
@@ -3830,6 +4719,7 @@ use super::text_processing::{clean_model_json, extract_json_object, strip_markdo
 /// opens the canvas window) and returns a brief spoken description.
 ///
 /// Returns `None` if the text doesn't parse as valid canvas content.
+#[cfg(feature = "canvas")]
 fn try_render_canvas_json(
     text: &str,
     canvas_registry: &Option<Arc<Mutex<CanvasSessionRegistry>>>,
@@ -3884,6 +4774,7 @@ fn try_render_canvas_json(
             name: "canvas_render".to_owned(),
             success: true,
             output_text: None,
+            structured: None,
         });
     }
 
@@ -3893,26 +4784,37 @@ fn try_render_canvas_json(
             title, chart_type, ..
         } => {
             if let Some(title) = title {
-                format!("I've put that on the canvas. {title}.")
+                crate::i18n::format("canvas.chart_titled", &[("title", title)])
             } else {
-                format!("Here's the {chart_type} chart on the canvas.")
+                crate::i18n::format("canvas.chart", &[("chart_type", &chart_type.to_string())])
             }
         }
         RenderContent::Image { alt, .. } => {
             if let Some(alt) = alt {
-                format!("I've shown the image on the canvas. {alt}.")
+                crate::i18n::format("canvas.image_described", &[("alt", alt)])
             } else {
-                "I've shown the image on the canvas.".to_owned()
+                crate::i18n::text("canvas.image").to_owned()
             }
         }
-        RenderContent::Text { .. } => "I've put that text on the canvas.".to_owned(),
-        _ => "I've rendered that on the canvas.".to_owned(),
+        RenderContent::Text { .. } => crate::i18n::text("canvas.text").to_owned(),
+        _ => crate::i18n::text("canvas.other").to_owned(),
     };
 
     info!("intercepted JSON canvas output → rendered to canvas session");
     Some(description)
 }
 
+/// Without the `canvas` feature there is nowhere to render, so JSON output is
+/// classified like any other non-canvas JSON.
+#[cfg(not(feature = "canvas"))]
+fn try_render_canvas_json(
+    _text: &str,
+    _canvas_registry: &Option<Arc<Mutex<CanvasSessionRegistry>>>,
+    _runtime_tx: &Option<broadcast::Sender<RuntimeEvent>>,
+) -> Option<String> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
@@ -4575,6 +5477,7 @@ mod tests {
                 audio_duration_secs: None,
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send transcription");
@@ -4633,6 +5536,7 @@ mod tests {
                 audio_duration_secs: None,
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send ambient-like transcription");
@@ -4650,6 +5554,7 @@ mod tests {
                 audio_duration_secs: None,
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send direct-address transcription");
@@ -4670,6 +5575,7 @@ mod tests {
                 audio_duration_secs: None,
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send follow-up transcription");
@@ -4724,6 +5630,7 @@ mod tests {
                 audio_duration_secs: None,
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send approval response");
@@ -4791,6 +5698,7 @@ mod tests {
                 audio_duration_secs: Some(1.1),
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send mismatch");
@@ -4809,6 +5717,7 @@ mod tests {
                 audio_duration_secs: Some(1.1),
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send match");
@@ -4877,6 +5786,7 @@ mod tests {
                 audio_duration_secs: Some(1.1),
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send mismatch");
@@ -4892,6 +5802,7 @@ mod tests {
                 audio_duration_secs: Some(1.1),
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send direct-address mismatch");
@@ -4960,6 +5871,7 @@ mod tests {
                 audio_duration_secs: Some(1.0),
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send sample one");
@@ -4976,6 +5888,7 @@ mod tests {
                 audio_duration_secs: Some(1.0),
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send sample two");
@@ -5014,6 +5927,7 @@ mod tests {
             audio_duration_secs: Some(0.6),
             audio_captured_at: Instant::now(),
             transcribed_at: Instant::now(),
+            speculation: None,
         };
         let (ok, sim) = approval_speaker_verified(Some(&profile), &mismatch);
         assert!(!ok);
@@ -5078,6 +5992,7 @@ mod tests {
                 audio_duration_secs: None,
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send while idle");
@@ -5110,6 +6025,7 @@ mod tests {
                 audio_duration_secs: None,
                 audio_captured_at: Instant::now(),
                 transcribed_at: Instant::now(),
+                speculation: None,
             })
             .await
             .expect("send after wake");
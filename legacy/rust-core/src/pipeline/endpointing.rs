@@ -0,0 +1,232 @@
+//! Two-stage endpointing: answer on a short pause, take it back if the user
+//! keeps talking.
+//!
+//! Waiting for `vad.min_silence_duration_ms` of silence before answering adds
+//! that whole pause to every reply. With `vad.provisional_silence_ms` set, the
+//! VAD also marks a *provisional* end after a shorter pause: the audio so far
+//! goes to STT and the LLM straight away while the VAD keeps listening.
+//!
+//! - If the silence lasts to the full threshold, the endpoint is confirmed
+//!   and the reply, already generated or underway, is released to TTS.
+//! - If the user resumes within that grace window, the endpoint is revised:
+//!   the conversation gate stops the speculative turn, its reply is dropped
+//!   unheard, and the VAD's full segment (both parts of the utterance) goes
+//!   through as a normal turn.
+//!
+//! Each provisional end gets its own [`Speculation`], carried on the segment
+//! and its transcription, so every stage can tell which utterance a revision
+//! applies to.
+
+use super::messages::{SentenceChunk, Transcription};
+use crate::error::{Result, SpeechError};
+use tokio::sync::{mpsc, watch};
+
+/// Where a provisional endpoint stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    /// The user paused; the VAD is still inside the grace window.
+    Provisional,
+    /// The pause lasted: the utterance ended where it was provisionally cut.
+    Confirmed,
+    /// The user kept talking; the provisional cut is void.
+    Revised,
+}
+
+/// The VAD's side of a provisional endpoint.
+///
+/// Dropping it without [`confirm`](Self::confirm) revises the endpoint, so a
+/// segment that is discarded for any reason never gets answered.
+#[derive(Debug)]
+pub struct Speculator(watch::Sender<Endpoint>);
+
+/// A provisional endpoint, as seen by the stages after the VAD.
+#[derive(Debug, Clone)]
+pub struct Speculation(watch::Receiver<Endpoint>);
+
+/// Open a provisional endpoint.
+pub fn speculate() -> (Speculator, Speculation) {
+    let (tx, rx) = watch::channel(Endpoint::Provisional);
+    (Speculator(tx), Speculation(rx))
+}
+
+impl Speculator {
+    /// The pause lasted: release the speculative turn.
+    pub fn confirm(self) {
+        self.0.send_replace(Endpoint::Confirmed);
+    }
+}
+
+impl Drop for Speculator {
+    fn drop(&mut self) {
+        self.0.send_if_modified(|endpoint| {
+            let open = *endpoint == Endpoint::Provisional;
+            if open {
+                *endpoint = Endpoint::Revised;
+            }
+            open
+        });
+    }
+}
+
+impl Speculation {
+    /// Current state of the endpoint.
+    pub fn endpoint(&self) -> Endpoint {
+        *self.0.borrow()
+    }
+
+    /// Wait for the endpoint to settle; `true` when it was confirmed.
+    pub async fn settled(&mut self) -> bool {
+        match self.0.wait_for(|e| *e != Endpoint::Provisional).await {
+            Ok(endpoint) => *endpoint == Endpoint::Confirmed,
+            Err(_) => false,
+        }
+    }
+}
+
+/// Wait for a speculative transcription's endpoint to settle.
+///
+/// Returns `false` when it was revised and the transcription should be
+/// dropped; otherwise the transcription is final from here on. A
+/// transcription that was never speculative passes straight through.
+pub async fn settle(transcription: &mut Transcription) -> bool {
+    if let Some(mut speculation) = transcription.speculation.take() {
+        if !speculation.settled().await {
+            return false;
+        }
+        transcription.is_final = true;
+    }
+    true
+}
+
+/// Forward one reply from `rx` to `tx`, up to and including its final chunk.
+///
+/// A speculative reply is held back until its endpoint settles: released on
+/// confirmation, dropped unheard on revision (the rest of it is still
+/// drained, so the generator is never blocked). Returns the reply text, or
+/// `None` when it was dropped.
+///
+/// # Errors
+///
+/// Returns an error if `tx` is closed.
+pub(crate) async fn forward_reply(
+    rx: &mut mpsc::Receiver<SentenceChunk>,
+    tx: &mpsc::Sender<SentenceChunk>,
+    mut speculation: Option<Speculation>,
+) -> Result<Option<String>> {
+    async fn send(tx: &mpsc::Sender<SentenceChunk>, chunk: SentenceChunk) -> Result<()> {
+        tx.send(chunk)
+            .await
+            .map_err(|e| SpeechError::Channel(format!("LLM output channel closed: {e}")))
+    }
+
+    let mut text = String::new();
+    let mut held = Vec::new();
+    let mut dropped = false;
+    let mut ended = false;
+    while !ended || speculation.is_some() {
+        tokio::select! {
+            chunk = rx.recv(), if !ended => {
+                let Some(chunk) = chunk else {
+                    ended = true;
+                    continue;
+                };
+                ended = chunk.is_final;
+                let trimmed = chunk.text.trim();
+                if !trimmed.is_empty() {
+                    if !text.is_empty() {
+                        text.push(' ');
+                    }
+                    text.push_str(trimmed);
+                }
+                if dropped {
+                    continue;
+                }
+                if speculation.is_some() {
+                    held.push(chunk);
+                } else {
+                    send(tx, chunk).await?;
+                }
+            }
+            confirmed = async {
+                match speculation.as_mut() {
+                    Some(speculation) => speculation.settled().await,
+                    None => std::future::pending().await,
+                }
+            }, if speculation.is_some() => {
+                speculation = None;
+                if confirmed {
+                    for chunk in held.drain(..) {
+                        send(tx, chunk).await?;
+                    }
+                } else {
+                    held.clear();
+                    dropped = true;
+                }
+            }
+        }
+    }
+    Ok((!dropped).then_some(text))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn chunk(text: &str, is_final: bool) -> SentenceChunk {
+        SentenceChunk {
+            text: text.to_owned(),
+            is_final,
+        }
+    }
+
+    #[tokio::test]
+    async fn dropped_speculator_revises_but_confirmation_sticks() {
+        let (speculator, mut revised) = speculate();
+        drop(speculator);
+        assert_eq!(revised.endpoint(), Endpoint::Revised);
+        assert!(!revised.settled().await);
+
+        let (speculator, mut confirmed) = speculate();
+        speculator.confirm();
+        assert!(confirmed.settled().await);
+    }
+
+    #[tokio::test]
+    async fn speculative_reply_waits_for_confirmation() {
+        let (in_tx, mut rx) = mpsc::channel(8);
+        let (tx, mut out_rx) = mpsc::channel(8);
+        let (speculator, speculation) = speculate();
+        in_tx.send(chunk("Paris is ", false)).await.unwrap();
+        in_tx.send(chunk("sunny.", true)).await.unwrap();
+
+        let speculation = Some(speculation);
+        let forward = tokio::spawn(async move { forward_reply(&mut rx, &tx, speculation).await });
+        tokio::task::yield_now().await;
+        assert!(out_rx.try_recv().is_err());
+
+        speculator.confirm();
+        let text = forward.await.unwrap().unwrap();
+        assert_eq!(text.as_deref(), Some("Paris is sunny."));
+        assert_eq!(out_rx.recv().await.unwrap().text, "Paris is ");
+        assert!(out_rx.recv().await.unwrap().is_final);
+    }
+
+    #[tokio::test]
+    async fn revised_reply_is_dropped_unheard() {
+        let (in_tx, mut in_rx) = mpsc::channel(8);
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let (speculator, speculation) = speculate();
+        in_tx.send(chunk("The weather ", false)).await.unwrap();
+        drop(speculator);
+        in_tx.send(chunk("", true)).await.unwrap();
+
+        let text = forward_reply(&mut in_rx, &out_tx, Some(speculation))
+            .await
+            .unwrap();
+        assert_eq!(text, None);
+        drop(out_tx);
+        assert!(out_rx.recv().await.is_none());
+    }
+}
@@ -0,0 +1,355 @@
+//! Hotword sensitivity and false-accept telemetry.
+//!
+//! The conversation gate treats a mention of Fae's name as the hotword: it
+//! lets the user barge in while Fae is speaking and, with
+//! `require_direct_address`, is what opens a conversation. Each mention is
+//! scored by how much it looks like the user addressing Fae — the canonical
+//! spelling at the start of an utterance scores 1.0, a misheard variant in
+//! the middle of a sentence much less — and accepted when the score reaches
+//! `1 - sensitivity`. Sensitivity can be set per environment:
+//!
+//! ```toml
+//! [hotword]
+//! sensitivity = 1.0        # accept every mention (the default)
+//! environment = "kitchen"
+//!
+//! [hotword.environments]
+//! kitchen = 0.5            # the radio is on
+//! office = 0.8
+//! ```
+//!
+//! Saying "that wasn't for you" right after an accepted mention stops Fae
+//! and counts a false accept. A rejected mention followed shortly by an
+//! accepted one counts as a likely false rejection: the user had to repeat
+//! themselves. The counts per environment are logged periodically and
+//! included in diagnostics bundles (see [`snapshot`]); the log records carry
+//! scores but never what was said.
+
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::info;
+
+use super::text_processing::strip_punctuation;
+use crate::config::HotwordConfig;
+
+/// How soon after an accepted mention "that wasn't for you" counts against it.
+const FALSE_ACCEPT_WINDOW: Duration = Duration::from_secs(60);
+
+/// How soon after a rejected mention an accepted one suggests the rejection
+/// was wrong.
+const FALSE_REJECT_WINDOW: Duration = Duration::from_secs(10);
+
+/// How often the gate logs the counts.
+pub(crate) const SUMMARY_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Environment name used when none is configured.
+const DEFAULT_ENVIRONMENT: &str = "default";
+
+/// Words dropped before looking for a dismissal phrase.
+const LEAD_INS: &[&str] = &["no", "sorry", "oh", "um", "fae", "hey"];
+
+/// Phrases, without punctuation, that say the last turn wasn't meant for Fae.
+const DISMISSAL_PHRASES: &[&str] = &[
+    "that wasnt for you",
+    "that was not for you",
+    "that wasnt meant for you",
+    "that was not meant for you",
+    "i wasnt talking to you",
+    "i was not talking to you",
+    "wasnt talking to you",
+    "not talking to you",
+    "i was talking to someone else",
+];
+
+/// Hotword counts for one environment.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HotwordStats {
+    pub environment: String,
+    /// Mentions accepted as the user addressing Fae.
+    pub accepts: u64,
+    /// Mentions scored below the threshold.
+    pub rejects: u64,
+    /// Accepted mentions the user said weren't for Fae.
+    pub false_accepts: u64,
+    /// Rejected mentions shortly followed by an accepted one.
+    pub likely_false_rejects: u64,
+}
+
+/// The last accepted or rejected mention.
+#[derive(Debug, Clone, Copy)]
+struct Mention {
+    at: Instant,
+    score: f32,
+}
+
+/// Threshold state and counts behind the process-wide hotword functions.
+#[derive(Debug)]
+pub(crate) struct HotwordTracker {
+    environment: String,
+    sensitivity: f32,
+    stats: BTreeMap<String, HotwordStats>,
+    last_accept: Option<Mention>,
+    last_reject: Option<Mention>,
+}
+
+impl Default for HotwordTracker {
+    fn default() -> Self {
+        Self {
+            environment: DEFAULT_ENVIRONMENT.to_owned(),
+            sensitivity: 1.0,
+            stats: BTreeMap::new(),
+            last_accept: None,
+            last_reject: None,
+        }
+    }
+}
+
+impl HotwordTracker {
+    pub(crate) fn apply(&mut self, config: &HotwordConfig) {
+        let environment = config
+            .environment
+            .as_deref()
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .unwrap_or(DEFAULT_ENVIRONMENT);
+        self.environment = environment.to_owned();
+        self.sensitivity = config.sensitivity_for(environment).clamp(0.0, 1.0);
+    }
+
+    /// Accept or reject a mention scored `score` at `now`, counting the
+    /// outcome for the active environment.
+    pub(crate) fn judge(&mut self, score: f32, now: Instant) -> bool {
+        let threshold = 1.0 - self.sensitivity;
+        // Tolerate rounding so sensitivity 0.0 still accepts a 1.0 score.
+        let accepted = score + 1e-4 >= threshold;
+        let environment = self.environment.clone();
+        let recent_reject = self
+            .last_reject
+            .take_if(|reject| now.saturating_duration_since(reject.at) <= FALSE_REJECT_WINDOW);
+        if accepted {
+            self.stats_mut().accepts += 1;
+            self.last_accept = Some(Mention { at: now, score });
+            info!(target: "fae::hotword", %environment, score, threshold, "hotword accepted");
+            if let Some(reject) = recent_reject {
+                self.stats_mut().likely_false_rejects += 1;
+                info!(
+                    target: "fae::hotword",
+                    %environment,
+                    score = reject.score,
+                    threshold,
+                    "hotword likely falsely rejected"
+                );
+            }
+        } else {
+            self.stats_mut().rejects += 1;
+            self.last_reject = Some(Mention { at: now, score });
+            info!(target: "fae::hotword", %environment, score, threshold, "hotword rejected");
+        }
+        accepted
+    }
+
+    /// Count the most recent accept as false if it was within the window.
+    pub(crate) fn dismiss(&mut self, now: Instant) -> bool {
+        let Some(accept) = self
+            .last_accept
+            .take_if(|accept| now.saturating_duration_since(accept.at) <= FALSE_ACCEPT_WINDOW)
+        else {
+            return false;
+        };
+        self.stats_mut().false_accepts += 1;
+        info!(
+            target: "fae::hotword",
+            environment = %self.environment,
+            score = accept.score,
+            threshold = 1.0 - self.sensitivity,
+            "hotword false accept"
+        );
+        true
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<HotwordStats> {
+        self.stats.values().cloned().collect()
+    }
+
+    fn stats_mut(&mut self) -> &mut HotwordStats {
+        self.stats
+            .entry(self.environment.clone())
+            .or_insert_with(|| HotwordStats {
+                environment: self.environment.clone(),
+                ..Default::default()
+            })
+    }
+}
+
+static TRACKER: LazyLock<Mutex<HotwordTracker>> =
+    LazyLock::new(|| Mutex::new(HotwordTracker::default()));
+
+fn with_tracker<T>(f: impl FnOnce(&mut HotwordTracker) -> T) -> T {
+    let mut guard = TRACKER.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut guard)
+}
+
+/// Apply a hotword configuration process-wide.
+pub fn apply(config: &HotwordConfig) {
+    with_tracker(|tracker| tracker.apply(config));
+}
+
+/// How much the name mention at `pos..pos + matched_len` of `lower_text`
+/// looks like the user addressing Fae, from 0.0 to 1.0.
+pub(crate) fn score(lower_text: &str, pos: usize, matched_len: usize) -> f32 {
+    let spelling = match &lower_text[pos..pos + matched_len] {
+        "fae" => 1.0,
+        "faye" | "fay" | "fey" => 0.8,
+        _ => 0.6,
+    };
+    let words_before = lower_text[..pos].split_whitespace().count();
+    let words_after = lower_text[pos + matched_len..].split_whitespace().count();
+    // "Fae, …" and "hey Fae, …" open with the name; "thanks Fae" ends with
+    // it; a mention mid-sentence is more often about Fae than to her.
+    let placement = if words_before <= 1 {
+        1.0
+    } else if words_after == 0 {
+        0.9
+    } else {
+        0.6
+    };
+    spelling * placement
+}
+
+/// Accept or reject a name mention with `score` under the active
+/// sensitivity, and count the outcome.
+pub(crate) fn judge(score: f32) -> bool {
+    with_tracker(|tracker| tracker.judge(score, Instant::now()))
+}
+
+/// Record that the last accepted mention wasn't meant for Fae.
+///
+/// Returns `false` when there was no accepted mention in the last minute.
+pub(crate) fn dismiss() -> bool {
+    with_tracker(|tracker| tracker.dismiss(Instant::now()))
+}
+
+/// Whether `text` says the last turn wasn't meant for Fae.
+pub(crate) fn is_dismissal(text: &str) -> bool {
+    let normalized = strip_punctuation(&text.to_lowercase().replace('\u{2019}', "'"));
+    let mut rest = normalized.as_str();
+    while let Some(after) = LEAD_INS.iter().find_map(|word| {
+        rest.strip_prefix(word)
+            .filter(|after| after.is_empty() || after.starts_with(' '))
+    }) {
+        rest = after.trim_start();
+    }
+    DISMISSAL_PHRASES
+        .iter()
+        .any(|phrase| rest.starts_with(phrase))
+}
+
+/// Hotword counts per environment since startup.
+pub fn snapshot() -> Vec<HotwordStats> {
+    with_tracker(|tracker| tracker.snapshot())
+}
+
+/// Log the counts for every environment seen since startup.
+pub(crate) fn log_summary() {
+    for stats in snapshot() {
+        info!(
+            target: "fae::hotword",
+            environment = %stats.environment,
+            accepts = stats.accepts,
+            rejects = stats.rejects,
+            false_accepts = stats.false_accepts,
+            likely_false_rejects = stats.likely_false_rejects,
+            "hotword stats"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn score_of(text: &str) -> f32 {
+        let (pos, len) = super::super::name_detection::find_name_mention(text).expect("mention");
+        score(text, pos, len)
+    }
+
+    fn tracker(sensitivity: f32) -> HotwordTracker {
+        let mut tracker = HotwordTracker::default();
+        tracker.apply(&HotwordConfig {
+            sensitivity,
+            ..Default::default()
+        });
+        tracker
+    }
+
+    #[test]
+    fn direct_address_scores_highest() {
+        assert_eq!(score_of("fae what time is it"), 1.0);
+        assert_eq!(score_of("hey fae what time is it"), 1.0);
+        assert!((score_of("thanks a lot fae") - 0.9).abs() < 1e-6);
+        assert!((score_of("i asked fee about it yesterday") - 0.36).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sensitivity_sets_the_threshold() {
+        let now = Instant::now();
+        let mut lenient = tracker(1.0);
+        assert!(lenient.judge(0.1, now));
+
+        let mut strict = tracker(0.0);
+        assert!(strict.judge(1.0, now));
+        assert!(!strict.judge(0.9, now));
+
+        let mut config = HotwordConfig {
+            environment: Some("kitchen".to_owned()),
+            ..Default::default()
+        };
+        config.environments.insert("kitchen".to_owned(), 0.5);
+        let mut kitchen = HotwordTracker::default();
+        kitchen.apply(&config);
+        assert!(kitchen.judge(0.6, now));
+        assert!(!kitchen.judge(0.36, now));
+        assert_eq!(kitchen.snapshot()[0].environment, "kitchen");
+    }
+
+    #[test]
+    fn false_accepts_and_rejects_are_counted() {
+        let now = Instant::now();
+        let mut tracker = tracker(0.5);
+        assert!(!tracker.dismiss(now));
+
+        assert!(!tracker.judge(0.36, now));
+        assert!(tracker.judge(1.0, now + Duration::from_secs(3)));
+        assert!(tracker.dismiss(now + Duration::from_secs(5)));
+        assert!(!tracker.dismiss(now + Duration::from_secs(6)));
+
+        assert!(!tracker.judge(0.36, now + Duration::from_secs(10)));
+        assert!(tracker.judge(1.0, now + Duration::from_secs(30)));
+        assert!(!tracker.dismiss(now + Duration::from_secs(120)));
+
+        assert_eq!(
+            tracker.snapshot(),
+            vec![HotwordStats {
+                environment: "default".to_owned(),
+                accepts: 2,
+                rejects: 2,
+                false_accepts: 1,
+                likely_false_rejects: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn dismissals_are_detected() {
+        assert!(is_dismissal("That wasn\u{2019}t for you."));
+        assert!(is_dismissal("No, sorry, I wasn't talking to you"));
+        assert!(is_dismissal("fae, I was talking to someone else"));
+        assert!(!is_dismissal("I think that wasn't for you to decide"));
+        assert!(!is_dismissal("what time is it"));
+    }
+}
@@ -138,6 +138,7 @@ impl LlmInputQueue {
                         break;
                     };
                     append_collected_text(&mut merged.text, &next.text);
+                    merged.images.extend(next.images);
                 }
                 Some(QueuedLlmInput::TextInjection(merged))
             }
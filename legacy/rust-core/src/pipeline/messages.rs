@@ -1,14 +1,22 @@
 //! Message types passed between pipeline stages.
 
+use super::endpointing::Speculation;
+use crate::fae_llm::providers::message::ImageAttachment;
+use serde::{Deserialize, Serialize};
 use std::time::Instant;
 use tokio::sync::oneshot;
 
 /// Control events emitted by stages to coordinate interruption and UI state.
-#[derive(Debug, Clone)]
+///
+/// Serialized as part of [`RuntimeEvent`](crate::runtime::RuntimeEvent);
+/// `Instant`s are process-local and are not serialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum ControlEvent {
     /// VAD detected the start of user speech (barge-in signal).
     UserSpeechStart {
         /// Timestamp for the chunk that triggered speech start.
+        #[serde(skip, default = "Instant::now")]
         captured_at: Instant,
         /// RMS energy of the triggering chunk.
         rms: f32,
@@ -29,6 +37,18 @@ pub enum ControlEvent {
         /// Display name of the new input device, or `None` if unavailable.
         device_name: Option<String>,
     },
+    /// The default output entered or left a low-quality link, typically a
+    /// Bluetooth headset switching to its hands-free profile.
+    ///
+    /// Emitted by the audio device watcher, not a pipeline stage.
+    AudioQualityChanged {
+        /// Display name of the output device, or `None` if unavailable.
+        device_name: Option<String>,
+        /// Output sample rate in Hz (0 when there is no output device).
+        sample_rate: u32,
+        /// Whether the output is now in low-quality (hands-free) mode.
+        low_quality: bool,
+    },
     /// The pipeline has entered a degraded operating mode.
     ///
     /// Emitted by the coordinator when it detects that a required stage is
@@ -59,10 +79,16 @@ pub struct SpeechSegment {
     pub sample_rate: u32,
     /// When the speech segment started.
     pub started_at: Instant,
+    /// Set when the segment ends at a provisional endpoint: the user only
+    /// paused, and may yet carry on.
+    pub speculation: Option<Speculation>,
 }
 
 /// A transcription result from the STT engine.
-#[derive(Debug, Clone)]
+///
+/// The capture and transcription `Instant`s are not serialized; a
+/// deserialized value carries the time it was decoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transcription {
     /// The transcribed text.
     pub text: String,
@@ -82,9 +108,15 @@ pub struct Transcription {
     /// Duration of the source audio segment in seconds.
     pub audio_duration_secs: Option<f32>,
     /// Time the original audio was captured.
+    #[serde(skip, default = "Instant::now")]
     pub audio_captured_at: Instant,
     /// Time the transcription completed.
+    #[serde(skip, default = "Instant::now")]
     pub transcribed_at: Instant,
+    /// Set while the utterance ends at an unconfirmed provisional endpoint;
+    /// such a transcription is not final.
+    #[serde(skip)]
+    pub speculation: Option<Speculation>,
 }
 
 /// A single token emitted by the LLM during streaming generation.
@@ -97,7 +129,7 @@ pub struct LlmToken {
 }
 
 /// A sentence accumulated from LLM tokens, ready for TTS.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SentenceChunk {
     /// Complete sentence text.
     pub text: String,
@@ -110,6 +142,8 @@ pub struct SentenceChunk {
 pub struct TextInjection {
     /// The user's typed text.
     pub text: String,
+    /// Images attached to the message, fitted to `[llm.images]`.
+    pub images: Vec<ImageAttachment>,
     /// If `Some`, truncate LLM history to keep only this many entries
     /// (system prompt + N user/assistant pairs) before injecting.
     pub fork_at_keep_count: Option<usize>,
@@ -122,7 +156,8 @@ pub enum GateCommand {
     Wake,
     /// Deactivate the gate (equivalent to stop phrase).
     Sleep,
-    /// Signal that the audio input device has changed.
+    /// Signal that the audio input device, or the stream format of the
+    /// input or output device, has changed.
     ///
     /// The pipeline should stop the current capture stage and re-initialize
     /// audio capture from the new default input device.  The new device name
@@ -153,6 +188,9 @@ pub struct ApprovalNotification {
     pub tool_name: String,
     /// JSON-encoded tool arguments for generating a human-readable prompt.
     pub input_json: String,
+    /// Read-back to speak instead of the generic prompt, for destructive
+    /// calls (see [`crate::approval::DestructiveAction`]).
+    pub read_back: Option<String>,
 }
 
 /// Synthesized audio from TTS, ready for playback.
@@ -164,6 +202,9 @@ pub struct SynthesizedAudio {
     pub sample_rate: u32,
     /// Whether this is the last chunk of the current response.
     pub is_final: bool,
+    /// Sentence text the samples speak, for captions. Empty for
+    /// end-of-response markers.
+    pub text: String,
 }
 
 /// A conversation request from the scheduler to the pipeline.
@@ -180,6 +221,10 @@ pub struct ConversationRequest {
     pub system_addon: Option<String>,
     /// Timeout in seconds for this conversation. Defaults to 300s if None.
     pub timeout_secs: Option<u64>,
+    /// Whether the conversation may run through a provider batch API.
+    pub latency_insensitive: bool,
+    /// Tools the agent may use; empty means chosen from the prompt.
+    pub allowed_tools: Vec<String>,
     /// Channel for sending the conversation result back to the scheduler.
     pub response_tx: oneshot::Sender<ConversationResponse>,
 }
@@ -193,6 +238,10 @@ pub enum ConversationResponse {
     Error(String),
     /// Conversation exceeded the timeout limit.
     Timeout,
+    /// Conversation was aborted to make way for an interactive turn.
+    Preempted,
+    /// Conversation was handed to a provider batch that has not finished.
+    Deferred(String),
 }
 
 #[cfg(test)]
@@ -207,6 +256,8 @@ mod tests {
             prompt: "Check my calendar".to_owned(),
             system_addon: Some("You are a calendar assistant".to_owned()),
             timeout_secs: Some(120),
+            latency_insensitive: false,
+            allowed_tools: Vec::new(),
             response_tx: tx,
         };
 
@@ -227,6 +278,8 @@ mod tests {
             prompt: "Simple prompt".to_owned(),
             system_addon: None,
             timeout_secs: None,
+            latency_insensitive: false,
+            allowed_tools: Vec::new(),
             response_tx: tx,
         };
 
@@ -290,6 +343,8 @@ mod tests {
             prompt: "test prompt".to_owned(),
             system_addon: None,
             timeout_secs: None,
+            latency_insensitive: false,
+            allowed_tools: Vec::new(),
             response_tx: tx,
         };
 
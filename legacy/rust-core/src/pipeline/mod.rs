@@ -2,9 +2,13 @@
 
 pub(crate) mod conversation;
 pub mod coordinator;
+pub mod endpointing;
+pub mod hotword;
 pub(crate) mod input_queue;
 pub mod messages;
 pub(crate) mod name_detection;
+pub mod queues;
+pub(crate) mod speechify;
 pub(crate) mod text_processing;
 pub(crate) mod voice_approval;
 pub(crate) mod voice_identity;
@@ -0,0 +1,271 @@
+//! Bounded links between pipeline stages, with per-link overflow policies
+//! and queue-depth metrics for diagnostics.
+//!
+//! Every stage-to-stage data link is a bounded `mpsc` channel, so a slow
+//! stage can never make memory grow without limit. What happens when a link
+//! fills up depends on the data it carries:
+//!
+//! | Link | Policy |
+//! |------|--------|
+//! | `audio` (capture → AEC/VAD) | [`OverflowPolicy::DropNewest`]: the audio thread must never block |
+//! | `audio.aec` (AEC → VAD) | [`OverflowPolicy::Block`] |
+//! | `speech` (VAD → STT) | [`OverflowPolicy::Block`] |
+//! | `transcription` (STT → identity gate) | [`OverflowPolicy::Coalesce`]: queued partials collapse into the newest transcript |
+//! | `sentence` (LLM → TTS) | [`OverflowPolicy::Block`] |
+//! | `synth` (TTS → playback) | [`OverflowPolicy::FlushOnInterrupt`]: audio queued before a barge-in is discarded |
+//!
+//! Control channels (control events, playback and queue commands, voice
+//! commands, host injections) stay unbounded on purpose: they carry a few
+//! messages per user action, and losing one (a `Stop`, a speech-end) would
+//! leave stage state out of sync.
+//!
+//! Each link is a process-wide [`Link`]; [`snapshot`] reports the current
+//! depth and overflow counters of all of them.
+
+use crate::pipeline::messages::Transcription;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use tokio::sync::mpsc;
+
+/// What a link does when its consumer falls behind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// The producer waits for space (lossless backpressure).
+    Block,
+    /// Items arriving while the link is full are discarded.
+    DropNewest,
+    /// The consumer merges queued items, keeping only the newest.
+    Coalesce,
+    /// Queued items are discarded when the pipeline is interrupted.
+    FlushOnInterrupt,
+}
+
+type DepthProbe = Box<dyn Fn() -> usize + Send + Sync>;
+
+/// A named stage-to-stage link and its counters.
+pub struct Link {
+    name: &'static str,
+    policy: OverflowPolicy,
+    capacity: AtomicUsize,
+    dropped: AtomicU64,
+    merged: AtomicU64,
+    probe: Mutex<Option<DepthProbe>>,
+}
+
+/// Point-in-time metrics for one link.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkStats {
+    pub name: &'static str,
+    pub policy: OverflowPolicy,
+    /// Channel capacity (0 if the link has not been created yet).
+    pub capacity: usize,
+    /// Items currently queued.
+    pub depth: usize,
+    /// Items discarded by the overflow policy since startup.
+    pub dropped: u64,
+    /// Items merged into a newer one since startup.
+    pub merged: u64,
+}
+
+impl Link {
+    const fn new(name: &'static str, policy: OverflowPolicy) -> Self {
+        Self {
+            name,
+            policy,
+            capacity: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+            merged: AtomicU64::new(0),
+            probe: Mutex::new(None),
+        }
+    }
+
+    /// Create the bounded channel for this link and start reporting its depth.
+    ///
+    /// A later call (pipeline restart) replaces the reported channel.
+    pub fn channel<T: Send + 'static>(
+        &self,
+        capacity: usize,
+    ) -> (mpsc::Sender<T>, mpsc::Receiver<T>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        // A weak handle so the probe never keeps a finished stage's channel open.
+        let weak = tx.downgrade();
+        let probe: DepthProbe = Box::new(move || {
+            weak.upgrade()
+                .map_or(0, |tx| tx.max_capacity() - tx.capacity())
+        });
+        self.capacity.store(capacity, Ordering::Relaxed);
+        if let Ok(mut guard) = self.probe.lock() {
+            *guard = Some(probe);
+        }
+        (tx, rx)
+    }
+
+    /// The link's name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The link's overflow policy.
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Count items discarded by the overflow policy.
+    ///
+    /// Lock-free, so it is safe to call from the audio thread.
+    pub fn record_dropped(&self, count: u64) {
+        self.dropped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Count items merged into a newer one.
+    pub fn record_merged(&self, count: u64) {
+        self.merged.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Discard everything queued in `rx`, returning how many items were dropped.
+    pub fn flush<T>(&self, rx: &mut mpsc::Receiver<T>) -> usize {
+        let mut flushed = 0;
+        while rx.try_recv().is_ok() {
+            flushed += 1;
+        }
+        if flushed > 0 {
+            self.record_dropped(flushed as u64);
+        }
+        flushed
+    }
+
+    /// Current metrics for this link.
+    pub fn stats(&self) -> LinkStats {
+        let depth = self
+            .probe
+            .lock()
+            .ok()
+            .and_then(|guard| guard.as_ref().map(|probe| probe()))
+            .unwrap_or(0);
+        LinkStats {
+            name: self.name,
+            policy: self.policy,
+            capacity: self.capacity.load(Ordering::Relaxed),
+            depth,
+            dropped: self.dropped.load(Ordering::Relaxed),
+            merged: self.merged.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Microphone capture to AEC (or VAD when AEC is off).
+pub static AUDIO: Link = Link::new("audio", OverflowPolicy::DropNewest);
+/// Echo-cancelled audio to VAD.
+pub static AEC_AUDIO: Link = Link::new("audio.aec", OverflowPolicy::Block);
+/// VAD speech segments to STT.
+pub static SPEECH: Link = Link::new("speech", OverflowPolicy::Block);
+/// STT transcriptions to the identity gate.
+pub static TRANSCRIPTION: Link = Link::new("transcription", OverflowPolicy::Coalesce);
+/// Assistant sentences to TTS.
+pub static SENTENCE: Link = Link::new("sentence", OverflowPolicy::Block);
+/// Synthesized audio to playback.
+pub static SYNTH: Link = Link::new("synth", OverflowPolicy::FlushOnInterrupt);
+
+/// All pipeline links, in stage order.
+pub static LINKS: [&Link; 6] = [
+    &AUDIO,
+    &AEC_AUDIO,
+    &SPEECH,
+    &TRANSCRIPTION,
+    &SENTENCE,
+    &SYNTH,
+];
+
+/// Current metrics for every pipeline link.
+pub fn snapshot() -> Vec<LinkStats> {
+    LINKS.iter().map(|link| link.stats()).collect()
+}
+
+/// Collapse a partial transcription with the transcripts already queued
+/// behind it.
+///
+/// A partial is superseded by whatever STT produced next, so while `first`
+/// is partial, queued items replace it until a final one (or an empty
+/// queue) is reached. Merged items are counted on [`TRANSCRIPTION`].
+pub(crate) fn coalesce_partials(
+    first: Transcription,
+    rx: &mut mpsc::Receiver<Transcription>,
+) -> Transcription {
+    let mut current = first;
+    let mut merged = 0;
+    while !current.is_final {
+        match rx.try_recv() {
+            Ok(next) => {
+                current = next;
+                merged += 1;
+            }
+            Err(_) => break,
+        }
+    }
+    if merged > 0 {
+        TRANSCRIPTION.record_merged(merged);
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use std::time::Instant;
+
+    fn transcript(text: &str, is_final: bool) -> Transcription {
+        Transcription {
+            text: text.to_owned(),
+            is_final,
+            voiceprint: None,
+            audio_rms: None,
+            audio_duration_secs: None,
+            audio_captured_at: Instant::now(),
+            transcribed_at: Instant::now(),
+            speculation: None,
+        }
+    }
+
+    #[test]
+    fn depth_follows_the_channel() {
+        let link = Link::new("test", OverflowPolicy::Block);
+        assert_eq!(link.stats().capacity, 0);
+
+        let (tx, mut rx) = link.channel::<u32>(4);
+        tx.try_send(1).unwrap();
+        tx.try_send(2).unwrap();
+        let stats = link.stats();
+        assert_eq!((stats.capacity, stats.depth), (4, 2));
+
+        assert_eq!(link.flush(&mut rx), 2);
+        let stats = link.stats();
+        assert_eq!((stats.depth, stats.dropped), (0, 2));
+
+        // The probe does not keep the channel open.
+        drop(tx);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(link.stats().depth, 0);
+    }
+
+    #[test]
+    fn partials_collapse_into_the_newest_transcript() {
+        let (tx, mut rx) = mpsc::channel(8);
+        tx.try_send(transcript("hello", false)).unwrap();
+        tx.try_send(transcript("hello there", true)).unwrap();
+        tx.try_send(transcript("next", true)).unwrap();
+
+        let before = TRANSCRIPTION.stats().merged;
+        let merged = coalesce_partials(transcript("hel", false), &mut rx);
+        assert_eq!(merged.text, "hello there");
+        assert!(merged.is_final);
+        assert!(TRANSCRIPTION.stats().merged >= before + 2);
+
+        // A final transcript is never merged with the next one.
+        let next = rx.try_recv().unwrap();
+        assert_eq!(coalesce_partials(next, &mut rx).text, "next");
+    }
+}
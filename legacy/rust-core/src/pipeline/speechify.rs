@@ -0,0 +1,389 @@
+//! Markdown-aware rendering of assistant text for speech.
+//!
+//! Models answer in markdown, and a TTS voice reading it verbatim spells out
+//! backticks, asterisks and whole URLs. [`speechify`] turns a chunk of
+//! assistant text into what a person would say reading it aloud:
+//!
+//! - fenced code blocks are summarized ("A 12-line code snippet.")
+//! - links are read by their title, images by their alt text and bare URLs
+//!   by their domain
+//! - headings, emphasis, list bullets, quotes and table pipes are dropped
+//! - in English, common written abbreviations (`e.g.`, `vs.`), percentages
+//!   and ISO dates are expanded
+//!
+//! Only the TTS copy of the text is rendered; the host UI still receives
+//! the raw markdown.
+
+use crate::i18n::{self, Locale};
+use std::borrow::Cow;
+
+/// Written abbreviations and how they are read aloud (English only).
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("e.g.", "for example"),
+    ("i.e.", "that is"),
+    ("etc.", "et cetera"),
+    ("vs.", "versus"),
+    ("approx.", "approximately"),
+    ("min.", "minutes"),
+    ("hrs.", "hours"),
+    ("w/", "with"),
+    ("w/o", "without"),
+];
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Render `text` for speech in the active language.
+pub(crate) fn speechify(text: &str) -> String {
+    speechify_in(i18n::locale(), text)
+}
+
+/// Render `text` for speech in `locale`.
+pub(crate) fn speechify_in(locale: Locale, text: &str) -> String {
+    let english = locale == Locale::English;
+    let mut parts: Vec<String> = Vec::new();
+    // Non-blank lines seen so far inside an open code fence.
+    let mut code_lines: Option<usize> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            match code_lines.take() {
+                Some(count) if count > 0 => {
+                    parts.push(i18n::plural_in(locale, "speech.code_block", count, &[]));
+                }
+                Some(_) => {}
+                None => code_lines = Some(0),
+            }
+        } else if let Some(count) = code_lines.as_mut() {
+            if !trimmed.is_empty() {
+                *count += 1;
+            }
+        } else {
+            let spoken = speak_line(locale, trimmed);
+            let spoken = spoken
+                .split_whitespace()
+                .map(|word| {
+                    if english {
+                        expand_english_word(word)
+                    } else {
+                        Cow::Borrowed(word)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !spoken.is_empty() {
+                parts.push(spoken);
+            }
+        }
+    }
+    // A block cut off by the end of the response is still summarized.
+    if let Some(count) = code_lines.filter(|&count| count > 0) {
+        parts.push(i18n::plural_in(locale, "speech.code_block", count, &[]));
+    }
+
+    // Headings and list items rarely end in punctuation; give each line its
+    // own sentence so the voice pauses between them.
+    let last = parts.len().saturating_sub(1);
+    for part in &mut parts[..last] {
+        if !part.ends_with(['.', '!', '?', ':', ';', ',']) {
+            part.push('.');
+        }
+    }
+    parts.join(" ")
+}
+
+/// Render one line of prose: block markers first, then inline markup.
+fn speak_line(locale: Locale, line: &str) -> String {
+    if line.starts_with('|') {
+        // Table separator rows (`|---|:--|`) say nothing.
+        if line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')) {
+            return String::new();
+        }
+        return line
+            .split('|')
+            .map(str::trim)
+            .filter(|cell| !cell.is_empty())
+            .map(|cell| speak_inline(locale, cell))
+            .collect::<Vec<_>>()
+            .join(", ");
+    }
+    if is_thematic_break(line) {
+        return String::new();
+    }
+    speak_inline(locale, strip_block_marker(line))
+}
+
+/// `---`, `***` or `___` (optionally spaced) on a line of its own.
+fn is_thematic_break(line: &str) -> bool {
+    let marks: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3
+        && ['-', '*', '_']
+            .into_iter()
+            .any(|mark| marks.chars().all(|c| c == mark))
+}
+
+/// Strip a leading quote, heading, bullet or task-list marker.
+fn strip_block_marker(line: &str) -> &str {
+    let mut line = line;
+    while let Some(rest) = line.strip_prefix('>') {
+        line = rest.trim_start();
+    }
+    if line.starts_with('#') {
+        let rest = line.trim_start_matches('#');
+        if rest.is_empty() || rest.starts_with(' ') {
+            return rest.trim_start();
+        }
+    }
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            let rest = rest.trim_start();
+            return ["[ ] ", "[x] ", "[X] "]
+                .iter()
+                .find_map(|task| rest.strip_prefix(task))
+                .unwrap_or(rest);
+        }
+    }
+    line
+}
+
+/// Render links, images, URLs and emphasis within a line.
+fn speak_inline(locale: Locale, text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let prev = text[..text.len() - rest.len()].chars().next_back();
+        let after = &rest[c.len_utf8()..];
+
+        if c == '!'
+            && let Some((alt, _, tail)) = parse_link(after)
+        {
+            let alt = alt.trim();
+            if alt.is_empty() {
+                out.push_str(i18n::text_in(locale, "speech.image_untitled"));
+            } else {
+                out.push_str(&i18n::format_in(locale, "speech.image", &[("alt", alt)]));
+            }
+            rest = tail;
+            continue;
+        }
+        if c == '['
+            && let Some((title, target, tail)) = parse_link(rest)
+        {
+            if title.trim().is_empty() {
+                out.push_str(url_domain(target.split_whitespace().next().unwrap_or("")));
+            } else {
+                out.push_str(&speak_inline(locale, title));
+            }
+            rest = tail;
+            continue;
+        }
+        if c == '<'
+            && (after.starts_with("https://") || after.starts_with("http://"))
+            && let Some(end) = after.find('>')
+        {
+            out.push_str(url_domain(&after[..end]));
+            rest = &after[end + 1..];
+            continue;
+        }
+        if (rest.starts_with("https://") || rest.starts_with("http://"))
+            && !prev.is_some_and(char::is_alphanumeric)
+        {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let url = rest[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+            out.push_str(url_domain(url));
+            rest = &rest[url.len()..];
+            continue;
+        }
+        if ["**", "__", "~~"].iter().any(|mark| rest.starts_with(mark)) {
+            rest = &rest[2..];
+            continue;
+        }
+
+        let next = after.chars().next();
+        let keep = match c {
+            '`' => false,
+            // `2 * 3` is arithmetic; anything else is emphasis.
+            '*' => prev.is_some_and(char::is_whitespace) && next.is_some_and(char::is_whitespace),
+            // `snake_case` keeps its underscores.
+            '_' => {
+                prev.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric)
+            }
+            _ => true,
+        };
+        if keep {
+            out.push(c);
+        }
+        rest = after;
+    }
+    out
+}
+
+/// Parse `[label](target)` at the start of `text`, returning the label, the
+/// target and the text after the closing parenthesis.
+fn parse_link(text: &str) -> Option<(&str, &str, &str)> {
+    let inner = text.strip_prefix('[')?;
+    let close = inner.find(']')?;
+    let target = inner[close + 1..].strip_prefix('(')?;
+    let mut depth = 0usize;
+    for (i, c) in target.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some((&inner[..close], &target[..i], &target[i + 1..])),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The host of `url` without scheme, credentials, port or `www.`.
+fn url_domain(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = without_scheme
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or(without_scheme);
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = host.split(':').next().unwrap_or(host);
+    host.strip_prefix("www.").unwrap_or(host)
+}
+
+/// Expand an English abbreviation, percentage or ISO date; other words are
+/// returned unchanged.
+fn expand_english_word(word: &str) -> Cow<'_, str> {
+    let core = word.trim_end_matches([',', ';', ':', '!', '?', ')']);
+    if let Some((_, spoken)) = ABBREVIATIONS.iter().find(|(written, _)| *written == core) {
+        return Cow::Owned(format!("{spoken}{}", &word[core.len()..]));
+    }
+    let bare = core.trim_end_matches('.');
+    let tail = &word[bare.len()..];
+    if let Some(number) = bare.strip_suffix('%').filter(|n| is_number(n)) {
+        return Cow::Owned(format!("{number} percent{tail}"));
+    }
+    if let Some(date) = spoken_iso_date(bare) {
+        return Cow::Owned(format!("{date}{tail}"));
+    }
+    Cow::Borrowed(word)
+}
+
+fn is_number(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+}
+
+/// `2026-10-16` → `October 16, 2026`.
+fn spoken_iso_date(text: &str) -> Option<String> {
+    let mut fields = text.split('-');
+    let (year, month, day) = (fields.next()?, fields.next()?, fields.next()?);
+    if fields.next().is_some()
+        || year.len() != 4
+        || month.len() != 2
+        || day.len() != 2
+        || ![year, month, day]
+            .iter()
+            .all(|f| f.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    let month: usize = month.parse().ok()?;
+    let day: u32 = day.parse().ok()?;
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+    let name = MONTHS.get(month.checked_sub(1)?)?;
+    Some(format!("{name} {day}, {year}"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    fn en(text: &str) -> String {
+        speechify_in(Locale::English, text)
+    }
+
+    #[test]
+    fn code_blocks_are_summarized() {
+        let text = "Try this:\n```rust\nfn main() {\n\n    println!(\"hi\");\n}\n```\nThen run it.";
+        assert_eq!(en(text), "Try this: A 3-line code snippet. Then run it.");
+        assert_eq!(en("```sh\nls\n```"), "A one-line code snippet.");
+        // Cut off mid-block by the end of the response.
+        assert_eq!(en("```\na\nb"), "A 2-line code snippet.");
+        assert_eq!(en("```\n```"), "");
+    }
+
+    #[test]
+    fn code_block_summary_is_localized() {
+        assert_eq!(
+            speechify_in(Locale::German, "```\na\nb\n```"),
+            "Ein Code-Beispiel mit 2 Zeilen."
+        );
+    }
+
+    #[test]
+    fn links_images_and_urls_are_read_naturally() {
+        assert_eq!(
+            en("See [the docs](https://docs.rs/tokio/latest/(x)) for details."),
+            "See the docs for details."
+        );
+        assert_eq!(
+            en("![A red fox in snow](fox.png)"),
+            "an image: A red fox in snow"
+        );
+        assert_eq!(en("![](fox.png)"), "an image");
+        assert_eq!(
+            en("It's at https://www.example.com/a?b=1."),
+            "It's at example.com."
+        );
+        assert_eq!(en("Open <https://user@host.org:8080/x>"), "Open host.org");
+    }
+
+    #[test]
+    fn markdown_formatting_is_dropped() {
+        assert_eq!(
+            en("## Summary\n- **Bold** and *italic*\n- Run `cargo test`"),
+            "Summary. Bold and italic. Run cargo test"
+        );
+        assert_eq!(en("> quoted ~~old~~ text"), "quoted old text");
+        assert_eq!(en("- [x] done"), "done");
+        assert_eq!(en("---"), "");
+        assert_eq!(en("2 * 3 is in my_var"), "2 * 3 is in my_var");
+        assert_eq!(
+            en("| Name | Size |\n|---|:--:|\n| a | 1 |"),
+            "Name, Size. a, 1"
+        );
+    }
+
+    #[test]
+    fn english_abbreviations_numbers_and_dates_are_expanded() {
+        assert_eq!(
+            en("Fruit, e.g. apples, pears, etc., rose 12%."),
+            "Fruit, for example apples, pears, et cetera, rose 12 percent."
+        );
+        assert_eq!(
+            en("Due 2026-10-16, not 2026-13-01."),
+            "Due October 16, 2026, not 2026-13-01."
+        );
+        assert_eq!(speechify_in(Locale::French, "50% e.g."), "50% e.g.");
+    }
+}
@@ -53,10 +53,16 @@ pub(crate) async fn start_voice_approval(
     awaiting_approval: &Arc<AtomicBool>,
     cancel: &CancellationToken,
 ) -> PendingVoiceApproval {
-    let prompt = crate::personality::format_approval_prompt(
-        &notification.tool_name,
-        &notification.input_json,
-    );
+    // Destructive calls read back exactly what will happen.
+    let base = match &notification.read_back {
+        Some(read_back) => read_back.clone(),
+        None => crate::personality::format_approval_prompt(
+            &notification.tool_name,
+            &notification.input_json,
+        ),
+    };
+    let prompt =
+        crate::accessibility::with_hint(&base, crate::accessibility::InteractionHint::Approval);
     info!(
         request_id = notification.request_id,
         tool = %notification.tool_name,
@@ -85,12 +91,12 @@ pub(crate) async fn start_voice_approval(
 /// for every approval resolution path (Approved, Denied, Ambiguous max, Timeout).
 pub(crate) async fn resolve_and_advance_approval(
     ctx: &mut ApprovalContext<'_>,
-    ack_list: &[&str],
+    ack_phrases: &'static str,
     approved: bool,
     source: &str,
     speaker_verified: Option<bool>,
 ) {
-    let ack = crate::personality::next_acknowledgment(ack_list, *ctx.ack_counter);
+    let ack = crate::personality::next_acknowledgment(ack_phrases, *ctx.ack_counter);
     *ctx.ack_counter += 1;
     resolve_voice_approval(
         ctx.pending,
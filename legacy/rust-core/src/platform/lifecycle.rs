@@ -0,0 +1,220 @@
+//! System sleep/wake lifecycle.
+//!
+//! The host app observes the OS power notifications (`NSWorkspace`
+//! `willSleep`/`didWake` on macOS, including lid close) and forwards them
+//! as the `system.lifecycle` command. [`LifecycleState`] turns those
+//! notifications into suspend/resume transitions: the handler stops the
+//! pipeline and pauses background jobs before the machine sleeps, then
+//! restarts it on wake so capture and playback re-probe the audio devices.
+//!
+//! A host may not forward the notifications, and a stream left running
+//! through a sleep can come back deaf. [`WakeWatcher`] catches that case
+//! after the fact: the monotonic clock stops while the machine sleeps (on
+//! macOS and Linux) but the wall clock does not, so a jump between the two
+//! means the system was asleep.
+
+use crate::pipeline::messages::GateCommand;
+use crate::runtime::RuntimeEvent;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// Smallest clock gap treated as a sleep rather than a clock adjustment.
+const MIN_SLEEP_GAP: Duration = Duration::from_secs(30);
+
+/// A power notification forwarded by the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerEvent {
+    /// The system (or lid) is about to sleep.
+    Sleep,
+    /// The system woke up.
+    Wake,
+}
+
+impl PowerEvent {
+    /// Parse the `state` field of a `system.lifecycle` command.
+    pub fn parse(state: &str) -> Option<Self> {
+        match state {
+            "sleep" | "will_sleep" => Some(Self::Sleep),
+            "wake" | "did_wake" => Some(Self::Wake),
+            _ => None,
+        }
+    }
+}
+
+/// What the runtime has to do for a power event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// Pause capture, flush state and suspend background jobs.
+    Suspend,
+    /// Resume after sleeping for `slept_for` (wall-clock time).
+    Resume { slept_for: Duration },
+}
+
+/// Whether the runtime is suspended for sleep.
+///
+/// Hosts may repeat notifications (several observers, screen sleep and
+/// system sleep), so a [`PowerEvent`] that does not change the state yields
+/// no transition.
+#[derive(Debug, Default)]
+pub struct LifecycleState {
+    suspended_at: Option<SystemTime>,
+}
+
+impl LifecycleState {
+    /// Whether the runtime is currently suspended.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some()
+    }
+
+    /// Apply `event` received at `now`.
+    pub fn apply(&mut self, event: PowerEvent, now: SystemTime) -> Option<Transition> {
+        match (event, self.suspended_at) {
+            (PowerEvent::Sleep, None) => {
+                self.suspended_at = Some(now);
+                Some(Transition::Suspend)
+            }
+            (PowerEvent::Wake, Some(since)) => {
+                self.suspended_at = None;
+                Some(Transition::Resume {
+                    slept_for: now.duration_since(since).unwrap_or_default(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Detects a sleep from the gap between the wall and monotonic clocks.
+#[derive(Debug)]
+pub struct WakeDetector {
+    wall: SystemTime,
+    mono: Instant,
+}
+
+impl WakeDetector {
+    /// Start measuring from now.
+    pub fn new() -> Self {
+        Self {
+            wall: SystemTime::now(),
+            mono: Instant::now(),
+        }
+    }
+
+    /// How long the system slept since the last check, if it did.
+    pub fn check(&mut self) -> Option<Duration> {
+        self.check_at(SystemTime::now(), Instant::now())
+    }
+
+    fn check_at(&mut self, wall: SystemTime, mono: Instant) -> Option<Duration> {
+        let wall_elapsed = wall.duration_since(self.wall).unwrap_or_default();
+        let mono_elapsed = mono.saturating_duration_since(self.mono);
+        self.wall = wall;
+        self.mono = mono;
+        let gap = wall_elapsed.saturating_sub(mono_elapsed);
+        (gap >= MIN_SLEEP_GAP).then_some(gap)
+    }
+}
+
+impl Default for WakeDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Watches a running pipeline for an unannounced sleep and, on wake,
+/// emits [`RuntimeEvent::Resumed`] and sends [`GateCommand::RestartAudio`]
+/// so the audio devices are probed again.
+pub struct WakeWatcher {
+    gate_tx: mpsc::UnboundedSender<GateCommand>,
+    runtime_tx: broadcast::Sender<RuntimeEvent>,
+    cancel: CancellationToken,
+    poll_interval: Duration,
+}
+
+impl WakeWatcher {
+    /// Create a watcher; call [`run`](Self::run) to start polling.
+    pub fn new(
+        gate_tx: mpsc::UnboundedSender<GateCommand>,
+        runtime_tx: broadcast::Sender<RuntimeEvent>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            gate_tx,
+            runtime_tx,
+            cancel,
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Run until cancelled or the pipeline stops accepting gate commands.
+    pub async fn run(self) {
+        let mut detector = WakeDetector::new();
+        loop {
+            tokio::select! {
+                () = self.cancel.cancelled() => break,
+                () = tokio::time::sleep(self.poll_interval) => {
+                    let Some(slept_for) = detector.check() else {
+                        continue;
+                    };
+                    info!(slept_secs = slept_for.as_secs(), "system woke from sleep — restarting audio");
+                    let _ = self.runtime_tx.send(RuntimeEvent::Resumed {
+                        slept_secs: slept_for.as_secs(),
+                    });
+                    if self.gate_tx.send(GateCommand::RestartAudio { device_name: None }).is_err() {
+                        warn!("wake watcher: gate_tx closed, stopping");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn repeated_notifications_are_ignored() {
+        let mut state = LifecycleState::default();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        assert_eq!(state.apply(PowerEvent::Wake, t0), None);
+        assert_eq!(
+            state.apply(PowerEvent::Sleep, t0),
+            Some(Transition::Suspend)
+        );
+        assert_eq!(state.apply(PowerEvent::Sleep, t0), None);
+        assert!(state.is_suspended());
+        assert_eq!(
+            state.apply(PowerEvent::Wake, t0 + Duration::from_secs(600)),
+            Some(Transition::Resume {
+                slept_for: Duration::from_secs(600)
+            })
+        );
+        assert!(!state.is_suspended());
+    }
+
+    #[test]
+    fn a_wall_clock_jump_is_a_sleep() {
+        let mut detector = WakeDetector::new();
+        let (wall, mono) = (detector.wall, detector.mono);
+        let step = Duration::from_secs(5);
+        assert_eq!(detector.check_at(wall + step, mono + step), None);
+        // The monotonic clock stood still for ten minutes.
+        assert_eq!(
+            detector.check_at(wall + step * 2 + Duration::from_secs(600), mono + step * 2),
+            Some(Duration::from_secs(600))
+        );
+    }
+
+    #[test]
+    fn power_events_parse_host_states() {
+        assert_eq!(PowerEvent::parse("will_sleep"), Some(PowerEvent::Sleep));
+        assert_eq!(PowerEvent::parse("wake"), Some(PowerEvent::Wake));
+        assert_eq!(PowerEvent::parse("hibernate"), None);
+    }
+}
@@ -3,9 +3,15 @@
 //! Provides a cross-platform [`BookmarkManager`] trait for security-scoped
 //! bookmark operations. On macOS, this uses Cocoa APIs to create, restore,
 //! and manage bookmark access. On other platforms, a no-op stub is used.
+//!
+//! System sleep/wake handling lives in [`lifecycle`], battery-aware
+//! profile switching in [`power`].
 
 use std::path::{Path, PathBuf};
 
+pub mod lifecycle;
+pub mod power;
+
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(not(target_os = "macos"))]
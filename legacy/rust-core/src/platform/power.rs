@@ -0,0 +1,375 @@
+//! Battery-aware performance profile.
+//!
+//! On a laptop running from battery Fae trades quality for battery life:
+//! [`PowerProfile::LowPower`] loads a smaller local model and a lighter
+//! Kokoro variant, and runs background jobs less often. [`PowerMonitor`]
+//! polls the power source and switches profiles on transitions; the
+//! background cadence changes immediately, the model choices apply the next
+//! time the pipeline loads its models ([`apply_to_models`]).
+//!
+//! The battery is read from `pmset -g batt` on macOS and from
+//! `/sys/class/power_supply` on Linux. Machines without a battery always use
+//! [`PowerProfile::Performance`]. Thresholds live in
+//! [`PowerConfig`](crate::config::PowerConfig).
+
+use crate::config::{PowerConfig, SpeechConfig, VoiceModelPreset};
+use crate::models::ModelManager;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+static LOW_POWER: AtomicBool = AtomicBool::new(false);
+
+/// Where the machine is drawing power from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatteryStatus {
+    /// Whether the charger is disconnected.
+    pub on_battery: bool,
+    /// Remaining charge, if reported.
+    pub percent: Option<u8>,
+}
+
+/// Performance profile selected from the power source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerProfile {
+    /// Plugged in (or no battery): configured models, normal job cadence.
+    Performance,
+    /// On battery: smaller models, fewer background jobs.
+    LowPower,
+}
+
+impl PowerProfile {
+    /// Select the profile for `status` under `config`.
+    pub fn for_status(status: Option<BatteryStatus>, config: &PowerConfig) -> Self {
+        match status {
+            Some(BatteryStatus {
+                on_battery: true,
+                percent,
+            }) if config.enabled && percent.is_none_or(|p| p <= config.low_power_below_percent) => {
+                Self::LowPower
+            }
+            _ => Self::Performance,
+        }
+    }
+
+    /// Wire name used in events.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Performance => "performance",
+            Self::LowPower => "low_power",
+        }
+    }
+}
+
+/// The profile currently in effect.
+pub fn current() -> PowerProfile {
+    if LOW_POWER.load(Ordering::Relaxed) {
+        PowerProfile::LowPower
+    } else {
+        PowerProfile::Performance
+    }
+}
+
+/// Make `profile` current and apply its background job cadence.
+pub fn set_profile(profile: PowerProfile, config: &PowerConfig) {
+    LOW_POWER.store(profile == PowerProfile::LowPower, Ordering::Relaxed);
+    crate::scheduler::priority::set_background_stride(match profile {
+        PowerProfile::Performance => 1,
+        PowerProfile::LowPower => config.background_interval_factor,
+    });
+}
+
+/// Read the power source now and make the matching profile current.
+pub fn refresh(config: &PowerConfig) -> PowerProfile {
+    let status = read_battery();
+    let profile = PowerProfile::for_status(status, config);
+    if profile != current() {
+        log_transition(profile, status);
+    }
+    set_profile(profile, config);
+    profile
+}
+
+fn log_transition(profile: PowerProfile, status: Option<BatteryStatus>) {
+    let percent = status.and_then(|s| s.percent);
+    match profile {
+        PowerProfile::LowPower => {
+            info!(
+                ?percent,
+                "power: on battery — switching to low-power profile"
+            );
+        }
+        PowerProfile::Performance => {
+            info!(?percent, "power: switching to performance profile");
+        }
+    }
+}
+
+/// Swap in the low-power model choices when that profile is current.
+///
+/// Only managed model selections are changed, never to something larger
+/// than the normal choice, and only to models already on disk: a multi-GB
+/// download is the last thing to start on battery.
+pub fn apply_to_models(config: &mut SpeechConfig) {
+    if !config.power.enabled || current() != PowerProfile::LowPower {
+        return;
+    }
+    let llm = &mut config.llm;
+    if llm.voice_model_preset == VoiceModelPreset::Auto
+        && crate::config::is_managed_default_model_id(&llm.model_id)
+    {
+        let ram = crate::system_profile::detect_total_memory_bytes();
+        let catalog = crate::models::catalog::embedded();
+        if let Some(smaller) = catalog.local_llm_for(config.power.low_power_model, ram)
+            && catalog
+                .by_repo_id(&llm.model_id)
+                .is_some_and(|current| smaller.min_ram_gib < current.min_ram_gib)
+            && ModelManager::is_file_cached(&smaller.repo_id, smaller.primary_file())
+        {
+            info!(model = %smaller.repo_id, "power: using low-power local model");
+            llm.model_id = smaller.repo_id.clone();
+            llm.gguf_file = smaller.primary_file().to_owned();
+            llm.tokenizer_id = smaller.tokenizer_id.clone().unwrap_or_default();
+            llm.enable_vision = smaller.vision;
+        }
+    }
+
+    let variant = &config.power.low_power_tts_variant;
+    if variant_cost(variant) < variant_cost(&config.tts.model_variant)
+        && ModelManager::is_file_cached(
+            crate::tts::kokoro::download::KOKORO_REPO_ID,
+            crate::tts::kokoro::download::model_filename(variant),
+        )
+    {
+        info!(%variant, "power: using low-power TTS model");
+        config.tts.model_variant = variant.clone();
+    }
+}
+
+/// Relative compute cost of a Kokoro model variant.
+fn variant_cost(variant: &str) -> u8 {
+    match variant {
+        "fp32" => 5,
+        "fp16" => 4,
+        "q8f16" => 3,
+        "q4f16" => 1,
+        "q4" => 0,
+        // "q8" and unknown variants (which load q8).
+        _ => 2,
+    }
+}
+
+/// An event emitted by [`PowerMonitor`] when the profile changes.
+#[derive(Debug, Clone)]
+pub struct PowerProfileEvent {
+    /// The new profile.
+    pub profile: PowerProfile,
+    /// Battery state that caused the switch, if a battery was found.
+    pub battery: Option<BatteryStatus>,
+}
+
+/// Polls the power source and switches profiles on transitions.
+pub struct PowerMonitor {
+    config: PowerConfig,
+    tx: broadcast::Sender<PowerProfileEvent>,
+    cancel: CancellationToken,
+}
+
+impl PowerMonitor {
+    /// Create a monitor; call [`run`](Self::run) to start polling.
+    pub fn new(
+        config: PowerConfig,
+        tx: broadcast::Sender<PowerProfileEvent>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self { config, tx, cancel }
+    }
+
+    /// Run until cancelled.
+    pub async fn run(self) {
+        let interval = Duration::from_secs(u64::from(self.config.poll_interval_s.max(1)));
+        loop {
+            tokio::select! {
+                () = self.cancel.cancelled() => break,
+                () = tokio::time::sleep(interval) => {
+                    let battery = read_battery();
+                    let profile = PowerProfile::for_status(battery, &self.config);
+                    if profile == current() {
+                        continue;
+                    }
+                    log_transition(profile, battery);
+                    set_profile(profile, &self.config);
+                    // No subscribers is not an error.
+                    let _ = self.tx.send(PowerProfileEvent { profile, battery });
+                }
+            }
+        }
+    }
+}
+
+/// Read the battery state, or `None` on machines without a battery.
+pub fn read_battery() -> Option<BatteryStatus> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .ok()?;
+        parse_pmset(&String::from_utf8_lossy(&output.stdout))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        read_power_supply(std::path::Path::new("/sys/class/power_supply"))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Parse `pmset -g batt` output:
+///
+/// ```text
+/// Now drawing from 'Battery Power'
+///  -InternalBattery-0 (id=1234)	85%; discharging; 4:12 remaining present: true
+/// ```
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn parse_pmset(output: &str) -> Option<BatteryStatus> {
+    let battery_line = output.lines().find(|l| l.contains("InternalBattery"))?;
+    let percent = battery_line
+        .split_whitespace()
+        .find_map(|word| word.strip_suffix("%;")?.parse().ok());
+    Some(BatteryStatus {
+        on_battery: output.contains("'Battery Power'"),
+        percent,
+    })
+}
+
+/// Read `/sys/class/power_supply`-style entries under `root`.
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn read_power_supply(root: &std::path::Path) -> Option<BatteryStatus> {
+    let read = |dir: &std::path::Path, file: &str| {
+        std::fs::read_to_string(dir.join(file))
+            .map(|s| s.trim().to_owned())
+            .unwrap_or_default()
+    };
+    let mut battery = None;
+    let mut mains_online = false;
+    for entry in std::fs::read_dir(root).ok()?.flatten() {
+        let dir = entry.path();
+        match read(&dir, "type").as_str() {
+            "Mains" => mains_online |= read(&dir, "online") == "1",
+            "Battery" if battery.is_none() => {
+                battery = Some((read(&dir, "status"), read(&dir, "capacity").parse().ok()));
+            }
+            _ => {}
+        }
+    }
+    let (status, percent) = battery?;
+    Some(BatteryStatus {
+        on_battery: !mains_online && status == "Discharging",
+        percent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    fn on_battery(percent: u8) -> Option<BatteryStatus> {
+        Some(BatteryStatus {
+            on_battery: true,
+            percent: Some(percent),
+        })
+    }
+
+    #[test]
+    fn profile_follows_the_power_source_and_threshold() {
+        let mut config = PowerConfig::default();
+        assert_eq!(
+            PowerProfile::for_status(None, &config),
+            PowerProfile::Performance
+        );
+        assert_eq!(
+            PowerProfile::for_status(on_battery(90), &config),
+            PowerProfile::LowPower
+        );
+
+        config.low_power_below_percent = 30;
+        assert_eq!(
+            PowerProfile::for_status(on_battery(90), &config),
+            PowerProfile::Performance
+        );
+        assert_eq!(
+            PowerProfile::for_status(on_battery(30), &config),
+            PowerProfile::LowPower
+        );
+
+        config.enabled = false;
+        assert_eq!(
+            PowerProfile::for_status(on_battery(5), &config),
+            PowerProfile::Performance
+        );
+    }
+
+    #[test]
+    fn pmset_output_is_parsed() {
+        let battery = "Now drawing from 'Battery Power'\n \
+                       -InternalBattery-0 (id=4653155)\t85%; discharging; 4:12 remaining present: true\n";
+        assert_eq!(parse_pmset(battery), on_battery(85));
+
+        let charging = "Now drawing from 'AC Power'\n \
+                        -InternalBattery-0 (id=4653155)\t100%; charged; 0:00 remaining present: true\n";
+        assert_eq!(
+            parse_pmset(charging),
+            Some(BatteryStatus {
+                on_battery: false,
+                percent: Some(100)
+            })
+        );
+
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'\n"), None);
+    }
+
+    #[test]
+    fn power_supply_entries_are_read() {
+        let root = tempfile::tempdir().unwrap();
+        let write = |name: &str, files: &[(&str, &str)]| {
+            let dir = root.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            for (file, value) in files {
+                std::fs::write(dir.join(file), format!("{value}\n")).unwrap();
+            }
+        };
+        write("AC", &[("type", "Mains"), ("online", "0")]);
+        write(
+            "BAT0",
+            &[
+                ("type", "Battery"),
+                ("status", "Discharging"),
+                ("capacity", "42"),
+            ],
+        );
+        assert_eq!(read_power_supply(root.path()), on_battery(42));
+
+        std::fs::write(root.path().join("AC/online"), "1\n").unwrap();
+        assert!(!read_power_supply(root.path()).unwrap().on_battery);
+    }
+
+    #[test]
+    fn desktops_without_a_battery_report_none() {
+        let root = tempfile::tempdir().unwrap();
+        assert_eq!(read_power_supply(root.path()), None);
+    }
+
+    #[test]
+    fn lighter_variants_cost_less() {
+        assert!(variant_cost("q4") < variant_cost("q8"));
+        assert!(variant_cost("q8") < variant_cost("fp32"));
+        assert_eq!(variant_cost("unknown"), variant_cost("q8"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Append-only audit log for personal data exports and wipes.
+//!
+//! Entries record what was requested, by whom, and the outcome — never the
+//! data itself. The log lives in the config directory so it survives a wipe
+//! of the data directory.
+
+use crate::audit_log::append_audit_line;
+use crate::error::Result;
+use crate::time_util::now_epoch_secs;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// What the user asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyAction {
+    /// Export personal data to a zip archive.
+    Export,
+    /// Securely wipe personal data.
+    Wipe,
+    /// Export, then wipe.
+    ExportAndWipe,
+}
+
+impl PrivacyAction {
+    /// Action for the given export/wipe flags, or `None` if both are off.
+    #[must_use]
+    pub fn from_flags(export: bool, wipe: bool) -> Option<Self> {
+        match (export, wipe) {
+            (true, true) => Some(Self::ExportAndWipe),
+            (true, false) => Some(Self::Export),
+            (false, true) => Some(Self::Wipe),
+            (false, false) => None,
+        }
+    }
+
+    /// Whether this action produces an export archive.
+    #[must_use]
+    pub fn exports(self) -> bool {
+        matches!(self, Self::Export | Self::ExportAndWipe)
+    }
+
+    /// Whether this action wipes personal data.
+    #[must_use]
+    pub fn wipes(self) -> bool {
+        matches!(self, Self::Wipe | Self::ExportAndWipe)
+    }
+}
+
+/// Where the request came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyRequestSource {
+    /// Host command (`data.forget`).
+    HostCommand,
+    /// Spoken request ("forget everything about me").
+    Voice,
+}
+
+/// Final state of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyOutcome {
+    /// The user declined, cancelled, or the confirmation timed out.
+    Declined,
+    /// All steps completed.
+    Completed,
+    /// A step failed; see the entry detail.
+    Failed,
+}
+
+/// One persisted audit entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivacyAuditEntry {
+    /// Timestamp when the entry was recorded.
+    pub timestamp_secs: u64,
+    /// Requested action.
+    pub action: PrivacyAction,
+    /// Request origin.
+    pub source: PrivacyRequestSource,
+    /// Outcome after confirmation.
+    pub outcome: PrivacyOutcome,
+    /// Counts or failure reason. Never contains personal data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl PrivacyAuditEntry {
+    /// Create a new entry with the current timestamp.
+    #[must_use]
+    pub fn new(
+        action: PrivacyAction,
+        source: PrivacyRequestSource,
+        outcome: PrivacyOutcome,
+        detail: Option<String>,
+    ) -> Self {
+        Self {
+            timestamp_secs: now_epoch_secs(),
+            action,
+            source,
+            outcome,
+            detail,
+        }
+    }
+}
+
+/// Append `entry` to the audit log at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the log cannot be opened or written.
+pub fn append_privacy_audit(path: &Path, entry: &PrivacyAuditEntry) -> Result<()> {
+    Ok(append_audit_line(path, entry)?)
+}
+
+/// Read all audit entries from `path`, oldest first. Malformed lines are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the log exists but cannot be read.
+pub fn read_privacy_audit(path: &Path) -> Result<Vec<PrivacyAuditEntry>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if let Ok(entry) = serde_json::from_str(&line) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn action_from_flags() {
+        assert_eq!(PrivacyAction::from_flags(false, false), None);
+        let both = PrivacyAction::from_flags(true, true).unwrap();
+        assert!(both.exports() && both.wipes());
+        assert!(!PrivacyAction::from_flags(false, true).unwrap().exports());
+    }
+
+    #[test]
+    fn append_and_read_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("privacy_audit.jsonl");
+        assert!(read_privacy_audit(&path).unwrap().is_empty());
+
+        let declined = PrivacyAuditEntry::new(
+            PrivacyAction::Wipe,
+            PrivacyRequestSource::Voice,
+            PrivacyOutcome::Declined,
+            None,
+        );
+        let completed = PrivacyAuditEntry::new(
+            PrivacyAction::ExportAndWipe,
+            PrivacyRequestSource::HostCommand,
+            PrivacyOutcome::Completed,
+            Some("wiped 3 files".to_owned()),
+        );
+        append_privacy_audit(&path, &declined).unwrap();
+        append_privacy_audit(&path, &completed).unwrap();
+
+        let entries = read_privacy_audit(&path).unwrap();
+        assert_eq!(entries, vec![declined, completed]);
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(raw.contains("\"action\":\"export_and_wipe\""));
+    }
+}
@@ -0,0 +1,332 @@
+//! Authenticated encryption for data persisted at rest.
+//!
+//! Sealed payloads are `MAGIC || nonce (24 bytes) || ciphertext+tag` using
+//! XChaCha20-Poly1305. The 256-bit data key lives in the OS keychain and is
+//! referenced from [`PrivacyConfig::encryption_key`].
+
+use crate::config::PrivacyConfig;
+use crate::credentials::{CredentialError, CredentialManager, CredentialRef, secure_clear};
+use crate::error::{Result, SpeechError};
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use std::io::Write;
+use std::path::Path;
+
+/// Header identifying a sealed payload (format version 1).
+pub const SEALED_MAGIC: &[u8; 8] = b"FAEENC1\0";
+
+/// Keychain account name for the data encryption key.
+pub const DATA_KEY_ACCOUNT: &str = "fae.privacy.data_key";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+
+/// Symmetric cipher for sealing sessions and memory backups.
+pub struct DataCipher {
+    aead: XChaCha20Poly1305,
+}
+
+impl std::fmt::Debug for DataCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataCipher").finish_non_exhaustive()
+    }
+}
+
+impl DataCipher {
+    /// Build a cipher from raw key bytes.
+    #[must_use]
+    pub fn from_key(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            aead: XChaCha20Poly1305::new(Key::from_slice(key)),
+        }
+    }
+
+    /// Load the data key referenced by `key_ref` from the keychain.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpeechError::Privacy`] if the key is missing or malformed.
+    pub fn load(manager: &dyn CredentialManager, key_ref: &CredentialRef) -> Result<Self> {
+        if !key_ref.is_keychain() {
+            return Err(SpeechError::Privacy(
+                "data encryption key is not stored in the keychain".to_owned(),
+            ));
+        }
+        let mut encoded = manager
+            .retrieve(key_ref)
+            .map_err(|e| SpeechError::Privacy(format!("cannot read data key: {e}")))?
+            .ok_or_else(|| SpeechError::Privacy("data encryption key missing".to_owned()))?;
+        let decoded = base64::engine::general_purpose::STANDARD.decode(encoded.trim());
+        secure_clear(&mut encoded);
+        let mut bytes =
+            decoded.map_err(|e| SpeechError::Privacy(format!("malformed data key: {e}")))?;
+        let key: [u8; KEY_LEN] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| SpeechError::Privacy("data key has wrong length".to_owned()))?;
+        bytes.fill(0);
+        Ok(Self::from_key(&key))
+    }
+
+    /// Generate a fresh data key, store it in the keychain, and return the
+    /// cipher together with the reference to persist in config.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpeechError::Privacy`] if the keychain write fails.
+    pub fn create(manager: &dyn CredentialManager) -> Result<(Self, CredentialRef)> {
+        let mut key = [0u8; KEY_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut key);
+        let mut encoded = base64::engine::general_purpose::STANDARD.encode(key);
+        let stored = manager.store(DATA_KEY_ACCOUNT, &encoded);
+        secure_clear(&mut encoded);
+        let key_ref =
+            stored.map_err(|e| SpeechError::Privacy(format!("cannot store data key: {e}")))?;
+        let cipher = Self::from_key(&key);
+        key.fill(0);
+        Ok((cipher, key_ref))
+    }
+
+    /// Whether `data` carries the sealed-payload header.
+    #[must_use]
+    pub fn is_sealed(data: &[u8]) -> bool {
+        data.starts_with(SEALED_MAGIC)
+    }
+
+    /// Encrypt `plaintext` with a random nonce.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpeechError::Privacy`] if encryption fails.
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        let ciphertext = self
+            .aead
+            .encrypt(XNonce::from_slice(&nonce), plaintext)
+            .map_err(|_| SpeechError::Privacy("encryption failed".to_owned()))?;
+
+        let mut out = Vec::with_capacity(SEALED_MAGIC.len() + NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(SEALED_MAGIC);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    /// Decrypt a sealed payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpeechError::Privacy`] if the payload is not sealed, is
+    /// truncated, or fails authentication (wrong key or tampering).
+    pub fn open(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        let body = sealed
+            .strip_prefix(SEALED_MAGIC.as_slice())
+            .ok_or_else(|| SpeechError::Privacy("payload is not sealed".to_owned()))?;
+        if body.len() < NONCE_LEN {
+            return Err(SpeechError::Privacy("sealed payload truncated".to_owned()));
+        }
+        let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+        self.aead
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| SpeechError::Privacy("decryption failed (wrong key or corrupted)".into()))
+    }
+
+    /// Seal a plaintext file in place.
+    ///
+    /// The sealed bytes are written to a temp file and synced to disk before
+    /// it is renamed over the original, so a crash leaves either the old
+    /// plaintext or the complete sealed file, never a torn one.
+    ///
+    /// Returns `false` if the file was already sealed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, encrypted, or replaced.
+    pub fn seal_file(&self, path: &Path) -> Result<bool> {
+        let data = std::fs::read(path)?;
+        if Self::is_sealed(&data) {
+            return Ok(false);
+        }
+        let sealed = self.seal(&data)?;
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let tmp = path.with_file_name(format!(".{file_name}.sealing"));
+        let written = std::fs::File::create(&tmp)
+            .and_then(|mut file| {
+                file.write_all(&sealed)?;
+                file.sync_all()
+            })
+            .and_then(|()| std::fs::rename(&tmp, path));
+        if let Err(e) = written {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+        // Make the rename itself durable.
+        #[cfg(unix)]
+        if let Some(parent) = path.parent()
+            && let Ok(dir) = std::fs::File::open(parent)
+        {
+            let _ = dir.sync_all();
+        }
+        Ok(true)
+    }
+}
+
+/// Cipher for the configured privacy settings, or `None` when encryption at
+/// rest is disabled.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Privacy`] if encryption is enabled but the key
+/// cannot be loaded.
+pub fn cipher_for(privacy: &PrivacyConfig) -> Result<Option<DataCipher>> {
+    if !privacy.encrypt_at_rest {
+        return Ok(None);
+    }
+    let manager = crate::credentials::create_manager();
+    DataCipher::load(manager.as_ref(), &privacy.encryption_key).map(Some)
+}
+
+/// Ensure a data key exists for `privacy`, creating one if needed.
+///
+/// Updates `privacy.encryption_key` when a new key is generated; the caller
+/// is responsible for saving the config.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Privacy`] if the keychain cannot be accessed.
+pub fn ensure_data_key(
+    privacy: &mut PrivacyConfig,
+    manager: &dyn CredentialManager,
+) -> Result<DataCipher> {
+    if privacy.encryption_key.is_keychain() {
+        match manager.retrieve(&privacy.encryption_key) {
+            Err(CredentialError::NotFound) => {
+                // Files sealed with the lost key stay unreadable; new data
+                // gets a fresh key rather than being written in plaintext.
+                tracing::warn!("data encryption key missing from keychain; generating a new one");
+            }
+            _ => return DataCipher::load(manager, &privacy.encryption_key),
+        }
+    }
+    let (cipher, key_ref) = DataCipher::create(manager)?;
+    privacy.encryption_key = key_ref;
+    Ok(cipher)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockManager {
+        entries: Mutex<HashMap<String, String>>,
+    }
+
+    impl CredentialManager for MockManager {
+        fn store(
+            &self,
+            account: &str,
+            value: &str,
+        ) -> std::result::Result<CredentialRef, CredentialError> {
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(account.to_owned(), value.to_owned());
+            Ok(CredentialRef::Keychain {
+                service: "test".to_owned(),
+                account: account.to_owned(),
+            })
+        }
+
+        fn retrieve(
+            &self,
+            cred_ref: &CredentialRef,
+        ) -> std::result::Result<Option<String>, CredentialError> {
+            match cred_ref {
+                CredentialRef::Keychain { account, .. } => self
+                    .entries
+                    .lock()
+                    .unwrap()
+                    .get(account)
+                    .cloned()
+                    .map(Some)
+                    .ok_or(CredentialError::NotFound),
+                _ => Ok(None),
+            }
+        }
+
+        fn delete(&self, _cred_ref: &CredentialRef) -> std::result::Result<(), CredentialError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn seal_open_round_trip() {
+        let cipher = DataCipher::from_key(&[7u8; KEY_LEN]);
+        let sealed = cipher.seal(b"hello session").unwrap();
+        assert!(DataCipher::is_sealed(&sealed));
+        assert!(!sealed.windows(5).any(|w| w == b"hello"));
+        assert_eq!(cipher.open(&sealed).unwrap(), b"hello session");
+    }
+
+    #[test]
+    fn open_rejects_wrong_key_and_tampering() {
+        let cipher = DataCipher::from_key(&[1u8; KEY_LEN]);
+        let other = DataCipher::from_key(&[2u8; KEY_LEN]);
+        let mut sealed = cipher.seal(b"secret").unwrap();
+        assert!(other.open(&sealed).is_err());
+
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0xff;
+        assert!(cipher.open(&sealed).is_err());
+        assert!(cipher.open(b"plain json").is_err());
+    }
+
+    #[test]
+    fn seal_file_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.db");
+        std::fs::write(&path, b"sqlite bytes").unwrap();
+
+        let cipher = DataCipher::from_key(&[3u8; KEY_LEN]);
+        assert!(cipher.seal_file(&path).unwrap());
+        assert!(!cipher.seal_file(&path).unwrap());
+        let on_disk = std::fs::read(&path).unwrap();
+        assert_eq!(cipher.open(&on_disk).unwrap(), b"sqlite bytes");
+        assert!(!dir.path().join(".backup.db.sealing").exists());
+    }
+
+    #[test]
+    fn ensure_data_key_creates_once_and_reloads() {
+        let manager = MockManager::default();
+        let mut privacy = PrivacyConfig {
+            encrypt_at_rest: true,
+            ..PrivacyConfig::default()
+        };
+
+        let first = ensure_data_key(&mut privacy, &manager).unwrap();
+        assert!(privacy.encryption_key.is_keychain());
+        let key_ref = privacy.encryption_key.clone();
+
+        let second = ensure_data_key(&mut privacy, &manager).unwrap();
+        assert_eq!(privacy.encryption_key, key_ref);
+        let sealed = first.seal(b"x").unwrap();
+        assert_eq!(second.open(&sealed).unwrap(), b"x");
+    }
+
+    #[test]
+    fn load_rejects_non_keychain_reference() {
+        let manager = MockManager::default();
+        assert!(DataCipher::load(&manager, &CredentialRef::None).is_err());
+    }
+}
@@ -0,0 +1,526 @@
+//! Personal data export and secure wipe ("forget everything about me").
+//!
+//! Personal data is everything under the data directory that describes the
+//! user: the memory database and its backups, memory records (including the
+//! primary user's voiceprints), voice samples and wakeword recordings,
+//! conversation sessions,
+//! meeting transcripts and minutes, the conversation journal, the todo list,
+//! unsent mail drafts, stored preferences, the corrections the user has
+//! given, experiment outcomes (which record when and how each conversation
+//! went), the undo history of changed files (which holds their earlier
+//! contents), and earlier exports. Under the cache directory it is the
+//! notes search index, which embeds the user's notes. Models, skills, logs,
+//! and config are left alone; a full factory reset is
+//! [`crate::diagnostics::delete_all_user_data`].
+//!
+//! Both operations are confirmed through the tool approval channel and
+//! recorded in the privacy audit log by the caller.
+
+use super::cipher::DataCipher;
+use crate::channels::history::ChannelMessage;
+use crate::config::PrivacyConfig;
+use crate::credentials::{CredentialError, CredentialManager};
+use crate::error::{Result, SpeechError};
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// Approval request name used to confirm an export or wipe.
+pub const FORGET_APPROVAL_NAME: &str = "data.forget";
+
+/// Entries under the data directory that hold personal data.
+pub const PERSONAL_DATA_ENTRIES: &[&str] = &[
+    "fae.db",
+    "fae.db-wal",
+    "fae.db-shm",
+    "memory",
+    "voices",
+    "wakeword",
+    "backups",
+    "sessions",
+    "meetings",
+    "journal",
+    "todos.json",
+    "mail_drafts.json",
+    "preferences.json",
+    "corrections.json",
+    "experiments.json",
+    "undo",
+    EXPORTS_DIR_NAME,
+];
+
+/// Entries under the cache directory that hold personal data.
+pub const PERSONAL_CACHE_ENTRIES: &[&str] =
+    &["notes_index.db", "notes_index.db-wal", "notes_index.db-shm"];
+
+/// Entries under the data and cache directories that are not personal data:
+/// logs and diagnostics bundles (logs and config only), skills and desktop
+/// macros the user set up, and downloaded models and tools.
+pub const NON_PERSONAL_ENTRIES: &[&str] = &[
+    "logs",
+    "diagnostics",
+    "skills",
+    "python-skills",
+    "desktop_macros",
+    "huggingface",
+    "uv",
+];
+
+/// Subdirectory of the data directory that receives export archives.
+pub const EXPORTS_DIR_NAME: &str = "exports";
+
+const WIPE_CHUNK: usize = 64 * 1024;
+
+/// Outcome of [`wipe_personal_data`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WipeReport {
+    /// Files overwritten and removed.
+    pub files_wiped: usize,
+    /// Bytes overwritten.
+    pub bytes_wiped: u64,
+    /// Paths that could not be wiped, with the reason.
+    pub failures: Vec<String>,
+}
+
+impl WipeReport {
+    /// One-line summary for the audit log.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        format!(
+            "wiped {} files ({} bytes), {} failures",
+            self.files_wiped,
+            self.bytes_wiped,
+            self.failures.len()
+        )
+    }
+}
+
+/// Default directory for export archives (`data_dir()/exports/`).
+#[must_use]
+pub fn exports_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(EXPORTS_DIR_NAME)
+}
+
+/// Write all personal data under `data_dir` and `cache_dir` to a zip at
+/// `destination`, under `data/` and `cache/` respectively.
+///
+/// Sealed files are decrypted with `cipher` so the archive is readable on
+/// its own; without a cipher they are copied as-is. `channel_history` is
+/// included as `channel_history.json` when non-empty, since channel messages
+/// are only held in memory. Earlier exports are not nested into the archive.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be created or a file cannot be read.
+pub fn export_personal_data(
+    data_dir: &Path,
+    cache_dir: &Path,
+    cipher: Option<&DataCipher>,
+    channel_history: &[ChannelMessage],
+    destination: &Path,
+) -> Result<PathBuf> {
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(destination)?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut included = Vec::new();
+    let roots = [
+        (data_dir, PERSONAL_DATA_ENTRIES, "data"),
+        (cache_dir, PERSONAL_CACHE_ENTRIES, "cache"),
+    ];
+    for (root, entries, prefix) in roots {
+        for entry in entries.iter().filter(|e| **e != EXPORTS_DIR_NAME) {
+            for file in collect_files(&root.join(entry)) {
+                let rel = file
+                    .strip_prefix(root)
+                    .unwrap_or(&file)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let mut bytes = std::fs::read(&file)?;
+                if DataCipher::is_sealed(&bytes)
+                    && let Some(cipher) = cipher
+                {
+                    bytes = cipher.open(&bytes)?;
+                }
+                let name = format!("{prefix}/{rel}");
+                start_zip_file(&mut zip, &name, options)?;
+                zip.write_all(&bytes)?;
+                included.push(name);
+            }
+        }
+    }
+
+    if !channel_history.is_empty() {
+        let json = serde_json::to_vec_pretty(channel_history)
+            .map_err(|e| SpeechError::Privacy(format!("cannot encode channel history: {e}")))?;
+        start_zip_file(&mut zip, "channel_history.json", options)?;
+        zip.write_all(&json)?;
+        included.push("channel_history.json".to_owned());
+    }
+
+    let info = format!(
+        "Fae personal data export\n\
+         Created: {}\n\
+         Version: {}\n\
+         Files: {}\n\n{}\n",
+        crate::diagnostics::chrono_timestamp(),
+        env!("CARGO_PKG_VERSION"),
+        included.len(),
+        included.join("\n"),
+    );
+    start_zip_file(&mut zip, "EXPORT_INFO.txt", options)?;
+    zip.write_all(info.as_bytes())?;
+
+    zip.finish()
+        .map_err(|e| SpeechError::Privacy(format!("zip finish error: {e}")))?;
+    Ok(destination.to_path_buf())
+}
+
+/// Overwrite and remove all personal data under `data_dir` and `cache_dir`,
+/// except `keep`.
+///
+/// Each file is overwritten with zeros and synced before it is unlinked.
+/// On copy-on-write and wear-levelled storage (APFS, SSDs) the old blocks
+/// may survive, which is why sealed data also has its key destroyed by
+/// [`shred_data_key`]. `keep` lets an export taken just before the wipe
+/// survive it. Individual failures are collected in the report.
+#[must_use]
+pub fn wipe_personal_data(data_dir: &Path, cache_dir: &Path, keep: &[PathBuf]) -> WipeReport {
+    let mut report = WipeReport::default();
+    let data = PERSONAL_DATA_ENTRIES.iter().map(|e| data_dir.join(e));
+    let cache = PERSONAL_CACHE_ENTRIES.iter().map(|e| cache_dir.join(e));
+    for path in data.chain(cache) {
+        for file in collect_files(&path) {
+            if keep.contains(&file) {
+                continue;
+            }
+            match overwrite_and_remove(&file) {
+                Ok(len) => {
+                    report.files_wiped += 1;
+                    report.bytes_wiped = report.bytes_wiped.saturating_add(len);
+                }
+                Err(e) => report.failures.push(format!("{}: {e}", file.display())),
+            }
+        }
+        if path.is_dir()
+            && !keep.iter().any(|k| k.starts_with(&path))
+            && let Err(e) = std::fs::remove_dir_all(&path)
+        {
+            report.failures.push(format!("{}: {e}", path.display()));
+        }
+    }
+    report
+}
+
+/// Destroy the data encryption key so sealed copies left on disk (or in
+/// Time Machine snapshots) can no longer be read.
+///
+/// When encryption at rest stays enabled a replacement key is stored under
+/// the same keychain account, so the persisted reference remains valid.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Privacy`] if the keychain cannot be updated.
+pub fn shred_data_key(privacy: &PrivacyConfig, manager: &dyn CredentialManager) -> Result<()> {
+    if !privacy.encryption_key.is_keychain() {
+        return Ok(());
+    }
+    match manager.delete(&privacy.encryption_key) {
+        Ok(()) | Err(CredentialError::NotFound) => {}
+        Err(e) => return Err(SpeechError::Privacy(format!("cannot delete data key: {e}"))),
+    }
+    if privacy.encrypt_at_rest {
+        let (_, key_ref) = DataCipher::create(manager)?;
+        if key_ref != privacy.encryption_key {
+            tracing::warn!("replacement data key stored under a different keychain reference");
+        }
+    }
+    Ok(())
+}
+
+fn start_zip_file<W: Write + std::io::Seek>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    options: SimpleFileOptions,
+) -> Result<()> {
+    zip.start_file(name, options)
+        .map_err(|e| SpeechError::Privacy(format!("zip error: {e}")))
+}
+
+/// Regular files at or below `path`, skipping symlinks.
+fn collect_files(path: &Path) -> Vec<PathBuf> {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return Vec::new();
+    };
+    if meta.is_file() {
+        return vec![path.to_path_buf()];
+    }
+    if !meta.is_dir() {
+        return Vec::new();
+    }
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .flat_map(|e| collect_files(&e.path()))
+        .collect();
+    files.sort();
+    files
+}
+
+fn overwrite_and_remove(path: &Path) -> std::io::Result<u64> {
+    let len = std::fs::metadata(path)?.len();
+    {
+        let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+        let zeros = vec![0u8; WIPE_CHUNK];
+        let mut remaining = len;
+        while remaining > 0 {
+            let n = remaining.min(WIPE_CHUNK as u64) as usize;
+            file.write_all(&zeros[..n])?;
+            remaining -= n as u64;
+        }
+        file.sync_all()?;
+    }
+    std::fs::remove_file(path)?;
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::channels::history::MessageDirection;
+    use crate::fae_dirs;
+    use std::io::Read;
+
+    fn seed(root: &Path) {
+        std::fs::write(root.join("fae.db"), b"sqlite").unwrap();
+        std::fs::create_dir_all(root.join("memory")).unwrap();
+        std::fs::write(root.join("memory/primary_user.md"), b"voiceprint").unwrap();
+        std::fs::create_dir_all(root.join("sessions/archive")).unwrap();
+        std::fs::write(root.join("sessions/archive/s1.json"), b"{}").unwrap();
+        std::fs::create_dir_all(root.join("skills")).unwrap();
+        std::fs::write(root.join("skills/keep.md"), b"skill").unwrap();
+    }
+
+    /// An empty cache directory beside the seeded data.
+    fn no_cache(dir: &tempfile::TempDir) -> PathBuf {
+        dir.path().join("no-cache")
+    }
+
+    fn zip_entry(path: &Path, name: &str) -> Vec<u8> {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut entry = archive.by_name(name).unwrap();
+        let mut buf = Vec::new();
+        entry.read_to_end(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn export_includes_personal_data_and_decrypts_sealed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        seed(dir.path());
+        let cipher = DataCipher::from_key(&[4u8; 32]);
+        cipher
+            .seal_file(&dir.path().join("memory/primary_user.md"))
+            .unwrap();
+        let history = vec![ChannelMessage {
+            id: "msg_1".to_owned(),
+            channel: "discord".to_owned(),
+            direction: MessageDirection::Inbound,
+            sender: "u1".to_owned(),
+            text: "hi".to_owned(),
+            timestamp: chrono::Utc::now(),
+            reply_target: "c1".to_owned(),
+        }];
+
+        let dest = exports_dir(dir.path()).join("export.zip");
+        export_personal_data(dir.path(), &no_cache(&dir), Some(&cipher), &history, &dest).unwrap();
+
+        assert_eq!(zip_entry(&dest, "data/fae.db"), b"sqlite");
+        assert_eq!(
+            zip_entry(&dest, "data/memory/primary_user.md"),
+            b"voiceprint"
+        );
+        assert_eq!(zip_entry(&dest, "data/sessions/archive/s1.json"), b"{}");
+        assert!(!zip_entry(&dest, "channel_history.json").is_empty());
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&dest).unwrap()).unwrap();
+        assert!(archive.by_name("data/skills/keep.md").is_err());
+    }
+
+    #[test]
+    fn wipe_removes_personal_data_only() {
+        let dir = tempfile::tempdir().unwrap();
+        seed(dir.path());
+        std::fs::create_dir_all(exports_dir(dir.path())).unwrap();
+        std::fs::write(exports_dir(dir.path()).join("old.zip"), b"zip").unwrap();
+
+        let report = wipe_personal_data(dir.path(), &no_cache(&dir), &[]);
+        assert_eq!(report.files_wiped, 4);
+        assert_eq!(report.bytes_wiped, 6 + 10 + 2 + 3);
+        assert!(report.failures.is_empty());
+        assert!(!dir.path().join("fae.db").exists());
+        assert!(!dir.path().join("memory").exists());
+        assert!(!dir.path().join("sessions").exists());
+        assert!(!exports_dir(dir.path()).exists());
+        assert!(dir.path().join("skills/keep.md").exists());
+    }
+
+    #[test]
+    fn wipe_keeps_fresh_export() {
+        let dir = tempfile::tempdir().unwrap();
+        seed(dir.path());
+        let dest = exports_dir(dir.path()).join("export.zip");
+        export_personal_data(dir.path(), &no_cache(&dir), None, &[], &dest).unwrap();
+
+        let report = wipe_personal_data(dir.path(), &no_cache(&dir), std::slice::from_ref(&dest));
+        assert_eq!(report.files_wiped, 3);
+        assert!(dest.exists());
+        assert!(!dir.path().join("fae.db").exists());
+    }
+
+    /// A file from each store besides the core ones in [`seed`], as named
+    /// in the export: data directory files under `data/`, cache files under
+    /// `cache/`.
+    const STORE_FILES: &[&str] = &[
+        "data/meetings/2026-03-02-standup/minutes.md",
+        "data/journal/2026-03-02.md",
+        "data/todos.json",
+        "data/mail_drafts.json",
+        "data/preferences.json",
+        "data/corrections.json",
+        "data/experiments.json",
+        "data/undo/0000000001.json",
+        "data/wakeword/reference-1.wav",
+        "cache/notes_index.db",
+    ];
+
+    #[test]
+    fn export_and_wipe_cover_every_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let (data_dir, cache_dir) = (dir.path().join("data"), dir.path().join("cache"));
+        for rel in STORE_FILES {
+            let path = dir.path().join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, rel.as_bytes()).unwrap();
+        }
+
+        let dest = exports_dir(&data_dir).join("export.zip");
+        export_personal_data(&data_dir, &cache_dir, None, &[], &dest).unwrap();
+        for rel in STORE_FILES {
+            assert_eq!(zip_entry(&dest, rel), rel.as_bytes());
+        }
+
+        let report = wipe_personal_data(&data_dir, &cache_dir, &[]);
+        assert!(report.failures.is_empty());
+        for rel in STORE_FILES {
+            assert!(!dir.path().join(rel).exists(), "{rel} survived the wipe");
+        }
+    }
+
+    type DirFn = fn() -> PathBuf;
+
+    /// Every path in [`crate::fae_dirs`], by the directory it lives under.
+    /// Roots and config-directory paths are outside the data rights.
+    const DATA_PATHS: &[(&str, DirFn)] = &[
+        ("logs_dir", fae_dirs::logs_dir),
+        ("scheduler_runs_dir", fae_dirs::scheduler_runs_dir),
+        ("skills_dir", fae_dirs::skills_dir),
+        ("python_skills_dir", fae_dirs::python_skills_dir),
+        ("sessions_dir", fae_dirs::sessions_dir),
+        ("journal_dir", fae_dirs::journal_dir),
+        ("meetings_dir", fae_dirs::meetings_dir),
+        ("todos_file", fae_dirs::todos_file),
+        ("mail_drafts_file", fae_dirs::mail_drafts_file),
+        ("preferences_file", fae_dirs::preferences_file),
+        ("experiments_file", fae_dirs::experiments_file),
+        ("corrections_file", fae_dirs::corrections_file),
+        ("undo_dir", fae_dirs::undo_dir),
+        ("desktop_macros_dir", fae_dirs::desktop_macros_dir),
+        ("diagnostics_dir", fae_dirs::diagnostics_dir),
+        ("wakeword_dir", fae_dirs::wakeword_dir),
+    ];
+    const CACHE_PATHS: &[(&str, DirFn)] = &[
+        ("hf_cache_dir", fae_dirs::hf_cache_dir),
+        ("uv_cache_dir", fae_dirs::uv_cache_dir),
+        ("notes_index_file", fae_dirs::notes_index_file),
+    ];
+    const OTHER_PATHS: &[&str] = &[
+        "data_dir",
+        "config_dir",
+        "cache_dir",
+        "memory_dir",
+        "config_file",
+        "llm_config_file",
+        "scheduler_file",
+        "scheduler_templates_file",
+        "scheduler_batches_file",
+        "runtime_audit_file",
+        "privacy_audit_file",
+        "guardrail_audit_file",
+        "permission_usage_file",
+        "skill_credential_grants_file",
+        "themes_dir",
+        "undo_audit_file",
+        "mutation_manifest_file",
+        "kernel_signatures_file",
+    ];
+
+    #[test]
+    fn every_store_is_personal_data_or_exempt() {
+        let declared: Vec<&str> = include_str!("../fae_dirs.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub fn "))
+            .filter_map(|rest| rest.strip_suffix("() -> PathBuf {"))
+            .collect();
+        let known: Vec<&str> = DATA_PATHS
+            .iter()
+            .chain(CACHE_PATHS)
+            .map(|(name, _)| *name)
+            .chain(OTHER_PATHS.iter().copied())
+            .collect();
+        for name in &declared {
+            assert!(known.contains(name), "classify fae_dirs::{name} here");
+        }
+
+        let roots = [
+            (fae_dirs::data_dir(), DATA_PATHS, PERSONAL_DATA_ENTRIES),
+            (fae_dirs::cache_dir(), CACHE_PATHS, PERSONAL_CACHE_ENTRIES),
+        ];
+        for (root, paths, personal) in roots {
+            for (name, path) in paths {
+                // Skipped when an environment override moves it elsewhere.
+                let Some(entry) = path()
+                    .strip_prefix(&root)
+                    .ok()
+                    .and_then(|rel| rel.iter().next())
+                    .and_then(|entry| entry.to_str())
+                    .map(str::to_owned)
+                else {
+                    continue;
+                };
+                assert!(
+                    personal.contains(&entry.as_str())
+                        || NON_PERSONAL_ENTRIES.contains(&entry.as_str()),
+                    "fae_dirs::{name} ({entry}) is neither personal data nor exempt"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn wipe_of_empty_dir_is_noop() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            wipe_personal_data(dir.path(), &no_cache(&dir), &[]),
+            WipeReport::default()
+        );
+    }
+}
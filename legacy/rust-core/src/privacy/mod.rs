@@ -0,0 +1,53 @@
+//! Privacy controls: encryption at rest, data retention, and data rights.
+//!
+//! - [`cipher`] — XChaCha20-Poly1305 sealing with a keychain-held data key
+//! - [`retention`] — session archival, age/size limits, and sealing passes
+//! - [`data_rights`] — personal data export and secure wipe
+//! - [`audit`] — append-only log of export and wipe requests
+//!
+//! Settings live in [`PrivacyConfig`](crate::config::PrivacyConfig) and are
+//! enforced daily by the `privacy_maintenance` scheduler task.
+//!
+//! Encryption at rest covers only [`ENCRYPTED_STORES`]: conversation sessions
+//! and memory database backups. The live memory database (`fae.db`) is
+//! opened by SQLite on every turn and stays plaintext; protecting it is left
+//! to OS disk encryption (FileVault).
+
+pub mod audit;
+pub mod cipher;
+pub mod data_rights;
+pub mod retention;
+
+pub use audit::{
+    PrivacyAction, PrivacyAuditEntry, PrivacyOutcome, PrivacyRequestSource, append_privacy_audit,
+};
+pub use cipher::{DataCipher, cipher_for, ensure_data_key};
+pub use data_rights::{
+    FORGET_APPROVAL_NAME, WipeReport, export_personal_data, shred_data_key, wipe_personal_data,
+};
+pub use retention::{RetentionReport, enforce_session_retention, seal_plaintext_files};
+
+use crate::config::PrivacyConfig;
+use crate::error::{Result, SpeechError};
+use crate::fae_llm::session::FsSessionStore;
+use std::sync::Arc;
+
+/// Stores sealed when `privacy.encrypt_at_rest` is on, as reported by
+/// `config.get privacy`.
+pub const ENCRYPTED_STORES: &[&str] = &["sessions", "memory_backups"];
+
+/// Open the conversation session store, sealing writes when encryption at
+/// rest is enabled.
+///
+/// # Errors
+///
+/// Returns an error if the sessions directory cannot be created or the data
+/// key cannot be loaded.
+pub fn open_session_store(privacy: &PrivacyConfig) -> Result<FsSessionStore> {
+    let store = FsSessionStore::new(crate::fae_dirs::sessions_dir())
+        .map_err(|e| SpeechError::Privacy(format!("session store unavailable: {e}")))?;
+    Ok(match cipher_for(privacy)? {
+        Some(cipher) => store.with_cipher(Arc::new(cipher)),
+        None => store,
+    })
+}
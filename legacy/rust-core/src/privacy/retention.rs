@@ -0,0 +1,264 @@
+//! Retention enforcement for persisted sessions and memory backups.
+//!
+//! Run periodically by the `privacy_maintenance` scheduler task. File ages
+//! come from modification times so the policy works on sealed files without
+//! decrypting them.
+
+use super::cipher::DataCipher;
+use crate::config::PrivacyConfig;
+use crate::error::Result;
+use crate::fae_llm::session::fs_store::ARCHIVE_DIR_NAME;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Outcome of a retention pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RetentionReport {
+    /// Sessions moved into the archive.
+    pub archived: usize,
+    /// Sessions deleted by age or size cap.
+    pub deleted: usize,
+    /// Bytes reclaimed by deletions.
+    pub bytes_freed: u64,
+    /// Plaintext files sealed because encryption at rest is enabled.
+    pub sealed: usize,
+}
+
+impl RetentionReport {
+    /// One-line summary for scheduler telemetry.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        format!(
+            "privacy maintenance: archived {}, deleted {} ({} bytes), sealed {}",
+            self.archived, self.deleted, self.bytes_freed, self.sealed
+        )
+    }
+}
+
+struct SessionFile {
+    path: PathBuf,
+    modified: SystemTime,
+    len: u64,
+}
+
+/// Apply archival, age-based deletion, and the size cap to `sessions_dir`.
+///
+/// # Errors
+///
+/// Returns an error if the archive directory cannot be created or a session
+/// cannot be moved. Individual deletion failures are logged and skipped.
+pub fn enforce_session_retention(
+    sessions_dir: &Path,
+    policy: &PrivacyConfig,
+    now: SystemTime,
+) -> Result<RetentionReport> {
+    let mut report = RetentionReport::default();
+    if !sessions_dir.exists() {
+        return Ok(report);
+    }
+    let archive_dir = sessions_dir.join(ARCHIVE_DIR_NAME);
+
+    if policy.session_archive_after_days > 0 {
+        let cutoff = days_before(now, policy.session_archive_after_days);
+        for file in list_session_files(sessions_dir) {
+            if file.modified < cutoff {
+                std::fs::create_dir_all(&archive_dir)?;
+                if let Some(name) = file.path.file_name() {
+                    std::fs::rename(&file.path, archive_dir.join(name))?;
+                    report.archived += 1;
+                }
+            }
+        }
+    }
+
+    let mut files = list_session_files(sessions_dir);
+    files.extend(list_session_files(&archive_dir));
+
+    if policy.session_retention_days > 0 {
+        let cutoff = days_before(now, policy.session_retention_days);
+        files.retain(|file| {
+            if file.modified < cutoff {
+                remove(file, &mut report);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    if policy.session_max_total_mb > 0 {
+        let cap = policy.session_max_total_mb.saturating_mul(1024 * 1024);
+        let mut total: u64 = files.iter().map(|f| f.len).sum();
+        files.sort_by_key(|f| f.modified);
+        for file in &files {
+            if total <= cap {
+                break;
+            }
+            total = total.saturating_sub(file.len);
+            remove(file, &mut report);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Seal any plaintext session files and memory backups with `cipher`.
+///
+/// Returns the number of files sealed. Files that fail to seal are logged
+/// and left in place.
+pub fn seal_plaintext_files(sessions_dir: &Path, backup_dir: &Path, cipher: &DataCipher) -> usize {
+    let archive_dir = sessions_dir.join(ARCHIVE_DIR_NAME);
+    let mut candidates: Vec<PathBuf> = list_session_files(sessions_dir)
+        .into_iter()
+        .chain(list_session_files(&archive_dir))
+        .map(|f| f.path)
+        .collect();
+    if let Ok(entries) = std::fs::read_dir(backup_dir) {
+        candidates.extend(
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("db")),
+        );
+    }
+
+    let mut sealed = 0;
+    for path in candidates {
+        match cipher.seal_file(&path) {
+            Ok(true) => sealed += 1,
+            Ok(false) => {}
+            Err(e) => tracing::warn!(path = %path.display(), error = %e, "failed to seal file"),
+        }
+    }
+    sealed
+}
+
+fn days_before(now: SystemTime, days: u32) -> SystemTime {
+    now.checked_sub(Duration::from_secs(u64::from(days) * SECS_PER_DAY))
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+fn list_session_files(dir: &Path) -> Vec<SessionFile> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?;
+            if name.starts_with('.') || path.extension().and_then(|e| e.to_str()) != Some("json") {
+                return None;
+            }
+            let meta = entry.metadata().ok()?;
+            if !meta.is_file() {
+                return None;
+            }
+            Some(SessionFile {
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                len: meta.len(),
+                path,
+            })
+        })
+        .collect()
+}
+
+fn remove(file: &SessionFile, report: &mut RetentionReport) {
+    match std::fs::remove_file(&file.path) {
+        Ok(()) => {
+            report.deleted += 1;
+            report.bytes_freed = report.bytes_freed.saturating_add(file.len);
+        }
+        Err(e) => {
+            tracing::warn!(path = %file.path.display(), error = %e, "failed to delete session");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn write_session(dir: &Path, id: &str, bytes: usize) {
+        std::fs::write(dir.join(format!("{id}.json")), vec![b'x'; bytes]).unwrap();
+    }
+
+    fn days_later(days: u64) -> SystemTime {
+        SystemTime::now() + Duration::from_secs(days * SECS_PER_DAY)
+    }
+
+    #[test]
+    fn default_policy_keeps_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        write_session(dir.path(), "a", 10);
+        let report =
+            enforce_session_retention(dir.path(), &PrivacyConfig::default(), days_later(1000))
+                .unwrap();
+        assert_eq!(report, RetentionReport::default());
+        assert!(dir.path().join("a.json").exists());
+    }
+
+    #[test]
+    fn archives_then_deletes_by_age() {
+        let dir = tempfile::tempdir().unwrap();
+        write_session(dir.path(), "old", 10);
+        let policy = PrivacyConfig {
+            session_archive_after_days: 30,
+            session_retention_days: 90,
+            ..PrivacyConfig::default()
+        };
+
+        let report = enforce_session_retention(dir.path(), &policy, days_later(31)).unwrap();
+        assert_eq!(report.archived, 1);
+        assert!(dir.path().join(ARCHIVE_DIR_NAME).join("old.json").exists());
+
+        let report = enforce_session_retention(dir.path(), &policy, days_later(91)).unwrap();
+        assert_eq!(report.deleted, 1);
+        assert_eq!(report.bytes_freed, 10);
+        assert!(!dir.path().join(ARCHIVE_DIR_NAME).join("old.json").exists());
+    }
+
+    #[test]
+    fn size_cap_deletes_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        write_session(dir.path(), "older", 700 * 1024);
+        std::thread::sleep(Duration::from_millis(20));
+        write_session(dir.path(), "newer", 700 * 1024);
+        let policy = PrivacyConfig {
+            session_max_total_mb: 1,
+            ..PrivacyConfig::default()
+        };
+
+        let report = enforce_session_retention(dir.path(), &policy, SystemTime::now()).unwrap();
+        assert_eq!(report.deleted, 1);
+        assert!(!dir.path().join("older.json").exists());
+        assert!(dir.path().join("newer.json").exists());
+    }
+
+    #[test]
+    fn seal_plaintext_files_covers_sessions_and_backups() {
+        let sessions = tempfile::tempdir().unwrap();
+        let backups = tempfile::tempdir().unwrap();
+        write_session(sessions.path(), "s1", 5);
+        std::fs::write(backups.path().join("fae-backup-1.db"), b"db").unwrap();
+        std::fs::write(backups.path().join("notes.txt"), b"skip").unwrap();
+
+        let cipher = DataCipher::from_key(&[9u8; 32]);
+        assert_eq!(
+            seal_plaintext_files(sessions.path(), backups.path(), &cipher),
+            2
+        );
+        assert_eq!(
+            seal_plaintext_files(sessions.path(), backups.path(), &cipher),
+            0
+        );
+        assert_eq!(
+            std::fs::read(backups.path().join("notes.txt")).unwrap(),
+            b"skip"
+        );
+    }
+}
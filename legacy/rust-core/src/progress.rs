@@ -2,6 +2,17 @@
 //!
 //! Provides callback-based progress reporting that decouples the model
 //! loading logic from UI presentation (CLI indicatif vs GUI signals).
+//!
+//! Work can be split into a tree of weighted tasks ("download models →
+//! `model.onnx`") with [`ProgressCallback::subtask`]; a [`ProgressTree`]
+//! folds the task events back into one overall fraction for a single
+//! progress bar. The callback also carries a cancellation token, so a UI
+//! that cancels stops every task reporting through it.
+
+use crate::error::{Result, SpeechError};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 /// A single file in the download plan.
 #[derive(Debug, Clone)]
@@ -16,6 +27,18 @@ pub struct DownloadFile {
     pub cached: bool,
 }
 
+impl DownloadFile {
+    /// Task id the file's download reports under.
+    pub fn task_id(&self) -> String {
+        download_task_id(&self.repo_id, &self.filename)
+    }
+}
+
+/// Task id for downloading `filename` from `repo_id`.
+pub fn download_task_id(repo_id: &str, filename: &str) -> String {
+    format!("{repo_id}/{filename}")
+}
+
 /// A plan of all files needed for startup, with cache status and sizes.
 ///
 /// Built before downloads begin so the UI can show total download size
@@ -127,6 +150,12 @@ pub enum ProgressEvent {
         duration_secs: f64,
     },
 
+    /// A pipeline component finished loading and can be used.
+    ComponentReady {
+        /// `"stt"`, `"tts"`, or `"llm"`.
+        component: String,
+    },
+
     /// The download plan is ready with file list and sizes.
     DownloadPlanReady {
         /// The computed download plan.
@@ -150,13 +179,295 @@ pub enum ProgressEvent {
         /// Human-readable error description.
         message: String,
     },
+
+    /// A first-run setup check has started.
+    CheckStarted {
+        /// Check identifier (e.g. `"provider.openai"`, `"local_model.load"`).
+        check: String,
+    },
+
+    /// A first-run setup check passed.
+    CheckPassed {
+        /// Check identifier.
+        check: String,
+        /// Human-readable result (e.g. `"key accepted, 42 models"`).
+        detail: String,
+    },
+
+    /// A first-run setup check failed.
+    CheckFailed {
+        /// Check identifier.
+        check: String,
+        /// What went wrong.
+        message: String,
+        /// What the user can do about it.
+        action: String,
+    },
+
+    /// A task was announced. Announcing a task again keeps its first weight.
+    TaskStarted {
+        /// Task path, e.g. `"startup/download/<repo>/<file>"`.
+        id: String,
+        /// Path of the enclosing task, if any.
+        parent: Option<String>,
+        /// Human-readable label (e.g. `"Downloading models"`).
+        label: String,
+        /// Share of the parent's progress relative to its siblings.
+        weight: f64,
+    },
+
+    /// Progress within a task, e.g. chunk 3 of 8.
+    TaskProgress {
+        /// Task path.
+        id: String,
+        /// Units done so far.
+        completed: u64,
+        /// Total units.
+        total: u64,
+    },
+
+    /// A task finished, including any subtasks it did not report.
+    TaskFinished {
+        /// Task path.
+        id: String,
+    },
 }
 
-/// Callback type for receiving progress events.
+/// Receives progress events for one task of a progress tree.
 ///
-/// Both CLI (indicatif) and GUI (Dioxus signals) implement this
-/// to receive updates from the model download/load pipeline.
-pub type ProgressCallback = Box<dyn Fn(ProgressEvent) + Send + Sync>;
+/// Both CLI (indicatif) and GUI (Dioxus signals) implement the sink
+/// to receive updates from the model download/load pipeline. Clones and
+/// subtasks share the sink; subtasks get a child cancellation token, so
+/// cancelling a task also cancels everything below it.
+#[derive(Clone)]
+pub struct ProgressCallback {
+    sink: Arc<dyn Fn(ProgressEvent) + Send + Sync>,
+    cancel: CancellationToken,
+    task: Option<String>,
+}
+
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressCallback")
+            .field("task", &self.task)
+            .field("cancelled", &self.cancel.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProgressCallback {
+    /// Create a root callback that forwards every event to `sink`.
+    pub fn new(sink: impl Fn(ProgressEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            cancel: CancellationToken::new(),
+            task: None,
+        }
+    }
+
+    /// Stop the reported work when `cancel` is cancelled.
+    #[must_use]
+    pub fn with_cancel(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Send `event` to the sink.
+    pub fn emit(&self, event: ProgressEvent) {
+        (self.sink)(event);
+    }
+
+    /// Announce a subtask of this task and return its callback.
+    ///
+    /// `weight` is the subtask's share of this task relative to its
+    /// siblings, e.g. a file's size in bytes.
+    pub fn subtask(&self, id: &str, label: &str, weight: f64) -> Self {
+        let path = match &self.task {
+            Some(parent) => format!("{parent}/{id}"),
+            None => id.to_owned(),
+        };
+        self.emit(ProgressEvent::TaskStarted {
+            id: path.clone(),
+            parent: self.task.clone(),
+            label: label.to_owned(),
+            weight,
+        });
+        Self {
+            sink: Arc::clone(&self.sink),
+            cancel: self.cancel.child_token(),
+            task: Some(path),
+        }
+    }
+
+    /// Report `completed` of `total` units done in this task.
+    pub fn progress(&self, completed: u64, total: u64) {
+        if let Some(id) = &self.task {
+            self.emit(ProgressEvent::TaskProgress {
+                id: id.clone(),
+                completed,
+                total,
+            });
+        }
+    }
+
+    /// Mark this task finished.
+    pub fn finish(&self) {
+        if let Some(id) = &self.task {
+            self.emit(ProgressEvent::TaskFinished { id: id.clone() });
+        }
+    }
+
+    /// Whether the UI cancelled this task or one enclosing it.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Fail with [`SpeechError::Cancelled`] once cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this task was cancelled.
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(SpeechError::Cancelled(
+                self.task.clone().unwrap_or_else(|| "progress".to_owned()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// One task known to a [`ProgressTree`].
+#[derive(Debug, Clone)]
+struct TaskNode {
+    parent: Option<String>,
+    label: String,
+    weight: f64,
+    fraction: f64,
+    finished: bool,
+}
+
+/// Weighted overall progress across a tree of tasks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverallProgress {
+    /// Share of all work done, from `0.0` to `1.0`.
+    pub fraction: f64,
+    /// Labels from the root to the task that moved last,
+    /// e.g. `"Downloading models › model.onnx"`.
+    pub label: String,
+}
+
+/// Folds task events into one weighted overall fraction.
+///
+/// A task with subtasks is as far along as the weighted mean of its
+/// subtasks; a finished task counts as complete whatever its subtasks said.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressTree {
+    tasks: BTreeMap<String, TaskNode>,
+}
+
+impl ProgressTree {
+    /// Create an empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply `event`, returning the new overall progress for task events.
+    pub fn observe(&mut self, event: &ProgressEvent) -> Option<OverallProgress> {
+        let id = match event {
+            ProgressEvent::TaskStarted {
+                id,
+                parent,
+                label,
+                weight,
+            } => {
+                self.tasks.entry(id.clone()).or_insert_with(|| TaskNode {
+                    parent: parent.clone(),
+                    label: label.clone(),
+                    weight: weight.max(0.0),
+                    fraction: 0.0,
+                    finished: false,
+                });
+                id
+            }
+            ProgressEvent::TaskProgress {
+                id,
+                completed,
+                total,
+            } => {
+                let node = self.tasks.get_mut(id)?;
+                if *total > 0 {
+                    node.fraction = (*completed as f64 / *total as f64).clamp(0.0, 1.0);
+                }
+                id
+            }
+            ProgressEvent::TaskFinished { id } => {
+                let node = self.tasks.get_mut(id)?;
+                node.fraction = 1.0;
+                node.finished = true;
+                id
+            }
+            _ => return None,
+        };
+        Some(OverallProgress {
+            fraction: self.fraction(),
+            label: self.label(id),
+        })
+    }
+
+    /// Overall share of work done across all root tasks.
+    pub fn fraction(&self) -> f64 {
+        self.weighted_mean(None).unwrap_or(0.0)
+    }
+
+    fn task_fraction(&self, id: &str) -> f64 {
+        let Some(node) = self.tasks.get(id) else {
+            return 0.0;
+        };
+        if node.finished {
+            return 1.0;
+        }
+        self.weighted_mean(Some(id)).unwrap_or(node.fraction)
+    }
+
+    /// Weighted mean over the children of `parent`, or `None` without any.
+    fn weighted_mean(&self, parent: Option<&str>) -> Option<f64> {
+        let children: Vec<(&String, &TaskNode)> = self
+            .tasks
+            .iter()
+            .filter(|(_, node)| node.parent.as_deref() == parent)
+            .collect();
+        if children.is_empty() {
+            return None;
+        }
+        let total: f64 = children.iter().map(|(_, node)| node.weight).sum();
+        if total <= 0.0 {
+            let done = children
+                .iter()
+                .map(|(id, _)| self.task_fraction(id))
+                .sum::<f64>();
+            return Some(done / children.len() as f64);
+        }
+        let done: f64 = children
+            .iter()
+            .map(|(id, node)| node.weight * self.task_fraction(id))
+            .sum();
+        Some(done / total)
+    }
+
+    fn label(&self, id: &str) -> String {
+        let mut labels = Vec::new();
+        let mut next = Some(id);
+        while let Some(current) = next
+            && let Some(node) = self.tasks.get(current)
+        {
+            labels.push(node.label.as_str());
+            next = node.parent.as_deref();
+        }
+        labels.reverse();
+        labels.join(" › ")
+    }
+}
 
 /// Tracks download speed and estimates time remaining.
 ///
@@ -274,7 +585,7 @@ mod tests {
         let events: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
         let events_clone = Arc::clone(&events);
 
-        let callback: ProgressCallback = Box::new(move |event| {
+        let callback = ProgressCallback::new(move |event| {
             let label = match &event {
                 ProgressEvent::DownloadStarted { .. } => "started",
                 ProgressEvent::DownloadProgress { .. } => "progress",
@@ -282,9 +593,16 @@ mod tests {
                 ProgressEvent::Cached { .. } => "cached",
                 ProgressEvent::LoadStarted { .. } => "load_started",
                 ProgressEvent::LoadComplete { .. } => "load_complete",
+                ProgressEvent::ComponentReady { .. } => "component_ready",
                 ProgressEvent::DownloadPlanReady { .. } => "plan_ready",
                 ProgressEvent::AggregateProgress { .. } => "aggregate",
                 ProgressEvent::Error { .. } => "error",
+                ProgressEvent::CheckStarted { .. } => "check_started",
+                ProgressEvent::CheckPassed { .. } => "check_passed",
+                ProgressEvent::CheckFailed { .. } => "check_failed",
+                ProgressEvent::TaskStarted { .. } => "task_started",
+                ProgressEvent::TaskProgress { .. } => "task_progress",
+                ProgressEvent::TaskFinished { .. } => "task_finished",
             };
             let Ok(mut guard) = events_clone.lock() else {
                 return;
@@ -292,18 +610,18 @@ mod tests {
             guard.push(label.to_owned());
         });
 
-        callback(ProgressEvent::DownloadStarted {
+        callback.emit(ProgressEvent::DownloadStarted {
             repo_id: "test/repo".into(),
             filename: "model.onnx".into(),
             total_bytes: Some(1000),
         });
-        callback(ProgressEvent::DownloadProgress {
+        callback.emit(ProgressEvent::DownloadProgress {
             repo_id: "test/repo".into(),
             filename: "model.onnx".into(),
             bytes_downloaded: 500,
             total_bytes: Some(1000),
         });
-        callback(ProgressEvent::DownloadComplete {
+        callback.emit(ProgressEvent::DownloadComplete {
             repo_id: "test/repo".into(),
             filename: "model.onnx".into(),
         });
@@ -320,17 +638,17 @@ mod tests {
         let events: Arc<Mutex<Vec<ProgressEvent>>> = Arc::new(Mutex::new(Vec::new()));
         let events_clone = Arc::clone(&events);
 
-        let callback: ProgressCallback = Box::new(move |event| {
+        let callback = ProgressCallback::new(move |event| {
             let Ok(mut guard) = events_clone.lock() else {
                 return;
             };
             guard.push(event);
         });
 
-        callback(ProgressEvent::LoadStarted {
+        callback.emit(ProgressEvent::LoadStarted {
             model_name: "STT (Parakeet)".into(),
         });
-        callback(ProgressEvent::LoadComplete {
+        callback.emit(ProgressEvent::LoadComplete {
             model_name: "STT (Parakeet)".into(),
             duration_secs: 2.5,
         });
@@ -502,7 +820,7 @@ mod tests {
         let events: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
         let events_clone = Arc::clone(&events);
 
-        let callback: ProgressCallback = Box::new(move |event| {
+        let callback = ProgressCallback::new(move |event| {
             let label = match &event {
                 ProgressEvent::DownloadPlanReady { .. } => "plan_ready",
                 ProgressEvent::AggregateProgress { .. } => "aggregate",
@@ -515,8 +833,8 @@ mod tests {
         });
 
         let plan = make_plan(vec![make_file("repo/a", "model.onnx", Some(1000), false)]);
-        callback(ProgressEvent::DownloadPlanReady { plan });
-        callback(ProgressEvent::AggregateProgress {
+        callback.emit(ProgressEvent::DownloadPlanReady { plan });
+        callback.emit(ProgressEvent::AggregateProgress {
             bytes_downloaded: 500,
             total_bytes: 1000,
             files_complete: 0,
@@ -528,4 +846,61 @@ mod tests {
         assert_eq!(guard[0], "plan_ready");
         assert_eq!(guard[1], "aggregate");
     }
+
+    #[test]
+    fn tree_weights_subtasks_into_one_fraction() {
+        let events: Arc<Mutex<Vec<ProgressEvent>>> = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = Arc::clone(&events);
+        let root = ProgressCallback::new(move |event| {
+            events_clone
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(event);
+        });
+
+        let download = root.subtask("download", "Downloading models", 3.0);
+        let load = root.subtask("load", "Loading models", 1.0);
+        let big = download.subtask("big.onnx", "big.onnx", 800.0);
+        download.subtask("small.bin", "small.bin", 200.0);
+        big.progress(3, 8);
+        big.progress(8, 8);
+        // Finishing a parent completes subtasks that never reported.
+        download.finish();
+        load.progress(1, 2);
+
+        let mut tree = ProgressTree::new();
+        let updates: Vec<OverallProgress> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| tree.observe(event))
+            .collect();
+        let fractions: Vec<f64> = updates.iter().map(|u| u.fraction).collect();
+        let expected = [0.0, 0.0, 0.0, 0.0, 0.225, 0.6, 0.75, 0.875];
+        assert_eq!(fractions.len(), expected.len());
+        for (got, want) in fractions.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-9, "{fractions:?}");
+        }
+        assert_eq!(updates[4].label, "Downloading models › big.onnx");
+        assert_eq!(updates[7].label, "Loading models");
+    }
+
+    #[test]
+    fn cancelling_a_task_cancels_its_subtasks() {
+        let cancel = CancellationToken::new();
+        let root = ProgressCallback::new(|_| {}).with_cancel(cancel.clone());
+        let download = root.subtask("download", "Downloading models", 1.0);
+        let file = download.subtask("model.onnx", "model.onnx", 1.0);
+        assert!(file.check_cancelled().is_ok());
+
+        file.subtask("chunk", "chunk", 1.0).cancel.cancel();
+        assert!(!file.is_cancelled());
+
+        cancel.cancel();
+        assert!(download.is_cancelled());
+        assert!(matches!(
+            file.check_cancelled(),
+            Err(SpeechError::Cancelled(task)) if task == "download/model.onnx"
+        ));
+    }
 }
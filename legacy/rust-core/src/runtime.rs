@@ -2,25 +2,61 @@
 //!
 //! This is intentionally lightweight (no heavy payloads) so the pipeline
 //! can emit events without blocking critical audio paths.
+//!
+//! # Serialized form
+//!
+//! [`RuntimeEvent::to_json`] produces a versioned JSON object for hosts that
+//! consume events over FFI; every host event envelope mapped from a runtime
+//! event carries it as `runtime_event`:
+//!
+//! ```json
+//! {"schema_version": 1, "type": "tool_executing", "data": {"name": "web_search"}}
+//! ```
+//!
+//! `type` is the variant name in `snake_case` and `data` holds its fields
+//! (absent for unit variants). Nested enums such as [`ControlEvent`] use the
+//! same `type`/`data` shape. Process-local `Instant`s are not serialized.
+//!
+//! Compatibility policy:
+//!
+//! - Adding a variant, or an optional field to an existing variant, is not a
+//!   breaking change and does not bump [`RUNTIME_EVENT_SCHEMA_VERSION`].
+//!   Hosts must ignore events whose `type` they do not know, and fields they
+//!   do not know.
+//! - Renaming or removing a variant or field, or changing a field's type, is
+//!   breaking and bumps the version. Hosts should reject records with a
+//!   version newer than the one they were written against.
+//!
+//! [`RuntimeEvent::from_json`] applies the same rules: unknown types decode
+//! to `None`, unknown fields are ignored, and newer versions are an error.
 
+use crate::error::{Result, SpeechError};
 use crate::pipeline::messages::{ControlEvent, SentenceChunk, Transcription};
+use serde::{Deserialize, Serialize};
+
+/// Version of the serialized [`RuntimeEvent`] schema.
+///
+/// Only bumped for breaking changes; see the module docs.
+pub const RUNTIME_EVENT_SCHEMA_VERSION: u32 = 1;
 
 /// Role used in conversation snapshot entries.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConversationSnapshotEntryRole {
     User,
     Assistant,
 }
 
 /// A single message entry in a conversation snapshot.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConversationSnapshotEntry {
     pub role: ConversationSnapshotEntryRole,
     pub text: String,
 }
 
 /// Events that describe what the pipeline is doing "right now".
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum RuntimeEvent {
     /// Low-latency control/state events (barge-in, playback start/end).
     Control(ControlEvent),
@@ -28,10 +64,31 @@ pub enum RuntimeEvent {
     Transcription(Transcription),
     /// Assistant sentence produced by the LLM (sentence-chunked stream).
     AssistantSentence(SentenceChunk),
+    /// Raw markdown of the assistant reply, streamed a word at a time.
+    ///
+    /// Runs alongside the sentence chunks sent to TTS so hosts can render
+    /// rich text (code blocks, lists) as it arrives. Each agent turn is its
+    /// own message; concatenating a message's deltas gives its full text.
+    AssistantTextDelta {
+        /// Identifies the message the delta belongs to.
+        message_id: String,
+        /// Markdown text to append; ends at a word boundary unless final.
+        delta: String,
+        /// Whether this is the last delta of the message.
+        is_final: bool,
+    },
+    /// Text streamed so far for `message_id` should be dropped; the reply
+    /// is regenerated (e.g. by the fallback provider) under the same id.
+    AssistantTextDiscarded { message_id: String },
+    /// The model's thinking before its reply, sent only when
+    /// `[llm.thinking] show_in_ui` is on. Never spoken.
+    AssistantThinking { text: String },
     /// Whether the assistant is currently generating a response.
     AssistantGenerating { active: bool },
     /// Agent tool is currently executing (for "thinking" indicator).
     ToolExecuting { name: String },
+    /// Progress of a long-running tool, as the fraction done (0.0–1.0).
+    ToolProgress { name: String, fraction: f32 },
     /// Agent tool call request (for UI/telemetry).
     ToolCall {
         /// Tool call identifier (stable across start/update/end).
@@ -47,6 +104,40 @@ pub enum RuntimeEvent {
         success: bool,
         /// Best-effort textual output for display (may be truncated).
         output_text: Option<String>,
+        /// Structured payload matching the tool's output schema, if any.
+        structured: Option<serde_json::Value>,
+    },
+    /// A tool call was refused because a rate-limit budget ran out.
+    ToolBudgetExhausted {
+        name: String,
+        /// `"tool"` for the tool's own budget, `"global"` for the shared one.
+        scope: String,
+        /// `"turn"` or `"minute"`.
+        window: String,
+        limit: u32,
+    },
+    /// A tool call went over its timeout, CPU budget or output limit.
+    ToolLimitExceeded {
+        name: String,
+        /// `"timeout"`, `"cpu_time"` or `"output"`.
+        kind: String,
+        /// The limit, in milliseconds (timeout, CPU time) or bytes (output).
+        limit: u64,
+        /// What the call used, in the same unit.
+        used: u64,
+    },
+    /// Tool output contained text that looked like instructions to the agent.
+    PromptInjectionDetected {
+        tool: String,
+        /// Patterns that matched.
+        patterns: Vec<String>,
+        /// Whether the matching lines were removed (otherwise only flagged).
+        stripped: bool,
+    },
+    /// The reflection pass found a problem in the final answer it could not fix.
+    AnswerFlagged {
+        /// Short description of the problem.
+        reason: String,
     },
     /// Best-effort assistant audio level (RMS) while playing back speech.
     ///
@@ -105,6 +196,51 @@ pub enum RuntimeEvent {
         /// Whether permissions are now granted.
         granted: bool,
     },
+    /// The user asked by voice to wipe all personal data.
+    ///
+    /// The host runtime turns this into a confirmed `data.forget` request;
+    /// nothing is deleted until the user approves.
+    DataForgetRequested,
+    /// A line of live captions for assistant speech.
+    ///
+    /// Only emitted when captions are on; see [`crate::captions`].
+    CaptionSegment(crate::captions::CaptionSegment),
+    /// The assistant utterance being captioned has ended.
+    CaptionsEnded {
+        utterance_id: u64,
+        /// Playback was cut off; remaining caption lines should be removed.
+        interrupted: bool,
+    },
+    /// Offline mode was switched by voice command.
+    ///
+    /// The host runtime persists the new setting to config.
+    OfflineModeChanged {
+        /// Whether offline mode is now on.
+        offline: bool,
+    },
+    /// Meeting mode was switched on or off; see [`crate::meeting`].
+    MeetingModeChanged {
+        /// Whether a meeting is now being transcribed.
+        active: bool,
+    },
+    /// Minutes for a finished meeting were written and saved.
+    MeetingMinutesReady {
+        meeting_id: String,
+        /// Where the minutes were saved.
+        path: String,
+        /// Number of action items in the minutes.
+        action_items: usize,
+    },
+    /// A conversation handed off from another device replaced the current
+    /// one; see [`crate::handoff`].
+    ConversationHandedOff {
+        /// Name of the device it came from.
+        source: String,
+        /// Number of messages continued.
+        messages: usize,
+        /// Ids of the memories the conversation wrote.
+        memory_ids: Vec<String>,
+    },
     /// A model switch was requested via voice command.
     ///
     /// Emitted after a `SwitchModel` voice command is parsed and before
@@ -131,6 +267,14 @@ pub enum RuntimeEvent {
         /// Whether audio data is being received from the microphone.
         active: bool,
     },
+    /// The conversation ended after a long silence: its history was
+    /// summarized into memory and the next turn starts a fresh session.
+    ConversationEnded {
+        /// Number of user/assistant turns in the conversation.
+        turns: usize,
+        /// Whether a summary was written to memory.
+        summarized: bool,
+    },
     /// Explicit canvas panel visibility command for conversation UX.
     ///
     /// This is emitted when the user asks to show/hide conversation canvas.
@@ -140,10 +284,10 @@ pub enum RuntimeEvent {
     /// Emitted when the user asks to show/hide the conversation panel
     /// (distinct from the canvas panel).
     ConversationVisibility { visible: bool },
-    /// The primary LLM provider failed and the request was retried against the
-    /// local fallback model.
+    /// The primary LLM provider failed or stalled mid-stream and the request
+    /// was retried against the fallback model.
     ///
-    /// Emitted by the fallback provider adapter so the GUI can show a
+    /// Emitted by the agent loop so the GUI can show a
     /// non-intrusive notification (e.g. "Using local model — network issue").
     ProviderFallback {
         /// Name of the primary provider that failed.
@@ -267,4 +411,467 @@ pub enum RuntimeEvent {
         /// Whether enrollment is now complete.
         enrolled: bool,
     },
+    /// The runtime was suspended because the system is going to sleep:
+    /// capture is stopped and background jobs are paused.
+    Suspended,
+    /// The system woke up and the runtime resumed, re-probing audio devices.
+    Resumed {
+        /// How long the system slept, in seconds.
+        slept_secs: u64,
+    },
+}
+
+impl RuntimeEvent {
+    /// Every serialized `type` this build understands.
+    pub const EVENT_TYPES: &'static [&'static str] = &[
+        "control",
+        "transcription",
+        "assistant_sentence",
+        "assistant_text_delta",
+        "assistant_text_discarded",
+        "assistant_thinking",
+        "assistant_generating",
+        "tool_executing",
+        "tool_progress",
+        "tool_call",
+        "tool_result",
+        "tool_budget_exhausted",
+        "tool_limit_exceeded",
+        "prompt_injection_detected",
+        "answer_flagged",
+        "assistant_audio_level",
+        "assistant_viseme",
+        "memory_recall",
+        "memory_write",
+        "memory_conflict",
+        "memory_migration",
+        "model_selection_prompt",
+        "model_selected",
+        "voice_command_detected",
+        "permissions_changed",
+        "data_forget_requested",
+        "caption_segment",
+        "captions_ended",
+        "offline_mode_changed",
+        "meeting_mode_changed",
+        "meeting_minutes_ready",
+        "conversation_handed_off",
+        "model_switch_requested",
+        "conversation_snapshot",
+        "conversation_ended",
+        "mic_status",
+        "conversation_canvas_visibility",
+        "conversation_visibility",
+        "provider_fallback",
+        "intelligence_extraction",
+        "proactive_briefing_ready",
+        "relationship_update",
+        "skill_proposal",
+        "noise_budget_update",
+        "orb_mood_update",
+        "pipeline_timing",
+        "background_task_started",
+        "background_task_completed",
+        "approval_resolved",
+        "voice_identity_decision",
+        "voiceprint_enrollment_progress",
+        "suspended",
+        "resumed",
+    ];
+
+    /// The serialized `type` of this event.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::Control(_) => "control",
+            Self::Transcription(_) => "transcription",
+            Self::AssistantSentence(_) => "assistant_sentence",
+            Self::AssistantTextDelta { .. } => "assistant_text_delta",
+            Self::AssistantTextDiscarded { .. } => "assistant_text_discarded",
+            Self::AssistantThinking { .. } => "assistant_thinking",
+            Self::AssistantGenerating { .. } => "assistant_generating",
+            Self::ToolExecuting { .. } => "tool_executing",
+            Self::ToolProgress { .. } => "tool_progress",
+            Self::ToolCall { .. } => "tool_call",
+            Self::ToolResult { .. } => "tool_result",
+            Self::ToolBudgetExhausted { .. } => "tool_budget_exhausted",
+            Self::ToolLimitExceeded { .. } => "tool_limit_exceeded",
+            Self::PromptInjectionDetected { .. } => "prompt_injection_detected",
+            Self::AnswerFlagged { .. } => "answer_flagged",
+            Self::AssistantAudioLevel { .. } => "assistant_audio_level",
+            Self::AssistantViseme { .. } => "assistant_viseme",
+            Self::MemoryRecall { .. } => "memory_recall",
+            Self::MemoryWrite { .. } => "memory_write",
+            Self::MemoryConflict { .. } => "memory_conflict",
+            Self::MemoryMigration { .. } => "memory_migration",
+            Self::ModelSelectionPrompt { .. } => "model_selection_prompt",
+            Self::ModelSelected { .. } => "model_selected",
+            Self::VoiceCommandDetected { .. } => "voice_command_detected",
+            Self::PermissionsChanged { .. } => "permissions_changed",
+            Self::DataForgetRequested => "data_forget_requested",
+            Self::CaptionSegment(_) => "caption_segment",
+            Self::CaptionsEnded { .. } => "captions_ended",
+            Self::OfflineModeChanged { .. } => "offline_mode_changed",
+            Self::MeetingModeChanged { .. } => "meeting_mode_changed",
+            Self::MeetingMinutesReady { .. } => "meeting_minutes_ready",
+            Self::ConversationHandedOff { .. } => "conversation_handed_off",
+            Self::ModelSwitchRequested { .. } => "model_switch_requested",
+            Self::ConversationSnapshot { .. } => "conversation_snapshot",
+            Self::ConversationEnded { .. } => "conversation_ended",
+            Self::MicStatus { .. } => "mic_status",
+            Self::ConversationCanvasVisibility { .. } => "conversation_canvas_visibility",
+            Self::ConversationVisibility { .. } => "conversation_visibility",
+            Self::ProviderFallback { .. } => "provider_fallback",
+            Self::IntelligenceExtraction { .. } => "intelligence_extraction",
+            Self::ProactiveBriefingReady { .. } => "proactive_briefing_ready",
+            Self::RelationshipUpdate { .. } => "relationship_update",
+            Self::SkillProposal { .. } => "skill_proposal",
+            Self::NoiseBudgetUpdate { .. } => "noise_budget_update",
+            Self::OrbMoodUpdate { .. } => "orb_mood_update",
+            Self::PipelineTiming { .. } => "pipeline_timing",
+            Self::BackgroundTaskStarted { .. } => "background_task_started",
+            Self::BackgroundTaskCompleted { .. } => "background_task_completed",
+            Self::ApprovalResolved { .. } => "approval_resolved",
+            Self::VoiceIdentityDecision { .. } => "voice_identity_decision",
+            Self::VoiceprintEnrollmentProgress { .. } => "voiceprint_enrollment_progress",
+            Self::Suspended => "suspended",
+            Self::Resumed { .. } => "resumed",
+        }
+    }
+
+    /// Serialize to the versioned JSON form described in the module docs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpeechError::Pipeline`] if the event cannot be serialized.
+    pub fn to_json(&self) -> Result<serde_json::Value> {
+        let mut value = serde_json::to_value(self).map_err(|e| {
+            SpeechError::Pipeline(format!("cannot serialize {} event: {e}", self.event_type()))
+        })?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert(
+                "schema_version".to_owned(),
+                RUNTIME_EVENT_SCHEMA_VERSION.into(),
+            );
+        }
+        Ok(value)
+    }
+
+    /// Decode an event produced by [`to_json`](Self::to_json).
+    ///
+    /// Returns `Ok(None)` for a `type` this build does not know, so newer
+    /// producers can add variants without breaking older consumers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpeechError::Pipeline`] if the record has no schema
+    /// version, comes from a newer schema version, or does not match the
+    /// shape of its `type`.
+    pub fn from_json(value: &serde_json::Value) -> Result<Option<Self>> {
+        let version = value
+            .get("schema_version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| {
+                SpeechError::Pipeline("runtime event has no schema_version".to_owned())
+            })?;
+        if version == 0 || version > u64::from(RUNTIME_EVENT_SCHEMA_VERSION) {
+            return Err(SpeechError::Pipeline(format!(
+                "unsupported runtime event schema version {version} (supported: {RUNTIME_EVENT_SCHEMA_VERSION})"
+            )));
+        }
+        let kind = value
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| SpeechError::Pipeline("runtime event has no type".to_owned()))?;
+        if !Self::EVENT_TYPES.contains(&kind) {
+            return Ok(None);
+        }
+        serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| SpeechError::Pipeline(format!("invalid {kind} event: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeSet;
+    use std::time::Instant;
+
+    /// One event of every variant, with every optional field populated.
+    fn samples() -> Vec<RuntimeEvent> {
+        vec![
+            RuntimeEvent::Control(ControlEvent::UserSpeechStart {
+                captured_at: Instant::now(),
+                rms: 0.125,
+            }),
+            RuntimeEvent::Transcription(Transcription {
+                text: "hello fae".to_owned(),
+                is_final: true,
+                voiceprint: Some(vec![0.5, -0.25]),
+                audio_rms: Some(0.03),
+                audio_duration_secs: Some(1.5),
+                audio_captured_at: Instant::now(),
+                transcribed_at: Instant::now(),
+                speculation: None,
+            }),
+            RuntimeEvent::AssistantSentence(SentenceChunk {
+                text: "Hi there.".to_owned(),
+                is_final: false,
+            }),
+            RuntimeEvent::AssistantTextDelta {
+                message_id: "msg-1".to_owned(),
+                delta: "```rust\n".to_owned(),
+                is_final: false,
+            },
+            RuntimeEvent::AssistantTextDiscarded {
+                message_id: "msg-1".to_owned(),
+            },
+            RuntimeEvent::AssistantThinking {
+                text: "The user wants the time.".to_owned(),
+            },
+            RuntimeEvent::AssistantGenerating { active: true },
+            RuntimeEvent::ToolExecuting {
+                name: "web_search".to_owned(),
+            },
+            RuntimeEvent::ToolProgress {
+                name: "transcribe_file".to_owned(),
+                fraction: 0.5,
+            },
+            RuntimeEvent::ToolCall {
+                id: "call-1".to_owned(),
+                name: "read".to_owned(),
+                input_json: r#"{"path":"a.txt"}"#.to_owned(),
+            },
+            RuntimeEvent::ToolResult {
+                id: "call-1".to_owned(),
+                name: "read".to_owned(),
+                success: true,
+                output_text: Some("contents".to_owned()),
+                structured: Some(json!({"lines": 3})),
+            },
+            RuntimeEvent::ToolBudgetExhausted {
+                name: "bash".to_owned(),
+                scope: "tool".to_owned(),
+                window: "turn".to_owned(),
+                limit: 5,
+            },
+            RuntimeEvent::ToolLimitExceeded {
+                name: "bash".to_owned(),
+                kind: "cpu_time".to_owned(),
+                limit: 1_000,
+                used: 2_400,
+            },
+            RuntimeEvent::PromptInjectionDetected {
+                tool: "fetch_url".to_owned(),
+                patterns: vec!["ignore previous".to_owned()],
+                stripped: true,
+            },
+            RuntimeEvent::AnswerFlagged {
+                reason: "unsupported claim".to_owned(),
+            },
+            RuntimeEvent::AssistantAudioLevel { rms: 0.5 },
+            RuntimeEvent::AssistantViseme {
+                mouth_png: "mouth_open.png".to_owned(),
+            },
+            RuntimeEvent::MemoryRecall {
+                query: "birthday".to_owned(),
+                hits: 2,
+            },
+            RuntimeEvent::MemoryWrite {
+                op: "insert".to_owned(),
+                target_id: Some("mem-1".to_owned()),
+            },
+            RuntimeEvent::MemoryConflict {
+                existing_id: "mem-1".to_owned(),
+                replacement_id: Some("mem-2".to_owned()),
+            },
+            RuntimeEvent::MemoryMigration {
+                from: 3,
+                to: 4,
+                success: true,
+            },
+            RuntimeEvent::ModelSelectionPrompt {
+                candidates: vec!["anthropic/claude".to_owned(), "local/qwen".to_owned()],
+                timeout_secs: 10,
+            },
+            RuntimeEvent::ModelSelected {
+                provider_model: "local/qwen".to_owned(),
+            },
+            RuntimeEvent::VoiceCommandDetected {
+                command: "switch to local".to_owned(),
+            },
+            RuntimeEvent::PermissionsChanged { granted: true },
+            RuntimeEvent::DataForgetRequested,
+            RuntimeEvent::CaptionSegment(crate::captions::CaptionSegment {
+                utterance_id: 7,
+                index: 1,
+                text: "as she says it".to_owned(),
+                start_ms: 400,
+                end_ms: 900,
+            }),
+            RuntimeEvent::CaptionsEnded {
+                utterance_id: 7,
+                interrupted: false,
+            },
+            RuntimeEvent::OfflineModeChanged { offline: true },
+            RuntimeEvent::MeetingModeChanged { active: true },
+            RuntimeEvent::MeetingMinutesReady {
+                meeting_id: "meeting-20261016-093000".to_owned(),
+                path: "/tmp/meeting-20261016-093000-minutes.md".to_owned(),
+                action_items: 3,
+            },
+            RuntimeEvent::ConversationHandedOff {
+                source: "Desktop".to_owned(),
+                messages: 12,
+                memory_ids: vec!["mem-1".to_owned()],
+            },
+            RuntimeEvent::ModelSwitchRequested {
+                target: "anthropic".to_owned(),
+            },
+            RuntimeEvent::ConversationSnapshot {
+                entries: vec![ConversationSnapshotEntry {
+                    role: ConversationSnapshotEntryRole::Assistant,
+                    text: "Hello.".to_owned(),
+                }],
+            },
+            RuntimeEvent::ConversationEnded {
+                turns: 6,
+                summarized: true,
+            },
+            RuntimeEvent::MicStatus { active: false },
+            RuntimeEvent::ConversationCanvasVisibility { visible: true },
+            RuntimeEvent::ConversationVisibility { visible: false },
+            RuntimeEvent::ProviderFallback {
+                primary: "anthropic".to_owned(),
+                error: "timeout".to_owned(),
+            },
+            RuntimeEvent::IntelligenceExtraction {
+                items_count: 4,
+                actions_count: 1,
+            },
+            RuntimeEvent::ProactiveBriefingReady { item_count: 3 },
+            RuntimeEvent::RelationshipUpdate {
+                name: "Sam".to_owned(),
+            },
+            RuntimeEvent::SkillProposal {
+                skill_name: "weather".to_owned(),
+            },
+            RuntimeEvent::NoiseBudgetUpdate { remaining: 2 },
+            RuntimeEvent::OrbMoodUpdate {
+                feeling: "warmth".to_owned(),
+                palette: Some("autumn-bracken".to_owned()),
+            },
+            RuntimeEvent::PipelineTiming {
+                stage: "stt".to_owned(),
+                duration_ms: 180,
+            },
+            RuntimeEvent::BackgroundTaskStarted {
+                task_id: "task-1".to_owned(),
+                description: "look up the weather".to_owned(),
+            },
+            RuntimeEvent::BackgroundTaskCompleted {
+                task_id: "task-1".to_owned(),
+                success: true,
+                summary: "Sunny.".to_owned(),
+            },
+            RuntimeEvent::ApprovalResolved {
+                request_id: 9,
+                approved: false,
+                source: "voice".to_owned(),
+                speaker_verified: Some(true),
+            },
+            RuntimeEvent::VoiceIdentityDecision {
+                accepted: true,
+                reason: "speaker_match".to_owned(),
+                similarity: Some(0.875),
+            },
+            RuntimeEvent::VoiceprintEnrollmentProgress {
+                sample_count: 2,
+                required_samples: 3,
+                enrolled: false,
+            },
+            RuntimeEvent::Suspended,
+            RuntimeEvent::Resumed { slept_secs: 3_600 },
+        ]
+    }
+
+    #[test]
+    fn samples_cover_every_event_type() {
+        let sampled: BTreeSet<&str> = samples().iter().map(RuntimeEvent::event_type).collect();
+        let known: BTreeSet<&str> = RuntimeEvent::EVENT_TYPES.iter().copied().collect();
+        assert_eq!(
+            known.len(),
+            RuntimeEvent::EVENT_TYPES.len(),
+            "duplicate type"
+        );
+        assert_eq!(sampled, known);
+    }
+
+    #[test]
+    fn every_variant_round_trips_through_json() {
+        for event in samples() {
+            let json = event.to_json().unwrap();
+            assert_eq!(json["schema_version"], RUNTIME_EVENT_SCHEMA_VERSION);
+            assert_eq!(json["type"], event.event_type());
+
+            let decoded = RuntimeEvent::from_json(&json)
+                .unwrap()
+                .unwrap_or_else(|| panic!("{} not decoded", event.event_type()));
+            assert_eq!(decoded.to_json().unwrap(), json);
+
+            // Survives a trip through text, as it would over FFI.
+            let text = serde_json::to_string(&json).unwrap();
+            let reparsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+            assert!(RuntimeEvent::from_json(&reparsed).unwrap().is_some());
+        }
+    }
+
+    #[test]
+    fn v1_wire_shape_is_stable() {
+        let event = RuntimeEvent::ToolExecuting {
+            name: "web_search".to_owned(),
+        };
+        assert_eq!(
+            event.to_json().unwrap(),
+            json!({"schema_version": 1, "type": "tool_executing", "data": {"name": "web_search"}})
+        );
+        assert_eq!(
+            RuntimeEvent::DataForgetRequested.to_json().unwrap(),
+            json!({"schema_version": 1, "type": "data_forget_requested"})
+        );
+        let control = RuntimeEvent::Control(ControlEvent::AssistantSpeechEnd { interrupted: true });
+        assert_eq!(
+            control.to_json().unwrap(),
+            json!({
+                "schema_version": 1,
+                "type": "control",
+                "data": {"type": "assistant_speech_end", "data": {"interrupted": true}},
+            })
+        );
+    }
+
+    #[test]
+    fn newer_records_follow_the_compatibility_policy() {
+        // A variant added after this build: skipped, not an error.
+        let unknown = json!({"schema_version": 1, "type": "hologram_ready", "data": {}});
+        assert!(RuntimeEvent::from_json(&unknown).unwrap().is_none());
+
+        // A field added after this build: ignored.
+        let extra = json!({
+            "schema_version": 1,
+            "type": "mic_status",
+            "data": {"active": true, "device": "USB mic"},
+        });
+        let decoded = RuntimeEvent::from_json(&extra).unwrap().unwrap();
+        assert!(matches!(decoded, RuntimeEvent::MicStatus { active: true }));
+
+        // A breaking schema change: rejected.
+        let newer = json!({"schema_version": 2, "type": "mic_status", "data": {"active": true}});
+        assert!(RuntimeEvent::from_json(&newer).is_err());
+        let unversioned = json!({"type": "mic_status", "data": {"active": true}});
+        assert!(RuntimeEvent::from_json(&unversioned).is_err());
+    }
 }
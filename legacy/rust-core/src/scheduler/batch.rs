@@ -0,0 +1,261 @@
+//! Scheduled conversations run through provider batch APIs.
+//!
+//! A latency-insensitive task is submitted as a one-request batch on its
+//! first run. The handle is persisted so later scheduler ticks (and
+//! restarts) can poll it; each tick the task is reported as waiting until
+//! the batch ends and its result becomes the task's result.
+
+use crate::fae_llm::providers::batch::{BatchHandle, BatchProvider, BatchRequest, BatchStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Minimum time between polls of the same batch.
+pub const BATCH_POLL_INTERVAL_SECS: u64 = 300;
+
+/// Batches still pending after this long are abandoned. Providers expire
+/// them after 24 hours.
+pub const BATCH_MAX_AGE_SECS: u64 = 26 * 60 * 60;
+
+/// A submitted batch waiting for its result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingBatch {
+    /// Provider the batch was submitted to.
+    pub provider: String,
+    /// Provider batch handle.
+    pub handle: BatchHandle,
+    /// Submission time (epoch seconds).
+    pub submitted_at: u64,
+    /// Last poll time (epoch seconds).
+    pub last_polled_at: u64,
+}
+
+/// Pending batches keyed by task ID.
+pub type PendingBatches = HashMap<String, PendingBatch>;
+
+/// Load pending batches from `path`; a missing file means none.
+pub fn load_pending(path: &Path) -> crate::Result<PendingBatches> {
+    match std::fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+            crate::SpeechError::Scheduler(format!("cannot parse pending batches: {e}"))
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PendingBatches::new()),
+        Err(e) => Err(crate::SpeechError::Scheduler(format!(
+            "cannot read pending batches: {e}"
+        ))),
+    }
+}
+
+/// Write pending batches to `path`.
+pub fn save_pending(path: &Path, pending: &PendingBatches) -> crate::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| crate::SpeechError::Scheduler(format!("cannot create state dir: {e}")))?;
+    }
+    let json = serde_json::to_string_pretty(pending).map_err(|e| {
+        crate::SpeechError::Scheduler(format!("cannot serialize pending batches: {e}"))
+    })?;
+    std::fs::write(path, json)
+        .map_err(|e| crate::SpeechError::Scheduler(format!("cannot write pending batches: {e}")))
+}
+
+/// Where a batched conversation stands after one scheduler run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchStep {
+    /// Submitted or still processing; carries a status line for run history.
+    Waiting(String),
+    /// The batch finished with this response text.
+    Done(String),
+    /// The batch, or this task's request in it, failed.
+    Failed(String),
+}
+
+/// Advance the batched conversation of `task_id`: submit it if nothing is
+/// pending, otherwise poll (at most every [`BATCH_POLL_INTERVAL_SECS`]) and
+/// collect the result once the batch has ended.
+///
+/// The pending-batch file at `path` is updated as the batch progresses.
+pub async fn advance(
+    path: &Path,
+    provider: &dyn BatchProvider,
+    task_id: &str,
+    prompt: &str,
+    system: Option<&str>,
+    now: u64,
+) -> crate::Result<BatchStep> {
+    let mut pending = load_pending(path)?;
+
+    let Some(entry) = pending.get(task_id).cloned() else {
+        let request = BatchRequest {
+            custom_id: task_id.to_owned(),
+            system: system.map(str::to_owned),
+            prompt: prompt.to_owned(),
+        };
+        let handle = match provider.submit(&[request]).await {
+            Ok(handle) => handle,
+            Err(e) => return Ok(BatchStep::Failed(format!("batch submission failed: {e}"))),
+        };
+        info!(
+            task_id,
+            provider = provider.name(),
+            batch = %handle.id,
+            "scheduled conversation submitted as batch"
+        );
+        let status = format!("submitted to {} batch {}", provider.name(), handle.id);
+        pending.insert(
+            task_id.to_owned(),
+            PendingBatch {
+                provider: provider.name().to_owned(),
+                handle,
+                submitted_at: now,
+                last_polled_at: now,
+            },
+        );
+        save_pending(path, &pending)?;
+        return Ok(BatchStep::Waiting(status));
+    };
+
+    let waiting = || BatchStep::Waiting(format!("waiting on batch {}", entry.handle.id));
+    if now.saturating_sub(entry.submitted_at) > BATCH_MAX_AGE_SECS {
+        pending.remove(task_id);
+        save_pending(path, &pending)?;
+        return Ok(BatchStep::Failed(format!(
+            "batch {} never finished",
+            entry.handle.id
+        )));
+    }
+    if now.saturating_sub(entry.last_polled_at) < BATCH_POLL_INTERVAL_SECS {
+        return Ok(waiting());
+    }
+
+    let status = match provider.poll(&entry.handle).await {
+        Ok(status) => status,
+        Err(e) => {
+            // Transient: keep the batch and poll again later.
+            warn!(task_id, batch = %entry.handle.id, "batch poll failed: {e}");
+            BatchStatus::InProgress
+        }
+    };
+    let step = match status {
+        BatchStatus::InProgress => {
+            if let Some(entry) = pending.get_mut(task_id) {
+                entry.last_polled_at = now;
+            }
+            save_pending(path, &pending)?;
+            return Ok(waiting());
+        }
+        BatchStatus::Completed => match provider.collect(&entry.handle).await {
+            Ok(results) => match results.into_iter().find(|r| r.custom_id == task_id) {
+                Some(result) => match result.outcome {
+                    Ok(text) => BatchStep::Done(text),
+                    Err(e) => BatchStep::Failed(e),
+                },
+                None => BatchStep::Failed(format!("batch {} has no result", entry.handle.id)),
+            },
+            Err(e) => BatchStep::Failed(format!("cannot collect batch results: {e}")),
+        },
+        BatchStatus::Failed(e) => BatchStep::Failed(e),
+        BatchStatus::Expired => BatchStep::Failed(format!("batch {} expired", entry.handle.id)),
+        BatchStatus::Cancelled => {
+            BatchStep::Failed(format!("batch {} was cancelled", entry.handle.id))
+        }
+    };
+    pending.remove(task_id);
+    save_pending(path, &pending)?;
+    Ok(step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fae_llm::error::FaeLlmError;
+    use crate::fae_llm::providers::batch::{BatchApi, BatchResult};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    struct FakeBatches {
+        status: Mutex<BatchStatus>,
+        submits: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl BatchProvider for FakeBatches {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        async fn submit(&self, _requests: &[BatchRequest]) -> Result<BatchHandle, FaeLlmError> {
+            *self.submits.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+            Ok(BatchHandle {
+                api: BatchApi::Anthropic,
+                id: "batch_1".to_owned(),
+            })
+        }
+
+        async fn poll(&self, _handle: &BatchHandle) -> Result<BatchStatus, FaeLlmError> {
+            Ok(self
+                .status
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone())
+        }
+
+        async fn collect(&self, _handle: &BatchHandle) -> Result<Vec<BatchResult>, FaeLlmError> {
+            Ok(vec![BatchResult {
+                custom_id: "digest".to_owned(),
+                outcome: Ok("Three meetings tomorrow.".to_owned()),
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_is_submitted_once_then_collected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("batches.json");
+        let provider = FakeBatches {
+            status: Mutex::new(BatchStatus::InProgress),
+            submits: Mutex::new(0),
+        };
+        let (path_ref, provider_ref) = (path.as_path(), &provider);
+        let run = move |now| advance(path_ref, provider_ref, "digest", "Summarize", None, now);
+
+        assert!(matches!(run(1_000).await, Ok(BatchStep::Waiting(_))));
+        assert_eq!(load_pending(&path).map(|p| p.len()).ok(), Some(1));
+        // Too soon to poll again; still pending after a poll.
+        assert!(matches!(run(1_010).await, Ok(BatchStep::Waiting(_))));
+        assert!(matches!(run(1_400).await, Ok(BatchStep::Waiting(_))));
+
+        *provider.status.lock().unwrap_or_else(|e| e.into_inner()) = BatchStatus::Completed;
+        assert_eq!(
+            run(1_800).await.ok(),
+            Some(BatchStep::Done("Three meetings tomorrow.".to_owned()))
+        );
+        assert_eq!(
+            *provider.submits.lock().unwrap_or_else(|e| e.into_inner()),
+            1
+        );
+        assert_eq!(load_pending(&path).map(|p| p.len()).ok(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn stale_batches_are_abandoned() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("batches.json");
+        let provider = FakeBatches {
+            status: Mutex::new(BatchStatus::InProgress),
+            submits: Mutex::new(0),
+        };
+        let _ = advance(&path, &provider, "digest", "Summarize", None, 0).await;
+        let step = advance(
+            &path,
+            &provider,
+            "digest",
+            "Summarize",
+            None,
+            BATCH_MAX_AGE_SECS + 1,
+        )
+        .await;
+        assert!(matches!(step, Ok(BatchStep::Failed(_))));
+    }
+}
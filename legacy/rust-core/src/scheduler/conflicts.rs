@@ -0,0 +1,233 @@
+//! Schedule conflict detection.
+//!
+//! When a task is created, its runs over the coming week are compared with
+//! the user's quiet hours and with the runs of other exclusive tasks.
+//! Conflicts are warnings: the task is still saved, and at run time the
+//! scheduler never starts an exclusive task while another one is running.
+
+use crate::config::IntelligenceConfig;
+use crate::scheduler::tasks::{Schedule, ScheduledTask, epoch_to_local};
+use chrono::Timelike;
+use serde::Serialize;
+
+/// Runs of two exclusive tasks closer than this collide.
+pub const CONFLICT_WINDOW_SECS: u64 = 10 * 60;
+
+/// How far ahead runs are compared.
+const HORIZON_SECS: u64 = 7 * 24 * 3600;
+
+/// Upper bound on runs compared per task (short intervals).
+const MAX_SAMPLED_RUNS: usize = 2_000;
+
+/// Local hours during which Fae stays quiet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct QuietHours {
+    /// First quiet hour (0-23, local time).
+    pub start: u8,
+    /// First hour after the quiet period (0-23, local time).
+    pub end: u8,
+}
+
+impl QuietHours {
+    /// Quiet hours from the intelligence settings.
+    pub fn from_intelligence(config: &IntelligenceConfig) -> Self {
+        Self {
+            start: config.quiet_hours_start.min(23),
+            end: config.quiet_hours_end.min(23),
+        }
+    }
+
+    /// Quiet hours from the intelligence settings in `config.toml`.
+    pub fn from_config() -> Self {
+        let config = crate::config::SpeechConfig::from_file(
+            &crate::config::SpeechConfig::default_config_path(),
+        )
+        .unwrap_or_default();
+        Self::from_intelligence(&config.intelligence)
+    }
+
+    /// Whether `hour` falls in the quiet period. Equal bounds mean none.
+    pub fn contains(self, hour: u8) -> bool {
+        if self.start <= self.end {
+            hour >= self.start && hour < self.end
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+}
+
+/// A reason a task's schedule may not run as the user expects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScheduleConflict {
+    /// A fixed-time run falls within quiet hours.
+    QuietHours { start: u8, end: u8 },
+    /// Runs land within [`CONFLICT_WINDOW_SECS`] of another exclusive task's.
+    Exclusive { task_id: String, task_name: String },
+}
+
+impl std::fmt::Display for ScheduleConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::QuietHours { start, end } => {
+                write!(f, "runs during quiet hours ({start:02}:00-{end:02}:00)")
+            }
+            Self::Exclusive { task_id, task_name } => write!(
+                f,
+                "runs within {} minutes of exclusive task '{task_name}' ({task_id}); \
+                 one of them will wait for the other",
+                CONFLICT_WINDOW_SECS / 60
+            ),
+        }
+    }
+}
+
+/// Conflicts between `task` and quiet hours or the other `tasks`, for runs
+/// in the week after `now`.
+///
+/// Interval tasks run around the clock by design, so only daily and weekly
+/// runs are checked against quiet hours. Disabled tasks never conflict.
+pub fn detect_conflicts(
+    task: &ScheduledTask,
+    tasks: &[ScheduledTask],
+    quiet: Option<QuietHours>,
+    now: u64,
+) -> Vec<ScheduleConflict> {
+    let mut conflicts = Vec::new();
+    if !task.enabled {
+        return conflicts;
+    }
+    let runs = upcoming_runs(&task.schedule, now);
+
+    if let Some(quiet) = quiet
+        && !matches!(task.schedule, Schedule::Interval { .. })
+        && runs
+            .iter()
+            .any(|&run| quiet.contains(epoch_to_local(run).hour() as u8))
+    {
+        conflicts.push(ScheduleConflict::QuietHours {
+            start: quiet.start,
+            end: quiet.end,
+        });
+    }
+
+    if task.exclusive {
+        for other in tasks
+            .iter()
+            .filter(|t| t.exclusive && t.enabled && t.id != task.id)
+        {
+            if runs_collide(&runs, &upcoming_runs(&other.schedule, now)) {
+                conflicts.push(ScheduleConflict::Exclusive {
+                    task_id: other.id.clone(),
+                    task_name: other.name.clone(),
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+/// Planned run times in the week after `now`, in order.
+fn upcoming_runs(schedule: &Schedule, now: u64) -> Vec<u64> {
+    let horizon = now.saturating_add(HORIZON_SECS);
+    let mut runs = Vec::new();
+    let mut at = now;
+    while runs.len() < MAX_SAMPLED_RUNS {
+        let next = schedule.next_after_epoch(at);
+        if next > horizon || next <= at {
+            break;
+        }
+        runs.push(next);
+        at = next;
+    }
+    runs
+}
+
+/// Whether any run in `a` is within [`CONFLICT_WINDOW_SECS`] of one in `b`.
+fn runs_collide(a: &[u64], b: &[u64]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i].abs_diff(b[j]) < CONFLICT_WINDOW_SECS {
+            return true;
+        }
+        if a[i] < b[j] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::scheduler::tasks::Weekday;
+
+    const QUIET: QuietHours = QuietHours { start: 23, end: 7 };
+
+    fn exclusive(id: &str, schedule: Schedule) -> ScheduledTask {
+        let mut task = ScheduledTask::user_task(id, id, schedule);
+        task.exclusive = true;
+        task
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight() {
+        assert!(QUIET.contains(23));
+        assert!(QUIET.contains(3));
+        assert!(!QUIET.contains(7));
+        assert!(!QuietHours { start: 0, end: 0 }.contains(0));
+    }
+
+    #[test]
+    fn fixed_time_runs_in_quiet_hours_are_reported() {
+        let now = crate::time_util::now_epoch_secs();
+        let night = ScheduledTask::user_task("n", "Night", Schedule::Daily { hour: 2, min: 0 });
+        assert_eq!(
+            detect_conflicts(&night, &[], Some(QUIET), now),
+            vec![ScheduleConflict::QuietHours { start: 23, end: 7 }]
+        );
+
+        let morning = ScheduledTask::user_task("m", "Morning", Schedule::Daily { hour: 9, min: 0 });
+        assert!(detect_conflicts(&morning, &[], Some(QUIET), now).is_empty());
+
+        let hourly = ScheduledTask::user_task("h", "Hourly", Schedule::Interval { secs: 3600 });
+        assert!(detect_conflicts(&hourly, &[], Some(QUIET), now).is_empty());
+    }
+
+    #[test]
+    fn exclusive_tasks_at_the_same_time_collide() {
+        let now = crate::time_util::now_epoch_secs();
+        let backup = exclusive("backup", Schedule::Daily { hour: 12, min: 0 });
+        let report = exclusive(
+            "report",
+            Schedule::Weekly {
+                weekdays: vec![Weekday::Mon],
+                hour: 12,
+                min: 5,
+            },
+        );
+        let later = exclusive("later", Schedule::Daily { hour: 15, min: 0 });
+        let mut shared = ScheduledTask::user_task("shared", "Shared", backup.schedule.clone());
+        shared.exclusive = false;
+
+        let conflicts = detect_conflicts(&report, &[backup, later, shared], None, now);
+        assert_eq!(
+            conflicts,
+            vec![ScheduleConflict::Exclusive {
+                task_id: "backup".to_owned(),
+                task_name: "backup".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn run_collision_uses_the_window() {
+        assert!(runs_collide(&[1_000, 5_000], &[5_100]));
+        assert!(!runs_collide(&[1_000], &[1_000 + CONFLICT_WINDOW_SECS]));
+        assert!(!runs_collide(&[], &[1_000]));
+    }
+}
@@ -4,7 +4,9 @@
 //! callback to connect scheduled tasks to the conversation pipeline.
 
 use crate::pipeline::messages::ConversationRequest;
-use crate::scheduler::tasks::{ConversationTrigger, ScheduledTask, TaskResult};
+use crate::scheduler::tasks::{
+    ConversationTrigger, OutputChannel, PromptAction, ScheduledTask, TaskResult, UserPrompt,
+};
 use std::time::Duration;
 use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, warn};
@@ -57,6 +59,8 @@ impl TaskExecutorBridge {
                 prompt: trigger.prompt.clone(),
                 system_addon: trigger.system_addon.clone(),
                 timeout_secs: trigger.timeout_secs,
+                latency_insensitive: trigger.latency_insensitive,
+                allowed_tools: trigger.allowed_tools.clone(),
                 response_tx,
             };
 
@@ -183,7 +187,17 @@ impl TaskExecutorBridge {
             match response {
                 crate::pipeline::messages::ConversationResponse::Success(text) => {
                     debug!("Task {} completed successfully: {}", task.id, text);
-                    TaskResult::Success(text)
+                    match trigger.output {
+                        OutputChannel::History => TaskResult::Success(text),
+                        OutputChannel::Notify => TaskResult::NeedsUserAction(UserPrompt {
+                            title: task.name.clone(),
+                            message: text,
+                            actions: vec![PromptAction {
+                                label: "Acknowledge".to_owned(),
+                                id: "acknowledge_scheduler_prompt".to_owned(),
+                            }],
+                        }),
+                    }
                 }
                 crate::pipeline::messages::ConversationResponse::Error(err) => {
                     warn!("Task {} failed: {}", task.id, err);
@@ -193,6 +207,14 @@ impl TaskExecutorBridge {
                     warn!("Task {} timed out", task.id);
                     TaskResult::Error("Conversation timed out".to_owned())
                 }
+                crate::pipeline::messages::ConversationResponse::Preempted => {
+                    debug!("Task {} preempted by an interactive turn", task.id);
+                    TaskResult::Preempted("Paused for conversation; will retry".to_owned())
+                }
+                crate::pipeline::messages::ConversationResponse::Deferred(status) => {
+                    debug!("Task {} waiting on provider batch: {}", task.id, status);
+                    TaskResult::Preempted(status)
+                }
             }
         })
     }
@@ -280,6 +302,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn executor_passes_tools_and_notifies_result() {
+        let rt = tokio::runtime::Runtime::new().expect("create runtime");
+
+        rt.block_on(async {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let executor = TaskExecutorBridge::new(tx).into_executor();
+
+            let trigger = ConversationTrigger::new("Fetch the page")
+                .with_allowed_tools(vec!["fetch_url".to_owned()])
+                .with_output(OutputChannel::Notify);
+            let mut task =
+                ScheduledTask::new("watch", "Watch page", Schedule::Interval { secs: 60 });
+            task.payload = Some(trigger.to_json().expect("to_json"));
+
+            tokio::spawn(async move {
+                if let Some(request) = rx.recv().await {
+                    assert_eq!(request.allowed_tools, vec!["fetch_url".to_owned()]);
+                    let _ = request.response_tx.send(
+                        crate::pipeline::messages::ConversationResponse::Success(
+                            "Nothing new".to_owned(),
+                        ),
+                    );
+                }
+            });
+
+            let result = tokio::task::spawn_blocking(move || executor(&task))
+                .await
+                .expect("spawn_blocking failed");
+            match result {
+                TaskResult::NeedsUserAction(prompt) => {
+                    assert_eq!(prompt.title, "Watch page");
+                    assert_eq!(prompt.message, "Nothing new");
+                }
+                other => panic!("Expected NeedsUserAction, got: {other:?}"),
+            }
+        });
+    }
+
     #[test]
     fn executor_handles_missing_payload() {
         let (tx, mut rx) = mpsc::unbounded_channel();
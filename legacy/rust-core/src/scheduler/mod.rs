@@ -5,10 +5,15 @@
 //! (calendar checks, research, reminders).
 
 pub mod authority;
+pub mod batch;
+pub mod conflicts;
 pub mod executor_bridge;
+pub mod priority;
 pub mod runner;
 pub mod tasks;
+pub mod templates;
 
+pub use conflicts::{QuietHours, ScheduleConflict, detect_conflicts};
 pub use executor_bridge::TaskExecutorBridge;
 pub use runner::{
     Scheduler, SchedulerSnapshot, clear_persisted_state, load_persisted_snapshot,
@@ -16,6 +21,7 @@ pub use runner::{
     set_persisted_task_enabled, upsert_persisted_user_task,
 };
 pub use tasks::{
-    ConversationTrigger, Schedule, ScheduledTask, TaskResult, TaskRunOutcome, TaskRunRecord,
-    Weekday,
+    ConversationTrigger, OutputChannel, Schedule, ScheduledTask, TaskResult, TaskRunOutcome,
+    TaskRunRecord, Weekday,
 };
+pub use templates::{JobTemplate, TemplateParam};
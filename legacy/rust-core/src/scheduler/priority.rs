@@ -0,0 +1,328 @@
+//! Priority between interactive turns and background jobs.
+//!
+//! Interactive pipeline work — the user speaking, Fae thinking or speaking —
+//! always wins. Background jobs (scheduled tasks and scheduled agent
+//! conversations) are admitted only when the machine has headroom for them,
+//! and a running job that shares resources with the conversation pauses
+//! while a turn is in progress and resumes when it ends. A job kept paused
+//! for longer than [`MAX_PAUSE`] is aborted and retried on a later tick.
+//! While the system sleeps ([`set_suspended`]) nothing is admitted and
+//! running jobs stay paused, however long the sleep lasts. On battery the
+//! scheduler runs jobs only on every Nth tick ([`set_background_stride`]).
+//!
+//! Admission limits come from the [`SystemProfile`]: how many background
+//! jobs may run at once, and whether CPU-bound jobs can keep running beside
+//! a turn (only when inference runs on a GPU and there are cores to spare).
+//! Current memory pressure is checked on every admission.
+
+use crate::memory_pressure::PressureLevel;
+use crate::scheduler::authority::now_epoch_millis;
+use crate::scheduler::tasks::{ScheduledTask, TaskKind};
+use crate::system_profile::SystemProfile;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// How long after the last interactive activity the pipeline still counts
+/// as busy, so a job does not resume between the user's sentence and Fae's
+/// reply.
+const INTERACTIVE_GRACE_MS: u64 = 3_000;
+
+/// Longest a running background job stays paused before it is aborted.
+pub const MAX_PAUSE: Duration = Duration::from_secs(20);
+
+/// How often a running job checks whether it should pause or resume.
+const PREEMPT_POLL: Duration = Duration::from_millis(250);
+
+static INTERACTIVE_TURNS: AtomicUsize = AtomicUsize::new(0);
+static LAST_INTERACTIVE_MS: AtomicU64 = AtomicU64::new(0);
+static RUNNING_JOBS: AtomicUsize = AtomicUsize::new(0);
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+static BACKGROUND_STRIDE: AtomicU32 = AtomicU32::new(1);
+static POLICY: OnceLock<AdmissionPolicy> = OnceLock::new();
+
+/// What a background job competes with the conversation for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobClass {
+    /// CPU-bound work such as memory maintenance.
+    Compute,
+    /// Work that runs the shared local model (scheduled agent conversations).
+    Model,
+}
+
+impl JobClass {
+    /// The class of a scheduled task: user tasks run the agent, built-ins
+    /// are maintenance.
+    pub fn for_task(task: &ScheduledTask) -> Self {
+        match task.kind {
+            TaskKind::User => Self::Model,
+            TaskKind::Builtin => Self::Compute,
+        }
+    }
+}
+
+/// Machine-dependent limits for background work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdmissionPolicy {
+    /// Background jobs allowed to run at once.
+    pub max_concurrent_jobs: usize,
+    /// Whether compute jobs may keep running during an interactive turn.
+    pub compute_alongside_turns: bool,
+}
+
+impl AdmissionPolicy {
+    /// Derive limits from the machine profile and its core count.
+    pub fn from_profile(profile: &SystemProfile, cores: usize) -> Self {
+        let ram_gib = profile.total_memory_bytes.unwrap_or(0) / (1024 * 1024 * 1024);
+        // On Apple Silicon the model runs on the integrated GPU.
+        let gpu_inference =
+            profile.gpu.is_some() || (profile.os == "macos" && profile.arch == "aarch64");
+        Self {
+            max_concurrent_jobs: if ram_gib >= 32 && cores >= 8 { 2 } else { 1 },
+            compute_alongside_turns: gpu_inference && cores >= 8,
+        }
+    }
+
+    /// Decide whether a new job of `class` may start.
+    pub fn admit(
+        &self,
+        class: JobClass,
+        interactive: bool,
+        running_jobs: usize,
+        pressure: PressureLevel,
+    ) -> Admission {
+        if running_jobs >= self.max_concurrent_jobs {
+            return Admission::Defer("background job limit reached");
+        }
+        match (class, pressure) {
+            (_, PressureLevel::Critical) | (JobClass::Model, PressureLevel::Warning) => {
+                return Admission::Defer("memory pressure");
+            }
+            _ => {}
+        }
+        if interactive && self.must_yield(class) {
+            return Admission::Defer("conversation in progress");
+        }
+        Admission::Admit
+    }
+
+    /// Whether a job of `class` has to give way to an interactive turn.
+    pub fn must_yield(&self, class: JobClass) -> bool {
+        match class {
+            JobClass::Model => true,
+            JobClass::Compute => !self.compute_alongside_turns,
+        }
+    }
+}
+
+/// Outcome of an admission check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    Admit,
+    /// Not now; the reason is for logs.
+    Defer(&'static str),
+}
+
+/// The process-wide policy, derived from the system profile on first use.
+pub fn policy() -> &'static AdmissionPolicy {
+    POLICY.get_or_init(|| {
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let mut profile = SystemProfile::detect();
+        profile.detect_gpu_slow();
+        let policy = AdmissionPolicy::from_profile(&profile, cores);
+        info!(?policy, cores, "background job admission policy");
+        policy
+    })
+}
+
+/// Marks an interactive turn in progress until dropped.
+#[must_use = "the turn ends when the guard is dropped"]
+pub struct InteractiveTurn(());
+
+impl Drop for InteractiveTurn {
+    fn drop(&mut self) {
+        touch_interactive();
+        INTERACTIVE_TURNS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Start an interactive turn; background jobs yield until it is dropped
+/// (plus a short grace period).
+pub fn begin_interactive() -> InteractiveTurn {
+    INTERACTIVE_TURNS.fetch_add(1, Ordering::SeqCst);
+    touch_interactive();
+    InteractiveTurn(())
+}
+
+/// Record momentary interactive activity (speech heard or played).
+///
+/// Lock-free, so it is safe to call from audio paths.
+pub fn touch_interactive() {
+    LAST_INTERACTIVE_MS.store(now_epoch_millis(), Ordering::Relaxed);
+}
+
+/// Whether the conversation is currently busy.
+pub fn interactive_active() -> bool {
+    INTERACTIVE_TURNS.load(Ordering::SeqCst) > 0
+        || now_epoch_millis().saturating_sub(LAST_INTERACTIVE_MS.load(Ordering::Relaxed))
+            < INTERACTIVE_GRACE_MS
+}
+
+/// Hold all background work while the system sleeps, or release it on wake.
+pub fn set_suspended(suspended: bool) {
+    SUSPENDED.store(suspended, Ordering::SeqCst);
+}
+
+/// Whether background work is held for system sleep.
+pub fn suspended() -> bool {
+    SUSPENDED.load(Ordering::SeqCst)
+}
+
+/// Run background jobs only on every `stride`th scheduler tick (1 = every
+/// tick). Used by the low-power profile.
+pub fn set_background_stride(stride: u32) {
+    BACKGROUND_STRIDE.store(stride.max(1), Ordering::Relaxed);
+}
+
+/// How many scheduler ticks pass between background job runs.
+pub fn background_stride() -> u32 {
+    BACKGROUND_STRIDE.load(Ordering::Relaxed)
+}
+
+/// A running background job; releases its slot when dropped.
+pub struct BackgroundSlot(());
+
+impl Drop for BackgroundSlot {
+    fn drop(&mut self) {
+        RUNNING_JOBS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Admit a background job of `class` now, or return why it must wait.
+pub fn try_admit(class: JobClass) -> std::result::Result<BackgroundSlot, &'static str> {
+    if suspended() {
+        return Err("system sleeping");
+    }
+    match policy().admit(
+        class,
+        interactive_active(),
+        RUNNING_JOBS.load(Ordering::SeqCst),
+        current_pressure(),
+    ) {
+        Admission::Admit => {
+            RUNNING_JOBS.fetch_add(1, Ordering::SeqCst);
+            Ok(BackgroundSlot(()))
+        }
+        Admission::Defer(reason) => Err(reason),
+    }
+}
+
+fn current_pressure() -> PressureLevel {
+    match crate::memory_pressure::available_memory_mb() {
+        // 0 means the platform could not report it.
+        0 => PressureLevel::Normal,
+        mb => PressureLevel::from_available_mb(mb),
+    }
+}
+
+/// Drive `job` while pausing it whenever an interactive turn needs the
+/// resources it shares.
+///
+/// Pausing stops polling the future, so it makes no progress until the turn
+/// ends. Returns `None` if the job stayed paused for [`MAX_PAUSE`] and was
+/// aborted (dropped). A pause for system sleep never aborts the job.
+pub async fn run_preemptible<F: Future>(class: JobClass, job: F) -> Option<F::Output> {
+    let mut job = std::pin::pin!(job);
+    let mut paused_at: Option<Instant> = None;
+    loop {
+        if suspended() {
+            tokio::time::sleep(PREEMPT_POLL).await;
+            continue;
+        }
+        if interactive_active() && policy().must_yield(class) {
+            let since = *paused_at.get_or_insert_with(|| {
+                debug!(?class, "background job paused for interactive turn");
+                Instant::now()
+            });
+            if since.elapsed() >= MAX_PAUSE {
+                warn!(?class, "background job aborted after pausing too long");
+                return None;
+            }
+            tokio::time::sleep(PREEMPT_POLL).await;
+            continue;
+        }
+        if let Some(since) = paused_at.take() {
+            debug!(
+                ?class,
+                paused_ms = since.elapsed().as_millis() as u64,
+                "background job resumed"
+            );
+        }
+        tokio::select! {
+            out = &mut job => return Some(out),
+            () = tokio::time::sleep(PREEMPT_POLL) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(os: &str, arch: &str, ram_gib: u64, gpu: Option<&str>) -> SystemProfile {
+        SystemProfile {
+            os: os.to_owned(),
+            arch: arch.to_owned(),
+            total_memory_bytes: Some(ram_gib * 1024 * 1024 * 1024),
+            cpu: None,
+            gpu: gpu.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn policy_follows_the_machine() {
+        let big_mac = AdmissionPolicy::from_profile(&profile("macos", "aarch64", 64, None), 12);
+        assert_eq!(big_mac.max_concurrent_jobs, 2);
+        assert!(big_mac.compute_alongside_turns);
+
+        // CPU inference uses every core; nothing runs beside a turn.
+        let cpu_box = AdmissionPolicy::from_profile(&profile("linux", "x86_64", 16, None), 16);
+        assert_eq!(cpu_box.max_concurrent_jobs, 1);
+        assert!(!cpu_box.compute_alongside_turns);
+    }
+
+    #[test]
+    fn interactive_turns_and_pressure_defer_jobs() {
+        let policy = AdmissionPolicy {
+            max_concurrent_jobs: 1,
+            compute_alongside_turns: true,
+        };
+        let normal = PressureLevel::Normal;
+        assert_eq!(
+            policy.admit(JobClass::Model, false, 0, normal),
+            Admission::Admit
+        );
+        assert!(matches!(
+            policy.admit(JobClass::Model, true, 0, normal),
+            Admission::Defer(_)
+        ));
+        assert_eq!(
+            policy.admit(JobClass::Compute, true, 0, normal),
+            Admission::Admit
+        );
+        assert!(matches!(
+            policy.admit(JobClass::Compute, false, 1, normal),
+            Admission::Defer(_)
+        ));
+        assert!(matches!(
+            policy.admit(JobClass::Model, false, 0, PressureLevel::Warning),
+            Admission::Defer(_)
+        ));
+        assert_eq!(
+            policy.admit(JobClass::Compute, false, 0, PressureLevel::Warning),
+            Admission::Admit
+        );
+    }
+}
@@ -2,16 +2,25 @@
 //!
 //! Spawns a tokio task that periodically checks for due tasks and
 //! executes them. Task definitions and run history are persisted to
-//! `~/.config/fae/scheduler.json`.
+//! `~/.config/fae/scheduler.json`; each recorded run also gets a log file
+//! with its full result under [`crate::fae_dirs::scheduler_runs_dir`].
+//!
+//! A task is never started while its previous run is still going (an
+//! executor can outlive [`TASK_EXECUTION_TIMEOUT_SECS`]), and an exclusive
+//! task never starts while another exclusive task runs; both wait for a
+//! later tick instead.
 
 use crate::scheduler::authority::{
     LeaderLease, LeadershipDecision, RunKeyLedger, now_epoch_millis,
 };
+use crate::scheduler::priority::{self, JobClass};
 use crate::scheduler::tasks::{
     Schedule, ScheduledTask, TaskKind, TaskResult, TaskRunOutcome, TaskRunRecord,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
@@ -59,6 +68,10 @@ pub struct Scheduler {
     leader_lease: Option<LeaderLease>,
     /// Optional run-key dedupe ledger shared across scheduler instances.
     run_key_ledger: Option<RunKeyLedger>,
+    /// Directory for per-run log files; none are written without persisted state.
+    run_log_dir: Option<PathBuf>,
+    /// Tasks whose executor is still running, mapped to their exclusivity.
+    running: Arc<Mutex<HashMap<String, bool>>>,
 }
 
 /// Persisted scheduler state.
@@ -92,9 +105,17 @@ impl Scheduler {
             max_history_entries: DEFAULT_HISTORY_LIMIT,
             leader_lease: None,
             run_key_ledger: None,
+            run_log_dir: Some(crate::fae_dirs::scheduler_runs_dir()),
+            running: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Write per-run log files to `dir`.
+    pub fn with_run_log_dir(mut self, dir: PathBuf) -> Self {
+        self.run_log_dir = Some(dir);
+        self
+    }
+
     /// Enable single-leader scheduling via a lease controller.
     pub fn with_leader_lease(mut self, lease: LeaderLease) -> Self {
         self.leader_lease = Some(lease);
@@ -233,6 +254,52 @@ impl Scheduler {
         self.add_task_if_missing(task);
     }
 
+    /// Register daily privacy maintenance (session retention, sealing).
+    pub fn with_privacy_maintenance(&mut self) {
+        use crate::scheduler::tasks::TASK_PRIVACY_MAINTENANCE;
+
+        let mut task = ScheduledTask::new(
+            TASK_PRIVACY_MAINTENANCE,
+            "Privacy retention and encryption",
+            Schedule::Daily { hour: 4, min: 0 },
+        );
+        task.kind = TaskKind::Builtin;
+        self.add_task_if_missing(task);
+    }
+
+    /// Register background sync every `interval_mins` minutes (0 = none).
+    pub fn with_settings_sync(&mut self, interval_mins: u32) {
+        use crate::scheduler::tasks::TASK_SETTINGS_SYNC;
+
+        if interval_mins == 0 {
+            return;
+        }
+        let mut task = ScheduledTask::new(
+            TASK_SETTINGS_SYNC,
+            "Sync with your other devices",
+            Schedule::Interval {
+                secs: u64::from(interval_mins) * 60,
+            },
+        );
+        task.kind = TaskKind::Builtin;
+        self.add_task_if_missing(task);
+    }
+
+    /// Register the monthly permission review.
+    pub fn with_permission_review(&mut self) {
+        use crate::scheduler::tasks::TASK_PERMISSION_REVIEW;
+
+        let mut task = ScheduledTask::new(
+            TASK_PERMISSION_REVIEW,
+            "Review granted permissions",
+            Schedule::Interval {
+                secs: 30 * 24 * 3600,
+            },
+        );
+        task.kind = TaskKind::Builtin;
+        self.add_task_if_missing(task);
+    }
+
     /// Add (or replace) a task.
     pub fn add_task(&mut self, task: ScheduledTask) {
         if let Some(existing) = self.tasks.iter_mut().find(|t| t.id == task.id) {
@@ -329,9 +396,15 @@ impl Scheduler {
             info!("scheduler started with {} tasks", self.tasks.len());
             let mut interval =
                 tokio::time::interval(std::time::Duration::from_secs(TICK_INTERVAL_SECS));
+            let mut ticks: u64 = 0;
 
             loop {
                 interval.tick().await;
+                ticks += 1;
+                // The low-power profile spreads background work out.
+                if ticks % u64::from(priority::background_stride()) != 0 {
+                    continue;
+                }
                 if !self.should_execute_tick() {
                     continue;
                 }
@@ -365,12 +438,27 @@ impl Scheduler {
                 }
             });
 
+            if let Some(reason) = self.overlap_reason(&task_snapshot) {
+                debug!("deferring scheduled task {}: {reason}", task_snapshot.id);
+                continue;
+            }
+
+            // Interactive turns come first; a deferred task stays due.
+            let slot = match priority::try_admit(JobClass::for_task(&task_snapshot)) {
+                Ok(slot) => slot,
+                Err(reason) => {
+                    debug!("deferring scheduled task {}: {reason}", task_snapshot.id);
+                    continue;
+                }
+            };
+
             let run_key = build_run_key(&task_snapshot.id, planned_at);
             if self.should_skip_duplicate_run(&run_key, &task_snapshot.id, started_at) {
                 continue;
             }
 
             let mut result = self.execute_task(&task_snapshot);
+            drop(slot);
             let finished_at = crate::time_util::now_epoch_secs();
 
             let elapsed_secs = finished_at.saturating_sub(started_at);
@@ -388,16 +476,20 @@ impl Scheduler {
             if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
                 match &result {
                     TaskResult::Error(err) => task.mark_run_failure(err),
+                    // Not the task's fault: retry on the next tick.
+                    TaskResult::Preempted(_) => task.mark_due_now(),
                     _ => task.mark_run_success(),
                 }
             }
 
+            let log_path = self.write_run_log(&task_snapshot, started_at, finished_at, &result);
             self.push_history(TaskRunRecord {
                 task_id: task_snapshot.id.clone(),
                 started_at,
                 finished_at,
                 outcome,
                 summary: result.summary(),
+                log_path,
             });
 
             if self.result_tx.send(result).is_err() {
@@ -463,6 +555,7 @@ impl Scheduler {
                     finished_at: started_at,
                     outcome: TaskRunOutcome::Success,
                     summary: format!("duplicate run suppressed ({run_key})"),
+                    log_path: None,
                 });
                 true
             }
@@ -478,7 +571,68 @@ impl Scheduler {
             return;
         }
         let drop_count = self.history.len().saturating_sub(self.max_history_entries);
-        self.history.drain(0..drop_count);
+        for run in self.history.drain(0..drop_count) {
+            if let Some(path) = run.log_path {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    /// Why `task` cannot start now, if a run it must not overlap is going.
+    fn overlap_reason(&self, task: &ScheduledTask) -> Option<String> {
+        let running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        if running.contains_key(&task.id) {
+            return Some("previous run still in progress".to_owned());
+        }
+        if task.exclusive
+            && let Some((other, _)) = running.iter().find(|(_, exclusive)| **exclusive)
+        {
+            return Some(format!("exclusive task {other} is running"));
+        }
+        None
+    }
+
+    /// Write the full result of one run to its log file.
+    ///
+    /// Returns the file path, or `None` when run history is not persisted or
+    /// the file could not be written.
+    fn write_run_log(
+        &self,
+        task: &ScheduledTask,
+        started_at: u64,
+        finished_at: u64,
+        result: &TaskResult,
+    ) -> Option<String> {
+        self.state_path.as_ref()?;
+        let dir = self.run_log_dir.as_ref()?;
+        let path = dir.join(format!("{}-{started_at}.log", task.id));
+        let detail = match result {
+            TaskResult::Success(msg) | TaskResult::Error(msg) | TaskResult::Preempted(msg) => {
+                msg.clone()
+            }
+            TaskResult::Telemetry(telemetry) => format!(
+                "{}\n{}",
+                telemetry.message,
+                serde_json::to_string_pretty(&telemetry.event).unwrap_or_default()
+            ),
+            TaskResult::NeedsUserAction(prompt) => {
+                format!("{}\n{}", prompt.title, prompt.message)
+            }
+        };
+        let text = format!(
+            "task: {} ({})\nstarted_at: {started_at}\nfinished_at: {finished_at}\noutcome: {:?}\n\n{detail}\n",
+            task.id,
+            task.name,
+            result.outcome(),
+        );
+        let written = std::fs::create_dir_all(dir).and_then(|()| std::fs::write(&path, text));
+        match written {
+            Ok(()) => Some(path.display().to_string()),
+            Err(e) => {
+                warn!("cannot write scheduler run log {}: {e}", path.display());
+                None
+            }
+        }
     }
 
     /// Execute a single task.
@@ -490,12 +644,33 @@ impl Scheduler {
             let task = task.clone();
             let (tx, rx) = std::sync::mpsc::channel::<TaskResult>();
 
-            let _ = std::thread::Builder::new()
+            // The entry outlives a timeout and is removed when the executor returns.
+            let running = Arc::clone(&self.running);
+            running
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(task.id.clone(), task.exclusive);
+            let task_id = task.id.clone();
+            let spawned = std::thread::Builder::new()
                 .name(format!("scheduler-task-{}", task.id))
-                .spawn(move || {
-                    let result = executor(&task);
-                    let _ = tx.send(result);
+                .spawn({
+                    let running = Arc::clone(&running);
+                    move || {
+                        let result = executor(&task);
+                        running
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .remove(&task.id);
+                        let _ = tx.send(result);
+                    }
                 });
+            if let Err(e) = spawned {
+                running
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(&task_id);
+                return TaskResult::Error(format!("cannot start scheduler task thread: {e}"));
+            }
 
             match rx.recv_timeout(std::time::Duration::from_secs(TASK_EXECUTION_TIMEOUT_SECS)) {
                 Ok(result) => result,
@@ -719,6 +894,10 @@ mod tests {
         scheduler.with_update_checks();
         scheduler.with_memory_maintenance();
         scheduler.with_memory_maintenance();
+        scheduler.with_privacy_maintenance();
+        scheduler.with_privacy_maintenance();
+        scheduler.with_permission_review();
+        scheduler.with_permission_review();
 
         let ids: Vec<&str> = scheduler.tasks().iter().map(|t| t.id.as_str()).collect();
         assert_eq!(
@@ -729,6 +908,16 @@ mod tests {
         assert_eq!(ids.iter().filter(|id| **id == "memory_reflect").count(), 1);
         assert_eq!(ids.iter().filter(|id| **id == "memory_reindex").count(), 1);
         assert_eq!(ids.iter().filter(|id| **id == "memory_gc").count(), 1);
+        assert_eq!(
+            ids.iter()
+                .filter(|id| **id == "privacy_maintenance")
+                .count(),
+            1
+        );
+        assert_eq!(
+            ids.iter().filter(|id| **id == "permission_review").count(),
+            1
+        );
     }
 
     #[test]
@@ -799,6 +988,58 @@ mod tests {
         assert_eq!(scheduler.history().len(), 2);
     }
 
+    #[test]
+    fn persisted_runs_get_log_files_pruned_with_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut scheduler = Scheduler::new(tx)
+            .with_history_limit(1)
+            .with_run_log_dir(dir.path().join("runs"));
+        scheduler.state_path = Some(dir.path().join("scheduler.json"));
+        scheduler.executor = Some(Arc::new(|_| TaskResult::Error("disk full".to_owned())));
+        scheduler.add_task(ScheduledTask::new("a", "A", Schedule::Interval { secs: 0 }));
+
+        scheduler.tick();
+        let first = scheduler.history()[0].log_path.clone().expect("log path");
+        let log = std::fs::read_to_string(&first).unwrap();
+        assert!(log.contains("outcome: Error"));
+        assert!(log.contains("disk full"));
+
+        // A run one second later gets its own file and evicts the first.
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        scheduler.mark_task_due_now("a");
+        scheduler.tick();
+        let second = scheduler.history()[0].log_path.clone().expect("log path");
+        assert_ne!(first, second);
+        assert!(!std::path::Path::new(&first).exists());
+        assert!(std::path::Path::new(&second).exists());
+    }
+
+    #[test]
+    fn running_tasks_are_not_overlapped() {
+        let (mut scheduler, mut rx) = make_scheduler();
+        scheduler.executor = Some(Arc::new(|task| TaskResult::Success(task.id.clone())));
+        scheduler.add_task(ScheduledTask::new("a", "A", Schedule::Interval { secs: 0 }));
+        let mut exclusive = ScheduledTask::new("b", "B", Schedule::Interval { secs: 0 });
+        exclusive.exclusive = true;
+        scheduler.add_task(exclusive);
+
+        // "a" is still running from an earlier tick, and it is exclusive.
+        scheduler
+            .running
+            .lock()
+            .unwrap()
+            .insert("a".to_owned(), true);
+        scheduler.tick();
+        assert!(rx.try_recv().is_err());
+        assert!(scheduler.history().is_empty());
+
+        scheduler.running.lock().unwrap().clear();
+        scheduler.tick();
+        assert_eq!(scheduler.history().len(), 2);
+        assert!(scheduler.running.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn persisted_snapshot_round_trip() {
         let dir = std::env::temp_dir().join("fae-scheduler-v2-roundtrip");
@@ -817,6 +1058,7 @@ mod tests {
                 finished_at: 2,
                 outcome: TaskRunOutcome::Success,
                 summary: "ok".to_owned(),
+                log_path: None,
             }],
         };
 
@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// How often a task should run.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Schedule {
     /// Run every N seconds.
@@ -121,7 +121,7 @@ impl Schedule {
         }
     }
 
-    fn next_after_epoch(&self, after_epoch: u64) -> u64 {
+    pub(crate) fn next_after_epoch(&self, after_epoch: u64) -> u64 {
         let after = epoch_to_local(after_epoch);
         let fallback = after + Duration::hours(24);
         match self {
@@ -163,6 +163,9 @@ pub enum TaskResult {
     NeedsUserAction(UserPrompt),
     /// Task failed with an error message.
     Error(String),
+    /// Task gave way to an interactive turn, or is waiting on a provider
+    /// batch, and will be retried.
+    Preempted(String),
 }
 
 impl TaskResult {
@@ -179,7 +182,7 @@ impl TaskResult {
             Self::Success(msg) => msg.clone(),
             Self::Telemetry(payload) => payload.message.clone(),
             Self::NeedsUserAction(prompt) => prompt.title.clone(),
-            Self::Error(msg) => msg.clone(),
+            Self::Error(msg) | Self::Preempted(msg) => msg.clone(),
         }
     }
 
@@ -191,6 +194,7 @@ impl TaskResult {
             Self::Telemetry(_) => TaskRunOutcome::Telemetry,
             Self::NeedsUserAction(_) => TaskRunOutcome::NeedsUserAction,
             Self::Error(_) => TaskRunOutcome::Error,
+            Self::Preempted(_) => TaskRunOutcome::Preempted,
         }
     }
 }
@@ -248,6 +252,9 @@ pub struct TaskRunRecord {
     pub outcome: TaskRunOutcome,
     /// Human-readable summary.
     pub summary: String,
+    /// Per-run log with the full result, when run history is persisted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_path: Option<String>,
 }
 
 /// Normalized run outcome for history and doctor checks.
@@ -259,6 +266,7 @@ pub enum TaskRunOutcome {
     NeedsUserAction,
     Error,
     SoftTimeout,
+    Preempted,
 }
 
 /// A task that runs on a schedule.
@@ -303,6 +311,9 @@ pub struct ScheduledTask {
     /// Last error message, if any.
     #[serde(default)]
     pub last_error: Option<String>,
+    /// Never run at the same time as another exclusive task.
+    #[serde(default)]
+    pub exclusive: bool,
 }
 
 fn default_enabled() -> bool {
@@ -343,6 +354,7 @@ impl ScheduledTask {
             max_failure_streak_before_pause: default_max_failure_streak_before_pause(),
             soft_timeout_secs: default_soft_timeout_secs(),
             last_error: None,
+            exclusive: false,
         }
     }
 
@@ -420,7 +432,7 @@ fn exponential_backoff(base_secs: u64, streak: u32) -> u64 {
     delay
 }
 
-fn epoch_to_local(ts: u64) -> chrono::DateTime<Local> {
+pub(crate) fn epoch_to_local(ts: u64) -> chrono::DateTime<Local> {
     let secs = match i64::try_from(ts) {
         Ok(value) => value,
         Err(_) => return Local::now(),
@@ -522,6 +534,35 @@ pub struct ConversationTrigger {
     /// Optional conversation timeout in seconds.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timeout_secs: Option<u64>,
+    /// Whether the result may take hours to arrive.
+    ///
+    /// Such jobs go through the default remote provider's batch API when it
+    /// has one (cheaper, no tools) and otherwise run as normal.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub latency_insensitive: bool,
+    /// Tools the agent may use; empty means chosen from the prompt.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    /// Where the result goes once the conversation completes.
+    #[serde(default, skip_serializing_if = "OutputChannel::is_default")]
+    pub output: OutputChannel,
+}
+
+/// Where a scheduled conversation's result is delivered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputChannel {
+    /// Kept in the run history only.
+    #[default]
+    History,
+    /// Shown to the user as a notification they acknowledge.
+    Notify,
+}
+
+impl OutputChannel {
+    fn is_default(&self) -> bool {
+        *self == Self::History
+    }
 }
 
 impl ConversationTrigger {
@@ -531,6 +572,9 @@ impl ConversationTrigger {
             prompt: prompt.into(),
             system_addon: None,
             timeout_secs: None,
+            latency_insensitive: false,
+            allowed_tools: Vec::new(),
+            output: OutputChannel::History,
         }
     }
 
@@ -546,6 +590,24 @@ impl ConversationTrigger {
         self
     }
 
+    /// Allow this conversation to run through a provider batch API.
+    pub fn latency_insensitive(mut self) -> Self {
+        self.latency_insensitive = true;
+        self
+    }
+
+    /// Restrict the agent to these tools.
+    pub fn with_allowed_tools(mut self, tools: Vec<String>) -> Self {
+        self.allowed_tools = tools;
+        self
+    }
+
+    /// Deliver the result through `output`.
+    pub fn with_output(mut self, output: OutputChannel) -> Self {
+        self.output = output;
+        self
+    }
+
     /// Parse a conversation trigger from a task payload.
     ///
     /// Returns `Ok(trigger)` if the payload is a valid ConversationTrigger.
@@ -603,6 +665,12 @@ pub const TASK_MORNING_BRIEFING: &str = "morning_briefing";
 pub const TASK_SKILL_PROPOSALS: &str = "skill_proposals";
 /// Well-known task ID for periodic Python skill health checks.
 pub const TASK_SKILL_HEALTH_CHECK: &str = "skill_health_check";
+/// Well-known task ID for session retention and encryption-at-rest upkeep.
+pub const TASK_PRIVACY_MAINTENANCE: &str = "privacy_maintenance";
+/// Well-known task ID for the monthly review of granted permissions.
+pub const TASK_PERMISSION_REVIEW: &str = "permission_review";
+/// Well-known task ID for background sync with the user's other devices.
+pub const TASK_SETTINGS_SYNC: &str = "settings_sync";
 
 /// Execute a built-in scheduled task by ID.
 ///
@@ -641,10 +709,94 @@ pub fn execute_builtin_with_memory_root(
         TASK_MORNING_BRIEFING => run_morning_briefing_check(memory_root),
         TASK_SKILL_PROPOSALS => run_skill_proposal_check(memory_root),
         TASK_SKILL_HEALTH_CHECK => run_skill_health_check(),
+        // Read the config when the task runs so retention and encryption
+        // changes made since startup apply.
+        TASK_PRIVACY_MAINTENANCE => match privacy_config(&crate::fae_dirs::config_file()) {
+            Ok(privacy) => run_privacy_maintenance(
+                &crate::fae_dirs::sessions_dir(),
+                &memory_root.join("backups"),
+                &privacy,
+            ),
+            Err(e) => {
+                tracing::warn!(error = %e, "config unreadable; skipping privacy maintenance");
+                TaskResult::Error(format!("privacy maintenance skipped: {e}"))
+            }
+        },
+        TASK_PERMISSION_REVIEW => crate::permission_usage::run_permission_review(
+            &crate::config::SpeechConfig::from_file(&crate::fae_dirs::config_file())
+                .unwrap_or_default()
+                .permissions,
+            &crate::fae_dirs::permission_usage_file(),
+        ),
+        TASK_SETTINGS_SYNC => run_settings_sync(memory_root),
         _ => TaskResult::Error(format!("unknown built-in task: {task_id}")),
     }
 }
 
+/// Privacy settings from the config file at `path`, or the defaults when
+/// there is none yet.
+///
+/// A file that exists but cannot be read or parsed is an error rather than
+/// the defaults, which would silently stop sealing and change retention.
+fn privacy_config(path: &Path) -> crate::error::Result<crate::config::PrivacyConfig> {
+    match crate::config::SpeechConfig::from_file(path) {
+        Ok(config) => Ok(config.privacy),
+        Err(crate::error::SpeechError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            Ok(crate::config::PrivacyConfig::default())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Enforce session retention and seal plaintext data when encryption at rest
+/// is enabled.
+pub fn run_privacy_maintenance(
+    sessions_dir: &Path,
+    backup_dir: &Path,
+    privacy: &crate::config::PrivacyConfig,
+) -> TaskResult {
+    let mut report = match crate::privacy::enforce_session_retention(
+        sessions_dir,
+        privacy,
+        std::time::SystemTime::now(),
+    ) {
+        Ok(report) => report,
+        Err(e) => return TaskResult::Error(format!("session retention failed: {e}")),
+    };
+
+    match crate::privacy::cipher_for(privacy) {
+        Ok(Some(cipher)) => {
+            report.sealed = crate::privacy::seal_plaintext_files(sessions_dir, backup_dir, &cipher);
+        }
+        Ok(None) => {}
+        Err(e) => return TaskResult::Error(format!("encryption at rest unavailable: {e}")),
+    }
+
+    TaskResult::Success(report.summary())
+}
+
+/// Sync skills, memories and preferences with the user's other devices.
+///
+/// The config itself is left to `sync.now`: the running app owns it, and a
+/// background write here would race its own saves.
+pub fn run_settings_sync(memory_root: &Path) -> TaskResult {
+    let mut config =
+        crate::config::SpeechConfig::from_file(&crate::fae_dirs::config_file()).unwrap_or_default();
+    if !config.sync.enabled {
+        return TaskResult::Success("sync is off".into());
+    }
+    let paths = crate::sync::SyncPaths::for_memory_root(memory_root);
+    match crate::sync::sync_now(&mut config, false, &paths) {
+        Ok(report) => TaskResult::Success(format!(
+            "synced: {} sent, {} received, {} conflicts",
+            report.pushed,
+            report.pulled,
+            report.conflicts.len()
+        )),
+        Err(e) => TaskResult::Error(format!("sync failed: {e}")),
+    }
+}
+
 /// Reset the daily noise budget.
 ///
 /// This is a lightweight task that logs the reset. The actual NoiseController
@@ -1445,6 +1597,46 @@ mod tests {
     #[test]
     fn task_id_constants() {
         assert_eq!(TASK_CHECK_FAE_UPDATE, "check_fae_update");
+        assert_eq!(TASK_PRIVACY_MAINTENANCE, "privacy_maintenance");
+        assert_eq!(TASK_PERMISSION_REVIEW, "permission_review");
+        assert_eq!(TASK_SETTINGS_SYNC, "settings_sync");
+    }
+
+    #[test]
+    fn privacy_config_refuses_to_default_an_unreadable_file() {
+        let root = temp_test_root("privacy", "config");
+        std::fs::create_dir_all(&root).unwrap();
+        let path = root.join("config.toml");
+
+        assert!(!privacy_config(&path).unwrap().encrypt_at_rest);
+
+        std::fs::write(&path, "[privacy]\nencrypt_at_rest = true\n").unwrap();
+        assert!(privacy_config(&path).unwrap().encrypt_at_rest);
+
+        std::fs::write(&path, "[privacy\nencrypt_at_rest = true\n").unwrap();
+        assert!(privacy_config(&path).is_err());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn privacy_maintenance_applies_retention_policy() {
+        let sessions = temp_test_root("privacy", "sessions");
+        std::fs::create_dir_all(&sessions).unwrap();
+        std::fs::write(sessions.join("s1.json"), vec![b'x'; 2 * 1024 * 1024]).unwrap();
+        let policy = crate::config::PrivacyConfig {
+            session_max_total_mb: 1,
+            ..crate::config::PrivacyConfig::default()
+        };
+
+        let result = run_privacy_maintenance(&sessions, &sessions.join("backups"), &policy);
+        match result {
+            TaskResult::Success(msg) => assert!(msg.contains("deleted 1"), "{msg}"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(!sessions.join("s1.json").exists());
+
+        let _ = std::fs::remove_dir_all(sessions);
     }
 
     // -----------------------------------------------------------------------
@@ -1457,6 +1649,18 @@ mod tests {
         assert_eq!(trigger.prompt, "What's the weather?");
         assert!(trigger.system_addon.is_none());
         assert!(trigger.timeout_secs.is_none());
+        assert!(!trigger.latency_insensitive);
+    }
+
+    #[test]
+    fn conversation_trigger_latency_insensitive_round_trips() {
+        let trigger = ConversationTrigger::new("Summarize the week").latency_insensitive();
+        let json = trigger.to_json().expect("serialize");
+        assert_eq!(json["latency_insensitive"], true);
+        let parsed = ConversationTrigger::from_task_payload(&Some(json)).expect("parse");
+        assert!(parsed.latency_insensitive);
+        let minimal = ConversationTrigger::new("Hi").to_json().expect("serialize");
+        assert!(minimal.get("latency_insensitive").is_none());
     }
 
     #[test]
@@ -0,0 +1,335 @@
+//! Reusable job templates.
+//!
+//! A template describes a scheduled conversation — prompt, allowed tools,
+//! output channel and a default schedule — with `{{name}}` placeholders
+//! that are filled in from parameters when a task is created from it.
+//! Built-in templates ship with Fae; user templates are read from
+//! [`crate::fae_dirs::scheduler_templates_file`] and replace built-ins with
+//! the same id.
+
+use crate::error::{Result, SpeechError};
+use crate::scheduler::tasks::{ConversationTrigger, OutputChannel, Schedule};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// A parameter accepted by a [`JobTemplate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateParam {
+    /// Placeholder name, used as `{{name}}`.
+    pub name: String,
+    /// What the value is for, shown to the LLM and the user.
+    #[serde(default)]
+    pub description: String,
+    /// Value used when none is given; without one the parameter is required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// A reusable scheduled-conversation definition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobTemplate {
+    /// Stable template identifier.
+    pub id: String,
+    /// Task name; may contain placeholders.
+    pub name: String,
+    /// One-line summary of what the job does.
+    #[serde(default)]
+    pub description: String,
+    /// Prompt sent to the agent; may contain placeholders.
+    pub prompt: String,
+    /// Optional system prompt addon; may contain placeholders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_addon: Option<String>,
+    /// Tools the agent may use; empty means chosen from the prompt.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    /// Where the result goes.
+    #[serde(default)]
+    pub output: OutputChannel,
+    /// Schedule used when the caller gives none; without one it is required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    /// Parameters the placeholders refer to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<TemplateParam>,
+}
+
+/// The parts of a scheduled task produced from a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateInstance {
+    /// Task name with placeholders filled in.
+    pub name: String,
+    /// The caller's schedule, or the template's.
+    pub schedule: Schedule,
+    /// Conversation payload for the task.
+    pub trigger: ConversationTrigger,
+}
+
+impl JobTemplate {
+    /// Fill in the template with `values` and pick its schedule.
+    ///
+    /// String and number values are accepted; parameters without a value
+    /// fall back to their default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpeechError::Scheduler`] when a placeholder has no value or
+    /// neither the caller nor the template provides a schedule.
+    pub fn instantiate(
+        &self,
+        values: &serde_json::Map<String, serde_json::Value>,
+        schedule: Option<Schedule>,
+    ) -> Result<TemplateInstance> {
+        let schedule = schedule.or_else(|| self.schedule.clone()).ok_or_else(|| {
+            SpeechError::Scheduler(format!("template '{}' needs a schedule", self.id))
+        })?;
+
+        let mut texts = vec![self.name.as_str(), self.prompt.as_str()];
+        texts.extend(self.system_addon.as_deref());
+        let mut resolved = Vec::new();
+        let mut missing = Vec::new();
+        for name in placeholders(&texts) {
+            let value = values
+                .get(&name)
+                .and_then(|v| match v {
+                    serde_json::Value::String(s) => Some(s.clone()),
+                    serde_json::Value::Number(n) => Some(n.to_string()),
+                    _ => None,
+                })
+                .or_else(|| {
+                    self.params
+                        .iter()
+                        .find(|p| p.name == name)
+                        .and_then(|p| p.default.clone())
+                });
+            match value {
+                Some(value) => resolved.push((name, value)),
+                None => missing.push(name),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(SpeechError::Scheduler(format!(
+                "template '{}' is missing parameters: {}",
+                self.id,
+                missing.join(", ")
+            )));
+        }
+
+        let fill = |text: &str| {
+            resolved.iter().fold(text.to_owned(), |acc, (name, value)| {
+                acc.replace(&format!("{{{{{name}}}}}"), value)
+            })
+        };
+        let mut trigger = ConversationTrigger::new(fill(&self.prompt))
+            .with_allowed_tools(self.allowed_tools.clone())
+            .with_output(self.output);
+        if let Some(addon) = &self.system_addon {
+            trigger = trigger.with_system_addon(fill(addon));
+        }
+        Ok(TemplateInstance {
+            name: fill(&self.name),
+            schedule,
+            trigger,
+        })
+    }
+}
+
+/// Placeholder names referenced as `{{name}}` in `texts`.
+fn placeholders(texts: &[&str]) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    for text in texts {
+        let mut rest = *text;
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            let name = rest[start + 2..start + 2 + len].trim();
+            if !name.is_empty() {
+                names.insert(name.to_owned());
+            }
+            rest = &rest[start + 2 + len + 2..];
+        }
+    }
+    names
+}
+
+/// Templates that ship with Fae.
+pub fn builtin_templates() -> Vec<JobTemplate> {
+    let param = |name: &str, description: &str, default: Option<&str>| TemplateParam {
+        name: name.to_owned(),
+        description: description.to_owned(),
+        default: default.map(str::to_owned),
+    };
+    vec![
+        JobTemplate {
+            id: "news_briefing".to_owned(),
+            name: "{{topic}} briefing".to_owned(),
+            description: "Summarise the latest news on a topic".to_owned(),
+            prompt: "Give me a short briefing on the most important {{topic}} news from the \
+                     last day, with a source for each item."
+                .to_owned(),
+            system_addon: None,
+            allowed_tools: vec!["web_search".to_owned(), "fetch_url".to_owned()],
+            output: OutputChannel::Notify,
+            schedule: Some(Schedule::Daily { hour: 8, min: 0 }),
+            params: vec![param("topic", "Subject of the briefing", None)],
+        },
+        JobTemplate {
+            id: "page_watch".to_owned(),
+            name: "Watch {{url}}".to_owned(),
+            description: "Check a web page and summarise it".to_owned(),
+            prompt: "Fetch {{url}} and summarise {{focus}}.".to_owned(),
+            system_addon: None,
+            allowed_tools: vec!["fetch_url".to_owned()],
+            output: OutputChannel::Notify,
+            schedule: Some(Schedule::Interval { secs: 6 * 3600 }),
+            params: vec![
+                param("url", "Page to check", None),
+                param("focus", "What to look for", Some("what is new")),
+            ],
+        },
+        JobTemplate {
+            id: "calendar_preview".to_owned(),
+            name: "Calendar preview".to_owned(),
+            description: "Preview upcoming events and flag what needs preparation".to_owned(),
+            prompt: "Look at my calendar for the next {{days}} days and tell me what needs \
+                     preparation."
+                .to_owned(),
+            system_addon: None,
+            allowed_tools: vec![
+                "list_calendars".to_owned(),
+                "list_calendar_events".to_owned(),
+            ],
+            output: OutputChannel::Notify,
+            schedule: None,
+            params: vec![param("days", "How many days ahead to look", Some("7"))],
+        },
+    ]
+}
+
+/// Built-in templates plus the user's templates in `path`.
+///
+/// A missing file yields the built-ins only.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Scheduler`] when the file exists but cannot be
+/// read or parsed.
+pub fn load_templates(path: &Path) -> Result<Vec<JobTemplate>> {
+    let mut templates = builtin_templates();
+    let user: Vec<JobTemplate> = match std::fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str(&raw).map_err(|e| {
+            SpeechError::Scheduler(format!("invalid job templates in {}: {e}", path.display()))
+        })?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            return Err(SpeechError::Scheduler(format!(
+                "cannot read job templates {}: {e}",
+                path.display()
+            )));
+        }
+    };
+    for template in user {
+        templates.retain(|t| t.id != template.id);
+        templates.push(template);
+    }
+    Ok(templates)
+}
+
+/// Look up the template `id` among the built-in and user templates.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Scheduler`] when the templates cannot be loaded
+/// or none has this id.
+pub fn find_template(id: &str) -> Result<JobTemplate> {
+    let templates = load_templates(&crate::fae_dirs::scheduler_templates_file())?;
+    let ids: Vec<String> = templates.iter().map(|t| t.id.clone()).collect();
+    templates.into_iter().find(|t| t.id == id).ok_or_else(|| {
+        SpeechError::Scheduler(format!(
+            "unknown job template '{id}'; available: {}",
+            ids.join(", ")
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use serde_json::json;
+
+    fn values(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    fn builtin(id: &str) -> JobTemplate {
+        builtin_templates()
+            .into_iter()
+            .find(|t| t.id == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn instantiate_fills_placeholders_and_defaults() {
+        let instance = builtin("page_watch")
+            .instantiate(&values(json!({ "url": "https://example.com" })), None)
+            .unwrap();
+        assert_eq!(instance.name, "Watch https://example.com");
+        assert_eq!(
+            instance.trigger.prompt,
+            "Fetch https://example.com and summarise what is new."
+        );
+        assert_eq!(instance.trigger.allowed_tools, vec!["fetch_url".to_owned()]);
+        assert_eq!(instance.trigger.output, OutputChannel::Notify);
+        assert_eq!(instance.schedule, Schedule::Interval { secs: 6 * 3600 });
+    }
+
+    #[test]
+    fn instantiate_reports_missing_parameters() {
+        let err = builtin("news_briefing")
+            .instantiate(&serde_json::Map::new(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("topic"), "{err}");
+    }
+
+    #[test]
+    fn schedule_placeholder_must_be_filled() {
+        let template = builtin("calendar_preview");
+        assert!(template.instantiate(&serde_json::Map::new(), None).is_err());
+
+        let mornings = Schedule::Daily { hour: 7, min: 30 };
+        let instance = template
+            .instantiate(&values(json!({ "days": 3 })), Some(mornings.clone()))
+            .unwrap();
+        assert_eq!(instance.schedule, mornings);
+        assert!(instance.trigger.prompt.contains("next 3 days"));
+    }
+
+    #[test]
+    fn user_templates_replace_builtins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("templates.json");
+        assert_eq!(load_templates(&path).unwrap(), builtin_templates());
+
+        std::fs::write(
+            &path,
+            json!([
+                { "id": "page_watch", "name": "Mine", "prompt": "Check {{url}}" },
+                { "id": "standup", "name": "Standup notes", "prompt": "Draft my standup notes." }
+            ])
+            .to_string(),
+        )
+        .unwrap();
+        let templates = load_templates(&path).unwrap();
+        assert_eq!(templates.len(), builtin_templates().len() + 1);
+        let watch = templates.iter().find(|t| t.id == "page_watch").unwrap();
+        assert_eq!(watch.name, "Mine");
+        assert_eq!(watch.output, OutputChannel::History);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_templates(&path).is_err());
+    }
+}
@@ -9,6 +9,8 @@
 //! 3. **Inject** — write credential values as environment variables into
 //!    `HashMap<String, String>` for subprocess spawning.
 //! 4. **Clear** — delete all stored credentials for a skill.
+//! 5. **Record** — append each launch-time grant to the grant ledger, naming
+//!    the environment variables a skill received but never their values.
 //!
 //! Skills never see raw Keychain storage. They receive their secrets only
 //! as environment variables in their subprocess environment.
@@ -23,9 +25,14 @@
 //! - Raw values are not stored in the registry, logs, or config files.
 
 use super::manifest::CredentialSchema;
+use crate::audit_log::append_audit_line;
 use crate::credentials::{CredentialManager, CredentialRef};
+use crate::time_util::now_epoch_secs;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
 
 // ── Constants ─────────────────────────────────────────────────────────────────
 
@@ -327,6 +334,68 @@ pub fn clear_skill_credentials(
     Ok(())
 }
 
+// ── Grant ledger ──────────────────────────────────────────────────────────────
+
+/// One launch of a skill process with credentials injected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialGrantEntry {
+    pub timestamp_secs: u64,
+    /// Skill identifier the credentials were granted to.
+    pub skill_id: String,
+    /// Environment variables injected, in schema declaration order.
+    pub env_vars: Vec<String>,
+}
+
+/// Appends a grant of `collection` to the ledger at `path`.
+///
+/// Only the skill id and environment variable names are written; secret
+/// values never leave memory.
+///
+/// # Errors
+///
+/// Returns an I/O error if the ledger cannot be opened or written.
+pub fn record_credential_grant(
+    path: &Path,
+    collection: &CredentialCollection,
+) -> std::io::Result<()> {
+    let entry = CredentialGrantEntry {
+        timestamp_secs: now_epoch_secs(),
+        skill_id: collection.skill_id.clone(),
+        env_vars: collection
+            .credentials
+            .iter()
+            .map(|cred| cred.env_var.clone())
+            .collect(),
+    };
+    append_audit_line(path, &entry)
+}
+
+/// Reads the grants recorded for `skill_id` from the ledger at `path`,
+/// oldest first. Malformed lines are skipped.
+///
+/// # Errors
+///
+/// Returns an I/O error if the ledger exists but cannot be read.
+pub fn read_credential_grants(
+    path: &Path,
+    skill_id: &str,
+) -> std::io::Result<Vec<CredentialGrantEntry>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(entry) = serde_json::from_str::<CredentialGrantEntry>(&line?)
+            && entry.skill_id == skill_id
+        {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -692,4 +761,35 @@ pub(crate) mod tests {
 
         assert_eq!(env.get("MY_VAR").map(String::as_str), Some("new-value"));
     }
+
+    // ── grant ledger ──
+
+    #[test]
+    fn grant_ledger_records_env_var_names_without_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = dir.path().join("grants.jsonl");
+        let manager = MockCredentialManager::new();
+        manager.store("weather.api_key", "sk-secret-1").unwrap();
+        manager.store("other.api_key", "sk-secret-2").unwrap();
+        let schema = vec![make_required("api_key", "WEATHER_API_KEY")];
+
+        let weather = retrieve_skill_credentials("weather", &schema, &manager).unwrap();
+        record_credential_grant(&ledger, &weather).unwrap();
+        let other = retrieve_skill_credentials("other", &schema, &manager).unwrap();
+        record_credential_grant(&ledger, &other).unwrap();
+
+        let grants = read_credential_grants(&ledger, "weather").unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].env_vars, vec!["WEATHER_API_KEY".to_owned()]);
+
+        let raw = std::fs::read_to_string(&ledger).unwrap();
+        assert!(!raw.contains("sk-secret"));
+    }
+
+    #[test]
+    fn grant_ledger_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let grants = read_credential_grants(&dir.path().join("none.jsonl"), "weather").unwrap();
+        assert!(grants.is_empty());
+    }
 }
@@ -90,6 +90,10 @@ pub enum PythonSkillError {
     /// I/O error during skill operations.
     #[error("skill I/O error: {0}")]
     IoError(#[source] std::io::Error),
+
+    /// Offline mode is on and the operation needs the network.
+    #[error(transparent)]
+    Offline(#[from] crate::offline::OfflineError),
 }
 
 impl From<serde_json::Error> for PythonSkillError {
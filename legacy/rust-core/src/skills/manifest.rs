@@ -30,7 +30,7 @@
 //! ```
 
 use super::error::PythonSkillError;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// A single credential required by a Python skill.
@@ -49,7 +49,7 @@ use std::path::Path;
 /// description = "Your Discord bot token"
 /// required = true
 /// ```
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CredentialSchema {
     /// Unique identifier for this credential within the skill.
     ///
@@ -140,6 +140,10 @@ pub(crate) struct PythonSkillRecord {
     pub installed_at: u64,
     /// Unix timestamp (seconds) when the record was last modified.
     pub updated_at: u64,
+    /// Credentials declared in the skill's manifest, resolved from the
+    /// Keychain and injected as environment variables at launch.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub credentials: Vec<super::manifest::CredentialSchema>,
 }
 
 /// Public view of a Python skill, safe to expose outside this module.
@@ -471,6 +475,7 @@ pub fn install_python_skill_at(
         last_error: None,
         installed_at,
         updated_at: now_secs(),
+        credentials: manifest.credentials.clone(),
     };
 
     registry.upsert(record.clone());
@@ -840,6 +845,7 @@ mod tests {
             last_error: None,
             installed_at: 1_000_000,
             updated_at: 1_000_000,
+            credentials: Vec::new(),
         }
     }
 
@@ -930,6 +930,8 @@ impl PythonSkillRunner {
 
     /// Spawns the raw child process and returns a `(PythonSkillProcess, JsonRpcComm)` pair.
     async fn spawn_child(&self) -> Result<(PythonSkillProcess, JsonRpcComm), PythonSkillError> {
+        // `uv run` resolves and downloads the script's dependencies.
+        crate::offline::ensure_online("starting Python skills")?;
         let mut cmd = tokio::process::Command::new(&self.config.uv_path);
         cmd.arg("run")
             .arg(&self.config.script_path)
@@ -0,0 +1,622 @@
+//! Skill testing harness: scripted conversations against mocked tools.
+//!
+//! A skill package may carry test cases in its `tests/` directory. Each
+//! `tests/<case>.toml` scripts one conversation — the user's message, the
+//! model's replies and the output of every tool — and states which tools the
+//! skill should lead to. The harness runs the real [`AgentLoop`] with the
+//! skill as the system prompt, a scripted provider and mock tools, then
+//! checks the expectations and compares the recorded transcript with
+//! `tests/<case>.golden.json` when it exists.
+//!
+//! Skill authors generate golden transcripts with
+//! `fae-skill-package test <path> --update` and check them into the package.
+//! [`install_skill_package`](super::install_skill_package) runs the tests and
+//! refuses packages whose tests fail.
+//!
+//! # Example `tests/weather.toml`
+//!
+//! ```toml
+//! user = "What's the weather in Paris?"
+//!
+//! [[responses]]
+//! tool_calls = [{ name = "web_search", arguments = { query = "weather Paris" } }]
+//!
+//! [[responses]]
+//! text = "It's sunny and 21 degrees in Paris."
+//!
+//! [[tools]]
+//! name = "web_search"
+//! output = "Paris: sunny, 21C"
+//!
+//! [expect]
+//! tools_called = ["web_search"]
+//! tools_not_called = ["bash"]
+//! ```
+
+use crate::fae_llm::agent::{AgentConfig, AgentLoop, AgentLoopResult};
+use crate::fae_llm::config::types::ToolMode;
+use crate::fae_llm::error::FaeLlmError;
+use crate::fae_llm::events::{FinishReason, LlmEvent};
+use crate::fae_llm::provider::{LlmEventStream, ProviderAdapter, ToolDefinition};
+use crate::fae_llm::providers::message::Message;
+use crate::fae_llm::tools::registry::ToolRegistry;
+use crate::fae_llm::tools::types::{Tool, ToolResult};
+use crate::fae_llm::types::{ModelRef, RequestOptions};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Directory inside a skill package that holds its test cases.
+pub const SKILL_TESTS_DIR: &str = "tests";
+
+/// File suffix of a golden transcript, next to its `<case>.toml`.
+const GOLDEN_SUFFIX: &str = ".golden.json";
+
+// ── Test case ─────────────────────────────────────────────────────────────────
+
+/// One scripted conversation, parsed from `tests/<case>.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SkillTestCase {
+    /// The user's message that starts the conversation.
+    pub user: String,
+    /// Model replies, one per provider round-trip, in order.
+    #[serde(default)]
+    pub responses: Vec<ScriptedResponse>,
+    /// Tools available to the model and what they return.
+    #[serde(default)]
+    pub tools: Vec<MockToolSpec>,
+    /// What the conversation must and must not do.
+    #[serde(default)]
+    pub expect: SkillTestExpectations,
+}
+
+/// One scripted model reply.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ScriptedResponse {
+    /// Text the model says.
+    #[serde(default)]
+    pub text: String,
+    /// Tools the model calls in this reply.
+    #[serde(default)]
+    pub tool_calls: Vec<ScriptedToolCall>,
+}
+
+/// A tool call made by a scripted reply.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptedToolCall {
+    pub name: String,
+    #[serde(default = "empty_arguments")]
+    pub arguments: serde_json::Value,
+}
+
+fn empty_arguments() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+/// A mocked tool and its canned result.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockToolSpec {
+    pub name: String,
+    /// Output returned on success.
+    #[serde(default)]
+    pub output: String,
+    /// When set, the tool fails with this error instead.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Assertions about the tools a conversation calls.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SkillTestExpectations {
+    /// Tools that must be called, in this order (other calls may come between).
+    #[serde(default)]
+    pub tools_called: Vec<String>,
+    /// Tools that must not be called at all.
+    #[serde(default)]
+    pub tools_not_called: Vec<String>,
+    /// Text the final answer must contain.
+    #[serde(default)]
+    pub final_text_contains: Option<String>,
+}
+
+// ── Transcript ────────────────────────────────────────────────────────────────
+
+/// A step of a recorded conversation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TranscriptEntry {
+    User {
+        text: String,
+    },
+    Assistant {
+        text: String,
+    },
+    ToolCall {
+        name: String,
+        arguments: serde_json::Value,
+    },
+    ToolResult {
+        name: String,
+        success: bool,
+        output: String,
+    },
+}
+
+/// The recorded conversation of one test case, stored as its golden file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillTranscript {
+    pub entries: Vec<TranscriptEntry>,
+    /// Why the agent loop stopped, e.g. `complete`.
+    pub stop_reason: String,
+}
+
+impl SkillTranscript {
+    fn from_result(user: &str, result: &AgentLoopResult) -> Self {
+        let mut entries = vec![TranscriptEntry::User {
+            text: user.to_owned(),
+        }];
+        for turn in &result.turns {
+            if !turn.text.is_empty() {
+                entries.push(TranscriptEntry::Assistant {
+                    text: turn.text.clone(),
+                });
+            }
+            for call in &turn.tool_calls {
+                entries.push(TranscriptEntry::ToolCall {
+                    name: call.function_name.clone(),
+                    arguments: call.arguments.clone(),
+                });
+            }
+            for call in &turn.tool_calls {
+                entries.push(TranscriptEntry::ToolResult {
+                    name: call.function_name.clone(),
+                    success: call.result.success,
+                    output: if call.result.success {
+                        call.result.content.clone()
+                    } else {
+                        call.result.error.clone().unwrap_or_default()
+                    },
+                });
+            }
+        }
+        Self {
+            entries,
+            stop_reason: result.stop_reason.to_string(),
+        }
+    }
+
+    /// Names of the tools called, in call order.
+    pub fn tools_called(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                TranscriptEntry::ToolCall { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The last thing the assistant said.
+    pub fn final_text(&self) -> &str {
+        self.entries
+            .iter()
+            .rev()
+            .find_map(|entry| match entry {
+                TranscriptEntry::Assistant { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Result of running one test case.
+#[derive(Debug, Clone)]
+pub struct SkillTestOutcome {
+    /// Case name, the file stem of its `.toml`.
+    pub name: String,
+    pub transcript: SkillTranscript,
+    /// Failed expectations; empty when the case passed.
+    pub failures: Vec<String>,
+}
+
+impl SkillTestOutcome {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+// ── Mocks ─────────────────────────────────────────────────────────────────────
+
+/// Provider that replays a test case's scripted responses.
+///
+/// Once the script runs out it answers with an empty final reply.
+struct ScriptedProvider {
+    responses: Mutex<Vec<Vec<LlmEvent>>>,
+    /// Turn number used for replies past the end of the script.
+    end_turn: usize,
+}
+
+impl ScriptedProvider {
+    fn new(responses: &[ScriptedResponse]) -> Self {
+        let responses_len = responses.len();
+        let responses = responses
+            .iter()
+            .enumerate()
+            .map(|(turn, response)| scripted_events(turn, response))
+            .collect();
+        Self {
+            responses: Mutex::new(responses),
+            end_turn: responses_len,
+        }
+    }
+}
+
+fn scripted_events(turn: usize, response: &ScriptedResponse) -> Vec<LlmEvent> {
+    let mut events = vec![LlmEvent::StreamStart {
+        request_id: format!("skill-test-{turn}"),
+        model: ModelRef::new("skill-test"),
+    }];
+    if !response.text.is_empty() {
+        events.push(LlmEvent::TextDelta {
+            text: response.text.clone(),
+        });
+    }
+    for (index, call) in response.tool_calls.iter().enumerate() {
+        let call_id = format!("call-{turn}-{index}");
+        events.push(LlmEvent::ToolCallStart {
+            call_id: call_id.clone(),
+            function_name: call.name.clone(),
+        });
+        events.push(LlmEvent::ToolCallArgsDelta {
+            call_id: call_id.clone(),
+            args_fragment: call.arguments.to_string(),
+        });
+        events.push(LlmEvent::ToolCallEnd { call_id });
+    }
+    events.push(LlmEvent::StreamEnd {
+        finish_reason: if response.tool_calls.is_empty() {
+            FinishReason::Stop
+        } else {
+            FinishReason::ToolCalls
+        },
+    });
+    events
+}
+
+#[async_trait]
+impl ProviderAdapter for ScriptedProvider {
+    fn name(&self) -> &str {
+        "skill-test"
+    }
+
+    async fn send(
+        &self,
+        _messages: &[Message],
+        _options: &RequestOptions,
+        _tools: &[ToolDefinition],
+    ) -> Result<LlmEventStream, FaeLlmError> {
+        let events = {
+            let mut responses = self.responses.lock().unwrap_or_else(|e| e.into_inner());
+            if responses.is_empty() {
+                scripted_events(self.end_turn, &ScriptedResponse::default())
+            } else {
+                responses.remove(0)
+            }
+        };
+        Ok(Box::pin(futures_util::stream::iter(events)))
+    }
+}
+
+/// Tool that returns a test case's canned result for any arguments.
+struct MockTool {
+    spec: MockToolSpec,
+}
+
+impl Tool for MockTool {
+    fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    fn description(&self) -> &str {
+        "Mocked tool for skill tests"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    fn execute(&self, _args: serde_json::Value) -> Result<ToolResult, FaeLlmError> {
+        Ok(match &self.spec.error {
+            Some(error) => ToolResult::failure(error.clone()),
+            None => ToolResult::success(self.spec.output.clone()),
+        })
+    }
+
+    fn allowed_in_mode(&self, _mode: ToolMode) -> bool {
+        true
+    }
+}
+
+// ── Running ───────────────────────────────────────────────────────────────────
+
+/// Run `case` with `skill` as the system prompt and check its expectations.
+///
+/// # Errors
+///
+/// Returns [`FaeLlmError`] if the agent loop fails to start.
+pub async fn run_skill_test(
+    name: &str,
+    skill: &str,
+    case: &SkillTestCase,
+) -> Result<SkillTestOutcome, FaeLlmError> {
+    let mut registry = ToolRegistry::new(ToolMode::Full);
+    for spec in &case.tools {
+        registry.register(Arc::new(MockTool { spec: spec.clone() }));
+    }
+    let max_turns = u32::try_from(case.responses.len() + 1).unwrap_or(u32::MAX);
+    let config = AgentConfig::new()
+        .with_system_prompt(skill)
+        .with_max_turns(max_turns);
+    let provider = Arc::new(ScriptedProvider::new(&case.responses));
+    let result = AgentLoop::new(config, provider, Arc::new(registry))
+        .run(&case.user)
+        .await?;
+
+    let transcript = SkillTranscript::from_result(&case.user, &result);
+    let failures = check_expectations(case, &transcript);
+    Ok(SkillTestOutcome {
+        name: name.to_owned(),
+        transcript,
+        failures,
+    })
+}
+
+fn check_expectations(case: &SkillTestCase, transcript: &SkillTranscript) -> Vec<String> {
+    let mut failures = Vec::new();
+    let called = transcript.tools_called();
+
+    for name in &called {
+        if !case.tools.iter().any(|spec| spec.name == *name) {
+            failures.push(format!("`{name}` was called but is not mocked"));
+        }
+    }
+
+    let mut remaining = called.iter();
+    for expected in &case.expect.tools_called {
+        if !remaining.any(|name| name == expected) {
+            failures.push(format!("`{expected}` was not called (in order)"));
+            break;
+        }
+    }
+
+    for forbidden in &case.expect.tools_not_called {
+        if called.contains(&forbidden.as_str()) {
+            failures.push(format!("`{forbidden}` was called"));
+        }
+    }
+
+    if let Some(needle) = &case.expect.final_text_contains
+        && !transcript.final_text().contains(needle.as_str())
+    {
+        failures.push(format!("final answer does not contain \"{needle}\""));
+    }
+
+    failures
+}
+
+/// Run every test case in `package_dir/tests/`, in file name order.
+///
+/// Each transcript is compared with its golden file when one exists; with
+/// `update` the golden files are rewritten from the new transcripts instead.
+///
+/// # Errors
+///
+/// Returns an error if a case cannot be read or parsed, a golden file cannot
+/// be read or written, or the agent loop fails to start.
+pub async fn run_package_tests(
+    package_dir: &Path,
+    skill: &str,
+    update: bool,
+) -> crate::Result<Vec<SkillTestOutcome>> {
+    let mut outcomes = Vec::new();
+    for case_path in test_case_paths(package_dir)? {
+        let name = case_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("case")
+            .to_owned();
+        let raw = std::fs::read_to_string(&case_path).map_err(|e| {
+            crate::SpeechError::Config(format!("cannot read {}: {e}", case_path.display()))
+        })?;
+        let case: SkillTestCase = toml::from_str(&raw).map_err(|e| {
+            crate::SpeechError::Config(format!("invalid {}: {e}", case_path.display()))
+        })?;
+        let mut outcome = run_skill_test(&name, skill, &case)
+            .await
+            .map_err(|e| crate::SpeechError::Config(format!("skill test `{name}`: {e}")))?;
+
+        let golden_path = case_path.with_file_name(format!("{name}{GOLDEN_SUFFIX}"));
+        if update {
+            write_golden(&golden_path, &outcome.transcript)?;
+        } else if let Some(golden) = read_golden(&golden_path)?
+            && golden != outcome.transcript
+        {
+            outcome
+                .failures
+                .push(format!("transcript differs from {}", golden_path.display()));
+        }
+        outcomes.push(outcome);
+    }
+    Ok(outcomes)
+}
+
+/// [`run_package_tests`] for synchronous callers.
+///
+/// Runs on a dedicated thread with its own runtime, so it may be called from
+/// inside or outside a Tokio runtime.
+///
+/// # Errors
+///
+/// As [`run_package_tests`], or if the runtime cannot be started.
+pub fn run_package_tests_blocking(
+    package_dir: &Path,
+    skill: &str,
+    update: bool,
+) -> crate::Result<Vec<SkillTestOutcome>> {
+    let package_dir = package_dir.to_path_buf();
+    let skill = skill.to_owned();
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(run_package_tests(&package_dir, &skill, update))
+    })
+    .join()
+    .map_err(|_| crate::SpeechError::Config("skill test thread panicked".to_owned()))?
+}
+
+fn test_case_paths(package_dir: &Path) -> crate::Result<Vec<PathBuf>> {
+    let dir = package_dir.join(SKILL_TESTS_DIR);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_golden(path: &Path) -> crate::Result<Option<SkillTranscript>> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| crate::SpeechError::Config(format!("invalid {}: {e}", path.display())))
+}
+
+fn write_golden(path: &Path, transcript: &SkillTranscript) -> crate::Result<()> {
+    let json = serde_json::to_string_pretty(transcript)
+        .map_err(|e| crate::SpeechError::Config(format!("cannot serialize transcript: {e}")))?;
+    std::fs::write(path, format!("{json}\n"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    const SKILL: &str = "Use web_search for weather questions.";
+
+    const WEATHER_CASE: &str = r#"
+user = "What's the weather in Paris?"
+
+[[responses]]
+tool_calls = [{ name = "web_search", arguments = { query = "weather Paris" } }]
+
+[[responses]]
+text = "It's sunny in Paris."
+
+[[tools]]
+name = "web_search"
+output = "Paris: sunny, 21C"
+
+[[tools]]
+name = "bash"
+
+[expect]
+tools_called = ["web_search"]
+tools_not_called = ["bash"]
+final_text_contains = "sunny"
+"#;
+
+    fn write_package(dir: &Path, case: &str) {
+        let tests = dir.join(SKILL_TESTS_DIR);
+        std::fs::create_dir_all(&tests).unwrap();
+        std::fs::write(tests.join("weather.toml"), case).unwrap();
+    }
+
+    #[tokio::test]
+    async fn scripted_case_records_tool_calls_and_passes() {
+        let case: SkillTestCase = toml::from_str(WEATHER_CASE).unwrap();
+        let outcome = run_skill_test("weather", SKILL, &case).await.unwrap();
+
+        assert!(outcome.passed(), "{:?}", outcome.failures);
+        assert_eq!(outcome.transcript.tools_called(), vec!["web_search"]);
+        assert_eq!(
+            outcome.transcript.entries[2],
+            TranscriptEntry::ToolResult {
+                name: "web_search".to_owned(),
+                success: true,
+                output: "Paris: sunny, 21C".to_owned(),
+            }
+        );
+        assert_eq!(outcome.transcript.final_text(), "It's sunny in Paris.");
+        assert_eq!(outcome.transcript.stop_reason, "complete");
+    }
+
+    #[tokio::test]
+    async fn unmet_expectations_are_reported() {
+        let mut case: SkillTestCase = toml::from_str(WEATHER_CASE).unwrap();
+        case.expect.tools_called = vec!["calendar".to_owned()];
+        case.expect.tools_not_called = vec!["web_search".to_owned()];
+        case.responses[0].tool_calls.push(ScriptedToolCall {
+            name: "unmocked".to_owned(),
+            arguments: empty_arguments(),
+        });
+
+        let outcome = run_skill_test("weather", SKILL, &case).await.unwrap();
+        assert_eq!(
+            outcome.failures,
+            vec![
+                "`unmocked` was called but is not mocked".to_owned(),
+                "`calendar` was not called (in order)".to_owned(),
+                "`web_search` was called".to_owned(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn golden_transcripts_are_written_and_compared() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(dir.path(), WEATHER_CASE);
+
+        let outcomes = run_package_tests(dir.path(), SKILL, true).await.unwrap();
+        assert!(outcomes[0].passed());
+        let golden = dir.path().join("tests/weather.golden.json");
+        assert!(golden.is_file());
+
+        let outcomes = run_package_tests(dir.path(), SKILL, false).await.unwrap();
+        assert!(outcomes[0].passed(), "{:?}", outcomes[0].failures);
+
+        let changed = WEATHER_CASE.replace("Paris: sunny, 21C", "Paris: rain, 12C");
+        write_package(dir.path(), &changed);
+        let outcomes = run_package_tests(dir.path(), SKILL, false).await.unwrap();
+        assert!(outcomes[0].failures[0].contains("transcript differs"));
+    }
+
+    #[test]
+    fn blocking_runner_works_without_a_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(dir.path(), WEATHER_CASE);
+        let outcomes = run_package_tests_blocking(dir.path(), SKILL, false).unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].passed());
+    }
+
+    #[test]
+    fn package_without_tests_has_no_outcomes() {
+        let dir = tempfile::tempdir().unwrap();
+        let outcomes = run_package_tests_blocking(dir.path(), SKILL, false).unwrap();
+        assert!(outcomes.is_empty());
+    }
+}
@@ -124,6 +124,8 @@ impl UvBootstrap {
     ///
     /// # Errors
     ///
+    /// - [`PythonSkillError::Offline`] if uv has to be installed while offline
+    ///   mode is on.
     /// - [`PythonSkillError::BootstrapFailed`] if the installer download or
     ///   execution fails.
     /// - Any error from [`discover`](Self::discover) if the post-install probe
@@ -156,9 +158,11 @@ impl UvBootstrap {
     ///
     /// # Errors
     ///
-    /// Returns [`PythonSkillError::BootstrapFailed`] if the warm-up command
-    /// fails to execute or exits with a non-zero status.
+    /// Returns [`PythonSkillError::Offline`] while offline mode is on, or
+    /// [`PythonSkillError::BootstrapFailed`] if the warm-up command fails to
+    /// execute or exits with a non-zero status.
     pub fn pre_warm(uv_path: &Path, script_path: &Path) -> Result<(), PythonSkillError> {
+        crate::offline::ensure_online("downloading Python skill dependencies")?;
         tracing::info!(
             "pre-warming Python environment for {}",
             script_path.display()
@@ -193,6 +197,7 @@ impl UvBootstrap {
     /// Sets `UV_INSTALL_DIR` so the binary lands in `uv_cache_dir()/bin/`
     /// and uses `--no-modify-path` to avoid touching shell profiles.
     fn auto_install() -> Result<(), PythonSkillError> {
+        crate::offline::ensure_online("installing uv")?;
         let install_dir = crate::fae_dirs::uv_cache_dir().join("bin");
         std::fs::create_dir_all(&install_dir).map_err(|e| PythonSkillError::BootstrapFailed {
             reason: format!("cannot create install dir {}: {e}", install_dir.display()),
@@ -6,16 +6,22 @@
 //! For GUI consumers, use [`initialize_models_with_progress`] which accepts a
 //! [`ProgressCallback`] for structured progress events.
 
-use crate::config::{MemoryConfig, SpeechConfig};
+use crate::config::{LlmConfig, MemoryConfig, SpeechConfig, TtsBackend};
+use crate::diagnostics::footprint;
 use crate::error::{Result, SpeechError};
+use crate::fae_llm::FaeLlmError;
+use crate::fae_llm::config::types::ProviderConfig;
 use crate::kernel_signature::{KernelSignatureStatus, run_kernel_signature_check};
 use crate::llm::LocalLlm;
 use crate::models::ModelManager;
 use crate::progress::{DownloadFile, DownloadPlan, ProgressCallback, ProgressEvent};
 use crate::stt::ParakeetStt;
-use crate::tts::KokoroTts;
+use crate::tts::{KokoroTts, TtsEngine};
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
+use tokio::sync::watch;
 use tracing::{info, warn};
 
 /// Pre-loaded model instances ready for the pipeline.
@@ -24,8 +30,82 @@ pub struct InitializedModels {
     pub stt: ParakeetStt,
     /// Optional preloaded local LLM for local brain mode or local fallback.
     pub llm: Option<LocalLlm>,
-    /// Kokoro TTS engine (None if using Fish Speech or other backend).
-    pub tts: Option<KokoroTts>,
+    /// Preloaded TTS engine (None when the backend is created lazily, e.g. Chatterbox).
+    pub tts: Option<Box<dyn TtsEngine>>,
+    /// Local LLM still loading in the background when `llm.lazy_load` is set.
+    pub llm_loading: Option<PendingLlm>,
+}
+
+/// A local LLM loading in the background (see [`LlmConfig::lazy_load`]).
+///
+/// Clones wait on the same load.
+#[derive(Clone)]
+pub struct PendingLlm {
+    rx: watch::Receiver<Option<std::result::Result<Arc<LocalLlm>, String>>>,
+}
+
+impl PendingLlm {
+    /// Start loading the LLM for `config` on the current tokio runtime.
+    ///
+    /// Progress and readiness are reported through `callback` outside the
+    /// startup task tree, so the startup bar can complete without it.
+    fn spawn(config: SpeechConfig, callback: Option<ProgressCallback>) -> Self {
+        let (tx, rx) = watch::channel(None);
+        tokio::spawn(async move {
+            let model_name = llm_model_name(&config.llm);
+            if let Some(cb) = &callback {
+                cb.emit(ProgressEvent::LoadStarted {
+                    model_name: model_name.clone(),
+                });
+            }
+            let start = Instant::now();
+            let loaded = match LocalLlm::new(&config.llm).await {
+                Ok(llm) => {
+                    info!("{model_name} loaded in background");
+                    if let Some(cb) = &callback {
+                        cb.emit(ProgressEvent::LoadComplete {
+                            model_name,
+                            duration_secs: start.elapsed().as_secs_f64(),
+                        });
+                        cb.emit(ProgressEvent::ComponentReady {
+                            component: "llm".to_owned(),
+                        });
+                    }
+                    Ok(Arc::new(llm))
+                }
+                Err(e) => {
+                    warn!("background LLM load failed: {e}");
+                    if let Some(cb) = &callback {
+                        cb.emit(ProgressEvent::Error {
+                            message: e.to_string(),
+                        });
+                    }
+                    Err(e.to_string())
+                }
+            };
+            let _ = tx.send(Some(loaded));
+        });
+        Self { rx }
+    }
+
+    /// Wait until the LLM has loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if loading failed or was abandoned.
+    pub async fn wait(&mut self) -> Result<Arc<LocalLlm>> {
+        let loaded = self
+            .rx
+            .wait_for(Option::is_some)
+            .await
+            .map_err(|_| SpeechError::Model("LLM loading was abandoned".to_owned()))?
+            .clone();
+        match loaded {
+            Some(Ok(llm)) => Ok(llm),
+            Some(Err(e)) => Err(SpeechError::Model(e)),
+            None => Err(SpeechError::Model("LLM has not loaded".to_owned())),
+        }
+    }
 }
 
 /// STT model files to pre-download.
@@ -39,6 +119,21 @@ const STT_FILES: &[&str] = &[
 /// LLM tokenizer files to pre-download (from the tokenizer repo).
 const LLM_TOKENIZER_FILES: &[&str] = &["tokenizer.json", "tokenizer_config.json"];
 
+/// Share of the overall startup bar taken by downloading, relative to loading.
+const DOWNLOAD_PHASE_WEIGHT: f64 = 4.0;
+
+/// Share of the overall startup bar taken by loading models.
+const LOAD_PHASE_WEIGHT: f64 = 1.0;
+
+/// Weight of a file whose size is unknown (100 MB).
+const UNKNOWN_FILE_WEIGHT: f64 = 100_000_000.0;
+
+/// Weight of loading the LLM relative to the speech models.
+const LLM_LOAD_WEIGHT: f64 = 4.0;
+
+const STT_MODEL_NAME: &str = "STT (Parakeet TDT)";
+const TTS_MODEL_NAME: &str = "TTS (Kokoro-82M)";
+
 fn should_preload_local_llm(_config: &SpeechConfig) -> bool {
     true
 }
@@ -115,7 +210,7 @@ pub fn build_download_plan(config: &SpeechConfig) -> DownloadPlan {
     }
 
     // TTS (Kokoro)
-    {
+    if config.tts.backend == TtsBackend::Kokoro {
         let tts_repo = crate::tts::kokoro::download::KOKORO_REPO_ID;
         let model_file = crate::tts::kokoro::download::model_filename(&config.tts.model_variant);
         let voice_file = crate::tts::kokoro::download::voice_filename(&config.tts.voice);
@@ -176,6 +271,9 @@ pub async fn initialize_models_with_progress(
     // This picks between Qwen3 4B and 1.7B GGUF presets and leaves
     // user-customized model IDs untouched.
     crate::config::apply_ram_model_selection(&mut resolved_config.llm);
+    // On battery, prefer the lighter models of the low-power profile.
+    crate::platform::power::refresh(&resolved_config.power);
+    crate::platform::power::apply_to_models(&mut resolved_config);
 
     let config = &resolved_config;
 
@@ -221,8 +319,31 @@ pub async fn initialize_models_with_progress(
     }
 
     if let Some(cb) = callback {
-        cb(ProgressEvent::DownloadPlanReady { plan: plan.clone() });
+        cb.emit(ProgressEvent::DownloadPlanReady { plan: plan.clone() });
     }
+
+    // Announce the task tree up front so the overall bar never moves
+    // backwards: files are weighted by size, models by how long they load.
+    let downloads = callback.filter(|_| plan.needs_download()).map(|cb| {
+        let phase = cb.subtask("download", "Downloading models", DOWNLOAD_PHASE_WEIGHT);
+        for file in plan.files.iter().filter(|f| !f.cached) {
+            let weight = file.size_bytes.map_or(UNKNOWN_FILE_WEIGHT, |b| b as f64);
+            phase.subtask(&file.task_id(), &file.filename, weight);
+        }
+        phase
+    });
+    let loads = callback.map(|cb| {
+        let phase = cb.subtask("load", "Loading models", LOAD_PHASE_WEIGHT);
+        phase.subtask(STT_MODEL_NAME, STT_MODEL_NAME, 1.0);
+        if use_local_llm && (!config.llm.lazy_load || config.runtime.memory_audit) {
+            let name = llm_model_name(&config.llm);
+            phase.subtask(&name, &name, LLM_LOAD_WEIGHT);
+        }
+        if config.tts.backend == TtsBackend::Kokoro {
+            phase.subtask(TTS_MODEL_NAME, TTS_MODEL_NAME, 1.0);
+        }
+        phase
+    });
     if plan.needs_download() {
         info!(
             "download plan: {} files to download ({} bytes), {} cached",
@@ -241,11 +362,12 @@ pub async fn initialize_models_with_progress(
     let mut files_complete: usize = 0;
 
     println!("\nChecking models...");
+    let download_callback = downloads.as_ref().or(callback);
 
     // STT files
     for filename in STT_FILES {
         let was_cached = ModelManager::is_file_cached(&config.stt.model_id, filename);
-        model_manager.download_with_progress(&config.stt.model_id, filename, callback)?;
+        model_manager.download_with_progress(&config.stt.model_id, filename, download_callback)?;
         if !was_cached {
             files_complete += 1;
             emit_aggregate(callback, files_complete, files_total, total_download_bytes);
@@ -264,7 +386,7 @@ pub async fn initialize_models_with_progress(
         model_manager.download_with_progress(
             &config.llm.model_id,
             &config.llm.gguf_file,
-            callback,
+            download_callback,
         )?;
         if !was_cached {
             files_complete += 1;
@@ -278,7 +400,7 @@ pub async fn initialize_models_with_progress(
                 model_manager.download_with_progress(
                     &config.llm.tokenizer_id,
                     filename,
-                    callback,
+                    download_callback,
                 )?;
                 if !was_cached {
                     files_complete += 1;
@@ -293,27 +415,169 @@ pub async fn initialize_models_with_progress(
         );
     }
 
-    // TTS: Pre-download Kokoro assets with progress callbacks.
-    let kokoro_paths = crate::tts::kokoro::download::download_kokoro_assets_with_progress(
-        &config.tts.model_variant,
-        &config.tts.voice,
-        &model_manager,
-        callback,
-    )?;
+    // TTS: Pre-download Kokoro assets with progress callbacks. Remote-server
+    // backends (Chatterbox) have nothing to download.
+    let kokoro_paths = if config.tts.backend == TtsBackend::Kokoro {
+        Some(
+            crate::tts::kokoro::download::download_kokoro_assets_with_progress(
+                &config.tts.model_variant,
+                &config.tts.voice,
+                &model_manager,
+                download_callback,
+            )?,
+        )
+    } else {
+        None
+    };
+    if let Some(phase) = &downloads {
+        phase.finish();
+    }
 
     // --- Phase 2: Load models ---
     println!("\nLoading models...");
+    let load_callback = loads.as_ref().or(callback);
+    let models = if config.runtime.memory_audit {
+        load_models_audited(config, kokoro_paths, use_local_llm, load_callback, callback).await?
+    } else {
+        load_models_concurrently(config, kokoro_paths, use_local_llm, load_callback, callback)
+            .await?
+    };
+
+    if let Some(phase) = &loads {
+        phase.finish();
+    }
+
+    Ok(models)
+}
+
+/// Load STT and TTS on blocking threads while the LLM loads here, so startup
+/// takes as long as the slowest model rather than all three.
+async fn load_models_concurrently(
+    config: &SpeechConfig,
+    kokoro_paths: Option<crate::tts::kokoro::download::KokoroPaths>,
+    use_local_llm: bool,
+    load_callback: Option<&ProgressCallback>,
+    callback: Option<&ProgressCallback>,
+) -> Result<InitializedModels> {
+    let stt_task = {
+        let config = config.clone();
+        let callback = load_callback.cloned();
+        tokio::task::spawn_blocking(move || load_stt(&config, callback.as_ref()))
+    };
+    let tts_task = kokoro_paths.map(|paths| {
+        let config = config.clone();
+        let callback = load_callback.cloned();
+        tokio::task::spawn_blocking(move || load_tts_from_paths(paths, &config, callback.as_ref()))
+    });
+
+    let (llm, llm_loading) = if !use_local_llm {
+        (None, None)
+    } else if config.llm.lazy_load {
+        println!("  LLM brain: local (embedded), loading in background");
+        (
+            None,
+            Some(PendingLlm::spawn(config.clone(), callback.cloned())),
+        )
+    } else {
+        println!("  LLM brain: local (embedded)");
+        let llm = load_llm(config, load_callback).await?;
+        component_ready(callback, "llm");
+        (Some(llm), None)
+    };
+
+    let stt = join_load(stt_task).await?;
+    component_ready(callback, "stt");
+    let tts = match tts_task {
+        Some(task) => {
+            let tts = join_load(task).await?;
+            component_ready(callback, "tts");
+            Some(Box::new(tts) as Box<dyn TtsEngine>)
+        }
+        None => {
+            println!("  TTS: {:?} (connects on first use)", config.tts.backend);
+            None
+        }
+    };
+
+    Ok(InitializedModels {
+        stt,
+        llm,
+        tts,
+        llm_loading,
+    })
+}
+
+/// Load models one at a time, recording the resident memory each adds
+/// (see [`RuntimeConfig::memory_audit`](crate::config::RuntimeConfig::memory_audit)).
+async fn load_models_audited(
+    config: &SpeechConfig,
+    kokoro_paths: Option<crate::tts::kokoro::download::KokoroPaths>,
+    use_local_llm: bool,
+    load_callback: Option<&ProgressCallback>,
+    callback: Option<&ProgressCallback>,
+) -> Result<InitializedModels> {
+    println!("  Memory audit: loading one model at a time");
+    footprint::reset();
+
+    let before = footprint::resident_bytes();
+    let stt = load_stt(config, load_callback)?;
+    footprint::record_since("stt", before);
+    component_ready(callback, "stt");
+
+    let tts = match kokoro_paths {
+        Some(paths) => {
+            let before = footprint::resident_bytes();
+            let tts = load_tts_from_paths(paths, config, load_callback)?;
+            footprint::record_since("tts", before);
+            component_ready(callback, "tts");
+            Some(Box::new(tts) as Box<dyn TtsEngine>)
+        }
+        None => {
+            println!("  TTS: {:?} (connects on first use)", config.tts.backend);
+            None
+        }
+    };
 
-    let stt = load_stt(config, callback)?;
     let llm = if use_local_llm {
         println!("  LLM brain: local (embedded)");
-        Some(load_llm(config, callback).await?)
+        let before = footprint::resident_bytes();
+        let llm = load_llm(config, load_callback).await?;
+        footprint::record_since("llm", before);
+        component_ready(callback, "llm");
+        Some(llm)
     } else {
         None
     };
-    let tts = Some(load_tts_from_paths(kokoro_paths, config, callback)?);
 
-    Ok(InitializedModels { stt, llm, tts })
+    for entry in footprint::report().components {
+        println!(
+            "  {}: {:.0} MB resident",
+            entry.component,
+            entry.resident_bytes as f64 / (1024.0 * 1024.0)
+        );
+    }
+
+    Ok(InitializedModels {
+        stt,
+        llm,
+        tts,
+        llm_loading: None,
+    })
+}
+
+/// Wait for a model loading on a blocking thread.
+async fn join_load<T>(task: tokio::task::JoinHandle<Result<T>>) -> Result<T> {
+    task.await
+        .map_err(|e| SpeechError::Model(format!("model load task failed: {e}")))?
+}
+
+/// Report that `component` (`stt`, `tts`, or `llm`) is ready to use.
+fn component_ready(callback: Option<&ProgressCallback>, component: &str) {
+    if let Some(cb) = callback {
+        cb.emit(ProgressEvent::ComponentReady {
+            component: component.to_owned(),
+        });
+    }
 }
 
 /// Emit an aggregate progress event after a file download completes.
@@ -324,7 +588,7 @@ fn emit_aggregate(
     total_bytes: u64,
 ) {
     if let Some(cb) = callback {
-        cb(ProgressEvent::AggregateProgress {
+        cb.emit(ProgressEvent::AggregateProgress {
             // After a file completes, we report aggregate bytes equal to
             // the proportion of files complete (approximation — exact byte
             // tracking would require wrapping every download_with_progress call).
@@ -340,36 +604,61 @@ fn emit_aggregate(
     }
 }
 
+/// Announce loading `model_name`, failing if startup was cancelled.
+///
+/// Returns the load task to finish with [`finish_load`].
+fn start_load(
+    model_name: &str,
+    callback: Option<&ProgressCallback>,
+) -> Result<Option<ProgressCallback>> {
+    println!("  Loading {model_name}...");
+    let Some(cb) = callback else {
+        return Ok(None);
+    };
+    cb.check_cancelled()?;
+    cb.emit(ProgressEvent::LoadStarted {
+        model_name: model_name.to_owned(),
+    });
+    Ok(Some(cb.subtask(model_name, model_name, 1.0)))
+}
+
+/// Report that `model_name` loaded in `elapsed`.
+fn finish_load(model_name: String, elapsed: std::time::Duration, task: Option<ProgressCallback>) {
+    println!("  {model_name} ready ({:.1}s)", elapsed.as_secs_f64());
+    if let Some(task) = task {
+        task.emit(ProgressEvent::LoadComplete {
+            model_name,
+            duration_secs: elapsed.as_secs_f64(),
+        });
+        task.finish();
+    }
+}
+
 /// Generic wrapper for model loading with timing, logging, and progress callbacks.
 fn load_model_with_progress<T>(
     model_name: String,
     callback: Option<&ProgressCallback>,
     loader: impl FnOnce() -> Result<T>,
 ) -> Result<T> {
-    print!("  Loading {model_name}...");
-    if let Some(cb) = callback {
-        cb(ProgressEvent::LoadStarted {
-            model_name: model_name.clone(),
-        });
-    }
-
+    let task = start_load(&model_name, callback)?;
     let start = Instant::now();
     let model = loader()?;
-    let elapsed = start.elapsed();
+    finish_load(model_name, start.elapsed(), task);
+    Ok(model)
+}
 
-    println!("  done ({:.1}s)", elapsed.as_secs_f64());
-    if let Some(cb) = callback {
-        cb(ProgressEvent::LoadComplete {
-            model_name,
-            duration_secs: elapsed.as_secs_f64(),
-        });
+/// Display name of the LLM, which also names its load task.
+fn llm_model_name(llm: &LlmConfig) -> String {
+    if llm.enable_vision && llm.gguf_file.is_empty() {
+        format!("LLM ({} / vision+ISQ)", llm.model_id)
+    } else {
+        format!("LLM ({} / {})", llm.model_id, llm.gguf_file)
     }
-    Ok(model)
 }
 
 /// Load STT with a status message and optional progress callback.
 fn load_stt(config: &SpeechConfig, callback: Option<&ProgressCallback>) -> Result<ParakeetStt> {
-    load_model_with_progress("STT (Parakeet TDT)".to_owned(), callback, || {
+    load_model_with_progress(STT_MODEL_NAME.to_owned(), callback, || {
         let mut stt = ParakeetStt::new(&config.stt, &config.models)?;
         stt.ensure_loaded()?;
         Ok(stt)
@@ -378,29 +667,11 @@ fn load_stt(config: &SpeechConfig, callback: Option<&ProgressCallback>) -> Resul
 
 /// Load LLM with a status message and optional progress callback.
 async fn load_llm(config: &SpeechConfig, callback: Option<&ProgressCallback>) -> Result<LocalLlm> {
-    let model_name = if config.llm.enable_vision && config.llm.gguf_file.is_empty() {
-        format!("LLM ({} / vision+ISQ)", config.llm.model_id)
-    } else {
-        format!("LLM ({} / {})", config.llm.model_id, config.llm.gguf_file)
-    };
-    print!("  Loading {model_name}...");
-    if let Some(cb) = callback {
-        cb(ProgressEvent::LoadStarted {
-            model_name: model_name.clone(),
-        });
-    }
-
+    let model_name = llm_model_name(&config.llm);
+    let task = start_load(&model_name, callback)?;
     let start = Instant::now();
     let llm = LocalLlm::new(&config.llm).await?;
-    let elapsed = start.elapsed();
-
-    println!("  done ({:.1}s)", elapsed.as_secs_f64());
-    if let Some(cb) = callback {
-        cb(ProgressEvent::LoadComplete {
-            model_name,
-            duration_secs: elapsed.as_secs_f64(),
-        });
-    }
+    finish_load(model_name, start.elapsed(), task);
     Ok(llm)
 }
 
@@ -410,7 +681,7 @@ fn load_tts_from_paths(
     config: &SpeechConfig,
     callback: Option<&ProgressCallback>,
 ) -> Result<KokoroTts> {
-    load_model_with_progress("TTS (Kokoro-82M)".to_owned(), callback, || {
+    load_model_with_progress(TTS_MODEL_NAME.to_owned(), callback, || {
         KokoroTts::from_paths(paths, &config.tts)
     })
 }
@@ -548,6 +819,257 @@ pub fn preflight_check(config: &SpeechConfig) -> Result<PreFlightResult> {
     })
 }
 
+// ── First-run setup check ───────────────────────────────────────────────────
+
+/// Outcome of one first-run setup check.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SetupCheck {
+    /// Check identifier (`"provider.<name>"`, `"local_model.ram"`,
+    /// `"local_model.load"`).
+    pub check: String,
+    /// Whether the check passed.
+    pub ok: bool,
+    /// Result on success, or what went wrong.
+    pub detail: String,
+    /// What the user can do to fix a failure.
+    pub action: Option<String>,
+}
+
+impl SetupCheck {
+    fn passed(check: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            ok: true,
+            detail: detail.into(),
+            action: None,
+        }
+    }
+
+    fn failed(
+        check: impl Into<String>,
+        detail: impl Into<String>,
+        action: impl Into<String>,
+    ) -> Self {
+        Self {
+            check: check.into(),
+            ok: false,
+            detail: detail.into(),
+            action: Some(action.into()),
+        }
+    }
+}
+
+/// Result of [`first_run_check`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SetupReport {
+    /// Every check that ran, in order.
+    pub checks: Vec<SetupCheck>,
+}
+
+impl SetupReport {
+    /// Whether every check passed.
+    pub fn ready(&self) -> bool {
+        self.checks.iter().all(|c| c.ok)
+    }
+
+    fn push(&mut self, check: SetupCheck, callback: Option<&ProgressCallback>) {
+        if let Some(cb) = callback {
+            cb.emit(match &check.action {
+                None => ProgressEvent::CheckPassed {
+                    check: check.check.clone(),
+                    detail: check.detail.clone(),
+                },
+                Some(action) => ProgressEvent::CheckFailed {
+                    check: check.check.clone(),
+                    message: check.detail.clone(),
+                    action: action.clone(),
+                },
+            });
+        }
+        self.checks.push(check);
+    }
+}
+
+/// What the user should do about a failed API key validation.
+pub fn provider_key_action(error: &FaeLlmError) -> &'static str {
+    match error {
+        FaeLlmError::SecretResolutionError(_) => "Enter an API key for this provider.",
+        FaeLlmError::AuthError(_) => {
+            "Check the key was copied in full and is still active in the provider's dashboard."
+        }
+        FaeLlmError::ProviderConfigError(_) => "Check the provider's base URL.",
+        FaeLlmError::RequestError(_) | FaeLlmError::TimeoutError(_) => {
+            "Check your internet connection (and that offline mode is off), then retry."
+        }
+        _ => "Check the provider's status and your account's billing, then retry.",
+    }
+}
+
+/// Check that the selected local model fits the machine's RAM.
+///
+/// Models missing from `catalog` have no known requirement and pass.
+pub fn check_model_ram_budget(
+    llm: &LlmConfig,
+    total_memory_bytes: Option<u64>,
+    catalog: &crate::models::catalog::ModelCatalog,
+) -> SetupCheck {
+    const CHECK: &str = "local_model.ram";
+    let Some(entry) = catalog.by_repo_id(&llm.model_id) else {
+        return SetupCheck::passed(
+            CHECK,
+            format!(
+                "{} is not in the model catalog; RAM needs unknown",
+                llm.model_id
+            ),
+        );
+    };
+    let ram_gib = total_memory_bytes.map(|b| b as f64 / (1024.0 * 1024.0 * 1024.0));
+    if entry.fits_memory(total_memory_bytes) {
+        return SetupCheck::passed(
+            CHECK,
+            format!(
+                "{} needs {} GB of RAM; this machine has {}",
+                entry.display_name,
+                entry.min_ram_gib,
+                ram_gib.map_or("an unknown amount".to_owned(), |g| format!("{g:.0} GB")),
+            ),
+        );
+    }
+
+    let suggestion = catalog
+        .local_llm_for(crate::config::VoiceModelPreset::Auto, total_memory_bytes)
+        .filter(|e| e.repo_id != entry.repo_id);
+    SetupCheck::failed(
+        CHECK,
+        format!(
+            "{} needs {} GB of RAM but this machine has {}",
+            entry.display_name,
+            entry.min_ram_gib,
+            ram_gib.map_or("an unknown amount".to_owned(), |g| format!("{g:.0} GB")),
+        ),
+        match suggestion {
+            Some(s) => format!(
+                "Switch the voice model preset to auto to use {} instead.",
+                s.display_name
+            ),
+            None => "Use a remote provider instead of a local model.".to_owned(),
+        },
+    )
+}
+
+/// Whether the selected local model's weights are already downloaded.
+fn local_model_cached(llm: &LlmConfig) -> bool {
+    if llm.enable_vision && llm.gguf_file.is_empty() {
+        ModelManager::is_file_cached(&llm.model_id, "config.json")
+    } else {
+        ModelManager::is_file_cached(&llm.model_id, &llm.gguf_file)
+    }
+}
+
+/// Load the local model once to confirm it works and leaves enough memory.
+async fn smoke_test_local_model(
+    config: &SpeechConfig,
+    callback: Option<&ProgressCallback>,
+) -> SetupCheck {
+    const CHECK: &str = "local_model.load";
+    if !local_model_cached(&config.llm) {
+        return SetupCheck::passed(
+            CHECK,
+            "not downloaded yet; it will be checked when it first loads",
+        );
+    }
+
+    let before_mb = crate::memory_pressure::available_memory_mb();
+    let start = Instant::now();
+    let llm = match load_llm(config, callback).await {
+        Ok(llm) => llm,
+        Err(e) => {
+            return SetupCheck::failed(
+                CHECK,
+                format!("the model failed to load: {e}"),
+                "Re-download the model from Settings, or pick a different voice model preset.",
+            );
+        }
+    };
+    let elapsed = start.elapsed();
+    let after_mb = crate::memory_pressure::available_memory_mb();
+    drop(llm);
+
+    let used_mb = before_mb.saturating_sub(after_mb);
+    if crate::memory_pressure::PressureLevel::from_available_mb(after_mb)
+        == crate::memory_pressure::PressureLevel::Critical
+    {
+        return SetupCheck::failed(
+            CHECK,
+            format!("the model loaded but left only {after_mb} MB of memory free"),
+            "Close other apps or pick a smaller voice model preset.",
+        );
+    }
+    SetupCheck::passed(
+        CHECK,
+        format!(
+            "loaded in {:.1}s using about {used_mb} MB",
+            elapsed.as_secs_f64()
+        ),
+    )
+}
+
+/// Run the first-run setup checks: validate each provider's API key with a
+/// live request, check the local model against the RAM budget, and (when
+/// `load_model` is set) load it once as a smoke test.
+///
+/// Every check reports through `callback` as it runs; failures carry an
+/// action the user can take.
+pub async fn first_run_check(
+    config: &SpeechConfig,
+    providers: &BTreeMap<String, ProviderConfig>,
+    load_model: bool,
+    callback: Option<&ProgressCallback>,
+) -> SetupReport {
+    let started = |check: &str| {
+        if let Some(cb) = callback {
+            cb.emit(ProgressEvent::CheckStarted {
+                check: check.to_owned(),
+            });
+        }
+    };
+    let mut report = SetupReport::default();
+
+    for (name, provider) in providers.iter().filter(|(_, p)| p.enabled) {
+        let check = format!("provider.{name}");
+        started(&check);
+        let result = match crate::fae_llm::providers::validate::validate_provider(provider).await {
+            Ok(0) => SetupCheck::passed(check, "key accepted"),
+            Ok(n) => SetupCheck::passed(check, format!("key accepted; {n} models available")),
+            Err(e) => {
+                let action = provider_key_action(&e);
+                SetupCheck::failed(check, e.message().to_owned(), action)
+            }
+        };
+        report.push(result, callback);
+    }
+
+    started("local_model.ram");
+    let ram = crate::system_profile::detect_total_memory_bytes();
+    let catalog = crate::models::catalog::active();
+    let budget = check_model_ram_budget(&config.llm, ram, &catalog);
+    let fits = budget.ok;
+    report.push(budget, callback);
+
+    if load_model && fits {
+        started("local_model.load");
+        let loaded = smoke_test_local_model(config, callback).await;
+        report.push(loaded, callback);
+    }
+
+    info!(
+        ready = report.ready(),
+        checks = report.checks.len(),
+        "first-run check finished"
+    );
+    report
+}
+
 /// Result of a startup update check that may include a staged download.
 pub struct UpdateCheckResult {
     /// The release that was found (if any).
@@ -821,9 +1343,13 @@ pub fn start_scheduler_with_llm(
         .with_run_key_ledger(run_key_ledger);
     scheduler.with_update_checks();
     scheduler.with_memory_maintenance();
+    scheduler.with_privacy_maintenance();
+    scheduler.with_permission_review();
+    scheduler.with_settings_sync(config.sync.interval_mins);
     let memory_root = config.memory.root_dir.clone();
     let retention_days = config.memory.retention_days;
     let backup_keep_count = config.memory.backup_keep_count;
+    let live_permissions = channels.shared_permissions.clone();
 
     let bridge_executor = bridge.into_executor();
     scheduler = scheduler.with_executor(std::sync::Arc::new(
@@ -831,6 +1357,19 @@ pub fn start_scheduler_with_llm(
             if task.kind == crate::scheduler::tasks::TaskKind::User {
                 return bridge_executor(task);
             }
+            // Review against the live grants rather than the config on disk.
+            if task.id == crate::scheduler::tasks::TASK_PERMISSION_REVIEW
+                && let Some(permissions) = &live_permissions
+            {
+                let store = permissions
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone();
+                return crate::permission_usage::run_permission_review(
+                    &store,
+                    &crate::fae_dirs::permission_usage_file(),
+                );
+            }
             execute_scheduler_task(task, &memory_root, retention_days, backup_keep_count)
         },
     ));
@@ -882,6 +1421,9 @@ pub fn start_scheduler_with_config(
         .with_run_key_ledger(run_key_ledger);
     scheduler.with_update_checks();
     scheduler.with_memory_maintenance();
+    scheduler.with_privacy_maintenance();
+    scheduler.with_permission_review();
+    scheduler.with_settings_sync(config.sync.interval_mins);
     let memory_root = config.memory.root_dir.clone();
     let retention_days = config.memory.retention_days;
     let backup_keep_count = config.memory.backup_keep_count;
@@ -941,31 +1483,57 @@ async fn handle_conversation_requests(
             request.task_id, request.prompt
         );
 
+        if request.latency_insensitive
+            && let Some(response) = run_batched_conversation(&request).await
+        {
+            if request.response_tx.send(response).is_err() {
+                error!(
+                    "Failed to send conversation response for task {}: receiver dropped",
+                    request.task_id
+                );
+            }
+            continue;
+        }
+
         // Execute conversation with timeout (use request.timeout_secs or default to 120)
         let timeout_secs = request.timeout_secs.unwrap_or(120);
         let conversation_timeout = Duration::from_secs(timeout_secs);
-        let result = timeout(
-            conversation_timeout,
-            execute_scheduled_conversation(&config, &request, &shared_llm, &channels),
+        // The agent shares the local model with the conversation, so it
+        // pauses during interactive turns.
+        let result = crate::scheduler::priority::run_preemptible(
+            crate::scheduler::priority::JobClass::Model,
+            timeout(
+                conversation_timeout,
+                execute_scheduled_conversation(&config, &request, &shared_llm, &channels),
+            ),
         )
         .await;
 
         let response = match result {
-            Ok(Ok(text)) => {
-                debug!(
-                    "Conversation completed for task {}: {}",
-                    request.task_id, text
+            None => {
+                info!(
+                    "Conversation for task {} preempted by an interactive turn",
+                    request.task_id
                 );
-                ConversationResponse::Success(text)
-            }
-            Ok(Err(e)) => {
-                error!("Conversation failed for task {}: {e}", request.task_id);
-                ConversationResponse::Error(format!("{e}"))
-            }
-            Err(_) => {
-                error!("Conversation timed out for task {}", request.task_id);
-                ConversationResponse::Timeout
+                ConversationResponse::Preempted
             }
+            Some(result) => match result {
+                Ok(Ok(text)) => {
+                    debug!(
+                        "Conversation completed for task {}: {}",
+                        request.task_id, text
+                    );
+                    ConversationResponse::Success(text)
+                }
+                Ok(Err(e)) => {
+                    error!("Conversation failed for task {}: {e}", request.task_id);
+                    ConversationResponse::Error(format!("{e}"))
+                }
+                Err(_) => {
+                    error!("Conversation timed out for task {}", request.task_id);
+                    ConversationResponse::Timeout
+                }
+            },
         };
 
         // Send response back to executor
@@ -978,6 +1546,36 @@ async fn handle_conversation_requests(
     }
 }
 
+/// Run a latency-insensitive scheduled conversation through the default
+/// provider's batch API.
+///
+/// Returns `None` when no batch-capable provider is configured, in which
+/// case the conversation runs on the local agent as usual.
+async fn run_batched_conversation(
+    request: &crate::pipeline::messages::ConversationRequest,
+) -> Option<crate::pipeline::messages::ConversationResponse> {
+    use crate::pipeline::messages::ConversationResponse;
+    use crate::scheduler::batch::{BatchStep, advance};
+
+    let config = crate::fae_llm::config::read_config(&crate::fae_dirs::llm_config_file()).ok()?;
+    let provider = crate::fae_llm::providers::batch::batch_provider_from_config(&config)?;
+    let step = advance(
+        &crate::fae_dirs::scheduler_batches_file(),
+        provider.as_ref(),
+        &request.task_id,
+        &request.prompt,
+        request.system_addon.as_deref(),
+        crate::time_util::now_epoch_secs(),
+    )
+    .await;
+    Some(match step {
+        Ok(BatchStep::Waiting(status)) => ConversationResponse::Deferred(status),
+        Ok(BatchStep::Done(text)) => ConversationResponse::Success(text),
+        Ok(BatchStep::Failed(e)) => ConversationResponse::Error(e),
+        Err(e) => ConversationResponse::Error(format!("{e}")),
+    })
+}
+
 /// Execute a scheduled conversation using the embedded local agent.
 ///
 /// Runs a background agent with intent-appropriate tools. If the LLM is not
@@ -1003,8 +1601,11 @@ async fn execute_scheduled_conversation(
         ));
     }
 
-    // Classify the prompt to select the appropriate tool allowlist.
-    let tool_allowlist = crate::agent::select_tool_allowlist_for_prompt(&request.prompt);
+    // Use the task's tools, or classify the prompt to select them.
+    let tool_allowlist = crate::agent::select_tool_allowlist_for_scheduled_task(
+        &request.prompt,
+        &request.allowed_tools,
+    );
 
     let task = BackgroundAgentTask {
         id: request.task_id.clone(),
@@ -1012,6 +1613,7 @@ async fn execute_scheduled_conversation(
         user_message: request.prompt.clone(),
         conversation_context: request.system_addon.clone().unwrap_or_default(),
         tool_allowlist,
+        resumed_call: None,
     };
 
     let result = spawn_background_agent(
@@ -1097,6 +1699,23 @@ mod tests {
 
     use super::*;
 
+    #[tokio::test]
+    async fn pending_llm_reports_failed_and_abandoned_loads() {
+        let (tx, rx) = watch::channel(None);
+        let mut pending = PendingLlm { rx };
+        let mut waiter = pending.clone();
+        tx.send(Some(Err("out of memory".to_owned()))).unwrap();
+        assert!(matches!(
+            pending.wait().await,
+            Err(SpeechError::Model(e)) if e == "out of memory"
+        ));
+        assert!(waiter.wait().await.is_err());
+
+        let (tx, rx) = watch::channel(None);
+        drop(tx);
+        assert!(PendingLlm { rx }.wait().await.is_err());
+    }
+
     #[test]
     fn disk_space_check_has_enough_space() {
         let check = DiskSpaceCheck {
@@ -1226,6 +1845,43 @@ mod tests {
         assert!(check.has_enough_space());
     }
 
+    #[test]
+    fn model_ram_budget_suggests_a_smaller_model() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let catalog = crate::models::catalog::embedded();
+        let llm = LlmConfig {
+            model_id: "unsloth/Qwen3-8B-GGUF".to_owned(),
+            ..LlmConfig::default()
+        };
+
+        let fits = check_model_ram_budget(&llm, Some(64 * GIB), catalog);
+        assert!(fits.ok, "{fits:?}");
+
+        let too_big = check_model_ram_budget(&llm, Some(16 * GIB), catalog);
+        assert!(!too_big.ok);
+        let action = too_big.action.unwrap();
+        let smaller = catalog
+            .local_llm_for(crate::config::VoiceModelPreset::Auto, Some(16 * GIB))
+            .unwrap();
+        assert!(action.contains(&smaller.display_name), "{action}");
+
+        let custom = LlmConfig {
+            model_id: "someone/custom-model".to_owned(),
+            ..LlmConfig::default()
+        };
+        assert!(check_model_ram_budget(&custom, None, catalog).ok);
+    }
+
+    #[test]
+    fn key_failures_have_distinct_actions() {
+        let auth = provider_key_action(&FaeLlmError::AuthError(String::new()));
+        let missing = provider_key_action(&FaeLlmError::SecretResolutionError(String::new()));
+        let offline = provider_key_action(&FaeLlmError::RequestError(String::new()));
+        assert_ne!(auth, missing);
+        assert_ne!(auth, offline);
+        assert!(offline.contains("offline"));
+    }
+
     #[test]
     fn disk_space_headroom_constant_is_500mb() {
         assert_eq!(DISK_SPACE_HEADROOM, 500 * 1024 * 1024);
@@ -0,0 +1,418 @@
+//! Contextual biasing toward a dynamic vocabulary.
+//!
+//! Parakeet often mangles names it has never seen ("Saorsa" → "sour sa").
+//! The runtime keeps a process-wide [`BiasVocabulary`] of terms the user is
+//! likely to say: contacts and project symbols pushed by the shell, people
+//! from memory (refreshed when a conversation starts), and the configured
+//! custom vocabulary.
+//!
+//! `parakeet-rs` decodes greedily and does not expose per-token scores, so
+//! boosting is applied to the decoded hypothesis: spans of one to three
+//! words whose sound-alike key is close to a vocabulary term are rewritten
+//! to that term. A term's boost widens how far a span may drift and still
+//! match.
+
+use crate::config::SpeechConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// Key similarity a span needs to match a term with boost `1.0`.
+const BASE_SIMILARITY: f32 = 0.8;
+
+/// How much each unit of boost above `1.0` lowers the required similarity.
+const BOOST_STEP: f32 = 0.05;
+
+/// Lowest similarity any boost can reach.
+const MIN_SIMILARITY: f32 = 0.65;
+
+/// Shortest key that may match approximately; shorter terms only fix case.
+const MIN_FUZZY_KEY_LEN: usize = 4;
+
+/// Most terms kept per source.
+const MAX_TERMS_PER_SOURCE: usize = 500;
+
+/// Where a vocabulary term came from. Each source is replaced as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BiasSource {
+    /// `stt.vocabulary` from the config file.
+    Custom,
+    /// Contact names pushed by the shell.
+    Contacts,
+    /// People recorded in memory.
+    Memory,
+    /// Symbols from the project the user is working on.
+    Project,
+}
+
+impl BiasSource {
+    /// Parse a wire name (`custom`, `contacts`, `memory`, `project`).
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "custom" => Some(Self::Custom),
+            "contacts" => Some(Self::Contacts),
+            "memory" => Some(Self::Memory),
+            "project" => Some(Self::Project),
+            _ => None,
+        }
+    }
+}
+
+/// A phrase to bias recognition toward.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BiasTerm {
+    /// Phrase as it should be written, e.g. `Siobhan` or `tokio::select`.
+    pub phrase: String,
+    /// How strongly to prefer the phrase; `1.0` is the default.
+    #[serde(default = "default_boost")]
+    pub boost: f32,
+}
+
+fn default_boost() -> f32 {
+    1.0
+}
+
+impl BiasTerm {
+    /// A term with the default boost.
+    pub fn new(phrase: impl Into<String>) -> Self {
+        Self {
+            phrase: phrase.into(),
+            boost: default_boost(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    phrase: String,
+    lower: String,
+    key: String,
+    word_count: usize,
+    min_similarity: f32,
+}
+
+impl Entry {
+    fn new(term: &BiasTerm) -> Option<Self> {
+        let phrase = term.phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+        let words = words(&phrase);
+        let key = words.iter().map(|w| sound_key(w.text)).collect::<String>();
+        if words.is_empty() || key.is_empty() {
+            return None;
+        }
+        let boost = if term.boost.is_finite() {
+            term.boost.max(0.0)
+        } else {
+            1.0
+        };
+        Some(Self {
+            lower: phrase.to_lowercase(),
+            word_count: words.len(),
+            min_similarity: (BASE_SIMILARITY - (boost - 1.0) * BOOST_STEP)
+                .clamp(MIN_SIMILARITY, 1.0),
+            phrase,
+            key,
+        })
+    }
+}
+
+/// Terms to bias recognition toward, grouped by source.
+#[derive(Debug, Default)]
+pub struct BiasVocabulary {
+    sources: BTreeMap<BiasSource, Vec<Entry>>,
+}
+
+static VOCABULARY: Mutex<BiasVocabulary> = Mutex::new(BiasVocabulary::new());
+
+/// The process-wide vocabulary applied to every transcription.
+pub fn vocabulary() -> MutexGuard<'static, BiasVocabulary> {
+    VOCABULARY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl BiasVocabulary {
+    /// An empty vocabulary.
+    pub const fn new() -> Self {
+        Self {
+            sources: BTreeMap::new(),
+        }
+    }
+
+    /// Replace every term from `source`. Blank phrases are ignored and at
+    /// most 500 terms are kept.
+    pub fn set(&mut self, source: BiasSource, terms: &[BiasTerm]) {
+        let entries: Vec<Entry> = terms
+            .iter()
+            .filter_map(Entry::new)
+            .take(MAX_TERMS_PER_SOURCE)
+            .collect();
+        if entries.is_empty() {
+            self.sources.remove(&source);
+        } else {
+            self.sources.insert(source, entries);
+        }
+    }
+
+    /// Number of terms per source.
+    pub fn counts(&self) -> BTreeMap<BiasSource, usize> {
+        self.sources
+            .iter()
+            .map(|(source, entries)| (*source, entries.len()))
+            .collect()
+    }
+
+    /// Whether no terms are loaded.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Rewrite spans of `text` that sound like a vocabulary term.
+    ///
+    /// Returns `None` when nothing changed.
+    pub fn apply(&self, text: &str) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let words = words(text);
+        let keys: Vec<String> = words.iter().map(|w| sound_key(w.text)).collect();
+
+        let mut candidates: Vec<(f32, usize, usize, &Entry)> = Vec::new();
+        for entry in self.sources.values().flatten() {
+            let min_len = entry.word_count.saturating_sub(1).max(1);
+            let max_len = entry.word_count + 1;
+            for start in 0..words.len() {
+                for len in min_len..=max_len {
+                    let end = start + len;
+                    if end > words.len() {
+                        break;
+                    }
+                    // An exact hit scores 1.0 too, so it keeps its span.
+                    let span = &text[words[start].start..words[end - 1].end];
+                    let score = if span.to_lowercase() == entry.lower {
+                        1.0
+                    } else if entry.key.chars().count() < MIN_FUZZY_KEY_LEN {
+                        continue;
+                    } else {
+                        similarity(&keys[start..end].concat(), &entry.key)
+                    };
+                    if score >= entry.min_similarity {
+                        candidates.push((score, start, end, entry));
+                    }
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+
+        // Best matches first; among equals prefer spans covering more words.
+        candidates.sort_by(|a, b| {
+            b.0.total_cmp(&a.0)
+                .then_with(|| (b.2 - b.1).cmp(&(a.2 - a.1)))
+        });
+        let mut taken = vec![false; words.len()];
+        let mut chosen = Vec::new();
+        for (_, start, end, entry) in candidates {
+            if taken[start..end].iter().any(|t| *t) {
+                continue;
+            }
+            taken[start..end].iter_mut().for_each(|t| *t = true);
+            chosen.push((words[start].start, words[end - 1].end, entry));
+        }
+
+        chosen.sort_by_key(|(start, _, _)| *start);
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for (start, end, entry) in chosen {
+            out.push_str(&text[cursor..start]);
+            out.push_str(&entry.phrase);
+            cursor = end;
+        }
+        out.push_str(&text[cursor..]);
+        (out != text).then_some(out)
+    }
+}
+
+/// Reload the [`BiasSource::Memory`] terms from the people in memory.
+///
+/// Called when a conversation starts so people learned in the previous one
+/// are recognised. Memory that cannot be read leaves the old terms in place.
+pub fn refresh_from_memory(config: &SpeechConfig) {
+    let mut names: Vec<String> = Vec::new();
+    let store = crate::memory::MemoryStore::new(&config.memory.root_dir);
+    if let Ok(Some(user)) = store.load_primary_user() {
+        names.push(user.name);
+    }
+    if let Ok(people) = store.load_people() {
+        names.extend(people.into_iter().map(|p| p.name));
+    }
+    match crate::memory::SqliteMemoryRepository::new(&config.memory.root_dir) {
+        // Records come most recently updated first.
+        Ok(repo) => match repo.list_records() {
+            Ok(records) => names.extend(
+                records
+                    .iter()
+                    .filter(|r| r.kind == crate::memory::MemoryKind::Person)
+                    .flat_map(|r| r.tags.iter())
+                    .filter_map(|t| t.strip_prefix("person:"))
+                    .map(str::to_owned),
+            ),
+            Err(e) => {
+                tracing::warn!("cannot read people for STT biasing: {e}");
+                return;
+            }
+        },
+        Err(e) => {
+            tracing::warn!("cannot open memory for STT biasing: {e}");
+            return;
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let terms: Vec<BiasTerm> = names
+        .into_iter()
+        .map(|n| n.trim().to_owned())
+        .filter(|n| !n.is_empty() && seen.insert(n.to_lowercase()))
+        .map(BiasTerm::new)
+        .collect();
+    tracing::debug!(count = terms.len(), "refreshed STT vocabulary from memory");
+    vocabulary().set(BiasSource::Memory, &terms);
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Word<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+}
+
+/// Split `text` into words of letters, digits, and inner apostrophes.
+fn words(text: &str) -> Vec<Word<'_>> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        let part_of_word = c.is_alphanumeric() || (c == '\'' && start.is_some());
+        match (part_of_word, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                out.push(Word {
+                    text: text[s..i].trim_end_matches('\''),
+                    start: s,
+                    end: s + text[s..i].trim_end_matches('\'').len(),
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        let text = text[s..].trim_end_matches('\'');
+        out.push(Word {
+            text,
+            start: s,
+            end: s + text.len(),
+        });
+    }
+    out
+}
+
+/// Sound-alike key: letters with similar-sounding ones merged, every vowel
+/// run reduced to `a`, silent-ish `h`/`w` dropped, and repeats collapsed.
+fn sound_key(word: &str) -> String {
+    let lower: Vec<char> = word
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+    let mut key = String::new();
+    for (i, &c) in lower.iter().enumerate() {
+        let next = lower.get(i + 1).copied();
+        let class = match c {
+            'h' | 'w' if i > 0 => continue,
+            'a' | 'e' | 'i' | 'o' | 'u' | 'y' => 'a',
+            'c' if matches!(next, Some('e' | 'i' | 'y')) => 's',
+            'c' | 'k' | 'q' | 'g' => 'k',
+            'b' | 'p' => 'p',
+            'd' | 't' => 't',
+            'f' | 'v' => 'f',
+            's' | 'z' | 'x' => 's',
+            'm' | 'n' => 'n',
+            other => other,
+        };
+        if !key.ends_with(class) {
+            key.push(class);
+        }
+    }
+    key
+}
+
+/// Normalised edit similarity of two keys (1.0 = identical).
+fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            row[j + 1] = substitution.min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+    1.0 - prev[b.len()] as f32 / longest as f32
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn vocab(terms: &[&str]) -> BiasVocabulary {
+        let mut v = BiasVocabulary::new();
+        let terms: Vec<BiasTerm> = terms.iter().map(|t| BiasTerm::new(*t)).collect();
+        v.set(BiasSource::Contacts, &terms);
+        v
+    }
+
+    #[test]
+    fn rewrites_sound_alike_spans() {
+        let v = vocab(&["Saorsa", "Kubernetes", "Siobhan"]);
+        assert_eq!(
+            v.apply("deploy sour sa to cooper netties.").as_deref(),
+            Some("deploy Saorsa to Kubernetes.")
+        );
+        assert_eq!(v.apply("call siobhan").as_deref(), Some("call Siobhan"));
+        assert_eq!(v.apply("what's the weather like"), None);
+    }
+
+    #[test]
+    fn short_terms_only_fix_case() {
+        let v = vocab(&["Ana"]);
+        assert_eq!(v.apply("text ana now").as_deref(), Some("text Ana now"));
+        assert_eq!(v.apply("and a coffee"), None);
+    }
+
+    #[test]
+    fn boost_widens_matching_and_sources_replace() {
+        let mut v = BiasVocabulary::new();
+        v.set(BiasSource::Contacts, &[BiasTerm::new("Siobhan")]);
+        assert_eq!(v.apply("ask sheave on"), None);
+
+        v.set(
+            BiasSource::Contacts,
+            &[BiasTerm {
+                phrase: "Siobhan".to_owned(),
+                boost: 4.0,
+            }],
+        );
+        assert_eq!(v.apply("ask sheave on").as_deref(), Some("ask Siobhan"));
+        assert_eq!(v.apply("Siobhan said hi"), None);
+
+        v.set(BiasSource::Contacts, &[]);
+        assert!(v.is_empty());
+    }
+}
@@ -0,0 +1,343 @@
+//! Transcription of recorded audio files.
+//!
+//! Voice memos and recordings are decoded with symphonia (WAV, MP3, and AAC
+//! or ALAC in M4A), mixed down to mono and resampled to 16 kHz, then fed to
+//! Parakeet in windows of about [`WINDOW_SECS`] seconds. Each window ends at
+//! the quietest moment shortly before its nominal end, so words are rarely
+//! cut in half, and progress is reported after every window.
+
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Mutex;
+
+use parakeet_rs::{TimestampMode, Transcriber};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as DecodeError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tracing::{info, warn};
+
+use super::ParakeetStt;
+use crate::config::{ModelConfig, SpeechConfig, SttConfig};
+use crate::error::{Result, SpeechError};
+
+/// Sample rate the STT model expects.
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+/// Nominal length of each window sent to the model.
+pub const WINDOW_SECS: u32 = 30;
+
+/// Longest recording accepted.
+pub const MAX_FILE_SECS: u32 = 60 * 60;
+
+/// How far before a window's nominal end to look for a pause.
+const PAUSE_SEARCH_SECS: u32 = 5;
+
+/// Frame length used to find the quietest point.
+const FRAME_MS: u32 = 100;
+
+/// A stretch of the transcript with its position in the recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedSegment {
+    pub start_secs: f32,
+    pub end_secs: f32,
+    pub text: String,
+}
+
+/// The transcript of a whole recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileTranscript {
+    pub text: String,
+    pub segments: Vec<TimedSegment>,
+    pub duration_secs: f32,
+}
+
+impl ParakeetStt {
+    /// Transcribe the recording at `path`.
+    ///
+    /// `progress` is called with the fraction done (0.0–1.0) after each
+    /// window.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded, is longer than
+    /// [`MAX_FILE_SECS`], or transcription fails.
+    pub fn transcribe_file(
+        &mut self,
+        path: &Path,
+        progress: &mut dyn FnMut(f32),
+    ) -> Result<FileTranscript> {
+        let (samples, sample_rate) = decode_audio_file(path)?;
+        let samples =
+            crate::audio::playback::resample_linear(&samples, sample_rate, TARGET_SAMPLE_RATE);
+        self.ensure_loaded()?;
+        let model = self
+            .model
+            .as_mut()
+            .ok_or_else(|| SpeechError::Stt("model not initialized".into()))?;
+
+        let duration_secs = samples.len() as f32 / TARGET_SAMPLE_RATE as f32;
+        info!(
+            "transcribing {duration_secs:.1}s recording {}",
+            path.display()
+        );
+        let windows = window_bounds(&samples, TARGET_SAMPLE_RATE);
+        let mut segments = Vec::new();
+        for (done, window) in windows.iter().enumerate() {
+            let offset = window.start as f32 / TARGET_SAMPLE_RATE as f32;
+            let result = model
+                .transcribe_samples(
+                    samples[window.clone()].to_vec(),
+                    TARGET_SAMPLE_RATE,
+                    1, // mono
+                    Some(TimestampMode::Sentences),
+                )
+                .map_err(|e| SpeechError::Stt(format!("transcription failed: {e}")))?;
+            let end = window.end as f32 / TARGET_SAMPLE_RATE as f32;
+            if result.tokens.is_empty() && !result.text.trim().is_empty() {
+                segments.push(TimedSegment {
+                    start_secs: offset,
+                    end_secs: end,
+                    text: result.text.trim().to_owned(),
+                });
+            }
+            segments.extend(
+                result
+                    .tokens
+                    .into_iter()
+                    .filter(|t| !t.text.trim().is_empty())
+                    .map(|t| TimedSegment {
+                        start_secs: offset + t.start,
+                        end_secs: offset + t.end,
+                        text: t.text.trim().to_owned(),
+                    }),
+            );
+            progress((done + 1) as f32 / windows.len() as f32);
+        }
+
+        let mut text = segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if self.contextual_biasing
+            && let Some(biased) = super::bias::vocabulary().apply(&text)
+        {
+            text = biased;
+        }
+        Ok(FileTranscript {
+            text,
+            segments,
+            duration_secs,
+        })
+    }
+}
+
+/// Lazily loaded STT engine for transcribing files, separate from the one
+/// listening to the microphone so a long recording never stalls the
+/// conversation.
+pub struct FileTranscriber {
+    config: SttConfig,
+    models: ModelConfig,
+    stt: Mutex<Option<ParakeetStt>>,
+}
+
+impl FileTranscriber {
+    /// Create a transcriber; the model is loaded on first use.
+    pub fn new(config: SttConfig, models: ModelConfig) -> Self {
+        Self {
+            config,
+            models,
+            stt: Mutex::new(None),
+        }
+    }
+
+    /// Create a transcriber from the user's `config.toml`, falling back to
+    /// defaults.
+    pub fn from_user_config() -> Self {
+        let config = SpeechConfig::from_file(&crate::fae_dirs::config_file()).unwrap_or_default();
+        Self::new(config.stt, config.models)
+    }
+
+    /// Transcribe the recording at `path`; see
+    /// [`ParakeetStt::transcribe_file`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model cannot be loaded or transcription fails.
+    pub fn transcribe(&self, path: &Path, progress: &mut dyn FnMut(f32)) -> Result<FileTranscript> {
+        let mut stt = self.stt.lock().unwrap_or_else(|e| e.into_inner());
+        if stt.is_none() {
+            *stt = Some(ParakeetStt::new(&self.config, &self.models)?);
+        }
+        let stt = stt
+            .as_mut()
+            .ok_or_else(|| SpeechError::Stt("model not initialized".into()))?;
+        stt.transcribe_file(path, progress)
+    }
+}
+
+/// Decode the audio file at `path` to mono samples.
+///
+/// Returns the samples and their sample rate.
+///
+/// # Errors
+///
+/// Returns an error if the format is unsupported, the file cannot be
+/// decoded, or it is longer than [`MAX_FILE_SECS`].
+pub fn decode_audio_file(path: &Path) -> Result<(Vec<f32>, u32)> {
+    let unsupported =
+        |e: DecodeError| SpeechError::Audio(format!("cannot decode {}: {e}", path.display()));
+    let source = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(unsupported)?
+        .format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| SpeechError::Audio(format!("{} has no audio track", path.display())))?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(unsupported)?;
+
+    let mut samples = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(DecodeError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(unsupported(e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(DecodeError::DecodeError(e)) => {
+                warn!("skipping corrupt packet in {}: {e}", path.display());
+                continue;
+            }
+            Err(e) => return Err(unsupported(e)),
+        };
+        let spec = *decoded.spec();
+        let rate = *sample_rate.get_or_insert(spec.rate);
+        let channels = spec.channels.count().max(1);
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buffer.copy_interleaved_ref(decoded);
+        samples.extend(
+            buffer
+                .samples()
+                .chunks(channels)
+                .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32),
+        );
+        if samples.len() > rate as usize * MAX_FILE_SECS as usize {
+            return Err(SpeechError::Audio(format!(
+                "{} is longer than {} minutes",
+                path.display(),
+                MAX_FILE_SECS / 60
+            )));
+        }
+    }
+    let sample_rate = sample_rate
+        .ok_or_else(|| SpeechError::Audio(format!("{} has no audio", path.display())))?;
+    Ok((samples, sample_rate))
+}
+
+/// Split `samples` into windows of about [`WINDOW_SECS`], each ending at
+/// the quietest [`FRAME_MS`] frame in the last [`PAUSE_SEARCH_SECS`].
+pub fn window_bounds(samples: &[f32], sample_rate: u32) -> Vec<Range<usize>> {
+    let window = (sample_rate * WINDOW_SECS) as usize;
+    let search = (sample_rate * PAUSE_SEARCH_SECS) as usize;
+    let frame = ((sample_rate * FRAME_MS / 1000) as usize).max(1);
+    let mut bounds = Vec::new();
+    let mut start = 0;
+    while samples.len() - start > window {
+        let nominal_end = start + window;
+        let energy = |at: usize| {
+            samples[at..(at + frame).min(samples.len())]
+                .iter()
+                .map(|s| s * s)
+                .sum::<f32>()
+        };
+        let end = (nominal_end - search..nominal_end)
+            .step_by(frame)
+            .min_by(|&a, &b| energy(a).total_cmp(&energy(b)))
+            .map_or(nominal_end, |quietest| quietest + frame / 2);
+        bounds.push(start..end);
+        start = end;
+    }
+    if start < samples.len() || bounds.is_empty() {
+        bounds.push(start..samples.len());
+    }
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn windows_end_in_pauses() {
+        let rate = 1_000;
+        // 70 s of tone with pauses 28-28.5 s and 57.5-58 s in.
+        let samples: Vec<f32> = (0..70 * rate)
+            .map(|i| {
+                let t = i as f32 / rate as f32;
+                let pause = (28.0..28.5).contains(&t) || (57.5..58.0).contains(&t);
+                if pause { 0.0 } else { (t * 440.0).sin() }
+            })
+            .collect();
+        let bounds = window_bounds(&samples, rate as u32);
+        assert_eq!(bounds.len(), 3);
+        assert!((28_000..28_500).contains(&bounds[0].end), "{bounds:?}");
+        assert!((57_500..58_000).contains(&bounds[1].end), "{bounds:?}");
+        assert_eq!(bounds[2].end, samples.len());
+        assert!(bounds.windows(2).all(|w| w[0].end == w[1].start));
+
+        assert_eq!(window_bounds(&samples[..500], rate as u32), vec![0..500]);
+        assert_eq!(window_bounds(&[], rate as u32), vec![0..0]);
+    }
+
+    #[test]
+    fn decodes_wav_to_mono() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("memo.wav");
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 8_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).expect("create wav");
+        for _ in 0..800 {
+            writer.write_sample(i16::MAX / 2).expect("left");
+            writer.write_sample(0_i16).expect("right");
+        }
+        writer.finalize().expect("finalize");
+
+        let (samples, rate) = decode_audio_file(&path).expect("decode");
+        assert_eq!((samples.len(), rate), (800, 8_000));
+        assert!((samples[0] - 0.25).abs() < 0.01, "{}", samples[0]);
+
+        std::fs::write(dir.path().join("notes.mp3"), b"not audio").expect("write");
+        assert!(decode_audio_file(&dir.path().join("notes.mp3")).is_err());
+    }
+}
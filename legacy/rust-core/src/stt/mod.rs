@@ -1,7 +1,12 @@
 //! Speech-to-text using NVIDIA Parakeet TDT.
 //!
 //! Uses `parakeet-rs` with the `ParakeetTDT` model for multilingual
-//! batch transcription with punctuation support.
+//! batch transcription with punctuation support. Transcripts are then
+//! biased toward known names and terms by [`bias`]. Recorded audio files
+//! are transcribed by [`file`].
+
+pub mod bias;
+pub mod file;
 
 use crate::config::{ModelConfig, SttConfig};
 use crate::error::{Result, SpeechError};
@@ -17,6 +22,7 @@ pub struct ParakeetStt {
     model: Option<ParakeetTDT>,
     model_id: String,
     model_manager: ModelManager,
+    contextual_biasing: bool,
 }
 
 /// Model files required by Parakeet TDT.
@@ -37,10 +43,18 @@ impl ParakeetStt {
         let model_manager = ModelManager::new(model_config)?;
         info!("STT configured with model: {}", config.model_id);
 
+        let custom: Vec<bias::BiasTerm> = config
+            .vocabulary
+            .iter()
+            .map(|phrase| bias::BiasTerm::new(phrase.as_str()))
+            .collect();
+        bias::vocabulary().set(bias::BiasSource::Custom, &custom);
+
         Ok(Self {
             model: None,
             model_id: config.model_id.clone(),
             model_manager,
+            contextual_biasing: config.contextual_biasing,
         })
     }
 
@@ -101,16 +115,26 @@ impl ParakeetStt {
             result.text
         );
 
+        let mut text = result.text;
+        if self.contextual_biasing
+            && let Some(biased) = bias::vocabulary().apply(&text)
+        {
+            info!("biased transcript: \"{biased}\"");
+            text = biased;
+        }
+
         let voiceprint = voiceprint::compute_voiceprint(&segment.samples, segment.sample_rate).ok();
 
         Ok(Transcription {
-            text: result.text,
-            is_final: true,
+            text,
+            // A segment cut at a provisional endpoint may still be revised.
+            is_final: segment.speculation.is_none(),
             voiceprint,
             audio_rms: None,
             audio_duration_secs: None,
             audio_captured_at: segment.started_at,
             transcribed_at,
+            speculation: segment.speculation.clone(),
         })
     }
 
@@ -0,0 +1,206 @@
+//! Storage backends for sync.
+//!
+//! A backend only stores opaque, already-sealed blobs by name: it never sees
+//! plaintext or the sync key, so any storage the user has can serve. Two are
+//! provided: [`FolderBackend`] for a folder another service replicates
+//! (iCloud Drive, Dropbox, a network share) and [`WebDavBackend`].
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use base64::Engine as _;
+
+use crate::config::{SyncBackendKind, SyncConfig};
+use crate::credentials::CredentialManager;
+use crate::error::{Result, SpeechError};
+
+/// Largest blob read from a backend.
+const MAX_BLOB_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Dumb blob storage shared by the user's devices.
+pub trait SyncBackend: Send + Sync {
+    /// Read blob `name`, or `None` if it does not exist yet.
+    fn get(&self, name: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Write blob `name`, replacing it.
+    fn put(&self, name: &str, data: &[u8]) -> Result<()>;
+}
+
+/// Blobs stored as files in a folder.
+#[derive(Debug, Clone)]
+pub struct FolderBackend {
+    root: PathBuf,
+}
+
+impl FolderBackend {
+    /// Store blobs in `root`, created on first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl SyncBackend for FolderBackend {
+    fn get(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        match std::fs::read(self.root.join(name)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn put(&self, name: &str, data: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(&self.root)?;
+        // Write beside the target and rename, so a replicating service never
+        // uploads a half-written file.
+        let tmp = self.root.join(format!(".{name}.tmp"));
+        std::fs::write(&tmp, data)?;
+        std::fs::rename(&tmp, self.root.join(name))?;
+        Ok(())
+    }
+}
+
+/// Blobs stored in a WebDAV collection with `GET` and `PUT`.
+pub struct WebDavBackend {
+    base_url: String,
+    authorization: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl std::fmt::Debug for WebDavBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebDavBackend")
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebDavBackend {
+    /// Store blobs in the collection at `url`, signing in with `username`
+    /// and `password` when given.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpeechError::Sync`] if `url` is not `https` and not on this
+    /// machine, or offline mode blocks it.
+    pub fn new(url: &str, username: Option<&str>, password: Option<&str>) -> Result<Self> {
+        let url = url.trim();
+        if !url.starts_with("https://") && !crate::offline::is_loopback_url(url) {
+            return Err(SpeechError::Sync(
+                "WebDAV sync needs an https:// URL".to_owned(),
+            ));
+        }
+        crate::offline::ensure_url_allowed("WebDAV sync", url)
+            .map_err(|e| SpeechError::Sync(e.to_string()))?;
+        let authorization = username.map(|user| {
+            let credentials = format!("{user}:{}", password.unwrap_or_default());
+            format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(credentials)
+            )
+        });
+        Ok(Self {
+            base_url: url.trim_end_matches('/').to_owned(),
+            authorization,
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(10))
+                .timeout_read(Duration::from_secs(60))
+                .timeout_write(Duration::from_secs(60))
+                .build(),
+        })
+    }
+
+    fn request(&self, method: &str, name: &str) -> ureq::Request {
+        let request = self
+            .agent
+            .request(method, &format!("{}/{name}", self.base_url));
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+}
+
+impl SyncBackend for WebDavBackend {
+    fn get(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        match self.request("GET", name).call() {
+            Ok(response) => {
+                let mut data = Vec::new();
+                std::io::Read::read_to_end(
+                    &mut std::io::Read::take(response.into_reader(), MAX_BLOB_BYTES),
+                    &mut data,
+                )?;
+                Ok(Some(data))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(SpeechError::Sync(format!("WebDAV GET {name} failed: {e}"))),
+        }
+    }
+
+    fn put(&self, name: &str, data: &[u8]) -> Result<()> {
+        self.request("PUT", name)
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(data)
+            .map(|_| ())
+            .map_err(|e| SpeechError::Sync(format!("WebDAV PUT {name} failed: {e}")))
+    }
+}
+
+/// The backend `sync` is set up for.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Sync`] if the backend's folder or URL is missing,
+/// or its password cannot be read.
+pub fn open_backend(
+    sync: &SyncConfig,
+    manager: &dyn CredentialManager,
+) -> Result<Box<dyn SyncBackend>> {
+    match sync.backend {
+        SyncBackendKind::Folder => {
+            let folder = sync.folder.as_ref().ok_or_else(|| {
+                SpeechError::Sync("sync.folder is not set for the folder backend".to_owned())
+            })?;
+            Ok(Box::new(FolderBackend::new(folder)))
+        }
+        SyncBackendKind::WebDav => {
+            let url = sync.webdav_url.as_deref().ok_or_else(|| {
+                SpeechError::Sync("sync.webdav_url is not set for the WebDAV backend".to_owned())
+            })?;
+            let password = manager
+                .retrieve(&sync.webdav_password)
+                .map_err(|e| SpeechError::Sync(format!("cannot read WebDAV password: {e}")))?;
+            Ok(Box::new(WebDavBackend::new(
+                url,
+                sync.webdav_username.as_deref(),
+                password.as_deref(),
+            )?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn folder_backend_round_trips_blobs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backend = FolderBackend::new(dir.path().join("iCloud/Fae"));
+        assert!(backend.get("state").expect("get").is_none());
+        backend.put("state", b"sealed").expect("put");
+        backend.put("state", b"sealed again").expect("put");
+        assert_eq!(
+            backend.get("state").expect("get").as_deref(),
+            Some(&b"sealed again"[..])
+        );
+
+        assert!(WebDavBackend::new("http://dav.example.com/fae", None, None).is_err());
+        assert!(WebDavBackend::new("http://localhost:8080/fae", None, None).is_ok());
+    }
+}
@@ -0,0 +1,347 @@
+//! This device's synced items: reading them and applying remote changes.
+//!
+//! Keys name the item: `config` (the shared config sections),
+//! `preferences`, `skill/<relative path>` and `memory/<record id>`.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::warn;
+
+use crate::config::{SpeechConfig, SyncConfig};
+use crate::error::{Result, SpeechError};
+use crate::memory::{MemoryRecord, SqliteMemoryError, SqliteMemoryRepository};
+
+use super::SyncPaths;
+use super::merge::SyncEntry;
+
+pub(super) const CONFIG_KEY: &str = "config";
+pub(super) const PREFERENCES_KEY: &str = "preferences";
+pub(super) const SKILL_PREFIX: &str = "skill/";
+pub(super) const MEMORY_PREFIX: &str = "memory/";
+
+/// Config sections shared between devices. The rest — audio devices, model
+/// paths, keychain references, permissions, sync itself — stays per device.
+pub const SHARED_CONFIG_KEYS: &[&str] = &[
+    "tts",
+    "intelligence",
+    "conversation",
+    "barge_in",
+    "voice_response",
+    "hotword",
+    "canvas",
+    "theme",
+    "accessibility",
+    "captions",
+    "content_filter",
+    "journal",
+    "turn_context",
+    "user_name",
+    "user_email",
+    "user_phone",
+    "family_relationships",
+    "language",
+];
+
+/// Skill files larger than this are not synced.
+const MAX_SKILL_FILE_BYTES: u64 = 512 * 1024;
+
+/// Which kinds of item a sync covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncScope {
+    pub config: bool,
+    pub preferences: bool,
+    pub skills: bool,
+    pub memories: bool,
+}
+
+impl SyncScope {
+    /// The items `sync` is set up to sync.
+    pub fn from_config(sync: &SyncConfig) -> Self {
+        Self {
+            config: sync.config,
+            preferences: sync.preferences,
+            skills: sync.skills,
+            memories: sync.memories,
+        }
+    }
+
+    /// Whether item `key` is covered.
+    pub fn contains(&self, key: &str) -> bool {
+        if key == CONFIG_KEY {
+            self.config
+        } else if key == PREFERENCES_KEY {
+            self.preferences
+        } else if key.starts_with(SKILL_PREFIX) {
+            self.skills
+        } else if key.starts_with(MEMORY_PREFIX) {
+            self.memories
+        } else {
+            false
+        }
+    }
+}
+
+/// The shared sections of `config` as TOML.
+pub fn shared_config(config: &SpeechConfig) -> Result<String> {
+    let mut table = config_table(config)?;
+    table.retain(|key, _| SHARED_CONFIG_KEYS.contains(&key.as_str()));
+    toml::to_string(&table).map_err(|e| SpeechError::Sync(format!("config encode failed: {e}")))
+}
+
+/// Replace the shared sections of `config` with those in `shared`.
+pub fn apply_shared_config(config: &mut SpeechConfig, shared: &str) -> Result<()> {
+    let incoming: toml::Table = toml::from_str(shared)
+        .map_err(|e| SpeechError::Sync(format!("synced config is malformed: {e}")))?;
+    let mut table = config_table(config)?;
+    table.retain(|key, _| !SHARED_CONFIG_KEYS.contains(&key.as_str()));
+    for (key, value) in incoming {
+        if SHARED_CONFIG_KEYS.contains(&key.as_str()) {
+            table.insert(key, value);
+        }
+    }
+    *config = toml::Value::Table(table)
+        .try_into()
+        .map_err(|e| SpeechError::Sync(format!("synced config is malformed: {e}")))?;
+    Ok(())
+}
+
+fn config_table(config: &SpeechConfig) -> Result<toml::Table> {
+    match toml::Value::try_from(config) {
+        Ok(toml::Value::Table(table)) => Ok(table),
+        Ok(_) => Err(SpeechError::Sync("config is not a table".to_owned())),
+        Err(e) => Err(SpeechError::Sync(format!("config encode failed: {e}"))),
+    }
+}
+
+/// This device's items in `scope`. `config` is `None` when the config is
+/// not synced this time.
+pub(super) fn collect(
+    scope: SyncScope,
+    config: Option<&SpeechConfig>,
+    paths: &SyncPaths,
+    device: &str,
+) -> Result<BTreeMap<String, SyncEntry>> {
+    let mut items = BTreeMap::new();
+    if scope.config
+        && let Some(config) = config
+    {
+        let modified_at = modified_secs(&paths.config_file).unwrap_or_else(now_secs);
+        items.insert(
+            CONFIG_KEY.to_owned(),
+            SyncEntry::new(shared_config(config)?, modified_at, device),
+        );
+    }
+    if scope.preferences
+        && let Some(entry) = file_entry(&paths.preferences_file, device)
+    {
+        items.insert(PREFERENCES_KEY.to_owned(), entry);
+    }
+    if scope.skills {
+        for path in skill_files(&paths.skills_dir) {
+            let Some(entry) = file_entry(&path, device) else {
+                continue;
+            };
+            let Ok(relative) = path.strip_prefix(&paths.skills_dir) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            items.insert(format!("{SKILL_PREFIX}{relative}"), entry);
+        }
+    }
+    if scope.memories {
+        let repo = open_memory(&paths.memory_root)?;
+        let records = repo.list_records_filtered(true).map_err(memory_error)?;
+        for record in records {
+            let content = serde_json::to_string(&record)
+                .map_err(|e| SpeechError::Sync(format!("memory encode failed: {e}")))?;
+            items.insert(
+                format!("{MEMORY_PREFIX}{}", record.id),
+                SyncEntry::new(content, record.updated_at, device),
+            );
+        }
+    }
+    Ok(items)
+}
+
+/// Apply remote changes: key → new content, `None` to delete. The config is
+/// only changed when `config` is given. Returns whether the config changed.
+pub(super) fn apply(
+    changes: &BTreeMap<String, Option<String>>,
+    config: Option<&mut SpeechConfig>,
+    paths: &SyncPaths,
+) -> Result<bool> {
+    let mut config_changed = false;
+    if let Some(config) = config
+        && let Some(Some(shared)) = changes.get(CONFIG_KEY)
+    {
+        apply_shared_config(config, shared)?;
+        config_changed = true;
+    }
+    if let Some(change) = changes.get(PREFERENCES_KEY) {
+        write_file(&paths.preferences_file, change.as_deref())?;
+    }
+
+    let mut repo = None;
+    for (key, change) in changes {
+        if let Some(relative) = key.strip_prefix(SKILL_PREFIX) {
+            let Some(path) = contained_path(&paths.skills_dir, relative) else {
+                warn!(key, "ignoring synced skill outside the skills folder");
+                continue;
+            };
+            write_file(&path, change.as_deref())?;
+        } else if let Some(id) = key.strip_prefix(MEMORY_PREFIX) {
+            if repo.is_none() {
+                repo = Some(open_memory(&paths.memory_root)?);
+            }
+            let Some(repo) = repo.as_ref() else {
+                continue;
+            };
+            match change {
+                Some(content) => {
+                    let record: MemoryRecord = serde_json::from_str(content).map_err(|e| {
+                        SpeechError::Sync(format!("synced memory is malformed: {e}"))
+                    })?;
+                    repo.upsert_synced_record(&record).map_err(memory_error)?;
+                }
+                None => match repo.forget_hard_record(id, "deleted on another device") {
+                    Ok(()) | Err(SqliteMemoryError::NotFound(_)) => {}
+                    Err(e) => return Err(memory_error(e)),
+                },
+            }
+        }
+    }
+    Ok(config_changed)
+}
+
+fn open_memory(root: &Path) -> Result<SqliteMemoryRepository> {
+    SqliteMemoryRepository::new(root).map_err(memory_error)
+}
+
+fn memory_error(e: SqliteMemoryError) -> SpeechError {
+    SpeechError::Sync(format!("memory store: {e}"))
+}
+
+/// `path` as an entry, if it is a readable text file within the size limit.
+fn file_entry(path: &Path, device: &str) -> Option<SyncEntry> {
+    let meta = std::fs::metadata(path).ok()?;
+    if !meta.is_file() || meta.len() > MAX_SKILL_FILE_BYTES {
+        return None;
+    }
+    let content = std::fs::read_to_string(path).ok()?;
+    let modified_at = modified_secs(path).unwrap_or_else(now_secs);
+    Some(SyncEntry::new(content, modified_at, device))
+}
+
+/// Files under `dir`, skipping hidden ones (and so `.git`, `.venv`).
+fn skill_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() => pending.push(entry.path()),
+                Ok(kind) if kind.is_file() => files.push(entry.path()),
+                _ => {}
+            }
+        }
+    }
+    files
+}
+
+/// `root` joined with `relative`, if that stays inside `root`.
+fn contained_path(root: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative);
+    let plain = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)));
+    (plain && !relative.as_os_str().is_empty()).then(|| root.join(relative))
+}
+
+/// Write `content` to `path`, or delete it for `None`.
+fn write_file(path: &Path, content: Option<&str>) -> Result<()> {
+    match content {
+        Some(content) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+        }
+        None => match std::fs::remove_file(path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+    Ok(())
+}
+
+fn modified_secs(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+pub(super) fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn shared_config_leaves_device_sections_alone() {
+        let mut desk = SpeechConfig::default();
+        desk.user_name = Some("Ada".to_owned());
+        desk.tts.speed = 1.2;
+        desk.offline_mode = true;
+        let shared = shared_config(&desk).expect("shared");
+        assert!(shared.contains("Ada"));
+        assert!(!shared.contains("offline_mode"));
+
+        let mut laptop = SpeechConfig::default();
+        laptop.memory.root_dir = PathBuf::from("/laptop/fae");
+        apply_shared_config(&mut laptop, &shared).expect("apply");
+        assert_eq!(laptop.user_name.as_deref(), Some("Ada"));
+        assert_eq!(laptop.tts.speed, 1.2);
+        assert!(!laptop.offline_mode);
+        assert_eq!(laptop.memory.root_dir, PathBuf::from("/laptop/fae"));
+    }
+
+    #[test]
+    fn synced_paths_stay_inside_the_skills_folder() {
+        let root = Path::new("/skills");
+        assert_eq!(
+            contained_path(root, "weather/SKILL.md"),
+            Some(PathBuf::from("/skills/weather/SKILL.md"))
+        );
+        assert!(contained_path(root, "../config.toml").is_none());
+        assert!(contained_path(root, "/etc/passwd").is_none());
+        assert!(contained_path(root, "").is_none());
+
+        let scope = SyncScope {
+            config: false,
+            preferences: true,
+            skills: true,
+            memories: false,
+        };
+        assert!(scope.contains("skill/weather/SKILL.md"));
+        assert!(!scope.contains("memory/m1"));
+        assert!(!scope.contains(CONFIG_KEY));
+    }
+}
@@ -0,0 +1,282 @@
+//! Three-way merge of synced items.
+//!
+//! Every synced item has a key (`config`, `skill/<path>`, `memory/<id>`, …)
+//! and text content. The backend holds a [`SyncDocument`] with the latest
+//! entry for each key; each device remembers the content hash of every key
+//! as of its last sync (the base). Comparing local, remote and base tells
+//! which side changed:
+//!
+//! - only one side changed — that side's version is taken;
+//! - both changed to different content — a conflict: the later edit wins
+//!   (ties go to the higher device id, so every device picks the same
+//!   winner) and the conflict is reported;
+//! - a deletion is a change like any other, kept as a tombstone entry.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+/// Document format version; documents from other versions are refused.
+pub const DOCUMENT_VERSION: u32 = 1;
+
+/// The synced state stored (sealed) on the backend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncDocument {
+    pub version: u32,
+    /// Random id given when the document is first created. A device that
+    /// finds a different id (the backend was reset or switched) starts from
+    /// an empty base, so nothing is taken as deleted.
+    pub id: String,
+    /// Latest entry per key.
+    pub entries: BTreeMap<String, SyncEntry>,
+    /// Device id → Unix epoch seconds of its last sync.
+    #[serde(default)]
+    pub devices: BTreeMap<String, u64>,
+}
+
+impl SyncDocument {
+    /// An empty document with id `id`.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            version: DOCUMENT_VERSION,
+            id: id.into(),
+            entries: BTreeMap::new(),
+            devices: BTreeMap::new(),
+        }
+    }
+}
+
+/// One version of a synced item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncEntry {
+    /// Unix epoch seconds of the edit.
+    pub modified_at: u64,
+    /// Device that made the edit.
+    pub device: String,
+    /// The item's content, or `None` once deleted.
+    pub content: Option<String>,
+}
+
+impl SyncEntry {
+    /// A present item.
+    pub fn new(content: impl Into<String>, modified_at: u64, device: &str) -> Self {
+        Self {
+            modified_at,
+            device: device.to_owned(),
+            content: Some(content.into()),
+        }
+    }
+
+    /// Content hash, or `None` for a deletion.
+    pub fn hash(&self) -> Option<String> {
+        self.content.as_deref().map(content_hash)
+    }
+}
+
+/// Hash identifying an item's content.
+pub fn content_hash(content: &str) -> String {
+    blake3::hash(content.as_bytes()).to_hex().to_string()
+}
+
+/// Both devices changed an item; `winner` says whose edit was kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncConflict {
+    pub key: String,
+    /// Device whose edit was kept.
+    pub winner: String,
+    /// Device whose edit was dropped.
+    pub loser: String,
+}
+
+/// What a merge decided.
+#[derive(Debug, Default)]
+pub struct MergeOutcome {
+    /// Remote changes to apply locally: key → new content, `None` to delete.
+    pub apply: BTreeMap<String, Option<String>>,
+    /// Keys whose local version was written to the document.
+    pub pushed: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// Merge `local` (this device's items in scope, by key) into `document`.
+///
+/// Only keys `in_scope` are considered; others are left untouched. `base`
+/// holds the hashes as of the last sync and is updated to the merged state.
+/// `now` stamps local deletions.
+pub fn merge(
+    document: &mut SyncDocument,
+    local: &BTreeMap<String, SyncEntry>,
+    base: &mut BTreeMap<String, String>,
+    in_scope: &dyn Fn(&str) -> bool,
+    device: &str,
+    now: u64,
+) -> MergeOutcome {
+    let keys: BTreeSet<String> = local
+        .keys()
+        .chain(document.entries.keys())
+        .chain(base.keys())
+        .filter(|key| in_scope(key))
+        .cloned()
+        .collect();
+
+    let mut outcome = MergeOutcome::default();
+    for key in keys {
+        let local_entry = local.get(&key);
+        let local_hash = local_entry.and_then(SyncEntry::hash);
+        let remote_entry = document.entries.get(&key);
+        let remote_hash = remote_entry.and_then(SyncEntry::hash);
+        let base_hash = base.get(&key).cloned();
+
+        if local_hash == remote_hash {
+            // Already in sync (or gone on both sides).
+        } else if local_hash == base_hash {
+            // Only the remote side changed.
+            let content = remote_entry.and_then(|e| e.content.clone());
+            outcome.apply.insert(key.clone(), content);
+        } else if remote_hash == base_hash {
+            // Only this device changed.
+            push(document, &key, local_entry, device, now);
+            outcome.pushed.push(key.clone());
+        } else {
+            let local_time = local_entry.map_or(now, |e| e.modified_at);
+            let remote = remote_entry.map_or((0, ""), |e| (e.modified_at, e.device.as_str()));
+            let local_wins = (local_time, device) >= remote;
+            let remote_device = remote.1.to_owned();
+            if local_wins {
+                push(document, &key, local_entry, device, now);
+                outcome.pushed.push(key.clone());
+                outcome.conflicts.push(SyncConflict {
+                    key: key.clone(),
+                    winner: device.to_owned(),
+                    loser: remote_device,
+                });
+            } else {
+                let content = remote_entry.and_then(|e| e.content.clone());
+                outcome.apply.insert(key.clone(), content);
+                outcome.conflicts.push(SyncConflict {
+                    key: key.clone(),
+                    winner: remote_device,
+                    loser: device.to_owned(),
+                });
+            }
+        }
+
+        match document.entries.get(&key).and_then(SyncEntry::hash) {
+            Some(hash) => base.insert(key, hash),
+            None => base.remove(&key),
+        };
+    }
+    document.devices.insert(device.to_owned(), now);
+    outcome
+}
+
+/// Write this device's version of `key` to the document.
+fn push(document: &mut SyncDocument, key: &str, local: Option<&SyncEntry>, device: &str, now: u64) {
+    let entry = local.cloned().unwrap_or_else(|| SyncEntry {
+        modified_at: now,
+        device: device.to_owned(),
+        content: None,
+    });
+    document.entries.insert(key.to_owned(), entry);
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn all(_: &str) -> bool {
+        true
+    }
+
+    #[test]
+    fn changes_flow_both_ways_and_deletions_propagate() {
+        let mut document = SyncDocument::new("doc");
+        let mut desk_base = BTreeMap::new();
+        let mut laptop_base = BTreeMap::new();
+
+        // The desktop syncs first.
+        let desk = BTreeMap::from([
+            (
+                "preferences".to_owned(),
+                SyncEntry::new("brief", 10, "desk"),
+            ),
+            ("skill/a.md".to_owned(), SyncEntry::new("A", 10, "desk")),
+        ]);
+        let outcome = merge(&mut document, &desk, &mut desk_base, &all, "desk", 20);
+        assert_eq!(outcome.pushed.len(), 2);
+        assert!(outcome.apply.is_empty());
+
+        // The laptop receives both and adds a memory.
+        let laptop = BTreeMap::from([("memory/m1".to_owned(), SyncEntry::new("{}", 30, "lap"))]);
+        let outcome = merge(&mut document, &laptop, &mut laptop_base, &all, "lap", 40);
+        assert_eq!(outcome.pushed, vec!["memory/m1"]);
+        assert_eq!(outcome.apply["preferences"].as_deref(), Some("brief"));
+        assert_eq!(outcome.apply.len(), 2);
+
+        // The desktop deletes the skill and picks up the memory.
+        let desk = BTreeMap::from([(
+            "preferences".to_owned(),
+            SyncEntry::new("brief", 10, "desk"),
+        )]);
+        let outcome = merge(&mut document, &desk, &mut desk_base, &all, "desk", 50);
+        assert_eq!(outcome.pushed, vec!["skill/a.md"]);
+        assert_eq!(outcome.apply.keys().collect::<Vec<_>>(), vec!["memory/m1"]);
+        assert!(document.entries["skill/a.md"].content.is_none());
+        assert!(!desk_base.contains_key("skill/a.md"));
+
+        // The laptop deletes it too.
+        let laptop = BTreeMap::from([
+            (
+                "preferences".to_owned(),
+                SyncEntry::new("brief", 10, "desk"),
+            ),
+            ("skill/a.md".to_owned(), SyncEntry::new("A", 10, "desk")),
+            ("memory/m1".to_owned(), SyncEntry::new("{}", 30, "lap")),
+        ]);
+        let outcome = merge(&mut document, &laptop, &mut laptop_base, &all, "lap", 60);
+        assert_eq!(outcome.apply.get("skill/a.md"), Some(&None));
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(document.devices.len(), 2);
+    }
+
+    #[test]
+    fn concurrent_edits_keep_the_later_one() {
+        let mut document = SyncDocument::new("doc");
+        let mut desk_base = BTreeMap::new();
+        let mut laptop_base = BTreeMap::new();
+        let start = BTreeMap::from([("config".to_owned(), SyncEntry::new("v1", 10, "desk"))]);
+        merge(&mut document, &start, &mut desk_base, &all, "desk", 10);
+        merge(&mut document, &start, &mut laptop_base, &all, "lap", 10);
+
+        let desk = BTreeMap::from([("config".to_owned(), SyncEntry::new("desk", 100, "desk"))]);
+        merge(&mut document, &desk, &mut desk_base, &all, "desk", 110);
+
+        // The laptop edited earlier, so its edit loses.
+        let laptop = BTreeMap::from([("config".to_owned(), SyncEntry::new("lap", 90, "lap"))]);
+        let outcome = merge(&mut document, &laptop, &mut laptop_base, &all, "lap", 120);
+        assert_eq!(outcome.apply["config"].as_deref(), Some("desk"));
+        assert_eq!(
+            outcome.conflicts,
+            vec![SyncConflict {
+                key: "config".to_owned(),
+                winner: "desk".to_owned(),
+                loser: "lap".to_owned(),
+            }]
+        );
+        assert_eq!(laptop_base["config"], content_hash("desk"));
+
+        // Keys out of scope are left alone.
+        let outcome = merge(
+            &mut document,
+            &BTreeMap::new(),
+            &mut laptop_base,
+            &|key: &str| key.starts_with("memory/"),
+            "lap",
+            130,
+        );
+        assert!(outcome.apply.is_empty() && outcome.pushed.is_empty());
+        assert!(document.entries["config"].content.is_some());
+    }
+}
@@ -0,0 +1,354 @@
+//! End-to-end encrypted sync of settings and memory between devices.
+//!
+//! Fae syncs the shared parts of the config, user skills, memories and
+//! learned preferences through storage the user already has: a replicated
+//! folder (iCloud Drive, Dropbox) or a WebDAV server. Everything is kept in
+//! one [`SyncDocument`], sealed with a key only the user's devices hold —
+//! the key is generated on the first device ([`create_key`]) and entered on
+//! the others ([`set_key`]); the backend sees only ciphertext.
+//!
+//! A sync ([`sync_now`]) reads and opens the document, three-way merges it
+//! with this device's items (see [`merge`]), applies remote changes locally
+//! and writes the document back if this device changed anything. Each device
+//! keeps its merge base and id in a small state file next to its data.
+//!
+//! Only one sync runs at a time in a process. Two devices writing at the
+//! same instant can still overwrite one another's push; the overwritten
+//! device re-pushes its changes on its next sync.
+
+pub mod backend;
+pub mod items;
+pub mod merge;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use rand::RngCore as _;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::config::{SpeechConfig, SyncConfig};
+use crate::credentials::{CredentialManager, secure_clear};
+use crate::error::{Result, SpeechError};
+use crate::privacy::cipher::DataCipher;
+
+pub use backend::{FolderBackend, SyncBackend, WebDavBackend, open_backend};
+pub use items::{SHARED_CONFIG_KEYS, SyncScope};
+pub use merge::{SyncConflict, SyncDocument, SyncEntry};
+
+/// Keychain account name for the sync key.
+pub const SYNC_KEY_ACCOUNT: &str = "fae.sync.key";
+
+/// Name of the sealed document on the backend.
+pub const DOCUMENT_NAME: &str = "fae-sync.sealed";
+
+const KEY_LEN: usize = 32;
+
+static RUNNING: Mutex<()> = Mutex::new(());
+static LAST_RUN: Mutex<LastRun> = Mutex::new(LastRun {
+    error: None,
+    report: None,
+});
+
+struct LastRun {
+    error: Option<String>,
+    report: Option<SyncReport>,
+}
+
+/// Where this device's synced items and sync state live.
+#[derive(Debug, Clone)]
+pub struct SyncPaths {
+    /// This device's id and merge base.
+    pub state_file: PathBuf,
+    /// The config file, whose modification time dates config edits.
+    pub config_file: PathBuf,
+    pub preferences_file: PathBuf,
+    pub skills_dir: PathBuf,
+    pub memory_root: PathBuf,
+}
+
+impl SyncPaths {
+    /// The standard locations, with memories under `memory_root`.
+    pub fn for_memory_root(memory_root: &Path) -> Self {
+        Self {
+            state_file: crate::fae_dirs::data_dir().join("sync").join("state.json"),
+            config_file: crate::fae_dirs::config_file(),
+            preferences_file: crate::fae_dirs::preferences_file(),
+            skills_dir: crate::skills::skills_dir(),
+            memory_root: memory_root.to_path_buf(),
+        }
+    }
+}
+
+/// What one sync did.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Items sent to the other devices.
+    pub pushed: usize,
+    /// Items received from them.
+    pub pulled: usize,
+    /// Items edited on both sides since the last sync.
+    pub conflicts: Vec<SyncConflict>,
+    /// Devices that have synced with the document.
+    pub devices: usize,
+    /// Whether the shared config was replaced.
+    pub config_changed: bool,
+}
+
+/// Sync state for display.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub enabled: bool,
+    pub key_set: bool,
+    /// Whether a sync is running now.
+    pub syncing: bool,
+    pub device_id: Option<String>,
+    /// Unix epoch seconds of the last successful sync.
+    pub last_sync_at: Option<u64>,
+    /// Error from the last sync in this process, if it failed.
+    pub last_error: Option<String>,
+    pub last_report: Option<SyncReport>,
+}
+
+/// This device's view of the document as of its last sync.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LocalState {
+    device_id: String,
+    document_id: String,
+    /// Content hash per key as of the last sync.
+    base: BTreeMap<String, String>,
+    last_sync_at: Option<u64>,
+}
+
+impl LocalState {
+    fn load(path: &Path) -> Self {
+        std::fs::read(path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|e| SpeechError::Sync(format!("state encode failed: {e}")))?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+}
+
+/// Generate a new sync key, store it in the keychain and point `sync.key`
+/// at it. Returns the key, encoded for entering on the other devices.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Sync`] if the keychain write fails.
+pub fn create_key(sync: &mut SyncConfig, manager: &dyn CredentialManager) -> Result<String> {
+    let mut key = [0u8; KEY_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut key);
+    let encoded = URL_SAFE_NO_PAD.encode(key);
+    key.fill(0);
+    store_key(sync, manager, &encoded)?;
+    Ok(encoded)
+}
+
+/// Store a sync key generated on another device.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Sync`] if `encoded` is not a sync key or the
+/// keychain write fails.
+pub fn set_key(
+    sync: &mut SyncConfig,
+    manager: &dyn CredentialManager,
+    encoded: &str,
+) -> Result<()> {
+    let encoded = encoded.trim();
+    let mut bytes = decode_key(encoded)?;
+    bytes.fill(0);
+    store_key(sync, manager, encoded)
+}
+
+fn store_key(sync: &mut SyncConfig, manager: &dyn CredentialManager, encoded: &str) -> Result<()> {
+    sync.key = manager
+        .store(SYNC_KEY_ACCOUNT, encoded)
+        .map_err(|e| SpeechError::Sync(format!("cannot store sync key: {e}")))?;
+    Ok(())
+}
+
+fn decode_key(encoded: &str) -> Result<Vec<u8>> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|_| SpeechError::Sync("sync key is malformed".to_owned()))?;
+    if bytes.len() != KEY_LEN {
+        return Err(SpeechError::Sync(
+            "sync key has the wrong length".to_owned(),
+        ));
+    }
+    Ok(bytes)
+}
+
+fn load_key(sync: &SyncConfig, manager: &dyn CredentialManager) -> Result<DataCipher> {
+    let mut encoded = manager
+        .retrieve(&sync.key)
+        .map_err(|e| SpeechError::Sync(format!("cannot read sync key: {e}")))?
+        .ok_or_else(|| SpeechError::Sync("no sync key is set".to_owned()))?;
+    let decoded = decode_key(&encoded);
+    secure_clear(&mut encoded);
+    let mut bytes = decoded?;
+    let mut key = [0u8; KEY_LEN];
+    key.copy_from_slice(&bytes);
+    bytes.fill(0);
+    let cipher = DataCipher::from_key(&key);
+    key.fill(0);
+    Ok(cipher)
+}
+
+/// Sync with the user's other devices.
+///
+/// The config is synced only when `include_config` is set; `config` is then
+/// updated in place and [`SyncReport::config_changed`] tells the caller to
+/// save it.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Sync`] if sync is off or already running, the
+/// backend fails, or the document was sealed with a different key.
+pub fn sync_now(
+    config: &mut SpeechConfig,
+    include_config: bool,
+    paths: &SyncPaths,
+) -> Result<SyncReport> {
+    if !config.sync.enabled {
+        return Err(SpeechError::Sync("sync is not enabled".to_owned()));
+    }
+    let Ok(_running) = RUNNING.try_lock() else {
+        return Err(SpeechError::Sync("a sync is already running".to_owned()));
+    };
+    let result = run(config, include_config, paths);
+    if let Ok(mut last) = LAST_RUN.lock() {
+        match &result {
+            Ok(report) => {
+                last.error = None;
+                last.report = Some(report.clone());
+            }
+            Err(e) => last.error = Some(e.to_string()),
+        }
+    }
+    result
+}
+
+fn run(config: &mut SpeechConfig, include_config: bool, paths: &SyncPaths) -> Result<SyncReport> {
+    let manager = crate::credentials::create_manager();
+    let cipher = load_key(&config.sync, manager.as_ref())?;
+    let backend = open_backend(&config.sync, manager.as_ref())?;
+
+    let mut state = LocalState::load(&paths.state_file);
+    if state.device_id.is_empty() {
+        state.device_id = uuid::Uuid::new_v4().to_string();
+    }
+    let mut document = match backend.get(DOCUMENT_NAME)? {
+        Some(sealed) => {
+            let plain = cipher.open(&sealed).map_err(|_| {
+                SpeechError::Sync(
+                    "the sync key does not match the one used on your other devices".to_owned(),
+                )
+            })?;
+            let document: SyncDocument = serde_json::from_slice(&plain)
+                .map_err(|e| SpeechError::Sync(format!("sync document is malformed: {e}")))?;
+            if document.version != merge::DOCUMENT_VERSION {
+                return Err(SpeechError::Sync(format!(
+                    "sync document version {} is not supported; update Fae",
+                    document.version
+                )));
+            }
+            document
+        }
+        None => SyncDocument::new(uuid::Uuid::new_v4().to_string()),
+    };
+    if state.document_id != document.id {
+        state.base.clear();
+        state.document_id = document.id.clone();
+    }
+
+    let mut scope = SyncScope::from_config(&config.sync);
+    scope.config &= include_config;
+    let device = state.device_id.clone();
+    let now = items::now_secs();
+    let local = items::collect(scope, include_config.then_some(&*config), paths, &device)?;
+    let known = document.devices.contains_key(&device);
+    let outcome = merge::merge(
+        &mut document,
+        &local,
+        &mut state.base,
+        &|key: &str| scope.contains(key),
+        &device,
+        now,
+    );
+
+    let config_changed = items::apply(
+        &outcome.apply,
+        include_config.then_some(&mut *config),
+        paths,
+    )?;
+    if !outcome.pushed.is_empty() || !known {
+        let plain = serde_json::to_vec(&document)
+            .map_err(|e| SpeechError::Sync(format!("sync document encode failed: {e}")))?;
+        backend.put(DOCUMENT_NAME, &cipher.seal(&plain)?)?;
+    }
+    state.last_sync_at = Some(now);
+    state.save(&paths.state_file)?;
+
+    let report = SyncReport {
+        pushed: outcome.pushed.len(),
+        pulled: outcome.apply.len(),
+        conflicts: outcome.conflicts,
+        devices: document.devices.len(),
+        config_changed,
+    };
+    info!(
+        pushed = report.pushed,
+        pulled = report.pulled,
+        conflicts = report.conflicts.len(),
+        "sync finished"
+    );
+    Ok(report)
+}
+
+/// Current sync state.
+pub fn status(sync: &SyncConfig, paths: &SyncPaths) -> SyncStatus {
+    let state = LocalState::load(&paths.state_file);
+    let (last_error, last_report) = match LAST_RUN.lock() {
+        Ok(last) => (last.error.clone(), last.report.clone()),
+        Err(_) => (None, None),
+    };
+    SyncStatus {
+        enabled: sync.enabled,
+        key_set: sync.key.is_set(),
+        syncing: RUNNING.try_lock().is_err(),
+        device_id: (!state.device_id.is_empty()).then_some(state.device_id),
+        last_sync_at: state.last_sync_at,
+        last_error,
+        last_report,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn sync_keys_must_decode_to_32_bytes() {
+        let key = URL_SAFE_NO_PAD.encode([7u8; KEY_LEN]);
+        assert_eq!(decode_key(&key).expect("decode").len(), KEY_LEN);
+        assert!(decode_key("not a key").is_err());
+        assert!(decode_key(&URL_SAFE_NO_PAD.encode([7u8; 16])).is_err());
+    }
+}
@@ -1,6 +1,26 @@
-//! System theme detection and CSS variable generation.
-
+//! System theme detection, CSS variable generation, and conversation-state
+//! theming.
+//!
+//! Alongside the light/dark system theme, the runtime tracks a
+//! [`ConversationState`] (idle, listening, thinking, speaking, error) from
+//! pipeline events. Each state has a [`StateStyle`] from the active
+//! [`ThemePack`]; when the state changes the host runtime emits a
+//! [`STATE_CHANGED_EVENT`] so shells can animate to it. Theme packs are TOML
+//! files in [`crate::fae_dirs::themes_dir`]; the built-in `fae` pack is used
+//! when none is selected.
+//!
+//! Like accessibility mode, the active pack is process-wide: it is set from
+//! `SpeechConfig::theme` at startup and through `config.patch`.
+
+use crate::config::ThemeConfig;
+use crate::error::{Result, SpeechError};
+use crate::pipeline::messages::ControlEvent;
+use crate::runtime::RuntimeEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
 
 /// System theme state (light or dark).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -116,6 +136,314 @@ const LIGHT_THEME_CSS: &str = r#"
     }
 "#;
 
+/// Event name for conversation-state theme updates.
+pub const STATE_CHANGED_EVENT: &str = "theme.state_changed";
+
+/// Id of the built-in theme pack.
+pub const DEFAULT_PACK_ID: &str = "fae";
+
+/// What the conversation is doing, for shells to animate.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationState {
+    /// Waiting for the user.
+    #[default]
+    Idle,
+    /// The user is speaking.
+    Listening,
+    /// Generating a reply or running tools.
+    Thinking,
+    /// Speaking a reply.
+    Speaking,
+    /// The runtime hit an error.
+    Error,
+}
+
+impl ConversationState {
+    /// The state `event` moves to from `self`, or `None` if it does not
+    /// affect the state.
+    pub fn after(self, event: &RuntimeEvent) -> Option<Self> {
+        match event {
+            RuntimeEvent::Control(ControlEvent::UserSpeechStart { .. }) => Some(Self::Listening),
+            RuntimeEvent::Transcription(t) if t.is_final => Some(Self::Thinking),
+            RuntimeEvent::AssistantGenerating { active: true }
+            | RuntimeEvent::ToolExecuting { .. } => Some(Self::Thinking),
+            // Generation can end before playback does; speech end settles it.
+            RuntimeEvent::AssistantGenerating { active: false }
+                if matches!(self, Self::Thinking | Self::Error) =>
+            {
+                Some(Self::Idle)
+            }
+            RuntimeEvent::Control(ControlEvent::AssistantSpeechStart) => Some(Self::Speaking),
+            RuntimeEvent::Control(ControlEvent::AssistantSpeechEnd { .. }) => Some(Self::Idle),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConversationState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Idle => "idle",
+            Self::Listening => "listening",
+            Self::Thinking => "thinking",
+            Self::Speaking => "speaking",
+            Self::Error => "error",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How a shell should render one [`ConversationState`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateStyle {
+    /// Main color as `#rrggbb`.
+    pub color: String,
+    /// Brightness of the glow, from 0.0 to 1.0.
+    pub intensity: f32,
+    /// Pulses per second; 0.0 holds steady.
+    #[serde(default)]
+    pub pulse_hz: f32,
+}
+
+impl StateStyle {
+    fn new(color: &str, intensity: f32, pulse_hz: f32) -> Self {
+        Self {
+            color: color.to_owned(),
+            intensity,
+            pulse_hz,
+        }
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        let hex = self.color.strip_prefix('#').unwrap_or_default();
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("color `{}` is not #rrggbb", self.color));
+        }
+        if !(0.0..=1.0).contains(&self.intensity) {
+            return Err(format!(
+                "intensity {} is not between 0 and 1",
+                self.intensity
+            ));
+        }
+        if !(0.0..=10.0).contains(&self.pulse_hz) {
+            return Err(format!(
+                "pulse_hz {} is not between 0 and 10",
+                self.pulse_hz
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A named set of per-state styles.
+///
+/// States a pack leaves out use the built-in style.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThemePack {
+    /// Stable identifier; defaults to the file name.
+    #[serde(default)]
+    pub id: String,
+    /// Display name.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub states: BTreeMap<ConversationState, StateStyle>,
+}
+
+impl ThemePack {
+    /// The built-in `fae` pack.
+    pub fn builtin() -> Self {
+        let states = BTreeMap::from([
+            (
+                ConversationState::Idle,
+                StateStyle::new("#a78bfa", 0.35, 0.2),
+            ),
+            (
+                ConversationState::Listening,
+                StateStyle::new("#3b82f6", 0.7, 0.0),
+            ),
+            (
+                ConversationState::Thinking,
+                StateStyle::new("#fbbf24", 0.6, 1.2),
+            ),
+            (
+                ConversationState::Speaking,
+                StateStyle::new("#22c55e", 0.85, 0.0),
+            ),
+            (
+                ConversationState::Error,
+                StateStyle::new("#ef4444", 0.9, 2.0),
+            ),
+        ]);
+        Self {
+            id: DEFAULT_PACK_ID.to_owned(),
+            name: "Fae".to_owned(),
+            states,
+        }
+    }
+
+    /// Style for `state`, falling back to the built-in pack.
+    pub fn style(&self, state: ConversationState) -> StateStyle {
+        self.states
+            .get(&state)
+            .cloned()
+            .or_else(|| Self::builtin().states.remove(&state))
+            .unwrap_or_else(|| StateStyle::new("#a78bfa", 0.5, 0.0))
+    }
+}
+
+/// The built-in pack plus every valid `*.toml` pack in `dir`.
+///
+/// Packs that fail to parse or validate are skipped with a warning; a pack
+/// with the built-in id replaces it.
+pub fn load_theme_packs(dir: &Path) -> Vec<ThemePack> {
+    let mut packs = vec![ThemePack::builtin()];
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return packs;
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        match load_theme_pack(&path) {
+            Ok(pack) => {
+                packs.retain(|p| p.id != pack.id);
+                packs.push(pack);
+            }
+            Err(e) => tracing::warn!("skipping theme pack {}: {e}", path.display()),
+        }
+    }
+    packs
+}
+
+/// Parse and validate one theme pack file.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Config`] when the file cannot be read, is not a
+/// theme pack, or has an invalid style.
+pub fn load_theme_pack(path: &Path) -> Result<ThemePack> {
+    let raw = std::fs::read_to_string(path)?;
+    let mut pack: ThemePack = toml::from_str(&raw)
+        .map_err(|e| SpeechError::Config(format!("invalid theme pack: {e}")))?;
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    if pack.id.trim().is_empty() {
+        pack.id = stem.to_owned();
+    }
+    if pack.name.trim().is_empty() {
+        pack.name = pack.id.clone();
+    }
+    for (state, style) in &pack.states {
+        style
+            .validate()
+            .map_err(|e| SpeechError::Config(format!("theme pack state `{state}`: {e}")))?;
+    }
+    Ok(pack)
+}
+
+/// Payload of a [`STATE_CHANGED_EVENT`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ThemeStateUpdate {
+    pub state: ConversationState,
+    pub pack: String,
+    #[serde(flatten)]
+    pub style: StateStyle,
+}
+
+impl ThemeStateUpdate {
+    /// The update as event JSON.
+    pub fn to_payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+/// Tracks the conversation state and the active pack.
+#[derive(Debug)]
+pub struct ThemeEngine {
+    state: ConversationState,
+    pack: Option<ThemePack>,
+}
+
+static ENGINE: Mutex<ThemeEngine> = Mutex::new(ThemeEngine::new());
+
+/// The process-wide theme engine.
+pub fn engine() -> MutexGuard<'static, ThemeEngine> {
+    ENGINE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Select the pack named in `config` from [`crate::fae_dirs::themes_dir`].
+///
+/// An unknown pack falls back to the built-in one.
+pub fn apply(config: &ThemeConfig) {
+    let id = config.pack.as_deref().unwrap_or(DEFAULT_PACK_ID);
+    let pack = load_theme_packs(&crate::fae_dirs::themes_dir())
+        .into_iter()
+        .find(|p| p.id == id);
+    if pack.is_none() {
+        tracing::warn!(pack = id, "theme pack not found, using the built-in pack");
+    }
+    engine().set_pack(pack);
+}
+
+impl ThemeEngine {
+    const fn new() -> Self {
+        Self {
+            state: ConversationState::Idle,
+            pack: None,
+        }
+    }
+
+    /// The current conversation state.
+    pub fn state(&self) -> ConversationState {
+        self.state
+    }
+
+    /// Use `pack`, or the built-in pack for `None`.
+    pub fn set_pack(&mut self, pack: Option<ThemePack>) {
+        self.pack = pack;
+    }
+
+    /// The current state rendered with the active pack.
+    pub fn current(&self) -> ThemeStateUpdate {
+        let builtin;
+        let pack = match &self.pack {
+            Some(pack) => pack,
+            None => {
+                builtin = ThemePack::builtin();
+                &builtin
+            }
+        };
+        ThemeStateUpdate {
+            state: self.state,
+            pack: pack.id.clone(),
+            style: pack.style(self.state),
+        }
+    }
+
+    /// Move to `state`; returns the update when it changed.
+    pub fn set_state(&mut self, state: ConversationState) -> Option<ThemeStateUpdate> {
+        if state == self.state {
+            return None;
+        }
+        self.state = state;
+        Some(self.current())
+    }
+
+    /// Apply a pipeline event; returns the update when the state changed.
+    pub fn observe(&mut self, event: &RuntimeEvent) -> Option<ThemeStateUpdate> {
+        let next = self.state.after(event)?;
+        self.set_state(next)
+    }
+}
+
 #[cfg(target_os = "macos")]
 fn detect_macos_theme() -> SystemTheme {
     use objc2::msg_send;
@@ -277,6 +605,75 @@ mod tests {
         assert!(css.contains("#0a0a0f"));
     }
 
+    #[test]
+    fn conversation_state_follows_pipeline_events() {
+        let mut engine = ThemeEngine::new();
+        let speech_start = RuntimeEvent::Control(ControlEvent::UserSpeechStart {
+            captured_at: std::time::Instant::now(),
+            rms: 0.2,
+        });
+
+        let update = engine.observe(&speech_start).unwrap();
+        assert_eq!(update.state, ConversationState::Listening);
+        assert_eq!(update.pack, DEFAULT_PACK_ID);
+        assert!(engine.observe(&speech_start).is_none());
+
+        let generating = RuntimeEvent::AssistantGenerating { active: true };
+        assert_eq!(
+            engine.observe(&generating).unwrap().state,
+            ConversationState::Thinking
+        );
+        let speaking = RuntimeEvent::Control(ControlEvent::AssistantSpeechStart);
+        assert_eq!(
+            engine.observe(&speaking).unwrap().state,
+            ConversationState::Speaking
+        );
+        // Generation finishing mid-playback keeps the speaking state.
+        let done = RuntimeEvent::AssistantGenerating { active: false };
+        assert!(engine.observe(&done).is_none());
+        let ended = RuntimeEvent::Control(ControlEvent::AssistantSpeechEnd { interrupted: false });
+        assert_eq!(
+            engine.observe(&ended).unwrap().state,
+            ConversationState::Idle
+        );
+
+        let error = engine.set_state(ConversationState::Error).unwrap();
+        assert_eq!(error.to_payload()["state"], "error");
+        assert_eq!(error.to_payload()["color"], "#ef4444");
+    }
+
+    #[test]
+    fn user_packs_override_states_and_fall_back_to_builtin() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("ocean.toml"),
+            "name = \"Ocean\"\n[states.idle]\ncolor = \"#0ea5e9\"\nintensity = 0.4\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("broken.toml"),
+            "[states.idle]\ncolor = \"blue\"\nintensity = 0.4\n",
+        )
+        .unwrap();
+
+        let packs = load_theme_packs(dir.path());
+        let ids: Vec<&str> = packs.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec![DEFAULT_PACK_ID, "ocean"]);
+
+        let ocean = &packs[1];
+        assert_eq!(ocean.name, "Ocean");
+        assert_eq!(ocean.style(ConversationState::Idle).color, "#0ea5e9");
+        assert_eq!(
+            ocean.style(ConversationState::Speaking),
+            ThemePack::builtin().style(ConversationState::Speaking)
+        );
+
+        let mut engine = ThemeEngine::new();
+        engine.set_pack(Some(ocean.clone()));
+        assert_eq!(engine.current().pack, "ocean");
+        assert_eq!(engine.current().style.color, "#0ea5e9");
+    }
+
     #[test]
     fn generate_theme_css_dark_has_dark_colors() {
         let css = generate_theme_css(SystemTheme::Dark);
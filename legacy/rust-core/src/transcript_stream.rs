@@ -0,0 +1,216 @@
+//! Live transcript streaming to a file or named pipe.
+//!
+//! With `[transcript_stream] path` set in `config.toml`, what the user says
+//! and (unless `include_assistant` is off) what Fae replies is appended to
+//! the path as it happens, one timestamped line each:
+//!
+//! ```text
+//! [14:32:05] User: what's on my calendar today
+//! [14:32:07] Fae: You have two meetings this afternoon.
+//! ```
+//!
+//! OBS text sources, caption overlays and note-taking apps can follow the
+//! file (or read the pipe) during a session without going through the FFI.
+//!
+//! Lines are written on a dedicated thread so a slow disk or an unread pipe
+//! never holds up the runtime; when the writer falls behind, new lines are
+//! dropped.
+
+use std::fs::File;
+use std::io::Write as _;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+
+use chrono::{Local, NaiveTime};
+use tracing::{debug, warn};
+
+use crate::config::TranscriptStreamConfig;
+use crate::runtime::RuntimeEvent;
+
+/// Lines queued for the writer before new ones are dropped.
+const QUEUE_LINES: usize = 256;
+
+/// Label of the user's lines.
+const USER_LABEL: &str = "User";
+
+/// Label of the assistant's lines.
+const ASSISTANT_LABEL: &str = "Fae";
+
+/// A running transcript stream. Dropping it stops the writer thread.
+pub struct TranscriptStream {
+    tx: SyncSender<String>,
+    include_assistant: bool,
+}
+
+impl TranscriptStream {
+    /// Start streaming to the configured path, or `None` when streaming is
+    /// off or the writer thread can't be started.
+    pub fn start(config: &TranscriptStreamConfig) -> Option<Self> {
+        let path = config.path.clone()?;
+        let (tx, rx) = mpsc::sync_channel(QUEUE_LINES);
+        let thread_path = path.clone();
+        if let Err(e) = std::thread::Builder::new()
+            .name("fae-transcript-stream".to_owned())
+            .spawn(move || run_writer(&thread_path, rx))
+        {
+            warn!("failed to start transcript stream: {e}");
+            return None;
+        }
+        debug!(path = %path.display(), "transcript stream started");
+        Some(Self {
+            tx,
+            include_assistant: config.include_assistant,
+        })
+    }
+
+    /// Stream the transcript line carried by `event`, if any.
+    pub fn observe(&self, event: &RuntimeEvent) {
+        let Some(line) = line_for(event, self.include_assistant, Local::now().time()) else {
+            return;
+        };
+        if let Err(TrySendError::Full(_)) = self.tx.try_send(line) {
+            debug!("transcript stream is behind, dropped a line");
+        }
+    }
+}
+
+/// The line `event` adds to the transcript at `time`: final user
+/// transcriptions and, with `include_assistant`, assistant sentences.
+pub fn line_for(event: &RuntimeEvent, include_assistant: bool, time: NaiveTime) -> Option<String> {
+    let (speaker, text) = match event {
+        RuntimeEvent::Transcription(t) if t.is_final => (USER_LABEL, t.text.as_str()),
+        RuntimeEvent::AssistantSentence(chunk) if include_assistant => {
+            (ASSISTANT_LABEL, chunk.text.as_str())
+        }
+        _ => return None,
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    Some(format!("[{}] {speaker}: {text}\n", time.format("%H:%M:%S")))
+}
+
+/// Append queued lines to `path` until the stream is dropped.
+///
+/// The path is opened on the first line and reopened after a write error, so
+/// a file that is rotated or deleted during a session is recreated.
+fn run_writer(path: &Path, rx: Receiver<String>) {
+    let mut file: Option<File> = None;
+    for line in rx {
+        if file.is_none() {
+            file = open(path)
+                .inspect_err(|e| warn!("failed to open transcript stream {}: {e}", path.display()))
+                .ok();
+        }
+        if let Some(f) = file.as_mut()
+            && let Err(e) = f.write_all(line.as_bytes()).and_then(|()| f.flush())
+        {
+            warn!("failed to write transcript stream {}: {e}", path.display());
+            file = None;
+        }
+    }
+}
+
+/// Open `path` for appending, creating it if needed.
+///
+/// Opened read-write so a named pipe always has a reader: opening doesn't
+/// wait for one, and writing after the consumer goes away neither fails nor
+/// raises `SIGPIPE` in the host app.
+fn open(path: &Path) -> std::io::Result<File> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::pipeline::messages::{SentenceChunk, Transcription};
+
+    fn time() -> NaiveTime {
+        NaiveTime::from_hms_opt(14, 32, 5).expect("time")
+    }
+
+    fn transcription(text: &str, is_final: bool) -> RuntimeEvent {
+        RuntimeEvent::Transcription(Transcription {
+            text: text.to_owned(),
+            is_final,
+            voiceprint: None,
+            audio_rms: None,
+            audio_duration_secs: None,
+            audio_captured_at: Instant::now(),
+            transcribed_at: Instant::now(),
+            speculation: None,
+        })
+    }
+
+    fn sentence(text: &str) -> RuntimeEvent {
+        RuntimeEvent::AssistantSentence(SentenceChunk {
+            text: text.to_owned(),
+            is_final: false,
+        })
+    }
+
+    #[test]
+    fn lines_are_timestamped_and_labelled() {
+        assert_eq!(
+            line_for(&transcription(" what's on\ntoday ", true), true, time()).as_deref(),
+            Some("[14:32:05] User: what's on today\n")
+        );
+        assert_eq!(
+            line_for(&sentence("Two meetings."), true, time()).as_deref(),
+            Some("[14:32:05] Fae: Two meetings.\n")
+        );
+    }
+
+    #[test]
+    fn partial_empty_and_excluded_text_is_skipped() {
+        assert_eq!(
+            line_for(&transcription("what's", false), true, time()),
+            None
+        );
+        assert_eq!(line_for(&sentence("  "), true, time()), None);
+        assert_eq!(line_for(&sentence("Two meetings."), false, time()), None);
+        assert!(line_for(&transcription("hello", true), false, time()).is_some());
+    }
+
+    #[test]
+    fn stream_appends_to_the_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("captions").join("live.txt");
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        std::fs::write(&path, "earlier\n").expect("seed");
+
+        let stream = TranscriptStream::start(&TranscriptStreamConfig {
+            path: Some(path.clone()),
+            include_assistant: true,
+        })
+        .expect("stream");
+        stream.observe(&transcription("hello", true));
+        stream.observe(&sentence("Hi there."));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let contents = loop {
+            let contents = std::fs::read_to_string(&path).expect("read");
+            if contents.lines().count() == 3 || Instant::now() > deadline {
+                break contents;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3, "{contents}");
+        assert_eq!(lines[0], "earlier");
+        assert!(lines[1].ends_with("] User: hello"), "{contents}");
+        assert!(lines[2].ends_with("] Fae: Hi there."), "{contents}");
+    }
+}
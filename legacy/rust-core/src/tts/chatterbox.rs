@@ -0,0 +1,381 @@
+//! Chatterbox TTS engine — HTTP client for a locally running Chatterbox server.
+//!
+//! The server exposes `GET /health`, `GET /voices`, and `POST /synthesize`
+//! (returns a WAV payload). Audio is decoded to f32 mono at the sample rate
+//! reported in the WAV header. Delivery tags map to the server's
+//! `exaggeration` (emotion intensity, 0.5 neutral) and `speed_factor`
+//! parameters.
+
+use super::SpeechStyle;
+use crate::config::TtsConfig;
+use crate::error::{Result, SpeechError};
+use std::io::Cursor;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Timeout for a single synthesis request.
+const SYNTHESIZE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Timeout for health and voice-listing probes.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Sample rate assumed until the first WAV header is decoded.
+const DEFAULT_SAMPLE_RATE: u32 = 24_000;
+
+/// Chatterbox TTS engine.
+pub struct ChatterboxTts {
+    client: reqwest::Client,
+    base_url: String,
+    voice: Option<String>,
+    voices: Vec<String>,
+    sample_rate: u32,
+}
+
+impl ChatterboxTts {
+    /// Connect to the Chatterbox server configured in `config.chatterbox_url`.
+    ///
+    /// Probes `/health` and caches the voice list from `/voices`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server is unreachable or unhealthy, or if it
+    /// is remote and offline mode is on.
+    pub async fn connect(config: &TtsConfig) -> Result<Self> {
+        let base_url = config.chatterbox_url.trim_end_matches('/').to_owned();
+        ensure_reachable(&base_url)?;
+        let client = reqwest::Client::builder()
+            .timeout(SYNTHESIZE_TIMEOUT)
+            .build()
+            .map_err(|e| SpeechError::Tts(format!("failed to build HTTP client: {e}")))?;
+
+        let health = client
+            .get(format!("{base_url}/health"))
+            .timeout(PROBE_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| {
+                SpeechError::Tts(format!("Chatterbox server unreachable at {base_url}: {e}"))
+            })?;
+        if !health.status().is_success() {
+            return Err(SpeechError::Tts(format!(
+                "Chatterbox health check failed at {base_url}: HTTP {}",
+                health.status()
+            )));
+        }
+
+        let voices = match fetch_voices(&client, &base_url).await {
+            Ok(voices) => voices,
+            Err(e) => {
+                warn!("Chatterbox voice listing failed (continuing): {e}");
+                Vec::new()
+            }
+        };
+
+        info!(
+            "Chatterbox TTS ready (url={base_url}, voices={})",
+            voices.len()
+        );
+
+        Ok(Self {
+            client,
+            base_url,
+            voice: config.chatterbox_voice.clone(),
+            voices,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+        })
+    }
+
+    /// Synthesize text to audio samples via `POST /synthesize`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the WAV payload is invalid.
+    pub async fn synthesize(&mut self, text: &str) -> Result<Vec<f32>> {
+        self.synthesize_styled(text, &SpeechStyle::default()).await
+    }
+
+    /// Synthesize text in `style` via `POST /synthesize`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the WAV payload is invalid.
+    pub async fn synthesize_styled(&mut self, text: &str, style: &SpeechStyle) -> Result<Vec<f32>> {
+        let text = super::kokoro::strip_non_speech_chars(text);
+        if text.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let body = synthesize_body(&text, self.voice.as_deref(), style);
+
+        ensure_reachable(&self.base_url)?;
+        let start = std::time::Instant::now();
+        let resp = self
+            .client
+            .post(format!("{}/synthesize", self.base_url))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| SpeechError::Tts(format!("Chatterbox request failed: {e}")))?;
+        if !resp.status().is_success() {
+            return Err(SpeechError::Tts(format!(
+                "Chatterbox synthesis failed: HTTP {}",
+                resp.status()
+            )));
+        }
+        let bytes = resp
+            .bytes()
+            .await
+            .map_err(|e| SpeechError::Tts(format!("failed to read Chatterbox response: {e}")))?;
+
+        let (mut samples, sample_rate) = decode_wav(&bytes)?;
+        self.sample_rate = sample_rate;
+        style.apply_gain(&mut samples);
+
+        info!(
+            "Chatterbox synthesized {} samples @ {sample_rate} Hz in {}ms",
+            samples.len(),
+            start.elapsed().as_millis()
+        );
+        Ok(samples)
+    }
+}
+
+#[async_trait::async_trait]
+impl super::TtsEngine for ChatterboxTts {
+    fn id(&self) -> &'static str {
+        "chatterbox"
+    }
+
+    async fn synthesize(&mut self, text: &str) -> Result<Vec<f32>> {
+        ChatterboxTts::synthesize(self, text).await
+    }
+
+    async fn synthesize_styled(&mut self, text: &str, style: &SpeechStyle) -> Result<Vec<f32>> {
+        ChatterboxTts::synthesize_styled(self, text, style).await
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn capabilities(&self) -> super::TtsCapabilities {
+        super::TtsCapabilities {
+            voices: self.voices.clone(),
+            sample_rate: self.sample_rate,
+            ..capabilities(&[])
+        }
+    }
+}
+
+/// What Chatterbox can do with `voices`, before connecting to the server.
+///
+/// The server's own voice list is only known once connected.
+pub fn capabilities(voices: &[String]) -> super::TtsCapabilities {
+    super::TtsCapabilities {
+        engine: "chatterbox".to_owned(),
+        languages: vec!["en".to_owned()],
+        voices: voices.to_vec(),
+        streaming: false,
+        sample_rate: DEFAULT_SAMPLE_RATE,
+    }
+}
+
+/// JSON body for `POST /synthesize`. Prosody fields are sent only for a
+/// non-neutral style so servers without them keep working.
+fn synthesize_body(text: &str, voice: Option<&str>, style: &SpeechStyle) -> serde_json::Value {
+    let mut body = serde_json::json!({ "text": text });
+    if let Some(voice) = voice {
+        body["voice"] = serde_json::Value::String(voice.to_owned());
+    }
+    if !style.is_neutral() {
+        body["exaggeration"] = serde_json::json!(style.expressiveness);
+        body["speed_factor"] = serde_json::json!(style.rate);
+    }
+    body
+}
+
+/// Blocking `/health` probe used by doctor.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Tts`] if the server is unreachable or unhealthy.
+pub fn preflight(config: &TtsConfig) -> Result<()> {
+    let base_url = config.chatterbox_url.trim_end_matches('/');
+    ensure_reachable(base_url)?;
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(PROBE_TIMEOUT)
+        .timeout_read(PROBE_TIMEOUT)
+        .build();
+    match agent.get(&format!("{base_url}/health")).call() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(SpeechError::Tts(format!(
+            "Chatterbox server not reachable at {base_url}: {e}"
+        ))),
+    }
+}
+
+/// Refuse remote Chatterbox servers while offline mode is on.
+fn ensure_reachable(base_url: &str) -> Result<()> {
+    crate::offline::ensure_url_allowed("remote Chatterbox TTS", base_url)
+        .map_err(|e| SpeechError::Tts(e.to_string()))
+}
+
+async fn fetch_voices(client: &reqwest::Client, base_url: &str) -> Result<Vec<String>> {
+    let value: serde_json::Value = client
+        .get(format!("{base_url}/voices"))
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| SpeechError::Tts(format!("voices request failed: {e}")))?
+        .json()
+        .await
+        .map_err(|e| SpeechError::Tts(format!("invalid voices response: {e}")))?;
+    Ok(parse_voice_list(&value))
+}
+
+/// Extract voice names from a `/voices` response.
+///
+/// Accepts either a plain array of strings or an array of objects carrying
+/// a `name` or `id` field.
+fn parse_voice_list(value: &serde_json::Value) -> Vec<String> {
+    let Some(items) = value.as_array() else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| match item {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Object(obj) => obj
+                .get("name")
+                .or_else(|| obj.get("id"))
+                .and_then(|v| v.as_str())
+                .map(str::to_owned),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decode a WAV payload to f32 mono samples, downmixing multi-channel audio.
+fn decode_wav(bytes: &[u8]) -> Result<(Vec<f32>, u32)> {
+    let reader = hound::WavReader::new(Cursor::new(bytes))
+        .map_err(|e| SpeechError::Tts(format!("invalid WAV from Chatterbox: {e}")))?;
+    let spec = reader.spec();
+    let channels = usize::from(spec.channels.max(1));
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|e| SpeechError::Tts(format!("failed to decode WAV samples: {e}")))?,
+        hound::SampleFormat::Int => {
+            let scale = (1_i64 << (spec.bits_per_sample.saturating_sub(1))) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / scale))
+                .collect::<std::result::Result<_, _>>()
+                .map_err(|e| SpeechError::Tts(format!("failed to decode WAV samples: {e}")))?
+        }
+    };
+
+    let samples = if channels == 1 {
+        interleaved
+    } else {
+        interleaved
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect()
+    };
+
+    Ok((samples, spec.sample_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn wav_bytes(spec: hound::WavSpec, samples: &[i16]) -> Vec<u8> {
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+            for s in samples {
+                writer.write_sample(*s).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn decode_wav_int16_mono() {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 22_050,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let bytes = wav_bytes(spec, &[0, 16_384, -16_384]);
+        let (samples, rate) = decode_wav(&bytes).unwrap();
+        assert_eq!(rate, 22_050);
+        assert_eq!(samples.len(), 3);
+        assert!((samples[1] - 0.5).abs() < 1e-4);
+        assert!((samples[2] + 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn decode_wav_downmixes_stereo() {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 24_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let bytes = wav_bytes(spec, &[16_384, 0, -16_384, -16_384]);
+        let (samples, _) = decode_wav(&bytes).unwrap();
+        assert_eq!(samples.len(), 2);
+        assert!((samples[0] - 0.25).abs() < 1e-4);
+        assert!((samples[1] + 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn decode_wav_rejects_garbage() {
+        assert!(decode_wav(b"not a wav").is_err());
+    }
+
+    #[test]
+    fn parse_voice_list_accepts_strings_and_objects() {
+        let value = serde_json::json!(["default", {"name": "warm"}, {"id": "calm"}, 42]);
+        assert_eq!(parse_voice_list(&value), vec!["default", "warm", "calm"]);
+        assert!(parse_voice_list(&serde_json::json!({"voices": []})).is_empty());
+    }
+
+    #[test]
+    fn synthesize_body_sends_prosody_only_for_styled_text() {
+        let plain = synthesize_body("Hi.", Some("warm"), &SpeechStyle::default());
+        assert_eq!(plain, serde_json::json!({"text": "Hi.", "voice": "warm"}));
+
+        let mut style = SpeechStyle::default();
+        style.apply(crate::tts::style::StyleTag::Slow);
+        let slow = synthesize_body("Hi.", None, &style);
+        assert_eq!(slow["exaggeration"], serde_json::json!(0.5));
+        assert_eq!(slow["speed_factor"], serde_json::json!(0.85f32));
+    }
+
+    #[test]
+    fn capabilities_without_a_server_report_the_configured_voice() {
+        let config = TtsConfig {
+            backend: crate::config::TtsBackend::Chatterbox,
+            chatterbox_voice: Some("alice".to_owned()),
+            ..TtsConfig::default()
+        };
+        let caps = crate::tts::capabilities(&config);
+        assert_eq!(caps.engine, "chatterbox");
+        assert_eq!(caps.voices, vec!["alice".to_owned()]);
+        assert_eq!(caps.sample_rate, DEFAULT_SAMPLE_RATE);
+
+        let kokoro = crate::tts::capabilities(&TtsConfig::default());
+        assert_eq!(kokoro.engine, "kokoro");
+        assert!(!kokoro.voices.is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+//! Long-form synthesis to an audio file.
+//!
+//! [`synthesize_to_file`] renders an article-length text — plain or
+//! [`ssml`](super::ssml) — with the current voice and writes it as WAV or
+//! MP3. The text is spoken sentence by sentence, since engines cap how much
+//! they synthesize per call, and stitched together with the pauses the SSML
+//! asks for. Inputs over [`MAX_TEXT_CHARS`] are refused and rendering stops
+//! at [`MAX_AUDIO_SECS`], keeping a runaway request from filling the disk.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::{Result, SpeechError};
+use crate::llm::find_sentence_boundary;
+
+use super::TtsEngine;
+use super::ssml::{self, SpeechSegment};
+
+/// Longest input text accepted, in characters (about a 30-page article).
+pub const MAX_TEXT_CHARS: usize = 200_000;
+
+/// Longest audio rendered, in seconds.
+pub const MAX_AUDIO_SECS: f32 = 4.0 * 3600.0;
+
+/// Sentences longer than this are split at a word boundary, so no single
+/// engine call is truncated.
+const MAX_CHUNK_CHARS: usize = 300;
+
+/// MP3 bitrate; speech needs no more.
+const MP3_BITRATE: mp3lame_encoder::Bitrate = mp3lame_encoder::Bitrate::Kbps64;
+
+/// Audio file formats that can be written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFileFormat {
+    Wav,
+    Mp3,
+}
+
+impl AudioFileFormat {
+    /// The format for `path`'s extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "wav" => Some(Self::Wav),
+            "mp3" => Some(Self::Mp3),
+            _ => None,
+        }
+    }
+}
+
+/// Mono audio rendered from text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedAudio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl RenderedAudio {
+    /// Length of the audio in seconds.
+    pub fn duration_secs(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate.max(1) as f32
+    }
+}
+
+/// A written audio file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AudioExport {
+    pub path: PathBuf,
+    pub format: AudioFileFormat,
+    pub duration_secs: f32,
+    pub bytes: u64,
+}
+
+/// Render `input` with `engine` and write it to `path`, in the format its
+/// extension names. `progress` receives the fraction of text spoken.
+///
+/// # Errors
+///
+/// Returns an error if the extension is not `.wav` or `.mp3`, the text is
+/// empty or over the limits, synthesis fails, or the file cannot be written.
+pub async fn synthesize_to_file(
+    engine: &mut dyn TtsEngine,
+    input: &str,
+    path: &Path,
+    progress: &mut dyn FnMut(f32),
+) -> Result<AudioExport> {
+    let format = AudioFileFormat::from_path(path).ok_or_else(|| {
+        SpeechError::Tts(format!(
+            "unsupported audio file type for {}: use .wav or .mp3",
+            path.display()
+        ))
+    })?;
+    let audio = render(engine, input, progress).await?;
+    let encoded = match format {
+        AudioFileFormat::Wav => encode_wav(&audio)?,
+        AudioFileFormat::Mp3 => encode_mp3(&audio)?,
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, &encoded)?;
+    Ok(AudioExport {
+        path: path.to_path_buf(),
+        format,
+        duration_secs: audio.duration_secs(),
+        bytes: encoded.len() as u64,
+    })
+}
+
+/// Render `input`, plain text or SSML, to samples at the engine's rate.
+///
+/// # Errors
+///
+/// Returns an error if the text is empty, longer than [`MAX_TEXT_CHARS`],
+/// renders to more than [`MAX_AUDIO_SECS`], or synthesis fails.
+pub async fn render(
+    engine: &mut dyn TtsEngine,
+    input: &str,
+    progress: &mut dyn FnMut(f32),
+) -> Result<RenderedAudio> {
+    let chars = input.chars().count();
+    if chars > MAX_TEXT_CHARS {
+        return Err(SpeechError::Tts(format!(
+            "text is {chars} characters; the limit is {MAX_TEXT_CHARS}"
+        )));
+    }
+    let segments = ssml::parse(input)?;
+    let total: usize = segments
+        .iter()
+        .map(|s| match s {
+            SpeechSegment::Text { text, .. } => text.len(),
+            SpeechSegment::Pause { .. } => 0,
+        })
+        .sum();
+    if total == 0 {
+        return Err(SpeechError::Tts("nothing to synthesize".to_owned()));
+    }
+
+    let sample_rate = engine.sample_rate();
+    let max_samples = (MAX_AUDIO_SECS * sample_rate as f32) as usize;
+    let mut samples = Vec::new();
+    let mut done = 0;
+    for segment in &segments {
+        match segment {
+            SpeechSegment::Pause { secs } => {
+                let len = (secs * sample_rate as f32) as usize;
+                samples.resize(samples.len() + len, 0.0);
+            }
+            SpeechSegment::Text { text, style } => {
+                for chunk in chunks(text) {
+                    samples.extend(engine.synthesize_styled(chunk, style).await?);
+                    done += chunk.len();
+                    progress(done as f32 / total as f32);
+                    if samples.len() > max_samples {
+                        return Err(SpeechError::Tts(format!(
+                            "audio would be longer than {} minutes",
+                            MAX_AUDIO_SECS as u32 / 60
+                        )));
+                    }
+                }
+            }
+        }
+    }
+    progress(1.0);
+    Ok(RenderedAudio {
+        samples,
+        sample_rate,
+    })
+}
+
+/// Split `text` into sentences, and over-long sentences at word boundaries.
+fn chunks(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut rest = text;
+    while let Some(pos) = find_sentence_boundary(rest) {
+        let end = pos + rest[pos..].chars().next().map_or(1, char::len_utf8);
+        sentences.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    sentences.push(rest);
+
+    let mut chunks = Vec::new();
+    for mut sentence in sentences {
+        while sentence.len() > MAX_CHUNK_CHARS {
+            let mut split = MAX_CHUNK_CHARS;
+            while !sentence.is_char_boundary(split) {
+                split -= 1;
+            }
+            let split = sentence[..split].rfind(' ').unwrap_or(split);
+            chunks.push(&sentence[..split]);
+            sentence = &sentence[split..];
+        }
+        chunks.push(sentence);
+    }
+    chunks
+        .into_iter()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+/// `audio` as a 16-bit PCM WAV file.
+///
+/// # Errors
+///
+/// Returns an error if encoding fails.
+pub fn encode_wav(audio: &RenderedAudio) -> Result<Vec<u8>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: audio.sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let wav_err = |e: hound::Error| SpeechError::Tts(format!("failed to encode WAV: {e}"));
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut cursor, spec).map_err(wav_err)?;
+    for sample in &audio.samples {
+        writer.write_sample(to_i16(*sample)).map_err(wav_err)?;
+    }
+    writer.finalize().map_err(wav_err)?;
+    Ok(cursor.into_inner())
+}
+
+/// `audio` as a mono MP3 file.
+///
+/// # Errors
+///
+/// Returns an error if the encoder rejects the sample rate or fails.
+pub fn encode_mp3(audio: &RenderedAudio) -> Result<Vec<u8>> {
+    use mp3lame_encoder::{Builder, FlushNoGap, MonoPcm, Quality};
+
+    let mut builder = Builder::new().ok_or_else(|| mp3_error("encoder unavailable"))?;
+    builder.set_num_channels(1).map_err(mp3_error)?;
+    builder
+        .set_sample_rate(audio.sample_rate)
+        .map_err(mp3_error)?;
+    builder.set_brate(MP3_BITRATE).map_err(mp3_error)?;
+    builder.set_quality(Quality::Good).map_err(mp3_error)?;
+    let mut encoder = builder.build().map_err(mp3_error)?;
+
+    let pcm: Vec<i16> = audio.samples.iter().map(|s| to_i16(*s)).collect();
+    let mut mp3 = Vec::with_capacity(mp3lame_encoder::max_required_buffer_size(pcm.len()));
+    encoder
+        .encode_to_vec(MonoPcm(&pcm), &mut mp3)
+        .map_err(mp3_error)?;
+    encoder
+        .flush_to_vec::<FlushNoGap>(&mut mp3)
+        .map_err(mp3_error)?;
+    Ok(mp3)
+}
+
+fn mp3_error(e: impl std::fmt::Debug) -> SpeechError {
+    SpeechError::Tts(format!("failed to encode MP3: {e:?}"))
+}
+
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::tts::{SpeechStyle, TtsCapabilities};
+
+    /// Speaks every character as 10 samples of 0.5.
+    struct CountingEngine {
+        calls: Vec<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl TtsEngine for CountingEngine {
+        fn id(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn synthesize(&mut self, text: &str) -> Result<Vec<f32>> {
+            self.calls.push(text.to_owned());
+            Ok(vec![0.5; text.chars().count() * 10])
+        }
+
+        async fn synthesize_styled(
+            &mut self,
+            text: &str,
+            _style: &SpeechStyle,
+        ) -> Result<Vec<f32>> {
+            self.synthesize(text).await
+        }
+
+        fn sample_rate(&self) -> u32 {
+            1_000
+        }
+
+        fn capabilities(&self) -> TtsCapabilities {
+            TtsCapabilities::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_sentences_and_pauses_to_wav() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("run/article.wav");
+        let mut engine = CountingEngine { calls: Vec::new() };
+        let mut fractions = Vec::new();
+
+        let export = synthesize_to_file(
+            &mut engine,
+            r#"<speak>First one. Second!<break time="2s"/>Third</speak>"#,
+            &path,
+            &mut |f| fractions.push(f),
+        )
+        .await
+        .expect("export");
+
+        assert_eq!(engine.calls, vec!["First one.", "Second!", "Third"]);
+        assert_eq!(fractions.last().copied(), Some(1.0));
+        assert_eq!(export.format, AudioFileFormat::Wav);
+        // 22 characters at 10 samples each, plus 2 s of silence at 1 kHz.
+        assert!((export.duration_secs - 2.22).abs() < 1e-3);
+        let reader = hound::WavReader::open(&path).expect("read wav");
+        assert_eq!(reader.spec().sample_rate, 1_000);
+        assert_eq!(reader.len(), 2_220);
+    }
+
+    #[tokio::test]
+    async fn refuses_unsupported_types_and_oversized_text() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut engine = CountingEngine { calls: Vec::new() };
+        let ogg = dir.path().join("article.ogg");
+        assert!(
+            synthesize_to_file(&mut engine, "Hi.", &ogg, &mut |_| {})
+                .await
+                .is_err()
+        );
+
+        let long = "a".repeat(MAX_TEXT_CHARS + 1);
+        assert!(render(&mut engine, &long, &mut |_| {}).await.is_err());
+        assert!(
+            render(&mut engine, "<speak> </speak>", &mut |_| {})
+                .await
+                .is_err()
+        );
+        assert!(engine.calls.is_empty());
+
+        let words = "word ".repeat(200);
+        assert!(chunks(&words).iter().all(|c| c.len() <= MAX_CHUNK_CHARS));
+    }
+}
@@ -166,19 +166,22 @@ pub fn download_kokoro_assets(variant: &str, voice: &str) -> Result<KokoroPaths>
     let api = hf_hub::api::sync::Api::new()
         .map_err(|e| SpeechError::Model(format!("HF Hub API init failed: {e}")))?;
     let repo = api.model(KOKORO_REPO_ID.to_owned());
+    let fetch = |filename: &str| -> Result<PathBuf> {
+        if crate::offline::is_offline() {
+            return ModelManager::cached_file(KOKORO_REPO_ID, filename);
+        }
+        repo.get(filename)
+            .map_err(|e| SpeechError::Model(format!("failed to download {filename}: {e}")))
+    };
 
     // Model ONNX
     let model_file = model_filename(variant);
     info!("ensuring Kokoro model: {KOKORO_REPO_ID}/{model_file}");
-    let model_onnx = repo
-        .get(model_file)
-        .map_err(|e| SpeechError::Model(format!("failed to download {model_file}: {e}")))?;
+    let model_onnx = fetch(model_file)?;
 
     // Tokenizer
     info!("ensuring tokenizer.json");
-    let tokenizer_json = repo
-        .get("tokenizer.json")
-        .map_err(|e| SpeechError::Model(format!("failed to download tokenizer.json: {e}")))?;
+    let tokenizer_json = fetch("tokenizer.json")?;
 
     // Voice style tensor
     let voice_bin = if voice == "fae" {
@@ -194,8 +197,7 @@ pub fn download_kokoro_assets(variant: &str, voice: &str) -> Result<KokoroPaths>
         } else {
             let voice_file = format!("voices/{resolved_voice}.bin");
             info!("ensuring voice: {voice_file}");
-            repo.get(&voice_file)
-                .map_err(|e| SpeechError::Model(format!("failed to download {voice_file}: {e}")))?
+            fetch(&voice_file)?
         }
     };
 
@@ -205,3 +207,36 @@ pub fn download_kokoro_assets(variant: &str, voice: &str) -> Result<KokoroPaths>
         voice_bin,
     })
 }
+
+/// List Kokoro assets that are not yet available locally.
+///
+/// Returns repo-relative filenames (or the custom voice path) that would need
+/// to be downloaded or created before the engine can load offline.
+pub fn missing_kokoro_assets(variant: &str, voice: &str) -> Vec<String> {
+    let mut missing = Vec::new();
+
+    let model_file = model_filename(variant);
+    if !ModelManager::is_file_cached(KOKORO_REPO_ID, model_file) {
+        missing.push(model_file.to_owned());
+    }
+    if !ModelManager::is_file_cached(KOKORO_REPO_ID, "tokenizer.json") {
+        missing.push("tokenizer.json".to_owned());
+    }
+
+    if voice != "fae" {
+        match voice_filename(voice) {
+            Some(vf) => {
+                if !ModelManager::is_file_cached(KOKORO_REPO_ID, &vf) {
+                    missing.push(vf);
+                }
+            }
+            None => {
+                if !std::path::Path::new(voice).is_file() {
+                    missing.push(voice.to_owned());
+                }
+            }
+        }
+    }
+
+    missing
+}
@@ -17,6 +17,24 @@ const MAX_CONTEXT: usize = 512;
 /// Output sample rate in Hz.
 const SAMPLE_RATE: u32 = 24_000;
 
+/// Preset voice names shipped with Kokoro-82M v1.0 (plus the bundled `fae` alias).
+///
+/// Custom `.bin` voice paths are also accepted but are not listed here.
+pub const KOKORO_VOICES: &[&str] = &[
+    "fae",
+    "af_heart",
+    "af_bella",
+    "af_nicole",
+    "af_sarah",
+    "af_sky",
+    "am_adam",
+    "am_michael",
+    "bf_emma",
+    "bf_isabella",
+    "bm_george",
+    "bm_lewis",
+];
+
 /// Kokoro TTS engine.
 ///
 /// Wraps a single ONNX session, the tokenizer, phonemizer, and a voice
@@ -98,6 +116,27 @@ impl KokoroTts {
     ///
     /// Returns an error if phonemization, tokenization, or inference fails.
     pub async fn synthesize(&mut self, text: &str) -> Result<Vec<f32>> {
+        self.synthesize_at(text, self.speed).await
+    }
+
+    /// Synthesize text in `style`: the rate scales the configured speed and
+    /// the volume is applied to the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if phonemization, tokenization, or inference fails.
+    pub async fn synthesize_styled(
+        &mut self,
+        text: &str,
+        style: &crate::tts::SpeechStyle,
+    ) -> Result<Vec<f32>> {
+        let speed = (self.speed * style.rate).clamp(0.5, 2.0);
+        let mut samples = self.synthesize_at(text, speed).await?;
+        style.apply_gain(&mut samples);
+        Ok(samples)
+    }
+
+    async fn synthesize_at(&mut self, text: &str, speed: f32) -> Result<Vec<f32>> {
         // Strip emojis and non-speech symbols — they phonemize as garbage.
         let text = strip_non_speech_chars(text);
         if text.is_empty() {
@@ -155,7 +194,6 @@ impl KokoroTts {
         let style_slice = &self.voice_styles[style_offset..style_offset + 256];
 
         // 4. Build input tensors and run inference (synchronous).
-        let speed = self.speed;
         let token_ids_owned = token_ids;
         let style_vec: Vec<f32> = style_slice.to_vec();
 
@@ -245,6 +283,44 @@ impl KokoroTts {
     }
 }
 
+#[async_trait::async_trait]
+impl crate::tts::TtsEngine for KokoroTts {
+    fn id(&self) -> &'static str {
+        "kokoro"
+    }
+
+    async fn synthesize(&mut self, text: &str) -> Result<Vec<f32>> {
+        KokoroTts::synthesize(self, text).await
+    }
+
+    async fn synthesize_styled(
+        &mut self,
+        text: &str,
+        style: &crate::tts::SpeechStyle,
+    ) -> Result<Vec<f32>> {
+        KokoroTts::synthesize_styled(self, text, style).await
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn capabilities(&self) -> crate::tts::TtsCapabilities {
+        capabilities()
+    }
+}
+
+/// What Kokoro can do; the same for every model variant.
+pub fn capabilities() -> crate::tts::TtsCapabilities {
+    crate::tts::TtsCapabilities {
+        engine: "kokoro".to_owned(),
+        languages: vec!["en-US".to_owned(), "en-GB".to_owned()],
+        voices: KOKORO_VOICES.iter().map(|v| (*v).to_owned()).collect(),
+        streaming: false,
+        sample_rate: SAMPLE_RATE,
+    }
+}
+
 fn build_kokoro_session(model_path: &std::path::Path) -> Result<Session> {
     let builder = Session::builder()
         .and_then(|b| b.with_intra_threads(4))
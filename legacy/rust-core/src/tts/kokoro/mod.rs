@@ -4,4 +4,24 @@ pub mod download;
 mod engine;
 pub mod phonemize;
 
-pub use engine::{KokoroTts, strip_non_speech_chars};
+pub use engine::{KOKORO_VOICES, KokoroTts, capabilities, strip_non_speech_chars};
+
+use crate::config::TtsConfig;
+use crate::error::{Result, SpeechError};
+
+/// Check that Kokoro assets for `config` are present in the local cache.
+///
+/// # Errors
+///
+/// Returns [`SpeechError::Tts`] listing the missing files.
+pub fn preflight(config: &TtsConfig) -> Result<()> {
+    let missing = download::missing_kokoro_assets(&config.model_variant, &config.voice);
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(SpeechError::Tts(format!(
+            "Kokoro assets not downloaded: {}",
+            missing.join(", ")
+        )))
+    }
+}
@@ -1,7 +1,124 @@
 //! Text-to-speech synthesis.
 //!
-//! Uses the Kokoro-82M ONNX engine with pre-trained voice styles.
+//! Engines implement [`TtsEngine`] and are selected at runtime from
+//! [`TtsConfig::backend`]:
+//!
+//! - [`KokoroTts`] — Kokoro-82M ONNX engine with pre-trained voice styles.
+//! - [`ChatterboxTts`] — local Chatterbox TTS server over HTTP.
+//!
+//! Delivery tags from the LLM (`[whisper]`, `[slow]`, …) reach engines as a
+//! [`SpeechStyle`]; see [`style`].
+//!
+//! [`export`] renders long texts, plain or [`ssml`], to WAV or MP3 files.
 
+pub mod chatterbox;
+pub mod export;
 pub mod kokoro;
+pub mod ssml;
+pub mod style;
 
+pub use chatterbox::ChatterboxTts;
 pub use kokoro::KokoroTts;
+pub use style::SpeechStyle;
+
+use crate::config::{TtsBackend, TtsConfig};
+use crate::error::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Static description of what a TTS engine can do.
+///
+/// Served to the host UI (voice pickers, language hints) by the
+/// `speech.capabilities` command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TtsCapabilities {
+    /// Engine identifier (matches the serialized [`TtsBackend`] name).
+    pub engine: String,
+    /// BCP-47 language tags the engine can speak.
+    pub languages: Vec<String>,
+    /// Voice names accepted by [`TtsConfig::voice`].
+    pub voices: Vec<String>,
+    /// Whether the engine can stream audio before a sentence is fully synthesized.
+    pub streaming: bool,
+    /// Native output sample rate in Hz.
+    pub sample_rate: u32,
+}
+
+/// Common contract for every text-to-speech engine.
+#[async_trait]
+pub trait TtsEngine: Send {
+    /// Stable engine identifier (e.g. `kokoro`, `chatterbox`).
+    fn id(&self) -> &'static str;
+
+    /// Synthesize text to f32 mono samples at [`Self::sample_rate`].
+    async fn synthesize(&mut self, text: &str) -> Result<Vec<f32>>;
+
+    /// Synthesize text delivered in `style`.
+    ///
+    /// The default only applies the volume to the finished audio; engines
+    /// with their own prosody controls override this.
+    async fn synthesize_styled(&mut self, text: &str, style: &SpeechStyle) -> Result<Vec<f32>> {
+        let mut samples = self.synthesize(text).await?;
+        style.apply_gain(&mut samples);
+        Ok(samples)
+    }
+
+    /// Output sample rate of the samples returned by [`Self::synthesize`].
+    fn sample_rate(&self) -> u32;
+
+    /// Capability report for this engine instance.
+    fn capabilities(&self) -> TtsCapabilities;
+}
+
+/// Construct the engine selected by `config.backend`.
+///
+/// In offline mode a remote Chatterbox server is replaced by local Kokoro.
+///
+/// # Errors
+///
+/// Returns an error if the selected engine fails to load or is unreachable.
+pub async fn create_engine(config: &TtsConfig) -> Result<Box<dyn TtsEngine>> {
+    match engine_backend(config) {
+        TtsBackend::Kokoro => Ok(Box::new(KokoroTts::new(config)?)),
+        TtsBackend::Chatterbox => Ok(Box::new(ChatterboxTts::connect(config).await?)),
+    }
+}
+
+/// Capabilities of the engine [`create_engine`] would construct, without
+/// loading a model or contacting a server.
+///
+/// A Chatterbox server's voice list is only known once connected, so only
+/// the configured voice is reported for it.
+pub fn capabilities(config: &TtsConfig) -> TtsCapabilities {
+    match engine_backend(config) {
+        TtsBackend::Kokoro => kokoro::capabilities(),
+        TtsBackend::Chatterbox => chatterbox::capabilities(config.chatterbox_voice.as_slice()),
+    }
+}
+
+/// The backend actually used for `config`: in offline mode a remote
+/// Chatterbox server is replaced by local Kokoro.
+fn engine_backend(config: &TtsConfig) -> TtsBackend {
+    if config.backend == TtsBackend::Chatterbox
+        && crate::offline::is_offline()
+        && !crate::offline::is_loopback_url(&config.chatterbox_url)
+    {
+        tracing::info!("offline mode: using local Kokoro TTS instead of remote Chatterbox");
+        return TtsBackend::Kokoro;
+    }
+    config.backend
+}
+
+/// Cheap, blocking readiness check for the engine selected by `backend`.
+///
+/// Does not load models; used by doctor to report misconfigured engines.
+///
+/// # Errors
+///
+/// Returns a description of why the engine is not ready.
+pub fn preflight(backend: TtsBackend, config: &TtsConfig) -> Result<()> {
+    match backend {
+        TtsBackend::Kokoro => kokoro::preflight(config),
+        TtsBackend::Chatterbox => chatterbox::preflight(config),
+    }
+}
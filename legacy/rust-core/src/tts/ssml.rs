@@ -0,0 +1,311 @@
+//! A small SSML subset for long-form synthesis.
+//!
+//! Text handed to [`export`](super::export) may be plain text or an SSML
+//! document (`<speak>…</speak>`). The supported elements map onto what every
+//! engine can do:
+//!
+//! - `<break time="500ms"/>` or `<break strength="strong"/>` — silence
+//! - `<p>` and `<s>` — a pause after each paragraph or sentence
+//! - `<prosody rate="slow" volume="soft">` — rate (`x-slow`…`x-fast`, `80%`)
+//!   and volume (`silent`…`x-loud`, `+6dB`) as a [`SpeechStyle`]
+//! - `<emphasis level="strong">` — more expressive, slightly slower delivery
+//!
+//! Other elements are dropped and their text kept, so `<voice>`, `<lang>` or
+//! `<say-as>` read as plain text rather than failing.
+
+use crate::error::{Result, SpeechError};
+
+use super::SpeechStyle;
+
+/// Longest pause a single `<break>` may insert, as in the SSML spec.
+pub const MAX_BREAK_SECS: f32 = 10.0;
+
+/// Pause after a `<p>` paragraph.
+const PARAGRAPH_PAUSE_SECS: f32 = 0.75;
+
+/// Pause after an `<s>` sentence.
+const SENTENCE_PAUSE_SECS: f32 = 0.4;
+
+/// A piece of parsed input: text to speak or a pause.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpeechSegment {
+    Text { text: String, style: SpeechStyle },
+    Pause { secs: f32 },
+}
+
+/// Whether `input` is an SSML document rather than plain text.
+pub fn is_ssml(input: &str) -> bool {
+    let mut input = input.trim_start();
+    if input.starts_with("<?xml")
+        && let Some(end) = input.find("?>")
+    {
+        input = input[end + 2..].trim_start();
+    }
+    input.starts_with("<speak")
+}
+
+/// Parse `input` into segments. Plain text is a single segment in the
+/// default style.
+///
+/// # Errors
+///
+/// Returns an error if an SSML tag is never closed with `>`.
+pub fn parse(input: &str) -> Result<Vec<SpeechSegment>> {
+    let mut segments = Vec::new();
+    if !is_ssml(input) {
+        push_text(&mut segments, input, SpeechStyle::default());
+        return Ok(segments);
+    }
+
+    // Open elements and the style in force inside each.
+    let mut stack: Vec<(String, SpeechStyle)> = Vec::new();
+    let mut rest = input;
+    while let Some(open) = rest.find('<') {
+        let style = current_style(&stack);
+        push_text(&mut segments, &decode_entities(&rest[..open]), style);
+        let close = rest[open..]
+            .find('>')
+            .ok_or_else(|| SpeechError::Tts("malformed SSML: unterminated tag".to_owned()))?;
+        let tag = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            if let Some(pos) = stack.iter().rposition(|(open, _)| *open == name) {
+                stack.truncate(pos);
+                match name.as_str() {
+                    "p" => push_pause(&mut segments, PARAGRAPH_PAUSE_SECS),
+                    "s" => push_pause(&mut segments, SENTENCE_PAUSE_SECS),
+                    _ => {}
+                }
+            }
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        let name = name.to_ascii_lowercase();
+        if name == "break" {
+            push_pause(&mut segments, break_secs(attrs));
+            continue;
+        }
+        if self_closing {
+            continue;
+        }
+        let mut style = style;
+        match name.as_str() {
+            "prosody" => apply_prosody(&mut style, attrs),
+            "emphasis" => apply_emphasis(&mut style, attribute(attrs, "level")),
+            _ => {}
+        }
+        stack.push((name, style));
+    }
+    push_text(&mut segments, &decode_entities(rest), current_style(&stack));
+    Ok(segments)
+}
+
+fn current_style(stack: &[(String, SpeechStyle)]) -> SpeechStyle {
+    stack
+        .last()
+        .map_or_else(SpeechStyle::default, |(_, style)| *style)
+}
+
+/// Append `text`, joining it to the previous segment when the style matches.
+fn push_text(segments: &mut Vec<SpeechSegment>, text: &str, style: SpeechStyle) {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return;
+    }
+    if let Some(SpeechSegment::Text {
+        text: last,
+        style: last_style,
+    }) = segments.last_mut()
+        && *last_style == style
+    {
+        last.push(' ');
+        last.push_str(&text);
+        return;
+    }
+    segments.push(SpeechSegment::Text { text, style });
+}
+
+/// Append a pause, merging it into a preceding one.
+fn push_pause(segments: &mut Vec<SpeechSegment>, secs: f32) {
+    if secs <= 0.0 {
+        return;
+    }
+    if let Some(SpeechSegment::Pause { secs: last }) = segments.last_mut() {
+        *last = last.max(secs);
+        return;
+    }
+    segments.push(SpeechSegment::Pause { secs });
+}
+
+/// Pause length of a `<break>` with `attrs`.
+fn break_secs(attrs: &str) -> f32 {
+    if let Some(time) = attribute(attrs, "time") {
+        let time = time.trim().to_ascii_lowercase();
+        let secs = if let Some(ms) = time.strip_suffix("ms") {
+            ms.trim().parse::<f32>().map(|ms| ms / 1000.0)
+        } else {
+            time.trim_end_matches('s').trim().parse::<f32>()
+        };
+        return secs.unwrap_or(0.0).clamp(0.0, MAX_BREAK_SECS);
+    }
+    match attribute(attrs, "strength").unwrap_or("medium") {
+        "none" => 0.0,
+        "x-weak" => 0.1,
+        "weak" => 0.25,
+        "strong" => 0.75,
+        "x-strong" => 1.2,
+        _ => 0.4,
+    }
+}
+
+fn apply_prosody(style: &mut SpeechStyle, attrs: &str) {
+    if let Some(rate) = attribute(attrs, "rate") {
+        style.rate = match rate {
+            "x-slow" => 0.7,
+            "slow" => 0.85,
+            "medium" | "default" => 1.0,
+            "fast" => 1.15,
+            "x-fast" => 1.3,
+            other => parse_relative(other).unwrap_or(style.rate),
+        }
+        .clamp(0.5, 2.0);
+    }
+    if let Some(volume) = attribute(attrs, "volume") {
+        style.volume = match volume {
+            "silent" => 0.0,
+            "x-soft" => 0.4,
+            "soft" => 0.7,
+            "medium" | "default" => 1.0,
+            "loud" => 1.2,
+            "x-loud" => 1.4,
+            other => other
+                .strip_suffix("dB")
+                .and_then(|db| db.trim_start_matches('+').parse::<f32>().ok())
+                .map_or(style.volume, |db| style.volume * 10f32.powf(db / 20.0)),
+        }
+        .clamp(0.0, 2.0);
+    }
+}
+
+fn apply_emphasis(style: &mut SpeechStyle, level: Option<&str>) {
+    match level.unwrap_or("moderate") {
+        "none" => {}
+        "reduced" => style.expressiveness = 0.35,
+        "strong" => {
+            style.expressiveness = 0.85;
+            style.rate *= 0.9;
+        }
+        _ => {
+            style.expressiveness = 0.7;
+            style.rate *= 0.95;
+        }
+    }
+}
+
+/// `80%` or `0.8` as a multiplier.
+fn parse_relative(value: &str) -> Option<f32> {
+    match value.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f32>().ok().map(|p| p / 100.0),
+        None => value.trim().parse::<f32>().ok(),
+    }
+}
+
+/// Value of attribute `name` in `attrs`, quoted with `"` or `'`.
+fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)?;
+        if key.eq_ignore_ascii_case(name) {
+            return Some(&value[1..=end]);
+        }
+        rest = &value[end + 2..];
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[test]
+    fn plain_text_is_one_segment() {
+        let segments = parse("  Read this\n article.  ").expect("parse");
+        assert_eq!(
+            segments,
+            vec![SpeechSegment::Text {
+                text: "Read this article.".to_owned(),
+                style: SpeechStyle::default(),
+            }]
+        );
+        assert!(parse("   ").expect("parse").is_empty());
+    }
+
+    #[test]
+    fn ssml_breaks_and_prosody_become_segments() {
+        let ssml = r#"<?xml version="1.0"?>
+            <speak version="1.1">
+              <p>Chapter one &amp; all that.</p>
+              <break time="1500ms"/>
+              <prosody rate="slow" volume="soft">Slowly <emphasis level="strong">now</emphasis>.</prosody>
+              <say-as interpret-as="characters">OK</say-as>
+            </speak>"#;
+        let segments = parse(ssml).expect("parse");
+
+        let slow_soft = SpeechStyle {
+            rate: 0.85,
+            volume: 0.7,
+            ..SpeechStyle::default()
+        };
+        assert_eq!(segments.len(), 6);
+        assert_eq!(
+            segments[0],
+            SpeechSegment::Text {
+                text: "Chapter one & all that.".to_owned(),
+                style: SpeechStyle::default(),
+            }
+        );
+        assert_eq!(segments[1], SpeechSegment::Pause { secs: 1.5 });
+        assert_eq!(
+            segments[2],
+            SpeechSegment::Text {
+                text: "Slowly".to_owned(),
+                style: slow_soft,
+            }
+        );
+        let SpeechSegment::Text { style, .. } = &segments[3] else {
+            panic!("expected emphasised text");
+        };
+        assert!(style.rate < slow_soft.rate && style.expressiveness > 0.8);
+        assert_eq!(
+            segments[5],
+            SpeechSegment::Text {
+                text: "OK".to_owned(),
+                style: SpeechStyle::default(),
+            }
+        );
+
+        assert_eq!(break_secs(r#"time="60s""#), MAX_BREAK_SECS);
+        assert!(parse("<speak>unterminated <break").is_err());
+    }
+}
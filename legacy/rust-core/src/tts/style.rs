@@ -0,0 +1,259 @@
+//! Inline delivery tags from the LLM.
+//!
+//! The system prompt teaches the model a small tag vocabulary (`[whisper]`,
+//! `[excited]`, `[slow]`, …). The TTS stage strips the tags from each
+//! sentence and turns them into a [`SpeechStyle`] that engines map to their
+//! own prosody controls. A tag holds from where it appears until `[normal]`
+//! or the end of the reply; bracketed words outside the vocabulary are
+//! dropped so they are never read aloud.
+
+use serde::{Deserialize, Serialize};
+
+/// Longest bracketed word treated as a tag rather than text.
+const MAX_TAG_LEN: usize = 24;
+
+/// A delivery tag the model may emit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleTag {
+    Whisper,
+    Soft,
+    Excited,
+    Happy,
+    Sad,
+    Calm,
+    Serious,
+    Slow,
+    Fast,
+    /// Back to the default delivery.
+    Normal,
+}
+
+impl StyleTag {
+    /// Every tag, in the order the prompt lists them.
+    pub const ALL: [StyleTag; 10] = [
+        StyleTag::Whisper,
+        StyleTag::Soft,
+        StyleTag::Excited,
+        StyleTag::Happy,
+        StyleTag::Sad,
+        StyleTag::Calm,
+        StyleTag::Serious,
+        StyleTag::Slow,
+        StyleTag::Fast,
+        StyleTag::Normal,
+    ];
+
+    /// Tag name as written inside the brackets.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Whisper => "whisper",
+            Self::Soft => "soft",
+            Self::Excited => "excited",
+            Self::Happy => "happy",
+            Self::Sad => "sad",
+            Self::Calm => "calm",
+            Self::Serious => "serious",
+            Self::Slow => "slow",
+            Self::Fast => "fast",
+            Self::Normal => "normal",
+        }
+    }
+
+    /// Parse a tag name (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|tag| tag.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Engine-neutral delivery settings for one sentence.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpeechStyle {
+    /// Speaking-rate multiplier on top of the configured speed.
+    pub rate: f32,
+    /// Output gain applied to the synthesized samples.
+    pub volume: f32,
+    /// Emotional intensity from 0.0 (flat) to 1.0; 0.5 is neutral.
+    pub expressiveness: f32,
+}
+
+impl Default for SpeechStyle {
+    fn default() -> Self {
+        Self {
+            rate: 1.0,
+            volume: 1.0,
+            expressiveness: 0.5,
+        }
+    }
+}
+
+impl SpeechStyle {
+    /// Whether this is the default delivery.
+    pub fn is_neutral(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Apply `tag`. Each tag sets only the controls it is about, so `[slow]`
+    /// after `[whisper]` keeps the whisper.
+    pub fn apply(&mut self, tag: StyleTag) {
+        match tag {
+            StyleTag::Whisper => {
+                self.volume = 0.45;
+                self.expressiveness = 0.3;
+            }
+            StyleTag::Soft => {
+                self.volume = 0.7;
+                self.expressiveness = 0.4;
+            }
+            StyleTag::Excited => {
+                self.rate = 1.1;
+                self.volume = 1.1;
+                self.expressiveness = 0.85;
+            }
+            StyleTag::Happy => {
+                self.rate = 1.05;
+                self.expressiveness = 0.7;
+            }
+            StyleTag::Sad => {
+                self.rate = 0.9;
+                self.volume = 0.85;
+                self.expressiveness = 0.35;
+            }
+            StyleTag::Calm => {
+                self.rate = 0.95;
+                self.expressiveness = 0.35;
+            }
+            StyleTag::Serious => self.expressiveness = 0.3,
+            StyleTag::Slow => self.rate = 0.85,
+            StyleTag::Fast => self.rate = 1.15,
+            StyleTag::Normal => *self = Self::default(),
+        }
+    }
+
+    /// Scale `samples` by [`Self::volume`], keeping them within `[-1, 1]`.
+    pub fn apply_gain(&self, samples: &mut [f32]) {
+        if (self.volume - 1.0).abs() < f32::EPSILON {
+            return;
+        }
+        for sample in samples {
+            *sample = (*sample * self.volume).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// Remove delivery tags from `text`, returning the remaining text and the
+/// recognised tags in order.
+///
+/// A tag is a bracketed word or short phrase of letters, spaces, `-` or `_`
+/// (`[whisper]`, `[laughs softly]`). Unknown tags are removed too. Brackets
+/// that open a markdown link (`[title](url)`) or hold anything else
+/// (`[1]`, `[x]`) are left alone.
+pub fn take_style_tags(text: &str) -> (String, Vec<StyleTag>) {
+    let mut out = String::with_capacity(text.len());
+    let mut tags = Vec::new();
+    let mut removed = false;
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let tag = after.find(']').and_then(|close| {
+            let name = &after[..close];
+            let is_tag = (2..=MAX_TAG_LEN).contains(&name.len())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphabetic() || matches!(c, ' ' | '-' | '_'))
+                && !after[close + 1..].starts_with('(');
+            is_tag.then_some((name, close))
+        });
+        match tag {
+            Some((name, close)) => {
+                match StyleTag::from_name(name) {
+                    Some(tag) => tags.push(tag),
+                    None => tracing::debug!(tag = name, "ignoring unknown delivery tag"),
+                }
+                rest = &after[close + 1..];
+                if out.is_empty() || out.ends_with(char::is_whitespace) {
+                    rest = rest.trim_start_matches(' ');
+                }
+                removed = true;
+            }
+            None => {
+                out.push('[');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    if removed {
+        out = out.trim().to_owned();
+    }
+    (out, tags)
+}
+
+/// Prompt section teaching the model the tag vocabulary.
+pub fn prompt_section() -> String {
+    let names: Vec<String> = StyleTag::ALL
+        .iter()
+        .map(|tag| format!("[{}]", tag.name()))
+        .collect();
+    format!(
+        "Delivery tags:\n\
+         - You may put a delivery tag before a sentence to change how it is spoken: {}.\n\
+         - A tag lasts until [normal] or the end of your reply. Tags are never read aloud.\n\
+         - Use them sparingly, only when the tone really calls for it.",
+        names.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+    use super::*;
+
+    #[test]
+    fn known_and_unknown_tags_are_stripped() {
+        let (text, tags) = take_style_tags("[whisper] It's a [SLOW] secret. [giggles]");
+        assert_eq!(text, "It's a secret.");
+        assert_eq!(tags, vec![StyleTag::Whisper, StyleTag::Slow]);
+    }
+
+    #[test]
+    fn other_brackets_are_left_alone() {
+        let text = "See [the docs](https://example.com), item [1] and [x] [";
+        assert_eq!(take_style_tags(text), (text.to_owned(), Vec::new()));
+    }
+
+    #[test]
+    fn tags_set_only_their_own_controls() {
+        let mut style = SpeechStyle::default();
+        style.apply(StyleTag::Whisper);
+        style.apply(StyleTag::Slow);
+        assert_eq!(style.volume, 0.45);
+        assert_eq!(style.rate, 0.85);
+        style.apply(StyleTag::Normal);
+        assert!(style.is_neutral());
+    }
+
+    #[test]
+    fn gain_is_clamped() {
+        let mut samples = vec![0.5, -0.95];
+        let style = SpeechStyle {
+            volume: 1.1,
+            ..SpeechStyle::default()
+        };
+        style.apply_gain(&mut samples);
+        assert_eq!(samples, vec![0.55, -1.0]);
+    }
+
+    #[test]
+    fn prompt_lists_every_tag() {
+        let prompt = prompt_section();
+        assert!(
+            StyleTag::ALL
+                .iter()
+                .all(|tag| prompt.contains(&format!("[{}]", tag.name())))
+        );
+    }
+}
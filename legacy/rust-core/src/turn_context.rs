@@ -0,0 +1,421 @@
+//! Current date, time, timezone and locale, injected into every turn.
+//!
+//! Models have no clock, and a local model's idea of "today" is its
+//! training cutoff. Before each turn the LLM stage prepends a
+//! `<current_context>` block to the user's message (not the system prompt,
+//! which stays identical between turns so its prefill can be reused):
+//!
+//! ```text
+//! <current_context>
+//! - Now: 2026-10-16T14:32:05+01:00 (Friday)
+//! - Timezone: Europe/London (UTC+01:00)
+//! - Locale: en-GB
+//! - Location: Edinburgh
+//! - Calendar today: busy 15:00–16:00, 17:30–18:00
+//! </current_context>
+//! ```
+//!
+//! The block is assembled by a chain of [`ContextProvider`]s: the built-in
+//! clock, timezone, locale, location and calendar providers, followed by any that
+//! skills and other modules [`register`]. Location and the calendar
+//! free/busy line are opt-in through `[turn_context]` in `config.toml`; the
+//! calendar line never includes event titles.
+//!
+//! Like offline mode, the settings are process-wide; see [`apply`].
+
+use std::sync::{Arc, LazyLock, RwLock};
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, NaiveTime};
+use tracing::debug;
+
+use crate::config::TurnContextConfig;
+use crate::fae_llm::tools::apple::calendar::{CalendarStore, EventQuery, parse_event_time};
+
+/// Busy blocks listed in the calendar line before the rest are counted.
+const MAX_BUSY_BLOCKS: usize = 6;
+
+/// Everything a provider may draw on for one turn.
+#[derive(Debug, Clone)]
+pub struct TurnContext {
+    pub now: DateTime<Local>,
+    pub config: TurnContextConfig,
+    /// Language for spoken system messages, if one is configured.
+    pub language: Option<String>,
+}
+
+/// A contributor of lines to the `<current_context>` block.
+pub trait ContextProvider: Send + Sync {
+    /// Stable name; registering a provider with the same name replaces it.
+    fn name(&self) -> &str;
+
+    /// Lines (without the leading `- `) this provider adds for the turn.
+    fn lines(&self, ctx: &TurnContext) -> Vec<String>;
+}
+
+/// ISO date and time with weekday.
+struct ClockProvider;
+
+impl ContextProvider for ClockProvider {
+    fn name(&self) -> &str {
+        "clock"
+    }
+
+    fn lines(&self, ctx: &TurnContext) -> Vec<String> {
+        vec![format!(
+            "Now: {} ({})",
+            ctx.now.format("%Y-%m-%dT%H:%M:%S%:z"),
+            ctx.now.format("%A")
+        )]
+    }
+}
+
+/// Timezone name, where the system reports one, and UTC offset.
+struct TimezoneProvider;
+
+impl ContextProvider for TimezoneProvider {
+    fn name(&self) -> &str {
+        "timezone"
+    }
+
+    fn lines(&self, ctx: &TurnContext) -> Vec<String> {
+        let offset = format!("UTC{}", ctx.now.format("%:z"));
+        vec![match system_timezone() {
+            Some(zone) => format!("Timezone: {zone} ({offset})"),
+            None => format!("Timezone: {offset}"),
+        }]
+    }
+}
+
+/// Configured locale, else the system's.
+struct LocaleProvider;
+
+impl ContextProvider for LocaleProvider {
+    fn name(&self) -> &str {
+        "locale"
+    }
+
+    fn lines(&self, ctx: &TurnContext) -> Vec<String> {
+        let locale = ctx
+            .config
+            .locale
+            .clone()
+            .or_else(system_locale)
+            .or_else(|| ctx.language.clone());
+        locale
+            .map(|locale| format!("Locale: {locale}"))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Location the user configured.
+struct LocationProvider;
+
+impl ContextProvider for LocationProvider {
+    fn name(&self) -> &str {
+        "location"
+    }
+
+    fn lines(&self, ctx: &TurnContext) -> Vec<String> {
+        ctx.config
+            .location
+            .as_deref()
+            .map(str::trim)
+            .filter(|location| !location.is_empty())
+            .map(|location| format!("Location: {location}"))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Busy blocks for the rest of today, without titles.
+struct CalendarBusyProvider;
+
+impl ContextProvider for CalendarBusyProvider {
+    fn name(&self) -> &str {
+        "calendar"
+    }
+
+    fn lines(&self, ctx: &TurnContext) -> Vec<String> {
+        if !ctx.config.calendar_busy {
+            return Vec::new();
+        }
+        let store = crate::fae_llm::tools::apple::global_calendar_store();
+        calendar_line(store.as_ref(), ctx.now).into_iter().collect()
+    }
+}
+
+/// The calendar free/busy line for the rest of the day of `now`, or `None`
+/// when the calendar can't be read.
+pub fn calendar_line(store: &dyn CalendarStore, now: DateTime<Local>) -> Option<String> {
+    let end_of_day = (now.date_naive() + Duration::days(1)).and_time(NaiveTime::MIN);
+    let query = EventQuery {
+        calendar_ids: Vec::new(),
+        start_after: None,
+        end_before: Some(end_of_day.format("%Y-%m-%dT%H:%M:%S").to_string()),
+        limit: 50,
+    };
+    let events = store
+        .list_events(&query)
+        .inspect_err(|e| debug!("no calendar for turn context: {e:?}"))
+        .ok()?;
+    let now = now.naive_local();
+    let mut all_day = 0usize;
+    let mut busy: Vec<(NaiveDateTime, NaiveDateTime)> = Vec::new();
+    for event in events {
+        if event.is_all_day {
+            all_day += 1;
+            continue;
+        }
+        let (Some(start), Some(end)) =
+            (parse_event_time(&event.start), parse_event_time(&event.end))
+        else {
+            continue;
+        };
+        if end > now && start < end_of_day {
+            busy.push((start.max(now), end.min(end_of_day)));
+        }
+    }
+    busy.sort();
+    // Merge overlapping and back-to-back events into one block.
+    let mut blocks: Vec<(NaiveDateTime, NaiveDateTime)> = Vec::new();
+    for (start, end) in busy {
+        match blocks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => blocks.push((start, end)),
+        }
+    }
+
+    let mut parts: Vec<String> = blocks
+        .iter()
+        .take(MAX_BUSY_BLOCKS)
+        .map(|(start, end)| format!("{}–{}", start.format("%H:%M"), end.format("%H:%M")))
+        .collect();
+    if blocks.len() > MAX_BUSY_BLOCKS {
+        parts.push(format!("{} more", blocks.len() - MAX_BUSY_BLOCKS));
+    }
+    let mut line = if parts.is_empty() {
+        "Calendar today: free for the rest of the day".to_owned()
+    } else {
+        format!("Calendar today: busy {}", parts.join(", "))
+    };
+    if all_day > 0 {
+        let noun = if all_day == 1 { "event" } else { "events" };
+        line.push_str(&format!("; {all_day} all-day {noun}"));
+    }
+    Some(line)
+}
+
+/// IANA name of the system timezone: `TZ`, else the `/etc/localtime` link.
+fn system_timezone() -> Option<String> {
+    if let Ok(tz) = std::env::var("TZ") {
+        let tz = tz.trim_start_matches(':').trim();
+        if !tz.is_empty() {
+            return Some(tz.to_owned());
+        }
+    }
+    let target = std::fs::read_link("/etc/localtime").ok()?;
+    let target = target.to_string_lossy();
+    target
+        .split_once("zoneinfo/")
+        .map(|(_, zone)| zone.to_owned())
+}
+
+/// System locale from the environment, as a BCP 47 tag (`en_GB.UTF-8` →
+/// `en-GB`).
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|value| {
+            value
+                .split(['.', '@'])
+                .next()
+                .unwrap_or_default()
+                .replace('_', "-")
+        })
+        .find(|locale| !locale.is_empty() && locale != "C" && locale != "POSIX")
+}
+
+/// The providers consulted for each turn, in order.
+pub struct ContextChain {
+    providers: Vec<Arc<dyn ContextProvider>>,
+}
+
+impl ContextChain {
+    /// The built-in providers.
+    pub fn builtin() -> Self {
+        Self {
+            providers: vec![
+                Arc::new(ClockProvider),
+                Arc::new(TimezoneProvider),
+                Arc::new(LocaleProvider),
+                Arc::new(LocationProvider),
+                Arc::new(CalendarBusyProvider),
+            ],
+        }
+    }
+
+    /// Add `provider` after the others, replacing one with the same name.
+    pub fn with(mut self, provider: Arc<dyn ContextProvider>) -> Self {
+        self.providers.retain(|p| p.name() != provider.name());
+        self.providers.push(provider);
+        self
+    }
+
+    /// The `<current_context>` block for `ctx`.
+    pub fn render(&self, ctx: &TurnContext) -> String {
+        let mut block = String::from("<current_context>\n");
+        for provider in &self.providers {
+            for line in provider.lines(ctx) {
+                block.push_str("- ");
+                block.push_str(line.trim());
+                block.push('\n');
+            }
+        }
+        block.push_str("</current_context>");
+        block
+    }
+}
+
+static SETTINGS: RwLock<Option<(TurnContextConfig, Option<String>)>> = RwLock::new(None);
+static EXTRA_PROVIDERS: LazyLock<RwLock<Vec<Arc<dyn ContextProvider>>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Apply a turn-context configuration process-wide. `language` is the
+/// configured language for spoken system messages, used as the locale when
+/// neither the configuration nor the system names one.
+pub fn apply(config: &TurnContextConfig, language: Option<&str>) {
+    let mut settings = SETTINGS.write().unwrap_or_else(|e| e.into_inner());
+    *settings = Some((config.clone(), language.map(str::to_owned)));
+}
+
+/// Add `provider` to every turn's context, replacing one with the same name.
+pub fn register(provider: Arc<dyn ContextProvider>) {
+    let mut extra = EXTRA_PROVIDERS.write().unwrap_or_else(|e| e.into_inner());
+    extra.retain(|p| p.name() != provider.name());
+    extra.push(provider);
+}
+
+/// The `<current_context>` block for a turn starting now, or `None` when
+/// turn context is disabled.
+pub fn render() -> Option<String> {
+    let (config, language) = SETTINGS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default();
+    if !config.enabled {
+        return None;
+    }
+    let chain = EXTRA_PROVIDERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .fold(ContextChain::builtin(), |chain, provider| {
+            chain.with(Arc::clone(provider))
+        });
+    let ctx = TurnContext {
+        now: Local::now(),
+        config,
+        language,
+    };
+    Some(chain.render(&ctx))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::fae_llm::tools::apple::calendar::CalendarEvent;
+    use crate::fae_llm::tools::apple::mock_stores::MockCalendarStore;
+
+    fn now() -> DateTime<Local> {
+        chrono::NaiveDate::from_ymd_opt(2026, 10, 16)
+            .and_then(|d| d.and_hms_opt(14, 32, 5))
+            .and_then(|t| t.and_local_timezone(Local).single())
+            .expect("time")
+    }
+
+    fn event(start: &str, end: &str, is_all_day: bool) -> CalendarEvent {
+        CalendarEvent {
+            identifier: start.to_owned(),
+            calendar_id: "work".to_owned(),
+            title: "Private".to_owned(),
+            start: start.to_owned(),
+            end: end.to_owned(),
+            location: None,
+            notes: None,
+            is_all_day,
+            alarms: Vec::new(),
+        }
+    }
+
+    struct WeatherProvider(&'static str);
+
+    impl ContextProvider for WeatherProvider {
+        fn name(&self) -> &str {
+            "weather"
+        }
+
+        fn lines(&self, _ctx: &TurnContext) -> Vec<String> {
+            vec![format!("Weather: {}", self.0)]
+        }
+    }
+
+    #[test]
+    fn block_lists_date_locale_and_contributed_lines() {
+        let ctx = TurnContext {
+            now: now(),
+            config: TurnContextConfig {
+                locale: Some("en-GB".to_owned()),
+                location: Some(" Edinburgh ".to_owned()),
+                ..Default::default()
+            },
+            language: None,
+        };
+        let block = ContextChain::builtin()
+            .with(Arc::new(WeatherProvider("rain")))
+            .with(Arc::new(WeatherProvider("sun")))
+            .render(&ctx);
+
+        let lines: Vec<&str> = block.lines().collect();
+        assert_eq!(lines[0], "<current_context>");
+        assert!(
+            lines[1].starts_with("- Now: 2026-10-16T14:32:05"),
+            "{block}"
+        );
+        assert!(lines[1].ends_with("(Friday)"), "{block}");
+        assert!(lines[2].starts_with("- Timezone: "), "{block}");
+        assert_eq!(lines[3], "- Locale: en-GB");
+        assert_eq!(lines[4], "- Location: Edinburgh");
+        assert_eq!(lines[5], "- Weather: sun");
+        assert_eq!(lines[6], "</current_context>");
+    }
+
+    #[test]
+    fn calendar_line_merges_busy_blocks_without_titles() {
+        let store = MockCalendarStore::new(
+            Vec::new(),
+            vec![
+                event("2026-10-16T09:00:00", "2026-10-16T10:00:00", false),
+                event("2026-10-16T14:00:00", "2026-10-16T15:00:00", false),
+                event("2026-10-16T15:00:00", "2026-10-16T16:00:00", false),
+                event("2026-10-16T17:30:00", "2026-10-16T18:00:00", false),
+                event("2026-10-16T00:00:00", "2026-10-17T00:00:00", true),
+            ],
+        );
+        let line = calendar_line(&store, now()).expect("line");
+        assert_eq!(
+            line,
+            "Calendar today: busy 14:32–16:00, 17:30–18:00; 1 all-day event"
+        );
+
+        let empty = MockCalendarStore::new(Vec::new(), Vec::new());
+        assert_eq!(
+            calendar_line(&empty, now()).as_deref(),
+            Some("Calendar today: free for the rest of the day")
+        );
+    }
+}
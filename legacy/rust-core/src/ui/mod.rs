@@ -2,3 +2,4 @@
 
 pub mod channel_panel;
 pub mod scheduler_panel;
+pub mod tui;
@@ -3,6 +3,7 @@
 //! Provides types for managing the scheduler UI panel, including editing
 //! tasks, form validation, and state management.
 
+use crate::scheduler::conflicts::{QuietHours, detect_conflicts};
 use crate::scheduler::tasks::{
     Schedule, ScheduledTask, TaskKind, TaskRunOutcome, TaskRunRecord, Weekday,
 };
@@ -34,6 +35,8 @@ pub struct EditingTask {
     pub enabled: bool,
     /// Optional JSON payload (as string for editing).
     pub payload: Option<String>,
+    /// Never run at the same time as another exclusive task.
+    pub exclusive: bool,
 }
 
 /// Form representation of schedule (uses Strings for user input).
@@ -122,6 +125,7 @@ impl EditingTask {
             schedule: ScheduleForm::default(),
             enabled: true,
             payload: None,
+            exclusive: false,
         }
     }
 
@@ -158,6 +162,7 @@ impl EditingTask {
             schedule,
             enabled: task.enabled,
             payload,
+            exclusive: task.exclusive,
         }
     }
 
@@ -263,6 +268,7 @@ impl EditingTask {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: self.exclusive,
         })
     }
 
@@ -496,6 +502,8 @@ pub struct TaskEditForm {
     pub editing_task: EditingTask,
     /// Validation errors (if any).
     pub validation_errors: Vec<ValidationError>,
+    /// Schedule conflicts found by [`Self::check_conflicts`]; saving is still allowed.
+    pub conflict_warnings: Vec<String>,
 }
 
 impl TaskEditForm {
@@ -505,6 +513,7 @@ impl TaskEditForm {
         Self {
             editing_task: EditingTask::new(),
             validation_errors: Vec::new(),
+            conflict_warnings: Vec::new(),
         }
     }
 
@@ -514,6 +523,7 @@ impl TaskEditForm {
         Self {
             editing_task: EditingTask::from_scheduled_task(task),
             validation_errors: Vec::new(),
+            conflict_warnings: Vec::new(),
         }
     }
 
@@ -532,6 +542,24 @@ impl TaskEditForm {
         }
     }
 
+    /// Check the form's schedule against quiet hours and the other `tasks`.
+    ///
+    /// Updates `conflict_warnings`; an invalid form has no warnings.
+    pub fn check_conflicts(&mut self, tasks: &[ScheduledTask], quiet: Option<QuietHours>) {
+        self.conflict_warnings = match self.editing_task.to_scheduled_task() {
+            Ok(task) => detect_conflicts(&task, tasks, quiet, crate::time_util::now_epoch_secs())
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+    }
+
+    /// Update the exclusive field.
+    pub fn set_exclusive(&mut self, exclusive: bool) {
+        self.editing_task.exclusive = exclusive;
+    }
+
     /// Save the task (convert to ScheduledTask).
     ///
     /// # Errors
@@ -620,10 +648,14 @@ impl ExecutionHistoryView {
         let duration = record.finished_at.saturating_sub(record.started_at);
         let duration_str = format_duration(duration);
 
-        format!(
+        let mut line = format!(
             "{} {} | {} | {} | {}",
             outcome_symbol, record.task_id, started, duration_str, record.summary
-        )
+        );
+        if let Some(path) = &record.log_path {
+            line.push_str(&format!(" | log: {path}"));
+        }
+        line
     }
 }
 
@@ -636,6 +668,7 @@ pub fn format_outcome(outcome: &TaskRunOutcome) -> &str {
         TaskRunOutcome::NeedsUserAction => "⚠",
         TaskRunOutcome::Error => "✗",
         TaskRunOutcome::SoftTimeout => "⏱",
+        TaskRunOutcome::Preempted => "⏸",
     }
 }
 
@@ -685,6 +718,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         };
 
         let editing = EditingTask::from_scheduled_task(&scheduled);
@@ -714,6 +748,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         };
 
         let editing = EditingTask::from_scheduled_task(&scheduled);
@@ -744,6 +779,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         };
 
         let editing = EditingTask::from_scheduled_task(&scheduled);
@@ -771,6 +807,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.to_scheduled_task();
@@ -791,6 +828,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.to_scheduled_task();
@@ -809,6 +847,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -825,6 +864,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -841,6 +881,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -858,6 +899,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -875,6 +917,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -893,6 +936,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -911,6 +955,7 @@ mod tests {
             },
             enabled: true,
             payload: None,
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -927,6 +972,7 @@ mod tests {
             },
             enabled: true,
             payload: Some("{not valid json}".to_owned()),
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -943,6 +989,7 @@ mod tests {
             },
             enabled: true,
             payload: Some("   ".to_owned()),
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -959,6 +1006,7 @@ mod tests {
             },
             enabled: true,
             payload: Some(r#"{"prompt": "test"}"#.to_owned()),
+            exclusive: false,
         };
 
         let result = editing.validate();
@@ -982,6 +1030,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         };
 
         let editing = EditingTask::from_scheduled_task(&original);
@@ -1031,6 +1080,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         }];
 
         let view = TaskListView::new(tasks.clone());
@@ -1056,6 +1106,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         }];
 
         let mut view = TaskListView::new(tasks);
@@ -1080,6 +1131,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         }];
 
         let mut view = TaskListView::new(tasks);
@@ -1109,6 +1161,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         }];
 
         let view = TaskListView::new(tasks);
@@ -1132,6 +1185,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         };
 
         let view = TaskListView::new(vec![task.clone()]);
@@ -1159,6 +1213,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         };
 
         let view = TaskListView::new(vec![task.clone()]);
@@ -1285,6 +1340,7 @@ mod tests {
             max_failure_streak_before_pause: 5,
             soft_timeout_secs: 300,
             last_error: None,
+            exclusive: false,
         };
 
         let form = TaskEditForm::from_task(&task);
@@ -1336,6 +1392,25 @@ mod tests {
         assert!(matches!(task.schedule, Schedule::Interval { secs: 120 }));
     }
 
+    #[test]
+    fn test_task_edit_form_check_conflicts() {
+        let mut existing =
+            ScheduledTask::user_task("backup", "Backup", Schedule::Daily { hour: 12, min: 0 });
+        existing.exclusive = true;
+
+        let mut form = TaskEditForm::new();
+        form.set_name("Report".to_owned());
+        form.set_schedule_daily("12".to_owned(), "5".to_owned());
+        form.check_conflicts(std::slice::from_ref(&existing), None);
+        assert!(form.conflict_warnings.is_empty());
+
+        form.set_exclusive(true);
+        form.check_conflicts(&[existing], None);
+        assert_eq!(form.conflict_warnings.len(), 1);
+        assert!(form.conflict_warnings[0].contains("Backup"));
+        assert!(form.save().unwrap().exclusive);
+    }
+
     #[test]
     fn test_task_edit_form_save_invalid() {
         let mut form = TaskEditForm::new();
@@ -1431,6 +1506,7 @@ mod tests {
             finished_at: 1010,
             outcome: TaskRunOutcome::Success,
             summary: "Test run".to_owned(),
+            log_path: None,
         }];
 
         let view = ExecutionHistoryView::new(records.clone());
@@ -1447,6 +1523,7 @@ mod tests {
                 finished_at: 1010,
                 outcome: TaskRunOutcome::Success,
                 summary: "Task 1 run".to_owned(),
+                log_path: None,
             },
             TaskRunRecord {
                 task_id: "task2".to_owned(),
@@ -1454,6 +1531,7 @@ mod tests {
                 finished_at: 2010,
                 outcome: TaskRunOutcome::Success,
                 summary: "Task 2 run".to_owned(),
+                log_path: None,
             },
         ];
 
@@ -1470,6 +1548,7 @@ mod tests {
                 finished_at: 1010,
                 outcome: TaskRunOutcome::Success,
                 summary: "Task 1".to_owned(),
+                log_path: None,
             },
             TaskRunRecord {
                 task_id: "task2".to_owned(),
@@ -1477,6 +1556,7 @@ mod tests {
                 finished_at: 2010,
                 outcome: TaskRunOutcome::Success,
                 summary: "Task 2".to_owned(),
+                log_path: None,
             },
         ];
 
@@ -1494,6 +1574,7 @@ mod tests {
                 finished_at: 1010,
                 outcome: TaskRunOutcome::Success,
                 summary: "Task 1".to_owned(),
+                log_path: None,
             },
             TaskRunRecord {
                 task_id: "task2".to_owned(),
@@ -1501,6 +1582,7 @@ mod tests {
                 finished_at: 2010,
                 outcome: TaskRunOutcome::Success,
                 summary: "Task 2".to_owned(),
+                log_path: None,
             },
             TaskRunRecord {
                 task_id: "task1".to_owned(),
@@ -1508,6 +1590,7 @@ mod tests {
                 finished_at: 3010,
                 outcome: TaskRunOutcome::Success,
                 summary: "Task 1 again".to_owned(),
+                log_path: None,
             },
         ];
 
@@ -1525,6 +1608,7 @@ mod tests {
             finished_at: 1010,
             outcome: TaskRunOutcome::Success,
             summary: "Completed successfully".to_owned(),
+            log_path: None,
         };
 
         let view = ExecutionHistoryView::new(vec![record.clone()]);
@@ -1558,6 +1642,7 @@ mod tests {
     #[test]
     fn test_format_outcome_soft_timeout() {
         assert_eq!(format_outcome(&TaskRunOutcome::SoftTimeout), "⏱");
+        assert_eq!(format_outcome(&TaskRunOutcome::Preempted), "⏸");
     }
 
     #[test]
@@ -0,0 +1,224 @@
+//! Terminal front-end event loop and rendering.
+
+use super::state::{Speaker, TuiState};
+use crate::error::{Result, SpeechError};
+use crate::host::channel::HostCommandClient;
+use crate::host::contract::{CommandEnvelope, CommandName, EventEnvelope};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+
+/// What a key press asks for.
+enum Action {
+    Send(String),
+    Approve(String, bool),
+    Quit,
+}
+
+/// Run the terminal front-end until the user quits.
+///
+/// Starts the runtime through `client`, renders its event stream, and sends
+/// typed text and approval answers back as host commands. Stops the runtime
+/// on exit.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be set up or drawn to.
+pub async fn run_tui(client: HostCommandClient) -> Result<()> {
+    let mut events = client.subscribe_events();
+    send(&client, CommandName::RuntimeStart, serde_json::json!({})).await;
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &client, &mut events).await;
+    ratatui::restore();
+
+    send(&client, CommandName::RuntimeStop, serde_json::json!({})).await;
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    client: &HostCommandClient,
+    events: &mut broadcast::Receiver<EventEnvelope>,
+) -> Result<()> {
+    let (key_tx, mut key_rx) = mpsc::unbounded_channel();
+    // crossterm reads block, so keys are read on their own thread.
+    std::thread::spawn(move || {
+        loop {
+            match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    if key_tx.send(key).is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+    });
+
+    let mut state = TuiState::new();
+    let mut redraw = tokio::time::interval(Duration::from_millis(250));
+    loop {
+        terminal
+            .draw(|frame| render(frame, &state))
+            .map_err(|e| SpeechError::Pipeline(format!("cannot draw terminal: {e}")))?;
+
+        tokio::select! {
+            key = key_rx.recv() => {
+                let Some(key) = key else { return Ok(()) };
+                match handle_key(&mut state, key) {
+                    Some(Action::Quit) => return Ok(()),
+                    Some(Action::Send(text)) => {
+                        send(
+                            client,
+                            CommandName::ConversationInjectText,
+                            serde_json::json!({"text": text}),
+                        )
+                        .await;
+                    }
+                    Some(Action::Approve(request_id, approved)) => {
+                        send(
+                            client,
+                            CommandName::ApprovalRespond,
+                            serde_json::json!({"request_id": request_id, "approved": approved}),
+                        )
+                        .await;
+                    }
+                    None => {}
+                }
+            }
+            event = events.recv() => match event {
+                Ok(event) => state.apply(&event),
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    tracing::warn!("terminal UI lagged, skipped {n} events");
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            _ = redraw.tick() => {}
+        }
+    }
+}
+
+fn handle_key(state: &mut TuiState, key: KeyEvent) -> Option<Action> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => Some(Action::Quit),
+        KeyCode::Char('c') if ctrl => Some(Action::Quit),
+        KeyCode::Char(answer @ ('y' | 'n')) if ctrl => state
+            .next_approval()
+            .map(|a| Action::Approve(a.request_id.clone(), answer == 'y')),
+        KeyCode::Char(c) if !ctrl => {
+            state.input.push(c);
+            None
+        }
+        KeyCode::Backspace => {
+            state.input.pop();
+            None
+        }
+        KeyCode::Enter => state.take_input().map(Action::Send),
+        _ => None,
+    }
+}
+
+async fn send(client: &HostCommandClient, command: CommandName, payload: serde_json::Value) {
+    let envelope = CommandEnvelope::new(uuid::Uuid::new_v4().to_string(), command, payload);
+    match client.send(envelope).await {
+        Ok(response) if !response.ok => {
+            tracing::warn!(command = command.as_str(), ?response.error, "command failed");
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(command = command.as_str(), "command failed: {e}"),
+    }
+}
+
+fn render(frame: &mut Frame, state: &TuiState) {
+    let approval_height = if state.approvals.is_empty() { 0 } else { 4 };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),
+            Constraint::Length(approval_height),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(rows[0]);
+
+    let transcript: Vec<Line> = state
+        .transcript
+        .iter()
+        .map(|line| {
+            let (who, color) = match line.speaker {
+                Speaker::User => ("you", Color::Cyan),
+                Speaker::Assistant => ("fae", Color::Magenta),
+            };
+            let mut text_style = Style::default();
+            if !line.is_final {
+                text_style = text_style.add_modifier(Modifier::DIM);
+            }
+            Line::from(vec![
+                Span::styled(format!("{who}: "), Style::default().fg(color)),
+                Span::styled(line.text.clone(), text_style),
+            ])
+        })
+        .collect();
+    let transcript_height = usize::from(columns[0].height.saturating_sub(2));
+    let skip = transcript.len().saturating_sub(transcript_height);
+    frame.render_widget(
+        Paragraph::new(transcript.into_iter().skip(skip).collect::<Vec<_>>())
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Fae — {} ", state.status)),
+            ),
+        columns[0],
+    );
+
+    let events_height = usize::from(columns[1].height.saturating_sub(2));
+    let events: Vec<Line> = state
+        .events
+        .iter()
+        .skip(state.events.len().saturating_sub(events_height))
+        .map(|e| Line::from(e.as_str()))
+        .collect();
+    frame.render_widget(
+        Paragraph::new(events).block(Block::default().borders(Borders::ALL).title(" Events ")),
+        columns[1],
+    );
+
+    if let Some(approval) = state.next_approval() {
+        let waiting = state.approvals.len();
+        frame.render_widget(
+            Paragraph::new(approval.detail.as_str())
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow))
+                        .title(format!(
+                            " Approve {}? Ctrl-Y yes, Ctrl-N no ({waiting} waiting) ",
+                            approval.name
+                        )),
+                ),
+            rows[1],
+        );
+    }
+
+    frame.render_widget(
+        Paragraph::new(state.input.as_str()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Message (Enter to send, Esc to quit) "),
+        ),
+        rows[2],
+    );
+}
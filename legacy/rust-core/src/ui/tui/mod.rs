@@ -0,0 +1,21 @@
+//! Terminal front-end for headless use.
+//!
+//! Renders the same host event stream the native shells receive — live
+//! transcripts, tool activity, and approval requests — and sends typed text
+//! and approval answers back as host commands, so Fae can be driven over
+//! SSH. The event-to-screen state is always built; the `ratatui` front-end
+//! itself sits behind the `tui` feature and is launched by the `fae-tui`
+//! binary.
+//!
+//! Keys: Enter sends the typed message, Ctrl-Y / Ctrl-N answer the oldest
+//! pending approval, Esc or Ctrl-C quits.
+
+mod state;
+
+pub use state::{PendingApproval, Speaker, TranscriptLine, TuiState};
+
+#[cfg(feature = "tui")]
+mod app;
+
+#[cfg(feature = "tui")]
+pub use app::run_tui;
@@ -0,0 +1,303 @@
+//! Terminal front-end state, reduced from host events.
+
+use crate::host::contract::EventEnvelope;
+use std::collections::VecDeque;
+
+/// Most transcript lines kept on screen.
+const MAX_TRANSCRIPT_LINES: usize = 500;
+
+/// Most agent event lines kept on screen.
+const MAX_EVENT_LINES: usize = 200;
+
+/// Longest tool output (in characters) shown in the event log.
+const MAX_EVENT_CHARS: usize = 160;
+
+/// Who a transcript line belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speaker {
+    User,
+    Assistant,
+}
+
+/// One line of the conversation transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptLine {
+    pub speaker: Speaker,
+    pub text: String,
+    /// Whether the line is complete; partial lines are still updating.
+    pub is_final: bool,
+    /// Assistant message id the line streams into.
+    message_id: Option<String>,
+}
+
+/// A tool approval waiting for the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingApproval {
+    pub request_id: String,
+    pub name: String,
+    /// Preview of the call, or its raw input.
+    pub detail: String,
+}
+
+/// Everything the terminal front-end shows.
+#[derive(Debug, Default)]
+pub struct TuiState {
+    pub transcript: VecDeque<TranscriptLine>,
+    pub events: VecDeque<String>,
+    pub approvals: Vec<PendingApproval>,
+    /// Conversation state from `theme.state_changed` (idle, listening, …).
+    pub status: String,
+    /// Text being typed.
+    pub input: String,
+}
+
+fn str_field<'a>(payload: &'a serde_json::Value, key: &str) -> &'a str {
+    payload
+        .get(key)
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default()
+}
+
+fn shorten(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.chars().count() > MAX_EVENT_CHARS || text.lines().nth(1).is_some() {
+        let cut: String = line.chars().take(MAX_EVENT_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        line.to_owned()
+    }
+}
+
+impl TuiState {
+    /// Create an idle state.
+    pub fn new() -> Self {
+        Self {
+            status: "idle".to_owned(),
+            ..Self::default()
+        }
+    }
+
+    /// Fold one host event into the state.
+    pub fn apply(&mut self, event: &EventEnvelope) {
+        let payload = &event.payload;
+        match event.event.as_str() {
+            "pipeline.transcription" => {
+                let is_final = payload["is_final"].as_bool().unwrap_or(true);
+                self.user_line(str_field(payload, "text"), is_final);
+            }
+            "conversation.text_injected" => self.user_line(str_field(payload, "text"), true),
+            "pipeline.assistant_text_delta" => self.assistant_delta(
+                str_field(payload, "message_id"),
+                str_field(payload, "delta"),
+                payload["is_final"].as_bool().unwrap_or(false),
+            ),
+            "pipeline.assistant_text_discarded" => {
+                let id = str_field(payload, "message_id");
+                self.transcript
+                    .retain(|line| line.message_id.as_deref() != Some(id));
+            }
+            "pipeline.tool_call" => {
+                self.log(format!("→ {}", str_field(payload, "name")));
+            }
+            "pipeline.tool_result" => {
+                let mark = if payload["success"].as_bool().unwrap_or(false) {
+                    "✓"
+                } else {
+                    "✗"
+                };
+                let output = shorten(str_field(payload, "output_text"));
+                self.log(format!("{mark} {} {output}", str_field(payload, "name")));
+            }
+            "approval.requested" => {
+                let preview = str_field(payload, "preview");
+                let detail = if preview.is_empty() {
+                    str_field(payload, "input_json")
+                } else {
+                    preview
+                };
+                let approval = PendingApproval {
+                    request_id: str_field(payload, "request_id").to_owned(),
+                    name: str_field(payload, "name").to_owned(),
+                    detail: detail.to_owned(),
+                };
+                self.log(format!("? approval needed: {}", approval.name));
+                self.approvals.push(approval);
+            }
+            "approval.resolved" => {
+                let id = str_field(payload, "request_id");
+                self.approvals.retain(|a| a.request_id != id);
+            }
+            "scheduler.prompt" => self.log(format!(
+                "⏰ {}: {}",
+                str_field(payload, "title"),
+                shorten(str_field(payload, "message"))
+            )),
+            "background_task.started" => {
+                self.log(format!("… {}", str_field(payload, "description")));
+            }
+            "background_task.completed" => {
+                self.log(format!("✓ {}", shorten(str_field(payload, "summary"))));
+            }
+            "runtime.error" => self.log(format!("! {}", str_field(payload, "error"))),
+            "runtime.started" => self.log("runtime started".to_owned()),
+            "runtime.stopped" => self.log("runtime stopped".to_owned()),
+            crate::theme::STATE_CHANGED_EVENT => {
+                self.status = str_field(payload, "state").to_owned();
+            }
+            _ => {}
+        }
+    }
+
+    /// The oldest approval still waiting, if any.
+    pub fn next_approval(&self) -> Option<&PendingApproval> {
+        self.approvals.first()
+    }
+
+    /// Take the typed text for sending, leaving the input empty.
+    ///
+    /// Returns `None` when only whitespace was typed.
+    pub fn take_input(&mut self) -> Option<String> {
+        let text = std::mem::take(&mut self.input);
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_owned())
+    }
+
+    fn user_line(&mut self, text: &str, is_final: bool) {
+        if text.trim().is_empty() {
+            return;
+        }
+        // A partial transcription is replaced by the next update.
+        if let Some(last) = self.transcript.back_mut()
+            && last.speaker == Speaker::User
+            && !last.is_final
+        {
+            last.text = text.to_owned();
+            last.is_final = is_final;
+            return;
+        }
+        self.push_line(TranscriptLine {
+            speaker: Speaker::User,
+            text: text.to_owned(),
+            is_final,
+            message_id: None,
+        });
+    }
+
+    fn assistant_delta(&mut self, message_id: &str, delta: &str, is_final: bool) {
+        if let Some(line) = self
+            .transcript
+            .iter_mut()
+            .rev()
+            .find(|line| line.message_id.as_deref() == Some(message_id))
+        {
+            line.text.push_str(delta);
+            line.is_final = is_final;
+            return;
+        }
+        self.push_line(TranscriptLine {
+            speaker: Speaker::Assistant,
+            text: delta.to_owned(),
+            is_final,
+            message_id: Some(message_id.to_owned()),
+        });
+    }
+
+    fn push_line(&mut self, line: TranscriptLine) {
+        if self.transcript.len() == MAX_TRANSCRIPT_LINES {
+            self.transcript.pop_front();
+        }
+        self.transcript.push_back(line);
+    }
+
+    fn log(&mut self, line: String) {
+        if self.events.len() == MAX_EVENT_LINES {
+            self.events.pop_front();
+        }
+        self.events.push_back(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use serde_json::json;
+
+    fn event(name: &str, payload: serde_json::Value) -> EventEnvelope {
+        EventEnvelope::new("e", name, payload)
+    }
+
+    #[test]
+    fn transcript_streams_user_and_assistant_text() {
+        let mut state = TuiState::new();
+        state.apply(&event(
+            "pipeline.transcription",
+            json!({"text": "what's the", "is_final": false}),
+        ));
+        state.apply(&event(
+            "pipeline.transcription",
+            json!({"text": "what's the time", "is_final": true}),
+        ));
+        for (delta, is_final) in [("It is ", false), ("noon.", true)] {
+            state.apply(&event(
+                "pipeline.assistant_text_delta",
+                json!({"message_id": "m1", "delta": delta, "is_final": is_final}),
+            ));
+        }
+
+        let lines: Vec<(Speaker, &str)> = state
+            .transcript
+            .iter()
+            .map(|l| (l.speaker, l.text.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (Speaker::User, "what's the time"),
+                (Speaker::Assistant, "It is noon.")
+            ]
+        );
+
+        state.apply(&event(
+            "pipeline.assistant_text_discarded",
+            json!({"message_id": "m1"}),
+        ));
+        assert_eq!(state.transcript.len(), 1);
+    }
+
+    #[test]
+    fn approvals_queue_until_resolved() {
+        let mut state = TuiState::new();
+        state.apply(&event(
+            "approval.requested",
+            json!({"request_id": "4", "name": "bash", "input_json": "{\"command\":\"ls\"}"}),
+        ));
+        let approval = state.next_approval().unwrap();
+        assert_eq!(approval.name, "bash");
+        assert_eq!(approval.detail, "{\"command\":\"ls\"}");
+
+        state.apply(&event(
+            "approval.resolved",
+            json!({"request_id": "4", "approved": true}),
+        ));
+        assert!(state.next_approval().is_none());
+        assert_eq!(state.events.back().unwrap(), "? approval needed: bash");
+    }
+
+    #[test]
+    fn status_and_input() {
+        let mut state = TuiState::new();
+        state.apply(&event(
+            crate::theme::STATE_CHANGED_EVENT,
+            json!({"state": "thinking"}),
+        ));
+        assert_eq!(state.status, "thinking");
+
+        state.input = "   ".to_owned();
+        assert!(state.take_input().is_none());
+        state.input = " hello ".to_owned();
+        assert_eq!(state.take_input().as_deref(), Some("hello"));
+        assert!(state.input.is_empty());
+    }
+}
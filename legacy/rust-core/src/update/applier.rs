@@ -758,6 +758,8 @@ fn binary_filename() -> &'static str {
 
 /// Download a file from a URL to a local path.
 fn download_binary(url: &str, dest: &Path) -> Result<()> {
+    crate::offline::ensure_online("update download")
+        .map_err(|e| SpeechError::Update(e.to_string()))?;
     let agent = ureq::AgentBuilder::new()
         .timeout_connect(Duration::from_secs(15))
         .timeout_read(Duration::from_secs(300))
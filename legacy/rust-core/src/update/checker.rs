@@ -78,9 +78,10 @@ impl UpdateChecker {
     ///
     /// # Errors
     ///
-    /// Returns an error if the HTTP request fails or the response cannot be
-    /// parsed.
+    /// Returns an error if offline mode is on, the HTTP request fails, or the
+    /// response cannot be parsed.
     pub fn fetch_releases(&self, max: usize) -> Result<Vec<Release>> {
+        ensure_online()?;
         let per_page = max.min(100);
         let url = format!(
             "https://api.github.com/repos/{}/releases?per_page={per_page}",
@@ -158,9 +159,10 @@ impl UpdateChecker {
     ///
     /// # Errors
     ///
-    /// Returns an error if the HTTP request fails or the response cannot be
-    /// parsed.
+    /// Returns an error if offline mode is on, the HTTP request fails, or the
+    /// response cannot be parsed.
     pub fn check(&self, etag: Option<&str>) -> Result<(Option<Release>, Option<String>)> {
+        ensure_online()?;
         let url = format!("https://api.github.com/repos/{}/releases/latest", self.repo);
 
         let agent = ureq::AgentBuilder::new()
@@ -253,6 +255,11 @@ impl UpdateChecker {
 }
 
 /// Parse a GitHub release JSON object into a [`Release`].
+/// Refuse release checks while offline mode is on.
+fn ensure_online() -> Result<()> {
+    crate::offline::ensure_online("update check").map_err(|e| SpeechError::Update(e.to_string()))
+}
+
 fn parse_github_release(body: &serde_json::Value) -> Result<Release> {
     let tag_name = body["tag_name"]
         .as_str()
@@ -20,6 +20,14 @@ pub struct VadOutput {
     pub segment: Option<SpeechSegment>,
     /// RMS energy of the processed chunk.
     pub rms: f32,
+    /// Audio so far, when this chunk reached a provisional endpoint.
+    pub provisional: Option<SpeechSegment>,
+    /// Whether speech resumed on this chunk after a provisional endpoint,
+    /// voiding it.
+    pub resumed: bool,
+    /// Whether `segment` ends where the last provisional endpoint cut it,
+    /// nothing having been said since.
+    pub confirmed: bool,
 }
 
 /// Voice activity detector using RMS energy thresholding with hysteresis.
@@ -41,6 +49,12 @@ pub struct SileroVad {
     silence_samples: usize,
     /// Threshold for the number of silence samples to end a segment.
     silence_samples_threshold: usize,
+    /// Silence samples after which a segment is provisionally ended
+    /// (`usize::MAX` when two-stage endpointing is off).
+    provisional_samples_threshold: usize,
+    /// Whether the current segment was provisionally ended with no speech
+    /// since.
+    provisional_open: bool,
     /// When the current speech segment started.
     speech_start: Option<Instant>,
     /// Configured sample rate.
@@ -73,13 +87,17 @@ impl SileroVad {
             usize::MAX // effectively disabled
         };
 
+        let provisional_samples_threshold =
+            provisional_samples(config.provisional_silence_ms, sample_rate);
+
         let sustain_threshold = config.threshold * config.hysteresis_ratio.clamp(0.1, 1.0);
         info!(
-            "VAD initialized: threshold={}, sustain_threshold={:.4}, hysteresis={}, silence_threshold={}ms, pad={}ms, min_speech={}ms, max_speech={}ms",
+            "VAD initialized: threshold={}, sustain_threshold={:.4}, hysteresis={}, silence_threshold={}ms, provisional_silence={}ms, pad={}ms, min_speech={}ms, max_speech={}ms",
             config.threshold,
             sustain_threshold,
             config.hysteresis_ratio,
             config.min_silence_duration_ms,
+            config.provisional_silence_ms,
             config.speech_pad_ms,
             config.min_speech_duration_ms,
             config.max_speech_duration_ms,
@@ -94,6 +112,8 @@ impl SileroVad {
             in_speech: false,
             silence_samples: 0,
             silence_samples_threshold,
+            provisional_samples_threshold,
+            provisional_open: false,
             speech_start: None,
             sample_rate,
             threshold: config.threshold,
@@ -132,6 +152,9 @@ impl SileroVad {
 
         let mut speech_started = false;
         let mut completed: Option<SpeechSegment> = None;
+        let mut provisional: Option<SpeechSegment> = None;
+        let mut resumed = false;
+        let mut confirmed = false;
 
         if is_speech {
             if !self.in_speech {
@@ -145,6 +168,7 @@ impl SileroVad {
                     self.speech_buffer.extend(self.pre_roll.iter().copied());
                 }
             }
+            resumed = std::mem::take(&mut self.provisional_open);
             self.silence_samples = 0;
             self.speech_buffer.extend_from_slice(&chunk.samples);
         } else if self.in_speech {
@@ -156,6 +180,7 @@ impl SileroVad {
                 // Speech segment ended
                 self.in_speech = false;
                 self.silence_samples = 0;
+                confirmed = std::mem::take(&mut self.provisional_open);
 
                 if self.speech_buffer.len() >= self.min_speech_samples {
                     let started_at = match self.speech_start {
@@ -166,11 +191,24 @@ impl SileroVad {
                         samples: std::mem::take(&mut self.speech_buffer),
                         sample_rate: self.sample_rate,
                         started_at,
+                        speculation: None,
                     };
                     completed = Some(segment);
                 } else {
                     self.speech_buffer.clear();
                 }
+            } else if !self.provisional_open
+                && self.silence_samples >= self.provisional_samples_threshold
+                && self.speech_buffer.len() >= self.min_speech_samples
+            {
+                // Provisional end: hand over what we have, keep listening.
+                self.provisional_open = true;
+                provisional = Some(SpeechSegment {
+                    samples: self.speech_buffer.clone(),
+                    sample_rate: self.sample_rate,
+                    started_at: self.speech_start.unwrap_or_else(Instant::now),
+                    speculation: None,
+                });
             }
         }
 
@@ -187,6 +225,7 @@ impl SileroVad {
             );
             self.in_speech = false;
             self.silence_samples = 0;
+            confirmed = std::mem::take(&mut self.provisional_open);
 
             if self.speech_buffer.len() >= self.min_speech_samples {
                 let started_at = match self.speech_start {
@@ -197,6 +236,7 @@ impl SileroVad {
                     samples: std::mem::take(&mut self.speech_buffer),
                     sample_rate: self.sample_rate,
                     started_at,
+                    speculation: None,
                 });
             } else {
                 self.speech_buffer.clear();
@@ -209,6 +249,9 @@ impl SileroVad {
             is_speech,
             segment: completed,
             rms,
+            provisional,
+            resumed,
+            confirmed,
         })
     }
 
@@ -221,6 +264,12 @@ impl SileroVad {
         self.silence_samples_threshold = (ms as usize * self.sample_rate as usize) / 1000;
     }
 
+    /// Update the provisional endpoint silence at runtime; `0` turns
+    /// two-stage endpointing off.
+    pub fn set_provisional_silence_ms(&mut self, ms: u32) {
+        self.provisional_samples_threshold = provisional_samples(ms, self.sample_rate);
+    }
+
     /// Reset the VAD state.
     pub fn reset(&mut self) {
         self.pre_roll.clear();
@@ -228,6 +277,15 @@ impl SileroVad {
         self.in_speech = false;
         self.silence_samples = 0;
         self.speech_start = None;
+        self.provisional_open = false;
+    }
+}
+
+fn provisional_samples(ms: u32, sample_rate: u32) -> usize {
+    if ms == 0 {
+        usize::MAX
+    } else {
+        (ms as usize * sample_rate as usize) / 1000
     }
 }
 
@@ -239,3 +297,66 @@ fn compute_rms_energy(samples: &[f32]) -> f32 {
     let sum_sq: f32 = samples.iter().map(|s| s * s).sum();
     (sum_sq / samples.len() as f32).sqrt()
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    const RATE: u32 = 16_000;
+
+    /// Feed 100ms chunks, speech (`true`) or silence, collecting outputs.
+    fn feed(vad: &mut SileroVad, pattern: &[bool]) -> Vec<VadOutput> {
+        pattern
+            .iter()
+            .map(|&speech| {
+                let level = if speech { 0.1 } else { 0.0 };
+                let chunk = AudioChunk {
+                    samples: vec![level; RATE as usize / 10],
+                    sample_rate: RATE,
+                    captured_at: Instant::now(),
+                };
+                vad.process_chunk(&chunk).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn provisional_endpoint_is_revised_then_confirmed() {
+        let config = VadConfig {
+            provisional_silence_ms: 300,
+            speech_pad_ms: 0,
+            ..VadConfig::default()
+        };
+        let mut vad = SileroVad::new(&config, &ModelConfig::default(), RATE).unwrap();
+
+        // Half a second of speech, a 300ms pause: provisional end.
+        let out = feed(
+            &mut vad,
+            &[true, true, true, true, true, false, false, false],
+        );
+        let provisional = out[7].provisional.as_ref().expect("provisional end");
+        assert_eq!(provisional.samples.len(), 8 * 1_600);
+        assert!(out[..7].iter().all(|o| o.provisional.is_none()));
+
+        // The user carries on: the provisional end is void.
+        let out = feed(&mut vad, &[true]);
+        assert!(out[0].resumed);
+
+        // A full second of silence ends the segment where the second
+        // provisional end cut it, with both parts of the utterance.
+        let out = feed(&mut vad, &[false; 10]);
+        assert!(out[2].provisional.is_some());
+        let last = &out[9];
+        assert!(last.confirmed);
+        assert_eq!(last.segment.as_ref().unwrap().samples.len(), 19 * 1_600);
+
+        // Without a provisional end there is nothing to confirm.
+        vad.set_provisional_silence_ms(0);
+        let pattern: Vec<bool> = [true; 3].into_iter().chain([false; 10]).collect();
+        let out = feed(&mut vad, &pattern);
+        assert!(out.iter().all(|o| o.provisional.is_none()));
+        assert!(out[12].segment.is_some() && !out[12].confirmed);
+    }
+}
@@ -17,6 +17,12 @@
 //! | "hide/close conversation" | `HideConversation` |
 //! | "show/open canvas" | `ShowCanvas` |
 //! | "hide/close canvas" | `HideCanvas` |
+//! | "forget everything about me" | `ForgetEverything` |
+//! | "go offline" / "offline mode" | `GoOffline` |
+//! | "go online" / "turn off offline mode" | `GoOnline` |
+//! | "undo that change" / "undo the last edit" | `UndoChange` |
+//! | "start meeting mode" / "take meeting notes" | `StartMeeting` |
+//! | "end the meeting" / "stop meeting mode" | `StopMeeting` |
 
 /// A voice command detected from user speech.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -44,6 +50,18 @@ pub enum VoiceCommand {
     GrantPermissions,
     /// Revoke all granted tool permissions.
     RevokePermissions,
+    /// Securely wipe all personal data, after confirmation.
+    ForgetEverything,
+    /// Turn offline mode on (block all network access).
+    GoOffline,
+    /// Turn offline mode off.
+    GoOnline,
+    /// Revert the most recent file change made by a tool.
+    UndoChange,
+    /// Start meeting mode: transcribe without replying.
+    StartMeeting,
+    /// End meeting mode and write the minutes.
+    StopMeeting,
 }
 
 /// Target specification for a model switch command.
@@ -184,6 +202,97 @@ pub fn parse_voice_command(text: &str) -> Option<VoiceCommand> {
         return Some(VoiceCommand::RevokePermissions);
     }
 
+    // --- Forget everything ---
+    if matches_any(
+        stripped,
+        &[
+            "forget everything about me",
+            "forget everything you know about me",
+            "delete all my data",
+            "erase all my data",
+            "wipe all my data",
+        ],
+    ) {
+        return Some(VoiceCommand::ForgetEverything);
+    }
+
+    // --- Offline mode ---
+    if matches_any(
+        stripped,
+        &[
+            "go online",
+            "go back online",
+            "turn off offline mode",
+            "disable offline mode",
+            "exit offline mode",
+        ],
+    ) {
+        return Some(VoiceCommand::GoOnline);
+    }
+    if matches_any(
+        stripped,
+        &[
+            "go offline",
+            "offline mode",
+            "turn on offline mode",
+            "enable offline mode",
+            "work offline",
+        ],
+    ) {
+        return Some(VoiceCommand::GoOffline);
+    }
+
+    // --- Undo ---
+    if matches_any(
+        stripped,
+        &[
+            "undo that change",
+            "undo that edit",
+            "undo the last change",
+            "undo the last edit",
+            "undo last change",
+            "undo your last change",
+            "undo your change",
+            "revert that change",
+            "revert the last change",
+        ],
+    ) {
+        return Some(VoiceCommand::UndoChange);
+    }
+
+    // --- Meeting mode ---
+    if matches_any(
+        stripped,
+        &[
+            "end the meeting",
+            "end meeting",
+            "stop the meeting",
+            "stop meeting mode",
+            "end meeting mode",
+            "exit meeting mode",
+            "turn off meeting mode",
+            "meeting mode off",
+            "stop taking meeting notes",
+            "the meeting is over",
+        ],
+    ) {
+        return Some(VoiceCommand::StopMeeting);
+    }
+    if matches_any(
+        stripped,
+        &[
+            "start meeting mode",
+            "turn on meeting mode",
+            "enable meeting mode",
+            "meeting mode on",
+            "take meeting notes",
+            "transcribe this meeting",
+            "transcribe the meeting",
+        ],
+    ) {
+        return Some(VoiceCommand::StartMeeting);
+    }
+
     None
 }
 
@@ -308,6 +417,13 @@ const APPROVE_PHRASES: &[&str] = &[
     "go for it",
     "yes go ahead",
     "affirmative",
+    // Localized approval prompts ask for yes or no in the user's language.
+    "ja",
+    "sí",
+    "si",
+    "vale",
+    "oui",
+    "d'accord",
 ];
 
 /// Deny words — exact-match first, then word-boundary fallback.
@@ -324,6 +440,8 @@ const DENY_PHRASES: &[&str] = &[
     "cancel that",
     "don't do that",
     "no way",
+    "nein",
+    "non",
 ];
 
 /// Parse a transcription as a yes/no approval response.
@@ -483,38 +601,42 @@ pub fn resolve_model_target(target: &ModelTarget, candidates: &[ModelCandidate])
 
 /// Acknowledgment for a successful model switch.
 pub fn switch_acknowledgment(model_name: &str) -> String {
-    format!("Switching to {model_name}.")
+    crate::i18n::format("voice.switching_model", &[("model", model_name)])
 }
 
 /// Acknowledgment when the requested model is already active.
 pub fn already_using_acknowledgment(model_name: &str) -> String {
-    format!("I'm already using {model_name}.")
+    crate::i18n::format("voice.already_using", &[("model", model_name)])
 }
 
 /// Response when the requested model cannot be found.
 pub fn model_not_found_response(target: &str) -> String {
-    format!("I couldn't find a model matching {target}.")
+    crate::i18n::format("voice.model_not_found", &[("target", target)])
 }
 
 /// Response listing all available models and the current one.
 pub fn list_models_response(models: &[String], current_idx: usize) -> String {
     if models.is_empty() {
-        return "I don't have any models configured.".to_owned();
+        return crate::i18n::text("voice.no_models").to_owned();
     }
     let list = models.join(", ");
-    let current = models.get(current_idx).map_or("unknown", |s| s.as_str());
-    format!("I have access to {list}. Currently using {current}.")
+    let current = models
+        .get(current_idx)
+        .map_or_else(|| crate::i18n::text("voice.unknown_model"), |s| s.as_str());
+    crate::i18n::format(
+        "voice.list_models",
+        &[("list", &list), ("current", current)],
+    )
 }
 
 /// Response stating which model is currently active.
 pub fn current_model_response(model_name: &str) -> String {
-    format!("I'm currently using {model_name}.")
+    crate::i18n::format("voice.current_model", &[("model", model_name)])
 }
 
 /// Help response listing available voice commands.
 pub fn help_response() -> String {
-    "You can say: switch to Claude, use the local model, list models, what model are you using, show conversation, show canvas, or grant permissions."
-        .to_owned()
+    crate::i18n::text("voice.help").to_owned()
 }
 
 #[cfg(test)]
@@ -1038,6 +1160,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn forget_everything_about_me() {
+        assert_eq!(
+            parse_voice_command("Fae, forget everything about me."),
+            Some(VoiceCommand::ForgetEverything)
+        );
+        assert_eq!(
+            parse_voice_command("delete all my data"),
+            Some(VoiceCommand::ForgetEverything)
+        );
+        assert_eq!(parse_voice_command("forget it"), None);
+    }
+
+    #[test]
+    fn offline_mode_commands() {
+        assert_eq!(
+            parse_voice_command("Fae, go offline."),
+            Some(VoiceCommand::GoOffline)
+        );
+        assert_eq!(
+            parse_voice_command("turn on offline mode"),
+            Some(VoiceCommand::GoOffline)
+        );
+        assert_eq!(
+            parse_voice_command("turn off offline mode"),
+            Some(VoiceCommand::GoOnline)
+        );
+        assert_eq!(
+            parse_voice_command("hey fae go back online"),
+            Some(VoiceCommand::GoOnline)
+        );
+        assert_eq!(parse_voice_command("is the shop online"), None);
+    }
+
+    #[test]
+    fn undo_change_commands() {
+        assert_eq!(
+            parse_voice_command("Fae, undo that change."),
+            Some(VoiceCommand::UndoChange)
+        );
+        assert_eq!(
+            parse_voice_command("revert the last change please"),
+            Some(VoiceCommand::UndoChange)
+        );
+        assert_eq!(parse_voice_command("how do I undo a git commit"), None);
+    }
+
+    #[test]
+    fn meeting_mode_commands() {
+        assert_eq!(
+            parse_voice_command("Fae, start meeting mode."),
+            Some(VoiceCommand::StartMeeting)
+        );
+        assert_eq!(
+            parse_voice_command("take meeting notes please"),
+            Some(VoiceCommand::StartMeeting)
+        );
+        assert_eq!(
+            parse_voice_command("Fae, end the meeting."),
+            Some(VoiceCommand::StopMeeting)
+        );
+        assert_eq!(
+            parse_voice_command("stop meeting mode"),
+            Some(VoiceCommand::StopMeeting)
+        );
+        assert_eq!(parse_voice_command("when does the meeting end"), None);
+    }
+
     #[test]
     fn help_response_lists_commands() {
         let response = help_response();
@@ -1275,6 +1465,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn approval_localized_yes_no() {
+        for yes in ["ja", "Sí.", "oui", "ja bitte"] {
+            assert_eq!(
+                parse_approval_response(yes),
+                ApprovalVoiceResponse::Approved,
+                "{yes}"
+            );
+        }
+        for no in ["nein", "Non!", "nein danke"] {
+            assert_eq!(
+                parse_approval_response(no),
+                ApprovalVoiceResponse::Denied,
+                "{no}"
+            );
+        }
+    }
+
     // -----------------------------------------------------------------------
     // contains_word tests
     // -----------------------------------------------------------------------
@@ -282,7 +282,8 @@ fn parse_sse_message(data_line: &str) -> Option<X0xIncomingMessage> {
 /// rate-limits, and delivers trusted messages to Fae's conversation pipeline
 /// via the `TextInjection` channel.
 ///
-/// Auto-reconnects with exponential backoff on connection failure.
+/// Auto-reconnects with exponential backoff on connection failure, and stays
+/// disconnected while offline mode is on.
 pub fn spawn_x0x_listener(
     text_injection_tx: mpsc::UnboundedSender<TextInjection>,
     cancel: CancellationToken,
@@ -309,6 +310,15 @@ pub fn spawn_x0x_listener(
                 return;
             }
 
+            // Stay disconnected while offline mode is on.
+            if crate::offline::is_offline() {
+                tokio::select! {
+                    () = cancel.cancelled() => return,
+                    () = tokio::time::sleep(MAX_BACKOFF) => {},
+                }
+                continue;
+            }
+
             // Publish presence announcement.
             publish_presence(&client, &user_label).await;
 
@@ -362,6 +372,10 @@ pub fn spawn_x0x_listener(
                     }
                     chunk = stream.next() => {
                         match chunk {
+                            Some(Ok(_)) if crate::offline::is_offline() => {
+                                info!("x0x listener: offline mode on, disconnecting");
+                                break;
+                            }
                             Some(Ok(bytes)) => {
                                 buffer.push_str(&String::from_utf8_lossy(&bytes));
 
@@ -419,6 +433,7 @@ pub fn spawn_x0x_listener(
                                         );
                                         if text_injection_tx.send(TextInjection {
                                             text: notification,
+                                            images: Vec::new(),
                                             fork_at_keep_count: None,
                                         }).is_err() {
                                             info!("x0x listener: text injection channel closed");
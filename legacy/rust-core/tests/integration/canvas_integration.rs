@@ -80,6 +80,7 @@ fn bridge_routes_transcription_to_user_message() {
         audio_duration_secs: None,
         audio_captured_at: Instant::now(),
         transcribed_at: Instant::now(),
+        speculation: None,
     }));
 
     assert_eq!(bridge.session().message_count(), 1);
@@ -105,6 +106,7 @@ fn bridge_routes_assistant_chunks_and_tools() {
         name: "canvas_render".into(),
         success: true,
         output_text: None,
+        structured: None,
     });
 
     // Assistant response
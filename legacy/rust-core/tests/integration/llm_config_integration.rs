@@ -207,6 +207,8 @@ fn test_partial_update_model() {
                     display_name: "Test Model".to_string(),
                     tier: fae::fae_llm::ModelTier::Balanced,
                     max_tokens: 4096,
+                    sampling: Default::default(),
+                    lora: None,
                 },
             );
             c.defaults.default_model = Some("test-model".to_string());
@@ -10,6 +10,7 @@ mod hang_regressions;
 mod helpers;
 
 mod apple_tool_registration;
+#[cfg(feature = "canvas")]
 mod canvas_integration;
 mod capability_bridge_e2e;
 mod e2e_host_bridge;
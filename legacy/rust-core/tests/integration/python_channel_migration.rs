@@ -165,6 +165,7 @@ fn validation_still_flags_missing_discord_token_after_migration() {
             whatsapp: None,
             rate_limits: Default::default(),
             extensions: Vec::new(),
+            remote_approval: Default::default(),
         },
         ..Default::default()
     };
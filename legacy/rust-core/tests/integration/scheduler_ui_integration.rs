@@ -64,6 +64,7 @@ fn test_full_task_editing_workflow() {
         max_failure_streak_before_pause: 5,
         soft_timeout_secs: 300,
         last_error: None,
+        exclusive: false,
     };
 
     // User opens edit form
@@ -95,6 +96,7 @@ fn test_execution_history_workflow() {
             finished_at: 1015,
             outcome: TaskRunOutcome::Success,
             summary: "Task completed successfully".to_owned(),
+            log_path: None,
         },
         TaskRunRecord {
             task_id: "task1".to_owned(),
@@ -102,6 +104,7 @@ fn test_execution_history_workflow() {
             finished_at: 2005,
             outcome: TaskRunOutcome::Error,
             summary: "Task failed with error".to_owned(),
+            log_path: None,
         },
         TaskRunRecord {
             task_id: "task2".to_owned(),
@@ -109,6 +112,7 @@ fn test_execution_history_workflow() {
             finished_at: 3120,
             outcome: TaskRunOutcome::Success,
             summary: "Different task completed".to_owned(),
+            log_path: None,
         },
     ];
 
@@ -285,6 +289,7 @@ fn test_round_trip_conversion_preserves_data() {
         max_failure_streak_before_pause: 10,
         soft_timeout_secs: 600,
         last_error: Some("Previous error".to_owned()),
+        exclusive: false,
     };
 
     // Convert to editing form